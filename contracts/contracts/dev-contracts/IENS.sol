@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: UNLICENSED
+
+pragma solidity ^0.7.0;
+
+/// @dev Parts of the ENS registry and the public resolver the ENS names are resolved with
+interface IENS {
+    /// @dev Returns the address of the resolver of the name, zero if the name has no resolver
+    /// @param _node Namehash of the name
+    function resolver(bytes32 _node) external view returns (address);
+}
+
+interface IENSResolver {
+    /// @dev Returns the address the name is resolved to, zero if the name has no address
+    /// @param _node Namehash of the name
+    function addr(bytes32 _node) external view returns (address payable);
+}
@@ -7,7 +7,9 @@ use web3::{
     contract::Options,
     types::{TransactionReceipt, U256, U64},
 };
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_config::{
+    configs::eth_sender::EthSignerType, ContractsConfig, ETHClientConfig, ETHSenderConfig,
+};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::StorageProcessor;
 use zksync_types::{aggregated_operations::stored_block_info, block::Block, BlockNumber, H256};
@@ -17,108 +19,15 @@ async fn revert_blocks_in_storage(
     storage: &mut StorageProcessor<'_>,
     last_block: BlockNumber,
 ) -> anyhow::Result<()> {
-    let mut transaction = storage.start_transaction().await?;
-
-    transaction
-        .chain()
-        .mempool_schema()
-        .return_executed_txs_to_mempool(last_block)
-        .await?;
-    println!("`mempool_txs`, `executed_transactions` tables are updated");
-    transaction
-        .chain()
-        .state_schema()
-        .clear_current_nonce_table(last_block)
-        .await?;
-    println!("`committed_nonce` table is updated");
-    transaction
+    storage
         .chain()
         .block_schema()
-        .remove_blocks(last_block)
+        .revert_blocks(last_block)
         .await?;
-    println!("`block` table is cleaned");
-    transaction
-        .chain()
-        .block_schema()
-        .remove_pending_block()
-        .await?;
-    println!("`pending_block` table is cleaned");
-    transaction
-        .chain()
-        .tree_cache_schema_bincode()
-        .remove_new_account_tree_cache(last_block)
-        .await?;
-    println!("`account_tree_cache` table is cleaned");
 
-    transaction
-        .chain()
-        .state_schema()
-        .remove_account_balance_updates(last_block)
-        .await?;
-    println!("`account_balance_updates` table is cleaned");
-    transaction
-        .chain()
-        .state_schema()
-        .remove_account_creates(last_block)
-        .await?;
-    println!("`account_creates` table is cleaned");
-    transaction
-        .chain()
-        .state_schema()
-        .remove_account_pubkey_updates(last_block)
-        .await?;
-    println!("`account_pubkey_updates` table is cleaned");
-
-    transaction
-        .chain()
-        .state_schema()
-        .remove_mint_nft_updates(last_block)
-        .await?;
-    println!("`mint_nft_updates` table is cleaned");
-
-    transaction
-        .chain()
-        .operations_schema()
-        .remove_eth_unprocessed_aggregated_ops()
-        .await?;
-    println!("`eth_unprocessed_aggregated_ops` table is cleaned");
-    transaction
-        .chain()
-        .operations_schema()
-        .remove_aggregate_operations_and_bindings(last_block)
-        .await?;
-    println!("`aggregate_operations`, `eth_aggregated_ops_binding`, `eth_tx_hashes`, `eth_operations` tables are cleaned");
-
-    transaction
-        .prover_schema()
-        .remove_witnesses(last_block)
-        .await?;
-    println!("`block_witness` table is cleaned");
-    transaction
-        .prover_schema()
-        .remove_proofs(last_block)
-        .await?;
-    println!("`proofs` table is cleaned");
-    transaction
-        .prover_schema()
-        .remove_aggregated_proofs(last_block)
-        .await?;
-    println!("`aggregated_proofs` table is cleaned");
-    transaction
-        .prover_schema()
-        .remove_prover_jobs(last_block)
-        .await?;
-    println!("`prover_job_queue` table is cleaned");
-
-    transaction
-        .ethereum_schema()
-        .update_eth_parameters(last_block)
-        .await?;
-    println!("`eth_parameters` table is updated");
-
-    transaction.commit().await?;
-
-    println!("Blocks were reverted in storage");
+    println!(
+        "Blocks were reverted in storage, transactions of the blocks are returned to the mempool"
+    );
     Ok(())
 }
 
@@ -152,15 +61,24 @@ async fn send_raw_tx_and_wait_confirmation(
     }
 }
 
+/// Returns the call data of the `revertBlocks` contract function for the blocks.
+fn revert_blocks_call_data(client: &EthereumGateway, blocks: &[Block]) -> Vec<u8> {
+    let tx_arg = Token::Array(blocks.iter().map(stored_block_info).collect());
+    client.encode_tx_data("revertBlocks", tx_arg)
+}
+
+fn revert_blocks_gas_limit(blocks: &[Block]) -> usize {
+    200000 + 15000 * blocks.len()
+}
+
 // TODO: don't use anyhow (ZKS-588)
 async fn revert_blocks_on_contract(
     storage: &mut StorageProcessor<'_>,
     client: &EthereumGateway,
     blocks: &[Block],
 ) -> anyhow::Result<()> {
-    let tx_arg = Token::Array(blocks.iter().map(stored_block_info).collect());
-    let data = client.encode_tx_data("revertBlocks", tx_arg);
-    let gas_limit = 200000 + 15000 * blocks.len();
+    let data = revert_blocks_call_data(client, blocks);
+    let gas_limit = revert_blocks_gas_limit(blocks);
     let signed_tx = client
         .sign_prepared_tx(data, Options::with(|f| f.gas = Some(U256::from(gas_limit))))
         .await
@@ -206,6 +124,9 @@ enum Command {
     Contract,
     /// Reverts blocks in storage
     Storage,
+    /// Prints the call data of the contract revert transaction without sending it,
+    /// e.g. to send it from the multisig wallet.
+    CallData,
 }
 
 #[derive(Debug, StructOpt)]
@@ -213,13 +134,17 @@ enum Command {
 #[structopt(about = "Tool to revert blocks in zkSync network on contract and/or in storage")]
 struct Opt {
     /// Last correct block, tool reverts blocks with numbers greater than this field.
+    #[structopt(long, required_unless = "blocks", conflicts_with = "blocks")]
+    last_correct_block: Option<u32>,
+    /// Number of the last committed blocks to revert, alternative to `--last-correct-block`.
     #[structopt(long)]
-    last_correct_block: u32,
+    blocks: Option<u32>,
     #[structopt(subcommand)]
     command: Command,
     /// Private key of operator which will call the contract function.
+    /// If not set, the operator signer from the config is used.
     #[structopt(long = "key", env = "ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")]
-    operator_private_key: String,
+    operator_private_key: Option<String>,
 }
 
 // TODO: don't use anyhow (ZKS-588)
@@ -227,17 +152,18 @@ struct Opt {
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
 
-    let key_without_prefix = opt
-        .operator_private_key
-        .strip_prefix("0x")
-        .unwrap_or_else(|| opt.operator_private_key.as_str());
-
     let contracts = ContractsConfig::from_env();
     let eth_client_config = ETHClientConfig::from_env();
     let mut eth_sender_config = ETHSenderConfig::from_env();
 
-    eth_sender_config.sender.operator_private_key =
-        H256::from_str(key_without_prefix).expect("Cannot deserialize private key");
+    if let Some(operator_private_key) = &opt.operator_private_key {
+        let key_without_prefix = operator_private_key
+            .strip_prefix("0x")
+            .unwrap_or_else(|| operator_private_key.as_str());
+        eth_sender_config.sender.operator_private_key =
+            Some(H256::from_str(key_without_prefix).expect("Cannot deserialize private key"));
+        eth_sender_config.sender.signer = EthSignerType::PrivateKey;
+    }
 
     let mut storage = StorageProcessor::establish_connection().await?;
     let client = EthereumGateway::from_config(
@@ -261,13 +187,27 @@ async fn main() -> anyhow::Result<()> {
         "Last committed block {} verified {}",
         &last_commited_block, &last_verified_block
     );
+    let last_correct_block = match (opt.last_correct_block, opt.blocks) {
+        (Some(last_correct_block), _) => last_correct_block,
+        (None, Some(blocks)) => (*last_commited_block).checked_sub(blocks).ok_or_else(|| {
+            format_err!("There are only {} committed blocks", last_commited_block)
+        })?,
+        (None, None) => {
+            unreachable!("Either the last correct block or the number of blocks is required")
+        }
+    };
     ensure!(
-        *last_verified_block <= opt.last_correct_block,
+        *last_verified_block <= last_correct_block,
         "Some blocks to revert are already verified"
     );
+    ensure!(
+        last_correct_block <= *last_commited_block,
+        "Block {} is not committed yet",
+        last_correct_block
+    );
 
-    let blocks_to_revert = *last_commited_block - opt.last_correct_block;
-    let last_block = BlockNumber(opt.last_correct_block);
+    let blocks_to_revert = *last_commited_block - last_correct_block;
+    let last_block = BlockNumber(last_correct_block);
 
     match opt.command {
         Command::All => {
@@ -286,6 +226,15 @@ async fn main() -> anyhow::Result<()> {
             println!("Start reverting blocks in database");
             revert_blocks_in_storage(&mut storage, last_block).await?;
         }
+        Command::CallData => {
+            let blocks = get_blocks(last_commited_block, blocks_to_revert, &mut storage).await?;
+            println!("Contract: {:?}", contracts.contract_addr);
+            println!("Gas limit: {}", revert_blocks_gas_limit(&blocks));
+            println!(
+                "Call data: 0x{}",
+                hex::encode(revert_blocks_call_data(&client, &blocks))
+            );
+        }
     }
 
     Ok(())
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 // External deps
+use futures::{stream, StreamExt, TryStreamExt};
 use web3::{
     contract::Contract,
     types::{H160, H256},
@@ -20,10 +21,12 @@ use zksync_types::{
 use crate::{
     contract::{get_genesis_account, ZkSyncDeployedContract},
     eth_tx_helpers::get_ethereum_transaction,
+    events::BlockEvent,
     events_state::EventsState,
     rollup_ops::RollupOpsBlock,
     storage_interactor::StorageInteractor,
     tree_state::TreeState,
+    CALLDATA_FETCH_CONCURRENCY, TREE_CHECKPOINT_BLOCKS,
 };
 
 /// Storage state update:
@@ -71,6 +74,10 @@ pub struct DataRestoreDriver<T: Transport> {
     pub eth_blocks_step: u64,
     /// The distance to the last ethereum block
     pub end_eth_blocks_offset: u64,
+    /// The number of Rollup blocks applied to the tree between the checkpoints of the restored state
+    pub checkpoint_blocks: usize,
+    /// The number of Ethereum transactions the calldata of which is fetched concurrently
+    pub calldata_fetch_concurrency: usize,
     /// Finite mode flag. In finite mode, driver will only work until
     /// amount of restored blocks will become equal to amount of known
     /// verified blocks. After that, it will stop.
@@ -131,6 +138,8 @@ impl<T: Transport> DataRestoreDriver<T> {
             tree_state,
             eth_blocks_step,
             end_eth_blocks_offset,
+            checkpoint_blocks: TREE_CHECKPOINT_BLOCKS,
+            calldata_fetch_concurrency: CALLDATA_FETCH_CONCURRENCY,
             finite_mode,
             final_hash,
             last_priority_op_serial_id: 0,
@@ -264,7 +273,8 @@ impl<T: Transport> DataRestoreDriver<T> {
             .await;
     }
 
-    /// Stops states from storage
+    /// Loads states from storage and applies the operations that have been saved
+    /// but not applied to the tree yet.
     pub async fn load_state_from_storage(
         &mut self,
         interactor: &mut StorageInteractor<'_>,
@@ -296,32 +306,33 @@ impl<T: Transport> DataRestoreDriver<T> {
                 tree_state.fee_acc_id,
             )
         };
-        match state {
+        self.last_priority_op_serial_id = transaction.get_max_priority_op_serial_id().await;
+
+        let new_ops_blocks = match state {
             StorageUpdateState::Events => {
                 // Update operations
-                let new_ops_blocks = self.update_operations_state(&mut transaction).await;
-
-                // Update tree
-                self.update_tree_state(&mut transaction, new_ops_blocks)
-                    .await;
+                self.update_operations_state(&mut transaction).await
             }
-            StorageUpdateState::Operations => {
-                // Update operations
-                let new_ops_blocks = transaction.get_ops_blocks_from_storage().await;
-                // Update tree
-                self.update_tree_state(&mut transaction, new_ops_blocks)
-                    .await;
+            StorageUpdateState::Operations | StorageUpdateState::None => {
+                // The restore may have been interrupted between the checkpoints,
+                // so the operations already applied to the tree are skipped.
+                transaction
+                    .get_ops_blocks_from_storage()
+                    .await
+                    .into_iter()
+                    .filter(|block| block.block_num > self.tree_state.block_number)
+                    .collect()
             }
-            StorageUpdateState::None => {}
-        }
+        };
+        transaction.commit().await;
 
-        self.last_priority_op_serial_id = transaction.get_max_priority_op_serial_id().await;
-        let total_verified_blocks = self.zksync_contract.get_total_verified_blocks().await;
+        // Update tree
+        let has_new_ops_blocks = !new_ops_blocks.is_empty();
+        self.apply_ops_blocks(interactor, new_ops_blocks).await;
 
+        let total_verified_blocks = self.zksync_contract.get_total_verified_blocks().await;
         let last_verified_block = self.tree_state.block_number;
 
-        transaction.commit().await;
-
         vlog::info!(
             "State has been loaded\nProcessed {:?} blocks on contract\nRoot hash: {:?}\n",
             last_verified_block,
@@ -329,8 +340,8 @@ impl<T: Transport> DataRestoreDriver<T> {
         );
 
         let is_finished = self.finite_mode && (total_verified_blocks == *last_verified_block);
-        // Save tree cache if necessary.
-        if !is_cached {
+        // Save tree cache if necessary, the checkpoints save it otherwise.
+        if !is_cached && !has_new_ops_blocks {
             vlog::info!("Saving tree cache for future re-uses");
             self.update_tree_cache(interactor).await;
         }
@@ -350,26 +361,14 @@ impl<T: Transport> DataRestoreDriver<T> {
                 let new_ops_blocks = self.update_operations_state(interactor).await;
 
                 if !new_ops_blocks.is_empty() {
-                    let mut transaction = interactor.start_transaction().await;
-
                     // Update tree
-                    self.update_tree_state(&mut transaction, new_ops_blocks)
-                        .await;
+                    self.apply_ops_blocks(interactor, new_ops_blocks).await;
 
                     let total_verified_blocks =
                         self.zksync_contract.get_total_verified_blocks().await;
 
                     let last_verified_block = self.tree_state.block_number;
 
-                    // We must update the Ethereum stats table to match the actual stored state
-                    // to keep the `state_keeper` consistent with the `eth_sender`.
-                    transaction.update_eth_state().await;
-
-                    // We update tree cache for each load of updates to allow fast restart.
-                    self.update_tree_cache(&mut transaction).await;
-
-                    transaction.commit().await;
-
                     vlog::info!(
                         "State updated\nProcessed {:?} blocks of total {:?} verified on contract\nRoot hash: {:?}\n",
                         last_verified_block,
@@ -439,6 +438,43 @@ impl<T: Transport> DataRestoreDriver<T> {
         !block_events.is_empty()
     }
 
+    /// Applies the new Rollup operations blocks to the tree state and saves the checkpoint
+    /// of the restored state after every `checkpoint_blocks` blocks, so the interrupted restore
+    /// is resumed from the last checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_ops_blocks` - the new Rollup operations blocks
+    ///
+    async fn apply_ops_blocks(
+        &mut self,
+        interactor: &mut StorageInteractor<'_>,
+        new_ops_blocks: Vec<RollupOpsBlock>,
+    ) {
+        let checkpoint_blocks = self.checkpoint_blocks.max(1);
+        let mut new_ops_blocks = new_ops_blocks.into_iter().peekable();
+        while new_ops_blocks.peek().is_some() {
+            let ops_blocks: Vec<_> = new_ops_blocks.by_ref().take(checkpoint_blocks).collect();
+            let mut transaction = interactor.start_transaction().await;
+
+            self.update_tree_state(&mut transaction, ops_blocks).await;
+
+            // We must update the Ethereum stats table to match the actual stored state
+            // to keep the `state_keeper` consistent with the `eth_sender`.
+            transaction.update_eth_state().await;
+
+            // We update tree cache for each checkpoint to allow fast restart.
+            self.update_tree_cache(&mut transaction).await;
+
+            transaction.commit().await;
+
+            vlog::info!(
+                "Saved the checkpoint of the restored state, block number: {}",
+                self.tree_state.block_number
+            );
+        }
+    }
+
     /// Updates tree state from the new Rollup operations blocks, saves it in storage
     ///
     /// # Arguments
@@ -519,57 +555,58 @@ impl<T: Transport> DataRestoreDriver<T> {
     }
 
     /// Returns operations blocks from verified op blocks events.
+    ///
+    /// The calldata of the different Ethereum transactions is fetched concurrently.
     pub async fn get_new_operation_blocks_from_events(&mut self) -> Vec<RollupOpsBlock> {
-        let mut blocks = Vec::new();
-
-        let mut last_event_tx_hash = None;
-        // The HashMap from block_num to the RollupOpsBlock data for the tx represented by last_event_tx_hash.
-        // It is used as a cache to reuse the fetched data.
-        let mut last_tx_blocks = HashMap::new();
-
         // TODO (ZKS-722): either due to Ethereum node lag or unknown
         // bug in the events state, we have to additionally filter out
         // already processed rollup blocks.
         let mut last_processed_block = self.tree_state.block_number;
+        let mut events = Vec::new();
         for event in self
             .events_state
             .get_only_verified_committed_events()
-            .iter()
+            .into_iter()
             .filter(|bl| bl.block_num > self.tree_state.block_number)
         {
             // For some reasons, we have a bug where event state contains duplicates for blocks
             if last_processed_block >= event.block_num {
                 continue;
             }
-            // We use an aggregated block in contracts, which means that several BlockEvent can include the same tx_hash,
-            // but for correct restore we need to generate RollupBlocks from this tx only once.
-            // These blocks go one after the other, and checking only the last transaction hash is safe.
-
-            // If the previous tx hash does not exist or it is not equal to the current one, we should
-            // re-fetch the blocks for the new tx hash.
-            if !last_event_tx_hash
-                .map(|tx| tx == event.transaction_hash)
-                .unwrap_or_default()
-            {
-                let blocks = RollupOpsBlock::get_rollup_ops_blocks(&self.web3, event)
-                    .await
-                    .expect("Cant get new operation blocks from events");
-
-                last_tx_blocks = blocks
-                    .into_iter()
-                    .map(|block| (block.block_num, block))
-                    .collect();
-                last_event_tx_hash = Some(event.transaction_hash);
-            }
+            last_processed_block = event.block_num;
+            events.push(event);
+        }
 
-            if let Some(rollup_block) = last_tx_blocks.remove(&event.block_num) {
-                blocks.push(rollup_block);
-                last_processed_block = event.block_num;
-            } else {
-                panic!("Block not found")
+        // We use an aggregated block in contracts, which means that several BlockEvent can include the same tx_hash,
+        // but for correct restore we need to generate RollupBlocks from this tx only once.
+        // These blocks go one after the other, and checking only the previous transaction hash is safe.
+        let mut tx_events: Vec<&BlockEvent> = Vec::new();
+        for event in &events {
+            if tx_events.last().map(|last| last.transaction_hash) != Some(event.transaction_hash) {
+                tx_events.push(event);
             }
         }
 
-        blocks
+        let web3 = &self.web3;
+        // The HashMap from block_num to the RollupOpsBlock data fetched from the transactions.
+        let mut rollup_blocks: HashMap<_, _> = stream::iter(tx_events)
+            .map(|event| RollupOpsBlock::get_rollup_ops_blocks(web3, event))
+            .buffered(self.calldata_fetch_concurrency.max(1))
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Cant get new operation blocks from events")
+            .into_iter()
+            .flatten()
+            .map(|block| (block.block_num, block))
+            .collect();
+
+        events
+            .iter()
+            .map(|event| {
+                rollup_blocks
+                    .remove(&event.block_num)
+                    .expect("Block not found")
+            })
+            .collect()
     }
 }
@@ -68,6 +68,14 @@ impl<'a> DatabaseStorageInteractor<'a> {
             .expect("Сant make u256 block_number in get_last_watched_block_number_from_storage")
     }
 
+    pub async fn is_restore_started(&mut self) -> bool {
+        self.storage
+            .data_restore_schema()
+            .is_last_watched_block_number_stored()
+            .await
+            .expect("Cant check whether the last watched block number is stored")
+    }
+
     pub async fn save_rollup_ops(&mut self, blocks: &[RollupOpsBlock]) {
         let mut ops = Vec::with_capacity(blocks.len());
 
@@ -300,6 +300,10 @@ impl InMemoryStorageInteractor {
         }
     }
 
+    pub async fn is_restore_started(&mut self) -> bool {
+        self.inner.borrow().last_watched_block != 0
+    }
+
     pub async fn get_ops_blocks_from_storage(&mut self) -> Vec<RollupOpsBlock> {
         self.inner.borrow().rollups.clone()
     }
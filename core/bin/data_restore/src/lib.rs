@@ -18,6 +18,10 @@ use zksync_types::{tokens::get_genesis_token_list, TokenId};
 // How many blocks we will process at once.
 pub const ETH_BLOCKS_STEP: u64 = 10_000;
 pub const END_ETH_BLOCKS_OFFSET: u64 = 40;
+// How many Rollup blocks are applied to the tree between the checkpoints of the restored state.
+pub const TREE_CHECKPOINT_BLOCKS: usize = 100;
+// How many Ethereum transactions with the Rollup blocks calldata are fetched at once.
+pub const CALLDATA_FETCH_CONCURRENCY: usize = 10;
 
 pub async fn add_tokens_to_storage(interactor: &mut StorageInteractor<'_>, eth_network: &str) {
     let genesis_tokens = get_genesis_token_list(eth_network).expect("Initial token list not found");
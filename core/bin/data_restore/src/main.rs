@@ -30,6 +30,11 @@ struct Opt {
     #[structopt(long = "continue", name = "continue")]
     continue_mode: bool,
 
+    /// Continues data restoring if it has been started before, restores data with provided
+    /// genesis (zero) block otherwise. Can be used to restart the interrupted restore
+    #[structopt(long)]
+    resume: bool,
+
     /// Restore data until the last verified block and exit
     #[structopt(long)]
     finite: bool,
@@ -42,6 +47,14 @@ struct Opt {
     #[structopt(long = "web3", name = "web3")]
     web3_url: Option<String>,
 
+    /// Sets the number of blocks applied to the tree between the checkpoints of the restored state
+    #[structopt(long)]
+    checkpoint_blocks: Option<usize>,
+
+    /// Sets the number of Ethereum transactions the calldata of which is fetched concurrently
+    #[structopt(long)]
+    calldata_fetch_concurrency: Option<usize>,
+
     /// Provides a path to the configuration file for data restore
     #[structopt(long = "config", name = "config")]
     config_path: Option<String>,
@@ -122,10 +135,26 @@ async fn main() {
         final_hash,
         contract,
     );
+    if let Some(checkpoint_blocks) = opt.checkpoint_blocks {
+        driver.checkpoint_blocks = checkpoint_blocks;
+    }
+    if let Some(calldata_fetch_concurrency) = opt.calldata_fetch_concurrency {
+        driver.calldata_fetch_concurrency = calldata_fetch_concurrency;
+    }
 
     let mut interactor = StorageInteractor::Database(DatabaseStorageInteractor::new(storage));
+    let (genesis, continue_mode) = if opt.resume {
+        let is_restore_started = interactor.is_restore_started().await;
+        if is_restore_started {
+            vlog::info!("Resuming the restore from the last checkpoint");
+        }
+        (!is_restore_started, is_restore_started)
+    } else {
+        (opt.genesis, opt.continue_mode)
+    };
+
     // If genesis is argument is present - there will be fetching contracts creation transactions to get first eth block and genesis acc address
-    if opt.genesis {
+    if genesis {
         // We have to load pre-defined tokens into the database before restoring state,
         // since these tokens do not have a corresponding Ethereum events.
         add_tokens_to_storage(&mut interactor, &config.eth_network.to_string()).await;
@@ -135,7 +164,7 @@ async fn main() {
             .await;
     }
 
-    if opt.continue_mode && driver.load_state_from_storage(&mut interactor).await {
+    if continue_mode && driver.load_state_from_storage(&mut interactor).await {
         std::process::exit(0);
     }
 
@@ -163,6 +163,12 @@ impl StorageInteractor<'_> {
         storage_interact!(self.save_special_token(token))
     }
 
+    /// Returns whether the restore has been started before, i.e. the genesis state is saved
+    /// and it should be continued.
+    pub async fn is_restore_started(&mut self) -> bool {
+        storage_interact!(self.is_restore_started())
+    }
+
     /// Returns Rollup contract events state from storage
     pub async fn get_block_events_state_from_storage(&mut self) -> EventsState {
         storage_interact!(self.get_block_events_state_from_storage())
@@ -1,8 +1,15 @@
-use zksync_prover::cli_utils::main_for_prover_impl;
+use zksync_config::configs::prover::ProverBackend;
+use zksync_config::ProverConfig;
+use zksync_prover::cli_utils::{main_for_gpu_prover, main_for_prover_impl};
 use zksync_prover::plonk_step_by_step_prover::PlonkStepByStepProver;
 
 #[tokio::main]
 async fn main() {
     let run_prometheus_exporter = true;
-    main_for_prover_impl::<PlonkStepByStepProver>(run_prometheus_exporter).await;
+    match ProverConfig::from_env().prover.backend {
+        ProverBackend::Cpu => {
+            main_for_prover_impl::<PlonkStepByStepProver>(run_prometheus_exporter).await
+        }
+        ProverBackend::Gpu => main_for_gpu_prover(run_prometheus_exporter).await,
+    }
 }
@@ -6,6 +6,8 @@ use structopt::StructOpt;
 use zksync_config::configs::ProverConfig as EnvProverConfig;
 use zksync_utils::{get_env, parse_env};
 // Local deps
+use crate::gpu::{self, GpuDevice};
+use crate::gpu_prover::{GpuProver, GpuProverConfig};
 use crate::{client, prover_work_cycle, ProverConfig, ProverImpl, ShutdownRequest};
 use zksync_config::configs::api::PrometheusConfig;
 use zksync_prometheus_exporter::run_prometheus_exporter;
@@ -46,8 +48,76 @@ where
 
     vlog::info!("creating prover, worker name: {}", worker_name);
 
-    // Create client.
+    let shutdown_request = shutdown_request_on_termination();
 
+    if run_prometheus {
+        let prom_config = PrometheusConfig::from_env();
+        run_prometheus_exporter("prover", prom_config.port);
+    }
+
+    prover_work_cycle(
+        prover,
+        api_client,
+        shutdown_request,
+        prover_options,
+        &worker_name,
+    )
+    .await;
+}
+
+/// Runs a GPU prover per each of the used GPU devices, so every device proves its own jobs.
+pub async fn main_for_gpu_prover(run_prometheus: bool) {
+    let opt = Opt::from_args();
+    let worker_name = opt.worker_name;
+
+    // used env
+    let prover_options = EnvProverConfig::from_env();
+    let prover_config = GpuProverConfig::from_env();
+    let api_client = api_client_from_env();
+
+    let _vlog_guard = vlog::init();
+
+    let devices = gpu::enumerate_devices()
+        .and_then(|available| gpu::select_devices(available, &prover_options.prover.gpu_devices))
+        .expect("Failed to choose the GPU devices");
+
+    let shutdown_request = shutdown_request_on_termination();
+
+    if run_prometheus {
+        let prom_config = PrometheusConfig::from_env();
+        run_prometheus_exporter("prover", prom_config.port);
+    }
+
+    let workers = devices.into_iter().map(|device: GpuDevice| {
+        let worker_name = format!("{}-gpu{}", worker_name, device.index);
+        vlog::info!(
+            "creating GPU prover, worker name: {}, device: {} ({}, {} MiB)",
+            worker_name,
+            device.index,
+            device.name,
+            device.memory_mb
+        );
+        let prover = GpuProver::create_from_config(prover_config.for_device(device.index));
+        let api_client = api_client.clone();
+        let shutdown_request = shutdown_request.clone();
+        let prover_options = prover_options.clone();
+
+        async move {
+            prover_work_cycle(
+                prover,
+                api_client,
+                shutdown_request,
+                prover_options,
+                &worker_name,
+            )
+            .await
+        }
+    });
+    futures::future::join_all(workers).await;
+}
+
+/// Creates the shutdown request set once the termination signal is received.
+fn shutdown_request_on_termination() -> ShutdownRequest {
     let shutdown_request = ShutdownRequest::new();
 
     // Handle termination requests.
@@ -68,17 +138,5 @@ where
         .expect("Failed to register ctrlc handler");
     }
 
-    if run_prometheus {
-        let prom_config = PrometheusConfig::from_env();
-        run_prometheus_exporter(prom_config.port);
-    }
-
-    prover_work_cycle(
-        prover,
-        api_client,
-        shutdown_request,
-        prover_options,
-        &worker_name,
-    )
-    .await;
+    shutdown_request
 }
@@ -0,0 +1,74 @@
+//! Enumeration of the GPU devices available to the `gpu` proving backend.
+
+// Built-in deps
+use std::process::Command;
+
+/// GPU device available to the prover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuDevice {
+    /// Index of the device, as used by `CUDA_VISIBLE_DEVICES`.
+    pub index: usize,
+    pub name: String,
+    /// Total memory of the device in MiB.
+    pub memory_mb: u64,
+}
+
+/// Lists the available GPU devices using `nvidia-smi`.
+pub fn enumerate_devices() -> anyhow::Result<Vec<GpuDevice>> {
+    let output = Command::new("nvidia-smi")
+        .args(&[
+            "--query-gpu=index,name,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .map_err(|e| anyhow::format_err!("Failed to run nvidia-smi: {}", e))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "nvidia-smi failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    parse_devices(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the `nvidia-smi --query-gpu=index,name,memory.total --format=csv,noheader,nounits` output.
+pub fn parse_devices(output: &str) -> anyhow::Result<Vec<GpuDevice>> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            anyhow::ensure!(fields.len() == 3, "Unexpected GPU device line: {}", line);
+
+            Ok(GpuDevice {
+                index: fields[0].parse()?,
+                name: fields[1].to_string(),
+                memory_mb: fields[2].parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Chooses the devices the workers are run on: the configured ones, or all the available
+/// devices if none are configured.
+pub fn select_devices(
+    available: Vec<GpuDevice>,
+    configured: &[usize],
+) -> anyhow::Result<Vec<GpuDevice>> {
+    if configured.is_empty() {
+        anyhow::ensure!(!available.is_empty(), "No GPU devices are available");
+        return Ok(available);
+    }
+
+    configured
+        .iter()
+        .map(|&index| {
+            available
+                .iter()
+                .find(|device| device.index == index)
+                .cloned()
+                .ok_or_else(|| anyhow::format_err!("GPU device {} is not available", index))
+        })
+        .collect()
+}
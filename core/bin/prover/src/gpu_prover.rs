@@ -0,0 +1,100 @@
+// Built-in deps
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+// Workspace deps
+use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_prover_utils::api::{JobRequestData, JobResultData};
+// Local deps
+use crate::{ProverConfig, ProverImpl};
+
+/// Prover delegating the proving to the external GPU proving command.
+///
+/// Each prover is assigned to a single GPU device, the command only sees this device
+/// through the `CUDA_VISIBLE_DEVICES`.
+#[derive(Debug)]
+pub struct GpuProver {
+    config: GpuProverConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct GpuProverConfig {
+    /// Command of the GPU proving backend, with its arguments separated by whitespaces.
+    pub command: String,
+    /// Index of the GPU device assigned to the prover.
+    pub device: usize,
+}
+
+impl ProverConfig for GpuProverConfig {
+    fn from_env() -> Self {
+        let env_config = EnvProverConfig::from_env();
+
+        Self {
+            command: env_config.prover.gpu_prover_command,
+            device: env_config.prover.gpu_devices.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl GpuProverConfig {
+    /// Returns the config of the prover assigned to the given device.
+    pub fn for_device(&self, device: usize) -> Self {
+        Self {
+            command: self.command.clone(),
+            device,
+        }
+    }
+}
+
+impl ProverImpl for GpuProver {
+    type Config = GpuProverConfig;
+
+    fn create_from_config(config: Self::Config) -> Self {
+        assert!(
+            !config.command.trim().is_empty(),
+            "GPU prover command should be set for the gpu backend"
+        );
+        Self { config }
+    }
+
+    fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData> {
+        let start = Instant::now();
+        let job_type = match &data {
+            JobRequestData::BlockProof(..) => "single_proof",
+            JobRequestData::AggregatedBlockProof(..) => "aggregated_proof",
+        };
+
+        let mut args = self.config.command.split_whitespace();
+        let program = args.next().expect("GPU prover command should not be empty");
+        let mut child = Command::new(program)
+            .args(args)
+            .env("CUDA_VISIBLE_DEVICES", self.config.device.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| anyhow::format_err!("Failed to start the GPU prover: {}", e))?;
+
+        {
+            // Stdin is closed once dropped, so the command knows the whole job is received.
+            let mut stdin = child
+                .stdin
+                .take()
+                .expect("Stdin of the GPU prover is piped");
+            serde_json::to_writer(&mut stdin, &data)?;
+            stdin.flush()?;
+        }
+        let output = child.wait_with_output()?;
+        anyhow::ensure!(
+            output.status.success(),
+            "GPU prover on device {} failed: {}",
+            self.config.device,
+            output.status
+        );
+        let proof = serde_json::from_slice(&output.stdout)
+            .map_err(|e| anyhow::format_err!("Failed to parse the GPU prover output: {}", e))?;
+
+        metrics::histogram!("prover", start.elapsed(), "stage" => "create_proof", "type" => job_type, "backend" => "gpu");
+        Ok(proof)
+    }
+}
@@ -2,6 +2,8 @@ pub mod auth_utils;
 pub mod cli_utils;
 pub mod client;
 pub mod dummy_prover;
+pub mod gpu;
+pub mod gpu_prover;
 pub mod plonk_step_by_step_prover;
 
 // Built-in deps
@@ -124,7 +126,7 @@ async fn heartbeat_future_handle<CLIENT>(
             let between = Range::new(0.8f64, 2.0);
             let mut rng = thread_rng();
             let random_multiplier = between.ind_sample(&mut rng);
-            Duration::from_secs((heartbeat_interval.as_secs_f64() * random_multiplier) as u64)
+            heartbeat_interval.mul_f64(random_multiplier)
         };
         tokio::time::sleep(timeout_value).await;
 
@@ -157,7 +159,10 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             break;
         }
 
-        let aux_data = prover.get_request_aux_data();
+        let aux_data = ProverInputRequestAuxData {
+            block_sizes: prover_options.prover.block_sizes.clone(),
+            ..prover.get_request_aux_data()
+        };
         let prover_input_response = match client
             .get_job(ProverInputRequest {
                 prover_name: prover_name.to_string(),
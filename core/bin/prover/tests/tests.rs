@@ -18,6 +18,7 @@ use zksync_crypto::{
     Fr,
 };
 use zksync_prover::dummy_prover::{DummyProver, DummyProverConfig};
+use zksync_prover::gpu::{parse_devices, select_devices, GpuDevice};
 use zksync_prover::plonk_step_by_step_prover::{
     PlonkStepByStepProver, PlonkStepByStepProverConfig,
 };
@@ -57,6 +58,10 @@ impl Default for MockProverConfigs {
                 cycle_wait: 500,
                 request_timeout: 1,
                 die_after_proof: false,
+                block_sizes: Vec::new(),
+                backend: Default::default(),
+                gpu_devices: Vec::new(),
+                gpu_prover_command: String::new(),
             },
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
@@ -65,6 +70,9 @@ impl Default for MockProverConfigs {
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
+                max_memory_mb: 4096,
+                witness_retention_days: None,
+                tree_cache_path: None,
             },
         };
 
@@ -300,3 +308,34 @@ impl zksync_prover::ApiClient for MockApiClient {
         Ok(())
     }
 }
+
+/// Checks that the GPU devices are parsed from the `nvidia-smi` output,
+/// and only the configured ones are used if any are configured.
+#[test]
+fn test_gpu_devices_selection() {
+    let output = "0, NVIDIA A100-SXM4-40GB, 40960\n1, NVIDIA A100-SXM4-40GB, 40960\n\n";
+    let devices = parse_devices(output).unwrap();
+    assert_eq!(
+        devices,
+        vec![
+            GpuDevice {
+                index: 0,
+                name: "NVIDIA A100-SXM4-40GB".to_string(),
+                memory_mb: 40960,
+            },
+            GpuDevice {
+                index: 1,
+                name: "NVIDIA A100-SXM4-40GB".to_string(),
+                memory_mb: 40960,
+            },
+        ]
+    );
+    assert!(parse_devices("0, NVIDIA A100-SXM4-40GB").is_err());
+
+    let all_devices = select_devices(devices.clone(), &[]).unwrap();
+    assert_eq!(all_devices, devices);
+    let configured_devices = select_devices(devices.clone(), &[1]).unwrap();
+    assert_eq!(configured_devices, vec![devices[1].clone()]);
+    assert!(select_devices(devices, &[2]).is_err());
+    assert!(select_devices(Vec::new(), &[]).is_err());
+}
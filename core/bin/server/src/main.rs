@@ -1,31 +1,60 @@
 use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
-use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
+use zksync_api::fee_ticker::{
+    gas_price_watcher::GasPriceWatcher, run_updaters, FeeTicker, TickerInfo,
+};
+use zksync_api::{
+    api_server::committed_state_cache::CommittedStateCache,
+    eth_checker::CircuitBreaker,
+    signature_checker::{EthClientSwitch, SignatureCheckerConfig},
+};
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_client::EthereumGateway;
 use zksync_forced_exit_requests::run_forced_exit_requests_actors;
 use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
 use zksync_witness_generator::run_prover_server;
 
-use tokio::task::JoinHandle;
-use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfig};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task::JoinHandle,
+};
+use zksync_config::configs::api::{
+    HealthCheckConfig, PrivateApiConfig, PrometheusConfig, TokenConfig,
+};
 use zksync_config::{
-    configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
-    ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    configs::api::{
+        AdminApiConfig, CommonApiConfig, GrpcConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig,
+        Web3Config, WebhooksConfig,
+    },
+    ChainConfig, ConfigFile, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig,
+    ETHWatchConfig, EventPublisherConfig, ForcedExitRequestsConfig, GatewayWatcherConfig,
+    ProverConfig, RuntimeConfig, TickerConfig, ZkSyncConfig, CONFIG_FILE_ENV_VAR,
+};
+use zksync_core::{
+    history_pruner::run_history_pruner, rejected_tx_cleaner::run_rejected_tx_cleaner,
+    stats_aggregator::run_stats_aggregator,
 };
-use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
-use zksync_mempool::run_mempool_tx_handler;
+use zksync_health_check::{run_health_check_server, CheckKind, HealthChecks, Heartbeat};
+use zksync_mempool::{run_mempool_tx_handler, MempoolLimits};
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
 use zksync_storage::ConnectionPool;
+use zksync_utils::{
+    panic_notify::spawn_panic_handler,
+    shutdown::{Shutdown, ShutdownToken},
+};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
+/// Time the components have to finish the in-flight work after the stop signal, it's less than
+/// the default termination grace period of Kubernetes.
+const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(20);
 
 #[derive(Debug, Clone, Copy)]
 pub enum ServerCommand {
@@ -40,6 +69,10 @@ pub enum Component {
     Web3Api,
     RpcApi,
     RpcWebSocketApi,
+    GrpcApi,
+    AdminApi,
+    WebhookNotifier,
+    EventPublisher,
 
     // Core components
     Fetchers,
@@ -52,6 +85,8 @@ pub enum Component {
     Prometheus,
     PrometheusPeriodicMetrics,
     RejectedTaskCleaner,
+    HistoryPruner,
+    StatsAggregator,
 }
 
 impl FromStr for Component {
@@ -63,6 +98,10 @@ impl FromStr for Component {
             "web3-api" => Ok(Component::Web3Api),
             "rpc-api" => Ok(Component::RpcApi),
             "rpc-websocket-api" => Ok(Component::RpcWebSocketApi),
+            "grpc-api" => Ok(Component::GrpcApi),
+            "admin-api" => Ok(Component::AdminApi),
+            "webhook-notifier" => Ok(Component::WebhookNotifier),
+            "event-publisher" => Ok(Component::EventPublisher),
             "eth-sender" => Ok(Component::EthSender),
             "witness-generator" => Ok(Component::WitnessGenerator),
             "forced-exit" => Ok(Component::ForcedExit),
@@ -70,6 +109,8 @@ impl FromStr for Component {
             "fetchers" => Ok(Component::Fetchers),
             "core" => Ok(Component::Core),
             "rejected-task-cleaner" => Ok(Component::RejectedTaskCleaner),
+            "history-pruner" => Ok(Component::HistoryPruner),
+            "stats-aggregator" => Ok(Component::StatsAggregator),
             "prometheus-periodic-metrics" => Ok(Component::PrometheusPeriodicMetrics),
             other => Err(format!("{} is not a valid component name", other)),
         }
@@ -92,6 +133,8 @@ impl Default for ComponentsToRun {
             Component::Prometheus,
             Component::Core,
             Component::RejectedTaskCleaner,
+            Component::HistoryPruner,
+            Component::StatsAggregator,
             Component::Fetchers,
             Component::PrometheusPeriodicMetrics,
         ])
@@ -122,11 +165,23 @@ struct Opt {
         default_value = "rest-api,web3-api,rpc-api,rpc-websocket-api,eth-sender,witness-generator,forced-exit,prometheus,core,rejected-task-cleaner,fetchers,prometheus-periodic-metrics"
     )]
     components: ComponentsToRun,
+    /// TOML or YAML config file, the environment variables override its options
+    #[structopt(long, env = "ZKSYNC_CONFIG_FILE")]
+    config: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
+    if let Some(path) = &opt.config {
+        // The file is loaded once again when the runtime config is reloaded.
+        std::env::set_var(CONFIG_FILE_ENV_VAR, path);
+        let effective_config = ConfigFile::load(path)?.apply();
+        // Fails at the startup if any option is missing or malformed.
+        ZkSyncConfig::from_env();
+        println!("Effective config from {:?}:\n{}", path, effective_config);
+    }
+
     let mut _vlog_guard = None;
     let server_mode = if opt.genesis {
         ServerCommand::Genesis
@@ -154,8 +209,27 @@ async fn run_server(components: &ComponentsToRun) {
     let connection_pool = ConnectionPool::new(None);
     let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
+    // Components finish the in-flight work on the stop signal. The signature checker is stopped
+    // before the mempool handlers of the API, so the checked transactions reach the mempool.
+    let shutdown = Shutdown::new();
+    let sign_checker_shutdown = Shutdown::new();
+    let api_mempool_shutdown = Shutdown::new();
 
     let mut tasks = vec![];
+    // Components add the checks of their database and Ethereum node connections, internal channels
+    // and event loops, which are served by the health check server.
+    let mut health_checks = HealthChecks::default();
+
+    // Components use the current values of the runtime config, so they can be changed
+    // without the restart by reloading the config file.
+    RuntimeConfig::from_env().init();
+    if let Some(task) = run_config_reloader() {
+        tasks.push(task);
+    }
+
+    if let Some(task) = read_only_connection_pool.run_replica_health_check() {
+        tasks.push(task);
+    }
 
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
@@ -172,14 +246,38 @@ async fn run_server(components: &ComponentsToRun) {
         tasks.append(&mut price_tasks);
     }
 
+    // Committed account states are cached for the API only if the committer runs in this process,
+    // since it's the committer who invalidates the cache.
+    let committed_state_cache_size = CommonApiConfig::from_env().committed_state_cache_size;
+    let (committed_states, committed_state_updates) =
+        if components.0.contains(&Component::Core) && committed_state_cache_size > 0 {
+            let (sender, receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+            let cache = CommittedStateCache::with_invalidator(
+                committed_state_cache_size,
+                connection_pool.clone(),
+                receiver,
+            );
+            (Some(cache), Some(sender))
+        } else {
+            (None, None)
+        };
+
+    // Ethereum nodes of the signature checker can be switched via the admin API
+    // if both run in this process.
+    let mut sign_checker_eth_client_switch = None;
     if components.0.iter().any(|c| {
         matches!(
             c,
-            Component::RpcWebSocketApi | Component::RpcApi | Component::RestApi
+            Component::RpcWebSocketApi
+                | Component::RpcApi
+                | Component::RestApi
+                | Component::GrpcApi
         )
     }) {
         // Create gateway
         let eth_gateway = create_eth_gateway();
+        health_checks.add_database("api.database", read_only_connection_pool.clone());
+        health_checks.add_web3("api.web3", eth_gateway.clone());
 
         let eth_watch_config = ETHWatchConfig::from_env();
         let gateway_watcher_config = GatewayWatcherConfig::from_env();
@@ -191,19 +289,85 @@ async fn run_server(components: &ComponentsToRun) {
             tasks.push(task);
         }
 
+        let contracts_config = ContractsConfig::from_env();
+        let common_config = CommonApiConfig::from_env();
+        let chain_config = ChainConfig::from_env();
+
         // Run signer
-        let (sign_check_sender, sign_check_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
-        tasks.push(zksync_api::signature_checker::start_sign_checker(
-            eth_gateway,
-            sign_check_receiver,
+        let (sign_checker_client_sender, sign_checker_client) =
+            tokio::sync::watch::channel(eth_gateway);
+        sign_checker_eth_client_switch = Some(EthClientSwitch::new(
+            sign_checker_client_sender,
+            ETHClientConfig::from_env(),
+            ETHSenderConfig::from_env(),
+            contracts_config.contract_addr,
         ));
+        let (sign_checker_panic_handler, sign_checker_panic_sender) = spawn_panic_handler();
+        let mut sign_checker_config = SignatureCheckerConfig::from(&common_config);
+        let forced_exit_requests_config = ForcedExitRequestsConfig::from_env();
+        if forced_exit_requests_config.enabled {
+            sign_checker_config.forced_exit_operator =
+                Some(forced_exit_requests_config.sender_account_address);
+        }
+        // Wallets signing the EIP-712 typed data or the chain-bound messages bind them
+        // to the chain the server works with.
+        let chain_id = ETHClientConfig::from_env().chain_id;
+        sign_checker_config.eip712_chain_id = Some(chain_id);
+        sign_checker_config.message_chain_id = Some(chain_id);
+        // Onchain `ChangePubKey` authorizations are aggregated via Multicall as well.
+        sign_checker_config.zksync_contract_address = Some(contracts_config.contract_addr);
+        sign_checker_config
+            .ensure_allowed_on(chain_config.eth.network)
+            .expect("Invalid signature checker configuration");
+        // Server isn't ready while the checks requiring the Ethereum node are rejected.
+        if let Some(threshold) = sign_checker_config.eth_failure_threshold {
+            let circuit_breaker =
+                CircuitBreaker::new(threshold, sign_checker_config.eth_probe_interval);
+            sign_checker_config.eth_circuit_breaker = Some(circuit_breaker.clone());
+            health_checks.add_check("api.eth_circuit_breaker", CheckKind::Readiness, move || {
+                let health = circuit_breaker.health();
+                async move { health }
+            });
+        }
+        let max_concurrent_verifications = sign_checker_config.max_concurrent_verifications;
+        let (sign_check_sender, sign_checker_task, sign_checker_queue_depth) =
+            zksync_api::signature_checker::spawn_sign_checker(
+                sign_checker_client,
+                sign_checker_config,
+                common_config.sign_checker_channel_capacity,
+                sign_checker_panic_sender,
+                sign_checker_shutdown.token(),
+            );
+        tasks.push(sign_checker_task);
+        tasks.push(sign_checker_panic_handler);
+        health_checks.add_channel("api.signature_checker", sign_check_sender.clone());
+        // Server isn't ready while all the signature check slots are taken, since the new
+        // transactions are rejected as overloaded until some of the checks finish.
+        if let Some(max_concurrent_verifications) = max_concurrent_verifications {
+            health_checks.add_check(
+                "api.signature_checker_queue",
+                CheckKind::Readiness,
+                move || {
+                    let depth = *sign_checker_queue_depth.borrow();
+                    async move {
+                        if depth >= max_concurrent_verifications {
+                            Err(format!("all {} signature check slots are taken", depth))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                },
+            );
+        }
 
-        let contracts_config = ContractsConfig::from_env();
-        let common_config = CommonApiConfig::from_env();
         let token_config = TokenConfig::from_env();
-        let chain_config = ChainConfig::from_env();
         let fee_ticker_config = TickerConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+        let gas_price_watcher =
+            GasPriceWatcher::with_updater(read_only_connection_pool.clone(), &fee_ticker_config);
+        let ticker_info = Box::new(TickerInfo::new(
+            read_only_connection_pool.clone(),
+            gas_price_watcher,
+        ));
 
         let ticker = FeeTicker::new_with_default_validator(
             ticker_info,
@@ -219,7 +383,10 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                MempoolLimits::from_config(&chain_config.state_keeper),
+                api_mempool_shutdown.token(),
             ));
+            health_checks.add_channel("ws_api.mempool", mempool_tx_request_sender.clone());
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
@@ -230,6 +397,7 @@ async fn run_server(components: &ComponentsToRun) {
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                committed_states.clone(),
             ));
         }
 
@@ -240,7 +408,10 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                MempoolLimits::from_config(&chain_config.state_keeper),
+                api_mempool_shutdown.token(),
             ));
+            health_checks.add_channel("rpc_api.mempool", mempool_tx_request_sender.clone());
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
                 sign_check_sender.clone(),
@@ -250,17 +421,46 @@ async fn run_server(components: &ComponentsToRun) {
                 &token_config,
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                committed_states.clone(),
+            ));
+        }
+
+        if components.0.contains(&Component::GrpcApi) {
+            let (mempool_tx_request_sender, mempool_tx_request_receiver) =
+                mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+            tasks.push(run_mempool_tx_handler(
+                connection_pool.clone(),
+                mempool_tx_request_receiver,
+                chain_config.state_keeper.block_chunk_sizes.clone(),
+                MempoolLimits::from_config(&chain_config.state_keeper),
+                api_mempool_shutdown.token(),
+            ));
+            health_checks.add_channel("grpc_api.mempool", mempool_tx_request_sender.clone());
+            tasks.push(zksync_api::api_server::grpc::start_grpc_server(
+                read_only_connection_pool.clone(),
+                sign_check_sender.clone(),
+                ticker.clone(),
+                &GrpcConfig::from_env(),
+                &common_config,
+                &token_config,
+                mempool_tx_request_sender,
+                eth_watch_config.confirmations_for_eth_event,
+                committed_states,
             ));
         }
 
         if components.0.contains(&Component::RestApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
                 mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+            let mempool_limits = MempoolLimits::from_config(&chain_config.state_keeper);
             tasks.push(run_mempool_tx_handler(
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes,
+                mempool_limits,
+                api_mempool_shutdown.token(),
             ));
+            health_checks.add_channel("rest_api.mempool", mempool_tx_request_sender.clone());
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
                 read_only_connection_pool.clone(),
@@ -276,7 +476,11 @@ async fn run_server(components: &ComponentsToRun) {
     }
 
     if components.0.contains(&Component::EthSender) {
-        tasks.push(run_eth_sender(connection_pool.clone()))
+        tasks.push(run_eth_sender(
+            connection_pool.clone(),
+            &mut health_checks,
+            shutdown.token(),
+        ))
     }
 
     if components.0.contains(&Component::Core) {
@@ -288,20 +492,64 @@ async fn run_server(components: &ComponentsToRun) {
                 read_only_connection_pool.clone(),
                 &ZkSyncConfig::from_env(),
                 eth_gateway.clone(),
+                &mut health_checks,
+                committed_state_updates,
+                shutdown.token(),
             )
             .await
             .unwrap(),
         );
     }
 
+    if components.0.contains(&Component::AdminApi) {
+        let state_keeper_config = ChainConfig::from_env().state_keeper;
+        let mempool_limits = MempoolLimits::from_config(&state_keeper_config);
+        let (mempool_tx_request_sender, mempool_tx_request_receiver) =
+            mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+        tasks.push(run_mempool_tx_handler(
+            connection_pool.clone(),
+            mempool_tx_request_receiver,
+            state_keeper_config.block_chunk_sizes,
+            mempool_limits,
+            api_mempool_shutdown.token(),
+        ));
+        // Admin API stores the minted API keys and evicts the mempool transactions,
+        // so it uses the main database.
+        tasks.push(zksync_api::api_server::admin_server::start_admin_server(
+            connection_pool.clone(),
+            mempool_tx_request_sender,
+            sign_checker_eth_client_switch,
+            AdminApiConfig::from_env(),
+        ));
+    }
+
+    if components.0.contains(&Component::WebhookNotifier) {
+        // Notifier stores the delivery state, so it uses the main database.
+        tasks.push(zksync_api::webhook_notifier::run_webhook_notifier(
+            connection_pool.clone(),
+            WebhooksConfig::from_env(),
+        ));
+    }
+
+    if components.0.contains(&Component::EventPublisher) {
+        // Publisher stores the id of the last published event, so it uses the main database.
+        tasks.push(zksync_event_listener::publisher::run_event_publisher(
+            connection_pool.clone(),
+            EventPublisherConfig::from_env(),
+        ));
+    }
+
     if components.0.contains(&Component::WitnessGenerator) {
-        tasks.push(run_witness_generator(connection_pool.clone()))
+        tasks.push(run_witness_generator(
+            connection_pool.clone(),
+            &mut health_checks,
+        ))
     }
 
     if components.0.contains(&Component::Prometheus) {
         // Run prometheus data exporter.
         let config = PrometheusConfig::from_env();
-        let prometheus_task_handle = run_prometheus_exporter(config.port);
+        let prometheus_task_handle = run_prometheus_exporter("server", config.port);
         tasks.push(prometheus_task_handle);
         // We can run them only with active prometheus
         if components.0.contains(&Component::PrometheusPeriodicMetrics) {
@@ -311,7 +559,22 @@ async fn run_server(components: &ComponentsToRun) {
     }
 
     if components.0.contains(&Component::ForcedExit) {
-        tasks.append(&mut run_forced_exit(connection_pool.clone()));
+        tasks.append(&mut run_forced_exit(
+            connection_pool.clone(),
+            api_mempool_shutdown.token(),
+        ));
+    }
+
+    if components.0.contains(&Component::HistoryPruner) {
+        let config = DBConfig::from_env();
+        if let Some(task) = run_history_pruner(&config, connection_pool.clone()) {
+            tasks.push(task);
+        }
+    }
+
+    if components.0.contains(&Component::StatsAggregator) {
+        let config = DBConfig::from_env();
+        tasks.push(run_stats_aggregator(&config, connection_pool.clone()));
     }
 
     if components.0.contains(&Component::RejectedTaskCleaner) {
@@ -319,6 +582,14 @@ async fn run_server(components: &ComponentsToRun) {
         tasks.push(run_rejected_tx_cleaner(&config, connection_pool));
     }
 
+    tasks.push(run_health_check_server(
+        HealthCheckConfig::from_env().bind_addr(),
+        health_checks,
+    ));
+    shutdown
+        .token()
+        .propagate(vec![sign_checker_shutdown, api_mempool_shutdown]);
+
     {
         let stop_signal_sender = RefCell::new(stop_signal_sender.clone());
         ctrlc::set_handler(move || {
@@ -336,9 +607,41 @@ async fn run_server(components: &ComponentsToRun) {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+
+    tokio::select! {
+        _ = shutdown.stop() => {
+            vlog::info!("All the components are stopped");
+        },
+        _ = tokio::time::sleep(GRACEFUL_SHUTDOWN_TIMEOUT) => {
+            vlog::warn!(
+                "Components are not stopped within {}s, exiting",
+                GRACEFUL_SHUTDOWN_TIMEOUT.as_secs()
+            );
+        },
+        _ = async { stop_signal_receiver.next().await } => {
+            vlog::warn!("Second stop signal received, exiting without waiting for the components");
+        }
+    };
+}
+
+/// Reloads the runtime config from the config file on `SIGHUP`, if the file is set.
+pub fn run_config_reloader() -> Option<JoinHandle<()>> {
+    std::env::var_os(CONFIG_FILE_ENV_VAR)?;
+    Some(tokio::spawn(async move {
+        let mut hangup = signal(SignalKind::hangup()).expect("failed to listen to SIGHUP");
+        while hangup.recv().await.is_some() {
+            match RuntimeConfig::reload_from_file() {
+                Ok(config) => vlog::info!("Reloaded the runtime config: {:?}", config),
+                Err(err) => vlog::error!("Failed to reload the runtime config: {}", err),
+            }
+        }
+    }))
 }
 
-pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
+pub fn run_forced_exit(
+    connection_pool: ConnectionPool,
+    shutdown: ShutdownToken,
+) -> Vec<JoinHandle<()>> {
     vlog::info!("Starting the ForcedExitRequests actors");
     let config = ForcedExitRequestsConfig::from_env();
     let common_config = CommonApiConfig::from_env();
@@ -348,10 +651,13 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
 
     let (mempool_tx_request_sender, mempool_tx_request_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+    let mempool_limits = MempoolLimits::from_config(&chain_config.state_keeper);
     let mempool_task = run_mempool_tx_handler(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         chain_config.state_keeper.block_chunk_sizes,
+        mempool_limits,
+        shutdown,
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -364,15 +670,29 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     vec![mempool_task, forced_exit_task]
 }
 
-pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()> {
+pub fn run_witness_generator(
+    connection_pool: ConnectionPool,
+    health_checks: &mut HealthChecks,
+) -> JoinHandle<()> {
     vlog::info!("Starting the Prover server actors");
     let prover_api_config = ProverApiConfig::from_env();
     let prover_config = ProverConfig::from_env();
+    let heartbeat = Heartbeat::default();
+    health_checks.add_database("prover_server.database", connection_pool.clone());
+    health_checks.add_heartbeat(
+        "prover_server.job_queue",
+        heartbeat.clone(),
+        HealthCheckConfig::from_env().heartbeat_timeout(),
+    );
     let database = zksync_witness_generator::database::Database::new(connection_pool);
-    run_prover_server(database, prover_api_config, prover_config)
+    run_prover_server(database, prover_api_config, prover_config, heartbeat)
 }
 
-pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
+pub fn run_eth_sender(
+    connection_pool: ConnectionPool,
+    health_checks: &mut HealthChecks,
+    shutdown: ShutdownToken,
+) -> JoinHandle<()> {
     vlog::info!("Starting the Ethereum sender actors");
     let eth_client_config = ETHClientConfig::from_env();
     let eth_sender_config = ETHSenderConfig::from_env();
@@ -382,8 +702,40 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
         &eth_sender_config,
         contracts.contract_addr,
     );
+    let additional_eth_gateways = EthereumGateway::additional_operators_from_config(
+        &eth_client_config,
+        &eth_sender_config,
+        contracts.contract_addr,
+    );
 
-    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config)
+    let withdrawals_eth_gateway = if eth_sender_config.withdrawals.is_enabled {
+        Some(EthereumGateway::withdrawals_sender_from_config(
+            &eth_client_config,
+            &eth_sender_config,
+            contracts.contract_addr,
+        ))
+    } else {
+        None
+    };
+
+    let heartbeat = Heartbeat::default();
+    health_checks.add_database("eth_sender.database", connection_pool.clone());
+    health_checks.add_web3("eth_sender.web3", eth_gateway.clone());
+    health_checks.add_heartbeat(
+        "eth_sender.main_loop",
+        heartbeat.clone(),
+        HealthCheckConfig::from_env().heartbeat_timeout(),
+    );
+
+    zksync_eth_sender::run_eth_sender(
+        connection_pool,
+        eth_gateway,
+        additional_eth_gateways,
+        withdrawals_eth_gateway,
+        eth_sender_config,
+        heartbeat,
+        shutdown,
+    )
 }
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -0,0 +1,191 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::ensure;
+use serde::Serialize;
+use structopt::StructOpt;
+use zksync_crypto::ff;
+use zksync_state::state::ZkSyncState;
+use zksync_storage::{snapshot::records::StateSnapshot, StorageProcessor};
+use zksync_types::{AccountId, AccountMap, Address, TokenInfo};
+
+/// Compression level of the snapshot files.
+const COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Exports the state at the last verified block into the snapshot file
+    Export {
+        /// Path of the snapshot file to create.
+        #[structopt(long, short)]
+        output: PathBuf,
+    },
+    /// Imports the snapshot file into the empty database
+    Import {
+        /// Path of the snapshot file to import.
+        #[structopt(long, short)]
+        input: PathBuf,
+    },
+    /// Imports the snapshot file into the empty database as the genesis state of a new network
+    /// and generates the parameters to deploy its contracts with
+    Genesis {
+        /// Path of the snapshot file to import.
+        #[structopt(long, short)]
+        input: PathBuf,
+        /// Path of the deployment parameters file to create.
+        #[structopt(long, short)]
+        output: PathBuf,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync state snapshot tool", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to export the state of zkSync network into a snapshot file and to bootstrap a new node or a new network from it"
+)]
+struct Opt {
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+/// Parameters to deploy the contracts of the network seeded from the snapshot with.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeploymentParams {
+    /// Root hash of the genesis state, `CONTRACTS_GENESIS_ROOT` of the contracts.
+    genesis_root: String,
+    /// Address of the account 0 that collects the fees of the genesis block.
+    fee_account_address: Address,
+    /// Tokens to list in the governance contract, in the order of their IDs.
+    tokens: Vec<TokenInfo>,
+}
+
+/// Checks that the accounts of the snapshot match the root hash of its block.
+fn verify_snapshot(snapshot: &StateSnapshot) -> anyhow::Result<()> {
+    let accounts: AccountMap = snapshot.accounts.iter().cloned().collect();
+    let root_hash = ZkSyncState::from_acc_map(accounts).root_hash();
+    ensure!(
+        root_hash == snapshot.block.new_root_hash,
+        "Root hash of the snapshot accounts doesn't match the root hash of the block {}",
+        *snapshot.block.block_number
+    );
+    Ok(())
+}
+
+async fn export_snapshot(output: PathBuf) -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    let snapshot = storage.snapshot_schema().export_snapshot().await?;
+    println!(
+        "Exporting the state at the block {}: {} accounts, {} tokens, {} NFTs",
+        *snapshot.block.block_number,
+        snapshot.accounts.len(),
+        snapshot.tokens.len(),
+        snapshot.nfts.len()
+    );
+
+    let file = BufWriter::new(File::create(&output)?);
+    let mut encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL)?;
+    serde_json::to_writer(&mut encoder, &snapshot)?;
+    encoder.finish()?;
+
+    println!("Snapshot is saved to {}", output.display());
+    Ok(())
+}
+
+async fn import_snapshot(input: PathBuf) -> anyhow::Result<()> {
+    let snapshot = read_snapshot(&input)?;
+    println!(
+        "Importing the state at the block {}: {} accounts, {} tokens, {} NFTs",
+        *snapshot.block.block_number,
+        snapshot.accounts.len(),
+        snapshot.tokens.len(),
+        snapshot.nfts.len()
+    );
+
+    // A corrupted snapshot would make the node diverge from the network, so it's rejected
+    // before anything is stored.
+    verify_snapshot(&snapshot)?;
+
+    let mut storage = StorageProcessor::establish_connection().await?;
+    storage.snapshot_schema().import_snapshot(snapshot).await?;
+
+    println!("Snapshot is imported");
+    Ok(())
+}
+
+fn read_snapshot(input: &Path) -> anyhow::Result<StateSnapshot> {
+    let decoder = zstd::Decoder::new(File::open(input)?)?;
+    let snapshot: StateSnapshot = serde_json::from_reader(decoder)?;
+    Ok(snapshot)
+}
+
+async fn import_snapshot_as_genesis(input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
+    let snapshot = read_snapshot(&input)?;
+    println!(
+        "Importing the state at the block {} as the genesis state: {} accounts, {} tokens, {} NFTs",
+        *snapshot.block.block_number,
+        snapshot.accounts.len(),
+        snapshot.tokens.len(),
+        snapshot.nfts.len()
+    );
+    verify_snapshot(&snapshot)?;
+
+    // The governance contract assigns the IDs to the listed tokens one after another,
+    // so the tokens of the new network get the same IDs only if there are no gaps.
+    let mut tokens: Vec<_> = snapshot
+        .tokens
+        .iter()
+        .filter(|token| *token.id != 0)
+        .collect();
+    tokens.sort_by_key(|token| token.id);
+    for (expected_id, token) in (1..).zip(&tokens) {
+        ensure!(
+            *token.id == expected_id,
+            "Token IDs of the snapshot are not contiguous: expected token {}, found {}",
+            expected_id,
+            *token.id
+        );
+    }
+    let fee_account_address = snapshot
+        .accounts
+        .iter()
+        .find(|(id, _)| *id == AccountId(0))
+        .map(|(_, account)| account.address)
+        .ok_or_else(|| anyhow::format_err!("Snapshot has no fee account"))?;
+    let params = DeploymentParams {
+        genesis_root: format!("0x{}", ff::to_hex(&snapshot.block.new_root_hash)),
+        fee_account_address,
+        tokens: tokens
+            .into_iter()
+            .map(|token| TokenInfo::new(token.address, &token.symbol, token.decimals))
+            .collect(),
+    };
+
+    let mut storage = StorageProcessor::establish_connection().await?;
+    storage
+        .snapshot_schema()
+        .import_snapshot_as_genesis(snapshot)
+        .await?;
+
+    serde_json::to_writer_pretty(BufWriter::new(File::create(&output)?), &params)?;
+    println!("Deployment parameters are saved to {}", output.display());
+    // Same format as the output of the server genesis, so the values can be put into the config as is.
+    println!("CONTRACTS_GENESIS_ROOT={}", params.genesis_root);
+    println!(
+        "CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR={:?}",
+        params.fee_account_address
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    match opt.command {
+        Command::Export { output } => export_snapshot(output).await,
+        Command::Import { input } => import_snapshot(input).await,
+        Command::Genesis { input, output } => import_snapshot_as_genesis(input, output).await,
+    }
+}
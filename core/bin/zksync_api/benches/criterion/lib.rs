@@ -33,7 +33,7 @@ fn get_txs_batch_fee(client: Client, url: String, batch_fee_request: BatchFeeReq
 fn get_txs_fee(client: Client, url: String) {
     let transaction = TxFeeRequest {
         tx_type: ApiTxFeeTypes::Withdraw,
-        address: Address::random(),
+        address: Address::random().into(),
         token_like: TokenLike::Id(TokenId(2)), // id of wBTC on localhost
     };
 
@@ -0,0 +1,301 @@
+//! Benchmarks for the signature checker hot paths.
+//!
+//! Every benchmark is parameterized over the number of transactions and reports
+//! the throughput in transactions per second. See `BENCHMARKS.md` for details.
+
+// Built-in deps
+// External uses
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use futures::{channel::mpsc, SinkExt};
+use tokio::sync::watch;
+// Workspace uses
+use zksync_api::{
+    eth_checker::{EthereumChecker, SignerCache},
+    signature_checker::{
+        spawn_sign_checker, BatchRequest, EthSignatureExemptions, RequestData,
+        SignatureCheckerConfig, TxRequest, ValidFromWindow, VerifiedTx, VerifySignatureRequest,
+    },
+};
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::{
+    tx::{EthSignData, PackedEthSignature, TxEthSignature},
+    AccountId, Address, SignedZkSyncTx, Token, TokenId, TokenKind, ZkSyncTx,
+};
+use zksync_utils::shutdown::ShutdownToken;
+
+const BATCH_SIZES: [usize; 3] = [1, 10, 50];
+/// Numbers of the checker threads compared by `bench_checker_threads`.
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+/// Number of the requests sent to the checker per iteration of `bench_checker_threads`.
+const CHECKER_REQUESTS: usize = 256;
+
+fn eth_token() -> Token {
+    Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20)
+}
+
+/// Creates transfers signed with both zkSync and Ethereum keys.
+///
+/// Cached zkSync signers are wiped, so the signature is actually checked.
+fn signed_transfers(account: &ZkSyncAccount, count: usize) -> Vec<SignedZkSyncTx> {
+    (0..count)
+        .map(|_| {
+            let (mut transfer, eth_signature) = account.sign_transfer(
+                TokenId(0),
+                "ETH",
+                100u64.into(),
+                10u64.into(),
+                &Address::repeat_byte(0x02),
+                None,
+                true,
+                Default::default(),
+            );
+            transfer.wipe_signer_cache();
+            let tx = ZkSyncTx::Transfer(Box::new(transfer));
+            let message = tx
+                .get_ethereum_sign_message(eth_token())
+                .unwrap()
+                .into_bytes();
+            SignedZkSyncTx {
+                tx,
+                eth_sign_data: Some(EthSignData {
+                    signature: TxEthSignature::EthereumSignature(eth_signature.unwrap()),
+                    message: message.into(),
+                }),
+                created_at: chrono::Utc::now(),
+                batch_hash: None,
+                parties_eth_sign_data: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+fn test_account() -> ZkSyncAccount {
+    let account = ZkSyncAccount::rand();
+    account.set_account_id(Some(AccountId(1)));
+    account
+}
+
+/// Correctness check of the transactions, including the zkSync signature check.
+fn bench_tx_correctness(c: &mut Criterion) {
+    let account = test_account();
+    let mut group = c.benchmark_group("verify_tx_correctness");
+    for &size in &BATCH_SIZES {
+        let txs: Vec<ZkSyncTx> = signed_transfers(&account, size)
+            .into_iter()
+            .map(|tx| tx.tx)
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &txs, |b, txs| {
+            b.iter_batched(
+                || txs.clone(),
+                |mut txs| {
+                    for tx in &mut txs {
+                        black_box(tx.check_correctness()).unwrap();
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Normalization of the Ethereum signature and the recovery of its signer.
+fn bench_ecdsa_recover(c: &mut Criterion) {
+    let account = test_account();
+    let mut group = c.benchmark_group("ecdsa_recover");
+    for &size in &BATCH_SIZES {
+        let signatures: Vec<(PackedEthSignature, Vec<u8>)> = signed_transfers(&account, size)
+            .into_iter()
+            .map(|tx| {
+                let sign_data = tx.eth_sign_data.unwrap();
+                match sign_data.signature {
+                    TxEthSignature::EthereumSignature(signature) => {
+                        (signature, sign_data.message.into_bytes())
+                    }
+                    _ => unreachable!(),
+                }
+            })
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &signatures,
+            |b, signatures| {
+                b.iter(|| {
+                    for (signature, message) in signatures {
+                        let signature = signature.normalize().unwrap();
+                        black_box(signature.signature_recover_signer(message)).unwrap();
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// The whole `VerifiedTx::verify` flow. Ethereum node is replaced with a mock,
+/// which is never called for the ECDSA signatures.
+fn bench_verify(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+    let valid_from_window = ValidFromWindow::new(None);
+    let eth_sig_exemptions = EthSignatureExemptions::default();
+    let account = test_account();
+
+    let mut group = c.benchmark_group("verify");
+    for &size in &BATCH_SIZES {
+        let txs = signed_transfers(&account, size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &txs, |b, txs| {
+            b.iter_batched(
+                || {
+                    RequestData::Batch(BatchRequest {
+                        txs: txs.clone(),
+                        batch_sign_data: None,
+                        senders: vec![account.address; txs.len()],
+                        tokens: vec![eth_token(); txs.len()],
+                        parties: Vec::new(),
+                    })
+                },
+                |request| {
+                    runtime
+                        .block_on(VerifiedTx::verify(
+                            request,
+                            &eth_checker,
+                            &valid_from_window,
+                            &eth_sig_exemptions,
+                        ))
+                        .unwrap()
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Repeated submissions of the same batch with and without the cache of the recovered
+/// Ethereum signers. The cache is warmed up before the measurement.
+fn bench_verify_repeated(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let uncached = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+    let cached = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()))
+        .with_signer_cache(SignerCache::new(1000));
+    let valid_from_window = ValidFromWindow::new(None);
+    let eth_sig_exemptions = EthSignatureExemptions::default();
+    let account = test_account();
+
+    let mut group = c.benchmark_group("verify_repeated");
+    for &size in &BATCH_SIZES {
+        let txs = signed_transfers(&account, size);
+        let request = || {
+            RequestData::Batch(BatchRequest {
+                txs: txs.clone(),
+                batch_sign_data: None,
+                senders: vec![account.address; txs.len()],
+                tokens: vec![eth_token(); txs.len()],
+                parties: Vec::new(),
+            })
+        };
+        runtime
+            .block_on(VerifiedTx::verify(
+                request(),
+                &cached,
+                &valid_from_window,
+                &eth_sig_exemptions,
+            ))
+            .unwrap();
+
+        group.throughput(Throughput::Elements(size as u64));
+        for (name, eth_checker) in [("uncached", &uncached), ("cached", &cached)] {
+            group.bench_function(BenchmarkId::new(name, size), |b| {
+                b.iter_batched(
+                    request,
+                    |request| {
+                        runtime
+                            .block_on(VerifiedTx::verify(
+                                request,
+                                eth_checker,
+                                &valid_from_window,
+                                &eth_sig_exemptions,
+                            ))
+                            .unwrap()
+                    },
+                    BatchSize::SmallInput,
+                );
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Throughput of the whole signature checker fed through its channel, depending on
+/// the number of the checker threads. Every request is a single transfer.
+fn bench_checker_threads(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let account = test_account();
+    let txs = signed_transfers(&account, CHECKER_REQUESTS);
+
+    let mut group = c.benchmark_group("checker_threads");
+    group.throughput(Throughput::Elements(CHECKER_REQUESTS as u64));
+    for &num_threads in &THREAD_COUNTS {
+        let (_client_sender, client) =
+            watch::channel(EthereumGateway::Mock(MockEthereum::default()));
+        let (panic_sender, _panic_receiver) = mpsc::channel(1);
+        let config = SignatureCheckerConfig {
+            num_threads: Some(num_threads),
+            ..Default::default()
+        };
+        let (sender, _handle, _queue_depth) = {
+            let _guard = runtime.enter();
+            spawn_sign_checker(
+                client,
+                config,
+                CHECKER_REQUESTS,
+                panic_sender,
+                ShutdownToken::never(),
+            )
+        };
+
+        group.bench_function(BenchmarkId::from_parameter(num_threads), |b| {
+            b.iter_batched(
+                || {
+                    txs.iter()
+                        .cloned()
+                        .map(|tx| TxRequest::new(tx, account.address, eth_token()))
+                        .collect::<Vec<_>>()
+                },
+                |requests| {
+                    runtime.block_on(async {
+                        let mut sender = sender.clone();
+                        let mut responses = Vec::with_capacity(requests.len());
+                        for request in requests {
+                            let (request, response) = VerifySignatureRequest::new_single(request);
+                            sender.send(request).await.unwrap();
+                            responses.push(response);
+                        }
+                        for response in responses {
+                            response.await.unwrap().unwrap();
+                        }
+                    })
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    signature_checker_benches,
+    bench_tx_correctness,
+    bench_ecdsa_recover,
+    bench_verify,
+    bench_verify_repeated,
+    bench_checker_threads
+);
+criterion_main!(signature_checker_benches);
@@ -0,0 +1,337 @@
+//! Access control of the API requests.
+//!
+//! Clients are either authenticated with the API key (see `api_keys`) and limited by its quota
+//! and method allowlist, or access the API anonymously and are rate limited by their IP.
+
+// Built-in uses
+use std::net::IpAddr;
+
+// External uses
+use futures::future::{self, Either, Ready};
+use jsonrpc_core::{middleware::NoopFuture, Call, Failure, Metadata, Middleware, Output, Version};
+use thiserror::Error;
+
+// Workspace uses
+use zksync_config::configs::api::{CommonApiConfig, TrustedProxy};
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{
+    api_keys::{ApiKeys, API_KEY_HEADER},
+    rate_limiter::{client_ip, RateLimiter},
+    rpc_server::error::RpcErrorCodes,
+};
+
+/// Client of the rate limiter standing for the clients with the unknown IP.
+/// It never matches the actual IP, as they're parsed before being used.
+const UNKNOWN_IP: &str = "unknown";
+
+/// Reason the request is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AccessError {
+    #[error("Invalid API key")]
+    InvalidApiKey,
+    #[error("API key is required")]
+    ApiKeyRequired,
+    #[error("Method is not allowed for the API key")]
+    MethodNotAllowed,
+    #[error("Too many requests")]
+    RateLimited,
+}
+
+impl AccessError {
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::InvalidApiKey | Self::ApiKeyRequired => 401,
+            Self::MethodNotAllowed => 403,
+            Self::RateLimited => 429,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            Self::InvalidApiKey => "INVALID_API_KEY",
+            Self::ApiKeyRequired => "API_KEY_REQUIRED",
+            Self::MethodNotAllowed => "METHOD_NOT_ALLOWED",
+            Self::RateLimited => "RATE_LIMITED",
+        }
+    }
+
+    fn rpc_error_code(&self) -> RpcErrorCodes {
+        match self {
+            Self::InvalidApiKey | Self::ApiKeyRequired => RpcErrorCodes::Unauthorized,
+            Self::MethodNotAllowed => RpcErrorCodes::MethodNotAllowed,
+            Self::RateLimited => RpcErrorCodes::RateLimited,
+        }
+    }
+}
+
+impl From<AccessError> for jsonrpc_core::Error {
+    fn from(err: AccessError) -> Self {
+        Self {
+            code: err.rpc_error_code().into(),
+            message: err.to_string(),
+            data: Some(serde_json::json!({
+                "errorCode": err.error_code(),
+                "httpStatus": err.http_status(),
+            })),
+        }
+    }
+}
+
+/// Client of the request: its IP and the API key, if they are known.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    pub ip: Option<String>,
+    pub api_key: Option<String>,
+}
+
+impl RequestMeta {
+    /// Reads the client of the request sent from the peer address, see `client_ip`.
+    pub fn from_headers<'a>(
+        peer: Option<IpAddr>,
+        trusted_proxies: &[TrustedProxy],
+        header: impl Fn(&str) -> Option<&'a str>,
+    ) -> Self {
+        Self {
+            ip: client_ip(peer, trusted_proxies, &header),
+            api_key: header(API_KEY_HEADER).map(|key| key.trim().to_owned()),
+        }
+    }
+}
+
+impl Metadata for RequestMeta {}
+
+/// Checks the API key and rate limits of the requests.
+#[derive(Debug, Clone)]
+pub struct AccessControl {
+    rate_limiter: RateLimiter,
+    /// Active API keys, `None` if the API keys are disabled.
+    api_keys: Option<ApiKeys>,
+    allow_anonymous_access: bool,
+}
+
+impl AccessControl {
+    /// Creates the access control of the server. If the API keys are enabled, spawns the task
+    /// reloading them from the database, so it has to be called within the Tokio runtime.
+    pub fn new(config: &CommonApiConfig, connection_pool: ConnectionPool) -> Self {
+        let api_keys = if config.api_keys_enabled {
+            Some(ApiKeys::with_updater(
+                connection_pool,
+                config.api_keys_refresh_interval(),
+            ))
+        } else {
+            None
+        };
+        Self {
+            rate_limiter: RateLimiter::new(config),
+            api_keys,
+            allow_anonymous_access: config.allow_anonymous_access,
+        }
+    }
+
+    /// Checks whether the client may access the endpoint, i.e. the JSON RPC method
+    /// or the REST API path.
+    ///
+    /// `api_type` is only used as the label of the rejected requests metrics.
+    pub fn check(
+        &self,
+        meta: &RequestMeta,
+        endpoint: &str,
+        api_type: &'static str,
+    ) -> Result<(), AccessError> {
+        if let (Some(api_keys), Some(key)) = (&self.api_keys, &meta.api_key) {
+            let api_key = api_keys.get(key).ok_or(AccessError::InvalidApiKey)?;
+            if !api_key.is_allowed(endpoint) {
+                return Err(AccessError::MethodNotAllowed);
+            }
+            if !self.rate_limiter.check_api_key(&api_key, api_type) {
+                return Err(AccessError::RateLimited);
+            }
+            return Ok(());
+        }
+
+        if self.api_keys.is_some() && !self.allow_anonymous_access {
+            return Err(AccessError::ApiKeyRequired);
+        }
+        // Anonymous clients with the unknown IP share a single limit.
+        let ip = meta.ip.as_deref().unwrap_or(UNKNOWN_IP);
+        if !self.rate_limiter.check(ip, endpoint, api_type) {
+            return Err(AccessError::RateLimited);
+        }
+        Ok(())
+    }
+}
+
+/// Rejects the JSON RPC calls not allowed by the access control.
+#[derive(Debug, Clone)]
+pub struct AccessControlMiddleware(pub AccessControl);
+
+impl Middleware<RequestMeta> for AccessControlMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = Ready<Option<Output>>;
+
+    fn on_call<F, X>(&self, call: Call, meta: RequestMeta, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, RequestMeta) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        if let Call::MethodCall(method_call) = &call {
+            if let Err(err) = self.0.check(&meta, &method_call.method, "rpc") {
+                let failure = Failure {
+                    jsonrpc: method_call.jsonrpc.or(Some(Version::V2)),
+                    error: err.into(),
+                    id: method_call.id.clone(),
+                };
+                return Either::Left(future::ready(Some(Output::Failure(failure))));
+            }
+        }
+        Either::Right(next(call, meta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::{ErrorCode, Params};
+    use zksync_storage::api_keys::records::StoredApiKey;
+
+    use crate::api_server::api_keys::hash_api_key;
+
+    const KEY: &str = "partner-key";
+
+    fn access_control(rate_limit_rps: Option<u32>, allow_anonymous_access: bool) -> AccessControl {
+        let mut config = CommonApiConfig::from_env();
+        config.rate_limit_rps = rate_limit_rps;
+        config.rate_limit_burst = 0;
+        config.rate_limit_endpoints = Vec::new();
+
+        let api_keys = ApiKeys::default();
+        api_keys.replace(vec![StoredApiKey {
+            id: 1,
+            key_hash: hash_api_key(KEY),
+            name: "partner".to_owned(),
+            rps: Some(2),
+            allowed_methods: vec!["tx_submit".to_owned()],
+            created_at: chrono::Utc::now(),
+            revoked_at: None,
+        }]);
+        AccessControl {
+            rate_limiter: RateLimiter::new(&config),
+            api_keys: Some(api_keys),
+            allow_anonymous_access,
+        }
+    }
+
+    fn meta(api_key: Option<&str>) -> RequestMeta {
+        RequestMeta {
+            ip: Some("1.1.1.1".to_owned()),
+            api_key: api_key.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn api_key_access() {
+        let access_control = access_control(Some(1), true);
+
+        assert_eq!(
+            access_control.check(&meta(Some("unknown")), "tx_submit", "rpc"),
+            Err(AccessError::InvalidApiKey)
+        );
+        assert_eq!(
+            access_control.check(&meta(Some(KEY)), "account_info", "rpc"),
+            Err(AccessError::MethodNotAllowed)
+        );
+        // Key has its own quota, which is greater than the one of the anonymous clients.
+        for _ in 0..2 {
+            assert_eq!(
+                access_control.check(&meta(Some(KEY)), "tx_submit", "rpc"),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            access_control.check(&meta(Some(KEY)), "tx_submit", "rpc"),
+            Err(AccessError::RateLimited)
+        );
+
+        // Anonymous client from the same IP is limited separately.
+        assert_eq!(
+            access_control.check(&meta(None), "tx_submit", "rpc"),
+            Ok(())
+        );
+        assert_eq!(
+            access_control.check(&meta(None), "tx_submit", "rpc"),
+            Err(AccessError::RateLimited)
+        );
+    }
+
+    #[test]
+    fn anonymous_access_disabled() {
+        let access_control = access_control(None, false);
+        assert_eq!(
+            access_control.check(&meta(None), "tx_submit", "rpc"),
+            Err(AccessError::ApiKeyRequired)
+        );
+        assert_eq!(
+            access_control.check(&meta(Some(KEY)), "tx_submit", "rpc"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejected_call() {
+        let middleware = AccessControlMiddleware(access_control(Some(2), true));
+        let call = || {
+            Call::MethodCall(jsonrpc_core::MethodCall {
+                jsonrpc: Some(Version::V2),
+                method: "tx_submit".to_owned(),
+                params: Params::None,
+                id: jsonrpc_core::Id::Num(1),
+            })
+        };
+        let next = |_, _| future::ready(None);
+
+        for _ in 0..2 {
+            assert!(matches!(
+                middleware.on_call(call(), meta(None), next),
+                Either::Right(_)
+            ));
+        }
+        match middleware.on_call(call(), meta(None), next) {
+            Either::Left(output) => match output.into_inner() {
+                Some(Output::Failure(failure)) => {
+                    assert_eq!(
+                        failure.error.code,
+                        ErrorCode::from(RpcErrorCodes::RateLimited)
+                    );
+                }
+                output => panic!("Unexpected output: {:?}", output),
+            },
+            Either::Right(_) => panic!("Call is not rate limited"),
+        }
+        // Clients with the unknown IP share a single limit.
+        for _ in 0..2 {
+            assert!(matches!(
+                middleware.on_call(call(), RequestMeta::default(), next),
+                Either::Right(_)
+            ));
+        }
+        assert!(matches!(
+            middleware.on_call(call(), RequestMeta::default(), next),
+            Either::Left(_)
+        ));
+
+        match middleware.on_call(call(), meta(Some("unknown")), next) {
+            Either::Left(output) => match output.into_inner() {
+                Some(Output::Failure(failure)) => {
+                    assert_eq!(
+                        failure.error.code,
+                        ErrorCode::from(RpcErrorCodes::Unauthorized)
+                    );
+                }
+                output => panic!("Unexpected output: {:?}", output),
+            },
+            Either::Right(_) => panic!("Call with the invalid key is not rejected"),
+        }
+    }
+}
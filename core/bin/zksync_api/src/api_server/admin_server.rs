@@ -0,0 +1,1393 @@
+//! Admin API server, used by the operator to mint and revoke the API keys
+//! of the partner integrations, to inspect and manage the mempool, to manage
+//! the address lists the incoming transactions are screened against, and to manage
+//! the fee policies: token subsidies, promotional discounts, fee-free accounts and
+//! the tokens that can be used to pay fees, and to register the liquidity providers fronting
+//! the fast withdrawals. The runtime config can be reloaded from the config file
+//! without the restart of the server. Before the upgrade, the API servers can be put into the drain mode,
+//! in which they reject the new transactions (see `drain_mode` module). The profitability of the blocks
+//! and the days is reported from the L1 costs of the operator and the fees it collected
+//! (see `zksync_types::operator_costs`). The signature checker running in the same process can be
+//! switched to other Ethereum nodes.
+//!
+//! Requests are authenticated with the JWT signed by the `AdminApiConfig::secret_auth`.
+
+// Built-in uses
+use std::{collections::HashMap, convert::TryFrom, str::FromStr, thread};
+
+// External uses
+use actix_web::{dev::ServiceRequest, web, App, HttpResponse, HttpServer};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use jsonwebtoken::{decode, errors::Error as JwtError, DecodingKey, Validation};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_config::{configs::api::AdminApiConfig, RuntimeConfig};
+use zksync_mempool::{MempoolTransactionRequest, MempoolTxInfo};
+use zksync_storage::{
+    api_keys::records::NewApiKey, fee_policies::records::NewFeePromotion, ConnectionPool,
+    StorageProcessor,
+};
+use zksync_types::{
+    fast_withdrawals::LiquidityProvider,
+    operator_costs::{Costs, TokenUsdPrice},
+    tx::{error::TxAddError, TxHash},
+    withdrawal_limits::WithdrawalLimits,
+    AccountId, Address, BlockNumber, TokenId,
+};
+use zksync_utils::{
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+    BigUintSerdeAsRadix10Str, BigUintSerdeWrapper,
+};
+
+// Local uses
+use super::api_keys::{generate_api_key, hash_api_key};
+use crate::signature_checker::EthClientSwitch;
+
+/// Maximum amount of the blocks in the report of the operator costs.
+const MAX_REPORT_BLOCKS: u32 = 1_000;
+/// Maximum amount of the days in the report of the operator costs.
+const MAX_REPORT_DAYS: i64 = 90;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Checks the JWT of the request against the shared secret.
+struct AuthTokenValidator<'a> {
+    decoding_key: DecodingKey<'a>,
+}
+
+impl<'a> AuthTokenValidator<'a> {
+    fn new(secret: &'a str) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+        }
+    }
+
+    fn validate_auth_token(&self, token: &str) -> Result<(), JwtError> {
+        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+        Ok(())
+    }
+
+    async fn validator(
+        &self,
+        req: ServiceRequest,
+        credentials: BearerAuth,
+    ) -> actix_web::Result<ServiceRequest> {
+        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+
+        self.validate_auth_token(credentials.token())
+            .map_err(|_| AuthenticationError::from(config))?;
+
+        Ok(req)
+    }
+}
+
+/// Request to mint the new API key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintApiKeyRequest {
+    /// Human-readable name of the key owner.
+    pub name: String,
+    /// Requests per second allowed for the key, the key is not rate limited if not set.
+    pub rps: Option<u32>,
+    /// JSON RPC methods and REST API path prefixes the key has access to,
+    /// all the API is accessible if the list is empty.
+    #[serde(default)]
+    pub allowed_methods: Vec<String>,
+}
+
+/// Minted API key. The key itself is not stored, so it can't be retrieved later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintedApiKey {
+    pub id: i64,
+    pub key: String,
+}
+
+/// Request to add the address to the allow or deny list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScreenAddressRequest {
+    /// Why the address is listed, e.g. the reference to the sanctions list.
+    pub reason: Option<String>,
+}
+
+/// Address of the allow or deny list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenedAddress {
+    pub address: Address,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Transactions removed from the mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvictedTxs {
+    pub tx_hashes: Vec<TxHash>,
+}
+
+/// Request to set the subsidy of the fees in the token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSubsidyRequest {
+    /// Part of the fee paid by the operator, in percent.
+    pub subsidy_percent: u8,
+}
+
+/// Subsidy of the fees in the token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSubsidy {
+    pub token_id: TokenId,
+    pub subsidy_percent: u8,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to set whether the fees can be paid in the token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenFeeAcceptabilityRequest {
+    /// Whether the fees can be paid in the token regardless of its market volume.
+    pub fee_acceptable: bool,
+}
+
+/// Whether the fees can be paid in the token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenFeeAcceptability {
+    pub token_id: TokenId,
+    pub fee_acceptable: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to add the promotional discount of the fees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePromotionRequest {
+    /// Token the fees of which are discounted, the fees in all the tokens are discounted if not set.
+    pub token_id: Option<TokenId>,
+    pub discount_percent: u8,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Promotional discount of the fees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePromotion {
+    pub id: i64,
+    pub token_id: Option<TokenId>,
+    pub discount_percent: u8,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Request to add the account to the fee-free list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeFreeAccountRequest {
+    /// Why the account doesn't pay fees, e.g. it's the market maker of the operator.
+    pub reason: Option<String>,
+}
+
+/// Account of the fee-free list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeFreeAccount {
+    pub account_id: AccountId,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to register the liquidity provider fronting the fast withdrawals of the token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityProviderRequest {
+    /// Largest amount of a single withdrawal the provider fronts.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_amount: BigUint,
+    /// Fee of the provider, in basis points of the withdrawn amount.
+    pub fee_bps: u16,
+}
+
+/// Request to set the limits of the withdrawals of the token, the unset limits are not applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalLimitsRequest {
+    /// Largest amount a single account may withdraw during a UTC day.
+    pub account_daily_limit: Option<BigUintSerdeWrapper>,
+    /// Largest amount all the accounts may withdraw during a UTC day.
+    pub daily_volume_limit: Option<BigUintSerdeWrapper>,
+    /// Smallest amount of the withdrawal which is delayed.
+    pub large_withdrawal_threshold: Option<BigUintSerdeWrapper>,
+    /// Delay of the large withdrawals, in seconds.
+    #[serde(default)]
+    pub large_withdrawal_delay: u64,
+}
+
+/// Range of the blocks of the operator costs report, inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockCostsQuery {
+    pub from_block: u32,
+    pub to_block: u32,
+}
+
+/// Range of the UTC days of the operator costs report, inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCostsQuery {
+    pub from_day: NaiveDate,
+    pub to_day: NaiveDate,
+}
+
+/// State of the drain mode of the API servers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainStatus {
+    pub drained: bool,
+    /// Number of the transactions in the mempool, the server can be stopped once
+    /// they are executed.
+    pub mempool_txs: u32,
+}
+
+#[derive(Debug, Clone)]
+struct AppState {
+    secret_auth: String,
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    eth_client_switch: Option<EthClientSwitch>,
+}
+
+impl AppState {
+    async fn access_storage(&self) -> actix_web::Result<zksync_storage::StorageProcessor<'_>> {
+        self.connection_pool.access_storage().await.map_err(|e| {
+            vlog::warn!("Failed to access storage: {}", e);
+            actix_web::error::ErrorInternalServerError(e)
+        })
+    }
+
+    /// Sends the request to the mempool and waits for the response.
+    async fn mempool_request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<Result<T, TxAddError>>) -> MempoolTransactionRequest,
+    ) -> actix_web::Result<T> {
+        let (sender, receiver) = oneshot::channel();
+        let mut mempool_sender = self.mempool_tx_sender.clone();
+        mempool_sender.send(request(sender)).await.map_err(|e| {
+            vlog::warn!("Failed to send the mempool request: {}", e);
+            actix_web::error::ErrorInternalServerError("mempool is not available")
+        })?;
+
+        receiver
+            .await
+            .map_err(|_| actix_web::error::ErrorInternalServerError("mempool is not available"))?
+            .map_err(|e| {
+                vlog::warn!("Mempool request failed: {}", e);
+                actix_web::error::ErrorInternalServerError(e)
+            })
+    }
+}
+
+fn parse_tx_hash(tx_hash: &str) -> actix_web::Result<TxHash> {
+    TxHash::from_str(tx_hash).map_err(|e| actix_web::error::ErrorBadRequest(e.to_string()))
+}
+
+/// Parses the name of the address list, returns whether it's the allow list.
+fn parse_address_list(list: &str) -> actix_web::Result<bool> {
+    match list {
+        "allow" => Ok(true),
+        "deny" => Ok(false),
+        _ => Err(actix_web::error::ErrorNotFound("unknown address list")),
+    }
+}
+
+/// Checks that the discount doesn't exceed the whole fee.
+fn check_percent(percent: u8) -> actix_web::Result<i16> {
+    if percent > 100 {
+        return Err(actix_web::error::ErrorBadRequest(
+            "percent should not exceed 100",
+        ));
+    }
+    Ok(i16::from(percent))
+}
+
+async fn mint_api_key(
+    data: web::Data<AppState>,
+    request: web::Json<MintApiKeyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    if request.name.is_empty() {
+        return Err(actix_web::error::ErrorBadRequest("empty name"));
+    }
+    let rps = match request.rps.map(i32::try_from).transpose() {
+        Ok(rps) => rps,
+        Err(_) => return Err(actix_web::error::ErrorBadRequest("rps is too big")),
+    };
+
+    let key = generate_api_key();
+    let new_key = NewApiKey {
+        key_hash: hash_api_key(&key),
+        name: request.name,
+        rps,
+        allowed_methods: request.allowed_methods,
+    };
+    let mut storage = data.access_storage().await?;
+    let id = storage
+        .api_keys_schema()
+        .store_key(&new_key)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to store the API key: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!("Minted the API key #{} for {}", id, new_key.name);
+
+    Ok(HttpResponse::Ok().json(MintedApiKey { id, key }))
+}
+
+async fn revoke_api_key(
+    data: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let id = id.into_inner();
+    let mut storage = data.access_storage().await?;
+    let revoked = storage
+        .api_keys_schema()
+        .revoke_key(id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to revoke the API key: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if revoked {
+        vlog::info!("Revoked the API key #{}", id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound("API key not found"))
+    }
+}
+
+async fn account_mempool_txs(
+    data: web::Data<AppState>,
+    address: web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    let address = address.into_inner();
+    let mut storage = data.access_storage().await?;
+    let account_id = storage
+        .chain()
+        .account_schema()
+        .account_id_by_address(address)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the account ID: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("account not found"))?;
+    drop(storage);
+
+    let txs: Vec<MempoolTxInfo> = data
+        .mempool_request(|sender| MempoolTransactionRequest::GetAccountTxs(account_id, sender))
+        .await?;
+    Ok(HttpResponse::Ok().json(txs))
+}
+
+async fn mempool_tx(
+    data: web::Data<AppState>,
+    tx_hash: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let tx_hash = parse_tx_hash(&tx_hash)?;
+    let tx = data
+        .mempool_request(|sender| MempoolTransactionRequest::GetTx(tx_hash, sender))
+        .await?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("transaction is not in the mempool"))?;
+
+    Ok(HttpResponse::Ok().json(tx))
+}
+
+async fn evict_mempool_tx(
+    data: web::Data<AppState>,
+    tx_hash: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let tx_hash = parse_tx_hash(&tx_hash)?;
+    let tx_hashes = data
+        .mempool_request(|sender| MempoolTransactionRequest::EvictTx(tx_hash, sender))
+        .await?;
+    if tx_hashes.is_empty() {
+        return Err(actix_web::error::ErrorNotFound(
+            "transaction is not in the mempool",
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(EvictedTxs { tx_hashes }))
+}
+
+async fn address_list(
+    data: web::Data<AppState>,
+    list: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let allowed = parse_address_list(&list)?;
+    let mut storage = data.access_storage().await?;
+    let addresses: Vec<_> = storage
+        .address_lists_schema()
+        .load_list(allowed)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the address list: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .into_iter()
+        .map(|stored| ScreenedAddress {
+            address: Address::from_slice(&stored.address),
+            reason: stored.reason,
+            created_at: stored.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(addresses))
+}
+
+async fn add_listed_address(
+    data: web::Data<AppState>,
+    path: web::Path<(String, Address)>,
+    request: web::Json<ScreenAddressRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let (list, address) = path.into_inner();
+    let allowed = parse_address_list(&list)?;
+    let mut storage = data.access_storage().await?;
+    storage
+        .address_lists_schema()
+        .add_address(address, allowed, request.reason.as_deref())
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to add the address to the list: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!("Added {:?} to the {} list", address, list);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_listed_address(
+    data: web::Data<AppState>,
+    path: web::Path<(String, Address)>,
+) -> actix_web::Result<HttpResponse> {
+    let (list, address) = path.into_inner();
+    let allowed = parse_address_list(&list)?;
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .address_lists_schema()
+        .remove_address(address, allowed)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the address from the list: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!("Removed {:?} from the {} list", address, list);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound("address is not listed"))
+    }
+}
+
+async fn token_subsidies(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let subsidies: Vec<_> = storage
+        .fee_policies_schema()
+        .load_token_subsidies()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the token subsidies: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .into_iter()
+        .map(|stored| TokenSubsidy {
+            token_id: TokenId(stored.token_id as u32),
+            subsidy_percent: stored.subsidy_percent as u8,
+            updated_at: stored.updated_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(subsidies))
+}
+
+async fn set_token_subsidy(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+    request: web::Json<TokenSubsidyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let subsidy_percent = check_percent(request.subsidy_percent)?;
+    let mut storage = data.access_storage().await?;
+    storage
+        .fee_policies_schema()
+        .set_token_subsidy(token_id, subsidy_percent)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to set the token subsidy: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Set the subsidy of the fees in the token {} to {}%",
+        token_id,
+        subsidy_percent
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_token_subsidy(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fee_policies_schema()
+        .remove_token_subsidy(token_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the token subsidy: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!("Removed the subsidy of the fees in the token {}", token_id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound("token is not subsidized"))
+    }
+}
+
+async fn fee_tokens(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let tokens: Vec<_> = storage
+        .fee_policies_schema()
+        .load_token_fee_acceptability()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the token fee acceptability: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .into_iter()
+        .map(|stored| TokenFeeAcceptability {
+            token_id: TokenId(stored.token_id as u32),
+            fee_acceptable: stored.fee_acceptable,
+            updated_at: stored.updated_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+async fn set_token_fee_acceptability(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+    request: web::Json<TokenFeeAcceptabilityRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    storage
+        .fee_policies_schema()
+        .set_token_fee_acceptability(token_id, request.fee_acceptable)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to set the token fee acceptability: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Set the fee acceptability of the token {} to {}",
+        token_id,
+        request.fee_acceptable
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_token_fee_acceptability(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fee_policies_schema()
+        .remove_token_fee_acceptability(token_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the token fee acceptability: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!(
+            "Removed the fee acceptability of the token {}, its market volume is checked",
+            token_id
+        );
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound(
+            "fee acceptability is not set for the token",
+        ))
+    }
+}
+
+async fn fee_promotions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let promotions: Vec<_> = storage
+        .fee_policies_schema()
+        .load_promotions(Utc::now())
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the fee promotions: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .into_iter()
+        .map(|stored| FeePromotion {
+            id: stored.id,
+            token_id: stored.token_id.map(|token_id| TokenId(token_id as u32)),
+            discount_percent: stored.discount_percent as u8,
+            starts_at: stored.starts_at,
+            ends_at: stored.ends_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(promotions))
+}
+
+async fn add_fee_promotion(
+    data: web::Data<AppState>,
+    request: web::Json<FeePromotionRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    if request.starts_at >= request.ends_at {
+        return Err(actix_web::error::ErrorBadRequest(
+            "promotion should end after it starts",
+        ));
+    }
+    let promotion = NewFeePromotion {
+        token_id: request.token_id.map(|token_id| *token_id as i32),
+        discount_percent: check_percent(request.discount_percent)?,
+        starts_at: request.starts_at,
+        ends_at: request.ends_at,
+    };
+    let mut storage = data.access_storage().await?;
+    let id = storage
+        .fee_policies_schema()
+        .add_promotion(&promotion)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to add the fee promotion: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Added the fee promotion #{}: {}% from {} to {}",
+        id,
+        promotion.discount_percent,
+        promotion.starts_at,
+        promotion.ends_at
+    );
+
+    Ok(HttpResponse::Ok().json(FeePromotion {
+        id,
+        token_id: request.token_id,
+        discount_percent: request.discount_percent,
+        starts_at: request.starts_at,
+        ends_at: request.ends_at,
+    }))
+}
+
+async fn remove_fee_promotion(
+    data: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let id = id.into_inner();
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fee_policies_schema()
+        .remove_promotion(id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the fee promotion: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!("Removed the fee promotion #{}", id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound("promotion not found"))
+    }
+}
+
+async fn fee_free_accounts(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let accounts: Vec<_> = storage
+        .fee_policies_schema()
+        .load_fee_free_accounts()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the fee-free accounts: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .into_iter()
+        .map(|stored| FeeFreeAccount {
+            account_id: AccountId(stored.account_id as u32),
+            reason: stored.reason,
+            created_at: stored.created_at,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(accounts))
+}
+
+async fn add_fee_free_account(
+    data: web::Data<AppState>,
+    account_id: web::Path<u32>,
+    request: web::Json<FeeFreeAccountRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let account_id = AccountId(account_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    storage
+        .fee_policies_schema()
+        .add_fee_free_account(account_id, request.reason.as_deref())
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to add the fee-free account: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!("Added the account {} to the fee-free list", account_id);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_fee_free_account(
+    data: web::Data<AppState>,
+    account_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let account_id = AccountId(account_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fee_policies_schema()
+        .remove_fee_free_account(account_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the fee-free account: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!("Removed the account {} from the fee-free list", account_id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound("account is not fee-free"))
+    }
+}
+
+async fn liquidity_providers(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let providers = storage
+        .fast_withdrawals_schema()
+        .load_providers()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the liquidity providers: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(providers))
+}
+
+async fn add_liquidity_provider(
+    data: web::Data<AppState>,
+    path: web::Path<(Address, u32)>,
+    request: web::Json<LiquidityProviderRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let (address, token_id) = path.into_inner();
+    let request = request.into_inner();
+    if request.fee_bps > LiquidityProvider::MAX_FEE_BPS {
+        return Err(actix_web::error::ErrorBadRequest(
+            "fee should not exceed the withdrawn amount",
+        ));
+    }
+    let provider = LiquidityProvider {
+        address,
+        token: TokenId(token_id),
+        max_amount: request.max_amount,
+        fee_bps: request.fee_bps,
+    };
+    let mut storage = data.access_storage().await?;
+    storage
+        .fast_withdrawals_schema()
+        .add_provider(&provider)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to add the liquidity provider: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Registered {:?} as the liquidity provider of the token {}: up to {} with the {} bps fee",
+        address,
+        token_id,
+        provider.max_amount,
+        provider.fee_bps
+    );
+
+    Ok(HttpResponse::Ok().json(provider))
+}
+
+async fn remove_liquidity_provider(
+    data: web::Data<AppState>,
+    path: web::Path<(Address, u32)>,
+) -> actix_web::Result<HttpResponse> {
+    let (address, token_id) = path.into_inner();
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .fast_withdrawals_schema()
+        .remove_provider(address, TokenId(token_id))
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the liquidity provider: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!(
+            "Removed {:?} from the liquidity providers of the token {}",
+            address,
+            token_id
+        );
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound(
+            "liquidity provider is not registered",
+        ))
+    }
+}
+
+async fn withdrawal_limits(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let limits = storage
+        .withdrawal_limits_schema()
+        .load_limits()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the withdrawal limits: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(limits))
+}
+
+async fn set_withdrawal_limits(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+    request: web::Json<WithdrawalLimitsRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let request = request.into_inner();
+    if request.large_withdrawal_delay > i64::MAX as u64 {
+        return Err(actix_web::error::ErrorBadRequest(
+            "large withdrawal delay is too long",
+        ));
+    }
+    let limits = WithdrawalLimits {
+        token: token_id,
+        account_daily_limit: request.account_daily_limit,
+        daily_volume_limit: request.daily_volume_limit,
+        large_withdrawal_threshold: request.large_withdrawal_threshold,
+        large_withdrawal_delay: request.large_withdrawal_delay,
+    };
+    let mut storage = data.access_storage().await?;
+    storage
+        .withdrawal_limits_schema()
+        .set_limits(&limits)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to set the withdrawal limits: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Set the withdrawal limits of the token {}: {:?}",
+        token_id,
+        limits
+    );
+
+    Ok(HttpResponse::Ok().json(limits))
+}
+
+async fn remove_withdrawal_limits(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let mut storage = data.access_storage().await?;
+    let removed = storage
+        .withdrawal_limits_schema()
+        .remove_limits(token_id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to remove the withdrawal limits: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    if removed {
+        vlog::info!("Removed the withdrawal limits of the token {}", token_id);
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(actix_web::error::ErrorNotFound(
+            "withdrawal limits are not set",
+        ))
+    }
+}
+
+/// Loads the prices of ETH and the tokens the fees are collected in.
+async fn load_cost_prices<'a>(
+    storage: &mut StorageProcessor<'_>,
+    costs: impl Iterator<Item = &'a Costs>,
+) -> actix_web::Result<HashMap<TokenId, TokenUsdPrice>> {
+    let mut tokens = vec![TokenId(0)];
+    for costs in costs {
+        tokens.extend(costs.collected_fees.keys());
+    }
+    tokens.sort_unstable();
+    tokens.dedup();
+
+    storage
+        .operator_costs_schema()
+        .load_token_prices(&tokens)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the token prices: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })
+}
+
+async fn block_costs(
+    data: web::Data<AppState>,
+    query: web::Query<BlockCostsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let query = query.into_inner();
+    if query.from_block > query.to_block {
+        return Err(actix_web::error::ErrorBadRequest(
+            "fromBlock should not exceed toBlock",
+        ));
+    }
+    if query.to_block - query.from_block >= MAX_REPORT_BLOCKS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "at most {} blocks can be reported",
+            MAX_REPORT_BLOCKS
+        )));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let mut blocks = storage
+        .operator_costs_schema()
+        .load_block_costs(BlockNumber(query.from_block), BlockNumber(query.to_block))
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the costs of the blocks: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    let prices = load_cost_prices(&mut storage, blocks.iter().map(|block| &block.costs)).await?;
+    for block in &mut blocks {
+        block.profitability = block.costs.profitability(&prices);
+    }
+
+    Ok(HttpResponse::Ok().json(blocks))
+}
+
+async fn daily_costs(
+    data: web::Data<AppState>,
+    query: web::Query<DailyCostsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let query = query.into_inner();
+    if query.from_day > query.to_day {
+        return Err(actix_web::error::ErrorBadRequest(
+            "fromDay should not exceed toDay",
+        ));
+    }
+    if (query.to_day - query.from_day).num_days() >= MAX_REPORT_DAYS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "at most {} days can be reported",
+            MAX_REPORT_DAYS
+        )));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let mut days = storage
+        .operator_costs_schema()
+        .load_daily_costs(query.from_day, query.to_day)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the daily costs: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    let prices = load_cost_prices(&mut storage, days.iter().map(|day| &day.costs)).await?;
+    for day in &mut days {
+        day.profitability = day.costs.profitability(&prices);
+    }
+
+    Ok(HttpResponse::Ok().json(days))
+}
+
+async fn drain_status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let drained = storage
+        .misc_schema()
+        .is_tx_intake_drained()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the drain mode: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    let mempool_txs = storage
+        .chain()
+        .mempool_schema()
+        .get_mempool_size()
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to load the mempool size: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(DrainStatus {
+        drained,
+        mempool_txs,
+    }))
+}
+
+/// Enables or disables the drain mode, it's applied by the API servers within a second.
+async fn set_drain_mode(data: &AppState, drained: bool) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    storage
+        .misc_schema()
+        .set_tx_intake_drained(drained)
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to set the drain mode: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Drain mode is {} via the admin API",
+        if drained { "enabled" } else { "disabled" }
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn enable_drain_mode(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    set_drain_mode(&data, true).await
+}
+
+async fn disable_drain_mode(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    set_drain_mode(&data, false).await
+}
+
+/// New Ethereum nodes of the signature checker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignCheckerEthClientRequest {
+    /// Addresses of the nodes, the requests are multiplexed if there are several of them.
+    pub web3_url: Vec<String>,
+}
+
+/// Switches the signature checker to other Ethereum nodes. Only available if the checker
+/// runs in the same process as the admin API.
+async fn switch_sign_checker_eth_client(
+    data: web::Data<AppState>,
+    request: web::Json<SignCheckerEthClientRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let switch = data.eth_client_switch.as_ref().ok_or_else(|| {
+        actix_web::error::ErrorNotFound("signature checker doesn't run in this process")
+    })?;
+    switch
+        .switch(request.into_inner().web3_url)
+        .map_err(actix_web::error::ErrorBadRequest)?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Reloads the runtime parameters from the config file, returns the new runtime config.
+async fn reload_config() -> actix_web::Result<HttpResponse> {
+    let config = RuntimeConfig::reload_from_file().map_err(|e| {
+        vlog::warn!("Failed to reload the config: {}", e);
+        actix_web::error::ErrorBadRequest(e.to_string())
+    })?;
+    vlog::info!("Reloaded the runtime config: {:?}", config);
+
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// Starts the admin API server. Connection pool has to access the main database,
+/// since the server stores the API keys.
+///
+/// Ethereum nodes of the signature checker can be switched if `eth_client_switch` is provided.
+#[must_use]
+pub fn start_admin_server(
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    eth_client_switch: Option<EthClientSwitch>,
+    config: AdminApiConfig,
+) -> JoinHandle<()> {
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    thread::Builder::new()
+        .name("actix-admin-api".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_sender);
+            actix_rt::System::new().block_on(async move {
+                let secret_auth = config.secret_auth.clone();
+                HttpServer::new(move || {
+                    let app_state = AppState {
+                        secret_auth: secret_auth.clone(),
+                        connection_pool: connection_pool.clone(),
+                        mempool_tx_sender: mempool_tx_sender.clone(),
+                        eth_client_switch: eth_client_switch.clone(),
+                    };
+
+                    let auth = HttpAuthentication::bearer(move |req, credentials| async {
+                        let secret_auth = req
+                            .app_data::<web::Data<AppState>>()
+                            .expect("failed get AppState upon receipt of the authentication token")
+                            .secret_auth
+                            .clone();
+                        AuthTokenValidator::new(&secret_auth)
+                            .validator(req, credentials)
+                            .await
+                    });
+
+                    App::new()
+                        .wrap(auth)
+                        .app_data(web::Data::new(app_state))
+                        .route("/api_keys", web::post().to(mint_api_key))
+                        .route("/api_keys/{id}", web::delete().to(revoke_api_key))
+                        .route(
+                            "/mempool/accounts/{address}",
+                            web::get().to(account_mempool_txs),
+                        )
+                        .route("/mempool/txs/{tx_hash}", web::get().to(mempool_tx))
+                        .route("/mempool/txs/{tx_hash}", web::delete().to(evict_mempool_tx))
+                        .route("/screening/{list}", web::get().to(address_list))
+                        .route(
+                            "/screening/{list}/{address}",
+                            web::put().to(add_listed_address),
+                        )
+                        .route(
+                            "/screening/{list}/{address}",
+                            web::delete().to(remove_listed_address),
+                        )
+                        .route("/fees/subsidies", web::get().to(token_subsidies))
+                        .route(
+                            "/fees/subsidies/{token_id}",
+                            web::put().to(set_token_subsidy),
+                        )
+                        .route(
+                            "/fees/subsidies/{token_id}",
+                            web::delete().to(remove_token_subsidy),
+                        )
+                        .route("/fees/tokens", web::get().to(fee_tokens))
+                        .route(
+                            "/fees/tokens/{token_id}",
+                            web::put().to(set_token_fee_acceptability),
+                        )
+                        .route(
+                            "/fees/tokens/{token_id}",
+                            web::delete().to(remove_token_fee_acceptability),
+                        )
+                        .route("/fees/promotions", web::get().to(fee_promotions))
+                        .route("/fees/promotions", web::post().to(add_fee_promotion))
+                        .route(
+                            "/fees/promotions/{id}",
+                            web::delete().to(remove_fee_promotion),
+                        )
+                        .route("/fees/free_accounts", web::get().to(fee_free_accounts))
+                        .route(
+                            "/fees/free_accounts/{account_id}",
+                            web::put().to(add_fee_free_account),
+                        )
+                        .route(
+                            "/fees/free_accounts/{account_id}",
+                            web::delete().to(remove_fee_free_account),
+                        )
+                        .route(
+                            "/fast_withdrawals/providers",
+                            web::get().to(liquidity_providers),
+                        )
+                        .route(
+                            "/fast_withdrawals/providers/{address}/{token_id}",
+                            web::put().to(add_liquidity_provider),
+                        )
+                        .route(
+                            "/fast_withdrawals/providers/{address}/{token_id}",
+                            web::delete().to(remove_liquidity_provider),
+                        )
+                        .route("/withdrawal_limits", web::get().to(withdrawal_limits))
+                        .route(
+                            "/withdrawal_limits/{token_id}",
+                            web::put().to(set_withdrawal_limits),
+                        )
+                        .route(
+                            "/withdrawal_limits/{token_id}",
+                            web::delete().to(remove_withdrawal_limits),
+                        )
+                        .route("/operator_costs/blocks", web::get().to(block_costs))
+                        .route("/operator_costs/days", web::get().to(daily_costs))
+                        .route(
+                            "/signature_checker/eth_client",
+                            web::put().to(switch_sign_checker_eth_client),
+                        )
+                        .route("/config/reload", web::post().to(reload_config))
+                        .route("/drain", web::get().to(drain_status))
+                        .route("/drain", web::put().to(enable_drain_mode))
+                        .route("/drain", web::delete().to(disable_drain_mode))
+                })
+                .bind(&config.bind_addr())
+                .expect("failed to bind")
+                .run()
+                .await
+                .expect("Admin API server has crashed");
+            })
+        })
+        .expect("Admin API server thread");
+    handler
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    fn auth_token(secret: &str, exp: usize) -> String {
+        let payload = PayloadAuthToken {
+            sub: "Authorization".to_string(),
+            exp,
+        };
+        encode(
+            &Header::default(),
+            &payload,
+            &EncodingKey::from_secret(secret.as_ref()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn auth_token_validation() {
+        let exp = chrono::Utc::now().timestamp() as usize + 3600;
+        let validator = AuthTokenValidator::new("secret");
+
+        assert!(validator
+            .validate_auth_token(&auth_token("secret", exp))
+            .is_ok());
+        assert!(validator
+            .validate_auth_token(&auth_token("other-secret", exp))
+            .is_err());
+        // Expired tokens are rejected.
+        assert!(validator
+            .validate_auth_token(&auth_token("secret", exp - 7200))
+            .is_err());
+    }
+
+    #[test]
+    fn mint_request_deserialization() {
+        let request: MintApiKeyRequest =
+            serde_json::from_str(r#"{"name":"partner","rps":10}"#).unwrap();
+        assert_eq!(request.rps, Some(10));
+        assert!(request.allowed_methods.is_empty());
+
+        let request: MintApiKeyRequest =
+            serde_json::from_str(r#"{"name":"partner","rps":null,"allowedMethods":["tx_submit"]}"#)
+                .unwrap();
+        assert_eq!(request.rps, None);
+        assert_eq!(request.allowed_methods, vec!["tx_submit".to_owned()]);
+    }
+
+    #[test]
+    fn address_list_parsing() {
+        assert!(parse_address_list("allow").unwrap());
+        assert!(!parse_address_list("deny").unwrap());
+        assert!(parse_address_list("other").is_err());
+    }
+
+    #[test]
+    fn fee_promotion_request_deserialization() {
+        let request: FeePromotionRequest = serde_json::from_str(
+            r#"{"discountPercent":25,"startsAt":"2022-10-17T00:00:00Z","endsAt":"2022-10-24T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.token_id, None);
+        assert_eq!(request.discount_percent, 25);
+
+        assert!(check_percent(100).is_ok());
+        assert!(check_percent(101).is_err());
+    }
+
+    #[test]
+    fn liquidity_provider_request_deserialization() {
+        let request: LiquidityProviderRequest =
+            serde_json::from_str(r#"{"maxAmount":"1000000000000000000000","feeBps":25}"#).unwrap();
+        assert_eq!(
+            request.max_amount,
+            BigUint::from(1_000_000_000_000_000_000_000u128)
+        );
+        assert_eq!(request.fee_bps, 25);
+    }
+
+    #[test]
+    fn withdrawal_limits_request_deserialization() {
+        let request: WithdrawalLimitsRequest = serde_json::from_str(
+            r#"{"accountDailyLimit":"1000000000000000000000","largeWithdrawalThreshold":"500","largeWithdrawalDelay":3600}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.account_daily_limit,
+            Some(BigUint::from(1_000_000_000_000_000_000_000u128).into())
+        );
+        assert_eq!(request.daily_volume_limit, None);
+        assert_eq!(
+            request.large_withdrawal_threshold,
+            Some(BigUint::from(500u32).into())
+        );
+        assert_eq!(request.large_withdrawal_delay, 3600);
+    }
+
+    #[test]
+    fn sign_checker_eth_client_request_deserialization() {
+        let request: SignCheckerEthClientRequest =
+            serde_json::from_str(r#"{"web3Url":["http://127.0.0.1:8545"]}"#).unwrap();
+        assert_eq!(request.web3_url, vec!["http://127.0.0.1:8545".to_owned()]);
+    }
+
+    #[test]
+    fn tx_hash_parsing() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        assert_eq!(
+            parse_tx_hash(&hash).unwrap().to_string(),
+            format!("sync-tx:{}", "ab".repeat(32))
+        );
+        assert!(parse_tx_hash(&hash.replace("0x", "sync-tx:")).is_ok());
+        assert!(parse_tx_hash("ab").is_err());
+        assert!(parse_tx_hash("0xab").is_err());
+    }
+}
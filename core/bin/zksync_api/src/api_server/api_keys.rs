@@ -0,0 +1,165 @@
+//! API keys of the partner integrations.
+//!
+//! Keys are minted and revoked via the admin API and stored in the database as hashes.
+//! Every API server keeps the active keys in memory and reloads them periodically,
+//! so the keys are checked without the database access.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+// External uses
+
+// Workspace uses
+use zksync_crypto::rand::{thread_rng, Rng};
+use zksync_storage::{api_keys::records::StoredApiKey, ConnectionPool};
+
+// Local uses
+use super::rate_limiter::endpoint_matches;
+
+/// Header the client sends its API key in.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Returns the hash the key is stored in the database with.
+pub fn hash_api_key(key: &str) -> Vec<u8> {
+    tiny_keccak::keccak256(key.as_bytes()).to_vec()
+}
+
+/// Generates the new random key.
+pub fn generate_api_key() -> String {
+    let bytes: [u8; 32] = thread_rng().gen();
+    hex::encode(bytes)
+}
+
+/// API key the client is authenticated with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    /// Requests per second allowed for the key, the key is not rate limited if not set.
+    pub rps: Option<u32>,
+    /// JSON RPC methods and REST API path prefixes the key has access to,
+    /// all the API is accessible if the list is empty.
+    pub allowed_methods: Vec<String>,
+}
+
+impl ApiKey {
+    /// Returns `true` if the key has access to the JSON RPC method or REST API path.
+    pub fn is_allowed(&self, endpoint: &str) -> bool {
+        self.allowed_methods.is_empty()
+            || self
+                .allowed_methods
+                .iter()
+                .any(|method| endpoint_matches(method, endpoint))
+    }
+}
+
+impl From<StoredApiKey> for ApiKey {
+    fn from(key: StoredApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            rps: key.rps.map(|rps| rps.max(0) as u32),
+            allowed_methods: key.allowed_methods,
+        }
+    }
+}
+
+/// Active API keys by their hashes.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys(Arc<RwLock<HashMap<Vec<u8>, ApiKey>>>);
+
+impl ApiKeys {
+    /// Creates the keys loaded from the database periodically with the interval.
+    ///
+    /// Until the keys are loaded for the first time, none of the keys are valid.
+    pub fn with_updater(connection_pool: ConnectionPool, interval: Duration) -> Self {
+        let api_keys = Self::default();
+        api_keys.spawn_updater(connection_pool, interval);
+        api_keys
+    }
+
+    /// Returns the key if it's active.
+    pub fn get(&self, key: &str) -> Option<ApiKey> {
+        self.0.read().unwrap().get(&hash_api_key(key)).cloned()
+    }
+
+    pub(crate) fn replace(&self, keys: Vec<StoredApiKey>) {
+        let keys = keys
+            .into_iter()
+            .map(|key| (key.key_hash.clone(), ApiKey::from(key)))
+            .collect();
+        *self.0.write().unwrap() = keys;
+    }
+
+    /// Reloads the active keys from the database.
+    pub async fn update(&self, connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let keys = storage.api_keys_schema().load_active_keys().await?;
+        self.replace(keys);
+        Ok(())
+    }
+
+    fn spawn_updater(&self, connection_pool: ConnectionPool, interval: Duration) {
+        let api_keys = self.clone();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(interval);
+            loop {
+                timer.tick().await;
+                if let Err(err) = api_keys.update(&connection_pool).await {
+                    vlog::warn!("Failed to load the API keys: {}", err);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn allowed_methods() {
+        let mut key = ApiKey {
+            id: 1,
+            name: "partner".to_owned(),
+            rps: None,
+            allowed_methods: Vec::new(),
+        };
+        assert!(key.is_allowed("tx_submit"));
+
+        key.allowed_methods = vec!["tx_submit".to_owned(), "/api/v0.2/accounts".to_owned()];
+        assert!(key.is_allowed("tx_submit"));
+        assert!(key.is_allowed("/api/v0.2/accounts/0x01/committed"));
+        assert!(!key.is_allowed("tx_submit_batch"));
+        assert!(!key.is_allowed("/api/v0.2/transactions"));
+    }
+
+    #[test]
+    fn keys_are_looked_up_by_hash() {
+        let key = generate_api_key();
+        let other_key = generate_api_key();
+        assert_ne!(key, other_key);
+
+        let api_keys = ApiKeys::default();
+        api_keys.replace(vec![StoredApiKey {
+            id: 1,
+            key_hash: hash_api_key(&key),
+            name: "partner".to_owned(),
+            rps: Some(10),
+            allowed_methods: Vec::new(),
+            created_at: Utc::now(),
+            revoked_at: None,
+        }]);
+        assert_eq!(api_keys.get(&key).unwrap().rps, Some(10));
+        assert!(api_keys.get(&other_key).is_none());
+
+        // Revoked keys are not loaded anymore.
+        api_keys.replace(Vec::new());
+        assert!(api_keys.get(&key).is_none());
+    }
+}
@@ -0,0 +1,177 @@
+//! Cache of the committed account states, so the states of the hot accounts are returned
+//! without querying the database.
+//!
+//! Committed state is changed only by the committer saving the account updates of the new blocks.
+//! Once they're saved, the committer sends the IDs of the updated accounts over the channel
+//! and their states are dropped from the cache. Thus the cache can only be used if the committer
+//! runs in the same process as the API.
+//!
+//! States are loaded from the main database rather than the replica, since the replica
+//! may lag behind the invalidation.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+// External uses
+use futures::{channel::mpsc, StreamExt};
+use lru_cache::LruCache;
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_types::{Account, AccountId, Address};
+// Local uses
+
+#[derive(Debug)]
+struct CacheInner {
+    states: LruCache<AccountId, Account>,
+    ids: HashMap<Address, AccountId>,
+    /// Number of the invalidations made. State loaded before the invalidation is not cached,
+    /// since it may be already outdated.
+    generation: u64,
+}
+
+impl CacheInner {
+    fn new(capacity: usize) -> Self {
+        Self {
+            states: LruCache::new(capacity),
+            ids: HashMap::new(),
+            generation: 0,
+        }
+    }
+
+    fn get(&mut self, address: Address) -> Option<(AccountId, Account)> {
+        let account_id = *self.ids.get(&address)?;
+        let account = self.states.get_mut(&account_id)?.clone();
+        Some((account_id, account))
+    }
+
+    /// Inserts the state loaded before the given number of the invalidations was made.
+    fn insert(&mut self, generation: u64, account_id: AccountId, account: Account) {
+        if self.generation != generation {
+            return;
+        }
+        if self.states.len() >= self.states.capacity() && !self.states.contains_key(&account_id) {
+            if let Some((_, evicted)) = self.states.remove_lru() {
+                self.ids.remove(&evicted.address);
+            }
+        }
+        self.ids.insert(account.address, account_id);
+        self.states.insert(account_id, account);
+    }
+
+    fn invalidate(&mut self, account_ids: &[AccountId]) {
+        self.generation += 1;
+        for account_id in account_ids {
+            if let Some(account) = self.states.remove(account_id) {
+                self.ids.remove(&account.address);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommittedStateCache {
+    inner: Arc<Mutex<CacheInner>>,
+    pool: ConnectionPool,
+}
+
+impl CommittedStateCache {
+    /// Creates the cache which states are loaded with the `pool` of the main database.
+    pub fn new(capacity: usize, pool: ConnectionPool) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CacheInner::new(capacity))),
+            pool,
+        }
+    }
+
+    /// Creates the cache invalidated by the IDs of the updated accounts sent by the committer.
+    pub fn with_invalidator(
+        capacity: usize,
+        pool: ConnectionPool,
+        mut updates: mpsc::Receiver<Vec<AccountId>>,
+    ) -> Self {
+        let cache = Self::new(capacity, pool);
+        let invalidated_cache = cache.clone();
+        tokio::spawn(async move {
+            while let Some(account_ids) = updates.next().await {
+                invalidated_cache.invalidate(&account_ids);
+            }
+        });
+        cache
+    }
+
+    /// Drops the states of the updated accounts.
+    pub fn invalidate(&self, account_ids: &[AccountId]) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.invalidate(account_ids);
+        metrics::gauge!("api.committed_state_cache.size", inner.states.len() as f64);
+    }
+
+    /// Returns the ID and the committed state of the account, `None` if there is no account
+    /// with such an address.
+    pub async fn committed_state(
+        &self,
+        address: Address,
+    ) -> QueryResult<Option<(AccountId, Account)>> {
+        let cached = self.inner.lock().unwrap().get(address);
+        if let Some(state) = cached {
+            metrics::increment_counter!("api.committed_state_cache.hits");
+            return Ok(Some(state));
+        }
+        metrics::increment_counter!("api.committed_state_cache.misses");
+
+        let generation = self.inner.lock().unwrap().generation;
+        let mut storage = self.pool.access_storage().await?;
+        let state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(address)
+            .await?
+            .committed;
+        if let Some((account_id, account)) = state.clone() {
+            let mut inner = self.inner.lock().unwrap();
+            inner.insert(generation, account_id, account);
+            metrics::gauge!("api.committed_state_cache.size", inner.states.len() as f64);
+        }
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(address: Address) -> Account {
+        Account::default_with_address(&address)
+    }
+
+    #[test]
+    fn states_are_invalidated() {
+        let mut cache = CacheInner::new(2);
+        let (first, second, third) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        );
+
+        cache.insert(0, AccountId(1), account(first));
+        cache.insert(0, AccountId(2), account(second));
+        assert_eq!(cache.get(first), Some((AccountId(1), account(first))));
+
+        // The least recently used state is evicted along with its address.
+        cache.insert(0, AccountId(3), account(third));
+        assert_eq!(cache.get(second), None);
+        assert_eq!(cache.ids.len(), 2);
+
+        cache.invalidate(&[AccountId(1)]);
+        assert_eq!(cache.get(first), None);
+        assert_eq!(cache.get(third), Some((AccountId(3), account(third))));
+
+        // State loaded before the invalidation is not cached.
+        cache.insert(0, AccountId(1), account(first));
+        assert_eq!(cache.get(first), None);
+        cache.insert(1, AccountId(1), account(first));
+        assert_eq!(cache.get(first), Some((AccountId(1), account(first))));
+    }
+}
@@ -0,0 +1,75 @@
+//! Drain mode of the transaction intake, used for the controlled upgrades.
+//!
+//! The mode is enabled via the admin API and stored in the database, every API server reloads it
+//! periodically. In the drain mode the new transactions are rejected with `TxAddError::Draining`,
+//! while the transactions accepted before finish their signature checks and are sent to the mempool,
+//! so the mempool can be processed to the end before the server is stopped.
+
+// Built-in uses
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+// External uses
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+
+// Local uses
+
+/// Interval between the reloads of the drain mode, it's the time the mode takes to be applied.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Default)]
+pub struct DrainMode(Arc<AtomicBool>);
+
+impl DrainMode {
+    /// Creates the drain mode loaded from the database periodically.
+    ///
+    /// Until it's loaded for the first time, the transactions are accepted.
+    pub fn with_updater(connection_pool: ConnectionPool) -> Self {
+        let drain_mode = Self::default();
+        drain_mode.spawn_updater(connection_pool);
+        drain_mode
+    }
+
+    /// Returns `true` if the new transactions are rejected.
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set(&self, enabled: bool) {
+        let was_enabled = self.0.swap(enabled, Ordering::Relaxed);
+        if was_enabled != enabled {
+            vlog::info!(
+                "Drain mode is {}",
+                if enabled { "enabled" } else { "disabled" }
+            );
+        }
+    }
+
+    /// Reloads the drain mode from the database.
+    pub async fn update(&self, connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let enabled = storage.misc_schema().is_tx_intake_drained().await?;
+        self.set(enabled);
+        Ok(())
+    }
+
+    fn spawn_updater(&self, connection_pool: ConnectionPool) {
+        let drain_mode = self.clone();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(UPDATE_INTERVAL);
+            loop {
+                timer.tick().await;
+                if let Err(err) = drain_mode.update(&connection_pool).await {
+                    vlog::warn!("Failed to load the drain mode: {}", err);
+                }
+            }
+        });
+    }
+}
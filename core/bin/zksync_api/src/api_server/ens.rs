@@ -0,0 +1,259 @@
+//! Resolution of the ENS names, so the API accepts `name.eth` in place of the account address.
+//!
+//! Name is resolved with the ENS registry set in the config: the registry returns the resolver
+//! of the name and the resolver returns the address of the name. Resolved names are cached
+//! for the configured TTL, so the name updated onchain may be resolved to its previous address
+//! until the TTL passes.
+//!
+//! Only the `.eth` names consisting of the lowercase letters, digits, `-` and `_` are accepted,
+//! the clients using the other characters have to normalize the names themselves.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+// External uses
+use chrono::Utc;
+use thiserror::Error;
+use web3::{
+    contract::Options,
+    types::{Address, H256},
+};
+// Workspace uses
+use zksync_api_types::v02::account::EnsResolution;
+use zksync_config::ZkSyncConfig;
+use zksync_contracts::{ens_registry_contract, ens_resolver_contract};
+use zksync_eth_client::ethereum_gateway::EthereumGateway;
+// Local uses
+use crate::utils::shared_lru_cache::SharedLruCache;
+
+const ENS_NAME_SUFFIX: &str = ".eth";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum EnsError {
+    #[error("ENS names are not supported by this node")]
+    NotSupported,
+    #[error("Invalid ENS name: {0}")]
+    InvalidName(String),
+    #[error("ENS name {0} is not resolved to any address")]
+    NotFound(String),
+    #[error("Failed to resolve the ENS name: {0}")]
+    Unavailable(String),
+}
+
+/// Returns `true` if the account is requested by the ENS name rather than by its address or ID.
+pub fn is_ens_name(name: &str) -> bool {
+    name.to_ascii_lowercase().ends_with(ENS_NAME_SUFFIX)
+}
+
+/// Computes the node of the name the ENS contracts are queried with, see EIP-137.
+pub fn namehash(name: &str) -> H256 {
+    // Empty name is the root node.
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(H256::zero(), |node, label| {
+            let label_hash = tiny_keccak::keccak256(label.as_bytes());
+            H256(tiny_keccak::keccak256(
+                &[node.as_bytes(), &label_hash].concat(),
+            ))
+        })
+}
+
+fn normalize_name(name: &str) -> Result<String, EnsError> {
+    let normalized = name.to_ascii_lowercase();
+    let is_valid = normalized.ends_with(ENS_NAME_SUFFIX)
+        && normalized.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        });
+    if is_valid {
+        Ok(normalized)
+    } else {
+        Err(EnsError::InvalidName(name.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EnsResolver {
+    client: EthereumGateway,
+    registry: Address,
+    registry_abi: ethabi::Contract,
+    resolver_abi: ethabi::Contract,
+    resolutions: SharedLruCache<String, (Instant, EnsResolution)>,
+    ttl: Duration,
+}
+
+impl EnsResolver {
+    pub fn new(client: EthereumGateway, registry: Address, capacity: usize, ttl: Duration) -> Self {
+        Self {
+            client,
+            registry,
+            registry_abi: ens_registry_contract(),
+            resolver_abi: ens_resolver_contract(),
+            resolutions: SharedLruCache::new(capacity),
+            ttl,
+        }
+    }
+
+    /// Creates the resolver if the ENS registry is set in the config.
+    pub fn from_config(config: &ZkSyncConfig) -> Option<Self> {
+        let registry = config.api.common.ens_registry_address?;
+        let client = EthereumGateway::from_config(
+            &config.eth_client,
+            &config.eth_sender,
+            config.contracts.contract_addr,
+        );
+        Some(Self::new(
+            client,
+            registry,
+            config.api.common.ens_cache_size,
+            config.api.common.ens_cache_ttl(),
+        ))
+    }
+
+    /// Resolves the name to the address, the resolution made within the TTL is reused.
+    pub async fn resolve(&self, name: &str) -> Result<EnsResolution, EnsError> {
+        let name = normalize_name(name)?;
+        match self.resolutions.get(&name) {
+            Some((resolved_at, resolution)) if resolved_at.elapsed() < self.ttl => {
+                metrics::increment_counter!("api.ens.cache_hits");
+                return Ok(resolution);
+            }
+            _ => metrics::increment_counter!("api.ens.cache_misses"),
+        }
+
+        let node = namehash(&name);
+        let resolver = self
+            .call(&self.registry_abi, "resolver", node, self.registry)
+            .await?;
+        if resolver.is_zero() {
+            return Err(EnsError::NotFound(name));
+        }
+        let address = self
+            .call(&self.resolver_abi, "addr", node, resolver)
+            .await?;
+        if address.is_zero() {
+            return Err(EnsError::NotFound(name));
+        }
+
+        let resolution = EnsResolution {
+            name: name.clone(),
+            address,
+            resolved_at: Utc::now(),
+        };
+        self.resolutions
+            .insert(name, (Instant::now(), resolution.clone()));
+        Ok(resolution)
+    }
+
+    async fn call(
+        &self,
+        abi: &ethabi::Contract,
+        func: &str,
+        node: H256,
+        contract: Address,
+    ) -> Result<Address, EnsError> {
+        self.client
+            .call_contract_function(
+                func,
+                (node,),
+                None,
+                Options::default(),
+                None,
+                contract,
+                abi.clone(),
+            )
+            .await
+            .map_err(|err| {
+                vlog::warn!("ENS contract call {} failed: {}", func, err);
+                EnsError::Unavailable(err.to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::Token;
+    use zksync_eth_client::clients::mock::MockEthereum;
+
+    #[test]
+    fn namehash_of_names() {
+        assert_eq!(namehash(""), H256::zero());
+        assert_eq!(
+            namehash("eth"),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn names_are_normalized() {
+        assert!(is_ens_name("Vitalik.ETH"));
+        assert!(!is_ens_name("0x0000000000000000000000000000000000000000"));
+        assert_eq!(normalize_name("Sub.Name.eth").unwrap(), "sub.name.eth");
+        for name in &["name.com", ".eth", "na..me.eth", "na me.eth", "nåme.eth"] {
+            assert_eq!(
+                normalize_name(name),
+                Err(EnsError::InvalidName(name.to_string()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn resolution_is_cached() {
+        let address = Address::repeat_byte(0x42);
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result("resolver", vec![Token::Address(Address::repeat_byte(1))])
+            .await;
+        client
+            .add_call_result("addr", vec![Token::Address(address)])
+            .await;
+        let resolver = EnsResolver::new(
+            EthereumGateway::Mock(client.clone()),
+            Address::repeat_byte(2),
+            10,
+            Duration::from_secs(60),
+        );
+
+        let resolution = resolver.resolve("Name.eth").await.unwrap();
+        assert_eq!(resolution.name, "name.eth");
+        assert_eq!(resolution.address, address);
+
+        // The name is updated onchain, but the cached resolution is returned within the TTL.
+        client
+            .add_call_result("addr", vec![Token::Address(Address::zero())])
+            .await;
+        assert_eq!(resolver.resolve("name.eth").await.unwrap(), resolution);
+        assert_eq!(
+            resolver.resolve("other.eth").await,
+            Err(EnsError::NotFound("other.eth".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn name_without_resolver_is_not_found() {
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result("resolver", vec![Token::Address(Address::zero())])
+            .await;
+        let resolver = EnsResolver::new(
+            EthereumGateway::Mock(client),
+            Address::repeat_byte(2),
+            10,
+            Duration::from_secs(60),
+        );
+        assert_eq!(
+            resolver.resolve("name.eth").await,
+            Err(EnsError::NotFound("name.eth".to_owned()))
+        );
+    }
+}
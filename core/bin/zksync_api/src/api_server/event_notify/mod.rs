@@ -1,4 +1,6 @@
-use super::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp};
+use super::rpc_server::types::{
+    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+};
 use futures::{channel::mpsc, select, stream::StreamExt};
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -42,6 +44,11 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    /// Every block committed or verified after the subscription.
+    NewBlocks {
+        action: ActionType,
+        subscriber: Subscriber<BlockInfo>,
+    },
 }
 
 pub enum EventNotifierRequest {
@@ -10,8 +10,9 @@ use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{NewBlocks, SubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps,
 };
 
 pub struct OperationNotifier {
@@ -20,6 +21,7 @@ pub struct OperationNotifier {
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    block_subs: SubStorage<NewBlocks, BlockInfo>,
 }
 
 impl OperationNotifier {
@@ -33,6 +35,7 @@ impl OperationNotifier {
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            block_subs: SubStorage::new(),
         }
     }
 
@@ -64,6 +67,9 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::NewBlocks { action, subscriber } => {
+                    self.add_new_blocks_sub(action, subscriber)
+                }
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -86,6 +92,15 @@ impl OperationNotifier {
         };
 
         for block in blocks {
+            self.block_subs.broadcast(
+                NewBlocks,
+                action,
+                BlockInfo {
+                    block_number: i64::from(*block.block_number),
+                    committed: true,
+                    verified: action == ActionType::VERIFY,
+                },
+            );
             self.handle_executed_operations(
                 block.block_transactions.clone(),
                 action,
@@ -143,6 +158,7 @@ impl OperationNotifier {
                             committed: true,
                             verified: action == ActionType::VERIFY,
                         }),
+                        signature: None,
                     };
                     self.tx_subs.notify(hash, action, resp);
                 }
@@ -180,7 +196,8 @@ impl OperationNotifier {
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.block_subs.remove(sub_id)?;
         Ok(())
     }
 
@@ -258,6 +275,7 @@ impl OperationNotifier {
                     committed: receipt.success,
                     verified: receipt.verified,
                 }),
+                signature: None,
             };
             match action {
                 ActionType::COMMIT => {
@@ -295,4 +313,15 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add new blocks subscription. Unlike the other subscriptions, it's notified about
+    /// every block until the subscriber unsubscribes.
+    fn add_new_blocks_sub(
+        &mut self,
+        action: ActionType,
+        sub: Subscriber<BlockInfo>,
+    ) -> Result<(), anyhow::Error> {
+        let sub_id = self.block_subs.generate_sub_id(NewBlocks, action);
+        self.block_subs.insert_new(sub_id, sub, NewBlocks, action)
+    }
 }
@@ -1,6 +1,6 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
+use std::{cmp::Ord, collections::BTreeMap, fmt, str::FromStr};
 use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
 
 use jsonrpc_pubsub::{
@@ -12,6 +12,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const BLOCK_SUB_PREFIX: &str = "blsub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -35,6 +36,33 @@ impl ActionId for AccountId {
     }
 }
 
+/// Key of the subscriptions to every new block, which are not tied to any entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NewBlocks;
+
+impl fmt::Display for NewBlocks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "all")
+    }
+}
+
+impl FromStr for NewBlocks {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self),
+            _ => anyhow::bail!("Unknown block subscription key: {}", s),
+        }
+    }
+}
+
+impl ActionId for NewBlocks {
+    fn sub_type() -> &'static str {
+        BLOCK_SUB_PREFIX
+    }
+}
+
 #[derive(Debug)]
 pub struct SubStorage<ID, RESP> {
     storage: BTreeMap<(ID, ActionType), Vec<SubscriptionSender<RESP>>>,
@@ -144,6 +172,24 @@ where
         }
     }
 
+    /// Notifies the subscribers without removing them, so they keep receiving the events.
+    /// Subscribers which can't be notified, e.g. the disconnected ones, are removed.
+    pub fn broadcast(&mut self, action_id: ID, action_type: ActionType, event: RESP) {
+        let key = (action_id, action_type);
+        if let Some(subs) = self.storage.get_mut(&key) {
+            subs.retain(|sub| match sub.sink.notify(Ok(event.clone())) {
+                Ok(()) => true,
+                Err(e) => {
+                    vlog::debug!("Removing the subscription {:?}: {}", sub.id, e);
+                    false
+                }
+            });
+            if subs.is_empty() {
+                self.storage.remove(&key);
+            }
+        }
+    }
+
     pub fn respond_once(
         &mut self,
         sub_id: SubscriptionId,
@@ -0,0 +1,393 @@
+//! gRPC interface of the API server, see `proto/zksync.proto`.
+//!
+//! The service is a thin layer on top of the JSON RPC implementation: the transactions
+//! are sent to the same signature checker and mempool, and the responses are converted
+//! to protobuf.
+
+// Built-in uses
+use std::{pin::Pin, time::Duration};
+
+// External uses
+use futures::{channel::mpsc, stream, Stream};
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+use tonic::{transport::Server, Request, Response, Status};
+
+// Workspace uses
+use zksync_api_types::TxWithSignature;
+use zksync_config::configs::api::{CommonApiConfig, GrpcConfig, TokenConfig};
+use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::ConnectionPool;
+use zksync_types::{tx::TxHash, Address, TokenLike};
+use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+
+// Local uses
+use super::rpc_server::{
+    types::{AccountInfoResp, ResponseAccountState, TransactionInfoResp},
+    RpcApp,
+};
+use crate::{
+    api_server::committed_state_cache::CommittedStateCache, fee_ticker::FeeTicker,
+    signature_checker::VerifySignatureRequest,
+};
+
+pub mod proto {
+    tonic::include_proto!("zksync.api.v1");
+}
+
+use self::proto::zk_sync_server::{ZkSync, ZkSyncServer};
+
+/// Interval between the storage polls of the streamed transaction receipts.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
+pub struct GrpcApp {
+    rpc_app: RpcApp,
+}
+
+impl GrpcApp {
+    pub fn new(rpc_app: RpcApp) -> Self {
+        Self { rpc_app }
+    }
+
+    async fn tx_receipt(&self, tx_hash: TxHash) -> Result<proto::TxReceipt, Status> {
+        let receipt = self
+            .rpc_app
+            .clone()
+            ._impl_tx_info(tx_hash)
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(tx_receipt(tx_hash, receipt))
+    }
+}
+
+#[tonic::async_trait]
+impl ZkSync for GrpcApp {
+    async fn submit_tx(
+        &self,
+        request: Request<proto::SubmitTxRequest>,
+    ) -> Result<Response<proto::SubmitTxResponse>, Status> {
+        let request = request.into_inner();
+        let tx = tx_with_signature(
+            request
+                .tx
+                .ok_or_else(|| Status::invalid_argument("Transaction is not set"))?,
+        )?;
+        let tx_hash = self
+            .rpc_app
+            .clone()
+            ._impl_tx_submit(
+                Box::new(tx.tx),
+                Box::new(tx.signature),
+                Some(request.fast_processing),
+                None,
+            )
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(Response::new(proto::SubmitTxResponse {
+            tx_hash: tx_hash.as_ref().to_vec(),
+        }))
+    }
+
+    async fn submit_tx_batch(
+        &self,
+        request: Request<proto::SubmitTxBatchRequest>,
+    ) -> Result<Response<proto::SubmitTxBatchResponse>, Status> {
+        let request = request.into_inner();
+        let txs = request
+            .txs
+            .into_iter()
+            .map(tx_with_signature)
+            .collect::<Result<Vec<_>, _>>()?;
+        let eth_signatures = optional_json(&request.eth_signatures, "eth_signatures")?;
+        let tx_hashes = self
+            .rpc_app
+            .clone()
+            ._impl_submit_txs_batch(txs, eth_signatures, None)
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(Response::new(proto::SubmitTxBatchResponse {
+            tx_hashes: tx_hashes
+                .iter()
+                .map(|tx_hash| tx_hash.as_ref().to_vec())
+                .collect(),
+        }))
+    }
+
+    async fn get_account_state(
+        &self,
+        request: Request<proto::GetAccountStateRequest>,
+    ) -> Result<Response<proto::AccountState>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+        let account_info = self
+            .rpc_app
+            .clone()
+            ._impl_account_info(address)
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(Response::new(account_state(account_info)))
+    }
+
+    async fn get_tx_receipt(
+        &self,
+        request: Request<proto::TxReceiptRequest>,
+    ) -> Result<Response<proto::TxReceipt>, Status> {
+        let tx_hash = parse_tx_hash(&request.into_inner().tx_hash)?;
+        self.tx_receipt(tx_hash).await.map(Response::new)
+    }
+
+    type StreamTxReceiptsStream =
+        Pin<Box<dyn Stream<Item = Result<proto::TxReceipt, Status>> + Send + 'static>>;
+
+    async fn stream_tx_receipts(
+        &self,
+        request: Request<proto::TxReceiptRequest>,
+    ) -> Result<Response<Self::StreamTxReceiptsStream>, Status> {
+        let tx_hash = parse_tx_hash(&request.into_inner().tx_hash)?;
+        let app = self.clone();
+        // The state is the last sent receipt, `None` once the stream is finished.
+        let receipts = stream::unfold(Some(proto::TxReceipt::default()), move |last_receipt| {
+            let app = app.clone();
+            async move {
+                let last_receipt = last_receipt?;
+                if is_final(&last_receipt) {
+                    return None;
+                }
+                loop {
+                    match app.tx_receipt(tx_hash).await {
+                        Ok(receipt) if receipt != last_receipt => {
+                            return Some((Ok(receipt.clone()), Some(receipt)));
+                        }
+                        Ok(_) => tokio::time::sleep(RECEIPT_POLL_INTERVAL).await,
+                        Err(err) => return Some((Err(err), None)),
+                    }
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(receipts)))
+    }
+
+    async fn get_txs_batch_fee(
+        &self,
+        request: Request<proto::GetTxsBatchFeeRequest>,
+    ) -> Result<Response<proto::TotalFee>, Status> {
+        let request = request.into_inner();
+        let mut tx_types = Vec::with_capacity(request.txs.len());
+        let mut addresses = Vec::with_capacity(request.txs.len());
+        for tx in request.txs {
+            tx_types.push(
+                optional_json(&tx.tx_type, "tx_type")?
+                    .ok_or_else(|| Status::invalid_argument("Transaction type is not set"))?,
+            );
+            addresses.push(parse_address(&tx.address)?);
+        }
+        let fee = self
+            .rpc_app
+            .clone()
+            ._impl_get_txs_batch_fee_in_wei(
+                tx_types,
+                addresses,
+                TokenLike::parse(&request.fee_token),
+                None,
+            )
+            .await
+            .map_err(status_from_rpc_error)?;
+        Ok(Response::new(proto::TotalFee {
+            total_fee: fee.total_fee.to_string(),
+        }))
+    }
+}
+
+/// Checks whether the receipt of the transaction won't change anymore.
+fn is_final(receipt: &proto::TxReceipt) -> bool {
+    let verified = receipt.block.as_ref().map_or(false, |block| block.verified);
+    receipt.executed && (!receipt.success || verified)
+}
+
+fn parse_address(bytes: &[u8]) -> Result<Address, Status> {
+    if bytes.len() != Address::len_bytes() {
+        return Err(Status::invalid_argument("Invalid account address"));
+    }
+    Ok(Address::from_slice(bytes))
+}
+
+fn parse_tx_hash(bytes: &[u8]) -> Result<TxHash, Status> {
+    TxHash::from_slice(bytes).ok_or_else(|| Status::invalid_argument("Invalid transaction hash"))
+}
+
+/// Deserializes the JSON-encoded field, treating the empty string as an absent value.
+fn optional_json<T: DeserializeOwned>(json: &str, field: &str) -> Result<Option<T>, Status> {
+    if json.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(json)
+        .map(Some)
+        .map_err(|err| Status::invalid_argument(format!("Cannot parse {}: {}", field, err)))
+}
+
+fn tx_with_signature(tx: proto::TxWithSignature) -> Result<TxWithSignature, Status> {
+    Ok(TxWithSignature {
+        tx: optional_json(&tx.tx, "tx")?
+            .ok_or_else(|| Status::invalid_argument("Transaction is not set"))?,
+        signature: optional_json(&tx.signature, "signature")?.unwrap_or_default(),
+    })
+}
+
+/// Converts the JSON RPC error to the gRPC status, keeping the error message.
+fn status_from_rpc_error(err: jsonrpc_core::Error) -> Status {
+    use jsonrpc_core::ErrorCode;
+
+    let http_status = err
+        .data
+        .as_ref()
+        .and_then(|data| data.get("httpStatus"))
+        .and_then(|status| status.as_u64());
+    match (err.code, http_status) {
+        (_, Some(503)) => Status::unavailable(err.message),
+        (ErrorCode::InternalError, _) => Status::internal(err.message),
+        _ => Status::invalid_argument(err.message),
+    }
+}
+
+fn tx_receipt(tx_hash: TxHash, receipt: TransactionInfoResp) -> proto::TxReceipt {
+    proto::TxReceipt {
+        tx_hash: tx_hash.as_ref().to_vec(),
+        executed: receipt.executed,
+        success: receipt.success.unwrap_or_default(),
+        fail_reason: receipt.fail_reason.unwrap_or_default(),
+        block: receipt.block.map(|block| proto::BlockInfo {
+            block_number: block.block_number,
+            committed: block.committed,
+            verified: block.verified,
+        }),
+    }
+}
+
+fn response_account_state(state: ResponseAccountState) -> proto::ResponseAccountState {
+    proto::ResponseAccountState {
+        balances: state
+            .balances
+            .into_iter()
+            .map(|(symbol, balance)| (symbol, balance.0.to_string()))
+            .collect(),
+        nfts: state.nfts.keys().map(|token_id| token_id.0).collect(),
+        minted_nfts: state
+            .minted_nfts
+            .keys()
+            .map(|token_id| token_id.0)
+            .collect(),
+        nonce: *state.nonce,
+        pub_key_hash: state.pub_key_hash.as_hex(),
+    }
+}
+
+fn account_state(account_info: AccountInfoResp) -> proto::AccountState {
+    proto::AccountState {
+        address: account_info.address.as_bytes().to_vec(),
+        id: account_info
+            .id
+            .map(|account_id| proto::AccountId { id: *account_id }),
+        committed: Some(response_account_state(account_info.committed)),
+        verified: Some(response_account_state(account_info.verified)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[must_use]
+pub fn start_grpc_server(
+    connection_pool: ConnectionPool,
+    sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
+    ticker: FeeTicker,
+    config: &GrpcConfig,
+    common_api_config: &CommonApiConfig,
+    token_config: &TokenConfig,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    confirmations_for_eth_event: u64,
+    committed_states: Option<CommittedStateCache>,
+) -> JoinHandle<()> {
+    let addr = config.bind_addr();
+    let rpc_app = RpcApp::new(
+        connection_pool,
+        sign_verify_request_sender,
+        ticker,
+        common_api_config,
+        token_config,
+        confirmations_for_eth_event,
+        mempool_tx_sender,
+        committed_states,
+    );
+    let grpc_app = GrpcApp::new(rpc_app);
+
+    let (handler, panic_sender) = spawn_panic_handler();
+    std::thread::spawn(move || {
+        let _panic_sentinel = ThreadPanicNotify(panic_sender);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(super::THREADS_PER_SERVER)
+            .enable_all()
+            .build()
+            .expect("Failed to create the gRPC server runtime");
+        runtime.block_on(async move {
+            Server::builder()
+                .add_service(ZkSyncServer::new(grpc_app))
+                .serve(addr)
+                .await
+                .expect("gRPC server failed");
+        });
+    });
+    handler
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt(executed: bool, success: bool, verified: bool) -> proto::TxReceipt {
+        proto::TxReceipt {
+            executed,
+            success,
+            block: Some(proto::BlockInfo {
+                block_number: 1,
+                committed: true,
+                verified,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn receipt_finality() {
+        assert!(!is_final(&proto::TxReceipt::default()));
+        assert!(!is_final(&receipt(true, true, false)));
+        assert!(is_final(&receipt(true, true, true)));
+        // Failed transactions are never verified.
+        assert!(is_final(&receipt(true, false, false)));
+    }
+
+    #[test]
+    fn rpc_error_conversion() {
+        let err = jsonrpc_core::Error {
+            code: jsonrpc_core::ErrorCode::ServerError(300),
+            message: "Ethereum node is unavailable".to_string(),
+            data: Some(serde_json::json!({ "httpStatus": 503 })),
+        };
+        let status = status_from_rpc_error(err);
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(status.message(), "Ethereum node is unavailable");
+
+        let status = status_from_rpc_error(jsonrpc_core::Error::internal_error());
+        assert_eq!(status.code(), tonic::Code::Internal);
+        let status = status_from_rpc_error(jsonrpc_core::Error::invalid_params("wrong"));
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn empty_json_fields() {
+        let tx = proto::TxWithSignature::default();
+        assert_eq!(
+            tx_with_signature(tx).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+        let signatures: Option<serde_json::Value> = optional_json("", "eth_signatures").unwrap();
+        assert!(signatures.is_none());
+    }
+}
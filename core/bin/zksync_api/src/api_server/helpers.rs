@@ -4,17 +4,23 @@
 use std::collections::HashMap;
 
 // External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use num::BigUint;
 
 // Workspace uses
 use zksync_api_types::v02::account::{DepositingAccountBalances, DepositingFunds, OngoingDeposit};
+use zksync_mempool::{AccountNonces, MempoolTransactionRequest};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Address, H256};
+use zksync_types::{AccountId, Address, Nonce, H256};
 use zksync_utils::remove_prefix;
 
 // Local uses
 use super::rest::v02::error::Error;
+use super::tx_sender::SubmitError;
 use crate::fee_ticker::PriceError;
 
 pub fn try_parse_hash(query: &str) -> Result<H256, hex::FromHexError> {
@@ -84,3 +90,34 @@ pub async fn get_depositing(
         .collect();
     depositing_from_pending_ops(storage, tokens, pending_ops, confirmations_for_eth_event).await
 }
+
+/// Requests the nonces of the account from the mempool. The account which doesn't exist yet
+/// has no pending transactions, so all its nonces are zero.
+pub async fn get_account_nonces(
+    mempool_tx_sender: &mpsc::Sender<MempoolTransactionRequest>,
+    account_id: Option<AccountId>,
+) -> Result<AccountNonces, SubmitError> {
+    let account_id = match account_id {
+        Some(account_id) => account_id,
+        None => {
+            return Ok(AccountNonces {
+                committed_nonce: Nonce(0),
+                next_nonce: Nonce(0),
+                pending_nonces: Vec::new(),
+            })
+        }
+    };
+
+    let (sender, receiver) = oneshot::channel();
+    mempool_tx_sender
+        .clone()
+        .send(MempoolTransactionRequest::GetAccountNonces(
+            account_id, sender,
+        ))
+        .await
+        .map_err(SubmitError::mempool_communication)?;
+    let nonces = receiver
+        .await
+        .map_err(SubmitError::mempool_communication)??;
+    Ok(nonces)
+}
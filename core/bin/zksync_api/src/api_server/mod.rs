@@ -3,15 +3,30 @@
 //! `mod rest` - api is used for block explorer.
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
+//! `mod grpc` - gRPC (for transaction submission and state queries)
+//! `mod admin_server` - admin API (for managing the API keys and the mempool)
 
+pub mod access_control;
+pub mod admin_server;
+pub mod api_keys;
+pub mod committed_state_cache;
+pub mod drain_mode;
+pub mod ens;
 mod event_notify;
 pub mod forced_exit_checker;
+pub mod grpc;
 mod helpers;
+pub mod rate_limiter;
+pub mod rejection_writer;
+pub mod response_signer;
 pub mod rest;
 pub mod rpc_server;
 pub mod rpc_subscriptions;
 mod tx_sender;
+pub mod tx_screening;
+pub mod tx_simulator;
 pub mod web3;
+pub mod withdrawal_limits;
 
 /// Amount of threads used by each server to serve requests.
 const THREADS_PER_SERVER: usize = 128;
@@ -0,0 +1,427 @@
+//! Rate limiting of the API requests.
+//!
+//! Every anonymous client IP has a token bucket shared by all the endpoints, and a separate
+//! bucket per endpoint with its own rate limit (see `CommonApiConfig::rate_limit_endpoints`).
+//! The client IP is the peer address, unless the request is sent by the trusted proxy
+//! (see `client_ip`).
+//! Clients authenticated with the API key have a single bucket with the quota of the key.
+//! The bucket holds `rps + burst` tokens and is refilled with `rps` tokens per second,
+//! every request takes one token. The limit of the anonymous clients and the burst can be
+//! changed by reloading the runtime config.
+
+// Built-in uses
+use std::{
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+// External uses
+use lru_cache::LruCache;
+
+// Workspace uses
+use zksync_config::{
+    configs::api::{CommonApiConfig, EndpointRateLimit, TrustedProxy},
+    RuntimeConfig,
+};
+
+// Local uses
+use super::api_keys::ApiKey;
+
+const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+const FORWARDED_FOR_HEADER: &str = "X-Forwarded-For";
+
+/// Number of the tracked buckets after which the least recently used ones are dropped.
+const MAX_TRACKED_BUCKETS: usize = 100_000;
+
+/// Returns the IP of the client of the request sent from the peer address.
+///
+/// Forwarding headers are only read if the peer is the trusted proxy, since any other client may
+/// set them. The IP set by Cloudflare is preferred, otherwise it's the rightmost `X-Forwarded-For`
+/// hop which is not a trusted proxy: the hops to the left of it may be set by the client.
+/// Headers of the requests from the unknown peer, i.e. the JSON RPC ones, are never read,
+/// so their client IP is unknown.
+pub fn client_ip<'a>(
+    peer: Option<IpAddr>,
+    trusted_proxies: &[TrustedProxy],
+    header: impl Fn(&str) -> Option<&'a str>,
+) -> Option<String> {
+    let is_trusted = |ip: IpAddr| trusted_proxies.iter().any(|proxy| proxy.contains(ip));
+    if !peer.map_or(false, is_trusted) {
+        return peer.map(|peer| peer.to_string());
+    }
+
+    let forwarded_ip = || {
+        let forwarded_for = header(FORWARDED_FOR_HEADER)?;
+        let mut client = None;
+        for hop in forwarded_for.rsplit(',') {
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    client = Some(ip);
+                    if !is_trusted(ip) {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        client
+    };
+    header(CLOUDFLARE_CONNECTING_IP_HEADER)
+        .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+        .or_else(forwarded_ip)
+        .or(peer)
+        .map(|ip| ip.to_string())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    rps: u32,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rps: u32, burst: u32, now: Instant) -> Self {
+        Self {
+            tokens: Self::capacity(rps, burst),
+            rps,
+            updated_at: now,
+        }
+    }
+
+    fn capacity(rps: u32, burst: u32) -> f64 {
+        f64::from(rps) + f64::from(burst)
+    }
+
+    fn refill(&mut self, burst: u32, now: Instant) {
+        let capacity = Self::capacity(self.rps, burst);
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * f64::from(self.rps)).min(capacity);
+        self.updated_at = now;
+    }
+}
+
+/// Client the bucket belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum BucketOwner {
+    /// Anonymous client with the IP and the index of the separately limited endpoint.
+    Ip(String, Option<usize>),
+    /// Client authenticated with the API key with the ID.
+    ApiKey(i64),
+}
+
+/// Returns `true` if the endpoint matches the pattern: JSON RPC methods are matched exactly,
+/// REST API paths by the prefix.
+pub fn endpoint_matches(pattern: &str, endpoint: &str) -> bool {
+    if pattern.starts_with('/') {
+        endpoint.starts_with(pattern)
+    } else {
+        endpoint == pattern
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiterInner {
+    /// Limit of the anonymous clients, they are not rate limited if it's not set.
+    rps: Option<u32>,
+    burst: u32,
+    endpoints: Vec<EndpointRateLimit>,
+    /// Buckets of the recently seen clients. The least recently used bucket is dropped once
+    /// there are too many of them, so the client is limited from scratch if it comes back.
+    buckets: Mutex<LruCache<BucketOwner, TokenBucket>>,
+}
+
+impl RateLimiterInner {
+    fn new(rps: Option<u32>, burst: u32, endpoints: Vec<EndpointRateLimit>) -> Self {
+        Self {
+            rps,
+            burst,
+            endpoints,
+            buckets: Mutex::new(LruCache::new(MAX_TRACKED_BUCKETS)),
+        }
+    }
+
+    fn endpoint_index(&self, endpoint: &str) -> Option<usize> {
+        self.endpoints
+            .iter()
+            .position(|limit| endpoint_matches(&limit.endpoint, endpoint))
+    }
+
+    /// Returns the rate limit and the burst of the reloaded runtime config, if it's initialized.
+    fn limits(&self) -> (Option<u32>, u32) {
+        match RuntimeConfig::current() {
+            Some(config) => (config.rate_limit_rps, config.rate_limit_burst),
+            None => (self.rps, self.burst),
+        }
+    }
+}
+
+/// Token bucket rate limiter of the requests by the client IP and endpoint,
+/// or by the API key the client is authenticated with.
+#[derive(Debug, Clone)]
+pub struct RateLimiter(Arc<RateLimiterInner>);
+
+impl RateLimiter {
+    pub fn new(config: &CommonApiConfig) -> Self {
+        Self(Arc::new(RateLimiterInner::new(
+            config.rate_limit_rps,
+            config.rate_limit_burst,
+            config.rate_limit_endpoints.clone(),
+        )))
+    }
+
+    /// Takes the token from the bucket of the anonymous client, returns `false` if it's empty
+    /// and the request has to be rejected.
+    ///
+    /// `api_type` is only used as the label of the rejected requests metric.
+    pub fn check(&self, ip: &str, endpoint: &str, api_type: &'static str) -> bool {
+        let allowed = self.check_at(ip, endpoint, Instant::now());
+        if !allowed {
+            // Only the configured endpoints are used as the label, so its cardinality is bounded.
+            let endpoint = match self.0.endpoint_index(endpoint) {
+                Some(index) => self.0.endpoints[index].endpoint.clone(),
+                None => "other".to_owned(),
+            };
+            metrics::increment_counter!("api.rate_limited", "type" => api_type, "endpoint" => endpoint);
+        }
+        allowed
+    }
+
+    /// Takes the token from the bucket of the API key, the quota of the key
+    /// is shared by all the endpoints.
+    pub fn check_api_key(&self, api_key: &ApiKey, api_type: &'static str) -> bool {
+        let allowed = self.check_api_key_at(api_key, Instant::now());
+        if !allowed {
+            metrics::increment_counter!("api.api_key_rate_limited", "type" => api_type, "api_key" => api_key.name.clone());
+        }
+        allowed
+    }
+
+    fn check_at(&self, ip: &str, endpoint: &str, now: Instant) -> bool {
+        let rps = match self.0.limits().0 {
+            Some(rps) => rps,
+            None => return true,
+        };
+        let index = self.0.endpoint_index(endpoint);
+        let rps = index.map_or(rps, |index| self.0.endpoints[index].rps);
+        self.take_token(BucketOwner::Ip(ip.to_owned(), index), rps, now)
+    }
+
+    fn check_api_key_at(&self, api_key: &ApiKey, now: Instant) -> bool {
+        match api_key.rps {
+            Some(rps) => self.take_token(BucketOwner::ApiKey(api_key.id), rps, now),
+            None => true,
+        }
+    }
+
+    fn take_token(&self, owner: BucketOwner, rps: u32, now: Instant) -> bool {
+        let (_, burst) = self.0.limits();
+        let mut buckets = self.0.buckets.lock().unwrap();
+        if !buckets.contains_key(&owner) {
+            // Least recently used bucket is dropped if the cache is full.
+            buckets.insert(owner.clone(), TokenBucket::new(rps, burst, now));
+        }
+
+        let bucket = buckets.get_mut(&owner).expect("Bucket is inserted above");
+        // Quota of the API key may be changed while the bucket is tracked.
+        bucket.rps = rps;
+        bucket.refill(burst, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{convert::TryFrom, time::Duration};
+
+    fn rate_limiter() -> RateLimiter {
+        RateLimiter(Arc::new(RateLimiterInner::new(
+            Some(2),
+            1,
+            vec![
+                EndpointRateLimit {
+                    endpoint: "tx_submit".to_owned(),
+                    rps: 1,
+                },
+                EndpointRateLimit {
+                    endpoint: "/api/v0.2/transactions".to_owned(),
+                    rps: 1,
+                },
+            ],
+        )))
+    }
+
+    #[test]
+    fn token_bucket() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+
+        // Bucket holds `rps + burst` tokens.
+        for _ in 0..3 {
+            assert!(rate_limiter.check_at("1.1.1.1", "account_info", now));
+        }
+        assert!(!rate_limiter.check_at("1.1.1.1", "tokens", now));
+        // Other clients have their own buckets.
+        assert!(rate_limiter.check_at("2.2.2.2", "account_info", now));
+
+        // Bucket is refilled with `rps` tokens per second.
+        let later = now + Duration::from_millis(500);
+        assert!(rate_limiter.check_at("1.1.1.1", "account_info", later));
+        assert!(!rate_limiter.check_at("1.1.1.1", "account_info", later));
+    }
+
+    #[test]
+    fn endpoint_limits() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            assert!(rate_limiter.check_at("1.1.1.1", "tx_submit", now));
+        }
+        assert!(!rate_limiter.check_at("1.1.1.1", "tx_submit", now));
+        // Separately limited endpoints don't take the tokens of the rest of the API.
+        assert!(rate_limiter.check_at("1.1.1.1", "account_info", now));
+
+        // REST API paths are matched by the prefix.
+        for _ in 0..2 {
+            assert!(rate_limiter.check_at("1.1.1.1", "/api/v0.2/transactions/0x01", now));
+        }
+        assert!(!rate_limiter.check_at("1.1.1.1", "/api/v0.2/transactions", now));
+        // Method names are matched exactly.
+        assert!(rate_limiter.check_at("1.1.1.1", "tx_submit_batch", now));
+    }
+
+    #[test]
+    fn least_recently_used_buckets_dropped() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert!(rate_limiter.check_at("1.1.1.1", "account_info", now));
+            assert!(rate_limiter.check_at("2.2.2.2", "account_info", now));
+        }
+        for i in 0..MAX_TRACKED_BUCKETS - 2 {
+            rate_limiter.check_at(&i.to_string(), "account_info", now);
+        }
+        // Recently used bucket is kept, while the least recently used one is dropped.
+        assert!(!rate_limiter.check_at("2.2.2.2", "account_info", now));
+        assert!(rate_limiter.check_at("3.3.3.3", "account_info", now));
+        assert!(rate_limiter.check_at("1.1.1.1", "account_info", now));
+        assert_eq!(
+            rate_limiter.0.buckets.lock().unwrap().len(),
+            MAX_TRACKED_BUCKETS
+        );
+    }
+
+    #[test]
+    fn client_ip_headers() {
+        let headers = |cf: Option<&'static str>, forwarded: Option<&'static str>| {
+            move |name: &str| match name {
+                CLOUDFLARE_CONNECTING_IP_HEADER => cf,
+                FORWARDED_FOR_HEADER => forwarded,
+                _ => None,
+            }
+        };
+        let ip = |value: &str| value.parse::<IpAddr>().unwrap();
+        let proxies = vec![
+            TrustedProxy::try_from("10.0.0.0/8".to_owned()).unwrap(),
+            TrustedProxy::try_from("192.168.1.1".to_owned()).unwrap(),
+        ];
+        let proxy = Some(ip("10.0.0.1"));
+
+        assert_eq!(
+            client_ip(proxy, &proxies, headers(Some("1.1.1.1"), Some("2.2.2.2"))),
+            Some("1.1.1.1".to_owned())
+        );
+        // Rightmost hop which is not a trusted proxy is the client.
+        assert_eq!(
+            client_ip(
+                proxy,
+                &proxies,
+                headers(None, Some("2.2.2.2, 3.3.3.3, 192.168.1.1"))
+            ),
+            Some("3.3.3.3".to_owned())
+        );
+        assert_eq!(
+            client_ip(proxy, &proxies, headers(None, Some("10.0.0.2"))),
+            Some("10.0.0.2".to_owned())
+        );
+        assert_eq!(
+            client_ip(proxy, &proxies, headers(None, Some("invalid"))),
+            Some("10.0.0.1".to_owned())
+        );
+        assert_eq!(
+            client_ip(proxy, &proxies, headers(None, None)),
+            Some("10.0.0.1".to_owned())
+        );
+
+        // Headers sent by the other clients are ignored.
+        assert_eq!(
+            client_ip(
+                Some(ip("4.4.4.4")),
+                &proxies,
+                headers(Some("1.1.1.1"), Some("2.2.2.2"))
+            ),
+            Some("4.4.4.4".to_owned())
+        );
+        assert_eq!(
+            client_ip(
+                Some(ip("10.0.0.1")),
+                &[],
+                headers(Some("1.1.1.1"), Some("2.2.2.2"))
+            ),
+            Some("10.0.0.1".to_owned())
+        );
+
+        // Headers of the requests from the unknown peer are never read.
+        assert_eq!(
+            client_ip(None, &proxies, headers(Some("1.1.1.1"), Some("2.2.2.2"))),
+            None
+        );
+        assert_eq!(
+            client_ip(None, &[], headers(Some("1.1.1.1"), Some("2.2.2.2"))),
+            None
+        );
+    }
+
+    #[test]
+    fn api_key_quota() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+        let api_key = |id, rps| ApiKey {
+            id,
+            name: "partner".to_owned(),
+            rps,
+            allowed_methods: Vec::new(),
+        };
+
+        // Quota of the key is shared by all the endpoints.
+        for _ in 0..11 {
+            assert!(rate_limiter.check_api_key_at(&api_key(1, Some(10)), now));
+        }
+        assert!(!rate_limiter.check_api_key_at(&api_key(1, Some(10)), now));
+        // Keys have their own buckets regardless of the client IP.
+        assert!(rate_limiter.check_api_key_at(&api_key(2, Some(10)), now));
+        assert!(rate_limiter.check_at("1.1.1.1", "tx_submit", now));
+
+        // Keys without the quota are not rate limited.
+        for _ in 0..100 {
+            assert!(rate_limiter.check_api_key_at(&api_key(3, None), now));
+        }
+    }
+
+    #[test]
+    fn anonymous_clients_not_limited() {
+        let rate_limiter = RateLimiter(Arc::new(RateLimiterInner::new(None, 0, Vec::new())));
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert!(rate_limiter.check_at("1.1.1.1", "tx_submit", now));
+        }
+    }
+}
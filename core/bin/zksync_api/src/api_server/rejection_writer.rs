@@ -0,0 +1,67 @@
+//! Background writer of the transaction rejection reasons.
+//!
+//! The rejections are sent to a single writer through a bounded channel and stored in batches,
+//! so a flood of the rejected transactions takes at most one database connection. Once
+//! the channel is full, the new rejections are dropped: they're only kept for the lookups
+//! by the users, and the responses are never delayed by the database.
+
+// Built-in uses
+
+// External uses
+use futures::FutureExt;
+use tokio::sync::mpsc;
+
+// Workspace uses
+use zksync_storage::{tx_rejections::records::NewTxRejection, ConnectionPool};
+
+// Local uses
+
+/// Number of the rejected requests waiting to be stored.
+const CHANNEL_CAPACITY: usize = 1024;
+/// Maximum number of the rejections stored at once.
+const MAX_BATCH_SIZE: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct RejectionWriter(mpsc::Sender<Vec<NewTxRejection>>);
+
+impl RejectionWriter {
+    /// Creates the writer storing the rejections in the background.
+    pub fn spawn(connection_pool: ConnectionPool) -> Self {
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(run_writer(connection_pool, receiver));
+        Self(sender)
+    }
+
+    /// Queues the rejections of the request to be stored, drops them if the writer lags behind.
+    pub fn send(&self, rejections: Vec<NewTxRejection>) {
+        if self.0.try_send(rejections).is_err() {
+            metrics::increment_counter!("api.tx_rejections.dropped");
+        }
+    }
+}
+
+async fn run_writer(
+    connection_pool: ConnectionPool,
+    mut receiver: mpsc::Receiver<Vec<NewTxRejection>>,
+) {
+    while let Some(mut rejections) = receiver.recv().await {
+        // Rejections sent while the previous batch was being stored are taken at once.
+        while rejections.len() < MAX_BATCH_SIZE {
+            match receiver.recv().now_or_never() {
+                Some(Some(more)) => rejections.extend(more),
+                _ => break,
+            }
+        }
+        let result = async {
+            let mut storage = connection_pool.access_storage().await?;
+            storage
+                .tx_rejections_schema()
+                .store_rejections(&rejections)
+                .await
+        }
+        .await;
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the transaction rejection reasons: {}", err);
+        }
+    }
+}
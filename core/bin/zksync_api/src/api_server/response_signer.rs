@@ -0,0 +1,167 @@
+//! Signatures of the API responses, so the services consuming the account state through
+//! intermediaries (proxies, CDNs) can detect the tampering.
+//!
+//! The responses are signed with the Ethereum key of the operator. The signed message commits to
+//! the method, the signing time and the Keccak-256 hash of the response payload serialized to the
+//! compact JSON with the sorted keys, without the `signature` field itself. The signature is checked
+//! by recovering the signer address and comparing it with the ones published by the
+//! `get_response_signers` method.
+//!
+//! To rotate the key, the new key is set as `response_signing_key`, and the address of the previous
+//! one is added to the `retired_response_signers`. Retired signers are still published, so the
+//! responses signed before the rotation can be verified until the address is removed from the list.
+
+// Built-in uses
+
+// External uses
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_types::{tx::PackedEthSignature, Address, H256};
+
+// Local uses
+
+/// Signature of the API response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseSignature {
+    pub signer: Address,
+    /// UNIX timestamp of the signing, in seconds.
+    pub signed_at: i64,
+    pub signature: PackedEthSignature,
+}
+
+/// Addresses of the keys the API responses are signed with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseSigners {
+    /// Signer of the new responses, the responses are not signed if it's not set.
+    pub current: Option<Address>,
+    /// Signers of the responses before the key rotation.
+    pub retired: Vec<Address>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseSigner {
+    private_key: H256,
+    address: Address,
+    retired: Vec<Address>,
+}
+
+impl ResponseSigner {
+    /// Creates the signer if the signing key is set in the config.
+    pub fn from_config(config: &CommonApiConfig) -> Option<Self> {
+        let private_key = config.response_signing_key?;
+        let address = PackedEthSignature::address_from_private_key(&private_key)
+            .expect("invalid response signing key");
+        Some(Self {
+            private_key,
+            address,
+            retired: config.retired_response_signers.clone(),
+        })
+    }
+
+    pub fn signers(signer: Option<&Self>) -> ResponseSigners {
+        ResponseSigners {
+            current: signer.map(|signer| signer.address),
+            retired: signer
+                .map(|signer| signer.retired.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Signs the payload of the response of the method.
+    pub fn sign<T: Serialize>(
+        &self,
+        method: &str,
+        payload: &T,
+    ) -> Result<ResponseSignature, anyhow::Error> {
+        let signed_at = Utc::now().timestamp();
+        let message = signed_message(method, signed_at, payload)?;
+        let signature = PackedEthSignature::sign(&self.private_key, &message)?;
+        Ok(ResponseSignature {
+            signer: self.address,
+            signed_at,
+            signature,
+        })
+    }
+}
+
+/// Returns the message signed for the response payload, see the module docs for details.
+pub fn signed_message<T: Serialize>(
+    method: &str,
+    signed_at: i64,
+    payload: &T,
+) -> Result<Vec<u8>, serde_json::Error> {
+    let mut payload = serde_json::to_value(payload)?;
+    if let Some(fields) = payload.as_object_mut() {
+        fields.remove("signature");
+    }
+    // Keys of the JSON objects are sorted, since the map of `serde_json::Value` is ordered.
+    let payload_hash = tiny_keccak::keccak256(&serde_json::to_vec(&payload)?);
+    Ok(format!(
+        "zkSync API response\nMethod: {}\nSigned at: {}\nPayload: 0x{}",
+        method,
+        signed_at,
+        hex::encode(payload_hash)
+    )
+    .into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn response_signature() {
+        let private_key = H256::repeat_byte(0x11);
+        let signer = ResponseSigner {
+            private_key,
+            address: PackedEthSignature::address_from_private_key(&private_key).unwrap(),
+            retired: vec![Address::repeat_byte(0x22)],
+        };
+        let payload = json!({ "id": 1, "committed": { "nonce": 2 }, "address": "0x01" });
+        let signature = signer.sign("account_info", &payload).unwrap();
+
+        // The field order and the signature itself don't affect the signed message.
+        let mut received = json!({ "address": "0x01", "committed": { "nonce": 2 }, "id": 1 });
+        received["signature"] = serde_json::to_value(&signature).unwrap();
+        let message = signed_message("account_info", signature.signed_at, &received).unwrap();
+        assert_eq!(
+            signature
+                .signature
+                .signature_recover_signer(&message)
+                .unwrap(),
+            signer.address
+        );
+
+        // Tampered payload is signed by someone else.
+        received["id"] = json!(2);
+        let message = signed_message("account_info", signature.signed_at, &received).unwrap();
+        assert_ne!(
+            signature
+                .signature
+                .signature_recover_signer(&message)
+                .unwrap(),
+            signer.address
+        );
+
+        assert_eq!(
+            ResponseSigner::signers(Some(&signer)),
+            ResponseSigners {
+                current: Some(signer.address),
+                retired: vec![Address::repeat_byte(0x22)],
+            }
+        );
+        assert_eq!(
+            ResponseSigner::signers(None),
+            ResponseSigners {
+                current: None,
+                retired: Vec::new(),
+            }
+        );
+    }
+}
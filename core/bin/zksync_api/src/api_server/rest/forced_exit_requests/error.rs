@@ -7,6 +7,7 @@ use reqwest::{header::CONTENT_TYPE, StatusCode};
 
 // Workspace uses
 use zksync_api_client::rest::error::ErrorBody;
+use zksync_types::tx::error::TxAddError;
 // Local uses
 use crate::api_server::tx_sender::SubmitError;
 
@@ -118,10 +119,12 @@ impl From<SubmitError> for ApiError {
     fn from(inner: SubmitError) -> Self {
         let internal_code = SumbitErrorCode::from_err(&inner).as_code();
 
-        if let SubmitError::Internal(err) = &inner {
-            ApiError::internal(err)
-        } else {
-            ApiError::bad_request(inner)
+        match &inner {
+            SubmitError::Internal(err) => ApiError::internal(err),
+            SubmitError::TxAdd(err @ TxAddError::ServiceOverloaded) => {
+                ApiError::with_code(err.to_http_status(), inner)
+            }
+            _ => ApiError::bad_request(inner),
         }
         .code(internal_code)
     }
@@ -1,6 +1,9 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
-use futures::channel::mpsc;
+use actix_web::{dev::Service, http::StatusCode, web, App, HttpResponse, HttpServer};
+use futures::{
+    channel::mpsc,
+    future::{self, Either},
+};
 use std::net::SocketAddr;
 use zksync_storage::ConnectionPool;
 use zksync_types::{SequentialTxId, H160};
@@ -12,6 +15,8 @@ use crate::signature_checker::VerifySignatureRequest;
 
 use super::tx_sender::TxSender;
 
+use crate::api_server::access_control::{AccessControl, RequestMeta};
+use crate::api_server::ens::EnsResolver;
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
 use tokio::task::JoinHandle;
@@ -31,8 +36,16 @@ async fn start_server(
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 ) {
+    // Access control is shared by the workers, so the client is limited regardless of the worker
+    // its request is handled by.
+    let access_control =
+        AccessControl::new(&api_v01.config.api.common, api_v01.connection_pool.clone());
+    // Resolver is shared by the workers, so the name is resolved once for all of them.
+    let ens = EnsResolver::from_config(&api_v01.config);
     HttpServer::new(move || {
+        let access_control = access_control.clone();
         let api_v01 = api_v01.clone();
+        let trusted_proxies = api_v01.config.api.common.trusted_proxies.clone();
         // This api stores forced exit requests, it's necessary to use main database connection
         let forced_exit_requests_api_scope = forced_exit_requests::api_scope(
             api_v01.main_database_connection_pool.clone(),
@@ -54,9 +67,36 @@ async fn start_server(
                 &api_v01.config.api.token_config,
                 mempool_tx_sender.clone(),
             );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
+            v02::api_scope(
+                tx_sender,
+                api_v01.main_database_connection_pool.clone(),
+                &api_v01.config,
+                api_v01.network_status.clone(),
+                ens.clone(),
+            )
         };
         App::new()
+            .wrap_fn(move |request, service| {
+                let meta = RequestMeta::from_headers(
+                    request.peer_addr().map(|addr| addr.ip()),
+                    &trusted_proxies,
+                    |name| {
+                        request
+                            .headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                    },
+                );
+                match access_control.check(&meta, request.path(), "rest") {
+                    Ok(()) => Either::Right(service.call(request)),
+                    Err(err) => {
+                        let status = StatusCode::from_u16(err.http_status())
+                            .expect("Access error has a valid HTTP status");
+                        let response = HttpResponse::build(status).body(err.to_string());
+                        Either::Left(future::ok(request.into_response(response)))
+                    }
+                }
+            })
             .wrap(
                 Cors::default()
                     .send_wildcard()
@@ -1,33 +1,50 @@
 //! Account part of API implementation.
 
 // Built-in uses
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::time::Instant;
 
 // External uses
 use actix_web::{web, Scope};
+use futures::channel::mpsc;
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountAddressOrId, AccountNfts, AccountState, BalanceHistoryItem, EnsResolution,
+        IncomingAccountTxsQuery, PendingDeposit,
+    },
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountBalanceHistoryRequest, AccountTxsFilters, AccountTxsRequest, ApiEither,
+        Paginated, PaginationQuery, PendingOpsRequest,
     },
-    transaction::{Transaction, TxHashSerializeWrapper},
+    transaction::{ApiTxRejection, Transaction, TxHashSerializeWrapper},
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
+use zksync_mempool::{AccountNonces, MempoolTransactionRequest};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenLike};
+use zksync_types::{
+    event::transaction::TransactionType, tx::TxHash, AccountId, Address, BlockNumber, SerialId,
+    TokenLike, ZkSyncPriorityOp,
+};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
     paginate_trait::Paginate,
     response::ApiResult,
+    TX_REJECTIONS_LIMIT,
+};
+use crate::{
+    api_server::{
+        ens::{is_ens_name, EnsError, EnsResolver},
+        helpers::{get_account_nonces, get_depositing},
+    },
+    api_try,
+    fee_ticker::PriceError,
 };
-use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
 
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
@@ -35,14 +52,24 @@ struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    ens: Option<EnsResolver>,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+        ens: Option<EnsResolver>,
+        mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    ) -> Self {
         Self {
             pool,
             tokens,
             confirmations_for_eth_event,
+            ens,
+            mempool_tx_sender,
         }
     }
 
@@ -84,10 +111,40 @@ impl ApiAccountData {
         }
     }
 
-    fn parse_account_id_or_address(
+    async fn parse_account_id_or_address(
         &self,
         account_address_or_id: &str,
     ) -> Result<AccountAddressOrId, Error> {
+        self.resolve_account_id_or_address(account_address_or_id)
+            .await
+            .map(|(address_or_id, _)| address_or_id)
+    }
+
+    /// Parses the account ID or address, the ENS name is resolved to the address and
+    /// the resolution is returned along with it.
+    async fn resolve_account_id_or_address(
+        &self,
+        account_address_or_id: &str,
+    ) -> Result<(AccountAddressOrId, Option<EnsResolution>), Error> {
+        if !is_ens_name(account_address_or_id) {
+            return Self::parse_id_or_address(account_address_or_id)
+                .map(|address_or_id| (address_or_id, None));
+        }
+        let resolver = self
+            .ens
+            .as_ref()
+            .ok_or_else(|| Error::from(EnsError::NotSupported))?;
+        let resolution = resolver
+            .resolve(account_address_or_id)
+            .await
+            .map_err(Error::from)?;
+        Ok((
+            AccountAddressOrId::Address(resolution.address),
+            Some(resolution),
+        ))
+    }
+
+    fn parse_id_or_address(account_address_or_id: &str) -> Result<AccountAddressOrId, Error> {
         if let Ok(account_id) = u32::from_str(account_address_or_id) {
             Ok(AccountAddressOrId::Id(AccountId(account_id)))
         } else {
@@ -287,6 +344,7 @@ impl ApiAccountData {
             depositing,
             committed,
             finalized,
+            ens_resolution: None,
         })
     }
 
@@ -296,6 +354,7 @@ impl ApiAccountData {
         address: Address,
         token_like: Option<TokenLike>,
         second_address: Option<Address>,
+        filters: AccountTxsFilters,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let token = if let Some(token_like) = token_like {
@@ -316,13 +375,46 @@ impl ApiAccountData {
                 address,
                 token,
                 second_address,
+                filters,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        storage.paginate_checked(&new_query).await
+    }
+
+    async fn account_balance_history(
+        &self,
+        query: PaginationQuery<ApiEither<TxHash>>,
+        address: Address,
+        account_id: Option<AccountId>,
+    ) -> Result<Paginated<BalanceHistoryItem, TxHashSerializeWrapper>, Error> {
+        let new_query = PaginationQuery {
+            from: AccountBalanceHistoryRequest {
+                address,
+                account_id,
+                tx_hash: query.from,
             },
             limit: query.limit,
             direction: query.direction,
         };
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         storage.paginate_checked(&new_query).await
     }
 
+    /// Parses the comma-separated list of the transaction types, e.g. `Transfer,Withdraw`.
+    fn parse_tx_types(&self, tx_types: &str) -> Result<Vec<TransactionType>, Error> {
+        tx_types
+            .split(',')
+            .map(str::trim)
+            .filter(|tx_type| !tx_type.is_empty())
+            .map(|tx_type| {
+                serde_json::from_value(serde_json::Value::String(tx_type.to_string()))
+                    .map_err(|_| Error::from(InvalidDataError::InvalidTxType(tx_type.to_string())))
+            })
+            .collect()
+    }
+
     /// Pending deposits can be matched only with addresses,
     /// while pending full exits can be matched only with account ids.
     /// If the account isn't created yet it doesn't have an id
@@ -345,6 +437,63 @@ impl ApiAccountData {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         storage.paginate_checked(&new_query).await
     }
+
+    async fn account_pending_deposits(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PendingDeposit>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let ops = storage
+            .chain()
+            .mempool_schema()
+            .get_pending_deposits_for_l1_address(address)
+            .await
+            .map_err(Error::storage)?;
+        let last_eth_block = storage
+            .ethereum_schema()
+            .load_last_eth_block()
+            .await
+            .map_err(Error::storage)?;
+
+        let mut account_ids = HashMap::new();
+        let mut deposits = Vec::with_capacity(ops.len());
+        for op in ops {
+            let to = match &op.data {
+                ZkSyncPriorityOp::Deposit(deposit) => deposit.to,
+                ZkSyncPriorityOp::FullExit(_) => continue,
+            };
+            let account_id = match account_ids.get(&to) {
+                Some(account_id) => *account_id,
+                None => {
+                    let account_id = storage
+                        .chain()
+                        .account_schema()
+                        .account_id_by_address(to)
+                        .await
+                        .map_err(Error::storage)?;
+                    account_ids.insert(to, account_id);
+                    account_id
+                }
+            };
+            deposits.extend(PendingDeposit::new(
+                op,
+                account_id,
+                self.confirmations_for_eth_event,
+                last_eth_block,
+            ));
+        }
+        Ok(deposits)
+    }
+
+    async fn account_rejected_txs(&self, address: Address) -> Result<Vec<ApiTxRejection>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let rejections = storage
+            .tx_rejections_schema()
+            .load_by_account(address, TX_REJECTIONS_LIMIT)
+            .await
+            .map_err(Error::storage)?;
+        Ok(rejections.into_iter().map(ApiTxRejection::from).collect())
+    }
 }
 
 async fn account_committed_info(
@@ -352,7 +501,10 @@ async fn account_committed_info(
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
         data.account_committed_info(account_id).await.into()
@@ -368,7 +520,10 @@ async fn account_finalized_info(
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
         data.account_finalized_info(account_id).await.into()
@@ -379,18 +534,72 @@ async fn account_finalized_info(
     res
 }
 
+async fn account_committed_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountNfts>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_committed_info(account_id)
+            .await
+            .map(|account| account.map(AccountNfts::from))
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_committed_nfts");
+    res
+}
+
+async fn account_finalized_nfts(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountNfts>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_finalized_info(account_id)
+            .await
+            .map(|account| account.map(AccountNfts::from))
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_nfts");
+    res
+}
+
 async fn account_full_info(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<AccountState> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let (address_or_id, ens_resolution) = api_try!(
+        data.resolve_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
     );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
-    let res = data.account_full_info(address, account_id).await.into();
+    let res = data
+        .account_full_info(address, account_id)
+        .await
+        .map(|state| AccountState {
+            ens_resolution,
+            ..state
+        })
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_full_info");
     res
 }
@@ -408,11 +617,14 @@ async fn account_txs(
     })
     .map_err(Error::from));
 
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
 
     let second_address = if let Some(second_account) = query.second_account {
-        let address_or_id = api_try!(data.parse_account_id_or_address(&second_account));
+        let address_or_id = api_try!(data.parse_account_id_or_address(&second_account).await);
         Some(api_try!(
             data.get_address_by_address_or_id(address_or_id).await
         ))
@@ -422,14 +634,49 @@ async fn account_txs(
 
     let token_like = query.token.map(|token| TokenLike::parse(&token));
 
+    let tx_types = if let Some(tx_types) = query.tx_types {
+        api_try!(data.parse_tx_types(&tx_types))
+    } else {
+        Vec::new()
+    };
+    let filters = AccountTxsFilters {
+        tx_types,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
+
     let res = data
-        .account_txs(pagination, address, token_like, second_address)
+        .account_txs(pagination, address, token_like, second_address, filters)
         .await
         .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
     res
 }
 
+async fn account_balance_history(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<BalanceHistoryItem, TxHashSerializeWrapper>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query(query).map_err(Error::from));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let address = api_try!(
+        data.get_address_by_address_or_id(address_or_id.clone())
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = data
+        .account_balance_history(query, address, account_id)
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_balance_history");
+    res
+}
+
 async fn account_pending_txs(
     data: web::Data<ApiAccountData>,
     account_id_or_address: web::Path<String>,
@@ -437,7 +684,10 @@ async fn account_pending_txs(
 ) -> ApiResult<Paginated<Transaction, SerialId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
@@ -451,12 +701,68 @@ async fn account_pending_txs(
     res
 }
 
+async fn account_pending_deposits(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<PendingDeposit>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_pending_deposits(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_pending_deposits");
+    res
+}
+
+async fn account_nonces(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<AccountNonces> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = get_account_nonces(&data.mempool_tx_sender, account_id)
+        .await
+        .map_err(Error::from)
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nonces");
+    res
+}
+
+async fn account_rejected_txs(
+    data: web::Data<ApiAccountData>,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<ApiTxRejection>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.parse_account_id_or_address(&account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_rejected_txs(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_rejected_txs");
+    res
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    ens: Option<EnsResolver>,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 ) -> Scope {
-    let data = ApiAccountData::new(pool, tokens, confirmations_for_eth_event);
+    let data = ApiAccountData::new(
+        pool,
+        tokens,
+        confirmations_for_eth_event,
+        ens,
+        mempool_tx_sender,
+    );
 
     web::scope("accounts")
         .app_data(web::Data::new(data))
@@ -468,6 +774,14 @@ pub fn api_scope(
             "{account_id_or_address}/finalized",
             web::get().to(account_finalized_info),
         )
+        .route(
+            "{account_id_or_address}/committed/nfts",
+            web::get().to(account_committed_nfts),
+        )
+        .route(
+            "{account_id_or_address}/finalized/nfts",
+            web::get().to(account_finalized_nfts),
+        )
         .route("{account_id_or_address}", web::get().to(account_full_info))
         .route(
             "{account_id_or_address}/transactions",
@@ -477,6 +791,22 @@ pub fn api_scope(
             "{account_id_or_address}/transactions/pending",
             web::get().to(account_pending_txs),
         )
+        .route(
+            "{account_id_or_address}/transactions/rejected",
+            web::get().to(account_rejected_txs),
+        )
+        .route(
+            "{account_id_or_address}/deposits/pending",
+            web::get().to(account_pending_deposits),
+        )
+        .route(
+            "{account_id_or_address}/nonces",
+            web::get().to(account_nonces),
+        )
+        .route(
+            "{account_id_or_address}/balance_history",
+            web::get().to(account_balance_history),
+        )
 }
 
 #[cfg(test)]
@@ -486,8 +816,11 @@ mod tests {
         test_utils::{deserialize_response_result, TestServerConfig},
         SharedData,
     };
+    use ethabi::Token;
+    use futures::StreamExt;
     use num::BigUint;
     use serde::Deserialize;
+    use std::time::Duration;
     use zksync_api_client::rest::client::Client;
     use zksync_api_types::v02::{
         account::{DepositingAccountBalances, DepositingFunds},
@@ -495,8 +828,32 @@ mod tests {
         transaction::{L1Transaction, TransactionData},
         ApiVersion,
     };
-    use zksync_storage::StorageProcessor;
-    use zksync_types::{AccountId, Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+    use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+    use zksync_storage::{tx_rejections::records::NewTxRejection, StorageProcessor};
+    use zksync_types::{
+        AccountId, Address, Deposit, Nonce, PriorityOp, TokenId, ZkSyncPriorityOp, H256,
+    };
+
+    fn account_nonces_loopback() -> mpsc::Sender<MempoolTransactionRequest> {
+        let (mempool_tx_request_sender, mut mempool_tx_request_receiver) = mpsc::channel(100);
+
+        tokio::spawn(async move {
+            while let Some(request) = mempool_tx_request_receiver.next().await {
+                match request {
+                    MempoolTransactionRequest::GetAccountNonces(_, resp) => resp
+                        .send(Ok(AccountNonces {
+                            committed_nonce: Nonce(1),
+                            next_nonce: Nonce(3),
+                            pending_nonces: vec![Nonce(1), Nonce(2)],
+                        }))
+                        .unwrap_or_default(),
+                    _ => unreachable!(),
+                }
+            }
+        });
+
+        mempool_tx_request_sender
+    }
 
     // While the values of the PendingOpsFlattenRequest's fields are never directly
     // used in the tests, we still need them to specify the JSON format of the `unconfirmed_ops` endpoint input in tests.
@@ -513,6 +870,7 @@ mod tests {
     struct TestServer {
         api_server: actix_test::TestServer,
         pool: ConnectionPool,
+        eth: MockEthereum,
         confirmations_for_eth_event: u64,
     }
 
@@ -522,6 +880,8 @@ mod tests {
             cfg.fill_database().await?;
 
             let pool = cfg.pool.clone();
+            let eth = MockEthereum::default();
+            let ens_eth = eth.clone();
 
             let shared_data = SharedData {
                 net: cfg.config.chain.eth.network,
@@ -535,6 +895,13 @@ mod tests {
                             cfg.config.api.token_config.invalidate_token_cache_period(),
                         ),
                         cfg.config.eth_watch.confirmations_for_eth_event,
+                        Some(EnsResolver::new(
+                            EthereumGateway::Mock(ens_eth.clone()),
+                            Address::repeat_byte(1),
+                            10,
+                            Duration::from_secs(60),
+                        )),
+                        account_nonces_loopback(),
                     )
                 },
                 Some(shared_data),
@@ -545,6 +912,7 @@ mod tests {
                 Self {
                     api_server,
                     pool,
+                    eth,
                     confirmations_for_eth_event: cfg.config.eth_watch.confirmations_for_eth_event,
                 },
             ))
@@ -611,6 +979,24 @@ mod tests {
             .await?;
         let account_finalized_info: Option<Account> = deserialize_response_result(response)?;
 
+        let response = client
+            .account_nfts(&format!("{:?}", address), "committed")
+            .await?;
+        let account_committed_nfts: Option<AccountNfts> = deserialize_response_result(response)?;
+        assert_eq!(
+            account_committed_nfts,
+            Some(account_committed_info_by_id.clone().into())
+        );
+
+        let response = client
+            .account_nfts(&account_id.to_string(), "finalized")
+            .await?;
+        let account_finalized_nfts: Option<AccountNfts> = deserialize_response_result(response)?;
+        assert_eq!(
+            account_finalized_nfts,
+            account_finalized_info.clone().map(AccountNfts::from)
+        );
+
         {
             let mut storage = server.pool.access_storage().await?;
             storage
@@ -681,6 +1067,32 @@ mod tests {
         );
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
+        assert_eq!(account_full_info.ens_resolution, None);
+
+        // Account is requested by the ENS name resolved to its address.
+        let mut eth = server.eth.clone();
+        eth.add_call_result("resolver", vec![Token::Address(Address::repeat_byte(2))])
+            .await;
+        eth.add_call_result("addr", vec![Token::Address(address)])
+            .await;
+        let response = client.account_full_info("Name.eth").await?;
+        let account_full_info_by_name: AccountState = deserialize_response_result(response)?;
+        let ens_resolution = account_full_info_by_name.ens_resolution.clone().unwrap();
+        assert_eq!(ens_resolution.name, "name.eth");
+        assert_eq!(ens_resolution.address, address);
+        assert_eq!(
+            AccountState {
+                ens_resolution: None,
+                ..account_full_info_by_name
+            },
+            account_full_info
+        );
+        let response = client.account_info("name.com", "committed").await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(
+            error,
+            Error::from(InvalidDataError::InvalidAccountIdOrAddress)
+        );
 
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
@@ -691,6 +1103,22 @@ mod tests {
         let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
         assert_eq!(txs.list[0].tx_hash, tx_hash);
 
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 10,
+            direction: PaginationDirection::Older,
+        };
+        let response = client
+            .account_balance_history(&query, &account_id.to_string())
+            .await?;
+        let history: Paginated<BalanceHistoryItem, TxHash> = deserialize_response_result(response)?;
+        assert!(!history.list.is_empty());
+        assert!(history.list.iter().all(|item| !item.changes.is_empty()));
+        assert!(history
+            .list
+            .windows(2)
+            .all(|items| items[0].block_number >= items[1].block_number));
+
         let query = PaginationQuery {
             from: ApiEither::from_str("latest").unwrap(),
             limit: 2,
@@ -711,6 +1139,52 @@ mod tests {
             _ => panic!("account_pending_txs returned L2 tx"),
         }
 
+        let response = client
+            .account_pending_deposits(&format!("{:?}", address))
+            .await?;
+        let deposits: Vec<PendingDeposit> = deserialize_response_result(response)?;
+        let deposits: Vec<_> = deposits
+            .into_iter()
+            .filter(|deposit| deposit.serial_id >= 10)
+            .collect();
+        let serial_ids: Vec<_> = deposits.iter().map(|deposit| deposit.serial_id).collect();
+        assert_eq!(serial_ids, vec![10, 11, 12]);
+        for deposit in deposits {
+            assert_eq!(deposit.to, address);
+            assert_eq!(deposit.account_id, Some(account_id));
+            assert_eq!(deposit.amount, BigUint::from(100500u64));
+        }
+
+        {
+            let mut storage = server.pool.access_storage().await?;
+            storage
+                .tx_rejections_schema()
+                .store_rejections(&[NewTxRejection {
+                    tx_hash,
+                    account: address,
+                    error_code: "INCORRECT_ETH_SIGNATURE".to_string(),
+                    error_message: "Eth signature is incorrect".to_string(),
+                    recovered_signer: None,
+                }])
+                .await?;
+        }
+        let response = client.account_nonces(&account_id.to_string()).await?;
+        let nonces: AccountNonces = deserialize_response_result(response)?;
+        assert_eq!(nonces.next_nonce, Nonce(3));
+
+        // The account which doesn't exist yet has no pending transactions.
+        let response = client
+            .account_nonces(&format!("{:?}", Address::repeat_byte(0xfe)))
+            .await?;
+        let nonces: AccountNonces = deserialize_response_result(response)?;
+        assert_eq!(nonces.next_nonce, Nonce(0));
+        assert!(nonces.pending_nonces.is_empty());
+
+        let response = client.account_rejected_txs(&account_id.to_string()).await?;
+        let rejections: Vec<ApiTxRejection> = deserialize_response_result(response)?;
+        assert_eq!(rejections[0].tx_hash, tx_hash);
+        assert_eq!(rejections[0].error_code, "INCORRECT_ETH_SIGNATURE");
+
         server.stop().await;
         Ok(())
     }
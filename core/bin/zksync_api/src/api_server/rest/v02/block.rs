@@ -9,8 +9,11 @@ use actix_web::{web, Scope};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    block::{BlockInfo, BlockStatus},
-    pagination::{parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
+    block::{BlockInfo, BlockStateDiff, BlockStatus, IncomingStateDiffQuery},
+    pagination::{
+        parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationDirection, PaginationQuery,
+        MAX_LIMIT,
+    },
     transaction::{Transaction, TxData, TxHashSerializeWrapper},
 };
 use zksync_crypto::{convert::FeConvert, Fr};
@@ -53,13 +56,41 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
 struct ApiBlockData {
     pool: ConnectionPool,
     verified_blocks_cache: BlockDetailsCache,
+    /// Whether the history of the old blocks may be pruned from the database.
+    light_node: bool,
 }
 
 impl ApiBlockData {
-    fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        verified_blocks_cache: BlockDetailsCache,
+        light_node: bool,
+    ) -> Self {
         Self {
             pool,
             verified_blocks_cache,
+            light_node,
+        }
+    }
+
+    /// Rejects the requests for the transactions of the blocks whose history is pruned,
+    /// which would otherwise look like the blocks without transactions.
+    async fn ensure_history_available(&self, block_number: BlockNumber) -> Result<(), Error> {
+        if !self.light_node {
+            return Ok(());
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_pruned_block = storage
+            .pruning_schema()
+            .load_last_pruned_block()
+            .await
+            .map_err(Error::storage)?;
+        match last_pruned_block {
+            Some(last_pruned_block) if block_number <= last_pruned_block => Err(Error::from(
+                InvalidDataError::HistoryPruned(last_pruned_block),
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -113,6 +144,7 @@ impl ApiBlockData {
         block_number: BlockNumber,
         query: PaginationQuery<ApiEither<TxHash>>,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        self.ensure_history_available(block_number).await?;
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
 
         let new_query = PaginationQuery {
@@ -132,6 +164,7 @@ impl ApiBlockData {
         block_number: BlockNumber,
         block_index: u64,
     ) -> Result<Option<TxData>, Error> {
+        self.ensure_history_available(block_number).await?;
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         Ok(storage
             .chain()
@@ -141,6 +174,58 @@ impl ApiBlockData {
             .map_err(Error::storage)?)
     }
 
+    /// Returns the updates of the account states made in the committed blocks of the range,
+    /// paginated by the blocks. The next page starts with the block `fromBlock + limit`.
+    async fn state_diff_page(
+        &self,
+        query: IncomingStateDiffQuery,
+    ) -> Result<Paginated<BlockStateDiff, BlockNumber>, Error> {
+        if query.limit > MAX_LIMIT {
+            return Err(Error::from(InvalidDataError::PaginationLimitTooBig));
+        }
+        if query.from_block > query.to_block {
+            return Err(Error::from(InvalidDataError::InvalidBlockRange));
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        let to_block = std::cmp::min(query.to_block, last_committed_block);
+        // Number of the committed blocks in the requested range.
+        let count = (*to_block + 1).saturating_sub(*query.from_block);
+
+        let list = if query.limit == 0 || query.from_block > to_block {
+            Vec::new()
+        } else {
+            let page_end = (*query.from_block).saturating_add(query.limit - 1);
+            let page_end = std::cmp::min(BlockNumber(page_end), to_block);
+            storage
+                .chain()
+                .state_schema()
+                .load_blocks_state_diff(query.from_block, page_end)
+                .await
+                .map_err(Error::storage)?
+                .into_iter()
+                .map(|(block_number, updates)| BlockStateDiff {
+                    block_number,
+                    updates: updates.into_iter().map(Into::into).collect(),
+                })
+                .collect()
+        };
+
+        Ok(Paginated::new(
+            list,
+            query.from_block,
+            query.limit,
+            PaginationDirection::Newer,
+            count,
+        ))
+    }
+
     async fn get_last_committed_block_number(&self) -> QueryResult<BlockNumber> {
         let mut storage = self.pool.access_storage().await?;
         storage
@@ -199,6 +284,16 @@ async fn block_transactions(
     res
 }
 
+async fn block_state_diff(
+    data: web::Data<ApiBlockData>,
+    web::Query(query): web::Query<IncomingStateDiffQuery>,
+) -> ApiResult<Paginated<BlockStateDiff, BlockNumber>> {
+    let start = Instant::now();
+    let res = data.state_diff_page(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_state_diff");
+    res
+}
+
 async fn transaction_in_block(
     data: web::Data<ApiBlockData>,
     path: web::Path<(BlockNumber, u64)>,
@@ -210,12 +305,13 @@ async fn transaction_in_block(
     ApiResult::Ok(res)
 }
 
-pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
-    let data = ApiBlockData::new(pool, cache);
+pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache, light_node: bool) -> Scope {
+    let data = ApiBlockData::new(pool, cache, light_node);
 
     web::scope("blocks")
         .app_data(web::Data::new(data))
         .route("", web::get().to(block_pagination))
+        .route("state_diff", web::get().to(block_state_diff))
         .route("{block_position}", web::get().to(block_by_position))
         .route(
             "{block_position}/transactions",
@@ -252,7 +348,7 @@ mod tests {
             api_version: ApiVersion::V02,
         };
         let (client, server) = cfg.start_server(
-            |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10)),
+            |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10), false),
             Some(shared_data),
         );
 
@@ -319,6 +415,31 @@ mod tests {
             }
         }
 
+        let query = IncomingStateDiffQuery {
+            from_block: BlockNumber(2),
+            to_block: BlockNumber(3),
+            limit: 1,
+        };
+        let expected_updates = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .state_schema()
+                .load_state_diff_for_block(BlockNumber(2))
+                .await?
+        };
+        let response = client.block_state_diff(&query).await?;
+        let paginated: Paginated<BlockStateDiff, BlockNumber> =
+            deserialize_response_result(response)?;
+        assert_eq!(paginated.pagination.count, 2);
+        assert_eq!(
+            paginated.list,
+            vec![BlockStateDiff {
+                block_number: BlockNumber(2),
+                updates: expected_updates.into_iter().map(Into::into).collect(),
+            }]
+        );
+
         for expected_tx in expected_txs {
             if !expected_tx.success {
                 continue;
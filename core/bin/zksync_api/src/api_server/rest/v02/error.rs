@@ -7,11 +7,19 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use thiserror::Error;
 
 // Workspace uses
-use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
+use zksync_api_types::v02::{
+    pagination::{UnknownFromParameter, MAX_LIMIT},
+    stats::MAX_STATS_DAYS,
+    transaction::MAX_TX_RECEIPTS,
+};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{tx::error::TxAddError, BlockNumber};
 
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
+use crate::{
+    api_server::{ens::EnsError, tx_sender::SubmitError},
+    fee_ticker::PriceError,
+};
 
 #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
 #[repr(u16)]
@@ -27,6 +35,11 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    InvalidTxType = 209,
+    HistoryPruned = 210,
+    TooManyTxHashes = 211,
+    StatsPeriodTooLong = 212,
+    InvalidBlockRange = 213,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
@@ -39,9 +52,26 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    InvalidApiKey = 700,
+    InvalidWebhookUrl = 701,
+    WebhookNotFound = 702,
+    TooManyWebhooks = 703,
+    EmptyWebhookEventTypes = 704,
+    EnsNotSupported = 800,
+    InvalidEnsName = 801,
+    EnsNameNotFound = 802,
+    EnsUnavailable = 803,
+    FastWithdrawalsDisabled = 900,
+    NoLiquidityProvider = 901,
+    FastWithdrawalNotFound = 902,
+    InvalidFastWithdrawalAmount = 903,
     Other = 60_000,
 }
 
+/// Number of seconds the clients are asked to wait before retrying the requests rejected
+/// because of the overload.
+const OVERLOAD_RETRY_AFTER_SECS: u64 = 1;
+
 /// Error object in a response
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +79,10 @@ pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// If set, the request may be retried after this number of seconds. Such errors are sent
+    /// with the 429 status and the `Retry-After` header instead of being a part of the body.
+    #[serde(skip)]
+    pub retry_after: Option<u64>,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -60,6 +94,11 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    /// Number of seconds after which the request may be retried, see `Error::retry_after`.
+    fn retry_after(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl<T> From<T> for Error
@@ -71,6 +110,7 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            retry_after: t.retry_after(),
         }
     }
 }
@@ -103,6 +143,19 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error("Cannot parse transaction type {0}")]
+    InvalidTxType(String),
+    #[error("History of the blocks up to {0} is pruned on this node")]
+    HistoryPruned(BlockNumber),
+    #[error(
+        "No more than {} transactions can be requested at once",
+        MAX_TX_RECEIPTS
+    )]
+    TooManyTxHashes,
+    #[error("Statistics can be requested for no more than {} days", MAX_STATS_DAYS)]
+    StatsPeriodTooLong,
+    #[error("Start of the block range should not be greater than its end")]
+    InvalidBlockRange,
 }
 
 impl ApiError for InvalidDataError {
@@ -120,6 +173,83 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::InvalidTxType(_) => ErrorCode::InvalidTxType,
+            Self::HistoryPruned(_) => ErrorCode::HistoryPruned,
+            Self::TooManyTxHashes => ErrorCode::TooManyTxHashes,
+            Self::StatsPeriodTooLong => ErrorCode::StatsPeriodTooLong,
+            Self::InvalidBlockRange => ErrorCode::InvalidBlockRange,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("Valid API key is required to manage the webhooks")]
+    InvalidApiKey,
+    #[error("Invalid webhook URL: {0}")]
+    InvalidUrl(String),
+    #[error("Webhook is not found")]
+    NotFound,
+    #[error("No more than {0} webhooks can be registered with the API key")]
+    TooManyWebhooks(u32),
+    #[error("Webhook has to be subscribed to at least one event type")]
+    EmptyEventTypes,
+}
+
+impl ApiError for WebhookError {
+    fn error_type(&self) -> String {
+        String::from("webhookError")
+    }
+
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidApiKey => ErrorCode::InvalidApiKey,
+            Self::InvalidUrl(_) => ErrorCode::InvalidWebhookUrl,
+            Self::NotFound => ErrorCode::WebhookNotFound,
+            Self::TooManyWebhooks(_) => ErrorCode::TooManyWebhooks,
+            Self::EmptyEventTypes => ErrorCode::EmptyWebhookEventTypes,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FastWithdrawalError {
+    #[error("Fast withdrawals are disabled on this node")]
+    Disabled,
+    #[error("There is no liquidity provider fronting the withdrawal")]
+    NoProvider,
+    #[error("Fast withdrawal is not found")]
+    NotFound,
+    #[error("Withdrawn amount should be positive")]
+    InvalidAmount,
+}
+
+impl ApiError for FastWithdrawalError {
+    fn error_type(&self) -> String {
+        String::from("fastWithdrawalError")
+    }
+
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::Disabled => ErrorCode::FastWithdrawalsDisabled,
+            Self::NoProvider => ErrorCode::NoLiquidityProvider,
+            Self::NotFound => ErrorCode::FastWithdrawalNotFound,
+            Self::InvalidAmount => ErrorCode::InvalidFastWithdrawalAmount,
+        }
+    }
+}
+
+impl ApiError for EnsError {
+    fn error_type(&self) -> String {
+        String::from("ensError")
+    }
+
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotSupported => ErrorCode::EnsNotSupported,
+            Self::InvalidName(_) => ErrorCode::InvalidEnsName,
+            Self::NotFound(_) => ErrorCode::EnsNameNotFound,
+            Self::Unavailable(_) => ErrorCode::EnsUnavailable,
         }
     }
 }
@@ -204,6 +334,15 @@ impl ApiError for SubmitError {
             Self::PriceError(_) => ErrorCode::InternalError,
         }
     }
+
+    fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::TxAdd(err) if matches!(err.reason(), TxAddError::ServiceOverloaded) => {
+                Some(OVERLOAD_RETRY_AFTER_SECS)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl ApiError for PriceError {
@@ -229,3 +368,18 @@ impl ApiError for UnknownFromParameter {
         ErrorCode::QueryDeserializationError
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overloaded_submission_is_retried() {
+        let error = Error::from(SubmitError::TxAdd(TxAddError::ServiceOverloaded));
+        assert_eq!(error.code, ErrorCode::TxAddError);
+        assert_eq!(error.retry_after, Some(OVERLOAD_RETRY_AFTER_SECS));
+
+        let error = Error::from(SubmitError::TxAdd(TxAddError::NonceMismatch));
+        assert_eq!(error.retry_after, None);
+    }
+}
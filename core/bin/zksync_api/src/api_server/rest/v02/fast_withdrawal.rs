@@ -0,0 +1,234 @@
+//! Fast withdrawals part of API implementation.
+//!
+//! The withdrawal is matched with the liquidity provider of the token with the lowest fee,
+//! and then the user submits the ordinary withdrawal to the address of the provider before
+//! the match expires. Once the withdrawal is submitted, the tx sender hands it off to the
+//! provider, which fronts the funds to the recipient on Ethereum.
+
+// Built-in uses
+use std::{cmp::Reverse, time::Instant};
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use chrono::Utc;
+use num::Zero;
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    fast_withdrawals::{
+        FastWithdrawal, FastWithdrawalId, FastWithdrawalRequest, LiquidityProvider,
+        SaveFastWithdrawalQuery,
+    },
+    Address,
+};
+
+// Local uses
+use super::{
+    error::{Error, FastWithdrawalError},
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Maximum number of the latest withdrawals of the provider returned by the API.
+const PROVIDER_WITHDRAWALS_LIMIT: u32 = 100;
+
+/// Chooses the provider fronting the withdrawal with the lowest fee. Out of the providers
+/// with the same fee, the one with the most liquidity is chosen.
+fn select_provider<'a>(
+    providers: &'a [LiquidityProvider],
+    request: &FastWithdrawalRequest,
+) -> Option<&'a LiquidityProvider> {
+    providers
+        .iter()
+        .filter(|provider| provider.address != request.account)
+        .filter(|provider| provider.can_front(request.token, &request.amount))
+        .min_by_key(|provider| (provider.fee_bps, Reverse(provider.max_amount.clone())))
+}
+
+/// Shared data between `api/v0.2/fast_withdrawals` endpoints.
+#[derive(Clone)]
+struct ApiFastWithdrawalData {
+    pool: ConnectionPool,
+    enabled: bool,
+    validity: chrono::Duration,
+}
+
+impl ApiFastWithdrawalData {
+    fn new(pool: ConnectionPool, config: &CommonApiConfig) -> Self {
+        Self {
+            pool,
+            enabled: config.fast_withdrawals_enabled,
+            validity: chrono::Duration::from_std(config.fast_withdrawal_validity())
+                .expect("invalid fast withdrawal validity"),
+        }
+    }
+
+    fn check_enabled(&self) -> Result<(), Error> {
+        if self.enabled {
+            Ok(())
+        } else {
+            Err(FastWithdrawalError::Disabled.into())
+        }
+    }
+
+    async fn match_withdrawal(
+        &self,
+        request: FastWithdrawalRequest,
+    ) -> Result<FastWithdrawal, Error> {
+        self.check_enabled()?;
+        if request.amount.is_zero() {
+            return Err(FastWithdrawalError::InvalidAmount.into());
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let providers = storage
+            .fast_withdrawals_schema()
+            .load_token_providers(request.token)
+            .await
+            .map_err(Error::storage)?;
+        let provider =
+            select_provider(&providers, &request).ok_or(FastWithdrawalError::NoProvider)?;
+
+        let created_at = Utc::now();
+        let query = SaveFastWithdrawalQuery {
+            provider: provider.address,
+            fronted_amount: provider.fronted_amount(&request.amount),
+            request,
+            created_at,
+            valid_until: created_at + self.validity,
+        };
+        let withdrawal = storage
+            .fast_withdrawals_schema()
+            .store_withdrawal(&query)
+            .await
+            .map_err(Error::storage)?;
+        metrics::increment_counter!("api.fast_withdrawals.matched");
+        Ok(withdrawal)
+    }
+
+    async fn withdrawal(&self, id: FastWithdrawalId) -> Result<FastWithdrawal, Error> {
+        self.check_enabled()?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .fast_withdrawals_schema()
+            .get_withdrawal(id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| FastWithdrawalError::NotFound.into())
+    }
+
+    async fn provider_withdrawals(&self, provider: Address) -> Result<Vec<FastWithdrawal>, Error> {
+        self.check_enabled()?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .fast_withdrawals_schema()
+            .load_provider_withdrawals(provider, PROVIDER_WITHDRAWALS_LIMIT)
+            .await
+            .map_err(Error::storage)
+    }
+}
+
+// Server implementation
+
+async fn match_withdrawal(
+    data: web::Data<ApiFastWithdrawalData>,
+    Json(request): Json<FastWithdrawalRequest>,
+) -> ApiResult<FastWithdrawal> {
+    let start = Instant::now();
+    let res = api_try!(data.match_withdrawal(request).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "match_fast_withdrawal");
+    ApiResult::Ok(res)
+}
+
+async fn withdrawal(
+    data: web::Data<ApiFastWithdrawalData>,
+    id: web::Path<FastWithdrawalId>,
+) -> ApiResult<FastWithdrawal> {
+    let start = Instant::now();
+    let res = api_try!(data.withdrawal(*id).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "fast_withdrawal");
+    ApiResult::Ok(res)
+}
+
+async fn provider_withdrawals(
+    data: web::Data<ApiFastWithdrawalData>,
+    provider: web::Path<Address>,
+) -> ApiResult<Vec<FastWithdrawal>> {
+    let start = Instant::now();
+    let res = api_try!(data.provider_withdrawals(*provider).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "provider_fast_withdrawals");
+    ApiResult::Ok(res)
+}
+
+pub fn api_scope(pool: ConnectionPool, config: &CommonApiConfig) -> Scope {
+    let data = ApiFastWithdrawalData::new(pool, config);
+
+    web::scope("fast_withdrawals")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(match_withdrawal))
+        .route("{id}", web::get().to(withdrawal))
+        .route("providers/{address}", web::get().to(provider_withdrawals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_types::TokenId;
+
+    fn provider(byte: u8, max_amount: u32, fee_bps: u16) -> LiquidityProvider {
+        LiquidityProvider {
+            address: Address::repeat_byte(byte),
+            token: TokenId(1),
+            max_amount: BigUint::from(max_amount),
+            fee_bps,
+        }
+    }
+
+    #[test]
+    fn provider_selection() {
+        let providers = vec![
+            provider(0x01, 100, 10),
+            provider(0x02, 1_000, 20),
+            provider(0x03, 500, 10),
+        ];
+        let request = |account: u8, amount: u32| FastWithdrawalRequest {
+            account: Address::repeat_byte(account),
+            recipient: Address::repeat_byte(0xff),
+            token: TokenId(1),
+            amount: BigUint::from(amount),
+        };
+
+        // The cheapest provider with the most liquidity.
+        assert_eq!(
+            select_provider(&providers, &request(0xaa, 50)),
+            Some(&providers[2])
+        );
+        // Only the expensive provider has enough liquidity.
+        assert_eq!(
+            select_provider(&providers, &request(0xaa, 800)),
+            Some(&providers[1])
+        );
+        assert_eq!(select_provider(&providers, &request(0xaa, 1_001)), None);
+        // Provider doesn't front its own withdrawals.
+        assert_eq!(
+            select_provider(&providers, &request(0x03, 50)),
+            Some(&providers[0])
+        );
+        assert_eq!(
+            select_provider(
+                &providers,
+                &FastWithdrawalRequest {
+                    token: TokenId(2),
+                    ..request(0xaa, 50)
+                }
+            ),
+            None
+        );
+    }
+}
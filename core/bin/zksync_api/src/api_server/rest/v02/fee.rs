@@ -10,12 +10,19 @@ use actix_web::{
 };
 
 // Workspace uses
-use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_api_types::v02::{
+    account::EnsResolution,
+    fee::{AddressOrEnsName, ApiFee, BatchFeeRequest, TxFeeRequest},
+};
+use zksync_types::Address;
 
 // Local uses
 use super::{error::Error, response::ApiResult};
 use crate::{
-    api_server::tx_sender::{SubmitError, TxSender},
+    api_server::{
+        ens::{EnsError, EnsResolver},
+        tx_sender::{SubmitError, TxSender},
+    },
     api_try,
 };
 
@@ -23,11 +30,30 @@ use crate::{
 #[derive(Clone)]
 struct ApiFeeData {
     tx_sender: TxSender,
+    ens: Option<EnsResolver>,
 }
 
 impl ApiFeeData {
-    fn new(tx_sender: TxSender) -> Self {
-        Self { tx_sender }
+    fn new(tx_sender: TxSender, ens: Option<EnsResolver>) -> Self {
+        Self { tx_sender, ens }
+    }
+
+    /// Resolves the ENS name of the recipient to its address.
+    async fn resolve_recipient(
+        &self,
+        recipient: AddressOrEnsName,
+    ) -> Result<(Address, Option<EnsResolution>), Error> {
+        match recipient {
+            AddressOrEnsName::Address(address) => Ok((address, None)),
+            AddressOrEnsName::EnsName(name) => {
+                let resolver = self
+                    .ens
+                    .as_ref()
+                    .ok_or_else(|| Error::from(EnsError::NotSupported))?;
+                let resolution = resolver.resolve(&name).await.map_err(Error::from)?;
+                Ok((resolution.address, Some(resolution)))
+            }
+        }
     }
 }
 
@@ -45,13 +71,17 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let (address, ens_resolution) = api_try!(data.resolve_recipient(body.address).await);
     // TODO implement subsidies for v02 api ZKS-888
     let res = data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, address)
         .await
-        .map(|fee| fee.normal_fee.into())
+        .map(|fee| ApiFee {
+            ens_resolution,
+            ..ApiFee::from(fee.normal_fee)
+        })
         .map_err(Error::from)
         .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tx_fee");
@@ -89,8 +119,8 @@ async fn get_batch_fee(
     res
 }
 
-pub fn api_scope(tx_sender: TxSender) -> Scope {
-    let data = ApiFeeData::new(tx_sender);
+pub fn api_scope(tx_sender: TxSender, ens: Option<EnsResolver>) -> Scope {
+    let data = ApiFeeData::new(tx_sender, ens);
 
     web::scope("fee")
         .app_data(web::Data::new(data))
@@ -165,14 +195,17 @@ mod tests {
             .with_market(market);
         let (client, server) = cfg.start_server(
             move |cfg: &TestServerConfig| {
-                api_scope(TxSender::new(
-                    cfg.pool.clone(),
-                    dummy_sign_verifier(),
-                    dummy_fee_ticker(&prices, Some(cache.clone())),
-                    &cfg.config.api.common,
-                    &cfg.config.api.token_config,
-                    mempool_tx_request_sender.clone(),
-                ))
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        mempool_tx_request_sender.clone(),
+                    ),
+                    None,
+                )
             },
             Some(shared_data),
         );
@@ -197,6 +230,18 @@ mod tests {
         assert_eq!(api_fee.gas_fee, BigUint::from(1u32));
         assert_eq!(api_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_fee.total_fee, BigUint::from(2u32));
+        assert_eq!(api_fee.ens_resolution, None);
+
+        // ENS names are not accepted since the resolver is not set.
+        let response = client
+            .get_txs_fee(
+                ApiTxFeeTypes::Transfer,
+                AddressOrEnsName::EnsName("name.eth".to_owned()),
+                allowed_token.clone(),
+            )
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, Error::from(EnsError::NotSupported));
 
         let tx = TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
@@ -7,24 +7,31 @@ use actix_web::{
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use zksync_api_types::v02::ApiVersion;
 use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
 use zksync_types::network::Network;
 
 // Local uses
-use crate::api_server::tx_sender::TxSender;
+use crate::api_server::{ens::EnsResolver, tx_sender::TxSender};
 
 mod account;
 mod block;
 mod config;
 pub mod error;
+mod fast_withdrawal;
 mod fee;
 mod paginate_impl;
 mod paginate_trait;
 mod response;
+mod stats;
 mod status;
 #[cfg(test)]
 pub mod test_utils;
 mod token;
 mod transaction;
+mod webhook;
+
+/// Maximum number of the latest transaction rejections returned by the API.
+const TX_REJECTIONS_LIMIT: u32 = 10;
 
 #[derive(Debug, Clone, Copy)]
 pub struct SharedData {
@@ -34,8 +41,10 @@ pub struct SharedData {
 
 pub(crate) fn api_scope(
     tx_sender: TxSender,
+    main_database_connection_pool: ConnectionPool,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
+    ens: Option<EnsResolver>,
 ) -> Scope {
     let data = SharedData {
         net: zk_config.chain.eth.network,
@@ -47,13 +56,22 @@ pub(crate) fn api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             zk_config.eth_watch.confirmations_for_eth_event,
+            ens.clone(),
+            tx_sender.mempool_tx_sender.clone(),
         ))
         .service(block::api_scope(
             tx_sender.pool.clone(),
             tx_sender.blocks.clone(),
+            zk_config.api.common.light_node,
         ))
         .service(config::api_scope(zk_config))
-        .service(fee::api_scope(tx_sender.clone()))
+        // Fast withdrawals are matched via the API, so it's necessary to use main database connection.
+        .service(fast_withdrawal::api_scope(
+            main_database_connection_pool.clone(),
+            &zk_config.api.common,
+        ))
+        .service(fee::api_scope(tx_sender.clone(), ens))
+        .service(stats::api_scope(tx_sender.pool.clone()))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
@@ -62,4 +80,9 @@ pub(crate) fn api_scope(
             tx_sender.ticker.clone(),
         ))
         .service(transaction::api_scope(tx_sender))
+        // Webhooks are registered via the API, so it's necessary to use main database connection.
+        .service(webhook::api_scope(
+            main_database_connection_pool,
+            zk_config.api.webhooks.clone(),
+        ))
 }
@@ -5,10 +5,11 @@
 // Workspace uses
 use zksync_api_types::{
     v02::{
+        account::BalanceHistoryItem,
         block::BlockInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            AccountBalanceHistoryRequest, AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated,
+            PaginationQuery, PendingOpsRequest,
         },
         transaction::{Transaction, TxHashSerializeWrapper},
     },
@@ -251,6 +252,7 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
                 query.from.address,
                 query.from.token,
                 query.from.second_address,
+                &query.from.filters,
             )
             .await
             .map_err(Error::storage)?;
@@ -267,6 +269,76 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
     }
 }
 
+#[async_trait::async_trait]
+impl Paginate<AccountBalanceHistoryRequest> for StorageProcessor<'_> {
+    type OutputObj = BalanceHistoryItem;
+    type OutputId = TxHashSerializeWrapper;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<AccountBalanceHistoryRequest>,
+    ) -> Result<Paginated<BalanceHistoryItem, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_account_last_tx_hash(query.from.address)
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let query = PaginationQuery {
+            from: AccountBalanceHistoryRequest {
+                tx_hash: ApiEither::from(tx_hash),
+                ..query.from
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+
+        let history = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_account_balance_history(&query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TransactionNotFound))?;
+        // History is paginated by the transactions of the account, so they're counted.
+        let count = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_count(query.from.address, None, None, &Default::default())
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            history,
+            TxHashSerializeWrapper(tx_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
     type OutputObj = Transaction;
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::convert::From;
 
 // External uses
-use actix_web::{web::Data, HttpRequest, HttpResponse, Responder};
+use actix_web::{http::header, web::Data, HttpRequest, HttpResponse, Responder};
 use chrono::Utc;
 use qstring::QString;
 use serde::{Deserialize, Serialize};
@@ -45,6 +45,10 @@ impl<R: Serialize> Responder for ApiResult<R> {
             timestamp: Utc::now(),
         };
 
+        let retry_after = match &self {
+            ApiResult::Error(err) => err.retry_after,
+            ApiResult::Ok(_) => None,
+        };
         let response = match self {
             ApiResult::Ok(res) => Response {
                 request,
@@ -62,9 +66,16 @@ impl<R: Serialize> Responder for ApiResult<R> {
 
         let body = serde_json::to_string(&response).expect("Should be correct serializable");
 
-        HttpResponse::Ok()
-            .content_type("application/json")
-            .body(body)
+        // Errors are sent with the 200 status, unless the client is asked to retry later.
+        let mut builder = match retry_after {
+            Some(retry_after) => {
+                let mut builder = HttpResponse::TooManyRequests();
+                builder.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+                builder
+            }
+            None => HttpResponse::Ok(),
+        };
+        builder.content_type("application/json").body(body)
     }
 }
 
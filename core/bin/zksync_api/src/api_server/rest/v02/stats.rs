@@ -0,0 +1,219 @@
+//! Network statistics part of API implementation.
+//!
+//! The statistics are aggregated from the finalized blocks by the stats aggregator,
+//! so the endpoints only read the aggregated tables.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+use chrono::{Duration, NaiveDate, Utc};
+
+// Workspace uses
+use zksync_api_types::v02::stats::{
+    DailyActiveAccounts, DailyFees, DailyTxCounts, StatsQuery, TotalValueLocked,
+    DEFAULT_STATS_DAYS, MAX_STATS_DAYS,
+};
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Shared data between `api/v0.2/stats` endpoints.
+#[derive(Debug, Clone)]
+struct ApiStatsData {
+    pool: ConnectionPool,
+}
+
+impl ApiStatsData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the first day of the requested period, the current day is the last one.
+    fn first_day(query: StatsQuery) -> Result<NaiveDate, Error> {
+        let days = query.days.unwrap_or(DEFAULT_STATS_DAYS);
+        if days > MAX_STATS_DAYS {
+            return Err(Error::from(InvalidDataError::StatsPeriodTooLong));
+        }
+        let today = Utc::now().date().naive_utc();
+        Ok(today - Duration::days(i64::from(days.saturating_sub(1))))
+    }
+
+    async fn daily_tx_counts(&self, query: StatsQuery) -> Result<Vec<DailyTxCounts>, Error> {
+        let first_day = Self::first_day(query)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .network_stats_schema()
+            .load_daily_tx_counts(first_day)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn daily_active_accounts(
+        &self,
+        query: StatsQuery,
+    ) -> Result<Vec<DailyActiveAccounts>, Error> {
+        let first_day = Self::first_day(query)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .network_stats_schema()
+            .load_daily_active_accounts(first_day)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn daily_fees(&self, query: StatsQuery) -> Result<Vec<DailyFees>, Error> {
+        let first_day = Self::first_day(query)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .network_stats_schema()
+            .load_daily_fees(first_day)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn total_value_locked(&self) -> Result<TotalValueLocked, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .network_stats_schema()
+            .load_total_value_locked()
+            .await
+            .map_err(Error::storage)
+    }
+}
+
+// Server implementation
+
+async fn daily_tx_counts(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<DailyTxCounts>> {
+    let start = Instant::now();
+    let res = api_try!(data.daily_tx_counts(query).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "daily_tx_counts");
+    ApiResult::Ok(res)
+}
+
+async fn daily_active_accounts(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<DailyActiveAccounts>> {
+    let start = Instant::now();
+    let res = api_try!(data.daily_active_accounts(query).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "daily_active_accounts");
+    ApiResult::Ok(res)
+}
+
+async fn daily_fees(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<DailyFees>> {
+    let start = Instant::now();
+    let res = api_try!(data.daily_fees(query).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "daily_fees");
+    ApiResult::Ok(res)
+}
+
+async fn total_value_locked(data: web::Data<ApiStatsData>) -> ApiResult<TotalValueLocked> {
+    let start = Instant::now();
+    let res = api_try!(data.total_value_locked().await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "total_value_locked");
+    ApiResult::Ok(res)
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiStatsData::new(pool);
+
+    web::scope("stats")
+        .app_data(web::Data::new(data))
+        .route("transactions", web::get().to(daily_tx_counts))
+        .route("activeAccounts", web::get().to(daily_active_accounts))
+        .route("fees", web::get().to(daily_fees))
+        .route("totalValueLocked", web::get().to(total_value_locked))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            |cfg: &TestServerConfig| api_scope(cfg.pool.clone()),
+            Some(shared_data),
+        );
+
+        let query = StatsQuery { days: Some(7) };
+        let first_day = ApiStatsData::first_day(query).unwrap();
+        let (expected_tx_counts, expected_fees, expected_value_locked) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let last_block = storage
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await?;
+            storage
+                .network_stats_schema()
+                .aggregate_blocks(last_block)
+                .await?;
+            (
+                storage
+                    .network_stats_schema()
+                    .load_daily_tx_counts(first_day)
+                    .await?,
+                storage
+                    .network_stats_schema()
+                    .load_daily_fees(first_day)
+                    .await?,
+                storage
+                    .network_stats_schema()
+                    .load_total_value_locked()
+                    .await?,
+            )
+        };
+
+        let response = client.daily_tx_counts(&query).await?;
+        let tx_counts: Vec<DailyTxCounts> = deserialize_response_result(response)?;
+        assert_eq!(tx_counts, expected_tx_counts);
+
+        let response = client.daily_fees(&query).await?;
+        let fees: Vec<DailyFees> = deserialize_response_result(response)?;
+        assert_eq!(fees, expected_fees);
+
+        let response = client.total_value_locked().await?;
+        let value_locked: TotalValueLocked = deserialize_response_result(response)?;
+        assert_eq!(value_locked, expected_value_locked);
+
+        let response = client
+            .daily_active_accounts(&StatsQuery {
+                days: Some(MAX_STATS_DAYS + 1),
+            })
+            .await?;
+        assert!(deserialize_response_result::<Vec<DailyActiveAccounts>>(response).is_err());
+
+        server.stop().await;
+        Ok(())
+    }
+}
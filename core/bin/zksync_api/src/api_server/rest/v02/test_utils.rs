@@ -522,6 +522,7 @@ impl TestServerConfig {
                         job_data.clone(),
                         0,
                         ProverJobType::SingleProof,
+                        Default::default(),
                     )
                     .await?;
                 ProverSchema(&mut storage)
@@ -531,17 +532,18 @@ impl TestServerConfig {
                         job_data,
                         1,
                         ProverJobType::AggregatedProof,
+                        Default::default(),
                     )
                     .await?;
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue(&[])
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue(&[])
                     .await?
                     .unwrap()
                     .job_id;
@@ -889,6 +891,10 @@ impl FeeTickerInfo for DummyFeeTickerInfo {
         })
     }
 
+    async fn get_fee_discount_percent(&self, _token_id: TokenId) -> anyhow::Result<u8> {
+        Ok(0)
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -1,6 +1,7 @@
 //! Tokens part of API implementation.
 
 // Built-in uses
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Instant;
 
@@ -68,6 +69,16 @@ impl ApiTokenData {
         storage: &mut StorageProcessor<'_>,
         token_id: TokenId,
     ) -> Result<bool, Error> {
+        // The fee acceptability set by the operator takes precedence over the market volume.
+        let fee_acceptable = storage
+            .fee_policies_schema()
+            .get_token_fee_acceptability(token_id)
+            .await
+            .map_err(Error::storage)?;
+        if let Some(fee_acceptable) = fee_acceptable {
+            return Ok(fee_acceptable);
+        }
+
         let result = storage
             .tokens_schema()
             .filter_tokens_by_market_volume(vec![token_id], &self.min_market_volume)
@@ -92,11 +103,22 @@ impl ApiTokenData {
                     .filter_tokens_by_market_volume(tokens_to_check, &self.min_market_volume)
                     .await
                     .map_err(Error::storage)?;
+                let fee_acceptability: HashMap<_, _> = storage
+                    .fee_policies_schema()
+                    .load_token_fee_acceptability()
+                    .await
+                    .map_err(Error::storage)?
+                    .into_iter()
+                    .map(|token| (TokenId(token.token_id as u32), token.fee_acceptable))
+                    .collect();
                 let list = paginated_tokens
                     .list
                     .into_iter()
                     .map(|token| {
-                        let eligibility = tokens_enabled_for_fees.contains(&token.id);
+                        let eligibility = fee_acceptability
+                            .get(&token.id)
+                            .copied()
+                            .unwrap_or_else(|| tokens_enabled_for_fees.contains(&token.id));
                         ApiToken::from_token_and_eligibility(token, eligibility)
                     })
                     .collect();
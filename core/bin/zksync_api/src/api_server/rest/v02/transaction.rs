@@ -11,16 +11,20 @@ use actix_web::{
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        ApiTxBatch, ApiTxRejection, IncomingTxBatch, L1Receipt, L1Transaction, Receipt,
+        SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData,
+        TxHashSerializeWrapper, TxInBlockStatus, TxReceiptsRequest, MAX_TX_RECEIPTS,
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_types::{tx::TxHash, EthBlockId, H256};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+    TX_REJECTIONS_LIMIT,
+};
 use crate::api_server::tx_sender::{SubmitError, TxSender};
 
 /// Shared data between `api/v0.2/transactions` endpoints.
@@ -66,6 +70,7 @@ impl ApiTransactionData {
                 eth_block: EthBlockId(op.eth_block),
                 rollup_block: None,
                 id: op.serial_id,
+                l1_finality: None,
             })))
         }
         // 3. No operation found, return nothing.
@@ -74,6 +79,55 @@ impl ApiTransactionData {
         }
     }
 
+    async fn tx_statuses(&self, tx_hashes: &[TxHash]) -> Result<Vec<Option<Receipt>>, Error> {
+        if tx_hashes.len() > MAX_TX_RECEIPTS {
+            return Err(Error::from(InvalidDataError::TooManyTxHashes));
+        }
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+
+        let mut receipts = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipts_api_v02(tx_hashes)
+            .await
+            .map_err(Error::storage)?;
+
+        // Look up the pending operations for the transactions not found, as in `tx_status`.
+        let not_found: Vec<H256> = tx_hashes
+            .iter()
+            .zip(&receipts)
+            .filter(|(_, receipt)| receipt.is_none())
+            .map(|(tx_hash, _)| (*tx_hash).into())
+            .collect();
+        if !not_found.is_empty() {
+            let pending_ops = storage
+                .chain()
+                .mempool_schema()
+                .get_pending_operations_by_hashes(&not_found)
+                .await
+                .map_err(Error::core_api)?;
+            for op in pending_ops {
+                for (tx_hash, receipt) in tx_hashes.iter().zip(&mut receipts) {
+                    if tx_hash.as_ref() == op.eth_hash.as_bytes() {
+                        *receipt = Some(Receipt::L1(L1Receipt {
+                            status: TxInBlockStatus::Queued,
+                            eth_block: EthBlockId(op.eth_block),
+                            rollup_block: None,
+                            id: op.serial_id,
+                            l1_finality: None,
+                        }));
+                    }
+                }
+            }
+        }
+        Ok(receipts)
+    }
+
     async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
@@ -136,6 +190,21 @@ impl ApiTransactionData {
             .await
             .map_err(Error::storage)
     }
+
+    async fn tx_rejections(&self, tx_hash: TxHash) -> Result<Vec<ApiTxRejection>, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let rejections = storage
+            .tx_rejections_schema()
+            .load_by_tx_hash(tx_hash, TX_REJECTIONS_LIMIT)
+            .await
+            .map_err(Error::storage)?;
+        Ok(rejections.into_iter().map(ApiTxRejection::from).collect())
+    }
 }
 
 // Server implementation
@@ -150,6 +219,16 @@ async fn tx_status(
     res
 }
 
+async fn tx_statuses(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<TxReceiptsRequest>,
+) -> ApiResult<Vec<Option<Receipt>>> {
+    let start = Instant::now();
+    let res = data.tx_statuses(&body.tx_hashes).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_statuses");
+    res
+}
+
 async fn tx_data(
     data: web::Data<ApiTransactionData>,
     tx_hash: web::Path<TxHash>,
@@ -235,6 +314,16 @@ async fn get_batch(
     res
 }
 
+async fn tx_rejections(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+) -> ApiResult<Vec<ApiTxRejection>> {
+    let start = Instant::now();
+    let res = data.tx_rejections(*tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_rejections");
+    res
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiTransactionData::new(tx_sender);
 
@@ -243,6 +332,8 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/rejections", web::get().to(tx_rejections))
+        .route("/receipts", web::post().to(tx_statuses))
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
         .route("/toggle2FA", web::post().to(toggle_2fa))
@@ -266,7 +357,7 @@ mod tests {
     use std::str::FromStr;
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
-        transaction::{L2Receipt, TxHashSerializeWrapper},
+        transaction::{L1Finality, L1TxInfo, L2Receipt, TxHashSerializeWrapper},
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
@@ -294,6 +385,7 @@ mod tests {
                     MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
+                    _ => unreachable!(),
                 }
             }
         });
@@ -419,6 +511,8 @@ mod tests {
                     tx: tx.tx,
                     eth_sign_data: None,
                     created_at: Utc::now(),
+                    batch_hash: None,
+                    parties_eth_sign_data: Vec::new(),
                 })
                 .collect();
             storage
@@ -448,6 +542,37 @@ mod tests {
 
             TxHash::from_str(&transactions[0].tx_hash).unwrap()
         };
+        let expected_finality = {
+            let mut storage = cfg.pool.access_storage().await?;
+
+            let block = storage
+                .chain()
+                .block_schema()
+                .load_block_range_desc(BlockNumber(1), 1)
+                .await?
+                .remove(0);
+            let commit_tx_hash = H256::from_slice(&block.commit_tx_hash.unwrap());
+            let verify_tx_hash = H256::from_slice(&block.verify_tx_hash.unwrap());
+            // Block of the verify transaction is not recorded.
+            storage
+                .ethereum_schema()
+                .save_eth_tx_block(&commit_tx_hash, 10)
+                .await?;
+            storage.ethereum_schema().update_last_eth_block(15).await?;
+
+            L1Finality {
+                commit: Some(L1TxInfo {
+                    tx_hash: commit_tx_hash,
+                    eth_block: Some(EthBlockId(10)),
+                    confirmations: Some(5),
+                }),
+                verify: Some(L1TxInfo {
+                    tx_hash: verify_tx_hash,
+                    eth_block: None,
+                    confirmations: None,
+                }),
+            }
+        };
         let response = client.tx_status(tx_hash).await?;
         let tx_status: Receipt = deserialize_response_result(response)?;
         let expected_tx_status = Receipt::L2(L2Receipt {
@@ -455,6 +580,7 @@ mod tests {
             rollup_block: Some(BlockNumber(1)),
             status: TxInBlockStatus::Finalized,
             fail_reason: None,
+            l1_finality: Some(expected_finality),
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -474,6 +600,8 @@ mod tests {
                     tx,
                     eth_sign_data: None,
                     created_at: Utc::now(),
+                    batch_hash: None,
+                    parties_eth_sign_data: Vec::new(),
                 })
                 .await?;
 
@@ -486,6 +614,7 @@ mod tests {
             rollup_block: None,
             status: TxInBlockStatus::Queued,
             fail_reason: None,
+            l1_finality: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
@@ -498,6 +627,27 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert!(tx_data.is_none());
 
+        // Receipts of several transactions are returned in the order of the hashes.
+        let response = client
+            .tx_statuses(vec![tx_hash, tx.hash(), pending_tx_hash])
+            .await?;
+        let tx_statuses: Vec<Option<Receipt>> = deserialize_response_result(response)?;
+        assert_eq!(tx_statuses.len(), 3);
+        assert!(matches!(
+            &tx_statuses[0],
+            Some(Receipt::L2(receipt))
+                if receipt.tx_hash == tx_hash && receipt.status == TxInBlockStatus::Finalized
+        ));
+        assert_eq!(tx_statuses[1], None);
+        assert_eq!(tx_statuses[2], Some(expected_tx_status));
+
+        let response = client
+            .tx_statuses(vec![tx_hash; MAX_TX_RECEIPTS + 1])
+            .await?;
+        let expected_error = Error::from(InvalidDataError::TooManyTxHashes);
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, expected_error);
+
         server.stop().await;
         task.abort();
         Ok(())
@@ -0,0 +1,293 @@
+//! Webhooks part of API implementation.
+//!
+//! Webhooks are registered with the API key passed in the `X-API-Key` header,
+//! and only the webhooks of the key can be listed or removed with it.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+
+// Workspace uses
+use zksync_api_types::v02::webhook::{
+    ApiWebhook, ApiWebhookDelivery, RegisterWebhook, RegisteredWebhook,
+};
+use zksync_config::configs::api::WebhooksConfig;
+use zksync_storage::{webhooks::records::NewWebhook, ConnectionPool};
+
+// Local uses
+use super::{
+    error::{Error, WebhookError},
+    response::ApiResult,
+};
+use crate::{
+    api_server::api_keys::{hash_api_key, API_KEY_HEADER},
+    webhook_notifier::{generate_secret, is_local_address},
+};
+
+/// Maximum number of the latest deliveries returned by the API.
+const DELIVERIES_LIMIT: u32 = 100;
+
+/// Checks that the events can be posted to the URL. Unless the insecure URLs are allowed,
+/// the URL has to use HTTPS and can't point to the local addresses, see `is_local_address`.
+fn validate_url(url: &str, allow_insecure_urls: bool) -> Result<(), WebhookError> {
+    let parsed =
+        reqwest::Url::parse(url).map_err(|err| WebhookError::InvalidUrl(err.to_string()))?;
+    match parsed.scheme() {
+        "https" => {}
+        "http" if allow_insecure_urls => {}
+        scheme => {
+            return Err(WebhookError::InvalidUrl(format!(
+                "unsupported scheme {}",
+                scheme
+            )))
+        }
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| WebhookError::InvalidUrl("host is missing".to_owned()))?;
+    if allow_insecure_urls {
+        return Ok(());
+    }
+
+    // Host names are resolved and checked once again before every delivery.
+    let is_local = match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => is_local_address(ip),
+        Err(_) => host == "localhost",
+    };
+    if is_local {
+        return Err(WebhookError::InvalidUrl(format!("local address {}", host)));
+    }
+    Ok(())
+}
+
+/// Shared data between `api/v0.2/webhooks` endpoints.
+#[derive(Clone)]
+struct ApiWebhookData {
+    pool: ConnectionPool,
+    config: WebhooksConfig,
+}
+
+impl ApiWebhookData {
+    fn new(pool: ConnectionPool, config: WebhooksConfig) -> Self {
+        Self { pool, config }
+    }
+
+    /// Returns the ID of the API key the request is authenticated with.
+    async fn api_key_id(&self, req: &HttpRequest) -> Result<i64, Error> {
+        let key = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .ok_or(WebhookError::InvalidApiKey)?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let api_key = storage
+            .api_keys_schema()
+            .load_active_key_by_hash(&hash_api_key(key))
+            .await
+            .map_err(Error::storage)?
+            .ok_or(WebhookError::InvalidApiKey)?;
+        Ok(api_key.id)
+    }
+
+    async fn register_webhook(
+        &self,
+        api_key_id: i64,
+        request: RegisterWebhook,
+    ) -> Result<RegisteredWebhook, Error> {
+        validate_url(&request.url, self.config.allow_insecure_urls)?;
+        if request.event_types.is_empty() {
+            return Err(WebhookError::EmptyEventTypes.into());
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let registered = transaction
+            .webhooks_schema()
+            .load_webhooks_by_api_key(api_key_id)
+            .await
+            .map_err(Error::storage)?
+            .len();
+        if registered >= self.config.max_webhooks_per_api_key as usize {
+            return Err(WebhookError::TooManyWebhooks(self.config.max_webhooks_per_api_key).into());
+        }
+
+        let secret = generate_secret();
+        let mut event_types = request.event_types;
+        event_types.sort_by_key(|event_type| event_type.as_str());
+        event_types.dedup();
+        let webhook = transaction
+            .webhooks_schema()
+            .add_webhook(&NewWebhook {
+                api_key_id,
+                url: request.url,
+                secret: secret.clone(),
+                event_types: event_types
+                    .iter()
+                    .map(|event_type| event_type.as_str().to_owned())
+                    .collect(),
+                account_id: request.account_id.map(|id| i64::from(*id)),
+            })
+            .await
+            .map_err(Error::storage)?;
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(RegisteredWebhook {
+            webhook: ApiWebhook::from(webhook),
+            secret,
+        })
+    }
+
+    async fn webhooks(&self, api_key_id: i64) -> Result<Vec<ApiWebhook>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let webhooks = storage
+            .webhooks_schema()
+            .load_webhooks_by_api_key(api_key_id)
+            .await
+            .map_err(Error::storage)?;
+        Ok(webhooks.into_iter().map(ApiWebhook::from).collect())
+    }
+
+    async fn remove_webhook(&self, api_key_id: i64, id: i64) -> Result<(), Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let removed = storage
+            .webhooks_schema()
+            .remove_webhook(id, api_key_id)
+            .await
+            .map_err(Error::storage)?;
+        if removed {
+            Ok(())
+        } else {
+            Err(WebhookError::NotFound.into())
+        }
+    }
+
+    async fn deliveries(&self, api_key_id: i64, id: i64) -> Result<Vec<ApiWebhookDelivery>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let is_owned = storage
+            .webhooks_schema()
+            .load_webhooks_by_api_key(api_key_id)
+            .await
+            .map_err(Error::storage)?
+            .iter()
+            .any(|webhook| webhook.id == id);
+        if !is_owned {
+            return Err(WebhookError::NotFound.into());
+        }
+
+        let deliveries = storage
+            .webhooks_schema()
+            .load_deliveries(id, DELIVERIES_LIMIT)
+            .await
+            .map_err(Error::storage)?;
+        Ok(deliveries
+            .into_iter()
+            .map(ApiWebhookDelivery::from)
+            .collect())
+    }
+}
+
+// Server implementation
+
+async fn register_webhook(
+    data: web::Data<ApiWebhookData>,
+    req: HttpRequest,
+    Json(request): Json<RegisterWebhook>,
+) -> ApiResult<RegisteredWebhook> {
+    let start = Instant::now();
+    let res = match data.api_key_id(&req).await {
+        Ok(api_key_id) => data.register_webhook(api_key_id, request).await,
+        Err(err) => Err(err),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "register_webhook");
+    res.into()
+}
+
+async fn webhooks(data: web::Data<ApiWebhookData>, req: HttpRequest) -> ApiResult<Vec<ApiWebhook>> {
+    let start = Instant::now();
+    let res = match data.api_key_id(&req).await {
+        Ok(api_key_id) => data.webhooks(api_key_id).await,
+        Err(err) => Err(err),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "webhooks");
+    res.into()
+}
+
+async fn remove_webhook(
+    data: web::Data<ApiWebhookData>,
+    req: HttpRequest,
+    id: web::Path<i64>,
+) -> ApiResult<()> {
+    let start = Instant::now();
+    let res = match data.api_key_id(&req).await {
+        Ok(api_key_id) => data.remove_webhook(api_key_id, *id).await,
+        Err(err) => Err(err),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "remove_webhook");
+    res.into()
+}
+
+async fn webhook_deliveries(
+    data: web::Data<ApiWebhookData>,
+    req: HttpRequest,
+    id: web::Path<i64>,
+) -> ApiResult<Vec<ApiWebhookDelivery>> {
+    let start = Instant::now();
+    let res = match data.api_key_id(&req).await {
+        Ok(api_key_id) => data.deliveries(api_key_id, *id).await,
+        Err(err) => Err(err),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "webhook_deliveries");
+    res.into()
+}
+
+pub fn api_scope(pool: ConnectionPool, config: WebhooksConfig) -> Scope {
+    let data = ApiWebhookData::new(pool, config);
+
+    web::scope("webhooks")
+        .app_data(web::Data::new(data))
+        .route("", web::post().to(register_webhook))
+        .route("", web::get().to(webhooks))
+        .route("{id}", web::delete().to(remove_webhook))
+        .route("{id}/deliveries", web::get().to(webhook_deliveries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_urls() {
+        assert!(validate_url("https://example.com/hook", false).is_ok());
+        assert!(validate_url("https://8.8.8.8/hook", false).is_ok());
+
+        for url in &[
+            "http://example.com/hook",
+            "ftp://example.com/hook",
+            "https://localhost/hook",
+            "https://127.0.0.1/hook",
+            "https://10.0.0.1/hook",
+            "https://192.168.1.1:8080/hook",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[fe80::1]/hook",
+            "https://[::ffff:127.0.0.1]/hook",
+            "not a url",
+        ] {
+            assert!(
+                matches!(validate_url(url, false), Err(WebhookError::InvalidUrl(_))),
+                "{}",
+                url
+            );
+        }
+
+        // Local webhooks are allowed for the development.
+        assert!(validate_url("http://localhost:3000/hook", true).is_ok());
+        assert!(validate_url("ftp://example.com/hook", true).is_err());
+    }
+}
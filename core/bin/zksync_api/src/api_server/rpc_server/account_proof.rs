@@ -0,0 +1,213 @@
+//! Merkle proofs of the account balances against the state root of the latest verified block.
+//!
+//! The API server doesn't maintain the account tree, so it's restored from the verified state
+//! stored in the database. Restoring the tree is expensive, thus it's kept until the next block
+//! is verified and shared by all the requests.
+
+// Built-in uses
+use std::sync::Arc;
+use std::time::Instant;
+
+// External uses
+use anyhow::{ensure, format_err};
+use tokio::sync::Mutex;
+
+// Workspace uses
+use zksync_crypto::{
+    circuit::{account::CircuitAccount, CircuitAccountTree},
+    convert::FeConvert,
+    franklin_crypto::bellman::pairing::ff::Field,
+    params::account_tree_depth,
+    Fr,
+};
+use zksync_storage::StorageProcessor;
+use zksync_types::{AccountId, AccountMap, BlockNumber, TokenId};
+
+// Local uses
+use super::types::{AccountProofResp, MerklePathNode};
+
+/// Account tree restored from the state of the verified block.
+struct VerifiedTree {
+    block_number: BlockNumber,
+    root_hash: Fr,
+    tree: Arc<CircuitAccountTree>,
+}
+
+/// Account tree of the latest verified block, restored on demand.
+#[derive(Clone, Default)]
+pub struct VerifiedAccountTree(Arc<Mutex<Option<VerifiedTree>>>);
+
+impl VerifiedAccountTree {
+    /// Builds the Merkle proof of the account balance, or returns `None` if the account
+    /// doesn't exist in the latest verified block.
+    pub async fn account_proof(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        account_id: AccountId,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<AccountProofResp>> {
+        let (block_number, root_hash, tree) = self.load(storage).await?;
+        // Building the paths calculates the missing hashes of the tree.
+        tokio::task::spawn_blocking(move || {
+            account_proof(&tree, block_number, root_hash, account_id, token_id)
+        })
+        .await
+        .map_err(|err| format_err!("Building the account proof panicked: {}", err))
+    }
+
+    /// Returns the tree of the latest verified block, restoring it if a new block
+    /// has been verified since the last call.
+    ///
+    /// The lock is held while the tree is restored, so the concurrent requests
+    /// don't restore it once again.
+    async fn load(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<(BlockNumber, Fr, Arc<CircuitAccountTree>)> {
+        let mut cached = self.0.lock().await;
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        if let Some(cached) = cached.as_ref() {
+            if cached.block_number == last_verified_block {
+                return Ok((cached.block_number, cached.root_hash, cached.tree.clone()));
+            }
+        }
+
+        let start = Instant::now();
+        let (block_number, accounts) = storage.chain().state_schema().load_verified_state().await?;
+        let expected_root_hash = storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await?
+            .ok_or_else(|| format_err!("Verified block {} is not stored", block_number))?
+            .root_hash;
+
+        let (tree, root_hash) = tokio::task::spawn_blocking(move || {
+            let tree = restore_tree(accounts);
+            let root_hash = tree.root_hash();
+            (tree, root_hash)
+        })
+        .await
+        .map_err(|err| format_err!("Restoring the account tree panicked: {}", err))?;
+        ensure!(
+            root_hash.to_bytes() == expected_root_hash,
+            "Root hash of the restored account tree doesn't match the one of the block {}",
+            block_number
+        );
+        metrics::histogram!("api.rpc.account_proof.restore_tree", start.elapsed());
+
+        let tree = Arc::new(tree);
+        *cached = Some(VerifiedTree {
+            block_number,
+            root_hash,
+            tree: tree.clone(),
+        });
+        Ok((block_number, root_hash, tree))
+    }
+}
+
+fn restore_tree(accounts: AccountMap) -> CircuitAccountTree {
+    let mut tree = CircuitAccountTree::new(account_tree_depth());
+    for (id, account) in accounts {
+        tree.insert(*id, CircuitAccount::from(account));
+    }
+    tree
+}
+
+fn account_proof(
+    tree: &CircuitAccountTree,
+    block_number: BlockNumber,
+    root_hash: Fr,
+    account_id: AccountId,
+    token_id: TokenId,
+) -> Option<AccountProofResp> {
+    let account = tree.get(*account_id)?;
+    let balance = account
+        .subtree
+        .get(*token_id)
+        .map(|balance| balance.value)
+        .unwrap_or_else(Fr::zero);
+    let balance_path = account
+        .subtree
+        .merkle_path(*token_id)
+        .into_iter()
+        .map(MerklePathNode::from)
+        .collect();
+    let account_path = tree
+        .merkle_path(*account_id)
+        .into_iter()
+        .map(MerklePathNode::from)
+        .collect();
+
+    Some(AccountProofResp {
+        block_number,
+        root_hash,
+        account_id,
+        token_id,
+        nonce: account.nonce,
+        pub_key_hash: account.pub_key_hash,
+        address: account.address,
+        balance,
+        balance_path,
+        account_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_crypto::{
+        circuit::account::Balance, franklin_crypto::bellman::pairing::ff::PrimeField,
+        params::balance_tree_depth,
+    };
+    use zksync_types::{Account, Address};
+
+    fn path(nodes: &[MerklePathNode]) -> Vec<(Fr, bool)> {
+        nodes
+            .iter()
+            .map(|node| (node.hash, node.is_right))
+            .collect()
+    }
+
+    #[test]
+    fn account_proof_is_valid() {
+        let mut accounts = AccountMap::default();
+        for id in 0..4u32 {
+            let mut account = Account::default_with_address(&Address::repeat_byte(id as u8 + 1));
+            account.set_balance(TokenId(0), BigUint::from(100u32 * (id + 1)));
+            account.set_balance(TokenId(5), BigUint::from(7u32));
+            accounts.insert(AccountId(id), account);
+        }
+        let tree = restore_tree(accounts);
+        let root_hash = tree.root_hash();
+
+        for &token_id in &[TokenId(0), TokenId(3)] {
+            let proof = account_proof(&tree, BlockNumber(1), root_hash, AccountId(2), token_id)
+                .expect("Account exists");
+            assert_eq!(proof.balance_path.len(), balance_tree_depth());
+            assert_eq!(proof.account_path.len(), account_tree_depth());
+
+            let account = tree.get(2).unwrap();
+            let balance = Balance {
+                value: proof.balance,
+            };
+            assert!(account
+                .subtree
+                .verify_proof(*token_id, balance, path(&proof.balance_path)));
+            assert!(tree.verify_proof(2, account.clone(), path(&proof.account_path)));
+        }
+
+        let proof = account_proof(&tree, BlockNumber(1), root_hash, AccountId(2), TokenId(0))
+            .expect("Account exists");
+        assert_eq!(proof.balance, Fr::from_str("300").unwrap());
+        // Accounts created after the verified block have no proof.
+        assert!(
+            account_proof(&tree, BlockNumber(1), root_hash, AccountId(10), TokenId(0)).is_none()
+        );
+    }
+}
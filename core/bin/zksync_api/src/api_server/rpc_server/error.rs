@@ -11,36 +11,72 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    /// Transactions of the batch pay non-zero fees in different tokens.
+    BatchFeeTokenMismatch = 106,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
     IncorrectEthSignature = 202,
     ChangePkNotAuthorized = 203,
+    MalformedEthSignature = 204,
+    UnsupportedEthSignatureType = 205,
+    /// Onchain `ChangePubKey` authorization isn't confirmed yet, the same transaction
+    /// is accepted once it is.
+    ChangePkAuthorizationNotConfirmed = 206,
 
     Other = 300,
     AccountCloseDisabled = 301,
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    ServiceOverloaded = 305,
+    RateLimited = 306,
+    Unauthorized = 307,
+    MethodNotAllowed = 308,
+    ServiceUnavailable = 309,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
     fn from(error: TxAddError) -> Self {
         match error {
             TxAddError::NonceMismatch => Self::NonceMismatch,
-            TxAddError::IncorrectTx(_) => Self::IncorrectTx,
+            TxAddError::ReplacementUnderpriced => Self::NonceMismatch,
+            TxAddError::NonceTooHigh => Self::NonceMismatch,
+            TxAddError::TooManyAccountTxs => Self::RateLimited,
+            TxAddError::MempoolFull => Self::ServiceOverloaded,
+            TxAddError::IncorrectTx { .. } => Self::IncorrectTx,
             TxAddError::TxFeeTooLow => Self::FeeTooLow,
             TxAddError::TxBatchFeeTooLow => Self::FeeTooLow,
             TxAddError::MissingEthSignature => Self::MissingEthSignature,
             TxAddError::EIP1271SignatureVerificationFail => Self::EIP1271SignatureVerificationFail,
-            TxAddError::IncorrectEthSignature => Self::IncorrectEthSignature,
+            TxAddError::IncorrectEthSignature { .. } => Self::IncorrectEthSignature,
+            // New signature errors are reported with the old codes to keep the clients working.
+            TxAddError::SignerMismatch { .. } => Self::IncorrectEthSignature,
+            TxAddError::Eip1271Rejected => Self::IncorrectEthSignature,
+            TxAddError::MalformedSignature => Self::MalformedEthSignature,
+            TxAddError::UnsupportedSignatureType => Self::UnsupportedEthSignatureType,
             TxAddError::ChangePkNotAuthorized => Self::ChangePkNotAuthorized,
+            TxAddError::ChangePkAuthorizationNotConfirmed => {
+                Self::ChangePkAuthorizationNotConfirmed
+            }
             TxAddError::Other => Self::Other,
             TxAddError::DbError => Self::Other,
             TxAddError::EmptyBatch => Self::Other,
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
+            TxAddError::BatchFeeTokenMismatch(..) => Self::BatchFeeTokenMismatch,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::NotYetValid => Self::IncorrectTx,
+            TxAddError::SignatureCheckUnavailable => Self::Other,
+            TxAddError::SignatureCheckTimeout => Self::Other,
+            TxAddError::EthereumNodeUnavailable => Self::Other,
+            TxAddError::ServiceOverloaded => Self::ServiceOverloaded,
+            TxAddError::AddressRejected { .. } => Self::Other,
+            TxAddError::ScreeningUnavailable => Self::Other,
+            TxAddError::Draining => Self::ServiceUnavailable,
+            TxAddError::WithdrawalLimitExceeded { .. } => Self::RateLimited,
+            TxAddError::WithdrawalDelayed { .. } => Self::Other,
+            TxAddError::BatchTx { error, .. } => Self::from(*error),
         }
     }
 }
@@ -51,6 +87,27 @@ impl From<RpcErrorCodes> for ErrorCode {
     }
 }
 
+/// The numeric code is kept for compatibility, the machine-readable error code and
+/// the HTTP status are passed in the `data` field. Errors of the batch transactions
+/// also pass the index and the hash of the rejected transaction there.
+impl From<TxAddError> for jsonrpc_core::Error {
+    fn from(inner: TxAddError) -> Self {
+        let mut data = serde_json::json!({
+            "errorCode": inner.to_error_code(),
+            "httpStatus": inner.to_http_status().as_u16(),
+        });
+        if let Some((index, tx_hash)) = inner.batch_tx_location() {
+            data["batchTxIndex"] = index.into();
+            data["batchTxHash"] = tx_hash.to_string().into();
+        }
+        Self {
+            message: inner.to_string(),
+            code: RpcErrorCodes::from(inner).into(),
+            data: Some(data),
+        }
+    }
+}
+
 impl From<SubmitError> for jsonrpc_core::Error {
     fn from(inner: SubmitError) -> Self {
         match inner {
@@ -72,11 +129,7 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message,
                 data: None,
             },
-            SubmitError::TxAdd(inner) => Self {
-                code: RpcErrorCodes::from(inner).into(),
-                message: inner.to_string(),
-                data: None,
-            },
+            SubmitError::TxAdd(inner) => inner.into(),
             SubmitError::Toggle2FA(inner) => Self {
                 code: RpcErrorCodes::Toggle2FA.into(),
                 message: inner.to_string(),
@@ -49,6 +49,8 @@ fn get_call_with_ip_if_needed(
     let methods_with_ip: HashMap<&'static str, MethodWithIpDescription> = HashMap::from_iter([
         ("tx_submit", MethodWithIpDescription::new(1, 4)),
         ("submit_txs_batch", MethodWithIpDescription::new(1, 3)),
+        ("simulate_tx", MethodWithIpDescription::new(1, 3)),
+        ("simulate_txs_batch", MethodWithIpDescription::new(1, 3)),
         ("get_tx_fee", MethodWithIpDescription::new(3, 4)),
         (
             "get_txs_batch_fee_in_wei",
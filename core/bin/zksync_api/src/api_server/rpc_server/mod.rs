@@ -3,15 +3,17 @@ use std::time::Instant;
 
 // External uses
 use futures::channel::mpsc;
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
+use serde::Serialize;
 use tokio::task::JoinHandle;
 
 // Workspace uses
 use zksync_config::configs::api::{CommonApiConfig, JsonRpcConfig, TokenConfig};
 use zksync_storage::{
     chain::{
-        block::records::StorageBlockDetails, operations::records::StoredExecutedPriorityOperation,
+        account::StoredAccountState, block::records::StorageBlockDetails,
+        operations::records::StoredExecutedPriorityOperation,
         operations_ext::records::TxReceiptResponse,
     },
     ConnectionPool, StorageProcessor,
@@ -20,14 +22,24 @@ use zksync_types::{tx::TxHash, Address, BlockNumber};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
 // Local uses
-use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::AsyncLruCache};
+use crate::{
+    api_server::{
+        access_control::{AccessControl, AccessControlMiddleware, RequestMeta},
+        committed_state_cache::CommittedStateCache,
+        response_signer::{ResponseSignature, ResponseSigner},
+    },
+    signature_checker::VerifySignatureRequest,
+    utils::shared_lru_cache::AsyncLruCache,
+};
 
+mod account_proof;
 pub mod error;
 mod ip_insert_middleware;
 mod rpc_impl;
 mod rpc_trait;
 pub mod types;
 
+use self::account_proof::VerifiedAccountTree;
 pub use self::rpc_trait::Rpc;
 use self::types::*;
 use super::tx_sender::TxSender;
@@ -40,6 +52,9 @@ pub struct RpcApp {
     cache_of_executed_priority_operations: AsyncLruCache<u32, StoredExecutedPriorityOperation>,
     cache_of_transaction_receipts: AsyncLruCache<Vec<u8>, TxReceiptResponse>,
     cache_of_complete_withdrawal_tx_hashes: AsyncLruCache<TxHash, String>,
+    verified_account_tree: VerifiedAccountTree,
+    committed_states: Option<CommittedStateCache>,
+    response_signer: Option<ResponseSigner>,
 
     pub confirmations_for_eth_event: u64,
 
@@ -47,6 +62,7 @@ pub struct RpcApp {
 }
 
 impl RpcApp {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
@@ -55,6 +71,7 @@ impl RpcApp {
         token_config: &TokenConfig,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        committed_states: Option<CommittedStateCache>,
     ) -> Self {
         let api_requests_caches_size = config.caches_size;
 
@@ -71,6 +88,9 @@ impl RpcApp {
             cache_of_executed_priority_operations: AsyncLruCache::new(api_requests_caches_size),
             cache_of_transaction_receipts: AsyncLruCache::new(api_requests_caches_size),
             cache_of_complete_withdrawal_tx_hashes: AsyncLruCache::new(api_requests_caches_size),
+            verified_account_tree: VerifiedAccountTree::default(),
+            committed_states,
+            response_signer: ResponseSigner::from_config(config),
 
             confirmations_for_eth_event,
 
@@ -92,6 +112,22 @@ impl RpcApp {
             .map_err(|_| Error::internal_error())
     }
 
+    /// Signs the response of the method if the node has the signing key.
+    fn sign_response<T: Serialize>(
+        &self,
+        method: &str,
+        response: &T,
+    ) -> Result<Option<ResponseSignature>> {
+        self.response_signer
+            .as_ref()
+            .map(|signer| signer.sign(method, response))
+            .transpose()
+            .map_err(|err| {
+                vlog::warn!("Failed to sign the {} response: {}", method, err);
+                Error::internal_error()
+            })
+    }
+
     // cache access functions
     async fn get_executed_priority_operation(
         &self,
@@ -183,12 +219,34 @@ impl RpcApp {
     async fn get_account_state(&self, address: Address) -> Result<AccountStateInfo> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
-        let account_info = storage
-            .chain()
-            .account_schema()
-            .account_state_by_address(address)
-            .await
-            .map_err(|_| Error::internal_error())?;
+        let account_info = if let Some(committed_states) = &self.committed_states {
+            let committed = committed_states
+                .committed_state(address)
+                .await
+                .map_err(|_| Error::internal_error())?;
+            // Account can't be verified before it's committed.
+            let verified = match &committed {
+                Some((account_id, _)) => storage
+                    .chain()
+                    .account_schema()
+                    .last_verified_state_for_account(*account_id)
+                    .await
+                    .map_err(|_| Error::internal_error())?
+                    .map(|account| (*account_id, account)),
+                None => None,
+            };
+            StoredAccountState {
+                committed,
+                verified,
+            }
+        } else {
+            storage
+                .chain()
+                .account_schema()
+                .account_state_by_address(address)
+                .await
+                .map_err(|_| Error::internal_error())?
+        };
 
         let mut result = AccountStateInfo {
             account_id: None,
@@ -266,8 +324,10 @@ pub fn start_rpc_server(
     token_config: &TokenConfig,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    committed_states: Option<CommittedStateCache>,
 ) -> JoinHandle<()> {
     let addr = config.http_bind_addr();
+    let access_control = AccessControl::new(common_api_config, connection_pool.clone());
     let rpc_app = RpcApp::new(
         connection_pool,
         sign_verify_request_sender,
@@ -276,15 +336,26 @@ pub fn start_rpc_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        committed_states,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_sender);
-        let mut io = IoHandler::new();
+        let mut io = MetaIoHandler::with_middleware(AccessControlMiddleware(access_control));
         rpc_app.extend(&mut io);
 
-        let server = ServerBuilder::new(io)
+        // Peer address of the request is not available to the meta extractor, so the client IP
+        // can't be taken from the forwarding headers and the anonymous clients share the limit.
+        let server =
+            ServerBuilder::with_meta_extractor(io, |request: &hyper::Request<hyper::Body>| {
+                RequestMeta::from_headers(None, &[], |name| {
+                    request
+                        .headers()
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                })
+            })
             .threads(super::THREADS_PER_SERVER)
             .request_middleware(IpInsertMiddleWare {})
             .start_http(&addr)
@@ -297,7 +368,11 @@ pub fn start_rpc_server(
 #[cfg(test)]
 mod test {
     use serde::{Deserialize, Serialize};
-    use zksync_types::TxFeeTypes;
+    use zksync_types::{
+        tx::{error::TxAddError, BatchMessageFormats, TransactionError, TxHash},
+        withdrawal_limits::WithdrawalLimitKind,
+        Address, TokenId, TxFeeTypes, H256,
+    };
 
     #[test]
     fn tx_fee_type_serialization() {
@@ -327,4 +402,164 @@ mod test {
             assert_eq!(query, de);
         }
     }
+
+    /// Transaction errors of the specific types are not exported, so the error is deserialized.
+    fn wrong_signature() -> TransactionError {
+        serde_json::from_str(r#"{"TransferError":"WrongSignature"}"#).unwrap()
+    }
+
+    #[test]
+    fn tx_add_error_serialization() {
+        let cases = vec![
+            (
+                TxAddError::IncorrectEthSignature {
+                    recovered: Some(Address::repeat_byte(0x02)),
+                    expected: Address::repeat_byte(0x01),
+                    message_hash: H256::repeat_byte(0x03),
+                    attempted_formats: BatchMessageFormats::all(),
+                },
+                202,
+                "INCORRECT_ETH_SIGNATURE",
+                400,
+            ),
+            (
+                TxAddError::IncorrectTx {
+                    account: Address::repeat_byte(0x01),
+                    reason: wrong_signature(),
+                },
+                103,
+                "INCORRECT_TX",
+                400,
+            ),
+            // Specific signature errors keep the old numeric codes.
+            (
+                TxAddError::SignerMismatch {
+                    expected: Address::repeat_byte(0x01),
+                    recovered: Address::repeat_byte(0x02),
+                },
+                202,
+                "SIGNER_MISMATCH",
+                400,
+            ),
+            (TxAddError::Eip1271Rejected, 202, "EIP1271_REJECTED", 400),
+            (
+                TxAddError::BatchFeeTokenMismatch(TokenId(0), TokenId(1)),
+                106,
+                "BATCH_FEE_TOKEN_MISMATCH",
+                400,
+            ),
+            // Underpriced replacements are reported with the nonce error code.
+            (
+                TxAddError::ReplacementUnderpriced,
+                101,
+                "REPLACEMENT_UNDERPRICED",
+                400,
+            ),
+            (TxAddError::NonceTooHigh, 101, "NONCE_TOO_HIGH", 400),
+            (
+                TxAddError::TooManyAccountTxs,
+                306,
+                "TOO_MANY_ACCOUNT_TXS",
+                400,
+            ),
+            (TxAddError::MempoolFull, 305, "MEMPOOL_FULL", 429),
+            (
+                TxAddError::AddressRejected {
+                    address: Address::repeat_byte(0x01),
+                },
+                300,
+                "ADDRESS_REJECTED",
+                403,
+            ),
+            (
+                TxAddError::ScreeningUnavailable,
+                300,
+                "SCREENING_UNAVAILABLE",
+                503,
+            ),
+            (
+                TxAddError::WithdrawalLimitExceeded {
+                    token: TokenId(1),
+                    limit: WithdrawalLimitKind::AccountDaily,
+                },
+                306,
+                "WITHDRAWAL_LIMIT_EXCEEDED",
+                429,
+            ),
+            (
+                TxAddError::ChangePkNotAuthorized,
+                203,
+                "CHANGE_PK_NOT_AUTHORIZED",
+                400,
+            ),
+            (
+                TxAddError::EthereumNodeUnavailable,
+                300,
+                "ETHEREUM_NODE_UNAVAILABLE",
+                503,
+            ),
+        ];
+        for (error, code, error_code, http_status) in cases {
+            let rpc_error = jsonrpc_core::Error::from(error.clone());
+            assert_eq!(rpc_error.code.code(), code);
+            assert_eq!(rpc_error.message, error.to_string());
+            assert_eq!(
+                rpc_error.data,
+                Some(serde_json::json!({
+                    "errorCode": error_code,
+                    "httpStatus": http_status,
+                }))
+            );
+        }
+    }
+
+    /// Errors of the batch transactions keep the codes of the wrapped error and point
+    /// to the rejected transaction.
+    #[test]
+    fn batch_tx_error_serialization() {
+        let tx_hash = TxHash::from_slice(&[0x01; 32]).unwrap();
+        let error = TxAddError::batch_tx(
+            2,
+            tx_hash,
+            TxAddError::SignerMismatch {
+                expected: Address::repeat_byte(0x01),
+                recovered: Address::repeat_byte(0x02),
+            },
+        );
+        let rpc_error = jsonrpc_core::Error::from(error.clone());
+        assert_eq!(rpc_error.code.code(), 202);
+        assert_eq!(rpc_error.message, error.reason().to_string());
+        assert_eq!(
+            rpc_error.data,
+            Some(serde_json::json!({
+                "errorCode": "SIGNER_MISMATCH",
+                "httpStatus": 400,
+                "batchTxIndex": 2,
+                "batchTxHash": tx_hash.to_string(),
+            }))
+        );
+    }
+
+    /// Messages of the errors only include the context the client can act on.
+    #[test]
+    fn tx_add_error_messages() {
+        let error = TxAddError::IncorrectEthSignature {
+            recovered: None,
+            expected: Address::repeat_byte(0x01),
+            message_hash: H256::zero(),
+            attempted_formats: BatchMessageFormats::all(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Eth signature is incorrect (tried: structured, legacy)"
+        );
+        let error = TxAddError::IncorrectTx {
+            account: Address::repeat_byte(0x01),
+            reason: wrong_signature(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Tx is incorrect: L2 signature is incorrect"
+        );
+    }
 }
@@ -13,14 +13,20 @@ use zksync_api_types::{
     TxWithSignature,
 };
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_mempool::AccountNonces;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, TxFeeTypes, ZkSyncTx,
+    AccountId, Address, BatchFee, BlockNumber, Fee, Token, TokenId, TokenLike, TxFeeTypes,
+    ZkSyncTx,
 };
 // Local uses
 use crate::{
     api_server::{
-        helpers::get_depositing, rpc_server::error::RpcErrorCodes, tx_sender::SubmitError,
+        helpers::{get_account_nonces, get_depositing},
+        response_signer::{ResponseSigner, ResponseSigners},
+        rpc_server::error::RpcErrorCodes,
+        tx_sender::SubmitError,
+        tx_simulator::TxSimulationResult,
     },
     fee_ticker::TokenPriceRequestType,
 };
@@ -72,14 +78,80 @@ impl RpcApp {
             None
         };
 
-        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "account_info");
-        Ok(AccountInfoResp {
+        let mut response = AccountInfoResp {
             address,
             id: account_state.account_id,
             committed: account_state.committed,
             verified: account_state.verified,
             depositing,
             account_type,
+            signature: None,
+        };
+        response.signature = self.sign_response("account_info", &response)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "account_info");
+        Ok(response)
+    }
+
+    pub async fn _impl_account_info_at_block(
+        self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> Result<AccountInfoAtBlockResp> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                Error::internal_error()
+            })?;
+        if block_number > last_committed_block {
+            return Err(Error::invalid_params(format!(
+                "Block {} is not committed yet",
+                block_number
+            )));
+        }
+
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                Error::internal_error()
+            })?;
+        let account = match account_id {
+            Some(account_id) => storage
+                .chain()
+                .account_schema()
+                .account_state_at_block(account_id, block_number)
+                .await
+                .map_err(|err| {
+                    vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                    Error::internal_error()
+                })?,
+            None => None,
+        };
+        let state = match account {
+            Some(account) => Some(
+                ResponseAccountState::try_restore(&mut storage, &self.tx_sender.tokens, account)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "account_info_at_block");
+        Ok(AccountInfoAtBlockResp {
+            address,
+            id: account_id,
+            block_number,
+            state,
         })
     }
 
@@ -114,8 +186,7 @@ impl RpcApp {
     pub async fn _impl_tx_info(self, tx_hash: TxHash) -> Result<TransactionInfoResp> {
         let start = Instant::now();
         let stored_receipt = self.get_tx_receipt(tx_hash).await?;
-        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "tx_info");
-        Ok(if let Some(stored_receipt) = stored_receipt {
+        let mut response = if let Some(stored_receipt) = stored_receipt {
             TransactionInfoResp {
                 executed: true,
                 success: Some(stored_receipt.success),
@@ -125,6 +196,7 @@ impl RpcApp {
                     committed: true,
                     verified: stored_receipt.verified,
                 }),
+                signature: None,
             }
         } else {
             TransactionInfoResp {
@@ -132,8 +204,17 @@ impl RpcApp {
                 success: None,
                 fail_reason: None,
                 block: None,
+                signature: None,
             }
-        })
+        };
+        response.signature = self.sign_response("tx_info", &response)?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "tx_info");
+        Ok(response)
+    }
+
+    pub fn _impl_get_response_signers(&self) -> Result<ResponseSigners> {
+        Ok(ResponseSigner::signers(self.response_signer.as_ref()))
     }
 
     #[allow(deprecated)]
@@ -203,6 +284,36 @@ impl RpcApp {
         result.map_err(Error::from)
     }
 
+    pub async fn _impl_simulate_tx(
+        self,
+        tx: Box<ZkSyncTx>,
+        signature: Box<TxEthSignatureVariant>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxSimulationResult> {
+        let start = Instant::now();
+        let result = self
+            .tx_sender
+            .simulate_tx(*tx, *signature, extracted_request_metadata)
+            .await;
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "simulate_tx");
+        result.map_err(Error::from)
+    }
+
+    pub async fn _impl_simulate_txs_batch(
+        self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxSimulationResult> {
+        let start = Instant::now();
+        let result = self
+            .tx_sender
+            .simulate_txs_batch(txs, eth_signatures, extracted_request_metadata)
+            .await;
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "simulate_txs_batch");
+        result.map_err(Error::from)
+    }
+
     pub async fn _impl_contract_address(self) -> Result<ContractAddressResp> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
@@ -323,7 +434,7 @@ impl RpcApp {
         addresses: Vec<Address>,
         token: TokenLike,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<TotalFee> {
+    ) -> Result<BatchFee> {
         let start = Instant::now();
         if tx_types.len() != addresses.len() {
             return Err(Error {
@@ -374,9 +485,7 @@ impl RpcApp {
         };
 
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_txs_batch_fee_in_wei");
-        Ok(TotalFee {
-            total_fee: fee.total_fee,
-        })
+        Ok(fee)
     }
 
     pub async fn _impl_get_token_price(self, token: TokenLike) -> Result<BigDecimal> {
@@ -451,4 +560,67 @@ impl RpcApp {
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_nft_id_by_tx_hash");
         Ok(response)
     }
+
+    pub async fn _impl_account_proof(
+        self,
+        address: Address,
+        token_like: TokenLike,
+    ) -> Result<Option<AccountProofResp>> {
+        let start = Instant::now();
+
+        let mut storage = self.access_storage().await?;
+        let token = self
+            .tx_sender
+            .tokens
+            .get_token(&mut storage, token_like.clone())
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {:?}", err, token_like);
+                Error::internal_error()
+            })?
+            .ok_or_else(|| Error::invalid_params("Token not found"))?;
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                Error::internal_error()
+            })?;
+
+        let response = match account_id {
+            Some(account_id) => self
+                .verified_account_tree
+                .account_proof(&mut storage, account_id, token.id)
+                .await
+                .map_err(|err| {
+                    vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                    Error::internal_error()
+                })?,
+            None => None,
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "account_proof");
+        Ok(response)
+    }
+
+    pub async fn _impl_account_nonces(self, address: Address) -> Result<AccountNonces> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {}", err, address);
+                Error::internal_error()
+            })?;
+        drop(storage);
+
+        let result = get_account_nonces(&self.tx_sender.mempool_tx_sender, account_id).await;
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "account_nonces");
+        result.map_err(Error::from)
+    }
 }
@@ -14,13 +14,15 @@ use zksync_api_types::{
     TxWithSignature,
 };
 use zksync_crypto::params::ZKSYNC_VERSION;
+use zksync_mempool::AccountNonces;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, ZkSyncTx,
+    AccountId, Address, BatchFee, BlockNumber, Fee, Token, TokenId, TokenLike, ZkSyncTx,
 };
 
 // Local uses
 use super::{types::*, RpcApp};
+use crate::api_server::{response_signer::ResponseSigners, tx_simulator::TxSimulationResult};
 
 pub type BoxFutureResult<T> = BoxFuture<Result<T>>;
 
@@ -36,6 +38,14 @@ pub trait Rpc {
     #[rpc(name = "account_info", returns = "AccountInfoResp")]
     fn account_info(&self, addr: Address) -> BoxFutureResult<AccountInfoResp>;
 
+    /// State of the account after the committed block, for the point-in-time balances.
+    #[rpc(name = "account_info_at_block", returns = "AccountInfoAtBlockResp")]
+    fn account_info_at_block(
+        &self,
+        addr: Address,
+        block_number: BlockNumber,
+    ) -> BoxFutureResult<AccountInfoAtBlockResp>;
+
     #[rpc(name = "ethop_info", returns = "ETHOpInfoResp")]
     fn ethop_info(&self, serial_id: u32) -> BoxFutureResult<ETHOpInfoResp>;
 
@@ -59,6 +69,25 @@ pub trait Rpc {
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<Vec<TxHash>>;
 
+    /// Checks the transaction and simulates its execution against the latest committed state,
+    /// without sending it to the mempool.
+    #[rpc(name = "simulate_tx", returns = "TxSimulationResult")]
+    fn simulate_tx(
+        &self,
+        tx: Box<ZkSyncTx>,
+        signature: Box<TxEthSignatureVariant>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> BoxFutureResult<TxSimulationResult>;
+
+    /// Batch version of the `simulate_tx`, the batch is executed as a whole.
+    #[rpc(name = "simulate_txs_batch", returns = "TxSimulationResult")]
+    fn simulate_txs_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> BoxFutureResult<TxSimulationResult>;
+
     #[rpc(name = "contract_address", returns = "ContractAddressResp")]
     fn contract_address(&self) -> BoxFutureResult<ContractAddressResp>;
 
@@ -77,14 +106,14 @@ pub trait Rpc {
     ) -> BoxFutureResult<Fee>;
 
     // _addresses argument is left for the backward compatibility.
-    #[rpc(name = "get_txs_batch_fee_in_wei", returns = "TotalFee")]
+    #[rpc(name = "get_txs_batch_fee_in_wei", returns = "BatchFee")]
     fn get_txs_batch_fee_in_wei(
         &self,
         tx_types: Vec<ApiTxFeeTypes>,
         _addresses: Vec<Address>,
         token_like: TokenLike,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> BoxFutureResult<TotalFee>;
+    ) -> BoxFutureResult<BatchFee>;
 
     #[rpc(name = "get_token_price", returns = "BigDecimal")]
     fn get_token_price(&self, token_like: TokenLike) -> BoxFutureResult<BigDecimal>;
@@ -110,6 +139,23 @@ pub trait Rpc {
 
     #[rpc(name = "get_nft_id_by_tx_hash", returns = "Option<TokenId>")]
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>>;
+
+    /// Merkle proof of the account balance against the root of the latest verified block.
+    #[rpc(name = "account_proof", returns = "Option<AccountProofResp>")]
+    fn account_proof(
+        &self,
+        address: Address,
+        token_like: TokenLike,
+    ) -> BoxFutureResult<Option<AccountProofResp>>;
+
+    /// Committed nonce of the account, the nonce its next transaction is expected to use
+    /// and the nonces of its pending transactions.
+    #[rpc(name = "account_nonces", returns = "AccountNonces")]
+    fn account_nonces(&self, addr: Address) -> BoxFutureResult<AccountNonces>;
+
+    /// Addresses of the keys the `account_info` and `tx_info` responses are signed with.
+    #[rpc(name = "get_response_signers", returns = "ResponseSigners")]
+    fn get_response_signers(&self) -> Result<ResponseSigners>;
 }
 
 impl Rpc for RpcApp {
@@ -117,6 +163,14 @@ impl Rpc for RpcApp {
         spawn!(self._impl_account_info(addr))
     }
 
+    fn account_info_at_block(
+        &self,
+        addr: Address,
+        block_number: BlockNumber,
+    ) -> BoxFutureResult<AccountInfoAtBlockResp> {
+        spawn!(self._impl_account_info_at_block(addr, block_number))
+    }
+
     fn ethop_info(&self, serial_id: u32) -> BoxFutureResult<ETHOpInfoResp> {
         spawn!(self._impl_ethop_info(serial_id))
     }
@@ -146,6 +200,26 @@ impl Rpc for RpcApp {
         spawn!(self._impl_submit_txs_batch(txs, eth_signatures, meta))
     }
 
+    // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
+    fn simulate_tx(
+        &self,
+        tx: Box<ZkSyncTx>,
+        signature: Box<TxEthSignatureVariant>,
+        meta: Option<RequestMetadata>,
+    ) -> BoxFutureResult<TxSimulationResult> {
+        spawn!(self._impl_simulate_tx(tx, signature, meta))
+    }
+
+    // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
+    fn simulate_txs_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        meta: Option<RequestMetadata>,
+    ) -> BoxFutureResult<TxSimulationResult> {
+        spawn!(self._impl_simulate_txs_batch(txs, eth_signatures, meta))
+    }
+
     fn contract_address(&self) -> BoxFutureResult<ContractAddressResp> {
         spawn!(self._impl_contract_address())
     }
@@ -172,7 +246,7 @@ impl Rpc for RpcApp {
         addresses: Vec<Address>,
         token_like: TokenLike,
         meta: Option<RequestMetadata>,
-    ) -> BoxFutureResult<TotalFee> {
+    ) -> BoxFutureResult<BatchFee> {
         spawn!(self._impl_get_txs_batch_fee_in_wei(tx_types, addresses, token_like, meta))
     }
 
@@ -210,4 +284,20 @@ impl Rpc for RpcApp {
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>> {
         spawn!(self._impl_get_nft_id_by_tx_hash(tx_hash))
     }
+
+    fn account_proof(
+        &self,
+        address: Address,
+        token_like: TokenLike,
+    ) -> BoxFutureResult<Option<AccountProofResp>> {
+        spawn!(self._impl_account_proof(address, token_like))
+    }
+
+    fn account_nonces(&self, addr: Address) -> BoxFutureResult<AccountNonces> {
+        spawn!(self._impl_account_nonces(addr))
+    }
+
+    fn get_response_signers(&self) -> Result<ResponseSigners> {
+        self._impl_get_response_signers()
+    }
 }
@@ -9,12 +9,19 @@ use zksync_api_types::v02::{
     account::{DepositingAccountBalances, EthAccountType},
     token::NFT,
 };
-use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
+use zksync_crypto::{
+    params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL},
+    serialization::FrSerde,
+    Fr,
+};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
+use zksync_types::{Account, AccountId, Address, BlockNumber, Nonce, PubKeyHash, TokenId};
 use zksync_utils::BigUintSerdeWrapper;
 
+// Local uses
+use crate::api_server::response_signer::ResponseSignature;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseAccountState {
@@ -93,6 +100,20 @@ pub struct AccountInfoResp {
     pub committed: ResponseAccountState,
     pub verified: ResponseAccountState,
     pub account_type: Option<EthAccountType>,
+    /// Set if the node signs the responses, see `response_signer` module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ResponseSignature>,
+}
+
+/// State of the account after the block, reconstructed from the history of its updates.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfoAtBlockResp {
+    pub address: Address,
+    pub id: Option<AccountId>,
+    pub block_number: BlockNumber,
+    /// `None` if the account didn't exist after the block.
+    pub state: Option<ResponseAccountState>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -110,6 +131,9 @@ pub struct TransactionInfoResp {
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
     pub block: Option<BlockInfo>,
+    /// Set if the node signs the responses, see `response_signer` module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ResponseSignature>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -126,6 +150,50 @@ pub struct ContractAddressResp {
     pub gov_contract: String,
 }
 
+/// Node on the Merkle path from the leaf to the root.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MerklePathNode {
+    /// Hash of the sibling node.
+    #[serde(with = "FrSerde")]
+    pub hash: Fr,
+    /// Whether the node on the path is the right child, i.e. the sibling is on the left.
+    pub is_right: bool,
+}
+
+impl From<(Fr, bool)> for MerklePathNode {
+    fn from((hash, is_right): (Fr, bool)) -> Self {
+        Self { hash, is_right }
+    }
+}
+
+/// Merkle proof of the account balance against the state root of the verified block.
+///
+/// Leaf values are given in the circuit representation, so the proof can be checked
+/// by hashing the balance up to the root of the account balances subtree, then the account
+/// leaf up to the state root.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProofResp {
+    pub block_number: BlockNumber,
+    #[serde(with = "FrSerde")]
+    pub root_hash: Fr,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    #[serde(with = "FrSerde")]
+    pub nonce: Fr,
+    #[serde(with = "FrSerde")]
+    pub pub_key_hash: Fr,
+    #[serde(with = "FrSerde")]
+    pub address: Fr,
+    #[serde(with = "FrSerde")]
+    pub balance: Fr,
+    /// Path from the balance leaf to the root of the account balances subtree.
+    pub balance_path: Vec<MerklePathNode>,
+    /// Path from the account leaf to the state root.
+    pub account_path: Vec<MerklePathNode>,
+}
+
 /// The metadata of the JSON-RPC call retrieved from the HTTP request of the call
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestMetadata {
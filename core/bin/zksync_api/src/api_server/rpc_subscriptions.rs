@@ -19,8 +19,11 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 // Local uses
 use crate::fee_ticker::FeeTicker;
 use crate::{
+    api_server::committed_state_cache::CommittedStateCache,
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
-    api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
+    api_server::rpc_server::types::{
+        BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    },
     signature_checker::VerifySignatureRequest,
 };
 
@@ -82,6 +85,25 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "block",
+        subscribe,
+        name = "block_subscribe",
+        alias("block_sub")
+    )]
+    fn subscribe_new_blocks(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<BlockInfo>,
+        action_type: ActionType,
+    );
+    #[pubsub(subscription = "block", unsubscribe, name = "block_unsubscribe")]
+    fn unsubscribe_new_blocks(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -170,6 +192,32 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_new_blocks(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<BlockInfo>,
+        action: ActionType,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::NewBlocks { action, subscriber },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_new_blocks(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -188,6 +236,7 @@ pub fn start_ws_server(
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    committed_states: Option<CommittedStateCache>,
 ) -> JoinHandle<()> {
     let addr = config.ws_bind_addr();
 
@@ -209,6 +258,7 @@ pub fn start_ws_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        committed_states,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
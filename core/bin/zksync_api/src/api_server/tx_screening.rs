@@ -0,0 +1,303 @@
+//! Screening of the addresses of the incoming transactions.
+//!
+//! Transactions are screened after their signatures are verified and before they are sent
+//! to the mempool, so the operator may reject the transactions involving the addresses it's not
+//! allowed to serve. Screening is done by the `TxScreener` chosen by `CommonApiConfig::tx_screening_mode`:
+//! either against the allow or deny list stored in the database (managed via the admin API),
+//! or by the external screening service.
+//!
+//! Screening fails closed: if the addresses could not be checked, the transaction is rejected
+//! with `TxAddError::ScreeningUnavailable` and can be resubmitted later.
+
+// Built-in uses
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_config::configs::api::{CommonApiConfig, TxScreeningMode};
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    tx::{error::TxAddError, TxHash},
+    Address, ZkSyncTx,
+};
+
+// Local uses
+
+/// Admission filter of the incoming transactions.
+#[async_trait::async_trait]
+pub trait TxScreener: Send + Sync {
+    /// Checks the addresses involved in the transaction, returns `TxAddError::AddressRejected`
+    /// if any of them is not allowed to transact.
+    async fn screen_tx(&self, tx: &ZkSyncTx, addresses: &[Address]) -> Result<(), TxAddError>;
+}
+
+/// Returns the addresses involved in the transaction: its Ethereum signer, the account
+/// it's sent from and the recipients, without duplicates.
+pub fn tx_addresses(tx: &ZkSyncTx, signer: Address) -> Vec<Address> {
+    let mut addresses = vec![signer, tx.account()];
+    match tx {
+        // The "recipient" of `ChangePubKey` is the new public key hash, not an address.
+        ZkSyncTx::ChangePubKey(_) => {}
+        ZkSyncTx::Swap(swap) => {
+            addresses.push(swap.orders.0.recipient_address);
+            addresses.push(swap.orders.1.recipient_address);
+        }
+        _ => addresses.extend(tx.to_account()),
+    }
+
+    let mut seen = HashSet::new();
+    addresses.retain(|address| seen.insert(*address));
+    addresses
+}
+
+/// Creates the screener for the configured screening mode, `None` if the screening is disabled.
+pub fn tx_screener_from_config(
+    pool: ConnectionPool,
+    config: &CommonApiConfig,
+) -> Option<Arc<dyn TxScreener>> {
+    match config.tx_screening_mode {
+        TxScreeningMode::Disabled => None,
+        TxScreeningMode::DenyList => Some(Arc::new(AddressListScreener::new(pool, false))),
+        TxScreeningMode::AllowList => Some(Arc::new(AddressListScreener::new(pool, true))),
+        TxScreeningMode::Http => {
+            let url = config
+                .tx_screening_url
+                .clone()
+                .expect("tx_screening_url is required for the http screening mode");
+            Some(Arc::new(HttpScreener::new(
+                url,
+                config.tx_screening_timeout(),
+            )))
+        }
+    }
+}
+
+/// Screener checking the addresses against the allow or deny list stored in the database.
+#[derive(Debug, Clone)]
+pub struct AddressListScreener {
+    pool: ConnectionPool,
+    /// Whether the allow list is used, the deny list is used otherwise.
+    allow_list: bool,
+}
+
+impl AddressListScreener {
+    pub fn new(pool: ConnectionPool, allow_list: bool) -> Self {
+        Self { pool, allow_list }
+    }
+}
+
+/// Returns the first of the `addresses` rejected by the list, given the ones of them found in the list.
+fn rejected_by_list(
+    addresses: &[Address],
+    listed: &HashSet<Address>,
+    allow_list: bool,
+) -> Option<Address> {
+    addresses
+        .iter()
+        .copied()
+        .find(|address| listed.contains(address) != allow_list)
+}
+
+#[async_trait::async_trait]
+impl TxScreener for AddressListScreener {
+    async fn screen_tx(&self, _tx: &ZkSyncTx, addresses: &[Address]) -> Result<(), TxAddError> {
+        let mut storage = self.pool.access_storage().await.map_err(|err| {
+            vlog::warn!("Failed to access storage: {}", err);
+            TxAddError::DbError
+        })?;
+        let listed = storage
+            .address_lists_schema()
+            .load_listed_addresses(addresses, self.allow_list)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Failed to load the screened addresses: {}", err);
+                TxAddError::DbError
+            })?
+            .into_iter()
+            .map(|listed| Address::from_slice(&listed.address))
+            .collect();
+
+        match rejected_by_list(addresses, &listed, self.allow_list) {
+            Some(address) => Err(TxAddError::AddressRejected { address }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Request sent to the screening service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningRequest {
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    pub addresses: Vec<Address>,
+}
+
+/// Decision of the screening service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningResponse {
+    pub allowed: bool,
+    /// Address the transaction is rejected because of, if the service reports it.
+    #[serde(default)]
+    pub rejected_address: Option<Address>,
+}
+
+impl ScreeningResponse {
+    fn into_result(self, addresses: &[Address]) -> Result<(), TxAddError> {
+        if self.allowed {
+            return Ok(());
+        }
+        // The signer is blamed if the service doesn't tell which address is rejected.
+        let address = self
+            .rejected_address
+            .or_else(|| addresses.first().copied())
+            .unwrap_or_default();
+        Err(TxAddError::AddressRejected { address })
+    }
+}
+
+/// Screener posting the addresses of the transaction to the external screening service.
+///
+/// The service is expected to respond with the `ScreeningResponse` JSON. Any other response,
+/// as well as the failed or timed out request, makes the transaction rejected.
+#[derive(Debug, Clone)]
+pub struct HttpScreener {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpScreener {
+    pub fn new(url: String, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to build reqwest::Client");
+        Self { client, url }
+    }
+
+    async fn request(&self, request: &ScreeningRequest) -> reqwest::Result<ScreeningResponse> {
+        self.client
+            .post(&self.url)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl TxScreener for HttpScreener {
+    async fn screen_tx(&self, tx: &ZkSyncTx, addresses: &[Address]) -> Result<(), TxAddError> {
+        let request = ScreeningRequest {
+            tx_hash: tx.hash(),
+            tx_type: tx.variance_name(),
+            addresses: addresses.to_vec(),
+        };
+        let response = self.request(&request).await.map_err(|err| {
+            vlog::warn!(
+                "Failed to screen the tx {}: {}",
+                request.tx_hash.to_string(),
+                err
+            );
+            metrics::increment_counter!("api.tx_screening.unavailable");
+            TxAddError::ScreeningUnavailable
+        })?;
+        response.into_result(addresses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use zksync_types::{
+        tx::{ChangePubKey, Transfer},
+        AccountId, Nonce, TokenId,
+    };
+
+    use super::*;
+
+    fn transfer(from: Address, to: Address) -> ZkSyncTx {
+        ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(1),
+            from,
+            to,
+            TokenId(0),
+            BigUint::from(100u32),
+            BigUint::from(1u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        )))
+    }
+
+    #[test]
+    fn addresses_of_tx() {
+        let (alice, bob, carol) = (
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+        );
+
+        assert_eq!(tx_addresses(&transfer(alice, bob), alice), vec![alice, bob]);
+        assert_eq!(tx_addresses(&transfer(alice, alice), alice), vec![alice]);
+        assert_eq!(
+            tx_addresses(&transfer(alice, bob), carol),
+            vec![carol, alice, bob]
+        );
+
+        let change_pubkey = ZkSyncTx::ChangePubKey(Box::new(ChangePubKey::new(
+            AccountId(1),
+            alice,
+            Default::default(),
+            TokenId(0),
+            BigUint::from(1u32),
+            Nonce(0),
+            Default::default(),
+            None,
+            None,
+        )));
+        assert_eq!(tx_addresses(&change_pubkey, alice), vec![alice]);
+    }
+
+    #[test]
+    fn address_lists() {
+        let (alice, bob) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let listed: HashSet<_> = vec![bob].into_iter().collect();
+
+        // Deny list rejects the listed addresses.
+        assert_eq!(rejected_by_list(&[alice], &listed, false), None);
+        assert_eq!(rejected_by_list(&[alice, bob], &listed, false), Some(bob));
+        // Allow list rejects the addresses not listed.
+        assert_eq!(rejected_by_list(&[bob], &listed, true), None);
+        assert_eq!(rejected_by_list(&[alice, bob], &listed, true), Some(alice));
+    }
+
+    #[test]
+    fn screening_response() {
+        let (alice, bob) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+
+        let response: ScreeningResponse = serde_json::from_str(r#"{"allowed":true}"#).unwrap();
+        assert!(response.into_result(&[alice, bob]).is_ok());
+
+        let response: ScreeningResponse = serde_json::from_value(serde_json::json!({
+            "allowed": false,
+            "rejectedAddress": bob,
+        }))
+        .unwrap();
+        assert!(matches!(
+            response.into_result(&[alice, bob]),
+            Err(TxAddError::AddressRejected { address }) if address == bob
+        ));
+
+        let response: ScreeningResponse = serde_json::from_str(r#"{"allowed":false}"#).unwrap();
+        assert!(matches!(
+            response.into_result(&[alice, bob]),
+            Err(TxAddError::AddressRejected { address }) if address == alice
+        ));
+    }
+}
@@ -6,6 +6,7 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
     str::FromStr,
+    sync::Arc,
 };
 
 // External uses
@@ -26,14 +27,16 @@ use zksync_api_types::{
     TxWithSignature,
 };
 use zksync_storage::misc::records::Subsidy;
+use zksync_storage::tx_rejections::records::NewTxRejection;
 use zksync_storage::{chain::account::records::EthAccountType, ConnectionPool};
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     tx::{
-        EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
-        TxEthSignatureVariant, TxHash,
+        EthBatchSignData, EthBatchSignatures, EthSignData, EthSigner, Order, SignedMessage,
+        SignedZkSyncTx, TxEthSignature, TxEthSignatureVariant, TxHash,
     },
-    AccountId, Address, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
+    AccountId, Address, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, Withdraw, ZkSyncTx,
+    H160,
 };
 use zksync_utils::{
     big_decimal_to_ratio, biguint_to_big_decimal, ratio_to_scaled_u64, scaled_big_decimal_to_ratio,
@@ -41,10 +44,17 @@ use zksync_utils::{
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        drain_mode::DrainMode,
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        rejection_writer::RejectionWriter,
+        tx_screening::{tx_addresses, tx_screener_from_config, TxScreener},
+        tx_simulator::{simulate_txs, TxSimulationResult},
+        withdrawal_limits::WithdrawalPolicy,
+    },
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
-        BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
+        BatchRequest, EthSignatureExemptions, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
         VerifySignatureRequest,
     },
     tx_error::Toggle2FAError,
@@ -72,16 +82,50 @@ pub struct TxSender {
     pub blocks: BlockDetailsCache,
     /// List of account IDs that do not have to pay fees for operations.
     pub fee_free_accounts: HashSet<AccountId>,
+    /// Types of transactions that are accepted without an Ethereum signature.
+    pub eth_sig_exemptions: EthSignatureExemptions,
     pub enforce_pubkey_change_fee: bool,
     // Limit the number of both transactions and Ethereum signatures per batch.
     pub max_number_of_transactions_per_batch: usize,
     pub max_number_of_authors_per_batch: usize,
+    /// Time a signature check request may wait for a free slot in the signature checker channel.
+    pub sign_check_enqueue_timeout: std::time::Duration,
+    /// Time the signature check may take, including the time the request waits in the queue.
+    pub sign_check_timeout: std::time::Duration,
+    /// Admission filter the verified transactions pass before they are sent to the mempool.
+    pub tx_screener: Option<Arc<dyn TxScreener>>,
+    /// New transactions are rejected in the drain mode.
+    pub drain_mode: DrainMode,
+    /// Whether the submitted withdrawals are handed off to the liquidity providers they are matched with.
+    pub fast_withdrawals_enabled: bool,
+    /// Limits the withdrawals are checked against before they are sent to the mempool.
+    pub withdrawal_policy: Option<WithdrawalPolicy>,
+    /// Stores the reasons of the rejected transactions in the background.
+    pub rejection_writer: RejectionWriter,
 
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
 }
 
+/// Transaction which passed the submission checks.
+struct CheckedTx {
+    tx: SignedZkSyncTx,
+    /// Set if the fee of the transaction is subsidized.
+    fee_data_for_subsidy: Option<ResponseFee>,
+    token: Token,
+}
+
+/// Batch which passed the submission checks.
+struct CheckedTxsBatch {
+    txs: Vec<SignedZkSyncTx>,
+    eth_signatures: Vec<TxEthSignature>,
+    /// Set if the fee of the batch is subsidized.
+    fee_data_for_subsidy: Option<ResponseBatchFee>,
+    /// Distinct tokens the fee of the batch is paid in.
+    token_fees_ids: Vec<TokenId>,
+}
+
 #[derive(Debug, Error)]
 pub enum SubmitError {
     #[error("Account close tx is disabled.")]
@@ -153,6 +197,14 @@ impl TxSender {
         let max_number_of_transactions_per_batch =
             config.max_number_of_transactions_per_batch as usize;
         let max_number_of_authors_per_batch = config.max_number_of_authors_per_batch as usize;
+        let tx_screener = tx_screener_from_config(connection_pool.clone(), config);
+        let drain_mode = DrainMode::with_updater(connection_pool.clone());
+        let rejection_writer = RejectionWriter::spawn(connection_pool.clone());
+        let withdrawal_policy = if config.withdrawal_limits_enabled {
+            Some(WithdrawalPolicy::new(connection_pool.clone()))
+        } else {
+            None
+        };
 
         Self {
             mempool_tx_sender,
@@ -167,21 +219,40 @@ impl TxSender {
             blocks: BlockDetailsCache::new(config.caches_size),
 
             fee_free_accounts: HashSet::from_iter(config.fee_free_accounts.clone()),
+            eth_sig_exemptions: EthSignatureExemptions::new(
+                config.eth_sig_exempt_tx_types.iter().copied(),
+            ),
             max_number_of_transactions_per_batch,
             max_number_of_authors_per_batch,
+            sign_check_enqueue_timeout: config.sign_check_enqueue_timeout(),
+            sign_check_timeout: config.sign_check_timeout(),
+            tx_screener,
+            drain_mode,
+            fast_withdrawals_enabled: config.fast_withdrawals_enabled,
+            withdrawal_policy,
+            rejection_writer,
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
         }
     }
 
-    /// If `ForcedExit` has Ethereum siganture (e.g. it's a part of a batch), an actual signer
-    /// is initiator, not the target, thus, this function will perform a database query to acquire
-    /// the corresponding address.
+    /// Rejects the new transactions in the drain mode. Transactions which have passed this check
+    /// are processed to the end, so the mempool gets all of them before the server is stopped.
+    fn check_drain_mode(&self) -> Result<(), SubmitError> {
+        if self.drain_mode.is_enabled() {
+            return Err(SubmitError::TxAdd(TxAddError::Draining));
+        }
+        Ok(())
+    }
+
+    /// Returns the address expected to sign the transaction with its Ethereum key.
+    /// If only the ID of the signer is known (e.g. the initiator of `ForcedExit`),
+    /// this function will perform a database query to acquire the corresponding address.
     async fn get_tx_sender(&self, tx: &ZkSyncTx) -> Result<Address, anyhow::Error> {
-        match tx {
-            ZkSyncTx::ForcedExit(tx) => self.get_address_by_id(tx.initiator_account_id).await,
-            _ => Ok(tx.account()),
+        match tx.eth_signer() {
+            EthSigner::Address(address) => Ok(address),
+            EthSigner::AccountId(id) => self.get_address_by_id(id).await,
         }
     }
     async fn get_address_by_id(&self, id: AccountId) -> Result<Address, anyhow::Error> {
@@ -213,6 +284,8 @@ impl TxSender {
             .unwrap_or(EthAccountType::Owned))
     }
 
+    /// Enables or disables the Ethereum signature requirement for the transactions of the account,
+    /// see `EthAccountType::No2FA`. The request must be signed by the Ethereum key of the account.
     pub async fn toggle_2fa(
         &self,
         toggle_2fa: Toggle2FA,
@@ -272,7 +345,7 @@ impl TxSender {
             )));
         }
 
-        let message = toggle_2fa.get_ethereum_sign_message().into_bytes();
+        let message = SignedMessage::Text(toggle_2fa.get_ethereum_sign_message());
 
         let signature = toggle_2fa.signature;
         let signer = self
@@ -281,25 +354,34 @@ impl TxSender {
             .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
 
         let eth_sign_data = EthSignData { signature, message };
-        let (sender, receiever) = oneshot::channel();
-
-        let request = VerifySignatureRequest {
-            data: RequestData::Toggle2FA(Toggle2FARequest {
+        let (request, receiever) =
+            VerifySignatureRequest::new(RequestData::Toggle2FA(Toggle2FARequest {
                 sign_data: eth_sign_data,
                 sender: signer,
-            }),
-            response: sender,
-        };
+            }));
 
-        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+        send_verify_request_and_recv(
+            request,
+            self.sign_verify_requests.clone(),
+            self.sign_check_enqueue_timeout,
+            self.sign_check_timeout,
+            receiever,
+        )
+        .await?;
         Ok(())
     }
 
-    async fn verify_order_eth_signature(
+    /// Resolves the owner of the order and the Ethereum sign data the order has to be
+    /// checked with. Sign data is `None` if the owner doesn't need the Ethereum signature.
+    async fn order_eth_sign_data(
         &self,
         order: &Order,
         signature: Option<TxEthSignature>,
-    ) -> Result<(), SubmitError> {
+    ) -> Result<(Address, Option<EthSignData>), SubmitError> {
+        let signer = self
+            .get_address_by_id(order.account_id)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
         let signer_type = self
             .get_sender_type(order.account_id)
             .await
@@ -310,48 +392,32 @@ impl TxSender {
                     "Eth signature from CREATE2 account not expected".to_string(),
                 ))
             } else {
-                Ok(())
+                Ok((signer, None))
             };
         }
 
         if matches!(signer_type, EthAccountType::No2FA(None)) {
             // We don't verify signatures for accounts with no 2FA
-            return Ok(());
+            return Ok((signer, None));
         }
         if let EthAccountType::No2FA(Some(unchecked_hash)) = signer_type {
             let order_pub_key_hash = PubKeyHash::from_pubkey(&order.signature.pub_key.0);
             // We don't scheck the signature only if the order was signed with the same
             // is the same as unchecked PubKey
             if order_pub_key_hash == unchecked_hash {
-                return Ok(());
+                return Ok((signer, None));
             }
         }
 
         let signature = signature.ok_or(SubmitError::TxAdd(TxAddError::MissingEthSignature))?;
-        let signer = self
-            .get_address_by_id(order.account_id)
-            .await
-            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
-
         let token_sell = self.token_info_from_id(order.token_sell).await?;
         let token_buy = self.token_info_from_id(order.token_buy).await?;
-        let message = order
-            .get_ethereum_sign_message(&token_sell.symbol, &token_buy.symbol, token_sell.decimals)
-            .into_bytes();
-        let eth_sign_data = EthSignData { signature, message };
-        let (sender, receiever) = oneshot::channel();
-
-        let request = VerifySignatureRequest {
-            data: RequestData::Order(OrderRequest {
-                order: Box::new(order.clone()),
-                sign_data: eth_sign_data,
-                sender: signer,
-            }),
-            response: sender,
-        };
-
-        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
-        Ok(())
+        let message = SignedMessage::Text(order.get_ethereum_sign_message(
+            &token_sell.symbol,
+            &token_buy.symbol,
+            token_sell.decimals,
+        ));
+        Ok((signer, Some(EthSignData { signature, message })))
     }
 
     // This method is left for RPC API
@@ -441,6 +507,30 @@ impl TxSender {
         Ok(result)
     }
 
+    /// Checks whether the transactions of the account are accepted without fees, either by the
+    /// config or by the fee-free list managed by the operator.
+    async fn is_fee_free_account(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<bool, SubmitError> {
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => return Ok(false),
+        };
+        if self.fee_free_accounts.contains(&account_id) {
+            return Ok(true);
+        }
+
+        self.pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .fee_policies_schema()
+            .is_fee_free_account(account_id)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
     pub async fn store_subsidy_data(
         &self,
         hash: TxHash,
@@ -481,38 +571,36 @@ impl TxSender {
         Ok(())
     }
 
-    pub async fn submit_tx(
+    /// Performs the checks of the transaction submission: the fee, the signatures, and
+    /// the restrictions on the transaction type. Rejections caused by the transaction itself
+    /// are stored if `record_rejections` is set.
+    async fn check_tx(
         &self,
-        tx: ZkSyncTx,
+        tx: &ZkSyncTx,
         signature: TxEthSignatureVariant,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<TxHash, SubmitError> {
-        let labels = vec![
-            ("stage", "api".to_string()),
-            ("name", tx.variance_name()),
-            ("token", tx.token_id().to_string()),
-        ];
-        // The initial state of processing tx
-        metrics::increment_counter!("process_tx_count", &labels);
-
+        record_rejections: bool,
+    ) -> Result<CheckedTx, SubmitError> {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
         }
 
-        if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
+        if let ZkSyncTx::ForcedExit(forced_exit) = tx {
             self.check_forced_exit(forced_exit).await?;
         }
 
         // Resolve the token.
         let token = self.token_info_from_id(tx.token_id()).await?;
-        let msg_to_sign = tx
-            .get_ethereum_sign_message(token.clone())
-            .map(String::into_bytes);
+        // Exempt transactions may omit the Ethereum signature, but if it's provided, it's checked.
+        let msg_to_sign =
+            if signature.tx_signature().is_none() && self.eth_sig_exemptions.is_exempt(tx) {
+                None
+            } else {
+                tx.get_ethereum_sign_message(token.clone())
+                    .map(SignedMessage::Text)
+            };
 
-        let is_whitelisted_initiator = tx
-            .account_id()
-            .map(|account_id| self.fee_free_accounts.contains(&account_id))
-            .unwrap_or(false);
+        let is_whitelisted_initiator = self.is_fee_free_account(tx.account_id().ok()).await?;
 
         let tx_fee_info = if !is_whitelisted_initiator {
             tx.get_fee_info()
@@ -569,28 +657,89 @@ impl TxSender {
             .await
             .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
 
+        // Orders of the swap are signed by their owners, so their signatures are checked
+        // along with the transaction signature.
+        let parties_sign_data = if let ZkSyncTx::Swap(tx) = tx {
+            if signature.is_single() {
+                return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
+            }
+            let signatures = signature.orders_signatures();
+            vec![
+                self.order_eth_sign_data(&tx.orders.0, signatures.0.clone())
+                    .await?,
+                self.order_eth_sign_data(&tx.orders.1, signatures.1.clone())
+                    .await?,
+            ]
+        } else {
+            Vec::new()
+        };
+
         let verified_tx = verify_tx_info_message_signature(
-            &tx,
+            tx,
             tx_sender,
             token.clone(),
-            self.get_tx_sender_type(&tx).await?,
+            self.get_tx_sender_type(tx).await?,
             signature.tx_signature().clone(),
             msg_to_sign,
+            parties_sign_data,
             sign_verify_channel,
+            self.sign_check_enqueue_timeout,
+            self.sign_check_timeout,
         )
-        .await?
+        .await
+        .map_err(|err| {
+            if record_rejections {
+                self.record_rejection(vec![(tx.hash(), tx_sender)], err)
+            } else {
+                err
+            }
+        })?
         .unwrap_tx();
 
-        if let ZkSyncTx::Swap(tx) = &tx {
-            if signature.is_single() {
-                return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
+        self.screen_tx(tx, tx_sender).await.map_err(|err| {
+            let err = SubmitError::TxAdd(err);
+            if record_rejections {
+                self.record_rejection(vec![(tx.hash(), tx_sender)], err)
+            } else {
+                err
             }
-            let signatures = signature.orders_signatures();
-            self.verify_order_eth_signature(&tx.orders.0, signatures.0.clone())
-                .await?;
-            self.verify_order_eth_signature(&tx.orders.1, signatures.1.clone())
-                .await?;
-        }
+        })?;
+
+        Ok(CheckedTx {
+            tx: verified_tx,
+            fee_data_for_subsidy,
+            token,
+        })
+    }
+
+    /// Transaction is traced by its hash through the signature check, the mempool and the state keeper.
+    #[tracing::instrument(
+        skip(self, tx, signature, extracted_request_metadata),
+        fields(tx_hash = %tx.hash().to_string())
+    )]
+    pub async fn submit_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxHash, SubmitError> {
+        let labels = vec![
+            ("stage", "api".to_string()),
+            ("name", tx.variance_name()),
+            ("token", tx.token_id().to_string()),
+        ];
+        // The initial state of processing tx
+        metrics::increment_counter!("process_tx_count", &labels);
+        self.check_drain_mode()?;
+
+        let CheckedTx {
+            tx: verified_tx,
+            fee_data_for_subsidy,
+            token,
+        } = self
+            .check_tx(&tx, signature, extracted_request_metadata, true)
+            .await?;
+        self.check_withdrawal_limits(&[&tx], false).await?;
 
         let (sender, receiver) = oneshot::channel();
         let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
@@ -601,6 +750,9 @@ impl TxSender {
             .map_err(SubmitError::internal)?;
 
         receiver.await.map_err(SubmitError::internal)??;
+        self.record_withdrawals(std::slice::from_ref(&tx)).await;
+        self.hand_off_fast_withdrawals(std::slice::from_ref(&tx))
+            .await;
 
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data_for_subsidy) = fee_data_for_subsidy {
@@ -630,12 +782,14 @@ impl TxSender {
         Ok(tx.hash())
     }
 
-    pub async fn submit_txs_batch(
+    /// Performs the checks of the batch submission, see `check_tx` for details.
+    async fn check_txs_batch(
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<SubmitBatchResponse, SubmitError> {
+        record_rejections: bool,
+    ) -> Result<CheckedTxsBatch, SubmitError> {
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
 
@@ -649,15 +803,6 @@ impl TxSender {
             return Err(SubmitError::TxAdd(TxAddError::BatchTooBig));
         }
 
-        for tx in &txs {
-            let labels = vec![
-                ("stage", "api".to_string()),
-                ("name", tx.tx.variance_name()),
-                ("token", tx.tx.token_id().to_string()),
-            ];
-            metrics::increment_counter!("process_tx_count", &labels);
-        }
-
         // Same check but in terms of signatures.
         if eth_signatures.len() > self.max_number_of_authors_per_batch {
             return Err(SubmitError::TxAdd(TxAddError::EthSignaturesLimitExceeded));
@@ -667,6 +812,12 @@ impl TxSender {
             return Err(SubmitError::AccountCloseDisabled);
         }
 
+        for tx in &txs {
+            if let ZkSyncTx::ForcedExit(forced_exit) = &tx.tx {
+                self.check_forced_exit(forced_exit).await?;
+            }
+        }
+
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
         let mut transaction_types = vec![];
@@ -677,6 +828,10 @@ impl TxSender {
         let mut token_fees_ids = vec![];
 
         for tx in &txs {
+            // Transactions of the fee-free accounts don't add up to the fee of the batch.
+            if self.is_fee_free_account(tx.tx.account_id().ok()).await? {
+                continue;
+            }
             let tx_fee_info = tx.tx.get_fee_info();
 
             if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
@@ -710,7 +865,11 @@ impl TxSender {
                     .await?;
 
                 let token_data = self.token_info_from_id(token).await?;
-                token_fees_ids.push(token_data.id);
+                // Batch may pay the fee in a single token with several transactions, the fee
+                // is checked in this token then, so the token is counted once.
+                if !token_fees_ids.contains(&token_data.id) {
+                    token_fees_ids.push(token_data.id);
+                }
                 let mut token_fee = token_fees.remove(&token_data.address).unwrap_or_default();
                 token_fee += &provided_fee;
                 token_fees.insert(token_data.address, token_fee);
@@ -803,17 +962,24 @@ impl TxSender {
             }
         }
 
+        // Orders of the swaps are checked along with the batch, the same way as in `check_tx`.
+        let mut parties_sign_data = Vec::with_capacity(txs.len());
         for tx in txs.iter() {
-            if let ZkSyncTx::Swap(swap) = &tx.tx {
+            let tx_parties_sign_data = if let ZkSyncTx::Swap(swap) = &tx.tx {
                 if tx.signature.is_single() {
                     return Err(SubmitError::TxAdd(TxAddError::MissingEthSignature));
                 }
                 let signatures = tx.signature.orders_signatures();
-                self.verify_order_eth_signature(&swap.orders.0, signatures.0.clone())
-                    .await?;
-                self.verify_order_eth_signature(&swap.orders.1, signatures.1.clone())
-                    .await?;
-            }
+                vec![
+                    self.order_eth_sign_data(&swap.orders.0, signatures.0.clone())
+                        .await?,
+                    self.order_eth_sign_data(&swap.orders.1, signatures.1.clone())
+                        .await?,
+                ]
+            } else {
+                Vec::new()
+            };
+            parties_sign_data.push(tx_parties_sign_data);
         }
 
         let mut verified_txs = Vec::with_capacity(txs.len());
@@ -823,12 +989,17 @@ impl TxSender {
         let mut tx_senders = Vec::with_capacity(txs.len());
         let mut tx_sender_types = Vec::with_capacity(txs.len());
         let mut tokens = Vec::with_capacity(txs.len());
-        for tx in txs.iter().map(|tx| &tx.tx) {
+        for TxWithSignature { tx, signature } in txs.iter() {
             // Resolve the token and save it for constructing the batch message.
             let token = self.token_info_from_id(tx.token_id()).await?;
             tokens.push(token.clone());
 
-            messages_to_sign.push(tx.get_ethereum_sign_message(token).map(String::into_bytes));
+            let msg_to_sign = if !signature.exists() && self.eth_sig_exemptions.is_exempt(tx) {
+                None
+            } else {
+                tx.get_ethereum_sign_message(token).map(SignedMessage::Text)
+            };
+            messages_to_sign.push(msg_to_sign);
             tx_senders.push(
                 self.get_tx_sender(tx)
                     .await
@@ -859,6 +1030,11 @@ impl TxSender {
         } else {
             None
         };
+        let batch_txs: Vec<_> = txs
+            .iter()
+            .map(|tx| tx.tx.hash())
+            .zip(tx_senders.iter().copied())
+            .collect();
         let (verified_batch, sign_data) = verify_txs_batch_signature(
             txs,
             tx_senders,
@@ -866,16 +1042,83 @@ impl TxSender {
             tx_sender_types,
             batch_sign_data,
             messages_to_sign,
+            parties_sign_data,
             self.sign_verify_requests.clone(),
+            self.sign_check_enqueue_timeout,
+            self.sign_check_timeout,
         )
-        .await?
+        .await
+        .map_err(|err| {
+            if record_rejections {
+                self.record_rejection(batch_txs.clone(), err)
+            } else {
+                err
+            }
+        })?
         .unwrap_batch();
+
+        for (index, (tx, (tx_hash, tx_sender))) in verified_batch.iter().zip(&batch_txs).enumerate()
+        {
+            if let Err(err) = self.screen_tx(&tx.tx, *tx_sender).await {
+                let err = SubmitError::TxAdd(TxAddError::batch_tx(index, *tx_hash, err));
+                return Err(if record_rejections {
+                    self.record_rejection(batch_txs.clone(), err)
+                } else {
+                    err
+                });
+            }
+        }
+
         if let Some(sign_data) = sign_data {
             verified_signatures.extend(sign_data.signatures.into_iter());
         }
         verified_txs.extend(verified_batch.into_iter());
 
+        Ok(CheckedTxsBatch {
+            txs: verified_txs,
+            eth_signatures: verified_signatures,
+            fee_data_for_subsidy,
+            token_fees_ids,
+        })
+    }
+
+    #[tracing::instrument(
+        skip(self, txs, eth_signatures, extracted_request_metadata),
+        fields(tx_hashes = ?txs.iter().map(|tx| tx.tx.hash().to_string()).collect::<Vec<_>>())
+    )]
+    pub async fn submit_txs_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        for tx in &txs {
+            let labels = vec![
+                ("stage", "api".to_string()),
+                ("name", tx.tx.variance_name()),
+                ("token", tx.tx.token_id().to_string()),
+            ];
+            metrics::increment_counter!("process_tx_count", &labels);
+        }
+        self.check_drain_mode()?;
+
+        let CheckedTxsBatch {
+            txs: verified_txs,
+            eth_signatures: verified_signatures,
+            fee_data_for_subsidy,
+            token_fees_ids,
+        } = self
+            .check_txs_batch(txs, eth_signatures, extracted_request_metadata, true)
+            .await?;
+        let batch_txs: Vec<&ZkSyncTx> = verified_txs.iter().map(|tx| &tx.tx).collect();
+        self.check_withdrawal_limits(&batch_txs, true).await?;
+
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
+        let withdrawals: Vec<ZkSyncTx> = verified_txs
+            .iter()
+            .filter(|tx| tx.tx.is_withdraw())
+            .map(|tx| tx.tx.clone())
+            .collect();
 
         let (sender, receiver) = oneshot::channel();
         let item =
@@ -887,6 +1130,8 @@ impl TxSender {
             .map_err(SubmitError::mempool_communication)?;
 
         receiver.await.map_err(SubmitError::internal)??;
+        self.record_withdrawals(&withdrawals).await;
+        self.hand_off_fast_withdrawals(&withdrawals).await;
 
         let batch_hash = TxHash::batch_hash(&tx_hashes);
 
@@ -931,11 +1176,187 @@ impl TxSender {
         })
     }
 
+    /// Checks the transaction the same way `submit_tx` does and simulates its execution
+    /// against the latest committed state. Transaction is not sent to the mempool.
+    pub async fn simulate_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxSimulationResult, SubmitError> {
+        let checked_tx = self
+            .check_tx(&tx, signature, extracted_request_metadata, false)
+            .await?;
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        simulate_txs(&mut storage, &[checked_tx.tx])
+            .await
+            .map_err(SubmitError::internal)
+    }
+
+    /// Checks the batch the same way `submit_txs_batch` does and simulates its execution
+    /// against the latest committed state. Batch is not sent to the mempool.
+    pub async fn simulate_txs_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxSimulationResult, SubmitError> {
+        let checked_batch = self
+            .check_txs_batch(txs, eth_signatures, extracted_request_metadata, false)
+            .await?;
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        simulate_txs(&mut storage, &checked_batch.txs)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
+    /// Screens the addresses of the verified transaction, see `tx_screening` for details.
+    async fn screen_tx(&self, tx: &ZkSyncTx, signer: Address) -> Result<(), TxAddError> {
+        let screener = match &self.tx_screener {
+            Some(screener) => screener,
+            None => return Ok(()),
+        };
+        let result = screener.screen_tx(tx, &tx_addresses(tx, signer)).await;
+        if let Err(TxAddError::AddressRejected { address }) = &result {
+            vlog::info!(
+                "Tx {} is rejected by the screening because of {:?}",
+                tx.hash().to_string(),
+                address
+            );
+            metrics::increment_counter!("api.tx_screening.rejected");
+        }
+        result
+    }
+
+    /// Stores the reason of the rejected verification, so it can be looked up later
+    /// by the hashes and the senders of the transactions.
+    ///
+    /// Only the rejections caused by the transactions themselves are stored. The write is
+    /// performed in the background by the `RejectionWriter`, so the response is never delayed
+    /// by the database.
+    fn record_rejection(&self, txs: Vec<(TxHash, Address)>, error: SubmitError) -> SubmitError {
+        let tx_add_error = match &error {
+            // Overload has nothing to do with the transaction, so it's not worth storing.
+            SubmitError::TxAdd(err)
+                if err.to_http_status().is_client_error()
+                    && !matches!(err, TxAddError::ServiceOverloaded | TxAddError::MempoolFull) =>
+            {
+                err.clone()
+            }
+            _ => return error,
+        };
+        let rejections: Vec<_> = txs
+            .into_iter()
+            .map(|(tx_hash, account)| NewTxRejection {
+                tx_hash,
+                account,
+                error_code: tx_add_error.to_error_code().to_string(),
+                error_message: tx_add_error.to_string(),
+                recovered_signer: tx_add_error.recovered_signer(),
+            })
+            .collect();
+        self.rejection_writer.send(rejections);
+        error
+    }
+
+    /// Checks the withdrawals against the limits set by the operator, see `withdrawal_limits` for details.
+    /// The rejections are stored the same way the ones of the submission checks are.
+    async fn check_withdrawal_limits(
+        &self,
+        txs: &[&ZkSyncTx],
+        batch: bool,
+    ) -> Result<(), SubmitError> {
+        let policy = match &self.withdrawal_policy {
+            Some(policy) => policy,
+            None => return Ok(()),
+        };
+        policy.check_withdrawals(txs, batch).await.map_err(|err| {
+            let txs = txs.iter().map(|tx| (tx.hash(), tx.account())).collect();
+            self.record_rejection(txs, SubmitError::TxAdd(err))
+        })
+    }
+
+    /// Counts the withdrawals accepted by the mempool in the volumes the limits are checked against.
+    /// Transactions are already in the mempool at this point, so the failure is only logged.
+    async fn record_withdrawals(&self, txs: &[ZkSyncTx]) {
+        if let Some(policy) = &self.withdrawal_policy {
+            if let Err(err) = policy.record_withdrawals(txs).await {
+                vlog::warn!("Failed to record the withdrawn amounts: {}", err);
+            }
+        }
+    }
+
+    /// Hands the withdrawals off to the liquidity providers they are matched with, so the providers
+    /// front the funds on Ethereum (see `rest::v02::fast_withdrawal`). Transactions are already
+    /// in the mempool at this point, so the failure is only logged.
+    async fn hand_off_fast_withdrawals(&self, txs: &[ZkSyncTx]) {
+        if !self.fast_withdrawals_enabled {
+            return;
+        }
+        for tx in txs {
+            if let ZkSyncTx::Withdraw(withdraw) = tx {
+                if let Err(err) = self.hand_off_fast_withdrawal(tx.hash(), withdraw).await {
+                    vlog::warn!(
+                        "Failed to hand off the fast withdrawal {}: {}",
+                        tx.hash().to_string(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+
+    async fn hand_off_fast_withdrawal(
+        &self,
+        tx_hash: TxHash,
+        withdraw: &Withdraw,
+    ) -> Result<(), anyhow::Error> {
+        let mut storage = self.pool.access_storage().await?;
+        let pending = storage
+            .fast_withdrawals_schema()
+            .load_pending_withdrawals(withdraw.from, Utc::now())
+            .await?;
+        for withdrawal in pending
+            .iter()
+            .filter(|withdrawal| withdrawal.is_handed_off_by(withdraw))
+        {
+            // The withdrawal may be handed off concurrently, then the next match is tried.
+            if storage
+                .fast_withdrawals_schema()
+                .set_withdrawal_tx_hash(withdrawal.id, tx_hash)
+                .await?
+            {
+                vlog::info!(
+                    "Fast withdrawal #{} is handed off to {:?} by {}",
+                    withdrawal.id,
+                    withdrawal.provider,
+                    tx_hash.to_string()
+                );
+                metrics::increment_counter!("api.fast_withdrawals.handed_off");
+                break;
+            }
+        }
+        Ok(())
+    }
+
     /// For forced exits, we must check that target account exists for more
     /// than 24 hours in order to give new account owners give an opportunity
     /// to set the signing key. While `ForcedExit` operation doesn't do anything
     /// bad to the account, it's more user-friendly to only allow this operation
     /// after we're somewhat sure that zkSync account is not owned by anybody.
+    ///
+    /// Target account must also have no signing key set in the committed state, otherwise
+    /// the transaction would be rejected by the state keeper anyway.
     async fn check_forced_exit(
         &self,
         forced_exit: &zksync_types::ForcedExit,
@@ -948,39 +1369,60 @@ impl TxSender {
 
         self.forced_exit_checker
             .validate_forced_exit(&mut storage, forced_exit.target)
+            .await?;
+
+        let target_account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(forced_exit.target)
             .await
+            .map_err(SubmitError::internal)?;
+        if let Some(target_account_id) = target_account_id {
+            let (_, committed_state) = storage
+                .chain()
+                .account_schema()
+                .last_committed_state_for_account(target_account_id)
+                .await
+                .map_err(SubmitError::internal)?;
+            let has_signing_key = committed_state.map_or(false, |account| {
+                account.pub_key_hash != PubKeyHash::default()
+            });
+            if has_signing_key {
+                return Err(SubmitError::invalid_params(
+                    "Target account has the signing key set",
+                ));
+            }
+        }
+        Ok(())
     }
 
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// If any error is encountered during the message generation, returns `jsonrpc_core::Error`.
     #[allow(dead_code)]
-    async fn tx_message_to_sign(&self, tx: &ZkSyncTx) -> Result<Option<Vec<u8>>, SubmitError> {
+    async fn tx_message_to_sign(
+        &self,
+        tx: &ZkSyncTx,
+    ) -> Result<Option<SignedMessage>, SubmitError> {
         Ok(match tx {
             ZkSyncTx::Transfer(tx) => {
                 let token = self.token_info_from_id(tx.token).await?;
 
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some(msg)
+                let msg = tx.get_ethereum_sign_message(&token.symbol, token.decimals);
+                Some(SignedMessage::Text(msg))
             }
             ZkSyncTx::Withdraw(tx) => {
                 let token = self.token_info_from_id(tx.token).await?;
 
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some(msg)
+                let msg = tx.get_ethereum_sign_message(&token.symbol, token.decimals);
+                Some(SignedMessage::Text(msg))
             }
 
             ZkSyncTx::MintNFT(tx) => {
                 let token = self.token_info_from_id(tx.fee_token).await?;
 
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some(msg)
+                let msg = tx.get_ethereum_sign_message(&token.symbol, token.decimals);
+                Some(SignedMessage::Text(msg))
             }
             _ => None,
         })
@@ -1014,33 +1456,56 @@ impl TxSender {
     }
 }
 
+/// Sends the check request and waits for the result.
+///
+/// If the request can't be queued within `enqueue_timeout`, the signature checker
+/// is considered overloaded and the request is rejected without waiting further.
+///
+/// The check has to be finished within `check_timeout` since the request is sent,
+/// otherwise it's abandoned and the request is rejected with `TxAddError::SignatureCheckTimeout`.
 async fn send_verify_request_and_recv(
-    request: VerifySignatureRequest,
+    mut request: VerifySignatureRequest,
     mut req_channel: mpsc::Sender<VerifySignatureRequest>,
+    enqueue_timeout: std::time::Duration,
+    check_timeout: std::time::Duration,
     receiver: oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
 ) -> Result<VerifiedTx, SubmitError> {
+    let deadline = std::time::Instant::now() + check_timeout;
+    request.deadline = Some(deadline);
     // Send the check request.
-    req_channel
-        .send(request)
+    tokio::time::timeout(enqueue_timeout, req_channel.send(request))
         .await
+        .map_err(|_| {
+            metrics::increment_counter!("signature_checker.overloaded_requests");
+            SubmitError::TxAdd(TxAddError::ServiceOverloaded)
+        })?
         .map_err(SubmitError::internal)?;
-    // Wait for the check result.
-    receiver
+    // Wait for the check result. The checker answers once the deadline passes as well,
+    // but the response is not awaited beyond it in any case.
+    tokio::time::timeout_at(deadline.into(), receiver)
         .await
+        .map_err(|_| SubmitError::TxAdd(TxAddError::SignatureCheckTimeout))?
         .map_err(|err| internal_error!(err))?
         .map_err(SubmitError::TxAdd)
 }
 
 /// Send a request for Ethereum signature verification and wait for the response.
 /// If `msg_to_sign` is not `None`, then the signature must be present.
+///
+/// `parties_sign_data` contains the addresses of the other parties of the transaction
+/// along with the sign data their signatures have to be checked with, if any.
+#[allow(clippy::too_many_arguments)]
 async fn verify_tx_info_message_signature(
     tx: &ZkSyncTx,
     tx_sender: Address,
     token: Token,
     account_type: EthAccountType,
     signature: Option<TxEthSignature>,
-    msg_to_sign: Option<Vec<u8>>,
+    msg_to_sign: Option<SignedMessage>,
+    parties_sign_data: Vec<(Address, Option<EthSignData>)>,
     req_channel: mpsc::Sender<VerifySignatureRequest>,
+    enqueue_timeout: std::time::Duration,
+    check_timeout: std::time::Duration,
 ) -> Result<VerifiedTx, SubmitError> {
     if matches!(
         (account_type, signature.clone(), msg_to_sign.clone()),
@@ -1075,45 +1540,63 @@ async fn verify_tx_info_message_signature(
         _ => None,
     };
 
-    let (sender, receiever) = oneshot::channel();
-
-    let request = VerifySignatureRequest {
-        data: RequestData::Tx(TxRequest {
-            tx: SignedZkSyncTx {
-                tx: tx.clone(),
-                eth_sign_data,
-                created_at: Utc::now(),
-            },
-            sender: tx_sender,
-            token,
-        }),
-        response: sender,
-    };
-
-    send_verify_request_and_recv(request, req_channel, receiever).await
+    let (parties, parties_eth_sign_data) = parties_sign_data.into_iter().unzip();
+    let (request, receiever) = VerifySignatureRequest::new_single(TxRequest::with_parties(
+        SignedZkSyncTx {
+            tx: tx.clone(),
+            eth_sign_data,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data,
+        },
+        tx_sender,
+        token,
+        parties,
+    ));
+
+    send_verify_request_and_recv(
+        request,
+        req_channel,
+        enqueue_timeout,
+        check_timeout,
+        receiever,
+    )
+    .await
 }
 
 /// Send a request for Ethereum signature verification and wait for the response.
 /// Unlike in case of `verify_tx_info_message_signature`, we do not require
 /// every transaction from the batch to be signed. The signature must be obtained
 /// through signing a human-readable message with accordance to zkSync protocol.
+///
+/// `parties_sign_data` contains the other parties of each transaction, the same way as
+/// for `verify_tx_info_message_signature`.
+#[allow(clippy::too_many_arguments)]
 async fn verify_txs_batch_signature(
     batch: Vec<TxWithSignature>,
     senders: Vec<Address>,
     tokens: Vec<Token>,
     sender_types: Vec<EthAccountType>,
     batch_sign_data: Option<EthBatchSignData>,
-    msgs_to_sign: Vec<Option<Vec<u8>>>,
+    msgs_to_sign: Vec<Option<SignedMessage>>,
+    parties_sign_data: Vec<Vec<(Address, Option<EthSignData>)>>,
     req_channel: mpsc::Sender<VerifySignatureRequest>,
+    enqueue_timeout: std::time::Duration,
+    check_timeout: std::time::Duration,
 ) -> Result<VerifiedTx, SubmitError> {
     // This hashset holds addresses that have performed a CREATE2 ChangePubKey
     // within this batch, so that we don't check ETH signatures on their transactions
     // from this batch. We save the account type to the db later.
     let mut create2_senders = HashSet::<H160>::new();
     let mut txs = Vec::with_capacity(batch.len());
-    for (tx, message, sender, mut sender_type) in
-        izip!(batch, msgs_to_sign, senders.iter(), sender_types)
-    {
+    let mut parties = Vec::with_capacity(batch.len());
+    for (tx, message, sender, mut sender_type, tx_parties_sign_data) in izip!(
+        batch,
+        msgs_to_sign,
+        senders.iter(),
+        sender_types,
+        parties_sign_data
+    ) {
         if create2_senders.contains(sender) {
             sender_type = EthAccountType::CREATE2;
         }
@@ -1166,26 +1649,33 @@ async fn verify_txs_batch_signature(
             None
         };
 
+        let (tx_parties, parties_eth_sign_data) = tx_parties_sign_data.into_iter().unzip();
+        parties.push(tx_parties);
         txs.push(SignedZkSyncTx {
             tx: tx.tx,
             eth_sign_data,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data,
         });
     }
 
-    let (sender, receiver) = oneshot::channel();
-
-    let request = VerifySignatureRequest {
-        data: RequestData::Batch(BatchRequest {
-            txs,
-            batch_sign_data,
-            senders,
-            tokens,
-        }),
-        response: sender,
-    };
-
-    send_verify_request_and_recv(request, req_channel, receiver).await
+    let (request, receiver) = VerifySignatureRequest::new_batch(BatchRequest {
+        txs,
+        batch_sign_data,
+        senders,
+        tokens,
+        parties,
+    });
+
+    send_verify_request_and_recv(
+        request,
+        req_channel,
+        enqueue_timeout,
+        check_timeout,
+        receiver,
+    )
+    .await
 }
 
 /// Scales the fee provided by user up to check whether the provided fee is enough to cover our expenses for
@@ -1225,6 +1715,7 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zksync_types::tx::EIP1271Signature;
 
     #[test]
     fn test_scaling_user_fee_by_two() {
@@ -1255,4 +1746,70 @@ mod tests {
 
         assert_eq!(provided_fee_scaled_by_five_percent, scaled_fee);
     }
+
+    /// Checks that the request is rejected promptly once the signature checker channel is full.
+    #[tokio::test]
+    async fn overloaded_sign_checker() {
+        const ENQUEUE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(50);
+
+        let request = || {
+            VerifySignatureRequest::new(RequestData::Toggle2FA(Toggle2FARequest {
+                sign_data: EthSignData {
+                    signature: TxEthSignature::EIP1271Signature(EIP1271Signature(Vec::new())),
+                    message: SignedMessage::default(),
+                },
+                sender: Address::zero(),
+            }))
+        };
+        // Nobody reads the channel, so it's full after the only guaranteed slot of the sender is taken.
+        let (mut sender, _receiver) = mpsc::channel(0);
+        sender.try_send(request().0).unwrap();
+
+        let (request, response) = request();
+        let start = std::time::Instant::now();
+        let result = send_verify_request_and_recv(
+            request,
+            sender,
+            ENQUEUE_TIMEOUT,
+            std::time::Duration::from_secs(30),
+            response,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::TxAdd(TxAddError::ServiceOverloaded))
+        ));
+        assert!(start.elapsed() < ENQUEUE_TIMEOUT * 10);
+    }
+
+    /// Checks that the request is rejected with the timeout error once its deadline passes,
+    /// even if the signature checker never answers.
+    #[tokio::test]
+    async fn stalled_sign_checker() {
+        const CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(50);
+
+        let (request, response) =
+            VerifySignatureRequest::new(RequestData::Toggle2FA(Toggle2FARequest {
+                sign_data: EthSignData {
+                    signature: TxEthSignature::EIP1271Signature(EIP1271Signature(Vec::new())),
+                    message: SignedMessage::default(),
+                },
+                sender: Address::zero(),
+            }));
+        // Request is queued, but nobody checks it.
+        let (sender, mut receiver) = mpsc::channel(1);
+
+        let start = std::time::Instant::now();
+        let result =
+            send_verify_request_and_recv(request, sender, CHECK_TIMEOUT, CHECK_TIMEOUT, response)
+                .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::TxAdd(TxAddError::SignatureCheckTimeout))
+        ));
+        assert!(start.elapsed() < CHECK_TIMEOUT * 10);
+        // The checker knows the deadline as well.
+        let request = receiver.try_next().unwrap().unwrap();
+        assert!(request.deadline.unwrap() <= std::time::Instant::now());
+    }
 }
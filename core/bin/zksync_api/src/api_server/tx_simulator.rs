@@ -0,0 +1,314 @@
+//! Simulation of the transactions execution, used to show the outcome of the transactions
+//! to the user before they are submitted.
+//!
+//! Transactions are executed against the latest committed state of the accounts they affect,
+//! so the pending transactions of the mempool are not taken into account.
+
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+
+// External uses
+use chrono::Utc;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_crypto::params::NFT_STORAGE_ACCOUNT_ID;
+use zksync_state::state::{OpSuccess, ZkSyncState};
+use zksync_storage::StorageProcessor;
+use zksync_types::{AccountId, AccountUpdate, Address, SignedZkSyncTx, TokenId, ZkSyncTx};
+use zksync_utils::BigUintSerdeWrapper;
+
+/// Change of the account balance caused by the transactions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub address: Address,
+    pub token_id: TokenId,
+    pub before: BigUintSerdeWrapper,
+    pub after: BigUintSerdeWrapper,
+}
+
+/// Would-be result of the transactions execution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSimulationResult {
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    /// Balance changes of all the affected accounts, empty if the execution fails.
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+/// Loads the committed state of the accounts and NFTs the transactions may affect.
+///
+/// Only the affected accounts are loaded, so IDs assigned to the new accounts may differ
+/// from the ones they get once the transactions are executed.
+async fn load_state(
+    storage: &mut StorageProcessor<'_>,
+    txs: &[SignedZkSyncTx],
+) -> anyhow::Result<ZkSyncState> {
+    let mut account_ids = HashSet::new();
+    let mut addresses = HashSet::new();
+    let mut nft_ids = HashSet::new();
+    for tx in txs {
+        account_ids.extend(tx.tx.account_id().ok());
+        addresses.insert(tx.tx.account());
+        addresses.extend(tx.tx.to_account());
+        match &tx.tx {
+            ZkSyncTx::Swap(swap) => {
+                for order in &[&swap.orders.0, &swap.orders.1] {
+                    account_ids.insert(order.account_id);
+                    addresses.insert(order.recipient_address);
+                }
+            }
+            ZkSyncTx::MintNFT(_) => {
+                account_ids.insert(NFT_STORAGE_ACCOUNT_ID);
+            }
+            ZkSyncTx::WithdrawNFT(withdraw) => {
+                nft_ids.insert(withdraw.token);
+            }
+            _ => {}
+        }
+    }
+
+    let mut nfts = HashMap::new();
+    for token_id in nft_ids {
+        if let Some(nft) = storage.tokens_schema().get_nft(token_id).await? {
+            addresses.insert(nft.creator_address);
+            nfts.insert(token_id, nft);
+        }
+    }
+    for address in addresses {
+        if let Some(account_id) = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await?
+        {
+            account_ids.insert(account_id);
+        }
+    }
+
+    let mut state = ZkSyncState::empty();
+    for account_id in account_ids {
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await?;
+        if let Some(account) = account {
+            state.insert_account(account_id, account);
+        }
+    }
+    state.nfts = nfts;
+    Ok(state)
+}
+
+/// Collects the balance changes of the executed transactions, in the order
+/// the balances are first changed.
+fn balance_changes(state: &ZkSyncState, executed: &[OpSuccess]) -> Vec<BalanceChange> {
+    let mut changes: Vec<(AccountId, TokenId, BigUint, BigUint)> = Vec::new();
+    for (account_id, update) in executed.iter().flat_map(|op| op.updates.iter()) {
+        if let AccountUpdate::UpdateBalance {
+            balance_update: (token_id, old_balance, new_balance),
+            ..
+        } = update
+        {
+            match changes
+                .iter_mut()
+                .find(|(id, token, _, _)| id == account_id && token == token_id)
+            {
+                Some((_, _, _, after)) => *after = new_balance.clone(),
+                None => changes.push((
+                    *account_id,
+                    *token_id,
+                    old_balance.clone(),
+                    new_balance.clone(),
+                )),
+            }
+        }
+    }
+
+    changes
+        .into_iter()
+        .filter(|(_, _, before, after)| before != after)
+        .filter_map(|(account_id, token_id, before, after)| {
+            let account = state.get_account(account_id)?;
+            Some(BalanceChange {
+                address: account.address,
+                token_id,
+                before: before.into(),
+                after: after.into(),
+            })
+        })
+        .collect()
+}
+
+/// Executes the transactions against the state. Several transactions are executed as a batch,
+/// so none of them is applied if any of them fails.
+fn execute(
+    state: &mut ZkSyncState,
+    txs: &[SignedZkSyncTx],
+    block_timestamp: u64,
+) -> TxSimulationResult {
+    let result = if let [tx] = txs {
+        state
+            .execute_tx(tx.tx.clone(), block_timestamp)
+            .map(|success| vec![success])
+            .map_err(|err| err.to_string())
+    } else {
+        state
+            .execute_txs_batch(txs, block_timestamp)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.to_string())
+    };
+
+    match result {
+        Ok(executed) => TxSimulationResult {
+            success: true,
+            fail_reason: None,
+            balance_changes: balance_changes(state, &executed),
+        },
+        Err(fail_reason) => TxSimulationResult {
+            success: false,
+            fail_reason: Some(fail_reason),
+            balance_changes: Vec::new(),
+        },
+    }
+}
+
+/// Simulates the execution of the verified transactions against the latest committed state.
+/// Nothing is stored, so the simulation doesn't affect the actual execution.
+pub async fn simulate_txs(
+    storage: &mut StorageProcessor<'_>,
+    txs: &[SignedZkSyncTx],
+) -> anyhow::Result<TxSimulationResult> {
+    let mut state = load_state(storage, txs).await?;
+    Ok(execute(&mut state, txs, Utc::now().timestamp() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{tx::TimeRange, Account, Nonce};
+
+    use super::*;
+
+    fn signed_transfer(
+        account: &ZkSyncAccount,
+        to: Address,
+        amount: u64,
+        nonce: u32,
+    ) -> SignedZkSyncTx {
+        let (transfer, _) = account.sign_transfer(
+            TokenId(0),
+            "ETH",
+            amount.into(),
+            10u64.into(),
+            &to,
+            Some(Nonce(nonce)),
+            false,
+            TimeRange::default(),
+        );
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        }
+    }
+
+    fn state_with_account(account: &ZkSyncAccount, balance: u64) -> ZkSyncState {
+        let mut state = ZkSyncState::empty();
+        let mut stored = Account::default_with_address(&account.address);
+        stored.pub_key_hash = account.pubkey_hash;
+        stored.set_balance(TokenId(0), balance.into());
+        state.insert_account(account.get_account_id().unwrap(), stored);
+        state
+    }
+
+    fn change(address: Address, before: u64, after: u64) -> BalanceChange {
+        BalanceChange {
+            address,
+            token_id: TokenId(0),
+            before: BigUint::from(before).into(),
+            after: BigUint::from(after).into(),
+        }
+    }
+
+    #[test]
+    fn simulate_transfer() {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+        let recipient = Address::repeat_byte(0x02);
+        let mut state = state_with_account(&account, 1000);
+
+        let result = execute(
+            &mut state,
+            &[signed_transfer(&account, recipient, 100, 0)],
+            0,
+        );
+        assert_eq!(
+            result,
+            TxSimulationResult {
+                success: true,
+                fail_reason: None,
+                balance_changes: vec![
+                    change(account.address, 1000, 890),
+                    change(recipient, 0, 100)
+                ],
+            }
+        );
+
+        let mut state = state_with_account(&account, 50);
+        let result = execute(
+            &mut state,
+            &[signed_transfer(&account, recipient, 100, 0)],
+            0,
+        );
+        assert!(!result.success);
+        assert!(result.fail_reason.is_some());
+        assert!(result.balance_changes.is_empty());
+    }
+
+    #[test]
+    fn simulate_batch() {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(1)));
+        let recipient = Address::repeat_byte(0x02);
+
+        let mut state = state_with_account(&account, 1000);
+        let result = execute(
+            &mut state,
+            &[
+                signed_transfer(&account, recipient, 100, 0),
+                signed_transfer(&account, recipient, 200, 1),
+            ],
+            0,
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.balance_changes,
+            vec![
+                change(account.address, 1000, 680),
+                change(recipient, 0, 300)
+            ]
+        );
+
+        // Batch fails as a whole, since the nonce of the second transfer is incorrect.
+        let mut state = state_with_account(&account, 1000);
+        let result = execute(
+            &mut state,
+            &[
+                signed_transfer(&account, recipient, 100, 0),
+                signed_transfer(&account, recipient, 200, 5),
+            ],
+            0,
+        );
+        assert!(!result.success);
+        assert!(result.balance_changes.is_empty());
+        assert_eq!(state.get_account(AccountId(1)).unwrap().nonce, Nonce(0));
+    }
+}
@@ -0,0 +1,170 @@
+//! Withdrawal limits set by the operator for the risk control, see `zksync_types::withdrawal_limits`.
+//!
+//! The withdrawals are checked after the transactions pass the submission checks and before they
+//! are sent to the mempool. The limits are managed via the admin API and stored in the database,
+//! so they apply without restarting the server. Only the `Withdraw` transactions are limited.
+//!
+//! The withdrawn amounts are counted once the withdrawals are accepted by the mempool, so
+//! the withdrawals submitted concurrently are checked against the same amounts and may exceed
+//! the limit together. The large withdrawal is delayed since the first submission of the transaction,
+//! and the same transaction is accepted once it's resubmitted after the delay window.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use chrono::{DateTime, Duration, Utc};
+use num::BigUint;
+
+// Workspace uses
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    tx::{error::TxAddError, TxHash},
+    Address, TokenId, Withdraw, ZkSyncTx,
+};
+
+// Local uses
+
+fn db_error(err: impl std::fmt::Display) -> TxAddError {
+    vlog::warn!("Failed to check the withdrawal limits: {}", err);
+    TxAddError::DbError
+}
+
+#[derive(Debug, Clone)]
+pub struct WithdrawalPolicy {
+    pool: ConnectionPool,
+}
+
+impl WithdrawalPolicy {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    /// Checks the withdrawals of the transactions against the limits of their tokens, taking into
+    /// account the preceding withdrawals of the batch. If `batch` is set, the violation is reported
+    /// for the transaction at its index.
+    pub async fn check_withdrawals(
+        &self,
+        txs: &[&ZkSyncTx],
+        batch: bool,
+    ) -> Result<(), TxAddError> {
+        let withdrawals: Vec<_> = txs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tx)| match tx {
+                ZkSyncTx::Withdraw(withdraw) => Some((index, tx.hash(), withdraw.as_ref())),
+                _ => None,
+            })
+            .collect();
+        if withdrawals.is_empty() {
+            return Ok(());
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(db_error)?;
+        let now = Utc::now();
+        // Amounts withdrawn by the preceding withdrawals of the batch.
+        let mut account_volumes: HashMap<(TokenId, Address), BigUint> = HashMap::new();
+        let mut total_volumes: HashMap<TokenId, BigUint> = HashMap::new();
+
+        for (index, tx_hash, withdraw) in withdrawals {
+            let account_key = (withdraw.token, withdraw.from);
+            let result = check_withdrawal(
+                &mut storage,
+                now,
+                tx_hash,
+                withdraw,
+                account_volumes.get(&account_key),
+                total_volumes.get(&withdraw.token),
+            )
+            .await;
+            match result {
+                Ok(()) => {}
+                Err(err @ TxAddError::DbError) => return Err(err),
+                Err(err) => {
+                    vlog::info!("Withdrawal {} is rejected: {}", tx_hash.to_string(), err);
+                    metrics::increment_counter!(
+                        "api.withdrawal_limits.rejected",
+                        "error" => err.to_error_code()
+                    );
+                    return Err(if batch {
+                        TxAddError::batch_tx(index, tx_hash, err)
+                    } else {
+                        err
+                    });
+                }
+            }
+
+            *account_volumes.entry(account_key).or_default() += &withdraw.amount;
+            *total_volumes.entry(withdraw.token).or_default() += &withdraw.amount;
+        }
+        Ok(())
+    }
+
+    /// Counts the amounts of the withdrawals accepted by the mempool in the volumes of the day.
+    pub async fn record_withdrawals(&self, txs: &[ZkSyncTx]) -> Result<(), anyhow::Error> {
+        let day = Utc::now().date().naive_utc();
+        let mut storage = self.pool.access_storage().await?;
+        for tx in txs {
+            if let ZkSyncTx::Withdraw(withdraw) = tx {
+                storage
+                    .withdrawal_limits_schema()
+                    .add_volume(day, withdraw.token, withdraw.from, &withdraw.amount)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the withdrawal against the limits of its token, the amounts withdrawn by the preceding
+/// withdrawals of the batch are added to the ones withdrawn today.
+async fn check_withdrawal(
+    storage: &mut StorageProcessor<'_>,
+    now: DateTime<Utc>,
+    tx_hash: TxHash,
+    withdraw: &Withdraw,
+    batch_account_volume: Option<&BigUint>,
+    batch_total_volume: Option<&BigUint>,
+) -> Result<(), TxAddError> {
+    let limits = match storage
+        .withdrawal_limits_schema()
+        .get_limits(withdraw.token)
+        .await
+        .map_err(db_error)?
+    {
+        Some(limits) => limits,
+        None => return Ok(()),
+    };
+
+    let (mut account_volume, mut total_volume) = storage
+        .withdrawal_limits_schema()
+        .load_volumes(now.date().naive_utc(), withdraw.token, withdraw.from)
+        .await
+        .map_err(db_error)?;
+    account_volume += batch_account_volume.cloned().unwrap_or_default();
+    total_volume += batch_total_volume.cloned().unwrap_or_default();
+    limits
+        .check(&withdraw.amount, &account_volume, &total_volume)
+        .map_err(|limit| TxAddError::WithdrawalLimitExceeded {
+            token: withdraw.token,
+            limit,
+        })?;
+
+    if limits.is_delayed(&withdraw.amount) {
+        let available_at = storage
+            .withdrawal_limits_schema()
+            .delay_withdrawal(
+                tx_hash,
+                withdraw.from,
+                withdraw.token,
+                &withdraw.amount,
+                now + Duration::seconds(limits.large_withdrawal_delay as i64),
+            )
+            .await
+            .map_err(db_error)?;
+        if available_at > now {
+            return Err(TxAddError::WithdrawalDelayed { available_at });
+        }
+    }
+    Ok(())
+}
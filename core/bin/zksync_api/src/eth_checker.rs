@@ -2,26 +2,842 @@
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
 
-use web3::{contract::Options, types::Address};
-use zksync_contracts::eip1271_contract;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
+
+use ethabi::Token;
+use thiserror::Error;
+use tokio::sync::{oneshot, watch};
+use web3::{
+    contract::{tokens::Tokenizable, Options},
+    types::{Address, BlockId, BlockNumber, U256},
+};
+use zksync_contracts::{eip1271_contract, multicall_contract, zksync_contract};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
-    tx::EIP1271Signature,
-    {Nonce, PubKeyHash},
+    tx::{
+        BatchMessageFormats, EIP1271Signature, Eip712Domain, MessageVersion,
+        PackedETHSignatureError, PackedEthSignature,
+    },
+    {Nonce, PubKeyHash, H256},
 };
+use zksync_utils::shutdown::ShutdownToken;
+
+use crate::utils::shared_lru_cache::SharedLruCache;
 
 /// isValidSignature return value according to EIP1271 standard
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Error returned instead of calling the Ethereum node while the `CircuitBreaker` is open.
+#[derive(Debug, Error)]
+#[error("Ethereum node is unavailable")]
+pub struct EthereumNodeUnavailable;
+
+/// State of the `CircuitBreaker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Ethereum node is called as usual.
+    Closed,
+    /// Ethereum node has been failing, so it's not called.
+    Open,
+    /// Ethereum node is called again after the outage. The first finished call
+    /// either closes the breaker or opens it again.
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Value of the `signature_checker.eth_circuit_breaker_state` gauge.
+    fn metric_value(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CircuitBreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+}
+
+impl CircuitBreakerInner {
+    fn set_state(&mut self, state: CircuitState) {
+        self.state = state;
+        metrics::gauge!(
+            "signature_checker.eth_circuit_breaker_state",
+            state.metric_value()
+        );
+    }
+}
+
+/// Stops calling the Ethereum node after the given number of consecutive failed calls,
+/// so the checks requiring the node are rejected immediately instead of waiting for
+/// the node to fail. Checks which don't need the node aren't affected.
+///
+/// Only the calls which didn't reach the node count as failures: transport errors,
+/// including the timeouts of the transport. Calls rejected by the node, e.g. reverted
+/// contract calls or the ones with invalid arguments, mean the node is available.
+///
+/// While the breaker is open, `run_probe` periodically half-opens it and calls the node.
+/// Breaker is closed as soon as the node answers.
+///
+/// State of the breaker is reported by the `signature_checker.eth_circuit_breaker_state` gauge
+/// (0 is closed, 1 is half-open, 2 is open) and can be added to the health checks with `health`.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    probe_interval: Duration,
+    inner: Arc<Mutex<CircuitBreakerInner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, probe_interval: Duration) -> Self {
+        let mut inner = CircuitBreakerInner {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+        };
+        inner.set_state(CircuitState::Closed);
+        Self {
+            failure_threshold,
+            probe_interval,
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Health check of the breaker: fails while the breaker is open, i.e. while the checks
+    /// requiring the Ethereum node are rejected.
+    pub fn health(&self) -> Result<(), String> {
+        match self.state() {
+            CircuitState::Open => {
+                Err("circuit breaker is open, Ethereum node is unavailable".to_owned())
+            }
+            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+        }
+    }
+
+    fn ensure_closed(&self) -> Result<(), EthereumNodeUnavailable> {
+        match self.state() {
+            CircuitState::Open => {
+                metrics::increment_counter!("signature_checker.eth_circuit_breaker_rejected");
+                Err(EthereumNodeUnavailable)
+            }
+            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Closed {
+            vlog::info!("Ethereum node is available again, closing the circuit breaker");
+            inner.set_state(CircuitState::Closed);
+        }
+        inner.consecutive_failures = 0;
+    }
+
+    fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        let should_open = inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.failure_threshold;
+        if should_open && inner.state != CircuitState::Open {
+            vlog::warn!(
+                "Ethereum node failed {} times in a row, opening the circuit breaker",
+                inner.consecutive_failures
+            );
+            metrics::increment_counter!("signature_checker.eth_circuit_breaker_opened");
+            inner.set_state(CircuitState::Open);
+        }
+    }
+
+    /// Lets the calls through an open breaker. Returns `false` if the breaker wasn't open.
+    fn half_open(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Open {
+            return false;
+        }
+        inner.set_state(CircuitState::HalfOpen);
+        true
+    }
+
+    /// Periodically probes the Ethereum node while the breaker is open, until the shutdown
+    /// is signalled.
+    pub async fn run_probe(
+        self,
+        client: watch::Receiver<EthereumGateway>,
+        shutdown: ShutdownToken,
+    ) {
+        let mut timer = tokio::time::interval(self.probe_interval);
+        loop {
+            tokio::select! {
+                _ = timer.tick() => {}
+                _ = shutdown.signalled() => return,
+            }
+            if !self.half_open() {
+                continue;
+            }
+            let client = client.borrow().clone();
+            match client.block_number().await {
+                Ok(_) => self.record_success(),
+                Err(err) => {
+                    vlog::warn!("Ethereum node probe failed: {:#}", err);
+                    self.record_failure();
+                }
+            }
+        }
+    }
+}
+
+/// Cache of the recovered ECDSA signers, shared by all the signature checker workers.
+///
+/// Keyed by the hash of the signed message along with the signature bytes, so
+/// the same signature of a different message is recovered again.
+#[derive(Debug, Clone)]
+pub struct SignerCache(SharedLruCache<(H256, [u8; 65]), Address>);
+
+impl SignerCache {
+    pub fn new(capacity: usize) -> Self {
+        Self(SharedLruCache::new(capacity))
+    }
+
+    /// Recovers the signer of the normalized `signature`, consulting the results of
+    /// the previous recoveries first.
+    pub fn recover_signer(
+        &self,
+        signature: &PackedEthSignature,
+        message: &[u8],
+    ) -> Result<Address, PackedETHSignatureError> {
+        let key = (
+            H256(tiny_keccak::keccak256(message)),
+            signature.serialize_packed(),
+        );
+        if let Some(signer) = self.0.get(&key) {
+            metrics::increment_counter!("signature_checker.signer_cache_hits");
+            return Ok(signer);
+        }
+        metrics::increment_counter!("signature_checker.signer_cache_misses");
+        let signer = signature.signature_recover_signer(message)?;
+        self.0.insert(key, signer);
+        Ok(signer)
+    }
+}
+
+/// Cache of the EIP1271 signature check results, so the same signature of a smart contract
+/// wallet (e.g. the one signing every transaction of the batch) is checked onchain once
+/// within the TTL.
+///
+/// Keyed by the account along with the hashes of the signed message and the signature,
+/// so any other signature is checked again. Results are only cached if the Ethereum node
+/// has answered.
+#[derive(Debug, Clone)]
+pub struct Eip1271Cache {
+    results: SharedLruCache<(Address, H256, H256), (Instant, bool)>,
+    ttl: Duration,
+}
+
+impl Eip1271Cache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            results: SharedLruCache::new(capacity),
+            ttl,
+        }
+    }
+
+    fn key(
+        address: Address,
+        message: &[u8],
+        signature: &EIP1271Signature,
+    ) -> (Address, H256, H256) {
+        (
+            address,
+            H256(tiny_keccak::keccak256(message)),
+            H256(tiny_keccak::keccak256(&signature.0)),
+        )
+    }
+
+    fn get(&self, key: &(Address, H256, H256)) -> Option<bool> {
+        match self.results.get(key) {
+            Some((checked_at, is_correct)) if checked_at.elapsed() < self.ttl => {
+                metrics::increment_counter!("signature_checker.eip1271_cache_hits");
+                Some(is_correct)
+            }
+            _ => {
+                metrics::increment_counter!("signature_checker.eip1271_cache_misses");
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: (Address, H256, H256), is_correct: bool) {
+        self.results.insert(key, (Instant::now(), is_correct));
+    }
+}
+
+/// Contract call waiting to be aggregated by the `MulticallBatcher`.
+#[derive(Debug)]
+struct PendingCall {
+    target: Address,
+    call_data: Vec<u8>,
+    response: oneshot::Sender<Option<Vec<u8>>>,
+}
+
+#[derive(Debug)]
+struct MulticallBatcherInner {
+    multicall_address: Address,
+    zksync_contract_address: Option<Address>,
+    window: Duration,
+    /// Calls waiting for the aggregated call, grouped by the block they are made at.
+    /// `None` stands for the latest block.
+    pending: Mutex<HashMap<Option<u64>, Vec<PendingCall>>>,
+}
+
+/// Aggregates the onchain checks requested within a short window into a single call of
+/// the Multicall contract's `aggregate`, so a batch of transactions from smart contract
+/// wallets costs one call to the Ethereum node instead of one call per transaction.
+///
+/// `aggregate` reverts as a whole if any of the calls reverts, e.g. when a wallet rejects
+/// the signature by reverting instead of returning a wrong value. In this case, as well as
+/// on any other failure of the aggregated call, the checks fall back to the individual calls,
+/// so their outcomes (and the retries or the circuit breaker) are not affected by the batching.
+#[derive(Debug, Clone)]
+pub struct MulticallBatcher(Arc<MulticallBatcherInner>);
+
+impl MulticallBatcher {
+    /// Creates a batcher calling the Multicall contract at `multicall_address`. The `authFacts`
+    /// calls are only aggregated if the address of the zkSync contract is known.
+    pub fn new(
+        multicall_address: Address,
+        zksync_contract_address: Option<Address>,
+        window: Duration,
+    ) -> Self {
+        Self(Arc::new(MulticallBatcherInner {
+            multicall_address,
+            zksync_contract_address,
+            window,
+            pending: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// Adds the call to the aggregated call made at the given block and waits for its result.
+    /// Returns `None` if the call has to be made individually.
+    async fn call(
+        &self,
+        client: &EthereumGateway,
+        target: Address,
+        call_data: Vec<u8>,
+        block: Option<u64>,
+    ) -> Option<Vec<u8>> {
+        let (response, receiver) = oneshot::channel();
+        let starts_window = {
+            let mut pending = self.0.pending.lock().unwrap();
+            let calls = pending.entry(block).or_default();
+            calls.push(PendingCall {
+                target,
+                call_data,
+                response,
+            });
+            calls.len() == 1
+        };
+        if starts_window {
+            // The aggregated call is made in the background, so the other calls of the window
+            // are answered even if the check which has started it is cancelled.
+            tokio::spawn(self.clone().flush(client.clone(), block));
+        }
+        receiver.await.ok().flatten()
+    }
+
+    async fn flush(self, client: EthereumGateway, block: Option<u64>) {
+        tokio::time::sleep(self.0.window).await;
+        let calls = self
+            .0
+            .pending
+            .lock()
+            .unwrap()
+            .remove(&block)
+            .unwrap_or_default();
+        // There is nothing to aggregate a single call with.
+        if calls.len() < 2 {
+            for call in calls {
+                call.response.send(None).ok();
+            }
+            return;
+        }
+
+        metrics::histogram!("signature_checker.multicall_size", calls.len() as f64);
+        let call_tokens = calls
+            .iter()
+            .map(|call| {
+                Token::Tuple(vec![
+                    Token::Address(call.target),
+                    Token::Bytes(call.call_data.clone()),
+                ])
+            })
+            .collect();
+        let block_id = block.map(|block| BlockId::Number(BlockNumber::Number(block.into())));
+        let result: Result<(U256, Token), anyhow::Error> = client
+            .call_contract_function(
+                "aggregate",
+                (Token::Array(call_tokens),),
+                None,
+                Options::default(),
+                block_id,
+                self.0.multicall_address,
+                multicall_contract(),
+            )
+            .await;
+        let return_data = match result {
+            Ok((_, Token::Array(return_data))) if return_data.len() == calls.len() => return_data,
+            Ok(_) => {
+                vlog::warn!("Multicall aggregate returned an unexpected result");
+                Vec::new()
+            }
+            Err(err) => {
+                vlog::debug!(
+                    "Multicall aggregate of {} calls failed, making them individually: {:#}",
+                    calls.len(),
+                    err
+                );
+                metrics::increment_counter!("signature_checker.multicall_fallbacks");
+                Vec::new()
+            }
+        };
+
+        let mut return_data = return_data.into_iter();
+        for call in calls {
+            let data = match return_data.next() {
+                Some(Token::Bytes(data)) => Some(data),
+                _ => None,
+            };
+            call.response.send(data).ok();
+        }
+    }
+}
+
+/// Result of the onchain `ChangePubKey` authorization check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PubKeyHashAuthorization {
+    /// Authorization is confirmed by the required number of blocks.
+    Authorized,
+    /// Authorization exists in the latest block, but may still be reorged away, or it's missing
+    /// but may have been mined without being seen by the node yet. The check has to be
+    /// repeated later.
+    NotConfirmed,
+    /// There is no authorization for the public key hash.
+    NotAuthorized,
+}
+
+/// Block the onchain `ChangePubKey` authorization is checked at.
+#[derive(Debug, Clone)]
+pub enum AuthFactBlock {
+    /// The latest block. The authorization may still be reorged away.
+    Latest,
+    /// The block the given number of blocks behind the latest one. The authorization has
+    /// to be present at both blocks: the one present only in the more recent blocks is reported
+    /// as `NotConfirmed`, and the one missing in the latest block (e.g. reorged away) is rejected.
+    Confirmed(u64),
+    /// The latest block. The authorization missing there is reported as `NotConfirmed` until
+    /// the delay passes since it was found missing for the first time, so the authorization
+    /// which has just been mined is not rejected because of the node lagging behind.
+    LatestWithRetry(AuthRetries),
+}
+
+impl Default for AuthFactBlock {
+    fn default() -> Self {
+        Self::Latest
+    }
+}
+
+/// Onchain `ChangePubKey` authorizations found missing in the latest block, shared by all
+/// the signature checker workers, see `AuthFactBlock::LatestWithRetry`.
+///
+/// The check is not delayed: the client resubmits the transaction instead, and it's rejected
+/// once the authorization is still missing after the delay.
+#[derive(Debug, Clone)]
+pub struct AuthRetries {
+    first_misses: SharedLruCache<(Address, Nonce, H256), Instant>,
+    delay: Duration,
+}
+
+impl AuthRetries {
+    pub fn new(capacity: usize, delay: Duration) -> Self {
+        Self {
+            first_misses: SharedLruCache::new(capacity),
+            delay,
+        }
+    }
+
+    /// Records the missing authorization, returns `true` if it may still appear,
+    /// i.e. it was found missing for the first time less than the delay ago.
+    fn may_appear(&self, key: (Address, Nonce, H256)) -> bool {
+        match self.first_misses.get(&key) {
+            Some(missed_at) => missed_at.elapsed() < self.delay,
+            None => {
+                self.first_misses.insert(key, Instant::now());
+                true
+            }
+        }
+    }
+}
+
+/// Retries of the calls to the Ethereum node which didn't reach it, see
+/// `EthereumChecker::with_call_retries`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthCallRetries {
+    /// Number of retries after the first failed attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry. Every next delay is twice as long.
+    pub initial_backoff: Duration,
+}
+
+/// Chain the text messages signed with the Ethereum key are bound to, see
+/// `EthereumChecker::with_chain_binding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainBinding {
+    /// ID of the chain the messages are bound to.
+    pub chain_id: u64,
+    /// Whether the signatures of the messages which aren't bound to the chain are still
+    /// accepted, so the wallets signing them keep working while they migrate.
+    pub accept_unbound: bool,
+}
+
+impl ChainBinding {
+    /// Versions of the message the signatures are accepted for, in the order they're checked.
+    pub fn message_versions(self) -> &'static [MessageVersion] {
+        if self.accept_unbound {
+            &[MessageVersion::ChainBound, MessageVersion::Unbound]
+        } else {
+            &[MessageVersion::ChainBound]
+        }
+    }
+}
+
+/// Constant outcome of the onchain checks made by the checker which doesn't call
+/// the Ethereum node, see `EthereumChecker::with_offline_checks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfflineEthChecks {
+    /// Every EIP1271 signature and onchain `ChangePubKey` authorization is accepted.
+    AcceptAll,
+    /// Every EIP1271 signature and onchain `ChangePubKey` authorization is rejected.
+    RejectAll,
+}
+
+/// Numbers of the onchain checks requested from the `EthereumChecker`.
+///
+/// A single check may result in several calls to the Ethereum node, e.g. the `ChangePubKey`
+/// authorization checked in the confirmed block also queries the latest block number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthCallCounts {
+    /// Calls to `is_eip1271_signature_correct`.
+    pub eip1271_signature: u32,
+    /// Calls to `is_new_pubkey_hash_authorized`.
+    pub pubkey_hash_authorization: u32,
+}
+
+impl EthCallCounts {
+    pub fn total(&self) -> u32 {
+        self.eip1271_signature + self.pubkey_hash_authorization
+    }
+}
+
+/// Counter of the onchain checks shared by the clones of the `EthereumChecker`,
+/// see `EthereumChecker::with_call_counter`.
+#[derive(Debug, Clone, Default)]
+pub struct EthCallCounter(Arc<EthCallCounterInner>);
+
+#[derive(Debug, Default)]
+struct EthCallCounterInner {
+    eip1271_signature: AtomicU32,
+    pubkey_hash_authorization: AtomicU32,
+}
+
+impl EthCallCounter {
+    pub fn counts(&self) -> EthCallCounts {
+        EthCallCounts {
+            eip1271_signature: self.0.eip1271_signature.load(Ordering::Relaxed),
+            pubkey_hash_authorization: self.0.pubkey_hash_authorization.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Maximum time the Ethereum node has to answer the health check.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connectivity of the Ethereum node, see `EthereumChecker::node_health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthNodeHealth {
+    /// Latest block reported by the node. `None` if the node is unreachable.
+    pub latest_block: Option<u64>,
+    /// Time the node took to answer or to fail.
+    pub latency: Duration,
+}
+
+impl EthNodeHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.latest_block.is_some()
+    }
+}
+
+/// Performs the onchain checks of the signatures and the `ChangePubKey` authorizations.
+///
+/// Checker calls the node through the `EthereumGateway`, so it doesn't depend on the transport:
+/// to verify the transactions without the Ethereum node, create it with `EthereumGateway::Mock`
+/// and set the call results on the `MockEthereum`, or use `with_offline_checks` to answer all
+/// the onchain checks with the constant outcome.
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
+    circuit_breaker: Option<CircuitBreaker>,
+    auth_block: AuthFactBlock,
+    signer_cache: Option<SignerCache>,
+    eip1271_cache: Option<Eip1271Cache>,
+    batch_message_formats: BatchMessageFormats,
+    call_counter: Option<EthCallCounter>,
+    offline_checks: Option<OfflineEthChecks>,
+    eip712_domain: Option<Eip712Domain>,
+    chain_binding: Option<ChainBinding>,
+    accept_untimed_messages: bool,
+    call_retries: Option<EthCallRetries>,
+    multicall: Option<MulticallBatcher>,
 }
 
 impl EthereumChecker {
     pub fn new(client: EthereumGateway) -> Self {
-        Self { client }
+        Self {
+            client,
+            circuit_breaker: None,
+            auth_block: AuthFactBlock::Latest,
+            signer_cache: None,
+            eip1271_cache: None,
+            batch_message_formats: BatchMessageFormats::all(),
+            call_counter: None,
+            offline_checks: None,
+            eip712_domain: None,
+            chain_binding: None,
+            accept_untimed_messages: false,
+            call_retries: None,
+            multicall: None,
+        }
+    }
+
+    /// Creates a checker which doesn't call the Ethereum node while the `circuit_breaker`
+    /// is open. Such calls fail with `EthereumNodeUnavailable`.
+    pub fn with_circuit_breaker(client: EthereumGateway, circuit_breaker: CircuitBreaker) -> Self {
+        Self {
+            client,
+            circuit_breaker: Some(circuit_breaker),
+            auth_block: AuthFactBlock::Latest,
+            signer_cache: None,
+            eip1271_cache: None,
+            batch_message_formats: BatchMessageFormats::all(),
+            call_counter: None,
+            offline_checks: None,
+            eip712_domain: None,
+            chain_binding: None,
+            accept_untimed_messages: false,
+            call_retries: None,
+            multicall: None,
+        }
+    }
+
+    /// Sets the block the onchain `ChangePubKey` authorization is checked at.
+    /// By default, the authorization is checked in the latest block.
+    pub fn with_auth_block(mut self, auth_block: AuthFactBlock) -> Self {
+        self.auth_block = auth_block;
+        self
+    }
+
+    /// Sets the cache consulted before recovering the signers of ECDSA signatures.
+    pub fn with_signer_cache(mut self, signer_cache: SignerCache) -> Self {
+        self.signer_cache = Some(signer_cache);
+        self
+    }
+
+    /// Sets the cache consulted before checking the EIP1271 signatures onchain.
+    pub fn with_eip1271_cache(mut self, eip1271_cache: Eip1271Cache) -> Self {
+        self.eip1271_cache = Some(eip1271_cache);
+        self
+    }
+
+    /// Sets the formats of the batch message the batch signatures are accepted for.
+    /// By default, all the formats are accepted.
+    pub fn with_batch_message_formats(mut self, formats: BatchMessageFormats) -> Self {
+        self.batch_message_formats = formats;
+        self
+    }
+
+    pub fn batch_message_formats(&self) -> BatchMessageFormats {
+        self.batch_message_formats
+    }
+
+    /// Makes the checker answer the onchain checks with the constant outcome instead of
+    /// calling the Ethereum node, so the API can run without one, e.g. locally or in CI.
+    ///
+    /// Accepting all the checks lets anyone impersonate the smart contract wallets,
+    /// so the offline checker must never be used on mainnet.
+    pub fn with_offline_checks(mut self, offline_checks: OfflineEthChecks) -> Self {
+        self.offline_checks = Some(offline_checks);
+        self
+    }
+
+    /// Sets the counter of the onchain checks made by the checker, so the cost of
+    /// a single verification in terms of the Ethereum node calls can be measured.
+    pub fn with_call_counter(mut self, call_counter: EthCallCounter) -> Self {
+        self.call_counter = Some(call_counter);
+        self
+    }
+
+    /// Makes the checker retry the calls to the Ethereum node which failed to reach it,
+    /// so a transient RPC failure doesn't fail the check. Calls answered by the node are
+    /// never retried. Retries stop once the circuit breaker opens.
+    pub fn with_call_retries(mut self, call_retries: EthCallRetries) -> Self {
+        self.call_retries = Some(call_retries);
+        self
+    }
+
+    /// Sets the domain of the EIP-712 typed data the `EIP712Signature`s are checked for.
+    /// Such signatures are not supported unless the domain is set.
+    pub fn with_eip712_domain(mut self, domain: Eip712Domain) -> Self {
+        self.eip712_domain = Some(domain);
+        self
+    }
+
+    /// Makes the checker expect the text messages to be bound to the chain, see `MessageVersion`.
+    /// Messages are accepted as is unless the binding is set. EIP-712 typed data is always
+    /// checked as is, since its domain includes the chain ID.
+    pub fn with_chain_binding(mut self, chain_binding: ChainBinding) -> Self {
+        self.chain_binding = Some(chain_binding);
+        self
+    }
+
+    /// Makes the checker accept the signatures of the text messages without the bounds of
+    /// the time ranges along with the ones of the messages including them, see
+    /// `SignedMessage::without_time_range`. By default, only the latter are accepted.
+    pub fn with_untimed_messages(mut self, accept: bool) -> Self {
+        self.accept_untimed_messages = accept;
+        self
+    }
+
+    /// Makes the checker aggregate the onchain checks made at the same time with the other
+    /// checkers sharing the `batcher`, see `MulticallBatcher`.
+    pub fn with_multicall(mut self, batcher: MulticallBatcher) -> Self {
+        self.multicall = Some(batcher);
+        self
+    }
+
+    /// Returns the checker with the same settings and caches calling the node through
+    /// the `client`, e.g. after the client is replaced at runtime.
+    pub fn with_client(&self, client: EthereumGateway) -> Self {
+        Self {
+            client,
+            ..self.clone()
+        }
+    }
+
+    pub fn eip712_domain(&self) -> Option<&Eip712Domain> {
+        self.eip712_domain.as_ref()
+    }
+
+    pub fn chain_binding(&self) -> Option<ChainBinding> {
+        self.chain_binding
+    }
+
+    pub fn accepts_untimed_messages(&self) -> bool {
+        self.accept_untimed_messages
+    }
+
+    /// Recovers the signer of the normalized ECDSA `signature`.
+    /// Doesn't require the Ethereum node.
+    pub fn recover_signer(
+        &self,
+        signature: &PackedEthSignature,
+        message: &[u8],
+    ) -> Result<Address, PackedETHSignatureError> {
+        match &self.signer_cache {
+            Some(signer_cache) => signer_cache.recover_signer(signature, message),
+            None => signature.signature_recover_signer(message),
+        }
+    }
+
+    /// Checks whether the Ethereum node is reachable by querying the latest block.
+    ///
+    /// Unlike the other checks, the node is called even if the circuit breaker is open,
+    /// and the result doesn't affect the breaker. The node which doesn't answer within
+    /// `HEALTH_CHECK_TIMEOUT` is considered unreachable.
+    pub async fn node_health(&self) -> EthNodeHealth {
+        let start = Instant::now();
+        let latest_block =
+            match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, self.client.block_number()).await {
+                Ok(Ok(block)) => Some(block.as_u64()),
+                Ok(Err(err)) => {
+                    vlog::debug!("Ethereum node health check failed: {:#}", err);
+                    None
+                }
+                Err(_) => {
+                    vlog::debug!("Ethereum node health check timed out");
+                    None
+                }
+            };
+        EthNodeHealth {
+            latest_block,
+            latency: start.elapsed(),
+        }
+    }
+
+    fn ensure_node_available(&self) -> Result<(), EthereumNodeUnavailable> {
+        match &self.circuit_breaker {
+            Some(circuit_breaker) => circuit_breaker.ensure_closed(),
+            None => Ok(()),
+        }
+    }
+
+    fn record_call(&self, node_answered: bool) {
+        match &self.circuit_breaker {
+            Some(circuit_breaker) if node_answered => circuit_breaker.record_success(),
+            Some(circuit_breaker) => circuit_breaker.record_failure(),
+            None => {}
+        }
+    }
+
+    /// Calls the Ethereum node, retrying the call with the exponential backoff if it didn't
+    /// reach the node according to `node_answered`. Every attempt is recorded by the circuit
+    /// breaker.
+    async fn call_node<T, F, Fut>(
+        &self,
+        call: F,
+        node_answered: fn(&Result<T, anyhow::Error>) -> bool,
+    ) -> Result<T, anyhow::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, anyhow::Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            self.ensure_node_available()?;
+            let result = call().await;
+            let answered = node_answered(&result);
+            self.record_call(answered);
+            match self.call_retries {
+                Some(retries) if !answered && attempt < retries.max_retries => {
+                    let backoff = retries.initial_backoff * 2u32.saturating_pow(attempt);
+                    attempt += 1;
+                    vlog::debug!(attempt, ?backoff, "Retrying the call to the Ethereum node");
+                    metrics::increment_counter!("signature_checker.eth_call_retries");
+                    tokio::time::sleep(backoff).await;
+                }
+                _ => return result,
+            }
+        }
     }
 
     /// Transforms the message into an array expected by EIP-1271 standard.
@@ -40,23 +856,55 @@ impl EthereumChecker {
         message: &[u8],
         signature: EIP1271Signature,
     ) -> Result<bool, anyhow::Error> {
+        if let Some(call_counter) = &self.call_counter {
+            call_counter
+                .0
+                .eip1271_signature
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(offline_checks) = self.offline_checks {
+            return Ok(offline_checks == OfflineEthChecks::AcceptAll);
+        }
+        let cache_key = Eip1271Cache::key(address, message, &signature);
+        if let Some(is_correct) = self
+            .eip1271_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&cache_key))
+        {
+            return Ok(is_correct);
+        }
         let sign_message = Self::get_sign_message(message);
 
-        let call_result = self
-            .client
-            .call_contract_function(
-                "isValidSignature",
-                (sign_message, signature.0),
-                Some(address),
-                Options::default(),
-                None,
-                address,
-                eip1271_contract(),
-            )
-            .await;
+        let call_result = match self
+            .multicall_eip1271(address, sign_message, &signature)
+            .await?
+        {
+            Some(result) => result,
+            None => {
+                self.call_node(
+                    move || {
+                        self.client.call_contract_function(
+                            "isValidSignature",
+                            (sign_message, signature.0.clone()),
+                            Some(address),
+                            Options::default(),
+                            None,
+                            address,
+                            eip1271_contract(),
+                        )
+                    },
+                    |result| !matches!(result, Err(error) if is_transport_error(error)),
+                )
+                .await
+            }
+        };
 
         let received: [u8; 4] = match call_result {
             Ok(val) => val,
+            // Ethereum node wasn't reached, so nothing is known about the signature.
+            Err(error) if is_transport_error(&error) => {
+                return Err(error.context("Unable to call isValidSignature"));
+            }
             Err(error) => {
                 // One error of this kind will mean that user provided incorrect signature.
                 // Many errors will likely mean that something is wrong with our implementation.
@@ -65,43 +913,249 @@ impl EthereumChecker {
             }
         };
 
-        Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
+        let is_correct = received == EIP1271_SUCCESS_RETURN_VALUE;
+        if let Some(cache) = &self.eip1271_cache {
+            cache.insert(cache_key, is_correct);
+        }
+        Ok(is_correct)
+    }
+
+    /// Calls `isValidSignature` as a part of the aggregated call if the checker has
+    /// the `MulticallBatcher`. Returns `None` if the call has to be made individually.
+    async fn multicall_eip1271(
+        &self,
+        address: Address,
+        sign_message: [u8; 32],
+        signature: &EIP1271Signature,
+    ) -> Result<Option<Result<[u8; 4], anyhow::Error>>, anyhow::Error> {
+        let multicall = match &self.multicall {
+            Some(multicall) => multicall,
+            None => return Ok(None),
+        };
+        self.ensure_node_available()?;
+        let contract = eip1271_contract();
+        let function = contract.function("isValidSignature")?;
+        let call_data = function.encode_input(&[
+            Token::FixedBytes(sign_message.to_vec()),
+            Token::Bytes(signature.0.clone()),
+        ])?;
+        let return_data = match multicall.call(&self.client, address, call_data, None).await {
+            Some(return_data) => return_data,
+            None => return Ok(None),
+        };
+        self.record_call(true);
+        let result = function
+            .decode_output(&return_data)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut tokens| match tokens.pop() {
+                Some(token) if tokens.is_empty() => Ok(<[u8; 4]>::from_token(token)?),
+                _ => Err(anyhow::format_err!("Unexpected isValidSignature output")),
+            });
+        Ok(Some(result))
     }
 
+    /// Checks whether the `pub_key_hash` is authorized onchain for the account's `ChangePubKey`.
+    ///
+    /// The block the authorization is looked up in is chosen according to the `AuthFactBlock`
+    /// of the checker. The authorization which may still appear or be confirmed later is
+    /// reported as `NotConfirmed` instead of being accepted or rejected.
     pub async fn is_new_pubkey_hash_authorized(
         &self,
         address: Address,
         nonce: Nonce,
         pub_key_hash: &PubKeyHash,
+    ) -> Result<PubKeyHashAuthorization, anyhow::Error> {
+        if let Some(call_counter) = &self.call_counter {
+            call_counter
+                .0
+                .pubkey_hash_authorization
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        match self.offline_checks {
+            Some(OfflineEthChecks::AcceptAll) => return Ok(PubKeyHashAuthorization::Authorized),
+            Some(OfflineEthChecks::RejectAll) => return Ok(PubKeyHashAuthorization::NotAuthorized),
+            None => {}
+        }
+        let latest_block = BlockId::Number(BlockNumber::Latest);
+        let authorized = match &self.auth_block {
+            AuthFactBlock::Latest | AuthFactBlock::Confirmed(0) => {
+                self.is_pubkey_hash_authorized_at(address, nonce, pub_key_hash, latest_block)
+                    .await?
+            }
+            AuthFactBlock::Confirmed(confirmations) => {
+                let block_result = self
+                    .call_node(move || self.client.block_number(), Result::is_ok)
+                    .await;
+                let latest_block_number = block_result
+                    .map_err(|e| anyhow::format_err!("Failed to query the latest block: {}", e))?;
+                let confirmed_block = latest_block_number.as_u64().saturating_sub(*confirmations);
+                let confirmed_block = BlockId::Number(BlockNumber::Number(confirmed_block.into()));
+                let (confirmed, latest) = futures::try_join!(
+                    self.is_pubkey_hash_authorized_at(
+                        address,
+                        nonce,
+                        pub_key_hash,
+                        confirmed_block
+                    ),
+                    self.is_pubkey_hash_authorized_at(address, nonce, pub_key_hash, latest_block),
+                )?;
+                match (confirmed, latest) {
+                    (true, true) => true,
+                    // Authorization may be too recent to be confirmed.
+                    (false, true) => return Ok(PubKeyHashAuthorization::NotConfirmed),
+                    // Authorization which is no longer present at the latest block is
+                    // about to be reorged away.
+                    _ => false,
+                }
+            }
+            AuthFactBlock::LatestWithRetry(retries) => {
+                if self
+                    .is_pubkey_hash_authorized_at(address, nonce, pub_key_hash, latest_block)
+                    .await?
+                {
+                    return Ok(PubKeyHashAuthorization::Authorized);
+                }
+                // Authorization may have been mined, but not yet seen by the node.
+                let auth_fact = H256(tiny_keccak::keccak256(&pub_key_hash.data[..]));
+                if retries.may_appear((address, nonce, auth_fact)) {
+                    return Ok(PubKeyHashAuthorization::NotConfirmed);
+                }
+                false
+            }
+        };
+        Ok(match authorized {
+            true => PubKeyHashAuthorization::Authorized,
+            false => PubKeyHashAuthorization::NotAuthorized,
+        })
+    }
+
+    /// Checks whether the `pub_key_hash` is authorized onchain for the account's `ChangePubKey`
+    /// at the given block.
+    pub async fn is_pubkey_hash_authorized_at(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        pub_key_hash: &PubKeyHash,
+        block: BlockId,
     ) -> Result<bool, anyhow::Error> {
-        let auth_fact: Vec<u8> = self
-            .client
-            .call_main_contract_function(
-                "authFacts",
-                (address, u64::from(*nonce)),
-                None,
-                Options::default(),
-                None,
+        if let Some(offline_checks) = self.offline_checks {
+            return Ok(offline_checks == OfflineEthChecks::AcceptAll);
+        }
+        let expected_auth_fact = tiny_keccak::keccak256(&pub_key_hash.data[..]);
+        let auth_fact = self.auth_fact(address, nonce, block).await?;
+        Ok(auth_fact == expected_auth_fact)
+    }
+
+    /// Queries the `authFacts` of the account at the given block.
+    async fn auth_fact(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        block: BlockId,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        if let Some(auth_fact) = self.multicall_auth_fact(address, nonce, block).await? {
+            return Ok(auth_fact);
+        }
+        let call_result = self
+            .call_node(
+                move || {
+                    self.client.call_main_contract_function(
+                        "authFacts",
+                        (address, u64::from(*nonce)),
+                        None,
+                        Options::default(),
+                        Some(block),
+                    )
+                },
+                |result| !matches!(result, Err(error) if is_transport_error(error)),
             )
+            .await;
+        call_result.map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))
+    }
+
+    /// Queries the `authFacts` as a part of the aggregated call if the checker has
+    /// the `MulticallBatcher`. Returns `None` if the call has to be made individually.
+    async fn multicall_auth_fact(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        block: BlockId,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        let (multicall, zksync_contract_address) = match &self.multicall {
+            Some(multicall) => match multicall.0.zksync_contract_address {
+                Some(zksync_contract_address) => (multicall, zksync_contract_address),
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        let block = match block {
+            BlockId::Number(BlockNumber::Latest) => None,
+            BlockId::Number(BlockNumber::Number(block)) => Some(block.as_u64()),
+            _ => return Ok(None),
+        };
+        self.ensure_node_available()?;
+        let contract = zksync_contract();
+        let function = contract.function("authFacts")?;
+        let call_data = function.encode_input(&[
+            Token::Address(address),
+            Token::Uint(u64::from(*nonce).into()),
+        ])?;
+        let return_data = match multicall
+            .call(&self.client, zksync_contract_address, call_data, block)
             .await
-            .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))?;
-        Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
+        {
+            Some(return_data) => return_data,
+            None => return Ok(None),
+        };
+        self.record_call(true);
+        let mut tokens = function
+            .decode_output(&return_data)
+            .map_err(|e| anyhow::format_err!("Failed to decode contract authFacts: {}", e))?;
+        match tokens.pop() {
+            Some(token) if tokens.is_empty() => Ok(Some(Vec::<u8>::from_token(token)?)),
+            _ => Err(anyhow::format_err!("Unexpected authFacts output")),
+        }
     }
 }
 
+/// Returns `true` if the Ethereum node of the checker is reachable, e.g. for the readiness
+/// probe. See `EthereumChecker::node_health` for details.
+pub async fn eth_checker_healthy(eth_checker: &EthereumChecker) -> bool {
+    eth_checker.node_health().await.is_healthy()
+}
+
+/// Checks whether the contract call failed before reaching the Ethereum node,
+/// as opposed to the call being processed and rejected by the node.
+fn is_transport_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<web3::contract::Error>(),
+        Some(web3::contract::Error::Api(
+            web3::Error::Transport(_) | web3::Error::Unreachable | web3::Error::Io(_)
+        ))
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::EthereumChecker;
+    use super::{
+        eth_checker_healthy, CircuitBreaker, CircuitState, Eip1271Cache, EthCallRetries,
+        EthereumChecker, EthereumNodeUnavailable, MulticallBatcher, OfflineEthChecks,
+        PubKeyHashAuthorization, SignerCache, EIP1271_SUCCESS_RETURN_VALUE,
+    };
     use std::str::FromStr;
+    use std::time::Duration;
+    use tokio::sync::watch;
     use zksync_config::test_config::TestConfig;
     use zksync_contracts::zksync_contract;
+    use zksync_eth_client::clients::mock::MockEthereum;
     use zksync_eth_client::ethereum_gateway::EthereumGateway;
-    use zksync_eth_client::ETHDirectClient;
+    use zksync_eth_client::{ETHDirectClient, MultiplexerEthereumClient};
     use zksync_eth_signer::PrivateKeySigner;
     use zksync_types::{
         tx::{EIP1271Signature, PackedEthSignature},
-        Address,
+        Address, Nonce, PubKeyHash, H256,
     };
+    use zksync_utils::shutdown::Shutdown;
 
     #[tokio::test]
     async fn test_eip1271() {
@@ -124,7 +1178,7 @@ mod tests {
             transport,
             zksync_contract(),
             Default::default(),
-            PrivateKeySigner::new(Default::default()),
+            PrivateKeySigner::new(Default::default()).into(),
             Default::default(),
             0,
             1.0,
@@ -144,6 +1198,357 @@ mod tests {
         assert!(result, "Signature is incorrect");
     }
 
+    /// Checks the transitions of the circuit breaker on the Ethereum node failures.
+    #[tokio::test]
+    async fn circuit_breaker_transitions() {
+        // Mock client has no `authFacts` result, so the node rejects every authorization check.
+        let mock = MockEthereum::default();
+        let client = EthereumGateway::Mock(mock.clone());
+        let circuit_breaker = CircuitBreaker::new(2, Duration::from_millis(10));
+        let eth_checker =
+            EthereumChecker::with_circuit_breaker(client.clone(), circuit_breaker.clone());
+        let pub_key_hash = PubKeyHash::zero();
+        let check_auth = || {
+            eth_checker.is_new_pubkey_hash_authorized(
+                Address::repeat_byte(0x01),
+                Nonce(0),
+                &pub_key_hash,
+            )
+        };
+
+        // Calls rejected by the node aren't failures of the node.
+        for _ in 0..3 {
+            let err = check_auth().await.unwrap_err();
+            assert!(!err.is::<EthereumNodeUnavailable>());
+            assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+        }
+
+        // Node is called until it's unreachable enough times in a row.
+        mock.fail_next_calls(3);
+        let err = check_auth().await.unwrap_err();
+        assert!(!err.is::<EthereumNodeUnavailable>());
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+        assert!(circuit_breaker.health().is_ok());
+        let err = check_auth().await.unwrap_err();
+        assert!(!err.is::<EthereumNodeUnavailable>());
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+        assert!(circuit_breaker.health().is_err());
+
+        // Open breaker rejects the checks without calling the node.
+        let err = check_auth().await.unwrap_err();
+        assert!(err.is::<EthereumNodeUnavailable>());
+
+        // A single failure of the half-open breaker opens it again.
+        assert!(circuit_breaker.half_open());
+        assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+        let err = check_auth().await.unwrap_err();
+        assert!(!err.is::<EthereumNodeUnavailable>());
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+
+        // Probe closes the breaker once the node answers.
+        let (_client_sender, client) = watch::channel(client);
+        let shutdown = Shutdown::new();
+        let probe = tokio::spawn(circuit_breaker.clone().run_probe(client, shutdown.token()));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while circuit_breaker.state() != CircuitState::Closed {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Circuit breaker wasn't closed by the probe");
+        let err = check_auth().await.unwrap_err();
+        assert!(!err.is::<EthereumNodeUnavailable>());
+
+        // Probe is stopped on the shutdown.
+        tokio::time::timeout(Duration::from_secs(5), shutdown.stop())
+            .await
+            .expect("Probe wasn't stopped on the shutdown");
+        probe.await.unwrap();
+    }
+
+    /// Checks that the health check reports the unreachable node, even with the circuit
+    /// breaker closed.
+    #[tokio::test]
+    async fn node_health() {
+        let mut client = MockEthereum::default();
+        client.set_block_number(10.into()).await.unwrap();
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(client));
+        let health = eth_checker.node_health().await;
+        assert_eq!(health.latest_block, Some(10));
+        assert!(eth_checker_healthy(&eth_checker).await);
+
+        let client = MockEthereum::default();
+        client.set_unreachable();
+        let circuit_breaker = CircuitBreaker::new(5, Duration::from_secs(60));
+        let eth_checker = EthereumChecker::with_circuit_breaker(
+            EthereumGateway::Mock(client),
+            circuit_breaker.clone(),
+        );
+        let health = eth_checker.node_health().await;
+        assert_eq!(health.latest_block, None);
+        assert!(!health.is_healthy());
+        assert!(!eth_checker_healthy(&eth_checker).await);
+        // Health check doesn't count as a failed call.
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+    }
+
+    /// Checks that the offline checker answers with the constant outcome without calling the node.
+    #[tokio::test]
+    async fn offline_checks() {
+        let client = MockEthereum::default();
+        client.set_unreachable();
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(client));
+        let pub_key_hash = PubKeyHash::default();
+        let signature = EIP1271Signature(vec![0; 65]);
+
+        for (offline_checks, accepted) in vec![
+            (OfflineEthChecks::AcceptAll, true),
+            (OfflineEthChecks::RejectAll, false),
+        ] {
+            let eth_checker = eth_checker.clone().with_offline_checks(offline_checks);
+            let eip1271_result = eth_checker
+                .is_eip1271_signature_correct(Address::zero(), b"hello-world", signature.clone())
+                .await
+                .expect("Offline check never fails");
+            assert_eq!(eip1271_result, accepted);
+
+            let authorization = eth_checker
+                .is_new_pubkey_hash_authorized(Address::zero(), Nonce(0), &pub_key_hash)
+                .await
+                .expect("Offline check never fails");
+            let expected_authorization = match accepted {
+                true => PubKeyHashAuthorization::Authorized,
+                false => PubKeyHashAuthorization::NotAuthorized,
+            };
+            assert_eq!(authorization, expected_authorization);
+        }
+
+        // Node is called otherwise.
+        let result = eth_checker
+            .is_new_pubkey_hash_authorized(Address::zero(), Nonce(0), &pub_key_hash)
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Checks that the EIP1271 signature isn't rejected when none of the multiplexed
+    /// Ethereum nodes can be reached, since nothing is known about the signature.
+    #[tokio::test]
+    async fn multiplexed_nodes_unreachable() {
+        let mut client = MultiplexerEthereumClient::new();
+        // Nothing listens on these ports, so the connections are refused.
+        for web3_url in vec!["http://127.0.0.1:1", "http://127.0.0.1:2"] {
+            let transport = web3::transports::Http::new(web3_url).unwrap();
+            client.add_client(
+                web3_url.to_string(),
+                ETHDirectClient::new(
+                    transport,
+                    zksync_contract(),
+                    Default::default(),
+                    PrivateKeySigner::new(Default::default()).into(),
+                    Default::default(),
+                    0,
+                    1.0,
+                ),
+            );
+        }
+        let eth_checker = EthereumChecker::new(EthereumGateway::Multiplexed(client));
+
+        let result = eth_checker
+            .is_eip1271_signature_correct(
+                Address::repeat_byte(0x0a),
+                b"hello-world",
+                EIP1271Signature(vec![0; 65]),
+            )
+            .await;
+        assert!(result.is_err(), "{:?}", result);
+    }
+
+    /// Checks that the calls which failed to reach the node are retried a limited number of times.
+    #[tokio::test]
+    async fn call_retries() {
+        let pub_key_hash = PubKeyHash::default();
+        let auth_fact = tiny_keccak::keccak256(&pub_key_hash.data[..]).to_vec();
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(auth_fact)])
+            .await;
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(client.clone()))
+            .with_call_retries(EthCallRetries {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(1),
+            });
+
+        client.fail_next_calls(2);
+        let authorization = eth_checker
+            .is_new_pubkey_hash_authorized(Address::zero(), Nonce(0), &pub_key_hash)
+            .await
+            .expect("Call must succeed after the retries");
+        assert_eq!(authorization, PubKeyHashAuthorization::Authorized);
+
+        client.fail_next_calls(3);
+        let result = eth_checker
+            .is_new_pubkey_hash_authorized(Address::zero(), Nonce(0), &pub_key_hash)
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Checks that the EIP1271 check result is reused for the same account, message
+    /// and signature until it expires.
+    #[tokio::test]
+    async fn eip1271_cache() {
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result(
+                "isValidSignature",
+                vec![ethabi::Token::FixedBytes(
+                    EIP1271_SUCCESS_RETURN_VALUE.to_vec(),
+                )],
+            )
+            .await;
+        // Mock client without the `isValidSignature` result rejects every EIP1271 signature,
+        // so the signature is only accepted by the checker using it if the result is cached.
+        let rejecting_client = EthereumGateway::Mock(MockEthereum::default());
+        let wallet = Address::repeat_byte(0x0a);
+        let signature = EIP1271Signature(vec![0; 65]);
+
+        let cache = Eip1271Cache::new(10, Duration::from_secs(60));
+        let eth_checker =
+            EthereumChecker::new(EthereumGateway::Mock(client)).with_eip1271_cache(cache.clone());
+        let cached_checker = EthereumChecker::new(rejecting_client).with_eip1271_cache(cache);
+        assert!(eth_checker
+            .is_eip1271_signature_correct(wallet, b"hello-world", signature.clone())
+            .await
+            .unwrap());
+        assert!(cached_checker
+            .is_eip1271_signature_correct(wallet, b"hello-world", signature.clone())
+            .await
+            .expect("Cached result must be returned"));
+
+        // Another account, message or signature is checked onchain.
+        let is_correct = cached_checker
+            .is_eip1271_signature_correct(Address::zero(), b"hello-world", signature.clone())
+            .await
+            .unwrap();
+        assert!(!is_correct);
+        let is_correct = cached_checker
+            .is_eip1271_signature_correct(wallet, b"bye-world", signature.clone())
+            .await
+            .unwrap();
+        assert!(!is_correct);
+        let is_correct = cached_checker
+            .is_eip1271_signature_correct(wallet, b"hello-world", EIP1271Signature(vec![1; 65]))
+            .await
+            .unwrap();
+        assert!(!is_correct);
+
+        // Expired results are not used.
+        let cache = Eip1271Cache::new(10, Duration::from_millis(0));
+        let eth_checker = eth_checker.with_eip1271_cache(cache.clone());
+        let cached_checker = cached_checker.with_eip1271_cache(cache);
+        assert!(eth_checker
+            .is_eip1271_signature_correct(wallet, b"hello-world", signature.clone())
+            .await
+            .unwrap());
+        let is_correct = cached_checker
+            .is_eip1271_signature_correct(wallet, b"hello-world", signature)
+            .await
+            .unwrap();
+        assert!(!is_correct);
+    }
+
+    /// Checks that the concurrent EIP1271 checks are aggregated into a single Multicall call,
+    /// and that they are made individually if there is nothing to aggregate or the aggregated
+    /// call fails.
+    #[tokio::test]
+    async fn multicall_batching() {
+        use ethabi::Token;
+
+        let success_output =
+            ethabi::encode(&[Token::FixedBytes(EIP1271_SUCCESS_RETURN_VALUE.to_vec())]);
+        // Mock client without the `isValidSignature` result rejects every EIP1271 signature,
+        // so the signatures are only accepted if they are checked via Multicall.
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result(
+                "aggregate",
+                vec![
+                    Token::Uint(10.into()),
+                    Token::Array(vec![Token::Bytes(success_output); 2]),
+                ],
+            )
+            .await;
+        let batcher =
+            MulticallBatcher::new(Address::repeat_byte(0x0c), None, Duration::from_millis(50));
+        let eth_checker =
+            EthereumChecker::new(EthereumGateway::Mock(client)).with_multicall(batcher.clone());
+        let check = |wallet| {
+            eth_checker.is_eip1271_signature_correct(
+                wallet,
+                b"hello-world",
+                EIP1271Signature(vec![0; 65]),
+            )
+        };
+
+        let (first, second) = tokio::join!(
+            check(Address::repeat_byte(0x0a)),
+            check(Address::repeat_byte(0x0b))
+        );
+        assert!(first.unwrap());
+        assert!(second.unwrap());
+        // There is nothing to aggregate a single check with.
+        assert!(!check(Address::repeat_byte(0x0a)).await.unwrap());
+
+        // Mock client without the `aggregate` result fails the aggregated call.
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result(
+                "isValidSignature",
+                vec![Token::FixedBytes(EIP1271_SUCCESS_RETURN_VALUE.to_vec())],
+            )
+            .await;
+        let eth_checker =
+            EthereumChecker::new(EthereumGateway::Mock(client)).with_multicall(batcher);
+        let check = |wallet| {
+            eth_checker.is_eip1271_signature_correct(
+                wallet,
+                b"hello-world",
+                EIP1271Signature(vec![0; 65]),
+            )
+        };
+        let (first, second) = tokio::join!(
+            check(Address::repeat_byte(0x0a)),
+            check(Address::repeat_byte(0x0b))
+        );
+        assert!(first.unwrap());
+        assert!(second.unwrap());
+    }
+
+    /// Checks that the cached signers are returned only for the same message and signature.
+    #[test]
+    fn signer_cache_recovery() {
+        let private_key = H256::from_low_u64_be(42);
+        let signer = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let message = b"hello-world";
+        let signature = PackedEthSignature::sign(&private_key, message).unwrap();
+
+        let cache = SignerCache::new(10);
+        // Miss, then hit.
+        for _ in 0..2 {
+            assert_eq!(cache.recover_signer(&signature, message).unwrap(), signer);
+        }
+        // The same signature over a different message must not be served from the cache.
+        let other_signer = cache
+            .recover_signer(&signature, b"another-message")
+            .unwrap_or_default();
+        assert_ne!(other_signer, signer);
+        assert_eq!(
+            other_signer,
+            signature
+                .signature_recover_signer(b"another-message")
+                .unwrap_or_default()
+        );
+    }
+
     /// This test checks that the actual signature data taken from
     /// mainnet / Argent smart wallet is valid in our codebase.
     #[test]
@@ -0,0 +1,152 @@
+//! Gas price the fees are calculated with.
+//!
+//! The average gas price of the Ethereum transactions is smoothed with the exponential moving
+//! average, and its change per update is bounded, so the fee quoted to the user is still
+//! valid by the time the signed transaction is submitted.
+
+// Built-in uses
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+// External uses
+
+// Workspace uses
+use zksync_config::TickerConfig;
+use zksync_storage::ConnectionPool;
+
+/// Parameters of the gas price smoothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasPriceSmoothing {
+    /// Weight of the latest gas price in the moving average, from 0 to 1.
+    pub smoothing_factor: f64,
+    /// Maximum change of the smoothed gas price per update, in percent.
+    pub max_change_percent: f64,
+}
+
+impl GasPriceSmoothing {
+    pub fn from_config(config: &TickerConfig) -> Self {
+        Self {
+            smoothing_factor: config.gas_price_smoothing_factor.max(0.0).min(1.0),
+            max_change_percent: config.max_gas_price_change_percent.max(0.0),
+        }
+    }
+
+    /// Returns the smoothed gas price after the latest gas price is observed.
+    pub fn next_price(&self, previous: Option<u64>, latest: u64) -> u64 {
+        let previous = match previous {
+            Some(previous) => previous as f64,
+            // Nothing to smooth yet.
+            None => return latest,
+        };
+
+        let average = previous + self.smoothing_factor * (latest as f64 - previous);
+        let max_change = previous * self.max_change_percent / 100.0;
+        let bounded = average
+            .max(previous - max_change)
+            .min(previous + max_change);
+        bounded.round() as u64
+    }
+}
+
+/// Smoothed gas price in wei, shared between the fee tickers.
+#[derive(Debug, Clone)]
+pub struct GasPriceWatcher {
+    smoothing: GasPriceSmoothing,
+    gas_price: Arc<RwLock<Option<u64>>>,
+}
+
+impl GasPriceWatcher {
+    pub fn new(smoothing: GasPriceSmoothing) -> Self {
+        Self {
+            smoothing,
+            gas_price: Arc::default(),
+        }
+    }
+
+    /// Creates the watcher updating the gas price from the database periodically.
+    ///
+    /// Until the gas price is loaded for the first time, the gas price is not known.
+    pub fn with_updater(connection_pool: ConnectionPool, config: &TickerConfig) -> Self {
+        let watcher = Self::new(GasPriceSmoothing::from_config(config));
+        watcher.spawn_updater(connection_pool, config.gas_price_update_interval());
+        watcher
+    }
+
+    /// Returns the smoothed gas price, `None` if no gas price has been observed yet.
+    pub fn gas_price(&self) -> Option<u64> {
+        *self.gas_price.read().unwrap()
+    }
+
+    /// Updates the smoothed gas price with the latest average gas price.
+    pub fn observe(&self, latest: u64) -> u64 {
+        let mut gas_price = self.gas_price.write().unwrap();
+        let smoothed = self.smoothing.next_price(*gas_price, latest);
+        *gas_price = Some(smoothed);
+
+        metrics::gauge!("ticker.gas_price_wei", latest as f64, "type" => "latest");
+        metrics::gauge!("ticker.gas_price_wei", smoothed as f64, "type" => "smoothed");
+        smoothed
+    }
+
+    /// Loads the latest average gas price from the database.
+    pub async fn update(&self, connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let average_gas_price = storage.ethereum_schema().load_average_gas_price().await?;
+        if let Some(average_gas_price) = average_gas_price {
+            self.observe(average_gas_price.as_u64());
+        }
+        Ok(())
+    }
+
+    fn spawn_updater(&self, connection_pool: ConnectionPool, interval: Duration) {
+        let watcher = self.clone();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(interval);
+            loop {
+                timer.tick().await;
+                if let Err(err) = watcher.update(&connection_pool).await {
+                    vlog::warn!("Failed to update the gas price: {}", err);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMOOTHING: GasPriceSmoothing = GasPriceSmoothing {
+        smoothing_factor: 0.5,
+        max_change_percent: 10.0,
+    };
+
+    #[test]
+    fn smoothed_price() {
+        // The first observed price is used as is.
+        assert_eq!(SMOOTHING.next_price(None, 100), 100);
+        // The price moves halfway to the latest one.
+        assert_eq!(SMOOTHING.next_price(Some(100), 110), 105);
+        assert_eq!(SMOOTHING.next_price(Some(100), 90), 95);
+        // But no more than by 10 percent per update.
+        assert_eq!(SMOOTHING.next_price(Some(100), 1000), 110);
+        assert_eq!(SMOOTHING.next_price(Some(100), 0), 90);
+    }
+
+    #[test]
+    fn watcher_converges_to_latest_price() {
+        let watcher = GasPriceWatcher::new(SMOOTHING);
+        assert_eq!(watcher.gas_price(), None);
+
+        watcher.observe(100);
+        let mut previous = 100;
+        for _ in 0..50 {
+            let smoothed = watcher.observe(200);
+            assert!(smoothed >= previous && smoothed <= previous + previous / 10);
+            previous = smoothed;
+        }
+        assert_eq!(watcher.gas_price(), Some(200));
+    }
+}
@@ -24,13 +24,13 @@ use tokio::time::Instant;
 
 // Workspace deps
 
-use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_config::{configs::ticker::TokenPriceSource, RuntimeConfig};
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     gas_counter::GasCounter, tokens::ChangePubKeyFeeTypeArg, tx::ChangePubKeyType, Address,
-    BatchFee, ChangePubKeyOp, Fee, MintNFTOp, OutputFeeType, SwapOp, Token, TokenId, TokenLike,
-    TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
+    BatchFee, ChangePubKeyOp, Fee, FeePricing, MintNFTOp, OutputFeeType, SwapOp, Token, TokenId,
+    TokenLike, TransferOp, TransferToNewOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
@@ -40,13 +40,14 @@ pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, fallback::FallbackPriceAPI,
+        uniswap_twap::UniswapTwapAPI, FeeTickerAPI, TickerApi, TokenPriceAPI, CONNECTION_TIMEOUT,
     },
     validator::{watcher::UniswapTokenWatcher, MarketUpdater},
 };
 
 mod constants;
+pub mod gas_price_watcher;
 mod ticker_api;
 pub(crate) mod ticker_info;
 pub mod validator;
@@ -145,6 +146,19 @@ pub struct TickerConfig {
     pub subsidy_cpk_price_usd: Ratio<BigUint>,
 }
 
+impl TickerConfig {
+    /// Returns the fee scale coefficient of the reloaded runtime config, if it's initialized.
+    fn scale_fee_coefficient(&self) -> Ratio<BigUint> {
+        match RuntimeConfig::current() {
+            Some(config) => Ratio::new(
+                BigUint::from(config.scale_fee_percent),
+                BigUint::from(100u32),
+            ),
+            None => self.scale_fee_coefficient.clone(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum TokenPriceRequestType {
     USDForOneWei,
@@ -165,7 +179,7 @@ pub struct ResponseBatchFee {
     pub subsidy_size_usd: Ratio<BigUint>,
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum PriceError {
     #[error("Token not found: {0}")]
     TokenNotFound(String),
@@ -221,26 +235,39 @@ pub fn run_updaters(
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.price_source();
-    let price_updater = match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
-            tokio::spawn(ticker_api.keep_price_updated())
+    let price_sources = config.price_sources();
+    let max_price_age = config.max_price_age();
+    let max_price_deviation_percent = config.max_price_deviation_percent;
+    let price_updater = tokio::spawn(async move {
+        let mut providers: Vec<(TokenPriceSource, Box<dyn TokenPriceAPI + Send + Sync>)> =
+            Vec::with_capacity(price_sources.len());
+        for (price_source, base_url) in price_sources {
+            let token_price_api: Box<dyn TokenPriceAPI + Send + Sync> = match price_source {
+                TokenPriceSource::CoinMarketCap => Box::new(CoinMarketCapAPI::new(
+                    client.clone(),
+                    base_url.parse().expect("Correct CoinMarketCap url"),
+                )),
+                TokenPriceSource::CoinGecko => Box::new(
+                    CoinGeckoAPI::new(
+                        client.clone(),
+                        base_url.parse().expect("Correct CoinGecko url"),
+                    )
+                    .await
+                    .expect("failed to init CoinGecko client"),
+                ),
+                TokenPriceSource::UniswapTwap => {
+                    Box::new(UniswapTwapAPI::new(client.clone(), base_url))
+                }
+            };
+            providers.push((price_source, token_price_api));
         }
 
-        TokenPriceSource::CoinGecko => tokio::spawn(async move {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .await
-                    .expect("failed to init CoinGecko client");
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
+        let token_price_api =
+            FallbackPriceAPI::new(providers, max_price_age, max_price_deviation_percent);
+        let ticker_api = TickerApi::new(db_pool, token_price_api);
 
-            ticker_api.keep_price_updated().await;
-        }),
-    };
+        ticker_api.keep_price_updated().await;
+    });
     tasks.push(price_updater);
     tasks
 }
@@ -335,7 +362,9 @@ impl FeeTicker {
         let gas_price_wei = self.info.get_gas_price_wei().await?;
         let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
-        let token_usd_risk = self.token_usd_risk(&token).await?;
+        // Discounts set by the operator apply to both the zkp and the gas parts of the fee.
+        let token_usd_risk =
+            self.token_usd_risk(&token).await? * self.fee_discount_coefficient(&token).await?;
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await?;
 
@@ -351,16 +380,18 @@ impl FeeTicker {
                 | OutputFeeType::MintNFT
                 | OutputFeeType::Swap
         ) {
-            normal_gas_fee *= self.config.scale_fee_coefficient.clone();
+            normal_gas_fee *= self.config.scale_fee_coefficient();
         }
 
-        let normal_fee = Fee::new(
+        let pricing = self.fee_pricing(&token).await?;
+        let mut normal_fee = Fee::new(
             fee_type,
             zkp_fee,
             normal_gas_fee,
             gas_tx_amount,
             gas_price_wei.clone(),
         );
+        normal_fee.pricing = Some(pricing.clone());
 
         if fee_type == CPK_CREATE2_FEE_TYPE {
             let token_price = self
@@ -376,13 +407,14 @@ impl FeeTicker {
                 .checked_div(&token_price)
                 .unwrap();
 
-            let subsidized_fee = Fee::new(
+            let mut subsidized_fee = Fee::new(
                 fee_type,
                 Ratio::from(BigUint::zero()),
                 full_amount,
                 BigUint::zero(),
                 BigUint::zero(),
             );
+            subsidized_fee.pricing = Some(pricing);
 
             let subsidy_size_usd = if normal_fee.total_fee > subsidized_fee.total_fee {
                 token_price * (&normal_fee.total_fee - &subsidized_fee.total_fee)
@@ -418,7 +450,9 @@ impl FeeTicker {
         let gas_price_wei = self.info.get_gas_price_wei().await?;
         let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
-        let token_usd_risk = self.token_usd_risk(&token).await?;
+        // Discounts set by the operator apply to both the zkp and the gas parts of the fee.
+        let token_usd_risk =
+            self.token_usd_risk(&token).await? * self.fee_discount_coefficient(&token).await?;
 
         let mut total_normal_gas_tx_amount = Ratio::from(BigUint::zero());
         let mut total_op_chunks = Ratio::from(BigUint::zero());
@@ -471,7 +505,7 @@ impl FeeTicker {
                     | OutputFeeType::Swap
                     | OutputFeeType::MintNFT
             ) {
-                self.config.scale_fee_coefficient.clone() * gas_tx_amount
+                self.config.scale_fee_coefficient() * gas_tx_amount
             } else {
                 gas_tx_amount.into()
             };
@@ -489,14 +523,14 @@ impl FeeTicker {
             }
         }
 
-        let normal_fee = {
+        let mut normal_fee = {
             let total_zkp_fee = (&zkp_cost_chunk * total_op_chunks) * &token_usd_risk;
             let total_gas_fee =
                 (&wei_price_usd * total_normal_gas_tx_amount * &scale_gas_price) * &token_usd_risk;
             BatchFee::new(total_zkp_fee, total_gas_fee)
         };
 
-        let subsidized_fee = {
+        let mut subsidized_fee = {
             let total_zkp_fee = (zkp_cost_chunk * total_subsidized_op_chunks) * &token_usd_risk;
             let total_gas_fee =
                 (&wei_price_usd * total_subsidized_gas_tx_amount * &scale_gas_price)
@@ -509,6 +543,9 @@ impl FeeTicker {
         } else {
             Ratio::from(BigUint::from(0u32))
         };
+        let pricing = self.fee_pricing(&token).await?;
+        normal_fee.pricing = Some(pricing.clone());
+        subsidized_fee.pricing = Some(pricing);
         metrics::histogram!("ticker.get_batch_from_ticker_in_wei", start.elapsed());
 
         Ok(ResponseBatchFee {
@@ -530,6 +567,29 @@ impl FeeTicker {
         Ok(res)
     }
 
+    /// Returns the prices the fees in the token are calculated with.
+    pub async fn fee_pricing(&self, token: &Token) -> anyhow::Result<FeePricing> {
+        let token_price = self
+            .info
+            .get_last_token_price(TokenLike::Id(token.id))
+            .await?;
+        Ok(FeePricing {
+            token_price_usd: ratio_to_big_decimal(&token_price.usd_price, 18),
+            price_updated_at: token_price.last_updated,
+            calculated_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Returns the coefficient the fees in the token are multiplied by according to the
+    /// subsidies and the promotional discounts set by the operator.
+    pub async fn fee_discount_coefficient(&self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
+        let discount_percent = self.info.get_fee_discount_percent(token.id).await?.min(100);
+        Ok(Ratio::new(
+            BigUint::from(100 - discount_percent),
+            BigUint::from(100u32),
+        ))
+    }
+
     pub async fn token_usd_risk(&self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
         let token_risk_factor = self
@@ -130,6 +130,7 @@ impl FeeTickerAPI for MockApiProvider {
 struct MockTickerInfo {
     pub future_blocks: BlocksInFutureAggregatedOperations,
     pub remaining_chunks: Option<usize>,
+    pub fee_discount_percent: u8,
 }
 
 impl Default for MockTickerInfo {
@@ -141,6 +142,7 @@ impl Default for MockTickerInfo {
                 blocks_to_execute: 0,
             },
             remaining_chunks: None,
+            fee_discount_percent: 0,
         }
     }
 }
@@ -195,6 +197,10 @@ impl FeeTickerInfo for MockTickerInfo {
         unreachable!("incorrect token input")
     }
 
+    async fn get_fee_discount_percent(&self, _token_id: TokenId) -> anyhow::Result<u8> {
+        Ok(self.fee_discount_percent)
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -679,6 +685,101 @@ fn test_ticker_formula() {
     }
 }
 
+#[test]
+fn test_fee_discount() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    let mut ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+    let token = TestToken::eth();
+
+    let (normal_fee, _) = get_normal_and_subsidy_fee(
+        &mut ticker,
+        TxFeeTypes::Transfer,
+        token.id.into(),
+        Address::default(),
+        None,
+        None,
+    );
+
+    let mut info: Box<MockTickerInfo> = ticker.info.clone().into_any().downcast().unwrap();
+    info.fee_discount_percent = 50;
+    ticker.info = info;
+    let (discounted_fee, discounted_subsidy_fee) = get_normal_and_subsidy_fee(
+        &mut ticker,
+        TxFeeTypes::Transfer,
+        token.id.into(),
+        Address::default(),
+        None,
+        None,
+    );
+    assert_eq!(discounted_fee, discounted_subsidy_fee);
+
+    // The fee is halved up to the precision of the packed fee amount.
+    let half_fee = normal_fee / BigUint::from(2u32);
+    let difference = if half_fee > discounted_fee {
+        &half_fee - &discounted_fee
+    } else {
+        &discounted_fee - &half_fee
+    };
+    assert!(difference * BigUint::from(100u32) < half_fee);
+
+    let mut info: Box<MockTickerInfo> = ticker.info.clone().into_any().downcast().unwrap();
+    info.fee_discount_percent = 100;
+    ticker.info = info;
+    let (free_fee, _) = get_normal_and_subsidy_fee(
+        &mut ticker,
+        TxFeeTypes::Transfer,
+        token.id.into(),
+        Address::default(),
+        None,
+        None,
+    );
+    assert!(free_fee.is_zero());
+}
+
+#[test]
+fn test_fee_pricing() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    let ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+    let token = TestToken::hex();
+
+    let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Transfer,
+        token.id.into(),
+        Address::default(),
+    ))
+    .unwrap();
+    let pricing = fee.normal_fee.pricing.expect("pricing is not set");
+    assert_eq!(
+        pricing.token_price_usd,
+        ratio_to_big_decimal(&token.price_usd, 18)
+    );
+    assert!(pricing.price_updated_at <= pricing.calculated_at);
+
+    let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+        token.id.into(),
+        vec![(TxFeeTypes::Transfer, Address::default())],
+    ))
+    .unwrap();
+    assert_eq!(
+        batch_fee.normal_fee.pricing.unwrap().token_price_usd,
+        pricing.token_price_usd
+    );
+}
+
 // It's temporary solution while zero-price tokens marked as allowed for fee
 #[test]
 fn test_zero_price_token_fee() {
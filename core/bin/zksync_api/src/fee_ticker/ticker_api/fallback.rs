@@ -0,0 +1,238 @@
+// Built-in deps
+use std::time::Duration;
+// External deps
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future::join_all;
+use num::{rational::Ratio, BigUint, Zero};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::ratio_to_big_decimal;
+
+/// Token prices API querying several price providers.
+///
+/// The price of the provider with the highest priority is used, the next provider is used
+/// if the previous one is unavailable or its price is stale. Prices of all the providers are
+/// compared, and an error is reported if they deviate from each other beyond the threshold.
+pub struct FallbackPriceAPI {
+    /// Providers in the order of priority.
+    providers: Vec<(TokenPriceSource, Box<dyn TokenPriceAPI + Send + Sync>)>,
+    max_price_age: Duration,
+    max_price_deviation: Ratio<BigUint>,
+}
+
+impl FallbackPriceAPI {
+    pub fn new(
+        providers: Vec<(TokenPriceSource, Box<dyn TokenPriceAPI + Send + Sync>)>,
+        max_price_age: Duration,
+        max_price_deviation_percent: f64,
+    ) -> Self {
+        assert!(
+            !providers.is_empty(),
+            "At least one token price source should be configured"
+        );
+        let max_price_deviation = Ratio::new(
+            BigUint::from((max_price_deviation_percent * 100.0) as u64),
+            BigUint::from(10_000u32),
+        );
+
+        Self {
+            providers,
+            max_price_age,
+            max_price_deviation,
+        }
+    }
+
+    fn is_fresh(&self, price: &TokenPrice) -> bool {
+        let age = Utc::now().signed_duration_since(price.last_updated);
+        age.to_std()
+            .map(|age| age <= self.max_price_age)
+            // The price is updated in the future according to the provider clock.
+            .unwrap_or(true)
+    }
+
+    /// Reports an error if the fresh prices of the providers deviate beyond the threshold.
+    fn check_consistency(&self, token: &Token, prices: &[(TokenPriceSource, &TokenPrice)]) {
+        let min_price = prices.iter().map(|(_, price)| &price.usd_price).min();
+        let max_price = prices.iter().map(|(_, price)| &price.usd_price).max();
+        let (min_price, max_price) = match (min_price, max_price) {
+            (Some(min_price), Some(max_price)) if !min_price.is_zero() => (min_price, max_price),
+            _ => return,
+        };
+
+        let deviation = (max_price - min_price) / min_price;
+        if deviation > self.max_price_deviation {
+            let prices: Vec<_> = prices
+                .iter()
+                .map(|(source, price)| {
+                    format!(
+                        "{:?}: {}",
+                        source,
+                        ratio_to_big_decimal(&price.usd_price, 6)
+                    )
+                })
+                .collect();
+            vlog::error!(
+                "Prices of the token {} from the different sources are inconsistent: {}",
+                token.symbol,
+                prices.join(", ")
+            );
+            metrics::increment_counter!("ticker.inconsistent_prices", "token" => token.symbol.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for FallbackPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let results = join_all(
+            self.providers
+                .iter()
+                .map(|(_, provider)| provider.get_price(token)),
+        )
+        .await;
+
+        let mut fresh_prices = Vec::new();
+        let mut api_error = None;
+        for ((source, _), result) in self.providers.iter().zip(&results) {
+            match result {
+                Ok(price) if self.is_fresh(price) => fresh_prices.push((*source, price)),
+                Ok(price) => {
+                    vlog::warn!(
+                        "Price of the token {} from {:?} is stale, last updated at {}",
+                        token.symbol,
+                        source,
+                        price.last_updated
+                    );
+                    metrics::increment_counter!("ticker.stale_price", "source" => format!("{:?}", source));
+                    api_error = Some(PriceError::api_error(format!(
+                        "Price from {:?} is stale",
+                        source
+                    )));
+                }
+                Err(PriceError::TokenNotFound(_)) => {}
+                Err(err) => {
+                    vlog::warn!(
+                        "Can't get the price of the token {} from {:?}: {}",
+                        token.symbol,
+                        source,
+                        err
+                    );
+                    metrics::increment_counter!("ticker.price_source_error", "source" => format!("{:?}", source));
+                    api_error = Some(err.clone());
+                }
+            }
+        }
+
+        self.check_consistency(token, &fresh_prices);
+        if let Some((source, price)) = fresh_prices.first() {
+            metrics::increment_counter!("ticker.price_source_used", "source" => format!("{:?}", source));
+            return Ok((*price).clone());
+        }
+
+        // The token is considered unknown only if none of the providers could have known it.
+        Err(api_error.unwrap_or_else(|| {
+            PriceError::token_not_found(format!(
+                "Token '{}, {:?}' is not listed by any of the price sources",
+                token.symbol, token.address
+            ))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use zksync_types::{TokenId, TokenKind};
+
+    struct FixedPriceAPI(Result<TokenPrice, PriceError>);
+
+    #[async_trait]
+    impl TokenPriceAPI for FixedPriceAPI {
+        async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+            self.0.clone()
+        }
+    }
+
+    fn price(usd_price: u64, last_updated: DateTime<Utc>) -> TokenPrice {
+        TokenPrice {
+            usd_price: Ratio::from_integer(usd_price.into()),
+            last_updated,
+        }
+    }
+
+    fn fallback_api(results: Vec<Result<TokenPrice, PriceError>>) -> FallbackPriceAPI {
+        let sources = [
+            TokenPriceSource::CoinGecko,
+            TokenPriceSource::CoinMarketCap,
+            TokenPriceSource::UniswapTwap,
+        ];
+        let providers = sources
+            .iter()
+            .zip(results)
+            .map(|(source, result)| {
+                let provider: Box<dyn TokenPriceAPI + Send + Sync> =
+                    Box::new(FixedPriceAPI(result));
+                (*source, provider)
+            })
+            .collect();
+        FallbackPriceAPI::new(providers, Duration::from_secs(3600), 10.0)
+    }
+
+    fn token() -> Token {
+        Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20)
+    }
+
+    /// Checks that the price of the provider with the highest priority is used.
+    #[tokio::test]
+    async fn uses_first_available_price() {
+        let now = Utc::now();
+        let api = fallback_api(vec![Ok(price(100, now)), Ok(price(105, now))]);
+        assert_eq!(
+            api.get_price(&token()).await.unwrap().usd_price,
+            price(100, now).usd_price
+        );
+    }
+
+    /// Checks that the next provider is used if the previous one fails or its price is stale.
+    #[tokio::test]
+    async fn falls_back_to_next_provider() {
+        let now = Utc::now();
+        let stale = now - chrono::Duration::hours(2);
+        let api = fallback_api(vec![
+            Err(PriceError::api_error("unavailable")),
+            Ok(price(100, stale)),
+            Ok(price(101, now)),
+        ]);
+        assert_eq!(
+            api.get_price(&token()).await.unwrap().usd_price,
+            price(101, now).usd_price
+        );
+    }
+
+    /// Checks that the token is reported as unknown only if none of the providers knows it.
+    #[tokio::test]
+    async fn token_not_found_by_all_providers() {
+        let api = fallback_api(vec![
+            Err(PriceError::token_not_found("unknown")),
+            Err(PriceError::token_not_found("unknown")),
+        ]);
+        assert!(matches!(
+            api.get_price(&token()).await,
+            Err(PriceError::TokenNotFound(_))
+        ));
+
+        let api = fallback_api(vec![
+            Err(PriceError::token_not_found("unknown")),
+            Err(PriceError::api_error("unavailable")),
+        ]);
+        assert!(matches!(
+            api.get_price(&token()).await,
+            Err(PriceError::ApiError(_))
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+// Built-in deps
+use std::str::FromStr;
+use std::time::Instant;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use super::{TokenPriceAPI, REQUEST_TIMEOUT};
+use crate::fee_ticker::PriceError;
+use zksync_types::{Address, Token, TokenPrice};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+/// Amount of the latest hours the average price is calculated over.
+const TWAP_HOURS: usize = 6;
+/// Address of WETH on mainnet, Uniswap has no pools for the ETH itself.
+const WETH_ADDRESS: &str = "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+/// Token prices API based on the Uniswap V3 subgraph.
+/// https://thegraph.com/explorer/subgraph/uniswap/uniswap-v3
+///
+/// The price is the time-weighted average of the hourly prices over the last hours,
+/// so it can't be moved by a single trade.
+#[derive(Debug, Clone)]
+pub struct UniswapTwapAPI {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl UniswapTwapAPI {
+    pub fn new(client: reqwest::Client, url: String) -> Self {
+        Self { client, url }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for UniswapTwapAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+        let address = if token.address == Address::zero() {
+            Address::from_str(WETH_ADDRESS).expect("Correct WETH address")
+        } else {
+            token.address
+        };
+        // Uniswap has graphql API, using full graphql client for one query is overkill for current task
+        let query = format!(
+            "{{tokenHourDatas(first: {}, orderBy: periodStartUnix, orderDirection: desc, where: {{token: \"{:#x}\"}}){{periodStartUnix, priceUSD}}}}",
+            TWAP_HOURS, address
+        );
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "query": query }))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| PriceError::api_error(format!("Uniswap API request failed: {}", err)))?
+            .json::<GraphqlResponse>()
+            .await
+            .map_err(PriceError::api_error)?;
+
+        let hour_prices = response.data.token_hour_datas;
+        let last_hour_start = hour_prices
+            .first()
+            .ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Token '{}, {:?}' is not traded on Uniswap",
+                    token.symbol, token.address
+                ))
+            })?
+            .period_start_unix;
+        // Every price corresponds to an hour, so the time-weighted average is the plain average.
+        let len = hour_prices.len();
+        let usd_price = hour_prices
+            .into_iter()
+            .map(|hour_price| hour_price.price_usd)
+            .sum::<Ratio<BigUint>>()
+            / BigUint::from(len);
+
+        // The price of the hour is updated until the hour ends.
+        let hour_end = DateTime::<Utc>::from_utc(
+            NaiveDateTime::from_timestamp(last_hour_start + 3600, 0),
+            Utc,
+        );
+        let last_updated = hour_end.min(Utc::now());
+        metrics::histogram!("ticker.uniswap_twap.request", start.elapsed());
+        Ok(TokenPrice {
+            usd_price,
+            last_updated,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GraphqlResponse {
+    data: TokenHourDatas,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenHourDatas {
+    #[serde(rename = "tokenHourDatas")]
+    token_hour_datas: Vec<TokenHourData>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenHourData {
+    /// Start of the hour the price corresponds to.
+    #[serde(rename = "periodStartUnix")]
+    period_start_unix: i64,
+    /// Price of the token in USD at the end of the hour.
+    #[serde(rename = "priceUSD", with = "UnsignedRatioSerializeAsDecimal")]
+    price_usd: Ratio<BigUint>,
+}
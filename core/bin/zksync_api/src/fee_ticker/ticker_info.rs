@@ -18,7 +18,7 @@ use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice};
 // Local deps
-use crate::fee_ticker::PriceError;
+use crate::fee_ticker::{gas_price_watcher::GasPriceWatcher, PriceError};
 
 pub trait FeeTickerClone {
     fn clone_box(&self) -> Box<dyn FeeTickerInfo>;
@@ -60,6 +60,9 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
 
+    /// Get the discount of the fees in the token set by the operator, in percent.
+    async fn get_fee_discount_percent(&self, token_id: TokenId) -> anyhow::Result<u8>;
+
     /// Make boxed value to any. Helpful for downcasting in tests
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
@@ -69,13 +72,15 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 pub struct TickerInfo {
     db: ConnectionPool,
     token_db_cache: TokenDBCache,
+    gas_price_watcher: GasPriceWatcher,
 }
 
 impl TickerInfo {
-    pub fn new(db: ConnectionPool) -> Self {
+    pub fn new(db: ConnectionPool, gas_price_watcher: GasPriceWatcher) -> Self {
         Self {
             db,
             token_db_cache: Default::default(),
+            gas_price_watcher,
         }
     }
 }
@@ -217,7 +222,11 @@ impl FeeTickerInfo for TickerInfo {
     /// Get current gas price in ETH
     async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error> {
         let start = Instant::now();
+        if let Some(gas_price) = self.gas_price_watcher.gas_price() {
+            return Ok(BigUint::from(gas_price));
+        }
 
+        // The smoothed gas price is not known yet, the latest average one is used.
         let mut storage = self
             .db
             .access_storage()
@@ -258,6 +267,18 @@ impl FeeTickerInfo for TickerInfo {
         result
     }
 
+    async fn get_fee_discount_percent(&self, token_id: TokenId) -> anyhow::Result<u8> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let discount_percent = storage
+            .fee_policies_schema()
+            .get_fee_discount_percent(token_id, Utc::now())
+            .await?;
+
+        metrics::histogram!("ticker_info.get_fee_discount_percent", start.elapsed());
+        Ok(discount_percent.max(0).min(100) as u8)
+    }
+
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
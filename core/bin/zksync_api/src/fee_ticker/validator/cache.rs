@@ -25,6 +25,7 @@ pub(crate) struct TokenInDBCache {
 pub struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    fee_acceptability: Arc<Mutex<HashMap<TokenId, bool>>>,
 }
 
 impl TokenInDBCache {
@@ -52,6 +53,13 @@ impl TokenInMemoryCache {
             ..self
         }
     }
+
+    pub fn with_fee_acceptability(self, fee_acceptability: HashMap<TokenId, bool>) -> Self {
+        Self {
+            fee_acceptability: Arc::new(Mutex::new(fee_acceptability)),
+            ..self
+        }
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +117,25 @@ impl TokenCacheWrapper {
         }
     }
 
+    /// Returns whether the fees can be paid in the token regardless of its market volume,
+    /// `None` if the operator hasn't decided it for the token.
+    pub async fn get_token_fee_acceptability(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<bool>> {
+        match self {
+            Self::DB(cache) => Ok(cache
+                .pool
+                .access_storage()
+                .await?
+                .fee_policies_schema()
+                .get_token_fee_acceptability(token_id)
+                .await?),
+            #[cfg(test)]
+            Self::Memory(cache) => Ok(cache.fee_acceptability.lock().await.get(&token_id).copied()),
+        }
+    }
+
     pub async fn update_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -118,9 +118,19 @@ impl FeeTokenValidator {
     }
 
     /// Returns `true` if token can be used to pay fees.
+    ///
+    /// The fee acceptability set for the token by the operator takes precedence over
+    /// the market volume of the token.
     pub(crate) async fn token_allowed(&self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
+            if let Some(fee_acceptable) = self
+                .tokens_cache
+                .get_token_fee_acceptability(token.id)
+                .await?
+            {
+                return Ok(fee_acceptable);
+            }
             if self.unconditionally_valid.contains(&token.address) {
                 return Ok(true);
             }
@@ -284,4 +294,52 @@ mod tests {
         assert!(!phnx_allowed);
         assert!(eth_allowed);
     }
+
+    #[tokio::test]
+    async fn fee_acceptability_overrides_market_volume() {
+        let dai_token_address =
+            Address::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+        let dai_token = Token::new(TokenId(1), dai_token_address, "DAI", 18, TokenKind::ERC20);
+        let phnx_token_address =
+            Address::from_str("38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7").unwrap();
+        let phnx_token = Token::new(TokenId(2), phnx_token_address, "PHNX", 18, TokenKind::ERC20);
+
+        let mut tokens = HashMap::new();
+        tokens.insert(TokenLike::Address(dai_token_address), dai_token.clone());
+        tokens.insert(TokenLike::Address(phnx_token_address), phnx_token.clone());
+
+        let mut market = HashMap::new();
+        market.insert(
+            dai_token.id,
+            TokenMarketVolume {
+                market_volume: Ratio::new(BigUint::from(200u32), BigUint::from(1u32)),
+                last_updated: Utc::now(),
+            },
+        );
+
+        // Liquid DAI is disabled by the operator, and PHNX without market volume is enabled.
+        let mut fee_acceptability = HashMap::new();
+        fee_acceptability.insert(dai_token.id, false);
+        fee_acceptability.insert(phnx_token.id, true);
+
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market)
+            .with_fee_acceptability(fee_acceptability);
+        let validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            HashSet::new(),
+        );
+
+        assert!(!validator
+            .token_allowed(TokenLike::Address(dai_token_address))
+            .await
+            .unwrap());
+        assert!(validator
+            .token_allowed(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap());
+    }
 }
@@ -6,3 +6,4 @@ pub mod fee_ticker;
 pub mod signature_checker;
 pub mod tx_error;
 pub mod utils;
+pub mod webhook_notifier;
@@ -0,0 +1,5317 @@
+//! `signature_checker` module provides a detached thread routine
+//! dedicated for checking the signatures of incoming transactions.
+//! Main routine of this module operates a multithreaded event loop,
+//! which is used to spawn concurrent tasks to efficiently check the
+//! transactions signatures.
+
+// Built-in uses
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// External uses
+use chrono::Utc;
+use futures::{
+    channel::{mpsc, oneshot},
+    FutureExt, StreamExt,
+};
+use num::{BigUint, Zero};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    runtime::Handle,
+    sync::{watch, Mutex, OwnedSemaphorePermit, Semaphore, TryAcquireError},
+    task::JoinHandle,
+};
+use tracing::Instrument;
+
+// Workspace uses
+use zksync_config::{
+    configs::api::{ChangePubKeyAuthBlock, CommonApiConfig, EthCheckerMode},
+    ETHClientConfig, ETHSenderConfig,
+};
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{
+    event::transaction::TransactionType,
+    mempool::SignedTxVariant,
+    network::Network,
+    tx::{
+        error::TxAddError, AggregatedSignature, BatchMessageFormat, BatchMessageFormats,
+        ChangePubKeyEthAuthData, Eip712Domain, EthBatchSignData, EthSignData, SignedMessage,
+        TxEthSignature,
+    },
+    Address, Order, SignedZkSyncTx, Token, TokenId, TokenLike, ZkSyncTx, H256,
+};
+// Local uses
+use crate::eth_checker::{
+    AuthFactBlock, AuthRetries, ChainBinding, CircuitBreaker, Eip1271Cache, EthCallCounter,
+    EthCallCounts, EthCallRetries, EthereumChecker, EthereumNodeUnavailable, MulticallBatcher,
+    OfflineEthChecks, PubKeyHashAuthorization, SignerCache,
+};
+use crate::utils::shared_lru_cache::SharedLruCache;
+use zksync_utils::{panic_notify::ThreadPanicNotify, shutdown::ShutdownToken};
+
+#[cfg(any(test, debug_assertions, feature = "replay"))]
+pub mod replay;
+
+/// `TxVariant` is used to form a verify request. It is possible to wrap
+/// either a single transaction, or the transaction batch.
+#[derive(Debug, Clone)]
+pub enum TxVariant {
+    Tx(SignedZkSyncTx),
+    /// Batch signature is optional: if it's absent, every transaction which
+    /// requires an Ethereum signature must carry its own one.
+    Batch(Vec<SignedZkSyncTx>, Option<EthBatchSignData>),
+    Order(Box<Order>),
+    Toggle2FA,
+}
+
+impl TxVariant {
+    /// Reconstructs the unverified variant from the one taken from the mempool, so it can be
+    /// verified once again, e.g. after the state it depends on has changed.
+    ///
+    /// Mempool doesn't keep the batch message, so the batch signature data has to be provided
+    /// by the caller. It's ignored for a single transaction.
+    pub fn from_signed(
+        signed: &SignedTxVariant,
+        batch_sign_data: Option<EthBatchSignData>,
+    ) -> Self {
+        match signed {
+            SignedTxVariant::Tx(tx) => TxVariant::Tx(tx.clone()),
+            SignedTxVariant::Batch(batch) => TxVariant::Batch(batch.txs.clone(), batch_sign_data),
+        }
+    }
+
+    /// Groups the transactions by the account they're applied to (see `ZkSyncTx::account`).
+    ///
+    /// Groups are ordered by the first transaction of each account, and transactions
+    /// within a group keep their original order, even if the batch interleaves the
+    /// transactions of different accounts. Orders and 2FA toggles contain no transactions.
+    pub fn split_by_account(&self) -> Vec<(Address, Vec<&SignedZkSyncTx>)> {
+        let txs = match self {
+            TxVariant::Tx(tx) => std::slice::from_ref(tx),
+            TxVariant::Batch(txs, _) => txs.as_slice(),
+            TxVariant::Order(_) | TxVariant::Toggle2FA => &[],
+        };
+
+        let mut groups: Vec<(Address, Vec<&SignedZkSyncTx>)> = Vec::new();
+        let mut group_indices = HashMap::new();
+        for tx in txs {
+            let account = tx.tx.account();
+            let index = *group_indices.entry(account).or_insert_with(|| {
+                groups.push((account, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(tx);
+        }
+        groups
+    }
+
+    /// Returns the hash of the variant contents: transactions with their Ethereum
+    /// signatures and, for a batch, the batch message with its signatures.
+    ///
+    /// Variants with the same contents always have the same hash, so it can be used
+    /// to identify a request in logs or as a cache key. Order of the batch transactions
+    /// affects the hash.
+    pub fn content_hash(&self) -> H256 {
+        let mut preimage = Vec::new();
+        match self {
+            TxVariant::Tx(tx) => {
+                preimage.push(0);
+                append_signed_tx(&mut preimage, tx);
+            }
+            TxVariant::Batch(txs, batch_sign_data) => {
+                preimage.push(1);
+                preimage.extend_from_slice(&(txs.len() as u64).to_be_bytes());
+                for tx in txs {
+                    append_signed_tx(&mut preimage, tx);
+                }
+                if let Some(batch_sign_data) = batch_sign_data {
+                    append_bytes(&mut preimage, batch_sign_data.message.as_bytes());
+                    for signature in &batch_sign_data.signatures {
+                        append_eth_signature(&mut preimage, signature);
+                    }
+                }
+            }
+            TxVariant::Order(order) => {
+                preimage.push(2);
+                let order = serde_json::to_vec(order).expect("Order is serializable");
+                append_bytes(&mut preimage, &order);
+            }
+            TxVariant::Toggle2FA => preimage.push(3),
+        }
+        H256::from(tiny_keccak::keccak256(&preimage))
+    }
+}
+
+/// Appends length-prefixed bytes, so the concatenated values can't be confused.
+fn append_bytes(preimage: &mut Vec<u8>, bytes: &[u8]) {
+    preimage.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    preimage.extend_from_slice(bytes);
+}
+
+fn append_eth_signature(preimage: &mut Vec<u8>, signature: &TxEthSignature) {
+    match signature {
+        TxEthSignature::EthereumSignature(signature) => {
+            preimage.push(0);
+            append_bytes(preimage, &signature.serialize_packed());
+        }
+        TxEthSignature::EIP1271Signature(signature) => {
+            preimage.push(1);
+            append_bytes(preimage, &signature.0);
+        }
+        TxEthSignature::AggregatedBLS(signature) => {
+            preimage.push(2);
+            append_bytes(preimage, &signature.signature);
+            preimage.extend_from_slice(&(signature.signers.len() as u64).to_be_bytes());
+            for signer in &signature.signers {
+                preimage.extend_from_slice(signer.as_bytes());
+            }
+        }
+        TxEthSignature::EIP712Signature(signature) => {
+            preimage.push(3);
+            append_bytes(preimage, &signature.0.serialize_packed());
+        }
+    }
+}
+
+fn append_signed_tx(preimage: &mut Vec<u8>, tx: &SignedZkSyncTx) {
+    append_bytes(preimage, tx.tx.hash().as_ref());
+    match &tx.eth_sign_data {
+        Some(eth_sign_data) => {
+            preimage.push(1);
+            append_eth_signature(preimage, &eth_sign_data.signature);
+            append_bytes(preimage, eth_sign_data.message.as_bytes());
+        }
+        None => preimage.push(0),
+    }
+    preimage.extend_from_slice(&(tx.parties_eth_sign_data.len() as u64).to_be_bytes());
+    for eth_sign_data in &tx.parties_eth_sign_data {
+        match eth_sign_data {
+            Some(eth_sign_data) => {
+                preimage.push(1);
+                append_eth_signature(preimage, &eth_sign_data.signature);
+                append_bytes(preimage, eth_sign_data.message.as_bytes());
+            }
+            None => preimage.push(0),
+        }
+    }
+}
+
+/// Wrapper on a `TxVariant` which guarantees that (a batch of)
+/// transaction(s) was checked and signatures associated with
+/// this transactions are correct.
+///
+/// Underlying `TxVariant` is a private field, thus no such
+/// object can be created without verification.
+#[derive(Debug, Clone)]
+pub struct VerifiedTx {
+    inner: TxVariant,
+    /// Request the transaction was verified with, kept to repeat the onchain checks.
+    /// `None` if the transaction was wrapped without the verification.
+    context: Option<Arc<VerificationContext>>,
+    /// `None` if the transaction was wrapped without the verification.
+    timings: Option<VerificationTimings>,
+    /// Onchain checks made during the verification.
+    /// `None` if the transaction was wrapped without the verification.
+    eth_calls: Option<EthCallCounts>,
+}
+
+/// Time spent in the phases of the transaction verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationTimings {
+    /// Check of the Ethereum signatures, including the calls to the Ethereum node
+    /// for the EIP1271 signatures and the onchain `ChangePubKey` authorizations.
+    pub eth_signature: Duration,
+    /// Correctness check of the transactions, including the `ZKSync` signature check.
+    pub tx_correctness: Duration,
+}
+
+#[derive(Debug)]
+struct VerificationContext {
+    request_data: RequestData,
+    eth_sig_exemptions: EthSignatureExemptions,
+}
+
+impl VerifiedTx {
+    /// Checks the (batch of) transaction(s) correctness by verifying its
+    /// Ethereum signature (if required) and `ZKSync` signature.
+    ///
+    /// See `verify_tx_signature` for details.
+    pub async fn verify(
+        request_data: RequestData,
+        eth_checker: &EthereumChecker,
+        valid_from_window: &ValidFromWindow,
+        eth_sig_exemptions: &EthSignatureExemptions,
+    ) -> Result<Self, TxAddError> {
+        verify_tx_signature(
+            request_data,
+            eth_checker,
+            valid_from_window,
+            eth_sig_exemptions,
+        )
+        .await
+    }
+
+    /// Repeats the checks which depend on the Ethereum state, so the transaction held for
+    /// a while can be re-confirmed before its submission: the EIP1271 signatures and the
+    /// onchain `ChangePubKey` authorizations may be revoked since the previous verification.
+    ///
+    /// Stateless checks, including the `ZKSync` signature check, are not repeated.
+    /// Transactions wrapped without the verification can't be verified again.
+    pub async fn verify_again(&self, eth_checker: &EthereumChecker) -> Result<Self, TxAddError> {
+        let context = self.context.as_ref().ok_or_else(|| {
+            vlog::warn!("Attempt to verify again the transaction which was never verified");
+            TxAddError::Other
+        })?;
+        verify_eth_signature(
+            &context.request_data,
+            eth_checker,
+            &context.eth_sig_exemptions,
+        )
+        .await?;
+        Ok(self.clone())
+    }
+
+    /// Creates a verified wrapper without actually verifying the original data.
+    #[cfg(test)]
+    pub(crate) fn unverified(inner: TxVariant) -> Self {
+        Self {
+            inner,
+            context: None,
+            timings: None,
+            eth_calls: None,
+        }
+    }
+
+    /// Wraps the transaction which signatures were already checked at an earlier
+    /// trusted boundary.
+    ///
+    /// This function bypasses all the checks performed by `verify`, so it's a caller's
+    /// responsibility to guarantee that the transaction was verified before.
+    #[cfg(feature = "trusted-construction")]
+    pub fn from_already_verified(tx: SignedZkSyncTx) -> Self {
+        Self {
+            inner: TxVariant::Tx(tx),
+            context: None,
+            timings: None,
+            eth_calls: None,
+        }
+    }
+
+    /// Returns the time spent in the phases of the verification, so it's possible to
+    /// tell whether the Ethereum node or the local checks dominate.
+    pub fn timings(&self) -> Option<VerificationTimings> {
+        self.timings
+    }
+
+    /// Returns the numbers of the onchain checks the verification required, e.g. to
+    /// account the cost of the request. Checks repeated by `verify_again` are not counted.
+    pub fn eth_calls(&self) -> Option<EthCallCounts> {
+        self.eth_calls
+    }
+
+    /// Takes the `TxVariant` out of the wrapper.
+    pub fn unwrap_tx(self) -> SignedZkSyncTx {
+        match self.inner {
+            TxVariant::Tx(tx) => tx,
+            TxVariant::Batch(_, _) => panic!("called `unwrap_tx` on a `Batch` value"),
+            TxVariant::Order(_) => panic!("called `unwrap_tx` on an `Order` value"),
+            TxVariant::Toggle2FA => panic!("called `unwrap_tx` on an `Toggle2FA` value"),
+        }
+    }
+
+    /// Takes the Vec of `SignedZkSyncTx` and the verified signature data out of the wrapper.
+    pub fn unwrap_batch(self) -> (Vec<SignedZkSyncTx>, Option<EthBatchSignData>) {
+        match self.inner {
+            TxVariant::Batch(txs, batch_sign_data) => (txs, batch_sign_data),
+            TxVariant::Tx(_) => panic!("called `unwrap_batch` on a `Tx` value"),
+            TxVariant::Order(_) => panic!("called `unwrap_batch` on an `Order` value"),
+            TxVariant::Toggle2FA => panic!("called `unwrap_batch` on an `Toggle2FA` value"),
+        }
+    }
+}
+
+/// Verifies the (batch of) transaction(s) exactly the way the signature checker does,
+/// but in the caller's task and without any channels involved.
+///
+/// Empty batches are rejected with `TxAddError::EmptyBatch` before any other check,
+/// and batches paying fees in different tokens with `TxAddError::BatchFeeTokenMismatch`
+/// right after that, so such batches never reach the Ethereum node. Then checks the
+/// `valid_from` of the transactions, their Ethereum signatures (unless the transaction type
+/// is exempt and the signature is missing) and finally the transactions correctness, which
+/// includes the `ZKSync` signature check. Ethereum node is queried for the EIP-1271
+/// signatures and the onchain authorization of the `ChangePubKey` transactions.
+///
+/// If several transactions of a batch have incorrect Ethereum signatures or are incorrect
+/// themselves, the failure of the transaction with the lowest index is reported, no matter
+/// in which order the checks are made. Failures which are not specific to a transaction,
+/// e.g. the unavailable Ethereum node, are reported as soon as they happen.
+///
+/// This is the single implementation used by both `VerifiedTx::verify` and the
+/// signature checker threads, so it's suitable for tests and tooling that need to
+/// get the same result as the API.
+pub async fn verify_tx_signature(
+    request_data: RequestData,
+    eth_checker: &EthereumChecker,
+    valid_from_window: &ValidFromWindow,
+    eth_sig_exemptions: &EthSignatureExemptions,
+) -> Result<VerifiedTx, TxAddError> {
+    // Signature of an empty batch covers nothing, so it's most likely a client bug.
+    if matches!(&request_data, RequestData::Batch(request) if request.txs.is_empty()) {
+        return Err(TxAddError::EmptyBatch);
+    }
+    if let RequestData::Batch(request) = &request_data {
+        verify_batch_fee_token_consistency(&request.txs)?;
+    }
+    verify_valid_from(&request_data, valid_from_window)?;
+    // Onchain checks are counted for this request only, while the checker itself is shared.
+    let call_counter = EthCallCounter::default();
+    let counting_eth_checker = eth_checker.clone().with_call_counter(call_counter.clone());
+    let start = Instant::now();
+    let eth_signature_failure = match &request_data {
+        RequestData::Batch(request) => {
+            match verify_eth_signature_batch(request, &counting_eth_checker, eth_sig_exemptions)
+                .await
+            {
+                Ok(()) => None,
+                Err(failure) if is_internal_error(&failure.error) => return Err(failure.error),
+                Err(failure) => Some(failure),
+            }
+        }
+        _ => {
+            verify_eth_signature(&request_data, &counting_eth_checker, eth_sig_exemptions).await?;
+            None
+        }
+    };
+    let eth_signature = start.elapsed();
+    metrics::histogram!("signature_checker.verify_phase", eth_signature, "phase" => "eth_signature");
+    let eth_calls = call_counter.counts();
+    metrics::histogram!("signature_checker.eth_calls", eth_calls.total() as f64);
+
+    let mut tx_variant = request_data.get_tx_variant();
+    // Orders don't contain the address of their owner, it's only known from the request.
+    let order_owner = match &request_data {
+        RequestData::Order(request) => request.sender,
+        _ => Address::zero(),
+    };
+    let start = Instant::now();
+    let result = tracing::debug_span!("check_correctness").in_scope(|| {
+        match (&mut tx_variant, eth_signature_failure) {
+            // Only the transactions before the one with the incorrect signature may be
+            // the lowest-index failure.
+            (TxVariant::Batch(batch, _), Some(failure)) => {
+                let failure = verify_batch_correctness(&mut batch[..failure.index])
+                    .err()
+                    .unwrap_or(failure);
+                Err(failure.into_error(batch))
+            }
+            (tx_variant, _) => verify_tx_correctness(tx_variant, order_owner),
+        }
+    });
+    let tx_correctness = start.elapsed();
+    metrics::histogram!("signature_checker.verify_phase", tx_correctness, "phase" => "tx_correctness");
+    result?;
+    set_batch_hash(&mut tx_variant);
+
+    Ok(VerifiedTx {
+        inner: tx_variant,
+        context: Some(Arc::new(VerificationContext {
+            request_data,
+            eth_sig_exemptions: eth_sig_exemptions.clone(),
+        })),
+        timings: Some(VerificationTimings {
+            eth_signature,
+            tx_correctness,
+        }),
+        eth_calls: Some(eth_calls),
+    })
+}
+
+/// Ties the transactions of the verified batch to the batch signature by setting their
+/// `batch_hash`. Values provided by the user are never trusted and get overwritten.
+fn set_batch_hash(tx_variant: &mut TxVariant) {
+    match tx_variant {
+        TxVariant::Tx(tx) => tx.batch_hash = None,
+        TxVariant::Batch(txs, batch_sign_data) => {
+            let batch_hash = batch_sign_data.as_ref().map(EthBatchSignData::message_hash);
+            for tx in txs {
+                tx.batch_hash = batch_hash;
+            }
+        }
+        TxVariant::Order(_) | TxVariant::Toggle2FA => {}
+    }
+}
+
+/// Source of the current UNIX timestamp in seconds.
+pub type Clock = fn() -> u64;
+
+fn system_clock() -> u64 {
+    Utc::now().timestamp() as u64
+}
+
+/// Limits how far in the future `valid_from` of the submitted transactions can be.
+///
+/// Both `valid_from` and `valid_until` are enforced by the state keeper against the
+/// block timestamp, so a pre-signed transaction can wait in the mempool until it becomes
+/// valid and is dropped once `valid_until` has passed. This check happens earlier and
+/// rejects the transaction with `TxAddError::NotYetValid` if its `valid_from` is ahead of
+/// the server time by more than the acceptance window, which also covers the clock skew
+/// between the user and the server. `valid_until` is not affected by the window.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidFromWindow {
+    window: Option<Duration>,
+    clock: Clock,
+}
+
+impl ValidFromWindow {
+    /// Creates a check with the given acceptance window. If the window is `None`,
+    /// transactions are accepted regardless of their `valid_from`.
+    pub fn new(window: Option<Duration>) -> Self {
+        Self::with_clock(window, system_clock)
+    }
+
+    /// Same as `new`, but uses the provided time source.
+    pub fn with_clock(window: Option<Duration>, clock: Clock) -> Self {
+        Self { window, clock }
+    }
+
+    fn check(&self, tx: &ZkSyncTx) -> Result<(), TxAddError> {
+        let window = match self.window {
+            Some(window) => window.as_secs(),
+            None => return Ok(()),
+        };
+        if tx.valid_from() > (self.clock)().saturating_add(window) {
+            return Err(TxAddError::NotYetValid);
+        }
+        Ok(())
+    }
+}
+
+impl Default for ValidFromWindow {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+/// Types of transactions which are accepted without an Ethereum signature, e.g.
+/// `ForcedExit` submitted by the operator.
+///
+/// Exemption only affects the Ethereum signature: if it's provided, it's checked as usual,
+/// and the zkSync signature of the exempt transactions is always verified.
+///
+/// Optionally, the transactions covered by a valid batch signature may be accepted even if
+/// their own Ethereum signatures don't match, see `with_batch_signature_coverage`, and
+/// the `ForcedExit` transactions may be signed by the operator, see `with_forced_exit_operator`.
+#[derive(Debug, Clone, Default)]
+pub struct EthSignatureExemptions {
+    tx_types: HashSet<TransactionType>,
+    batch_signature_covers_txs: bool,
+    forced_exit_operator: Option<Address>,
+}
+
+impl EthSignatureExemptions {
+    pub fn new(tx_types: impl IntoIterator<Item = TransactionType>) -> Self {
+        Self {
+            tx_types: tx_types.into_iter().collect(),
+            batch_signature_covers_txs: false,
+            forced_exit_operator: None,
+        }
+    }
+
+    /// If enabled, a valid batch signature is sufficient for the transactions of the batch:
+    /// their own Ethereum signatures which don't match (e.g. stale ones attached by the client)
+    /// are ignored instead of failing the batch. Disabled by default.
+    pub fn with_batch_signature_coverage(mut self, enabled: bool) -> Self {
+        self.batch_signature_covers_txs = enabled;
+        self
+    }
+
+    /// Accepts the `ForcedExit` transactions signed by the `operator` in addition to the ones
+    /// signed by their initiators, since the operator submits such transactions on behalf of
+    /// the users who requested the forced exit.
+    pub fn with_forced_exit_operator(mut self, operator: Option<Address>) -> Self {
+        self.forced_exit_operator = operator;
+        self
+    }
+
+    /// Returns `true` if the transaction can be accepted without an Ethereum signature.
+    pub fn is_exempt(&self, tx: &ZkSyncTx) -> bool {
+        let tx_type = match tx {
+            ZkSyncTx::Transfer(_) => TransactionType::Transfer,
+            ZkSyncTx::Withdraw(_) => TransactionType::Withdraw,
+            ZkSyncTx::WithdrawNFT(_) => TransactionType::WithdrawNFT,
+            ZkSyncTx::MintNFT(_) => TransactionType::MintNFT,
+            ZkSyncTx::Swap(_) => TransactionType::Swap,
+            ZkSyncTx::ChangePubKey(_) => TransactionType::ChangePubKey,
+            ZkSyncTx::ForcedExit(_) => TransactionType::ForcedExit,
+            ZkSyncTx::Close(_) => return false,
+        };
+        self.tx_types.contains(&tx_type)
+    }
+}
+
+/// Checks that the (batch of) transaction(s) is not submitted too early.
+fn verify_valid_from(
+    request_data: &RequestData,
+    valid_from_window: &ValidFromWindow,
+) -> Result<(), TxAddError> {
+    match request_data {
+        RequestData::Tx(request) => valid_from_window.check(&request.tx.tx),
+        RequestData::Batch(request) => request
+            .txs
+            .iter()
+            .try_for_each(|tx| valid_from_window.check(&tx.tx)),
+        RequestData::Order(_) | RequestData::Toggle2FA(_) => Ok(()),
+    }
+}
+
+/// Verifies the Ethereum signature of the (batch of) transaction(s).
+async fn verify_eth_signature(
+    request_data: &RequestData,
+    eth_checker: &EthereumChecker,
+    eth_sig_exemptions: &EthSignatureExemptions,
+) -> Result<(), TxAddError> {
+    match request_data {
+        RequestData::Tx(request) => {
+            verify_eth_signature_single_tx(
+                &request.tx,
+                request.sender,
+                &request.parties,
+                request.token.clone(),
+                eth_checker,
+                eth_sig_exemptions,
+            )
+            .await?;
+        }
+        RequestData::Batch(request) => {
+            verify_eth_signature_batch(request, eth_checker, eth_sig_exemptions)
+                .await
+                .map_err(|failure| failure.error)?;
+        }
+        RequestData::Order(request) => {
+            verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+            )
+            .await?;
+        }
+        RequestData::Toggle2FA(request) => {
+            verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the error of a check requiring the Ethereum node.
+fn eth_check_error(err: anyhow::Error) -> TxAddError {
+    if err.is::<EthereumNodeUnavailable>() {
+        TxAddError::EthereumNodeUnavailable
+    } else {
+        TxAddError::SignatureCheckUnavailable
+    }
+}
+
+/// Given a single Ethereum signature and a message, checks that it
+/// was signed by an expected address.
+///
+/// The signature is checked against the bytes of the message in its representation,
+/// i.e. the UTF-8 encoding of the text or the raw binary data.
+///
+/// ECDSA signatures are normalized before the recovery, so the ones with `v` out of the allowed
+/// set or `r`, `s` out of range are rejected with `TxAddError::MalformedSignature`. Signatures of
+/// the wrong length can't be deserialized at all.
+///
+/// Rejected signatures are reported with `TxAddError::SignerMismatch` for ECDSA and
+/// `TxAddError::Eip1271Rejected` for EIP1271 ones. Both of them, along with the malformed
+/// signatures, are the rejections of a single candidate, see `is_signature_rejected`.
+/// Returns `TxAddError::SignatureCheckUnavailable` if the check itself failed,
+/// so the signature is neither correct nor incorrect.
+///
+/// If the `eth_checker` binds the text messages to the chain, the signature is checked against
+/// the accepted versions of the message, see `verify_ethereum_signature_versions`.
+/// If it accepts the untimed messages, the signature of the text message is also checked against
+/// the message without the bounds of the time ranges.
+///
+/// Duration of the check is recorded per signature kind, see `signature_kind`.
+async fn verify_ethereum_signature(
+    eth_signature: &TxEthSignature,
+    message: &SignedMessage,
+    sender_address: Address,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let start = Instant::now();
+    let mut result =
+        verify_ethereum_signature_versions(eth_signature, message, sender_address, eth_checker)
+            .await;
+    // Wallets which don't sign the time ranges yet keep working while they migrate. The rejection
+    // for the current message is reported if the untimed one doesn't match either.
+    let is_rejected = matches!(&result, Err(err) if is_signature_rejected(err));
+    let untimed_message = match is_rejected && eth_checker.accepts_untimed_messages() {
+        true => message.without_time_range(),
+        false => None,
+    };
+    if let Some(untimed_message) = untimed_message {
+        let untimed_result = verify_ethereum_signature_versions(
+            eth_signature,
+            &untimed_message,
+            sender_address,
+            eth_checker,
+        )
+        .await;
+        if untimed_result.is_ok() {
+            metrics::increment_counter!("signature_checker.untimed_eth_message");
+        }
+        if !matches!(&untimed_result, Err(err) if is_signature_rejected(err)) {
+            result = untimed_result;
+        }
+    }
+    metrics::histogram!(
+        "signature_checker.verify_signature",
+        start.elapsed(),
+        "kind" => signature_kind(eth_signature)
+    );
+    result
+}
+
+/// Name of the signature kind the check duration is recorded for.
+fn signature_kind(eth_signature: &TxEthSignature) -> &'static str {
+    match eth_signature {
+        TxEthSignature::EthereumSignature(_) => "ecdsa",
+        TxEthSignature::EIP1271Signature(_) => "eip1271",
+        TxEthSignature::EIP712Signature(_) => "eip712",
+        TxEthSignature::AggregatedBLS(_) => "aggregated_bls",
+    }
+}
+
+/// Checks the signature against the versions of the message accepted by the `eth_checker`,
+/// chain-bound one first. The rejection for the first version is reported if none of them match.
+///
+/// EIP-712 typed data is checked as is, since its domain includes the chain ID already.
+/// Binary messages, e.g. the legacy batch message, can't be bound to the chain and are checked
+/// as is as well: they're accepted according to the `BatchMessageFormats`.
+async fn verify_ethereum_signature_versions(
+    eth_signature: &TxEthSignature,
+    message: &SignedMessage,
+    sender_address: Address,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let chain_binding = match (eth_checker.chain_binding(), eth_signature, message) {
+        (None, _, _)
+        | (_, TxEthSignature::EIP712Signature(_), _)
+        | (_, _, SignedMessage::Bytes(_)) => {
+            return verify_ethereum_signature_inner(
+                eth_signature,
+                message,
+                sender_address,
+                eth_checker,
+            )
+            .await;
+        }
+        (Some(chain_binding), _, _) => chain_binding,
+    };
+    let mut rejection = None;
+    for &version in chain_binding.message_versions() {
+        let message = match message.in_version(version, chain_binding.chain_id) {
+            Some(message) => message,
+            None => continue,
+        };
+        match verify_ethereum_signature_inner(eth_signature, &message, sender_address, eth_checker)
+            .await
+        {
+            Ok(()) => {
+                // Shows how many wallets still sign the unbound messages.
+                metrics::increment_counter!(
+                    "signature_checker.eth_message_version",
+                    "version" => version.name()
+                );
+                return Ok(());
+            }
+            Err(err) if is_signature_rejected(&err) => {
+                rejection.get_or_insert(err);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    // Text message exists in every version, so it's been checked at least once.
+    Err(rejection.unwrap_or(TxAddError::Other))
+}
+
+async fn verify_ethereum_signature_inner(
+    eth_signature: &TxEthSignature,
+    message: &SignedMessage,
+    sender_address: Address,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let signer_account = match eth_signature {
+        TxEthSignature::EthereumSignature(packed_signature) => {
+            let packed_signature = packed_signature.normalize().map_err(|err| {
+                vlog::debug!(%err, "Malformed Ethereum signature");
+                TxAddError::MalformedSignature
+            })?;
+            tracing::debug_span!("ecdsa_recover")
+                .in_scope(|| eth_checker.recover_signer(&packed_signature, message.as_bytes()))
+        }
+        TxEthSignature::EIP1271Signature(signature) => {
+            let is_correct = eth_checker
+                .is_eip1271_signature_correct(sender_address, message.as_bytes(), signature.clone())
+                .instrument(tracing::debug_span!("eip1271_check", account = ?sender_address))
+                .await
+                .map_err(|err| {
+                    vlog::warn!("Unable to check EIP1271 signature: {:#}", err);
+                    eth_check_error(err)
+                })?;
+            vlog::debug!(is_correct, "EIP1271 signature checked");
+            return match is_correct {
+                true => Ok(()),
+                false => Err(TxAddError::Eip1271Rejected),
+            };
+        }
+        TxEthSignature::EIP712Signature(signature) => {
+            // Typed data wraps the human-readable message, binary ones can't be signed this way.
+            let (domain, text) = match (eth_checker.eip712_domain(), message) {
+                (Some(domain), SignedMessage::Text(text)) => (domain, text),
+                _ => return Err(TxAddError::UnsupportedSignatureType),
+            };
+            let signature = signature.normalize().map_err(|err| {
+                vlog::debug!(%err, "Malformed EIP712 signature");
+                TxAddError::MalformedSignature
+            })?;
+            tracing::debug_span!("eip712_recover")
+                .in_scope(|| signature.recover_signer(domain, text))
+        }
+        // Aggregated signatures only make sense for batches.
+        TxEthSignature::AggregatedBLS(_) => return Err(TxAddError::UnsupportedSignatureType),
+    };
+    match signer_account {
+        Ok(address) if address == sender_address => Ok(()),
+        Ok(address) => {
+            vlog::debug!(
+                signer = ?address,
+                message_kind = message.kind(),
+                "Ethereum signer recovered"
+            );
+            Err(TxAddError::SignerMismatch {
+                expected: sender_address,
+                recovered: address,
+            })
+        }
+        Err(err) => {
+            vlog::debug!(%err, "Unable to recover Ethereum signer");
+            Err(TxAddError::MalformedSignature)
+        }
+    }
+}
+
+/// Returns `true` if the signature doesn't belong to the expected signer of the message or
+/// is malformed, so another message, signer or signature may still be checked.
+/// The errors of the batch transactions are unwrapped.
+fn is_signature_rejected(err: &TxAddError) -> bool {
+    matches!(
+        err.reason(),
+        TxAddError::SignerMismatch { .. }
+            | TxAddError::Eip1271Rejected
+            | TxAddError::MalformedSignature
+    )
+}
+
+/// Returns the number of the transaction parties other than the sender which may need
+/// to sign the transaction with their Ethereum keys.
+fn eth_signature_parties_count(tx: &ZkSyncTx) -> usize {
+    match tx {
+        // Orders of both swapping accounts.
+        ZkSyncTx::Swap(_) => 2,
+        _ => 0,
+    }
+}
+
+async fn verify_eth_signature_single_tx(
+    tx: &SignedZkSyncTx,
+    sender_address: Address,
+    parties: &[Address],
+    token: Token,
+    eth_checker: &EthereumChecker,
+    eth_sig_exemptions: &EthSignatureExemptions,
+) -> Result<(), TxAddError> {
+    verify_eth_signature_sender(tx, sender_address, token, eth_checker, eth_sig_exemptions).await?;
+    verify_eth_signature_parties(tx, parties, eth_checker).await
+}
+
+/// Verifies the Ethereum signature of the transaction sender, along with the onchain
+/// `ChangePubKey` authorization.
+async fn verify_eth_signature_sender(
+    tx: &SignedZkSyncTx,
+    sender_address: Address,
+    token: Token,
+    eth_checker: &EthereumChecker,
+    eth_sig_exemptions: &EthSignatureExemptions,
+) -> Result<(), TxAddError> {
+    let start = Instant::now();
+    // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
+    if let ZkSyncTx::ChangePubKey(change_pk) = &tx.tx {
+        // Counterfactual wallet deployed with CREATE2 has no key to sign with, so the new
+        // public key hash is authorized by the account address being derived from it.
+        if let Some(ChangePubKeyEthAuthData::CREATE2(create2_data)) = &change_pk.eth_auth_data {
+            if create2_data.get_address(&change_pk.new_pk_hash) != change_pk.account {
+                vlog::debug!(account = ?change_pk.account, "CREATE2 address doesn't match the account");
+                return Err(TxAddError::ChangePkNotAuthorized);
+            }
+        }
+        if change_pk.is_onchain() {
+            // Check that user is allowed to perform this operation.
+            let auth_start = Instant::now();
+            let authorization = eth_checker
+                .is_new_pubkey_hash_authorized(
+                    change_pk.account,
+                    change_pk.nonce,
+                    &change_pk.new_pk_hash,
+                )
+                .instrument(
+                    tracing::debug_span!("change_pubkey_auth", account = ?change_pk.account),
+                )
+                .await
+                .map_err(|err| {
+                    vlog::warn!(
+                        "Unable to check onchain ChangePubKey authorization: {:#}",
+                        err
+                    );
+                    eth_check_error(err)
+                });
+            metrics::histogram!(
+                "signature_checker.verify_signature",
+                auth_start.elapsed(),
+                "kind" => "change_pubkey_onchain"
+            );
+            let authorization = authorization?;
+            vlog::debug!(?authorization, "Onchain ChangePubKey authorization checked");
+
+            match authorization {
+                PubKeyHashAuthorization::Authorized => {}
+                PubKeyHashAuthorization::NotConfirmed => {
+                    return Err(TxAddError::ChangePkAuthorizationNotConfirmed);
+                }
+                PubKeyHashAuthorization::NotAuthorized => {
+                    return Err(TxAddError::ChangePkNotAuthorized);
+                }
+            }
+        }
+    }
+
+    // Transactions of the exempt types don't need the Ethereum signature. Their
+    // correctness, including the zkSync signature, is checked separately. `ChangePubKey`
+    // authorization is checked above regardless of the exemption, since it's not
+    // an Ethereum signature of the transaction.
+    if tx.eth_sign_data.is_none() && eth_sig_exemptions.is_exempt(&tx.tx) {
+        return Ok(());
+    }
+
+    // Check the signature.
+    if let Some(sign_data) = &tx.eth_sign_data {
+        let old_message = tx.get_old_ethereum_sign_message(token);
+        let result =
+            verify_tx_signed_by(sign_data, &old_message, sender_address, eth_checker).await;
+        // `ForcedExit` submitted by the operator is signed with the operator key.
+        let operator = match &tx.tx {
+            ZkSyncTx::ForcedExit(_) => eth_sig_exemptions.forced_exit_operator,
+            _ => None,
+        };
+        match (result, operator) {
+            // The error for the sender is reported if the operator doesn't match either.
+            (Err(err), Some(operator)) if is_signature_rejected(&err) => {
+                verify_tx_signed_by(sign_data, &old_message, operator, eth_checker)
+                    .await
+                    .map_err(|operator_err| {
+                        if is_signature_rejected(&operator_err) {
+                            err
+                        } else {
+                            operator_err
+                        }
+                    })?;
+            }
+            (result, _) => result?,
+        }
+    }
+
+    metrics::histogram!(
+        "signature_checker.verify_eth_signature_single_tx",
+        start.elapsed()
+    );
+    Ok(())
+}
+
+/// Verifies the Ethereum signatures of the transaction parties other than the sender,
+/// e.g. of the `Swap` orders owners. Signature from `parties_eth_sign_data` is checked
+/// against the party with the same index.
+async fn verify_eth_signature_parties(
+    tx: &SignedZkSyncTx,
+    parties: &[Address],
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    if tx.parties_eth_sign_data.len() > eth_signature_parties_count(&tx.tx) {
+        return Err(TxAddError::EthSignaturesLimitExceeded);
+    }
+    if tx.parties_eth_sign_data.len() > parties.len() {
+        vlog::warn!(
+            "Transaction has {} parties signatures, but only {} parties are provided",
+            tx.parties_eth_sign_data.len(),
+            parties.len()
+        );
+        return Err(TxAddError::Other);
+    }
+    for (sign_data, party) in tx.parties_eth_sign_data.iter().zip(parties) {
+        if let Some(sign_data) = sign_data {
+            verify_ethereum_signature(
+                &sign_data.signature,
+                &sign_data.message,
+                *party,
+                eth_checker,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the Ethereum signature of the transaction is made by the `signer` either for
+/// the current message or for the old one, if the transaction has it.
+async fn verify_tx_signed_by(
+    sign_data: &EthSignData,
+    old_message: &Option<String>,
+    signer: Address,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let signature = &sign_data.signature;
+    let result =
+        verify_ethereum_signature(signature, &sign_data.message, signer, eth_checker).await;
+    match (result, old_message) {
+        // The error for the current message is reported if the old one doesn't match either.
+        (Err(err), Some(message)) if is_signature_rejected(&err) => verify_ethereum_signature(
+            signature,
+            &SignedMessage::Text(message.clone()),
+            signer,
+            eth_checker,
+        )
+        .await
+        .map_err(|old_err| {
+            if is_signature_rejected(&old_err) {
+                err
+            } else {
+                old_err
+            }
+        }),
+        (result, _) => result,
+    }
+}
+
+/// Checks that the aggregated BLS signature covers the batch message and all of its senders.
+///
+/// BLS signatures are not supported yet, so such batches are always rejected.
+fn verify_aggregated_bls_signature(
+    _signature: &AggregatedSignature,
+    _message: &SignedMessage,
+    _senders: &[Address],
+) -> Result<(), TxAddError> {
+    Err(TxAddError::UnsupportedSignatureType)
+}
+
+/// Failure of the batch verification attributed to the transaction at `index`.
+/// Failures of the batch as a whole are attributed to its first transaction, but
+/// they're reported without the transaction location.
+#[derive(Debug)]
+struct BatchTxFailure {
+    index: usize,
+    error: TxAddError,
+    whole_batch: bool,
+}
+
+impl BatchTxFailure {
+    fn tx(index: usize, error: TxAddError) -> Self {
+        Self {
+            index,
+            error,
+            whole_batch: false,
+        }
+    }
+
+    fn whole_batch(error: TxAddError) -> Self {
+        Self {
+            index: 0,
+            error,
+            whole_batch: true,
+        }
+    }
+
+    /// Returns the error reported to the client, see `TxAddError::BatchTx`.
+    fn into_error(self, batch: &[SignedZkSyncTx]) -> TxAddError {
+        if self.whole_batch {
+            return self.error;
+        }
+        TxAddError::batch_tx(self.index, batch[self.index].hash(), self.error)
+    }
+}
+
+/// Returns `true` if the error is caused by the signature checker itself rather than
+/// by the checked transaction.
+fn is_internal_error(err: &TxAddError) -> bool {
+    matches!(
+        err,
+        TxAddError::EthereumNodeUnavailable
+            | TxAddError::SignatureCheckUnavailable
+            | TxAddError::SignatureCheckTimeout
+            | TxAddError::Other
+    )
+}
+
+/// Maximum number of the batch transactions which Ethereum signatures are checked at once.
+const BATCH_TX_SIGNATURES_CONCURRENCY: usize = 8;
+
+/// Verifies the Ethereum signatures of the batch: the batch signature, if any, and the
+/// signatures of the transactions. Reports the failure of the lowest-index transaction.
+async fn verify_eth_signature_batch(
+    request: &BatchRequest,
+    eth_checker: &EthereumChecker,
+    eth_sig_exemptions: &EthSignatureExemptions,
+) -> Result<(), BatchTxFailure> {
+    let senders = &request.senders;
+    let txs = &request.txs;
+
+    if senders.len() != txs.len() {
+        return Err(BatchTxFailure::whole_batch(TxAddError::Other));
+    }
+    let mut batch_signature_failure = None;
+    let covered_by_batch_signature = match &request.batch_sign_data {
+        Some(batch_sign_data) => {
+            match verify_eth_signature_txs_batch(txs, senders, batch_sign_data, eth_checker).await {
+                Ok(()) => eth_sig_exemptions.batch_signature_covers_txs,
+                Err(failure) if is_internal_error(&failure.error) => return Err(failure),
+                Err(failure) => {
+                    batch_signature_failure = Some(failure);
+                    false
+                }
+            }
+        }
+        None => false,
+    };
+    // In case there're signatures provided for some of transactions we still verify them,
+    // but only the ones before the transaction the batch signature failed for.
+    let checked_txs = batch_signature_failure
+        .as_ref()
+        .map_or(txs.len(), |failure| failure.index);
+    // Transactions past the end of `parties` have no other parties.
+    let parties = request
+        .parties
+        .iter()
+        .map(Vec::as_slice)
+        .chain(std::iter::repeat(&[][..]));
+    // Signatures of the transactions are checked concurrently, since the EIP1271 ones require
+    // a call to the Ethereum node each. Results are taken in the batch order, so the failure
+    // of the lowest-index transaction is reported and the checks after it are abandoned.
+    let mut results = futures::stream::iter(
+        txs.iter()
+            .zip(senders)
+            .zip(request.tokens.iter().cloned())
+            .zip(parties)
+            .take(checked_txs),
+    )
+    .map(|(((tx, &sender), token), parties)| async move {
+        let result =
+            verify_eth_signature_sender(tx, sender, token, eth_checker, eth_sig_exemptions).await;
+        let parties_result = verify_eth_signature_parties(tx, parties, eth_checker).await;
+        (tx, result, parties_result)
+    })
+    .buffered(BATCH_TX_SIGNATURES_CONCURRENCY)
+    .enumerate();
+    while let Some((index, (tx, result, parties_result))) = results.next().await {
+        // Only the mismatching signatures are covered: other checks, e.g. of the onchain
+        // `ChangePubKey` authorization, still apply.
+        match result {
+            Ok(()) => {}
+            Err(err) if covered_by_batch_signature && is_signature_rejected(&err) => {
+                vlog::debug!(
+                    tx_hash = %tx.hash(),
+                    "Ignoring the Ethereum signature of the transaction covered by the batch signature: {}",
+                    err
+                );
+            }
+            Err(error) => return Err(BatchTxFailure::tx(index, error)),
+        }
+        // Batch signature is made by the senders, so the signatures of the other parties
+        // are never covered by it.
+        parties_result.map_err(|error| BatchTxFailure::tx(index, error))?;
+    }
+    batch_signature_failure.map_or(Ok(()), Err)
+}
+
+/// Checks that every sender of the batch has made one of the batch signatures.
+///
+/// Signatures are accepted for the batch message of any format enabled in the `eth_checker`,
+/// tried in the order of `BatchMessageFormat::ALL`. If none of the signatures match,
+/// `TxAddError::IncorrectEthSignature` lists the formats the signatures were checked for.
+async fn verify_eth_signature_txs_batch(
+    txs: &[SignedZkSyncTx],
+    senders: &[Address],
+    batch_sign_data: &EthBatchSignData,
+    eth_checker: &EthereumChecker,
+) -> Result<(), BatchTxFailure> {
+    let start = Instant::now();
+    if batch_sign_data.signatures.is_empty() {
+        return Err(BatchTxFailure::whole_batch(TxAddError::MissingEthSignature));
+    }
+    for signature in &batch_sign_data.signatures {
+        if let TxEthSignature::AggregatedBLS(signature) = signature {
+            verify_aggregated_bls_signature(signature, &batch_sign_data.message, senders)
+                .map_err(BatchTxFailure::whole_batch)?;
+        }
+    }
+    // Cache for verified senders.
+    let mut signers = HashSet::with_capacity(senders.len());
+    // Messages of the accepted formats the batch can be signed in. Signatures are checked
+    // against them in order, so the batches signed in the legacy format are still accepted
+    // while wallets migrate to the structured one.
+    let mut attempted_formats = BatchMessageFormats::default();
+    let messages: Vec<_> = eth_checker
+        .batch_message_formats()
+        .iter()
+        .filter_map(|format| {
+            let message = batch_sign_data.message_in_format(format, txs.iter().map(|tx| &tx.tx))?;
+            attempted_formats = attempted_formats.with(format);
+            Some(message)
+        })
+        .collect();
+    if messages.is_empty() {
+        return Err(BatchTxFailure::whole_batch(
+            TxAddError::IncorrectEthSignature {
+                recovered: None,
+                expected: senders[0],
+                message_hash: batch_sign_data.message_hash(),
+                attempted_formats,
+            },
+        ));
+    }
+    // Batch may be signed by both EOA and smart contract wallets. Each sender is checked
+    // against the signatures of every kind, but the ECDSA ones are tried first: they're
+    // recovered locally, while the EIP1271 check requires a call to the Ethereum node.
+    let signatures: Vec<_> = batch_sign_data
+        .signatures
+        .iter()
+        .filter(|signature| {
+            matches!(
+                signature,
+                TxEthSignature::EthereumSignature(_) | TxEthSignature::EIP712Signature(_)
+            )
+        })
+        .chain(
+            batch_sign_data
+                .signatures
+                .iter()
+                .filter(|signature| matches!(signature, TxEthSignature::EIP1271Signature(_))),
+        )
+        .collect();
+
+    // Senders are checked in the batch order, so the failure is reported for the lowest index.
+    for (index, sender) in senders.iter().enumerate() {
+        if signers.contains(sender) {
+            continue;
+        }
+        // All possible signers are cached already and this sender didn't match any of them.
+        // The only signature is checked anyway to report the specific reason.
+        if signatures.len() > 1 && signers.len() == signatures.len() {
+            return Err(BatchTxFailure::tx(
+                index,
+                TxAddError::IncorrectEthSignature {
+                    recovered: None,
+                    expected: *sender,
+                    message_hash: batch_sign_data.message_hash(),
+                    attempted_formats,
+                },
+            ));
+        }
+        // This block will set the `sender_correct` variable to `true` at the first match.
+        let mut sender_correct = false;
+        let mut rejection = None;
+        // Signer of the first ECDSA signature, reported if none of the signatures match.
+        let mut recovered = None;
+        for &signature in &signatures {
+            // The rejection for the first format is reported if none of the messages match.
+            let mut result =
+                verify_ethereum_signature(signature, &messages[0], *sender, eth_checker).await;
+            for message in &messages[1..] {
+                if !matches!(&result, Err(err) if is_signature_rejected(err)) {
+                    break;
+                }
+                let other_result =
+                    verify_ethereum_signature(signature, message, *sender, eth_checker).await;
+                if !matches!(&other_result, Err(err) if is_signature_rejected(err)) {
+                    result = other_result;
+                }
+            }
+            match result {
+                Ok(()) => {
+                    signers.insert(sender);
+                    sender_correct = true;
+                    break;
+                }
+                Err(err) if is_signature_rejected(&err) => {
+                    if let TxAddError::SignerMismatch {
+                        recovered: signer, ..
+                    } = err
+                    {
+                        recovered.get_or_insert(signer);
+                    }
+                    rejection = Some(err);
+                }
+                Err(error) => return Err(BatchTxFailure::tx(index, error)),
+            }
+        }
+        // No signature for this transaction found, return error. The specific reason is only
+        // known if there is a single signature to check.
+        if !sender_correct {
+            let error = match (signatures.len(), rejection) {
+                (1, Some(rejection)) => rejection,
+                _ => TxAddError::IncorrectEthSignature {
+                    recovered,
+                    expected: *sender,
+                    message_hash: batch_sign_data.message_hash(),
+                    attempted_formats,
+                },
+            };
+            return Err(BatchTxFailure::tx(index, error));
+        }
+    }
+    metrics::histogram!(
+        "signature_checker.verify_eth_signature_txs_batch",
+        start.elapsed()
+    );
+    Ok(())
+}
+
+/// Checks that all the transactions of the batch paying a fee pay it in the same token,
+/// otherwise the fee accounting of the batch is ambiguous. The token of the zero fee
+/// doesn't matter, e.g. the transactions whose fee is paid by another one of the batch
+/// may transfer any token.
+///
+/// Returns the fee token of the batch, or `None` if none of its transactions pays a fee.
+fn verify_batch_fee_token_consistency(
+    txs: &[SignedZkSyncTx],
+) -> Result<Option<TokenId>, TxAddError> {
+    let mut fee_tokens = txs.iter().filter_map(|tx| match tx.tx.get_fee_info()? {
+        (_, TokenLike::Id(token), _, fee) if !fee.is_zero() => Some(token),
+        _ => None,
+    });
+    let first = fee_tokens.next();
+    if let Some(first) = first {
+        if let Some(conflicting) = fee_tokens.find(|&token| token != first) {
+            return Err(TxAddError::BatchFeeTokenMismatch(first, conflicting));
+        }
+    }
+    Ok(first)
+}
+
+/// Rejects the batch paying less than the minimum total fee of its fee token before its
+/// signatures are checked. It's not a replacement for the fee checks of the individual
+/// transactions, but the batches which fail them anyway don't take the signature checker resources.
+///
+/// Fees are summed up in the fee token of the batch, see `verify_batch_fee_token_consistency`.
+/// The batch none of whose transactions pays a fee is checked against the minimum of the token
+/// of its first transaction. Batches paying in the tokens without the minimum are not checked.
+fn verify_batch_total_fee(
+    request_data: &RequestData,
+    min_total_fees: &HashMap<TokenId, BigUint>,
+) -> Result<(), TxAddError> {
+    let txs = match request_data {
+        RequestData::Batch(request) if !min_total_fees.is_empty() => &request.txs,
+        _ => return Ok(()),
+    };
+    // Empty batch is rejected with its own error by `verify_tx_signature`.
+    let first_tx = match txs.first() {
+        Some(tx) => tx,
+        None => return Ok(()),
+    };
+    let fee_token =
+        verify_batch_fee_token_consistency(txs)?.unwrap_or_else(|| first_tx.tx.token_id());
+    let min_total_fee = match min_total_fees.get(&fee_token) {
+        Some(min_total_fee) => min_total_fee,
+        None => return Ok(()),
+    };
+    let total_fee: BigUint = txs
+        .iter()
+        .filter_map(|tx| tx.tx.get_fee_info())
+        .map(|(_, _, _, fee)| fee)
+        .sum();
+    if &total_fee < min_total_fee {
+        vlog::debug!(%fee_token, %total_fee, "Batch total fee is below the minimum");
+        return Err(TxAddError::TxBatchFeeTooLow);
+    }
+    Ok(())
+}
+
+/// Minimal size of a batch which transactions are checked in parallel.
+/// Smaller batches are not worth the synchronization overhead.
+const PARALLEL_CORRECTNESS_CHECK_THRESHOLD: usize = 8;
+
+/// Verifies the correctness of the ZKSync transaction(s) (including the
+/// signature check).
+///
+/// `order_owner` is reported as the account of the incorrect order.
+fn verify_tx_correctness(tx: &mut TxVariant, order_owner: Address) -> Result<(), TxAddError> {
+    match tx {
+        TxVariant::Tx(tx) => {
+            let account = tx.tx.account();
+            tx.tx
+                .check_correctness()
+                .map_err(|err| TxAddError::incorrect_tx(account, err))?;
+        }
+        TxVariant::Batch(batch, _) => {
+            verify_batch_correctness(batch).map_err(|failure| failure.error)?;
+        }
+        TxVariant::Order(order) => order
+            .check_correctness()
+            .map_err(|err| TxAddError::incorrect_tx(order_owner, err))?,
+        TxVariant::Toggle2FA => {} // There is no data to check correctness of
+    }
+    Ok(())
+}
+
+/// Verifies the correctness of the batch transactions and reports the failure
+/// of the lowest-index one.
+fn verify_batch_correctness(batch: &mut [SignedZkSyncTx]) -> Result<(), BatchTxFailure> {
+    let check = |(index, tx): (usize, &mut SignedZkSyncTx)| {
+        let account = tx.tx.account();
+        tx.tx
+            .check_correctness()
+            .map_err(|err| BatchTxFailure::tx(index, TxAddError::incorrect_tx(account, err)))
+    };
+    if batch.len() >= PARALLEL_CORRECTNESS_CHECK_THRESHOLD {
+        // Checking the zkSync signatures is CPU-heavy, so large batches are spread
+        // over the rayon thread pool. The first failed transaction in the batch order
+        // is reported, so the error doesn't depend on the scheduling.
+        match batch
+            .par_iter_mut()
+            .enumerate()
+            .map(check)
+            .find_first(Result::is_err)
+        {
+            Some(result) => result,
+            None => Ok(()),
+        }
+    } else {
+        batch.iter_mut().enumerate().try_for_each(check)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxRequest {
+    pub tx: SignedZkSyncTx,
+    /// Expected Ethereum signer of the transaction, see `ZkSyncTx::eth_signer`. This field is
+    /// needed since for `ForcedExit` account affected by the transaction and actual sender can
+    /// be different, and only the ID of the sender is known. Thus, we require request sender to
+    /// perform a database query and fetch actual addresses if necessary.
+    pub sender: Address,
+    /// Resolved token might be used to obtain old-formatted 2-FA messages.
+    /// Needed for backwards compatibility.
+    pub token: Token,
+    /// Addresses of the transaction parties other than the sender, e.g. of the `Swap` orders
+    /// owners. Signatures from `parties_eth_sign_data` of the transaction are checked against
+    /// the party with the same index.
+    pub parties: Vec<Address>,
+}
+
+impl TxRequest {
+    /// Creates a request for the transaction signed by the sender only.
+    pub fn new(tx: SignedZkSyncTx, sender: Address, token: Token) -> Self {
+        Self::with_parties(tx, sender, token, Vec::new())
+    }
+
+    /// Creates a request for the transaction signed by several parties.
+    pub fn with_parties(
+        tx: SignedZkSyncTx,
+        sender: Address,
+        token: Token,
+        parties: Vec<Address>,
+    ) -> Self {
+        Self {
+            tx,
+            sender,
+            token,
+            parties,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchRequest {
+    pub txs: Vec<SignedZkSyncTx>,
+    /// Signature for the whole batch. If it's not provided, request sender is responsible
+    /// for checking that each transaction requiring an Ethereum signature has `eth_sign_data` set.
+    pub batch_sign_data: Option<EthBatchSignData>,
+    /// Expected Ethereum signers of the transactions, resolved the same way as `TxRequest::sender`.
+    pub senders: Vec<Address>,
+    pub tokens: Vec<Token>,
+    /// Addresses of the other parties of each transaction, see `TxRequest::parties`.
+    /// Transactions past the end of the list have no other parties.
+    pub parties: Vec<Vec<Address>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderRequest {
+    pub order: Box<Order>,
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Toggle2FARequest {
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
+/// Request for the signature check.
+#[derive(Debug)]
+pub struct VerifySignatureRequest {
+    pub data: RequestData,
+    /// Channel for sending the check response.
+    pub response: oneshot::Sender<Result<VerifiedTx, TxAddError>>,
+    /// Span of the request sender. Verification span is created as its child, so
+    /// the request can be traced from the API to the signature checker.
+    pub span: tracing::Span,
+    /// Point in time after which the response is no longer needed. If the check
+    /// can't be finished before it, the request is abandoned and answered with
+    /// `TxAddError::SignatureCheckTimeout`.
+    pub deadline: Option<Instant>,
+    /// Point in time the request has been created at, used to measure the time
+    /// it has been waiting in the queue.
+    pub created_at: Instant,
+}
+
+impl VerifySignatureRequest {
+    /// Creates a request along with the receiver of its response.
+    ///
+    /// Request is traced as a part of the current span and has no deadline.
+    pub fn new(data: RequestData) -> (Self, oneshot::Receiver<Result<VerifiedTx, TxAddError>>) {
+        let (response, receiver) = oneshot::channel();
+        let request = Self {
+            data,
+            response,
+            span: tracing::Span::current(),
+            deadline: None,
+            created_at: Instant::now(),
+        };
+        (request, receiver)
+    }
+
+    /// Creates a request to check a single transaction. See `new` for details.
+    pub fn new_single(
+        request: TxRequest,
+    ) -> (Self, oneshot::Receiver<Result<VerifiedTx, TxAddError>>) {
+        Self::new(RequestData::Tx(request))
+    }
+
+    /// Creates a request to check a batch of transactions. See `new` for details.
+    pub fn new_batch(
+        request: BatchRequest,
+    ) -> (Self, oneshot::Receiver<Result<VerifiedTx, TxAddError>>) {
+        Self::new(RequestData::Batch(request))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RequestData {
+    Tx(TxRequest),
+    Batch(BatchRequest),
+    Order(OrderRequest),
+    Toggle2FA(Toggle2FARequest),
+}
+
+impl RequestData {
+    fn variant_name(&self) -> &'static str {
+        match self {
+            RequestData::Tx(_) => "tx",
+            RequestData::Batch(_) => "batch",
+            RequestData::Order(_) => "order",
+            RequestData::Toggle2FA(_) => "toggle_2fa",
+        }
+    }
+
+    /// Hashes of the transactions in the format they're traced with.
+    fn tx_hashes(&self) -> Vec<String> {
+        match self {
+            RequestData::Tx(request) => vec![request.tx.tx.hash().to_string()],
+            RequestData::Batch(request) => request
+                .txs
+                .iter()
+                .map(|tx| tx.tx.hash().to_string())
+                .collect(),
+            RequestData::Order(_) | RequestData::Toggle2FA(_) => Vec::new(),
+        }
+    }
+
+    fn senders(&self) -> Vec<Address> {
+        match self {
+            RequestData::Tx(request) => vec![request.sender],
+            RequestData::Batch(request) => request.senders.clone(),
+            RequestData::Order(request) => vec![request.sender],
+            RequestData::Toggle2FA(request) => vec![request.sender],
+        }
+    }
+
+    /// Creates the span covering the whole verification of the request.
+    fn verification_span(&self, parent: &tracing::Span) -> tracing::Span {
+        tracing::info_span!(
+            parent: parent,
+            "verify_signature",
+            variant = self.variant_name(),
+            tx_hashes = ?self.tx_hashes(),
+            accounts = ?self.senders(),
+            outcome = tracing::field::Empty,
+        )
+    }
+
+    pub fn get_tx_variant(&self) -> TxVariant {
+        match &self {
+            RequestData::Tx(request) => TxVariant::Tx(request.tx.clone()),
+            RequestData::Batch(request) => {
+                TxVariant::Batch(request.txs.clone(), request.batch_sign_data.clone())
+            }
+            RequestData::Order(request) => TxVariant::Order(request.order.clone()),
+            RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
+        }
+    }
+}
+
+/// Number of the verification requests that were received by the signature checker,
+/// but not yet processed. Every update is published to the watch channel.
+#[derive(Debug, Clone)]
+struct QueueDepth {
+    depth: Arc<std::sync::Mutex<usize>>,
+    sender: Arc<watch::Sender<usize>>,
+}
+
+impl QueueDepth {
+    fn new(sender: watch::Sender<usize>) -> Self {
+        Self {
+            depth: Arc::new(std::sync::Mutex::new(0)),
+            sender: Arc::new(sender),
+        }
+    }
+
+    fn increment(&self) {
+        let mut depth = self.depth.lock().unwrap();
+        *depth += 1;
+        self.update(*depth);
+    }
+
+    fn decrement(&self) {
+        let mut depth = self.depth.lock().unwrap();
+        *depth -= 1;
+        self.update(*depth);
+    }
+
+    fn update(&self, depth: usize) {
+        metrics::gauge!("signature_checker.queue_depth", depth as f64);
+        self.sender.send(depth).unwrap_or_default();
+    }
+}
+
+/// Collects the requests that are already available in the channel without waiting
+/// for the new ones. Returned vector contains `first` request and at most
+/// `max_requests - 1` requests taken from the channel.
+fn drain_requests(
+    input: &mut mpsc::Receiver<VerifySignatureRequest>,
+    first: VerifySignatureRequest,
+    max_requests: usize,
+) -> Vec<VerifySignatureRequest> {
+    let mut requests = vec![first];
+    while requests.len() < max_requests {
+        // Channel is either empty or closed, the latter is handled by the main loop.
+        match input.try_next() {
+            Ok(Some(request)) => requests.push(request),
+            _ => break,
+        }
+    }
+    requests
+}
+
+/// Receiver of the verification requests shared between the signature checker threads.
+type SharedReceiver = Arc<Mutex<mpsc::Receiver<VerifySignatureRequest>>>;
+
+/// Takes a slot for the request if the number of requests checked at once is limited,
+/// fails if all the slots are taken.
+fn try_acquire_slot(
+    in_flight_limit: &Option<Arc<Semaphore>>,
+) -> Result<Option<OwnedSemaphorePermit>, TryAcquireError> {
+    match in_flight_limit {
+        Some(limit) => limit.clone().try_acquire_owned().map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Settings and state of the checker shared by all its routines. Built once from
+/// the `SignatureCheckerConfig` when the checker is started.
+struct CheckerContext {
+    /// Checker with all the settings and caches applied. Requests of every poll are checked
+    /// by its copy calling the latest Ethereum client, see `EthereumChecker::with_client`.
+    eth_checker: EthereumChecker,
+    circuit_breaker: Option<CircuitBreaker>,
+    rejection_cache: Option<RejectionCache>,
+    queue_depth: QueueDepth,
+    in_flight_limit: Option<Arc<Semaphore>>,
+    requests_per_poll: usize,
+    valid_from_window: ValidFromWindow,
+    eth_sig_exemptions: EthSignatureExemptions,
+    min_batch_total_fees: HashMap<TokenId, BigUint>,
+    check_timeout: Duration,
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    record_path: Option<PathBuf>,
+}
+
+impl CheckerContext {
+    /// Creates the context of the checker calling the node through `eth_client` until
+    /// it's replaced.
+    fn new(
+        config: SignatureCheckerConfig,
+        eth_client: EthereumGateway,
+        queue_depth: QueueDepth,
+    ) -> Self {
+        let eth_probe_interval = config.eth_probe_interval;
+        let circuit_breaker = config.eth_circuit_breaker.or_else(|| {
+            config
+                .eth_failure_threshold
+                .map(|threshold| CircuitBreaker::new(threshold, eth_probe_interval))
+        });
+        let eth_checker = match &circuit_breaker {
+            Some(circuit_breaker) => {
+                EthereumChecker::with_circuit_breaker(eth_client, circuit_breaker.clone())
+            }
+            None => EthereumChecker::new(eth_client),
+        }
+        .with_auth_block(config.change_pubkey_auth_block)
+        .with_batch_message_formats(config.batch_message_formats)
+        .with_untimed_messages(config.accept_untimed_messages);
+        let eth_checker = match config.signer_cache_size {
+            0 => eth_checker,
+            size => eth_checker.with_signer_cache(SignerCache::new(size)),
+        };
+        let eth_checker = match config.eip1271_cache_size {
+            0 => eth_checker,
+            size => {
+                eth_checker.with_eip1271_cache(Eip1271Cache::new(size, config.eip1271_cache_ttl))
+            }
+        };
+        let eth_checker = match config.offline_eth_checks {
+            Some(offline_eth_checks) => {
+                vlog::warn!(
+                    "Signature checker doesn't call the Ethereum node: EIP1271 signatures and onchain \
+                     ChangePubKey authorizations are checked offline with {:?}. Never use it in production!",
+                    offline_eth_checks
+                );
+                eth_checker.with_offline_checks(offline_eth_checks)
+            }
+            None => eth_checker,
+        };
+        let eth_checker = match config.eip712_chain_id {
+            Some(chain_id) => eth_checker.with_eip712_domain(Eip712Domain::new(chain_id)),
+            None => eth_checker,
+        };
+        let eth_checker = match config.message_chain_id {
+            Some(chain_id) => eth_checker.with_chain_binding(ChainBinding {
+                chain_id,
+                accept_unbound: config.accept_unbound_messages,
+            }),
+            None => eth_checker,
+        };
+        let eth_checker = match config.eth_call_retries {
+            Some(eth_call_retries) => eth_checker.with_call_retries(eth_call_retries),
+            None => eth_checker,
+        };
+        // Batcher is shared by all the routines, so the checks made by different threads
+        // are aggregated as well.
+        let eth_checker = match config.multicall_address {
+            Some(multicall_address) => eth_checker.with_multicall(MulticallBatcher::new(
+                multicall_address,
+                config.zksync_contract_address,
+                config.eth_call_batch_window,
+            )),
+            None => eth_checker,
+        };
+
+        Self {
+            eth_checker,
+            circuit_breaker,
+            rejection_cache: match config.rejection_cache_size {
+                0 => None,
+                size => Some(RejectionCache::new(size, config.rejection_cache_ttl)),
+            },
+            queue_depth,
+            in_flight_limit: config
+                .max_concurrent_verifications
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            requests_per_poll: config.requests_per_poll,
+            valid_from_window: ValidFromWindow::new(config.valid_from_window),
+            eth_sig_exemptions: EthSignatureExemptions::new(config.eth_sig_exempt_tx_types)
+                .with_batch_signature_coverage(config.batch_signature_covers_txs)
+                .with_forced_exit_operator(config.forced_exit_operator),
+            min_batch_total_fees: config.min_batch_total_fees,
+            check_timeout: config.verification_timeout,
+            record_path: config.record_path,
+        }
+    }
+}
+
+/// Basically it receives the requests through the channel and verifies signatures,
+/// notifying the request sender about the check result.
+///
+/// Up to `requests_per_poll` requests of the `context` are taken from the channel at once
+/// and processed within a single spawned task. Checks that don't finish within
+/// `check_timeout` are answered with `TxAddError::SignatureCheckTimeout`.
+///
+/// Every taken batch of requests is checked with the latest Ethereum client
+/// received through `eth_client`.
+///
+/// If `in_flight_limit` is set, the requests taken from the channel while all the slots
+/// are taken are rejected with `TxAddError::ServiceOverloaded` without the check.
+///
+/// In debug builds, the taken requests are appended to the `record_path` file if it's set.
+///
+/// Once `shutdown` is signalled, the channel is closed: the requests in the channel are still
+/// checked and the routine returns after the responses to all the taken requests are sent.
+async fn checker_routine(
+    input: SharedReceiver,
+    eth_client: watch::Receiver<EthereumGateway>,
+    context: Arc<CheckerContext>,
+    shutdown: ShutdownToken,
+) {
+    // Every spawned check holds the sender, so the receiver is closed once all of them are finished.
+    let (checks_running, mut checks_finished) = tokio::sync::mpsc::channel::<()>(1);
+    loop {
+        // Routines running in different threads race to take the next requests.
+        let requests = {
+            let mut input = input.lock().await;
+            match shutdown.recv(&mut *input).await {
+                Some(request) => drain_requests(&mut input, request, context.requests_per_poll),
+                None => break,
+            }
+        };
+        #[cfg(debug_assertions)]
+        if let Some(record_path) = &context.record_path {
+            for request in &requests {
+                replay::record_to_file(record_path, &request.data);
+            }
+        }
+        // Requests over the limit are rejected right away, so the clients can retry later
+        // instead of waiting behind the saturated checker.
+        let (requests, slots): (Vec<_>, Vec<_>) = requests
+            .into_iter()
+            .filter_map(|request| match try_acquire_slot(&context.in_flight_limit) {
+                Ok(slot) => Some((request, slot)),
+                Err(_) => {
+                    metrics::increment_counter!("signature_checker.overloaded_requests");
+                    request
+                        .response
+                        .send(Err(TxAddError::ServiceOverloaded))
+                        .unwrap_or_default();
+                    None
+                }
+            })
+            .unzip();
+        if requests.is_empty() {
+            continue;
+        }
+        let eth_checker = context.eth_checker.with_client(eth_client.borrow().clone());
+        let context = context.clone();
+        for _ in 0..requests.len() {
+            context.queue_depth.increment();
+        }
+        let checks_running = checks_running.clone();
+        tokio::spawn(async move {
+            let _checks_running = checks_running;
+            let checks = requests.into_iter().zip(slots).map(
+                |(
+                    VerifySignatureRequest {
+                        data,
+                        response,
+                        span,
+                        deadline,
+                        created_at,
+                    },
+                    slot,
+                )| {
+                    let eth_checker = &eth_checker;
+                    let context = &context;
+                    let span = data.verification_span(&span);
+                    async move {
+                        metrics::histogram!("signature_checker.queue_wait", created_at.elapsed());
+                        check_request(data, response, deadline, eth_checker, context).await;
+                        context.queue_depth.decrement();
+                        drop(slot);
+                    }
+                    .instrument(span)
+                },
+            );
+            futures::future::join_all(checks).await;
+        });
+    }
+    drop(checks_running);
+    checks_finished.recv().await;
+}
+
+/// Checks a single request and sends the response back.
+///
+/// The check is skipped or abandoned once the response receiver is dropped, since
+/// nobody waits for the result anymore. The check is also abandoned when the request
+/// deadline passes.
+///
+/// Panic during the check is reported to the requester as `TxAddError::Other`
+/// instead of leaving it waiting for the response.
+///
+/// Requests rejected recently are rejected once again without the check, see `RejectionCache`.
+///
+/// Rejections are counted by their error codes, e.g. `INCORRECT_ETH_SIGNATURE`.
+async fn check_request(
+    data: RequestData,
+    mut response: oneshot::Sender<Result<VerifiedTx, TxAddError>>,
+    deadline: Option<Instant>,
+    eth_checker: &EthereumChecker,
+    context: &CheckerContext,
+) {
+    let check_timeout = context.check_timeout;
+    let rejection_cache = context.rejection_cache.as_ref();
+    let span = tracing::Span::current();
+    metrics::increment_counter!("signature_checker.requests", "variant" => data.variant_name());
+    if response.is_canceled() {
+        cancel_request(&span);
+        return;
+    }
+    let timeout = match deadline {
+        Some(deadline) => check_timeout.min(deadline.saturating_duration_since(Instant::now())),
+        None => check_timeout,
+    };
+    let rejection_key = rejection_cache.and_then(|_| RejectionCache::key(&data));
+    let cached_rejection = match (rejection_cache, rejection_key) {
+        (Some(rejection_cache), Some(key)) => rejection_cache.get(key),
+        _ => None,
+    };
+    let is_cached_rejection = cached_rejection.is_some();
+    let resp = if let Some(err) = cached_rejection {
+        vlog::debug!("Request has been rejected recently, the rejection is repeated");
+        metrics::increment_counter!("signature_checker.cached_rejections");
+        Err(err)
+    } else if timeout == Duration::from_secs(0) {
+        vlog::warn!("Signature check deadline has passed before the check");
+        metrics::increment_counter!("signature_checker.expired_requests");
+        Err(TxAddError::SignatureCheckTimeout)
+    } else {
+        let check = tokio::time::timeout(timeout, async {
+            verify_batch_total_fee(&data, &context.min_batch_total_fees)?;
+            verify_tx_signature(
+                data,
+                eth_checker,
+                &context.valid_from_window,
+                &context.eth_sig_exemptions,
+            )
+            .await
+        });
+        // Panic would otherwise kill the whole task along with the other requests it checks.
+        let check = AssertUnwindSafe(check).catch_unwind();
+        tokio::select! {
+            resp = check => match resp {
+                Ok(resp) => resp.unwrap_or_else(|_| {
+                    vlog::warn!("Signature check timed out");
+                    metrics::increment_counter!("signature_checker.timed_out_requests");
+                    Err(TxAddError::SignatureCheckTimeout)
+                }),
+                Err(panic) => {
+                    // Transaction hashes are recorded in the span of the request.
+                    vlog::error!("Signature check panicked: {}", panic_message(&*panic));
+                    metrics::increment_counter!("signature_checker.panicked_requests");
+                    Err(TxAddError::Other)
+                }
+            },
+            _ = response.cancellation() => {
+                cancel_request(&span);
+                return;
+            }
+        }
+    };
+    // Repeated rejection doesn't prolong the cached one.
+    if let (Some(rejection_cache), Some(key), Err(err)) = (rejection_cache, rejection_key, &resp) {
+        if !is_cached_rejection {
+            rejection_cache.insert(key, err.clone());
+        }
+    }
+    match &resp {
+        Ok(_) => span.record("outcome", &"verified"),
+        Err(err) => {
+            metrics::increment_counter!(
+                "signature_checker.rejections",
+                "error" => err.to_error_code()
+            );
+            span.record("outcome", &tracing::field::display(err))
+        }
+    };
+    vlog::debug!("Signature check finished");
+
+    response.send(resp).unwrap_or_default();
+}
+
+/// Short-living cache of the rejected requests, so the repeated submissions of the same
+/// invalid transaction (e.g. by a client probing the API) are rejected without paying
+/// for the check, including the calls to the Ethereum node.
+///
+/// Requests are identified by the contents of their transactions along with the expected
+/// signers. Only the rejections which can't change within the TTL are cached, so internal
+/// errors and the rejections depending on the Ethereum state or on time (such as the missing
+/// onchain `ChangePubKey` authorization or the rejected EIP1271 signature) are never cached.
+#[derive(Debug, Clone)]
+pub struct RejectionCache {
+    rejections: SharedLruCache<H256, (Instant, TxAddError)>,
+    ttl: Duration,
+}
+
+impl RejectionCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            rejections: SharedLruCache::new(capacity),
+            ttl,
+        }
+    }
+
+    /// Returns the key of the request, or `None` for the requests which are not cached.
+    fn key(data: &RequestData) -> Option<H256> {
+        let signers: Vec<_> = match data {
+            RequestData::Tx(request) => std::iter::once(&request.sender)
+                .chain(&request.parties)
+                .collect(),
+            RequestData::Batch(request) => request
+                .senders
+                .iter()
+                .chain(request.parties.iter().flatten())
+                .collect(),
+            // Orders and 2FA toggles are not submitted on their own.
+            RequestData::Order(_) | RequestData::Toggle2FA(_) => return None,
+        };
+        let mut preimage = data.get_tx_variant().content_hash().as_bytes().to_vec();
+        for signer in signers {
+            preimage.extend_from_slice(signer.as_bytes());
+        }
+        Some(H256::from(tiny_keccak::keccak256(&preimage)))
+    }
+
+    fn get(&self, key: H256) -> Option<TxAddError> {
+        match self.rejections.get(&key) {
+            Some((rejected_at, err)) if rejected_at.elapsed() < self.ttl => Some(err),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, key: H256, err: TxAddError) {
+        if is_cacheable_rejection(&err) {
+            self.rejections.insert(key, (Instant::now(), err));
+        }
+    }
+}
+
+/// Returns `true` if the same request is guaranteed to be rejected with the same error
+/// for a while. The errors of the batch transactions are unwrapped, so the internal error
+/// of a single transaction doesn't make the whole batch rejected without the check.
+fn is_cacheable_rejection(err: &TxAddError) -> bool {
+    !is_internal_error(err.reason())
+        && !matches!(
+            err.reason(),
+            // Authorization may appear onchain at any moment, and the contract may
+            // start accepting the EIP1271 signature.
+            TxAddError::ChangePkNotAuthorized
+                | TxAddError::Eip1271Rejected
+                | TxAddError::ChangePkAuthorizationNotConfirmed
+                // Transaction becomes valid over time.
+                | TxAddError::NotYetValid
+                | TxAddError::ServiceOverloaded
+                | TxAddError::DbError
+        )
+}
+
+/// Extracts the message of the caught panic, if it's a string.
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    match panic.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => panic
+            .downcast_ref::<String>()
+            .map_or("<non-string panic payload>", String::as_str),
+    }
+}
+
+fn cancel_request(span: &tracing::Span) {
+    span.record("outcome", &"cancelled");
+    vlog::debug!("Signature check cancelled, response receiver is dropped");
+    metrics::increment_counter!("signature_checker.cancelled_requests");
+}
+
+/// Default maximum number of requests taken from the channel by the signature checker at once.
+pub const DEFAULT_REQUESTS_PER_POLL: usize = 32;
+
+/// Maximum time a single request can be checked, including the calls to the Ethereum node.
+const SIGNATURE_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of the missing `ChangePubKey` authorizations remembered by `AuthFactBlock::LatestWithRetry`.
+const AUTH_RETRIES_CAPACITY: usize = 10_000;
+
+/// Settings of the signature checker.
+///
+/// Only the settings the checker actually uses are kept here, so it can be started
+/// without the whole API configuration, e.g. in tests or as a library component.
+/// The Ethereum client is passed to the checker separately, since it can be replaced
+/// at runtime.
+#[derive(Debug, Clone)]
+pub struct SignatureCheckerConfig {
+    /// Maximum number of requests taken from the channel at once.
+    pub requests_per_poll: usize,
+    /// See `ValidFromWindow`.
+    pub valid_from_window: Option<Duration>,
+    /// Types of transactions that are accepted without an Ethereum signature.
+    pub eth_sig_exempt_tx_types: Vec<TransactionType>,
+    /// See `EthSignatureExemptions::with_batch_signature_coverage`.
+    pub batch_signature_covers_txs: bool,
+    /// Address the `ForcedExit` transactions submitted by the operator are signed with.
+    /// See `EthSignatureExemptions::with_forced_exit_operator`.
+    pub forced_exit_operator: Option<Address>,
+    /// Number of the checker threads, one per CPU by default.
+    pub num_threads: Option<usize>,
+    /// Maximum number of requests checked at once, the requests taken from the channel over it
+    /// are rejected with `TxAddError::ServiceOverloaded`. Not limited if `None`.
+    pub max_concurrent_verifications: Option<usize>,
+    /// Maximum time a single request can be checked.
+    pub verification_timeout: Duration,
+    /// Number of consecutive failed calls to the Ethereum node after which the checks
+    /// requiring it are rejected without calling it. Such checks are never rejected if `None`.
+    pub eth_failure_threshold: Option<u32>,
+    /// Interval between the Ethereum node probes while the checks requiring it are rejected.
+    pub eth_probe_interval: Duration,
+    /// Circuit breaker shared with the caller, e.g. to report its state. Created from
+    /// `eth_failure_threshold` and `eth_probe_interval` if `None`.
+    pub eth_circuit_breaker: Option<CircuitBreaker>,
+    /// Retries of the calls which failed to reach the Ethereum node. Not retried if `None`.
+    pub eth_call_retries: Option<EthCallRetries>,
+    /// Interval between the logged health checks of the Ethereum node. Not logged if `None`.
+    pub eth_health_log_interval: Option<Duration>,
+    /// Block the onchain `ChangePubKey` authorization is checked at.
+    pub change_pubkey_auth_block: AuthFactBlock,
+    /// Formats of the batch message the batch signatures are accepted for.
+    pub batch_message_formats: BatchMessageFormats,
+    /// Constant outcome of the onchain checks which are made without calling the Ethereum node.
+    /// The node is called if `None`. See `EthereumChecker::with_offline_checks`.
+    pub offline_eth_checks: Option<OfflineEthChecks>,
+    /// Chain ID of the EIP-712 typed data the `EIP712Signature`s are checked for.
+    /// Such signatures are rejected if `None`.
+    pub eip712_chain_id: Option<u64>,
+    /// Chain ID the text messages signed with the Ethereum key are bound to, see `ChainBinding`.
+    /// Messages are accepted as is if `None`.
+    pub message_chain_id: Option<u64>,
+    /// Whether the signatures of the messages which aren't bound to the chain are accepted
+    /// along with the chain-bound ones. Only used if `message_chain_id` is set.
+    pub accept_unbound_messages: bool,
+    /// Whether the signatures of the text messages without the bounds of the time ranges
+    /// are accepted along with the ones of the messages including them.
+    pub accept_untimed_messages: bool,
+    /// Minimum total fees of the batches by their fee tokens. Batches paying less are rejected
+    /// with `TxAddError::TxBatchFeeTooLow` before their signatures are checked, the batches
+    /// paying in the other tokens are not checked.
+    pub min_batch_total_fees: HashMap<TokenId, BigUint>,
+    /// Maximum number of the cached ECDSA signers. The cache is disabled if zero.
+    pub signer_cache_size: usize,
+    /// Maximum number of the cached EIP1271 signature check results, see `Eip1271Cache`.
+    /// The cache is disabled if zero.
+    pub eip1271_cache_size: usize,
+    /// Time the EIP1271 signature check result is reused for.
+    pub eip1271_cache_ttl: Duration,
+    /// Address of the Multicall contract the onchain checks are aggregated with,
+    /// see `MulticallBatcher`. Checks are made individually if `None`.
+    pub multicall_address: Option<Address>,
+    /// Address of the zkSync contract. Onchain `ChangePubKey` authorizations are only
+    /// aggregated with the other checks if it's set.
+    pub zksync_contract_address: Option<Address>,
+    /// Time the onchain check waits for the other checks to be aggregated with.
+    pub eth_call_batch_window: Duration,
+    /// Maximum number of the cached rejections, see `RejectionCache`.
+    /// The cache is disabled if zero.
+    pub rejection_cache_size: usize,
+    /// Time the cached rejection is returned for the repeated request.
+    pub rejection_cache_ttl: Duration,
+    /// File the requests are appended to, see `replay::record_to_file`.
+    /// Requests are only recorded by the debug builds.
+    pub record_path: Option<PathBuf>,
+}
+
+impl Default for SignatureCheckerConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_poll: DEFAULT_REQUESTS_PER_POLL,
+            valid_from_window: None,
+            eth_sig_exempt_tx_types: Vec::new(),
+            batch_signature_covers_txs: false,
+            forced_exit_operator: None,
+            num_threads: None,
+            max_concurrent_verifications: None,
+            verification_timeout: SIGNATURE_CHECK_TIMEOUT,
+            eth_failure_threshold: None,
+            eth_probe_interval: Duration::from_secs(10),
+            eth_circuit_breaker: None,
+            eth_call_retries: None,
+            eth_health_log_interval: None,
+            change_pubkey_auth_block: AuthFactBlock::Latest,
+            batch_message_formats: BatchMessageFormats::all(),
+            offline_eth_checks: None,
+            eip712_chain_id: None,
+            message_chain_id: None,
+            accept_unbound_messages: true,
+            accept_untimed_messages: true,
+            min_batch_total_fees: HashMap::new(),
+            signer_cache_size: 0,
+            eip1271_cache_size: 0,
+            eip1271_cache_ttl: Duration::from_secs(30),
+            multicall_address: None,
+            zksync_contract_address: None,
+            eth_call_batch_window: Duration::from_millis(5),
+            rejection_cache_size: 0,
+            rejection_cache_ttl: Duration::from_secs(10),
+            record_path: None,
+        }
+    }
+}
+
+impl From<&CommonApiConfig> for SignatureCheckerConfig {
+    fn from(config: &CommonApiConfig) -> Self {
+        Self {
+            valid_from_window: config.valid_from_acceptance_window(),
+            eth_sig_exempt_tx_types: config.eth_sig_exempt_tx_types.clone(),
+            batch_signature_covers_txs: config.batch_signature_covers_txs,
+            min_batch_total_fees: config
+                .min_batch_total_fees
+                .iter()
+                .map(|min_fee| (min_fee.token, min_fee.fee.clone()))
+                .collect(),
+            // Checker runs a thread per CPU.
+            max_concurrent_verifications: Some(
+                num_cpus::get() * config.sign_checker_checks_per_thread,
+            ),
+            eth_failure_threshold: Some(config.eth_failure_threshold),
+            eth_probe_interval: config.eth_probe_interval(),
+            eth_call_retries: match config.eth_call_retries {
+                0 => None,
+                max_retries => Some(EthCallRetries {
+                    max_retries,
+                    initial_backoff: config.eth_call_retry_backoff(),
+                }),
+            },
+            eth_health_log_interval: config.eth_health_log_interval(),
+            change_pubkey_auth_block: match config.change_pubkey_auth_block {
+                ChangePubKeyAuthBlock::Latest => AuthFactBlock::Latest,
+                ChangePubKeyAuthBlock::Confirmed => {
+                    AuthFactBlock::Confirmed(config.change_pubkey_auth_confirmations)
+                }
+                ChangePubKeyAuthBlock::LatestWithRetry => {
+                    AuthFactBlock::LatestWithRetry(AuthRetries::new(
+                        AUTH_RETRIES_CAPACITY,
+                        config.change_pubkey_auth_retry_delay(),
+                    ))
+                }
+            },
+            batch_message_formats: match config.accept_legacy_batch_message {
+                true => BatchMessageFormats::all(),
+                false => BatchMessageFormats::all().without(BatchMessageFormat::Legacy),
+            },
+            accept_unbound_messages: config.accept_unbound_eth_messages,
+            accept_untimed_messages: config.accept_untimed_eth_messages,
+            offline_eth_checks: match config.eth_checker_mode {
+                EthCheckerMode::Node => None,
+                EthCheckerMode::AcceptAll => Some(OfflineEthChecks::AcceptAll),
+                EthCheckerMode::RejectAll => Some(OfflineEthChecks::RejectAll),
+            },
+            signer_cache_size: config.signer_cache_size,
+            eip1271_cache_size: config.eip1271_cache_size,
+            eip1271_cache_ttl: config.eip1271_cache_ttl(),
+            multicall_address: config.multicall_address,
+            eth_call_batch_window: config.eth_call_batch_window(),
+            rejection_cache_size: config.rejection_cache_size,
+            rejection_cache_ttl: config.rejection_cache_ttl(),
+            record_path: config.sign_checker_record_path.as_ref().map(PathBuf::from),
+            ..Default::default()
+        }
+    }
+}
+
+impl SignatureCheckerConfig {
+    /// Checks that the settings are safe to use on the given Ethereum network:
+    /// the onchain checks accepting everything are only allowed outside of mainnet.
+    pub fn ensure_allowed_on(&self, network: Network) -> anyhow::Result<()> {
+        if network == Network::Mainnet
+            && self.offline_eth_checks == Some(OfflineEthChecks::AcceptAll)
+        {
+            anyhow::bail!("Offline onchain checks accepting everything are not allowed on mainnet");
+        }
+        Ok(())
+    }
+}
+
+/// Main routine of the concurrent signature checker.
+/// See the module documentation for details.
+///
+/// Spawns `num_threads` OS threads (by default, one per CPU), each running its own
+/// event loop and taking the requests from the shared channel.
+///
+/// Transactions of `eth_sig_exempt_tx_types` are accepted without an Ethereum signature.
+///
+/// Ethereum client (and thus the web3 URL and the contract address) can be replaced
+/// at runtime by sending a new one through the `client` channel. Requests which are
+/// already being checked finish with the client they started with.
+///
+/// Along with the task handle returns the receiver of the number of requests
+/// which are currently being processed. Request producers may use it to apply
+/// backpressure when the signature checker falls behind.
+///
+/// Requests are taken from `input` as soon as possible, so it's the request producer's
+/// responsibility to limit the number of requests. See `spawn_sign_checker` for the
+/// checker fed through a bounded channel.
+pub fn start_sign_checker(
+    client: watch::Receiver<EthereumGateway>,
+    input: mpsc::Receiver<VerifySignatureRequest>,
+    requests_per_poll: usize,
+    valid_from_window: Option<Duration>,
+    eth_sig_exempt_tx_types: Vec<TransactionType>,
+    num_threads: Option<usize>,
+) -> (JoinHandle<()>, watch::Receiver<usize>) {
+    let config = SignatureCheckerConfig {
+        requests_per_poll,
+        valid_from_window,
+        eth_sig_exempt_tx_types,
+        num_threads,
+        ..Default::default()
+    };
+    run_sign_checker(client, input, config, None, None, ShutdownToken::never())
+}
+
+/// Starts the signature checker fed through a bounded channel of the given capacity
+/// and returns the sender side of that channel.
+///
+/// Along with the task handle returns the receiver of the number of requests taken
+/// from the channel, but not yet processed, same as `start_sign_checker`.
+///
+/// At most `max_concurrent_verifications` requests are checked at once, the requests taken
+/// from the channel over it are rejected with `TxAddError::ServiceOverloaded`. Once the channel
+/// is full, `try_send` fails with an error for which `is_full()` returns `true`, so the request
+/// producers can reject the request instead of waiting. Panics of the checker threads are
+/// reported through `panic_notify`.
+///
+/// Checks requiring the Ethereum node are rejected with `TxAddError::EthereumNodeUnavailable`
+/// once the node fails `eth_failure_threshold` times in a row, until a probe shows that
+/// the node is available again.
+///
+/// Onchain `ChangePubKey` authorizations are checked at `change_pubkey_auth_block`. The ones which may
+/// still be confirmed or appear later are rejected with `TxAddError::ChangePkAuthorizationNotConfirmed`.
+///
+/// Once `shutdown` is signalled, the new requests are rejected, the ones in the channel are checked
+/// and the threads exit after sending the responses.
+pub fn spawn_sign_checker(
+    client: watch::Receiver<EthereumGateway>,
+    config: SignatureCheckerConfig,
+    capacity: usize,
+    panic_notify: mpsc::Sender<bool>,
+    shutdown: ShutdownToken,
+) -> (
+    mpsc::Sender<VerifySignatureRequest>,
+    JoinHandle<()>,
+    watch::Receiver<usize>,
+) {
+    let (sender, input) = mpsc::channel(capacity);
+    let (handle, queue_depth) =
+        run_sign_checker(client, input, config, Some(panic_notify), None, shutdown);
+    (sender, handle, queue_depth)
+}
+
+/// Starts the signature checker on the provided runtime instead of spawning dedicated
+/// OS threads, so the checks share the worker pool with the rest of the application.
+///
+/// The checker is configured the same way as in `spawn_sign_checker`, except for
+/// `num_threads`. The returned task finishes once `input` is closed.
+pub fn start_sign_checker_on_handle(
+    client: watch::Receiver<EthereumGateway>,
+    config: SignatureCheckerConfig,
+    input: mpsc::Receiver<VerifySignatureRequest>,
+    handle: Handle,
+) -> JoinHandle<()> {
+    let (handle, _queue_depth) = run_sign_checker(
+        client,
+        input,
+        config,
+        None,
+        Some(handle),
+        ShutdownToken::never(),
+    );
+    handle
+}
+
+/// Replaces the Ethereum client of the signature checker running in the same process,
+/// e.g. to switch to another node via the admin API while the current one is unhealthy.
+///
+/// Requests that are already being checked finish with the client they started with.
+#[derive(Debug, Clone)]
+pub struct EthClientSwitch {
+    sender: Arc<watch::Sender<EthereumGateway>>,
+    eth_client_config: ETHClientConfig,
+    eth_sender_config: ETHSenderConfig,
+    main_contract: Address,
+}
+
+impl EthClientSwitch {
+    /// Creates the switch of the client updates consumed by the checker. The configs
+    /// are used to create the new clients, except for the addresses of the nodes.
+    pub fn new(
+        sender: watch::Sender<EthereumGateway>,
+        eth_client_config: ETHClientConfig,
+        eth_sender_config: ETHSenderConfig,
+        main_contract: Address,
+    ) -> Self {
+        Self {
+            sender: Arc::new(sender),
+            eth_client_config,
+            eth_sender_config,
+            main_contract,
+        }
+    }
+
+    /// Switches the checker to the nodes with the given addresses, the requests are
+    /// multiplexed if there are several of them.
+    pub fn switch(&self, web3_url: Vec<String>) -> Result<(), String> {
+        if web3_url.is_empty() {
+            return Err("at least one node address is required".to_owned());
+        }
+        for url in &web3_url {
+            web3::transports::Http::new(url)
+                .map_err(|err| format!("invalid node address {}: {}", url, err))?;
+        }
+
+        let eth_client_config = ETHClientConfig {
+            web3_url,
+            ..self.eth_client_config.clone()
+        };
+        let client = EthereumGateway::from_config(
+            &eth_client_config,
+            &self.eth_sender_config,
+            self.main_contract,
+        );
+        self.sender
+            .send(client)
+            .map_err(|_| "signature checker is stopped".to_owned())?;
+        vlog::info!(
+            "Signature checker is switched to the Ethereum nodes {:?}",
+            eth_client_config.web3_url
+        );
+        Ok(())
+    }
+}
+
+/// Periodically checks whether the Ethereum node used by the checker is reachable
+/// and logs the result.
+async fn log_eth_health(client: watch::Receiver<EthereumGateway>, interval: Duration) {
+    let mut timer = tokio::time::interval(interval);
+    loop {
+        timer.tick().await;
+        let eth_checker = EthereumChecker::new(client.borrow().clone());
+        let health = eth_checker.node_health().await;
+        metrics::gauge!(
+            "signature_checker.eth_node_healthy",
+            if health.is_healthy() { 1.0 } else { 0.0 }
+        );
+        match health.latest_block {
+            Some(block) => vlog::debug!(
+                "Ethereum node is healthy: latest block {}, answered in {:?}",
+                block,
+                health.latency
+            ),
+            None => vlog::warn!(
+                "Ethereum node is unreachable, failed in {:?}",
+                health.latency
+            ),
+        }
+    }
+}
+
+fn run_sign_checker(
+    client: watch::Receiver<EthereumGateway>,
+    input: mpsc::Receiver<VerifySignatureRequest>,
+    config: SignatureCheckerConfig,
+    panic_notify: Option<mpsc::Sender<bool>>,
+    runtime: Option<Handle>,
+    shutdown: ShutdownToken,
+) -> (JoinHandle<()>, watch::Receiver<usize>) {
+    let (queue_depth_sender, queue_depth_receiver) = watch::channel(0);
+    let num_threads = config.num_threads.unwrap_or_else(num_cpus::get);
+    let eth_health_log_interval = config.eth_health_log_interval;
+    let context = Arc::new(CheckerContext::new(
+        config,
+        client.borrow().clone(),
+        QueueDepth::new(queue_depth_sender),
+    ));
+    let input = Arc::new(Mutex::new(input));
+    if let Some(circuit_breaker) = &context.circuit_breaker {
+        let probe = circuit_breaker
+            .clone()
+            .run_probe(client.clone(), shutdown.clone());
+        match &runtime {
+            Some(runtime) => runtime.spawn(probe),
+            None => tokio::spawn(probe),
+        };
+    }
+    if let Some(interval) = eth_health_log_interval {
+        let health_log = log_eth_health(client.clone(), interval);
+        match &runtime {
+            Some(runtime) => runtime.spawn(health_log),
+            None => tokio::spawn(health_log),
+        };
+    }
+
+    // Tasks spawned on a shared runtime are distributed over its workers,
+    // so a single routine is enough.
+    if let Some(runtime) = runtime {
+        let handle = runtime.spawn(checker_routine(input, client, context, shutdown));
+        return (handle, queue_depth_receiver);
+    }
+
+    let threads: Vec<_> = (0..num_threads)
+        .map(|thread_id| {
+            let input = input.clone();
+            let client = client.clone();
+            let context = context.clone();
+            let panic_notify = panic_notify.clone();
+            let shutdown = shutdown.clone();
+            std::thread::Builder::new()
+                .name(format!("sign-checker-{}", thread_id))
+                .spawn(move || {
+                    let _panic_sentinel = panic_notify.map(ThreadPanicNotify);
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("Unable to build runtime for a signature checker");
+                    runtime.block_on(checker_routine(input, client, context, shutdown));
+                })
+                .expect("Failed to spawn signature checker thread")
+        })
+        .collect();
+
+    let handle = tokio::task::spawn_blocking(move || {
+        for thread in threads {
+            thread.join().expect("Signature checker thread panicked");
+        }
+    });
+    (handle, queue_depth_receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::SinkExt;
+    use zksync_config::configs::api::TxScreeningMode;
+    use zksync_eth_client::clients::mock::MockEthereum;
+    use zksync_test_account::{ZkSyncAccount, ZkSyncETHAccountData};
+    use zksync_types::{
+        tx::{
+            error::{WRONG_SIGNATURE, WRONG_TO_ADDRESS},
+            ChangePubKey, ChangePubKeyCREATE2Data, ChangePubKeyType, EIP1271Signature,
+            EIP712Signature, EthSigner, MessageVersion, PackedEthSignature, TimeRange,
+            TransactionError, TxHash,
+        },
+        AccountId, Nonce, PubKeyHash, TokenId, TokenKind,
+    };
+
+    use super::*;
+    use crate::eth_checker::EIP1271_SUCCESS_RETURN_VALUE;
+    use zksync_utils::shutdown::Shutdown;
+
+    /// Creates an Ethereum client which approves every EIP1271 signature after the given delay.
+    async fn slow_eth_client(delay: Duration) -> EthereumGateway {
+        let mut client = MockEthereum::default();
+        client.set_call_delay(delay);
+        client
+            .add_call_result(
+                "isValidSignature",
+                vec![ethabi::Token::FixedBytes(
+                    EIP1271_SUCCESS_RETURN_VALUE.to_vec(),
+                )],
+            )
+            .await;
+        EthereumGateway::Mock(client)
+    }
+
+    /// Creates a channel of the Ethereum client updates initialized with the given client.
+    fn eth_client_updates(
+        client: EthereumGateway,
+    ) -> (
+        watch::Sender<EthereumGateway>,
+        watch::Receiver<EthereumGateway>,
+    ) {
+        watch::channel(client)
+    }
+
+    pub(super) fn eth_token() -> Token {
+        Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20)
+    }
+
+    /// Creates a transfer from the given account signed with both zkSync and Ethereum keys.
+    pub(super) fn signed_transfer(account: &ZkSyncAccount) -> SignedZkSyncTx {
+        signed_transfer_with_time_range(account, Default::default())
+    }
+
+    fn signed_transfer_with_time_range(
+        account: &ZkSyncAccount,
+        time_range: TimeRange,
+    ) -> SignedZkSyncTx {
+        let (transfer, eth_signature) = account.sign_transfer(
+            TokenId(0),
+            "ETH",
+            100u64.into(),
+            10u64.into(),
+            &Address::repeat_byte(0x02),
+            None,
+            true,
+            time_range,
+        );
+        let tx = ZkSyncTx::Transfer(Box::new(transfer));
+        let message = tx.get_ethereum_sign_message(eth_token()).unwrap();
+        SignedZkSyncTx {
+            tx,
+            eth_sign_data: Some(EthSignData {
+                signature: TxEthSignature::EthereumSignature(eth_signature.unwrap()),
+                message: SignedMessage::Text(message),
+            }),
+            created_at: chrono::Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        }
+    }
+
+    pub(super) fn test_account(id: u32) -> ZkSyncAccount {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(id)));
+        account
+    }
+
+    fn batch_request(txs: Vec<SignedZkSyncTx>, senders: Vec<Address>) -> RequestData {
+        let tokens = vec![eth_token(); txs.len()];
+        RequestData::Batch(BatchRequest {
+            txs,
+            batch_sign_data: None,
+            senders,
+            tokens,
+            parties: Vec::new(),
+        })
+    }
+
+    fn eip1271_request() -> (
+        VerifySignatureRequest,
+        oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
+    ) {
+        VerifySignatureRequest::new(RequestData::Toggle2FA(Toggle2FARequest {
+            sign_data: EthSignData {
+                signature: TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65])),
+                message: "hello-world".into(),
+            },
+            sender: Address::repeat_byte(0x01),
+        }))
+    }
+
+    #[tokio::test]
+    async fn queue_depth_tracks_in_flight_requests() {
+        let (_, eth_client) = eth_client_updates(slow_eth_client(Duration::from_millis(500)).await);
+        let (mut sender, input) = mpsc::channel(10);
+        let (queue_depth_sender, mut queue_depth) = watch::channel(0);
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: 1,
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+
+        let mut responses = Vec::new();
+        for _ in 0..3 {
+            let (request, response) = eip1271_request();
+            sender.send(request).await.unwrap();
+            responses.push(response);
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while *queue_depth.borrow() != 3 {
+                queue_depth.changed().await.unwrap();
+            }
+        })
+        .await
+        .expect("Queue depth didn't reach the number of in-flight requests");
+
+        for response in responses {
+            response.await.unwrap().expect("Signature must be correct");
+        }
+        assert_eq!(*queue_depth.borrow(), 0);
+    }
+
+    /// Checks that the check is abandoned once the response receiver is dropped.
+    #[tokio::test]
+    async fn dropped_receivers_are_skipped() {
+        let (_, eth_client) = eth_client_updates(slow_eth_client(Duration::from_secs(10)).await);
+        let (mut sender, input) = mpsc::channel(10);
+        let (queue_depth_sender, mut queue_depth) = watch::channel(0);
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: 1,
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while *queue_depth.borrow() != 1 {
+                queue_depth.changed().await.unwrap();
+            }
+        })
+        .await
+        .expect("Request wasn't taken by the checker");
+
+        // Ethereum node would answer in 10 seconds, but nobody waits for it.
+        drop(response);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while *queue_depth.borrow() != 0 {
+                queue_depth.changed().await.unwrap();
+            }
+        })
+        .await
+        .expect("Check wasn't abandoned after the receiver was dropped");
+    }
+
+    /// Checks that requests are abandoned after their deadline.
+    #[tokio::test]
+    async fn request_deadline() {
+        let (_, eth_client) = eth_client_updates(slow_eth_client(Duration::from_secs(10)).await);
+        let (mut sender, input) = mpsc::channel(10);
+        let (queue_depth_sender, _) = watch::channel(0);
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: 1,
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+
+        let deadlines = vec![Instant::now(), Instant::now() + Duration::from_millis(100)];
+        for deadline in deadlines {
+            let (mut request, response) = eip1271_request();
+            request.deadline = Some(deadline);
+            sender.send(request).await.unwrap();
+            let result = tokio::time::timeout(Duration::from_secs(5), response)
+                .await
+                .expect("Request wasn't abandoned after the deadline")
+                .unwrap();
+            assert!(matches!(result, Err(TxAddError::SignatureCheckTimeout)));
+        }
+    }
+
+    /// Checks that an empty batch is rejected before its signature is checked.
+    #[tokio::test]
+    async fn empty_batch() {
+        // Mock client has no `isValidSignature` result, so the EIP1271 check would fail.
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let request = RequestData::Batch(BatchRequest {
+            txs: Vec::new(),
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![TxEthSignature::EIP1271Signature(EIP1271Signature(vec![
+                    0;
+                    65
+                ]))],
+                message: SignedMessage::default(),
+            }),
+            senders: Vec::new(),
+            tokens: Vec::new(),
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::EmptyBatch)));
+    }
+
+    /// Checks that batches paying fees in different tokens are rejected before
+    /// their signatures are checked.
+    #[tokio::test]
+    async fn batch_fee_token_consistency() {
+        let (alice, bob) = (test_account(1), test_account(2));
+        let single = vec![signed_transfer(&alice)];
+        assert!(verify_batch_fee_token_consistency(&single).is_ok());
+        let matching = vec![signed_transfer(&alice), signed_transfer(&bob)];
+        assert!(verify_batch_fee_token_consistency(&matching).is_ok());
+
+        // The token of the zero fee is ignored.
+        let mut zero_fee = matching.clone();
+        if let ZkSyncTx::Transfer(transfer) = &mut zero_fee[1].tx {
+            transfer.token = TokenId(1);
+            transfer.fee = BigUint::zero();
+        }
+        assert!(matches!(
+            verify_batch_fee_token_consistency(&zero_fee),
+            Ok(Some(TokenId(0)))
+        ));
+        assert!(matches!(
+            verify_batch_fee_token_consistency(&zero_fee[1..]),
+            Ok(None)
+        ));
+
+        let mut mismatching = matching;
+        mismatching.push(signed_transfer(&alice));
+        if let ZkSyncTx::Transfer(transfer) = &mut mismatching[1].tx {
+            transfer.token = TokenId(1);
+        }
+        assert!(matches!(
+            verify_batch_fee_token_consistency(&mismatching),
+            Err(TxAddError::BatchFeeTokenMismatch(TokenId(0), TokenId(1)))
+        ));
+
+        // Mock client has no `isValidSignature` result, so the EIP1271 check would fail.
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let request = RequestData::Batch(BatchRequest {
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![TxEthSignature::EIP1271Signature(EIP1271Signature(vec![
+                    0;
+                    65
+                ]))],
+                message: SignedMessage::default(),
+            }),
+            senders: vec![alice.address, bob.address, alice.address],
+            tokens: vec![eth_token(); 3],
+            txs: mismatching,
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::BatchFeeTokenMismatch(TokenId(0), TokenId(1)))
+        ));
+    }
+
+    /// Checks that batches paying less than the minimum total fee are rejected by the checker
+    /// before their signatures are checked.
+    #[tokio::test]
+    async fn batch_total_fee() {
+        let (alice, bob) = (test_account(1), test_account(2));
+        // Every transfer pays 10 in fees.
+        let txs = vec![signed_transfer(&alice), signed_transfer(&bob)];
+        let request = || batch_request(txs.clone(), vec![alice.address, bob.address]);
+
+        let min_fees = |token: u32, fee: u32| {
+            let mut min_fees = HashMap::new();
+            min_fees.insert(TokenId(token), BigUint::from(fee));
+            min_fees
+        };
+        assert!(verify_batch_total_fee(&request(), &HashMap::new()).is_ok());
+        assert!(verify_batch_total_fee(&request(), &min_fees(0, 20)).is_ok());
+        assert!(matches!(
+            verify_batch_total_fee(&request(), &min_fees(0, 21)),
+            Err(TxAddError::TxBatchFeeTooLow)
+        ));
+        // Batches paying in the other tokens are not affected.
+        assert!(verify_batch_total_fee(&request(), &min_fees(1, 21)).is_ok());
+        // Single transactions are not affected.
+        let single = RequestData::Tx(TxRequest::new(txs[0].clone(), alice.address, eth_token()));
+        assert!(verify_batch_total_fee(&single, &min_fees(0, 21)).is_ok());
+
+        // Batch without fees is checked in the token of its first transaction.
+        let mut free_txs = txs.clone();
+        for tx in &mut free_txs {
+            if let ZkSyncTx::Transfer(transfer) = &mut tx.tx {
+                transfer.fee = BigUint::zero();
+            }
+        }
+        let free_batch = batch_request(free_txs, vec![alice.address, bob.address]);
+        assert!(matches!(
+            verify_batch_total_fee(&free_batch, &min_fees(0, 1)),
+            Err(TxAddError::TxBatchFeeTooLow)
+        ));
+
+        // Mock client has no `isValidSignature` result, so the EIP1271 check would fail.
+        let (_client_sender, client) =
+            watch::channel(EthereumGateway::Mock(MockEthereum::default()));
+        let (mut sender, input) = mpsc::channel(1);
+        let handle = start_sign_checker_on_handle(
+            client,
+            SignatureCheckerConfig {
+                min_batch_total_fees: min_fees(0, 21),
+                ..Default::default()
+            },
+            input,
+            Handle::current(),
+        );
+        let eip1271_batch = RequestData::Batch(BatchRequest {
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![TxEthSignature::EIP1271Signature(EIP1271Signature(vec![
+                    0;
+                    65
+                ]))],
+                message: SignedMessage::default(),
+            }),
+            txs,
+            senders: vec![alice.address, bob.address],
+            tokens: vec![eth_token(); 2],
+            parties: Vec::new(),
+        });
+        let (request, response) = VerifySignatureRequest::new(eip1271_batch);
+        sender.send(request).await.unwrap();
+        assert!(matches!(
+            response.await.unwrap(),
+            Err(TxAddError::TxBatchFeeTooLow)
+        ));
+        drop(sender);
+        handle.await.unwrap();
+    }
+
+    /// Checks that the Ethereum signature of `ForcedExit` is expected from its initiator
+    /// rather than from the target account.
+    #[tokio::test]
+    async fn forced_exit_signed_by_initiator() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (initiator, target) = (test_account(1), test_account(2));
+        let forced_exit = initiator.sign_forced_exit(
+            TokenId(0),
+            10u64.into(),
+            &target.address,
+            None,
+            true,
+            Default::default(),
+        );
+        let tx = ZkSyncTx::ForcedExit(Box::new(forced_exit));
+        assert_eq!(tx.eth_signer(), EthSigner::AccountId(AccountId(1)));
+        assert_eq!(tx.account(), target.address);
+
+        let message = tx.get_ethereum_sign_message(eth_token()).unwrap();
+        let signature = PackedEthSignature::sign(
+            initiator.try_get_eth_private_key().unwrap(),
+            message.as_bytes(),
+        )
+        .unwrap();
+        let tx = SignedZkSyncTx {
+            eth_sign_data: Some(EthSignData {
+                signature: TxEthSignature::EthereumSignature(signature),
+                message: SignedMessage::Text(message),
+            }),
+            ..SignedZkSyncTx::from(tx)
+        };
+        let (eth_checker, window, exemptions) = (
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        );
+        let verify = |sender| {
+            let request = RequestData::Tx(TxRequest::new(tx.clone(), sender, eth_token()));
+            VerifiedTx::verify(request, eth_checker, window, exemptions)
+        };
+
+        verify(initiator.address)
+            .await
+            .expect("ForcedExit signed by the initiator must be accepted");
+        let result = verify(target.address).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == target.address && recovered == initiator.address
+        ));
+    }
+
+    /// Checks that `ForcedExit` signed by the configured operator is accepted, while the one
+    /// signed by another key is not.
+    #[tokio::test]
+    async fn forced_exit_signed_by_operator() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (operator, initiator, stranger) = (test_account(1), test_account(2), test_account(3));
+        let forced_exit = operator.sign_forced_exit(
+            TokenId(0),
+            10u64.into(),
+            &Address::repeat_byte(0x0f),
+            None,
+            true,
+            Default::default(),
+        );
+        let tx = ZkSyncTx::ForcedExit(Box::new(forced_exit));
+        let message = tx.get_ethereum_sign_message(eth_token()).unwrap();
+        let signed_by = |account: &ZkSyncAccount| {
+            let signature = PackedEthSignature::sign(
+                account.try_get_eth_private_key().unwrap(),
+                message.as_bytes(),
+            )
+            .unwrap();
+            SignedZkSyncTx {
+                eth_sign_data: Some(EthSignData {
+                    signature: TxEthSignature::EthereumSignature(signature),
+                    message: SignedMessage::Text(message.clone()),
+                }),
+                ..SignedZkSyncTx::from(tx.clone())
+            }
+        };
+        let (eth_checker, window) = (&eth_checker, &ValidFromWindow::default());
+        let exemptions =
+            EthSignatureExemptions::default().with_forced_exit_operator(Some(operator.address));
+        let sender = initiator.address;
+        let verify = |tx, exemptions: EthSignatureExemptions| async move {
+            let request = RequestData::Tx(TxRequest::new(tx, sender, eth_token()));
+            VerifiedTx::verify(request, eth_checker, window, &exemptions).await
+        };
+
+        verify(signed_by(&operator), exemptions.clone())
+            .await
+            .expect("ForcedExit signed by the operator must be accepted");
+        verify(signed_by(&initiator), exemptions.clone())
+            .await
+            .expect("ForcedExit signed by the initiator must be accepted");
+        // The error for the initiator is reported.
+        let result = verify(signed_by(&stranger), exemptions).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == initiator.address && recovered == stranger.address
+        ));
+        // Operator signature is not accepted unless the operator is configured.
+        let result = verify(signed_by(&operator), EthSignatureExemptions::default()).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { recovered, .. }) if recovered == operator.address
+        ));
+    }
+
+    /// Checks that the signer is recovered from both the text and the binary messages,
+    /// and that the text is not confused with the binary data it represents.
+    #[tokio::test]
+    async fn text_and_bytes_signed_messages() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let account = test_account(1);
+        let private_key = account.try_get_eth_private_key().unwrap();
+        let tx = signed_transfer(&account);
+        let text = tx.tx.get_ethereum_sign_message(eth_token()).unwrap();
+        let hash = tiny_keccak::keccak256(text.as_bytes()).to_vec();
+        let request = |signed: &[u8], message: SignedMessage| {
+            let signature = PackedEthSignature::sign(private_key, signed).unwrap();
+            RequestData::Tx(TxRequest::new(
+                SignedZkSyncTx {
+                    eth_sign_data: Some(EthSignData {
+                        signature: TxEthSignature::EthereumSignature(signature),
+                        message,
+                    }),
+                    ..tx.clone()
+                },
+                account.address,
+                eth_token(),
+            ))
+        };
+        let (eth_checker, window, exemptions) = (
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        );
+        let verify = move |request| VerifiedTx::verify(request, eth_checker, window, exemptions);
+
+        verify(request(text.as_bytes(), SignedMessage::Text(text.clone())))
+            .await
+            .expect("Text-signed message must be accepted");
+        verify(request(&hash, SignedMessage::Bytes(hash.clone())))
+            .await
+            .expect("Bytes-signed message must be accepted");
+
+        // Wallet has signed the hex-encoded hash as a text.
+        let hex_hash = format!("0x{}", hex::encode(&hash));
+        let result = verify(request(hex_hash.as_bytes(), SignedMessage::Bytes(hash))).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == account.address && recovered != account.address
+        ));
+        verify(request(
+            hex_hash.as_bytes(),
+            SignedMessage::Text(hex_hash.clone()),
+        ))
+        .await
+        .expect("Hex-encoded hash signed as a text must be accepted");
+    }
+
+    /// Checks the reasons reported for the batch signatures not matching the senders.
+    #[tokio::test]
+    async fn batch_signature_failures() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (alice, bob, carol) = (test_account(1), test_account(2), test_account(3));
+        let txs = vec![signed_transfer(&carol)];
+        let message = b"batch message".to_vec();
+        let signature = |account: &ZkSyncAccount| {
+            TxEthSignature::EthereumSignature(
+                PackedEthSignature::sign(account.try_get_eth_private_key().unwrap(), &message)
+                    .unwrap(),
+            )
+        };
+        let request = |signatures| {
+            RequestData::Batch(BatchRequest {
+                txs: txs.clone(),
+                batch_sign_data: Some(EthBatchSignData {
+                    signatures,
+                    message: message.clone().into(),
+                }),
+                senders: vec![carol.address],
+                tokens: vec![eth_token()],
+                parties: Vec::new(),
+            })
+        };
+        let (valid_from_window, exemptions) = (
+            ValidFromWindow::default(),
+            EthSignatureExemptions::default(),
+        );
+        let verify =
+            |request| VerifiedTx::verify(request, &eth_checker, &valid_from_window, &exemptions);
+
+        let result = verify(request(Vec::new())).await;
+        assert!(matches!(result, Err(TxAddError::MissingEthSignature)));
+
+        // The only signature is made by another account.
+        let result = verify(request(vec![signature(&alice)])).await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == carol.address && recovered == alice.address
+        ));
+
+        // None of the several signatures is made by the sender.
+        let result = verify(request(vec![signature(&alice), signature(&bob)])).await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::IncorrectEthSignature { recovered, expected, .. })
+                if recovered == Some(alice.address) && expected == carol.address
+        ));
+    }
+
+    /// Checks that the time of both verification phases is reported.
+    #[tokio::test]
+    async fn verification_timings() {
+        const NODE_DELAY: Duration = Duration::from_millis(50);
+
+        let eth_checker = EthereumChecker::new(slow_eth_client(NODE_DELAY).await);
+        let account = test_account(1);
+        // Smart contract wallet signature is checked by the (slow) Ethereum node.
+        let mut tx = signed_transfer(&account);
+        tx.eth_sign_data.as_mut().unwrap().signature =
+            TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+        let verified = VerifiedTx::verify(
+            RequestData::Tx(TxRequest::new(tx, account.address, eth_token())),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Transaction must be verified");
+
+        let timings = verified.timings().expect("Timings must be reported");
+        assert!(timings.eth_signature >= NODE_DELAY);
+        assert!(timings.tx_correctness > Duration::from_secs(0));
+        assert!(VerifiedTx::unverified(verified.inner).timings().is_none());
+    }
+
+    /// Checks that a batch without the batch signature is accepted if every
+    /// transaction carries its own Ethereum signature.
+    #[tokio::test]
+    async fn batch_with_individual_signatures_only() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (alice, bob) = (test_account(1), test_account(2));
+        let txs = vec![
+            signed_transfer(&alice),
+            signed_transfer(&bob),
+            signed_transfer(&alice),
+        ];
+        let senders = vec![alice.address, bob.address, alice.address];
+
+        let (verified_txs, batch_sign_data) = verify_tx_signature(
+            batch_request(txs.clone(), senders),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Batch must be verified")
+        .unwrap_batch();
+        assert!(batch_sign_data.is_none());
+        assert_eq!(verified_txs.len(), txs.len());
+        // There is no batch signature to tie the transactions to.
+        assert!(verified_txs.iter().all(|tx| tx.batch_hash.is_none()));
+
+        // Individual signatures are still checked to belong to the transaction sender.
+        let senders = vec![alice.address, alice.address, alice.address];
+        let result = verify_tx_signature(
+            batch_request(txs, senders),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == alice.address && recovered == bob.address
+        ));
+    }
+
+    /// Checks that the failure of the lowest-index transaction is reported for the batch,
+    /// even if the failed checks are made in a different order.
+    #[tokio::test]
+    async fn lowest_index_batch_failure() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (alice, bob) = (test_account(1), test_account(2));
+
+        // Both the sequential and the parallel correctness checks are covered.
+        for batch_size in vec![4, PARALLEL_CORRECTNESS_CHECK_THRESHOLD] {
+            let mut txs: Vec<_> = (0..batch_size).map(|_| signed_transfer(&alice)).collect();
+            // Transaction #1 has the incorrect zkSync signature.
+            if let ZkSyncTx::Transfer(transfer) = &mut txs[1].tx {
+                transfer.amount += 1u64;
+            }
+            // Transaction #3 is signed by another Ethereum account. Ethereum signatures
+            // are checked before the transactions correctness.
+            txs[3].eth_sign_data = signed_transfer(&bob).eth_sign_data;
+            let failed_tx_hash = txs[1].hash();
+
+            let err = verify_tx_signature(
+                batch_request(txs, vec![alice.address; batch_size]),
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await
+            .err()
+            .expect("Batch must be rejected");
+            // Rejected transaction is pointed to, so the client knows which one to fix.
+            assert_eq!(err.batch_tx_location(), Some((1, failed_tx_hash)));
+            assert!(matches!(
+                err.into_reason(),
+                TxAddError::IncorrectTx {
+                    account,
+                    reason: reason @ TransactionError::TransferError(_),
+                } if account == alice.address && reason.to_string() == WRONG_SIGNATURE
+            ));
+        }
+    }
+
+    /// Checks that the EIP1271 signatures of the batch transactions are checked concurrently.
+    #[tokio::test]
+    async fn batch_eip1271_signatures_are_checked_concurrently() {
+        const CALL_DELAY: Duration = Duration::from_millis(200);
+        const BATCH_SIZE: usize = 2 * BATCH_TX_SIGNATURES_CONCURRENCY;
+
+        let eth_checker = EthereumChecker::new(slow_eth_client(CALL_DELAY).await);
+        let account = test_account(1);
+        let wallet = Address::repeat_byte(0x0a);
+        let txs: Vec<_> = (0..BATCH_SIZE)
+            .map(|_| {
+                let mut tx = signed_transfer(&account);
+                let signature = TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+                tx.eth_sign_data.as_mut().unwrap().signature = signature;
+                tx
+            })
+            .collect();
+        let request = BatchRequest {
+            txs,
+            batch_sign_data: None,
+            senders: vec![wallet; BATCH_SIZE],
+            tokens: vec![eth_token(); BATCH_SIZE],
+            parties: Vec::new(),
+        };
+
+        let start = Instant::now();
+        verify_eth_signature_batch(&request, &eth_checker, &EthSignatureExemptions::default())
+            .await
+            .expect("Batch signatures must be verified");
+        // Sequential checks would take `BATCH_SIZE` call delays.
+        assert!(start.elapsed() < CALL_DELAY * (BATCH_SIZE as u32 / 2));
+    }
+
+    /// Checks that the requester gets an error instead of waiting forever if the check panics.
+    #[tokio::test]
+    async fn panicked_check_is_reported() {
+        let client = MockEthereum::default();
+        client.set_panicking();
+        let (_client_sender, client) = eth_client_updates(EthereumGateway::Mock(client));
+        let (mut sender, input) = mpsc::channel(1);
+        let handle = start_sign_checker_on_handle(
+            client,
+            SignatureCheckerConfig::default(),
+            input,
+            Handle::current(),
+        );
+
+        // Check of the EIP1271 signature calls the panicking Ethereum client.
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), response)
+            .await
+            .expect("Response must be sent")
+            .unwrap();
+        assert!(matches!(result, Err(TxAddError::Other)));
+
+        // Checker keeps serving the requests.
+        let account = test_account(1);
+        let (request, response) = VerifySignatureRequest::new(RequestData::Tx(TxRequest::new(
+            signed_transfer(&account),
+            account.address,
+            eth_token(),
+        )));
+        sender.send(request).await.unwrap();
+        response
+            .await
+            .unwrap()
+            .expect("Transaction must be verified");
+        drop(sender);
+        handle.await.unwrap();
+    }
+
+    /// Checks that the transaction signed as the EIP-712 typed data is accepted
+    /// for the configured chain only.
+    #[tokio::test]
+    async fn eip712_signature() {
+        let account = test_account(1);
+        let domain = Eip712Domain::new(1);
+        let request = |domain: &Eip712Domain| {
+            let mut tx = signed_transfer(&account);
+            let eth_sign_data = tx.eth_sign_data.as_mut().unwrap();
+            let message = String::from_utf8(eth_sign_data.message.to_vec()).unwrap();
+            let signature =
+                EIP712Signature::sign(&account.eth_account_data.unwrap_eoa_pk(), domain, &message)
+                    .unwrap();
+            eth_sign_data.signature = TxEthSignature::EIP712Signature(signature);
+            RequestData::Tx(TxRequest::new(tx, account.address, eth_token()))
+        };
+        let verify = |request: RequestData, eth_checker: EthereumChecker| async move {
+            verify_tx_signature(
+                request,
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await
+        };
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+
+        verify(
+            request(&domain),
+            eth_checker.clone().with_eip712_domain(domain),
+        )
+        .await
+        .expect("EIP712 signature must be accepted");
+
+        // Signature for another chain.
+        let result = verify(
+            request(&Eip712Domain::new(4)),
+            eth_checker.clone().with_eip712_domain(domain),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, .. }) if expected == account.address
+        ));
+
+        // EIP712 signatures are not accepted unless the domain is configured.
+        let result = verify(request(&domain), eth_checker).await;
+        assert!(matches!(result, Err(TxAddError::UnsupportedSignatureType)));
+    }
+
+    /// Checks that the signature of the chain-bound message is accepted for the configured
+    /// chain only, and the unbound messages are accepted unless disabled.
+    #[tokio::test]
+    async fn chain_bound_messages() {
+        let account = test_account(1);
+        let request = |version: MessageVersion, chain_id: u64| {
+            let mut tx = signed_transfer(&account);
+            let eth_sign_data = tx.eth_sign_data.as_mut().unwrap();
+            let message = eth_sign_data.message.in_version(version, chain_id).unwrap();
+            let signature = PackedEthSignature::sign(
+                &account.eth_account_data.unwrap_eoa_pk(),
+                message.as_bytes(),
+            )
+            .unwrap();
+            eth_sign_data.signature = TxEthSignature::EthereumSignature(signature);
+            RequestData::Tx(TxRequest::new(tx, account.address, eth_token()))
+        };
+        let verify = |request: RequestData, accept_unbound: bool| async move {
+            let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()))
+                .with_chain_binding(ChainBinding {
+                    chain_id: 1,
+                    accept_unbound,
+                });
+            verify_tx_signature(
+                request,
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await
+        };
+
+        verify(request(MessageVersion::ChainBound, 1), false)
+            .await
+            .expect("Chain-bound message must be accepted");
+        verify(request(MessageVersion::Unbound, 1), true)
+            .await
+            .expect("Unbound message must be accepted");
+
+        // Message bound to another chain.
+        let result = verify(request(MessageVersion::ChainBound, 4), true).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, .. }) if expected == account.address
+        ));
+        // Unbound messages are disabled.
+        let result = verify(request(MessageVersion::Unbound, 1), false).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, .. }) if expected == account.address
+        ));
+    }
+
+    /// Checks that the signatures of the messages signed before the time ranges were included
+    /// into them are accepted unless disabled, for both the transactions and the swap orders.
+    #[tokio::test]
+    async fn untimed_messages() {
+        let account = test_account(1);
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let sign = |message: &SignedMessage| {
+            let private_key = account.eth_account_data.unwrap_eoa_pk();
+            let signature = PackedEthSignature::sign(&private_key, message.as_bytes()).unwrap();
+            TxEthSignature::EthereumSignature(signature)
+        };
+
+        let mut tx = signed_transfer_with_time_range(&account, TimeRange::new(1_000, 2_000));
+        let eth_sign_data = tx.eth_sign_data.as_mut().unwrap();
+        let untimed_message = eth_sign_data.message.without_time_range().unwrap();
+        eth_sign_data.signature = sign(&untimed_message);
+        let verify = |tx: SignedZkSyncTx, eth_checker: EthereumChecker| async move {
+            verify_tx_signature(
+                RequestData::Tx(TxRequest::new(tx, account.address, eth_token())),
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await
+        };
+        verify(tx.clone(), eth_checker.clone().with_untimed_messages(true))
+            .await
+            .expect("Untimed message must be accepted");
+        let result = verify(tx, eth_checker.clone()).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, .. }) if expected == account.address
+        ));
+
+        // Nonce of the order follows its time range, so it's kept in the untimed message.
+        let order = account.sign_order(
+            TokenId(0),
+            TokenId(1),
+            1u64.into(),
+            1u64.into(),
+            100u64.into(),
+            &account.address,
+            None,
+            true,
+            TimeRange::new(1_000, 2_000),
+        );
+        let message = SignedMessage::Text(order.get_ethereum_sign_message("ETH", "TKN", 18));
+        let untimed_message = message.without_time_range().unwrap();
+        let nonce_line = format!("\nNonce: {}", order.nonce);
+        assert!(untimed_message.as_bytes().ends_with(nonce_line.as_bytes()));
+        let signature = sign(&untimed_message);
+        verify_ethereum_signature(
+            &signature,
+            &message,
+            account.address,
+            &eth_checker.clone().with_untimed_messages(true),
+        )
+        .await
+        .expect("Untimed order message must be accepted");
+        let result =
+            verify_ethereum_signature(&signature, &message, account.address, &eth_checker).await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, .. }) if expected == account.address
+        ));
+    }
+
+    /// Checks that the repeated request with the bad signature is rejected from the cache,
+    /// while the rejected EIP1271 signature and the missing `ChangePubKey` authorization
+    /// are checked every time.
+    #[tokio::test]
+    async fn rejections_are_cached() {
+        async fn check(
+            data: RequestData,
+            eth_checker: &EthereumChecker,
+            rejection_cache: &RejectionCache,
+        ) -> Result<VerifiedTx, TxAddError> {
+            let context = CheckerContext {
+                rejection_cache: Some(rejection_cache.clone()),
+                ..CheckerContext::new(
+                    SignatureCheckerConfig::default(),
+                    EthereumGateway::Mock(MockEthereum::default()),
+                    QueueDepth::new(watch::channel(0).0),
+                )
+            };
+            let (response, receiver) = oneshot::channel();
+            check_request(data, response, None, eth_checker, &context).await;
+            receiver.await.unwrap()
+        }
+
+        let rejection_cache = RejectionCache::new(10, Duration::from_secs(60));
+        let account = test_account(1);
+        // Any call to the node fails the check with `TxAddError::Other`.
+        let panicking_client = MockEthereum::default();
+        panicking_client.set_panicking();
+        let panicking_checker = EthereumChecker::new(EthereumGateway::Mock(panicking_client));
+
+        // Transfer signed by the account, but submitted on behalf of another one.
+        let mismatching_transfer = || {
+            let tx = signed_transfer(&account);
+            RequestData::Tx(TxRequest::new(tx, test_account(2).address, eth_token()))
+        };
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let result = check(mismatching_transfer(), &eth_checker, &rejection_cache).await;
+        assert!(matches!(result, Err(TxAddError::SignerMismatch { .. })));
+        let key = RejectionCache::key(&mismatching_transfer()).unwrap();
+        assert!(matches!(
+            rejection_cache.get(key),
+            Some(TxAddError::SignerMismatch { .. })
+        ));
+
+        // The cached rejection expires.
+        let expiring_cache = RejectionCache::new(10, Duration::from_millis(0));
+        let result = check(mismatching_transfer(), &eth_checker, &expiring_cache).await;
+        assert!(matches!(result, Err(TxAddError::SignerMismatch { .. })));
+        assert!(expiring_cache.get(key).is_none());
+
+        // Mock client without the `isValidSignature` result rejects every EIP1271 signature.
+        // The result depends on the contract state, so the repeated request is checked
+        // once again.
+        let eip1271_transfer = || {
+            let mut tx = signed_transfer(&account);
+            let signature = TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+            tx.eth_sign_data.as_mut().unwrap().signature = signature;
+            RequestData::Tx(TxRequest::new(tx, account.address, eth_token()))
+        };
+        let result = check(eip1271_transfer(), &eth_checker, &rejection_cache).await;
+        assert!(matches!(result, Err(TxAddError::Eip1271Rejected)));
+        let result = check(eip1271_transfer(), &panicking_checker, &rejection_cache).await;
+        assert!(matches!(result, Err(TxAddError::Other)));
+
+        // Mock client has no `authFacts` result, so the onchain authorization is missing.
+        let rejection_cache = RejectionCache::new(10, Duration::from_secs(60));
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let auth_fact = tiny_keccak::keccak256(&change_pubkey.new_pk_hash.data[..]).to_vec();
+        let change_pubkey_request = || {
+            let tx = SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey.clone())));
+            RequestData::Tx(TxRequest::new(tx, account.address, eth_token()))
+        };
+        let result = check(change_pubkey_request(), &eth_checker, &rejection_cache).await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+
+        // Authorization appears onchain and the repeated request is checked once again.
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(auth_fact)])
+            .await;
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(client));
+        check(change_pubkey_request(), &eth_checker, &rejection_cache)
+            .await
+            .expect("Authorized ChangePubKey must be verified");
+    }
+
+    /// Checks that the internal error and the rejected EIP1271 signature of a batch
+    /// transaction are not cached, unlike its rejected ECDSA signature.
+    #[test]
+    fn batch_tx_internal_error_is_not_cached() {
+        let rejection_cache = RejectionCache::new(10, Duration::from_secs(60));
+        let internal_error =
+            TxAddError::batch_tx(1, TxHash::default(), TxAddError::EthereumNodeUnavailable);
+        rejection_cache.insert(H256::repeat_byte(0x01), internal_error);
+        assert!(rejection_cache.get(H256::repeat_byte(0x01)).is_none());
+
+        let rejected_signature = TxAddError::batch_tx(
+            1,
+            TxHash::default(),
+            TxAddError::SignerMismatch {
+                expected: Address::repeat_byte(0x01),
+                recovered: Address::repeat_byte(0x02),
+            },
+        );
+        assert!(is_signature_rejected(&rejected_signature));
+        rejection_cache.insert(H256::repeat_byte(0x02), rejected_signature);
+        assert!(matches!(
+            rejection_cache.get(H256::repeat_byte(0x02)),
+            Some(TxAddError::BatchTx { index: 1, .. })
+        ));
+
+        // Rejected EIP1271 signature depends on the contract state.
+        let rejected_eip1271 =
+            TxAddError::batch_tx(1, TxHash::default(), TxAddError::Eip1271Rejected);
+        rejection_cache.insert(H256::repeat_byte(0x03), rejected_eip1271);
+        assert!(rejection_cache.get(H256::repeat_byte(0x03)).is_none());
+    }
+
+    /// Checks that all the requests are processed when the channel is flooded
+    /// and the checker takes several requests per poll.
+    #[tokio::test]
+    async fn flooded_channel_is_drained() {
+        const REQUESTS: usize = 50;
+        const REQUESTS_PER_POLL: usize = 16;
+
+        let (_, eth_client) = eth_client_updates(slow_eth_client(Duration::from_millis(10)).await);
+        let (mut sender, mut input) = mpsc::channel(REQUESTS);
+
+        let mut responses = Vec::new();
+        for _ in 0..REQUESTS {
+            let (request, response) = eip1271_request();
+            sender.send(request).await.unwrap();
+            responses.push(response);
+        }
+
+        // More than one request is taken from the flooded channel at once.
+        let first = input.next().await.unwrap();
+        let drained = drain_requests(&mut input, first, REQUESTS_PER_POLL);
+        assert_eq!(drained.len(), REQUESTS_PER_POLL);
+        for request in drained {
+            sender.send(request).await.unwrap();
+        }
+
+        let (queue_depth_sender, _queue_depth) = watch::channel(0);
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: REQUESTS_PER_POLL,
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+
+        for response in responses {
+            response.await.unwrap().expect("Signature must be correct");
+        }
+    }
+
+    /// Checks that transactions with `valid_from` too far in the future are rejected,
+    /// while the ones within the acceptance window are verified as usual.
+    #[tokio::test]
+    async fn valid_from_acceptance_window() {
+        const NOW: u64 = 1_000_000;
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let window = ValidFromWindow::with_clock(Some(Duration::from_secs(60)), || NOW);
+        let account = test_account(1);
+
+        let tx_request = |valid_from| {
+            let tx =
+                signed_transfer_with_time_range(&account, TimeRange::new(valid_from, u64::MAX));
+            RequestData::Tx(TxRequest {
+                tx,
+                sender: account.address,
+                token: eth_token(),
+                parties: Vec::new(),
+            })
+        };
+
+        // Already valid and slightly future-dated transactions are accepted.
+        for valid_from in &[0, NOW, NOW + 60] {
+            VerifiedTx::verify(
+                tx_request(*valid_from),
+                &eth_checker,
+                &window,
+                &EthSignatureExemptions::default(),
+            )
+            .await
+            .expect("Transaction within the window must be accepted");
+        }
+
+        let result = VerifiedTx::verify(
+            tx_request(NOW + 61),
+            &eth_checker,
+            &window,
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::NotYetValid)));
+
+        // Without the window any `valid_from` is accepted.
+        VerifiedTx::verify(
+            tx_request(u64::MAX),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Transaction must be accepted without the window");
+    }
+
+    /// Checks that the exempt transactions are accepted without an Ethereum signature,
+    /// while their zkSync signature is still verified.
+    #[tokio::test]
+    async fn eth_signature_exemptions() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let exemptions = EthSignatureExemptions::new(vec![
+            TransactionType::ForcedExit,
+            TransactionType::Transfer,
+        ]);
+        let account = test_account(1);
+        let tx_request = |tx| {
+            RequestData::Tx(TxRequest {
+                tx: SignedZkSyncTx {
+                    tx,
+                    eth_sign_data: None,
+                    created_at: chrono::Utc::now(),
+                    batch_hash: None,
+                    parties_eth_sign_data: Vec::new(),
+                },
+                sender: account.address,
+                token: eth_token(),
+                parties: Vec::new(),
+            })
+        };
+
+        let forced_exit = account.sign_forced_exit(
+            TokenId(0),
+            10u64.into(),
+            &Address::repeat_byte(0x02),
+            None,
+            true,
+            Default::default(),
+        );
+        assert!(exemptions.is_exempt(&ZkSyncTx::ForcedExit(Box::new(forced_exit.clone()))));
+        VerifiedTx::verify(
+            tx_request(ZkSyncTx::ForcedExit(Box::new(forced_exit))),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &exemptions,
+        )
+        .await
+        .expect("Exempt transaction must be accepted without an Ethereum signature");
+
+        // Exemption doesn't affect the zkSync signature check.
+        let mut transfer = signed_transfer(&account).tx;
+        if let ZkSyncTx::Transfer(transfer) = &mut transfer {
+            transfer.amount += 1u64;
+        }
+        let result = VerifiedTx::verify(
+            tx_request(transfer),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &exemptions,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::IncorrectTx {
+                account: incorrect_account,
+                reason: reason @ TransactionError::TransferError(_),
+            }) if incorrect_account == account.address && reason.to_string() == WRONG_SIGNATURE
+        ));
+    }
+
+    /// Checks that the exemption of `ChangePubKey` from the Ethereum signature doesn't skip
+    /// the check of its onchain authorization.
+    #[tokio::test]
+    async fn exempt_change_pubkey_authorization() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()))
+            .with_offline_checks(OfflineEthChecks::RejectAll);
+        let exemptions = EthSignatureExemptions::new(vec![TransactionType::ChangePubKey]);
+        let account = test_account(1);
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let tx = ZkSyncTx::ChangePubKey(Box::new(change_pubkey));
+        assert!(exemptions.is_exempt(&tx));
+
+        let result = verify_tx_signature(
+            RequestData::Tx(TxRequest::new(
+                SignedZkSyncTx::from(tx),
+                account.address,
+                eth_token(),
+            )),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &exemptions,
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+    }
+
+    /// Checks that the checks not requiring the Ethereum node are served while
+    /// the node is considered unavailable.
+    #[tokio::test]
+    async fn local_checks_with_open_circuit_breaker() {
+        // Mock client has no `authFacts` result, so the onchain authorization check fails.
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let eth_checker = EthereumChecker::with_circuit_breaker(
+            EthereumGateway::Mock(MockEthereum::default()),
+            circuit_breaker,
+        );
+        let account = test_account(1);
+        let onchain_change_pubkey = || {
+            let change_pubkey = account.sign_change_pubkey_tx(
+                None,
+                true,
+                TokenId(0),
+                0u64.into(),
+                ChangePubKeyType::Onchain,
+                Default::default(),
+            );
+            RequestData::Tx(TxRequest {
+                tx: SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey))),
+                sender: account.address,
+                token: eth_token(),
+                parties: Vec::new(),
+            })
+        };
+
+        let result = verify_tx_signature(
+            onchain_change_pubkey(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::SignatureCheckUnavailable)));
+        let result = verify_tx_signature(
+            onchain_change_pubkey(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::EthereumNodeUnavailable)));
+
+        let request = RequestData::Tx(TxRequest {
+            tx: signed_transfer(&account),
+            sender: account.address,
+            token: eth_token(),
+            parties: Vec::new(),
+        });
+        verify_tx_signature(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("ECDSA signature doesn't require the Ethereum node");
+    }
+
+    /// Checks that the `ChangePubKey` of the counterfactual CREATE2 wallet is accepted without
+    /// the Ethereum node only if the account address is derived from the new public key hash.
+    #[tokio::test]
+    async fn create2_change_pubkey() {
+        let private_key = ZkSyncAccount::rand().private_key;
+        let create2_data = ChangePubKeyCREATE2Data {
+            creator_address: Address::repeat_byte(0x0c),
+            salt_arg: H256::repeat_byte(0x01),
+            code_hash: H256::repeat_byte(0x02),
+        };
+        let address = create2_data.get_address(&PubKeyHash::from_privkey(&private_key));
+        let account = ZkSyncAccount::new(
+            private_key,
+            Nonce(0),
+            address,
+            ZkSyncETHAccountData::Create2(create2_data.clone()),
+        );
+        account.set_account_id(Some(AccountId(1)));
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::CREATE2,
+            Default::default(),
+        );
+        let request = |change_pubkey: ChangePubKey| {
+            let tx = SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey)));
+            RequestData::Tx(TxRequest::new(tx, address, eth_token()))
+        };
+        // Panicking client makes sure the node isn't called.
+        let client = MockEthereum::default();
+        client.set_panicking();
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(client));
+
+        verify_tx_signature(
+            request(change_pubkey.clone()),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("CREATE2 authorization must be accepted");
+
+        // Address derived with another salt belongs to another wallet.
+        let mut foreign_change_pubkey = change_pubkey;
+        foreign_change_pubkey.eth_auth_data =
+            Some(ChangePubKeyEthAuthData::CREATE2(ChangePubKeyCREATE2Data {
+                salt_arg: H256::repeat_byte(0x03),
+                ..create2_data
+            }));
+        let result = verify_tx_signature(
+            request(foreign_change_pubkey),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+    }
+
+    /// Checks that the onchain `ChangePubKey` authorization is accepted only once it's
+    /// confirmed by the required number of blocks.
+    #[tokio::test]
+    async fn unconfirmed_change_pubkey_authorization() {
+        const CONFIRMATIONS: u64 = 3;
+        const LATEST_BLOCK: u64 = 10;
+
+        let account = test_account(1);
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let auth_fact = tiny_keccak::keccak256(&change_pubkey.new_pk_hash.data[..]).to_vec();
+        let request = || {
+            RequestData::Tx(TxRequest {
+                tx: SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey.clone()))),
+                sender: account.address,
+                token: eth_token(),
+                parties: Vec::new(),
+            })
+        };
+
+        // Authorization is present in the latest block only.
+        let mut client = MockEthereum::default();
+        client.set_block_number(LATEST_BLOCK.into()).await.unwrap();
+        client
+            .add_call_result(
+                "authFacts",
+                vec![ethabi::Token::FixedBytes(auth_fact.clone())],
+            )
+            .await;
+        client
+            .add_call_result_at_block(
+                "authFacts",
+                LATEST_BLOCK - CONFIRMATIONS,
+                vec![ethabi::Token::FixedBytes(vec![0; 32])],
+            )
+            .await;
+        let client = EthereumGateway::Mock(client);
+
+        let eth_checker = EthereumChecker::new(client.clone())
+            .with_auth_block(AuthFactBlock::Confirmed(CONFIRMATIONS));
+        let result = verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::ChangePkAuthorizationNotConfirmed)
+        ));
+
+        // Without the required confirmations the latest block is enough.
+        let eth_checker = EthereumChecker::new(client.clone());
+        verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Authorization in the latest block must be accepted");
+
+        // Authorization becomes confirmed.
+        let mut client = client;
+        client
+            .get_mut_mock()
+            .unwrap()
+            .add_call_result_at_block(
+                "authFacts",
+                LATEST_BLOCK - CONFIRMATIONS,
+                vec![ethabi::Token::FixedBytes(auth_fact)],
+            )
+            .await;
+        let eth_checker =
+            EthereumChecker::new(client).with_auth_block(AuthFactBlock::Confirmed(CONFIRMATIONS));
+        verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Confirmed authorization must be accepted");
+    }
+
+    /// Checks the onchain `ChangePubKey` authorization present only in the confirmed block,
+    /// as well as the authorization missing at the latest block checked with the retry delay.
+    #[tokio::test]
+    async fn change_pubkey_authorization_block() {
+        const CONFIRMATIONS: u64 = 3;
+        const LATEST_BLOCK: u64 = 10;
+        const RETRY_DELAY: Duration = Duration::from_millis(200);
+
+        let account = test_account(1);
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let auth_fact = tiny_keccak::keccak256(&change_pubkey.new_pk_hash.data[..]).to_vec();
+        let request = || {
+            RequestData::Tx(TxRequest {
+                tx: SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey.clone()))),
+                sender: account.address,
+                token: eth_token(),
+                parties: Vec::new(),
+            })
+        };
+
+        // Authorization is present at the confirmed block only, e.g. it has been reorged away.
+        let mut client = MockEthereum::default();
+        client.set_block_number(LATEST_BLOCK.into()).await.unwrap();
+        client
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(vec![0; 32])])
+            .await;
+        client
+            .add_call_result_at_block(
+                "authFacts",
+                LATEST_BLOCK - CONFIRMATIONS,
+                vec![ethabi::Token::FixedBytes(auth_fact.clone())],
+            )
+            .await;
+        let client = EthereumGateway::Mock(client);
+
+        // Authorization is no longer present at the latest block.
+        let eth_checker = EthereumChecker::new(client.clone())
+            .with_auth_block(AuthFactBlock::Confirmed(CONFIRMATIONS));
+        let result = verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+
+        let eth_checker = EthereumChecker::new(client.clone());
+        let result = verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+
+        // Missing authorization is reported as not confirmed until the retry delay passes.
+        let eth_checker = EthereumChecker::new(client.clone()).with_auth_block(
+            AuthFactBlock::LatestWithRetry(AuthRetries::new(16, RETRY_DELAY)),
+        );
+        for _ in 0..2 {
+            let result = verify_tx_signature(
+                request(),
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await;
+            assert!(matches!(
+                result,
+                Err(TxAddError::ChangePkAuthorizationNotConfirmed)
+            ));
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+        let result = verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+
+        // Authorization appears at the latest block before the transaction is resubmitted.
+        let eth_checker = EthereumChecker::new(client.clone()).with_auth_block(
+            AuthFactBlock::LatestWithRetry(AuthRetries::new(16, RETRY_DELAY)),
+        );
+        let result = verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::ChangePkAuthorizationNotConfirmed)
+        ));
+        let mut client = client;
+        client
+            .get_mut_mock()
+            .unwrap()
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(auth_fact)])
+            .await;
+        verify_tx_signature(
+            request(),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Authorization which has appeared must be accepted");
+    }
+
+    /// Checks that the verified transaction is rejected by `verify_again` once its onchain
+    /// authorization is revoked.
+    #[tokio::test]
+    async fn verify_again_after_revoked_authorization() {
+        let account = test_account(1);
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let auth_fact = tiny_keccak::keccak256(&change_pubkey.new_pk_hash.data[..]).to_vec();
+        let mut client = MockEthereum::default();
+        client
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(auth_fact)])
+            .await;
+        let mut client = EthereumGateway::Mock(client);
+        let eth_checker = EthereumChecker::new(client.clone());
+
+        let request = RequestData::Tx(TxRequest::new(
+            SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey))),
+            account.address,
+            eth_token(),
+        ));
+        let verified = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Authorized ChangePubKey must be accepted");
+        let verified = verified
+            .verify_again(&eth_checker)
+            .await
+            .expect("Authorization is still valid");
+
+        // Authorization is revoked.
+        client
+            .get_mut_mock()
+            .unwrap()
+            .add_call_result("authFacts", vec![ethabi::Token::FixedBytes(vec![0; 32])])
+            .await;
+        let result = verified.verify_again(&eth_checker).await;
+        assert!(matches!(result, Err(TxAddError::ChangePkNotAuthorized)));
+
+        // Transactions wrapped without the verification can't be verified again.
+        let result = VerifiedTx::unverified(verified.inner.clone())
+            .verify_again(&eth_checker)
+            .await;
+        assert!(matches!(result, Err(TxAddError::Other)));
+    }
+
+    /// Returns the swap of the `parties` orders, each one signed by its owner with
+    /// the Ethereum key.
+    fn signed_swap(submitter: &ZkSyncAccount, parties: &[ZkSyncAccount]) -> SignedZkSyncTx {
+        let orders: Vec<_> = parties
+            .iter()
+            .map(|account| {
+                account.sign_order(
+                    TokenId(0),
+                    TokenId(1),
+                    1u64.into(),
+                    1u64.into(),
+                    100u64.into(),
+                    &account.address,
+                    None,
+                    true,
+                    Default::default(),
+                )
+            })
+            .collect();
+        let parties_eth_sign_data: Vec<_> = orders
+            .iter()
+            .zip(&parties)
+            .map(|(order, account)| {
+                let message = order
+                    .get_ethereum_sign_message("ETH", "TKN", 18)
+                    .into_bytes();
+                let private_key = account.eth_account_data.unwrap_eoa_pk();
+                let signature = PackedEthSignature::sign(&private_key, &message).unwrap();
+                Some(EthSignData {
+                    signature: TxEthSignature::EthereumSignature(signature),
+                    message: message.into(),
+                })
+            })
+            .collect();
+        let (swap, _) = submitter.sign_swap(
+            (orders[0].clone(), orders[1].clone()),
+            (100u64.into(), 100u64.into()),
+            None,
+            true,
+            TokenId(0),
+            "ETH",
+            0u64.into(),
+        );
+        let mut tx = SignedZkSyncTx::from(ZkSyncTx::Swap(Box::new(swap)));
+        tx.parties_eth_sign_data = parties_eth_sign_data;
+        tx
+    }
+
+    /// Checks that the signatures of the swap orders are verified against the addresses
+    /// of the corresponding orders owners.
+    #[tokio::test]
+    async fn swap_parties_signatures() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let exemptions = EthSignatureExemptions::default();
+        let submitter = test_account(1);
+        let parties = [test_account(2), test_account(3)];
+        let mut tx = signed_swap(&submitter, &parties);
+        let addresses = [parties[0].address, parties[1].address];
+
+        verify_eth_signature_single_tx(
+            &tx,
+            submitter.address,
+            &addresses,
+            eth_token(),
+            &eth_checker,
+            &exemptions,
+        )
+        .await
+        .expect("Orders signatures must be accepted");
+
+        // Each signature is checked against its own party.
+        let swapped_addresses = [parties[1].address, parties[0].address];
+        let result = verify_eth_signature_single_tx(
+            &tx,
+            submitter.address,
+            &swapped_addresses,
+            eth_token(),
+            &eth_checker,
+            &exemptions,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == parties[1].address && recovered == parties[0].address
+        ));
+
+        // Party without the Ethereum signature is skipped.
+        tx.parties_eth_sign_data[1] = None;
+        verify_eth_signature_single_tx(
+            &tx,
+            submitter.address,
+            &addresses,
+            eth_token(),
+            &eth_checker,
+            &exemptions,
+        )
+        .await
+        .expect("Party without a signature must be skipped");
+
+        // Transfer has no parties besides the sender.
+        let mut transfer = signed_transfer(&submitter);
+        transfer.parties_eth_sign_data = tx.parties_eth_sign_data.clone();
+        let result = verify_eth_signature_single_tx(
+            &transfer,
+            submitter.address,
+            &addresses,
+            eth_token(),
+            &eth_checker,
+            &exemptions,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::EthSignaturesLimitExceeded)
+        ));
+    }
+
+    /// Checks that the signatures of the swap orders are verified for the batch transactions.
+    #[tokio::test]
+    async fn swap_parties_signatures_in_batch() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let exemptions = EthSignatureExemptions::default();
+        let submitter = test_account(1);
+        let parties = [test_account(2), test_account(3)];
+        let request = |tx_parties: Vec<Vec<Address>>| BatchRequest {
+            txs: vec![
+                signed_transfer(&submitter),
+                signed_swap(&submitter, &parties),
+            ],
+            batch_sign_data: None,
+            senders: vec![submitter.address; 2],
+            tokens: vec![eth_token(); 2],
+            parties: tx_parties,
+        };
+
+        let addresses = vec![parties[0].address, parties[1].address];
+        verify_eth_signature_batch(
+            &request(vec![Vec::new(), addresses]),
+            &eth_checker,
+            &exemptions,
+        )
+        .await
+        .expect("Orders signatures must be accepted");
+
+        let swapped_addresses = vec![parties[1].address, parties[0].address];
+        let failure = verify_eth_signature_batch(
+            &request(vec![Vec::new(), swapped_addresses]),
+            &eth_checker,
+            &exemptions,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(failure.index, 1);
+        assert!(matches!(
+            failure.error,
+            TxAddError::SignerMismatch { expected, recovered }
+                if expected == parties[1].address && recovered == parties[0].address
+        ));
+
+        // Parties must be provided for the orders signatures.
+        let failure = verify_eth_signature_batch(&request(Vec::new()), &eth_checker, &exemptions)
+            .await
+            .unwrap_err();
+        assert_eq!(failure.index, 1);
+        assert!(matches!(failure.error, TxAddError::Other));
+    }
+
+    /// Checks that failures of the signature checker itself are reported separately
+    /// from the incorrect signatures.
+    #[tokio::test]
+    async fn internal_errors_are_distinguished() {
+        // Mock client has no `authFacts` result, thus the onchain authorization can't be checked.
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let account = test_account(1);
+        let change_pubkey = account.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let request = RequestData::Tx(TxRequest {
+            tx: SignedZkSyncTx {
+                tx: ZkSyncTx::ChangePubKey(Box::new(change_pubkey)),
+                eth_sign_data: None,
+                created_at: chrono::Utc::now(),
+                batch_hash: None,
+                parties_eth_sign_data: Vec::new(),
+            },
+            sender: account.address,
+            token: eth_token(),
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::SignatureCheckUnavailable)));
+
+        // Signature of another account is the client's fault.
+        let request = RequestData::Tx(TxRequest {
+            tx: signed_transfer(&account),
+            sender: Address::repeat_byte(0x01),
+            token: eth_token(),
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == Address::repeat_byte(0x01) && recovered == account.address
+        ));
+
+        // Checks which take too long are considered failed.
+        let (mut sender, input) = mpsc::channel(1);
+        let (queue_depth_sender, _queue_depth) = watch::channel(0);
+        let eth_client = eth_client_updates(slow_eth_client(Duration::from_secs(5)).await).1;
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: 1,
+                verification_timeout: Duration::from_millis(50),
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        let result = response.await.unwrap();
+        assert!(matches!(result, Err(TxAddError::SignatureCheckTimeout)));
+    }
+
+    /// Checks that the malformed Ethereum signature is reported as such rather than as
+    /// a signature of another account.
+    #[tokio::test]
+    async fn malformed_eth_signature() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let account = test_account(1);
+        let mut tx = signed_transfer(&account);
+        let sign_data = tx.eth_sign_data.as_mut().unwrap();
+        if let TxEthSignature::EthereumSignature(signature) = &sign_data.signature {
+            let mut bytes = signature.serialize_packed();
+            bytes[64] = 5;
+            sign_data.signature = TxEthSignature::EthereumSignature(
+                PackedEthSignature::deserialize_packed(&bytes).unwrap(),
+            );
+        }
+
+        let request = RequestData::Tx(TxRequest {
+            tx,
+            sender: account.address,
+            token: eth_token(),
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(TxAddError::MalformedSignature)));
+    }
+
+    /// Checks that the malformed batch signatures are rejected before the recovery.
+    #[tokio::test]
+    async fn malformed_batch_eth_signature() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let account = test_account(1);
+        let message = b"batch message".to_vec();
+        let signature =
+            PackedEthSignature::sign(account.try_get_eth_private_key().unwrap(), &message).unwrap();
+        let mut bytes = signature.serialize_packed();
+
+        // Truncated signature is rejected on deserialization. 64 bytes long signatures
+        // are accepted in the EIP-2098 compact form.
+        let truncated = serde_json::json!({
+            "type": "EthereumSignature",
+            "signature": format!("0x{}", hex::encode(&bytes[..63])),
+        });
+        assert!(serde_json::from_value::<TxEthSignature>(truncated).is_err());
+
+        // Signature with `v` out of the allowed set.
+        bytes[64] = 5;
+        let request = RequestData::Batch(BatchRequest {
+            txs: vec![signed_transfer(&account)],
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![TxEthSignature::EthereumSignature(
+                    PackedEthSignature::deserialize_packed(&bytes).unwrap(),
+                )],
+                message: message.into(),
+            }),
+            senders: vec![account.address],
+            tokens: vec![eth_token()],
+            parties: Vec::new(),
+        });
+        let result = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::MalformedSignature)
+        ));
+
+        // Malformed signature doesn't prevent the other signatures from being checked.
+        let malformed = TxEthSignature::EthereumSignature(
+            PackedEthSignature::deserialize_packed(&bytes).unwrap(),
+        );
+        let request = RequestData::Batch(BatchRequest {
+            txs: vec![signed_transfer(&account)],
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![malformed, TxEthSignature::EthereumSignature(signature)],
+                message: message.into(),
+            }),
+            senders: vec![account.address],
+            tokens: vec![eth_token()],
+            parties: Vec::new(),
+        });
+        VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Batch must be verified with the correct signature");
+    }
+
+    #[cfg(feature = "trusted-construction")]
+    #[test]
+    fn from_already_verified_unwraps_to_the_same_tx() {
+        let tx = signed_transfer(&test_account(1));
+
+        let verified = VerifiedTx::from_already_verified(tx.clone()).unwrap_tx();
+        assert_eq!(verified.tx.hash(), tx.tx.hash());
+        assert_eq!(verified.eth_sign_data, tx.eth_sign_data);
+    }
+
+    /// Checks that requests are processed when the checker runs in several threads
+    /// sharing the same channel.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn multiple_checker_threads() {
+        const REQUESTS: usize = 100;
+        const THREADS: usize = 4;
+
+        let (_, client) = eth_client_updates(slow_eth_client(Duration::from_millis(10)).await);
+        let (mut sender, input) = mpsc::channel(REQUESTS);
+        let (handle, _queue_depth) =
+            start_sign_checker(client, input, 1, None, Vec::new(), Some(THREADS));
+
+        let mut responses = Vec::new();
+        for _ in 0..REQUESTS {
+            let (request, response) = eip1271_request();
+            sender.send(request).await.unwrap();
+            responses.push(response);
+        }
+        for response in responses {
+            response.await.unwrap().expect("Signature must be correct");
+        }
+
+        // All the threads are stopped once the channel is closed.
+        drop(sender);
+        handle.await.unwrap();
+    }
+
+    /// Checks that requests are processed by the checker running on an external runtime.
+    #[test]
+    fn checker_on_external_runtime() {
+        const REQUESTS: usize = 10;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let (_, client) = eth_client_updates(slow_eth_client(Duration::from_millis(10)).await);
+            let (mut sender, input) = mpsc::channel(REQUESTS);
+            let handle = start_sign_checker_on_handle(
+                client,
+                SignatureCheckerConfig::from(&api_config()),
+                input,
+                Handle::current(),
+            );
+
+            let mut responses = Vec::new();
+            for _ in 0..REQUESTS {
+                let (request, response) = eip1271_request();
+                sender.send(request).await.unwrap();
+                responses.push(response);
+            }
+            for response in responses {
+                response.await.unwrap().expect("Signature must be correct");
+            }
+
+            drop(sender);
+            handle.await.unwrap();
+        });
+    }
+
+    /// Checks that the Ethereum client can be replaced without restarting the checker:
+    /// new requests use the new client, while the in-flight ones finish with the old one.
+    #[tokio::test]
+    async fn eth_client_is_replaced_at_runtime() {
+        // Mock client without the `isValidSignature` result rejects every EIP1271 signature.
+        let failing_client = EthereumGateway::Mock(MockEthereum::default());
+        let (client_sender, eth_client) = eth_client_updates(failing_client.clone());
+        let (mut sender, input) = mpsc::channel(10);
+        let (queue_depth_sender, mut queue_depth) = watch::channel(0);
+        let context = CheckerContext::new(
+            SignatureCheckerConfig {
+                requests_per_poll: 1,
+                ..Default::default()
+            },
+            eth_client.borrow().clone(),
+            QueueDepth::new(queue_depth_sender),
+        );
+        tokio::spawn(checker_routine(
+            Arc::new(Mutex::new(input)),
+            eth_client,
+            Arc::new(context),
+            ShutdownToken::never(),
+        ));
+
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        let result = response.await.unwrap();
+        assert!(matches!(result, Err(TxAddError::Eip1271Rejected)));
+
+        // Switch to the working client.
+        client_sender
+            .send(slow_eth_client(Duration::from_millis(200)).await)
+            .unwrap();
+        let (request, in_flight_response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while *queue_depth.borrow() != 1 {
+                queue_depth.changed().await.unwrap();
+            }
+        })
+        .await
+        .expect("Request wasn't taken by the checker");
+
+        // Switch back while the request is being checked.
+        client_sender.send(failing_client).unwrap();
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+
+        in_flight_response
+            .await
+            .unwrap()
+            .expect("In-flight request must be checked with the working client");
+        let result = response.await.unwrap();
+        assert!(matches!(result, Err(TxAddError::Eip1271Rejected)));
+    }
+
+    /// Checks that the correctness of large batches is checked in parallel, and
+    /// the first incorrect transaction in the batch order is reported.
+    #[test]
+    fn large_batch_correctness() {
+        const BATCH_SIZE: usize = 64;
+        let alice = test_account(1);
+        let txs: Vec<_> = (0..BATCH_SIZE).map(|_| signed_transfer(&alice)).collect();
+
+        let mut batch = TxVariant::Batch(txs.clone(), None);
+        verify_tx_correctness(&mut batch, Address::zero()).expect("Batch must be correct");
+
+        let mut corrupted = txs;
+        for (idx, tx) in corrupted.iter_mut().enumerate().skip(BATCH_SIZE / 4) {
+            if let ZkSyncTx::Transfer(transfer) = &mut tx.tx {
+                if idx == BATCH_SIZE / 4 {
+                    transfer.to = Address::zero();
+                } else {
+                    transfer.fee += 1u32;
+                }
+                transfer.wipe_signer_cache();
+            }
+        }
+        let mut batch = TxVariant::Batch(corrupted, None);
+        let result = verify_tx_correctness(&mut batch, Address::zero());
+        assert!(matches!(
+            result,
+            Err(TxAddError::IncorrectTx {
+                account,
+                reason: reason @ TransactionError::TransferError(_),
+            }) if account == alice.address && reason.to_string() == WRONG_TO_ADDRESS
+        ));
+    }
+
+    #[test]
+    fn content_hash() {
+        let (alice, bob) = (test_account(1), test_account(2));
+        let txs = vec![signed_transfer(&alice), signed_transfer(&bob)];
+
+        // Identical contents give the same hash.
+        let batch = TxVariant::Batch(txs.clone(), None);
+        assert_eq!(
+            batch.content_hash(),
+            TxVariant::Batch(txs.clone(), None).content_hash()
+        );
+        assert_eq!(
+            TxVariant::Tx(txs[0].clone()).content_hash(),
+            TxVariant::Tx(txs[0].clone()).content_hash()
+        );
+
+        // Order of transactions matters.
+        let reordered = TxVariant::Batch(vec![txs[1].clone(), txs[0].clone()], None);
+        assert_ne!(batch.content_hash(), reordered.content_hash());
+
+        // Batch signature is taken into account.
+        let batch_sign_data = EthBatchSignData {
+            signatures: Vec::new(),
+            message: b"batch message".to_vec().into(),
+        };
+        let signed_batch = TxVariant::Batch(txs.clone(), Some(batch_sign_data));
+        assert_ne!(batch.content_hash(), signed_batch.content_hash());
+
+        // Single transaction differs from a batch of it.
+        assert_ne!(
+            TxVariant::Tx(txs[0].clone()).content_hash(),
+            TxVariant::Batch(vec![txs[0].clone()], None).content_hash()
+        );
+    }
+
+    #[test]
+    fn from_signed_roundtrip() {
+        let (alice, bob) = (test_account(1), test_account(2));
+        let txs = vec![signed_transfer(&alice), signed_transfer(&bob)];
+        let signature = TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+        let batch_sign_data = EthBatchSignData {
+            signatures: vec![signature],
+            message: b"batch message".to_vec().into(),
+        };
+        // Transactions and their Ethereum signatures are compared via the content hash.
+        let assert_same_txs = |restored: &[SignedZkSyncTx], original: &[SignedZkSyncTx]| {
+            assert_eq!(restored.len(), original.len());
+            for (restored, original) in restored.iter().zip(original) {
+                assert_eq!(restored.created_at, original.created_at);
+                assert_eq!(restored.batch_hash, original.batch_hash);
+            }
+        };
+
+        let tx = TxVariant::Tx(txs[0].clone());
+        let signed = SignedTxVariant::from(txs[0].clone());
+        let restored = TxVariant::from_signed(&signed, None);
+        assert_eq!(restored.content_hash(), tx.content_hash());
+        match restored {
+            TxVariant::Tx(restored) => assert_same_txs(&[restored], &txs[..1]),
+            _ => panic!("Single transaction must be restored"),
+        }
+
+        let batch = TxVariant::Batch(txs.clone(), Some(batch_sign_data.clone()));
+        let signed = SignedTxVariant::batch(txs.clone(), 1, batch_sign_data.signatures.clone());
+        let restored = TxVariant::from_signed(&signed, Some(batch_sign_data.clone()));
+        assert_eq!(restored.content_hash(), batch.content_hash());
+        match restored {
+            TxVariant::Batch(restored, Some(restored_sign_data)) => {
+                assert_same_txs(&restored, &txs);
+                assert_eq!(restored_sign_data.message, batch_sign_data.message);
+                assert_eq!(
+                    restored_sign_data.signatures.len(),
+                    batch_sign_data.signatures.len()
+                );
+            }
+            _ => panic!("Batch must be restored with its signature data"),
+        }
+    }
+
+    #[test]
+    fn split_by_account() {
+        let (alice, bob, carol) = (test_account(1), test_account(2), test_account(3));
+        let hashes = |groups: Vec<(Address, Vec<&SignedZkSyncTx>)>| {
+            groups
+                .into_iter()
+                .map(|(account, txs)| (account, txs.iter().map(|tx| tx.tx.hash()).collect()))
+                .collect::<Vec<(Address, Vec<TxHash>)>>()
+        };
+
+        // Single account.
+        let txs = vec![signed_transfer(&alice), signed_transfer(&alice)];
+        let batch = TxVariant::Batch(txs.clone(), None);
+        assert_eq!(
+            hashes(batch.split_by_account()),
+            vec![(alice.address, vec![txs[0].tx.hash(), txs[1].tx.hash()])]
+        );
+        let single = TxVariant::Tx(txs[0].clone());
+        assert_eq!(
+            hashes(single.split_by_account()),
+            vec![(alice.address, vec![txs[0].tx.hash()])]
+        );
+
+        // Contiguous groups of different accounts.
+        let txs = vec![
+            signed_transfer(&bob),
+            signed_transfer(&alice),
+            signed_transfer(&alice),
+            signed_transfer(&carol),
+        ];
+        let batch = TxVariant::Batch(txs.clone(), None);
+        assert_eq!(
+            hashes(batch.split_by_account()),
+            vec![
+                (bob.address, vec![txs[0].tx.hash()]),
+                (alice.address, vec![txs[1].tx.hash(), txs[2].tx.hash()]),
+                (carol.address, vec![txs[3].tx.hash()]),
+            ]
+        );
+
+        // Interleaved transactions of the same account are merged into one group.
+        let txs = vec![
+            signed_transfer(&alice),
+            signed_transfer(&bob),
+            signed_transfer(&alice),
+            signed_transfer(&bob),
+        ];
+        let batch = TxVariant::Batch(txs.clone(), None);
+        assert_eq!(
+            hashes(batch.split_by_account()),
+            vec![
+                (alice.address, vec![txs[0].tx.hash(), txs[2].tx.hash()]),
+                (bob.address, vec![txs[1].tx.hash(), txs[3].tx.hash()]),
+            ]
+        );
+
+        assert!(TxVariant::Toggle2FA.split_by_account().is_empty());
+    }
+
+    /// Checks that the batch signatures are accepted for the messages of both formats,
+    /// unless the legacy one is disabled.
+    #[tokio::test]
+    async fn batch_message_formats() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (alice, bob) = (test_account(1), test_account(2));
+        let txs: Vec<_> = vec![signed_transfer(&alice), signed_transfer(&alice)]
+            .into_iter()
+            .map(|tx| SignedZkSyncTx {
+                eth_sign_data: None,
+                ..tx
+            })
+            .collect();
+        let structured_message = EthBatchSignData::get_batch_sign_message(
+            txs.iter()
+                .map(|tx| (tx.tx.clone(), eth_token(), alice.address))
+                .collect(),
+        );
+        let legacy_message =
+            EthBatchSignData::get_old_ethereum_batch_message(txs.iter().map(|tx| &tx.tx));
+        let signature = |account: &ZkSyncAccount, message: &[u8]| {
+            TxEthSignature::EthereumSignature(
+                PackedEthSignature::sign(account.try_get_eth_private_key().unwrap(), message)
+                    .unwrap(),
+            )
+        };
+        let request = |signatures| {
+            RequestData::Batch(BatchRequest {
+                txs: txs.clone(),
+                batch_sign_data: Some(EthBatchSignData {
+                    signatures,
+                    message: SignedMessage::Text(
+                        String::from_utf8(structured_message.clone()).unwrap(),
+                    ),
+                }),
+                senders: vec![alice.address; 2],
+                tokens: vec![eth_token(); 2],
+                parties: Vec::new(),
+            })
+        };
+        let (window, exemptions) = (
+            ValidFromWindow::default(),
+            EthSignatureExemptions::default(),
+        );
+        let verify =
+            |eth_checker, request| VerifiedTx::verify(request, eth_checker, &window, &exemptions);
+
+        verify(
+            &eth_checker,
+            request(vec![signature(&alice, &structured_message)]),
+        )
+        .await
+        .expect("Batch signed for the structured message must be accepted");
+        verify(
+            &eth_checker,
+            request(vec![signature(&alice, &legacy_message)]),
+        )
+        .await
+        .expect("Batch signed for the legacy message must be accepted");
+
+        // Neither of the messages is signed by the sender.
+        let result = verify(
+            &eth_checker,
+            request(vec![
+                signature(&bob, &structured_message),
+                signature(&bob, &legacy_message),
+            ]),
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::IncorrectEthSignature { expected, attempted_formats, .. })
+                if expected == alice.address && attempted_formats == BatchMessageFormats::all()
+        ));
+
+        // Legacy message is not accepted once it's disabled.
+        let structured_only = BatchMessageFormats::all().without(BatchMessageFormat::Legacy);
+        let eth_checker = eth_checker
+            .clone()
+            .with_batch_message_formats(structured_only);
+        let result = verify(
+            &eth_checker,
+            request(vec![
+                signature(&alice, &legacy_message),
+                signature(&bob, &legacy_message),
+            ]),
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::IncorrectEthSignature { attempted_formats, .. })
+                if attempted_formats == structured_only
+        ));
+        assert_eq!(structured_only.to_string(), "structured");
+    }
+
+    /// Checks that the mismatching Ethereum signatures of the transactions are ignored
+    /// if the batch signature is valid only when it's explicitly enabled.
+    #[tokio::test]
+    async fn batch_signature_covers_txs() {
+        let eth_checker = EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default()));
+        let (alice, bob) = (test_account(1), test_account(2));
+        // Transactions carry stale signatures made by another account.
+        let stale_sign_data = signed_transfer(&bob).eth_sign_data;
+        let txs: Vec<_> = vec![signed_transfer(&alice), signed_transfer(&alice)]
+            .into_iter()
+            .map(|tx| SignedZkSyncTx {
+                eth_sign_data: stale_sign_data.clone(),
+                ..tx
+            })
+            .collect();
+        let message = EthBatchSignData::get_batch_sign_message(
+            txs.iter()
+                .map(|tx| (tx.tx.clone(), eth_token(), alice.address))
+                .collect(),
+        );
+        let batch_signer = |account: &ZkSyncAccount| {
+            TxEthSignature::EthereumSignature(
+                PackedEthSignature::sign(account.try_get_eth_private_key().unwrap(), &message)
+                    .unwrap(),
+            )
+        };
+        let request = |batch_signer| {
+            RequestData::Batch(BatchRequest {
+                txs: txs.clone(),
+                batch_sign_data: Some(EthBatchSignData {
+                    signatures: vec![batch_signer],
+                    message: SignedMessage::Text(String::from_utf8(message.clone()).unwrap()),
+                }),
+                senders: vec![alice.address; 2],
+                tokens: vec![eth_token(); 2],
+                parties: Vec::new(),
+            })
+        };
+        let window = ValidFromWindow::default();
+        let strict = EthSignatureExemptions::default();
+        let lenient = EthSignatureExemptions::default().with_batch_signature_coverage(true);
+
+        // Strict mode is the default: signatures of the transactions must match as well.
+        let result = VerifiedTx::verify(
+            request(batch_signer(&alice)),
+            &eth_checker,
+            &window,
+            &strict,
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == alice.address && recovered == bob.address
+        ));
+
+        let (verified_txs, batch_sign_data) = VerifiedTx::verify(
+            request(batch_signer(&alice)),
+            &eth_checker,
+            &window,
+            &lenient,
+        )
+        .await
+        .expect("Batch signature must cover the transactions in the lenient mode")
+        .unwrap_batch();
+        assert!(batch_sign_data.is_some());
+        assert_eq!(verified_txs.len(), txs.len());
+
+        // Batch signature itself is still required to be valid.
+        let result =
+            VerifiedTx::verify(request(batch_signer(&bob)), &eth_checker, &window, &lenient).await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::IncorrectEthSignature { expected, .. }) if expected == alice.address
+        ));
+    }
+
+    /// Checks that a batch signed by both an EOA and a smart contract wallet is verified.
+    #[tokio::test]
+    async fn batch_with_mixed_signature_schemes() {
+        // Every EIP1271 signature is approved by the mock.
+        let eth_checker = EthereumChecker::new(slow_eth_client(Duration::from_millis(0)).await);
+        let (alice, bob) = (test_account(1), test_account(2));
+        let wallet = Address::repeat_byte(0x0a);
+        // Transactions are covered by the batch signature only.
+        let txs: Vec<_> = vec![signed_transfer(&alice), signed_transfer(&bob)]
+            .into_iter()
+            .map(|tx| SignedZkSyncTx {
+                eth_sign_data: None,
+                ..tx
+            })
+            .collect();
+        let senders = vec![alice.address, wallet];
+        let message = EthBatchSignData::get_batch_sign_message(
+            txs.iter()
+                .zip(&senders)
+                .map(|(tx, sender)| (tx.tx.clone(), eth_token(), *sender))
+                .collect(),
+        );
+        let ecdsa_signature = TxEthSignature::EthereumSignature(
+            PackedEthSignature::sign(alice.try_get_eth_private_key().unwrap(), &message).unwrap(),
+        );
+        let eip1271_signature = TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+
+        let request = |signatures| {
+            RequestData::Batch(BatchRequest {
+                txs: txs.clone(),
+                batch_sign_data: Some(EthBatchSignData {
+                    signatures,
+                    message: message.clone().into(),
+                }),
+                senders: senders.clone(),
+                tokens: vec![eth_token(); txs.len()],
+                parties: Vec::new(),
+            })
+        };
+
+        // Order of the signatures doesn't matter.
+        for signatures in vec![
+            vec![ecdsa_signature.clone(), eip1271_signature.clone()],
+            vec![eip1271_signature, ecdsa_signature.clone()],
+        ] {
+            let (verified_txs, _) = VerifiedTx::verify(
+                request(signatures),
+                &eth_checker,
+                &ValidFromWindow::default(),
+                &EthSignatureExemptions::default(),
+            )
+            .await
+            .expect("Batch with mixed signatures must be verified")
+            .unwrap_batch();
+            // Every transaction is tied to the batch signature.
+            let batch_hash = H256::from(tiny_keccak::keccak256(&message));
+            assert!(verified_txs
+                .iter()
+                .all(|tx| tx.batch_hash == Some(batch_hash)));
+        }
+
+        // Smart contract wallet can't be covered by the ECDSA signature of another account.
+        let result = VerifiedTx::verify(
+            request(vec![ecdsa_signature]),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(TxAddError::into_reason),
+            Err(TxAddError::SignerMismatch { expected, recovered })
+                if expected == wallet && recovered == alice.address
+        ));
+    }
+
+    /// Checks that the onchain checks made during the verification are counted.
+    #[tokio::test]
+    async fn eth_calls_are_counted() {
+        // Every EIP1271 signature is approved by the mock.
+        let eth_checker = EthereumChecker::new(slow_eth_client(Duration::from_millis(0)).await);
+        let account = test_account(1);
+        let wallet = Address::repeat_byte(0x0a);
+        // Transactions are covered by the batch signature only.
+        let txs: Vec<_> = (0..3)
+            .map(|_| SignedZkSyncTx {
+                eth_sign_data: None,
+                ..signed_transfer(&account)
+            })
+            .collect();
+        let message = EthBatchSignData::get_batch_sign_message(
+            txs.iter()
+                .map(|tx| (tx.tx.clone(), eth_token(), wallet))
+                .collect(),
+        );
+        let eip1271_signature = TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65]));
+        let request = RequestData::Batch(BatchRequest {
+            txs: txs.clone(),
+            batch_sign_data: Some(EthBatchSignData {
+                signatures: vec![eip1271_signature],
+                message: message.into(),
+            }),
+            senders: vec![wallet; txs.len()],
+            tokens: vec![eth_token(); txs.len()],
+            parties: Vec::new(),
+        });
+
+        let verified = VerifiedTx::verify(
+            request,
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Batch must be verified");
+        // The signature of the same signer is only checked once for the whole batch.
+        let eth_calls = verified.eth_calls().expect("Calls must be counted");
+        assert_eq!(
+            eth_calls,
+            EthCallCounts {
+                eip1271_signature: 1,
+                pubkey_hash_authorization: 0,
+            }
+        );
+        assert_eq!(eth_calls.total(), 1);
+
+        // No calls are needed for the ECDSA signatures.
+        let verified = VerifiedTx::verify(
+            RequestData::Tx(TxRequest::new(
+                signed_transfer(&account),
+                account.address,
+                eth_token(),
+            )),
+            &eth_checker,
+            &ValidFromWindow::default(),
+            &EthSignatureExemptions::default(),
+        )
+        .await
+        .expect("Transaction must be verified");
+        assert_eq!(verified.eth_calls(), Some(EthCallCounts::default()));
+        assert!(VerifiedTx::unverified(verified.inner).eth_calls().is_none());
+    }
+
+    /// Checks that the onchain checks accepting everything can't be enabled on mainnet.
+    #[test]
+    fn offline_eth_checks_on_mainnet() {
+        let mut api_config = api_config();
+        let config = SignatureCheckerConfig::from(&api_config);
+        assert_eq!(config.offline_eth_checks, None);
+        config.ensure_allowed_on(Network::Mainnet).unwrap();
+
+        api_config.eth_checker_mode = EthCheckerMode::AcceptAll;
+        let config = SignatureCheckerConfig::from(&api_config);
+        assert_eq!(config.offline_eth_checks, Some(OfflineEthChecks::AcceptAll));
+        config.ensure_allowed_on(Network::Localhost).unwrap();
+        config.ensure_allowed_on(Network::Mainnet).unwrap_err();
+
+        api_config.eth_checker_mode = EthCheckerMode::RejectAll;
+        let config = SignatureCheckerConfig::from(&api_config);
+        config.ensure_allowed_on(Network::Mainnet).unwrap();
+    }
+
+    fn api_config() -> CommonApiConfig {
+        CommonApiConfig {
+            caches_size: 100,
+            forced_exit_minimum_account_age_secs: 0,
+            fee_free_accounts: Vec::new(),
+            enforce_pubkey_change_fee: true,
+            max_number_of_transactions_per_batch: 200,
+            max_number_of_authors_per_batch: 10,
+            subsidized_ips: Vec::new(),
+            max_subsidy_usd_scaled: 0,
+            subsidy_name: String::new(),
+            valid_from_acceptance_window_secs: None,
+            eth_sig_exempt_tx_types: Vec::new(),
+            accept_legacy_batch_message: true,
+            accept_unbound_eth_messages: true,
+            accept_untimed_eth_messages: true,
+            batch_signature_covers_txs: false,
+            min_batch_total_fees: Vec::new(),
+            eth_failure_threshold: 5,
+            eth_probe_interval_secs: 10,
+            eth_call_retries: 2,
+            eth_call_retry_backoff_ms: 100,
+            eth_health_log_interval_secs: None,
+            eth_checker_mode: EthCheckerMode::Node,
+            change_pubkey_auth_block: ChangePubKeyAuthBlock::Confirmed,
+            change_pubkey_auth_confirmations: 3,
+            change_pubkey_auth_retry_delay_ms: 2000,
+            signer_cache_size: 10_000,
+            eip1271_cache_size: 10_000,
+            eip1271_cache_ttl_ms: 30_000,
+            multicall_address: None,
+            eth_call_batch_window_ms: 5,
+            rejection_cache_size: 10_000,
+            rejection_cache_ttl_ms: 10_000,
+            sign_checker_channel_capacity: 32_768,
+            sign_checker_checks_per_thread: 16,
+            sign_check_enqueue_timeout_ms: 100,
+            sign_check_timeout_ms: 30000,
+            sign_checker_record_path: None,
+            rate_limit_rps: None,
+            rate_limit_burst: 40,
+            rate_limit_endpoints: Vec::new(),
+            trusted_proxies: Vec::new(),
+            api_keys_enabled: false,
+            api_keys_refresh_interval_secs: 30,
+            allow_anonymous_access: true,
+            tx_screening_mode: TxScreeningMode::Disabled,
+            tx_screening_url: None,
+            tx_screening_timeout_ms: 1000,
+            light_node: false,
+            ens_registry_address: None,
+            ens_cache_size: 10,
+            ens_cache_ttl_secs: 300,
+            committed_state_cache_size: 0,
+            fast_withdrawals_enabled: false,
+            fast_withdrawal_validity_secs: 600,
+            response_signing_key: None,
+            retired_response_signers: Vec::new(),
+            withdrawal_limits_enabled: false,
+        }
+    }
+
+    /// Checks that the requests over the limit of the checks running at once are rejected
+    /// by the saturated checker instead of waiting for it.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn saturated_checker_rejects_requests() {
+        const MAX_CONCURRENT: usize = 2;
+
+        let (_, client) = eth_client_updates(slow_eth_client(Duration::from_secs(1)).await);
+        let (panic_sender, _panic_receiver) = mpsc::channel(1);
+        let (mut sender, _handle, queue_depth) = spawn_sign_checker(
+            client,
+            SignatureCheckerConfig {
+                max_concurrent_verifications: Some(MAX_CONCURRENT),
+                ..SignatureCheckerConfig::from(&api_config())
+            },
+            16,
+            panic_sender,
+            ShutdownToken::never(),
+        );
+
+        let mut responses = Vec::new();
+        for _ in 0..MAX_CONCURRENT + 2 {
+            let (request, response) = eip1271_request();
+            sender.send(request).await.unwrap();
+            responses.push(response);
+        }
+        let mut verified = 0;
+        let mut overloaded = 0;
+        for response in responses {
+            match response.await.unwrap() {
+                Ok(_) => verified += 1,
+                Err(TxAddError::ServiceOverloaded) => overloaded += 1,
+                Err(err) => panic!("Unexpected error: {}", err),
+            }
+        }
+        assert_eq!((verified, overloaded), (MAX_CONCURRENT, 2));
+        assert_eq!(*queue_depth.borrow(), 0);
+
+        // Slots are freed once the checks are finished.
+        let (request, response) = eip1271_request();
+        sender.send(request).await.unwrap();
+        response.await.unwrap().expect("Signature must be correct");
+    }
+
+    /// Checks that the requests sent before the shutdown are answered before the threads exit.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shutdown_answers_sent_requests() {
+        let (_, client) = eth_client_updates(slow_eth_client(Duration::from_millis(100)).await);
+        let (panic_sender, _panic_receiver) = mpsc::channel(1);
+        let shutdown = Shutdown::new();
+        let (mut sender, handle, _queue_depth) = spawn_sign_checker(
+            client,
+            SignatureCheckerConfig {
+                num_threads: Some(2),
+                ..SignatureCheckerConfig::from(&api_config())
+            },
+            8,
+            panic_sender,
+            shutdown.token(),
+        );
+
+        let mut responses = Vec::new();
+        for _ in 0..4 {
+            let (request, response) = eip1271_request();
+            sender.send(request).await.unwrap();
+            responses.push(response);
+        }
+        shutdown.stop().await;
+        handle.await.unwrap();
+
+        for response in responses {
+            response.await.unwrap().expect("Signature must be correct");
+        }
+        // New requests are rejected.
+        let (request, _) = eip1271_request();
+        assert!(sender.send(request).await.is_err());
+    }
+}
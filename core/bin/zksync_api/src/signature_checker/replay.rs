@@ -0,0 +1,342 @@
+//! Replay of the recorded signature check requests.
+//!
+//! Debug builds of the signature checker can append every taken request to a file
+//! (see `SignatureCheckerConfig::record_path`). Recorded requests can then be driven
+//! through the verification once again against the Ethereum state loaded from a snapshot,
+//! so the sequence of requests which triggered a bug can be reproduced in a test.
+
+// Built-in uses
+#[cfg(debug_assertions)]
+use std::fs::OpenOptions;
+#[cfg(debug_assertions)]
+use std::io::Write;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+// External uses
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+use zksync_types::{tx::error::TxAddError, TokenId};
+
+// Local uses
+use super::{
+    verify_batch_total_fee, verify_tx_signature, EthSignatureExemptions, RequestData,
+    ValidFromWindow, VerifiedTx,
+};
+use crate::eth_checker::EthereumChecker;
+
+/// Body of the signature check request serialized to JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedRequest(pub String);
+
+impl SerializedRequest {
+    pub fn new(request_data: &RequestData) -> serde_json::Result<Self> {
+        serde_json::to_string(request_data).map(Self)
+    }
+
+    pub fn request_data(&self) -> serde_json::Result<RequestData> {
+        serde_json::from_str(&self.0)
+    }
+}
+
+/// Appends the request to the file, one request per line.
+///
+/// Recording is best effort: the request is checked regardless of the result,
+/// so failures are only logged.
+#[cfg(debug_assertions)]
+pub fn record_to_file(path: &Path, request_data: &RequestData) {
+    let result = SerializedRequest::new(request_data)
+        .map_err(anyhow::Error::from)
+        .and_then(|request| {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            // The line is written at once, so the requests recorded by different
+            // checker threads are not mixed up.
+            file.write_all(format!("{}\n", request.0).as_bytes())?;
+            Ok(())
+        });
+    if let Err(err) = result {
+        vlog::warn!(
+            "Failed to record the signature check request to {}: {}",
+            path.display(),
+            err
+        );
+    }
+}
+
+/// Reads the requests recorded by `record_to_file`.
+pub fn read_from_file(path: &Path) -> io::Result<Vec<SerializedRequest>> {
+    let mut requests = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.is_empty() {
+            requests.push(SerializedRequest(line));
+        }
+    }
+    Ok(requests)
+}
+
+/// Result of the contract call stored in the `EthereumSnapshot`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotCallResult {
+    /// Name of the called contract function, e.g. `authFacts`.
+    pub function: String,
+    /// Block the result is returned at. If not set, the result is returned at every block
+    /// which has no result of its own.
+    pub block: Option<u64>,
+    pub result: Vec<u8>,
+}
+
+/// State of the Ethereum node the replayed requests are checked against.
+///
+/// Signature checker only calls the contract functions returning a single fixed-size
+/// byte array (`authFacts` and `isValidSignature`), so the results are stored as bytes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EthereumSnapshot {
+    pub block_number: u64,
+    pub call_results: Vec<SnapshotCallResult>,
+}
+
+impl EthereumSnapshot {
+    /// Creates the mock Ethereum client answering with the state from the snapshot.
+    pub async fn mock_client(&self) -> EthereumGateway {
+        let mut client = MockEthereum::default();
+        client
+            .set_block_number(self.block_number.into())
+            .await
+            .expect("Mock client never fails");
+        for call in &self.call_results {
+            let result = vec![ethabi::Token::FixedBytes(call.result.clone())];
+            match call.block {
+                Some(block) => {
+                    client
+                        .add_call_result_at_block(&call.function, block, result)
+                        .await
+                }
+                None => client.add_call_result(&call.function, result).await,
+            }
+        }
+        EthereumGateway::Mock(client)
+    }
+}
+
+/// Drives the recorded requests through the verification the same way the signature
+/// checker does, but one by one and without any channels involved, so the outcomes
+/// are deterministic.
+///
+/// `valid_from` of the transactions is not checked by default, since the requests are
+/// replayed long after they have been recorded. Use `with_valid_from_window` along with
+/// `ValidFromWindow::with_clock` to reproduce the check as of the recording time.
+#[derive(Clone)]
+pub struct SignatureCheckerReplay {
+    requests: Vec<SerializedRequest>,
+    eth_checker: EthereumChecker,
+    valid_from_window: ValidFromWindow,
+    eth_sig_exemptions: EthSignatureExemptions,
+    min_batch_total_fees: HashMap<TokenId, BigUint>,
+}
+
+impl SignatureCheckerReplay {
+    pub fn new(requests: Vec<SerializedRequest>, eth_checker: EthereumChecker) -> Self {
+        Self {
+            requests,
+            eth_checker,
+            valid_from_window: ValidFromWindow::default(),
+            eth_sig_exemptions: EthSignatureExemptions::default(),
+            min_batch_total_fees: HashMap::new(),
+        }
+    }
+
+    pub fn with_valid_from_window(mut self, valid_from_window: ValidFromWindow) -> Self {
+        self.valid_from_window = valid_from_window;
+        self
+    }
+
+    pub fn with_eth_sig_exemptions(mut self, eth_sig_exemptions: EthSignatureExemptions) -> Self {
+        self.eth_sig_exemptions = eth_sig_exemptions;
+        self
+    }
+
+    pub fn with_min_batch_total_fees(
+        mut self,
+        min_batch_total_fees: HashMap<TokenId, BigUint>,
+    ) -> Self {
+        self.min_batch_total_fees = min_batch_total_fees;
+        self
+    }
+
+    /// Verifies all the requests in the recorded order and returns their outcomes.
+    ///
+    /// Panics if any of the requests can't be deserialized.
+    pub async fn run_all(&self) -> Vec<Result<VerifiedTx, TxAddError>> {
+        let mut outcomes = Vec::with_capacity(self.requests.len());
+        for (index, request) in self.requests.iter().enumerate() {
+            let request_data = request
+                .request_data()
+                .unwrap_or_else(|err| panic!("Recorded request #{} is malformed: {}", index, err));
+            let outcome = match verify_batch_total_fee(&request_data, &self.min_batch_total_fees) {
+                Ok(()) => {
+                    verify_tx_signature(
+                        request_data,
+                        &self.eth_checker,
+                        &self.valid_from_window,
+                        &self.eth_sig_exemptions,
+                    )
+                    .await
+                }
+                Err(err) => Err(err),
+            };
+            outcomes.push(outcome);
+        }
+        outcomes
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use futures::{channel::mpsc, SinkExt};
+    use tokio::{runtime::Handle, sync::watch};
+    use zksync_types::{
+        tx::{ChangePubKeyType, EIP1271Signature, EthSignData, TxEthSignature},
+        Address, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+    use crate::eth_checker::EIP1271_SUCCESS_RETURN_VALUE;
+    use crate::signature_checker::{
+        start_sign_checker_on_handle,
+        tests::{eth_token, signed_transfer, test_account},
+        BatchRequest, SignatureCheckerConfig, Toggle2FARequest, TxRequest, TxVariant,
+        VerifySignatureRequest,
+    };
+
+    /// Comparable representation of the verification outcome.
+    fn outcome_summary(outcome: &Result<VerifiedTx, TxAddError>) -> String {
+        match outcome {
+            Ok(verified) => match &verified.inner {
+                TxVariant::Tx(tx) => format!("Ok({:?})", tx.hash()),
+                TxVariant::Batch(txs, _) => {
+                    let hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+                    format!("Ok({:?})", hashes)
+                }
+                TxVariant::Order(_) | TxVariant::Toggle2FA => "Ok".to_string(),
+            },
+            Err(err) => format!("Err({:?})", err),
+        }
+    }
+
+    fn requests() -> Vec<RequestData> {
+        let alice = test_account(1);
+        let bob = test_account(2);
+
+        let transfer = |tx: SignedZkSyncTx, sender: Address| {
+            RequestData::Tx(TxRequest::new(tx, sender, eth_token()))
+        };
+        let change_pubkey = alice.sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            0u64.into(),
+            ChangePubKeyType::Onchain,
+            Default::default(),
+        );
+        let toggle_2fa = Toggle2FARequest {
+            sign_data: EthSignData {
+                signature: TxEthSignature::EIP1271Signature(EIP1271Signature(vec![0; 65])),
+                message: "hello-world".into(),
+            },
+            sender: Address::repeat_byte(0x01),
+        };
+        let batch = BatchRequest {
+            txs: vec![signed_transfer(&alice), signed_transfer(&bob)],
+            batch_sign_data: None,
+            senders: vec![alice.address, bob.address],
+            tokens: vec![eth_token(); 2],
+            parties: Vec::new(),
+        };
+
+        vec![
+            transfer(signed_transfer(&alice), alice.address),
+            // Ethereum signature is made by another account.
+            transfer(signed_transfer(&bob), alice.address),
+            transfer(
+                SignedZkSyncTx::from(ZkSyncTx::ChangePubKey(Box::new(change_pubkey))),
+                alice.address,
+            ),
+            RequestData::Toggle2FA(toggle_2fa),
+            RequestData::Batch(batch),
+        ]
+    }
+
+    /// Checks that the requests recorded by the signature checker are replayed
+    /// with the same outcomes.
+    #[tokio::test]
+    async fn record_and_replay() {
+        let snapshot = EthereumSnapshot {
+            block_number: 10,
+            call_results: vec![
+                SnapshotCallResult {
+                    function: "authFacts".to_string(),
+                    block: None,
+                    // `authFacts` of the unknown account.
+                    result: vec![0; 32],
+                },
+                SnapshotCallResult {
+                    function: "isValidSignature".to_string(),
+                    block: None,
+                    result: EIP1271_SUCCESS_RETURN_VALUE.to_vec(),
+                },
+            ],
+        };
+        let record_path = std::env::temp_dir().join(format!(
+            "sign_checker_replay_{}_{}.jsonl",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos()
+        ));
+
+        // Record the requests checked by the running signature checker.
+        let (_client_sender, client) = watch::channel(snapshot.mock_client().await);
+        let (mut sender, input) = mpsc::channel(1);
+        let handle = start_sign_checker_on_handle(
+            client,
+            SignatureCheckerConfig {
+                record_path: Some(record_path.clone()),
+                ..Default::default()
+            },
+            input,
+            Handle::current(),
+        );
+        let mut recorded_outcomes = Vec::new();
+        for request_data in requests() {
+            let (request, response) = VerifySignatureRequest::new(request_data);
+            sender.send(request).await.unwrap();
+            recorded_outcomes.push(response.await.unwrap());
+        }
+        drop(sender);
+        handle.await.unwrap();
+
+        let recorded = read_from_file(&record_path).unwrap();
+        std::fs::remove_file(&record_path).unwrap();
+        assert_eq!(recorded.len(), 5);
+
+        // Replay them against the same Ethereum state.
+        let eth_checker = EthereumChecker::new(snapshot.mock_client().await);
+        let replayed_outcomes = SignatureCheckerReplay::new(recorded, eth_checker)
+            .run_all()
+            .await;
+
+        let recorded_outcomes: Vec<_> = recorded_outcomes.iter().map(outcome_summary).collect();
+        let replayed_outcomes: Vec<_> = replayed_outcomes.iter().map(outcome_summary).collect();
+        assert_eq!(recorded_outcomes, replayed_outcomes);
+        // Both the accepted and the rejected requests are replayed.
+        assert!(recorded_outcomes[0].starts_with("Ok"));
+        assert!(recorded_outcomes[1].starts_with("Err"));
+        assert!(recorded_outcomes[2].starts_with("Err(ChangePkNotAuthorized"));
+    }
+}
@@ -0,0 +1,667 @@
+//! Notifier posting the transaction lifecycle events to the webhooks registered via the REST API.
+//!
+//! The notifier reads the events stored by the core and creates the delivery of every event
+//! for each webhook subscribed to it. Deliveries are stored in the database, so the failed ones
+//! are retried with the exponential backoff even after the restart of the server.
+//!
+//! Every request is signed with the secret of the webhook: the `X-ZkSync-Signature` header
+//! holds the hex-encoded HMAC-SHA256 of the `{timestamp}.{body}` string, where the timestamp
+//! is passed in the `X-ZkSync-Timestamp` header. Only one notifier should be run at once,
+//! otherwise the events are delivered several times.
+
+// Built-in uses
+use std::{
+    convert::TryFrom,
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+// External uses
+use chrono::Utc;
+use hmac::{Hmac, Mac, NewMac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_api_types::v02::webhook::WebhookEventType;
+use zksync_config::configs::api::WebhooksConfig;
+use zksync_crypto::rand::{thread_rng, Rng};
+use zksync_storage::{
+    webhooks::records::{NewWebhookDelivery, PendingWebhookDelivery, StoredWebhook},
+    ConnectionPool,
+};
+use zksync_types::{
+    event::{
+        transaction::{TransactionStatus, TransactionType},
+        EventData, EventId, ZkSyncEvent,
+    },
+    AccountId,
+};
+
+// Local uses
+
+pub const SIGNATURE_HEADER: &str = "X-ZkSync-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-ZkSync-Timestamp";
+pub const DELIVERY_ID_HEADER: &str = "X-ZkSync-Delivery";
+
+/// Generates the new random secret of the webhook.
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = thread_rng().gen();
+    hex::encode(bytes)
+}
+
+/// Returns `true` if the address belongs to the loopback, private or link-local network,
+/// so the webhooks can't be used to reach the internal services. IPv4 addresses mapped
+/// to IPv6 are checked as IPv4 ones.
+pub fn is_local_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+        }
+        IpAddr::V6(ip) => {
+            let first_segment = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local addresses, `fc00::/7`.
+                || first_segment & 0xfe00 == 0xfc00
+                // Link-local addresses, `fe80::/10`.
+                || first_segment & 0xffc0 == 0xfe80
+                || ip
+                    .to_ipv4()
+                    .map_or(false, |ip| is_local_address(IpAddr::V4(ip)))
+        }
+    }
+}
+
+/// Returns the signature of the request body sent at the timestamp.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts the keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Returns the types of the webhook events corresponding to the network event.
+fn webhook_event_types(event: &ZkSyncEvent) -> Vec<WebhookEventType> {
+    let tx_event = match &event.data {
+        EventData::Transaction(tx_event) => tx_event,
+        _ => return Vec::new(),
+    };
+    let tx_type = tx_event.tx_type();
+    match tx_event.status {
+        TransactionStatus::Committed if tx_type == TransactionType::Deposit => vec![
+            WebhookEventType::TxCommitted,
+            WebhookEventType::DepositReceived,
+        ],
+        TransactionStatus::Committed => vec![WebhookEventType::TxCommitted],
+        TransactionStatus::Finalized
+            if matches!(
+                tx_type,
+                TransactionType::Withdraw
+                    | TransactionType::WithdrawNFT
+                    | TransactionType::ForcedExit
+                    | TransactionType::FullExit
+            ) =>
+        {
+            vec![
+                WebhookEventType::TxVerified,
+                WebhookEventType::WithdrawalFinalized,
+            ]
+        }
+        TransactionStatus::Finalized => vec![WebhookEventType::TxVerified],
+        TransactionStatus::Queued
+        | TransactionStatus::Rejected
+        | TransactionStatus::Replaced
+        | TransactionStatus::Dropped => Vec::new(),
+    }
+}
+
+/// Returns the account of the transaction event, `None` for the rest of the events.
+fn event_account_id(event: &ZkSyncEvent) -> Option<AccountId> {
+    match &event.data {
+        EventData::Transaction(tx_event) => Some(tx_event.account_id),
+        _ => None,
+    }
+}
+
+/// Body of the request to the webhook.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    event_id: u64,
+    event_type: WebhookEventType,
+    event: &'a ZkSyncEvent,
+}
+
+/// Webhook with the parsed subscription.
+#[derive(Debug, Clone)]
+struct Webhook {
+    id: i64,
+    event_types: Vec<WebhookEventType>,
+    account_id: Option<AccountId>,
+}
+
+impl Webhook {
+    fn is_subscribed(&self, event_type: WebhookEventType, account_id: Option<AccountId>) -> bool {
+        self.event_types.contains(&event_type)
+            && (self.account_id.is_none() || self.account_id == account_id)
+    }
+}
+
+impl From<StoredWebhook> for Webhook {
+    fn from(webhook: StoredWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            event_types: webhook.event_types(),
+            account_id: webhook.account_id.map(|id| AccountId(id as u32)),
+        }
+    }
+}
+
+/// Creates the deliveries of the events to the webhooks subscribed to them.
+fn deliveries(webhooks: &[Webhook], events: &[ZkSyncEvent]) -> Vec<NewWebhookDelivery> {
+    let mut deliveries = Vec::new();
+    for event in events {
+        let account_id = event_account_id(event);
+        for event_type in webhook_event_types(event) {
+            let payload = serde_json::to_value(WebhookPayload {
+                event_id: *event.id,
+                event_type,
+                event,
+            })
+            .expect("Event is serializable");
+            deliveries.extend(
+                webhooks
+                    .iter()
+                    .filter(|webhook| webhook.is_subscribed(event_type, account_id))
+                    .map(|webhook| NewWebhookDelivery {
+                        webhook_id: webhook.id,
+                        event_id: *event.id as i64,
+                        event_type: event_type.as_str().to_owned(),
+                        payload: payload.clone(),
+                    }),
+            );
+        }
+    }
+    deliveries
+}
+
+/// HTTP client posting the events to the webhooks.
+///
+/// Unless the insecure URLs are allowed, the host of the webhook is resolved before every
+/// delivery and the request is only sent if none of its addresses is local, see
+/// `is_local_address`. The request is then pinned to the checked address, so the host can't
+/// be rebound to a local address in between. Redirects are not followed, since they could
+/// lead to the unchecked URLs.
+struct WebhookClient {
+    client: reqwest::Client,
+    request_timeout: Duration,
+    allow_insecure_urls: bool,
+}
+
+impl WebhookClient {
+    fn new(config: &WebhooksConfig) -> Self {
+        let request_timeout = config.request_timeout();
+        Self {
+            client: Self::builder(request_timeout)
+                .build()
+                .expect("Failed to build the HTTP client"),
+            request_timeout,
+            allow_insecure_urls: config.allow_insecure_urls,
+        }
+    }
+
+    fn builder(request_timeout: Duration) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .timeout(request_timeout)
+            .redirect(reqwest::redirect::Policy::none())
+    }
+
+    /// Returns the client to post the event to the URL with, rejecting the URLs resolved
+    /// to the local addresses.
+    async fn client_for(&self, url: &str) -> Result<reqwest::Client, String> {
+        if self.allow_insecure_urls {
+            return Ok(self.client.clone());
+        }
+        let url = reqwest::Url::parse(url).map_err(|err| err.to_string())?;
+        let host = url.host_str().ok_or("Webhook URL has no host")?;
+        let port = url.port_or_known_default().unwrap_or(443);
+        let host_ip = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = host_ip.parse() {
+            // Literal addresses are not resolved, so there's nothing to pin.
+            return if is_local_address(ip) {
+                Err(format!("Webhook URL points to the local address {}", ip))
+            } else {
+                Ok(self.client.clone())
+            };
+        }
+
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|err| format!("Failed to resolve the webhook host {}: {}", host, err))?
+            .collect();
+        if let Some(addr) = addrs.iter().find(|addr| is_local_address(addr.ip())) {
+            return Err(format!(
+                "Webhook host {} resolves to the local address {}",
+                host,
+                addr.ip()
+            ));
+        }
+        let addr = *addrs
+            .first()
+            .ok_or_else(|| format!("Webhook host {} has no addresses", host))?;
+        Self::builder(self.request_timeout)
+            .resolve(host, addr)
+            .build()
+            .map_err(|err| err.to_string())
+    }
+
+    /// Posts the event to the webhook.
+    async fn post(&self, delivery: &PendingWebhookDelivery) -> Result<(), String> {
+        let client = self.client_for(&delivery.url).await?;
+        let body = delivery.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+        let response = client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(
+                SIGNATURE_HEADER,
+                sign_payload(&delivery.secret, timestamp, &body),
+            )
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(DELIVERY_ID_HEADER, delivery.id.to_string())
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Webhook responded with {}", response.status()))
+        }
+    }
+}
+
+struct WebhookNotifier {
+    connection_pool: ConnectionPool,
+    config: WebhooksConfig,
+    client: WebhookClient,
+}
+
+impl WebhookNotifier {
+    fn new(connection_pool: ConnectionPool, config: WebhooksConfig) -> Self {
+        Self {
+            connection_pool,
+            client: WebhookClient::new(&config),
+            config,
+        }
+    }
+
+    /// Creates the deliveries of the events stored since the last call.
+    async fn process_new_events(&self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_event_id = match storage
+            .webhooks_schema()
+            .get_last_processed_event_id()
+            .await?
+        {
+            Some(event_id) => event_id,
+            None => {
+                // Events emitted before the first start of the notifier are not delivered.
+                let last_event_id = storage
+                    .event_schema()
+                    .get_last_event_id()
+                    .await?
+                    .unwrap_or(EventId(0));
+                storage
+                    .webhooks_schema()
+                    .store_deliveries(&[], last_event_id)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let stored_events = storage
+            .event_schema()
+            .fetch_new_events(last_event_id)
+            .await?;
+        let new_last_event_id = match stored_events.last() {
+            Some(event) => EventId(event.id as u64),
+            None => return Ok(()),
+        };
+        let events: Vec<_> = stored_events
+            .into_iter()
+            .filter_map(|event| {
+                let event_id = event.id;
+                ZkSyncEvent::try_from(event)
+                    .map_err(|err| {
+                        vlog::warn!("Failed to deserialize the event #{}: {}", event_id, err);
+                    })
+                    .ok()
+            })
+            .collect();
+        let webhooks: Vec<_> = storage
+            .webhooks_schema()
+            .load_active_webhooks()
+            .await?
+            .into_iter()
+            .map(Webhook::from)
+            .collect();
+
+        let deliveries = deliveries(&webhooks, &events);
+        storage
+            .webhooks_schema()
+            .store_deliveries(&deliveries, new_last_event_id)
+            .await?;
+        Ok(())
+    }
+
+    /// Attempts the deliveries which are due, concurrently.
+    async fn send_pending_deliveries(&self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let pending = storage
+            .webhooks_schema()
+            .load_pending_deliveries(self.config.max_attempts, self.config.batch_size)
+            .await?;
+        let results =
+            futures::future::join_all(pending.iter().map(|delivery| self.client.post(delivery)))
+                .await;
+
+        for (delivery, result) in pending.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    storage
+                        .webhooks_schema()
+                        .mark_delivered(delivery.id)
+                        .await?;
+                    metrics::increment_counter!("webhooks.delivered", "event_type" => delivery.event_type.clone());
+                }
+                Err(err) => {
+                    let attempts = delivery.attempts as u32 + 1;
+                    if attempts >= self.config.max_attempts {
+                        vlog::warn!(
+                            "Delivery #{} to the webhook #{} is dropped after {} attempts: {}",
+                            delivery.id,
+                            delivery.webhook_id,
+                            attempts,
+                            err
+                        );
+                    }
+                    let backoff = chrono::Duration::from_std(self.config.retry_backoff(attempts))
+                        .expect("Retry backoff is bounded");
+                    storage
+                        .webhooks_schema()
+                        .mark_failed(delivery.id, &err, Utc::now() + backoff)
+                        .await?;
+                    metrics::increment_counter!("webhooks.failed", "event_type" => delivery.event_type.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn run(self) {
+        let mut timer = tokio::time::interval(self.config.poll_interval());
+        loop {
+            timer.tick().await;
+
+            let start = Instant::now();
+            if let Err(err) = self.process_new_events().await {
+                vlog::warn!("Failed to process the new events for the webhooks: {}", err);
+            }
+            if let Err(err) = self.send_pending_deliveries().await {
+                vlog::warn!("Failed to deliver the events to the webhooks: {}", err);
+            }
+            metrics::histogram!("webhooks.iteration", start.elapsed());
+        }
+    }
+}
+
+/// Runs the notifier posting the events to the webhooks. Connection pool has to access
+/// the main database, since the notifier stores the delivery state.
+#[must_use]
+pub fn run_webhook_notifier(
+    connection_pool: ConnectionPool,
+    config: WebhooksConfig,
+) -> JoinHandle<()> {
+    vlog::info!("Starting the webhook notifier");
+    tokio::spawn(WebhookNotifier::new(connection_pool, config).run())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use zksync_types::{event::transaction::TransactionEvent, BlockNumber, TokenId};
+
+    fn tx_event(id: u64, tx_type: &str, status: TransactionStatus) -> ZkSyncEvent {
+        let tx_event: TransactionEvent = serde_json::from_value(json!({
+            "tx_hash": "0x01",
+            "account_id": 1,
+            "token_id": 0,
+            "block_number": 1,
+            "tx": { "type": tx_type },
+            "status": status,
+            "fail_reason": null,
+            "created_at": Utc::now(),
+        }))
+        .unwrap();
+        assert_eq!(tx_event.token_id, TokenId(0));
+        ZkSyncEvent {
+            id: EventId(id),
+            block_number: BlockNumber(1),
+            data: EventData::Transaction(tx_event),
+        }
+    }
+
+    fn webhook(id: i64, event_types: &[WebhookEventType], account_id: Option<u32>) -> Webhook {
+        Webhook {
+            id,
+            event_types: event_types.to_vec(),
+            account_id: account_id.map(AccountId),
+        }
+    }
+
+    #[test]
+    fn event_types() {
+        use WebhookEventType::*;
+
+        let cases = [
+            ("Transfer", TransactionStatus::Committed, vec![TxCommitted]),
+            ("Transfer", TransactionStatus::Finalized, vec![TxVerified]),
+            (
+                "Deposit",
+                TransactionStatus::Committed,
+                vec![TxCommitted, DepositReceived],
+            ),
+            ("Deposit", TransactionStatus::Finalized, vec![TxVerified]),
+            ("Withdraw", TransactionStatus::Committed, vec![TxCommitted]),
+            (
+                "Withdraw",
+                TransactionStatus::Finalized,
+                vec![TxVerified, WithdrawalFinalized],
+            ),
+            (
+                "FullExit",
+                TransactionStatus::Finalized,
+                vec![TxVerified, WithdrawalFinalized],
+            ),
+            ("Transfer", TransactionStatus::Rejected, vec![]),
+        ];
+        for (tx_type, status, expected) in cases.iter() {
+            assert_eq!(
+                webhook_event_types(&tx_event(1, tx_type, *status)),
+                *expected,
+                "{} {:?}",
+                tx_type,
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn deliveries_of_subscribed_webhooks() {
+        let webhooks = [
+            webhook(1, &[WebhookEventType::DepositReceived], None),
+            webhook(2, &[WebhookEventType::TxCommitted], Some(2)),
+            webhook(
+                3,
+                &[
+                    WebhookEventType::TxCommitted,
+                    WebhookEventType::DepositReceived,
+                ],
+                Some(1),
+            ),
+        ];
+        let events = [
+            tx_event(5, "Deposit", TransactionStatus::Committed),
+            tx_event(6, "Transfer", TransactionStatus::Rejected),
+            tx_event(7, "Transfer", TransactionStatus::Finalized),
+        ];
+
+        let deliveries = deliveries(&webhooks, &events);
+        let delivered: Vec<_> = deliveries
+            .iter()
+            .map(|delivery| {
+                (
+                    delivery.webhook_id,
+                    delivery.event_id,
+                    delivery.event_type.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            delivered,
+            vec![
+                (3, 5, "tx_committed"),
+                (1, 5, "deposit_received"),
+                (3, 5, "deposit_received"),
+            ]
+        );
+        assert_eq!(deliveries[0].payload["eventId"], 5);
+        assert_eq!(deliveries[0].payload["eventType"], "tx_committed");
+        assert_eq!(deliveries[0].payload["event"]["type"], "transaction");
+    }
+
+    fn webhooks_config(allow_insecure_urls: bool) -> WebhooksConfig {
+        WebhooksConfig {
+            poll_interval_ms: 1000,
+            request_timeout_ms: 1000,
+            max_attempts: 3,
+            retry_backoff_ms: 1000,
+            max_retry_backoff_secs: 60,
+            batch_size: 10,
+            max_webhooks_per_api_key: 10,
+            allow_insecure_urls,
+        }
+    }
+
+    fn pending_delivery(url: String) -> PendingWebhookDelivery {
+        PendingWebhookDelivery {
+            id: 1,
+            webhook_id: 1,
+            url,
+            secret: "secret".to_owned(),
+            event_type: "tx_committed".to_owned(),
+            payload: json!({ "eventId": 1 }),
+            attempts: 0,
+        }
+    }
+
+    #[test]
+    fn local_addresses() {
+        for ip in &[
+            "127.0.0.1",
+            "10.1.2.3",
+            "169.254.169.254",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "febf::1",
+            "::ffff:127.0.0.1",
+            "::ffff:192.168.0.1",
+        ] {
+            assert!(is_local_address(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in &[
+            "8.8.8.8",
+            "2001:4860:4860::8888",
+            "fec0::1",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(!is_local_address(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    /// Checks that the event is not posted to the host resolved to the local address,
+    /// even though its name doesn't look local.
+    #[tokio::test]
+    async fn host_resolved_to_local_address() {
+        let client = WebhookClient::new(&webhooks_config(false));
+        for url in &[
+            "https://localhost:8443/hook",
+            "https://[fd00::1]/hook",
+            "https://[::ffff:10.0.0.1]/hook",
+        ] {
+            let err = client
+                .post(&pending_delivery(url.to_string()))
+                .await
+                .unwrap_err();
+            assert!(err.contains("local address"), "{}: {}", url, err);
+        }
+    }
+
+    /// Checks that the redirect response fails the delivery instead of being followed.
+    #[tokio::test]
+    async fn redirect_is_not_followed() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        let target = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target_addr = target.local_addr().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0_u8; 4096];
+            let read = stream.read(&mut request).await.unwrap();
+            assert!(read > 0);
+            let response = format!(
+                "HTTP/1.1 307 Temporary Redirect\r\nLocation: http://{}/hook\r\nContent-Length: 0\r\n\r\n",
+                target_addr
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        // Local addresses are only allowed to run the test without the external services.
+        let client = WebhookClient::new(&webhooks_config(true));
+        let err = client
+            .post(&pending_delivery(format!("http://{}/hook", addr)))
+            .await
+            .unwrap_err();
+        assert!(err.contains("307"), "{}", err);
+        let redirected = tokio::time::timeout(Duration::from_millis(100), target.accept()).await;
+        assert!(redirected.is_err(), "Redirect must not be followed");
+    }
+
+    #[test]
+    fn payload_signature() {
+        let body = r#"{"eventId":1}"#;
+        let signature = sign_payload("secret", 1_600_000_000, body);
+        assert_eq!(
+            signature,
+            "9c05e39e14010d40b749074080812cd0f7b0af385ce73635a1a53e8e51519f43"
+        );
+        assert_ne!(signature, sign_payload("other", 1_600_000_000, body));
+        assert_ne!(signature, sign_payload("secret", 1_600_000_001, body));
+    }
+}
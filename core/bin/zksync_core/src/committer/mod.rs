@@ -1,7 +1,10 @@
 // Built-in uses
 use std::time::{Duration, Instant};
 // External uses
-use futures::{channel::mpsc::Receiver, StreamExt};
+use futures::{
+    channel::mpsc::{Receiver, Sender},
+    SinkExt,
+};
 use serde::{Deserialize, Serialize};
 use tokio::{task::JoinHandle, time};
 
@@ -12,8 +15,9 @@ use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
-    AccountUpdates, BlockNumber,
+    AccountId, AccountUpdates, BlockNumber,
 };
+use zksync_utils::shutdown::ShutdownToken;
 
 mod aggregated_committer;
 
@@ -54,15 +58,21 @@ pub struct ExecutedOpsNotify {
 
 const PROOF_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: ConnectionPool) {
+async fn handle_new_commit_task(
+    mut rx_for_ops: Receiver<CommitRequest>,
+    pool: ConnectionPool,
+    mut committed_state_updates: Option<Sender<Vec<AccountId>>>,
+    shutdown: ShutdownToken,
+) {
     vlog::info!("Run committer");
     let mut token_db_cache = TokenDBCache::new(TOKEN_INVALIDATE_CACHE);
     token_db_cache
         .fill_token_cache(&mut pool.access_storage().await.unwrap())
         .await;
-    while let Some(request) = rx_for_ops.next().await {
+    while let Some(request) = shutdown.recv(&mut rx_for_ops).await {
         match request {
             CommitRequest::SealIncompleteBlock((block_commit_request, applied_updates_req)) => {
+                let updated_accounts = updated_account_ids(&applied_updates_req.account_updates);
                 seal_incomplete_block(
                     block_commit_request,
                     applied_updates_req,
@@ -70,9 +80,14 @@ async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: C
                     &mut token_db_cache,
                 )
                 .await;
+                notify_committed_state_updates(&mut committed_state_updates, updated_accounts)
+                    .await;
             }
             CommitRequest::PendingBlock((pending_block, applied_updates_req)) => {
+                let updated_accounts = updated_account_ids(&applied_updates_req.account_updates);
                 save_pending_block(pending_block, applied_updates_req, &pool).await;
+                notify_committed_state_updates(&mut committed_state_updates, updated_accounts)
+                    .await;
             }
             CommitRequest::FinishBlock(request) => {
                 finish_block(request, &pool).await;
@@ -82,6 +97,30 @@ async fn handle_new_commit_task(mut rx_for_ops: Receiver<CommitRequest>, pool: C
             }
         }
     }
+    vlog::info!("Committer is stopped");
+}
+
+fn updated_account_ids(account_updates: &AccountUpdates) -> Vec<AccountId> {
+    let mut account_ids: Vec<_> = account_updates
+        .iter()
+        .map(|(account_id, _)| *account_id)
+        .collect();
+    account_ids.sort_unstable();
+    account_ids.dedup();
+    account_ids
+}
+
+/// Sends the IDs of the accounts which committed state is saved to the API cache, so their
+/// outdated states are dropped from it.
+async fn notify_committed_state_updates(
+    sender: &mut Option<Sender<Vec<AccountId>>>,
+    account_ids: Vec<AccountId>,
+) {
+    if let Some(sender) = sender {
+        if !account_ids.is_empty() && sender.send(account_ids).await.is_err() {
+            vlog::warn!("Committed state cache is stopped, the updates are not sent to it");
+        }
+    }
 }
 
 async fn remove_reverted_block(block_number: BlockNumber, pool: &ConnectionPool) {
@@ -191,6 +230,17 @@ async fn seal_incomplete_block(
         .expect("committer must commit the pending block into db");
 
     vlog::info!("seal incomplete block #{}", block.block_number);
+    let tx_hashes: Vec<String> = block
+        .block_transactions
+        .iter()
+        .filter_map(|tx| tx.get_executed_tx())
+        .map(|tx| tx.signed_tx.hash().to_string())
+        .collect();
+    vlog::debug!(
+        "Transactions of the block #{}: {:?}",
+        block.block_number,
+        tx_hashes
+    );
 
     let block_number = block.block_number;
 
@@ -281,10 +331,17 @@ async fn finish_block(request: BlockFinishRequest, pool: &ConnectionPool) {
     metrics::histogram!("committer.finish_block", start.elapsed());
 }
 
-async fn poll_for_new_proofs_task(pool: ConnectionPool, config: ChainConfig) {
+async fn poll_for_new_proofs_task(
+    pool: ConnectionPool,
+    config: ChainConfig,
+    shutdown: ShutdownToken,
+) {
     let mut timer = time::interval(PROOF_POLL_INTERVAL);
     loop {
-        timer.tick().await;
+        tokio::select! {
+            _ = timer.tick() => {},
+            _ = shutdown.signalled() => break,
+        }
 
         let mut storage = pool
             .access_storage()
@@ -298,12 +355,21 @@ async fn poll_for_new_proofs_task(pool: ConnectionPool, config: ChainConfig) {
     }
 }
 
+/// Starts the committer. It has to be stopped after the state keeper and the root hash calculator,
+/// so the requests they have sent are handled.
 #[must_use]
 pub fn run_committer(
     rx_for_ops: Receiver<CommitRequest>,
     pool: ConnectionPool,
     config: ChainConfig,
+    committed_state_updates: Option<Sender<Vec<AccountId>>>,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
-    tokio::spawn(handle_new_commit_task(rx_for_ops, pool.clone()));
-    tokio::spawn(poll_for_new_proofs_task(pool, config))
+    tokio::spawn(handle_new_commit_task(
+        rx_for_ops,
+        pool.clone(),
+        committed_state_updates,
+        shutdown.clone(),
+    ));
+    tokio::spawn(poll_for_new_proofs_task(pool, config, shutdown))
 }
@@ -0,0 +1,103 @@
+// Built-in deps
+use std::{future::Future, time::Duration};
+// External uses
+use tokio::time;
+// Workspace deps
+use zksync_config::ETHWatchConfig;
+use zksync_storage::{misc::records::EthWatchBackfillProgress, ConnectionPool};
+
+/// Storage of the backfill progress, so the restarted watcher resumes the backfill
+/// instead of requesting the already processed blocks again.
+#[async_trait::async_trait]
+pub trait BackfillProgressStorage: Send + Sync {
+    async fn load_progress(&self) -> anyhow::Result<Option<EthWatchBackfillProgress>>;
+    async fn store_progress(&self, progress: EthWatchBackfillProgress) -> anyhow::Result<()>;
+}
+
+#[async_trait::async_trait]
+impl BackfillProgressStorage for ConnectionPool {
+    async fn load_progress(&self) -> anyhow::Result<Option<EthWatchBackfillProgress>> {
+        let mut storage = self.access_storage().await?;
+        storage
+            .misc_schema()
+            .load_eth_watch_backfill_progress()
+            .await
+    }
+
+    async fn store_progress(&self, progress: EthWatchBackfillProgress) -> anyhow::Result<()> {
+        let mut storage = self.access_storage().await?;
+        storage
+            .misc_schema()
+            .store_eth_watch_backfill_progress(progress)
+            .await
+    }
+}
+
+/// Parameters of the events backfill performed on startup.
+///
+/// Many providers reject `eth_getLogs` requests for the large block ranges,
+/// so the events are requested in chunks, and the failed requests are retried.
+pub struct Backfill {
+    storage: Box<dyn BackfillProgressStorage>,
+    chunk_size: u64,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl Backfill {
+    pub fn new(storage: Box<dyn BackfillProgressStorage>, config: &ETHWatchConfig) -> Self {
+        Self {
+            storage,
+            chunk_size: config.backfill_chunk_size.max(1),
+            max_retries: config.backfill_max_retries,
+            retry_delay: config.backfill_retry_delay(),
+        }
+    }
+
+    pub async fn load_progress(&self) -> anyhow::Result<Option<EthWatchBackfillProgress>> {
+        self.storage.load_progress().await
+    }
+
+    pub async fn store_progress(&self, progress: EthWatchBackfillProgress) -> anyhow::Result<()> {
+        self.storage.store_progress(progress).await
+    }
+
+    /// Splits the inclusive range of blocks into the inclusive chunks of at most `chunk_size` blocks.
+    pub fn chunks(&self, from_block: u64, to_block: u64) -> impl Iterator<Item = (u64, u64)> {
+        let chunk_size = self.chunk_size;
+        (from_block..=to_block)
+            .step_by(chunk_size as usize)
+            .map(move |chunk_from| {
+                let chunk_to = chunk_from.saturating_add(chunk_size - 1).min(to_block);
+                (chunk_from, chunk_to)
+            })
+    }
+
+    /// Performs the request, retrying it up to `max_retries` times if it fails.
+    pub async fn with_retries<T, F, Fut>(&self, mut request: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            match request().await {
+                Ok(result) => return Ok(result),
+                Err(error) if retries < self.max_retries => {
+                    retries += 1;
+                    vlog::warn!(
+                        "Failed to request the events from the Ethereum node: {}. \
+                         Retrying in {} ms (retry {}/{})",
+                        error,
+                        self.retry_delay.as_millis(),
+                        retries,
+                        self.max_retries
+                    );
+                    metrics::increment_counter!("eth_watcher.backfill_retry");
+                    time::sleep(self.retry_delay).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
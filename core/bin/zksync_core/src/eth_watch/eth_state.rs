@@ -94,10 +94,64 @@ impl ETHState {
         self.next_priority_op_id
     }
 
+    /// Makes sure the serial id of the next priority operation is not less than the given one,
+    /// e.g. if the preceding operations were received before the restart and aren't in the queue.
+    pub fn with_next_priority_op_id(mut self, next_priority_op_id: SerialId) -> Self {
+        self.next_priority_op_id = self.next_priority_op_id.max(next_priority_op_id);
+        self
+    }
+
     pub fn reset_last_ethereum_block(&mut self) {
         self.last_ethereum_block = self.last_ethereum_block_backup;
     }
 
+    /// Returns the state as it was after processing the given Ethereum block,
+    /// i.e. without any events received in the subsequent blocks.
+    ///
+    /// Used to roll the state back once the subsequent blocks are orphaned by the reorg,
+    /// so the events from the canonical chain are processed again.
+    pub fn reverted(&self, ethereum_block: u64) -> Self {
+        let ethereum_block = ethereum_block.min(self.last_ethereum_block);
+        let priority_queue: HashMap<_, _> = self
+            .priority_queue
+            .iter()
+            .filter(|(_, op)| op.as_ref().eth_block <= ethereum_block)
+            .map(|(serial_id, op)| (*serial_id, op.clone()))
+            .collect();
+        let next_priority_op_id = self
+            .priority_queue
+            .iter()
+            .filter(|(_, op)| op.as_ref().eth_block > ethereum_block)
+            .map(|(serial_id, _)| *serial_id)
+            .min()
+            .unwrap_or(self.next_priority_op_id);
+
+        Self {
+            last_ethereum_block: ethereum_block,
+            last_ethereum_block_backup: ethereum_block,
+            next_priority_op_id,
+            unconfirmed_queue: self
+                .unconfirmed_queue
+                .iter()
+                .filter(|op| op.eth_block <= ethereum_block)
+                .cloned()
+                .collect(),
+            priority_queue,
+            new_tokens: self
+                .new_tokens
+                .iter()
+                .filter(|token| token.eth_block_number <= ethereum_block)
+                .cloned()
+                .collect(),
+            register_nft_factory_events: self
+                .register_nft_factory_events
+                .iter()
+                .filter(|event| event.eth_block <= ethereum_block)
+                .cloned()
+                .collect(),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn last_ethereum_block_backup(&self) -> u64 {
         self.last_ethereum_block_backup
@@ -4,6 +4,14 @@
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
+//!
+//! Hashes of the recently processed blocks are tracked to detect the reorgs of the Ethereum chain.
+//! Once the processed blocks are orphaned, the state is rolled back to the last block still
+//! in the canonical chain and the events are processed again. The reorgs deeper than the number
+//! of confirmations are reported as errors, since they may have orphaned the accepted events.
+//!
+//! On startup, the events are backfilled in chunks of blocks configured by `ETH_WATCH_BACKFILL_CHUNK_SIZE`,
+//! and the backfill progress is persisted, so the restarted watcher resumes where it stopped.
 
 // Built-in deps
 use std::collections::HashMap;
@@ -12,7 +20,7 @@ use std::time::{Duration, Instant};
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
-    SinkExt, StreamExt,
+    SinkExt,
 };
 use thiserror::Error;
 
@@ -25,13 +33,20 @@ use zksync_config::{ContractsConfig, ETHWatchConfig};
 use zksync_crypto::params::PRIORITY_EXPIRATION;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::{misc::records::EthWatchBackfillProgress, ConnectionPool};
 use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
+use zksync_utils::shutdown::ShutdownToken;
 
 // Local deps
-use self::{client::EthClient, eth_state::ETHState, received_ops::sift_outdated_ops};
+use self::{
+    backfill::Backfill, client::EthClient, eth_state::ETHState, processed_blocks::ProcessedBlocks,
+    received_ops::sift_outdated_ops,
+};
 
+mod backfill;
 mod client;
 mod eth_state;
+mod processed_blocks;
 mod received_ops;
 
 #[cfg(test)]
@@ -83,6 +98,9 @@ pub struct EthWatch<W: EthClient> {
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
+    /// Hashes of the recently processed blocks, used to detect the reorgs.
+    processed_blocks: ProcessedBlocks,
+    backfill: Backfill,
     mode: WatcherMode,
 }
 
@@ -91,6 +109,8 @@ impl<W: EthClient> EthWatch<W> {
         client: W,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
         number_of_confirmations_for_event: u64,
+        reorg_detection_depth: u64,
+        backfill: Backfill,
     ) -> Self {
         Self {
             client,
@@ -98,6 +118,8 @@ impl<W: EthClient> EthWatch<W> {
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            processed_blocks: ProcessedBlocks::new(reorg_detection_depth),
+            backfill,
         }
     }
 
@@ -204,11 +226,13 @@ impl<W: EthClient> EthWatch<W> {
     }
 
     async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
-        let new_state = self
-            .update_eth_state(last_ethereum_block, PRIORITY_EXPIRATION)
-            .await?;
+        let last_block_hash = self.client.block_hash(last_ethereum_block).await?;
+        let new_state = self.backfill_eth_state(last_ethereum_block).await?;
 
         self.set_new_state(new_state);
+        if let Some(hash) = last_block_hash {
+            self.processed_blocks.add(last_ethereum_block, hash);
+        }
 
         vlog::debug!("ETH state: {:#?}", self.eth_state);
         Ok(())
@@ -264,29 +288,11 @@ impl<W: EthClient> EthWatch<W> {
         );
 
         // Add unconfirmed priority ops to queue
-        let (sender, receiver) = oneshot::channel();
-        self.mempool_tx_sender
-            .send(MempoolTransactionRequest::NewPriorityOps(
-                unconfirmed_queue.clone(),
-                false,
-                sender,
-            ))
+        self.send_priority_ops_to_mempool(unconfirmed_queue.clone(), false)
             .await?;
-
-        // TODO maybe retry? It can be the only problem is database
-        receiver.await.expect("Mempool actor was dropped")?;
         // Add confirmed priority ops to queue
-        let (sender, receiver) = oneshot::channel();
-        self.mempool_tx_sender
-            .send(MempoolTransactionRequest::NewPriorityOps(
-                priority_queue,
-                true,
-                sender,
-            ))
+        self.send_priority_ops_to_mempool(priority_queue, true)
             .await?;
-
-        // TODO maybe retry? It can be the only problem is database
-        receiver.await.expect("Mempool actor was dropped")?;
         // The backup block number is not used.
         let state = ETHState::new(
             current_ethereum_block,
@@ -299,6 +305,125 @@ impl<W: EthClient> EthWatch<W> {
         Ok(state)
     }
 
+    /// Restores the state by requesting the events of the last `PRIORITY_EXPIRATION` blocks in chunks.
+    ///
+    /// The progress is persisted after each chunk, so the priority operations
+    /// already stored in the mempool are not requested again after the restart.
+    async fn backfill_eth_state(
+        &mut self,
+        current_ethereum_block: u64,
+    ) -> anyhow::Result<ETHState> {
+        let last_block_with_accepted_events =
+            current_ethereum_block.saturating_sub(self.number_of_confirmations_for_event);
+        let first_block_with_accepted_events =
+            last_block_with_accepted_events.saturating_sub(PRIORITY_EXPIRATION);
+
+        let progress = self.backfill.load_progress().await?.filter(|progress| {
+            (first_block_with_accepted_events..=last_block_with_accepted_events)
+                .contains(&progress.last_processed_block)
+        });
+        let mut next_priority_op_id = match progress {
+            Some(progress) => {
+                vlog::info!(
+                    "Resuming the priority operations backfill after the Ethereum block {}",
+                    progress.last_processed_block
+                );
+                progress.next_priority_op_id
+            }
+            None => 0,
+        };
+
+        let mut priority_queue = HashMap::new();
+        let mut new_tokens = Vec::new();
+        let mut new_register_nft_factory_events = Vec::new();
+        let chunks = self.backfill.chunks(
+            first_block_with_accepted_events,
+            last_block_with_accepted_events,
+        );
+        for (chunk_from, chunk_to) in chunks {
+            let start = Instant::now();
+            let from = BlockNumber::Number(chunk_from.into());
+            let to = BlockNumber::Number(chunk_to.into());
+            let client = &self.client;
+
+            // Token and NFT factory events are not persisted by the watcher,
+            // so they're requested even for the chunks processed before the restart.
+            new_tokens.extend(
+                self.backfill
+                    .with_retries(|| client.get_new_tokens_events(from, to))
+                    .await?,
+            );
+            new_register_nft_factory_events.extend(
+                self.backfill
+                    .with_retries(|| client.get_new_register_nft_factory_events(from, to))
+                    .await?,
+            );
+
+            let is_processed = progress
+                .map(|progress| chunk_to <= progress.last_processed_block)
+                .unwrap_or(false);
+            if is_processed {
+                continue;
+            }
+
+            let priority_ops = self
+                .backfill
+                .with_retries(|| client.get_priority_op_events(from, to))
+                .await?;
+            for op in &priority_ops {
+                next_priority_op_id = next_priority_op_id.max(op.serial_id + 1);
+                priority_queue.insert(op.serial_id, op.clone().into());
+            }
+            self.send_priority_ops_to_mempool(priority_ops, true)
+                .await?;
+            self.backfill
+                .store_progress(EthWatchBackfillProgress {
+                    last_processed_block: chunk_to,
+                    next_priority_op_id,
+                })
+                .await?;
+
+            vlog::debug!(
+                "Backfilled the events: block_range=[{},{}]",
+                chunk_from,
+                chunk_to
+            );
+            metrics::histogram!("eth_watcher.backfill_chunk", start.elapsed());
+        }
+
+        let unconfirmed_queue = self.get_unconfirmed_ops(current_ethereum_block).await?;
+        self.send_priority_ops_to_mempool(unconfirmed_queue.clone(), false)
+            .await?;
+
+        let state = ETHState::new(
+            current_ethereum_block,
+            current_ethereum_block,
+            unconfirmed_queue,
+            priority_queue,
+            new_tokens,
+            new_register_nft_factory_events,
+        )
+        .with_next_priority_op_id(next_priority_op_id);
+        Ok(state)
+    }
+
+    async fn send_priority_ops_to_mempool(
+        &mut self,
+        ops: Vec<PriorityOp>,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::NewPriorityOps(
+                ops, confirmed, sender,
+            ))
+            .await?;
+
+        // TODO maybe retry? It can be the only problem is database
+        receiver.await.expect("Mempool actor was dropped")?;
+        Ok(())
+    }
+
     fn get_register_factory_event(
         &self,
         last_block_number: Option<u64>,
@@ -329,12 +454,120 @@ impl<W: EthClient> EthWatch<W> {
         new_tokens
     }
 
+    /// Finds the last processed block which is still in the canonical chain.
+    ///
+    /// Returns `None` if the last processed block wasn't orphaned, i.e. there was no reorg.
+    async fn find_reorg_common_block(&self) -> anyhow::Result<Option<u64>> {
+        let mut orphaned_block = None;
+        for &(number, hash) in self.processed_blocks.iter_rev() {
+            if self.client.block_hash(number).await? == Some(hash) {
+                return Ok(orphaned_block.map(|_| number));
+            }
+            orphaned_block = Some(number);
+        }
+
+        match orphaned_block {
+            Some(oldest_tracked_block) => {
+                // All the tracked blocks were orphaned, so we can't tell where the chains diverged.
+                // Replay the events starting from the oldest block which may contain
+                // the not yet expired priority operations.
+                let common_block = oldest_tracked_block.saturating_sub(PRIORITY_EXPIRATION);
+                vlog::error!(
+                    "All the tracked Ethereum blocks starting from {} were orphaned by the reorg, \
+                     replaying the events starting from the block {}",
+                    oldest_tracked_block,
+                    common_block
+                );
+                Ok(Some(common_block))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Rolls the state back to the given block after the subsequent blocks were orphaned by the reorg.
+    async fn revert_to_block(&mut self, common_block: u64) -> anyhow::Result<()> {
+        let last_processed_block = self.eth_state.last_ethereum_block();
+        let reorg_depth = last_processed_block.saturating_sub(common_block);
+        if reorg_depth > self.number_of_confirmations_for_event {
+            vlog::error!(
+                "Deep Ethereum reorg detected: blocks after {} were orphaned ({} blocks, \
+                 while {} confirmations are required for the events)",
+                common_block,
+                reorg_depth,
+                self.number_of_confirmations_for_event
+            );
+            metrics::increment_counter!("eth_watcher.deep_reorg");
+        } else {
+            vlog::warn!(
+                "Ethereum reorg detected: blocks after {} were orphaned ({} blocks)",
+                common_block,
+                reorg_depth
+            );
+        }
+        metrics::histogram!("eth_watcher.reorg_depth", reorg_depth as f64);
+
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::RevertPriorityOps(
+                common_block,
+                sender,
+            ))
+            .await?;
+        let last_executed_priority_op = receiver.await.expect("Mempool actor was dropped")?;
+
+        let orphaned_executed_ops: Vec<_> = self
+            .eth_state
+            .priority_queue()
+            .iter()
+            .filter(|(serial_id, op)| {
+                op.as_ref().eth_block > common_block
+                    && last_executed_priority_op
+                        .map(|last_executed| **serial_id <= last_executed)
+                        .unwrap_or(false)
+            })
+            .map(|(serial_id, _)| *serial_id)
+            .sorted()
+            .collect();
+        if !orphaned_executed_ops.is_empty() {
+            // The executed operations can't be reverted automatically,
+            // node administrators have to handle the situation.
+            vlog::error!(
+                "Priority operations {:?} were executed, but their Ethereum blocks were orphaned by the reorg",
+                orphaned_executed_ops
+            );
+            metrics::counter!(
+                "eth_watcher.orphaned_executed_priority_ops",
+                orphaned_executed_ops.len() as u64
+            );
+        }
+
+        let new_state = self.eth_state.reverted(common_block);
+        self.set_new_state(new_state);
+        self.processed_blocks.remove_from(common_block + 1);
+        Ok(())
+    }
+
     async fn poll_eth_node(&mut self) -> anyhow::Result<()> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
 
+        let is_reorg_possible = self
+            .processed_blocks
+            .last_block_number()
+            .map(|last_processed| last_block_number >= last_processed)
+            .unwrap_or(false);
+        if is_reorg_possible {
+            if let Some(common_block) = self.find_reorg_common_block().await? {
+                self.revert_to_block(common_block).await?;
+            }
+        }
+
         if last_block_number > self.eth_state.last_ethereum_block() {
+            let last_block_hash = self.client.block_hash(last_block_number).await?;
             self.process_new_blocks(last_block_number).await?;
+            if let Some(hash) = last_block_hash {
+                self.processed_blocks.add(last_block_number, hash);
+            }
         }
 
         metrics::histogram!("eth_watcher.poll_eth_node", start.elapsed());
@@ -402,8 +635,12 @@ impl<W: EthClient> EthWatch<W> {
             .expect("Unable to restore ETHWatcher state");
     }
 
-    pub async fn run(mut self, mut eth_watch_req: mpsc::Receiver<EthWatchRequest>) {
-        while let Some(request) = eth_watch_req.next().await {
+    pub async fn run(
+        mut self,
+        mut eth_watch_req: mpsc::Receiver<EthWatchRequest>,
+        shutdown: ShutdownToken,
+    ) {
+        while let Some(request) = shutdown.recv(&mut eth_watch_req).await {
             match request {
                 EthWatchRequest::PollETHNode => {
                     if !self.polling_allowed() {
@@ -456,6 +693,8 @@ pub async fn start_eth_watch(
     contract_config: &ContractsConfig,
     eth_watcher_config: &ETHWatchConfig,
     mempool_req_sender: mpsc::Sender<MempoolTransactionRequest>,
+    connection_pool: ConnectionPool,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     let eth_client = EthHttpClient::new(
         eth_gateway,
@@ -467,23 +706,32 @@ pub async fn start_eth_watch(
         eth_client,
         mempool_req_sender,
         eth_watcher_config.confirmations_for_eth_event,
+        eth_watcher_config.reorg_detection_depth,
+        Backfill::new(Box::new(connection_pool), eth_watcher_config),
     );
 
     eth_watch.restore_from_eth_using_latest_block_number().await;
 
-    tokio::spawn(eth_watch.run(eth_req_receiver));
+    tokio::spawn(eth_watch.run(eth_req_receiver, shutdown.clone()));
 
     let poll_interval = eth_watcher_config.poll_interval();
     tokio::spawn(async move {
         let mut timer = time::interval(poll_interval);
 
         loop {
-            timer.tick().await;
-            eth_req_sender
+            tokio::select! {
+                _ = timer.tick() => {},
+                _ = shutdown.signalled() => break,
+            }
+            let sent = eth_req_sender
                 .clone()
                 .send(EthWatchRequest::PollETHNode)
-                .await
-                .expect("ETH watch receiver dropped");
+                .await;
+            // Watcher may have been stopped right after the timer has ticked.
+            if sent.is_err() && shutdown.is_signalled() {
+                break;
+            }
+            sent.expect("ETH watch receiver dropped");
         }
     })
 }
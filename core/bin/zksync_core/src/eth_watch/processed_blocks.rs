@@ -0,0 +1,57 @@
+// Built-in deps
+use std::collections::VecDeque;
+// Workspace deps
+use zksync_types::H256;
+
+/// Hashes of the Ethereum blocks recently processed by the watcher.
+///
+/// The blocks are checked to still be in the canonical chain before processing the new ones,
+/// so the reorgs orphaning the processed blocks are detected. Only the blocks within the
+/// `depth` from the last processed one are kept.
+#[derive(Debug, Default)]
+pub struct ProcessedBlocks {
+    /// Numbers and hashes of the processed blocks, in the ascending order.
+    blocks: VecDeque<(u64, H256)>,
+    depth: u64,
+}
+
+impl ProcessedBlocks {
+    pub fn new(depth: u64) -> Self {
+        Self {
+            blocks: VecDeque::new(),
+            depth,
+        }
+    }
+
+    /// Adds the processed block, replacing the blocks with the same or greater numbers.
+    pub fn add(&mut self, number: u64, hash: H256) {
+        self.remove_from(number);
+        self.blocks.push_back((number, hash));
+        while let Some((oldest, _)) = self.blocks.front() {
+            if oldest + self.depth >= number {
+                break;
+            }
+            self.blocks.pop_front();
+        }
+    }
+
+    /// Removes the blocks with the numbers greater than or equal to the given one.
+    pub fn remove_from(&mut self, number: u64) {
+        while let Some((last, _)) = self.blocks.back() {
+            if *last < number {
+                break;
+            }
+            self.blocks.pop_back();
+        }
+    }
+
+    /// Returns the number of the last processed block, if any.
+    pub fn last_block_number(&self) -> Option<u64> {
+        self.blocks.back().map(|(number, _)| *number)
+    }
+
+    /// Iterates over the processed blocks starting from the last one.
+    pub fn iter_rev(&self) -> impl Iterator<Item = &(u64, H256)> {
+        self.blocks.iter().rev()
+    }
+}
@@ -12,22 +12,56 @@ use zksync_types::{
 use futures::channel::mpsc;
 use futures::StreamExt;
 use tokio::sync::RwLock;
+use zksync_config::ETHWatchConfig;
 use zksync_mempool::MempoolTransactionRequest;
+use zksync_storage::misc::records::EthWatchBackfillProgress;
 
 use super::is_missing_priority_op_error;
-use crate::eth_watch::{client::EthClient, EthWatch};
+use crate::eth_watch::{
+    backfill::{Backfill, BackfillProgressStorage},
+    client::EthClient,
+    EthWatch,
+};
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
+    /// Hashes of the blocks replaced by the reorgs, the other blocks have hashes equal to their numbers.
+    reorged_block_hashes: HashMap<u64, H256>,
     last_block_number: u64,
+    /// Amount of the subsequent requests for the priority operations that will fail.
+    failing_requests: u32,
 }
 
 impl FakeEthClientData {
     fn new() -> Self {
         Self {
             priority_ops: Default::default(),
+            reorged_block_hashes: Default::default(),
             last_block_number: 0,
+            failing_requests: 0,
+        }
+    }
+
+    fn block_hash(&self, block_number: u64) -> Option<H256> {
+        if block_number > self.last_block_number {
+            return None;
         }
+        let hash = self
+            .reorged_block_hashes
+            .get(&block_number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(block_number));
+        Some(hash)
+    }
+
+    fn reorg(&mut self, first_orphaned_block: u64, ops: &[PriorityOp]) {
+        self.priority_ops
+            .retain(|&block_number, _| block_number < first_orphaned_block);
+        for block_number in first_orphaned_block..=self.last_block_number {
+            let hash = H256::from_low_u64_be(block_number | (1 << 63));
+            self.reorged_block_hashes.insert(block_number, hash);
+        }
+        self.add_operations(ops);
     }
 
     fn add_operations(&mut self, ops: &[PriorityOp]) {
@@ -70,6 +104,15 @@ impl FakeEthClient {
         let mut inner = self.inner.write().await;
         inner.last_block_number = block_number;
     }
+
+    async fn fail_requests(&mut self, failing_requests: u32) {
+        self.inner.write().await.failing_requests = failing_requests;
+    }
+
+    /// Replaces the blocks starting from `first_orphaned_block` with the new ones containing `ops`.
+    async fn reorg(&mut self, first_orphaned_block: u64, ops: &[PriorityOp]) {
+        self.inner.write().await.reorg(first_orphaned_block, ops);
+    }
 }
 
 #[async_trait::async_trait]
@@ -79,6 +122,13 @@ impl EthClient for FakeEthClient {
         from: BlockNumber,
         to: BlockNumber,
     ) -> Result<Vec<PriorityOp>, anyhow::Error> {
+        {
+            let mut inner = self.inner.write().await;
+            if inner.failing_requests > 0 {
+                inner.failing_requests -= 1;
+                anyhow::bail!("query timeout exceeded");
+            }
+        }
         let from = self.block_to_number(&from).await;
         let to = self.block_to_number(&to).await;
         let mut operations = vec![];
@@ -111,6 +161,10 @@ impl EthClient for FakeEthClient {
         Ok(self.inner.read().await.last_block_number)
     }
 
+    async fn block_hash(&self, block_number: u64) -> anyhow::Result<Option<H256>> {
+        Ok(self.inner.read().await.block_hash(block_number))
+    }
+
     async fn get_auth_fact(
         &self,
         _address: Address,
@@ -128,11 +182,58 @@ impl EthClient for FakeEthClient {
     }
 }
 
+#[derive(Clone, Default)]
+struct FakeBackfillProgressStorage {
+    progress: Arc<RwLock<Option<EthWatchBackfillProgress>>>,
+}
+
+#[async_trait::async_trait]
+impl BackfillProgressStorage for FakeBackfillProgressStorage {
+    async fn load_progress(&self) -> anyhow::Result<Option<EthWatchBackfillProgress>> {
+        Ok(*self.progress.read().await)
+    }
+
+    async fn store_progress(&self, progress: EthWatchBackfillProgress) -> anyhow::Result<()> {
+        *self.progress.write().await = Some(progress);
+        Ok(())
+    }
+}
+
 fn create_watcher<T: EthClient>(
     client: T,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 ) -> EthWatch<T> {
-    EthWatch::new(client, mempool_tx_sender, 1)
+    create_watcher_with_backfill_storage(
+        client,
+        mempool_tx_sender,
+        FakeBackfillProgressStorage::default(),
+    )
+}
+
+fn create_watcher_with_backfill_storage<T: EthClient>(
+    client: T,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    backfill_storage: FakeBackfillProgressStorage,
+) -> EthWatch<T> {
+    let config = ETHWatchConfig {
+        confirmations_for_eth_event: 1,
+        eth_node_poll_interval: 100,
+        reorg_detection_depth: 16,
+        backfill_chunk_size: 2,
+        backfill_max_retries: 2,
+        backfill_retry_delay: 0,
+        priority_op_watchdog_interval: 100,
+        priority_op_warning_blocks: 100,
+        priority_op_emergency_blocks: 10,
+    };
+    let backfill = Backfill::new(Box::new(backfill_storage), &config);
+    EthWatch::new(
+        client,
+        mempool_tx_sender,
+        config.confirmations_for_eth_event,
+        config.reorg_detection_depth,
+        backfill,
+    )
 }
 
 async fn fake_mempool(
@@ -151,7 +252,12 @@ async fn fake_mempool(
                 }
                 channel.send(Ok(())).unwrap_or_default()
             }
-            MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::RevertPriorityOps(eth_block, channel) => {
+                let mut lock = data.write().await;
+                lock.retain(|_, (op, _)| op.eth_block <= eth_block);
+                channel.send(Ok(None)).unwrap_or_default()
+            }
+            _ => unreachable!(),
         }
     }
 }
@@ -546,3 +652,158 @@ async fn test_serial_id_gaps() {
     assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 0);
     assert_eq!(watcher.eth_state.last_ethereum_block(), 3);
 }
+
+/// Checks that the reorg orphaning the processed blocks is detected,
+/// and the priority operations are replayed from the canonical chain.
+#[tokio::test]
+async fn test_reorg_is_reverted() {
+    let (sender, receiver) = mpsc::channel(10);
+    let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+        from: Default::default(),
+        token: TokenId(0),
+        amount: Default::default(),
+        to: [2u8; 20].into(),
+    });
+
+    let data = Arc::new(RwLock::new(HashMap::new()));
+    tokio::spawn(fake_mempool(receiver, data.clone()));
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[
+            PriorityOp {
+                serial_id: 0,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [2; 32].into(),
+                eth_block: 1,
+                eth_block_index: Some(1),
+            },
+            PriorityOp {
+                serial_id: 1,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [3; 32].into(),
+                eth_block: 3,
+                eth_block_index: Some(1),
+            },
+        ])
+        .await;
+
+    let mut watcher = create_watcher(client.clone(), sender);
+    client.set_last_block_number(2).await;
+    watcher.poll_eth_node().await.unwrap();
+    client.set_last_block_number(4).await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.priority_queue().len(), 2);
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 2);
+
+    // Blocks starting from the 3rd one are orphaned, and the operation is included in the 5th block instead.
+    client
+        .reorg(
+            3,
+            &[PriorityOp {
+                serial_id: 1,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [4; 32].into(),
+                eth_block: 5,
+                eth_block_index: Some(1),
+            }],
+        )
+        .await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 5);
+    assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 2);
+
+    // The orphaned operation isn't confirmed anymore.
+    let priority_queue = watcher.eth_state.priority_queue();
+    assert_eq!(priority_queue.len(), 1);
+    priority_queue.get(&0).unwrap();
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 1);
+    let unconfirmed_queue = watcher.eth_state.unconfirmed_queue();
+    assert_eq!(unconfirmed_queue.len(), 1);
+    assert_eq!(unconfirmed_queue[0].eth_block, 5);
+
+    let reader = data.read().await;
+    let (op, confirmed) = reader.get(&1).unwrap();
+    assert_eq!(op.eth_hash, H256::from_slice(&[4u8; 32]));
+    assert!(!confirmed);
+}
+
+/// Checks that the backfill resumes after the persisted progress and retries the failed requests.
+#[tokio::test]
+async fn test_backfill_resumes_from_progress() {
+    let (sender, receiver) = mpsc::channel(10);
+    let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+        from: Default::default(),
+        token: TokenId(0),
+        amount: Default::default(),
+        to: [2u8; 20].into(),
+    });
+
+    let data = Arc::new(RwLock::new(HashMap::new()));
+    tokio::spawn(fake_mempool(receiver, data.clone()));
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[
+            PriorityOp {
+                serial_id: 0,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [2; 32].into(),
+                eth_block: 1,
+                eth_block_index: Some(1),
+            },
+            PriorityOp {
+                serial_id: 1,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [3; 32].into(),
+                eth_block: 5,
+                eth_block_index: Some(1),
+            },
+            PriorityOp {
+                serial_id: 2,
+                data: deposit.clone(),
+                deadline_block: 0,
+                eth_hash: [4; 32].into(),
+                eth_block: 9,
+                eth_block_index: Some(1),
+            },
+        ])
+        .await;
+    client.set_last_block_number(10).await;
+    client.fail_requests(2).await;
+
+    // The operations up to the 6th block were backfilled before the restart.
+    let backfill_storage = FakeBackfillProgressStorage::default();
+    backfill_storage
+        .store_progress(EthWatchBackfillProgress {
+            last_processed_block: 6,
+            next_priority_op_id: 2,
+        })
+        .await
+        .unwrap();
+
+    let mut watcher =
+        create_watcher_with_backfill_storage(client.clone(), sender, backfill_storage.clone());
+    watcher.restore_state_from_eth(10).await.unwrap();
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 10);
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 3);
+    let priority_queue = watcher.eth_state.priority_queue();
+    assert_eq!(priority_queue.len(), 1);
+    priority_queue.get(&2).unwrap();
+
+    // The operations from the processed blocks are not requested again.
+    let reader = data.read().await;
+    assert_eq!(reader.len(), 1);
+    assert!(reader.get(&2).unwrap().1);
+
+    assert_eq!(
+        backfill_storage.load_progress().await.unwrap(),
+        Some(EthWatchBackfillProgress {
+            last_processed_block: 9,
+            next_priority_op_id: 3,
+        })
+    );
+}
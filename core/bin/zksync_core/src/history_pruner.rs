@@ -0,0 +1,69 @@
+//! The pruner is responsible for removing the history of the old blocks from the database,
+//! so the nodes don't have to store the full history which takes most of the disk space.
+//!
+//! Only the executed transactions, events and witnesses of the blocks older than the configured
+//! amount of the latest verified blocks are removed. The data required to continue the operation
+//! and to build the exodus proofs (blocks, accounts, priority operations) is kept.
+
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+/// Maximum amount of blocks pruned in a single database transaction,
+/// so the tables aren't locked by a huge removal for a long time.
+const PRUNING_CHUNK_BLOCKS: u32 = 100;
+
+/// Runs the pruner if the history retention is configured.
+#[must_use]
+pub fn run_history_pruner(config: &DBConfig, db_pool: ConnectionPool) -> Option<JoinHandle<()>> {
+    let retention_blocks = config.history_retention_blocks?;
+    let mut timer = time::interval(config.history_pruner_interval());
+
+    Some(tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = prune_history(&db_pool, retention_blocks).await {
+                vlog::error!("Can't prune the history of the old blocks {:?}", e);
+            }
+        }
+    }))
+}
+
+async fn prune_history(db_pool: &ConnectionPool, retention_blocks: u32) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    let target_block = BlockNumber(last_verified_block.saturating_sub(retention_blocks));
+
+    let mut last_pruned_block = storage
+        .pruning_schema()
+        .load_last_pruned_block()
+        .await?
+        .unwrap_or(BlockNumber(0));
+    while last_pruned_block < target_block {
+        let to_block = BlockNumber((*last_pruned_block + PRUNING_CHUNK_BLOCKS).min(*target_block));
+        let pruned = storage.pruning_schema().prune_history(to_block).await?;
+        vlog::info!(
+            "Pruned the history of the blocks up to {}: {} transactions, {} events, {} witnesses",
+            *to_block,
+            pruned.transactions,
+            pruned.events,
+            pruned.witnesses
+        );
+        metrics::counter!("history_pruner.transactions", pruned.transactions);
+        metrics::counter!("history_pruner.events", pruned.events);
+        metrics::counter!("history_pruner.witnesses", pruned.witnesses);
+        metrics::gauge!("history_pruner.last_pruned_block", *to_block as f64);
+
+        last_pruned_block = to_block;
+    }
+
+    Ok(())
+}
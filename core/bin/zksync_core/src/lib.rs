@@ -3,24 +3,33 @@ use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
     committer::run_committer,
     eth_watch::start_eth_watch,
-    state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
+    priority_op_watchdog::run_priority_op_watchdog,
+    state_keeper::{
+        start_root_hash_calculator, start_state_keeper, BlockSealPolicy, BlockSealRequest,
+        ZkSyncStateKeeper,
+    },
     token_handler::run_token_handler,
 };
 use futures::{channel::mpsc, future};
 use tokio::task::JoinHandle;
 use zksync_config::{ChainConfig, ZkSyncConfig};
 use zksync_eth_client::EthereumGateway;
-use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler};
+use zksync_health_check::{HealthChecks, Heartbeat};
+use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler, MempoolLimits};
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
+use zksync_types::{tokens::get_genesis_token_list, AccountId, Token, TokenId, TokenKind};
+use zksync_utils::shutdown::{Shutdown, ShutdownToken};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod committer;
 pub mod eth_watch;
+pub mod history_pruner;
+pub mod priority_op_watchdog;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
+pub mod stats_aggregator;
 pub mod token_handler;
 pub mod tx_event_emitter;
 
@@ -86,13 +95,29 @@ pub async fn genesis_init(config: &ChainConfig) {
 /// - mempool, module to organize incoming transactions.
 /// - block proposer, module to create block proposals for state keeper.
 /// - committer, module to store pending and completed blocks into the database.
+/// - priority operations watchdog, module to keep the priority operations from expiring.
 /// - private Core API server.
+///
+/// The health checks of the started components are added to `health_checks`.
+/// If `committed_state_updates` is set, the committer sends the IDs of the accounts which committed
+/// state is saved to it.
+/// Once `shutdown` is signalled, the state keeper finishes the current miniblock and the committer
+/// saves the blocks it has received, then the rest of the components stop.
 pub async fn run_core(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     config: &ZkSyncConfig,
     eth_gateway: EthereumGateway,
+    health_checks: &mut HealthChecks,
+    committed_state_updates: Option<mpsc::Sender<Vec<AccountId>>>,
+    shutdown: ShutdownToken,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
+    // Components are stopped in the order the requests flow between them, so every component
+    // handles the requests sent by the ones stopped before it.
+    let producers_shutdown = Shutdown::new();
+    let consumers_shutdown = Shutdown::new();
+    let mempool_shutdown = Shutdown::new();
+
     let (proposed_blocks_sender, proposed_blocks_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
     let (eth_watch_req_sender, eth_watch_req_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
@@ -108,6 +133,8 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        MempoolLimits::from_config(&config.chain.state_keeper),
+        mempool_shutdown.token(),
     );
 
     // Run health check api for core
@@ -126,6 +153,8 @@ pub async fn run_core(
         &config.contracts,
         &config.eth_watch,
         mempool_tx_request_sender.clone(),
+        connection_pool.clone(),
+        consumers_shutdown.token(),
     )
     .await;
 
@@ -137,34 +166,49 @@ pub async fn run_core(
         &mut storage_processor,
         config.chain.state_keeper.fee_account_addr,
         &config.chain.state_keeper.block_chunk_sizes,
+        config.chain.state_keeper.tree_cache_path.as_deref(),
     )
     .await;
 
+    // Watchdog seals the pending block if its priority operations are about to expire.
+    let seal_request = BlockSealRequest::new();
+    let seal_policy = BlockSealPolicy {
+        seal_request: Some(seal_request.clone()),
+        ..BlockSealPolicy::from_config(&config.chain.state_keeper)
+    };
     let (mut state_keeper, root_hash_calculator) = ZkSyncStateKeeper::new(
         state_keeper_init,
         config.chain.state_keeper.fee_account_addr,
-        proposed_blocks_sender,
+        proposed_blocks_sender.clone(),
         mempool_block_request_sender,
         config.chain.state_keeper.block_chunk_sizes.clone(),
         config.chain.state_keeper.miniblock_iterations as usize,
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
+        config.chain.state_keeper.nonce_gap_timeout(),
+        seal_policy,
         processed_tx_events_sender,
     );
 
     // Execute reverted blocks before start
     state_keeper.execute_reverted_blocks().await;
 
+    let state_keeper_heartbeat = Heartbeat::default();
     let state_keeper_task = start_state_keeper(
         state_keeper,
         config.chain.state_keeper.miniblock_iteration_interval(),
+        state_keeper_heartbeat.clone(),
+        producers_shutdown.token(),
     );
-    let root_hash_calculator_task = start_root_hash_calculator(root_hash_calculator);
+    let root_hash_calculator_task =
+        start_root_hash_calculator(root_hash_calculator, producers_shutdown.token());
 
     // Start committer.
     let committer_task = run_committer(
         proposed_blocks_receiver,
         connection_pool.clone(),
         config.chain.clone(),
+        committed_state_updates,
+        consumers_shutdown.token(),
     );
 
     // Start mempool.
@@ -172,6 +216,8 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_block_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.state_keeper.mempool_tx_ordering,
+        config.chain.state_keeper.mempool_tx_ttl(),
     );
 
     // Start token handler.
@@ -180,6 +226,7 @@ pub async fn run_core(
         eth_gateway.clone(),
         &config.token_handler,
         eth_watch_req_sender.clone(),
+        producers_shutdown.token(),
     );
 
     // Start token handler.
@@ -187,12 +234,38 @@ pub async fn run_core(
         connection_pool.clone(),
         eth_watch_req_sender.clone(),
         config.token_handler.clone(),
+        producers_shutdown.token(),
+    );
+
+    let priority_op_watchdog_task = run_priority_op_watchdog(
+        &config.eth_watch,
+        connection_pool.clone(),
+        eth_gateway.clone(),
+        seal_request,
+        producers_shutdown.token(),
     );
 
     let tx_event_emitter_task = tx_event_emitter::run_tx_event_emitter_task(
         connection_pool.clone(),
         processed_tx_events_receiver,
+        consumers_shutdown.token(),
+    );
+    shutdown.propagate(vec![
+        producers_shutdown,
+        consumers_shutdown,
+        mempool_shutdown,
+    ]);
+
+    health_checks.add_database("core.database", connection_pool.clone());
+    health_checks.add_web3("core.web3", eth_gateway.clone());
+    health_checks.add_heartbeat(
+        "core.state_keeper",
+        state_keeper_heartbeat,
+        config.api.health_check.heartbeat_timeout(),
     );
+    health_checks.add_channel("core.mempool", mempool_tx_request_sender);
+    health_checks.add_channel("core.eth_watch", eth_watch_req_sender);
+    health_checks.add_channel("core.committer", proposed_blocks_sender);
 
     let task_futures = vec![
         eth_watch_task,
@@ -201,6 +274,7 @@ pub async fn run_core(
         committer_task,
         token_handler_task,
         register_factory_task,
+        priority_op_watchdog_task,
         tx_event_emitter_task,
         mempool_block_handler_task,
         mempool_tx_handler_task,
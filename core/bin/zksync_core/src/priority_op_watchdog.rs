@@ -0,0 +1,202 @@
+//! The watchdog keeps the priority operations from expiring.
+//!
+//! Priority operation has to be executed on Ethereum before its deadline block, otherwise anyone
+//! can switch the contract to the exodus mode. Every run the watchdog finds the earliest deadline
+//! of the operations that are not executed yet and reports the amount of Ethereum blocks left
+//! before it. Once the deadline of an operation that is not even committed is close, the watchdog
+//! requests the state keeper to seal the pending block, so the operation is committed right away.
+
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::ETHWatchConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_utils::shutdown::ShutdownToken;
+
+// Local deps
+use crate::state_keeper::BlockSealRequest;
+
+/// Earliest deadline blocks of the priority operations that are not executed on Ethereum.
+/// Deadline is `None` if there are no such operations.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct PriorityOpDeadlines {
+    /// Operations in the mempool or in the blocks that are not committed yet.
+    uncommitted: Option<u64>,
+    /// All the operations that are not executed, including the uncommitted ones.
+    unexecuted: Option<u64>,
+}
+
+/// Amounts of the Ethereum blocks left before the deadline the watchdog reacts at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DeadlineThresholds {
+    warning_blocks: u64,
+    emergency_blocks: u64,
+}
+
+impl DeadlineThresholds {
+    fn from_config(config: &ETHWatchConfig) -> Self {
+        Self {
+            warning_blocks: config.priority_op_warning_blocks,
+            emergency_blocks: config.priority_op_emergency_blocks,
+        }
+    }
+}
+
+#[must_use]
+pub fn run_priority_op_watchdog(
+    config: &ETHWatchConfig,
+    db_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    seal_request: BlockSealRequest,
+    shutdown: ShutdownToken,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(config.priority_op_watchdog_interval());
+    let thresholds = DeadlineThresholds::from_config(config);
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = timer.tick() => {},
+                _ = shutdown.signalled() => break,
+            }
+            let current_block = match eth_gateway.block_number().await {
+                Ok(block) => block.as_u64(),
+                Err(e) => {
+                    vlog::warn!("Can't get the Ethereum block number: {:?}", e);
+                    continue;
+                }
+            };
+            let deadlines = match load_deadlines(&db_pool).await {
+                Ok(deadlines) => deadlines,
+                Err(e) => {
+                    vlog::error!("Can't load the priority operations deadlines {:?}", e);
+                    continue;
+                }
+            };
+            if check_deadlines(deadlines, current_block, thresholds) {
+                seal_request.request();
+            }
+        }
+    })
+}
+
+async fn load_deadlines(db_pool: &ConnectionPool) -> anyhow::Result<PriorityOpDeadlines> {
+    let mut storage = db_pool.access_storage().await?;
+    let last_committed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_committed_block()
+        .await?;
+    let last_executed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    let in_mempool = storage
+        .chain()
+        .mempool_schema()
+        .get_min_priority_op_deadline()
+        .await?;
+    let in_uncommitted_blocks = storage
+        .chain()
+        .operations_schema()
+        .get_min_priority_op_deadline_after_block(last_committed_block)
+        .await?;
+    let in_unexecuted_blocks = storage
+        .chain()
+        .operations_schema()
+        .get_min_priority_op_deadline_after_block(last_executed_block)
+        .await?;
+
+    let min = |a: Option<u64>, b: Option<u64>| a.into_iter().chain(b).min();
+    Ok(PriorityOpDeadlines {
+        uncommitted: min(in_mempool, in_uncommitted_blocks),
+        unexecuted: min(in_mempool, in_unexecuted_blocks),
+    })
+}
+
+/// Reports the amount of blocks left before the deadlines. Returns `true` if the pending block
+/// has to be sealed so the uncommitted operations are committed before their deadline.
+fn check_deadlines(
+    deadlines: PriorityOpDeadlines,
+    current_block: u64,
+    thresholds: DeadlineThresholds,
+) -> bool {
+    let blocks_left =
+        |deadline: Option<u64>| deadline.map(|deadline| deadline as i64 - current_block as i64);
+    let stages = [
+        ("uncommitted", blocks_left(deadlines.uncommitted)),
+        ("unexecuted", blocks_left(deadlines.unexecuted)),
+    ];
+    for (stage, blocks_left) in stages.iter().copied() {
+        // There's nothing to wait for if there are no operations.
+        let value = blocks_left.map_or(f64::INFINITY, |blocks| blocks as f64);
+        metrics::gauge!("priority_op_watchdog.blocks_to_deadline", value, "stage" => stage);
+    }
+
+    match blocks_left(deadlines.unexecuted) {
+        Some(blocks) if blocks <= 0 => {
+            vlog::error!(
+                "Deadline of the unexecuted priority operation has passed {} blocks ago, \
+                 the contract may be switched to the exodus mode",
+                -blocks
+            );
+        }
+        Some(blocks) if blocks <= thresholds.warning_blocks as i64 => {
+            vlog::warn!(
+                "Priority operation has to be executed on Ethereum within {} blocks",
+                blocks
+            );
+        }
+        _ => {}
+    }
+
+    match blocks_left(deadlines.uncommitted) {
+        Some(blocks) if blocks <= thresholds.emergency_blocks as i64 => {
+            vlog::error!(
+                "Priority operation has to be executed on Ethereum within {} blocks, \
+                 but it's not committed yet. Sealing the pending block",
+                blocks
+            );
+            metrics::increment_counter!("priority_op_watchdog.emergency_seal");
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: DeadlineThresholds = DeadlineThresholds {
+        warning_blocks: 100,
+        emergency_blocks: 10,
+    };
+
+    #[test]
+    fn emergency_seal() {
+        let deadlines = PriorityOpDeadlines {
+            uncommitted: Some(1_050),
+            unexecuted: Some(1_020),
+        };
+        assert!(!check_deadlines(deadlines, 1_000, THRESHOLDS));
+        assert!(!check_deadlines(deadlines, 1_039, THRESHOLDS));
+        assert!(check_deadlines(deadlines, 1_040, THRESHOLDS));
+        assert!(check_deadlines(deadlines, 1_100, THRESHOLDS));
+
+        // Only the uncommitted operations are sealed for.
+        let deadlines = PriorityOpDeadlines {
+            uncommitted: None,
+            unexecuted: Some(1_000),
+        };
+        assert!(!check_deadlines(deadlines, 1_000, THRESHOLDS));
+        assert!(!check_deadlines(
+            PriorityOpDeadlines::default(),
+            1_000,
+            THRESHOLDS
+        ));
+    }
+}
@@ -46,11 +46,13 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
 
     let mut status = data.status_cache.write().await;
     let main_database_status = data.connection_pool.access_storage().await.is_ok();
-    let replica_database_status = data
-        .read_only_connection_pool
-        .access_storage()
-        .await
-        .is_ok();
+    // The read-only pool falls back to the main database, so the replicas are checked separately.
+    let replica_database_status = data.read_only_connection_pool.replicas_available()
+        && data
+            .read_only_connection_pool
+            .access_storage()
+            .await
+            .is_ok();
     let eth_status = data.eth_client.block_number().await.is_ok();
 
     let response = CoreStatus {
@@ -11,6 +11,7 @@ use tokio::task::JoinHandle;
 use zksync_config::TokenHandlerConfig;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::RegisterNFTFactoryEvent;
+use zksync_utils::shutdown::ShutdownToken;
 // Local uses
 use crate::eth_watch::EthWatchRequest;
 
@@ -94,10 +95,13 @@ impl NFTFactoryHandler {
         Ok(())
     }
 
-    async fn run(&mut self) {
+    async fn run(&mut self, shutdown: ShutdownToken) {
         let mut timer = tokio::time::interval(self.poll_interval);
         loop {
-            timer.tick().await;
+            tokio::select! {
+                _ = timer.tick() => {},
+                _ = shutdown.signalled() => break,
+            }
 
             let register_nft_factory_events = self.load_register_nft_factory_events().await;
 
@@ -125,10 +129,11 @@ pub fn run_register_factory_handler(
     db_pool: ConnectionPool,
     eth_watch_req: mpsc::Sender<EthWatchRequest>,
     config: TokenHandlerConfig,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut handler = NFTFactoryHandler::new(db_pool, eth_watch_req, &config).await;
 
-        handler.run().await
+        handler.run(shutdown).await
     })
 }
@@ -5,6 +5,8 @@
 //! which is useful only for a short period of time. Since such transactions are not actually
 //! included in the block and don't affect the state hash, there is no much sense to keep
 //! them forever.
+//!
+//! The reasons of the transactions rejected by the API are removed after the same period.
 
 // External uses
 use tokio::{task::JoinHandle, time};
@@ -33,6 +35,13 @@ pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> Jo
             {
                 vlog::error!("Can't delete rejected transactions {:?}", e);
             }
+            if let Err(e) = storage
+                .tx_rejections_schema()
+                .remove_old_rejections(max_age)
+                .await
+            {
+                vlog::error!("Can't delete transactions rejection reasons {:?}", e);
+            }
             timer.tick().await;
         }
     })
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 // External uses
 // Workspace uses
+use zksync_crypto::merkle_tree::persistent_cache::PersistentTreeCache;
 use zksync_state::state::ZkSyncState;
 use zksync_types::{
     block::{IncompleteBlock, PendingBlock as SendablePendingBlock},
@@ -63,8 +64,10 @@ impl ZkSyncStateInitParams {
         storage: &mut zksync_storage::StorageProcessor<'_>,
         fee_account_addr: Address,
         available_chunk_sizes: &[usize],
+        tree_cache_path: Option<&str>,
     ) -> Self {
-        let (last_block_number, tree, acc_id_by_addr) = Self::load_account_tree(storage).await;
+        let (last_block_number, tree, acc_id_by_addr) =
+            Self::load_account_tree(storage, tree_cache_path).await;
 
         let unprocessed_priority_op = Self::unprocessed_priority_op_id(storage).await;
         let nfts = Self::load_nft_tokens(storage, last_block_number).await;
@@ -100,8 +103,17 @@ impl ZkSyncStateInitParams {
 
     async fn load_account_tree(
         storage: &mut zksync_storage::StorageProcessor<'_>,
+        tree_cache_path: Option<&str>,
     ) -> (BlockNumber, AccountTree, HashMap<Address, AccountId>) {
         let mut restored_tree = RestoredTree::new(StateRestoreStorage::new(storage));
+        if let Some(path) = tree_cache_path {
+            match PersistentTreeCache::open(path) {
+                Ok(persistent_cache) => {
+                    restored_tree = restored_tree.with_persistent_cache(persistent_cache)
+                }
+                Err(err) => vlog::warn!("Failed to open the persistent tree cache: {}", err),
+            }
+        }
         let last_block_number = restored_tree.restore().await;
         (
             last_block_number,
@@ -1,7 +1,8 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
 // External uses
+use chrono::Utc;
 use futures::{
     channel::{mpsc, oneshot},
     stream::StreamExt,
@@ -10,6 +11,7 @@ use futures::{
 use tokio::task::JoinHandle;
 use tokio::time;
 // Workspace uses
+use zksync_health_check::Heartbeat;
 use zksync_mempool::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
 use zksync_state::state::{OpSuccess, ZkSyncState};
 use zksync_types::{
@@ -18,6 +20,7 @@ use zksync_types::{
         PendingBlock as SendablePendingBlock,
     },
     gas_counter::GasCounter,
+    helpers::reverse_updates,
     mempool::SignedTxVariant,
     tx::ZkSyncTx,
     Address, PriorityOp, SignedZkSyncTx,
@@ -27,7 +30,7 @@ use self::{
     pending_block::PendingBlock,
     root_hash_calculator::{BlockRootHashJob, RootHashCalculator},
     types::{ApplyOutcome, StateKeeperConfig},
-    utils::system_time_timestamp,
+    utils::{system_time_timestamp, verify_signatures},
 };
 use crate::{
     committer::{BlockCommitRequest, CommitRequest},
@@ -37,12 +40,14 @@ use crate::{
 pub use self::{
     init_params::ZkSyncStateInitParams,
     root_hash_calculator::{start_root_hash_calculator, BlockRootHashJobQueue},
+    seal_policy::{BlockSealPolicy, BlockSealRequest},
     types::StateKeeperTestkitRequest,
 };
 
 mod init_params;
 mod pending_block;
 mod root_hash_calculator;
+mod seal_policy;
 mod state_restore;
 mod types;
 mod utils;
@@ -50,6 +55,10 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+/// Minimal number of the consecutive transactions executed in parallel,
+/// for the fewer ones copying the touched accounts isn't worth it.
+const MIN_PARALLEL_TXS: usize = 4;
+
 /// Responsible for tx processing and block forming.
 pub struct ZkSyncStateKeeper {
     /// Current plasma state
@@ -85,6 +94,8 @@ impl ZkSyncStateKeeper {
         available_block_chunk_sizes: Vec<usize>,
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
+        nonce_gap_timeout: Duration,
+        seal_policy: BlockSealPolicy,
         processed_tx_events_sender: mpsc::Sender<ProcessedOperations>,
     ) -> (Self, RootHashCalculator) {
         // We need two copies of state:
@@ -133,6 +144,8 @@ impl ZkSyncStateKeeper {
             available_block_chunk_sizes,
             max_miniblock_iterations,
             fast_miniblock_iterations,
+            nonce_gap_timeout,
+            seal_policy,
         );
 
         let pending_block = {
@@ -275,11 +288,22 @@ impl ZkSyncStateKeeper {
     }
 
     // Generate and execute new miniblock every miniblock_interval
-    async fn run(mut self, miniblock_interval: Duration) {
+    async fn run(
+        mut self,
+        miniblock_interval: Duration,
+        heartbeat: Heartbeat,
+        shutdown: ShutdownToken,
+    ) {
         let mut timer = time::interval(miniblock_interval);
         loop {
+            heartbeat.beat();
             let start = Instant::now();
-            timer.tick().await;
+            // Miniblock is never interrupted by the shutdown, the executed transactions
+            // are sent to the committer at the end of every iteration.
+            tokio::select! {
+                _ = timer.tick() => {},
+                _ = shutdown.signalled() => break,
+            }
             // Report timings between two miniblocks.
             // If reported value stays at 0, most likely we have `miniblock_interval` variable too small and
             // spend more time in the loop iteration than this interval.
@@ -297,6 +321,10 @@ impl ZkSyncStateKeeper {
 
             self.execute_proposed_block(proposed_block).await;
         }
+        vlog::info!(
+            "State keeper is stopped, pending block: {}",
+            *self.pending_block.number
+        );
     }
 
     async fn propose_new_block(&mut self, block_timestamp: u64) -> ProposedBlock {
@@ -374,9 +402,10 @@ impl ZkSyncStateKeeper {
             .expect("committer receiver dropped");
     }
 
-    async fn execute_proposed_block(&mut self, proposed_block: ProposedBlock) {
+    async fn execute_proposed_block(&mut self, mut proposed_block: ProposedBlock) {
         let start = Instant::now();
         let mut executed_ops = Vec::new();
+        verify_signatures(&mut proposed_block.txs);
 
         // If pending block is empty we update timestamp
         if self.pending_block.success_operations.is_empty() {
@@ -407,6 +436,32 @@ impl ZkSyncStateKeeper {
         while let Some(variant) = tx_queue.pop_front() {
             match &variant {
                 SignedTxVariant::Tx(tx) => {
+                    if self.is_waiting_for_nonce(std::slice::from_ref(tx)) {
+                        continue;
+                    }
+                    // The transactions following this one may be executed along with it.
+                    let parallel_txs = self.parallel_txs_count(tx, &tx_queue);
+                    if parallel_txs >= MIN_PARALLEL_TXS {
+                        let mut txs = vec![tx.clone()];
+                        txs.extend(tx_queue.drain(..parallel_txs - 1).filter_map(|variant| {
+                            match variant {
+                                SignedTxVariant::Tx(tx) => Some(tx),
+                                SignedTxVariant::Batch(_) => None,
+                            }
+                        }));
+                        let (mut ops, not_included) = self.apply_txs_parallel(txs);
+                        executed_ops.append(&mut ops);
+                        if !not_included.is_empty() {
+                            // We've reached the gas limit, so we seal this block and the
+                            // transactions not included will go to the next block instead.
+                            self.seal_pending_block().await;
+
+                            for tx in not_included.into_iter().rev() {
+                                tx_queue.push_front(SignedTxVariant::Tx(tx));
+                            }
+                        }
+                        continue;
+                    }
                     match self.apply_tx(tx) {
                         ApplyOutcome::Included(exec_op) => {
                             executed_ops.push(exec_op);
@@ -422,6 +477,9 @@ impl ZkSyncStateKeeper {
                     }
                 }
                 SignedTxVariant::Batch(batch) => {
+                    if self.is_waiting_for_nonce(&batch.txs) {
+                        continue;
+                    }
                     match self.apply_batch(&batch.txs, batch.batch_id) {
                         ApplyOutcome::Included(mut ops) => {
                             executed_ops.append(&mut ops);
@@ -461,9 +519,19 @@ impl ZkSyncStateKeeper {
             self.config.max_miniblock_iterations
         };
 
+        // Besides the iterations and the block size, the block may be sealed by the configured policy.
+        let seal_trigger = self.config.seal_policy.seal_trigger(
+            &self.pending_block,
+            self.config.max_block_size(),
+            system_time_timestamp(),
+        );
+        if let Some(seal_trigger) = seal_trigger {
+            metrics::increment_counter!("state_keeper.seal_trigger", "trigger" => seal_trigger.as_str());
+        }
+
         // Check whether we should seal this block and start processing the next one, or we just need
         // to persist the pending block.
-        if self.pending_block.should_seal(max_miniblock_iterations) {
+        if self.pending_block.should_seal(max_miniblock_iterations) || seal_trigger.is_some() {
             self.seal_pending_block().await;
         } else {
             // State keeper may process empty blocks (or blocks containing rejected transactions only), and it's an
@@ -529,6 +597,48 @@ impl ZkSyncStateKeeper {
         ApplyOutcome::Included(exec_result)
     }
 
+    /// Checks whether the transactions have to wait for the missing transactions with the lower nonces.
+    /// Such transactions are left in the mempool and proposed again, unless they were received more than
+    /// `nonce_gap_timeout` ago: then they are executed and fail.
+    fn is_waiting_for_nonce(&self, txs: &[SignedZkSyncTx]) -> bool {
+        let mut expected_nonces = HashMap::new();
+        let mut has_nonce_gap = false;
+        for tx in txs {
+            let account_id = match tx.account_id() {
+                Ok(account_id) => account_id,
+                Err(_) => continue,
+            };
+            let expected_nonce = match expected_nonces.get(&account_id) {
+                Some(nonce) => *nonce,
+                None => match self.state.get_account(account_id) {
+                    Some(account) => account.nonce,
+                    None => continue,
+                },
+            };
+            if tx.nonce() > expected_nonce {
+                has_nonce_gap = true;
+                break;
+            }
+            expected_nonces.insert(account_id, expected_nonce + 1);
+        }
+        if !has_nonce_gap {
+            return false;
+        }
+
+        let received_at = txs.iter().map(|tx| tx.created_at).min();
+        let is_waiting = received_at
+            .map(|received_at| {
+                (Utc::now() - received_at)
+                    .to_std()
+                    .map_or(true, |elapsed| elapsed < self.config.nonce_gap_timeout)
+            })
+            .unwrap_or(false);
+        if is_waiting {
+            metrics::increment_counter!("state_keeper.nonce_gap_txs");
+        }
+        is_waiting
+    }
+
     fn apply_batch(
         &mut self,
         txs: &[SignedZkSyncTx],
@@ -536,6 +646,8 @@ impl ZkSyncStateKeeper {
     ) -> ApplyOutcome<Vec<ExecutedOperations>> {
         metrics::gauge!("tx_batch_size", txs.len() as f64);
         let start = Instant::now();
+        let span = tracing::info_span!("apply_batch", tx_hashes = ?tx_hashes(txs));
+        let _span_guard = span.enter();
 
         let chunks_needed = self.state.chunks_for_batch(txs);
 
@@ -646,8 +758,151 @@ impl ZkSyncStateKeeper {
         ApplyOutcome::Included(executed_operations)
     }
 
+    /// Returns the number of the transactions, starting with `tx` and followed by the single
+    /// transactions from the front of the queue, which can be executed in parallel: they all fit
+    /// into the pending block by size and none of them waits for the missing nonces.
+    fn parallel_txs_count(
+        &self,
+        tx: &SignedZkSyncTx,
+        tx_queue: &VecDeque<SignedTxVariant>,
+    ) -> usize {
+        let queued_txs = tx_queue.iter().map(|variant| match variant {
+            SignedTxVariant::Tx(tx) => Some(tx),
+            SignedTxVariant::Batch(_) => None,
+        });
+
+        // Chunks are estimated against the current state, which may only overestimate them.
+        let mut chunks_left = self.pending_block.chunks_left;
+        let mut count = 0;
+        for tx in std::iter::once(Some(tx)).chain(queued_txs) {
+            let tx = match tx {
+                Some(tx) => tx,
+                None => break,
+            };
+            if count > 0 && self.is_waiting_for_nonce(std::slice::from_ref(tx)) {
+                break;
+            }
+            let chunks_needed = self.state.chunks_for_tx(tx);
+            if chunks_left < chunks_needed {
+                break;
+            }
+            chunks_left -= chunks_needed;
+            count += 1;
+        }
+        count
+    }
+
+    /// Executes the transactions fitting into the pending block by size, transactions touching
+    /// the different accounts are executed in parallel.
+    ///
+    /// Returns the executed operations and the transactions not included because of the gas limit,
+    /// the updates of the latter are reverted.
+    fn apply_txs_parallel(
+        &mut self,
+        txs: Vec<SignedZkSyncTx>,
+    ) -> (Vec<ExecutedOperations>, Vec<SignedZkSyncTx>) {
+        metrics::gauge!("state_keeper.parallel_txs", txs.len() as f64);
+        let start = Instant::now();
+        let span = tracing::info_span!("apply_txs_parallel", tx_hashes = ?tx_hashes(&txs));
+        let _span_guard = span.enter();
+
+        let zksync_txs: Vec<_> = txs.iter().map(|tx| tx.tx.clone()).collect();
+        let mut all_updates = self
+            .state
+            .execute_txs_parallel(&zksync_txs, self.pending_block.timestamp)
+            .into_iter();
+
+        let mut executed_operations = Vec::with_capacity(txs.len());
+        let mut txs = txs.into_iter();
+        while let Some(tx) = txs.next() {
+            let tx_updates = all_updates
+                .next()
+                .expect("Each transaction has the execution result");
+            match tx_updates {
+                Ok(OpSuccess {
+                    fee,
+                    updates,
+                    executed_op,
+                }) => {
+                    if self.pending_block.gas_counter.add_op(&executed_op).is_err() {
+                        // We've reached the gas limit, so this transaction and the ones after it
+                        // will go into the next block. Their updates are reverted in reverse order.
+                        let not_executed_updates: Vec<_> = std::iter::once(updates)
+                            .chain(
+                                all_updates
+                                    .filter_map(|result| result.ok())
+                                    .map(|success| success.updates),
+                            )
+                            .collect();
+                        for mut updates in not_executed_updates.into_iter().rev() {
+                            reverse_updates(&mut updates);
+                            self.state.apply_account_updates(updates);
+                        }
+
+                        let not_included = std::iter::once(tx).chain(txs).collect();
+                        metrics::histogram!("state_keeper.apply_txs_parallel", start.elapsed());
+                        return (executed_operations, not_included);
+                    }
+                    if let ZkSyncTx::Withdraw(tx) = &tx.tx {
+                        // Check if we should mark this block as requiring fast processing.
+                        if tx.fast {
+                            self.pending_block.fast_processing_required = true;
+                        }
+                    }
+                    let chunks_used = executed_op.chunks();
+
+                    let block_index = self.pending_block.pending_op_block_index;
+                    let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
+                        signed_tx: tx,
+                        success: true,
+                        op: Some(executed_op),
+                        fail_reason: None,
+                        block_index: Some(block_index),
+                        created_at: chrono::Utc::now(),
+                        batch_id: None,
+                    }));
+
+                    self.pending_block.add_successful_execution(
+                        chunks_used,
+                        updates,
+                        fee,
+                        exec_result.clone(),
+                    );
+
+                    executed_operations.push(exec_result);
+                }
+                Err(e) => {
+                    vlog::warn!("Failed to execute transaction: {:?}, {}", tx, e);
+                    if let ZkSyncTx::Withdraw(tx) = &tx.tx {
+                        if tx.fast {
+                            self.pending_block.fast_processing_required = true;
+                        }
+                    }
+                    let labels = vec![("stage", "state".to_string()), ("error", e.to_string())];
+                    metrics::increment_counter!("rejected_txs", &labels);
+                    let failed_tx = ExecutedTx {
+                        signed_tx: tx,
+                        success: false,
+                        op: None,
+                        fail_reason: Some(e.to_string()),
+                        block_index: None,
+                        created_at: chrono::Utc::now(),
+                        batch_id: None,
+                    };
+                    self.pending_block.failed_txs.push(failed_tx.clone());
+                    executed_operations.push(ExecutedOperations::Tx(Box::new(failed_tx)));
+                }
+            }
+        }
+
+        metrics::histogram!("state_keeper.apply_txs_parallel", start.elapsed());
+        (executed_operations, Vec::new())
+    }
+
     fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
+        let span = tracing::info_span!("apply_tx", tx_hash = %tx.hash().to_string());
+        let _span_guard = span.enter();
         let chunks_needed = self.state.chunks_for_tx(tx);
 
         // If we can't add the tx to the block due to the size limit, we return this tx,
@@ -888,7 +1143,19 @@ impl ZkSyncStateKeeper {
     }
 }
 
+/// Hashes of the transactions, used to trace the transactions across the services.
+fn tx_hashes(txs: &[SignedZkSyncTx]) -> Vec<String> {
+    txs.iter().map(|tx| tx.hash().to_string()).collect()
+}
+
+/// Starts the state keeper, `heartbeat` is updated on every miniblock iteration.
+/// On shutdown the state keeper finishes the current miniblock and stops.
 #[must_use]
-pub fn start_state_keeper(sk: ZkSyncStateKeeper, miniblock_interval: Duration) -> JoinHandle<()> {
-    tokio::spawn(sk.run(miniblock_interval))
+pub fn start_state_keeper(
+    sk: ZkSyncStateKeeper,
+    miniblock_interval: Duration,
+    heartbeat: Heartbeat,
+    shutdown: ShutdownToken,
+) -> JoinHandle<()> {
+    tokio::spawn(sk.run(miniblock_interval, heartbeat, shutdown))
 }
@@ -1,10 +1,12 @@
+// Built-in uses
+use std::time::Instant;
 // External uses
 // Workspace uses
 use zksync_state::state::CollectedFee;
 use zksync_types::{
     block::{ExecutedOperations, ExecutedTx, PendingBlock as SendablePendingBlock},
     gas_counter::GasCounter,
-    AccountUpdates, BlockNumber,
+    AccountUpdates, BlockNumber, ZkSyncPriorityOp, ZkSyncTx,
 };
 
 use crate::committer::AppliedUpdatesRequest;
@@ -25,6 +27,10 @@ pub(super) struct PendingBlock {
     pub(super) gas_counter: GasCounter,
     /// Option denoting if this block should be generated faster than usual.
     pub(super) fast_processing_required: bool,
+    /// Whether the block contains successful `ForcedExit` or `FullExit` operations.
+    pub(super) contains_exit: bool,
+    /// Time the last successful operation has been added to the block.
+    pub(super) last_op_at: Option<Instant>,
     /// Fee should be applied only when sealing the block (because of corresponding logic in the circuit)
     pub(super) collected_fees: Vec<CollectedFee>,
     /// Number of stored account updates in the db (from `account_updates` field)
@@ -58,6 +64,8 @@ impl PendingBlock {
             pending_block_iteration: 0,
             gas_counter: GasCounter::new(),
             fast_processing_required: false,
+            contains_exit: false,
+            last_op_at: None,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             timestamp,
@@ -110,6 +118,13 @@ impl PendingBlock {
         if exec_result.is_priority() {
             self.unprocessed_priority_op_current += 1;
         }
+        self.contains_exit |= match &exec_result {
+            ExecutedOperations::Tx(tx) => matches!(tx.signed_tx.tx, ZkSyncTx::ForcedExit(_)),
+            ExecutedOperations::PriorityOp(op) => {
+                matches!(op.priority_op.data, ZkSyncPriorityOp::FullExit(_))
+            }
+        };
+        self.last_op_at = Some(Instant::now());
 
         self.success_operations.push(exec_result);
     }
@@ -202,6 +217,8 @@ mod tests {
             tx,
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
 
         ExecutedOperations::Tx(Box::new(ExecutedTx {
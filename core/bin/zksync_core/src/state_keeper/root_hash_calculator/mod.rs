@@ -5,6 +5,7 @@ use tokio::task::JoinHandle;
 
 use zksync_state::state::ZkSyncState;
 use zksync_types::BlockNumber;
+use zksync_utils::shutdown::ShutdownToken;
 
 use crate::committer::{BlockFinishRequest, CommitRequest};
 
@@ -55,9 +56,14 @@ impl RootHashCalculator {
         }
     }
 
-    pub async fn run(mut self) {
+    /// Processes the jobs until the shutdown. Jobs left in the queue are restored
+    /// from the incomplete blocks on the restart.
+    pub async fn run(mut self, shutdown: ShutdownToken) {
         loop {
-            let job = self.job_queue.pop().await;
+            let job = tokio::select! {
+                job = self.job_queue.pop() => job,
+                _ = shutdown.signalled() => break,
+            };
             self.process_job(job).await;
         }
     }
@@ -112,6 +118,9 @@ impl RootHashCalculator {
 }
 
 #[must_use]
-pub fn start_root_hash_calculator(rhc: RootHashCalculator) -> JoinHandle<()> {
-    tokio::spawn(rhc.run())
+pub fn start_root_hash_calculator(
+    rhc: RootHashCalculator,
+    shutdown: ShutdownToken,
+) -> JoinHandle<()> {
+    tokio::spawn(rhc.run(shutdown))
 }
@@ -0,0 +1,306 @@
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+// External uses
+// Workspace uses
+use zksync_config::configs::chain::StateKeeper;
+// Local uses
+use super::pending_block::PendingBlock;
+
+/// Conditions the pending block is sealed on in addition to the full block
+/// and the exhausted miniblock iterations.
+///
+/// Only the non-empty blocks are sealed by the policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSealPolicy {
+    /// Time since the first operation of the block after which it's sealed.
+    pub max_block_age: Option<Duration>,
+    /// Time without new operations after which the block is sealed.
+    pub max_idle_time: Option<Duration>,
+    /// Share of the block chunks in percent, once it's used the block is sealed.
+    pub min_chunk_utilization_percent: u64,
+    /// Whether the block including `ForcedExit` or `FullExit` is sealed right away.
+    pub seal_on_exit: bool,
+    /// Request to seal the block right away made by another component.
+    pub seal_request: Option<BlockSealRequest>,
+}
+
+impl Default for BlockSealPolicy {
+    /// Policy which never seals the block before it's full or the miniblock iterations are exhausted.
+    fn default() -> Self {
+        Self {
+            max_block_age: None,
+            max_idle_time: None,
+            min_chunk_utilization_percent: 100,
+            seal_on_exit: false,
+            seal_request: None,
+        }
+    }
+}
+
+/// Request to seal the pending block regardless of the other conditions of the `BlockSealPolicy`,
+/// e.g. so its priority operations are committed before their deadline.
+///
+/// Request is shared by the requesting component and the state keeper. It's fulfilled by sealing
+/// the next non-empty block, and every request seals a single block.
+#[derive(Debug, Clone, Default)]
+pub struct BlockSealRequest(Arc<AtomicBool>);
+
+impl BlockSealRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the pending block to be sealed. Does nothing if it's requested already.
+    pub fn request(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the request isn't fulfilled yet.
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for BlockSealRequest {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Condition of the `BlockSealPolicy` the block has been sealed on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum SealTrigger {
+    MaxBlockAge,
+    MaxIdleTime,
+    ChunkUtilization,
+    Exit,
+    Requested,
+}
+
+impl SealTrigger {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::MaxBlockAge => "max_block_age",
+            Self::MaxIdleTime => "max_idle_time",
+            Self::ChunkUtilization => "chunk_utilization",
+            Self::Exit => "exit",
+            Self::Requested => "requested",
+        }
+    }
+}
+
+impl BlockSealPolicy {
+    pub fn from_config(config: &StateKeeper) -> Self {
+        Self {
+            max_block_age: config.seal_max_block_age(),
+            max_idle_time: config.seal_max_idle_time(),
+            min_chunk_utilization_percent: config.seal_min_chunk_utilization_percent,
+            seal_on_exit: config.seal_on_exit,
+            seal_request: None,
+        }
+    }
+
+    /// Returns the condition the block has to be sealed on, if any.
+    /// `now` is the current UNIX timestamp in seconds, the same as the block timestamp.
+    pub(super) fn seal_trigger(
+        &self,
+        block: &PendingBlock,
+        max_block_size: usize,
+        now: u64,
+    ) -> Option<SealTrigger> {
+        if block.success_operations.is_empty() {
+            return None;
+        }
+
+        if let Some(seal_request) = &self.seal_request {
+            if seal_request.take() {
+                return Some(SealTrigger::Requested);
+            }
+        }
+
+        if self.seal_on_exit && block.contains_exit {
+            return Some(SealTrigger::Exit);
+        }
+
+        let used_chunks = max_block_size - block.chunks_left;
+        if used_chunks as u64 * 100 >= max_block_size as u64 * self.min_chunk_utilization_percent {
+            return Some(SealTrigger::ChunkUtilization);
+        }
+
+        if let Some(max_block_age) = self.max_block_age {
+            let block_age = Duration::from_secs(now.saturating_sub(block.timestamp));
+            if block_age >= max_block_age {
+                return Some(SealTrigger::MaxBlockAge);
+            }
+        }
+
+        if let (Some(max_idle_time), Some(last_op_at)) = (self.max_idle_time, block.last_op_at) {
+            if last_op_at.elapsed() >= max_idle_time {
+                return Some(SealTrigger::MaxIdleTime);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use zksync_types::{
+        AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, FullExit,
+        FullExitOp, PriorityOp, TokenId, ZkSyncOp, ZkSyncPriorityOp, H256,
+    };
+
+    use super::*;
+
+    const MAX_BLOCK_SIZE: usize = 100;
+    const TIMESTAMP: u64 = 1_000;
+
+    fn full_exit() -> ExecutedOperations {
+        let priority_op = FullExit {
+            account_id: AccountId(1),
+            eth_address: Address::repeat_byte(0x01),
+            token: TokenId(0),
+            is_legacy: false,
+        };
+        ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            op: ZkSyncOp::FullExit(Box::new(FullExitOp {
+                priority_op: priority_op.clone(),
+                withdraw_amount: None,
+                creator_account_id: None,
+                creator_address: None,
+                serial_id: None,
+                content_hash: None,
+            })),
+            priority_op: PriorityOp {
+                serial_id: 0,
+                data: ZkSyncPriorityOp::FullExit(priority_op),
+                deadline_block: 0,
+                eth_hash: H256::zero(),
+                eth_block: 0,
+                eth_block_index: None,
+            },
+            block_index: 0,
+            created_at: chrono::Utc::now(),
+        }))
+    }
+
+    fn block_with_exit(chunks: usize) -> PendingBlock {
+        let mut block = PendingBlock::new(BlockNumber(1), 0, MAX_BLOCK_SIZE, TIMESTAMP);
+        block.add_successful_execution(chunks, Vec::new(), None, full_exit());
+        block
+    }
+
+    #[test]
+    fn default_policy() {
+        let policy = BlockSealPolicy::default();
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP + 1_000),
+            None
+        );
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(MAX_BLOCK_SIZE), MAX_BLOCK_SIZE, TIMESTAMP),
+            Some(SealTrigger::ChunkUtilization)
+        );
+    }
+
+    #[test]
+    fn empty_block_is_not_sealed() {
+        let policy = BlockSealPolicy {
+            max_block_age: Some(Duration::from_secs(0)),
+            max_idle_time: Some(Duration::from_secs(0)),
+            min_chunk_utilization_percent: 0,
+            seal_on_exit: true,
+            seal_request: Some(BlockSealRequest::new()),
+        };
+        policy.seal_request.as_ref().unwrap().request();
+        let block = PendingBlock::new(BlockNumber(1), 0, MAX_BLOCK_SIZE, TIMESTAMP);
+        assert_eq!(
+            policy.seal_trigger(&block, MAX_BLOCK_SIZE, TIMESTAMP + 1_000),
+            None
+        );
+        // Request waits for the block with operations.
+        assert!(policy.seal_request.as_ref().unwrap().is_requested());
+    }
+
+    #[test]
+    fn seal_triggers() {
+        let policy = BlockSealPolicy {
+            seal_on_exit: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP),
+            Some(SealTrigger::Exit)
+        );
+
+        let policy = BlockSealPolicy {
+            min_chunk_utilization_percent: 50,
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(49), MAX_BLOCK_SIZE, TIMESTAMP),
+            None
+        );
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(50), MAX_BLOCK_SIZE, TIMESTAMP),
+            Some(SealTrigger::ChunkUtilization)
+        );
+
+        let policy = BlockSealPolicy {
+            max_block_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP + 59),
+            None
+        );
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP + 60),
+            Some(SealTrigger::MaxBlockAge)
+        );
+
+        let policy = BlockSealPolicy {
+            max_idle_time: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let mut block = block_with_exit(10);
+        assert_eq!(policy.seal_trigger(&block, MAX_BLOCK_SIZE, TIMESTAMP), None);
+        block.last_op_at = Some(Instant::now() - Duration::from_secs(60));
+        assert_eq!(
+            policy.seal_trigger(&block, MAX_BLOCK_SIZE, TIMESTAMP),
+            Some(SealTrigger::MaxIdleTime)
+        );
+
+        let seal_request = BlockSealRequest::new();
+        let policy = BlockSealPolicy {
+            seal_request: Some(seal_request.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP),
+            None
+        );
+        seal_request.request();
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP),
+            Some(SealTrigger::Requested)
+        );
+        // Every request seals a single block.
+        assert!(!seal_request.is_requested());
+        assert_eq!(
+            policy.seal_trigger(&block_with_exit(10), MAX_BLOCK_SIZE, TIMESTAMP),
+            None
+        );
+    }
+}
@@ -212,3 +212,53 @@ async fn verified_state_wrong_root() {
     let mut restorer = RestoredTree::new(db);
     restorer.restore().await;
 }
+
+/// Checks that the tree is restored from the persistent cache, and the account updates made
+/// since the cached block are written to it.
+#[tokio::test]
+async fn persistent_cache_restore() {
+    use zksync_crypto::merkle_tree::persistent_cache::PersistentTreeCache;
+    use zksync_types::Account;
+
+    const N_BLOCKS: usize = 3;
+    const LAST_BLOCK: BlockNumber = BlockNumber(N_BLOCKS as u32);
+
+    let path = std::env::temp_dir().join(format!("restore_tree_cache_{}", std::process::id()));
+    let mut state_generator = StateGenerator::new();
+    generate_blocks(&mut state_generator, N_BLOCKS - 1, None);
+
+    // Cache the tree of the previous block.
+    let mut restorer = RestoredTree::new(state_generator.create_db())
+        .with_persistent_cache(PersistentTreeCache::open(&path).unwrap());
+    restorer.restore().await;
+    drop(restorer);
+
+    let account = state_generator.create_account();
+    state_generator.change_account_balance(account, TokenId(0), 500u64);
+    state_generator.seal_block();
+
+    // The database cache is not used, as the persistent cache is loaded in the first place.
+    let mut db = state_generator.create_db();
+    db.save_cache(LAST_BLOCK, StateGenerator::empty_tree().get_internals());
+    let mut restorer =
+        RestoredTree::new(db).with_persistent_cache(PersistentTreeCache::open(&path).unwrap());
+    assert_eq!(restorer.restore().await, LAST_BLOCK);
+    assert_eq!(restorer.tree.root_hash(), state_generator.tree.root_hash());
+    assert_eq!(
+        restorer
+            .acc_id_by_addr
+            .get(&state_generator.tree.get(*account).unwrap().address),
+        Some(&account)
+    );
+    drop(restorer);
+
+    let cached = PersistentTreeCache::open(&path)
+        .unwrap()
+        .load::<Account>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(cached.block, *LAST_BLOCK);
+    assert_eq!(cached.items.len(), state_generator.tree.items.len());
+
+    std::fs::remove_dir_all(path).unwrap();
+}
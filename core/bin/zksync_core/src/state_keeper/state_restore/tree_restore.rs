@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 // External uses
 // Workspace uses
+use zksync_crypto::merkle_tree::persistent_cache::PersistentTreeCache;
 use zksync_types::{Account, AccountId, AccountTree, AccountUpdates, Address, BlockNumber};
 // Local uses
 use super::db::StateRestoreDb;
@@ -11,6 +12,9 @@ use super::db::StateRestoreDb;
 /// By default, it will try to load the last tree cache and update from there by loading the state difference.
 /// If there is no cache, tree will be recalculated from scratch.
 ///
+/// If the persistent tree cache is set, the tree is loaded from it in the first place, so only the account
+/// updates made since the cached block are loaded from the database.
+///
 /// If the tree root hash will not match the hash from the database, `RestoredTree` will find the block
 /// at which hashes diverged and panic with the corresponding message containing the block number.
 #[derive(Debug)]
@@ -19,6 +23,8 @@ pub(crate) struct RestoredTree<S: StateRestoreDb> {
 
     pub(crate) tree: AccountTree,
     pub(crate) acc_id_by_addr: HashMap<Address, AccountId>,
+
+    persistent_cache: Option<PersistentTreeCache>,
 }
 
 impl<S> RestoredTree<S>
@@ -31,31 +37,26 @@ where
 
             tree: AccountTree::new(zksync_crypto::params::account_tree_depth()),
             acc_id_by_addr: HashMap::default(),
+
+            persistent_cache: None,
         }
     }
 
+    pub(crate) fn with_persistent_cache(mut self, persistent_cache: PersistentTreeCache) -> Self {
+        self.persistent_cache = Some(persistent_cache);
+        self
+    }
+
     /// Restores the tree state.
     /// Returns the block number to which the state was initialized.
     /// This block number is guaranteed to be the last committed block.
     pub(crate) async fn restore(&mut self) -> BlockNumber {
         let last_block = self.storage.load_last_committed_block().await;
 
-        if let Some(cached_block) = self.storage.load_last_cached_block().await {
-            self.init_tree_with_cache(cached_block).await;
-            self.assert_calculated_root(
-                "Root hash from the cached tree doesn't match the root hash from the database",
-                cached_block,
-            )
-            .await;
-
-            // We may not be at the latest point in time.
-            // If so, we need to load the state diff and apply it to the tree.
-            if let Some(diff) = self.storage.load_state_diff(cached_block, last_block).await {
-                self.apply_state_diff(last_block, diff).await;
-            }
-        } else {
-            self.init_tree_without_cache(last_block).await;
-        };
+        let persistent_updates = self.init_tree_with_persistent_cache(last_block).await;
+        if persistent_updates.is_none() {
+            self.init_tree_with_database(last_block).await;
+        }
 
         // Now we *must* have the newest tree state. At this point we should check the root hash
         // and ensure that it corresponds to the previously calculated root hash that is already stored in
@@ -76,10 +77,131 @@ where
         self.storage
             .store_account_tree_cache(last_block, self.tree.get_internals())
             .await;
+        self.store_persistent_cache(last_block, persistent_updates);
 
         last_block
     }
 
+    /// Initializes the tree with the tree cache stored in the database, if there is one.
+    async fn init_tree_with_database(&mut self, last_block: BlockNumber) {
+        if let Some(cached_block) = self.storage.load_last_cached_block().await {
+            self.init_tree_with_cache(cached_block).await;
+            self.assert_calculated_root(
+                "Root hash from the cached tree doesn't match the root hash from the database",
+                cached_block,
+            )
+            .await;
+
+            // We may not be at the latest point in time.
+            // If so, we need to load the state diff and apply it to the tree.
+            if let Some(diff) = self.storage.load_state_diff(cached_block, last_block).await {
+                self.apply_state_diff(last_block, diff).await;
+            }
+        } else {
+            self.init_tree_without_cache(last_block).await;
+        };
+    }
+
+    /// Loads the tree from the persistent cache and applies the account updates made since the cached block.
+    /// Returns the IDs of the updated accounts, `None` if there is no cache to use.
+    async fn init_tree_with_persistent_cache(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> Option<Vec<AccountId>> {
+        let cached = match self.persistent_cache.as_ref()?.load::<Account>() {
+            Ok(Some(cached)) if cached.block <= *last_block => cached,
+            Ok(_) => return None,
+            Err(err) => {
+                vlog::warn!("Failed to load the persistent tree cache: {}", err);
+                return None;
+            }
+        };
+        let cached_block = BlockNumber(cached.block);
+
+        for (id, account) in cached.items {
+            self.insert_account(AccountId(id), account);
+        }
+        self.tree.set_internals(cached.internals);
+
+        // Cached block may be reverted since the cache was stored.
+        let root_hash_from_db = self.storage.load_block_hash_from_db(cached_block).await;
+        if self.tree.root_hash() != root_hash_from_db {
+            vlog::warn!(
+                "Persistent tree cache for the block {} doesn't match the database, it's not used",
+                cached_block
+            );
+            self.tree = AccountTree::new(zksync_crypto::params::account_tree_depth());
+            self.acc_id_by_addr = HashMap::new();
+            return None;
+        }
+
+        let diff = self
+            .storage
+            .load_state_diff(cached_block, last_block)
+            .await
+            .unwrap_or_default();
+        vlog::info!(
+            "Account tree is loaded from the persistent cache for the block {}, applying {} account updates",
+            cached_block,
+            diff.len()
+        );
+        Some(self.apply_account_updates(diff))
+    }
+
+    /// Applies the account updates to the tree without loading the state of the other accounts.
+    /// Returns the IDs of the updated accounts.
+    fn apply_account_updates(&mut self, diff: AccountUpdates) -> Vec<AccountId> {
+        let mut updated_accounts: HashMap<AccountId, Option<Account>> = HashMap::new();
+        for (id, update) in diff {
+            let account = updated_accounts
+                .entry(id)
+                .or_insert_with(|| self.tree.get(*id).cloned());
+            *account = Account::apply_update(account.take(), update);
+        }
+
+        let mut updated_ids = Vec::with_capacity(updated_accounts.len());
+        for (id, account) in updated_accounts {
+            match account {
+                Some(account) => self.insert_account(id, account),
+                None => {
+                    self.remove_account(id);
+                }
+            }
+            updated_ids.push(id);
+        }
+        updated_ids
+    }
+
+    /// Stores the restored tree into the persistent cache. If the tree was loaded from it,
+    /// only the updated accounts are written.
+    fn store_persistent_cache(&self, block: BlockNumber, updated_accounts: Option<Vec<AccountId>>) {
+        let persistent_cache = match &self.persistent_cache {
+            Some(persistent_cache) => persistent_cache,
+            None => return,
+        };
+        let internals = self.tree.get_internals();
+        let result = match updated_accounts {
+            Some(updated_accounts) => persistent_cache.store_updates(
+                *block,
+                updated_accounts
+                    .into_iter()
+                    .map(|id| (*id, self.tree.get(*id))),
+                &internals,
+            ),
+            None => persistent_cache.store(
+                *block,
+                self.tree
+                    .items
+                    .iter()
+                    .map(|(id, account)| (*id as u32, account)),
+                &internals,
+            ),
+        };
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the persistent tree cache: {}", err);
+        }
+    }
+
     async fn init_tree_with_cache(&mut self, cache_block: BlockNumber) {
         let committed_state = self.storage.load_committed_state(cache_block).await;
         let cache = self.storage.load_account_tree_cache(cache_block).await;
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use chrono::Utc;
 use zksync_mempool::ProposedBlock;
 use zksync_types::{
@@ -6,6 +8,7 @@ use zksync_types::{
 };
 
 use super::utils::*;
+use crate::state_keeper::BlockSealPolicy;
 
 /// Checks if executing a proposed_block with just enough chunks is done correctly
 /// and checks if number of chunks left is correct after each operation.
@@ -515,16 +518,22 @@ async fn transfers_with_different_timestamps() {
         tx: ZkSyncTx::Transfer(Box::new(correct_transfer)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let premature_transfer = SignedZkSyncTx {
         tx: ZkSyncTx::Transfer(Box::new(premature_transfer)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let belated_transfer = SignedZkSyncTx {
         tx: ZkSyncTx::Transfer(Box::new(belated_transfer)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let proposed_block = ProposedBlock {
         txs: vec![
@@ -565,3 +574,156 @@ async fn transfers_with_different_timestamps() {
         })
         .await;
 }
+
+/// Checks that the transaction with a nonce gap waits for the missing transaction
+/// and is executed along with it.
+#[tokio::test]
+async fn nonce_gap() {
+    let mut tester = StateKeeperTester::new(20, 5, 5);
+    tester.state_keeper.config.nonce_gap_timeout = Duration::from_secs(60);
+    let (account, sk) = tester.add_account(AccountId(1));
+    tester.set_balance(AccountId(1), TokenId(0), 200u32);
+    let transfer = |nonce: u32| {
+        let transfer = Transfer::new_signed(
+            AccountId(1),
+            account.address,
+            account.address,
+            TokenId(0),
+            10u32.into(),
+            1u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            &sk,
+        )
+        .unwrap();
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        }
+    };
+
+    let waiting_tx = transfer(1);
+    let proposed_block = ProposedBlock {
+        txs: vec![SignedTxVariant::Tx(waiting_tx.clone())],
+        priority_ops: Vec::new(),
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    tester
+        .assert_pending_with(|block| {
+            assert!(block.success_operations.is_empty());
+            assert!(block.failed_txs.is_empty());
+        })
+        .await;
+
+    // Once the missing transaction arrives, both of them are executed.
+    let proposed_block = ProposedBlock {
+        txs: vec![
+            SignedTxVariant::Tx(transfer(0)),
+            SignedTxVariant::Tx(waiting_tx),
+        ],
+        priority_ops: Vec::new(),
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    tester
+        .assert_pending_with(|block| {
+            assert_eq!(block.success_operations.len(), 2);
+            assert!(block.failed_txs.is_empty());
+        })
+        .await;
+
+    // Without the timeout the transaction with a nonce gap fails right away.
+    tester.state_keeper.config.nonce_gap_timeout = Duration::from_secs(0);
+    let proposed_block = ProposedBlock {
+        txs: vec![SignedTxVariant::Tx(transfer(5))],
+        priority_ops: Vec::new(),
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    tester
+        .assert_pending_with(|block| assert_eq!(block.failed_txs.len(), 1))
+        .await;
+}
+
+/// Checks that the block is sealed by the seal policy before it's full.
+#[tokio::test]
+async fn seal_policy() {
+    let mut tester = StateKeeperTester::new(10, 100, 100);
+    tester.state_keeper.config.seal_policy = BlockSealPolicy {
+        min_chunk_utilization_percent: 40,
+        ..Default::default()
+    };
+
+    // Single transfer takes 20% of the block.
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_pending_with(|block| assert_eq!(block.chunks_left, 8))
+        .await;
+
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_sealed_with(|block| assert_eq!(block.block_transactions.len(), 2))
+        .await;
+
+    // Idle block is sealed once the new operations stop arriving.
+    tester.state_keeper.config.seal_policy = BlockSealPolicy {
+        max_idle_time: Some(Duration::from_secs(0)),
+        ..Default::default()
+    };
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_sealed_with(|block| assert_eq!(block.block_transactions.len(), 1))
+        .await;
+}
+
+/// Checks that the consecutive transactions are executed in parallel the same way as
+/// they're executed sequentially, including the failed ones and the ones not fitting into the block.
+#[tokio::test]
+async fn parallel_txs() {
+    let mut tester = StateKeeperTester::new(10, 100, 100);
+    let txs = (1..=6)
+        .map(|account_id| {
+            // Transfer of the third account fails, so it doesn't take the block chunks.
+            let amount = if account_id == 3 { 300u32 } else { 100u32 };
+            let transfer = create_account_and_transfer(
+                &mut tester,
+                TokenId(0),
+                AccountId(account_id),
+                200u32,
+                amount,
+            );
+            SignedTxVariant::Tx(transfer)
+        })
+        .collect();
+    let proposed_block = ProposedBlock {
+        txs,
+        priority_ops: Vec::new(),
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    tester
+        .assert_sealed_with(|block| {
+            assert_eq!(block.block_transactions.len(), 6);
+            assert_eq!(
+                block
+                    .block_transactions
+                    .iter()
+                    .filter(|op| op.is_successful())
+                    .count(),
+                5
+            );
+        })
+        .await;
+}
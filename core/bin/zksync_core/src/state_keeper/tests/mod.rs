@@ -1,5 +1,6 @@
-use super::{ZkSyncStateInitParams, ZkSyncStateKeeper};
+use super::{BlockSealPolicy, ZkSyncStateInitParams, ZkSyncStateKeeper};
 use futures::channel::mpsc;
+use std::time::Duration;
 use zksync_types::{AccountId, H160, *};
 
 mod apply_priority_op;
@@ -37,6 +38,8 @@ fn test_create_incorrect_state_keeper() {
         vec![1, 2, 2], // `available_block_chunk_sizes` must be strictly increasing.
         MAX_ITERATIONS,
         FAST_ITERATIONS,
+        Duration::from_secs(0),
+        BlockSealPolicy::default(),
         events_sender,
     );
 }
@@ -171,6 +171,8 @@ async fn correctly_restore_pending_block_timestamp() {
             tx: ZkSyncTx::Transfer(Box::new(transfer)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         }
     };
     assert!(tester.state_keeper.apply_tx(&good_transfer).is_included());
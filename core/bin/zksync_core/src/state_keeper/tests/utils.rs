@@ -1,8 +1,11 @@
 use crate::committer::{AppliedUpdatesRequest, BlockCommitRequest};
-use crate::state_keeper::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
+use crate::state_keeper::{
+    BlockSealPolicy, CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper,
+};
 use chrono::Utc;
 use futures::{channel::mpsc, stream::StreamExt};
 use num::BigUint;
+use std::time::Duration;
 use zksync_crypto::{
     priv_key_from_fs,
     rand::{Rng, SeedableRng, XorShiftRng},
@@ -43,6 +46,8 @@ impl StateKeeperTester {
             vec![available_chunk_size],
             max_iterations,
             fast_iterations,
+            Duration::from_secs(0),
+            BlockSealPolicy::default(),
             events_sender,
         );
 
@@ -213,6 +218,8 @@ pub fn create_account_and_transfer<B: Into<BigUint>>(
         tx: ZkSyncTx::Transfer(Box::new(transfer)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     }
 }
 
@@ -286,6 +293,8 @@ pub fn create_account_and_withdrawal_impl<B: Into<BigUint>>(
         tx: ZkSyncTx::Withdraw(Box::new(withdraw)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     }
 }
 
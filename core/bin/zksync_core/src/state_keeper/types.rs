@@ -1,3 +1,5 @@
+// Built-in uses
+use std::time::Duration;
 // External uses
 use futures::channel::oneshot;
 use itertools::Itertools;
@@ -5,7 +7,7 @@ use itertools::Itertools;
 use zksync_mempool::ProposedBlock;
 use zksync_types::{Account, AccountId, Address};
 // Local uses
-use crate::state_keeper::init_params::ZkSyncStateInitParams;
+use crate::state_keeper::{init_params::ZkSyncStateInitParams, seal_policy::BlockSealPolicy};
 
 // Enum for manual control StateKeeper from testkit
 #[derive(Debug)]
@@ -48,6 +50,11 @@ pub(super) struct StateKeeperConfig {
     pub(super) available_block_chunk_sizes: Vec<usize>,
     pub(super) max_miniblock_iterations: usize,
     pub(super) fast_miniblock_iterations: usize,
+    /// Time the transactions with a nonce gap wait for the missing transactions
+    /// before they are executed and fail.
+    pub(super) nonce_gap_timeout: Duration,
+    /// Conditions the block is sealed on besides the block size and the miniblock iterations.
+    pub(super) seal_policy: BlockSealPolicy,
     max_block_size: usize,
 }
 
@@ -57,6 +64,8 @@ impl StateKeeperConfig {
         available_block_chunk_sizes: Vec<usize>,
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
+        nonce_gap_timeout: Duration,
+        seal_policy: BlockSealPolicy,
     ) -> Self {
         // Ensure that available block chunk sizes are sorted and not empty.
         assert!(
@@ -81,6 +90,8 @@ impl StateKeeperConfig {
             available_block_chunk_sizes,
             max_miniblock_iterations,
             fast_miniblock_iterations,
+            nonce_gap_timeout,
+            seal_policy,
             max_block_size,
         }
     }
@@ -97,7 +108,14 @@ mod tests {
     /// Checks that config can be created if provided values are correct.
     #[test]
     fn create_config() {
-        let config = StateKeeperConfig::new(AccountId(0), vec![1, 2, 3], 10, 20);
+        let config = StateKeeperConfig::new(
+            AccountId(0),
+            vec![1, 2, 3],
+            10,
+            20,
+            Duration::from_secs(0),
+            BlockSealPolicy::default(),
+        );
         assert_eq!(config.max_block_size, 3);
     }
 
@@ -106,7 +124,14 @@ mod tests {
     #[should_panic(expected = "Block chunk sizes are not in order")]
     fn config_chunks_out_of_order() {
         let incorrect_chunks = vec![3, 1, 2];
-        let _config = StateKeeperConfig::new(AccountId(0), incorrect_chunks, 10, 20);
+        let _config = StateKeeperConfig::new(
+            AccountId(0),
+            incorrect_chunks,
+            10,
+            20,
+            Duration::from_secs(0),
+            BlockSealPolicy::default(),
+        );
     }
 
     /// Checks that if chunk sizes are empty, it will panic.
@@ -114,6 +139,13 @@ mod tests {
     #[should_panic(expected = "Block chunk sizes are empty")]
     fn config_chunks_empty() {
         let incorrect_chunks = vec![];
-        let _config = StateKeeperConfig::new(AccountId(0), incorrect_chunks, 10, 20);
+        let _config = StateKeeperConfig::new(
+            AccountId(0),
+            incorrect_chunks,
+            10,
+            20,
+            Duration::from_secs(0),
+            BlockSealPolicy::default(),
+        );
     }
 }
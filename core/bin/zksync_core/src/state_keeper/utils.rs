@@ -1,4 +1,6 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use zksync_types::{mempool::SignedTxVariant, tx::ZkSyncTx};
 
 pub(super) fn system_time_timestamp() -> u64 {
     SystemTime::now()
@@ -6,3 +8,21 @@ pub(super) fn system_time_timestamp() -> u64 {
         .expect("failed to get system time")
         .as_secs()
 }
+
+/// Verifies the zkSync signatures of the proposed transactions at once, so they're not
+/// verified one by one when the transactions are executed.
+pub(super) fn verify_signatures(txs: &mut [SignedTxVariant]) {
+    let start = Instant::now();
+    let txs = txs
+        .iter_mut()
+        .flat_map(|variant| match variant {
+            SignedTxVariant::Tx(tx) => std::slice::from_mut(tx).iter_mut(),
+            SignedTxVariant::Batch(batch) => batch.txs.iter_mut(),
+        })
+        .map(|tx| &mut tx.tx);
+    if !ZkSyncTx::verify_signatures_batch(txs) {
+        // The invalid signatures are found when the transactions are executed.
+        metrics::increment_counter!("state_keeper.invalid_signatures_batch");
+    }
+    metrics::histogram!("state_keeper.verify_signatures", start.elapsed());
+}
@@ -0,0 +1,108 @@
+//! The aggregator is responsible for updating the network statistics shown by the block explorer,
+//! so the API doesn't have to scan the transactions for them.
+//!
+//! Every run the finalized blocks that are not aggregated yet are added to the statistics
+//! (see `NetworkStatsSchema::aggregate_blocks`), so the statistics lag behind the last verified block
+//! no more than by the aggregation interval.
+//!
+//! Every run also reports the profitability of the operator during the current UTC day to the metrics
+//! (see `zksync_types::operator_costs`).
+
+// External uses
+use chrono::Utc;
+use num::ToPrimitive;
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::DBConfig;
+use zksync_storage::{BigDecimal, ConnectionPool};
+use zksync_types::{BlockNumber, TokenId};
+
+/// Maximum amount of blocks aggregated in a single database transaction,
+/// so the aggregation of the whole history doesn't lock the statistics for a long time.
+const AGGREGATION_CHUNK_BLOCKS: u32 = 100;
+
+#[must_use]
+pub fn run_stats_aggregator(config: &DBConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let mut timer = time::interval(config.stats_aggregator_interval());
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            if let Err(e) = aggregate_stats(&db_pool).await {
+                vlog::error!("Can't aggregate the network statistics {:?}", e);
+            }
+            if let Err(e) = report_daily_profitability(&db_pool).await {
+                vlog::error!("Can't report the profitability of the operator {:?}", e);
+            }
+        }
+    })
+}
+
+async fn aggregate_stats(db_pool: &ConnectionPool) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+
+    let mut last_aggregated_block = storage
+        .network_stats_schema()
+        .load_last_aggregated_block()
+        .await?
+        .unwrap_or(BlockNumber(0));
+    while last_aggregated_block < last_verified_block {
+        let to_block = BlockNumber(
+            (*last_aggregated_block + AGGREGATION_CHUNK_BLOCKS).min(*last_verified_block),
+        );
+        storage
+            .network_stats_schema()
+            .aggregate_blocks(to_block)
+            .await?;
+        metrics::gauge!("stats_aggregator.last_aggregated_block", *to_block as f64);
+
+        last_aggregated_block = to_block;
+    }
+
+    Ok(())
+}
+
+async fn report_daily_profitability(db_pool: &ConnectionPool) -> anyhow::Result<()> {
+    let mut storage = db_pool.access_storage().await?;
+    let today = Utc::now().date().naive_utc();
+    let costs = match storage
+        .operator_costs_schema()
+        .load_daily_costs(today, today)
+        .await?
+        .pop()
+    {
+        Some(day) => day.costs,
+        None => return Ok(()),
+    };
+
+    let mut tokens: Vec<TokenId> = costs.collected_fees.keys().copied().collect();
+    tokens.push(TokenId(0));
+    let prices = storage
+        .operator_costs_schema()
+        .load_token_prices(&tokens)
+        .await?;
+    // The profitability is unknown until the prices of all the tokens are known.
+    if let Some(profitability) = costs.profitability(&prices) {
+        let to_f64 = |value: &BigDecimal| value.to_f64().unwrap_or_default();
+        metrics::gauge!(
+            "operator_costs.daily_cost_usd",
+            to_f64(&profitability.cost_usd)
+        );
+        metrics::gauge!(
+            "operator_costs.daily_revenue_usd",
+            to_f64(&profitability.revenue_usd)
+        );
+        metrics::gauge!(
+            "operator_costs.daily_profit_usd",
+            to_f64(&profitability.profit_usd)
+        );
+    }
+
+    Ok(())
+}
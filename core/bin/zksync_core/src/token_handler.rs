@@ -3,7 +3,10 @@
 //!
 //! To set the name and the decimals parameter for the token, a match is searched for with the
 //! token list (which is taken from the environment). If the token address is not found in the
-//! trusted token list, then the default values are used (name = "ERC20-{id}", decimals = 18).
+//! trusted token list, then the `symbol` and `decimals` of the token contract are used, so the
+//! tokens listed by anyone through the token governance contract get their actual metadata.
+//! If the contract doesn't provide valid metadata, then the default values are used
+//! (name = "ERC20-{id}", decimals = 18).
 
 // Built-in deps
 use std::collections::HashMap;
@@ -21,12 +24,45 @@ use zksync_types::{
     tokens::{NewTokenEvent, Token, TokenInfo},
     Address, TokenId, TokenKind, U256,
 };
+use zksync_utils::shutdown::ShutdownToken;
 // Local uses
 use crate::eth_watch::EthWatchRequest;
 use web3::contract::Options;
-use zksync_contracts::erc20_contract;
+use zksync_contracts::{erc20_contract, erc20_metadata_contract};
 use zksync_eth_client::EthereumGateway;
 
+/// Maximum length of the token symbol taken from the token contract.
+const MAX_TOKEN_SYMBOL_LENGTH: usize = 16;
+/// Maximum number of decimals of the token taken from the token contract.
+const MAX_TOKEN_DECIMALS: u8 = 18;
+
+/// Checks that the metadata provided by the token contract can be used for the token.
+fn validate_token_metadata(symbol: &str, decimals: u8) -> anyhow::Result<()> {
+    anyhow::ensure!(!symbol.is_empty(), "symbol is empty");
+    anyhow::ensure!(
+        symbol.len() <= MAX_TOKEN_SYMBOL_LENGTH,
+        "symbol is longer than {} characters",
+        MAX_TOKEN_SYMBOL_LENGTH
+    );
+    anyhow::ensure!(
+        symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'),
+        "symbol contains invalid characters"
+    );
+    // Symbols of the default format are reserved for the tokens without valid metadata.
+    anyhow::ensure!(
+        !symbol.to_ascii_uppercase().starts_with("ERC20-"),
+        "symbol is reserved"
+    );
+    anyhow::ensure!(
+        decimals <= MAX_TOKEN_DECIMALS,
+        "decimals are greater than {}",
+        MAX_TOKEN_DECIMALS
+    );
+    Ok(())
+}
+
 struct TokenHandler {
     connection_pool: ConnectionPool,
     poll_interval: std::time::Duration,
@@ -94,6 +130,60 @@ impl TokenHandler {
             .is_ok()
     }
 
+    /// Loads the `symbol` and `decimals` of the token from its contract.
+    ///
+    /// Returns `None` if the contract doesn't implement these functions or the metadata is invalid.
+    async fn load_token_metadata(&self, address: Address) -> Option<TokenInfo> {
+        let metadata = async {
+            let symbol: String = self
+                .eth_client
+                .call_contract_function(
+                    "symbol",
+                    (),
+                    None,
+                    Options::default(),
+                    None,
+                    address,
+                    erc20_metadata_contract(),
+                )
+                .await?;
+            let decimals: U256 = self
+                .eth_client
+                .call_contract_function(
+                    "decimals",
+                    (),
+                    None,
+                    Options::default(),
+                    None,
+                    address,
+                    erc20_metadata_contract(),
+                )
+                .await?;
+            anyhow::ensure!(
+                decimals <= U256::from(MAX_TOKEN_DECIMALS),
+                "decimals are greater than {}",
+                MAX_TOKEN_DECIMALS
+            );
+            let decimals = decimals.low_u32() as u8;
+            validate_token_metadata(&symbol, decimals)?;
+            Ok::<_, anyhow::Error>(TokenInfo::new(address, &symbol, decimals))
+        }
+        .await;
+
+        match metadata {
+            Ok(token_info) => Some(token_info),
+            Err(err) => {
+                vlog::warn!(
+                    "Can't use the metadata of the token {:#x}, default values are used: {}",
+                    address,
+                    err
+                );
+                metrics::increment_counter!("token_handler.invalid_token_metadata");
+                None
+            }
+        }
+    }
+
     async fn save_new_tokens(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -110,7 +200,7 @@ impl TokenHandler {
                 continue;
             }
 
-            // Find a token in the list of trusted tokens
+            // Find a token in the list of trusted tokens, then take the metadata from the token contract
             // or use default values (name = "ERC20-{id}", decimals = 18).
             let default_symbol = format!("ERC20-{}", token_event.id);
             let default_decimals = 18;
@@ -123,19 +213,21 @@ impl TokenHandler {
             };
 
             let token_from_list = {
-                let token_info = self.token_list.get(&token_event.address).cloned();
+                let token_info = match self.token_list.get(&token_event.address).cloned() {
+                    Some(token_info) => Some(token_info),
+                    None if is_erc20 => self.load_token_metadata(token_event.address).await,
+                    None => None,
+                };
 
-                if let Some(token_info) = token_info {
-                    Some(Token::new(
+                token_info.map(|token_info| {
+                    Token::new(
                         token_event.id,
                         token_info.address,
                         &token_info.symbol,
                         token_info.decimals,
                         token_kind,
-                    ))
-                } else {
-                    None
-                }
+                    )
+                })
             };
 
             let token = match token_from_list {
@@ -197,10 +289,13 @@ impl TokenHandler {
         Ok(new_tokens)
     }
 
-    async fn run(&mut self) {
+    async fn run(&mut self, shutdown: ShutdownToken) {
         let mut timer = tokio::time::interval(self.poll_interval);
         loop {
-            timer.tick().await;
+            tokio::select! {
+                _ = timer.tick() => {},
+                _ = shutdown.signalled() => break,
+            }
 
             let new_tokens_events = self.load_new_token_events().await;
 
@@ -243,12 +338,30 @@ pub fn run_token_handler(
     eth_client: EthereumGateway,
     config: &TokenHandlerConfig,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     let config = config.clone();
     tokio::spawn(async move {
         let mut token_handler =
             TokenHandler::new(db_pool, eth_watcher_req, eth_client, config.clone());
 
-        token_handler.run().await
+        token_handler.run(shutdown).await
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_metadata_validation() {
+        assert!(validate_token_metadata("USDC", 6).is_ok());
+        assert!(validate_token_metadata("wBTC.e", 8).is_ok());
+
+        assert!(validate_token_metadata("", 18).is_err());
+        assert!(validate_token_metadata("VERYLONGTOKENSYMBOL", 18).is_err());
+        assert!(validate_token_metadata("ETH ", 18).is_err());
+        assert!(validate_token_metadata("erc20-1", 18).is_err());
+        assert!(validate_token_metadata("DAI", 19).is_err());
+    }
+}
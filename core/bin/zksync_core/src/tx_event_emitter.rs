@@ -5,12 +5,13 @@
 //! it's used as the event queue backend.
 
 // External uses
-use futures::{channel::mpsc, StreamExt};
+use futures::channel::mpsc;
 use tokio::task::JoinHandle;
 
 // Workspace deps
 use zksync_storage::ConnectionPool;
 use zksync_types::{BlockNumber, ExecutedOperations};
+use zksync_utils::shutdown::ShutdownToken;
 
 /// Miniblock operations processed by the state keeper.
 #[derive(Debug)]
@@ -23,12 +24,13 @@ pub struct ProcessedOperations {
 pub fn run_tx_event_emitter_task(
     db_pool: ConnectionPool,
     mut receiever: mpsc::Receiver<ProcessedOperations>,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         while let Some(ProcessedOperations {
             block_number,
             executed_ops,
-        }) = receiever.next().await
+        }) = shutdown.recv(&mut receiever).await
         {
             let mut storage = db_pool
                 .access_storage()
@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use std::str::FromStr;
 // External uses
 use num::BigUint;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
@@ -11,6 +11,8 @@ use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
 use super::transactions::ETHStats;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::block::Block;
+use zksync_types::operator_costs::{split_fee, L1CostKind};
+use zksync_types::{BlockNumber, TokenId, TokenLike};
 
 /// Abstract database access trait, optimized for the needs of `ETHSender`.
 #[async_trait::async_trait]
@@ -48,9 +50,12 @@ pub(super) trait DatabaseInterface {
     ) -> anyhow::Result<()>;
 
     /// Saves a new unconfirmed operation to the database.
+    /// `sender` is the additional operator account the operation is sent from, if any.
+    #[allow(clippy::too_many_arguments)]
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
@@ -58,6 +63,14 @@ pub(super) trait DatabaseInterface {
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
+    /// Stores the initial nonce of the additional operator account, unless it's already stored.
+    async fn initialize_sender_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        sender: Address,
+        nonce: i64,
+    ) -> anyhow::Result<()>;
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -75,14 +88,26 @@ pub(super) trait DatabaseInterface {
         new_gas_value: U256,
     ) -> anyhow::Result<()>;
 
-    /// Marks an operation as completed in the database.
+    /// Marks an operation as completed in the database, `eth_block` is the block
+    /// the confirmed transaction is included in. If the `fee` paid for the transaction
+    /// is known, it's split between the blocks of the operation and stored as their L1 costs.
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
+        eth_block: u64,
+        fee: Option<U256>,
         op: &ETHOperation,
     ) -> anyhow::Result<()>;
 
+    /// Stores the L1 costs of the blocks the withdrawals of which were completed by the transaction.
+    async fn save_withdrawals_costs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        hash: &H256,
+        costs: &[(BlockNumber, BigUint)],
+    ) -> anyhow::Result<()>;
+
     /// Loads the stored Ethereum operations stats.
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats>;
 
@@ -105,6 +130,20 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
+
+    /// Loads the L1 address of the token.
+    async fn load_token_address(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        token: TokenId,
+    ) -> anyhow::Result<Address>;
+
+    /// Stores the latest Ethereum block, the confirmations of the transactions are counted up to it.
+    async fn update_last_eth_block(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: u64,
+    ) -> anyhow::Result<()>;
 }
 
 /// The actual database wrapper.
@@ -180,6 +219,7 @@ impl DatabaseInterface for Database {
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
@@ -188,7 +228,8 @@ impl DatabaseInterface for Database {
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
             .ethereum_schema()
-            .save_new_eth_tx(
+            .save_new_eth_tx_from_sender(
+                sender,
                 op_type,
                 op,
                 deadline_block,
@@ -200,6 +241,18 @@ impl DatabaseInterface for Database {
         Ok(result)
     }
 
+    async fn initialize_sender_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        sender: Address,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        Ok(connection
+            .ethereum_schema()
+            .initialize_sender_nonce(sender, nonce)
+            .await?)
+    }
+
     async fn add_hash_entry(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -255,6 +308,8 @@ impl DatabaseInterface for Database {
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
+        eth_block: u64,
+        fee: Option<U256>,
         op: &ETHOperation,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
@@ -312,12 +367,43 @@ impl DatabaseInterface for Database {
             _ => {}
         }
 
+        if let (Some(fee), Some((_, aggregated_op))) = (fee, &op.op) {
+            if let Some(kind) = L1CostKind::from_action_type(aggregated_op.get_action_type()) {
+                let (first_block, last_block) = aggregated_op.get_block_range();
+                let blocks: Vec<_> = (*first_block..=*last_block).map(BlockNumber).collect();
+                let fee = BigUint::from_str(&fee.to_string()).unwrap();
+                let shares = split_fee(&fee, &vec![1; blocks.len()]);
+                let costs: Vec<_> = blocks.into_iter().zip(shares).collect();
+                transaction
+                    .operator_costs_schema()
+                    .save_l1_costs(hash, kind, &costs)
+                    .await?;
+            }
+        }
+
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
+        transaction
+            .ethereum_schema()
+            .save_eth_tx_block(hash, eth_block)
+            .await?;
         transaction.commit().await?;
 
         Ok(())
     }
 
+    async fn save_withdrawals_costs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        hash: &H256,
+        costs: &[(BlockNumber, BigUint)],
+    ) -> anyhow::Result<()> {
+        connection
+            .operator_costs_schema()
+            .save_l1_costs(hash, L1CostKind::Withdrawals, costs)
+            .await?;
+        Ok(())
+    }
+
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats> {
         let stats = connection.ethereum_schema().load_stats().await?;
         Ok(stats.into())
@@ -343,6 +429,31 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn load_token_address(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        token: TokenId,
+    ) -> anyhow::Result<Address> {
+        let token = connection
+            .tokens_schema()
+            .get_token(TokenLike::Id(token))
+            .await?
+            .ok_or_else(|| anyhow::format_err!("Token {} is not found", token))?;
+        Ok(token.address)
+    }
+
+    async fn update_last_eth_block(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block_number: u64,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .update_last_eth_block(block_number)
+            .await?;
+        Ok(())
+    }
 }
 
 impl Database {
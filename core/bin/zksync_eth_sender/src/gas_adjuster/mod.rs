@@ -1,7 +1,9 @@
 // Built-in deps
 use std::{collections::VecDeque, marker::PhantomData, time::Instant};
 // External deps
+use web3::contract::Options;
 use zksync_basic_types::U256;
+use zksync_config::configs::eth_sender::EthTxType;
 use zksync_eth_client::EthereumGateway;
 // Local deps
 use crate::database::DatabaseInterface;
@@ -11,6 +13,51 @@ mod parameters;
 #[cfg(test)]
 mod tests;
 
+/// Type identifier of the EIP-1559 transactions.
+const EIP1559_TX_TYPE: u64 = 2;
+
+/// Fees of the Ethereum transaction, depending on its type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum TxFees {
+    Legacy {
+        gas_price: U256,
+    },
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+}
+
+impl TxFees {
+    /// Returns the highest price per gas the transaction may pay.
+    /// This is the price stored as the last used gas price of the operation.
+    pub fn gas_price(&self) -> U256 {
+        match self {
+            Self::Legacy { gas_price } => *gas_price,
+            Self::Eip1559 {
+                max_fee_per_gas, ..
+            } => *max_fee_per_gas,
+        }
+    }
+
+    /// Sets the transaction type and the fees to the transaction options.
+    pub fn apply(self, options: &mut Options) {
+        match self {
+            Self::Legacy { gas_price } => {
+                options.gas_price = Some(gas_price);
+            }
+            Self::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => {
+                options.transaction_type = Some(EIP1559_TX_TYPE.into());
+                options.max_fee_per_gas = Some(max_fee_per_gas);
+                options.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+            }
+        }
+    }
+}
+
 /// Gas adjuster is an entity capable of scaling the gas price for
 /// all the Ethereum transactions.
 ///
@@ -71,7 +118,8 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     }
 
     /// Calculates a new gas amount for the replacement of the stuck tx.
-    /// Replacement price is usually suggested to be at least 10% higher, we make it 15% higher.
+    /// Replacement price is usually suggested to be at least 10% higher, we make it higher
+    /// by the configured bump percent (15% by default).
     pub async fn get_gas_price(
         &mut self,
         ethereum: &EthereumGateway,
@@ -85,6 +133,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             // We're suggesting the max price, so we must notify the log
             // entry about it.
             vlog::warn!("Maximum possible gas price will be used: <{}>", price);
+            Self::report_hard_cap(price);
         }
 
         // Report used price to be gathered by the statistics module.
@@ -93,6 +142,77 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         Ok(price)
     }
 
+    /// Calculates the fees of the transaction of the given type.
+    /// `sent_txs` is the amount of the transactions already sent for the operation,
+    /// `old_tx_gas_price` is the gas price of the last one of them.
+    pub async fn get_fees(
+        &mut self,
+        ethereum: &EthereumGateway,
+        tx_type: EthTxType,
+        old_tx_gas_price: Option<U256>,
+        sent_txs: usize,
+    ) -> anyhow::Result<TxFees> {
+        match tx_type {
+            EthTxType::Legacy => {
+                let gas_price = self.get_gas_price(ethereum, old_tx_gas_price).await?;
+                Ok(TxFees::Legacy { gas_price })
+            }
+            EthTxType::Eip1559 => {
+                self.get_eip1559_fees(ethereum, old_tx_gas_price, sent_txs)
+                    .await
+            }
+        }
+    }
+
+    /// Calculates the fees of the EIP-1559 transaction based on the base fee of the latest block.
+    ///
+    /// Max fee per gas covers the doubled base fee, so the transaction stays valid even if the
+    /// base fee grows for several blocks in a row, while only the actual base fee and the priority
+    /// fee are paid. Replacement of the stuck tx must increase both fees: the max fee is scaled up
+    /// the same way as the legacy gas price, and the priority fee is scaled up once per every
+    /// transaction already sent for the operation, since it's not stored.
+    async fn get_eip1559_fees(
+        &mut self,
+        ethereum: &EthereumGateway,
+        old_tx_max_fee: Option<U256>,
+        sent_txs: usize,
+    ) -> anyhow::Result<TxFees> {
+        let base_fee = ethereum.get_base_fee().await?;
+
+        let mut priority_fee = U256::from(parameters::priority_fee());
+        for _ in 0..sent_txs {
+            priority_fee = self.limit_max(self.scale_up(priority_fee, U256::zero()));
+        }
+
+        let network_max_fee = base_fee * 2 + priority_fee;
+        let max_fee = if let Some(old_max_fee) = old_tx_max_fee {
+            // Stuck transaction, scale it up.
+            self.scale_up(old_max_fee, network_max_fee)
+        } else {
+            network_max_fee
+        };
+        // Now, cut the max fee if it's too big.
+        let max_fee = self.limit_max(max_fee);
+        let priority_fee = std::cmp::min(priority_fee, max_fee);
+
+        if max_fee == self.get_current_max_price() {
+            vlog::warn!(
+                "Maximum possible max fee per gas will be used: <{}>",
+                max_fee
+            );
+            Self::report_hard_cap(max_fee);
+        }
+
+        // Report the price expected to be paid to be gathered by the statistics module.
+        self.statistics
+            .add_sample(std::cmp::min(base_fee + priority_fee, max_fee));
+
+        Ok(TxFees::Eip1559 {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: priority_fee,
+        })
+    }
+
     /// Performs an actualization routine for `GasAdjuster`:
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
@@ -151,10 +271,23 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     }
 
     fn scale_up(&self, price_to_scale: U256, current_network_price: U256) -> U256 {
-        let replacement_price = (price_to_scale * U256::from(115)) / U256::from(100);
+        let multiplier = U256::from(100 + parameters::bump_percent());
+        let replacement_price = (price_to_scale * multiplier) / U256::from(100);
         std::cmp::max(current_network_price, replacement_price)
     }
 
+    /// Alerts about the price reaching the hard cap: transactions may get stuck until
+    /// the network price drops or the cap is raised by an administrator.
+    fn report_hard_cap(price: U256) {
+        if parameters::hard_cap().map(U256::from) == Some(price) {
+            vlog::error!(
+                "Gas price reached the hard cap: <{}>, transactions may not be mined",
+                price
+            );
+            metrics::increment_counter!("eth_sender.gas_price_hard_cap_reached");
+        }
+    }
+
     fn limit_max(&self, price: U256) -> U256 {
         let limit = self.get_current_max_price();
 
@@ -162,8 +295,13 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     }
 
     /// Returns current max gas price that can be used to send transactions.
+    /// It's the gas price limit based on the statistics, but never above the hard cap.
     pub fn get_current_max_price(&self) -> U256 {
-        self.statistics.get_limit()
+        let limit = self.statistics.get_limit();
+        match parameters::hard_cap() {
+            Some(hard_cap) => std::cmp::min(limit, hard_cap.into()),
+            None => limit,
+        }
     }
 }
 
@@ -5,6 +5,9 @@
 //!   gas price suggested by `GasAdjuster`.
 //! - Maximum gas price scale: multiplier to be applied to the average gas price to
 //!   calculate the upper limit for gas price in `GasAdjuster`.
+//! - Priority fee: priority fee per gas of the EIP-1559 transactions.
+//! - Bump percent: percent the gas price of the stuck transaction is increased by on its replacement.
+//! - Hard cap: gas price the upper limit never exceeds, regardless of the statistics.
 //!
 //! The module uses a child module `parameters_impl` which contains two implementations
 //! for functions declared in module: one for the actual usage, and one for tests.
@@ -41,6 +44,33 @@ pub fn sample_adding_interval() -> Duration {
     parameters_impl::sample_adding_interval()
 }
 
+/// Obtains the priority fee per gas of the EIP-1559 transactions.
+///
+/// This value is not cached internally, as it may be changed for the already running
+/// server by an administrator. This may be required if the transactions aren't included
+/// by the miners fast enough.
+pub fn priority_fee() -> u64 {
+    parameters_impl::priority_fee()
+}
+
+/// Obtains the percent the gas price of the stuck transaction is increased by on its replacement.
+///
+/// This value is not cached internally, as it may be changed for the already running
+/// server by an administrator. This may be required if the replaced transactions
+/// aren't included by the miners fast enough.
+pub fn bump_percent() -> u64 {
+    parameters_impl::bump_percent()
+}
+
+/// Obtains the hard cap of the gas price, if any.
+///
+/// This value is not cached internally, as it may be changed for the already running
+/// server by an administrator. This may be required if the network price stays
+/// above the cap for too long.
+pub fn hard_cap() -> Option<u64> {
+    parameters_impl::hard_cap()
+}
+
 // Actual methods implementation for non-test purposes.
 #[cfg(not(test))]
 mod parameters_impl {
@@ -78,6 +108,36 @@ mod parameters_impl {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.sample_interval()
     }
+
+    /// Obtains the priority fee per gas of the EIP-1559 transactions.
+    ///
+    /// This value is not cached internally, as it may be changed for the already running
+    /// server by an administrator. This may be required if the transactions aren't included
+    /// by the miners fast enough.
+    pub fn priority_fee() -> u64 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.priority_fee
+    }
+
+    /// Obtains the percent the gas price of the stuck transaction is increased by on its replacement.
+    ///
+    /// This value is not cached internally, as it may be changed for the already running
+    /// server by an administrator. This may be required if the replaced transactions
+    /// aren't included by the miners fast enough.
+    pub fn bump_percent() -> u64 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.bump_percent
+    }
+
+    /// Obtains the hard cap of the gas price, if any.
+    ///
+    /// This value is not cached internally, as it may be changed for the already running
+    /// server by an administrator. This may be required if the network price stays
+    /// above the cap for too long.
+    pub fn hard_cap() -> Option<u64> {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.hard_cap
+    }
 }
 
 // Hard-coded implementation for tests.
@@ -103,4 +163,22 @@ mod parameters_impl {
     pub fn sample_adding_interval() -> Duration {
         Duration::from_secs(0)
     }
+
+    /// `priority_fee` version for tests not looking for an environment variable value
+    /// but using a fixed priority fee (10 wei) instead.
+    pub fn priority_fee() -> u64 {
+        10
+    }
+
+    /// `bump_percent` version for tests not looking for an environment variable value
+    /// but using a fixed bump (15%) instead.
+    pub fn bump_percent() -> u64 {
+        15
+    }
+
+    /// `hard_cap` version for tests not looking for an environment variable value
+    /// but using a fixed cap (1000 gwei) instead.
+    pub fn hard_cap() -> Option<u64> {
+        Some(1_000_000_000_000)
+    }
 }
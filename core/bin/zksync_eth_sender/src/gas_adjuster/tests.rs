@@ -1,9 +1,13 @@
 // Built-in uses
 // Workspace uses
 use zksync_basic_types::U256;
+use zksync_config::configs::eth_sender::EthTxType;
 // Local uses
 use crate::{
-    gas_adjuster::{parameters::limit_scale_factor, GasStatistics},
+    gas_adjuster::{
+        parameters::{hard_cap, limit_scale_factor, priority_fee},
+        GasStatistics, TxFees,
+    },
     tests::mock::{default_eth_sender, MockDatabase},
     DatabaseInterface, GasAdjuster,
 };
@@ -91,6 +95,56 @@ async fn lower_gas_limit() {
     }
 }
 
+/// Checks the fees of the EIP-1559 transactions: max fee covers the doubled base fee, and
+/// for the stuck transactions both fees are increased by at least 15%.
+#[tokio::test]
+async fn eip1559_fees() {
+    const BASE_FEE: u64 = 100;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee(BASE_FEE.into())
+        .await
+        .unwrap();
+
+    let fees = gas_adjuster
+        .get_fees(&ethereum, EthTxType::Eip1559, None, 0)
+        .await
+        .unwrap();
+    let max_fee = BASE_FEE * 2 + priority_fee();
+    assert_eq!(
+        fees,
+        TxFees::Eip1559 {
+            max_fee_per_gas: max_fee.into(),
+            max_priority_fee_per_gas: priority_fee().into(),
+        }
+    );
+
+    // Replacement of the stuck transaction.
+    let fees = gas_adjuster
+        .get_fees(&ethereum, EthTxType::Eip1559, Some(max_fee.into()), 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        fees,
+        TxFees::Eip1559 {
+            max_fee_per_gas: (max_fee * 115 / 100).into(),
+            max_priority_fee_per_gas: (priority_fee() * 115 / 100).into(),
+        }
+    );
+
+    // Legacy transactions are not affected by the base fee.
+    let fees = gas_adjuster
+        .get_fees(&ethereum, EthTxType::Legacy, None, 0)
+        .await
+        .unwrap();
+    let gas_price = ethereum.get_mock().unwrap().get_gas_price().await.unwrap();
+    assert_eq!(fees, TxFees::Legacy { gas_price });
+}
+
 // Checks that after re-creation the price limit is restored from the database.
 #[tokio::test]
 async fn gas_price_limit_restore() {
@@ -146,6 +200,32 @@ async fn initial_upper_gas_limit() {
     assert_eq!(scaled_gas, PRICE_LIMIT.into());
 }
 
+/// Checks that the price is clamped by the hard cap, even if the limit
+/// loaded from the database is higher.
+#[tokio::test]
+async fn gas_price_hard_cap() {
+    let hard_cap = U256::from(hard_cap().unwrap());
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+
+    db.update_gas_price_limit((hard_cap * 2).as_u64() as i64)
+        .await
+        .unwrap();
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    assert_eq!(gas_adjuster.get_current_max_price(), hard_cap);
+
+    // Set the gas price in Ethereum, which is greater than the hard cap.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_gas_price(hard_cap + 1)
+        .await
+        .unwrap();
+
+    let scaled_gas = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+    assert_eq!(scaled_gas, hard_cap);
+}
+
 /// Checks whether the average gas price is stored to the database correctly.
 #[tokio::test]
 async fn average_gas_price_stored_correctly() {
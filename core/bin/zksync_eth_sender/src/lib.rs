@@ -11,29 +11,34 @@ use anyhow::format_err;
 use tokio::{task::JoinHandle, time};
 use web3::{
     contract::Options,
-    types::{TransactionReceipt, H256, U256},
+    types::{Address, TransactionReceipt, H256, U256},
 };
 // Workspace uses
 use zksync_config::ETHSenderConfig;
 use zksync_eth_client::{EthereumGateway, SignedCallResult};
+use zksync_health_check::Heartbeat;
 use zksync_storage::ConnectionPool;
 use zksync_types::ethereum::ETHOperation;
+use zksync_utils::shutdown::ShutdownToken;
 // Local uses
 use self::{
     database::{Database, DatabaseInterface},
-    gas_adjuster::GasAdjuster,
+    gas_adjuster::{GasAdjuster, TxFees},
     transactions::*,
     tx_queue::{TxData, TxQueue, TxQueueBuilder},
+    withdrawals::WithdrawalsAggregator,
 };
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     gas_counter::GasCounter,
+    operator_costs::L1CostKind,
 };
 
 mod database;
 mod gas_adjuster;
 mod transactions;
 mod tx_queue;
+mod withdrawals;
 
 #[cfg(test)]
 mod tests;
@@ -95,6 +100,20 @@ enum TxCheckMode {
 /// transaction is still guaranteed to be preserved, since every sent tx has the assigned nonce
 /// which makes it impossible to get sent transactions committed out of order.
 ///
+/// # Additional operator accounts
+///
+/// Proofs and block executions can be sent from the additional operator accounts, so they're not
+/// queued behind the commits in the same nonce sequence. Every account has its own nonce tracked in
+/// the database. Since the transactions of the different accounts can be executed in any order,
+/// operations depending on the operations sent from another account are only sent after the latter
+/// are confirmed (see `TxQueue` for details).
+///
+/// # Withdrawals
+///
+/// Blocks are executed without completing their withdrawals, which are stored in the pending
+/// balances of the contract. If enabled, the withdrawals of the executed blocks are completed
+/// in batches by the `WithdrawalsAggregator`, sending them from its own account.
+///
 /// Internally order of the transaction is determined by the underlying `TxQueue`, which provides
 /// transactions to send for `ETHSender` according to the following priority:
 ///
@@ -115,16 +134,26 @@ struct ETHSender<DB: DatabaseInterface> {
     db: DB,
     /// Ethereum intermediator.
     ethereum: EthereumGateway,
+    /// Ethereum intermediators sending the transactions from the additional operator accounts.
+    additional_senders: Vec<(Address, EthereumGateway)>,
     /// Queue for ordered transaction processing.
     tx_queue: TxQueue,
     /// Utility for managing the gas price for transactions.
     gas_adjuster: GasAdjuster<DB>,
+    /// Utility for completing the pending withdrawals of the executed blocks, if enabled.
+    withdrawals: Option<WithdrawalsAggregator>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
-    pub async fn new(options: ETHSenderConfig, db: DB, ethereum: EthereumGateway) -> Self {
+    pub async fn new(
+        options: ETHSenderConfig,
+        db: DB,
+        ethereum: EthereumGateway,
+        additional_senders: Vec<(Address, EthereumGateway)>,
+        withdrawals: Option<WithdrawalsAggregator>,
+    ) -> Self {
         let mut connection = db
             .acquire_connection()
             .await
@@ -157,11 +186,50 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .await
             .expect("Failed loading ETH operations stats");
 
+        for (address, gateway) in &additional_senders {
+            let nonce = gateway
+                .pending_nonce()
+                .await
+                .expect("Can't get the nonce of the additional operator account");
+            db.initialize_sender_nonce(&mut transaction, *address, nonce.as_u64() as i64)
+                .await
+                .expect("Can't initialize the nonce of the additional operator account");
+        }
+
+        // Blocks before the first unconfirmed operation of the given type are confirmed.
+        let confirmed_operations_count =
+            |op_type: AggregatedActionType, sent_operations_count: usize| {
+                ongoing_ops
+                    .iter()
+                    .filter(|eth_op| eth_op.op_type == op_type)
+                    .filter_map(|eth_op| eth_op.op.as_ref())
+                    .map(|(_, op)| *op.get_block_range().0 as usize - 1)
+                    .min()
+                    .unwrap_or(sent_operations_count)
+            };
+        let verify_sender = sender_for(
+            &additional_senders,
+            AggregatedActionType::PublishProofBlocksOnchain,
+        );
+        let separate_verify_sender =
+            verify_sender != sender_for(&additional_senders, AggregatedActionType::CommitBlocks);
+        let separate_execute_sender =
+            verify_sender != sender_for(&additional_senders, AggregatedActionType::ExecuteBlocks);
+
         let tx_queue = TxQueueBuilder::new(options.sender.max_txs_in_flight as usize)
             .with_sent_pending_txs(ongoing_ops.len())
             .with_commit_operations_count(stats.last_committed_block)
             .with_verify_operations_count(stats.last_verified_block)
             .with_execute_operations_count(stats.last_executed_block)
+            .with_separate_senders(separate_verify_sender, separate_execute_sender)
+            .with_confirmed_commit_operations_count(confirmed_operations_count(
+                AggregatedActionType::CommitBlocks,
+                stats.last_committed_block,
+            ))
+            .with_confirmed_verify_operations_count(confirmed_operations_count(
+                AggregatedActionType::PublishProofBlocksOnchain,
+                stats.last_verified_block,
+            ))
             .build();
 
         let gas_adjuster = GasAdjuster::new(&db).await;
@@ -176,20 +244,28 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             ongoing_ops,
             db,
             ethereum,
+            additional_senders,
             tx_queue,
             gas_adjuster,
+            withdrawals,
             options,
         }
     }
 
-    /// Main routine of `ETHSender`.
-    pub async fn run(mut self) {
+    /// Main routine of `ETHSender`, `heartbeat` is updated on every iteration.
+    pub async fn run(mut self, heartbeat: Heartbeat, shutdown: ShutdownToken) {
         // `eth_sender` must perform some of the activities only once per block change.
         // Having `0` as an initial value is to ensure that on the first iteration we will run all the activities.
         let mut last_used_block = 0;
         loop {
+            heartbeat.beat();
             // We perform a loading routine every X seconds.
-            tokio::time::sleep(self.options.sender.tx_poll_period()).await;
+            // On shutdown the loop is left between the iterations, so the sent transactions
+            // are always stored in the database.
+            tokio::select! {
+                _ = tokio::time::sleep(self.options.sender.tx_poll_period()) => {},
+                _ = shutdown.signalled() => break,
+            }
             // If we received an error when loading a new operation, we can't do anything about it and should panic.
             if let Err(error) = self.load_new_operations().await {
                 vlog::error!("Unable to restore operations from the database: {}", error);
@@ -278,6 +354,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         // states because it would be spare requests.
         // The ongoing operations list would be the same for the next step
         if last_used_block != current_block {
+            // The confirmations of the transactions reported by the API are counted up to this block.
+            if let Err(e) = self.update_last_eth_block(current_block).await {
+                Self::process_error(e).await;
+            }
+
             // Queue for storing all the operations that were not finished at this iteration.
             let mut new_ongoing_ops = VecDeque::new();
 
@@ -302,6 +383,19 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     OperationCommitment::Committed => {
                         // Free a slot for the next tx in the queue.
                         self.tx_queue.report_commitment();
+                        if let Some((_, op)) = &current_op.op {
+                            self.tx_queue.report_confirmed_blocks(
+                                current_op.op_type,
+                                op.get_block_range().1,
+                            );
+                        }
+                        if let (
+                            Some(withdrawals),
+                            Some((_, AggregatedOperation::ExecuteBlocks(op))),
+                        ) = (&mut self.withdrawals, &current_op.op)
+                        {
+                            withdrawals.add_executed_blocks(&op.blocks);
+                        }
                     }
                     OperationCommitment::Pending => {
                         // Poll this operation on the next iteration.
@@ -315,6 +409,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             );
             // Store the ongoing operations for the next round.
             self.ongoing_ops = new_ongoing_ops;
+
+            if let Some(withdrawals) = &mut self.withdrawals {
+                if let Err(e) = withdrawals
+                    .proceed(
+                        &self.db,
+                        &mut self.gas_adjuster,
+                        &self.options.sender,
+                        current_block,
+                    )
+                    .await
+                {
+                    Self::process_error(e).await;
+                }
+            }
         }
 
         metrics::histogram!("eth_sender.proceed_next_operations", start.elapsed());
@@ -338,10 +446,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     /// Stores the new operation in the database and sends the corresponding transaction.
     async fn initialize_operation(&mut self, tx: TxData, current_block: u64) -> anyhow::Result<()> {
         let deadline_block = self.get_deadline_block(current_block);
-        let gas_price = self
+        let fees = self
             .gas_adjuster
-            .get_gas_price(&self.ethereum, None)
+            .get_fees(&self.ethereum, self.options.sender.tx_type, None, 0)
             .await?;
+        let gas_price = fees.gas_price();
 
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
@@ -350,10 +459,12 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         let (new_op, signed_tx) = {
             // First, we should store the operation in the database and obtain the assigned
             // operation ID and nonce. Without them we won't be able to sign the transaction.
+            let sender = sender_for(&self.additional_senders, tx.op_type);
             let assigned_data = self
                 .db
                 .save_new_eth_tx(
                     &mut transaction,
+                    sender,
                     tx.op_type,
                     Some(tx.operation.clone()),
                     deadline_block as i64,
@@ -367,6 +478,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op_type: tx.op_type,
                 op: Some(tx.operation),
                 nonce: assigned_data.nonce,
+                sender,
                 last_deadline_block: deadline_block,
                 last_used_gas_price: gas_price,
                 used_tx_hashes: vec![], // No hash yet, will be added below.
@@ -376,7 +488,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             };
 
             // Sign the transaction.
-            let signed_tx = Self::sign_new_tx(&self.ethereum, &new_op).await?;
+            let signed_tx = Self::sign_new_tx(self.gateway(sender)?, &new_op, fees).await?;
 
             // With signed tx, update the hash in the operation entry and in the db.
             new_op.used_tx_hashes.push(signed_tx.hash);
@@ -396,7 +508,11 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             "Sending new tx: [ETH Operation <id: {}, type: {:?}>. ETH tx: {}. ZKSync operation: {}]",
             new_op.id, new_op.op_type, self.eth_tx_description(&signed_tx), self.zksync_operation_description(&new_op),
         );
-        if let Err(e) = self.ethereum.send_raw_tx(signed_tx.raw_tx).await {
+        if let Err(e) = self
+            .gateway(new_op.sender)?
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+        {
             // Sending tx error is not critical: this will result in transaction being considered stuck,
             // and resent. We can't do anything about this failure either, since it's most probably is not
             // related to the node logic, so we just log this error and pretend to have this operation
@@ -472,7 +588,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed { eth_block, fee } => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -506,9 +622,16 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, eth_block, fee, op)
                         .await?;
                     transaction.commit().await?;
+                    if let (Some(fee), Some((_, aggregated_op))) = (fee, &op.op) {
+                        if let Some(kind) =
+                            L1CostKind::from_action_type(aggregated_op.get_action_type())
+                        {
+                            report_tx_fee(kind, fee);
+                        }
+                    }
                     return Ok(OperationCommitment::Committed);
                 }
                 TxCheckOutcome::Stuck => {
@@ -556,7 +679,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             op.id,
             self.eth_tx_description(&new_tx),
         );
-        self.ethereum.send_raw_tx(new_tx.raw_tx).await?;
+        self.gateway(op.sender)?.send_raw_tx(new_tx.raw_tx).await?;
         transaction.commit().await?;
 
         metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
@@ -578,6 +701,27 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         panic!("Cannot operate after unexpected TX failure");
     }
 
+    /// Returns the Ethereum intermediator sending the transactions from the given operator account,
+    /// `None` stands for the main operator account.
+    fn gateway(&self, sender: Option<Address>) -> anyhow::Result<&EthereumGateway> {
+        match sender {
+            None => Ok(&self.ethereum),
+            Some(sender) => self
+                .additional_senders
+                .iter()
+                .find(|(address, _)| *address == sender)
+                .map(|(_, gateway)| gateway)
+                .ok_or_else(|| format_err!("Operator account {:#x} is not configured", sender)),
+        }
+    }
+
+    async fn update_last_eth_block(&self, current_block: u64) -> anyhow::Result<()> {
+        let mut connection = self.db.acquire_connection().await?;
+        self.db
+            .update_last_eth_block(&mut connection, current_block)
+            .await
+    }
+
     /// Helper method encapsulating the logic of determining the next deadline block.
     fn get_deadline_block(&self, current_block: u64) -> u64 {
         current_block + self.options.sender.expected_wait_time_block
@@ -602,7 +746,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed {
+                        eth_block: current_block.saturating_sub(status.confirmations),
+                        fee: status
+                            .receipt
+                            .as_ref()
+                            .and_then(|receipt| tx_fee(receipt, op.last_used_gas_price)),
+                    }
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -638,6 +788,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     async fn sign_new_tx(
         ethereum: &EthereumGateway,
         op: &ETHOperation,
+        fees: TxFees,
     ) -> anyhow::Result<SignedCallResult> {
         let tx_options = {
             // We set the gas limit for commit / verify operations as pre-calculated estimation.
@@ -656,14 +807,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 gas_limit
             );
 
-            Options {
+            let mut options = Options {
                 nonce: Some(op.nonce),
-                gas_price: Some(op.last_used_gas_price),
                 gas: Some(gas_limit),
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
                 ..Default::default()
-            }
+            };
+            fees.apply(&mut options);
+            options
         };
 
         let signed_tx = ethereum
@@ -705,7 +855,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         let tx_options = self.tx_options_from_stuck_tx(stuck_tx).await?;
 
         let raw_tx = stuck_tx.encoded_tx_data.clone();
-        let signed_tx = self.ethereum.sign_prepared_tx(raw_tx, tx_options).await?;
+        let signed_tx = self
+            .gateway(stuck_tx.sender)?
+            .sign_prepared_tx(raw_tx, tx_options)
+            .await?;
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
@@ -722,10 +875,16 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     ) -> anyhow::Result<Options> {
         let old_tx_gas_price = stuck_tx.last_used_gas_price;
 
-        let new_gas_price = self
+        let fees = self
             .gas_adjuster
-            .get_gas_price(&self.ethereum, Some(old_tx_gas_price))
+            .get_fees(
+                &self.ethereum,
+                self.options.sender.tx_type,
+                Some(old_tx_gas_price),
+                stuck_tx.used_tx_hashes.len(),
+            )
             .await?;
+        let new_gas_price = fees.gas_price();
         let nonce = stuck_tx.nonce;
         let gas_limit = Self::gas_limit_for_op(stuck_tx);
 
@@ -745,7 +904,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         );
 
         Ok(Options::with(move |opt| {
-            opt.gas_price = Some(new_gas_price);
+            fees.apply(opt);
             opt.nonce = Some(nonce);
             opt.gas = Some(gas_limit);
         }))
@@ -796,17 +955,49 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     }
 }
 
+/// Returns the additional operator account the operations of the given type are sent from,
+/// `None` stands for the main operator account.
+///
+/// The first additional account sends the proofs, and the second one (or the first one,
+/// if it's the only one) sends the block executions. Commits are always sent from the main account.
+fn sender_for(
+    additional_senders: &[(Address, EthereumGateway)],
+    op_type: AggregatedActionType,
+) -> Option<Address> {
+    let sender = match op_type {
+        AggregatedActionType::PublishProofBlocksOnchain => additional_senders.first(),
+        AggregatedActionType::ExecuteBlocks => additional_senders
+            .get(1)
+            .or_else(|| additional_senders.first()),
+        AggregatedActionType::CommitBlocks | AggregatedActionType::CreateProofBlocks => None,
+    };
+    sender.map(|(address, _)| *address)
+}
+
 #[must_use]
 pub fn run_eth_sender(
     pool: ConnectionPool,
     eth_gateway: EthereumGateway,
+    additional_eth_gateways: Vec<(Address, EthereumGateway)>,
+    withdrawals_eth_gateway: Option<EthereumGateway>,
     options: ETHSenderConfig,
+    heartbeat: Heartbeat,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     let db = Database::new(pool);
+    let withdrawals = withdrawals_eth_gateway
+        .map(|gateway| WithdrawalsAggregator::new(gateway, options.withdrawals.clone()));
 
     tokio::spawn(async move {
-        let eth_sender = ETHSender::new(options, db, eth_gateway).await;
+        let eth_sender = ETHSender::new(
+            options,
+            db,
+            eth_gateway,
+            additional_eth_gateways,
+            withdrawals,
+        )
+        .await;
 
-        eth_sender.run().await
+        eth_sender.run(heartbeat, shutdown).await
     })
 }
@@ -4,15 +4,19 @@
 use std::collections::VecDeque;
 use std::convert::TryFrom;
 // External uses
+use num::BigUint;
 use tokio::sync::RwLock;
 use web3::contract::Options;
-use zksync_basic_types::{BlockNumber, H256, U256};
+use zksync_basic_types::{Address, BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{
+    ETHSenderConfig, EthSignerType, EthTxType, GasLimit, Sender, Withdrawals,
+};
 use zksync_eth_client::EthereumGateway;
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+use zksync_types::TokenId;
 // Local uses
 use super::ETHSender;
 use crate::database::DatabaseInterface;
@@ -200,6 +204,7 @@ impl DatabaseInterface for MockDatabase {
     async fn save_new_eth_tx(
         &self,
         _connection: &mut StorageProcessor<'_>,
+        sender: Option<Address>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
@@ -208,7 +213,11 @@ impl DatabaseInterface for MockDatabase {
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
         let id = eth_operations.len() as i64;
-        let nonce = eth_operations.len();
+        // Every operator account has its own nonce sequence.
+        let nonce = eth_operations
+            .iter()
+            .filter(|eth_op| eth_op.sender == sender)
+            .count();
 
         // Store with the assigned ID.
         let eth_operation = ETHOperation {
@@ -216,6 +225,7 @@ impl DatabaseInterface for MockDatabase {
             op_type,
             op,
             nonce: nonce.into(),
+            sender,
             last_deadline_block: deadline_block as u64,
             last_used_gas_price: used_gas_price,
             used_tx_hashes: vec![],
@@ -234,6 +244,15 @@ impl DatabaseInterface for MockDatabase {
         Ok(response)
     }
 
+    async fn initialize_sender_nonce(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        _sender: Address,
+        _nonce: i64,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -281,6 +300,8 @@ impl DatabaseInterface for MockDatabase {
         &self,
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
+        _eth_block: u64,
+        _fee: Option<U256>,
         _op: &ETHOperation,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
@@ -323,6 +344,32 @@ impl DatabaseInterface for MockDatabase {
         Ok(eth_stats)
     }
 
+    /// Derives the token address from its ID, as no tokens are stored.
+    async fn load_token_address(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        token: TokenId,
+    ) -> anyhow::Result<Address> {
+        Ok(Address::from_low_u64_be(token.0 as u64))
+    }
+
+    async fn update_last_eth_block(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        _block_number: u64,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn save_withdrawals_costs(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        _hash: &H256,
+        _costs: &[(BlockNumber, BigUint)],
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn is_previous_operation_confirmed(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -440,18 +487,43 @@ async fn build_eth_sender(
             wait_confirmations: super::WAIT_CONFIRMATIONS,
             tx_poll_period: 0,
             is_enabled: true,
+            tx_type: EthTxType::Legacy,
             operator_commit_eth_addr: Default::default(),
-            operator_private_key: Default::default(),
+            signer: EthSignerType::PrivateKey,
+            operator_private_key: Some(Default::default()),
+            signer_url: None,
+            kms_key_id: None,
+            kms_region: None,
+            keystore_path: None,
+            keystore_password: None,
+            additional_operator_eth_addrs: Vec::new(),
+            additional_operator_private_keys: Vec::new(),
+            additional_kms_key_ids: Vec::new(),
+            additional_keystore_paths: Vec::new(),
         },
         gas_price_limit: GasLimit {
             default: 1000,
             sample_interval: 15,
             update_interval: 15,
             scale_factor: 1.0f64,
+            priority_fee: 10,
+            bump_percent: 15,
+            hard_cap: None,
+        },
+        withdrawals: Withdrawals {
+            is_enabled: false,
+            withdrawer_contract_addr: Default::default(),
+            sender_eth_addr: Default::default(),
+            sender_private_key: None,
+            sender_kms_key_id: None,
+            sender_keystore_path: None,
+            max_batch_size: 50,
+            max_delay: 600,
+            gas_budget: 6_000_000,
         },
     };
 
-    ETHSender::new(options, db, ethereum).await
+    ETHSender::new(options, db, ethereum, Vec::new(), None).await
 }
 
 /// Behaves the same as `ETHSender::sign_new_tx`, but does not affect nonce.
@@ -483,6 +555,7 @@ pub(crate) async fn create_signed_tx(
         op_type,
         op: Some(aggregated_operation.clone()),
         nonce: signed_tx.nonce,
+        sender: None,
         last_deadline_block: deadline_block,
         last_used_gas_price: signed_tx.gas_price,
         used_tx_hashes: vec![signed_tx.hash],
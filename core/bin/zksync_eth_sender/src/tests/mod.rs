@@ -139,7 +139,10 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed {
+            eth_block: current_block,
+            fee: None,
+        }
     );
 
     // Pending operation (no enough confirmations).
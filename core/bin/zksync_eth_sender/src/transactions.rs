@@ -5,9 +5,10 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
+use zksync_types::operator_costs::L1CostKind;
 
 /// Collected statistics of the amount of operations sent to the Ethereum.
 /// This structure represents the count of **operations**, and not transactions.
@@ -36,8 +37,9 @@ impl From<StorageETHStats> for ETHStats {
 /// The result of the check for the Ethereum transaction commitment.
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
-    /// Transaction was committed and confirmed.
-    Committed,
+    /// Transaction was committed and confirmed, it's included in the `eth_block`.
+    /// The `fee` paid for the transaction is not set if the receipt isn't available.
+    Committed { eth_block: u64, fee: Option<U256> },
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
@@ -46,6 +48,24 @@ pub enum TxCheckOutcome {
     Failed(Box<TransactionReceipt>),
 }
 
+/// Returns the fee paid for the executed transaction, `None` if the receipt doesn't report the gas used.
+/// The receipts of the nodes not supporting EIP-1559 don't have the effective gas price, so the gas
+/// price the transaction was sent with is used instead.
+pub fn tx_fee(receipt: &TransactionReceipt, gas_price: U256) -> Option<U256> {
+    let gas_used = receipt.gas_used?;
+    Some(gas_used * receipt.effective_gas_price.unwrap_or(gas_price))
+}
+
+/// Reports the fee paid for the transaction to the metrics, in gwei.
+pub fn report_tx_fee(kind: L1CostKind, fee: U256) {
+    let fee_gwei = fee / U256::exp10(9);
+    metrics::counter!(
+        "eth_sender.l1_costs",
+        fee_gwei.low_u64(),
+        "kind" => kind.to_string()
+    );
+}
+
 /// Enumeration denoting if the operation was successfully committed, or not yet.
 #[derive(Debug, PartialEq, Eq)]
 pub enum OperationCommitment {
@@ -58,6 +58,11 @@ pub struct TxQueueBuilder {
     commit_operations_count: usize,
     verify_operations_count: usize,
     execute_operations_count: usize,
+
+    separate_verify_sender: bool,
+    separate_execute_sender: bool,
+    confirmed_commit_operations_count: usize,
+    confirmed_verify_operations_count: usize,
 }
 
 impl TxQueueBuilder {
@@ -69,6 +74,10 @@ impl TxQueueBuilder {
             commit_operations_count: 0,
             verify_operations_count: 0,
             execute_operations_count: 0,
+            separate_verify_sender: false,
+            separate_execute_sender: false,
+            confirmed_commit_operations_count: 0,
+            confirmed_verify_operations_count: 0,
         }
     }
 
@@ -104,12 +113,53 @@ impl TxQueueBuilder {
         }
     }
 
+    /// Sets whether the `verify` operations are sent from another account than the `commit` ones,
+    /// and whether the `execute` operations are sent from another account than the `verify` ones.
+    pub fn with_separate_senders(
+        self,
+        separate_verify_sender: bool,
+        separate_execute_sender: bool,
+    ) -> Self {
+        Self {
+            separate_verify_sender,
+            separate_execute_sender,
+            ..self
+        }
+    }
+
+    /// Sets the amount of blocks with the confirmed `commit` operations.
+    pub fn with_confirmed_commit_operations_count(
+        self,
+        confirmed_commit_operations_count: usize,
+    ) -> Self {
+        Self {
+            confirmed_commit_operations_count,
+            ..self
+        }
+    }
+
+    /// Sets the amount of blocks with the confirmed `verify` operations.
+    pub fn with_confirmed_verify_operations_count(
+        self,
+        confirmed_verify_operations_count: usize,
+    ) -> Self {
+        Self {
+            confirmed_verify_operations_count,
+            ..self
+        }
+    }
+
     /// Finishes the queue building process.
     pub fn build(self) -> TxQueue {
         TxQueue {
             max_pending_txs: self.max_pending_txs,
             sent_pending_txs: self.sent_pending_txs,
 
+            separate_verify_sender: self.separate_verify_sender,
+            separate_execute_sender: self.separate_execute_sender,
+            confirmed_commit_block: BlockNumber(self.confirmed_commit_operations_count as u32),
+            confirmed_verify_block: BlockNumber(self.confirmed_verify_operations_count as u32),
+
             commit_operations: OperationQueue::new(BlockNumber(
                 self.commit_operations_count as u32,
             )),
@@ -136,11 +186,21 @@ impl TxQueueBuilder {
 ///     for corresponding blocks is committed, the `verify` operation is yielded.
 ///   - Otherwise, if `commit` queue is not empty, a `commit` operation is yielded.
 /// 3. If all the queues are empty, no operation is returned.
+///
+/// Operations sent from the same account are executed in the order of their nonces,
+/// but there is no such guarantee for the different accounts. So if the `verify` (or `execute`)
+/// operations are sent from another account than the operations they depend on, they're yielded
+/// only after the corresponding `commit` (or `verify`) operations are confirmed rather than sent.
 #[derive(Debug)]
 pub struct TxQueue {
     max_pending_txs: usize,
     sent_pending_txs: usize,
 
+    separate_verify_sender: bool,
+    separate_execute_sender: bool,
+    confirmed_commit_block: BlockNumber,
+    confirmed_verify_block: BlockNumber,
+
     commit_operations: OperationQueue,
     verify_operations: OperationQueue,
     execute_operations: OperationQueue,
@@ -247,7 +307,11 @@ impl TxQueue {
     fn get_next_operation(&mut self) -> Option<TxData> {
         // 1. Highest priority: execute operations.
         if let Some(next_execute_block) = self.execute_operations.get_next_last_block_number() {
-            let current_verify_block = self.verify_operations.get_last_block_number();
+            let current_verify_block = if self.separate_execute_sender {
+                self.confirmed_verify_block
+            } else {
+                self.verify_operations.get_last_block_number()
+            };
             if *next_execute_block <= *current_verify_block {
                 return Some(self.execute_operations.pop_front().unwrap());
             }
@@ -255,7 +319,11 @@ impl TxQueue {
 
         // 2. After execute operations we should process verify operation.
         if let Some(next_verify_block) = self.verify_operations.get_next_last_block_number() {
-            let current_commit_block = self.commit_operations.get_last_block_number();
+            let current_commit_block = if self.separate_verify_sender {
+                self.confirmed_commit_block
+            } else {
+                self.commit_operations.get_last_block_number()
+            };
             if *next_verify_block <= *current_commit_block {
                 return Some(self.verify_operations.pop_front().unwrap());
             }
@@ -275,6 +343,24 @@ impl TxQueue {
 
         self.sent_pending_txs -= 1;
     }
+
+    /// Notifies the queue about the operation of the given type for the blocks up to `last_block`
+    /// being confirmed on the Ethereum blockchain.
+    pub fn report_confirmed_blocks(
+        &mut self,
+        op_type: AggregatedActionType,
+        last_block: BlockNumber,
+    ) {
+        match op_type {
+            AggregatedActionType::CommitBlocks => {
+                self.confirmed_commit_block = self.confirmed_commit_block.max(last_block);
+            }
+            AggregatedActionType::PublishProofBlocksOnchain => {
+                self.confirmed_verify_block = self.confirmed_verify_block.max(last_block);
+            }
+            AggregatedActionType::ExecuteBlocks | AggregatedActionType::CreateProofBlocks => {}
+        }
+    }
 }
 
 #[cfg(test)]
@@ -408,6 +494,59 @@ mod tests {
         assert_eq!(queue.sent_pending_txs, pending_count);
     }
 
+    /// Checks that the operations sent from another account than the operations they depend on
+    /// are yielded only after the latter are confirmed.
+    #[test]
+    fn separate_senders() {
+        const MAX_IN_FLY: usize = 3;
+        const COMMIT_MARK: u8 = 0;
+        const VERIFY_MARK: u8 = 1;
+        const EXECUTE_MARK: u8 = 2;
+
+        // `verify` operations are sent from another account, `execute` ones from the same as `verify`.
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY)
+            .with_separate_senders(true, false)
+            .build();
+
+        queue
+            .add_commit_operation(get_tx_data(
+                AggregatedActionType::CommitBlocks,
+                BlockNumber(1),
+                vec![COMMIT_MARK, 0],
+            ))
+            .unwrap();
+        queue
+            .add_verify_operation(get_tx_data(
+                AggregatedActionType::PublishProofBlocksOnchain,
+                BlockNumber(1),
+                vec![VERIFY_MARK, 0],
+            ))
+            .unwrap();
+        queue
+            .add_execute_operation(get_tx_data(
+                AggregatedActionType::ExecuteBlocks,
+                BlockNumber(1),
+                vec![EXECUTE_MARK, 0],
+            ))
+            .unwrap();
+
+        let op_1 = queue.pop_front().unwrap();
+        assert_eq!(op_1.raw, vec![COMMIT_MARK, 0]);
+
+        // The `commit` operation is sent, but not confirmed yet, so `verify` can't be sent.
+        assert_eq!(queue.pop_front(), None);
+
+        queue.report_commitment();
+        queue.report_confirmed_blocks(AggregatedActionType::CommitBlocks, BlockNumber(1));
+
+        // `execute` is sent from the same account as `verify`, so it only waits for `verify` to be sent.
+        let op_2 = queue.pop_front().unwrap();
+        assert_eq!(op_2.raw, vec![VERIFY_MARK, 0]);
+        let op_3 = queue.pop_front().unwrap();
+        assert_eq!(op_3.raw, vec![EXECUTE_MARK, 0]);
+        assert_eq!(queue.pop_front(), None);
+    }
+
     #[test]
     #[should_panic(expected = "No transactions are expected to be returned")]
     fn return_popped_empty() {
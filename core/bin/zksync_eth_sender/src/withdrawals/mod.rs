@@ -0,0 +1,550 @@
+//! Completion of the pending withdrawals of the executed blocks.
+//!
+//! Blocks are executed without completing the withdrawals, so the withdrawn funds are stored
+//! as the pending balances in the zkSync contract. `WithdrawalsAggregator` collects the pending
+//! withdrawals across the executed blocks and completes them in batches, with one
+//! `withdrawPendingBalances` call of the `PendingBalanceWithdrawer` contract per batch.
+
+// Built-in deps
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::time::Instant;
+// External uses
+use ethabi::{Contract, Token};
+use num::BigUint;
+use web3::{
+    contract::Options,
+    types::{Address, H256, U256},
+};
+// Workspace uses
+use zksync_config::configs::eth_sender::{Sender, Withdrawals};
+use zksync_contracts::pending_balance_withdrawer_contract;
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{
+    block::Block,
+    operator_costs::{split_fee, L1CostKind},
+    BlockNumber, ExecutedOperations, TokenId, ZkSyncOp,
+};
+// Local uses
+use crate::{
+    database::DatabaseInterface,
+    gas_adjuster::GasAdjuster,
+    transactions::{report_tx_fee, tx_fee},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Gas forwarded to the completion of the fungible token withdrawal.
+const FT_WITHDRAWAL_GAS: u64 = 200_000;
+/// Gas forwarded to the completion of the NFT withdrawal.
+const NFT_WITHDRAWAL_GAS: u64 = 300_000;
+/// Gas spent by the withdrawer contract on every withdrawal in addition to the forwarded gas.
+const WITHDRAWAL_OVERHEAD_GAS: u64 = 10_000;
+/// Gas spent by the transaction regardless of the amount of withdrawals.
+const BATCH_BASE_GAS: u64 = 50_000;
+
+/// Withdrawal waiting in the pending balances of the zkSync contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum PendingWithdrawal {
+    /// Pending balance of the fungible token, completed by `withdrawPendingBalance`.
+    Fungible { owner: Address, token: TokenId },
+    /// Pending NFT, completed by `withdrawPendingNFTBalance`.
+    Nft { token: TokenId },
+}
+
+impl PendingWithdrawal {
+    /// Returns the withdrawal made by the executed operation, if any.
+    pub fn from_operation(operation: &ExecutedOperations) -> Option<Self> {
+        if !operation.is_successful() {
+            return None;
+        }
+
+        match operation.get_executed_op()? {
+            ZkSyncOp::Withdraw(op) => Some(Self::Fungible {
+                owner: op.tx.to,
+                token: op.tx.token,
+            }),
+            ZkSyncOp::ForcedExit(op) if op.withdraw_amount.is_some() => Some(Self::Fungible {
+                owner: op.tx.target,
+                token: op.tx.token,
+            }),
+            ZkSyncOp::FullExit(op) if op.withdraw_amount.is_some() => {
+                let full_exit = &op.priority_op;
+                if full_exit.token.0 >= MIN_NFT_TOKEN_ID {
+                    Some(Self::Nft {
+                        token: full_exit.token,
+                    })
+                } else {
+                    Some(Self::Fungible {
+                        owner: full_exit.eth_address,
+                        token: full_exit.token,
+                    })
+                }
+            }
+            ZkSyncOp::WithdrawNFT(op) => Some(Self::Nft { token: op.tx.token }),
+            _ => None,
+        }
+    }
+
+    /// Returns the gas limit of the withdrawal completion.
+    fn gas_limit(&self) -> u64 {
+        let forwarded_gas = match self {
+            Self::Fungible { .. } => FT_WITHDRAWAL_GAS,
+            Self::Nft { .. } => NFT_WITHDRAWAL_GAS,
+        };
+        forwarded_gas + WITHDRAWAL_OVERHEAD_GAS
+    }
+}
+
+/// Returns the gas limit of the transaction completing the batch of withdrawals.
+pub(super) fn batch_gas_limit(batch: &[PendingWithdrawal]) -> u64 {
+    BATCH_BASE_GAS + batch.iter().map(PendingWithdrawal::gas_limit).sum::<u64>()
+}
+
+/// Pending withdrawals waiting to be completed, in the order they were made.
+/// Withdrawals of the same balance are only queued once, since the whole balance is withdrawn.
+#[derive(Debug, Default)]
+pub(super) struct WithdrawalsQueue {
+    queue: VecDeque<(PendingWithdrawal, Instant)>,
+    queued: HashSet<PendingWithdrawal>,
+}
+
+impl WithdrawalsQueue {
+    /// Adds the withdrawal made at the `made_at` moment, unless it's already queued.
+    pub fn push(&mut self, withdrawal: PendingWithdrawal, made_at: Instant) {
+        if self.queued.insert(withdrawal) {
+            self.queue.push_back((withdrawal, made_at));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Takes the next batch of withdrawals, either if it's full (by the amount of withdrawals
+    /// or by the gas budget), or if its oldest withdrawal waited for `max_delay`.
+    pub fn pop_batch(
+        &mut self,
+        config: &Withdrawals,
+        now: Instant,
+    ) -> Option<Vec<(PendingWithdrawal, Instant)>> {
+        let (_, oldest) = self.queue.front()?;
+
+        let max_batch_size = config.max_batch_size.max(1);
+        let mut gas_limit = BATCH_BASE_GAS;
+        let mut batch_size = 0;
+        for (withdrawal, _) in &self.queue {
+            // The batch always has at least one withdrawal, even if it exceeds the gas budget.
+            let exceeds_budget =
+                batch_size > 0 && gas_limit + withdrawal.gas_limit() > config.gas_budget;
+            if batch_size == max_batch_size || exceeds_budget {
+                break;
+            }
+            gas_limit += withdrawal.gas_limit();
+            batch_size += 1;
+        }
+
+        let is_full = batch_size < self.queue.len()
+            || batch_size == max_batch_size
+            || gas_limit >= config.gas_budget;
+        if !is_full && now.saturating_duration_since(*oldest) < config.max_delay() {
+            return None;
+        }
+
+        let batch: Vec<_> = self.queue.drain(..batch_size).collect();
+        for (withdrawal, _) in &batch {
+            self.queued.remove(withdrawal);
+        }
+        Some(batch)
+    }
+
+    /// Returns the batch that failed to be sent to the front of the queue.
+    /// Withdrawals queued again since the batch was taken are moved along with it.
+    pub fn return_batch(&mut self, batch: Vec<(PendingWithdrawal, Instant)>) {
+        for (withdrawal, made_at) in batch.into_iter().rev() {
+            if !self.queued.insert(withdrawal) {
+                self.queue.retain(|(queued, _)| *queued != withdrawal);
+            }
+            self.queue.push_front((withdrawal, made_at));
+        }
+    }
+}
+
+/// Sent transaction completing the batch of withdrawals.
+#[derive(Debug)]
+struct OngoingBatch {
+    size: usize,
+    /// Blocks of the withdrawals and their gas limits, the fee of the transaction
+    /// is attributed to the blocks in proportion to the gas limits.
+    blocks: Vec<(BlockNumber, u64)>,
+    encoded_tx_data: Vec<u8>,
+    nonce: U256,
+    gas_limit: U256,
+    last_deadline_block: u64,
+    last_used_gas_price: U256,
+    used_tx_hashes: Vec<H256>,
+}
+
+/// Outcome of the check of the ongoing batch.
+#[derive(Debug, PartialEq)]
+enum BatchStatus {
+    /// The `fee` is not set if the receipt of the transaction isn't available.
+    Completed {
+        tx_hash: H256,
+        fee: Option<U256>,
+    },
+    Failed {
+        tx_hash: H256,
+        fee: Option<U256>,
+    },
+    Pending,
+    Stuck,
+}
+
+/// `WithdrawalsAggregator` completes the pending withdrawals of the executed blocks
+/// from its own account, with at most one transaction in flight.
+///
+/// Unlike the operator transactions, the batches are not stored in the database:
+/// the withdrawals not completed before the restart stay in the pending balances,
+/// and can be completed by their owners.
+pub(super) struct WithdrawalsAggregator {
+    /// Ethereum intermediator of the account sending the batches.
+    ethereum: EthereumGateway,
+    /// ABI of the `PendingBalanceWithdrawer` contract.
+    contract: Contract,
+    queue: WithdrawalsQueue,
+    /// Blocks of the queued withdrawals.
+    withdrawal_blocks: HashMap<PendingWithdrawal, BlockNumber>,
+    ongoing_batch: Option<OngoingBatch>,
+    config: Withdrawals,
+}
+
+impl WithdrawalsAggregator {
+    pub fn new(ethereum: EthereumGateway, config: Withdrawals) -> Self {
+        Self {
+            ethereum,
+            contract: pending_balance_withdrawer_contract(),
+            queue: WithdrawalsQueue::default(),
+            withdrawal_blocks: HashMap::new(),
+            ongoing_batch: None,
+            config,
+        }
+    }
+
+    /// Queues the withdrawals of the blocks which execution was confirmed.
+    pub fn add_executed_blocks(&mut self, blocks: &[Block]) {
+        let now = Instant::now();
+        for block in blocks {
+            for withdrawal in block
+                .block_transactions
+                .iter()
+                .filter_map(PendingWithdrawal::from_operation)
+            {
+                // Withdrawals of the same balance are queued once, so the first block is kept.
+                self.withdrawal_blocks
+                    .entry(withdrawal)
+                    .or_insert(block.block_number);
+                self.queue.push(withdrawal, now);
+            }
+        }
+        metrics::gauge!("eth_sender.withdrawals.pending", self.queue.len() as f64);
+    }
+
+    /// Checks the state of the ongoing batch and sends the next one when it's ready.
+    pub async fn proceed<DB: DatabaseInterface>(
+        &mut self,
+        db: &DB,
+        gas_adjuster: &mut GasAdjuster<DB>,
+        options: &Sender,
+        current_block: u64,
+    ) -> anyhow::Result<()> {
+        if let Some(batch) = &self.ongoing_batch {
+            match self.check_batch(batch, options, current_block).await? {
+                BatchStatus::Completed { tx_hash, fee } => {
+                    vlog::info!("Completed the batch of {} withdrawals", batch.size);
+                    metrics::counter!("eth_sender.withdrawals.completed", batch.size as u64);
+                    save_batch_costs(db, batch, tx_hash, fee).await;
+                    self.ongoing_batch = None;
+                }
+                BatchStatus::Failed { tx_hash, fee } => {
+                    // Funds of the failed batch stay in the pending balances,
+                    // so the owners can withdraw them manually.
+                    vlog::error!(
+                        "Transaction {:#x} completing the batch of {} withdrawals failed",
+                        tx_hash,
+                        batch.size
+                    );
+                    save_batch_costs(db, batch, tx_hash, fee).await;
+                    self.ongoing_batch = None;
+                }
+                BatchStatus::Pending => return Ok(()),
+                BatchStatus::Stuck => {
+                    return self
+                        .resend_stuck_batch(gas_adjuster, options, current_block)
+                        .await;
+                }
+            }
+        }
+
+        if let Some(batch) = self.queue.pop_batch(&self.config, Instant::now()) {
+            let withdrawals: Vec<_> = batch.iter().map(|(withdrawal, _)| *withdrawal).collect();
+            match self
+                .send_batch(db, gas_adjuster, options, current_block, &withdrawals)
+                .await
+            {
+                Ok(batch) => {
+                    for withdrawal in &withdrawals {
+                        self.withdrawal_blocks.remove(withdrawal);
+                    }
+                    self.ongoing_batch = Some(batch);
+                }
+                Err(err) => {
+                    self.queue.return_batch(batch);
+                    return Err(err);
+                }
+            }
+        }
+        metrics::gauge!("eth_sender.withdrawals.pending", self.queue.len() as f64);
+
+        Ok(())
+    }
+
+    async fn check_batch(
+        &self,
+        batch: &OngoingBatch,
+        options: &Sender,
+        current_block: u64,
+    ) -> anyhow::Result<BatchStatus> {
+        // Any of the sent transactions may be included, since they have the same nonce.
+        for tx_hash in &batch.used_tx_hashes {
+            let status = self
+                .ethereum
+                .get_tx_status(*tx_hash, Some(current_block))
+                .await?;
+            match status {
+                Some(status) if status.confirmations < options.wait_confirmations => {
+                    return Ok(BatchStatus::Pending);
+                }
+                Some(status) => {
+                    let tx_hash = *tx_hash;
+                    let fee = status
+                        .receipt
+                        .as_ref()
+                        .and_then(|receipt| tx_fee(receipt, batch.last_used_gas_price));
+                    return Ok(if status.success {
+                        BatchStatus::Completed { tx_hash, fee }
+                    } else {
+                        BatchStatus::Failed { tx_hash, fee }
+                    });
+                }
+                None => {}
+            }
+        }
+
+        if current_block >= batch.last_deadline_block {
+            Ok(BatchStatus::Stuck)
+        } else {
+            Ok(BatchStatus::Pending)
+        }
+    }
+
+    async fn send_batch<DB: DatabaseInterface>(
+        &self,
+        db: &DB,
+        gas_adjuster: &mut GasAdjuster<DB>,
+        options: &Sender,
+        current_block: u64,
+        withdrawals: &[PendingWithdrawal],
+    ) -> anyhow::Result<OngoingBatch> {
+        let encoded_tx_data = self.encode_batch(db, withdrawals).await?;
+        let nonce = self.ethereum.pending_nonce().await?;
+        let fees = gas_adjuster
+            .get_fees(&self.ethereum, options.tx_type, None, 0)
+            .await?;
+
+        let mut batch = OngoingBatch {
+            size: withdrawals.len(),
+            blocks: withdrawals
+                .iter()
+                .filter_map(|withdrawal| {
+                    let block = self.withdrawal_blocks.get(withdrawal)?;
+                    Some((*block, withdrawal.gas_limit()))
+                })
+                .collect(),
+            encoded_tx_data,
+            nonce,
+            gas_limit: batch_gas_limit(withdrawals).into(),
+            last_deadline_block: 0,
+            last_used_gas_price: fees.gas_price(),
+            used_tx_hashes: Vec::new(),
+        };
+        let tx_options = Options::with(|opt| {
+            fees.apply(opt);
+            opt.nonce = Some(batch.nonce);
+            opt.gas = Some(batch.gas_limit);
+        });
+        self.send_tx(&mut batch, tx_options, options, current_block)
+            .await?;
+
+        vlog::info!(
+            "Sent the batch of {} withdrawals, tx hash: {:#x}, nonce: {}",
+            batch.size,
+            batch.used_tx_hashes[0],
+            batch.nonce
+        );
+        Ok(batch)
+    }
+
+    async fn resend_stuck_batch<DB: DatabaseInterface>(
+        &mut self,
+        gas_adjuster: &mut GasAdjuster<DB>,
+        options: &Sender,
+        current_block: u64,
+    ) -> anyhow::Result<()> {
+        let mut batch = self
+            .ongoing_batch
+            .take()
+            .expect("No ongoing batch to resend");
+
+        let fees = match gas_adjuster
+            .get_fees(
+                &self.ethereum,
+                options.tx_type,
+                Some(batch.last_used_gas_price),
+                batch.used_tx_hashes.len(),
+            )
+            .await
+        {
+            Ok(fees) => fees,
+            Err(err) => {
+                self.ongoing_batch = Some(batch);
+                return Err(err);
+            }
+        };
+        let tx_options = Options::with(|opt| {
+            fees.apply(opt);
+            opt.nonce = Some(batch.nonce);
+            opt.gas = Some(batch.gas_limit);
+        });
+        let old_gas_price = batch.last_used_gas_price;
+        batch.last_used_gas_price = fees.gas_price();
+
+        let result = self
+            .send_tx(&mut batch, tx_options, options, current_block)
+            .await;
+        if result.is_ok() {
+            vlog::info!(
+                "Replaced the stuck batch of withdrawals tx: nonce: {}, old gas price: {}, new gas price: {}",
+                batch.nonce,
+                old_gas_price,
+                batch.last_used_gas_price
+            );
+        }
+        self.ongoing_batch = Some(batch);
+        result
+    }
+
+    /// Signs and sends the transaction of the batch, adding its hash to the batch.
+    async fn send_tx(
+        &self,
+        batch: &mut OngoingBatch,
+        tx_options: Options,
+        options: &Sender,
+        current_block: u64,
+    ) -> anyhow::Result<()> {
+        let signed_tx = self
+            .ethereum
+            .sign_prepared_tx_for_addr(
+                batch.encoded_tx_data.clone(),
+                self.config.withdrawer_contract_addr,
+                tx_options,
+            )
+            .await?;
+        // The hash is stored before sending, since the transaction may be
+        // broadcasted even if the node responded with an error.
+        batch.used_tx_hashes.push(signed_tx.hash);
+        batch.last_deadline_block = current_block + options.expected_wait_time_block;
+        self.ethereum.send_raw_tx(signed_tx.raw_tx).await?;
+        Ok(())
+    }
+
+    /// Encodes the `withdrawPendingBalances` call completing the withdrawals.
+    async fn encode_batch<DB: DatabaseInterface>(
+        &self,
+        db: &DB,
+        withdrawals: &[PendingWithdrawal],
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut connection = db.acquire_connection().await?;
+        let mut ft_requests = Vec::new();
+        let mut nft_requests = Vec::new();
+        for withdrawal in withdrawals {
+            match *withdrawal {
+                PendingWithdrawal::Fungible { owner, token } => {
+                    let token_address = db.load_token_address(&mut connection, token).await?;
+                    ft_requests.push(Token::Tuple(vec![
+                        Token::Address(owner),
+                        Token::Address(token_address),
+                        Token::Uint(FT_WITHDRAWAL_GAS.into()),
+                    ]));
+                }
+                PendingWithdrawal::Nft { token } => {
+                    nft_requests.push(Token::Tuple(vec![
+                        Token::Uint(token.0.into()),
+                        Token::Uint(NFT_WITHDRAWAL_GAS.into()),
+                    ]));
+                }
+            }
+        }
+
+        let data = self
+            .contract
+            .function("withdrawPendingBalances")
+            .expect("withdrawPendingBalances function is not found in the contract ABI")
+            .encode_input(&[Token::Array(ft_requests), Token::Array(nft_requests)])?;
+        Ok(data)
+    }
+}
+
+/// Attributes the fee of the transaction completing the batch to the blocks of its withdrawals.
+/// The failure to store the costs is only logged, so it doesn't hold the withdrawals.
+async fn save_batch_costs<DB: DatabaseInterface>(
+    db: &DB,
+    batch: &OngoingBatch,
+    tx_hash: H256,
+    fee: Option<U256>,
+) {
+    let fee = match fee {
+        Some(fee) => fee,
+        None => return,
+    };
+    report_tx_fee(L1CostKind::Withdrawals, fee);
+
+    let weights: Vec<_> = batch
+        .blocks
+        .iter()
+        .map(|(_, gas_limit)| *gas_limit)
+        .collect();
+    let shares = split_fee(&BigUint::from_str(&fee.to_string()).unwrap(), &weights);
+    let mut costs: BTreeMap<BlockNumber, BigUint> = BTreeMap::new();
+    for ((block, _), share) in batch.blocks.iter().zip(shares) {
+        *costs.entry(*block).or_default() += share;
+    }
+    let costs: Vec<_> = costs.into_iter().collect();
+
+    let result = match db.acquire_connection().await {
+        Ok(mut connection) => {
+            db.save_withdrawals_costs(&mut connection, &tx_hash, &costs)
+                .await
+        }
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        vlog::warn!(
+            "Failed to store the costs of the withdrawals tx {:#x}: {}",
+            tx_hash,
+            err
+        );
+    }
+}
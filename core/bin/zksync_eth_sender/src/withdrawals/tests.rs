@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use zksync_config::configs::eth_sender::Withdrawals;
+use zksync_types::{Address, TokenId};
+
+use super::{batch_gas_limit, PendingWithdrawal, WithdrawalsQueue};
+
+fn config(max_batch_size: usize, gas_budget: u64) -> Withdrawals {
+    Withdrawals {
+        is_enabled: true,
+        withdrawer_contract_addr: Address::zero(),
+        sender_eth_addr: Address::zero(),
+        sender_private_key: None,
+        sender_kms_key_id: None,
+        sender_keystore_path: None,
+        max_batch_size,
+        max_delay: 60,
+        gas_budget,
+    }
+}
+
+fn fungible(owner: u64) -> PendingWithdrawal {
+    PendingWithdrawal::Fungible {
+        owner: Address::from_low_u64_be(owner),
+        token: TokenId(0),
+    }
+}
+
+fn withdrawals(batch: Vec<(PendingWithdrawal, Instant)>) -> Vec<PendingWithdrawal> {
+    batch
+        .into_iter()
+        .map(|(withdrawal, _)| withdrawal)
+        .collect()
+}
+
+/// Checks that the batch is sent once it's full or once its oldest withdrawal waited for `max_delay`.
+#[test]
+fn batch_is_sent_when_full_or_delayed() {
+    let config = config(2, 10_000_000);
+    let start = Instant::now();
+    let mut queue = WithdrawalsQueue::default();
+
+    assert!(queue.pop_batch(&config, start).is_none());
+
+    queue.push(fungible(1), start);
+    assert!(queue.pop_batch(&config, start).is_none());
+
+    queue.push(fungible(2), start);
+    queue.push(fungible(3), start);
+    let batch = queue.pop_batch(&config, start).expect("batch is full");
+    assert_eq!(withdrawals(batch), vec![fungible(1), fungible(2)]);
+
+    // The last withdrawal is only sent after the delay.
+    assert!(queue.pop_batch(&config, start).is_none());
+    let batch = queue
+        .pop_batch(&config, start + config.max_delay())
+        .expect("withdrawal waited for max_delay");
+    assert_eq!(withdrawals(batch), vec![fungible(3)]);
+    assert_eq!(queue.len(), 0);
+}
+
+/// Checks that the batch doesn't exceed the gas budget, unless it consists of a single withdrawal.
+#[test]
+fn batch_is_limited_by_gas_budget() {
+    let nft = PendingWithdrawal::Nft {
+        token: TokenId(70_000),
+    };
+    let gas_budget = batch_gas_limit(&[fungible(1), fungible(2)]);
+    let config = config(10, gas_budget);
+    let start = Instant::now();
+    let mut queue = WithdrawalsQueue::default();
+
+    queue.push(fungible(1), start);
+    queue.push(fungible(2), start);
+    queue.push(nft, start);
+    let batch = queue
+        .pop_batch(&config, start)
+        .expect("gas budget is filled");
+    assert_eq!(withdrawals(batch), vec![fungible(1), fungible(2)]);
+
+    let config = Withdrawals {
+        gas_budget: 0,
+        ..config
+    };
+    let batch = queue
+        .pop_batch(&config, start)
+        .expect("gas budget is filled");
+    assert_eq!(withdrawals(batch), vec![nft]);
+}
+
+/// Checks that the withdrawals of the same balance are only queued once,
+/// and that the returned batch is sent first.
+#[test]
+fn queue_deduplicates_and_returns_batches() {
+    let config = config(2, 10_000_000);
+    let start = Instant::now();
+    let later = start + Duration::from_secs(1);
+    let mut queue = WithdrawalsQueue::default();
+
+    queue.push(fungible(1), start);
+    queue.push(fungible(1), later);
+    queue.push(fungible(2), later);
+    assert_eq!(queue.len(), 2);
+
+    let batch = queue.pop_batch(&config, later).expect("batch is full");
+    queue.push(fungible(3), later);
+    queue.push(fungible(1), later);
+    queue.return_batch(batch);
+    assert_eq!(queue.len(), 3);
+
+    let batch = queue.pop_batch(&config, later).expect("batch is full");
+    assert_eq!(batch, vec![(fungible(1), start), (fungible(2), later)]);
+    let batch = queue
+        .pop_batch(&config, later + config.max_delay())
+        .expect("withdrawal waited for max_delay");
+    assert_eq!(withdrawals(batch), vec![fungible(3)]);
+}
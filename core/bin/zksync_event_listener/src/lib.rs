@@ -18,6 +18,7 @@ use subscriber::Subscriber;
 pub mod listener;
 pub mod messages;
 pub mod monitor;
+pub mod publisher;
 pub mod subscriber;
 
 #[derive(Debug)]
@@ -1,5 +1,6 @@
 use zksync_config::ZkSyncConfig;
 use zksync_event_listener::run_event_server;
+use zksync_prometheus_exporter::run_prometheus_exporter;
 
 fn main() {
     let _vlog_guard = vlog::init();
@@ -7,5 +8,10 @@ fn main() {
 
     let sys = actix_web::rt::System::new();
 
-    sys.block_on(run_event_server(config));
+    sys.block_on(async move {
+        if let Some(port) = config.event_listener.prometheus_port {
+            run_prometheus_exporter("event_listener", port);
+        }
+        run_event_server(config).await
+    });
 }
@@ -0,0 +1,335 @@
+//! Publisher streaming the network events to the message broker (Kafka or NATS JetStream),
+//! so the downstream services don't have to poll the API or the database.
+//!
+//! The publisher reads the events stored by the core in order and publishes every event
+//! to the `{topic_prefix}.{event_type}` topic, waiting for the broker to acknowledge it.
+//! Id of the last published event is stored in the database, so the publisher continues
+//! from it after the restart. Delivery is at-least-once: the events published before
+//! the failure to store the progress are published again, so the consumers are expected
+//! to deduplicate them by the `eventId` field. Only one publisher per broker should be run at once.
+
+// Built-in uses
+use std::{convert::TryFrom, time::Instant};
+
+// External uses
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_config::configs::event_publisher::EventPublisherConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::event::{
+    block::BlockStatus,
+    transaction::{TransactionStatus, TransactionType},
+    EventData, EventId, ZkSyncEvent,
+};
+
+// Local uses
+use self::sink::{EventSink, Message};
+
+pub mod sink;
+
+/// Types of the events published to the broker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishedEventType {
+    /// L2 transaction has been included into the committed block.
+    TxExecuted,
+    /// Priority operation (deposit or full exit) has been included into the committed block.
+    PriorityOpProcessed,
+    BlockCommitted,
+    BlockVerified,
+    BlockReverted,
+}
+
+impl PublishedEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TxExecuted => "tx_executed",
+            Self::PriorityOpProcessed => "priority_op_processed",
+            Self::BlockCommitted => "block_committed",
+            Self::BlockVerified => "block_verified",
+            Self::BlockReverted => "block_reverted",
+        }
+    }
+}
+
+/// Returns the type of the published event corresponding to the network event,
+/// `None` if the event is not published.
+fn published_event_type(event: &ZkSyncEvent) -> Option<PublishedEventType> {
+    match &event.data {
+        EventData::Block(block_event) => Some(match block_event.status {
+            BlockStatus::Committed => PublishedEventType::BlockCommitted,
+            BlockStatus::Finalized => PublishedEventType::BlockVerified,
+            BlockStatus::Reverted => PublishedEventType::BlockReverted,
+        }),
+        EventData::Transaction(tx_event) if tx_event.status == TransactionStatus::Committed => {
+            match tx_event.tx_type() {
+                TransactionType::Deposit | TransactionType::FullExit => {
+                    Some(PublishedEventType::PriorityOpProcessed)
+                }
+                _ => Some(PublishedEventType::TxExecuted),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Payload of the published message.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PublishedEvent<'a> {
+    event_id: u64,
+    event_type: PublishedEventType,
+    event: &'a ZkSyncEvent,
+}
+
+/// Creates the message for the event of the given type. Transaction events are keyed
+/// by the transaction hash and block events by the block number.
+fn event_message(
+    topic_prefix: &str,
+    event_type: PublishedEventType,
+    event: &ZkSyncEvent,
+) -> Message {
+    let key = match &event.data {
+        EventData::Transaction(tx_event) => tx_event.tx_hash.clone(),
+        _ => event.block_number.to_string(),
+    };
+    let payload = serde_json::to_vec(&PublishedEvent {
+        event_id: *event.id,
+        event_type,
+        event,
+    })
+    .expect("Event is serializable");
+
+    Message {
+        topic: format!("{}.{}", topic_prefix, event_type.as_str()),
+        key,
+        id: event.id.to_string(),
+        payload,
+    }
+}
+
+struct EventPublisher {
+    connection_pool: ConnectionPool,
+    config: EventPublisherConfig,
+    sink: Box<dyn EventSink>,
+}
+
+impl EventPublisher {
+    /// Publishes the batch of the events stored since the last published one.
+    /// Returns the number of the processed events.
+    async fn publish_new_events(&self) -> anyhow::Result<usize> {
+        let broker = self.config.broker.as_str();
+        let mut storage = self.connection_pool.access_storage().await?;
+        let last_event_id = match storage
+            .event_schema()
+            .get_last_published_event_id(broker)
+            .await?
+        {
+            Some(event_id) => event_id,
+            None => {
+                // Events emitted before the first start of the publisher are not published.
+                let last_event_id = storage
+                    .event_schema()
+                    .get_last_event_id()
+                    .await?
+                    .unwrap_or(EventId(0));
+                storage
+                    .event_schema()
+                    .update_last_published_event_id(broker, last_event_id)
+                    .await?;
+                return Ok(0);
+            }
+        };
+
+        let stored_events = storage
+            .event_schema()
+            .fetch_events_batch(last_event_id, self.config.batch_size)
+            .await?;
+        let events_count = stored_events.len();
+
+        let mut published_event_id = last_event_id;
+        let mut result = Ok(());
+        for stored_event in stored_events {
+            let event_id = EventId(stored_event.id as u64);
+            match ZkSyncEvent::try_from(stored_event) {
+                Ok(event) => {
+                    if let Some(event_type) = published_event_type(&event) {
+                        let message = event_message(&self.config.topic_prefix, event_type, &event);
+                        if let Err(err) = self.sink.publish(message).await {
+                            result = Err(err);
+                            break;
+                        }
+                        metrics::increment_counter!("event_publisher.published", "event_type" => event_type.as_str());
+                    }
+                }
+                Err(err) => {
+                    vlog::warn!("Failed to deserialize the event #{}: {}", event_id, err);
+                }
+            }
+            published_event_id = event_id;
+        }
+
+        // The progress is stored even if the batch is published partially,
+        // so the acknowledged events are not published again.
+        if published_event_id != last_event_id {
+            storage
+                .event_schema()
+                .update_last_published_event_id(broker, published_event_id)
+                .await?;
+            metrics::gauge!(
+                "event_publisher.last_published_event_id",
+                *published_event_id as f64
+            );
+        }
+        result.map(|_| events_count)
+    }
+
+    async fn run(self) {
+        let mut timer = tokio::time::interval(self.config.poll_interval());
+        loop {
+            timer.tick().await;
+
+            // Full batches are followed by the next one right away to catch up faster.
+            loop {
+                let start = Instant::now();
+                let result = self.publish_new_events().await;
+                metrics::histogram!("event_publisher.iteration", start.elapsed());
+                match result {
+                    Ok(events_count) if events_count == self.config.batch_size as usize => {}
+                    Ok(_) => break,
+                    Err(err) => {
+                        vlog::warn!("Failed to publish the events: {}", err);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs the publisher streaming the events to the broker. Connection pool has to access
+/// the main database, since the publisher stores the id of the last published event.
+#[must_use]
+pub fn run_event_publisher(
+    connection_pool: ConnectionPool,
+    config: EventPublisherConfig,
+) -> JoinHandle<()> {
+    vlog::info!(
+        "Starting the event publisher to {} at {}",
+        config.broker.as_str(),
+        config.broker_url
+    );
+    tokio::spawn(async move {
+        let sink = sink::connect(&config)
+            .await
+            .expect("Failed to connect to the event broker");
+        EventPublisher {
+            connection_pool,
+            config,
+            sink,
+        }
+        .run()
+        .await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::{json, Value};
+    use zksync_types::{event::transaction::TransactionEvent, BlockNumber};
+
+    fn tx_event(id: u64, tx_type: &str, status: TransactionStatus) -> ZkSyncEvent {
+        let tx_event: TransactionEvent = serde_json::from_value(json!({
+            "tx_hash": "0x01",
+            "account_id": 1,
+            "token_id": 0,
+            "block_number": 1,
+            "tx": { "type": tx_type },
+            "status": status,
+            "fail_reason": null,
+            "created_at": Utc::now(),
+        }))
+        .unwrap();
+        ZkSyncEvent {
+            id: EventId(id),
+            block_number: BlockNumber(1),
+            data: EventData::Transaction(tx_event),
+        }
+    }
+
+    fn block_event(id: u64, status: BlockStatus) -> ZkSyncEvent {
+        let block_event = serde_json::from_value(json!({
+            "status": status,
+            "block_details": {
+                "block_number": 1,
+                "new_state_root": "sync-bl:0000000000000000000000000000000000000000000000000000000000000000",
+                "block_size": 10,
+                "committed_at": Utc::now(),
+            },
+        }))
+        .unwrap();
+        ZkSyncEvent {
+            id: EventId(id),
+            block_number: BlockNumber(1),
+            data: EventData::Block(block_event),
+        }
+    }
+
+    #[test]
+    fn event_types() {
+        use PublishedEventType::*;
+
+        let cases = [
+            (
+                tx_event(1, "Transfer", TransactionStatus::Committed),
+                Some(TxExecuted),
+            ),
+            (
+                tx_event(1, "Deposit", TransactionStatus::Committed),
+                Some(PriorityOpProcessed),
+            ),
+            (
+                tx_event(1, "FullExit", TransactionStatus::Committed),
+                Some(PriorityOpProcessed),
+            ),
+            (tx_event(1, "Transfer", TransactionStatus::Queued), None),
+            (tx_event(1, "Deposit", TransactionStatus::Finalized), None),
+            (tx_event(1, "Transfer", TransactionStatus::Rejected), None),
+            (block_event(1, BlockStatus::Committed), Some(BlockCommitted)),
+            (block_event(1, BlockStatus::Finalized), Some(BlockVerified)),
+            (block_event(1, BlockStatus::Reverted), Some(BlockReverted)),
+        ];
+        for (event, expected) in cases.iter() {
+            assert_eq!(published_event_type(event), *expected, "{:?}", event);
+        }
+    }
+
+    #[test]
+    fn messages() {
+        let message = event_message(
+            "zksync",
+            PublishedEventType::PriorityOpProcessed,
+            &tx_event(5, "Deposit", TransactionStatus::Committed),
+        );
+        assert_eq!(message.topic, "zksync.priority_op_processed");
+        assert_eq!(message.key, "0x01");
+        assert_eq!(message.id, "5");
+        let payload: Value = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(payload["eventId"], 5);
+        assert_eq!(payload["eventType"], "priority_op_processed");
+        assert_eq!(payload["event"]["type"], "transaction");
+
+        let message = event_message(
+            "zksync",
+            PublishedEventType::BlockVerified,
+            &block_event(6, BlockStatus::Finalized),
+        );
+        assert_eq!(message.topic, "zksync.block_verified");
+        assert_eq!(message.key, "1");
+        assert_eq!(message.id, "6");
+    }
+}
@@ -0,0 +1,110 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use async_trait::async_trait;
+// Workspace uses
+use zksync_config::configs::event_publisher::{EventBroker, EventPublisherConfig};
+// Local uses
+
+/// Message with the serialized event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub topic: String,
+    /// Messages with the same key are kept in order by Kafka.
+    pub key: String,
+    /// Unique id of the message, used by NATS to deduplicate the messages published again.
+    pub id: String,
+    pub payload: Vec<u8>,
+}
+
+/// Message broker the events are published to.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Publishes the message and waits until the broker acknowledges it.
+    async fn publish(&self, message: Message) -> anyhow::Result<()>;
+}
+
+/// Connects to the broker set in the config.
+pub async fn connect(config: &EventPublisherConfig) -> anyhow::Result<Box<dyn EventSink>> {
+    match config.broker {
+        EventBroker::Nats => Ok(Box::new(
+            NatsSink::connect(&config.broker_url, config.publish_timeout()).await?,
+        )),
+        #[cfg(feature = "kafka")]
+        EventBroker::Kafka => Ok(Box::new(KafkaSink::new(
+            &config.broker_url,
+            config.publish_timeout(),
+        )?)),
+        #[cfg(not(feature = "kafka"))]
+        EventBroker::Kafka => anyhow::bail!("Publishing to Kafka requires the `kafka` feature"),
+    }
+}
+
+/// Publishes the events to NATS JetStream, which acknowledges the message once it's stored
+/// in the stream. Messages are deduplicated by the stream within its duplicate window
+/// using the `Nats-Msg-Id` header.
+pub struct NatsSink {
+    jetstream: async_nats::jetstream::Context,
+    timeout: Duration,
+}
+
+impl NatsSink {
+    pub async fn connect(url: &str, timeout: Duration) -> anyhow::Result<Self> {
+        let client = async_nats::connect(url).await?;
+        Ok(Self {
+            jetstream: async_nats::jetstream::new(client),
+            timeout,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&self, message: Message) -> anyhow::Result<()> {
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert("Nats-Msg-Id", message.id.as_str());
+        let ack =
+            self.jetstream
+                .publish_with_headers(message.topic, headers, message.payload.into());
+        tokio::time::timeout(self.timeout, ack)
+            .await?
+            .map_err(|err| anyhow::anyhow!("NATS didn't acknowledge the event: {}", err))?;
+        Ok(())
+    }
+}
+
+/// Publishes the events to Kafka with the idempotent producer, the message is acknowledged
+/// once it's written to all the in-sync replicas.
+#[cfg(feature = "kafka")]
+pub struct KafkaSink {
+    producer: rdkafka::producer::FutureProducer,
+    timeout: Duration,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaSink {
+    pub fn new(bootstrap_servers: &str, timeout: Duration) -> anyhow::Result<Self> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", bootstrap_servers)
+            .set("enable.idempotence", "true")
+            .set("acks", "all")
+            .set("message.timeout.ms", timeout.as_millis().to_string())
+            .create()?;
+        Ok(Self { producer, timeout })
+    }
+}
+
+#[cfg(feature = "kafka")]
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(&self, message: Message) -> anyhow::Result<()> {
+        let record = rdkafka::producer::FutureRecord::to(&message.topic)
+            .key(&message.key)
+            .payload(&message.payload);
+        self.producer
+            .send(record, self.timeout)
+            .await
+            .map_err(|(err, _)| anyhow::anyhow!("Kafka didn't acknowledge the event: {}", err))?;
+        Ok(())
+    }
+}
@@ -100,6 +100,8 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             tx: ZkSyncTx::ForcedExit(Box::new(tx)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         }
     }
 
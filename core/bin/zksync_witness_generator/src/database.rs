@@ -5,13 +5,14 @@
 
 // Built-in
 use std::clone::Clone;
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_storage::{prover::records::ProverJobQueueStats, ConnectionPool, StorageProcessor};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobScheduling, ProverJobType, StuckProverJob},
     AccountMap, AccountUpdates, BlockNumber,
 };
 // Local uses
@@ -72,10 +73,18 @@ impl DatabaseInterface for Database {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        scheduling: ProverJobScheduling,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .add_prover_job_to_job_queue(first_block, last_block, job_data, job_priority, job_type)
+            .add_prover_job_to_job_queue(
+                first_block,
+                last_block,
+                job_data,
+                job_priority,
+                job_type,
+                scheduling,
+            )
             .await?;
 
         Ok(())
@@ -109,10 +118,27 @@ impl DatabaseInterface for Database {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()> {
-        connection.prover_schema().mark_stale_jobs_as_idle().await?;
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<i32>> {
+        let jobs = connection
+            .prover_schema()
+            .mark_stale_jobs_as_idle(chrono::Duration::from_std(heartbeat_timeout)?)
+            .await?;
 
-        Ok(())
+        Ok(jobs)
+    }
+
+    async fn load_stuck_prover_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<StuckProverJob>> {
+        let jobs = connection
+            .prover_schema()
+            .load_stuck_prover_jobs(chrono::Duration::from_std(heartbeat_timeout)?)
+            .await?;
+
+        Ok(jobs)
     }
 
     async fn load_last_verified_block(
@@ -154,10 +180,11 @@ impl DatabaseInterface for Database {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        block_sizes: &[usize],
     ) -> anyhow::Result<Option<ProverJob>> {
         let proof = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .get_idle_prover_job_from_job_queue(block_sizes)
             .await?;
 
         Ok(proof)
@@ -285,6 +312,20 @@ impl DatabaseInterface for Database {
         Ok(())
     }
 
+    async fn remove_old_witnesses(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        last_verified_block: BlockNumber,
+        max_age: Duration,
+    ) -> anyhow::Result<u64> {
+        let removed = connection
+            .prover_schema()
+            .remove_old_witnesses(last_verified_block, chrono::Duration::from_std(max_age)?)
+            .await?;
+
+        Ok(removed)
+    }
+
     async fn pending_jobs_count(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -293,4 +334,13 @@ impl DatabaseInterface for Database {
 
         Ok(count)
     }
+
+    async fn load_prover_job_queue_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<ProverJobQueueStats>> {
+        let stats = connection.prover_schema().prover_job_queue_stats().await?;
+
+        Ok(stats)
+    }
 }
@@ -6,13 +6,14 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
-use zksync_storage::StorageProcessor;
+use zksync_storage::{prover::records::ProverJobQueueStats, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobScheduling, ProverJobType, StuckProverJob},
     AccountMap, AccountUpdates, BlockNumber,
 };
 
@@ -36,6 +37,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<serde_json::Value>>;
 
+    #[allow(clippy::too_many_arguments)]
     async fn add_prover_job_to_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -44,6 +46,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        scheduling: ProverJobScheduling,
     ) -> anyhow::Result<()>;
 
     async fn load_aggregated_op_that_affects_block(
@@ -59,10 +62,19 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<SingleProof>>;
 
+    /// Requeues the jobs whose provers haven't sent a heartbeat for longer than `heartbeat_timeout`,
+    /// returns the ids of the requeued jobs.
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()>;
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<i32>>;
+
+    async fn load_stuck_prover_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<StuckProverJob>>;
 
     async fn load_last_verified_block(
         &self,
@@ -80,9 +92,11 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<Option<(BlockNumber, Vec<u8>)>>;
 
+    /// Hands out the idle prover job, only considering the blocks of `block_sizes` if it's not empty.
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        block_sizes: &[usize],
     ) -> anyhow::Result<Option<ProverJob>>;
 
     async fn record_prover_is_working(
@@ -142,8 +156,23 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         witness: serde_json::Value,
     ) -> anyhow::Result<()>;
 
+    /// Removes the witnesses of the blocks up to `last_verified_block` stored earlier than `max_age` ago,
+    /// returns the amount of the removed witnesses.
+    async fn remove_old_witnesses(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        last_verified_block: BlockNumber,
+        max_age: Duration,
+    ) -> anyhow::Result<u64>;
+
     async fn pending_jobs_count(
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<u32>;
+
+    /// Returns the amount of the unfinished prover jobs grouped by the type, status and block size.
+    async fn load_prover_job_queue_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<ProverJobQueueStats>>;
 }
@@ -20,9 +20,12 @@ use zksync_config::ProverConfig;
 // Local deps
 use self::database_interface::DatabaseInterface;
 use self::scaler::ScalerOracle;
+use self::scheduler::{job_scheduling, report_job_queue_metrics};
 use tokio::task::JoinHandle;
 use zksync_circuit::serialization::ProverData;
 use zksync_config::configs::api::ProverApiConfig;
+use zksync_crypto::merkle_tree::persistent_cache::PersistentTreeCache;
+use zksync_health_check::Heartbeat;
 use zksync_prover_utils::api::{
     JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
     WorkingOn,
@@ -42,6 +45,7 @@ mod tests;
 pub mod database;
 mod database_interface;
 mod scaler;
+mod scheduler;
 mod witness_generator;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,10 +61,17 @@ struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
     database: DB,
     scaler_oracle: Arc<RwLock<ScalerOracle<DB>>>,
+    /// Timeout to consider the prover gone if it doesn't send the heartbeats.
+    gone_timeout: Duration,
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(
+        secret_auth: String,
+        database: DB,
+        idle_provers: u32,
+        gone_timeout: Duration,
+    ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
             idle_provers,
@@ -70,6 +81,7 @@ impl<DB: DatabaseInterface> AppState<DB> {
             secret_auth,
             database,
             scaler_oracle,
+            gone_timeout,
         }
     }
 
@@ -129,7 +141,7 @@ async fn get_job<DB: DatabaseInterface>(
     let mut storage = data.access_storage().await?;
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .load_idle_prover_job_from_job_queue(&mut storage, &r.aux_data.block_sizes)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
@@ -253,6 +265,24 @@ async fn stopped<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Lists the unfinished jobs that were taken from the silent provers,
+/// or whose provers haven't sent a heartbeat for longer than the gone timeout.
+async fn stuck_jobs<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let stuck_jobs = data
+        .database
+        .load_stuck_prover_jobs(&mut storage, data.gone_timeout)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load stuck prover jobs: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(stuck_jobs))
+}
+
 /// Input of the `/scaler/replicas` endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequiredReplicasInput {
@@ -287,6 +317,8 @@ async fn required_replicas<DB: DatabaseInterface>(
 async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     database: DB,
     prepare_data_interval: Duration,
+    gone_timeout: Duration,
+    heartbeat: Heartbeat,
 ) {
     // We use `prepare_data_interval` as timeout in this function to align creating prover jobs
     // with witness generator routine.
@@ -294,8 +326,9 @@ async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     let mut interval = tokio::time::interval(prepare_data_interval);
     loop {
         interval.tick().await;
+        heartbeat.beat();
 
-        update_prover_job_queue(database.clone())
+        update_prover_job_queue(database.clone(), gone_timeout)
             .await
             .unwrap_or_else(|e| {
                 vlog::warn!("Failed to update prover job queue: {}", e);
@@ -303,7 +336,48 @@ async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     }
 }
 
-async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow::Result<()> {
+/// Interval between the removals of the old witnesses.
+const WITNESS_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically removes the witnesses of the verified blocks stored earlier than `retention` ago.
+async fn prune_witnesses_loop<DB: DatabaseInterface>(database: DB, retention: Duration) {
+    let mut interval = tokio::time::interval(WITNESS_PRUNING_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        prune_witnesses(&database, retention)
+            .await
+            .unwrap_or_else(|e| {
+                vlog::warn!("Failed to remove old witnesses: {}", e);
+            });
+    }
+}
+
+async fn prune_witnesses<DB: DatabaseInterface>(
+    database: &DB,
+    retention: Duration,
+) -> anyhow::Result<()> {
+    let mut connection = database.acquire_connection().await?;
+    let last_verified_block = database.load_last_verified_block(&mut connection).await?;
+    let removed = database
+        .remove_old_witnesses(&mut connection, last_verified_block, retention)
+        .await?;
+    if removed > 0 {
+        vlog::info!(
+            "Removed {} witnesses of the blocks up to {}",
+            removed,
+            *last_verified_block
+        );
+    }
+    metrics::counter!("prover_server.removed_witnesses", removed);
+
+    Ok(())
+}
+
+async fn update_prover_job_queue<DB: DatabaseInterface>(
+    database: DB,
+    gone_timeout: Duration,
+) -> anyhow::Result<()> {
     let mut connection = database.acquire_connection().await?;
     {
         let next_single_block_to_add = database
@@ -320,6 +394,11 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
             let job_data =
                 serde_json::to_value(JobRequestData::BlockProof(prover_data, block_size))
                     .expect("Failed to serialize single proof job data");
+            let blocks = database
+                .load_block(&mut connection, next_single_block_to_add)
+                .await?
+                .into_iter()
+                .collect::<Vec<_>>();
             database
                 .add_prover_job_to_job_queue(
                     &mut connection,
@@ -328,6 +407,7 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                     job_data,
                     SINGLE_PROOF_JOB_PRIORITY,
                     ProverJobType::SingleProof,
+                    job_scheduling(&blocks, Some(block_size)),
                 )
                 .await?;
         }
@@ -358,6 +438,7 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                 .last()
                 .map(|b| b.block_number)
                 .expect("should have 1 block");
+            let scheduling = job_scheduling(&blocks, None);
             let mut data = Vec::new();
             for block in blocks {
                 let proof = database
@@ -377,19 +458,31 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                     job_data,
                     AGGREGATED_PROOF_JOB_PRIORITY,
                     ProverJobType::AggregatedProof,
+                    scheduling,
                 )
                 .await?;
         }
     }
-    database.mark_stale_jobs_as_idle(&mut connection).await?;
+    let stale_jobs = database
+        .mark_stale_jobs_as_idle(&mut connection, gone_timeout)
+        .await?;
+    if !stale_jobs.is_empty() {
+        vlog::warn!(
+            "Provers of the jobs {:?} stopped sending heartbeats, the jobs are returned to the queue",
+            stale_jobs
+        );
+    }
+    report_job_queue_metrics(&database, &mut connection, gone_timeout).await?;
 
     Ok(())
 }
 
+/// Runs the prover server, `heartbeat` is updated on every update of the prover job queue.
 pub fn run_prover_server<DB: DatabaseInterface>(
     database: DB,
     prover_api_opts: ProverApiConfig,
     prover_opts: ProverConfig,
+    heartbeat: Heartbeat,
 ) -> JoinHandle<()> {
     let witness_generator_opts = prover_opts.witness_generator;
     let core_opts = prover_opts.core;
@@ -405,7 +498,12 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                 tokio::spawn(update_prover_job_queue_loop(
                     database.clone(),
                     witness_generator_opts.prepare_data_interval(),
+                    core_opts.gone_timeout(),
+                    heartbeat,
                 ));
+                if let Some(retention) = witness_generator_opts.witness_retention() {
+                    tokio::spawn(prune_witnesses_loop(database.clone(), retention));
+                }
 
                 let last_verified_block = {
                     let mut storage = database
@@ -420,29 +518,36 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                         as usize
                 };
 
-                // Start pool maintainer threads.
-                for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
-                    vlog::info!(
-                        "Starting witness generator ({},{})",
-                        start_block,
-                        block_step
-                    );
-                    let pool_maintainer = witness_generator::WitnessGenerator::new(
-                        database.clone(),
-                        witness_generator_opts.prepare_data_interval(),
-                        BlockNumber(start_block),
-                        BlockNumber(block_step),
-                    );
-                    pool_maintainer.start(panic_sender.clone());
+                // Start the witness generator pool.
+                let mut witness_generator = witness_generator::WitnessGenerator::new(
+                    database.clone(),
+                    witness_generator_opts.prepare_data_interval(),
+                    BlockNumber(last_verified_block as u32 + 1),
+                    witness_generator_opts.witness_generators,
+                    witness_generator_opts.max_memory_mb,
+                );
+                if let Some(path) = &witness_generator_opts.tree_cache_path {
+                    match PersistentTreeCache::open(path) {
+                        Ok(cache) => {
+                            witness_generator = witness_generator.with_persistent_cache(cache)
+                        }
+                        Err(err) => {
+                            vlog::warn!("Failed to open the persistent tree cache: {}", err)
+                        }
+                    }
                 }
+                witness_generator.start(panic_sender.clone());
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let idle_provers = core_opts.idle_provers;
+                let gone_timeout = core_opts.gone_timeout();
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(
+                        secret_auth.clone(),
+                        database.clone(),
+                        idle_provers,
+                        gone_timeout,
+                    );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -465,6 +570,7 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                         .route("/working_on", web::post().to(working_on::<DB>))
                         .route("/publish", web::post().to(publish::<DB>))
                         .route("/stopped", web::post().to(stopped::<DB>))
+                        .route("/stuck_jobs", web::get().to(stuck_jobs::<DB>))
                         .route(
                             "/api/internal/prover/replicas",
                             web::post().to(required_replicas::<DB>),
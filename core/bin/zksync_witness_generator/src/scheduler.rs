@@ -0,0 +1,85 @@
+//! Scheduling of the prover jobs.
+//!
+//! Idle jobs are handed out by the priority of their type, and then by the deadline
+//! of the priority operations in the proven blocks, so the blocks with the operations
+//! closest to the expiration are proven first.
+//! Provers may declare the block sizes they support, which splits them into the
+//! per-block-size pools: a prover only gets the single proof jobs for the blocks of these sizes.
+
+// Built-in uses
+use std::time::Duration;
+// Workspace uses
+use zksync_storage::StorageProcessor;
+use zksync_types::{
+    block::Block,
+    prover::{ProverJobScheduling, ProverJobStatus},
+    ExecutedOperations,
+};
+// Local uses
+use crate::DatabaseInterface;
+
+/// Returns the scheduling parameters of the job proving `blocks`.
+pub fn job_scheduling(blocks: &[Block], block_size: Option<usize>) -> ProverJobScheduling {
+    let deadline_block = blocks
+        .iter()
+        .flat_map(|block| block.block_transactions.iter())
+        .filter_map(|op| match op {
+            ExecutedOperations::PriorityOp(op) => Some(op.priority_op.deadline_block),
+            ExecutedOperations::Tx(_) => None,
+        })
+        .min();
+
+    ProverJobScheduling {
+        block_size,
+        deadline_block,
+    }
+}
+
+/// Reports the amount of the unfinished jobs in the queue and their earliest deadlines,
+/// so the operators can tell whether the prover pools keep up with the deadlines,
+/// along with the amount of the jobs stuck because of the silent provers.
+pub async fn report_job_queue_metrics<DB: DatabaseInterface>(
+    database: &DB,
+    connection: &mut StorageProcessor<'_>,
+    gone_timeout: Duration,
+) -> anyhow::Result<()> {
+    let stats = database.load_prover_job_queue_stats(connection).await?;
+    for group in stats {
+        let status = match ProverJobStatus::from_number(group.job_status)? {
+            ProverJobStatus::Idle => "idle",
+            ProverJobStatus::InProgress => "in_progress",
+            ProverJobStatus::Done => "done",
+        };
+        let block_size = group
+            .block_size
+            .map(|size| size.to_string())
+            .unwrap_or_else(|| "any".to_string());
+
+        metrics::gauge!(
+            "prover_server.job_queue.jobs",
+            group.jobs_count as f64,
+            "type" => group.job_type.clone(),
+            "status" => status,
+            "block_size" => block_size.clone()
+        );
+        if let Some(deadline_block) = group.min_deadline_block {
+            metrics::gauge!(
+                "prover_server.job_queue.min_deadline_block",
+                deadline_block as f64,
+                "type" => group.job_type,
+                "status" => status,
+                "block_size" => block_size
+            );
+        }
+    }
+
+    let stuck_jobs = database
+        .load_stuck_prover_jobs(connection, gone_timeout)
+        .await?;
+    metrics::gauge!(
+        "prover_server.job_queue.stuck_jobs",
+        stuck_jobs.len() as f64
+    );
+
+    Ok(())
+}
@@ -11,12 +11,14 @@ use tokio::time::sleep;
 use zksync_crypto::params::account_tree_depth;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::chain::tree_cache::records::AccountTreeCache;
-use zksync_storage::prover::records::{StorageBlockWitness, StorageProverJobQueue, StoredProof};
+use zksync_storage::prover::records::{
+    ProverJobQueueStats, StorageBlockWitness, StorageProverJobQueue, StoredProof,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobStatus, ProverJobType},
+    prover::{ProverJob, ProverJobScheduling, ProverJobStatus, ProverJobType, StuckProverJob},
     AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
 };
 // Local uses
@@ -93,6 +95,7 @@ impl DatabaseInterface for MockDatabase {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        scheduling: ProverJobScheduling,
     ) -> anyhow::Result<()> {
         let mut prover_job_queue = self.prover_job_queue.write().await;
         let id = prover_job_queue.0;
@@ -109,6 +112,9 @@ impl DatabaseInterface for MockDatabase {
             id,
             job_priority,
             job_data,
+            block_size: scheduling.block_size.map(|size| size as i64),
+            deadline_block: scheduling.deadline_block.map(|block| block as i64),
+            reassignments: 0,
         };
 
         prover_job_queue.1.push(new_job);
@@ -148,6 +154,43 @@ impl DatabaseInterface for MockDatabase {
         Ok(count as u32)
     }
 
+    async fn load_prover_job_queue_stats(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<ProverJobQueueStats>> {
+        let mut stats: Vec<ProverJobQueueStats> = Vec::new();
+        let prover_job_queue = &self.prover_job_queue.read().await.1;
+        for job in prover_job_queue
+            .iter()
+            .filter(|job| job.job_status != ProverJobStatus::Done.to_number())
+        {
+            let group = stats.iter_mut().find(|stats| {
+                stats.job_type == job.job_type
+                    && stats.job_status == job.job_status
+                    && stats.block_size == job.block_size
+            });
+            match group {
+                Some(group) => {
+                    group.jobs_count += 1;
+                    group.min_deadline_block = match (group.min_deadline_block, job.deadline_block)
+                    {
+                        (Some(min), Some(deadline)) => Some(min.min(deadline)),
+                        (min, deadline) => min.or(deadline),
+                    };
+                }
+                None => stats.push(ProverJobQueueStats {
+                    job_type: job.job_type.clone(),
+                    job_status: job.job_status,
+                    block_size: job.block_size,
+                    jobs_count: 1,
+                    min_deadline_block: job.deadline_block,
+                }),
+            }
+        }
+
+        Ok(stats)
+    }
+
     async fn load_aggregated_op_that_affects_block(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -171,19 +214,60 @@ impl DatabaseInterface for MockDatabase {
         Ok(single_proof)
     }
 
-    async fn mark_stale_jobs_as_idle(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    async fn mark_stale_jobs_as_idle(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<i32>> {
         let now = Utc::now();
+        let heartbeat_timeout = chrono::Duration::from_std(heartbeat_timeout)?;
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
+        let mut stale_jobs = Vec::new();
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
+            if job.job_status == ProverJobStatus::InProgress.to_number()
+                && now - job.updated_at > heartbeat_timeout
+            {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = now;
                 job.updated_by = "server_clean_idle".to_string();
+                job.reassignments += 1;
+                stale_jobs.push(job.id);
             }
         }
 
-        Ok(())
+        Ok(stale_jobs)
+    }
+
+    async fn load_stuck_prover_jobs(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        heartbeat_timeout: Duration,
+    ) -> anyhow::Result<Vec<StuckProverJob>> {
+        let now = Utc::now();
+        let heartbeat_timeout = chrono::Duration::from_std(heartbeat_timeout)?;
+        let prover_job_queue = &self.prover_job_queue.read().await.1;
+
+        let stuck_jobs = prover_job_queue
+            .iter()
+            .filter(|job| job.job_status != ProverJobStatus::Done.to_number())
+            .filter(|job| {
+                let in_progress = job.job_status == ProverJobStatus::InProgress.to_number();
+                job.reassignments > 0 || (in_progress && now - job.updated_at > heartbeat_timeout)
+            })
+            .map(|job| StuckProverJob {
+                job_id: job.id,
+                job_type: job.job_type.clone(),
+                first_block: BlockNumber(job.first_block as u32),
+                last_block: BlockNumber(job.last_block as u32),
+                in_progress: job.job_status == ProverJobStatus::InProgress.to_number(),
+                updated_by: job.updated_by.clone(),
+                updated_at: job.updated_at,
+                reassignments: job.reassignments as u32,
+            })
+            .collect();
+
+        Ok(stuck_jobs)
     }
 
     async fn load_last_verified_block(
@@ -224,12 +308,25 @@ impl DatabaseInterface for MockDatabase {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         _: &mut StorageProcessor<'_>,
+        block_sizes: &[usize],
     ) -> anyhow::Result<Option<ProverJob>> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let idle_prover_job = prover_job_queue
             .iter_mut()
             .filter(|job| job.job_status == ProverJobStatus::Idle.to_number())
-            .max_by_key(|job| (job.job_priority, job.id));
+            .filter(|job| match job.block_size {
+                Some(block_size) => {
+                    block_sizes.is_empty() || block_sizes.contains(&(block_size as usize))
+                }
+                None => true,
+            })
+            .min_by_key(|job| {
+                (
+                    job.job_priority,
+                    job.deadline_block.unwrap_or(i64::MAX),
+                    job.id,
+                )
+            });
 
         let prover_job = if let Some(job) = idle_prover_job {
             job.job_status = ProverJobStatus::InProgress.to_number();
@@ -368,7 +465,9 @@ impl DatabaseInterface for MockDatabase {
         let witness = block_witness
             .iter()
             .find(|witness| witness.block == *block_number as i64)
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness"));
+            .cloned()
+            .map(StorageBlockWitness::into_witness)
+            .transpose()?;
 
         Ok(witness)
     }
@@ -379,19 +478,29 @@ impl DatabaseInterface for MockDatabase {
         block: BlockNumber,
         witness: serde_json::Value,
     ) -> anyhow::Result<()> {
-        let witness_str = serde_json::to_string(&witness).expect("Failed to serialize witness");
         let mut block_witness = self.block_witness.write().await;
         let is_block_not_saved_yet = !block_witness
             .iter()
             .any(|witness| witness.block == *block as i64);
 
         if is_block_not_saved_yet {
-            block_witness.push(StorageBlockWitness {
-                block: *block as i64,
-                witness: witness_str,
-            });
+            block_witness.push(StorageBlockWitness::new(*block as i64, &witness)?);
         }
 
         Ok(())
     }
+
+    async fn remove_old_witnesses(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        last_verified_block: BlockNumber,
+        _: Duration,
+    ) -> anyhow::Result<u64> {
+        // Witnesses aren't timestamped in the mock, so all the witnesses of the verified blocks are old.
+        let mut block_witness = self.block_witness.write().await;
+        let witnesses_count = block_witness.len();
+        block_witness.retain(|witness| witness.block > *last_verified_block as i64);
+
+        Ok((witnesses_count - block_witness.len()) as u64)
+    }
 }
@@ -12,6 +12,7 @@ use zksync_config::{
     ProverConfig,
 };
 use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
+use zksync_health_check::Heartbeat;
 use zksync_prover::{client, ApiClient};
 use zksync_prover_utils::api::ProverInputRequest;
 use zksync_types::{block::Block, AccountId, BlockNumber, TokenId, H256};
@@ -39,14 +40,21 @@ impl Default for MockProverOptions {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                block_sizes: Vec::new(),
+                backend: Default::default(),
+                gpu_devices: Vec::new(),
+                gpu_prover_command: String::new(),
             },
             core: Core {
-                gone_timeout: 60000,
+                gone_timeout: 10000,
                 idle_provers: 1,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 1,
+                max_memory_mb: 4096,
+                witness_retention_days: None,
+                tree_cache_path: None,
             },
         };
 
@@ -57,7 +65,12 @@ impl Default for MockProverOptions {
 async fn spawn_server(database: MockDatabase) {
     let prover_options = MockProverOptions::default();
 
-    run_prover_server(database, prover_options.0, prover_options.1);
+    run_prover_server(
+        database,
+        prover_options.0,
+        prover_options.1,
+        Heartbeat::default(),
+    );
 }
 
 #[tokio::test]
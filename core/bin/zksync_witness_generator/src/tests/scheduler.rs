@@ -0,0 +1,33 @@
+// Workspace deps
+use zksync_types::prover::ProverJobScheduling;
+// Local deps
+use super::prover_server::get_test_block;
+use crate::scheduler::job_scheduling;
+
+/// Checks that the job deadline is the earliest deadline of the priority operations in the blocks.
+#[tokio::test]
+async fn job_scheduling_uses_earliest_deadline() {
+    let block = get_test_block().await;
+    let mut later_block = block.clone();
+    for op in later_block.block_transactions.iter_mut() {
+        if let zksync_types::ExecutedOperations::PriorityOp(op) = op {
+            op.priority_op.deadline_block += 10;
+        }
+    }
+
+    assert_eq!(
+        job_scheduling(&[later_block.clone(), block], Some(10)),
+        ProverJobScheduling {
+            block_size: Some(10),
+            deadline_block: Some(2),
+        }
+    );
+    assert_eq!(
+        job_scheduling(&[later_block], None),
+        ProverJobScheduling {
+            block_size: None,
+            deadline_block: Some(12),
+        }
+    );
+    assert_eq!(job_scheduling(&[], None), ProverJobScheduling::default());
+}
@@ -1,8 +1,10 @@
 use std::time::Instant;
 // Built-in
+use std::sync::Arc;
 use std::{thread, time};
 // External
 use futures::channel::mpsc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::sleep;
 use zksync_crypto::merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
 // Workspace deps
@@ -10,16 +12,22 @@ use crate::database_interface::DatabaseInterface;
 use zksync_circuit::serialization::ProverData;
 use zksync_circuit::witness::utils::build_block_witness;
 use zksync_crypto::circuit::CircuitAccountTree;
+use zksync_crypto::merkle_tree::persistent_cache::PersistentTreeCache;
 use zksync_crypto::params::account_tree_depth;
 use zksync_types::block::Block;
-use zksync_types::BlockNumber;
+use zksync_types::{helpers::apply_updates, Account, AccountId, AccountMap, BlockNumber};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 /// The essential part of this structure is `maintain` function
 /// which runs forever and adds data to the database.
 ///
 /// This will generate and store in db witnesses for blocks with indexes
-/// start_block, start_block + block_step, start_block + 2*block_step, ...
+/// start_block, start_block + 1, start_block + 2, ...
+///
+/// The account tree is kept in memory and updated from block to block, while the witnesses
+/// themselves are built concurrently by the pool of `workers` threads, each on its own copy
+/// of the tree. The memory taken by these copies is bounded by `max_memory_mb`.
+#[derive(Clone)]
 pub struct WitnessGenerator<DB: DatabaseInterface> {
     /// Connection to the database.
     database: DB,
@@ -27,7 +35,14 @@ pub struct WitnessGenerator<DB: DatabaseInterface> {
     rounds_interval: time::Duration,
 
     start_block: BlockNumber,
-    block_step: BlockNumber,
+    /// Amount of the witnesses built concurrently.
+    workers: usize,
+    /// Memory limit for the account tree copies, in megabytes.
+    max_memory_mb: u32,
+    /// Permits for the memory taken by the account tree copies, one per megabyte.
+    memory_permits: Arc<Semaphore>,
+    /// Cache the account tree is restored from before loading it from the storage.
+    persistent_cache: Option<Arc<PersistentTreeCache>>,
 }
 
 #[derive(Debug)]
@@ -37,22 +52,40 @@ enum BlockInfo {
     NoWitness(Block),
 }
 
+/// State of the accounts after the block, kept in memory to be updated to the next blocks
+/// without restoring the account tree from the storage.
+struct AccountTreeState {
+    block: BlockNumber,
+    accounts: AccountMap,
+    tree: CircuitAccountTree,
+}
+
 impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     /// Creates a new `WitnessGenerator` object.
     pub fn new(
         database: DB,
         rounds_interval: time::Duration,
         start_block: BlockNumber,
-        block_step: BlockNumber,
+        workers: usize,
+        max_memory_mb: u32,
     ) -> Self {
+        let max_memory_mb = max_memory_mb.max(1);
         Self {
             database,
             rounds_interval,
             start_block,
-            block_step,
+            workers: workers.max(1),
+            max_memory_mb,
+            memory_permits: Arc::new(Semaphore::new(max_memory_mb as usize)),
+            persistent_cache: None,
         }
     }
 
+    pub fn with_persistent_cache(mut self, persistent_cache: PersistentTreeCache) -> Self {
+        self.persistent_cache = Some(Arc::new(persistent_cache));
+        self
+    }
+
     /// Starts the thread running `maintain` method.
     pub fn start(self, panic_notify: mpsc::Sender<bool>) {
         thread::Builder::new()
@@ -60,6 +93,7 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             .spawn(move || {
                 let _panic_sentinel = ThreadPanicNotify(panic_notify);
                 let runtime = tokio::runtime::Builder::new_multi_thread()
+                    .max_blocking_threads(self.workers)
                     .enable_all()
                     .build()
                     .expect("Unable to build runtime for a witness generator");
@@ -101,10 +135,14 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         Ok(block_info)
     }
 
+    /// Restores the account tree after the given block from the storage.
     async fn load_account_tree(
         &self,
         block: BlockNumber,
-    ) -> Result<CircuitAccountTree, anyhow::Error> {
+    ) -> Result<AccountTreeState, anyhow::Error> {
+        if let Some(state) = self.load_persistent_account_tree(block).await? {
+            return Ok(state);
+        }
         let fn_start = Instant::now();
 
         let mut storage = self.database.acquire_connection().await?;
@@ -115,13 +153,13 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "load_cache");
 
         let start = Instant::now();
+        let (_, accounts) = self
+            .database
+            .load_committed_state(&mut storage, Some(block))
+            .await?;
         if let Some((cached_block, account_tree_cache)) = cache {
-            let (_, accounts) = self
-                .database
-                .load_committed_state(&mut storage, Some(block))
-                .await?;
-            for (id, account) in accounts {
-                circuit_account_tree.insert(*id, account.into());
+            for (id, account) in &accounts {
+                circuit_account_tree.insert(**id, account.clone().into());
             }
             circuit_account_tree.set_internals(
                 SparseMerkleTreeSerializableCacheBN256::decode_bincode(&account_tree_cache),
@@ -137,10 +175,6 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
 
                 vlog::info!("Reconstructing the cache for the block {} using the cached tree for the block {}", block, cached_block);
 
-                let (_, accounts) = self
-                    .database
-                    .load_committed_state(&mut storage, Some(block))
-                    .await?;
                 if let Some((_, account_updates)) = self
                     .database
                     .load_state_diff(&mut storage, block, Some(cached_block))
@@ -174,12 +208,8 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             }
         } else {
             // There are no caches at all.
-            let (_, accounts) = self
-                .database
-                .load_committed_state(&mut storage, Some(block))
-                .await?;
-            for (id, account) in accounts {
-                circuit_account_tree.insert(*id, account.into());
+            for (id, account) in &accounts {
+                circuit_account_tree.insert(**id, account.clone().into());
             }
             circuit_account_tree.root_hash();
 
@@ -198,7 +228,116 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         }
 
         let start = Instant::now();
+        self.ensure_root_hash(block, &circuit_account_tree).await?;
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "ensure_root_hash");
+
+        metrics::histogram!("witness_generator", fn_start.elapsed(), "stage" => "load_account_tree");
+        let state = AccountTreeState {
+            block,
+            accounts,
+            tree: circuit_account_tree,
+        };
+        self.store_persistent_cache(&state, None);
+        Ok(state)
+    }
+
+    /// Restores the account tree after the given block from the persistent cache, so only the changes
+    /// made since the cached block are loaded from the storage.
+    /// Returns `None` if there is no cache for this or any previous block.
+    async fn load_persistent_account_tree(
+        &self,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<AccountTreeState>> {
+        let persistent_cache = match &self.persistent_cache {
+            Some(persistent_cache) => persistent_cache,
+            None => return Ok(None),
+        };
+        let start = Instant::now();
+        let cached = match persistent_cache.load::<Account>() {
+            Ok(Some(cached)) if cached.block > 0 && cached.block <= *block => cached,
+            Ok(_) => return Ok(None),
+            Err(err) => {
+                vlog::warn!("Failed to load the persistent tree cache: {}", err);
+                return Ok(None);
+            }
+        };
+
+        let mut state = AccountTreeState {
+            block: BlockNumber(cached.block),
+            accounts: AccountMap::default(),
+            tree: CircuitAccountTree::new(account_tree_depth()),
+        };
+        for (id, account) in cached.items {
+            state.tree.insert(id, account.clone().into());
+            state.accounts.insert(AccountId(id), account);
+        }
+        state.tree.set_internals(cached.internals);
+
+        // Cached block may be reverted since the cache was stored.
+        let mut storage = self.database.acquire_connection().await?;
+        let cached_root_hash = self
+            .database
+            .load_block(&mut storage, state.block)
+            .await?
+            .map(|block| block.new_root_hash);
+        if cached_root_hash != Some(state.tree.root_hash()) {
+            vlog::warn!(
+                "Persistent tree cache for the block {} doesn't match the storage, it's not used",
+                state.block
+            );
+            return Ok(None);
+        }
+
+        let updated_accounts = self.apply_state_diff(&mut state, block).await?;
+        self.ensure_root_hash(block, &state.tree).await?;
+        self.store_persistent_cache(&state, Some(updated_accounts));
+
+        metrics::increment_counter!("witness_generator.cache_access", "type" => "persistent");
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "load_persistent_cache");
+        Ok(Some(state))
+    }
+
+    /// Stores the account tree into the persistent cache. If `updated_accounts` are set,
+    /// only these accounts are written.
+    fn store_persistent_cache(
+        &self,
+        state: &AccountTreeState,
+        updated_accounts: Option<Vec<AccountId>>,
+    ) {
+        let persistent_cache = match &self.persistent_cache {
+            Some(persistent_cache) => persistent_cache,
+            None => return,
+        };
+        let start = Instant::now();
+        let internals = state.tree.get_internals();
+        let result = match updated_accounts {
+            Some(updated_accounts) => persistent_cache.store_updates(
+                *state.block,
+                updated_accounts
+                    .into_iter()
+                    .map(|id| (*id, state.accounts.get(&id))),
+                &internals,
+            ),
+            None => persistent_cache.store(
+                *state.block,
+                state.accounts.iter().map(|(id, account)| (**id, account)),
+                &internals,
+            ),
+        };
+        if let Err(err) = result {
+            vlog::warn!("Failed to store the persistent tree cache: {}", err);
+        }
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "store_persistent_cache");
+    }
+
+    /// Checks that the tree root hash matches the one of the block.
+    async fn ensure_root_hash(
+        &self,
+        block: BlockNumber,
+        circuit_account_tree: &CircuitAccountTree,
+    ) -> anyhow::Result<()> {
         if block != BlockNumber(0) {
+            let mut storage = self.database.acquire_connection().await?;
             let storage_block = self
                 .database
                 .load_block(&mut storage, block)
@@ -210,29 +349,141 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
                 "account tree root hash restored incorrectly"
             );
         }
-        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "ensure_root_hash");
+        Ok(())
+    }
 
-        metrics::histogram!("witness_generator", fn_start.elapsed(), "stage" => "load_account_tree");
-        Ok(circuit_account_tree)
+    /// Returns the account tree after the given block.
+    ///
+    /// The tree kept in memory is updated with the changes of the subsequent blocks if it's behind,
+    /// otherwise the tree is restored from the storage.
+    async fn account_tree_state(
+        &self,
+        cached: Option<AccountTreeState>,
+        block: BlockNumber,
+    ) -> anyhow::Result<AccountTreeState> {
+        let mut state = match cached {
+            Some(state) if state.block <= block => state,
+            _ => return self.load_account_tree(block).await,
+        };
+        if state.block == block {
+            metrics::increment_counter!("witness_generator.cache_access", "type" => "memory");
+            return Ok(state);
+        }
+
+        let start = Instant::now();
+        self.apply_state_diff(&mut state, block).await?;
+        self.ensure_root_hash(block, &state.tree).await?;
+        metrics::increment_counter!("witness_generator.cache_access", "type" => "memory");
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "update_tree_in_memory");
+
+        Ok(state)
     }
 
-    async fn prepare_witness_and_save_it(&self, block: Block) -> anyhow::Result<()> {
-        let fn_start = Instant::now();
+    /// Updates the account tree to the given block with the changes made since the block of the tree.
+    /// Returns the IDs of the updated accounts.
+    async fn apply_state_diff(
+        &self,
+        state: &mut AccountTreeState,
+        block: BlockNumber,
+    ) -> anyhow::Result<Vec<AccountId>> {
+        let mut updated_accounts = Vec::new();
         let mut storage = self.database.acquire_connection().await?;
+        if let Some((_, account_updates)) = self
+            .database
+            .load_state_diff(&mut storage, state.block, Some(block))
+            .await?
+        {
+            updated_accounts = account_updates
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            updated_accounts.sort_unstable();
+            updated_accounts.dedup();
+
+            apply_updates(&mut state.accounts, account_updates);
+            for idx in &updated_accounts {
+                state.tree.insert(
+                    **idx,
+                    state.accounts.get(idx).cloned().unwrap_or_default().into(),
+                );
+            }
+        }
+        state.block = block;
+        Ok(updated_accounts)
+    }
 
-        let start = Instant::now();
-        let mut circuit_account_tree = self.load_account_tree(block.block_number - 1).await?;
-        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "load_tree_full");
+    /// Waits until there is enough memory for another copy of the account tree.
+    async fn acquire_memory(&self, tree: &CircuitAccountTree) -> OwnedSemaphorePermit {
+        let tree_mb = tree.memory_stats().allocated_total / (1024 * 1024);
+        // A copy larger than the whole limit would never get enough permits,
+        // so such copies are built one at a time.
+        let permits = (tree_mb as u64).clamp(1, self.max_memory_mb as u64) as u32;
+        self.memory_permits
+            .clone()
+            .acquire_many_owned(permits)
+            .await
+            .expect("Memory semaphore is never closed")
+    }
+
+    /// Builds and stores the witness for the block, retrying until it succeeds.
+    ///
+    /// The first attempt uses the provided copy of the account tree, the retries restore
+    /// the tree from the storage.
+    async fn generate_witness(
+        self,
+        block: Block,
+        tree: CircuitAccountTree,
+        _memory_permit: OwnedSemaphorePermit,
+    ) {
+        let block_number = block.block_number;
+        let mut tree = Some(tree);
+        loop {
+            let result = match tree.take() {
+                Some(tree) => Ok(tree),
+                None => self
+                    .load_account_tree(block_number - 1)
+                    .await
+                    .map(|state| state.tree),
+            };
+            let result = match result {
+                Ok(tree) => self.prepare_witness_and_save_it(block.clone(), tree).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => return,
+                Err(err) => {
+                    vlog::warn!(
+                        "Witness generator failed to prepare witness for block: {}, err: {}",
+                        block_number,
+                        err
+                    );
+                    sleep(self.rounds_interval).await;
+                }
+            }
+        }
+    }
+
+    async fn prepare_witness_and_save_it(
+        &self,
+        block: Block,
+        mut circuit_account_tree: CircuitAccountTree,
+    ) -> anyhow::Result<()> {
+        let fn_start = Instant::now();
+        let block_number = block.block_number;
 
         let start = Instant::now();
-        let witness: ProverData = build_block_witness(&mut circuit_account_tree, &block)?.into();
+        let witness: ProverData = tokio::task::spawn_blocking(move || {
+            build_block_witness(&mut circuit_account_tree, &block).map(ProverData::from)
+        })
+        .await??;
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness");
 
         let start = Instant::now();
+        let mut storage = self.database.acquire_connection().await?;
         self.database
             .store_witness(
                 &mut storage,
-                block.block_number,
+                block_number,
                 serde_json::to_value(witness).expect("Witness serialize to json"),
             )
             .await?;
@@ -242,58 +493,75 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
 
         metrics::gauge!(
             "last_processed_block",
-            block.block_number.0 as f64,
+            block_number.0 as f64,
             "stage" => "witness_generator"
         );
         Ok(())
     }
 
     /// Returns next block for generating witness
-    fn next_witness_block(
-        current_block: BlockNumber,
-        block_step: BlockNumber,
-        block_info: &BlockInfo,
-    ) -> BlockNumber {
+    fn next_witness_block(current_block: BlockNumber, block_info: &BlockInfo) -> BlockNumber {
         match block_info {
             BlockInfo::NotReadyBlock => current_block, // Keep waiting
-            BlockInfo::WithWitness | BlockInfo::NoWitness(_) => {
-                BlockNumber(*current_block + *block_step)
-            } // Go to the next block
+            BlockInfo::WithWitness | BlockInfo::NoWitness(_) => current_block + 1, // Go to the next block
         }
     }
 
     /// Updates witness data in database in an infinite loop,
-    /// awaiting `rounds_interval` time between updates.
+    /// awaiting `rounds_interval` time while there are no new blocks.
     async fn maintain(self) {
         vlog::info!(
-            "preparing prover data routine started with start_block({}), block_step({})",
+            "preparing prover data routine started with start_block({}), workers({})",
             *self.start_block,
-            *self.block_step
+            self.workers
         );
 
         // Initialize counters for cache hits/misses.
         metrics::register_counter!("witness_generator.cache_access", "type" => "hit");
         metrics::register_counter!("witness_generator.cache_access", "type" => "off_by_1");
         metrics::register_counter!("witness_generator.cache_access", "type" => "miss");
+        metrics::register_counter!("witness_generator.cache_access", "type" => "memory");
+        metrics::register_counter!("witness_generator.cache_access", "type" => "persistent");
 
+        let mut tree_state = None;
         let mut current_block = self.start_block;
         loop {
-            sleep(self.rounds_interval).await;
-            let should_work = match self.should_work_on_block(current_block).await {
-                Ok(should_work) => should_work,
+            let block_info = match self.should_work_on_block(current_block).await {
+                Ok(block_info) => block_info,
                 Err(err) => {
                     vlog::warn!("witness for block {} check failed: {}", current_block, err);
+                    sleep(self.rounds_interval).await;
                     continue;
                 }
             };
 
-            let next_block = Self::next_witness_block(current_block, self.block_step, &should_work);
-            if let BlockInfo::NoWitness(block) = should_work {
-                let block_number = block.block_number;
-                if let Err(err) = self.prepare_witness_and_save_it(block).await {
-                    vlog::warn!("Witness generator ({},{}) failed to prepare witness for block: {}, err: {}",
-                        self.start_block, self.block_step, block_number, err);
-                    continue; // Retry the same block on the next iteration.
+            let next_block = Self::next_witness_block(current_block, &block_info);
+            match block_info {
+                BlockInfo::NotReadyBlock => {
+                    sleep(self.rounds_interval).await;
+                }
+                BlockInfo::WithWitness => {}
+                BlockInfo::NoWitness(block) => {
+                    let state = match self
+                        .account_tree_state(tree_state.take(), current_block - 1)
+                        .await
+                    {
+                        Ok(state) => state,
+                        Err(err) => {
+                            vlog::warn!(
+                                "Witness generator failed to restore the account tree for block: {}, err: {}",
+                                current_block,
+                                err
+                            );
+                            sleep(self.rounds_interval).await;
+                            continue; // Retry the same block on the next iteration.
+                        }
+                    };
+
+                    let memory_permit = self.acquire_memory(&state.tree).await;
+                    let tree = state.tree.clone();
+                    tree_state = Some(state);
+                    tokio::spawn(self.clone().generate_witness(block, tree, memory_permit));
                 }
             }
 
@@ -315,7 +583,6 @@ mod tests {
         assert_eq!(
             WitnessGenerator::<Database>::next_witness_block(
                 BlockNumber(3),
-                BlockNumber(4),
                 &BlockInfo::NotReadyBlock
             ),
             BlockNumber(3)
@@ -323,10 +590,9 @@ mod tests {
         assert_eq!(
             WitnessGenerator::<Database>::next_witness_block(
                 BlockNumber(3),
-                BlockNumber(4),
                 &BlockInfo::WithWitness
             ),
-            BlockNumber(7)
+            BlockNumber(4)
         );
         let empty_block = Block::new(
             BlockNumber(0),
@@ -343,10 +609,9 @@ mod tests {
         assert_eq!(
             WitnessGenerator::<Database>::next_witness_block(
                 BlockNumber(3),
-                BlockNumber(4),
                 &BlockInfo::NoWitness(empty_block)
             ),
-            BlockNumber(7)
+            BlockNumber(4)
         );
     }
 }
@@ -20,6 +20,19 @@ impl Client {
         .await
     }
 
+    pub async fn account_nfts(
+        &self,
+        account_id_or_address: &str,
+        state_type: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/{}/nfts", account_id_or_address, state_type),
+        )
+        .send()
+        .await
+    }
+
     pub async fn account_full_info(&self, account_id_or_address: &str) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -56,4 +69,44 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn account_pending_deposits(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/deposits/pending", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_nonces(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nonces", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_rejected_txs(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions/rejected", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+    pub async fn account_balance_history(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/balance_history", account_id_or_address),
+        )
+        .query(pagination_query)
+        .send()
+        .await
+    }
 }
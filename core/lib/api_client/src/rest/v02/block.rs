@@ -1,6 +1,7 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
+    block::IncomingStateDiffQuery,
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
@@ -45,4 +46,11 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn block_state_diff(&self, query: &IncomingStateDiffQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks/state_diff")
+            .query(query)
+            .send()
+            .await
+    }
 }
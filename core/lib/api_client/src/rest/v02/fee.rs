@@ -1,22 +1,22 @@
 // Local uses
 use crate::rest::client::{Client, Result};
 use zksync_api_types::v02::{
-    fee::{ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
+    fee::{AddressOrEnsName, ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
     Response,
 };
-use zksync_types::{Address, TokenLike};
+use zksync_types::TokenLike;
 
 impl Client {
     pub async fn get_txs_fee(
         &self,
         tx_type: ApiTxFeeTypes,
-        address: Address,
+        address: impl Into<AddressOrEnsName>,
         token_like: TokenLike,
     ) -> Result<Response> {
         self.post_with_scope(super::API_V02_SCOPE, "fee")
             .body(&TxFeeRequest {
                 tx_type,
-                address,
+                address: address.into(),
                 token_like,
             })
             .send()
@@ -0,0 +1,31 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{stats::StatsQuery, Response};
+
+impl Client {
+    pub async fn daily_tx_counts(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/transactions")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn daily_active_accounts(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/activeAccounts")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn daily_fees(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/fees")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn total_value_locked(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/totalValueLocked")
+            .send()
+            .await
+    }
+}
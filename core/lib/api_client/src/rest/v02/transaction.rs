@@ -1,6 +1,9 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        transaction::{IncomingTxBatch, TxReceiptsRequest},
+        Response,
+    },
     TxWithSignature,
 };
 use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
@@ -37,6 +40,13 @@ impl Client {
         .await
     }
 
+    pub async fn tx_statuses(&self, tx_hashes: Vec<TxHash>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/receipts")
+            .body(&TxReceiptsRequest { tx_hashes })
+            .send()
+            .await
+    }
+
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -46,6 +56,15 @@ impl Client {
         .await
     }
 
+    pub async fn tx_rejections(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/rejections", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
     pub async fn get_batch(&self, batch_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
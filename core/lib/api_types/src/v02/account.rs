@@ -1,15 +1,19 @@
 use std::collections::{BTreeMap, HashMap};
 
-use num::{BigUint, ToPrimitive};
+use chrono::{DateTime, Utc};
+use num::{BigUint, ToPrimitive, Zero};
 use serde::{Deserialize, Serialize};
 
 use zksync_types::{
-    AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, TokenId, ZkSyncPriorityOp,
+    tx::{Transfer, TxHash},
+    AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, SerialId, TokenId, ZkSyncOp,
+    ZkSyncPriorityOp, H256,
 };
-use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
+use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper, ZeroPrefixHexSerde};
 
 use super::pagination::PaginationDirection;
 use super::token::NFT;
+use super::transaction::TxInBlockStatus;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +21,20 @@ pub struct AccountState {
     pub depositing: DepositingAccountBalances,
     pub committed: Option<Account>,
     pub finalized: Option<Account>,
+    /// Resolution of the ENS name, if the account was requested by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ens_resolution: Option<EnsResolution>,
+}
+
+/// Address the ENS name (e.g. `name.eth`) is resolved to.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnsResolution {
+    pub name: String,
+    pub address: Address,
+    /// Time the name was resolved with the ENS contracts at. Resolutions are cached,
+    /// so it may be earlier than the time of the request.
+    pub resolved_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -33,6 +51,23 @@ pub struct Account {
     pub account_type: Option<EthAccountType>,
 }
 
+/// NFTs owned and minted by the account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNfts {
+    pub nfts: BTreeMap<TokenId, NFT>,
+    pub minted_nfts: BTreeMap<TokenId, NFT>,
+}
+
+impl From<Account> for AccountNfts {
+    fn from(account: Account) -> Self {
+        Self {
+            nfts: account.nfts,
+            minted_nfts: account.minted_nfts,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum AccountAddressOrId {
@@ -92,6 +127,58 @@ impl OngoingDeposit {
     }
 }
 
+/// Deposit seen on Ethereum but not executed in the rollup block yet.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDeposit {
+    pub serial_id: SerialId,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    pub from: Address,
+    pub to: Address,
+    /// ID of the account receiving the deposit, `None` if the account is created by the deposit.
+    pub account_id: Option<AccountId>,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Number of the Ethereum block confirmations left for the deposit to be accepted,
+    /// `None` if the latest Ethereum block is unknown.
+    pub confirmations_left: Option<u64>,
+}
+
+impl PendingDeposit {
+    /// Returns `None` if the priority operation isn't a deposit.
+    pub fn new(
+        priority_op: PriorityOp,
+        account_id: Option<AccountId>,
+        confirmations_for_eth_event: u64,
+        last_eth_block: Option<u64>,
+    ) -> Option<Self> {
+        let tx_hash = priority_op.tx_hash();
+        let deposit = match priority_op.data {
+            ZkSyncPriorityOp::Deposit(deposit) => deposit,
+            ZkSyncPriorityOp::FullExit(_) => return None,
+        };
+        let confirmations_left = last_eth_block.map(|last_eth_block| {
+            (priority_op.eth_block + confirmations_for_eth_event).saturating_sub(last_eth_block)
+        });
+        Some(Self {
+            serial_id: priority_op.serial_id,
+            tx_hash,
+            eth_hash: priority_op.eth_hash,
+            eth_block: priority_op.eth_block,
+            from: deposit.from,
+            to: deposit.to,
+            account_id,
+            token_id: deposit.token,
+            amount: deposit.amount,
+            confirmations_left,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
@@ -114,4 +201,180 @@ pub struct IncomingAccountTxsQuery {
     pub direction: PaginationDirection,
     pub token: Option<String>,
     pub second_account: Option<String>,
+    /// Comma-separated types of the returned transactions, e.g. `Transfer,Withdraw`.
+    pub tx_types: Option<String>,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// Executed operation changing the balances of the account, an entry of its balance history.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistoryItem {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub block_number: BlockNumber,
+    pub block_index: Option<u32>,
+    pub status: TxInBlockStatus,
+    pub created_at: DateTime<Utc>,
+    pub changes: Vec<BalanceChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BalanceChangeKind {
+    Deposit,
+    TransferIn,
+    TransferOut,
+    Withdrawal,
+    ForcedExit,
+    FullExit,
+    SwapIn,
+    SwapOut,
+    Fee,
+}
+
+/// Change of the balance of a single token, the amount is added to the balance for
+/// the `Deposit`, `TransferIn` and `SwapIn` kinds and is subtracted for the other ones.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub kind: BalanceChangeKind,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// The other side of the transfer, the L1 address for the deposits and the withdrawals.
+    pub counterparty: Option<Address>,
+}
+
+impl BalanceChange {
+    fn new(
+        kind: BalanceChangeKind,
+        token_id: TokenId,
+        amount: &BigUint,
+        counterparty: Option<Address>,
+    ) -> Self {
+        Self {
+            kind,
+            token_id,
+            amount: amount.clone(),
+            counterparty,
+        }
+    }
+
+    /// Returns the changes of the balances of the account made by the executed operation
+    /// in the order they're applied. Zero amounts are skipped, so the operations not changing
+    /// the balances (e.g. `ChangePubKey` without fee) have no changes.
+    ///
+    /// Minted NFT isn't reported for the recipient, since its token ID isn't stored in the operation.
+    pub fn from_executed_op(op: &ZkSyncOp, account_id: AccountId) -> Vec<Self> {
+        use BalanceChangeKind::*;
+
+        let mut changes = Vec::new();
+        match op {
+            ZkSyncOp::Deposit(op) if op.account_id == account_id => {
+                let deposit = &op.priority_op;
+                changes.push(Self::new(
+                    Deposit,
+                    deposit.token,
+                    &deposit.amount,
+                    Some(deposit.from),
+                ));
+            }
+            ZkSyncOp::FullExit(op) if op.priority_op.account_id == account_id => {
+                if let Some(amount) = &op.withdraw_amount {
+                    changes.push(Self::new(
+                        FullExit,
+                        op.priority_op.token,
+                        &amount.0,
+                        Some(op.priority_op.eth_address),
+                    ));
+                }
+            }
+            ZkSyncOp::Transfer(op) => {
+                Self::transfer(&mut changes, &op.tx, op.from, op.to, account_id)
+            }
+            ZkSyncOp::TransferToNew(op) => {
+                Self::transfer(&mut changes, &op.tx, op.from, op.to, account_id)
+            }
+            ZkSyncOp::Withdraw(op) if op.account_id == account_id => {
+                let tx = &op.tx;
+                changes.push(Self::new(Withdrawal, tx.token, &tx.amount, Some(tx.to)));
+                changes.push(Self::new(Fee, tx.token, &tx.fee, None));
+            }
+            ZkSyncOp::WithdrawNFT(op) if op.tx.account_id == account_id => {
+                let tx = &op.tx;
+                changes.push(Self::new(Withdrawal, tx.token, &1u32.into(), Some(tx.to)));
+                changes.push(Self::new(Fee, tx.fee_token, &tx.fee, None));
+            }
+            ZkSyncOp::ForcedExit(op) => {
+                let tx = &op.tx;
+                if op.target_account_id == account_id {
+                    if let Some(amount) = &op.withdraw_amount {
+                        changes.push(Self::new(ForcedExit, tx.token, &amount.0, Some(tx.target)));
+                    }
+                }
+                if tx.initiator_account_id == account_id {
+                    changes.push(Self::new(Fee, tx.token, &tx.fee, None));
+                }
+            }
+            ZkSyncOp::ChangePubKeyOffchain(op) if op.account_id == account_id => {
+                changes.push(Self::new(Fee, op.tx.fee_token, &op.tx.fee, None));
+            }
+            ZkSyncOp::MintNFTOp(op) if op.creator_account_id == account_id => {
+                changes.push(Self::new(Fee, op.tx.fee_token, &op.tx.fee, None));
+            }
+            ZkSyncOp::Swap(op) => {
+                let tx = &op.tx;
+                let (order_0, order_1) = &tx.orders;
+                let (amount_0, amount_1) = &tx.amounts;
+                let token_0 = order_0.token_sell;
+                let token_1 = order_1.token_sell;
+                if op.accounts.0 == account_id {
+                    changes.push(Self::new(SwapOut, token_0, amount_0, None));
+                }
+                if op.recipients.1 == account_id {
+                    changes.push(Self::new(SwapIn, token_0, amount_0, None));
+                }
+                if op.accounts.1 == account_id {
+                    changes.push(Self::new(SwapOut, token_1, amount_1, None));
+                }
+                if op.recipients.0 == account_id {
+                    changes.push(Self::new(SwapIn, token_1, amount_1, None));
+                }
+                if op.submitter == account_id {
+                    changes.push(Self::new(Fee, tx.fee_token, &tx.fee, None));
+                }
+            }
+            _ => {}
+        }
+        changes.retain(|change| !change.amount.is_zero());
+        changes
+    }
+
+    fn transfer(
+        changes: &mut Vec<Self>,
+        tx: &Transfer,
+        from: AccountId,
+        to: AccountId,
+        account_id: AccountId,
+    ) {
+        if from == account_id {
+            changes.push(Self::new(
+                BalanceChangeKind::TransferOut,
+                tx.token,
+                &tx.amount,
+                Some(tx.to),
+            ));
+            changes.push(Self::new(BalanceChangeKind::Fee, tx.token, &tx.fee, None));
+        }
+        if to == account_id {
+            changes.push(Self::new(
+                BalanceChangeKind::TransferIn,
+                tx.token,
+                &tx.amount,
+                Some(tx.from),
+            ));
+        }
+    }
 }
@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_crypto::{serialization::FrSerde, Fr};
-use zksync_types::{BlockNumber, H256};
+use zksync_types::{
+    AccountId, AccountUpdate, Address, BlockNumber, Nonce, PubKeyHash, TokenId, H256,
+};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use super::token::NFT;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
@@ -23,3 +29,110 @@ pub struct BlockInfo {
     pub finalized_at: Option<DateTime<Utc>>,
     pub status: BlockStatus,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingStateDiffQuery {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+    pub limit: u32,
+}
+
+/// Updates of the account states made in the committed block, in the order they were applied.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateDiff {
+    pub block_number: BlockNumber,
+    pub updates: Vec<ApiAccountUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAccountUpdate {
+    pub account_id: AccountId,
+    #[serde(flatten)]
+    pub update: AccountUpdateKind,
+}
+
+/// Atomic change of the account state, see `zksync_types::AccountUpdate`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum AccountUpdateKind {
+    Create {
+        address: Address,
+        nonce: Nonce,
+    },
+    Delete {
+        address: Address,
+        nonce: Nonce,
+    },
+    #[serde(rename_all = "camelCase")]
+    UpdateBalance {
+        token_id: TokenId,
+        #[serde(with = "BigUintSerdeAsRadix10Str")]
+        old_balance: BigUint,
+        #[serde(with = "BigUintSerdeAsRadix10Str")]
+        new_balance: BigUint,
+        old_nonce: Nonce,
+        new_nonce: Nonce,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChangePubKeyHash {
+        old_pub_key_hash: PubKeyHash,
+        new_pub_key_hash: PubKeyHash,
+        old_nonce: Nonce,
+        new_nonce: Nonce,
+    },
+    MintNFT {
+        token: NFT,
+        nonce: Nonce,
+    },
+    RemoveNFT {
+        token: NFT,
+        nonce: Nonce,
+    },
+}
+
+impl From<(AccountId, AccountUpdate)> for ApiAccountUpdate {
+    fn from((account_id, update): (AccountId, AccountUpdate)) -> Self {
+        let update = match update {
+            AccountUpdate::Create { address, nonce } => {
+                AccountUpdateKind::Create { address, nonce }
+            }
+            AccountUpdate::Delete { address, nonce } => {
+                AccountUpdateKind::Delete { address, nonce }
+            }
+            AccountUpdate::UpdateBalance {
+                old_nonce,
+                new_nonce,
+                balance_update: (token_id, old_balance, new_balance),
+            } => AccountUpdateKind::UpdateBalance {
+                token_id,
+                old_balance,
+                new_balance,
+                old_nonce,
+                new_nonce,
+            },
+            AccountUpdate::ChangePubKeyHash {
+                old_pub_key_hash,
+                new_pub_key_hash,
+                old_nonce,
+                new_nonce,
+            } => AccountUpdateKind::ChangePubKeyHash {
+                old_pub_key_hash,
+                new_pub_key_hash,
+                old_nonce,
+                new_nonce,
+            },
+            AccountUpdate::MintNFT { token, nonce } => AccountUpdateKind::MintNFT {
+                token: token.into(),
+                nonce,
+            },
+            AccountUpdate::RemoveNFT { token, nonce } => AccountUpdateKind::RemoveNFT {
+                token: token.into(),
+                nonce,
+            },
+        };
+        Self { account_id, update }
+    }
+}
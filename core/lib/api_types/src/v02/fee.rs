@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
+use super::account::EnsResolution;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiFee {
@@ -12,6 +14,9 @@ pub struct ApiFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Resolution of the ENS name, if the fee was requested for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ens_resolution: Option<EnsResolution>,
 }
 
 impl From<Fee> for ApiFee {
@@ -20,6 +25,7 @@ impl From<Fee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            ens_resolution: None,
         }
     }
 }
@@ -30,6 +36,7 @@ impl From<BatchFee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            ens_resolution: None,
         }
     }
 }
@@ -71,11 +78,26 @@ impl From<ApiTxFeeTypes> for TxFeeTypes {
     }
 }
 
+/// Address of the account or the ENS name (e.g. `name.eth`) to be resolved to it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AddressOrEnsName {
+    Address(Address),
+    EnsName(String),
+}
+
+impl From<Address> for AddressOrEnsName {
+    fn from(address: Address) -> Self {
+        Self::Address(address)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TxFeeRequest {
     pub tx_type: ApiTxFeeTypes,
-    pub address: Address,
+    /// Recipient of the transaction, it's also accepted as the ENS name.
+    pub address: AddressOrEnsName,
     pub token_like: TokenLike,
 }
 
@@ -8,9 +8,11 @@ pub mod account;
 pub mod block;
 pub mod fee;
 pub mod pagination;
+pub mod stats;
 pub mod status;
 pub mod token;
 pub mod transaction;
+pub mod webhook;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
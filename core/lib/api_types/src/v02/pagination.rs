@@ -1,8 +1,12 @@
+use chrono::{DateTime, Utc};
 use either::Either;
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
 use thiserror::Error;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenId};
+use zksync_types::{
+    event::transaction::TransactionType, tx::TxHash, AccountId, Address, BlockNumber, SerialId,
+    TokenId,
+};
 
 pub const MAX_LIMIT: u32 = 100;
 
@@ -132,10 +136,36 @@ pub struct PendingOpsRequest {
     pub serial_id: ApiEither<SerialId>,
 }
 
+/// Filters of the account transactions history applied on top of the pagination.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AccountTxsFilters {
+    /// Types of the returned transactions. Transactions of all types are returned if empty.
+    pub tx_types: Vec<TransactionType>,
+    /// Only the transactions created at this time or later are returned.
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only the transactions created before this time are returned.
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl AccountTxsFilters {
+    pub fn is_empty(&self) -> bool {
+        self.tx_types.is_empty() && self.from_date.is_none() && self.to_date.is_none()
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct AccountTxsRequest {
     pub address: Address,
     pub tx_hash: ApiEither<TxHash>,
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
+    pub filters: AccountTxsFilters,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountBalanceHistoryRequest {
+    pub address: Address,
+    /// `None` if the account doesn't exist yet, its history is empty then.
+    pub account_id: Option<AccountId>,
+    pub tx_hash: ApiEither<TxHash>,
 }
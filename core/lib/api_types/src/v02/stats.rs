@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zksync_types::{BlockNumber, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Maximum amount of the days the daily statistics can be requested for.
+pub const MAX_STATS_DAYS: u32 = 365;
+/// Amount of the days the daily statistics are returned for if not set in the query.
+pub const DEFAULT_STATS_DAYS: u32 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsQuery {
+    /// Amount of the latest days, including the current one.
+    pub days: Option<u32>,
+}
+
+/// Amounts of the successful transactions and priority operations executed on the day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTxCounts {
+    pub date: NaiveDate,
+    pub total: u64,
+    /// Amounts by the transaction type, e.g. `Transfer` or `Deposit`.
+    pub by_type: BTreeMap<String, u64>,
+}
+
+/// Amount of the accounts that initiated at least one transaction or priority operation on the day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyActiveAccounts {
+    pub date: NaiveDate,
+    pub active_accounts: u64,
+}
+
+/// Fees paid in the token on the day, the transactions paying no fee are not counted.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyFees {
+    pub date: NaiveDate,
+    pub token_id: TokenId,
+    pub tx_count: u64,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub average_fee: BigUint,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenValueLocked {
+    pub token_id: TokenId,
+    /// Sum of the balances of all the accounts in the token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalValueLocked {
+    /// Last block the statistics are aggregated for, `None` if nothing is aggregated yet.
+    pub last_aggregated_block: Option<BlockNumber>,
+    pub tokens: Vec<TokenValueLocked>,
+}
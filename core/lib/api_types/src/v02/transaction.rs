@@ -52,6 +52,8 @@ pub struct L1Receipt {
     pub eth_block: EthBlockId,
     pub rollup_block: Option<BlockNumber>,
     pub id: SerialId,
+    /// Ethereum transactions of the rollup block, `None` if the operation is not in a block yet.
+    pub l1_finality: Option<L1Finality>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -62,6 +64,29 @@ pub struct L2Receipt {
     pub rollup_block: Option<BlockNumber>,
     pub status: TxInBlockStatus,
     pub fail_reason: Option<String>,
+    /// Ethereum transactions of the rollup block, `None` if the transaction is not in a block yet.
+    pub l1_finality: Option<L1Finality>,
+}
+
+/// Confirmed Ethereum transaction of the operator.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct L1TxInfo {
+    pub tx_hash: H256,
+    /// Ethereum block the transaction is included in, `None` for the transactions confirmed
+    /// before the blocks were recorded.
+    pub eth_block: Option<EthBlockId>,
+    /// Number of the Ethereum blocks mined after the one with the transaction.
+    pub confirmations: Option<u64>,
+}
+
+/// Ethereum transactions committing and verifying the rollup block, `None` until confirmed.
+/// Once the verify transaction has enough confirmations, the block can't be reverted.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct L1Finality {
+    pub commit: Option<L1TxInfo>,
+    pub verify: Option<L1TxInfo>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -71,6 +96,17 @@ pub enum Receipt {
     L2(L2Receipt),
 }
 
+/// Maximum number of the transactions in the `TxReceiptsRequest`.
+pub const MAX_TX_RECEIPTS: usize = 100;
+
+/// Request of the receipts of the transactions, the hashes of the priority operations
+/// may be their Ethereum hashes as well.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxReceiptsRequest {
+    pub tx_hashes: Vec<TxHash>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
@@ -243,6 +279,21 @@ pub struct ApiTxBatch {
     pub batch_status: BatchStatus,
 }
 
+/// Reason of the transaction rejection by the API.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTxRejection {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub account: Address,
+    /// Machine-readable code of the error, e.g. `INCORRECT_ETH_SIGNATURE`.
+    pub error_code: String,
+    pub message: String,
+    /// Signer of the Ethereum signature, if it doesn't match the account.
+    pub recovered_signer: Option<Address>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchStatus {
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+use zksync_types::AccountId;
+
+/// Event of the transaction lifecycle the webhook can be subscribed to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    /// Transaction is included into the committed block.
+    TxCommitted,
+    /// Block with the transaction is verified and executed onchain.
+    TxVerified,
+    /// Deposit is included into the committed block.
+    DepositReceived,
+    /// Block with the withdrawal is verified and executed onchain, so the funds
+    /// can be withdrawn on L1.
+    WithdrawalFinalized,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TxCommitted => "tx_committed",
+            Self::TxVerified => "tx_verified",
+            Self::DepositReceived => "deposit_received",
+            Self::WithdrawalFinalized => "withdrawal_finalized",
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown webhook event type: {0}")]
+pub struct UnknownWebhookEventType(pub String);
+
+impl FromStr for WebhookEventType {
+    type Err = UnknownWebhookEventType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tx_committed" => Ok(Self::TxCommitted),
+            "tx_verified" => Ok(Self::TxVerified),
+            "deposit_received" => Ok(Self::DepositReceived),
+            "withdrawal_finalized" => Ok(Self::WithdrawalFinalized),
+            _ => Err(UnknownWebhookEventType(s.to_owned())),
+        }
+    }
+}
+
+/// Request to register the webhook.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterWebhook {
+    /// HTTPS URL the events are posted to.
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    /// Account the events are filtered by, the events of all the accounts are posted if not set.
+    pub account_id: Option<AccountId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiWebhook {
+    pub id: i64,
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub account_id: Option<AccountId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Registered webhook along with the secret its payloads are signed with.
+/// The secret is only returned once, upon the registration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredWebhook {
+    #[serde(flatten)]
+    pub webhook: ApiWebhook,
+    pub secret: String,
+}
+
+/// State of the event delivery to the webhook.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiWebhookDelivery {
+    pub id: i64,
+    pub event_id: u64,
+    pub event_type: WebhookEventType,
+    /// Number of the attempts to post the event.
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    /// Error of the last failed attempt.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -752,10 +752,13 @@ pub fn build_block_witness<'a>(
         block.timestamp,
     );
 
-    let ops = block
+    let mut ops: Vec<_> = block
         .block_transactions
         .iter()
-        .filter_map(|tx| tx.get_executed_op().cloned());
+        .filter_map(|tx| tx.get_executed_op().cloned())
+        .collect();
+    // Signatures are verified at once, so they're not verified one by one to encode the operations.
+    ZkSyncOp::verify_signatures_batch(&mut ops);
 
     let mut operations = vec![];
     let mut pub_data = vec![];
@@ -0,0 +1,294 @@
+//! Configuration file with the same options as the environment variables.
+//!
+//! The options are grouped in the per-component sections, the same way as in the `etc/env` configs:
+//! `caches_size = 10000` in the `[api.common]` section is the `API_COMMON_CACHES_SIZE` variable.
+//! The file is applied by setting the environment variables that are not set yet, so the environment
+//! overrides the file: the nodes of the deployment can share the file and only set their own options.
+
+// Built-in uses
+use std::{
+    collections::BTreeMap,
+    env, fmt,
+    path::{Path, PathBuf},
+};
+// External uses
+use serde_json::Value;
+
+/// Environment variable with the path to the configuration file.
+pub const CONFIG_FILE_ENV_VAR: &str = "ZKSYNC_CONFIG_FILE";
+
+/// Sections of the configuration file, the same as the prefixes of the environment variables.
+const SECTIONS: &[&str] = &[
+    "api",
+    "chain",
+    "contracts",
+    "database",
+    "dev_liquidity_token_watcher",
+    "eth_client",
+    "eth_sender",
+    "eth_watch",
+    "event_listener",
+    "fee_ticker",
+    "forced_exit_requests",
+    "gateway_watcher",
+    "misc",
+    "nft_factory",
+    "prover",
+    "token_handler",
+];
+
+/// Parts of the variable names with the secret values, which are not printed.
+const SECRET_NAME_PARTS: &[&str] = &[
+    "PRIVATE_KEY",
+    "PASSWORD",
+    "SECRET",
+    "API_KEY",
+    "DATABASE_URL",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigFileError {
+    #[error("Cannot read the config file {0:?}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Cannot parse the config file: {0}")]
+    Parse(String),
+    #[error("Config file {0:?} is neither TOML nor YAML")]
+    UnknownFormat(PathBuf),
+    #[error("Unknown config section [{0}], expected one of: {}", SECTIONS.join(", "))]
+    UnknownSection(String),
+    #[error("Invalid value of the option {0}: {1}")]
+    InvalidOption(String, String),
+    #[error("Config file is not set, it can't be reloaded")]
+    NotSet,
+    #[error("Runtime config is not initialized")]
+    RuntimeConfigNotInitialized,
+}
+
+/// Where the effective value of the option comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionSource {
+    File,
+    /// The environment variable overrides the value from the file.
+    Env,
+}
+
+/// Options of the configuration file as the environment variables.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigFile {
+    variables: BTreeMap<String, String>,
+}
+
+impl ConfigFile {
+    /// Loads the configuration file, its format is chosen by the extension: `.toml`, `.yaml` or `.yml`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigFileError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| ConfigFileError::Read(path.to_owned(), err))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("yaml") | Some("yml") => Self::from_yaml(&contents),
+            _ => Err(ConfigFileError::UnknownFormat(path.to_owned())),
+        }
+    }
+
+    /// Loads the configuration file set in the `ZKSYNC_CONFIG_FILE` variable, if it's set.
+    pub fn from_env() -> Result<Option<Self>, ConfigFileError> {
+        env::var_os(CONFIG_FILE_ENV_VAR).map(Self::load).transpose()
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigFileError> {
+        let config: Value =
+            toml::from_str(contents).map_err(|err| ConfigFileError::Parse(err.to_string()))?;
+        Self::from_value(config)
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, ConfigFileError> {
+        let config: Value = serde_yaml::from_str(contents)
+            .map_err(|err| ConfigFileError::Parse(err.to_string()))?;
+        Self::from_value(config)
+    }
+
+    fn from_value(config: Value) -> Result<Self, ConfigFileError> {
+        let sections = match config {
+            Value::Object(sections) => sections,
+            // The empty YAML file.
+            Value::Null => return Ok(Self::default()),
+            _ => {
+                return Err(ConfigFileError::Parse(
+                    "expected the sections of the options".to_owned(),
+                ))
+            }
+        };
+
+        let mut variables = BTreeMap::new();
+        for (name, value) in sections {
+            // Options outside of the sections, e.g. `RUST_LOG`, are set as they are.
+            if value.is_object() && !SECTIONS.contains(&name.as_str()) {
+                return Err(ConfigFileError::UnknownSection(name));
+            }
+            collect_variables(&name.to_uppercase(), value, &mut variables);
+        }
+        Ok(Self { variables })
+    }
+
+    /// Returns the options of the file as the environment variables.
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// Sets the environment variables from the file unless they're already set.
+    /// Returns the effective value of every option of the file and where it comes from.
+    pub fn apply(&self) -> EffectiveConfig {
+        let options = self
+            .variables
+            .iter()
+            .map(|(name, value)| match env::var(name) {
+                Ok(env_value) => (name.clone(), env_value, OptionSource::Env),
+                Err(_) => {
+                    env::set_var(name, value);
+                    (name.clone(), value.clone(), OptionSource::File)
+                }
+            })
+            .collect();
+        EffectiveConfig { options }
+    }
+}
+
+/// Flattens the option into the variables, the same way as `zk config compile` does:
+/// the nested sections are joined with `_` and the lists are joined with `,`.
+fn collect_variables(name: &str, value: Value, variables: &mut BTreeMap<String, String>) {
+    let value = match value {
+        Value::Object(options) => {
+            for (option, value) in options {
+                let name = format!("{}_{}", name, option.to_uppercase());
+                collect_variables(&name, value, variables);
+            }
+            return;
+        }
+        Value::Array(values) => values
+            .into_iter()
+            .map(scalar_to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Null => return,
+        value => scalar_to_string(value),
+    };
+    variables.insert(name.to_owned(), value);
+}
+
+fn scalar_to_string(value: Value) -> String {
+    match value {
+        Value::String(value) => value,
+        value => value.to_string(),
+    }
+}
+
+/// Effective values of the options of the configuration file, printed at the startup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveConfig {
+    pub options: Vec<(String, String, OptionSource)>,
+}
+
+impl fmt::Display for EffectiveConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value, source) in &self.options {
+            let value = if SECRET_NAME_PARTS.iter().any(|part| name.contains(part)) {
+                "<hidden>"
+            } else {
+                value.as_str()
+            };
+            let source = match source {
+                OptionSource::File => "",
+                OptionSource::Env => " (env)",
+            };
+            writeln!(f, "{}={}{}", name, value, source)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG_TOML: &str = r#"
+        RUST_LOG = "info"
+
+        [api.common]
+        caches_size = 10000
+        fee_free_accounts = [1, 2]
+
+        [eth_sender.sender]
+        operator_private_key = "0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+        is_enabled = true
+    "#;
+
+    const CONFIG_YAML: &str = "\
+RUST_LOG: info
+api:
+  common:
+    caches_size: 10000
+    fee_free_accounts: [1, 2]
+eth_sender:
+  sender:
+    operator_private_key: \"0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be\"
+    is_enabled: true
+";
+
+    #[test]
+    fn parse_config_file() {
+        let config = ConfigFile::from_toml(CONFIG_TOML).unwrap();
+        let variables: Vec<_> = config
+            .variables()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            variables,
+            vec![
+                ("API_COMMON_CACHES_SIZE", "10000"),
+                ("API_COMMON_FEE_FREE_ACCOUNTS", "1,2"),
+                ("ETH_SENDER_SENDER_IS_ENABLED", "true"),
+                (
+                    "ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY",
+                    "0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+                ),
+                ("RUST_LOG", "info"),
+            ]
+        );
+
+        let yaml_config = ConfigFile::from_yaml(CONFIG_YAML).unwrap();
+        assert_eq!(yaml_config, config);
+    }
+
+    #[test]
+    fn unknown_section() {
+        let err = ConfigFile::from_toml("[ticker]\nmax_price_age = 10").unwrap_err();
+        assert!(matches!(err, ConfigFileError::UnknownSection(section) if section == "ticker"));
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let config = ConfigFile::from_toml(
+            r#"
+            [misc]
+            config_file_test_from_file = "file"
+            config_file_test_overridden = "file"
+            config_file_test_secret = "file"
+            "#,
+        )
+        .unwrap();
+        env::set_var("MISC_CONFIG_FILE_TEST_OVERRIDDEN", "env");
+
+        let effective = config.apply();
+        assert_eq!(env::var("MISC_CONFIG_FILE_TEST_FROM_FILE").unwrap(), "file");
+        assert_eq!(env::var("MISC_CONFIG_FILE_TEST_OVERRIDDEN").unwrap(), "env");
+        assert_eq!(
+            effective.to_string(),
+            "MISC_CONFIG_FILE_TEST_FROM_FILE=file\n\
+             MISC_CONFIG_FILE_TEST_OVERRIDDEN=env (env)\n\
+             MISC_CONFIG_FILE_TEST_SECRET=<hidden>\n"
+        );
+    }
+}
@@ -2,11 +2,12 @@ use num::{rational::Ratio, BigUint};
 /// External uses
 use serde::Deserialize;
 /// Built-in uses
-use std::net::SocketAddr;
+use std::convert::TryFrom;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
 // Workspace uses
-use zksync_types::AccountId;
+use zksync_types::{event::transaction::TransactionType, AccountId, Address, TokenId, H256};
 // Local uses
 use crate::envy_load;
 
@@ -23,12 +24,18 @@ pub struct ApiConfig {
     pub json_rpc: JsonRpcConfig,
     /// Configuration options for the web3 JSON RPC server.
     pub web3: Web3Config,
+    /// Configuration options for the gRPC server.
+    pub grpc: GrpcConfig,
+    /// Configuration options for the webhook notifier.
+    pub webhooks: WebhooksConfig,
     /// Configuration options for the private core API.
     pub private: PrivateApiConfig,
     /// Configuration options for the prover server.
     pub prover: ProverApiConfig,
     /// Configuration options for the Prometheus exporter.
     pub prometheus: PrometheusConfig,
+    /// Configuration options for the health check server.
+    pub health_check: HealthCheckConfig,
     pub token_config: TokenConfig,
 }
 
@@ -40,9 +47,12 @@ impl ApiConfig {
             rest: envy_load!("rest", "API_REST_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
             web3: envy_load!("web3", "API_WEB3_"),
+            grpc: envy_load!("grpc", "API_GRPC_"),
+            webhooks: envy_load!("webhooks", "API_WEBHOOKS_"),
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
             prometheus: envy_load!("prometheus", "API_PROMETHEUS_"),
+            health_check: envy_load!("health_check", "API_HEALTH_CHECK_"),
             token_config: envy_load!("token", "API_TOKEN_"),
         }
     }
@@ -53,6 +63,63 @@ impl CommonApiConfig {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
     }
 
+    pub fn valid_from_acceptance_window(&self) -> Option<Duration> {
+        self.valid_from_acceptance_window_secs
+            .map(Duration::from_secs)
+    }
+
+    pub fn api_keys_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.api_keys_refresh_interval_secs)
+    }
+
+    pub fn eth_probe_interval(&self) -> Duration {
+        Duration::from_secs(self.eth_probe_interval_secs)
+    }
+
+    pub fn eth_call_retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.eth_call_retry_backoff_ms)
+    }
+
+    pub fn sign_check_enqueue_timeout(&self) -> Duration {
+        Duration::from_millis(self.sign_check_enqueue_timeout_ms)
+    }
+
+    pub fn sign_check_timeout(&self) -> Duration {
+        Duration::from_millis(self.sign_check_timeout_ms)
+    }
+
+    pub fn change_pubkey_auth_retry_delay(&self) -> Duration {
+        Duration::from_millis(self.change_pubkey_auth_retry_delay_ms)
+    }
+
+    pub fn eth_health_log_interval(&self) -> Option<Duration> {
+        self.eth_health_log_interval_secs.map(Duration::from_secs)
+    }
+
+    pub fn rejection_cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.rejection_cache_ttl_ms)
+    }
+
+    pub fn eip1271_cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.eip1271_cache_ttl_ms)
+    }
+
+    pub fn eth_call_batch_window(&self) -> Duration {
+        Duration::from_millis(self.eth_call_batch_window_ms)
+    }
+
+    pub fn tx_screening_timeout(&self) -> Duration {
+        Duration::from_millis(self.tx_screening_timeout_ms)
+    }
+
+    pub fn ens_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.ens_cache_ttl_secs)
+    }
+
+    pub fn fast_withdrawal_validity(&self) -> Duration {
+        Duration::from_secs(self.fast_withdrawal_validity_secs)
+    }
+
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
@@ -76,6 +143,18 @@ impl JsonRpcConfig {
     }
 }
 
+impl GrpcConfig {
+    pub fn from_env() -> Self {
+        envy_load!("grpc", "API_GRPC_")
+    }
+}
+
+impl WebhooksConfig {
+    pub fn from_env() -> Self {
+        envy_load!("webhooks", "API_WEBHOOKS_")
+    }
+}
+
 impl Web3Config {
     pub fn from_env() -> Self {
         envy_load!("web3", "API_WEB3_")
@@ -99,6 +178,54 @@ impl PrometheusConfig {
     }
 }
 
+impl HealthCheckConfig {
+    pub fn from_env() -> Self {
+        envy_load!("health_check", "API_HEALTH_CHECK_")
+    }
+}
+
+/// Block the onchain `ChangePubKey` authorization is checked at.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangePubKeyAuthBlock {
+    /// The latest block.
+    Latest,
+    /// The block `change_pubkey_auth_confirmations` blocks behind the latest one.
+    Confirmed,
+    /// The latest block. The transaction with the authorization missing there is rejected
+    /// as not confirmed yet, so the client resubmits it, until `change_pubkey_auth_retry_delay_ms`
+    /// passes.
+    LatestWithRetry,
+}
+
+/// Source of the onchain checks made by the signature checker.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EthCheckerMode {
+    /// Checks are made by calling the Ethereum node.
+    Node,
+    /// Every EIP1271 signature and onchain `ChangePubKey` authorization is accepted
+    /// without calling the Ethereum node. Not allowed on mainnet.
+    AcceptAll,
+    /// Every EIP1271 signature and onchain `ChangePubKey` authorization is rejected
+    /// without calling the Ethereum node.
+    RejectAll,
+}
+
+/// Screening of the addresses of the incoming transactions, done before they are sent to the mempool.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxScreeningMode {
+    /// Transactions are not screened.
+    Disabled,
+    /// Transactions involving the addresses from the deny list are rejected.
+    DenyList,
+    /// Only the transactions involving no addresses but the ones from the allow list are accepted.
+    AllowList,
+    /// Addresses are sent to the screening service at `tx_screening_url`.
+    Http,
+}
+
 // Common configuration options for the API
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct CommonApiConfig {
@@ -122,6 +249,232 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// Maximum number of seconds `valid_from` of the incoming transaction can be ahead of the server time.
+    /// If not set, pre-signed transactions are accepted regardless of their `valid_from`.
+    pub valid_from_acceptance_window_secs: Option<u64>,
+
+    /// Types of transactions that are accepted without an Ethereum signature.
+    /// zkSync signature is still required for such transactions.
+    #[serde(default)]
+    pub eth_sig_exempt_tx_types: Vec<TransactionType>,
+    /// Whether the batch signatures made for the legacy batch message (hash of the transactions)
+    /// are accepted along with the ones made for the structured message.
+    pub accept_legacy_batch_message: bool,
+    /// Whether the Ethereum signatures made for the messages which aren't bound to the chain
+    /// are accepted along with the ones made for the chain-bound messages.
+    pub accept_unbound_eth_messages: bool,
+    /// Whether the Ethereum signatures made for the messages without the time range bounds
+    /// of the transactions and swap orders are accepted along with the ones made for the
+    /// messages including them.
+    pub accept_untimed_eth_messages: bool,
+    /// Whether a valid batch signature is sufficient for the transactions of the batch, so their
+    /// own Ethereum signatures which don't match are ignored instead of failing the batch.
+    pub batch_signature_covers_txs: bool,
+    /// Minimum total fees of the batches paying in the specific tokens, e.g. `0=1000000000000`.
+    /// Batches paying less are rejected before their signatures are checked, the batches paying
+    /// in the other tokens are not checked.
+    #[serde(default)]
+    pub min_batch_total_fees: Vec<MinBatchTotalFee>,
+
+    /// Number of consecutive failed calls to the Ethereum node after which the signature checks
+    /// requiring the node are rejected without calling it.
+    pub eth_failure_threshold: u32,
+    /// Interval in seconds between the Ethereum node probes while the checks requiring the node are rejected.
+    pub eth_probe_interval_secs: u64,
+    /// Number of retries of the call to the Ethereum node which failed to reach it.
+    /// Calls are not retried if zero.
+    pub eth_call_retries: u32,
+    /// Delay in milliseconds before the first retry of the call to the Ethereum node.
+    /// Every next delay is twice as long.
+    pub eth_call_retry_backoff_ms: u64,
+    /// Interval in seconds between the logged health checks of the Ethereum node.
+    /// If not set, the health of the node is not logged.
+    pub eth_health_log_interval_secs: Option<u64>,
+    /// Source of the onchain checks, e.g. to run the API without the Ethereum node.
+    pub eth_checker_mode: EthCheckerMode,
+    /// Block the onchain `ChangePubKey` authorization is checked at.
+    pub change_pubkey_auth_block: ChangePubKeyAuthBlock,
+    /// Number of blocks the onchain `ChangePubKey` authorization has to be confirmed by
+    /// before the transaction is accepted. Only used with the `confirmed` block.
+    pub change_pubkey_auth_confirmations: u64,
+    /// Time in milliseconds the missing onchain `ChangePubKey` authorization is reported as not
+    /// confirmed yet instead of being rejected. Only used with the `latest_with_retry` block.
+    pub change_pubkey_auth_retry_delay_ms: u64,
+    /// Maximum number of recovered Ethereum signers kept by the signature checker, so repeated
+    /// submissions of the same signed message skip the ECDSA recovery.
+    pub signer_cache_size: usize,
+    /// Maximum number of recently rejected requests kept by the signature checker, so repeated
+    /// submissions of the same invalid transaction are rejected without the check.
+    /// Rejections are not cached if zero.
+    pub rejection_cache_size: usize,
+    /// Time in milliseconds the rejection is returned for the repeated request.
+    pub rejection_cache_ttl_ms: u64,
+    /// Maximum number of EIP1271 signature check results kept by the signature checker, so
+    /// repeated checks of the same signature skip the call to the Ethereum node.
+    /// Results are not cached if zero.
+    pub eip1271_cache_size: usize,
+    /// Time in milliseconds the EIP1271 signature check result is reused for.
+    pub eip1271_cache_ttl_ms: u64,
+    /// Address of the Multicall contract the concurrent onchain checks are aggregated with,
+    /// so they take a single call to the Ethereum node. Checks are made one by one if not set.
+    pub multicall_address: Option<Address>,
+    /// Time in milliseconds an onchain check waits for the other checks to be aggregated with.
+    pub eth_call_batch_window_ms: u64,
+    /// Capacity of the channel of the signature check requests.
+    pub sign_checker_channel_capacity: usize,
+    /// Number of the signature checks a single checker thread runs at once. Requests taken from
+    /// the channel over this limit are rejected because the server is overloaded.
+    pub sign_checker_checks_per_thread: usize,
+    /// Time in milliseconds a signature check request may wait for a free slot in the channel.
+    /// Once it passes, the transaction is rejected because the server is overloaded.
+    pub sign_check_enqueue_timeout_ms: u64,
+    /// Time in milliseconds the signature check of the submitted transaction may take, including
+    /// the time the request waits in the queue. Once it passes, the transaction is rejected with
+    /// the timeout error instead of holding the API request open.
+    pub sign_check_timeout_ms: u64,
+    /// File the signature check requests are appended to, so they can be replayed later.
+    /// Requests are only recorded by the debug builds.
+    pub sign_checker_record_path: Option<String>,
+    /// Number of requests per second a single IP may send to the API.
+    /// Requests are not rate limited if not set.
+    pub rate_limit_rps: Option<u32>,
+    /// Number of requests a single IP may send at once above the steady rate.
+    pub rate_limit_burst: u32,
+    /// Endpoints rate limited separately from the rest of the API, e.g. `tx_submit=5`.
+    #[serde(default)]
+    pub rate_limit_endpoints: Vec<EndpointRateLimit>,
+    /// Proxies in front of the server, e.g. `10.0.0.0/8`. The client IP is only taken from the
+    /// forwarding headers of the requests sent by these proxies, otherwise the peer address is used.
+    #[serde(default)]
+    pub trusted_proxies: Vec<TrustedProxy>,
+    /// Whether the clients may authenticate with the API keys sent in the `X-API-Key` header.
+    /// Requests with the API key are rate limited by the quota of the key instead of the IP.
+    pub api_keys_enabled: bool,
+    /// Interval in seconds the API keys are reloaded from the database with,
+    /// i.e. how soon the minted or revoked key takes effect.
+    pub api_keys_refresh_interval_secs: u64,
+    /// Whether the clients without the API key may access the API.
+    /// Only takes effect if the API keys are enabled.
+    pub allow_anonymous_access: bool,
+    /// Screening of the sender and recipient addresses of the incoming transactions.
+    pub tx_screening_mode: TxScreeningMode,
+    /// URL of the screening service, only used with the `http` screening mode.
+    pub tx_screening_url: Option<String>,
+    /// Time in milliseconds the screening service may take to respond. Once it passes,
+    /// the transaction is rejected since it could not be screened.
+    pub tx_screening_timeout_ms: u64,
+    /// Whether the API node runs on the database with the pruned history of the old blocks,
+    /// so the requests for the transactions of the pruned blocks are rejected instead of
+    /// returning the empty results.
+    pub light_node: bool,
+    /// Address of the ENS registry the `name.eth` recipients are resolved with.
+    /// ENS names are not accepted if it's not set.
+    pub ens_registry_address: Option<Address>,
+    /// Maximum number of the resolved ENS names kept in the cache.
+    pub ens_cache_size: usize,
+    /// Time in seconds the resolved ENS name is reused for.
+    pub ens_cache_ttl_secs: u64,
+    /// Maximum number of the committed account states kept in the cache, `0` disables the cache.
+    /// The cache is only used if the core runs in the same process as the API.
+    pub committed_state_cache_size: usize,
+    /// Whether the withdrawals can be matched with the liquidity providers registered
+    /// via the admin API, so they are fronted on Ethereum without waiting for the block.
+    pub fast_withdrawals_enabled: bool,
+    /// Time in seconds the withdrawal matched with the liquidity provider has to be submitted in.
+    pub fast_withdrawal_validity_secs: u64,
+    /// Ethereum private key the `account_info` and `tx_info` responses are signed with,
+    /// the responses are not signed if it's not set.
+    pub response_signing_key: Option<H256>,
+    /// Addresses of the keys the responses were signed with before the key rotation.
+    #[serde(default)]
+    pub retired_response_signers: Vec<Address>,
+    /// Whether the withdrawals are checked against the limits set via the admin API
+    /// before they are sent to the mempool.
+    pub withdrawal_limits_enabled: bool,
+}
+
+/// Rate limit of the specific endpoint, parsed from the `endpoint=rps` string.
+///
+/// Endpoint is either the JSON RPC method name or the prefix of the REST API path,
+/// e.g. `/api/v0.2/transactions`.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub struct EndpointRateLimit {
+    pub endpoint: String,
+    pub rps: u32,
+}
+
+impl TryFrom<String> for EndpointRateLimit {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts = value.splitn(2, '=');
+        let (endpoint, rps) = match (parts.next(), parts.next()) {
+            (Some(endpoint), Some(rps)) => (endpoint, rps),
+            _ => {
+                return Err(format!(
+                    "Endpoint rate limit {} is not in the endpoint=rps format",
+                    value
+                ))
+            }
+        };
+        let rps = rps
+            .trim()
+            .parse()
+            .map_err(|err| format!("Invalid rate limit of the endpoint {}: {}", endpoint, err))?;
+        Ok(Self {
+            endpoint: endpoint.trim().to_owned(),
+            rps,
+        })
+    }
+}
+
+/// Network of the trusted proxies, parsed from the `ip/prefix` string or a single IP.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub struct TrustedProxy {
+    pub addr: IpAddr,
+    pub prefix_len: u8,
+}
+
+impl TrustedProxy {
+    /// Returns `true` if the IP belongs to the network of the proxy.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let (addr, ip, bits) = match (self.addr, ip) {
+            (IpAddr::V4(addr), IpAddr::V4(ip)) => {
+                (u128::from(u32::from(addr)), u128::from(u32::from(ip)), 32)
+            }
+            (IpAddr::V6(addr), IpAddr::V6(ip)) => (u128::from(addr), u128::from(ip), 128),
+            _ => return false,
+        };
+        let shift = bits - u32::from(self.prefix_len);
+        // Shifting by the whole width of `u128` overflows, so the empty prefix is matched separately.
+        shift == 128 || (addr >> shift) == (ip >> shift)
+    }
+}
+
+impl TryFrom<String> for TrustedProxy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts = value.trim().splitn(2, '/');
+        let addr: IpAddr = parts
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|err| format!("Invalid IP of the trusted proxy {}: {}", value, err))?;
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match parts.next() {
+            Some(prefix_len) => prefix_len
+                .parse()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("Invalid prefix of the trusted proxy {}", value))?,
+            None => max_prefix_len,
+        };
+        Ok(Self { addr, prefix_len })
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -140,6 +493,46 @@ impl TokenConfig {
     }
 }
 
+/// Minimum total fee of the batches paying in the token, parsed from the `token_id=fee` string.
+/// The fee is in the smallest units of the token.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub struct MinBatchTotalFee {
+    pub token: TokenId,
+    pub fee: BigUint,
+}
+
+impl TryFrom<String> for MinBatchTotalFee {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts = value.splitn(2, '=');
+        let (token, fee) = match (parts.next(), parts.next()) {
+            (Some(token), Some(fee)) => (token, fee),
+            _ => {
+                return Err(format!(
+                    "Minimum batch total fee {} is not in the token_id=fee format",
+                    value
+                ))
+            }
+        };
+        let token = token
+            .trim()
+            .parse()
+            .map_err(|err| format!("Invalid token ID {}: {}", token, err))?;
+        let fee = fee.trim().parse().map_err(|err| {
+            format!(
+                "Invalid minimum batch total fee of the token {}: {}",
+                token, err
+            )
+        })?;
+        Ok(Self {
+            token: TokenId(token),
+            fee,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct AdminApiConfig {
     /// Port to which the API server is listening.
@@ -239,17 +632,87 @@ impl Web3Config {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct GrpcConfig {
+    /// Port to which the gRPC server is listening.
+    pub port: u16,
+    /// URL to access gRPC server.
+    pub url: String,
+}
+
+impl GrpcConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
+}
+
+/// Configuration of the notifier posting the network events to the webhooks.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WebhooksConfig {
+    /// Interval between the checks for the new events and pending deliveries.
+    pub poll_interval_ms: u64,
+    /// Timeout of the request to the webhook.
+    pub request_timeout_ms: u64,
+    /// Number of the attempts to deliver the event, after which it's dropped.
+    pub max_attempts: u32,
+    /// Delay before the first retry of the failed delivery, doubled after every failed attempt.
+    pub retry_backoff_ms: u64,
+    /// Maximum delay between the retries of the delivery.
+    pub max_retry_backoff_secs: u64,
+    /// Maximum number of the deliveries attempted at once.
+    pub batch_size: u32,
+    /// Maximum number of the webhooks registered with the API key.
+    pub max_webhooks_per_api_key: u32,
+    /// Allows the webhooks with `http://` URLs and local addresses, only intended for the
+    /// local development.
+    pub allow_insecure_urls: bool,
+}
+
+impl WebhooksConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+
+    /// Returns the delay before the next attempt of the delivery which has failed `attempts` times.
+    pub fn retry_backoff(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(32);
+        let backoff = self.retry_backoff_ms.saturating_mul(1 << exponent);
+        Duration::from_millis(backoff).min(Duration::from_secs(self.max_retry_backoff_secs))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct PrometheusConfig {
     /// Port to which the Prometheus exporter server is listening.
     pub port: u16,
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HealthCheckConfig {
+    /// Port to which the health check server is listening.
+    pub port: u16,
+    /// Time without the iterations of the event loop after which the component is considered stuck.
+    pub heartbeat_timeout_secs: u64,
+}
+
+impl HealthCheckConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_timeout_secs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
-    use std::net::IpAddr;
+    use crate::configs::test_utils::{addr, hash, set_env};
 
     fn expected_config() -> ApiConfig {
         ApiConfig {
@@ -263,6 +726,77 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                valid_from_acceptance_window_secs: Some(3600),
+                eth_sig_exempt_tx_types: vec![TransactionType::ForcedExit],
+                accept_legacy_batch_message: true,
+                accept_unbound_eth_messages: true,
+                accept_untimed_eth_messages: true,
+                batch_signature_covers_txs: false,
+                min_batch_total_fees: vec![MinBatchTotalFee {
+                    token: TokenId(0),
+                    fee: BigUint::from(1_000_000_000_000u64),
+                }],
+                eth_failure_threshold: 5,
+                eth_probe_interval_secs: 10,
+                eth_call_retries: 2,
+                eth_call_retry_backoff_ms: 100,
+                eth_health_log_interval_secs: None,
+                eth_checker_mode: EthCheckerMode::Node,
+                change_pubkey_auth_block: ChangePubKeyAuthBlock::Confirmed,
+                change_pubkey_auth_confirmations: 3,
+                change_pubkey_auth_retry_delay_ms: 2000,
+                signer_cache_size: 10000,
+                rejection_cache_size: 10000,
+                rejection_cache_ttl_ms: 10000,
+                eip1271_cache_size: 10000,
+                eip1271_cache_ttl_ms: 30000,
+                multicall_address: None,
+                eth_call_batch_window_ms: 5,
+                sign_checker_channel_capacity: 32768,
+                sign_checker_checks_per_thread: 16,
+                sign_check_enqueue_timeout_ms: 100,
+                sign_check_timeout_ms: 30000,
+                sign_checker_record_path: None,
+                rate_limit_rps: Some(20),
+                rate_limit_burst: 40,
+                rate_limit_endpoints: vec![
+                    EndpointRateLimit {
+                        endpoint: "tx_submit".to_owned(),
+                        rps: 5,
+                    },
+                    EndpointRateLimit {
+                        endpoint: "/api/v0.2/transactions".to_owned(),
+                        rps: 5,
+                    },
+                ],
+                trusted_proxies: vec![
+                    TrustedProxy {
+                        addr: "10.0.0.0".parse().unwrap(),
+                        prefix_len: 8,
+                    },
+                    TrustedProxy {
+                        addr: "::1".parse().unwrap(),
+                        prefix_len: 128,
+                    },
+                ],
+                api_keys_enabled: true,
+                api_keys_refresh_interval_secs: 30,
+                allow_anonymous_access: true,
+                tx_screening_mode: TxScreeningMode::DenyList,
+                tx_screening_url: None,
+                tx_screening_timeout_ms: 1000,
+                light_node: true,
+                ens_registry_address: Some(addr("00000000000C2E074eC69A0dFb2997BA6C7d2e1e")),
+                ens_cache_size: 10000,
+                ens_cache_ttl_secs: 300,
+                committed_state_cache_size: 10000,
+                fast_withdrawals_enabled: true,
+                fast_withdrawal_validity_secs: 600,
+                response_signing_key: Some(hash(
+                    "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
+                )),
+                retired_response_signers: vec![addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7")],
+                withdrawal_limits_enabled: true,
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -285,6 +819,20 @@ mod tests {
                 max_block_range: 10,
                 chain_id: 240,
             },
+            grpc: GrpcConfig {
+                port: 3040,
+                url: "http://127.0.0.1:3040".into(),
+            },
+            webhooks: WebhooksConfig {
+                poll_interval_ms: 1000,
+                request_timeout_ms: 5000,
+                max_attempts: 10,
+                retry_backoff_ms: 1000,
+                max_retry_backoff_secs: 3600,
+                batch_size: 100,
+                max_webhooks_per_api_key: 10,
+                allow_insecure_urls: false,
+            },
             private: PrivateApiConfig {
                 port: 8090,
                 url: "http://127.0.0.1:8090".into(),
@@ -295,6 +843,10 @@ mod tests {
                 secret_auth: "sample".into(),
             },
             prometheus: PrometheusConfig { port: 3312 },
+            health_check: HealthCheckConfig {
+                port: 3314,
+                heartbeat_timeout_secs: 300,
+            },
             token_config: TokenConfig {
                 invalidate_token_cache_period_sec: 10,
             },
@@ -313,6 +865,50 @@ API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_VALID_FROM_ACCEPTANCE_WINDOW_SECS=3600
+API_COMMON_ETH_SIG_EXEMPT_TX_TYPES=ForcedExit
+API_COMMON_ACCEPT_LEGACY_BATCH_MESSAGE=true
+API_COMMON_ACCEPT_UNBOUND_ETH_MESSAGES=true
+API_COMMON_ACCEPT_UNTIMED_ETH_MESSAGES=true
+API_COMMON_BATCH_SIGNATURE_COVERS_TXS=false
+API_COMMON_MIN_BATCH_TOTAL_FEES="0=1000000000000"
+API_COMMON_ETH_FAILURE_THRESHOLD=5
+API_COMMON_ETH_PROBE_INTERVAL_SECS=10
+API_COMMON_ETH_CALL_RETRIES=2
+API_COMMON_ETH_CALL_RETRY_BACKOFF_MS=100
+API_COMMON_ETH_CHECKER_MODE=node
+API_COMMON_CHANGE_PUBKEY_AUTH_BLOCK=confirmed
+API_COMMON_CHANGE_PUBKEY_AUTH_CONFIRMATIONS=3
+API_COMMON_CHANGE_PUBKEY_AUTH_RETRY_DELAY_MS=2000
+API_COMMON_SIGNER_CACHE_SIZE=10000
+API_COMMON_REJECTION_CACHE_SIZE=10000
+API_COMMON_REJECTION_CACHE_TTL_MS=10000
+API_COMMON_EIP1271_CACHE_SIZE=10000
+API_COMMON_EIP1271_CACHE_TTL_MS=30000
+API_COMMON_ETH_CALL_BATCH_WINDOW_MS=5
+API_COMMON_SIGN_CHECKER_CHANNEL_CAPACITY=32768
+API_COMMON_SIGN_CHECKER_CHECKS_PER_THREAD=16
+API_COMMON_SIGN_CHECK_ENQUEUE_TIMEOUT_MS=100
+API_COMMON_SIGN_CHECK_TIMEOUT_MS=30000
+API_COMMON_RATE_LIMIT_RPS=20
+API_COMMON_RATE_LIMIT_BURST=40
+API_COMMON_RATE_LIMIT_ENDPOINTS="tx_submit=5,/api/v0.2/transactions=5"
+API_COMMON_TRUSTED_PROXIES="10.0.0.0/8,::1"
+API_COMMON_API_KEYS_ENABLED=true
+API_COMMON_API_KEYS_REFRESH_INTERVAL_SECS=30
+API_COMMON_ALLOW_ANONYMOUS_ACCESS=true
+API_COMMON_TX_SCREENING_MODE=deny_list
+API_COMMON_TX_SCREENING_TIMEOUT_MS=1000
+API_COMMON_LIGHT_NODE=true
+API_COMMON_ENS_REGISTRY_ADDRESS=0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e
+API_COMMON_ENS_CACHE_SIZE=10000
+API_COMMON_ENS_CACHE_TTL_SECS=300
+API_COMMON_COMMITTED_STATE_CACHE_SIZE=10000
+API_COMMON_FAST_WITHDRAWALS_ENABLED=true
+API_COMMON_FAST_WITHDRAWAL_VALIDITY_SECS=600
+API_COMMON_RESPONSE_SIGNING_KEY=0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be
+API_COMMON_RETIRED_RESPONSE_SIGNERS=0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7
+API_COMMON_WITHDRAWAL_LIMITS_ENABLED=true
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
@@ -327,12 +923,24 @@ API_WEB3_PORT="3002"
 API_WEB3_URL="http://127.0.0.1:3002"
 API_WEB3_CHAIN_ID="240"
 API_WEB3_MAX_BLOCK_RANGE="10"
+API_GRPC_PORT="3040"
+API_GRPC_URL="http://127.0.0.1:3040"
+API_WEBHOOKS_POLL_INTERVAL_MS="1000"
+API_WEBHOOKS_REQUEST_TIMEOUT_MS="5000"
+API_WEBHOOKS_MAX_ATTEMPTS="10"
+API_WEBHOOKS_RETRY_BACKOFF_MS="1000"
+API_WEBHOOKS_MAX_RETRY_BACKOFF_SECS="3600"
+API_WEBHOOKS_BATCH_SIZE="100"
+API_WEBHOOKS_MAX_WEBHOOKS_PER_API_KEY="10"
+API_WEBHOOKS_ALLOW_INSECURE_URLS="false"
 API_PRIVATE_PORT="8090"
 API_PRIVATE_URL="http://127.0.0.1:8090"
 API_PROVER_PORT="8088"
 API_PROVER_URL="http://127.0.0.1:8088"
 API_PROVER_SECRET_AUTH="sample"
 API_PROMETHEUS_PORT="3312"
+API_HEALTH_CHECK_PORT="3314"
+API_HEALTH_CHECK_HEARTBEAT_TIMEOUT_SECS="300"
         "#;
         set_env(config);
 
@@ -340,6 +948,25 @@ API_PROMETHEUS_PORT="3312"
         assert_eq!(actual, expected_config());
     }
 
+    #[test]
+    fn trusted_proxy() {
+        let proxy = |value: &str| TrustedProxy::try_from(value.to_owned());
+        let ip = |value: &str| value.parse::<IpAddr>().unwrap();
+
+        let network = proxy("10.1.0.0/16").unwrap();
+        assert!(network.contains(ip("10.1.2.3")));
+        assert!(!network.contains(ip("10.2.0.1")));
+        assert!(!network.contains(ip("::ffff:10.1.2.3")));
+        let single = proxy("2001:db8::1").unwrap();
+        assert!(single.contains(ip("2001:db8::1")));
+        assert!(!single.contains(ip("2001:db8::2")));
+        assert!(proxy("::/0").unwrap().contains(ip("2001:db8::2")));
+        assert!(proxy("0.0.0.0/0").unwrap().contains(ip("1.1.1.1")));
+
+        assert!(proxy("10.0.0.0/33").is_err());
+        assert!(proxy("localhost").is_err());
+    }
+
     /// Checks the correctness of the config helper methods.
     #[test]
     fn methods() {
@@ -370,5 +997,16 @@ API_PROMETHEUS_PORT="3312"
             config.web3.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
+        assert_eq!(
+            config.grpc.bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.grpc.port)
+        );
+
+        assert_eq!(config.webhooks.retry_backoff(1), Duration::from_secs(1));
+        assert_eq!(config.webhooks.retry_backoff(4), Duration::from_secs(8));
+        assert_eq!(
+            config.webhooks.retry_backoff(100),
+            Duration::from_secs(config.webhooks.max_retry_backoff_secs)
+        );
     }
 }
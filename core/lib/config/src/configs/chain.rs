@@ -74,6 +74,17 @@ pub struct Eth {
     pub network: Network,
 }
 
+/// Order the ready mempool transactions are proposed for the next block in.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolTxOrdering {
+    /// Transactions are proposed in the order they are received.
+    Fifo,
+    /// Transactions paying the higher fee in USD per chunk are proposed first,
+    /// while the transactions of each account are kept in the order of their nonces.
+    FeePerChunk,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct StateKeeper {
     /// Block sizes to be generated by server. Has to contain only values set in the `supported_block_chunks_sizes`,
@@ -86,6 +97,36 @@ pub struct StateKeeper {
     pub miniblock_iterations: u64,
     /// Maximum amount of miniblock iterations in case of block containing a fast withdrawal request.
     pub fast_block_miniblock_iterations: u64,
+    /// Order the mempool transactions are proposed for the next block in.
+    pub mempool_tx_ordering: MempoolTxOrdering,
+    /// Maximum difference between the nonce of the incoming transaction and the committed nonce
+    /// of the account. Transactions with a nonce gap wait in the mempool for the missing ones.
+    pub max_nonce_gap: u32,
+    /// Time in seconds the transactions with a nonce gap wait for the missing transactions,
+    /// after that they are executed and fail.
+    pub nonce_gap_timeout: u64,
+    /// Maximum number of the pending transactions in the mempool.
+    pub mempool_max_txs: u32,
+    /// Maximum number of the pending transactions of a single account in the mempool.
+    pub mempool_max_account_txs: u32,
+    /// Time in seconds after which the pending transactions not included into a block
+    /// are removed from the mempool.
+    pub mempool_tx_ttl: u64,
+    /// Time in seconds since the first operation of the block after which the block is sealed,
+    /// regardless of the miniblock iterations. Not limited if not set.
+    pub seal_max_block_age: Option<u64>,
+    /// Time in seconds without new operations after which the non-empty block is sealed.
+    /// Not limited if not set.
+    pub seal_max_idle_time: Option<u64>,
+    /// Share of the block chunks in percent, once it's used the block is sealed.
+    /// With `100` the block is only sealed by the chunks once it's full.
+    pub seal_min_chunk_utilization_percent: u64,
+    /// Whether the block is sealed right after the miniblock which included `ForcedExit` or `FullExit`,
+    /// so the exits are not delayed by the miniblock iterations.
+    pub seal_on_exit: bool,
+    /// Directory of the RocksDB cache of the account tree. With the cache, only the changes made
+    /// since the cached block are loaded from the database on restart. Not used if not set.
+    pub tree_cache_path: Option<String>,
     pub fee_account_addr: Address,
     pub aggregated_proof_sizes: Vec<usize>,
     pub max_aggregated_blocks_to_commit: usize,
@@ -102,6 +143,22 @@ impl StateKeeper {
         Duration::from_millis(self.miniblock_iteration_interval)
     }
 
+    pub fn nonce_gap_timeout(&self) -> Duration {
+        Duration::from_secs(self.nonce_gap_timeout)
+    }
+
+    pub fn mempool_tx_ttl(&self) -> Duration {
+        Duration::from_secs(self.mempool_tx_ttl)
+    }
+
+    pub fn seal_max_block_age(&self) -> Option<Duration> {
+        self.seal_max_block_age.map(Duration::from_secs)
+    }
+
+    pub fn seal_max_idle_time(&self) -> Option<Duration> {
+        self.seal_max_idle_time.map(Duration::from_secs)
+    }
+
     pub fn block_commit_deadline(&self) -> Duration {
         Duration::from_secs(self.block_commit_deadline)
     }
@@ -139,6 +196,17 @@ mod tests {
                 miniblock_iteration_interval: 200,
                 miniblock_iterations: 10,
                 fast_block_miniblock_iterations: 5,
+                mempool_tx_ordering: MempoolTxOrdering::FeePerChunk,
+                max_nonce_gap: 16,
+                nonce_gap_timeout: 60,
+                mempool_max_txs: 100_000,
+                mempool_max_account_txs: 100,
+                mempool_tx_ttl: 3_600,
+                seal_max_block_age: Some(120),
+                seal_max_idle_time: Some(30),
+                seal_min_chunk_utilization_percent: 100,
+                seal_on_exit: true,
+                tree_cache_path: Some("db/tree_cache".into()),
                 fee_account_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
                 aggregated_proof_sizes: vec![1, 5],
                 max_aggregated_blocks_to_commit: 3,
@@ -167,6 +235,17 @@ CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES="6,30"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATION_INTERVAL="200"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATIONS="10"
 CHAIN_STATE_KEEPER_FAST_BLOCK_MINIBLOCK_ITERATIONS="5"
+CHAIN_STATE_KEEPER_MEMPOOL_TX_ORDERING="fee_per_chunk"
+CHAIN_STATE_KEEPER_MAX_NONCE_GAP="16"
+CHAIN_STATE_KEEPER_NONCE_GAP_TIMEOUT="60"
+CHAIN_STATE_KEEPER_MEMPOOL_MAX_TXS="100000"
+CHAIN_STATE_KEEPER_MEMPOOL_MAX_ACCOUNT_TXS="100"
+CHAIN_STATE_KEEPER_MEMPOOL_TX_TTL="3600"
+CHAIN_STATE_KEEPER_SEAL_MAX_BLOCK_AGE="120"
+CHAIN_STATE_KEEPER_SEAL_MAX_IDLE_TIME="30"
+CHAIN_STATE_KEEPER_SEAL_MIN_CHUNK_UTILIZATION_PERCENT="100"
+CHAIN_STATE_KEEPER_SEAL_ON_EXIT="true"
+CHAIN_STATE_KEEPER_TREE_CACHE_PATH="db/tree_cache"
 CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
 CHAIN_STATE_KEEPER_AGGREGATED_PROOF_SIZES="1,5"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_BLOCKS_TO_COMMIT="3"
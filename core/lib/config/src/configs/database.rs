@@ -18,6 +18,24 @@ pub struct DBConfig {
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Amount of the latest verified blocks whose history (executed transactions, events and witnesses)
+    /// is kept in the database. History is kept forever if not set.
+    pub history_retention_blocks: Option<u32>,
+    /// Sleep time (in seconds) of the actor responsible for pruning the history of the old blocks.
+    pub history_pruner_interval: u64,
+    /// Sleep time (in seconds) of the actor responsible for aggregating the network statistics.
+    pub stats_aggregator_interval: u64,
+    /// URLs of the read-only replicas the API queries are routed to.
+    /// The primary database is used if the list is empty.
+    #[serde(default)]
+    pub replica_urls: Vec<String>,
+    /// Interval between the health checks of the replicas in ms.
+    pub replica_health_check_interval: u64,
+    /// Replication lag in seconds after which the replica is not used until it catches up.
+    /// Not limited if not set.
+    pub replica_max_lag: Option<u64>,
+    /// Queries taking longer than this amount of ms are logged. Not logged if not set.
+    pub slow_query_threshold: Option<u64>,
 }
 
 impl DBConfig {
@@ -34,6 +52,26 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval)
     }
+
+    pub fn history_pruner_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.history_pruner_interval)
+    }
+
+    pub fn stats_aggregator_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.stats_aggregator_interval)
+    }
+
+    pub fn replica_health_check_interval(&self) -> time::Duration {
+        time::Duration::from_millis(self.replica_health_check_interval)
+    }
+
+    pub fn replica_max_lag(&self) -> Option<time::Duration> {
+        self.replica_max_lag.map(time::Duration::from_secs)
+    }
+
+    pub fn slow_query_threshold(&self) -> Option<time::Duration> {
+        self.slow_query_threshold.map(time::Duration::from_millis)
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +85,16 @@ mod tests {
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            history_retention_blocks: Some(100000),
+            history_pruner_interval: 600,
+            stats_aggregator_interval: 60,
+            replica_urls: vec![
+                "postgres://postgres@replica1/plasma".into(),
+                "postgres://postgres@replica2/plasma".into(),
+            ],
+            replica_health_check_interval: 5000,
+            replica_max_lag: Some(60),
+            slow_query_threshold: Some(1000),
         }
     }
 
@@ -57,6 +105,13 @@ DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_HISTORY_RETENTION_BLOCKS="100000"
+DATABASE_HISTORY_PRUNER_INTERVAL="600"
+DATABASE_STATS_AGGREGATOR_INTERVAL="60"
+DATABASE_REPLICA_URLS="postgres://postgres@replica1/plasma,postgres://postgres@replica2/plasma"
+DATABASE_REPLICA_HEALTH_CHECK_INTERVAL="5000"
+DATABASE_REPLICA_MAX_LAG="60"
+DATABASE_SLOW_QUERY_THRESHOLD="1000"
         "#;
         set_env(config);
 
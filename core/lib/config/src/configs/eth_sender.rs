@@ -14,6 +14,8 @@ pub struct ETHSenderConfig {
     pub sender: Sender,
     /// Options related to the `gas_adjuster` submodule.
     pub gas_price_limit: GasLimit,
+    /// Options related to the completion of the pending withdrawals.
+    pub withdrawals: Withdrawals,
 }
 
 impl ETHSenderConfig {
@@ -24,16 +26,46 @@ impl ETHSenderConfig {
                 "eth_sender.gas_price_limit",
                 "ETH_SENDER_GAS_PRICE_LIMIT_"
             ),
+            withdrawals: envy_load!("eth_sender.withdrawals", "ETH_SENDER_WITHDRAWALS_"),
         }
     }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Sender {
-    /// Private key of the operator account.
-    pub operator_private_key: H256,
+    /// Signer of the operator transactions.
+    pub signer: EthSignerType,
+    /// Private key of the operator account, only used by the `private_key` signer.
+    pub operator_private_key: Option<H256>,
+    /// URL of the JSON RPC signer holding the operator account, only used by the `json_rpc` signer.
+    /// The account is expected to be unlocked.
+    pub signer_url: Option<String>,
+    /// ID or ARN of the AWS KMS key of the operator account, only used by the `aws_kms` signer.
+    pub kms_key_id: Option<String>,
+    /// AWS region of the KMS key, taken from the AWS environment variables if not set.
+    pub kms_region: Option<String>,
+    /// Path to the keystore file of the operator account, only used by the `keystore` signer.
+    pub keystore_path: Option<String>,
+    /// Password of the keystore file.
+    pub keystore_password: Option<String>,
     /// Address of the operator account.
     pub operator_commit_eth_addr: Address,
+    /// Addresses of the additional operator accounts, each one sending the transactions in its own
+    /// nonce sequence, in parallel with the commits sent from `operator_commit_eth_addr`.
+    /// The first account sends the proofs, and the second one (or the first one, if it's the only one)
+    /// sends the block executions.
+    #[serde(default)]
+    pub additional_operator_eth_addrs: Vec<Address>,
+    /// Private keys of the additional operator accounts, only used by the `private_key` signer.
+    #[serde(default)]
+    pub additional_operator_private_keys: Vec<H256>,
+    /// IDs or ARNs of the AWS KMS keys of the additional operator accounts, only used by the `aws_kms` signer.
+    #[serde(default)]
+    pub additional_kms_key_ids: Vec<String>,
+    /// Paths to the keystore files of the additional operator accounts, only used by the `keystore` signer.
+    /// They're decrypted with the same `keystore_password`.
+    #[serde(default)]
+    pub additional_keystore_paths: Vec<String>,
     /// mount of confirmations required to consider L1 transaction committed.
     pub wait_confirmations: u64,
     /// Amount of blocks we will wait before considering L1 transaction stuck.
@@ -44,6 +76,32 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// Type of the sent Ethereum transactions.
+    pub tx_type: EthTxType,
+}
+
+/// Signer of the operator transactions.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EthSignerType {
+    /// Signer using the private key from `operator_private_key`.
+    PrivateKey,
+    /// Remote signer supporting the `eth_signTransaction` JSON RPC method at `signer_url`.
+    JsonRpc,
+    /// Signer using the key stored in the AWS KMS, so it never leaves it.
+    AwsKms,
+    /// Signer using the private key decrypted from the keystore file.
+    Keystore,
+}
+
+/// Type of the Ethereum transactions sent by the operator.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EthTxType {
+    /// Transactions paying the fixed gas price.
+    Legacy,
+    /// Transactions paying the block base fee plus the priority fee, supported after the London fork.
+    Eip1559,
 }
 
 impl Sender {
@@ -63,6 +121,14 @@ pub struct GasLimit {
     pub sample_interval: u64,
     /// Scale factor for gas price limit (used by GasAdjuster).
     pub scale_factor: f64,
+    /// Priority fee per gas of the EIP-1559 transactions in wei (used by GasAdjuster).
+    pub priority_fee: u64,
+    /// Percent the gas price of the stuck transaction is increased by on its replacement
+    /// (used by GasAdjuster). Ethereum nodes require at least 10%.
+    pub bump_percent: u64,
+    /// Gas price in wei the gas price limit never exceeds (used by GasAdjuster).
+    /// Reaching it is reported as an error.
+    pub hard_cap: Option<u64>,
 }
 
 impl GasLimit {
@@ -77,6 +143,36 @@ impl GasLimit {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Withdrawals {
+    /// Whether the pending withdrawals of the executed blocks are completed by the `eth_sender`.
+    pub is_enabled: bool,
+    /// Address of the `PendingBalanceWithdrawer` contract completing the batches of withdrawals.
+    pub withdrawer_contract_addr: Address,
+    /// Address of the account sending the batches of withdrawals.
+    /// It uses the same signer type as the operator accounts.
+    pub sender_eth_addr: Address,
+    /// Private key of the withdrawals sender account, only used by the `private_key` signer.
+    pub sender_private_key: Option<H256>,
+    /// ID or ARN of the AWS KMS key of the withdrawals sender account, only used by the `aws_kms` signer.
+    pub sender_kms_key_id: Option<String>,
+    /// Path to the keystore file of the withdrawals sender account, only used by the `keystore` signer.
+    pub sender_keystore_path: Option<String>,
+    /// The maximum amount of withdrawals completed in one transaction.
+    pub max_batch_size: usize,
+    /// The maximum time in seconds the withdrawal waits for the batch to be filled.
+    pub max_delay: u64,
+    /// The maximum amount of gas forwarded to the withdrawals of one transaction.
+    pub gas_budget: u64,
+}
+
+impl Withdrawals {
+    /// Converts `self.max_delay` into `Duration`.
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_secs(self.max_delay)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,16 +186,49 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
-                operator_private_key: hash(
+                tx_type: EthTxType::Eip1559,
+                signer: EthSignerType::PrivateKey,
+                operator_private_key: Some(hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
-                ),
+                )),
+                signer_url: None,
+                kms_key_id: None,
+                kms_region: None,
+                keystore_path: None,
+                keystore_password: None,
                 operator_commit_eth_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                additional_operator_eth_addrs: vec![
+                    addr("a61464658AfeAf65CccaaFD3a512b69A83B77618"),
+                    addr("0D43eB5B8a47bA8900d84AA36656c92024e9772e"),
+                ],
+                additional_operator_private_keys: vec![
+                    hash("ac1e735be8536c6534bb4f17f06f6afc73b2b5ba84ac2cfb12f7461b20c0bbe3"),
+                    hash("d293c684d884d56f8d6abd64fc76757d3664904e309a0645baf8522ab6366d9e"),
+                ],
+                additional_kms_key_ids: vec![],
+                additional_keystore_paths: vec![],
             },
             gas_price_limit: GasLimit {
                 default: 400000000000,
                 update_interval: 150,
                 sample_interval: 15,
                 scale_factor: 1.0f64,
+                priority_fee: 2000000000,
+                bump_percent: 15,
+                hard_cap: Some(1000000000000),
+            },
+            withdrawals: Withdrawals {
+                is_enabled: true,
+                withdrawer_contract_addr: addr("a8f4ae4e9fbd9ca5bb2e9e1e1e4c0fb01b9ba8f2"),
+                sender_eth_addr: addr("52312AD6f01657413b2eaE9287f6B9ADaD93D5FE"),
+                sender_private_key: Some(hash(
+                    "c37e9f5a7ab5a3c11ac2b4c1df3dbf9a5da1b2bbd6e39d07b7bd1ed0a5fd1fa5",
+                )),
+                sender_kms_key_id: None,
+                sender_keystore_path: None,
+                max_batch_size: 50,
+                max_delay: 600,
+                gas_budget: 6000000,
             },
         }
     }
@@ -112,12 +241,26 @@ ETH_SENDER_SENDER_EXPECTED_WAIT_TIME_BLOCK="30"
 ETH_SENDER_SENDER_TX_POLL_PERIOD="3"
 ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
+ETH_SENDER_SENDER_TX_TYPE="eip1559"
+ETH_SENDER_SENDER_SIGNER="private_key"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+ETH_SENDER_SENDER_ADDITIONAL_OPERATOR_ETH_ADDRS="0xa61464658AfeAf65CccaaFD3a512b69A83B77618,0x0D43eB5B8a47bA8900d84AA36656c92024e9772e"
+ETH_SENDER_SENDER_ADDITIONAL_OPERATOR_PRIVATE_KEYS="0xac1e735be8536c6534bb4f17f06f6afc73b2b5ba84ac2cfb12f7461b20c0bbe3,0xd293c684d884d56f8d6abd64fc76757d3664904e309a0645baf8522ab6366d9e"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_GAS_PRICE_LIMIT_PRIORITY_FEE="2000000000"
+ETH_SENDER_GAS_PRICE_LIMIT_BUMP_PERCENT="15"
+ETH_SENDER_GAS_PRICE_LIMIT_HARD_CAP="1000000000000"
+ETH_SENDER_WITHDRAWALS_IS_ENABLED="true"
+ETH_SENDER_WITHDRAWALS_WITHDRAWER_CONTRACT_ADDR="0xa8f4ae4e9fbd9ca5bb2e9e1e1e4c0fb01b9ba8f2"
+ETH_SENDER_WITHDRAWALS_SENDER_ETH_ADDR="0x52312AD6f01657413b2eaE9287f6B9ADaD93D5FE"
+ETH_SENDER_WITHDRAWALS_SENDER_PRIVATE_KEY="0xc37e9f5a7ab5a3c11ac2b4c1df3dbf9a5da1b2bbd6e39d07b7bd1ed0a5fd1fa5"
+ETH_SENDER_WITHDRAWALS_MAX_BATCH_SIZE="50"
+ETH_SENDER_WITHDRAWALS_MAX_DELAY="600"
+ETH_SENDER_WITHDRAWALS_GAS_BUDGET="6000000"
         "#;
         set_env(config);
 
@@ -143,5 +286,10 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             config.gas_price_limit.sample_interval(),
             Duration::from_secs(config.gas_price_limit.sample_interval)
         );
+
+        assert_eq!(
+            config.withdrawals.max_delay(),
+            Duration::from_secs(config.withdrawals.max_delay)
+        );
     }
 }
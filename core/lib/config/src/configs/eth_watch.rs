@@ -14,6 +14,26 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Depth (in blocks) of the Ethereum reorgs that are detected and recovered from.
+    /// Hashes of the processed blocks within this depth are tracked to detect the reorgs.
+    pub reorg_detection_depth: u64,
+    /// Maximum amount of blocks requested at once while backfilling the events on startup.
+    pub backfill_chunk_size: u64,
+    /// Amount of times the failed request for a chunk of the events is retried
+    /// before the backfill is considered failed.
+    pub backfill_max_retries: u32,
+    /// Delay between the attempts to request a chunk of the events.
+    /// Value in milliseconds.
+    pub backfill_retry_delay: u64,
+    /// How often the deadlines of the priority operations not executed on Ethereum are checked.
+    /// Value in milliseconds.
+    pub priority_op_watchdog_interval: u64,
+    /// Amount of Ethereum blocks left before the deadline of a priority operation not executed
+    /// on Ethereum, at which the priority operation watchdog starts reporting it.
+    pub priority_op_warning_blocks: u64,
+    /// Amount of Ethereum blocks left before the deadline of a priority operation not committed
+    /// yet, at which the pending block is sealed right away so the operation is committed.
+    pub priority_op_emergency_blocks: u64,
 }
 
 impl ETHWatchConfig {
@@ -25,6 +45,16 @@ impl ETHWatchConfig {
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Converts `self.backfill_retry_delay` into `Duration`.
+    pub fn backfill_retry_delay(&self) -> Duration {
+        Duration::from_millis(self.backfill_retry_delay)
+    }
+
+    /// Converts `self.priority_op_watchdog_interval` into `Duration`.
+    pub fn priority_op_watchdog_interval(&self) -> Duration {
+        Duration::from_millis(self.priority_op_watchdog_interval)
+    }
 }
 
 #[cfg(test)]
@@ -36,6 +66,13 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            reorg_detection_depth: 256,
+            backfill_chunk_size: 10000,
+            backfill_max_retries: 5,
+            backfill_retry_delay: 1000,
+            priority_op_watchdog_interval: 10000,
+            priority_op_warning_blocks: 10000,
+            priority_op_emergency_blocks: 2000,
         }
     }
 
@@ -44,6 +81,13 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_REORG_DETECTION_DEPTH="256"
+ETH_WATCH_BACKFILL_CHUNK_SIZE="10000"
+ETH_WATCH_BACKFILL_MAX_RETRIES="5"
+ETH_WATCH_BACKFILL_RETRY_DELAY="1000"
+ETH_WATCH_PRIORITY_OP_WATCHDOG_INTERVAL="10000"
+ETH_WATCH_PRIORITY_OP_WARNING_BLOCKS="10000"
+ETH_WATCH_PRIORITY_OP_EMERGENCY_BLOCKS="2000"
         "#;
         set_env(config);
 
@@ -60,5 +104,13 @@ ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             config.poll_interval(),
             Duration::from_millis(config.eth_node_poll_interval)
         );
+        assert_eq!(
+            config.backfill_retry_delay(),
+            Duration::from_millis(config.backfill_retry_delay)
+        );
+        assert_eq!(
+            config.priority_op_watchdog_interval(),
+            Duration::from_millis(config.priority_op_watchdog_interval)
+        );
     }
 }
@@ -17,6 +17,8 @@ pub struct EventListenerConfig {
     /// PostgreSQL channel name to listen on. Must be equal to the one
     /// hardcoded into database migrations.
     pub channel_name: String,
+    /// Port of the Prometheus exporter of the event listener, metrics are not exported if it's not set.
+    pub prometheus_port: Option<u16>,
 }
 
 impl EventListenerConfig {
@@ -40,6 +42,7 @@ mod tests {
             ws_port: 65535,
             ws_url: "ws://localhost:12345".into(),
             channel_name: "zksync_event_channel".into(),
+            prometheus_port: Some(3313),
         }
     }
 
@@ -49,6 +52,7 @@ mod tests {
 EVENT_LISTENER_WS_URL="ws://localhost:12345"
 EVENT_LISTENER_WS_PORT="65535"
 EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
+EVENT_LISTENER_PROMETHEUS_PORT="3313"
         "#;
         set_env(config);
 
@@ -0,0 +1,89 @@
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use serde::Deserialize;
+
+// Local uses
+use crate::envy_load;
+
+/// Message broker the events are published to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventBroker {
+    Kafka,
+    /// NATS JetStream, the stream capturing the subjects has to be created in advance.
+    Nats,
+}
+
+impl EventBroker {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Kafka => "kafka",
+            Self::Nats => "nats",
+        }
+    }
+}
+
+/// Configuration for the publisher of the network events to the message broker.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EventPublisherConfig {
+    pub broker: EventBroker,
+    /// Kafka bootstrap servers (comma-separated) or the NATS server URL.
+    pub broker_url: String,
+    /// Events are published to the `{topic_prefix}.{event_type}` topics (subjects in NATS).
+    pub topic_prefix: String,
+    /// Interval between the polls of the new events in ms.
+    pub poll_interval: u64,
+    /// Time the broker has to acknowledge the event in ms.
+    pub publish_timeout: u64,
+    /// Maximum amount of the events loaded from the database at once.
+    pub batch_size: u32,
+}
+
+impl EventPublisherConfig {
+    pub fn from_env() -> Self {
+        envy_load!("event_publisher", "EVENT_PUBLISHER_")
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+
+    pub fn publish_timeout(&self) -> Duration {
+        Duration::from_millis(self.publish_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> EventPublisherConfig {
+        EventPublisherConfig {
+            broker: EventBroker::Nats,
+            broker_url: "nats://localhost:4222".into(),
+            topic_prefix: "zksync".into(),
+            poll_interval: 1000,
+            publish_timeout: 5000,
+            batch_size: 500,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+EVENT_PUBLISHER_BROKER="nats"
+EVENT_PUBLISHER_BROKER_URL="nats://localhost:4222"
+EVENT_PUBLISHER_TOPIC_PREFIX="zksync"
+EVENT_PUBLISHER_POLL_INTERVAL="1000"
+EVENT_PUBLISHER_PUBLISH_TIMEOUT="5000"
+EVENT_PUBLISHER_BATCH_SIZE="500"
+        "#;
+        set_env(config);
+
+        let actual = EventPublisherConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+}
@@ -3,9 +3,9 @@ pub use self::{
     api::ApiConfig, chain::ChainConfig, contracts::ContractsConfig, database::DBConfig,
     dev_liquidity_token_watcher::DevLiquidityTokenWatcherConfig, eth_client::ETHClientConfig,
     eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, event_listener::EventListenerConfig,
-    forced_exit_requests::ForcedExitRequestsConfig, gateway_watcher::GatewayWatcherConfig,
-    misc::MiscConfig, prover::ProverConfig, ticker::TickerConfig,
-    token_handler::TokenHandlerConfig,
+    event_publisher::EventPublisherConfig, forced_exit_requests::ForcedExitRequestsConfig,
+    gateway_watcher::GatewayWatcherConfig, misc::MiscConfig, prover::ProverConfig,
+    ticker::TickerConfig, token_handler::TokenHandlerConfig,
 };
 
 pub mod api;
@@ -17,6 +17,7 @@ pub mod eth_client;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod event_listener;
+pub mod event_publisher;
 pub mod forced_exit_requests;
 pub mod gateway_watcher;
 pub mod misc;
@@ -34,6 +34,38 @@ pub struct Prover {
     pub request_timeout: u64,
     /// Flag for dying after proving cycle
     pub die_after_proof: bool,
+    /// Block sizes the prover is able to prove, in chunks.
+    /// The prover only gets the single proof jobs for these block sizes, empty list means any block size.
+    #[serde(default)]
+    pub block_sizes: Vec<usize>,
+    /// Backend performing the proving.
+    #[serde(default)]
+    pub backend: ProverBackend,
+    /// Indices of the GPU devices used by the `gpu` backend, one worker is run per device.
+    /// Empty list means all the available devices.
+    #[serde(default)]
+    pub gpu_devices: Vec<usize>,
+    /// Command of the GPU proving backend, used by the `gpu` backend.
+    /// It reads the prover job from the stdin and writes the proof to the stdout, both as JSON,
+    /// and runs with the `CUDA_VISIBLE_DEVICES` set to the device assigned to the worker.
+    #[serde(default)]
+    pub gpu_prover_command: String,
+}
+
+/// Backend performing the proving.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProverBackend {
+    /// Proving on the CPU within the prover process.
+    Cpu,
+    /// Proving on the GPU devices by the external GPU proving command.
+    Gpu,
+}
+
+impl Default for ProverBackend {
+    fn default() -> Self {
+        Self::Cpu
+    }
 }
 
 impl Prover {
@@ -57,6 +89,8 @@ impl Prover {
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Core {
     /// Timeout to consider prover gone in ms.
+    /// Jobs of the provers that haven't sent a heartbeat for this long are handed out again,
+    /// so it should exceed the doubled heartbeat interval of the provers.
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
@@ -73,8 +107,17 @@ impl Core {
 pub struct WitnessGenerator {
     /// Interval to check whether a new witness generation job should be started in ms.
     pub prepare_data_interval: u64,
-    /// Amount of witness generator threads.
+    /// Amount of the witnesses built concurrently.
     pub witness_generators: usize,
+    /// Memory limit in MB for the account tree copies held by the witnesses built concurrently.
+    /// Each witness is built on its own copy of the account tree.
+    pub max_memory_mb: u32,
+    /// Time in days after which the witnesses of the verified blocks are removed.
+    /// Witnesses are kept forever if not set.
+    pub witness_retention_days: Option<u64>,
+    /// Directory of the RocksDB cache of the account tree. With the cache, only the changes made
+    /// since the cached block are loaded from the database on restart. Not used if not set.
+    pub tree_cache_path: Option<String>,
 }
 
 impl WitnessGenerator {
@@ -82,6 +125,12 @@ impl WitnessGenerator {
     pub fn prepare_data_interval(&self) -> Duration {
         Duration::from_millis(self.prepare_data_interval)
     }
+
+    /// Converts `self.witness_retention_days` into `Duration`.
+    pub fn witness_retention(&self) -> Option<Duration> {
+        self.witness_retention_days
+            .map(|days| Duration::from_secs(days * 24 * 60 * 60))
+    }
 }
 
 #[cfg(test)]
@@ -96,6 +145,10 @@ mod tests {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                block_sizes: vec![26, 78],
+                backend: ProverBackend::Gpu,
+                gpu_devices: vec![0, 1],
+                gpu_prover_command: "gpu_prover --setup-dir keys".to_string(),
             },
             core: Core {
                 gone_timeout: 60000,
@@ -104,6 +157,9 @@ mod tests {
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                max_memory_mb: 16384,
+                witness_retention_days: Some(30),
+                tree_cache_path: Some("db/witness_tree_cache".into()),
             },
         }
     }
@@ -115,10 +171,17 @@ PROVER_PROVER_HEARTBEAT_INTERVAL="1000"
 PROVER_PROVER_CYCLE_WAIT="500"
 PROVER_PROVER_REQUEST_TIMEOUT="10"
 PROVER_PROVER_DIE_AFTER_PROOF=false
+PROVER_PROVER_BLOCK_SIZES="26,78"
+PROVER_PROVER_BACKEND="gpu"
+PROVER_PROVER_GPU_DEVICES="0,1"
+PROVER_PROVER_GPU_PROVER_COMMAND="gpu_prover --setup-dir keys"
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_MAX_MEMORY_MB="16384"
+PROVER_WITNESS_GENERATOR_WITNESS_RETENTION_DAYS="30"
+PROVER_WITNESS_GENERATOR_TREE_CACHE_PATH="db/witness_tree_cache"
         "#;
         set_env(config);
 
@@ -153,5 +216,9 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.witness_generator.prepare_data_interval(),
             Duration::from_millis(config.witness_generator.prepare_data_interval)
         );
+        assert_eq!(
+            config.witness_generator.witness_retention(),
+            Some(Duration::from_secs(30 * 24 * 60 * 60))
+        );
     }
 }
@@ -1,5 +1,7 @@
-use num::{rational::Ratio, BigUint};
+// Built-in uses
+use std::time::Duration;
 // External uses
+use num::{rational::Ratio, BigUint};
 use serde::Deserialize;
 // Workspace uses
 use zksync_types::Address;
@@ -11,6 +13,8 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Time-weighted average price over the last hours from the Uniswap subgraph.
+    UniswapTwap,
 }
 
 /// Configuration for the fee ticker.
@@ -18,10 +22,23 @@ pub enum TokenPriceSource {
 pub struct TickerConfig {
     /// Indicator of the API to be used for getting token prices.
     pub token_price_source: TokenPriceSource,
+    /// APIs used for getting token prices in the order of priority, the next API is used
+    /// if the previous one is unavailable or its price is stale.
+    /// Only `token_price_source` is used if the list is empty.
+    #[serde(default)]
+    pub token_price_sources: Vec<TokenPriceSource>,
     /// URL of CoinMarketCap API. Can be set to the mock server for local development.
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
+    /// URL of the Uniswap subgraph the time-weighted average prices are calculated from.
+    #[serde(default)]
+    pub uniswap_twap_url: String,
+    /// Prices updated more than this amount of seconds ago are considered stale and not used.
+    pub max_price_age: u64,
+    /// Deviation of the prices from the different APIs in percent, after which the prices
+    /// are reported as inconsistent.
+    pub max_price_deviation_percent: f64,
     /// Coefficient for scaling all fees in percent.
     pub scale_fee_percent: u32,
     /// Coefficient for the fee price for fast withdrawal requests.
@@ -40,6 +57,13 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Interval of the gas price updates in seconds.
+    pub gas_price_update_interval: u64,
+    /// Weight of the latest gas price in the exponential moving average of the gas prices
+    /// the fees are calculated with, from 0 (the price never changes) to 1 (no smoothing).
+    pub gas_price_smoothing_factor: f64,
+    /// Maximum change of the gas price used in fees per update, in percent.
+    pub max_gas_price_change_percent: f64,
 }
 
 impl TickerConfig {
@@ -53,11 +77,40 @@ impl TickerConfig {
 
     /// Returns the token price source type and the corresponding API URL.
     pub fn price_source(&self) -> (TokenPriceSource, String) {
-        let url = match self.token_price_source {
+        (
+            self.token_price_source,
+            self.price_source_url(self.token_price_source),
+        )
+    }
+
+    /// Returns the token price source types in the order of priority along with the corresponding API URLs.
+    pub fn price_sources(&self) -> Vec<(TokenPriceSource, String)> {
+        if self.token_price_sources.is_empty() {
+            return vec![self.price_source()];
+        }
+
+        self.token_price_sources
+            .iter()
+            .map(|&source| (source, self.price_source_url(source)))
+            .collect()
+    }
+
+    /// Converts `self.gas_price_update_interval` into `Duration`.
+    pub fn gas_price_update_interval(&self) -> Duration {
+        Duration::from_secs(self.gas_price_update_interval)
+    }
+
+    /// Converts `self.max_price_age` into `Duration`.
+    pub fn max_price_age(&self) -> Duration {
+        Duration::from_secs(self.max_price_age)
+    }
+
+    fn price_source_url(&self, source: TokenPriceSource) -> String {
+        match source {
             TokenPriceSource::CoinGecko => self.coingecko_base_url.clone(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.clone(),
-        };
-        (self.token_price_source, url)
+            TokenPriceSource::UniswapTwap => self.uniswap_twap_url.clone(),
+        }
     }
 }
 
@@ -69,8 +122,16 @@ mod tests {
     fn expected_config() -> TickerConfig {
         TickerConfig {
             token_price_source: TokenPriceSource::CoinGecko,
+            token_price_sources: vec![
+                TokenPriceSource::CoinGecko,
+                TokenPriceSource::CoinMarketCap,
+                TokenPriceSource::UniswapTwap,
+            ],
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
+            uniswap_twap_url: "http://127.0.0.1:9975/graphql".into(),
+            max_price_age: 3600,
+            max_price_deviation_percent: 10.0,
             scale_fee_percent: 100,
             fast_processing_coeff: 10.0f64,
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
@@ -80,6 +141,9 @@ mod tests {
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            gas_price_update_interval: 15,
+            gas_price_smoothing_factor: 0.2,
+            max_gas_price_change_percent: 5.0,
         }
     }
 
@@ -87,8 +151,12 @@ mod tests {
     fn from_env() {
         let config = r#"
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
+FEE_TICKER_TOKEN_PRICE_SOURCES="CoinGecko,CoinMarketCap,UniswapTwap"
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
+FEE_TICKER_UNISWAP_TWAP_URL="http://127.0.0.1:9975/graphql"
+FEE_TICKER_MAX_PRICE_AGE="3600"
+FEE_TICKER_MAX_PRICE_DEVIATION_PERCENT="10"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
 FEE_TICKER_UNISWAP_URL=http://127.0.0.1:9975/graphql
 FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
@@ -99,6 +167,9 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_GAS_PRICE_UPDATE_INTERVAL=15
+FEE_TICKER_GAS_PRICE_SMOOTHING_FACTOR=0.2
+FEE_TICKER_MAX_GAS_PRICE_CHANGE_PERCENT=5
         "#;
         set_env(config);
 
@@ -128,5 +199,23 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        assert_eq!(
+            config.price_sources(),
+            vec![
+                (TokenPriceSource::CoinGecko, COINGECKO_URL.into()),
+                (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into()),
+                (
+                    TokenPriceSource::UniswapTwap,
+                    config.uniswap_twap_url.clone()
+                ),
+            ]
+        );
+        config.token_price_sources = Vec::new();
+        assert_eq!(
+            config.price_sources(),
+            vec![(TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())]
+        );
+        assert_eq!(config.max_price_age(), Duration::from_secs(3600));
     }
 }
@@ -1,11 +1,16 @@
 pub use crate::configs::{
     ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevLiquidityTokenWatcherConfig,
-    ETHClientConfig, ETHSenderConfig, ETHWatchConfig, EventListenerConfig,
+    ETHClientConfig, ETHSenderConfig, ETHWatchConfig, EventListenerConfig, EventPublisherConfig,
     ForcedExitRequestsConfig, GatewayWatcherConfig, MiscConfig, ProverConfig, TickerConfig,
     TokenHandlerConfig,
 };
 
+pub use crate::config_file::{ConfigFile, ConfigFileError, CONFIG_FILE_ENV_VAR};
+pub use crate::runtime_config::RuntimeConfig;
+
+pub mod config_file;
 pub mod configs;
+pub mod runtime_config;
 pub mod test_config;
 
 #[derive(Debug, Clone)]
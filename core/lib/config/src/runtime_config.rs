@@ -0,0 +1,200 @@
+//! Parameters which can be changed without the restart of the server.
+//!
+//! The server initializes the runtime config at the startup and reloads it from the config file
+//! on `SIGHUP` or via the admin API. Components read the parameters on every use, so the new values
+//! take effect right away, without dropping the in-flight requests and the mempool contents.
+//! If the runtime config is not initialized, components use the values they were created with.
+
+// Built-in uses
+use std::{collections::BTreeMap, env, fmt::Display, str::FromStr, sync::RwLock};
+// External uses
+use once_cell::sync::Lazy;
+use serde::Serialize;
+// Local uses
+use crate::{
+    config_file::{ConfigFile, ConfigFileError},
+    configs::api::CommonApiConfig,
+    ChainConfig, ETHSenderConfig, TickerConfig,
+};
+
+static RUNTIME_CONFIG: Lazy<RwLock<Option<RuntimeConfig>>> = Lazy::new(RwLock::default);
+
+/// Options of the config file that are applied on the reload, the other ones require the restart.
+pub const RELOADABLE_OPTIONS: &[&str] = &[
+    "FEE_TICKER_SCALE_FEE_PERCENT",
+    "API_COMMON_RATE_LIMIT_RPS",
+    "API_COMMON_RATE_LIMIT_BURST",
+    "CHAIN_STATE_KEEPER_MEMPOOL_MAX_TXS",
+    "CHAIN_STATE_KEEPER_MEMPOOL_MAX_ACCOUNT_TXS",
+    "CHAIN_STATE_KEEPER_MAX_NONCE_GAP",
+    "ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR",
+    "ETH_SENDER_GAS_PRICE_LIMIT_HARD_CAP",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RuntimeConfig {
+    /// Coefficient for scaling all fees in percent.
+    pub scale_fee_percent: u32,
+    /// Rate limit of the anonymous API clients, they are not rate limited if it's not set.
+    pub rate_limit_rps: Option<u32>,
+    pub rate_limit_burst: u32,
+    /// Maximum number of the pending transactions in the mempool.
+    pub mempool_max_txs: u32,
+    /// Maximum number of the pending transactions of a single account in the mempool.
+    pub mempool_max_account_txs: u32,
+    /// Maximum difference between the nonce of the incoming transaction and the committed nonce.
+    pub max_nonce_gap: u32,
+    /// Scale factor of the gas price limit of the Ethereum transactions.
+    pub gas_price_scale_factor: f64,
+    /// Gas price in wei the gas price limit of the Ethereum transactions never exceeds.
+    pub gas_price_hard_cap: Option<u64>,
+}
+
+impl RuntimeConfig {
+    pub fn from_env() -> Self {
+        let api = CommonApiConfig::from_env();
+        let state_keeper = ChainConfig::from_env().state_keeper;
+        let gas_price_limit = ETHSenderConfig::from_env().gas_price_limit;
+        Self {
+            scale_fee_percent: TickerConfig::from_env().scale_fee_percent,
+            rate_limit_rps: api.rate_limit_rps,
+            rate_limit_burst: api.rate_limit_burst,
+            mempool_max_txs: state_keeper.mempool_max_txs,
+            mempool_max_account_txs: state_keeper.mempool_max_account_txs,
+            max_nonce_gap: state_keeper.max_nonce_gap,
+            gas_price_scale_factor: gas_price_limit.scale_factor,
+            gas_price_hard_cap: gas_price_limit.hard_cap,
+        }
+    }
+
+    /// Makes the config current, so the components start using its values.
+    pub fn init(self) {
+        *RUNTIME_CONFIG.write().unwrap() = Some(self);
+    }
+
+    /// Returns the current runtime config, `None` if it's not initialized.
+    pub fn current() -> Option<Self> {
+        *RUNTIME_CONFIG.read().unwrap()
+    }
+
+    /// Reloads the config file set in the `ZKSYNC_CONFIG_FILE` variable, see `RuntimeConfig::reload`.
+    pub fn reload_from_file() -> Result<Self, ConfigFileError> {
+        let file = ConfigFile::from_env()?.ok_or(ConfigFileError::NotSet)?;
+        Self::reload(&file)
+    }
+
+    /// Applies the reloadable options of the config file to the current runtime config.
+    /// The options of the file override the environment variables, the options missing
+    /// in the file keep their current values.
+    ///
+    /// Nothing is changed if any of the options is invalid.
+    pub fn reload(file: &ConfigFile) -> Result<Self, ConfigFileError> {
+        let mut current = RUNTIME_CONFIG.write().unwrap();
+        let mut config = current.ok_or(ConfigFileError::RuntimeConfigNotInitialized)?;
+        config.apply_options(file.variables())?;
+
+        // Gas price limit parameters are read from the environment on every use by `eth_sender`.
+        for (name, value) in file.variables() {
+            if RELOADABLE_OPTIONS.contains(&name.as_str()) {
+                env::set_var(name, value);
+            }
+        }
+        *current = Some(config);
+        Ok(config)
+    }
+
+    fn apply_options(
+        &mut self,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<(), ConfigFileError> {
+        for (name, value) in variables {
+            match name.as_str() {
+                "FEE_TICKER_SCALE_FEE_PERCENT" => self.scale_fee_percent = parse(name, value)?,
+                "API_COMMON_RATE_LIMIT_RPS" => self.rate_limit_rps = Some(parse(name, value)?),
+                "API_COMMON_RATE_LIMIT_BURST" => self.rate_limit_burst = parse(name, value)?,
+                "CHAIN_STATE_KEEPER_MEMPOOL_MAX_TXS" => self.mempool_max_txs = parse(name, value)?,
+                "CHAIN_STATE_KEEPER_MEMPOOL_MAX_ACCOUNT_TXS" => {
+                    self.mempool_max_account_txs = parse(name, value)?
+                }
+                "CHAIN_STATE_KEEPER_MAX_NONCE_GAP" => self.max_nonce_gap = parse(name, value)?,
+                "ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR" => {
+                    self.gas_price_scale_factor = parse(name, value)?
+                }
+                "ETH_SENDER_GAS_PRICE_LIMIT_HARD_CAP" => {
+                    self.gas_price_hard_cap = Some(parse(name, value)?)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse<T>(name: &str, value: &str) -> Result<T, ConfigFileError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|err: T::Err| ConfigFileError::InvalidOption(name.to_owned(), err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values of the reloaded environment variables are the same as in the other tests of the crate,
+    // since the tests are run concurrently.
+    #[test]
+    fn reload() {
+        let config = RuntimeConfig {
+            scale_fee_percent: 100,
+            rate_limit_rps: None,
+            rate_limit_burst: 40,
+            mempool_max_txs: 100_000,
+            mempool_max_account_txs: 100,
+            max_nonce_gap: 16,
+            gas_price_scale_factor: 1.0,
+            gas_price_hard_cap: None,
+        };
+        let file = ConfigFile::from_toml(
+            r#"
+            # Requires the restart.
+            [fee_ticker]
+            max_price_age = 60
+
+            [api.common]
+            rate_limit_rps = 20
+
+            [eth_sender.gas_price_limit]
+            hard_cap = 1000000000000
+            "#,
+        )
+        .unwrap();
+        let invalid_file =
+            ConfigFile::from_toml("[chain.state_keeper]\nmax_nonce_gap = -1").unwrap();
+
+        config.init();
+        assert!(matches!(
+            RuntimeConfig::reload(&invalid_file),
+            Err(ConfigFileError::InvalidOption(name, _)) if name == "CHAIN_STATE_KEEPER_MAX_NONCE_GAP"
+        ));
+        assert_eq!(RuntimeConfig::current(), Some(config));
+
+        let reloaded = RuntimeConfig::reload(&file).unwrap();
+        assert_eq!(
+            reloaded,
+            RuntimeConfig {
+                rate_limit_rps: Some(20),
+                gas_price_hard_cap: Some(1_000_000_000_000),
+                ..config
+            }
+        );
+        assert_eq!(RuntimeConfig::current(), Some(reloaded));
+        assert_eq!(
+            env::var("ETH_SENDER_GAS_PRICE_LIMIT_HARD_CAP").unwrap(),
+            "1000000000000"
+        );
+    }
+}
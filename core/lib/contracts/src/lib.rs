@@ -13,12 +13,22 @@ const GOVERNANCE_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/Governance.sol/Governance.json";
 const IERC20_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/IERC20.sol/IERC20.json";
+const IERC20_METADATA_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IERC20Metadata.sol/IERC20Metadata.json";
 const IEIP1271_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/IEIP1271.sol/IEIP1271.json";
 const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/UpgradeGatekeeper.sol/UpgradeGatekeeper.json";
 const FORCED_EXIT_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/ForcedExit.sol/ForcedExit.json";
+const MULTICALL_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/Multicall.sol/Multicall.json";
+const ENS_REGISTRY_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/IENS.sol/IENS.json";
+const ENS_RESOLVER_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/IENS.sol/IENSResolver.json";
+const PENDING_BALANCE_WITHDRAWER_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/PendingBalanceWithdrawer.sol/PendingBalanceWithdrawer.json";
 
 fn read_file_to_json_value(path: &str) -> io::Result<serde_json::Value> {
     let zksync_home = std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| ".".into());
@@ -91,6 +101,15 @@ pub fn erc20_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("erc20 contract abi")
 }
 
+pub fn erc20_metadata_contract() -> Contract {
+    let abi_string = read_file_to_json_value(IERC20_METADATA_CONTRACT_FILE)
+        .expect("couldn't read IERC20_METADATA_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from IERC20_METADATA_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("erc20 metadata contract abi")
+}
+
 pub fn eip1271_contract() -> Contract {
     let abi_string = read_file_to_json_value(IEIP1271_CONTRACT_FILE)
         .expect("couldn't read IEIP1271_CONTRACT_FILE")
@@ -116,3 +135,39 @@ pub fn forced_exit_contract() -> Contract {
         .to_string();
     Contract::load(abi_string.as_bytes()).expect("forced_exit contract abi")
 }
+
+pub fn multicall_contract() -> Contract {
+    let abi_string = read_file_to_json_value(MULTICALL_CONTRACT_FILE)
+        .expect("couldn't read MULTICALL_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from MULTICALL_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("multicall contract abi")
+}
+
+pub fn ens_registry_contract() -> Contract {
+    let abi_string = read_file_to_json_value(ENS_REGISTRY_CONTRACT_FILE)
+        .expect("couldn't read ENS_REGISTRY_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from ENS_REGISTRY_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("ens registry contract abi")
+}
+
+pub fn ens_resolver_contract() -> Contract {
+    let abi_string = read_file_to_json_value(ENS_RESOLVER_CONTRACT_FILE)
+        .expect("couldn't read ENS_RESOLVER_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from ENS_RESOLVER_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("ens resolver contract abi")
+}
+
+pub fn pending_balance_withdrawer_contract() -> Contract {
+    let abi_string = read_file_to_json_value(PENDING_BALANCE_WITHDRAWER_CONTRACT_FILE)
+        .expect("couldn't read PENDING_BALANCE_WITHDRAWER_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from PENDING_BALANCE_WITHDRAWER_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("pending_balance_withdrawer contract abi")
+}
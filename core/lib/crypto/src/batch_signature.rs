@@ -0,0 +1,180 @@
+//! Batch verification of the MuSig Rescue signatures.
+//!
+//! Instead of checking `S * G = R + c * PK` for every signature separately, the random
+//! linear combination of all the equations is checked at once, so the multiplication of
+//! the generator is done once per batch. The batch is valid only if all of its signatures
+//! are valid, except with the negligible probability.
+
+// External deps
+use rayon::prelude::*;
+// Local deps
+use crate::{
+    franklin_crypto::{
+        bellman::pairing::ff::{BitIterator, Field, PrimeField},
+        circuit::multipack,
+        eddsa::Signature,
+        jubjub::{edwards::Point, FixedGenerators, Unknown},
+        rescue::StatefulRescue,
+    },
+    params::{FR_BIT_WIDTH_PADDED, JUBJUB_PARAMS, RESCUE_PARAMS},
+    primitives::BitConvert,
+    rand::{thread_rng, Rng},
+    Engine, Fs, PublicKey,
+};
+
+/// Signature to verify in the batch: the public key of the signer, the signature and the signed message.
+pub type SignatureBatchItem<'a> = (&'a PublicKey, &'a Signature<Engine>, &'a [u8]);
+
+/// Computes the Fiat-Shamir challenge `c` of the MuSig Rescue signature.
+///
+/// The hash input is the x coordinate of the public key, the x coordinate of the signature `R`
+/// and the message, each padded to 256 bits, the same way the circuit computes it.
+pub fn musig_rescue_challenge(pub_key: &PublicKey, r: &Point<Engine, Unknown>, msg: &[u8]) -> Fs {
+    let msg_len = FR_BIT_WIDTH_PADDED / 8;
+    assert!(
+        msg.len() <= msg_len,
+        "Signed message is too long: {}/{} bytes",
+        msg.len(),
+        msg_len
+    );
+    let mut msg = msg.to_vec();
+    msg.resize(msg_len, 0);
+
+    let mut hash_input = x_coordinate_bits(&pub_key.0);
+    hash_input.extend(x_coordinate_bits(r));
+    hash_input.extend(BitConvert::from_be_bytes(&msg));
+    let hash_input = multipack::compute_multipacking::<Engine>(&hash_input);
+
+    let mut sponge = StatefulRescue::<Engine>::new(&RESCUE_PARAMS);
+    sponge.specialize(hash_input.len() as u8);
+    sponge.absorb(&hash_input);
+
+    // The challenge is built from the lowest bits of the two words squeezed out of the sponge.
+    let take_bits = (Fs::CAPACITY / 2) as usize;
+    let mut challenge_bits = Vec::with_capacity(2 * take_bits);
+    for _ in 0..2 {
+        let word = sponge.squeeze_out_single();
+        let mut word_bits: Vec<bool> = BitIterator::new(word.into_repr()).collect();
+        word_bits.reverse();
+        challenge_bits.extend_from_slice(&word_bits[..take_bits]);
+    }
+
+    let mut challenge = Fs::zero();
+    for bit in challenge_bits.into_iter().rev() {
+        challenge.double();
+        if bit {
+            challenge.add_assign(&Fs::one());
+        }
+    }
+    challenge
+}
+
+/// Verifies all the MuSig Rescue signatures at once, returns `true` only if all of them are valid.
+///
+/// Messages are expected to be hashed the same way as for `verify_musig_rescue`. If the batch is
+/// invalid, signatures have to be verified one by one to find out which of them are invalid.
+pub fn verify_musig_rescue_batch(signatures: &[SignatureBatchItem<'_>]) -> bool {
+    let params = &*JUBJUB_PARAMS;
+    let (points_sum, s_sum) = signatures
+        .par_iter()
+        .map(|(pub_key, signature, msg)| {
+            // Random coefficient, so the invalid signatures can't cancel each other out.
+            let z: Fs = thread_rng().gen();
+            let mut zc = musig_rescue_challenge(pub_key, &signature.r, msg);
+            zc.mul_assign(&z);
+            let mut zs = signature.s;
+            zs.mul_assign(&z);
+
+            let point = signature
+                .r
+                .mul(z, params)
+                .add(&pub_key.0.mul(zc, params), params);
+            (point, zs)
+        })
+        .reduce(
+            || (Point::zero(), Fs::zero()),
+            |(lhs_point, mut lhs_s), (rhs_point, rhs_s)| {
+                lhs_s.add_assign(&rhs_s);
+                (lhs_point.add(&rhs_point, params), lhs_s)
+            },
+        );
+
+    // sum(z * R) + sum(z * c * PK) - sum(z * S) * G = 0
+    let s_g: Point<Engine, Unknown> = params
+        .generator(FixedGenerators::SpendingKeyGenerator)
+        .mul(s_sum, params)
+        .into();
+    points_sum
+        .add(&s_g.negate(), params)
+        .mul_by_cofactor(params)
+        == Point::zero()
+}
+
+/// Bits of the x coordinate of the point as they're hashed: little-endian bytes, each byte
+/// starting with the most significant bit.
+fn x_coordinate_bits<Subgroup>(point: &Point<Engine, Subgroup>) -> Vec<bool> {
+    let (x, _) = point.into_xy();
+    let mut bits: Vec<bool> = BitIterator::new(x.into_repr()).collect();
+    bits.reverse();
+    bits.resize(FR_BIT_WIDTH_PADDED, false);
+    let bytes = BitConvert::into_bytes(bits);
+    BitConvert::from_be_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        franklin_crypto::eddsa::{PrivateKey, Seed},
+        public_key_from_private,
+        rand::{Rand, SeedableRng, XorShiftRng},
+    };
+
+    fn sign(rng: &mut XorShiftRng, msg: &[u8]) -> (PublicKey, Signature<Engine>) {
+        let private_key = PrivateKey::<Engine>(Fs::rand(rng));
+        let seed = Seed::deterministic_seed(&private_key, msg);
+        let signature = private_key.musig_rescue_sign(
+            msg,
+            &seed,
+            FixedGenerators::SpendingKeyGenerator,
+            &RESCUE_PARAMS,
+            &JUBJUB_PARAMS,
+        );
+        (public_key_from_private(&private_key), signature)
+    }
+
+    /// Checks that the batch is valid only if all of its signatures are valid.
+    #[test]
+    fn batch_verification() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let messages: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i; 32]).collect();
+        let signatures: Vec<_> = messages.iter().map(|msg| sign(&mut rng, msg)).collect();
+
+        for ((pub_key, signature), msg) in signatures.iter().zip(&messages) {
+            assert!(pub_key.verify_musig_rescue(
+                msg,
+                signature,
+                FixedGenerators::SpendingKeyGenerator,
+                &RESCUE_PARAMS,
+                &JUBJUB_PARAMS,
+            ));
+        }
+        let batch: Vec<SignatureBatchItem<'_>> = signatures
+            .iter()
+            .zip(&messages)
+            .map(|((pub_key, signature), msg)| (pub_key, signature, msg.as_slice()))
+            .collect();
+        assert!(verify_musig_rescue_batch(&batch));
+        assert!(verify_musig_rescue_batch(&[]));
+
+        // The signature of the other message.
+        let mut invalid_batch = batch.clone();
+        invalid_batch[3].2 = messages[4].as_slice();
+        assert!(!verify_musig_rescue_batch(&invalid_batch));
+
+        // The signature of the other signer.
+        let mut invalid_batch = batch;
+        invalid_batch[5].0 = &signatures[6].0;
+        assert!(!verify_musig_rescue_batch(&invalid_batch));
+    }
+}
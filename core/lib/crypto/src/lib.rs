@@ -19,6 +19,7 @@ mod crypto_exports {
 
 pub use crypto_exports::*;
 
+pub mod batch_signature;
 pub mod circuit;
 pub mod convert;
 pub mod error;
@@ -1,5 +1,7 @@
 pub mod hasher;
 pub mod parallel_smt;
+#[cfg(feature = "persistent-tree-cache")]
+pub mod persistent_cache;
 pub mod rescue_hasher;
 #[cfg(test)]
 mod tests;
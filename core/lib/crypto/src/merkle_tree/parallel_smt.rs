@@ -728,9 +728,11 @@ impl SparseMerkleTreeSerializableCacheBN256 {
     }
 
     pub fn decode_bincode(data: &[u8]) -> Self {
-        bincode::decode_from_slice(data, bincode::config::standard())
-            .expect("Unable to decode Merkle Tree cache")
-            .0
+        Self::try_decode_bincode(data).expect("Unable to decode Merkle Tree cache")
+    }
+
+    pub fn try_decode_bincode(data: &[u8]) -> Result<Self, bincode::error::DecodeError> {
+        bincode::decode_from_slice(data, bincode::config::standard()).map(|(cache, _)| cache)
     }
 }
 
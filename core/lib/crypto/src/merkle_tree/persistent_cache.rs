@@ -0,0 +1,234 @@
+//! Persistent cache of the sparse Merkle tree, so the tree isn't rebuilt from the database on every restart.
+//!
+//! The cache is a RocksDB database holding the tree items along with the tree internals
+//! (see `SparseMerkleTree::get_internals`) as of some block. Once the tree is loaded from the cache,
+//! only the changes made after the cached block have to be applied to it.
+//!
+//! Every write is atomic, so the cache always holds the complete tree of a single block.
+//! The cache isn't aware of the block reverts: the user has to check the root hash of the loaded
+//! tree against the root hash of the cached block.
+
+// Built-in deps
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+// External deps
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+// Local deps
+use super::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
+
+const BLOCK_KEY: &[u8] = b"block";
+const INTERNALS_KEY: &[u8] = b"internals";
+/// Items are keyed by the prefix followed by the big-endian item index, so they're iterated in order.
+const ITEM_PREFIX: &[u8] = b"item:";
+/// Exclusive upper bound of the item keys.
+const ITEM_PREFIX_END: &[u8] = b"item;";
+
+#[derive(Debug, Error)]
+pub enum PersistentCacheError {
+    #[error("Tree cache storage error: {0}")]
+    Storage(#[from] rocksdb::Error),
+    #[error("Tree cache is corrupted: {0}")]
+    Corrupted(String),
+}
+
+/// Tree loaded from the cache.
+#[derive(Debug)]
+pub struct CachedTree<T> {
+    /// Number of the block the tree corresponds to.
+    pub block: u32,
+    /// Tree items along with their indices.
+    pub items: Vec<(u32, T)>,
+    pub internals: SparseMerkleTreeSerializableCacheBN256,
+}
+
+pub struct PersistentTreeCache {
+    path: PathBuf,
+    db: DB,
+}
+
+impl fmt::Debug for PersistentTreeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentTreeCache")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl PersistentTreeCache {
+    /// Opens the cache stored in the directory, the empty cache is created if there is none.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PersistentCacheError> {
+        let path = path.as_ref().to_owned();
+        let db = DB::open_default(&path)?;
+        Ok(Self { path, db })
+    }
+
+    /// Loads the cached tree, `None` if nothing is cached yet.
+    pub fn load<T: DeserializeOwned>(&self) -> Result<Option<CachedTree<T>>, PersistentCacheError> {
+        let block = match self.db.get(BLOCK_KEY)? {
+            Some(block) => decode_index(&block)?,
+            None => return Ok(None),
+        };
+        let internals = self
+            .db
+            .get(INTERNALS_KEY)?
+            .ok_or_else(|| PersistentCacheError::Corrupted("tree internals are missing".into()))?;
+        let internals = SparseMerkleTreeSerializableCacheBN256::try_decode_bincode(&internals)
+            .map_err(|err| PersistentCacheError::Corrupted(err.to_string()))?;
+
+        let mut items = Vec::new();
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(ITEM_PREFIX, Direction::Forward));
+        for (key, value) in iter {
+            if !key.starts_with(ITEM_PREFIX) {
+                break;
+            }
+            let index = decode_index(&key[ITEM_PREFIX.len()..])?;
+            let item = serde_json::from_slice(&value).map_err(|err| {
+                PersistentCacheError::Corrupted(format!("item {}: {}", index, err))
+            })?;
+            items.push((index, item));
+        }
+
+        Ok(Some(CachedTree {
+            block,
+            items,
+            internals,
+        }))
+    }
+
+    /// Replaces the cached tree with the tree of the block.
+    pub fn store<'a, T: Serialize + 'a>(
+        &self,
+        block: u32,
+        items: impl IntoIterator<Item = (u32, &'a T)>,
+        internals: &SparseMerkleTreeSerializableCacheBN256,
+    ) -> Result<(), PersistentCacheError> {
+        let mut batch = WriteBatch::default();
+        batch.delete_range(ITEM_PREFIX, ITEM_PREFIX_END);
+        for (index, item) in items {
+            batch.put(item_key(index), encode_item(item));
+        }
+        self.write(batch, block, internals)
+    }
+
+    /// Updates the cached tree to the tree of the block. Only the items changed since the cached block
+    /// are written, `None` stands for the item removed from the tree.
+    pub fn store_updates<'a, T: Serialize + 'a>(
+        &self,
+        block: u32,
+        updates: impl IntoIterator<Item = (u32, Option<&'a T>)>,
+        internals: &SparseMerkleTreeSerializableCacheBN256,
+    ) -> Result<(), PersistentCacheError> {
+        let mut batch = WriteBatch::default();
+        for (index, item) in updates {
+            match item {
+                Some(item) => batch.put(item_key(index), encode_item(item)),
+                None => batch.delete(item_key(index)),
+            }
+        }
+        self.write(batch, block, internals)
+    }
+
+    fn write(
+        &self,
+        mut batch: WriteBatch,
+        block: u32,
+        internals: &SparseMerkleTreeSerializableCacheBN256,
+    ) -> Result<(), PersistentCacheError> {
+        batch.put(INTERNALS_KEY, internals.encode_bincode());
+        batch.put(BLOCK_KEY, block.to_be_bytes());
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
+
+fn item_key(index: u32) -> Vec<u8> {
+    [ITEM_PREFIX, &index.to_be_bytes()].concat()
+}
+
+fn encode_item<T: Serialize>(item: &T) -> Vec<u8> {
+    serde_json::to_vec(item).expect("Unable to encode Merkle Tree item")
+}
+
+fn decode_index(bytes: &[u8]) -> Result<u32, PersistentCacheError> {
+    let mut index = [0u8; 4];
+    if bytes.len() != index.len() {
+        return Err(PersistentCacheError::Corrupted(format!(
+            "invalid index length {}",
+            bytes.len()
+        )));
+    }
+    index.copy_from_slice(bytes);
+    Ok(u32::from_be_bytes(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        merkle_tree::{parallel_smt::SparseMerkleTree, RescueHasher},
+        Engine, Fr,
+    };
+
+    type Tree = SparseMerkleTree<u64, Fr, RescueHasher<Engine>>;
+
+    fn restore(cached: CachedTree<u64>) -> Tree {
+        let mut tree = Tree::new(8);
+        for (index, item) in cached.items {
+            tree.insert(index, item);
+        }
+        tree.set_internals(cached.internals);
+        tree
+    }
+
+    #[test]
+    fn tree_is_restored_from_cache() {
+        let path = std::env::temp_dir().join(format!("tree_cache_{}", std::process::id()));
+        let cache = PersistentTreeCache::open(&path).unwrap();
+        assert!(cache.load::<u64>().unwrap().is_none());
+
+        let mut tree = Tree::new(8);
+        for index in 0..10 {
+            tree.insert(index, index as u64 * 100);
+        }
+        tree.root_hash();
+        cache
+            .store(
+                1,
+                tree.items.iter().map(|(index, item)| (*index as u32, item)),
+                &tree.get_internals(),
+            )
+            .unwrap();
+
+        // Only the changed items are written for the next block.
+        tree.insert(3, 42);
+        tree.remove(5);
+        tree.root_hash();
+        cache
+            .store_updates(2, vec![(3, tree.get(3)), (5, None)], &tree.get_internals())
+            .unwrap();
+
+        let cached = cache.load::<u64>().unwrap().unwrap();
+        assert_eq!(cached.block, 2);
+        assert_eq!(cached.items.len(), 9);
+        assert_eq!(restore(cached).root_hash(), tree.root_hash());
+
+        // The full store replaces all the previous items.
+        let mut tree = Tree::new(8);
+        tree.insert(7, 1);
+        cache
+            .store(3, vec![(7, &1u64)], &tree.get_internals())
+            .unwrap();
+        let cached = cache.load::<u64>().unwrap().unwrap();
+        assert_eq!(cached.items, vec![(7, 1)]);
+        assert_eq!(restore(cached).root_hash(), tree.root_hash());
+
+        drop(cache);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -153,6 +153,25 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    /// Returns the base fee per gas of the latest block, which is only set after the London fork.
+    pub async fn get_base_fee(&self) -> Result<U256, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let block = self
+            .inner
+            .web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Latest))
+            .await?
+            .ok_or_else(|| anyhow::format_err!("Latest block is not found"))?;
+        let base_fee = block.base_fee_per_gas.ok_or_else(|| {
+            anyhow::format_err!("Latest block has no base fee, EIP-1559 is not activated")
+        })?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_base_fee", start.elapsed());
+        Ok(base_fee)
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -171,8 +190,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         #[cfg(feature = "with-metrics")]
         let start = Instant::now();
 
-        // fetch current gas_price
-        let gas_price = match options.gas_price {
+        // EIP-1559 transactions are priced by the max fee per gas instead of the gas price.
+        let gas_price = match options.max_fee_per_gas.or(options.gas_price) {
             Some(gas_price) => gas_price,
             None => self.get_gas_price().await?,
         };
@@ -200,16 +219,16 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         // form and sign tx
         let tx = RawTransaction {
             chain_id: self.inner.chain_id,
-            transaction_type: None,
+            transaction_type: options.transaction_type,
             access_list: None,
-            max_fee_per_gas: Default::default(),
+            max_fee_per_gas: options.max_fee_per_gas,
             nonce,
             to: Some(contract_addr),
             value: options.value.unwrap_or_default(),
             gas_price,
             gas,
             data,
-            max_priority_fee_per_gas: Default::default(),
+            max_priority_fee_per_gas: options.max_priority_fee_per_gas,
         };
 
         let signed_tx = self.inner.eth_signer.sign_transaction(tx).await?;
@@ -443,13 +462,6 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
 
-                // Set the receipt only for failures.
-                let receipt = if success {
-                    None
-                } else {
-                    Some(receipt.unwrap())
-                };
-
                 Ok(Some(ExecutedTxStatus {
                     confirmations,
                     success,
@@ -515,4 +527,20 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         metrics::histogram!("eth_client.direct.get_tx", start.elapsed());
         Ok(tx)
     }
+
+    /// Returns the hash of the block with the given number,
+    /// or `None` if the node doesn't have such a block yet.
+    pub async fn block_hash(&self, block_number: u64) -> Result<Option<H256>, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let block = self
+            .inner
+            .web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Number(block_number.into())))
+            .await?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.block_hash", start.elapsed());
+        Ok(block.and_then(|block| block.hash))
+    }
 }
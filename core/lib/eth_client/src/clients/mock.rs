@@ -1,13 +1,15 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
 
 use anyhow::Error;
-use ethabi::{Address, Contract};
+use ethabi::{Address, Contract, Token};
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
 use web3::transports::Http;
-use web3::types::{BlockId, Filter, Log, Transaction, U64};
+use web3::types::{BlockId, BlockNumber, Filter, Log, Transaction, U64};
 
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
@@ -20,8 +22,15 @@ use crate::{
 struct MockEthereumInner {
     block_number: u64,
     gas_price: U256,
+    base_fee: U256,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    call_results: Arc<RwLock<HashMap<String, Vec<Token>>>>,
+    block_call_results: Arc<RwLock<HashMap<(String, u64), Vec<Token>>>>,
+    call_delay: Mutex<Option<Duration>>,
+    unreachable: AtomicBool,
+    panicking: AtomicBool,
+    failing_calls: AtomicU32,
 }
 
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
@@ -35,8 +44,15 @@ impl Default for MockEthereumInner {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            base_fee: 50.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            call_results: Default::default(),
+            block_call_results: Default::default(),
+            call_delay: Mutex::new(None),
+            unreachable: AtomicBool::new(false),
+            panicking: AtomicBool::new(false),
+            failing_calls: AtomicU32::new(0),
         }
     }
 }
@@ -98,6 +114,100 @@ impl MockEthereum {
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
+
+    /// Sets the tokens returned by the contract call of the function with the provided name.
+    pub async fn add_call_result(&mut self, func: &str, result: Vec<Token>) {
+        self.inner
+            .call_results
+            .write()
+            .await
+            .insert(func.to_string(), result);
+    }
+
+    /// Sets the result of the contract call made at the given block. Calls made at
+    /// other blocks get the result set by `add_call_result`.
+    pub async fn add_call_result_at_block(&mut self, func: &str, block: u64, result: Vec<Token>) {
+        self.inner
+            .block_call_results
+            .write()
+            .await
+            .insert((func.to_string(), block), result);
+    }
+
+    /// Sets the artificial latency of the contract calls. Affects the clones of the client as well.
+    pub fn set_call_delay(&self, delay: Duration) {
+        *self.inner.call_delay.lock().unwrap() = Some(delay);
+    }
+
+    /// Makes the block number queries and the contract calls fail, as if the node was unreachable.
+    pub fn set_unreachable(&self) {
+        self.inner.unreachable.store(true, Ordering::SeqCst);
+    }
+
+    /// Makes the contract calls panic, e.g. to check how the callers survive their own bugs.
+    pub fn set_panicking(&self) {
+        self.inner.panicking.store(true, Ordering::SeqCst);
+    }
+
+    /// Makes the next `count` contract calls fail, as if the node was unreachable for a while.
+    pub fn fail_next_calls(&self, count: u32) {
+        self.inner.failing_calls.store(count, Ordering::SeqCst);
+    }
+
+    /// Error of the call which didn't reach the node, the same as the one of the real transport.
+    fn unreachable_error() -> anyhow::Error {
+        web3::contract::Error::Api(web3::Error::Unreachable).into()
+    }
+
+    fn ensure_reachable(&self) -> Result<(), anyhow::Error> {
+        match self.inner.unreachable.load(Ordering::SeqCst) {
+            true => Err(Self::unreachable_error()),
+            false => Ok(()),
+        }
+    }
+
+    async fn mock_call<R: Detokenize>(
+        &self,
+        func: &str,
+        block: Option<BlockId>,
+    ) -> Result<R, anyhow::Error> {
+        let call_delay = *self.inner.call_delay.lock().unwrap();
+        if let Some(delay) = call_delay {
+            tokio::time::sleep(delay).await;
+        }
+        assert!(
+            !self.inner.panicking.load(Ordering::SeqCst),
+            "Mock Ethereum contract call panicked"
+        );
+        self.ensure_reachable()?;
+        let failing_call = self
+            .inner
+            .failing_calls
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                count.checked_sub(1)
+            })
+            .is_ok();
+        if failing_call {
+            return Err(Self::unreachable_error());
+        }
+        let block_tokens = match block {
+            Some(BlockId::Number(BlockNumber::Number(block))) => self
+                .inner
+                .block_call_results
+                .read()
+                .await
+                .get(&(func.to_string(), block.as_u64()))
+                .cloned(),
+            _ => None,
+        };
+        let tokens = match block_tokens {
+            Some(tokens) => Some(tokens),
+            None => self.inner.call_results.read().await.get(func).cloned(),
+        }
+        .ok_or_else(|| anyhow::format_err!("No mock result for the `{}` call", func))?;
+        R::from_tokens(tokens).map_err(|e| anyhow::format_err!("Invalid mock result: {}", e))
+    }
+
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -107,6 +217,7 @@ impl MockEthereum {
     }
 
     pub async fn block_number(&self) -> anyhow::Result<U64> {
+        self.ensure_reachable()?;
         Ok(self.inner.block_number.into())
     }
 
@@ -124,6 +235,15 @@ impl MockEthereum {
         Ok(self.inner.gas_price)
     }
 
+    pub async fn get_base_fee(&self) -> anyhow::Result<U256> {
+        Ok(self.inner.base_fee)
+    }
+
+    pub async fn set_base_fee(&mut self, val: U256) -> anyhow::Result<U256> {
+        Arc::get_mut(&mut self.inner).unwrap().base_fee = val;
+        Ok(self.inner.base_fee)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -137,7 +257,10 @@ impl MockEthereum {
         raw_tx: Vec<u8>,
         options: Options,
     ) -> anyhow::Result<SignedCallResult> {
-        let gas_price = options.gas_price.unwrap_or(self.inner.gas_price);
+        let gas_price = options
+            .max_fee_per_gas
+            .or(options.gas_price)
+            .unwrap_or(self.inner.gas_price);
         let nonce = options.nonce.expect("Nonce must be set for every tx");
 
         // Nonce and gas_price are appended to distinguish the same transactions
@@ -219,11 +342,11 @@ impl MockEthereum {
 
     pub async fn call_main_contract_function<R, A, P, B>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
-        _block: B,
+        block: B,
     ) -> Result<R, anyhow::Error>
     where
         R: Detokenize + Unpin,
@@ -231,7 +354,7 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        self.mock_call(func, block.into()).await
     }
 
     pub async fn logs(&self, _filter: Filter) -> anyhow::Result<Vec<Log>> {
@@ -241,11 +364,11 @@ impl MockEthereum {
     #[allow(clippy::too_many_arguments)]
     pub async fn call_contract_function<R, A, B, P>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
-        _block: B,
+        block: B,
         _token_address: Address,
         _erc20_abi: ethabi::Contract,
     ) -> Result<R, anyhow::Error>
@@ -255,7 +378,7 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        self.mock_call(func, block.into()).await
     }
 
     pub fn create_contract(
@@ -269,4 +392,8 @@ impl MockEthereum {
     pub async fn get_tx(&self, _hash: H256) -> Result<Option<Transaction>, anyhow::Error> {
         unreachable!()
     }
+
+    pub async fn block_hash(&self, _block_number: u64) -> Result<Option<H256>, anyhow::Error> {
+        unreachable!()
+    }
 }
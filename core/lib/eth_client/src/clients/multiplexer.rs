@@ -7,15 +7,14 @@ use web3::{
     transports::Http,
     types::{Address, BlockId, Filter, Log, Transaction, U64},
 };
-use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
-use crate::ETHDirectClient;
+use crate::{ETHDirectClient, OperatorSigner};
 
 #[derive(Debug, Default)]
 struct MultiplexerEthereumClientInner {
-    clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
+    clients: Vec<(String, ETHDirectClient<OperatorSigner>)>,
     preferred: AtomicUsize,
 }
 
@@ -24,15 +23,27 @@ pub struct MultiplexerEthereumClient {
     inner: Arc<MultiplexerEthereumClientInner>,
 }
 
+/// Calls the interfaces in the order of preference until one of them succeeds.
+///
+/// If all the interfaces fail, the error of the last one is returned with the context,
+/// so the caller can still tell whether the nodes were reached at all, e.g. by downcasting
+/// it to the `web3::contract::Error`.
 macro_rules! multiple_call {
     ($self:expr, $func:ident($($attr:expr),*)) => {
+        let mut last_error = None;
         for (name, client) in $self.clients() {
             match client.$func($($attr.clone()),*).await {
                 Ok(res) => return Ok(res),
-                Err(err) => vlog::error!("Error in interface: {}, {} ", name, err),
+                Err(err) => {
+                    vlog::error!("Error in interface: {}, {} ", name, err);
+                    last_error = Some(err);
+                }
             }
         }
-        anyhow::bail!("All interfaces was wrong please try again")
+        return Err(match last_error {
+            Some(err) => err.context("All interfaces was wrong please try again"),
+            None => anyhow::format_err!("All interfaces was wrong please try again"),
+        });
     };
 }
 
@@ -44,7 +55,7 @@ impl MultiplexerEthereumClient {
     pub fn add_client(
         &mut self,
         name: String,
-        client: ETHDirectClient<PrivateKeySigner>,
+        client: ETHDirectClient<OperatorSigner>,
     ) -> &mut Self {
         Arc::get_mut(&mut self.inner)
             .unwrap()
@@ -61,7 +72,7 @@ impl MultiplexerEthereumClient {
         }
     }
 
-    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<PrivateKeySigner>)> {
+    pub fn clients(&self) -> impl Iterator<Item = (&str, &ETHDirectClient<OperatorSigner>)> {
         let preferred = self.inner.preferred.load(Ordering::Relaxed);
         self.inner
             .clients
@@ -106,6 +117,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_base_fee(&self) -> Result<U256, anyhow::Error> {
+        multiple_call!(self, get_base_fee());
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_eth_balance());
     }
@@ -226,4 +241,8 @@ impl MultiplexerEthereumClient {
     pub async fn get_tx(&self, hash: H256) -> Result<Option<Transaction>, anyhow::Error> {
         multiple_call!(self, get_tx(hash));
     }
+
+    pub async fn block_hash(&self, block_number: u64) -> Result<Option<H256>, anyhow::Error> {
+        multiple_call!(self, block_hash(block_number));
+    }
 }
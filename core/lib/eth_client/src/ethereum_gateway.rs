@@ -6,12 +6,11 @@ use web3::types::{Address, BlockId, Filter, Log, Transaction, U64};
 use std::fmt::Debug;
 use zksync_config::{ETHClientConfig, ETHSenderConfig};
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::clients::mock::MockEthereum;
 use crate::clients::multiplexer::MultiplexerEthereumClient;
-use crate::ETHDirectClient;
+use crate::{ETHDirectClient, OperatorSigner};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignedCallResult {
@@ -28,8 +27,9 @@ pub struct ExecutedTxStatus {
     pub confirmations: u64,
     /// Whether transaction was executed successfully or failed.
     pub success: bool,
-    /// Receipt for a transaction. Will be set to `Some` only if the transaction
-    /// failed during execution.
+    /// Receipt for a transaction, used to report the failures and the fees paid.
+    /// Always set for the failed transactions, the mock client doesn't set it
+    /// for the successful ones.
     pub receipt: Option<TransactionReceipt>,
 }
 /// Information about transaction failure.
@@ -43,7 +43,7 @@ pub struct FailureInfo {
 
 #[derive(Debug, Clone)]
 pub enum EthereumGateway {
-    Direct(ETHDirectClient<PrivateKeySigner>),
+    Direct(ETHDirectClient<OperatorSigner>),
     Multiplexed(MultiplexerEthereumClient),
     Mock(MockEthereum),
 }
@@ -53,6 +53,50 @@ impl EthereumGateway {
         eth_client_config: &ETHClientConfig,
         eth_sender_config: &ETHSenderConfig,
         main_contract: Address,
+    ) -> Self {
+        Self::for_operator(
+            eth_client_config,
+            eth_sender_config.sender.operator_commit_eth_addr,
+            OperatorSigner::from_config(&eth_sender_config.sender),
+            main_contract,
+        )
+    }
+
+    /// Creates the gateways sending the transactions from the additional operator accounts,
+    /// along with the addresses of these accounts.
+    pub fn additional_operators_from_config(
+        eth_client_config: &ETHClientConfig,
+        eth_sender_config: &ETHSenderConfig,
+        main_contract: Address,
+    ) -> Vec<(Address, Self)> {
+        OperatorSigner::additional_from_config(&eth_sender_config.sender)
+            .into_iter()
+            .map(|(address, signer)| {
+                let gateway = Self::for_operator(eth_client_config, address, signer, main_contract);
+                (address, gateway)
+            })
+            .collect()
+    }
+
+    /// Creates the gateway sending the batches of withdrawals.
+    pub fn withdrawals_sender_from_config(
+        eth_client_config: &ETHClientConfig,
+        eth_sender_config: &ETHSenderConfig,
+        main_contract: Address,
+    ) -> Self {
+        Self::for_operator(
+            eth_client_config,
+            eth_sender_config.withdrawals.sender_eth_addr,
+            OperatorSigner::withdrawals_sender_from_config(eth_sender_config),
+            main_contract,
+        )
+    }
+
+    fn for_operator(
+        eth_client_config: &ETHClientConfig,
+        operator_eth_addr: Address,
+        signer: OperatorSigner,
+        main_contract: Address,
     ) -> Self {
         if eth_client_config.web3_url.len() == 1 {
             let transport = web3::transports::Http::new(&eth_client_config.web3_url()).unwrap();
@@ -60,8 +104,8 @@ impl EthereumGateway {
             EthereumGateway::Direct(ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                eth_sender_config.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
+                operator_eth_addr,
+                signer,
                 main_contract,
                 eth_client_config.chain_id,
                 eth_client_config.gas_price_factor,
@@ -77,8 +121,8 @@ impl EthereumGateway {
                     ETHDirectClient::new(
                         transport,
                         contract.clone(),
-                        eth_sender_config.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(eth_sender_config.sender.operator_private_key),
+                        operator_eth_addr,
+                        signer.clone(),
                         main_contract,
                         eth_client_config.chain_id,
                         eth_client_config.gas_price_factor,
@@ -126,6 +170,13 @@ impl EthereumGateway {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the base fee per gas of the latest block.
+    /// Fails if the network doesn't support EIP-1559 transactions.
+    pub async fn get_base_fee(&self) -> Result<U256, anyhow::Error> {
+        delegate_call!(self.get_base_fee())
+    }
+
     /// Returns the account balance.
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_eth_balance())
@@ -265,6 +316,12 @@ impl EthereumGateway {
         delegate_call!(self.get_tx(hash))
     }
 
+    /// Returns the hash of the block with the given number,
+    /// or `None` if the node doesn't have such a block yet.
+    pub async fn block_hash(&self, block_number: u64) -> anyhow::Result<Option<H256>> {
+        delegate_call!(self.block_hash(block_number))
+    }
+
     pub fn is_multiplexed(&self) -> bool {
         matches!(self, EthereumGateway::Multiplexed(_))
     }
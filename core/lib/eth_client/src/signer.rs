@@ -0,0 +1,162 @@
+// External uses
+use web3::types::{Address, H256};
+// Workspace uses
+use zksync_config::configs::eth_sender::{ETHSenderConfig, EthSignerType, Sender};
+use zksync_eth_signer::{
+    error::SignerError, json_rpc_signer::SignerType, AwsKmsSigner, EthereumSigner, JsonRpcSigner,
+    PrivateKeySigner, RawTransaction,
+};
+use zksync_types::tx::TxEthSignature;
+
+/// Signer of the operator transactions, chosen by the `eth_sender.sender.signer` config option.
+///
+/// Only the `PrivateKey` signer holds the raw operator key in the process memory:
+/// the remote signers keep it in the signing service or the HSM.
+#[derive(Debug, Clone)]
+pub enum OperatorSigner {
+    PrivateKey(PrivateKeySigner),
+    JsonRpc(JsonRpcSigner),
+    AwsKms(AwsKmsSigner),
+}
+
+impl OperatorSigner {
+    /// Creates the signer of the operator account configured in `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options required by the configured signer are not set.
+    pub fn from_config(config: &Sender) -> Self {
+        Self::for_account(
+            config,
+            config.operator_commit_eth_addr,
+            config.operator_private_key,
+            config.kms_key_id.as_ref(),
+            config.keystore_path.as_ref(),
+        )
+    }
+
+    /// Creates the signers of the additional operator accounts configured in `config`,
+    /// along with the addresses of these accounts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options required by the configured signer are not set for every account.
+    pub fn additional_from_config(config: &Sender) -> Vec<(Address, Self)> {
+        config
+            .additional_operator_eth_addrs
+            .iter()
+            .enumerate()
+            .map(|(index, &address)| {
+                let signer = Self::for_account(
+                    config,
+                    address,
+                    config.additional_operator_private_keys.get(index).copied(),
+                    config.additional_kms_key_ids.get(index),
+                    config.additional_keystore_paths.get(index),
+                );
+                (address, signer)
+            })
+            .collect()
+    }
+
+    /// Creates the signer of the account sending the batches of withdrawals configured in `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the options required by the configured signer are not set.
+    pub fn withdrawals_sender_from_config(config: &ETHSenderConfig) -> Self {
+        let withdrawals = &config.withdrawals;
+        Self::for_account(
+            &config.sender,
+            withdrawals.sender_eth_addr,
+            withdrawals.sender_private_key,
+            withdrawals.sender_kms_key_id.as_ref(),
+            withdrawals.sender_keystore_path.as_ref(),
+        )
+    }
+
+    fn for_account(
+        config: &Sender,
+        address: Address,
+        private_key: Option<H256>,
+        kms_key_id: Option<&String>,
+        keystore_path: Option<&String>,
+    ) -> Self {
+        match config.signer {
+            EthSignerType::PrivateKey => {
+                let private_key = private_key.unwrap_or_else(|| {
+                    panic!(
+                        "Private key of the operator account {:#x} is required for the private_key signer",
+                        address
+                    )
+                });
+                Self::PrivateKey(PrivateKeySigner::new(private_key))
+            }
+            EthSignerType::JsonRpc => {
+                let url = config
+                    .signer_url
+                    .clone()
+                    .expect("signer_url is required for the json_rpc signer");
+                Self::JsonRpc(JsonRpcSigner::with_address(
+                    url,
+                    address,
+                    Some(SignerType::NeedPrefix),
+                ))
+            }
+            EthSignerType::AwsKms => {
+                let key_id = kms_key_id.cloned().unwrap_or_else(|| {
+                    panic!(
+                        "KMS key ID of the operator account {:#x} is required for the aws_kms signer",
+                        address
+                    )
+                });
+                let signer = AwsKmsSigner::new(key_id, config.kms_region.as_deref(), address)
+                    .expect("Failed to create the AWS KMS signer");
+                Self::AwsKms(signer)
+            }
+            EthSignerType::Keystore => {
+                let path = keystore_path.unwrap_or_else(|| {
+                    panic!(
+                        "Keystore path of the operator account {:#x} is required for the keystore signer",
+                        address
+                    )
+                });
+                let password = config.keystore_password.clone().unwrap_or_default();
+                let signer = PrivateKeySigner::from_keystore(path, password)
+                    .expect("Failed to decrypt the keystore");
+                Self::PrivateKey(signer)
+            }
+        }
+    }
+}
+
+impl From<PrivateKeySigner> for OperatorSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::PrivateKey(signer)
+    }
+}
+
+macro_rules! delegate_sign {
+    ($self:ident.$method:ident($($args:ident),*)) => {
+        match $self {
+            Self::PrivateKey(signer) => signer.$method($($args),*).await,
+            Self::JsonRpc(signer) => signer.$method($($args),*).await,
+            Self::AwsKms(signer) => signer.$method($($args),*).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for OperatorSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        delegate_sign!(self.sign_message(message))
+    }
+
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        delegate_sign!(self.sign_transaction(raw_tx))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        delegate_sign!(self.get_address())
+    }
+}
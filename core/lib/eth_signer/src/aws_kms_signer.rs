@@ -0,0 +1,141 @@
+use crate::raw_ethereum_tx::{RawTransaction, Transaction};
+use crate::{EthereumSigner, SignerError};
+
+use rusoto_core::Region;
+use rusoto_kms::{Kms, KmsClient, SignRequest};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId, Signature},
+    Message, PublicKey, Secp256k1,
+};
+use web3::signing::keccak256;
+
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::{Address, H256};
+
+/// Signer using the secp256k1 key stored in the AWS KMS, so the private key never leaves it.
+///
+/// AWS credentials are taken from the environment, the same way as by the AWS CLI.
+#[derive(Clone)]
+pub struct AwsKmsSigner {
+    client: KmsClient,
+    key_id: String,
+    address: Address,
+}
+
+impl std::fmt::Debug for AwsKmsSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsKmsSigner")
+            .field("key_id", &self.key_id)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl AwsKmsSigner {
+    /// Creates the signer for the KMS key with the given ID or ARN.
+    /// `address` is the Ethereum address of the key, every signature is checked to match it.
+    /// If the `region` is not set, it's taken from the environment.
+    pub fn new(
+        key_id: impl Into<String>,
+        region: Option<&str>,
+        address: Address,
+    ) -> Result<Self, SignerError> {
+        let region = match region {
+            Some(region) => region
+                .parse()
+                .map_err(|err| SignerError::CustomError(format!("Invalid AWS region: {}", err)))?,
+            None => Region::default(),
+        };
+        Ok(Self {
+            client: KmsClient::new(region),
+            key_id: key_id.into(),
+            address,
+        })
+    }
+
+    /// Signs the 32-byte digest, returns the compact signature and its recovery ID.
+    async fn sign_digest(&self, digest: [u8; 32]) -> Result<([u8; 64], u64), SignerError> {
+        let request = SignRequest {
+            key_id: self.key_id.clone(),
+            message: digest.to_vec().into(),
+            message_type: Some("DIGEST".to_owned()),
+            signing_algorithm: "ECDSA_SHA_256".to_owned(),
+            ..Default::default()
+        };
+        let response = self
+            .client
+            .sign(request)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let der_signature = response.signature.ok_or_else(|| {
+            SignerError::SigningFailed("KMS responded with no signature".to_owned())
+        })?;
+
+        let mut signature = Signature::from_der(&der_signature)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        // Ethereum only accepts the signatures with the low `s` value (EIP-2).
+        signature.normalize_s();
+        let signature = signature.serialize_compact();
+
+        // KMS doesn't return the recovery ID, so it's found by recovering the address.
+        let message = Message::from_slice(&digest).expect("digest is 32 bytes");
+        let secp = Secp256k1::verification_only();
+        for recovery_id in 0..2 {
+            let recoverable = RecoverableSignature::from_compact(
+                &signature,
+                RecoveryId::from_i32(recovery_id).expect("recovery ID is valid"),
+            )
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+            if let Ok(public_key) = secp.recover_ecdsa(&message, &recoverable) {
+                if public_key_address(&public_key) == self.address {
+                    return Ok((signature, recovery_id as u64));
+                }
+            }
+        }
+        Err(SignerError::SigningFailed(format!(
+            "KMS key {} doesn't belong to the address {:?}",
+            self.key_id, self.address
+        )))
+    }
+}
+
+fn public_key_address(public_key: &PublicKey) -> Address {
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+    Address::from_slice(&hash[12..])
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for AwsKmsSigner {
+    /// The sign method calculates an Ethereum specific signature with:
+    /// sign(keccak256("\x19Ethereum Signed Message:\n" + len(message) + message))).
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+        let digest = keccak256(&[prefix.as_bytes(), message].concat());
+        let (signature, recovery_id) = self.sign_digest(digest).await?;
+
+        let mut packed = [0u8; 65];
+        packed[..64].copy_from_slice(&signature);
+        packed[64] = recovery_id as u8;
+        let signature = PackedEthSignature::deserialize_packed(&packed)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        Ok(TxEthSignature::EthereumSignature(signature))
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+
+        let (signature, recovery_id) = self.sign_digest(tx.signing_hash(chain_id)).await?;
+        Ok(tx.encode_signed(
+            chain_id,
+            H256::from_slice(&signature[..32]),
+            H256::from_slice(&signature[32..]),
+            recovery_id,
+        ))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
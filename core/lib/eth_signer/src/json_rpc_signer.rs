@@ -153,6 +153,21 @@ impl JsonRpcSigner {
         Ok(signer)
     }
 
+    /// Creates the signer for the known address without querying the server,
+    /// the account is expected to be unlocked already.
+    pub fn with_address(
+        rpc_addr: impl Into<String>,
+        address: Address,
+        signer_type: Option<SignerType>,
+    ) -> Self {
+        Self {
+            rpc_addr: rpc_addr.into(),
+            client: reqwest::Client::new(),
+            address: Some(address),
+            signer_type,
+        }
+    }
+
     /// Get Ethereum address.
     pub fn address(&self) -> Result<Address, SignerError> {
         self.address.ok_or(SignerError::DefineAddress)
@@ -290,6 +305,7 @@ impl JsonRpcSigner {
 mod messages {
     use crate::RawTransaction;
     use hex::encode;
+    use web3::types::U64;
     use zksync_types::Address;
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -342,7 +358,7 @@ mod messages {
             let mut params = Vec::new();
 
             // Parameter `To` is optional, so we add it only if it is not None
-            let tx = if let Some(to) = tx_data.to {
+            let mut tx = if let Some(to) = tx_data.to {
                 serde_json::json!({
                     "from": serde_json::to_value(from).expect("serialization fail"),
                     "to": serde_json::to_value(to).expect("serialization fail"),
@@ -362,6 +378,24 @@ mod messages {
                     "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
                 })
             };
+            // Typed transactions include the chain ID, EIP-1559 ones are priced by the fees
+            // instead of the gas price.
+            if let Some(transaction_type) = tx_data.transaction_type {
+                tx["type"] = serde_json::to_value(transaction_type).expect("serialization fail");
+                tx["chainId"] =
+                    serde_json::to_value(U64::from(tx_data.chain_id)).expect("serialization fail");
+            }
+            if let Some(max_fee_per_gas) = tx_data.max_fee_per_gas {
+                tx["maxFeePerGas"] =
+                    serde_json::to_value(max_fee_per_gas).expect("serialization fail");
+                tx.as_object_mut()
+                    .expect("transaction is an object")
+                    .remove("gasPrice");
+            }
+            if let Some(max_priority_fee_per_gas) = tx_data.max_priority_fee_per_gas {
+                tx["maxPriorityFeePerGas"] =
+                    serde_json::to_value(max_priority_fee_per_gas).expect("serialization fail");
+            }
             params.push(tx);
             Self::create("eth_signTransaction", params)
         }
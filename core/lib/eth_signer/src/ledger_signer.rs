@@ -0,0 +1,537 @@
+//! Signer using the Ethereum app of the Ledger hardware wallet, so the private key never leaves the device.
+//!
+//! The device is driven with the APDU commands of the Ledger Ethereum app. Every signature has to be
+//! confirmed by the user on the device, so signing blocks until the user approves or rejects it.
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+
+use crate::raw_ethereum_tx::{RawTransaction, Transaction};
+use crate::{EthereumSigner, SignerError};
+
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::{Address, H256};
+
+const CLA: u8 = 0xe0;
+const INS_GET_ADDRESS: u8 = 0x02;
+const INS_SIGN_TX: u8 = 0x04;
+const INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+/// `P1` of the first chunk of the signed data and of the subsequent ones.
+const P1_FIRST_CHUNK: u8 = 0x00;
+const P1_MORE_CHUNKS: u8 = 0x80;
+/// Maximum length of the APDU command data.
+const MAX_CHUNK_LEN: usize = 255;
+
+const SW_OK: u16 = 0x9000;
+const SW_REJECTED: u16 = 0x6985;
+const SW_APP_NOT_OPEN: [u16; 2] = [0x6d00, 0x6e00];
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// Transport delivering the APDU commands to the Ledger device.
+pub trait LedgerTransport: Send + Sync {
+    /// Sends the APDU command and returns the response of the device, ending with the status word.
+    /// Blocks until the device responds, i.e. until the user confirms the signature.
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError>;
+}
+
+/// Signer using the key with the given derivation path on the Ledger device.
+/// Used for the Ethereum signatures of the zkSync transactions, i.e. the `ChangePubKey`
+/// authorization and the transaction messages, as well as for the Ethereum transactions.
+#[derive(Clone)]
+pub struct LedgerSigner {
+    transport: Arc<dyn LedgerTransport>,
+    path: Vec<u32>,
+    address: Address,
+}
+
+impl std::fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("path", &self.path)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl LedgerSigner {
+    /// Derivation path of the first account in Ledger Live.
+    pub const DEFAULT_PATH: &'static str = "m/44'/60'/0'/0/0";
+
+    /// Creates the signer for the key with the given BIP-32 derivation path, e.g. `m/44'/60'/0'/0/0`.
+    /// The Ethereum app has to be open on the device.
+    pub fn new(transport: impl LedgerTransport + 'static, path: &str) -> Result<Self, SignerError> {
+        let path = parse_derivation_path(path)?;
+        let transport: Arc<dyn LedgerTransport> = Arc::new(transport);
+
+        let response = exchange(
+            transport.as_ref(),
+            INS_GET_ADDRESS,
+            0x00,
+            &encode_derivation_path(&path),
+        )?;
+        let address = parse_address_response(&response)?;
+
+        Ok(Self {
+            transport,
+            path,
+            address,
+        })
+    }
+
+    /// Creates the signer for the first Ledger device connected via USB.
+    #[cfg(feature = "ledger-hid")]
+    pub fn connect_hid(path: &str) -> Result<Self, SignerError> {
+        Self::new(HidTransport::connect()?, path)
+    }
+
+    /// Sends the data to sign in chunks, the first one starting with the derivation path.
+    /// Returns the signature as `v || r || s`.
+    fn sign(&self, ins: u8, data: &[u8]) -> Result<[u8; 65], SignerError> {
+        let mut payload = encode_derivation_path(&self.path);
+        payload.extend_from_slice(data);
+
+        let mut response = Vec::new();
+        for (idx, chunk) in payload.chunks(MAX_CHUNK_LEN).enumerate() {
+            let p1 = if idx == 0 {
+                P1_FIRST_CHUNK
+            } else {
+                P1_MORE_CHUNKS
+            };
+            response = exchange(self.transport.as_ref(), ins, p1, chunk)?;
+        }
+
+        if response.len() != 65 {
+            return Err(SignerError::SigningFailed(format!(
+                "Ledger responded with the signature of {} bytes",
+                response.len()
+            )));
+        }
+        let mut signature = [0u8; 65];
+        signature.copy_from_slice(&response);
+        Ok(signature)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for LedgerSigner {
+    /// The sign method calculates an Ethereum specific signature with:
+    /// sign(keccak256("\x19Ethereum Signed Message:\n" + len(message) + message))).
+    async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let mut data = (message.len() as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(message);
+        let signature = self.sign(INS_SIGN_PERSONAL_MESSAGE, &data)?;
+
+        // Ledger returns `v || r || s`, packed signature is `r || s || v`.
+        let mut packed = [0u8; 65];
+        packed[..64].copy_from_slice(&signature[1..]);
+        packed[64] = signature[0];
+        let signature = PackedEthSignature::deserialize_packed(&packed)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        Ok(TxEthSignature::EthereumSignature(signature))
+    }
+
+    /// Signs and returns the RLP-encoded transaction.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+
+        let signature = self.sign(INS_SIGN_TX, &tx.encode_unsigned(chain_id))?;
+        let v = u64::from(signature[0]);
+        let recovery_id = if tx.is_legacy() {
+            // Ledger returns only the lowest byte of the EIP-155 `v`.
+            let base = (chain_id * 2 + 35) % 256;
+            (v + 256 - base) % 256
+        } else {
+            v
+        };
+        if recovery_id > 1 {
+            return Err(SignerError::SigningFailed(format!(
+                "Ledger responded with the invalid signature `v`: {}",
+                v
+            )));
+        }
+
+        Ok(tx.encode_signed(
+            chain_id,
+            H256::from_slice(&signature[1..33]),
+            H256::from_slice(&signature[33..]),
+            recovery_id,
+        ))
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+/// Sends the APDU command, returns the response data if the command has succeeded.
+fn exchange(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    p1: u8,
+    data: &[u8],
+) -> Result<Vec<u8>, SignerError> {
+    let mut apdu = vec![CLA, ins, p1, 0x00, data.len() as u8];
+    apdu.extend_from_slice(data);
+
+    let mut response = transport.exchange(&apdu)?;
+    if response.len() < 2 {
+        return Err(SignerError::SigningFailed(
+            "Ledger responded with no status word".to_owned(),
+        ));
+    }
+    let status = response.split_off(response.len() - 2);
+    match u16::from_be_bytes([status[0], status[1]]) {
+        SW_OK => Ok(response),
+        SW_REJECTED => Err(SignerError::SigningFailed(
+            "Rejected by the user on the Ledger device".to_owned(),
+        )),
+        status if SW_APP_NOT_OPEN.contains(&status) => Err(SignerError::CustomError(
+            "Ethereum app is not open on the Ledger device".to_owned(),
+        )),
+        status => Err(SignerError::SigningFailed(format!(
+            "Ledger responded with the status {:#06x}",
+            status
+        ))),
+    }
+}
+
+/// Parses the BIP-32 derivation path like `m/44'/60'/0'/0/0`.
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>, SignerError> {
+    let invalid_path = || SignerError::CustomError(format!("Invalid derivation path: {}", path));
+
+    let mut components = path.split('/');
+    if components.next() != Some("m") {
+        return Err(invalid_path());
+    }
+    let path = components
+        .map(|component| {
+            let (index, hardened) = match component.strip_suffix('\'') {
+                Some(index) => (index, true),
+                None => (component, false),
+            };
+            let index: u32 = index.parse().map_err(|_| invalid_path())?;
+            if index >= HARDENED {
+                return Err(invalid_path());
+            }
+            Ok(if hardened { index | HARDENED } else { index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Ledger accepts up to 10 path components.
+    if path.is_empty() || path.len() > 10 {
+        return Err(invalid_path());
+    }
+    Ok(path)
+}
+
+fn encode_derivation_path(path: &[u32]) -> Vec<u8> {
+    let mut bytes = vec![path.len() as u8];
+    for index in path {
+        bytes.extend_from_slice(&index.to_be_bytes());
+    }
+    bytes
+}
+
+/// Parses the response of `GET ETH PUBLIC ADDRESS`: the length-prefixed public key
+/// followed by the length-prefixed hex address.
+fn parse_address_response(response: &[u8]) -> Result<Address, SignerError> {
+    let pub_key_len = *response.first().ok_or(SignerError::DefineAddress)? as usize;
+    let address_offset = 1 + pub_key_len;
+    let address_len = *response
+        .get(address_offset)
+        .ok_or(SignerError::DefineAddress)? as usize;
+    let address = response
+        .get(address_offset + 1..address_offset + 1 + address_len)
+        .ok_or(SignerError::DefineAddress)?;
+
+    let address = std::str::from_utf8(address).map_err(|_| SignerError::DefineAddress)?;
+    let address =
+        hex::decode(address.trim_start_matches("0x")).map_err(|_| SignerError::DefineAddress)?;
+    if address.len() != Address::len_bytes() {
+        return Err(SignerError::DefineAddress);
+    }
+    Ok(Address::from_slice(&address))
+}
+
+/// Transport to the Speculos emulator of the Ledger device, for testing the signing flows
+/// without the hardware.
+#[derive(Debug)]
+pub struct SpeculosTransport {
+    stream: Mutex<TcpStream>,
+}
+
+impl SpeculosTransport {
+    /// Connects to the APDU port of the emulator, `127.0.0.1:9999` by default.
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self, SignerError> {
+        let stream = TcpStream::connect(addr).map_err(transport_error)?;
+        Ok(Self {
+            stream: Mutex::new(stream),
+        })
+    }
+}
+
+impl LedgerTransport for SpeculosTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let mut stream = self.stream.lock().unwrap();
+
+        let mut request = (apdu.len() as u32).to_be_bytes().to_vec();
+        request.extend_from_slice(apdu);
+        stream.write_all(&request).map_err(transport_error)?;
+
+        // The length of the response doesn't include the status word.
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).map_err(transport_error)?;
+        let mut response = vec![0u8; u32::from_be_bytes(len) as usize + 2];
+        stream.read_exact(&mut response).map_err(transport_error)?;
+        Ok(response)
+    }
+}
+
+fn transport_error(err: impl std::fmt::Display) -> SignerError {
+    SignerError::CustomError(format!("Ledger transport error: {}", err))
+}
+
+const HID_PACKET_LEN: usize = 64;
+const HID_CHANNEL: u16 = 0x0101;
+const HID_TAG_APDU: u8 = 0x05;
+
+/// Splits the APDU command into the HID packets: each packet starts with the channel, the tag and
+/// the sequence index, the first one is followed by the length of the command.
+fn wrap_hid_packets(apdu: &[u8]) -> Vec<[u8; HID_PACKET_LEN]> {
+    let mut data = (apdu.len() as u16).to_be_bytes().to_vec();
+    data.extend_from_slice(apdu);
+
+    data.chunks(HID_PACKET_LEN - 5)
+        .enumerate()
+        .map(|(seq, chunk)| {
+            let mut packet = [0u8; HID_PACKET_LEN];
+            packet[..2].copy_from_slice(&HID_CHANNEL.to_be_bytes());
+            packet[2] = HID_TAG_APDU;
+            packet[3..5].copy_from_slice(&(seq as u16).to_be_bytes());
+            packet[5..5 + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Assembles the response from the HID packets read one by one.
+fn unwrap_hid_packets(
+    mut read_packet: impl FnMut() -> Result<[u8; HID_PACKET_LEN], SignerError>,
+) -> Result<Vec<u8>, SignerError> {
+    let mut data = Vec::new();
+    let mut response_len = None;
+    for seq in 0u16.. {
+        let packet = read_packet()?;
+        if packet[..2] != HID_CHANNEL.to_be_bytes()
+            || packet[2] != HID_TAG_APDU
+            || packet[3..5] != seq.to_be_bytes()
+        {
+            return Err(transport_error("unexpected HID packet"));
+        }
+        data.extend_from_slice(&packet[5..]);
+
+        let len = *response_len.get_or_insert_with(|| u16::from_be_bytes([data[0], data[1]]));
+        if data.len() >= len as usize + 2 {
+            data.truncate(len as usize + 2);
+            break;
+        }
+    }
+    Ok(data.split_off(2))
+}
+
+/// Transport to the Ledger device connected via USB.
+#[cfg(feature = "ledger-hid")]
+pub struct HidTransport {
+    device: Mutex<hidapi::HidDevice>,
+}
+
+#[cfg(feature = "ledger-hid")]
+impl std::fmt::Debug for HidTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HidTransport")
+    }
+}
+
+#[cfg(feature = "ledger-hid")]
+impl HidTransport {
+    const VENDOR_ID: u16 = 0x2c97;
+    const USAGE_PAGE: u16 = 0xffa0;
+
+    /// Connects to the first Ledger device found.
+    pub fn connect() -> Result<Self, SignerError> {
+        let api = hidapi::HidApi::new().map_err(transport_error)?;
+        let device = api
+            .device_list()
+            .find(|info| {
+                info.vendor_id() == Self::VENDOR_ID
+                    && (info.usage_page() == Self::USAGE_PAGE || info.interface_number() == 0)
+            })
+            .ok_or_else(|| transport_error("no Ledger device is connected"))?
+            .open_device(&api)
+            .map_err(transport_error)?;
+        Ok(Self {
+            device: Mutex::new(device),
+        })
+    }
+}
+
+#[cfg(feature = "ledger-hid")]
+impl LedgerTransport for HidTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let device = self.device.lock().unwrap();
+        for packet in wrap_hid_packets(apdu) {
+            // Packets are written with the zero report ID.
+            let mut report = vec![0u8];
+            report.extend_from_slice(&packet);
+            device.write(&report).map_err(transport_error)?;
+        }
+
+        unwrap_hid_packets(|| {
+            let mut packet = [0u8; HID_PACKET_LEN];
+            let len = device.read(&mut packet).map_err(transport_error)?;
+            if len != HID_PACKET_LEN {
+                return Err(transport_error("truncated HID packet"));
+            }
+            Ok(packet)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+    use web3::signing::keccak256;
+
+    /// Emulates the Ethereum app holding the given private key.
+    struct MockDevice {
+        private_key: H256,
+        pending: Mutex<Vec<u8>>,
+    }
+
+    impl MockDevice {
+        fn signature_response(&self, digest: [u8; 32], v_offset: u8) -> Vec<u8> {
+            let key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
+            let (recovery_id, signature) = Secp256k1::signing_only()
+                .sign_ecdsa_recoverable(&Message::from_slice(&digest).unwrap(), &key)
+                .serialize_compact();
+            let mut response = vec![recovery_id.to_i32() as u8 + v_offset];
+            response.extend_from_slice(&signature);
+            response
+        }
+    }
+
+    impl LedgerTransport for MockDevice {
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+            assert_eq!(apdu[0], CLA);
+            assert_eq!(apdu[4] as usize, apdu.len() - 5);
+            let data = &apdu[5..];
+
+            let mut response = match (apdu[1], apdu[2]) {
+                (INS_GET_ADDRESS, _) => {
+                    let key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
+                    let pub_key = PublicKey::from_secret_key(&Secp256k1::signing_only(), &key)
+                        .serialize_uncompressed();
+                    let address =
+                        PackedEthSignature::address_from_private_key(&self.private_key).unwrap();
+                    let mut response = vec![pub_key.len() as u8];
+                    response.extend_from_slice(&pub_key);
+                    response.push(40);
+                    response.extend_from_slice(hex::encode(address).as_bytes());
+                    response
+                }
+                (INS_SIGN_PERSONAL_MESSAGE, p1) => {
+                    let mut pending = self.pending.lock().unwrap();
+                    if p1 == P1_FIRST_CHUNK {
+                        // Skip the derivation path.
+                        let path_len = 1 + 4 * data[0] as usize;
+                        *pending = data[path_len..].to_vec();
+                    } else {
+                        pending.extend_from_slice(data);
+                    }
+
+                    let message_len =
+                        u32::from_be_bytes([pending[0], pending[1], pending[2], pending[3]]);
+                    if pending.len() < 4 + message_len as usize {
+                        Vec::new()
+                    } else {
+                        let message = &pending[4..];
+                        let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+                        let digest = keccak256(&[prefix.as_bytes(), message].concat());
+                        self.signature_response(digest, 27)
+                    }
+                }
+                _ => return Ok(SW_REJECTED.to_be_bytes().to_vec()),
+            };
+            response.extend_from_slice(&SW_OK.to_be_bytes());
+            Ok(response)
+        }
+    }
+
+    #[test]
+    fn derivation_path() {
+        assert_eq!(
+            parse_derivation_path(LedgerSigner::DEFAULT_PATH).unwrap(),
+            vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, 0]
+        );
+        assert_eq!(
+            encode_derivation_path(&[44 | HARDENED, 1]),
+            vec![2, 0x80, 0, 0, 44, 0, 0, 0, 1]
+        );
+        for path in &["44'/60'", "m", "m/44'/x", "m/2147483648"] {
+            assert!(parse_derivation_path(path).is_err(), "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn sign_message() {
+        let private_key = H256::repeat_byte(0x17);
+        let device = MockDevice {
+            private_key,
+            pending: Mutex::default(),
+        };
+        let signer = LedgerSigner::new(device, LedgerSigner::DEFAULT_PATH).unwrap();
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        assert_eq!(signer.get_address().await.unwrap(), address);
+
+        // The long message is sent in several chunks.
+        for message in &[b"Hello, zkSync".to_vec(), vec![0xab; 1000]] {
+            let signature = match signer.sign_message(message).await.unwrap() {
+                TxEthSignature::EthereumSignature(signature) => signature,
+                signature => panic!("Unexpected signature: {:?}", signature),
+            };
+            assert_eq!(
+                signature.signature_recover_signer(message).unwrap(),
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn hid_packets() {
+        let apdu: Vec<u8> = (0..200).collect();
+        let packets = wrap_hid_packets(&apdu);
+        assert_eq!(packets.len(), 4);
+
+        let mut response = apdu.clone();
+        response.extend_from_slice(&SW_OK.to_be_bytes());
+        let mut packets = wrap_hid_packets(&response).into_iter();
+        let unwrapped = unwrap_hid_packets(|| Ok(packets.next().unwrap())).unwrap();
+        assert_eq!(unwrapped, response);
+    }
+
+    #[test]
+    fn status_words() {
+        struct Rejecting;
+        impl LedgerTransport for Rejecting {
+            fn exchange(&self, _apdu: &[u8]) -> Result<Vec<u8>, SignerError> {
+                Ok(SW_REJECTED.to_be_bytes().to_vec())
+            }
+        }
+
+        let err = exchange(&Rejecting, INS_SIGN_TX, P1_FIRST_CHUNK, &[]).unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)));
+    }
+}
@@ -6,12 +6,16 @@ use error::SignerError;
 use zksync_types::tx::TxEthSignature;
 use zksync_types::Address;
 
+pub use aws_kms_signer::AwsKmsSigner;
 pub use json_rpc_signer::JsonRpcSigner;
+pub use ledger_signer::LedgerSigner;
 pub use pk_signer::PrivateKeySigner;
 pub use raw_ethereum_tx::RawTransaction;
 
+pub mod aws_kms_signer;
 pub mod error;
 pub mod json_rpc_signer;
+pub mod ledger_signer;
 pub mod pk_signer;
 pub mod raw_ethereum_tx;
 
@@ -2,6 +2,7 @@ use crate::raw_ethereum_tx::{RawTransaction, Transaction};
 use crate::{EthereumSigner, SignerError};
 
 use secp256k1::SecretKey;
+use std::path::Path;
 
 use zksync_types::tx::{PackedEthSignature, TxEthSignature};
 use zksync_types::{Address, H256};
@@ -21,6 +22,21 @@ impl PrivateKeySigner {
     pub fn new(private_key: H256) -> Self {
         Self { private_key }
     }
+
+    /// Creates the signer with the private key decrypted from the Ethereum keystore (V3) file.
+    pub fn from_keystore(
+        path: impl AsRef<Path>,
+        password: impl AsRef<[u8]>,
+    ) -> Result<Self, SignerError> {
+        let private_key = eth_keystore::decrypt_key(path, password)
+            .map_err(|err| SignerError::CustomError(format!("Invalid keystore: {}", err)))?;
+        if private_key.len() != H256::len_bytes() {
+            return Err(SignerError::CustomError(
+                "Invalid keystore: wrong private key length".to_owned(),
+            ));
+        }
+        Ok(Self::new(H256::from_slice(&private_key)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -43,23 +59,10 @@ impl EthereumSigner for PrivateKeySigner {
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
         let key = SecretKey::from_slice(self.private_key.as_bytes()).unwrap();
 
-        let gas_price = match raw_tx.max_fee_per_gas {
-            Some(val) => val,
-            None => raw_tx.gas_price,
-        };
-        let tx = Transaction {
-            to: raw_tx.to,
-            nonce: raw_tx.nonce,
-            gas: raw_tx.gas,
-            gas_price,
-            value: raw_tx.value,
-            data: raw_tx.data,
-            transaction_type: raw_tx.transaction_type,
-            access_list: raw_tx.access_list.unwrap_or_default(),
-            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas.unwrap_or_default(),
-        };
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
 
-        let signed = tx.sign(&key, raw_tx.chain_id);
+        let signed = tx.sign(&key, chain_id);
         Ok(signed.raw_transaction.0)
     }
 }
@@ -67,8 +70,9 @@ impl EthereumSigner for PrivateKeySigner {
 #[cfg(test)]
 mod test {
     use super::PrivateKeySigner;
-    use super::RawTransaction;
+    use super::{RawTransaction, Transaction};
     use crate::EthereumSigner;
+    use secp256k1::{Message, Secp256k1, SecretKey};
     use web3::types::U64;
     use zksync_types::{H160, H256, U256};
 
@@ -105,4 +109,78 @@ mod test {
         ];
         assert_eq!(raw_tx, precalculated_raw_tx);
     }
+
+    /// Checks that the transaction signed by the signature of its hash, the way the remote
+    /// signers do it, is the same as the one signed with the private key.
+    #[tokio::test]
+    async fn test_encoding_signed_raw_transaction() {
+        let private_key = H256::from([5; 32]);
+        let signer = PrivateKeySigner::new(private_key);
+        let secret_key = SecretKey::from_slice(private_key.as_bytes()).unwrap();
+
+        for transaction_type in vec![None, Some(U64::from(2u32))] {
+            let raw_transaction = RawTransaction {
+                nonce: U256::from(1u32),
+                to: Some(H160::default()),
+                gas: U256::from(100_000u32),
+                gas_price: U256::from(2u32),
+                max_fee_per_gas: transaction_type.map(|_| U256::from(2u32)),
+                max_priority_fee_per_gas: transaction_type.map(|_| U256::from(1u32)),
+                value: Default::default(),
+                data: vec![1, 2, 3],
+                chain_id: 270,
+                transaction_type,
+                access_list: None,
+            };
+            let raw_tx = signer
+                .sign_transaction(raw_transaction.clone())
+                .await
+                .unwrap();
+
+            let tx = Transaction::from(raw_transaction);
+            let hash = tx.signing_hash(270);
+            let (recovery_id, signature) = Secp256k1::new()
+                .sign_ecdsa_recoverable(&Message::from_slice(&hash).unwrap(), &secret_key)
+                .serialize_compact();
+            let encoded_tx = tx.encode_signed(
+                270,
+                H256::from_slice(&signature[..32]),
+                H256::from_slice(&signature[32..]),
+                recovery_id.to_i32() as u64,
+            );
+            assert_eq!(encoded_tx, raw_tx);
+        }
+    }
+
+    /// Checks the decryption of the test vector from the Web3 Secret Storage Definition.
+    #[test]
+    fn test_keystore_signer() {
+        let keystore = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let path = std::env::temp_dir().join("zksync_eth_signer_test_keystore.json");
+        std::fs::write(&path, keystore).unwrap();
+
+        let signer = PrivateKeySigner::from_keystore(&path, "testpassword").unwrap();
+        let private_key =
+            hex::decode("7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d")
+                .unwrap();
+        assert_eq!(signer.private_key, H256::from_slice(&private_key));
+        assert!(PrivateKeySigner::from_keystore(&path, "wrongpassword").is_err());
+        std::fs::remove_file(path).unwrap();
+    }
 }
@@ -11,7 +11,7 @@
 use rlp::RlpStream;
 use web3::{
     signing::{self, Signature},
-    types::{AccessList, Address, SignedTransaction, U256, U64},
+    types::{AccessList, Address, SignedTransaction, H256, U256, U64},
 };
 
 const LEGACY_TX_ID: u64 = 0;
@@ -58,7 +58,35 @@ pub struct Transaction {
     pub max_priority_fee_per_gas: U256,
 }
 
+impl From<RawTransaction> for Transaction {
+    fn from(raw_tx: RawTransaction) -> Self {
+        // EIP-1559 transactions are encoded with the max fee per gas in place of the gas price.
+        let gas_price = match raw_tx.max_fee_per_gas {
+            Some(val) => val,
+            None => raw_tx.gas_price,
+        };
+        Self {
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            gas_price,
+            value: raw_tx.value,
+            data: raw_tx.data,
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas.unwrap_or_default(),
+        }
+    }
+}
+
 impl Transaction {
+    pub(crate) fn is_legacy(&self) -> bool {
+        matches!(
+            self.transaction_type.map(|t| t.as_u64()),
+            Some(LEGACY_TX_ID) | None
+        )
+    }
+
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
         stream.append(&self.gas_price);
@@ -185,16 +213,34 @@ impl Transaction {
         }
     }
 
+    /// Returns the encoded transaction without the signature, the way it's hashed for signing.
+    /// Used by the hardware wallets, which parse the transaction before signing it.
+    pub fn encode_unsigned(&self, chain_id: u64) -> Vec<u8> {
+        self.encode(chain_id, None)
+    }
+
+    /// Returns the hash to be signed by the sender of the transaction.
+    pub fn signing_hash(&self, chain_id: u64) -> [u8; 32] {
+        signing::keccak256(self.encode_unsigned(chain_id).as_ref())
+    }
+
+    /// Returns the raw transaction signed with the given recoverable signature of its `signing_hash`.
+    /// Used by the signers which don't hold the key, so the transaction can't be signed with `sign`.
+    pub fn encode_signed(&self, chain_id: u64, r: H256, s: H256, recovery_id: u64) -> Vec<u8> {
+        // Legacy transactions are protected from the replay by the chain ID in `v` (EIP-155).
+        let v = if self.is_legacy() {
+            recovery_id + 35 + chain_id * 2
+        } else {
+            recovery_id
+        };
+        self.encode(chain_id, Some(&Signature { v, r, s }))
+    }
+
     /// Sign and return a raw signed transaction.
     pub fn sign(self, sign: impl signing::Key, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
-            self.transaction_type.map(|t| t.as_u64()),
-            Some(LEGACY_TX_ID) | None
-        );
-
-        let encoded = self.encode(chain_id, None);
+        let adjust_v_value = self.is_legacy();
 
-        let hash = signing::keccak256(encoded.as_ref());
+        let hash = self.signing_hash(chain_id);
 
         let signature = if adjust_v_value {
             sign.sign(&hash, Some(chain_id))
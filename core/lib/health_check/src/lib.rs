@@ -0,0 +1,294 @@
+//! Health checks of the server components for the orchestrator, e.g. the Kubernetes probes.
+//!
+//! - `GET /healthz` runs the liveness checks: the internal channels are open and the event loops
+//!   are not stuck. The process is expected to be restarted if it fails.
+//! - `GET /readyz` runs the readiness checks along with the liveness ones: the database and
+//!   the Ethereum node are reachable. The process is not expected to get any traffic while it fails.
+//!
+//! Both endpoints respond with `200 OK` if all the checks pass and with `503 Service Unavailable`
+//! otherwise, the result of every check is in the JSON body.
+
+// Built-in uses
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+// External uses
+use actix_web::{web, App, HttpResponse, HttpServer};
+use futures::{channel::mpsc, future::BoxFuture, FutureExt, StreamExt};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Maximum time the check has to complete in, otherwise it's failed.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    /// Fails if the component is wedged and the process has to be restarted.
+    Liveness,
+    /// Fails if the component can't serve the requests for now, e.g. the database is unreachable.
+    Readiness,
+}
+
+type CheckFn = Box<dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+struct Check {
+    name: String,
+    kind: CheckKind,
+    check: CheckFn,
+}
+
+/// Time of the last iteration of the event loop, used to find out that the loop is stuck.
+#[derive(Debug, Clone)]
+pub struct Heartbeat(Arc<Mutex<Instant>>);
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+}
+
+impl Heartbeat {
+    /// Marks the event loop as alive, should be called on every iteration.
+    pub fn beat(&self) {
+        *self.0.lock().unwrap() = Instant::now();
+    }
+
+    /// Returns the time passed since the last iteration of the loop.
+    pub fn elapsed(&self) -> Duration {
+        self.0.lock().unwrap().elapsed()
+    }
+}
+
+/// Result of the checks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    /// Error of every failed check, `ok` for the passed ones.
+    pub checks: BTreeMap<String, String>,
+}
+
+/// Checks of the components run by the process.
+#[derive(Default)]
+pub struct HealthChecks {
+    checks: Vec<Check>,
+}
+
+impl fmt::Debug for HealthChecks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.checks.iter().map(|check| (&check.name, check.kind)))
+            .finish()
+    }
+}
+
+impl HealthChecks {
+    pub fn add_check<F, Fut>(&mut self, name: impl Into<String>, kind: CheckKind, check: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.checks.push(Check {
+            name: name.into(),
+            kind,
+            check: Box::new(move || check().boxed()),
+        });
+    }
+
+    /// Checks that the receiver of the channel is not dropped, i.e. the component handling
+    /// the messages is running.
+    pub fn add_channel<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        sender: mpsc::Sender<T>,
+    ) {
+        self.add_check(name, CheckKind::Liveness, move || {
+            let closed = sender.is_closed();
+            async move {
+                if closed {
+                    Err("channel is closed".to_owned())
+                } else {
+                    Ok(())
+                }
+            }
+        });
+    }
+
+    /// Checks that the event loop has made an iteration within the `timeout`.
+    pub fn add_heartbeat(
+        &mut self,
+        name: impl Into<String>,
+        heartbeat: Heartbeat,
+        timeout: Duration,
+    ) {
+        self.add_check(name, CheckKind::Liveness, move || {
+            let elapsed = heartbeat.elapsed();
+            async move {
+                if elapsed > timeout {
+                    Err(format!("no heartbeat for {}s", elapsed.as_secs()))
+                } else {
+                    Ok(())
+                }
+            }
+        });
+    }
+
+    /// Checks that the connection to the database can be acquired.
+    pub fn add_database(&mut self, name: impl Into<String>, pool: ConnectionPool) {
+        self.add_check(name, CheckKind::Readiness, move || {
+            let pool = pool.clone();
+            async move {
+                pool.access_storage()
+                    .await
+                    .map(drop)
+                    .map_err(|err| err.to_string())
+            }
+        });
+    }
+
+    /// Checks that the Ethereum node responds.
+    pub fn add_web3(&mut self, name: impl Into<String>, client: EthereumGateway) {
+        self.add_check(name, CheckKind::Readiness, move || {
+            let client = client.clone();
+            async move {
+                client
+                    .block_number()
+                    .await
+                    .map(drop)
+                    .map_err(|err| err.to_string())
+            }
+        });
+    }
+
+    /// Runs the checks of the given kind, the liveness checks are run for the readiness as well.
+    pub async fn check(&self, kind: CheckKind) -> HealthReport {
+        let checks = self
+            .checks
+            .iter()
+            .filter(|check| kind == CheckKind::Readiness || check.kind == kind)
+            .map(|check| async move {
+                let result = match tokio::time::timeout(CHECK_TIMEOUT, (check.check)()).await {
+                    Ok(result) => result,
+                    Err(_) => Err("timed out".to_owned()),
+                };
+                (check.name.clone(), result)
+            });
+
+        let mut report = HealthReport {
+            healthy: true,
+            checks: BTreeMap::new(),
+        };
+        for (name, result) in futures::future::join_all(checks).await {
+            let status = match result {
+                Ok(()) => "ok".to_owned(),
+                Err(err) => {
+                    vlog::warn!("Health check {} failed: {}", name, err);
+                    report.healthy = false;
+                    err
+                }
+            };
+            report.checks.insert(name, status);
+        }
+        report
+    }
+}
+
+fn respond(report: HealthReport) -> HttpResponse {
+    if report.healthy {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
+
+#[actix_web::get("/healthz")]
+async fn healthz(checks: web::Data<HealthChecks>) -> HttpResponse {
+    respond(checks.check(CheckKind::Liveness).await)
+}
+
+#[actix_web::get("/readyz")]
+async fn readyz(checks: web::Data<HealthChecks>) -> HttpResponse {
+    respond(checks.check(CheckKind::Readiness).await)
+}
+
+/// Serves the `/healthz` and `/readyz` endpoints on the separate thread, so they respond
+/// even if the Tokio runtime of the server is overloaded.
+pub fn run_health_check_server(bind_addr: SocketAddr, checks: HealthChecks) -> JoinHandle<()> {
+    let (panic_sender, mut panic_receiver) = mpsc::channel(1);
+    let checks = web::Data::new(checks);
+
+    thread::Builder::new()
+        .name("health-check-server".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_sender.clone());
+            let actix_runtime = actix_rt::System::new();
+
+            actix_runtime.block_on(async move {
+                HttpServer::new(move || {
+                    App::new()
+                        .app_data(checks.clone())
+                        .service(healthz)
+                        .service(readyz)
+                })
+                .workers(1)
+                .bind(bind_addr)
+                .expect("failed to bind")
+                .run()
+                .await
+            })
+        })
+        .expect("failed to start health check server");
+    tokio::spawn(async move {
+        panic_receiver.next().await.unwrap();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn health_checks() {
+        let (sender, receiver) = mpsc::channel::<()>(1);
+        let mut checks = HealthChecks::default();
+        checks.add_channel("channel", sender);
+        checks.add_heartbeat("heartbeat", Heartbeat::default(), Duration::from_secs(60));
+        checks.add_check("database", CheckKind::Readiness, || async {
+            Err("unreachable".to_owned())
+        });
+
+        // Readiness checks are not run for the liveness.
+        let report = checks.check(CheckKind::Liveness).await;
+        assert!(report.healthy);
+        assert_eq!(report.checks.len(), 2);
+
+        let report = checks.check(CheckKind::Readiness).await;
+        assert!(!report.healthy);
+        assert_eq!(report.checks["channel"], "ok");
+        assert_eq!(report.checks["database"], "unreachable");
+
+        drop(receiver);
+        let report = checks.check(CheckKind::Liveness).await;
+        assert!(!report.healthy);
+        assert_eq!(report.checks["channel"], "channel is closed");
+    }
+
+    #[test]
+    fn heartbeat() {
+        let heartbeat = Heartbeat::default();
+        *heartbeat.0.lock().unwrap() -= Duration::from_secs(10);
+        assert!(heartbeat.elapsed() >= Duration::from_secs(10));
+
+        heartbeat.clone().beat();
+        assert!(heartbeat.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,6 +1,9 @@
+use std::time::{Duration, Instant};
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::{
     mempool::SignedTxVariant,
     tx::{error::TxAddError, TxHash},
@@ -9,6 +12,10 @@ use zksync_types::{
 
 use crate::state::MempoolState;
 
+/// Interval between the checks for the expired transactions. Pending transactions
+/// are loaded to find the expired ones, so they aren't checked on every miniblock.
+const EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Clone, Debug, Default)]
 pub struct ProposedBlock {
     pub priority_ops: Vec<PriorityOp>,
@@ -47,6 +54,10 @@ pub(crate) struct MempoolBlocksHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolBlocksRequest>,
     pub max_block_size_chunks: usize,
+    pub tx_ordering: MempoolTxOrdering,
+    /// Time after which the pending transactions are removed from the mempool.
+    pub tx_ttl: Duration,
+    pub last_expiration_check: Instant,
 }
 
 impl MempoolBlocksHandler {
@@ -56,7 +67,14 @@ impl MempoolBlocksHandler {
         block_timestamp: u64,
         executed_txs: &[TxHash],
     ) -> Result<ProposedBlock, TxAddError> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
+        if self.last_expiration_check.elapsed() >= EXPIRATION_CHECK_INTERVAL {
+            self.mempool_state
+                .remove_expired_txs(self.tx_ttl, executed_txs)
+                .await?;
+            self.last_expiration_check = Instant::now();
+        }
+
         // Try to exhaust the reverted transactions queue. Most of the time it
         // will be empty unless the server is restarted after reverting blocks.
         let mut tx_queue = self
@@ -70,6 +88,7 @@ impl MempoolBlocksHandler {
                 current_unprocessed_priority_op,
                 block_timestamp,
                 &self.mempool_state,
+                self.tx_ordering,
             )
             .await?;
 
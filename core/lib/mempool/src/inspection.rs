@@ -0,0 +1,336 @@
+//! Inspection of the pending transactions, used by the operator to find out
+//! why the transactions of the account are not executed.
+
+// External uses
+use chrono::{DateTime, Utc};
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{
+    mempool::SignedTxVariant,
+    tokens::TokenLike,
+    tx::{TxHash, ZkSyncTx},
+    Account, AccountId, Nonce, TokenId,
+};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Reason the pending transaction can't be executed yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum StuckTxReason {
+    /// Transactions with the lower nonces are not in the mempool,
+    /// `expected` is the first missing nonce.
+    #[serde(rename_all = "camelCase")]
+    MissingNonce { expected: Nonce },
+    /// Nonce is already used by the committed transaction, so the transaction will fail.
+    #[serde(rename_all = "camelCase")]
+    OutdatedNonce { committed: Nonce },
+    /// Another pending transaction of the account has the same nonce.
+    DuplicateNonce,
+    /// Transaction can't be executed before the `valid_from` timestamp.
+    #[serde(rename_all = "camelCase")]
+    NotValidYet { valid_from: u64 },
+    /// Transaction can't be executed after the `valid_until` timestamp.
+    #[serde(rename_all = "camelCase")]
+    Expired { valid_until: u64 },
+    /// Committed balance of the account is not enough to pay the fee.
+    #[serde(rename_all = "camelCase")]
+    InsufficientFee {
+        fee_token: TokenId,
+        #[serde(with = "BigUintSerdeAsRadix10Str")]
+        fee: BigUint,
+        #[serde(with = "BigUintSerdeAsRadix10Str")]
+        balance: BigUint,
+    },
+    /// Account is not in the committed state.
+    AccountNotFound,
+}
+
+/// Pending transaction along with the reasons it's not executed yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTxInfo {
+    pub tx_hash: TxHash,
+    pub account_id: AccountId,
+    pub nonce: Nonce,
+    pub tx_type: String,
+    /// ID of the batch the transaction belongs to, the whole batch is evicted along with it.
+    pub batch_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// The transaction is expected to be executed in the next blocks if the list is empty.
+    pub stuck_reasons: Vec<StuckTxReason>,
+}
+
+/// Nonces of the account, so the clients sending several transactions at once
+/// don't have to track the pending ones themselves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonces {
+    /// Nonce of the account in the committed state.
+    pub committed_nonce: Nonce,
+    /// Nonce the next transaction of the account is expected to use: the first nonce
+    /// starting from the committed one which is not used by the pending transactions.
+    pub next_nonce: Nonce,
+    /// Nonces of the pending transactions of the account, in the ascending order.
+    pub pending_nonces: Vec<Nonce>,
+}
+
+/// Committed state of the account the pending transactions are checked against.
+#[derive(Debug, Clone)]
+pub(crate) struct CommittedAccountState {
+    pub nonce: Nonce,
+    pub account: Option<Account>,
+}
+
+/// Returns the fee token and fee of the transaction.
+pub(crate) fn tx_fee(tx: &ZkSyncTx) -> Option<(TokenId, BigUint)> {
+    match tx.get_fee_info()? {
+        (_, TokenLike::Id(token), _, fee) => Some((token, fee)),
+        _ => None,
+    }
+}
+
+/// Inspects the pending transactions of the account, in the order of their nonces.
+/// Transactions of the other accounts from the same batches are skipped.
+pub(crate) fn inspect_account_txs(
+    account_id: AccountId,
+    txs: &[SignedTxVariant],
+    state: &CommittedAccountState,
+    now: u64,
+) -> Vec<MempoolTxInfo> {
+    let mut account_txs = Vec::new();
+    for variant in txs {
+        let batch_id = match variant {
+            SignedTxVariant::Tx(_) => None,
+            SignedTxVariant::Batch(batch) => Some(batch.batch_id),
+        };
+        for tx in variant.get_transactions() {
+            if tx.tx.account_id().ok() == Some(account_id) {
+                account_txs.push((tx, batch_id));
+            }
+        }
+    }
+    account_txs.sort_by_key(|(tx, _)| tx.nonce());
+
+    let mut expected_nonce = state.nonce;
+    account_txs
+        .into_iter()
+        .map(|(tx, batch_id)| {
+            let mut stuck_reasons = Vec::new();
+            let nonce = tx.nonce();
+            if nonce < state.nonce {
+                stuck_reasons.push(StuckTxReason::OutdatedNonce {
+                    committed: state.nonce,
+                });
+            } else if nonce < expected_nonce {
+                stuck_reasons.push(StuckTxReason::DuplicateNonce);
+            } else if nonce > expected_nonce {
+                stuck_reasons.push(StuckTxReason::MissingNonce {
+                    expected: expected_nonce,
+                });
+            } else {
+                expected_nonce = expected_nonce + 1;
+            }
+
+            let time_range = tx.tx.time_range();
+            if time_range.valid_from > now {
+                stuck_reasons.push(StuckTxReason::NotValidYet {
+                    valid_from: time_range.valid_from,
+                });
+            }
+            if time_range.valid_until < now {
+                stuck_reasons.push(StuckTxReason::Expired {
+                    valid_until: time_range.valid_until,
+                });
+            }
+
+            match &state.account {
+                Some(account) => {
+                    if let Some((fee_token, fee)) = tx_fee(&tx.tx) {
+                        let balance = account.get_balance(fee_token);
+                        if !fee.is_zero() && balance < fee {
+                            stuck_reasons.push(StuckTxReason::InsufficientFee {
+                                fee_token,
+                                fee,
+                                balance,
+                            });
+                        }
+                    }
+                }
+                None => stuck_reasons.push(StuckTxReason::AccountNotFound),
+            }
+
+            MempoolTxInfo {
+                tx_hash: tx.hash(),
+                account_id,
+                nonce,
+                tx_type: tx.tx.variance_name(),
+                batch_id,
+                created_at: tx.created_at,
+                stuck_reasons,
+            }
+        })
+        .collect()
+}
+
+/// Returns the nonces of the account given its pending transactions.
+pub(crate) fn account_nonces(
+    account_id: AccountId,
+    txs: &[SignedTxVariant],
+    state: &CommittedAccountState,
+) -> AccountNonces {
+    let mut pending_nonces: Vec<_> = txs
+        .iter()
+        .flat_map(|variant| variant.get_transactions())
+        .filter(|tx| tx.tx.account_id().ok() == Some(account_id))
+        .map(|tx| tx.nonce())
+        .collect();
+    pending_nonces.sort_unstable();
+
+    let mut next_nonce = state.nonce;
+    for nonce in &pending_nonces {
+        if *nonce == next_nonce {
+            next_nonce = next_nonce + 1;
+        }
+    }
+    AccountNonces {
+        committed_nonce: state.nonce,
+        next_nonce,
+        pending_nonces,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        tx::{TimeRange, Transfer},
+        Address, SignedZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(nonce: u32, fee: u32, time_range: TimeRange) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            fee.into(),
+            Nonce(nonce),
+            time_range,
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        })
+    }
+
+    fn reasons(txs: &[MempoolTxInfo]) -> Vec<Vec<StuckTxReason>> {
+        txs.iter().map(|tx| tx.stuck_reasons.clone()).collect()
+    }
+
+    #[test]
+    fn nonce_gaps() {
+        let mut account = Account::default_with_address(&Address::repeat_byte(1));
+        account.set_balance(TokenId(0), 1000u32.into());
+        let state = CommittedAccountState {
+            nonce: Nonce(5),
+            account: Some(account),
+        };
+        let txs = vec![
+            transfer(8, 10, Default::default()),
+            transfer(5, 10, Default::default()),
+            transfer(4, 10, Default::default()),
+            transfer(6, 10, Default::default()),
+            transfer(6, 10, Default::default()),
+        ];
+
+        let info = inspect_account_txs(AccountId(1), &txs, &state, 1000);
+        assert_eq!(
+            info.iter().map(|tx| tx.nonce).collect::<Vec<_>>(),
+            vec![Nonce(4), Nonce(5), Nonce(6), Nonce(6), Nonce(8)]
+        );
+        assert_eq!(
+            reasons(&info),
+            vec![
+                vec![StuckTxReason::OutdatedNonce {
+                    committed: Nonce(5)
+                }],
+                vec![],
+                vec![],
+                vec![StuckTxReason::DuplicateNonce],
+                vec![StuckTxReason::MissingNonce { expected: Nonce(7) }],
+            ]
+        );
+
+        // Transactions of the other accounts are not returned.
+        assert!(inspect_account_txs(AccountId(2), &txs, &state, 1000).is_empty());
+
+        // The gap is the next nonce to use.
+        assert_eq!(
+            account_nonces(AccountId(1), &txs, &state),
+            AccountNonces {
+                committed_nonce: Nonce(5),
+                next_nonce: Nonce(7),
+                pending_nonces: vec![Nonce(4), Nonce(5), Nonce(6), Nonce(6), Nonce(8)],
+            }
+        );
+        assert_eq!(
+            account_nonces(AccountId(2), &txs, &state),
+            AccountNonces {
+                committed_nonce: Nonce(5),
+                next_nonce: Nonce(5),
+                pending_nonces: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn time_range_and_fee() {
+        let mut account = Account::default_with_address(&Address::repeat_byte(1));
+        account.set_balance(TokenId(0), 15u32.into());
+        let state = CommittedAccountState {
+            nonce: Nonce(0),
+            account: Some(account),
+        };
+        let txs = vec![
+            transfer(0, 10, TimeRange::new(2000, u64::MAX)),
+            transfer(1, 10, TimeRange::new(0, 500)),
+            transfer(2, 20, Default::default()),
+        ];
+
+        let info = inspect_account_txs(AccountId(1), &txs, &state, 1000);
+        assert_eq!(
+            reasons(&info),
+            vec![
+                vec![StuckTxReason::NotValidYet { valid_from: 2000 }],
+                vec![StuckTxReason::Expired { valid_until: 500 }],
+                vec![StuckTxReason::InsufficientFee {
+                    fee_token: TokenId(0),
+                    fee: 20u32.into(),
+                    balance: 15u32.into(),
+                }],
+            ]
+        );
+
+        let state = CommittedAccountState {
+            nonce: Nonce(0),
+            account: None,
+        };
+        let info = inspect_account_txs(AccountId(1), &txs[2..], &state, 1000);
+        assert_eq!(
+            reasons(&info),
+            vec![vec![
+                StuckTxReason::MissingNonce { expected: Nonce(0) },
+                StuckTxReason::AccountNotFound
+            ]]
+        );
+    }
+}
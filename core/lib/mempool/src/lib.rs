@@ -6,38 +6,55 @@
 //! 3) When polled return vector of the transactions in the queue.
 //!
 //! For better consistency, we always store all txs in the database and get them only if they are requested.
+//! Transactions are stored before the submission is acknowledged and removed once they are executed,
+//! so the pending transactions survive the restarts of both the API and the core.
 //!
 //! Communication channel with other actors:
 //! Mempool does not push information to other actors, only accepts requests. (see `MempoolRequest`)
 
+// Built-in uses
+use std::time::{Duration, Instant};
+
 // External uses
 use futures::channel::mpsc;
 
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_storage::ConnectionPool;
+use zksync_utils::shutdown::ShutdownToken;
 
 // Local uses
 use crate::block_handler::MempoolBlocksHandler;
 pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
+pub use crate::inspection::{AccountNonces, MempoolTxInfo, StuckTxReason};
+pub use crate::limits::MempoolLimits;
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
 use crate::state::MempoolState;
 pub use crate::transactions_handler::MempoolTransactionRequest;
 use crate::transactions_handler::MempoolTransactionsHandler;
 
 mod block_handler;
+mod inspection;
+mod limits;
 mod mempool_transactions_queue;
+mod ordering;
+mod replacement;
 mod state;
 mod transactions_handler;
 
 // Due channel based nature, for better performance,
-// you need to run independent mempool_tx_handler for each actor, e.g. for each API actor
+// you need to run independent mempool_tx_handler for each actor, e.g. for each API actor.
+// On shutdown the handler stops taking the new requests and handles the ones already sent,
+// so it has to be stopped after the actor.
 #[must_use]
 pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    limits: MempoolLimits,
+    shutdown: ShutdownToken,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool.clone());
     let max_block_size_chunks = *block_chunk_sizes
@@ -49,8 +66,9 @@ pub fn run_mempool_tx_handler(
         mempool_state,
         requests: tx_requests,
         max_block_size_chunks,
+        limits,
     };
-    tokio::spawn(handler.run())
+    tokio::spawn(handler.run(shutdown))
 }
 
 #[must_use]
@@ -58,6 +76,8 @@ pub fn run_mempool_block_handler(
     db_pool: ConnectionPool,
     block_requests: mpsc::Receiver<MempoolBlocksRequest>,
     block_chunk_sizes: Vec<usize>,
+    tx_ordering: MempoolTxOrdering,
+    tx_ttl: Duration,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool);
     let max_block_size_chunks = *block_chunk_sizes
@@ -69,6 +89,9 @@ pub fn run_mempool_block_handler(
         mempool_state,
         requests: block_requests,
         max_block_size_chunks,
+        tx_ordering,
+        tx_ttl,
+        last_expiration_check: Instant::now(),
     };
 
     tokio::spawn(blocks_handler.run())
@@ -0,0 +1,122 @@
+//! Limits of the pending transactions accepted by the mempool.
+//!
+//! Without the limits a flood of the transactions nobody executes could grow the mempool
+//! without bound, so the total number of the pending transactions and the number of the
+//! pending transactions of each account are limited. Transactions not included into a block
+//! in time are removed from the mempool by the block handler.
+
+// Workspace uses
+use zksync_config::{configs::chain::StateKeeper, RuntimeConfig};
+use zksync_types::{tx::error::TxAddError, Nonce};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MempoolLimits {
+    /// Maximum difference between the nonce of the transaction and the committed nonce
+    /// of the account, transactions with a gap wait for the missing ones in the mempool.
+    pub max_nonce_gap: u32,
+    /// Maximum number of the pending transactions.
+    pub max_txs: u32,
+    /// Maximum number of the pending transactions of a single account.
+    pub max_account_txs: u32,
+}
+
+impl MempoolLimits {
+    pub fn from_config(config: &StateKeeper) -> Self {
+        Self {
+            max_nonce_gap: config.max_nonce_gap,
+            max_txs: config.mempool_max_txs,
+            max_account_txs: config.mempool_max_account_txs,
+        }
+    }
+
+    /// Returns the limits of the reloaded runtime config, or `self` if it's not initialized.
+    pub(crate) fn reloaded(self) -> Self {
+        match RuntimeConfig::current() {
+            Some(config) => Self {
+                max_nonce_gap: config.max_nonce_gap,
+                max_txs: config.mempool_max_txs,
+                max_account_txs: config.mempool_max_account_txs,
+            },
+            None => self,
+        }
+    }
+
+    /// Checks the nonce of the transaction against the committed nonce of the account.
+    /// Transactions with the nonce gap are accepted, since the missing transactions
+    /// may be sent concurrently.
+    pub(crate) fn check_nonce(
+        &self,
+        tx_nonce: Nonce,
+        committed_nonce: Nonce,
+    ) -> Result<(), TxAddError> {
+        if tx_nonce < committed_nonce {
+            return Err(TxAddError::NonceMismatch);
+        }
+        if *tx_nonce - *committed_nonce > self.max_nonce_gap {
+            return Err(TxAddError::NonceTooHigh);
+        }
+        Ok(())
+    }
+
+    /// Checks that `new_txs` transactions fit into the mempool with `mempool_size` pending transactions.
+    pub(crate) fn check_mempool_size(
+        &self,
+        mempool_size: u32,
+        new_txs: usize,
+    ) -> Result<(), TxAddError> {
+        if mempool_size as usize + new_txs > self.max_txs as usize {
+            return Err(TxAddError::MempoolFull);
+        }
+        Ok(())
+    }
+
+    /// Checks that `new_txs` transactions of the account with `account_txs` pending transactions
+    /// can be added to the mempool.
+    pub(crate) fn check_account_txs(
+        &self,
+        account_txs: u32,
+        new_txs: usize,
+    ) -> Result<(), TxAddError> {
+        if account_txs as usize + new_txs > self.max_account_txs as usize {
+            return Err(TxAddError::TooManyAccountTxs);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mempool_limits() {
+        let limits = MempoolLimits {
+            max_nonce_gap: 2,
+            max_txs: 10,
+            max_account_txs: 3,
+        };
+
+        assert!(limits.check_nonce(Nonce(5), Nonce(5)).is_ok());
+        assert!(limits.check_nonce(Nonce(7), Nonce(5)).is_ok());
+        assert!(matches!(
+            limits.check_nonce(Nonce(4), Nonce(5)),
+            Err(TxAddError::NonceMismatch)
+        ));
+        assert!(matches!(
+            limits.check_nonce(Nonce(8), Nonce(5)),
+            Err(TxAddError::NonceTooHigh)
+        ));
+
+        assert!(limits.check_mempool_size(9, 1).is_ok());
+        assert!(matches!(
+            limits.check_mempool_size(9, 2),
+            Err(TxAddError::MempoolFull)
+        ));
+
+        assert!(limits.check_account_txs(0, 3).is_ok());
+        assert!(matches!(
+            limits.check_account_txs(3, 1),
+            Err(TxAddError::TooManyAccountTxs)
+        ));
+    }
+}
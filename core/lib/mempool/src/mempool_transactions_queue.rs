@@ -1,6 +1,8 @@
+use crate::ordering::order_by_priority;
 use crate::MempoolState;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::mempool::SignedTxVariant;
 use zksync_types::tx::error::TxAddError;
 use zksync_types::PriorityOp;
@@ -132,12 +134,13 @@ impl MempoolTransactionsQueue {
         current_unprocessed_priority_op: u64,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        ordering: MempoolTxOrdering,
     ) -> Result<(Vec<SignedTxVariant>, Vec<PriorityOp>, usize), TxAddError> {
         let (chunks_left, priority_ops) =
             self.select_l1_transactions(chunks, current_unprocessed_priority_op);
 
         let (chunks_left, executed_txs) = self
-            .select_l2_transactions(chunks_left, block_timestamp, mempool_state)
+            .select_l2_transactions(chunks_left, block_timestamp, mempool_state, ordering)
             .await?;
 
         Ok((executed_txs, priority_ops, chunks_left))
@@ -180,8 +183,14 @@ impl MempoolTransactionsQueue {
         mut chunks_left: usize,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        ordering: MempoolTxOrdering,
     ) -> Result<(usize, Vec<SignedTxVariant>), TxAddError> {
         self.prepare_new_ready_l2_transactions(block_timestamp);
+        if ordering == MempoolTxOrdering::FeePerChunk {
+            let ready_txs = std::mem::take(&mut self.ready_l2_transactions);
+            let fees_per_chunk = mempool_state.fees_per_chunk(&ready_txs).await?;
+            self.ready_l2_transactions = order_by_priority(ready_txs, fees_per_chunk);
+        }
 
         let mut txs_for_commit = Vec::new();
 
@@ -227,6 +236,8 @@ mod tests {
             tx: ZkSyncTx::Transfer(Box::new(transfer)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         })
     }
 
@@ -247,6 +258,8 @@ mod tests {
             tx: ZkSyncTx::Withdraw(Box::new(withdraw)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         })
     }
 
@@ -0,0 +1,170 @@
+//! Ordering of the ready transactions by the fee they pay per chunk.
+//!
+//! Transactions of each account have to be executed in the order of their nonces, so the
+//! transaction is only proposed after all the preceding transactions of its account.
+//! Batches are ordered along with the transactions of the account paying the batch fee.
+
+// Built-in uses
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+// External uses
+use num::{rational::Ratio, BigUint};
+
+// Workspace uses
+use zksync_types::{mempool::SignedTxVariant, AccountId};
+
+/// Account the transaction is ordered along with. The fee of the batch is paid
+/// by its last transaction.
+fn tx_account(tx: &SignedTxVariant) -> Option<AccountId> {
+    match tx {
+        SignedTxVariant::Tx(tx) => tx.account_id().ok(),
+        SignedTxVariant::Batch(batch) => batch.txs.last()?.account_id().ok(),
+    }
+}
+
+/// Transaction at the head of the account queue, the one with the highest priority
+/// is proposed first. Transactions with the same priority keep their original order.
+#[derive(Debug, PartialEq, Eq)]
+struct QueueHead {
+    priority: Ratio<BigUint>,
+    position: Reverse<usize>,
+    queue: usize,
+}
+
+impl Ord for QueueHead {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for QueueHead {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reorders the transactions sorted by their nonces, so the ones with the higher
+/// `priorities` are proposed first.
+pub(crate) fn order_by_priority(
+    txs: VecDeque<SignedTxVariant>,
+    priorities: Vec<Ratio<BigUint>>,
+) -> VecDeque<SignedTxVariant> {
+    let mut queue_ids = HashMap::new();
+    let mut queues: Vec<VecDeque<(usize, SignedTxVariant, Ratio<BigUint>)>> = Vec::new();
+    for (position, (tx, priority)) in txs.into_iter().zip(priorities).enumerate() {
+        let queue = match tx_account(&tx) {
+            Some(account_id) => *queue_ids.entry(account_id).or_insert_with(|| {
+                queues.push(VecDeque::new());
+                queues.len() - 1
+            }),
+            None => {
+                queues.push(VecDeque::new());
+                queues.len() - 1
+            }
+        };
+        queues[queue].push_back((position, tx, priority));
+    }
+
+    let mut heads = BinaryHeap::new();
+    for (queue, txs) in queues.iter().enumerate() {
+        if let Some((position, _, priority)) = txs.front() {
+            heads.push(QueueHead {
+                priority: priority.clone(),
+                position: Reverse(*position),
+                queue,
+            });
+        }
+    }
+
+    let mut ordered = VecDeque::new();
+    while let Some(head) = heads.pop() {
+        let (_, tx, _) = queues[head.queue]
+            .pop_front()
+            .expect("queue head is taken from the non-empty queue");
+        ordered.push_back(tx);
+        if let Some((position, _, priority)) = queues[head.queue].front() {
+            heads.push(QueueHead {
+                priority: priority.clone(),
+                position: Reverse(*position),
+                queue: head.queue,
+            });
+        }
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use zksync_types::{
+        tx::{Transfer, TxHash},
+        Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    use super::*;
+
+    fn transfer(account_id: u32, nonce: u32) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        })
+    }
+
+    fn hashes(txs: &VecDeque<SignedTxVariant>) -> Vec<Vec<TxHash>> {
+        txs.iter().map(SignedTxVariant::hashes).collect()
+    }
+
+    #[test]
+    fn fee_priority_keeps_nonce_order() {
+        let txs: VecDeque<_> = vec![
+            transfer(1, 0),
+            transfer(2, 0),
+            transfer(1, 1),
+            transfer(3, 0),
+            transfer(2, 1),
+        ]
+        .into();
+        let priorities = [1u32, 2, 10, 2, 5]
+            .iter()
+            .map(|&priority| Ratio::from_integer(priority.into()))
+            .collect();
+
+        let ordered = order_by_priority(txs.clone(), priorities);
+        // The second transaction of the first account pays the most, but it can't be
+        // executed before the first one paying the least.
+        let expected: VecDeque<_> = vec![
+            txs[1].clone(),
+            txs[4].clone(),
+            txs[3].clone(),
+            txs[0].clone(),
+            txs[2].clone(),
+        ]
+        .into();
+        assert_eq!(hashes(&ordered), hashes(&expected));
+
+        // Transactions with the same priority keep the original order.
+        let priorities = vec![Ratio::from_integer(BigUint::from(0u32)); txs.len()];
+        assert_eq!(
+            hashes(&order_by_priority(txs.clone(), priorities)),
+            hashes(&txs)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Replace-by-fee rules of the mempool.
+//!
+//! Pending transaction is replaced by the new transaction of the account with the same nonce
+//! if the new one pays a strictly higher fee in the same token. Transactions of the batches
+//! are never replaced, since the batch can only be executed as a whole.
+
+// Workspace uses
+use zksync_types::{tx::error::TxAddError, SignedZkSyncTx};
+
+// Local uses
+use crate::inspection::tx_fee;
+
+/// Checks that the transaction can replace the pending transactions with the same nonce.
+pub(crate) fn check_replacement(
+    tx: &SignedZkSyncTx,
+    pending_txs: &[SignedZkSyncTx],
+) -> Result<(), TxAddError> {
+    let (fee_token, fee) = tx_fee(&tx.tx).ok_or(TxAddError::ReplacementUnderpriced)?;
+    for pending_tx in pending_txs {
+        match tx_fee(&pending_tx.tx) {
+            Some((pending_token, pending_fee)) if pending_token == fee_token => {
+                if fee <= pending_fee {
+                    return Err(TxAddError::ReplacementUnderpriced);
+                }
+            }
+            _ => return Err(TxAddError::ReplacementUnderpriced),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use zksync_types::{
+        tx::{Transfer, ZkSyncTx},
+        AccountId, Address, Nonce, TokenId,
+    };
+
+    use super::*;
+
+    fn transfer(fee_token: u32, fee: u32) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(fee_token),
+            100u32.into(),
+            fee.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn replacement_fee() {
+        let pending_txs = vec![transfer(0, 10), transfer(0, 20)];
+        assert!(check_replacement(&transfer(0, 21), &pending_txs).is_ok());
+        assert!(check_replacement(&transfer(0, 11), &[transfer(0, 10)]).is_ok());
+
+        // The fee has to be strictly higher than the fee of every pending transaction.
+        for tx in &[transfer(0, 20), transfer(0, 15)] {
+            assert!(matches!(
+                check_replacement(tx, &pending_txs),
+                Err(TxAddError::ReplacementUnderpriced)
+            ));
+        }
+        // Fees in different tokens are not comparable.
+        assert!(matches!(
+            check_replacement(&transfer(1, 100), &pending_txs),
+            Err(TxAddError::ReplacementUnderpriced)
+        ));
+    }
+}
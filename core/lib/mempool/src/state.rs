@@ -1,15 +1,27 @@
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use num::{rational::Ratio, BigUint, Zero};
+
+use zksync_storage::{ConnectionPool, QueryResult, StorageProcessor};
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
+    tokens::TokenLike,
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    Address, SignedZkSyncTx, TokenId, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
+use crate::inspection::tx_fee;
 use crate::MempoolTransactionsQueue;
 
+/// Checks whether the transaction or the batch is received more than `tx_ttl` ago.
+fn variant_expired(variant: &SignedTxVariant, tx_ttl: Duration) -> bool {
+    match variant {
+        SignedTxVariant::Tx(tx) => tx.elapsed() > tx_ttl,
+        SignedTxVariant::Batch(batch) => batch.txs.iter().any(|tx| tx.elapsed() > tx_ttl),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolState {
     db_pool: ConnectionPool,
@@ -108,12 +120,146 @@ impl MempoolState {
             .collect_garbage()
             .await
             .expect("Db error");
+        // Transactions are stored before being acknowledged, so the pending ones
+        // are recovered from the database after the restart.
+        let pending_txs = storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_size()
+            .await
+            .expect("Db error");
+        vlog::info!(
+            "Recovered {} pending transactions from the database",
+            pending_txs
+        );
+    }
+
+    /// Returns the fee of each transaction in USD per chunk. The minimal number of chunks
+    /// is used, and the fees paid in the tokens without the known price are zero.
+    pub async fn fees_per_chunk(
+        &self,
+        txs: &VecDeque<SignedTxVariant>,
+    ) -> Result<Vec<Ratio<BigUint>>, TxAddError> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        // Price of the smallest unit of the token in USD.
+        let mut prices: HashMap<TokenId, Ratio<BigUint>> = HashMap::new();
+        let mut fees_per_chunk = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let mut fee_usd = Ratio::zero();
+            let mut chunks = 0;
+            for tx in tx.get_transactions() {
+                chunks += tx.tx.min_chunks();
+                let (token_id, fee) = match tx_fee(&tx.tx) {
+                    Some(fee) => fee,
+                    None => continue,
+                };
+                if !prices.contains_key(&token_id) {
+                    let price = Self::token_unit_price(&mut storage, token_id)
+                        .await
+                        .map_err(|_| TxAddError::DbError)?;
+                    prices.insert(token_id, price);
+                }
+                fee_usd += Ratio::from_integer(fee) * &prices[&token_id];
+            }
+            fees_per_chunk.push(fee_usd / BigUint::from(chunks.max(1)));
+        }
+
+        metrics::histogram!("mempool_state.fees_per_chunk", start.elapsed());
+        Ok(fees_per_chunk)
+    }
+
+    async fn token_unit_price(
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> QueryResult<Ratio<BigUint>> {
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(token_id))
+            .await?;
+        let price = storage
+            .tokens_schema()
+            .get_historical_ticker_price(token_id)
+            .await?;
+        Ok(match (token, price) {
+            (Some(token), Some(price)) => {
+                price.usd_price / BigUint::from(10u32).pow(u32::from(token.decimals))
+            }
+            _ => Ratio::zero(),
+        })
     }
 
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
 
+    /// Removes the pending transactions received more than `tx_ttl` ago and emits their events.
+    /// Batches are removed as a whole, and the transactions executed in the pending block
+    /// are kept, since they're removed once the block is committed.
+    pub async fn remove_expired_txs(
+        &self,
+        tx_ttl: Duration,
+        executed_txs: &[TxHash],
+    ) -> Result<(), TxAddError> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let expired_txs: Vec<SignedZkSyncTx> = transaction
+            .chain()
+            .mempool_schema()
+            .load_txs(executed_txs)
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .into_iter()
+            .filter(|variant| variant_expired(variant, tx_ttl))
+            .flat_map(|variant| variant.get_transactions())
+            .collect();
+        if expired_txs.is_empty() {
+            return Ok(());
+        }
+
+        let hashes: Vec<TxHash> = expired_txs.iter().map(|tx| tx.hash()).collect();
+        transaction
+            .chain()
+            .mempool_schema()
+            .remove_txs(&hashes)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        transaction
+            .event_schema()
+            .store_dropped_transaction_event(
+                &expired_txs,
+                &format!("Not included into a block in {} seconds", tx_ttl.as_secs()),
+            )
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        vlog::info!(
+            "Removed {} expired transactions from the mempool",
+            expired_txs.len()
+        );
+        metrics::counter!("mempool.expired_txs", expired_txs.len() as u64);
+        metrics::histogram!("mempool_state.remove_expired_txs", start.elapsed());
+        Ok(())
+    }
+
     pub async fn get_transaction_queue(
         &self,
         executed_txs: &[TxHash],
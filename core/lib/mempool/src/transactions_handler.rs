@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
 use futures::channel::{mpsc, oneshot};
-use futures::StreamExt;
+use tracing::Instrument;
 
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, QueryResult, StorageProcessor};
 use zksync_types::{
-    mempool::SignedTxsBatch,
-    tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    mempool::{SignedTxVariant, SignedTxsBatch},
+    tx::{error::TxAddError, TxEthSignature, TxHash},
+    AccountId, PriorityOp, SerialId, SignedZkSyncTx,
 };
+use zksync_utils::shutdown::ShutdownToken;
 
+use crate::inspection::{
+    account_nonces, inspect_account_txs, AccountNonces, CommittedAccountState, MempoolTxInfo,
+};
+use crate::limits::MempoolLimits;
+use crate::replacement::check_replacement;
 use crate::state::MempoolState;
 
 #[derive(Debug)]
@@ -23,6 +32,10 @@ pub enum MempoolTransactionRequest {
         bool,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Remove the pending priority ops received in the Ethereum blocks after the given one,
+    /// since these blocks were orphaned by the reorg. The serial ID of the last executed
+    /// priority op is returned, so the caller can tell whether the reverted ops were executed.
+    RevertPriorityOps(u64, oneshot::Sender<Result<Option<SerialId>, TxAddError>>),
     /// Add a new batch of transactions to the mempool. All transactions in batch must
     /// be either executed successfully, or otherwise fail all together.
     /// Invariants for each individual transaction in the batch are the same as in
@@ -32,6 +45,28 @@ pub enum MempoolTransactionRequest {
         Vec<TxEthSignature>,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Get the pending transactions of the account along with the reasons
+    /// they are not executed yet.
+    GetAccountTxs(
+        AccountId,
+        oneshot::Sender<Result<Vec<MempoolTxInfo>, TxAddError>>,
+    ),
+    /// Get the committed nonce of the account, the nonce its next transaction is expected
+    /// to use and the nonces of its pending transactions.
+    GetAccountNonces(
+        AccountId,
+        oneshot::Sender<Result<AccountNonces, TxAddError>>,
+    ),
+    /// Get the pending transaction with the given hash, `None` is returned
+    /// if there is no such transaction in the mempool.
+    GetTx(
+        TxHash,
+        oneshot::Sender<Result<Option<MempoolTxInfo>, TxAddError>>,
+    ),
+    /// Remove the pending transaction from the mempool. Transactions of the batch
+    /// can't be executed separately, so the whole batch is removed.
+    /// Hashes of the removed transactions are returned.
+    EvictTx(TxHash, oneshot::Sender<Result<Vec<TxHash>, TxAddError>>),
 }
 
 pub(crate) struct MempoolTransactionsHandler {
@@ -39,9 +74,44 @@ pub(crate) struct MempoolTransactionsHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolTransactionRequest>,
     pub max_block_size_chunks: usize,
+    pub limits: MempoolLimits,
 }
 
 impl MempoolTransactionsHandler {
+    /// Checks that the transactions of the accounts fit into the mempool.
+    async fn check_capacity(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        account_ids: &[AccountId],
+    ) -> Result<(), TxAddError> {
+        let mempool_size = storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_size()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        self.limits
+            .reloaded()
+            .check_mempool_size(mempool_size, account_ids.len())?;
+
+        let mut new_account_txs: HashMap<AccountId, usize> = HashMap::new();
+        for account_id in account_ids {
+            *new_account_txs.entry(*account_id).or_default() += 1;
+        }
+        for (account_id, new_txs) in new_account_txs {
+            let account_txs = storage
+                .chain()
+                .mempool_schema()
+                .get_account_txs_count(account_id)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            self.limits
+                .reloaded()
+                .check_account_txs(account_txs, new_txs)?;
+        }
+        Ok(())
+    }
+
     async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
         // Correctness should be checked by `signature_checker`, thus
         // `tx.check_correctness()` is not invoked here.
@@ -50,28 +120,48 @@ impl MempoolTransactionsHandler {
             TxAddError::DbError
         })?;
 
+        // Close operation does not exist so we will never met this error
+        let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
         let nonce = storage
             .chain()
             .account_schema()
-            // Close operation does not exist so we will never met this error
-            .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
+            .estimate_nonce(account_id)
             .await
             .map_err(|_| TxAddError::DbError)?
             .unwrap_or_default();
 
-        if tx.nonce() < nonce {
-            return Err(TxAddError::NonceMismatch);
-        }
+        self.limits.reloaded().check_nonce(tx.nonce(), nonce)?;
 
-        storage
+        let pending_txs = storage
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .load_txs_by_nonce(account_id, tx.nonce())
             .await
             .map_err(|err| {
                 vlog::error!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        if pending_txs.is_empty() {
+            // Replacement doesn't change the number of the pending transactions.
+            self.check_capacity(&mut storage, &[account_id]).await?;
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_tx(&tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        } else {
+            check_replacement(&tx, &pending_txs)?;
+            Self::replace_txs(&mut storage, &pending_txs, &tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
 
         let labels = vec![
             ("stage", "mempool".to_string()),
@@ -83,6 +173,42 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    /// Replaces the pending transactions with the same nonce by the transaction paying
+    /// a higher fee, and emits the events of the replaced transactions.
+    ///
+    /// Transactions may be already proposed to the state keeper, in this case they're executed
+    /// despite the replacement and the replacing transaction fails because of the nonce.
+    async fn replace_txs(
+        storage: &mut StorageProcessor<'_>,
+        pending_txs: &[SignedZkSyncTx],
+        tx: &SignedZkSyncTx,
+    ) -> QueryResult<()> {
+        let replaced_hashes: Vec<TxHash> = pending_txs.iter().map(|tx| tx.hash()).collect();
+
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .remove_txs(&replaced_hashes)
+            .await?;
+        transaction.chain().mempool_schema().insert_tx(tx).await?;
+        transaction
+            .event_schema()
+            .store_replaced_transaction_event(pending_txs, tx.hash())
+            .await?;
+        transaction.commit().await?;
+
+        vlog::info!(
+            "Replaced transactions {:?} in the mempool by {}",
+            replaced_hashes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            tx.hash().to_string()
+        );
+        Ok(())
+    }
+
     /// Add priority operations to the mempool. For a better UX, we save unconfirmed transactions
     /// to the database. And we will move them to the real queue when they are confirmed.
     async fn add_priority_ops(
@@ -149,22 +275,24 @@ impl MempoolTransactionsHandler {
             TxAddError::DbError
         })?;
 
+        let mut account_ids = Vec::with_capacity(txs.len());
         for tx in txs.iter() {
             // Correctness should be checked by `signature_checker`, thus
             // `tx.check_correctness()` is not invoked here.
+            // Close operation does not exist so we will never met this error
+            let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
             let nonce = storage
                 .chain()
                 .account_schema()
-                // Close operation does not exist so we will never met this error
-                .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
+                .estimate_nonce(account_id)
                 .await
                 .map_err(|_| TxAddError::DbError)?
                 .unwrap_or_default();
 
-            if tx.nonce() < nonce {
-                return Err(TxAddError::NonceMismatch);
-            }
+            self.limits.reloaded().check_nonce(tx.nonce(), nonce)?;
+            account_ids.push(account_id);
         }
+        self.check_capacity(&mut storage, &account_ids).await?;
 
         if self.mempool_state.chunks_for_batch(&batch).await? > self.max_block_size_chunks {
             return Err(TxAddError::BatchTooBig);
@@ -193,22 +321,209 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
-    pub async fn run(mut self) {
+    /// Loads the pending transactions along with the committed state of the account.
+    async fn load_account_txs(
+        storage: &mut StorageProcessor<'_>,
+        account_id: AccountId,
+    ) -> Result<(Vec<SignedTxVariant>, CommittedAccountState), TxAddError> {
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .load_txs(&[])
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .estimate_nonce(account_id)
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .unwrap_or_default();
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        Ok((txs.into(), CommittedAccountState { nonce, account }))
+    }
+
+    async fn account_txs(
+        &mut self,
+        account_id: AccountId,
+    ) -> Result<Vec<MempoolTxInfo>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let (txs, state) = Self::load_account_txs(&mut storage, account_id).await?;
+
+        Ok(inspect_account_txs(
+            account_id,
+            &txs,
+            &state,
+            Utc::now().timestamp() as u64,
+        ))
+    }
+
+    async fn account_nonces(&mut self, account_id: AccountId) -> Result<AccountNonces, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let (txs, state) = Self::load_account_txs(&mut storage, account_id).await?;
+
+        Ok(account_nonces(account_id, &txs, &state))
+    }
+
+    async fn tx_info(&mut self, tx_hash: TxHash) -> Result<Option<MempoolTxInfo>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let tx = storage
+            .chain()
+            .mempool_schema()
+            .get_tx(tx_hash.as_ref())
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let account_id = match tx.map(|tx| tx.account_id()) {
+            Some(Ok(account_id)) => account_id,
+            Some(Err(_)) => return Err(TxAddError::Other),
+            None => return Ok(None),
+        };
+
+        // Nonce gaps are only visible among all the pending transactions of the account.
+        let (txs, state) = Self::load_account_txs(&mut storage, account_id).await?;
+        let tx_info = inspect_account_txs(account_id, &txs, &state, Utc::now().timestamp() as u64)
+            .into_iter()
+            .find(|tx| tx.tx_hash == tx_hash);
+        Ok(tx_info)
+    }
+
+    async fn revert_priority_ops(
+        &mut self,
+        eth_block: u64,
+    ) -> Result<Option<SerialId>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .remove_priority_ops_after_eth_block(eth_block)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        let last_executed_priority_op = transaction
+            .chain()
+            .operations_schema()
+            .get_max_priority_op_serial_id()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        transaction
+            .commit()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        vlog::info!(
+            "Removed the priority operations received after the Ethereum block {}",
+            eth_block
+        );
+        Ok(last_executed_priority_op)
+    }
+
+    async fn evict_tx(&mut self, tx_hash: TxHash) -> Result<Vec<TxHash>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .load_txs(&[])
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        let hashes = match txs.iter().find(|tx| tx.hashes().contains(&tx_hash)) {
+            Some(tx) => tx.hashes(),
+            None => return Ok(Vec::new()),
+        };
+
+        // Transaction may be already proposed to the state keeper,
+        // in this case it's executed despite the eviction.
+        storage
+            .chain()
+            .mempool_schema()
+            .remove_txs(&hashes)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        vlog::info!(
+            "Evicted transactions from the mempool: {:?}",
+            hashes.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+
+        Ok(hashes)
+    }
+
+    pub async fn run(mut self, shutdown: ShutdownToken) {
         vlog::info!("Transaction mempool handler is running");
-        while let Some(request) = self.requests.next().await {
+        while let Some(request) = shutdown.recv(&mut self.requests).await {
             match request {
                 MempoolTransactionRequest::NewTx(tx, resp) => {
-                    let tx_add_result = self.add_tx(*tx).await;
+                    let span = tracing::info_span!(
+                        "mempool_add_tx",
+                        tx_hash = %tx.hash().to_string(),
+                    );
+                    let tx_add_result = self.add_tx(*tx).instrument(span).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
-                    let tx_add_result = self.add_batch(txs, eth_signatures).await;
+                    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.hash().to_string()).collect();
+                    let span = tracing::info_span!("mempool_add_batch", tx_hashes = ?tx_hashes);
+                    let tx_add_result = self.add_batch(txs, eth_signatures).instrument(span).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewPriorityOps(ops, confirmed, resp) => {
                     let tx_add_result = self.add_priority_ops(ops, confirmed).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
+                MempoolTransactionRequest::RevertPriorityOps(eth_block, resp) => {
+                    let result = self.revert_priority_ops(eth_block).await;
+                    resp.send(result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::GetAccountTxs(account_id, resp) => {
+                    let result = self.account_txs(account_id).await;
+                    resp.send(result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::GetAccountNonces(account_id, resp) => {
+                    let result = self.account_nonces(account_id).await;
+                    resp.send(result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::GetTx(tx_hash, resp) => {
+                    let result = self.tx_info(tx_hash).await;
+                    resp.send(result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::EvictTx(tx_hash, resp) => {
+                    let result = self.evict_tx(tx_hash).await;
+                    resp.send(result).unwrap_or_default();
+                }
             }
         }
     }
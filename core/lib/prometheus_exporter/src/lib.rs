@@ -14,6 +14,8 @@ use zksync_types::aggregated_operations::AggregatedActionType::*;
 use zksync_types::block::IncompleteBlock;
 use zksync_types::{ExecutedOperations, TokenId};
 
+mod process_metrics;
+
 const QUERY_INTERVAL: Duration = Duration::from_secs(30);
 
 pub fn run_operation_counter(connection_pool: ConnectionPool) -> JoinHandle<()> {
@@ -111,13 +113,17 @@ fn token_amount_to_usd(amount: BigUint, decimals: u8, usd_price: Ratio<BigUint>)
     Ratio::from(amount) / BigUint::from(10u32).pow(u32::from(decimals)) * usd_price
 }
 
-pub fn run_prometheus_exporter(port: u16) -> JoinHandle<()> {
+/// Runs the Prometheus exporter server on the port, along with the metrics of the process
+/// labeled with the name of the binary. Has to be called once per process.
+pub fn run_prometheus_exporter(binary: &'static str, port: u16) -> JoinHandle<()> {
     let addr = ([0, 0, 0, 0], port);
     let (recorder, exporter) = PrometheusBuilder::new()
         .listen_address(addr)
         .build_with_exporter()
         .expect("failed to install Prometheus recorder");
     metrics::set_boxed_recorder(Box::new(recorder)).expect("failed to set metrics recorder");
+    // Collector is stopped along with the runtime, its failures don't affect the exporter.
+    process_metrics::run_process_metrics_collector(binary);
 
     tokio::spawn(async move {
         tokio::pin!(exporter);
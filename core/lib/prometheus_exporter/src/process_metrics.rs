@@ -0,0 +1,93 @@
+//! Metrics of the process the exporter runs in, the same for every binary: CPU time, memory usage,
+//! number of the threads and of the open file descriptors, and uptime.
+//!
+//! Metrics are read from `/proc/self`, so they are only reported on Linux.
+
+// Built-in uses
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+// External uses
+use tokio::{task::JoinHandle, time::sleep};
+
+const COLLECT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Clock ticks per second the CPU time is measured in, it's 100 on all the supported platforms.
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+const PAGE_SIZE: f64 = 4096.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcessStat {
+    cpu_seconds: f64,
+    threads: u64,
+    virtual_memory_bytes: f64,
+    resident_memory_bytes: f64,
+}
+
+/// Parses the contents of `/proc/self/stat`, see `man 5 proc` for the format.
+fn parse_stat(stat: &str) -> Option<ProcessStat> {
+    // Process name may contain spaces and parentheses, so the fields after it are found
+    // by the last parenthesis. The first of them is the 3rd field of the line, process state.
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    let field = |index: usize| -> Option<u64> { fields.get(index - 3)?.parse().ok() };
+
+    let cpu_ticks = field(14)? + field(15)?;
+    Some(ProcessStat {
+        cpu_seconds: cpu_ticks as f64 / CLOCK_TICKS_PER_SECOND,
+        threads: field(20)?,
+        virtual_memory_bytes: field(23)? as f64,
+        resident_memory_bytes: field(24)? as f64 * PAGE_SIZE,
+    })
+}
+
+fn collect_process_metrics(binary: &'static str, started_at: Instant) {
+    metrics::gauge!("process.uptime_seconds", started_at.elapsed().as_secs_f64(), "binary" => binary);
+
+    let stat = fs::read_to_string("/proc/self/stat")
+        .ok()
+        .and_then(|stat| parse_stat(&stat));
+    if let Some(stat) = stat {
+        metrics::gauge!("process.cpu_seconds", stat.cpu_seconds, "binary" => binary);
+        metrics::gauge!("process.threads", stat.threads as f64, "binary" => binary);
+        metrics::gauge!("process.virtual_memory_bytes", stat.virtual_memory_bytes, "binary" => binary);
+        metrics::gauge!("process.resident_memory_bytes", stat.resident_memory_bytes, "binary" => binary);
+    }
+
+    if let Ok(fds) = fs::read_dir("/proc/self/fd") {
+        metrics::gauge!("process.open_fds", fds.count() as f64, "binary" => binary);
+    }
+}
+
+/// Periodically reports the metrics of the process, labeled with the name of the binary.
+pub(crate) fn run_process_metrics_collector(binary: &'static str) -> JoinHandle<()> {
+    let started_at = Instant::now();
+    tokio::spawn(async move {
+        loop {
+            collect_process_metrics(binary, started_at);
+            sleep(COLLECT_INTERVAL).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_stat() {
+        let stat = "4242 (zksync (server)) S 1 4242 4242 0 -1 4194560 33526 0 0 0 \
+                    1250 250 0 0 20 0 31 0 1766 2147483648 8192 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(ProcessStat {
+                cpu_seconds: 15.0,
+                threads: 31,
+                virtual_memory_bytes: 2_147_483_648.0,
+                resident_memory_bytes: 8192.0 * PAGE_SIZE,
+            })
+        );
+
+        assert_eq!(parse_stat("4242 (server) S 1"), None);
+    }
+}
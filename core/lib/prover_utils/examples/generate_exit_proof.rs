@@ -1,16 +1,21 @@
-//! Generate exit proof for exodus mode given account and token
-//! correct verified state should be present in the db (could be restored using `data-restore` module)
+//! Generate exit proofs for exodus mode given account and token, or for all the tokens of the account
+//! correct verified state should be present in the db (could be restored using `data-restore` module
+//! from the Ethereum node or imported from the snapshot using `state_snapshot` module)
 
 use serde::Serialize;
+use std::path::PathBuf;
 use std::time::Instant;
 use structopt::StructOpt;
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_crypto::proof::EncodedSingleProof;
+use zksync_prover_utils::exit_proof::ExitProofGenerator;
 use zksync_storage::{ConnectionPool, StorageProcessor};
-use zksync_types::{block::Block, AccountId, Address, BlockNumber, TokenId, TokenLike, H256, NFT};
+use zksync_types::{
+    block::Block, AccountId, Address, BlockNumber, Token, TokenId, TokenLike, H256, NFT,
+};
 use zksync_utils::BigUintSerdeWrapper;
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 struct StoredBlockInfo {
     block_number: BlockNumber,
@@ -69,9 +74,14 @@ struct Opt {
     #[structopt(long)]
     address: Address,
 
-    /// Token to withdraw - "ETH" or address of the ERC20 token
+    /// Token to withdraw - "ETH" or address of the ERC20 token,
+    /// proofs for all the tokens of the account are generated if not set
+    #[structopt(long)]
+    token: Option<String>,
+
+    /// Path of the file to write the exit transaction inputs to, in JSON
     #[structopt(long)]
-    token: String,
+    output: Option<PathBuf>,
 }
 
 async fn get_nft_info(storage: &mut StorageProcessor<'_>, nft: Option<NFT>) -> NFTInfo {
@@ -103,6 +113,64 @@ async fn get_nft_info(storage: &mut StorageProcessor<'_>, nft: Option<NFT>) -> N
     }
 }
 
+async fn load_token(storage: &mut StorageProcessor<'_>, token: TokenLike) -> Token {
+    storage
+        .tokens_schema()
+        .get_token(token)
+        .await
+        .expect("Db access fail")
+        .expect(
+            "Token not found. If you're addressing an ERC-20 token by it's symbol, \
+              it may not be available after data restore. Try using token address in that case",
+        )
+}
+
+async fn create_exit_proof_data(
+    storage: &mut StorageProcessor<'_>,
+    generator: &mut ExitProofGenerator,
+    stored_block_info: &StoredBlockInfo,
+    account_id: AccountId,
+    address: Address,
+    token: Token,
+) -> ExitProofData {
+    let token_id = token.id;
+    let (proof, amount, nft) = if token_id.0 < MIN_NFT_TOKEN_ID {
+        let (proof, amount) = generator
+            .create_exit_proof_fungible(token_id)
+            .expect("Failed to generate exit proof");
+
+        (proof, amount, None)
+    } else {
+        let nft = storage
+            .tokens_schema()
+            .get_nft(token_id)
+            .await
+            .expect("Db access fail")
+            .expect("NFT token should exist");
+        let (proof, amount) = generator
+            .create_exit_proof_nft(token_id, nft.creator_id, nft.serial_id, nft.content_hash)
+            .expect("Failed to generate exit proof");
+
+        (proof, amount, Some(nft))
+    };
+
+    let nft_info = get_nft_info(storage, nft).await;
+
+    ExitProofData {
+        stored_block_info: stored_block_info.clone(),
+        owner: address,
+        token_id,
+        account_id,
+        nft_creator_id: nft_info.creator_id,
+        nft_creator_address: nft_info.creator_address,
+        nft_serial_id: nft_info.serial_id,
+        nft_content_hash: nft_info.content_hash,
+        amount: amount.into(),
+        proof,
+        token_address: token.address,
+    }
+}
+
 #[tokio::main]
 async fn main() {
     vlog::init();
@@ -110,7 +178,6 @@ async fn main() {
     let opt = Opt::from_args();
 
     let address = opt.address;
-    let token = TokenLike::parse(&opt.token);
 
     let timer = Instant::now();
     vlog::info!("Restoring state from db");
@@ -120,18 +187,6 @@ async fn main() {
         .await
         .expect("Storage access failed");
 
-    let token_info = storage
-        .tokens_schema()
-        .get_token(token)
-        .await
-        .expect("Db access fail")
-        .expect(
-            "Token not found. If you're addressing an ERC-20 token by it's symbol, \
-              it may not be available after data restore. Try using token address in that case",
-        );
-    let token_id = token_info.id;
-    let token_address = token_info.address;
-
     let account_id = storage
         .chain()
         .account_schema()
@@ -165,61 +220,69 @@ async fn main() {
 
     vlog::info!("Restored state from db: {} s", timer.elapsed().as_secs());
 
-    let (proof, amount, nft) = if token_id.0 < MIN_NFT_TOKEN_ID {
-        let (proof, amount) = zksync_prover_utils::exit_proof::create_exit_proof_fungible(
-            accounts, account_id, address, token_id,
-        )
-        .expect("Failed to generate exit proof");
+    let mut generator = ExitProofGenerator::new(accounts, account_id, address)
+        .expect("Failed to build the accounts tree");
 
-        (proof, amount, None)
-    } else {
-        let nft = storage
-            .tokens_schema()
-            .get_nft(token_id)
-            .await
-            .expect("Db access fail")
-            .expect("NFT token should exist");
-        let (proof, amount) = zksync_prover_utils::exit_proof::create_exit_proof_nft(
-            accounts,
+    let tokens = match &opt.token {
+        Some(token) => vec![load_token(&mut storage, TokenLike::parse(token)).await],
+        None => {
+            let mut tokens = Vec::new();
+            for token_id in generator.nonzero_balance_tokens() {
+                tokens.push(load_token(&mut storage, TokenLike::Id(token_id)).await);
+            }
+            tokens
+        }
+    };
+    if tokens.is_empty() {
+        println!("The account has no balances to withdraw");
+        return;
+    }
+
+    let mut proofs = Vec::with_capacity(tokens.len());
+    let tokens_count = tokens.len();
+    for (i, token) in tokens.into_iter().enumerate() {
+        println!(
+            "[{}/{}] Generating the exit proof for the token {} (id {})",
+            i + 1,
+            tokens_count,
+            token.symbol,
+            *token.id
+        );
+        let proof_data = create_exit_proof_data(
+            &mut storage,
+            &mut generator,
+            &stored_block_info,
             account_id,
             address,
-            token_id,
-            nft.creator_id,
-            nft.serial_id,
-            nft.content_hash,
+            token,
         )
-        .expect("Failed to generate exit proof");
-
-        (proof, amount, Some(nft))
-    };
-
-    let nft_info = get_nft_info(&mut storage, nft).await;
+        .await;
+        proofs.push(proof_data);
+    }
 
-    let proof_data = ExitProofData {
-        stored_block_info,
-        owner: address,
-        token_id,
-        account_id,
-        nft_creator_id: nft_info.creator_id,
-        nft_creator_address: nft_info.creator_address,
-        nft_serial_id: nft_info.serial_id,
-        nft_content_hash: nft_info.content_hash,
-        amount: amount.into(),
-        proof,
-        token_address,
-    };
+    // The inputs for the single token are kept as an object for the compatibility.
+    let proofs_json = if opt.token.is_some() {
+        serde_json::to_string_pretty(&proofs[0])
+    } else {
+        serde_json::to_string_pretty(&proofs)
+    }
+    .expect("proof data serialize");
 
     println!("\n\n");
     println!("==========================");
-    println!("Generating proof completed");
-    println!("Below you can see the input data for the exit transaction on zkSync contract");
+    println!("Generating proofs completed");
+    println!("Below you can see the input data for the exit transactions on zkSync contract");
     println!("Look up the manuals of your desired smart wallet in order to know how to sign and send this transaction to the Ethereum");
     println!("==========================");
 
-    println!("Exit transaction inputs:");
-
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&proof_data).expect("proof data serialize")
-    );
+    if let Some(output) = opt.output {
+        std::fs::write(&output, proofs_json).expect("Failed to write the exit transaction inputs");
+        println!(
+            "Exit transaction inputs are written to {}",
+            output.display()
+        );
+    } else {
+        println!("Exit transaction inputs:");
+        println!("{}", proofs_json);
+    }
 }
@@ -13,6 +13,10 @@ pub struct ProverInputRequest {
 pub struct ProverInputRequestAuxData {
     pub prefer_aggregated_proof: Option<bool>,
     pub preferred_block_size: Option<usize>,
+    /// Block sizes the prover is able to prove, the single proof jobs for other
+    /// block sizes aren't handed to it. Empty list means any block size.
+    #[serde(default)]
+    pub block_sizes: Vec<usize>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
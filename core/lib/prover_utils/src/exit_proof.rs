@@ -8,57 +8,115 @@ use zksync_circuit::exit_circuit::create_exit_circuit_with_public_input;
 use zksync_crypto::circuit::account::CircuitAccount;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_crypto::proof::EncodedSingleProof;
-use zksync_types::{AccountId, AccountMap, Address, TokenId, H256};
+use zksync_types::{Account, AccountId, AccountMap, Address, TokenId, H256};
 
-fn create_exit_proof(
-    accounts: AccountMap,
+/// Generator of the exit proofs for the tokens of the account.
+///
+/// Building the accounts tree takes a while if there are many accounts, so the tree
+/// is built once and used to generate the exit proofs for all the tokens of the account.
+pub struct ExitProofGenerator {
+    circuit_account_tree: CircuitAccountTree,
+    account: Account,
     account_id: AccountId,
-    owner: Address,
-    token_id: TokenId,
-    nft_creator_id: AccountId,
-    nft_serial_id: u32,
-    nft_content_hash: H256,
-) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
-    let timer = Instant::now();
-    let mut circuit_account_tree =
-        CircuitAccountTree::new(zksync_crypto::params::account_tree_depth());
+}
 
-    let mut target_account = None;
-    for (id, account) in accounts {
-        if id == account_id {
-            target_account = Some(account.clone());
+impl ExitProofGenerator {
+    pub fn new(
+        accounts: AccountMap,
+        account_id: AccountId,
+        owner: Address,
+    ) -> Result<Self, anyhow::Error> {
+        let timer = Instant::now();
+        let mut circuit_account_tree =
+            CircuitAccountTree::new(zksync_crypto::params::account_tree_depth());
+
+        let mut target_account = None;
+        for (id, account) in accounts {
+            if id == account_id {
+                target_account = Some(account.clone());
+            }
+            circuit_account_tree.insert(*id, CircuitAccount::from(account));
         }
-        circuit_account_tree.insert(*id, CircuitAccount::from(account));
-    }
 
-    let balance = target_account
-        .map(|acc| acc.get_balance(token_id))
-        .ok_or_else(|| {
+        let account = target_account.ok_or_else(|| {
             format_err!(
                 "Fund account not found: id: {}, address: 0x{:x}",
                 *account_id,
                 owner
             )
         })?;
+        vlog::info!("Accounts tree built: {} s", timer.elapsed().as_secs());
 
-    let zksync_exit_circuit = create_exit_circuit_with_public_input(
-        &mut circuit_account_tree,
-        account_id,
-        token_id,
-        nft_creator_id,
-        nft_serial_id,
-        nft_content_hash,
-    );
-    let commitment = zksync_exit_circuit
-        .pub_data_commitment
-        .expect("Witness should contract commitment");
-    vlog::info!("Proof commitment: {:?}", commitment);
+        Ok(Self {
+            circuit_account_tree,
+            account,
+            account_id,
+        })
+    }
+
+    /// Returns the tokens the account has non-zero balance in.
+    pub fn nonzero_balance_tokens(&self) -> Vec<TokenId> {
+        let mut tokens: Vec<_> = self
+            .account
+            .get_nonzero_balances()
+            .into_iter()
+            .map(|(token_id, _)| token_id)
+            .collect();
+        tokens.sort_unstable();
+        tokens
+    }
+
+    pub fn create_exit_proof_fungible(
+        &mut self,
+        token_id: TokenId,
+    ) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
+        self.create_exit_proof(
+            token_id,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    pub fn create_exit_proof_nft(
+        &mut self,
+        token_id: TokenId,
+        creator_id: AccountId,
+        serial_id: u32,
+        content_hash: H256,
+    ) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
+        self.create_exit_proof(token_id, creator_id, serial_id, content_hash)
+    }
 
-    let proof = gen_verified_proof_for_exit_circuit(zksync_exit_circuit)
-        .map_err(|e| format_err!("Failed to generate proof: {}", e))?;
+    fn create_exit_proof(
+        &mut self,
+        token_id: TokenId,
+        nft_creator_id: AccountId,
+        nft_serial_id: u32,
+        nft_content_hash: H256,
+    ) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
+        let timer = Instant::now();
+        let balance = self.account.get_balance(token_id);
 
-    vlog::info!("Exit proof created: {} s", timer.elapsed().as_secs());
-    Ok((proof.serialize_single_proof(), balance))
+        let zksync_exit_circuit = create_exit_circuit_with_public_input(
+            &mut self.circuit_account_tree,
+            self.account_id,
+            token_id,
+            nft_creator_id,
+            nft_serial_id,
+            nft_content_hash,
+        );
+        let commitment = zksync_exit_circuit
+            .pub_data_commitment
+            .expect("Witness should contract commitment");
+        vlog::info!("Proof commitment: {:?}", commitment);
+
+        let proof = gen_verified_proof_for_exit_circuit(zksync_exit_circuit)
+            .map_err(|e| format_err!("Failed to generate proof: {}", e))?;
+
+        vlog::info!("Exit proof created: {} s", timer.elapsed().as_secs());
+        Ok((proof.serialize_single_proof(), balance))
+    }
 }
 
 pub fn create_exit_proof_fungible(
@@ -67,15 +125,7 @@ pub fn create_exit_proof_fungible(
     owner: Address,
     token_id: TokenId,
 ) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
-    create_exit_proof(
-        accounts,
-        account_id,
-        owner,
-        token_id,
-        Default::default(),
-        Default::default(),
-        Default::default(),
-    )
+    ExitProofGenerator::new(accounts, account_id, owner)?.create_exit_proof_fungible(token_id)
 }
 
 pub fn create_exit_proof_nft(
@@ -87,10 +137,7 @@ pub fn create_exit_proof_nft(
     serial_id: u32,
     content_hash: H256,
 ) -> Result<(EncodedSingleProof, BigUint), anyhow::Error> {
-    create_exit_proof(
-        accounts,
-        account_id,
-        owner,
+    ExitProofGenerator::new(accounts, account_id, owner)?.create_exit_proof_nft(
         token_id,
         creator_id,
         serial_id,
@@ -2,6 +2,7 @@ pub mod handler;
 pub mod state;
 
 pub mod error;
+mod parallel;
 #[cfg(test)]
 pub mod tests;
 
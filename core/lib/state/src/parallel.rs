@@ -0,0 +1,227 @@
+//! Parallel execution of the transactions touching disjoint sets of accounts.
+//!
+//! Transactions are grouped by the accounts they touch: two transactions touching the same account
+//! get into the same group, so the groups are independent of each other and can be executed
+//! concurrently, each one on its own copy of the touched part of the state. Transactions within
+//! the group are executed in their original order.
+//!
+//! Transactions that may create an account, or refer to an account that doesn't exist yet, always
+//! get into the same group, so the new accounts get the same IDs as in the sequential execution.
+//! Once all the groups are executed, the updates are applied to the state in the original order
+//! of the transactions, so the result is exactly the one of the sequential execution.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use rayon::prelude::*;
+
+// Workspace uses
+use zksync_crypto::params::NFT_STORAGE_ACCOUNT_ID;
+use zksync_types::{AccountId, Address, TokenId, ZkSyncTx};
+
+// Local uses
+use crate::{
+    error::OpError,
+    state::{OpSuccess, ZkSyncState},
+};
+
+/// Account the transaction depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TouchedAccount {
+    Existing(AccountId),
+    /// Account which doesn't exist in the state before the execution.
+    New,
+}
+
+/// Transactions depending on each other, along with the part of the state they need.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TxGroup {
+    /// Indices of the transactions in the original order.
+    pub txs: Vec<usize>,
+    pub accounts: Vec<AccountId>,
+    pub nft_tokens: Vec<TokenId>,
+}
+
+impl ZkSyncState {
+    fn touched_account_by_id(&self, account_id: AccountId) -> TouchedAccount {
+        // NFT storage account is created by the first `MintNFT`, but is never assigned a free ID.
+        if account_id == NFT_STORAGE_ACCOUNT_ID || self.get_account(account_id).is_some() {
+            TouchedAccount::Existing(account_id)
+        } else {
+            TouchedAccount::New
+        }
+    }
+
+    fn touched_account_by_address(&self, address: &Address) -> TouchedAccount {
+        self.get_account_id_by_address(address)
+            .map_or(TouchedAccount::New, TouchedAccount::Existing)
+    }
+
+    /// Returns the accounts the transaction reads or updates, and the NFTs it reads.
+    fn touched_by_tx(&self, tx: &ZkSyncTx) -> (Vec<TouchedAccount>, Vec<TokenId>) {
+        let mut nft_tokens = Vec::new();
+        let accounts = match tx {
+            ZkSyncTx::Transfer(tx) => vec![
+                self.touched_account_by_id(tx.account_id),
+                self.touched_account_by_address(&tx.from),
+                self.touched_account_by_address(&tx.to),
+            ],
+            ZkSyncTx::Withdraw(tx) => vec![
+                self.touched_account_by_id(tx.account_id),
+                self.touched_account_by_address(&tx.from),
+            ],
+            ZkSyncTx::Close(tx) => vec![self.touched_account_by_address(&tx.account)],
+            ZkSyncTx::ChangePubKey(tx) => vec![
+                self.touched_account_by_id(tx.account_id),
+                self.touched_account_by_address(&tx.account),
+            ],
+            ZkSyncTx::ForcedExit(tx) => vec![
+                self.touched_account_by_id(tx.initiator_account_id),
+                self.touched_account_by_address(&tx.target),
+            ],
+            ZkSyncTx::Swap(tx) => vec![
+                self.touched_account_by_id(tx.submitter_id),
+                self.touched_account_by_address(&tx.submitter_address),
+                self.touched_account_by_id(tx.orders.0.account_id),
+                self.touched_account_by_address(&tx.orders.0.recipient_address),
+                self.touched_account_by_id(tx.orders.1.account_id),
+                self.touched_account_by_address(&tx.orders.1.recipient_address),
+            ],
+            // NFTs are minted and withdrawn in the order they're minted in, so all the NFT
+            // operations depend on the NFT storage account.
+            ZkSyncTx::MintNFT(tx) => vec![
+                self.touched_account_by_id(tx.creator_id),
+                self.touched_account_by_address(&tx.creator_address),
+                self.touched_account_by_address(&tx.recipient),
+                TouchedAccount::Existing(NFT_STORAGE_ACCOUNT_ID),
+            ],
+            ZkSyncTx::WithdrawNFT(tx) => {
+                nft_tokens.push(tx.token);
+                let mut accounts = vec![
+                    self.touched_account_by_id(tx.account_id),
+                    self.touched_account_by_address(&tx.from),
+                    TouchedAccount::Existing(NFT_STORAGE_ACCOUNT_ID),
+                ];
+                if let Some(nft) = self.nfts.get(&tx.token) {
+                    accounts.push(self.touched_account_by_address(&nft.creator_address));
+                }
+                accounts
+            }
+        };
+        (accounts, nft_tokens)
+    }
+
+    /// Groups the transactions so that the transactions of the different groups don't touch
+    /// the same accounts. Groups are ordered by their first transaction.
+    pub(crate) fn group_txs(&self, txs: &[ZkSyncTx]) -> Vec<TxGroup> {
+        // Disjoint set of the transaction indices, each set is represented by its lowest index.
+        fn root(parents: &mut [usize], mut index: usize) -> usize {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        }
+
+        let touched: Vec<_> = txs.iter().map(|tx| self.touched_by_tx(tx)).collect();
+
+        let mut parents: Vec<usize> = (0..txs.len()).collect();
+        let mut first_tx_by_account = HashMap::new();
+        for (index, (accounts, _)) in touched.iter().enumerate() {
+            for account in accounts {
+                let first_tx = *first_tx_by_account.entry(*account).or_insert(index);
+                let (tx_root, other_root) =
+                    (root(&mut parents, index), root(&mut parents, first_tx));
+                let (min_root, max_root) = if tx_root < other_root {
+                    (tx_root, other_root)
+                } else {
+                    (other_root, tx_root)
+                };
+                parents[max_root] = min_root;
+            }
+        }
+
+        let mut groups: Vec<TxGroup> = Vec::new();
+        let mut group_by_root = HashMap::new();
+        for (index, (accounts, nft_tokens)) in touched.into_iter().enumerate() {
+            let group_root = root(&mut parents, index);
+            let group_index = *group_by_root.entry(group_root).or_insert_with(|| {
+                groups.push(TxGroup::default());
+                groups.len() - 1
+            });
+            let group = &mut groups[group_index];
+            group.txs.push(index);
+            group
+                .accounts
+                .extend(accounts.into_iter().filter_map(|account| match account {
+                    TouchedAccount::Existing(account_id) => Some(account_id),
+                    TouchedAccount::New => None,
+                }));
+            group.nft_tokens.extend(nft_tokens);
+        }
+        for group in &mut groups {
+            group.accounts.sort_unstable();
+            group.accounts.dedup();
+            group.nft_tokens.sort_unstable();
+            group.nft_tokens.dedup();
+        }
+        groups
+    }
+
+    /// Executes the transactions the same way as `execute_tx` does one by one, but the transactions
+    /// touching the disjoint sets of accounts are executed concurrently on the rayon thread pool.
+    ///
+    /// Results are returned in the order of the transactions, the failed transactions don't change the state.
+    pub fn execute_txs_parallel(
+        &mut self,
+        txs: &[ZkSyncTx],
+        block_timestamp: u64,
+    ) -> Vec<Result<OpSuccess, OpError>> {
+        let start = std::time::Instant::now();
+
+        let groups = self.group_txs(txs);
+        if groups.len() < 2 {
+            return txs
+                .iter()
+                .map(|tx| self.execute_tx(tx.clone(), block_timestamp))
+                .collect();
+        }
+        metrics::histogram!("state.execute_txs_parallel.groups", groups.len() as f64);
+
+        let empty = ZkSyncState::empty();
+        let state = &*self;
+        let group_results: Vec<Vec<_>> = groups
+            .par_iter()
+            .map(|group| {
+                let mut group_state =
+                    state.partial_copy(&empty, &group.accounts, &group.nft_tokens);
+                group
+                    .txs
+                    .iter()
+                    .map(|&index| {
+                        let result = group_state.execute_tx(txs[index].clone(), block_timestamp);
+                        (index, result)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut results: Vec<Option<Result<OpSuccess, OpError>>> =
+            (0..txs.len()).map(|_| None).collect();
+        for (index, result) in group_results.into_iter().flatten() {
+            results[index] = Some(result);
+        }
+
+        let results: Vec<_> = results
+            .into_iter()
+            .map(|result| result.expect("each transaction belongs to a group"))
+            .collect();
+        for success in results.iter().flatten() {
+            self.apply_account_updates(success.updates.clone());
+        }
+
+        metrics::histogram!("state.execute_txs_parallel", start.elapsed());
+        results
+    }
+}
@@ -290,6 +290,12 @@ impl ZkSyncState {
         }
     }
 
+    /// Executes the transactions of the batch one by one. If any of them fails, the whole batch
+    /// is reverted and every transaction gets the same error.
+    ///
+    /// Since the batch is atomic, its fee may be paid by any of its transactions. This way
+    /// a sponsor can cover the fees of the other senders: their transactions have zero fee,
+    /// and the sponsor's one (e.g. a transfer to itself) pays the fee for the whole batch.
     pub fn execute_txs_batch(
         &mut self,
         txs: &[SignedZkSyncTx],
@@ -351,6 +357,35 @@ impl ZkSyncState {
         self.next_free_id
     }
 
+    pub(crate) fn get_account_id_by_address(&self, address: &Address) -> Option<AccountId> {
+        self.account_id_by_address.get(address).copied()
+    }
+
+    /// Creates the copy of the state containing only the given accounts and NFTs, which
+    /// assigns the same IDs to the new accounts as this state does.
+    ///
+    /// `empty` is the empty state the copy is made of, so the empty tree isn't rehashed for every copy.
+    pub(crate) fn partial_copy(
+        &self,
+        empty: &Self,
+        account_ids: &[AccountId],
+        nft_tokens: &[TokenId],
+    ) -> Self {
+        let mut copy = empty.clone();
+        for &account_id in account_ids {
+            if let Some(account) = self.get_account(account_id) {
+                copy.insert_account(account_id, account);
+            }
+        }
+        for token_id in nft_tokens {
+            if let Some(nft) = self.nfts.get(token_id) {
+                copy.nfts.insert(*token_id, nft.clone());
+            }
+        }
+        copy.next_free_id = self.next_free_id;
+        copy
+    }
+
     pub fn collect_fee(&mut self, fees: &[CollectedFee], fee_account: AccountId) -> AccountUpdates {
         let mut updates = Vec::new();
 
@@ -571,11 +606,15 @@ mod tests {
             tx: ZkSyncTx::Withdraw(Box::new(withdraw1)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         let signed_zk_sync_tx2 = SignedZkSyncTx {
             tx: ZkSyncTx::Withdraw(Box::new(withdraw2)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         tb.test_txs_batch_fail(
             &[signed_zk_sync_tx1, signed_zk_sync_tx2],
@@ -627,11 +666,15 @@ mod tests {
             tx: ZkSyncTx::Transfer(Box::new(transfer_1)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         let signed_zk_sync_tx2 = SignedZkSyncTx {
             tx: ZkSyncTx::Transfer(Box::new(transfer_2)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         tb.test_txs_batch_fail(
             &[signed_zk_sync_tx1, signed_zk_sync_tx2],
@@ -639,6 +682,74 @@ mod tests {
         );
     }
 
+    /// Checks that the fee of the batch can be paid by the sponsor instead of the sender.
+    #[test]
+    fn execute_sponsored_txs_batch() {
+        let (token_id, fee_token_id) = (TokenId(1), TokenId(0));
+        let mut tb = PlasmaTestBuilder::new();
+
+        // Sender has no funds in the token the fee is paid in.
+        let (sender_id, sender, sender_sk) = tb.add_account(Unlocked);
+        tb.set_balance(sender_id, token_id, BigUint::from(100u32));
+        let (sponsor_id, sponsor, sponsor_sk) = tb.add_account(Unlocked);
+        tb.set_balance(sponsor_id, fee_token_id, BigUint::from(10u32));
+        let (recipient_id, recipient, _) = tb.add_account(Unlocked);
+
+        let transfer = Transfer::new_signed(
+            sender_id,
+            sender.address,
+            recipient.address,
+            token_id,
+            BigUint::from(100u32),
+            BigUint::from(0u32),
+            sender.nonce,
+            Default::default(),
+            &sender_sk,
+        )
+        .unwrap();
+        let fee_transfer = Transfer::new_signed(
+            sponsor_id,
+            sponsor.address,
+            sponsor.address,
+            fee_token_id,
+            BigUint::from(0u32),
+            BigUint::from(10u32),
+            sponsor.nonce,
+            Default::default(),
+            &sponsor_sk,
+        )
+        .unwrap();
+        let txs: Vec<_> = vec![
+            ZkSyncTx::Transfer(Box::new(transfer)),
+            ZkSyncTx::Transfer(Box::new(fee_transfer)),
+        ]
+        .into_iter()
+        .map(SignedZkSyncTx::from)
+        .collect();
+
+        let results = tb.state.execute_txs_batch(&txs, tb.block_timestamp);
+        let fees: Vec<_> = results
+            .into_iter()
+            .map(|result| result.expect("sponsored batch failed").fee)
+            .collect();
+        assert_eq!(fees[0].as_ref().unwrap().amount, BigUint::from(0u32));
+        assert_eq!(fees[1].as_ref().unwrap().amount, BigUint::from(10u32));
+        assert_eq!(fees[1].as_ref().unwrap().token, fee_token_id);
+
+        let balance = |state: &ZkSyncState, account_id, token_id| {
+            state.get_account(account_id).unwrap().get_balance(token_id)
+        };
+        assert_eq!(balance(&tb.state, sender_id, token_id), BigUint::from(0u32));
+        assert_eq!(
+            balance(&tb.state, recipient_id, token_id),
+            BigUint::from(100u32)
+        );
+        assert_eq!(
+            balance(&tb.state, sponsor_id, fee_token_id),
+            BigUint::from(0u32)
+        );
+    }
+
     /// Checks if execute_txs_batch executes normally with valid operations.
     #[test]
     fn execute_txs_batch_success() {
@@ -676,11 +787,15 @@ mod tests {
             tx: ZkSyncTx::Withdraw(Box::new(withdraw1)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         let signed_zk_sync_tx2 = SignedZkSyncTx {
             tx: ZkSyncTx::Withdraw(Box::new(withdraw2)),
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         };
         let expected_updates = vec![
             (
@@ -1,5 +1,6 @@
 mod collect_fee;
 mod operations;
+mod parallel;
 mod timestamp;
 
 use crate::state::ZkSyncState;
@@ -260,6 +260,8 @@ fn mint_token_to_new_account() {
         tx: ZkSyncTx::Transfer(Box::new(transfer_1)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
 
     let new_id = tb.state.get_free_account_id();
@@ -296,6 +298,8 @@ fn mint_token_to_new_account() {
         tx: ZkSyncTx::MintNFT(Box::new(mint_nft)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
 
     tb.test_txs_batch_success(
@@ -334,16 +334,22 @@ fn execute_txs_batch_success_transfers() {
         tx: ZkSyncTx::Transfer(Box::new(transfer_1)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let signed_zk_sync_tx2 = SignedZkSyncTx {
         tx: ZkSyncTx::Transfer(Box::new(transfer_2)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let signed_zk_sync_tx_bad = SignedZkSyncTx {
         tx: ZkSyncTx::Transfer(Box::new(transfer_bad)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
 
     tb.test_txs_batch_fail(
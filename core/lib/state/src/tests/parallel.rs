@@ -0,0 +1,122 @@
+use crate::tests::{AccountState::*, PlasmaTestBuilder};
+use num::BigUint;
+use web3::types::H160;
+use zksync_crypto::PrivateKey;
+use zksync_types::{Account, AccountId, Nonce, TokenId, Transfer, ZkSyncTx};
+
+fn transfer(
+    from: (AccountId, &Account, &PrivateKey),
+    to: H160,
+    amount: u32,
+    nonce: Nonce,
+) -> ZkSyncTx {
+    let (account_id, account, sk) = from;
+    Transfer::new_signed(
+        account_id,
+        account.address,
+        to,
+        TokenId(0),
+        BigUint::from(amount),
+        BigUint::from(1u32),
+        nonce,
+        Default::default(),
+        sk,
+    )
+    .unwrap()
+    .into()
+}
+
+/// Check that the parallel execution gives the same results as the sequential one
+#[test]
+fn parallel_execution_matches_sequential() {
+    let mut tb = PlasmaTestBuilder::new();
+    let mut accounts = Vec::new();
+    for _ in 0..5 {
+        let (account_id, account, sk) = tb.add_account(Unlocked);
+        tb.set_balance(account_id, TokenId(0), 1_000u32);
+        accounts.push((account_id, account, sk));
+    }
+    let account = |index: usize| {
+        let (account_id, account, sk) = &accounts[index];
+        (*account_id, account, sk)
+    };
+    let address = |index: usize| accounts[index].1.address;
+
+    let new_address = H160::random();
+    let txs = vec![
+        transfer(account(0), address(1), 100, Nonce(0)),
+        transfer(account(2), address(3), 100, Nonce(0)),
+        transfer(account(4), new_address, 100, Nonce(0)),
+        transfer(account(2), address(3), 100, Nonce(1)),
+        // Fails because of the wrong nonce.
+        transfer(account(1), address(0), 100, Nonce(5)),
+    ];
+
+    let groups: Vec<_> = tb
+        .state
+        .group_txs(&txs)
+        .into_iter()
+        .map(|group| group.txs)
+        .collect();
+    assert_eq!(groups, vec![vec![0, 4], vec![1, 3], vec![2]]);
+
+    let mut sequential_state = tb.state.clone();
+    let sequential_results: Vec<_> = txs
+        .iter()
+        .map(|tx| sequential_state.execute_tx(tx.clone(), tb.block_timestamp))
+        .collect();
+    let parallel_results = tb.state.execute_txs_parallel(&txs, tb.block_timestamp);
+
+    assert_eq!(parallel_results.len(), sequential_results.len());
+    for (parallel, sequential) in parallel_results.into_iter().zip(sequential_results) {
+        match (parallel, sequential) {
+            (Ok(parallel), Ok(sequential)) => assert_eq!(parallel.updates, sequential.updates),
+            (Err(parallel), Err(sequential)) => assert_eq!(parallel, sequential),
+            (parallel, sequential) => panic!(
+                "results differ: parallel {:?}, sequential {:?}",
+                parallel, sequential
+            ),
+        }
+    }
+    assert_eq!(tb.state.root_hash(), sequential_state.root_hash());
+    assert_eq!(
+        tb.state.get_account_by_address(&new_address),
+        sequential_state.get_account_by_address(&new_address)
+    );
+}
+
+/// Check that the transactions creating the accounts are executed in the same group
+#[test]
+fn account_creation_is_not_parallel() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (from_id, from, from_sk) = tb.add_account(Unlocked);
+    let (other_id, other, other_sk) = tb.add_account(Unlocked);
+    tb.set_balance(from_id, TokenId(0), 1_000u32);
+    tb.set_balance(other_id, TokenId(0), 1_000u32);
+
+    let new_id = tb.state.get_free_account_id();
+    let (first_new, second_new) = (H160::random(), H160::random());
+    let txs = vec![
+        transfer((from_id, &from, &from_sk), first_new, 100, Nonce(0)),
+        transfer((other_id, &other, &other_sk), second_new, 100, Nonce(0)),
+    ];
+
+    let groups: Vec<_> = tb
+        .state
+        .group_txs(&txs)
+        .into_iter()
+        .map(|group| group.txs)
+        .collect();
+    assert_eq!(groups, vec![vec![0, 1]]);
+
+    let results = tb.state.execute_txs_parallel(&txs, tb.block_timestamp);
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(
+        tb.state.get_account_by_address(&first_new).unwrap().0,
+        new_id
+    );
+    assert_eq!(
+        tb.state.get_account_by_address(&second_new).unwrap().0,
+        new_id + 1
+    );
+}
@@ -120,11 +120,15 @@ fn batch_invalid_timestamp() {
         tx: ZkSyncTx::ChangePubKey(Box::new(change_pub_key)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     let signed_zk_sync_tx2 = SignedZkSyncTx {
         tx: ZkSyncTx::Withdraw(Box::new(withdraw)),
         eth_sign_data: None,
         created_at: Utc::now(),
+        batch_hash: None,
+        parties_eth_sign_data: Vec::new(),
     };
     tb.test_txs_batch_fail(
         &[signed_zk_sync_tx1.clone(), signed_zk_sync_tx2.clone()],
@@ -0,0 +1 @@
+ALTER TABLE mempool_txs ADD COLUMN batch_hash bytea;
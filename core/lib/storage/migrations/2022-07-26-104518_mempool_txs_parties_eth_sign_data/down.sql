@@ -0,0 +1 @@
+ALTER TABLE mempool_txs DROP COLUMN parties_eth_sign_data;
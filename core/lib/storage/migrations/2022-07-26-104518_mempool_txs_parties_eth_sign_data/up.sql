@@ -0,0 +1 @@
+ALTER TABLE mempool_txs ADD COLUMN parties_eth_sign_data jsonb;
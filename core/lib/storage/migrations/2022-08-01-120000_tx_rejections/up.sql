@@ -0,0 +1,13 @@
+CREATE TABLE tx_rejections (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    account bytea NOT NULL,
+    error_code TEXT NOT NULL,
+    error_message TEXT NOT NULL,
+    recovered_signer bytea,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX tx_rejections_tx_hash_idx ON tx_rejections (tx_hash);
+CREATE INDEX tx_rejections_account_idx ON tx_rejections (account);
+CREATE INDEX tx_rejections_created_at_idx ON tx_rejections (created_at);
@@ -0,0 +1,9 @@
+CREATE TABLE api_keys (
+    id BIGSERIAL PRIMARY KEY,
+    key_hash bytea NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    rps INTEGER,
+    allowed_methods TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    revoked_at TIMESTAMP WITH TIME ZONE
+);
@@ -0,0 +1,7 @@
+CREATE TABLE screened_addresses (
+    address bytea NOT NULL,
+    allowed BOOLEAN NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (address, allowed)
+);
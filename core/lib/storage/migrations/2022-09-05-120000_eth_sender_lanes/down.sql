@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS eth_operation_senders;
+DROP TABLE IF EXISTS eth_sender_nonces;
@@ -0,0 +1,12 @@
+-- Nonces of the additional operator accounts, the main account nonce is stored in `eth_parameters`.
+CREATE TABLE eth_sender_nonces (
+    sender_address bytea PRIMARY KEY,
+    nonce BIGINT NOT NULL
+);
+
+-- Additional operator accounts the Ethereum operations are sent from.
+-- Operations sent from the main account have no entry.
+CREATE TABLE eth_operation_senders (
+    eth_op_id BIGINT PRIMARY KEY REFERENCES eth_operations(id) ON DELETE CASCADE,
+    sender_address bytea NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Progress of the priority operations backfill performed by `eth_watch` on startup,
+-- the table holds at most one row.
+CREATE TABLE eth_watch_backfill_progress (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_processed_block BIGINT NOT NULL,
+    next_priority_op_id BIGINT NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS ix_prover_job_queue_job_status_job_priority_deadline_block;
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS deadline_block;
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS block_size;
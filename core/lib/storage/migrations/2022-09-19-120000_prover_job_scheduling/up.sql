@@ -0,0 +1,10 @@
+-- Size of the proven block, only the provers supporting it get the job. NULL for the aggregated proofs.
+ALTER TABLE prover_job_queue ADD COLUMN block_size BIGINT;
+-- The earliest Ethereum block by which the priority operations of the proven blocks must be executed.
+ALTER TABLE prover_job_queue ADD COLUMN deadline_block BIGINT;
+
+UPDATE prover_job_queue SET block_size = (job_data->'BlockProof'->>1)::BIGINT
+    WHERE job_type = 'SINGLE_PROOF' AND job_status != 2;
+
+CREATE INDEX IF NOT EXISTS ix_prover_job_queue_job_status_job_priority_deadline_block
+    ON prover_job_queue ( job_status, job_priority, deadline_block );
@@ -0,0 +1,2 @@
+-- Amount of times the job was taken from the silent provers and handed out again.
+ALTER TABLE prover_job_queue ADD COLUMN reassignments INT NOT NULL DEFAULT 0;
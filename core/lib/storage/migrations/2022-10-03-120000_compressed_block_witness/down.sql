@@ -0,0 +1,5 @@
+-- Compressed witnesses can't be converted back to JSON in SQL, they are built again.
+DELETE FROM block_witness WHERE witness IS NULL;
+ALTER TABLE block_witness DROP COLUMN IF EXISTS created_at;
+ALTER TABLE block_witness ALTER COLUMN witness SET NOT NULL;
+ALTER TABLE block_witness DROP COLUMN IF EXISTS witness_data;
@@ -0,0 +1,6 @@
+-- Witness encoded as CBOR and compressed with zstd. The JSON `witness` is only set
+-- for the witnesses stored before, they are decoded as JSON when loaded.
+ALTER TABLE block_witness ADD COLUMN witness_data BYTEA;
+ALTER TABLE block_witness ALTER COLUMN witness DROP NOT NULL;
+-- Time the witness was stored at, the witnesses of the verified blocks are pruned by it.
+ALTER TABLE block_witness ADD COLUMN created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
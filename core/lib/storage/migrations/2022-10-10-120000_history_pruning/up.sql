@@ -0,0 +1,7 @@
+-- Last block whose history (executed transactions, events and witnesses) is pruned,
+-- the table holds at most one row.
+CREATE TABLE history_pruning (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_pruned_block BIGINT NOT NULL,
+    pruned_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS fee_free_accounts;
+DROP TABLE IF EXISTS fee_promotions;
+DROP TABLE IF EXISTS token_fee_subsidies;
@@ -0,0 +1,22 @@
+CREATE TABLE token_fee_subsidies (
+    token_id INTEGER PRIMARY KEY,
+    subsidy_percent SMALLINT NOT NULL CHECK (subsidy_percent BETWEEN 0 AND 100),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE fee_promotions (
+    id BIGSERIAL PRIMARY KEY,
+    -- The promotion applies to the fees in all the tokens if not set.
+    token_id INTEGER,
+    discount_percent SMALLINT NOT NULL CHECK (discount_percent BETWEEN 0 AND 100),
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX fee_promotions_ends_at_idx ON fee_promotions (ends_at);
+
+CREATE TABLE fee_free_accounts (
+    account_id BIGINT PRIMARY KEY,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
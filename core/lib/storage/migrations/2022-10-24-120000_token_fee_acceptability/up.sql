@@ -0,0 +1,6 @@
+-- Overrides of the market volume check deciding whether the token can be used to pay fees.
+CREATE TABLE token_fee_acceptability (
+    token_id INTEGER PRIMARY KEY,
+    fee_acceptable BOOLEAN NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,7 @@
+-- Drain mode of the API servers set via the admin API: if it's enabled, the new transactions
+-- are rejected. The table holds at most one row.
+CREATE TABLE tx_intake_drain (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    drained BOOLEAN NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
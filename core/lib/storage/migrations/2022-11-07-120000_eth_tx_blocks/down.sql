@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS eth_last_block;
+DROP TABLE IF EXISTS eth_tx_blocks;
@@ -0,0 +1,14 @@
+-- Ethereum blocks the operator transactions are included in, recorded by `eth_sender`
+-- on the confirmation of the transaction.
+CREATE TABLE eth_tx_blocks (
+    tx_hash BYTEA PRIMARY KEY,
+    eth_block BIGINT NOT NULL
+);
+
+-- Latest Ethereum block seen by `eth_sender`, the confirmations of the transactions
+-- are counted up to it. The table holds at most one row.
+CREATE TABLE eth_last_block (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    block_number BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS token_value_locked;
+DROP TABLE IF EXISTS daily_active_accounts;
+DROP TABLE IF EXISTS daily_fee_stats;
+DROP TABLE IF EXISTS daily_tx_stats;
+DROP TABLE IF EXISTS stats_aggregation;
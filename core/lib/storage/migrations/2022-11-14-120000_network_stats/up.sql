@@ -0,0 +1,39 @@
+-- Network statistics aggregated incrementally from the finalized blocks by the stats aggregator,
+-- so they're not computed by scanning the whole history.
+
+-- Last block the statistics are aggregated for. The table holds at most one row.
+CREATE TABLE stats_aggregation (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_block BIGINT NOT NULL,
+    aggregated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Amounts of the successful transactions and priority operations of every type per day (UTC).
+CREATE TABLE daily_tx_stats (
+    day DATE NOT NULL,
+    tx_type TEXT NOT NULL,
+    tx_count BIGINT NOT NULL,
+    PRIMARY KEY (day, tx_type)
+);
+
+-- Fees paid in every token per day, the transactions paying no fee are not counted.
+CREATE TABLE daily_fee_stats (
+    day DATE NOT NULL,
+    token_id INTEGER NOT NULL,
+    tx_count BIGINT NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    PRIMARY KEY (day, token_id)
+);
+
+-- Accounts that initiated at least one transaction or priority operation on the day.
+CREATE TABLE daily_active_accounts (
+    day DATE NOT NULL,
+    address BYTEA NOT NULL,
+    PRIMARY KEY (day, address)
+);
+
+-- Sum of the balances of all the accounts in every fungible token.
+CREATE TABLE token_value_locked (
+    token_id INTEGER PRIMARY KEY,
+    amount NUMERIC NOT NULL
+);
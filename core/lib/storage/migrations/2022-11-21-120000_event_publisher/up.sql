@@ -0,0 +1,7 @@
+-- The last event published by the event publisher to each broker. It's updated only after
+-- the broker has acknowledged the events, so every event is published at least once.
+CREATE TABLE event_publisher_offsets (
+    broker TEXT PRIMARY KEY,
+    last_event_id BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fast_withdrawals;
+DROP TABLE IF EXISTS liquidity_providers;
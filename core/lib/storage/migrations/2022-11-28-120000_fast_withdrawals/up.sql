@@ -0,0 +1,24 @@
+CREATE TABLE liquidity_providers (
+    address bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    max_amount NUMERIC NOT NULL,
+    fee_bps INTEGER NOT NULL CHECK (fee_bps BETWEEN 0 AND 10000),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (address, token_id)
+);
+
+CREATE TABLE fast_withdrawals (
+    id BIGSERIAL PRIMARY KEY,
+    account bytea NOT NULL,
+    recipient bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    provider bytea NOT NULL,
+    fronted_amount NUMERIC NOT NULL,
+    -- Hash of the withdrawal to the provider, set once it's submitted.
+    tx_hash bytea,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    valid_until TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX fast_withdrawals_account_idx ON fast_withdrawals (account);
+CREATE INDEX fast_withdrawals_provider_idx ON fast_withdrawals (provider);
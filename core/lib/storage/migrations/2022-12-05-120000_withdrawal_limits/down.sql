@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS delayed_withdrawals;
+DROP TABLE IF EXISTS withdrawal_volumes;
+DROP TABLE IF EXISTS withdrawal_limits;
@@ -0,0 +1,31 @@
+-- Limits of the withdrawals set by the operator per token, the withdrawals are not limited
+-- if the limit is not set.
+CREATE TABLE withdrawal_limits (
+    token_id INTEGER PRIMARY KEY,
+    account_daily_limit NUMERIC,
+    daily_volume_limit NUMERIC,
+    large_withdrawal_threshold NUMERIC,
+    -- Delay of the large withdrawals, in seconds.
+    large_withdrawal_delay BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Amounts withdrawn by the accounts per day (UTC), counted once the withdrawals are accepted
+-- by the mempool.
+CREATE TABLE withdrawal_volumes (
+    day DATE NOT NULL,
+    token_id INTEGER NOT NULL,
+    account bytea NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (day, token_id, account)
+);
+
+-- Large withdrawals waiting for the delay window since they were first submitted.
+CREATE TABLE delayed_withdrawals (
+    tx_hash bytea PRIMARY KEY,
+    account bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    available_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
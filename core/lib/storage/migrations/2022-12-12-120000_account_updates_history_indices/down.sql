@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS account_pubkey_updates_account_idx ON account_pubkey_updates (account_id);
+CREATE INDEX IF NOT EXISTS account_balance_updates_account_id_idx ON account_balance_updates (account_id);
+DROP INDEX IF EXISTS account_pubkey_updates_account_id_block_number;
+DROP INDEX IF EXISTS account_creates_account_id_block_number;
+DROP INDEX IF EXISTS account_balance_updates_account_id_block_number;
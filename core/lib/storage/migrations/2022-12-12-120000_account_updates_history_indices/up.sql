@@ -0,0 +1,6 @@
+-- Updates of a single account after the given block are loaded to reconstruct its historical state.
+CREATE INDEX IF NOT EXISTS account_balance_updates_account_id_block_number ON account_balance_updates (account_id, block_number);
+CREATE INDEX IF NOT EXISTS account_creates_account_id_block_number ON account_creates (account_id, block_number);
+CREATE INDEX IF NOT EXISTS account_pubkey_updates_account_id_block_number ON account_pubkey_updates (account_id, block_number);
+DROP INDEX IF EXISTS account_balance_updates_account_id_idx;
+DROP INDEX IF EXISTS account_pubkey_updates_account_idx;
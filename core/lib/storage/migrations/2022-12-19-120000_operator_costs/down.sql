@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS blocks_timestamp_index;
+DROP TABLE IF EXISTS block_l1_costs;
@@ -0,0 +1,15 @@
+-- Fees of the Ethereum transactions sent by the operator, attributed to the blocks
+-- the transactions are sent for.
+CREATE TABLE block_l1_costs (
+    block_number BIGINT NOT NULL,
+    tx_hash bytea NOT NULL,
+    -- Kind of the transaction: `commit`, `publishProof`, `execute` or `withdrawals`.
+    kind TEXT NOT NULL,
+    -- Share of the fee of the transaction attributed to the block, in wei.
+    fee NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (block_number, tx_hash)
+);
+
+-- The daily reports select the blocks by their timestamps.
+CREATE INDEX IF NOT EXISTS blocks_timestamp_index ON blocks (timestamp);
@@ -0,0 +1 @@
+ALTER TABLE executed_transactions ADD COLUMN batch_hash bytea;
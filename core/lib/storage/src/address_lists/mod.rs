@@ -0,0 +1,97 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::StoredScreenedAddress;
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the allow and deny lists of the addresses the transactions are screened against.
+///
+/// The same address may be in both lists, it's up to the screening mode which list is used.
+#[derive(Debug)]
+pub struct AddressListsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AddressListsSchema<'a, 'c> {
+    /// Adds the address to the list, updates the reason if the address is already listed.
+    pub async fn add_address(
+        &mut self,
+        address: Address,
+        allowed: bool,
+        reason: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO screened_addresses ( address, allowed, reason )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT ( address, allowed ) DO UPDATE SET reason = $3
+            "#,
+            address.as_bytes(),
+            allowed,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("address_lists.add_address", start);
+        Ok(())
+    }
+
+    /// Removes the address from the list, returns `false` if the address is not listed.
+    pub async fn remove_address(&mut self, address: Address, allowed: bool) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM screened_addresses WHERE address = $1 AND allowed = $2",
+            address.as_bytes(),
+            allowed
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("address_lists.remove_address", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all the addresses of the list.
+    pub async fn load_list(&mut self, allowed: bool) -> QueryResult<Vec<StoredScreenedAddress>> {
+        let start = Instant::now();
+        let addresses = sqlx::query_as!(
+            StoredScreenedAddress,
+            "SELECT * FROM screened_addresses WHERE allowed = $1 ORDER BY created_at, address",
+            allowed
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("address_lists.load_list", start);
+        Ok(addresses)
+    }
+
+    /// Loads the addresses of the list among the given ones.
+    pub async fn load_listed_addresses(
+        &mut self,
+        addresses: &[Address],
+        allowed: bool,
+    ) -> QueryResult<Vec<StoredScreenedAddress>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let listed = sqlx::query_as!(
+            StoredScreenedAddress,
+            "SELECT * FROM screened_addresses WHERE address = ANY($1) AND allowed = $2",
+            &addresses,
+            allowed
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("address_lists.load_listed_addresses", start);
+        Ok(listed)
+    }
+}
@@ -0,0 +1,15 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredScreenedAddress {
+    pub address: Vec<u8>,
+    /// `true` if the address is in the allow list, `false` if it's in the deny list.
+    pub allowed: bool,
+    /// Why the address has been listed, e.g. the reference to the sanctions list.
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
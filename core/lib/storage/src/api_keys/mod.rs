@@ -0,0 +1,86 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::{NewApiKey, StoredApiKey};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the API keys of the partner integrations.
+///
+/// Only the hashes of the keys are stored. Revoked keys are kept in the database
+/// to know whom the key belonged to.
+#[derive(Debug)]
+pub struct ApiKeysSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ApiKeysSchema<'a, 'c> {
+    /// Stores the new key and returns its ID.
+    pub async fn store_key(&mut self, key: &NewApiKey) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO api_keys ( key_hash, name, rps, allowed_methods )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING id
+            "#,
+            key.key_hash,
+            key.name,
+            key.rps,
+            &key.allowed_methods
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        report_query("api_keys.store_key", start);
+        Ok(id)
+    }
+
+    /// Loads all the keys which are not revoked.
+    pub async fn load_active_keys(&mut self) -> QueryResult<Vec<StoredApiKey>> {
+        let start = Instant::now();
+        let keys = sqlx::query_as!(
+            StoredApiKey,
+            "SELECT * FROM api_keys WHERE revoked_at IS NULL ORDER BY id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("api_keys.load_active_keys", start);
+        Ok(keys)
+    }
+
+    /// Loads the key by its hash, returns `None` if there is no such key or it's revoked.
+    pub async fn load_active_key_by_hash(
+        &mut self,
+        key_hash: &[u8],
+    ) -> QueryResult<Option<StoredApiKey>> {
+        let start = Instant::now();
+        let key = sqlx::query_as!(
+            StoredApiKey,
+            "SELECT * FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+            key_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query("api_keys.load_active_key_by_hash", start);
+        Ok(key)
+    }
+
+    /// Revokes the key, returns `false` if there is no such key or it's already revoked.
+    pub async fn revoke_key(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE api_keys SET revoked_at = now() WHERE id = $1 AND revoked_at IS NULL",
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("api_keys.revoke_key", start);
+        Ok(result.rows_affected() > 0)
+    }
+}
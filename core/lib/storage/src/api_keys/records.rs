@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// API key to be stored in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewApiKey {
+    /// Hash of the key, the key itself is only known to its owner.
+    pub key_hash: Vec<u8>,
+    /// Human-readable name of the key owner, e.g. the name of the partner.
+    pub name: String,
+    /// Requests per second allowed for the key, `None` if the key is not rate limited.
+    pub rps: Option<i32>,
+    /// JSON RPC methods and REST API path prefixes the key has access to,
+    /// all the API is accessible if the list is empty.
+    pub allowed_methods: Vec<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredApiKey {
+    pub id: i64,
+    pub key_hash: Vec<u8>,
+    pub name: String,
+    pub rps: Option<i32>,
+    pub allowed_methods: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -6,13 +6,14 @@ use sqlx::{types::BigDecimal, Acquire};
 // Workspace imports
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+    helpers::reverse_updates, Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce,
+    PubKeyHash, TokenId,
 };
 // Local imports
 use self::records::*;
 use crate::chain::block::BlockSchema;
 use crate::diff::StorageAccountDiff;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 pub mod records;
 pub mod restore_account;
@@ -76,7 +77,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.set_account_type", start.elapsed());
+        report_query("chain.state.set_account_type", start);
         Ok(())
     }
 
@@ -107,7 +108,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             verified_nonce.map(|nonce| nonce.nonce)
         };
 
-        metrics::histogram!("sql.chain.account.current_nonce", start.elapsed());
+        report_query("chain.account.current_nonce", start);
         Ok(current_nonce.map(|v| Nonce(v as u32)))
     }
 
@@ -150,7 +151,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         let account_type =
             db_account_type.map(|db_type| EthAccountType::from_db(db_type, pub_key_hash));
-        metrics::histogram!("sql.chain.account.account_type_by_id", start.elapsed());
+        report_query("chain.account.account_type_by_id", start);
         Ok(account_type)
     }
 
@@ -168,7 +169,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .last_committed_state_for_account(account_id)
             .await?;
 
-        metrics::histogram!("sql.chain.account.account_state_by_id", start.elapsed());
+        report_query("chain.account.account_state_by_id", start);
         Ok(StoredAccountState {
             committed: committed_state.map(|a| (account_id, a)),
             verified: verified_state.1.map(|a| (account_id, a)),
@@ -189,7 +190,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.account.does_account_exist", start.elapsed());
+        report_query("chain.account.does_account_exist", start);
         Ok(result.is_some())
     }
 
@@ -209,10 +210,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             })
         };
 
-        metrics::histogram!(
-            "sql.chain.account.account_state_by_address",
-            start.elapsed()
-        );
+        report_query("chain.account.account_state_by_address", start);
         account_state
     }
 
@@ -238,6 +236,57 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .await?
             .0 as i64;
 
+        let account_diff =
+            Self::load_account_updates(&mut transaction, account_id, last_verified_block).await?;
+
+        // Apply all the diffs to obtain the most recent account state.
+        let account_state = account_diff
+            .into_iter()
+            .map(|(_, upd)| upd)
+            .fold(account.clone(), Account::apply_update);
+
+        transaction.commit().await?;
+
+        report_query("chain.account.last_committed_state_for_account", start);
+        Ok(((last_block, account), account_state))
+    }
+
+    /// Loads the state of the account after the given block. The state is reconstructed from
+    /// the last committed one by reverting the updates of the account made in the later blocks.
+    /// Returns `None` if the account didn't exist after the block.
+    pub async fn account_state_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<Account>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (_, committed_state) = AccountSchema(&mut transaction)
+            .last_committed_state_for_account(account_id)
+            .await?;
+        let mut account_diff =
+            Self::load_account_updates(&mut transaction, account_id, i64::from(*block_number))
+                .await?;
+        reverse_updates(&mut account_diff);
+        let account_state = account_diff
+            .into_iter()
+            .map(|(_, upd)| upd)
+            .fold(committed_state, Account::apply_update);
+
+        transaction.commit().await?;
+
+        report_query("chain.account.account_state_at_block", start);
+        Ok(account_state)
+    }
+
+    /// Loads the updates of the account made in the blocks after `after_block`,
+    /// in the order they were applied.
+    async fn load_account_updates(
+        storage: &mut StorageProcessor<'_>,
+        account_id: AccountId,
+        after_block: i64,
+    ) -> QueryResult<AccountUpdates> {
         let account_balance_diff = sqlx::query_as!(
             StorageAccountUpdate,
             "
@@ -245,9 +294,9 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
                 WHERE account_id = $1 AND block_number > $2
             ",
             i64::from(*account_id),
-            last_verified_block
+            after_block
         )
-        .fetch_all(transaction.conn())
+        .fetch_all(storage.conn())
         .await?;
 
         let account_creation_diff = sqlx::query_as!(
@@ -257,9 +306,9 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
                 WHERE account_id = $1 AND block_number > $2
             ",
             i64::from(*account_id),
-            last_verified_block
+            after_block
         )
-        .fetch_all(transaction.conn())
+        .fetch_all(storage.conn())
         .await?;
 
         let account_pubkey_diff = sqlx::query_as!(
@@ -269,9 +318,9 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
                 WHERE account_id = $1 AND block_number > $2
             ",
             i64::from(*account_id),
-            last_verified_block
+            after_block
         )
-        .fetch_all(transaction.conn())
+        .fetch_all(storage.conn())
         .await?;
         let mint_nft_updates = sqlx::query_as!(
             StorageMintNFTUpdate,
@@ -280,51 +329,35 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
                 WHERE creator_account_id = $1 AND block_number > $2
             ",
             *account_id as i32,
-            last_verified_block
+            after_block
         )
-        .fetch_all(transaction.conn())
+        .fetch_all(storage.conn())
         .await?;
 
         // Chain the diffs, converting them into `StorageAccountDiff`.
-        let account_diff = {
-            let mut account_diff = Vec::new();
-            account_diff.extend(
-                account_balance_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(
-                account_creation_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(
-                account_pubkey_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
-            account_diff.sort_by(StorageAccountDiff::cmp_order);
-
-            account_diff
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
                 .into_iter()
-                .map(Into::into)
-                .collect::<AccountUpdates>()
-        };
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
 
-        // Apply all the diffs to obtain the most recent account state.
-        let account_state = account_diff
+        Ok(account_diff
             .into_iter()
-            .map(|(_, upd)| upd)
-            .fold(account.clone(), Account::apply_update);
-
-        transaction.commit().await?;
-
-        metrics::histogram!(
-            "sql.chain.account.last_committed_state_for_account",
-            start.elapsed()
-        );
-        Ok(((last_block, account), account_state))
+            .map(Into::into)
+            .collect::<AccountUpdates>())
     }
 
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
@@ -335,10 +368,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
     ) -> QueryResult<Option<Account>> {
         let start = Instant::now();
         let (_, account) = self.account_and_last_block(account_id).await?;
-        metrics::histogram!(
-            "sql.chain.account.last_verified_state_for_account",
-            start.elapsed()
-        );
+        report_query("chain.account.last_verified_state_for_account", start);
         Ok(account)
     }
 
@@ -399,10 +429,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.account.get_account_and_last_block",
-            start.elapsed()
-        );
+        report_query("chain.account.get_account_and_last_block", start);
         result
     }
 
@@ -426,7 +453,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let account_id = result.map(|record| AccountId(record.account_id as u32));
-        metrics::histogram!("sql.chain.account.account_id_by_address", start.elapsed());
+        report_query("chain.account.account_id_by_address", start);
         Ok(account_id)
     }
 
@@ -444,7 +471,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let address = result.map(|record| Address::from_slice(&record.address));
-        metrics::histogram!("sql.chain.account.account_address_by_id", start.elapsed());
+        report_query("chain.account.account_address_by_id", start);
         Ok(address)
     }
 
@@ -479,9 +506,9 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .greatest
         .unwrap_or(block_number);
 
-        metrics::histogram!(
-            "sql.chain.account.last_committed_block_with_update_for_acc",
-            start.elapsed()
+        report_query(
+            "chain.account.last_committed_block_with_update_for_acc",
+            start,
         );
         Ok(BlockNumber(block_number as u32))
     }
@@ -527,10 +554,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .unwrap_or_else(BigUint::zero);
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.account.get_account_balance_for_block",
-            start.elapsed()
-        );
+        report_query("chain.account.get_account_balance_for_block", start);
 
         Ok(result)
     }
@@ -570,7 +594,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .unwrap_or(0) as u32;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.account.get_account_nft_balance", start.elapsed());
+        report_query("chain.account.get_account_nft_balance", start);
 
         Ok(balance)
     }
@@ -590,7 +614,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
         let owner_id = record.map(|record| AccountId(record.account_id as u32));
 
-        metrics::histogram!("sql.chain.account.get_nft_owner", start.elapsed());
+        report_query("chain.account.get_nft_owner", start);
         Ok(owner_id)
     }
 }
@@ -99,10 +99,17 @@ pub(crate) struct StorageAccountType {
     pub account_type: DbAccountType,
 }
 
+/// Kind of the account owner, which determines whether the transactions of the account
+/// require the Ethereum signature along with the zkSync one.
 #[derive(Debug, Clone, Copy)]
 pub enum EthAccountType {
+    /// Account owned by the Ethereum key: every transaction requires its signature.
     Owned,
+    /// Counterfactual wallet which has no Ethereum key to sign with.
     CREATE2,
+    /// Owner has disabled the Ethereum signature requirement (2FA) with the `Toggle2FA` request
+    /// signed by its Ethereum key. If the public key hash is set, the requirement is only disabled
+    /// for the transactions signed by that zkSync key. `ChangePubKey` is always checked.
     No2FA(Option<PubKeyHash>),
 }
 
@@ -42,6 +42,8 @@ impl StoredExecutedTransaction {
                 tx,
                 eth_sign_data,
                 created_at: self.created_at,
+                batch_hash: self.batch_hash.map(|hash| H256::from_slice(&hash)),
+                parties_eth_sign_data: Vec::new(),
             },
             success: self.success,
             op: franklin_op,
@@ -210,6 +212,10 @@ impl NewExecutedTransaction {
             created_at: exec_tx.created_at,
             eth_sign_data,
             batch_id: exec_tx.batch_id,
+            batch_hash: exec_tx
+                .signed_tx
+                .batch_hash
+                .map(|hash| hash.as_bytes().to_vec()),
             affected_accounts,
             used_tokens,
         })
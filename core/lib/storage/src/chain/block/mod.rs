@@ -30,6 +30,7 @@ use crate::{
         OperationsSchema,
     },
     chain::{account::records::EthAccountType, block::records::StorageIncompleteBlock},
+    instrumentation::report_query,
     QueryResult, StorageProcessor,
 };
 
@@ -120,7 +121,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.save_block_transactions", start.elapsed());
+        report_query("chain.block.save_block_transactions", start);
         Ok(())
     }
 
@@ -138,7 +139,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_storage_block", start.elapsed());
+        report_query("chain.block.get_storage_block", start);
 
         Ok(block)
     }
@@ -179,7 +180,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             stored_block.timestamp.unwrap_or_default() as u64,
         ));
 
-        metrics::histogram!("sql.chain.block.get_block", start.elapsed());
+        report_query("chain.block.get_block", start);
 
         Ok(result)
     }
@@ -200,7 +201,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_block_metadata", start.elapsed());
+        report_query("chain.block.get_block_metadata", start);
 
         let result = db_result.map(|md| BlockMetadata {
             fast_processing: md.fast_processing,
@@ -221,7 +222,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 ExecutedOperations::PriorityOp(priorop) => Some(priorop.op),
             })
             .collect();
-        metrics::histogram!("sql.chain.block.get_block_operations", start.elapsed());
+        report_query("chain.block.get_block_operations", start);
         Ok(result)
     }
 
@@ -282,7 +283,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_block_transactions", start.elapsed());
+        report_query("chain.block.get_block_transactions", start);
         Ok(block_txs)
     }
 
@@ -345,7 +346,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             }
         });
 
-        metrics::histogram!("sql.chain.block.get_block_executed_ops", start.elapsed());
+        report_query("chain.block.get_block_executed_ops", start);
         Ok(executed_operations)
     }
 
@@ -409,7 +410,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         ).fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.load_block_range", start.elapsed());
+        report_query("chain.block.load_block_range", start);
         Ok(details)
     }
 
@@ -473,7 +474,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         ).fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.load_block_range_asc", start.elapsed());
+        report_query("chain.block.load_block_range_asc", start);
         Ok(details)
     }
 
@@ -609,10 +610,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .ok()
             .flatten();
 
-        metrics::histogram!(
-            "sql.chain.block.find_block_by_height_or_hash",
-            start.elapsed()
-        );
+        report_query("chain.block.find_block_by_height_or_hash", start);
         result
     }
 
@@ -624,7 +622,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?
             .max
             .unwrap_or(0);
-        metrics::histogram!("sql.chain.block.get_last_committed_block", start.elapsed());
+        report_query("chain.block.get_last_saved_block", start);
         Ok(BlockNumber(count as u32))
     }
 
@@ -634,7 +632,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::CommitBlocks, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_committed_block", start.elapsed());
+        report_query("chain.block.get_last_committed_block", start);
         result
     }
 
@@ -652,7 +650,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?
             .max
             .map(|block| BlockNumber(block as u32));
-        metrics::histogram!("sql.chain.block.get_last_incomplete_block", start.elapsed());
+        report_query("chain.block.get_last_incomplete_block", start);
         Ok(result)
     }
 
@@ -662,10 +660,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::CommitBlocks, Some(true))
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_committed_confirmed_block",
-            start.elapsed()
-        );
+        report_query("chain.block.get_last_committed_confirmed_block", start);
         result
     }
 
@@ -679,7 +674,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::ExecuteBlocks, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_verified_block", start.elapsed());
+        report_query("chain.block.get_last_verified_block", start);
         result
     }
 
@@ -692,10 +687,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 Some(true),
             )
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_proven_confirmed_block",
-            start.elapsed()
-        );
+        report_query("chain.block.get_last_proven_confirmed_block", start);
         result
     }
 
@@ -706,10 +698,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_aggregated_action(AggregatedActionType::ExecuteBlocks, Some(true))
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_verified_confirmed_block",
-            start.elapsed()
-        );
+        report_query("chain.block.get_last_verified_confirmed_block", start);
         result
     }
 
@@ -731,7 +720,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.block.pending_block_chunks_left", start.elapsed());
+        report_query("chain.block.pending_block_chunks_left", start);
 
         Ok(maybe_block_chunks.map(|val| val.chunks_left as usize))
     }
@@ -747,10 +736,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.block.load_storage_pending_block",
-            start.elapsed()
-        );
+        report_query("chain.block.load_storage_pending_block", start);
 
         Ok(maybe_block)
     }
@@ -799,7 +785,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.load_pending_block", start.elapsed());
+        report_query("chain.block.load_pending_block", start);
         Ok(Some(result))
     }
 
@@ -808,7 +794,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let start = Instant::now();
         let result = self.load_storage_pending_block().await?.is_some();
 
-        metrics::histogram!("sql.chain.block.pending_block_exists", start.elapsed());
+        report_query("chain.block.pending_block_exists", start);
         Ok(result)
     }
 
@@ -855,7 +841,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.load_pending_block", start.elapsed());
+        report_query("chain.block.save_pending_block", start);
 
         Ok(())
     }
@@ -870,7 +856,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.chain.block.count_rejected_txs", start.elapsed());
+        report_query("chain.block.count_rejected_txs", start);
         Ok(count)
     }
     /// Returns the number of aggregated operations with the given `action_type` and `is_confirmed` status.
@@ -889,7 +875,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.chain.block.count_operations", start.elapsed());
+        report_query("chain.block.count_operations", start);
         Ok(count)
     }
 
@@ -935,7 +921,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.save_block", start.elapsed());
+        report_query("chain.block.save_block", start);
         Ok(())
     }
 
@@ -991,7 +977,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.save_incomplete_block", start.elapsed());
+        report_query("chain.block.save_incomplete_block", start);
         Ok(())
     }
 
@@ -1050,7 +1036,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .map(|val| val as u64)
         .unwrap_or_default();
 
-        metrics::histogram!("sql.chain.block.next_expected_serial_id", start.elapsed());
+        report_query("chain.block.next_expected_serial_id", start);
         Ok(next_expected_serial_id)
     }
 
@@ -1080,7 +1066,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql.chain.block.incomplete_blocks_range", start.elapsed());
+        report_query("chain.block.incomplete_blocks_range", start);
         Ok(block_numbers)
     }
 
@@ -1098,10 +1084,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_storage_incomplete_block",
-            start.elapsed()
-        );
+        report_query("chain.block.get_storage_incomplete_block", start);
 
         Ok(block)
     }
@@ -1152,10 +1135,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .map(|entry| FeConvert::from_bytes(&entry.root_hash).expect("Unparsable root hash"));
 
-        metrics::histogram!(
-            "sql.chain.block.get_data_to_complete_block",
-            start.elapsed()
-        );
+        report_query("chain.block.get_data_to_complete_block", start);
 
         Ok((block, previous_root_hash))
     }
@@ -1178,7 +1158,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.save_block_metadata", start.elapsed());
+        report_query("chain.block.save_block_metadata", start);
         Ok(())
     }
 
@@ -1376,10 +1356,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_page",
-            start.elapsed()
-        );
+        report_query("chain.block.get_block_transactions_page", start);
         Ok(block_txs)
     }
 
@@ -1407,10 +1384,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .count;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_count",
-            start.elapsed()
-        );
+        report_query("chain.block.get_block_transactions_count", start);
         Ok((tx_count + priority_op_count) as u32)
     }
 
@@ -1461,7 +1435,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.remove_blocks", start.elapsed());
+        report_query("chain.block.remove_blocks", start);
         Ok(())
     }
 
@@ -1472,7 +1446,114 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .execute(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.chain.block.remove_pending_block", start.elapsed());
+        report_query("chain.block.remove_pending_block", start);
+        Ok(())
+    }
+
+    /// Reverts the blocks with numbers greater than `last_block` in a single database transaction:
+    /// the executed transactions are returned to the mempool, the committed state updates,
+    /// the aggregated operations and the prover data of the blocks are removed.
+    /// The committed state of the accounts is restored from the remaining state updates,
+    /// so the account tree is rolled back once the server is restarted.
+    ///
+    /// Verified blocks can't be reverted, the server must not be running during the revert.
+    pub async fn revert_blocks(&mut self, last_block: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let last_verified_block = transaction
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        anyhow::ensure!(
+            last_block >= last_verified_block,
+            "Block {} is verified, only the blocks after it can be reverted",
+            *last_verified_block
+        );
+
+        transaction
+            .chain()
+            .mempool_schema()
+            .return_executed_txs_to_mempool(last_block)
+            .await?;
+        transaction
+            .chain()
+            .state_schema()
+            .clear_current_nonce_table(last_block)
+            .await?;
+        transaction
+            .chain()
+            .block_schema()
+            .remove_blocks(last_block)
+            .await?;
+        transaction
+            .chain()
+            .block_schema()
+            .remove_pending_block()
+            .await?;
+        transaction
+            .chain()
+            .tree_cache_schema_bincode()
+            .remove_new_account_tree_cache(last_block)
+            .await?;
+
+        transaction
+            .chain()
+            .state_schema()
+            .remove_account_balance_updates(last_block)
+            .await?;
+        transaction
+            .chain()
+            .state_schema()
+            .remove_account_creates(last_block)
+            .await?;
+        transaction
+            .chain()
+            .state_schema()
+            .remove_account_pubkey_updates(last_block)
+            .await?;
+        transaction
+            .chain()
+            .state_schema()
+            .remove_mint_nft_updates(last_block)
+            .await?;
+
+        transaction
+            .chain()
+            .operations_schema()
+            .remove_eth_unprocessed_aggregated_ops()
+            .await?;
+        transaction
+            .chain()
+            .operations_schema()
+            .remove_aggregate_operations_and_bindings(last_block)
+            .await?;
+
+        transaction
+            .prover_schema()
+            .remove_witnesses(last_block)
+            .await?;
+        transaction
+            .prover_schema()
+            .remove_proofs(last_block)
+            .await?;
+        transaction
+            .prover_schema()
+            .remove_aggregated_proofs(last_block)
+            .await?;
+        transaction
+            .prover_schema()
+            .remove_prover_jobs(last_block)
+            .await?;
+
+        transaction
+            .ethereum_schema()
+            .update_eth_parameters(last_block)
+            .await?;
+
+        transaction.commit().await?;
+        report_query("chain.block.revert_blocks", start);
         Ok(())
     }
 
@@ -1518,10 +1599,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.block.store_factories_for_block_withdraw_nfts",
-            start.elapsed()
-        );
+        report_query("chain.block.store_factories_for_block_withdraw_nfts", start);
         Ok(())
     }
 
@@ -1535,7 +1613,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?;
         let block_number = record.map(|r| BlockNumber(r.number as u32));
 
-        metrics::histogram!("sql.chain.block.get_block_number_by_hash", start.elapsed());
+        report_query("chain.block.get_block_number_by_hash", start);
         Ok(block_number)
     }
 
@@ -1569,10 +1647,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?;
         let hashes = records.into_iter().map(|record| record.tx_hash).collect();
 
-        metrics::histogram!(
-            "sql.chain.block.get_block_transactions_hashes",
-            start.elapsed()
-        );
+        report_query("chain.block.get_block_transactions_hashes", start);
         Ok(hashes)
     }
 }
@@ -11,12 +11,12 @@ use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
     PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
 };
 // Local imports
 use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
     StoredExecutedPriorityOperation, StoredExecutedTransaction,
@@ -113,7 +113,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             }
         }
 
-        metrics::histogram!("sql.chain.mempool.load_txs", start.elapsed());
+        report_query("chain.mempool.load_txs", start);
         Ok(txs.into())
     }
 
@@ -133,7 +133,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.mempool.remove_reverted_block", start.elapsed());
+        report_query("chain.mempool.remove_reverted_block", start);
         Ok(())
     }
 
@@ -165,14 +165,18 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .eth_sign_data
                 .as_ref()
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let batch_hash = first_tx_data.batch_hash.as_ref().map(H256::as_bytes);
+            let parties_eth_sign_data = parties_eth_sign_data_to_value(&first_tx_data);
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data)
-                VALUES ($1, $2, $3, $4)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_hash, parties_eth_sign_data)
+                VALUES ($1, $2, $3, $4, $5, $6)",
                 tx_hash,
                 tx,
                 first_tx_data.created_at,
                 eth_sign_data,
+                batch_hash,
+                parties_eth_sign_data,
             )
             .execute(transaction.conn())
             .await?;
@@ -199,15 +203,19 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .eth_sign_data
                 .as_ref()
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let batch_hash = tx_data.batch_hash.as_ref().map(H256::as_bytes);
+            let parties_eth_sign_data = parties_eth_sign_data_to_value(tx_data);
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, batch_hash, parties_eth_sign_data)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)",
                 tx_hash,
                 tx,
                 tx_data.created_at,
                 eth_sign_data,
-                batch_id
+                batch_id,
+                batch_hash,
+                parties_eth_sign_data,
             )
             .execute(transaction.conn())
             .await?;
@@ -236,7 +244,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.mempool.insert_batch", start.elapsed());
+        report_query("chain.mempool.insert_batch", start);
         Ok(batch_id)
     }
 
@@ -251,20 +259,22 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .eth_sign_data
             .as_ref()
             .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+        let parties_eth_sign_data = parties_eth_sign_data_to_value(tx_data);
 
         sqlx::query!(
-            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-            VALUES ($1, $2, $3, $4, $5)",
+            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, parties_eth_sign_data)
+            VALUES ($1, $2, $3, $4, $5, $6)",
             tx_hash,
             tx,
             tx_data.created_at,
             eth_sign_data,
             batch_id,
+            parties_eth_sign_data,
         )
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.insert_tx", start.elapsed());
+        report_query("chain.mempool.insert_tx", start);
         Ok(())
     }
 
@@ -280,7 +290,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_tx", start.elapsed());
+        report_query("chain.mempool.remove_tx", start);
         Ok(())
     }
 
@@ -296,10 +306,65 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_txs", start.elapsed());
+        report_query("chain.mempool.remove_txs", start);
         Ok(())
     }
 
+    /// Loads the pending transactions of the account with the given nonce,
+    /// transactions of the batches are not returned.
+    pub async fn load_txs_by_nonce(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+        // Field containing the account ID depends on the type of the transaction.
+        let txs: Vec<MempoolTx> = sqlx::query_as!(
+            MempoolTx,
+            r#"
+            SELECT * FROM mempool_txs
+            WHERE reverted = false AND batch_id = 0
+                AND (tx->>'nonce')::bigint = $2
+                AND COALESCE(
+                    tx->>'accountId', tx->>'initiatorAccountId', tx->>'creatorId', tx->>'submitterId'
+                )::bigint = $1
+            ORDER BY id
+            "#,
+            i64::from(*account_id),
+            i64::from(*nonce)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("chain.mempool.load_txs_by_nonce", start);
+        txs.into_iter()
+            .map(SignedZkSyncTx::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Returns the number of the pending transactions of the account, including
+    /// the transactions of the batches.
+    pub async fn get_account_txs_count(&mut self, account_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FROM mempool_txs
+            WHERE reverted = false
+                AND COALESCE(
+                    tx->>'accountId', tx->>'initiatorAccountId', tx->>'creatorId', tx->>'submitterId'
+                )::bigint = $1
+            "#,
+            i64::from(*account_id)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        report_query("chain.mempool.get_account_txs_count", start);
+        Ok(count.unwrap_or(0) as u32)
+    }
+
     /// Checks if the memory pool contains transaction with the given hash.
     pub async fn contains_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
         let start = Instant::now();
@@ -317,7 +382,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         let contains = row.filter(|&counter| counter > 0).is_some();
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "contains_tx");
+        report_query("chain.mempool.contains_tx", start);
         Ok(contains)
     }
 
@@ -327,7 +392,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         let mempool_tx = self.get_mempool_tx(tx_hash).await?;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
+        report_query("chain.mempool.get_tx", start);
         mempool_tx
             .map(SignedZkSyncTx::try_from)
             .transpose()
@@ -349,7 +414,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
+        report_query("chain.mempool.get_mempool_tx", start);
         Ok(mempool_tx)
     }
 
@@ -399,7 +464,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         self.remove_txs(&tx_hashes_to_remove).await?;
 
-        metrics::histogram!("sql.chain.mempool.collect_garbage", start.elapsed());
+        report_query("chain.mempool.collect_garbage", start);
         Ok(())
     }
 
@@ -463,7 +528,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.chain", start.elapsed(), "schema" => "mempool", "method" => "insert_priority_ops");
+        report_query("chain.mempool.insert_priority_ops", start);
         Ok(())
     }
 
@@ -548,6 +613,33 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .map(|op| op.into());
         Ok(op)
     }
+    /// Loads the pending priority operations by their Ethereum hashes in one query.
+    pub async fn get_pending_operations_by_hashes(
+        &mut self,
+        eth_hashes: &[H256],
+    ) -> QueryResult<Vec<PriorityOp>> {
+        let start = Instant::now();
+        let eth_hashes: Vec<Vec<u8>> = eth_hashes
+            .iter()
+            .map(|hash| hash.as_bytes().to_vec())
+            .collect();
+        let ops = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+                SELECT serial_id,data,deadline_block,eth_hash,
+                       tx_hash,eth_block,eth_block_index,created_at
+                FROM mempool_priority_operations
+                WHERE eth_hash = ANY($1)
+            "#,
+            &eth_hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("chain.mempool.get_pending_operations_by_hashes", start);
+        Ok(ops.into_iter().map(|op| op.into()).collect())
+    }
+
     pub async fn get_pending_deposits(&mut self, address: Address) -> QueryResult<Vec<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
@@ -564,6 +656,30 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    /// Loads the deposits not executed yet, which are either sent from the given L1 address
+    /// or sent to the account with the given address.
+    pub async fn get_pending_deposits_for_l1_address(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Vec<PriorityOp>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+            SELECT serial_id,data,deadline_block,eth_hash,
+                   tx_hash,eth_block,eth_block_index,created_at
+            FROM mempool_priority_operations
+            WHERE type = 'Deposit' AND (l1_address = $1 OR l2_address = $1)
+            ORDER BY serial_id"#,
+            address.as_bytes().to_vec()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("chain.mempool.get_pending_deposits_for_l1_address", start);
+        Ok(ops.into_iter().map(|op| op.into()).collect())
+    }
+
     pub async fn remove_priority_ops_from_mempool(&mut self, ids: &[u64]) -> QueryResult<()> {
         let ids: Vec<_> = ids.iter().map(|v| *v as i64).collect();
         sqlx::query!(
@@ -575,6 +691,36 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Removes the priority operations received in the Ethereum blocks after the given one,
+    /// e.g. if these blocks were orphaned by the reorg.
+    pub async fn remove_priority_ops_after_eth_block(&mut self, eth_block: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM mempool_priority_operations WHERE eth_block > $1",
+            eth_block as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+        report_query("chain.mempool.remove_priority_ops_after_eth_block", start);
+        Ok(())
+    }
+
+    /// Returns the earliest Ethereum deadline block of the confirmed priority operations
+    /// waiting in the mempool. Returns `None` if there are no such operations.
+    pub async fn get_min_priority_op_deadline(&mut self) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let deadline_block = sqlx::query!(
+            r#"SELECT min(deadline_block) as "deadline_block" FROM mempool_priority_operations WHERE confirmed AND reverted = false"#
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .deadline_block
+        .map(|block| block as u64);
+
+        report_query("chain.mempool.get_min_priority_op_deadline", start);
+        Ok(deadline_block)
+    }
+
     /// Returns mempool size.
     pub async fn get_mempool_size(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
@@ -584,7 +730,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?
             .count;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_mempool_size");
+        report_query("chain.mempool.get_mempool_size", start);
         Ok(size.unwrap_or(0) as u32)
     }
 
@@ -630,7 +776,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_batch_info");
+        report_query("chain.mempool.get_queued_batch_info", start);
         Ok(result)
     }
 
@@ -668,7 +814,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 mempool_reverted_txs_meta.primary_account_address,
                 mempool_txs.created_at,
                 mempool_txs.eth_sign_data,
-                mempool_txs.batch_id as "batch_id?"
+                mempool_txs.batch_id as "batch_id?",
+                mempool_txs.batch_hash
                 FROM mempool_txs INNER JOIN mempool_reverted_txs_meta 
                 ON mempool_txs.tx_hash = mempool_reverted_txs_meta.tx_hash 
                 WHERE mempool_reverted_txs_meta.block_number=$1 AND mempool_reverted_txs_meta.tx_type='L2'"#, 
@@ -873,8 +1020,12 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             let to_account = signed_tx.to_account().map(|a| a.as_bytes().to_vec());
             let primary_account_address = signed_tx.account().as_bytes().to_vec();
 
+            let parties_eth_sign_data = parties_eth_sign_data_to_value(&signed_tx);
             let SignedZkSyncTx {
-                tx, eth_sign_data, ..
+                tx,
+                eth_sign_data,
+                batch_hash,
+                ..
             } = signed_tx;
 
             let tx_hash_bytes = tx.hash().as_ref().to_vec();
@@ -909,14 +1060,16 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?;
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted)
-                VALUES ($1, $2, $3, $4, $5, $6, true)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted, batch_hash, parties_eth_sign_data)
+                VALUES ($1, $2, $3, $4, $5, $6, true, $7, $8)",
                 tx_hash,
                 tx_value,
                 created_at,
                 eth_sign_data,
                 batch_id.unwrap_or(0i64),
                 next_priority_op_serial_id as i64,
+                batch_hash.as_ref().map(H256::as_bytes),
+                parties_eth_sign_data,
             )
             .execute(transaction.conn())
             .await?;
@@ -1020,10 +1173,19 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.mempool.return_executed_txs_to_mempool",
-            start.elapsed()
-        );
+        report_query("chain.mempool.return_executed_txs_to_mempool", start);
         Ok(())
     }
 }
+
+/// Encodes the Ethereum signatures of the transaction parties other than the sender.
+/// Returns `None` for the transactions signed by the sender only.
+fn parties_eth_sign_data_to_value(tx: &SignedZkSyncTx) -> Option<serde_json::Value> {
+    if tx.parties_eth_sign_data.is_empty() {
+        return None;
+    }
+    Some(
+        serde_json::to_value(&tx.parties_eth_sign_data)
+            .expect("failed to encode parties EthSignData"),
+    )
+}
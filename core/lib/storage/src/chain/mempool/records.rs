@@ -33,6 +33,8 @@ pub(crate) struct MempoolTx {
     pub next_priority_op_serial_id: Option<i64>,
     #[allow(dead_code)]
     pub reverted: bool,
+    pub batch_hash: Option<Vec<u8>>,
+    pub parties_eth_sign_data: Option<serde_json::Value>,
 }
 
 impl TryFrom<MempoolTx> for SignedZkSyncTx {
@@ -46,6 +48,12 @@ impl TryFrom<MempoolTx> for SignedZkSyncTx {
                 .map(serde_json::from_value)
                 .transpose()?,
             created_at: value.created_at,
+            batch_hash: value.batch_hash.map(|hash| H256::from_slice(&hash)),
+            parties_eth_sign_data: value
+                .parties_eth_sign_data
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
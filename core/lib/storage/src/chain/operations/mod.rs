@@ -16,7 +16,9 @@ use self::records::{
 use crate::chain::operations::records::StoredExecutedTransaction;
 use crate::chain::operations_ext::OperationsExtSchema;
 use crate::ethereum::EthereumSchema;
-use crate::{chain::mempool::MempoolSchema, QueryResult, StorageProcessor};
+use crate::{
+    chain::mempool::MempoolSchema, instrumentation::report_query, QueryResult, StorageProcessor,
+};
 
 pub mod records;
 
@@ -44,9 +46,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .max
         .unwrap_or(0);
 
-        metrics::histogram!(
-            "sql.chain.operations.get_last_block_by_aggregated_action",
-            start.elapsed()
+        report_query(
+            "chain.operations.get_last_block_by_aggregated_action",
+            start,
         );
         Ok(BlockNumber(max_block as u32))
     }
@@ -69,10 +71,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .ok()
         .flatten();
 
-        metrics::histogram!(
-            "sql.chain.operations.get_stored_aggregated_operations",
-            start.elapsed()
-        );
+        report_query("chain.operations.get_stored_aggregated_operations", start);
         result
     }
 
@@ -89,10 +88,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_operation",
-            start.elapsed()
-        );
+        report_query("chain.operations.get_executed_operation", start);
         Ok(op)
     }
 
@@ -110,10 +106,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_priority_operation",
-            start.elapsed()
-        );
+        report_query("chain.operations.get_executed_priority_operation", start);
         Ok(op)
     }
 
@@ -131,9 +124,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_priority_operation_by_eth_hash",
-            start.elapsed()
+        report_query(
+            "chain.operations.get_executed_priority_operation_by_eth_hash",
+            start,
         );
         Ok(op)
     }
@@ -156,10 +149,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.confirm_aggregated_operations",
-            start.elapsed()
-        );
+        report_query("chain.operations.confirm_aggregated_operations", start);
         Ok(())
     }
 
@@ -182,11 +172,11 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             // Possible scenario: user had no enough funds for transfer, then deposited some and
             // sent the same transfer again.
             sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, batch_hash)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
                 ON CONFLICT (tx_hash)
                 DO UPDATE
-                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14
+                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14, batch_hash = $15
                 RETURNING sequence_number
                 ",
                 operation.block_number,
@@ -203,6 +193,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.batch_hash,
             )
             .fetch_optional(transaction.conn())
             .await?.map(|a| a.sequence_number).flatten()
@@ -210,8 +201,8 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             // If transaction failed, we do nothing on conflict.
             sqlx::query!(
                 "
-                INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, batch_hash)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
                 ON CONFLICT (tx_hash)
                 DO NOTHING
                 RETURNING sequence_number
@@ -230,6 +221,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.batch_hash,
             )
             .fetch_optional(transaction.conn())
             .await?.map(|a| a.sequence_number).flatten()
@@ -260,7 +252,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations.store_executed_tx", start.elapsed());
+        report_query("chain.operations.store_executed_tx", start);
         // It's almost impossible situation, but it could be triggered in tests
         let tx_duration = (Utc::now() - operation.created_at)
             .to_std()
@@ -303,10 +295,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_rejected_transactions",
-            start.elapsed()
-        );
+        report_query("chain.operations.remove_rejected_transactions", start);
         Ok(())
     }
 
@@ -372,10 +361,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations.store_executed_priority_op",
-            start.elapsed()
-        );
+        report_query("chain.operations.store_executed_priority_op", start);
         Ok(())
     }
 
@@ -390,13 +376,33 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .await?;
         let max_serial_id = max_serial_id.max.map(|record| record as u64);
 
-        metrics::histogram!(
-            "sql.chain.operations.get_max_priority_op_serial_id",
-            start.elapsed()
-        );
+        report_query("chain.operations.get_max_priority_op_serial_id", start);
         Ok(max_serial_id)
     }
 
+    /// Returns the earliest Ethereum deadline block of the priority operations executed
+    /// in the blocks after the given one. Returns `None` if there are no such operations.
+    pub async fn get_min_priority_op_deadline_after_block(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let deadline_block = sqlx::query!(
+            r#"SELECT min(deadline_block) as "deadline_block" FROM executed_priority_operations WHERE block_number > $1"#,
+            i64::from(*block_number)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .deadline_block
+        .map(|block| block as u64);
+
+        report_query(
+            "chain.operations.get_min_priority_op_deadline_after_block",
+            start,
+        );
+        Ok(deadline_block)
+    }
+
     /// On old contracts, a separate operation was used to withdraw - `CompleteWithdrawals`.
     ///
     /// NOTE: Currently `CompleteWithdrawals` is deprecated but the information is still stored
@@ -437,9 +443,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             None => None,
         };
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_withdraw_tx_for_complete_withdrawal",
-            start.elapsed()
+        report_query(
+            "chain.operations.eth_withdraw_tx_for_complete_withdrawal",
+            start,
         );
         Ok(res)
     }
@@ -465,10 +471,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .aggregated_op_final_hash(block_number)
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_withdraw_tx_for_execute_block",
-            start.elapsed()
-        );
+        report_query("chain.operations.eth_withdraw_tx_for_execute_block", start);
         Ok(withdrawal_hash)
     }
 
@@ -492,10 +495,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         let eth_tx_hash =
             eth_withdraw_tx_for_execute_block.or(eth_withdraw_tx_for_complete_withdrawal);
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_tx_for_withdrawal",
-            start.elapsed()
-        );
+        report_query("chain.operations.eth_tx_for_withdrawal", start);
 
         Ok(eth_tx_hash)
     }
@@ -612,9 +612,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .execute(self.0.conn())
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_eth_unprocessed_aggregated_ops",
-            start.elapsed()
+        report_query(
+            "chain.operations.remove_eth_unprocessed_aggregated_ops",
+            start,
         );
         Ok(())
     }
@@ -655,9 +655,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .await?;
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations.remove_executed_priority_operations",
-            start.elapsed()
+        report_query(
+            "chain.operations.remove_executed_priority_operations",
+            start,
         );
         Ok(())
     }
@@ -676,10 +676,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .execute(transaction.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_aggregate_operations",
-            start.elapsed()
-        );
+        report_query("chain.operations.remove_aggregate_operations", start);
         Ok(())
     }
 
@@ -739,9 +736,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.remove_aggregate_operations_and_bindings",
-            start.elapsed()
+        report_query(
+            "chain.operations.remove_aggregate_operations_and_bindings",
+            start,
         );
         Ok(())
     }
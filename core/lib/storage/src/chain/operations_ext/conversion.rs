@@ -2,8 +2,8 @@
 // External imports
 // Workspace imports
 use zksync_api_types::v02::transaction::{
-    ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
-    TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
+    ForcedExitData, L1Finality, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt,
+    Transaction, TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
 };
 use zksync_types::{
     tx::{EthSignData, TxHash},
@@ -16,6 +16,7 @@ impl StorageTxReceipt {
     pub(super) fn receipt_from_storage_receipt(
         receipt: StorageTxReceipt,
         is_block_finalized: Option<bool>,
+        l1_finality: Option<L1Finality>,
     ) -> Receipt {
         if receipt.block_number.is_some() {
             let status = if receipt.success.unwrap() {
@@ -35,6 +36,7 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     id: receipt.priority_op_serialid.unwrap() as u64,
+                    l1_finality,
                 })
             } else {
                 Receipt::L2(L2Receipt {
@@ -44,6 +46,7 @@ impl StorageTxReceipt {
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
                     fail_reason: receipt.fail_reason,
+                    l1_finality,
                 })
             }
         } else {
@@ -52,6 +55,7 @@ impl StorageTxReceipt {
                 tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                 rollup_block: None,
                 fail_reason: None,
+                l1_finality: None,
             })
         }
     }
@@ -1,5 +1,5 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashMap, str::FromStr, time::Instant};
 
 // External imports
 use chrono::{DateTime, Utc};
@@ -7,24 +7,29 @@ use chrono::{DateTime, Utc};
 // Workspace imports
 use zksync_api_types::{
     v02::{
-        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
+        account::{BalanceChange, BalanceHistoryItem},
+        pagination::{
+            AccountBalanceHistoryRequest, AccountTxsFilters, AccountTxsRequest,
+            PaginationDirection, PaginationQuery,
+        },
         transaction::{
-            ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
-            TxInBlockStatus,
+            ApiTxBatch, BatchStatus, L1Finality, L1TxInfo, Receipt, Transaction, TxData,
+            TxHashSerializeWrapper, TxInBlockStatus,
         },
     },
     Either,
 };
 use zksync_crypto::params;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, TokenId,
-    ZkSyncOp, ZkSyncTx, H256,
+    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, EthBlockId,
+    TokenId, ZkSyncOp, ZkSyncTx, H256,
 };
 
 // Local imports
 use self::records::{
-    AccountCreatedAt, InBlockBatchTx, PriorityOpReceiptResponse, StorageTxData, StorageTxReceipt,
-    TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse, Web3TxData, Web3TxReceipt,
+    AccountCreatedAt, AccountOperationItem, InBlockBatchTx, PriorityOpReceiptResponse,
+    StorageTxData, StorageTxReceipt, StorageTxReceiptByHash, TransactionsHistoryItem,
+    TxByHashResponse, TxReceiptResponse, Web3TxData, Web3TxReceipt,
 };
 use crate::chain::operations_ext::records::SequenceNumberRecord;
 use crate::{
@@ -32,6 +37,7 @@ use crate::{
         block::records::TransactionItem,
         operations::{records::StoredExecutedPriorityOperation, OperationsSchema},
     },
+    instrumentation::report_query,
     QueryResult, StorageProcessor,
 };
 use itertools::Itertools;
@@ -85,7 +91,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             Ok(None)
         };
 
-        metrics::histogram!("sql.chain.operations_ext.tx_receipt", start.elapsed());
+        report_query("chain.operations_ext.tx_receipt", start);
         result
     }
 
@@ -152,20 +158,142 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         let result = if let Some(receipt) = receipt {
             let is_block_finalized =
                 is_block_finalized(&mut transaction, receipt.block_number).await?;
+            let l1_finality = l1_finality(&mut transaction, receipt.block_number).await?;
 
             Some(StorageTxReceipt::receipt_from_storage_receipt(
                 receipt,
                 is_block_finalized,
+                l1_finality,
             ))
         } else {
             None
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.tx_receipt_api_v02",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.tx_receipt_api_v02", start);
+        Ok(result)
+    }
+
+    /// Loads the receipts of the transactions in one query, the receipts are returned in the order
+    /// of the `hashes`, `None` for the transactions which are not found. See `tx_receipt_api_v02`.
+    pub async fn tx_receipts_api_v02(
+        &mut self,
+        hashes: &[TxHash],
+    ) -> QueryResult<Vec<Option<Receipt>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let hashes_bytes: Vec<Vec<u8>> = hashes.iter().map(|hash| hash.as_ref().to_vec()).collect();
+        let hashes_str: Vec<String> = hashes.iter().map(hex::encode).collect();
+        let receipts: Vec<StorageTxReceiptByHash> = sqlx::query_as!(
+            StorageTxReceiptByHash,
+            r#"
+                WITH transaction AS (
+                    SELECT
+                        tx_hash as hash,
+                        tx_hash,
+                        block_number,
+                        success,
+                        fail_reason,
+                        Null::bigint as eth_block,
+                        Null::bigint as priority_op_serialid
+                    FROM executed_transactions
+                    WHERE tx_hash = ANY($1)
+                ), priority_op AS (
+                    SELECT
+                        tx_hash as hash,
+                        tx_hash,
+                        block_number,
+                        true as success,
+                        Null as fail_reason,
+                        eth_block,
+                        priority_op_serialid
+                    FROM executed_priority_operations
+                    WHERE tx_hash = ANY($1)
+                    UNION ALL
+                    SELECT
+                        eth_hash as hash,
+                        tx_hash,
+                        block_number,
+                        true as success,
+                        Null as fail_reason,
+                        eth_block,
+                        priority_op_serialid
+                    FROM executed_priority_operations
+                    WHERE eth_hash = ANY($1)
+                ), mempool_tx AS (
+                    SELECT
+                        decode(tx_hash, 'hex') as hash,
+                        decode(tx_hash, 'hex'),
+                        Null::bigint as block_number,
+                        Null::boolean as success,
+                        Null as fail_reason,
+                        Null::bigint as eth_block,
+                        Null::bigint as priority_op_serialid
+                    FROM mempool_txs
+                    WHERE tx_hash = ANY($2)
+                ),
+                everything AS (
+                    SELECT * FROM transaction
+                    UNION ALL
+                    SELECT * FROM priority_op
+                    UNION ALL
+                    SELECT * FROM mempool_tx
+                )
+                SELECT
+                    hash as "hash!",
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number?",
+                    success as "success?",
+                    fail_reason as "fail_reason?",
+                    eth_block as "eth_block?",
+                    priority_op_serialid as "priority_op_serialid?"
+                FROM everything
+            "#,
+            &hashes_bytes,
+            &hashes_str
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        // The first receipt found for the hash is used, as in `tx_receipt_api_v02`.
+        let mut receipts_by_hash = HashMap::new();
+        for receipt in receipts {
+            receipts_by_hash
+                .entry(receipt.hash.clone())
+                .or_insert_with(|| StorageTxReceipt::from(receipt));
+        }
+
+        // Transactions of the same block share the block status.
+        let mut blocks = HashMap::new();
+        let mut result = Vec::with_capacity(hashes.len());
+        for hash in &hashes_bytes {
+            let receipt = match receipts_by_hash.remove(hash) {
+                Some(receipt) => receipt,
+                None => {
+                    result.push(None);
+                    continue;
+                }
+            };
+            let (is_block_finalized, l1_finality) = match blocks.get(&receipt.block_number) {
+                Some(block_status) => *block_status,
+                None => {
+                    let block_status = (
+                        is_block_finalized(&mut transaction, receipt.block_number).await?,
+                        l1_finality(&mut transaction, receipt.block_number).await?,
+                    );
+                    blocks.insert(receipt.block_number, block_status);
+                    block_status
+                }
+            };
+            result.push(Some(StorageTxReceipt::receipt_from_storage_receipt(
+                receipt,
+                is_block_finalized,
+                l1_finality,
+            )));
+        }
+
+        transaction.commit().await?;
+        report_query("chain.operations_ext.tx_receipts_api_v02", start);
         Ok(result)
     }
 
@@ -242,9 +370,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.tx_data_by_block_and_index_api_v02",
-            start.elapsed()
+        report_query(
+            "chain.operations_ext.tx_data_by_block_and_index_api_v02",
+            start,
         );
         Ok(result)
     }
@@ -336,7 +464,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations_ext.tx_data_api_v02", start.elapsed());
+        report_query("chain.operations_ext.tx_data_api_v02", start);
         Ok(result)
     }
 
@@ -373,10 +501,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             }),
         };
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_priority_op_receipt",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.get_priority_op_receipt", start);
         result
     }
 
@@ -391,7 +516,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             self.find_priority_op_by_hash(hash).await?
         };
 
-        metrics::histogram!("sql.chain.operations_ext.get_tx_by_hash", start.elapsed());
+        report_query("chain.operations_ext.get_tx_by_hash", start);
         Ok(result)
     }
 
@@ -525,7 +650,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!("sql.chain.operations_ext.find_tx_by_hash", start.elapsed());
+        report_query("chain.operations_ext.find_tx_by_hash", start);
         Ok(result)
     }
 
@@ -606,10 +731,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.find_priority_op_by_hash",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.find_priority_op_by_hash", start);
         Ok(result)
     }
 
@@ -658,10 +780,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.account_created_on",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.account_created_on", start);
         Ok(first_history_entry.map(|entry| entry.created_at))
     }
 
@@ -804,9 +923,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_history",
-            start.elapsed()
+        report_query(
+            "chain.operations_ext.get_account_transactions_history",
+            start,
         );
         Ok(tx_history)
     }
@@ -990,9 +1109,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_history_from",
-            start.elapsed()
+        report_query(
+            "chain.operations_ext.get_account_transactions_history_from",
+            start,
         );
         Ok(tx_history)
     }
@@ -1034,6 +1153,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         query.from.address,
                         address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1048,6 +1168,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     .get_tx_seq_numbers_for_account(
                         query.from.address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1076,10 +1197,12 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         .await?,
                 );
 
+                // Sequence numbers are unique, unlike the creation time,
+                // so the order of the transactions is the same for every page.
                 txs.into_iter()
                     .sorted_by(|tx1, tx2| match query.direction {
-                        PaginationDirection::Newer => tx1.created_at.cmp(&tx2.created_at),
-                        PaginationDirection::Older => tx2.created_at.cmp(&tx1.created_at),
+                        PaginationDirection::Newer => tx1.sequence_number.cmp(&tx2.sequence_number),
+                        PaginationDirection::Older => tx2.sequence_number.cmp(&tx1.sequence_number),
                     })
                     .collect()
             };
@@ -1105,34 +1228,164 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.get_account_transactions", start);
         Ok(txs)
     }
 
+    /// Loads the balance history of the account: its executed operations along with
+    /// the changes of the balances they made, see `BalanceChange::from_executed_op`.
+    ///
+    /// The page is taken from the transactions of the account, so it may contain fewer items
+    /// than the limit, since the failed transactions and the ones not changing the balances
+    /// are skipped. Returns `None` if the transaction to paginate from is not found.
+    pub async fn get_account_balance_history(
+        &mut self,
+        query: &PaginationQuery<AccountBalanceHistoryRequest>,
+    ) -> QueryResult<Option<Vec<BalanceHistoryItem>>> {
+        let start = Instant::now();
+        let account_id = match query.from.account_id {
+            Some(account_id) => account_id,
+            None => return Ok(Some(Vec::new())),
+        };
+        let mut transaction = self.0.start_transaction().await?;
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_account_last_tx_hash(query.from.address)
+                    .await?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Some(Vec::new()));
+                }
+            }
+        };
+        let sequence_number = transaction
+            .chain()
+            .operations_ext_schema()
+            .get_tx_sequence_number(tx_hash)
+            .await?;
+
+        let history = if let Some(id_from) = sequence_number {
+            let sequence_numbers: Vec<i64> = transaction
+                .chain()
+                .operations_ext_schema()
+                .get_tx_seq_numbers_for_account(
+                    query.from.address,
+                    None,
+                    &AccountTxsFilters::default(),
+                    i64::from(query.limit),
+                    id_from,
+                    query.direction,
+                )
+                .await?
+                .into_iter()
+                .map(|record| record.sequence_number)
+                .collect();
+
+            let operations = sqlx::query_as!(
+                AccountOperationItem,
+                r#"
+                SELECT
+                    sequence_number as "sequence_number!",
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number!",
+                    block_index,
+                    operation as "operation!",
+                    created_at as "created_at!"
+                FROM executed_transactions
+                WHERE success = true AND sequence_number = ANY($1)
+                UNION ALL
+                SELECT
+                    sequence_number as "sequence_number!",
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number!",
+                    block_index,
+                    operation as "operation!",
+                    created_at as "created_at!"
+                FROM executed_priority_operations
+                WHERE sequence_number = ANY($1)
+                "#,
+                &sequence_numbers
+            )
+            .fetch_all(transaction.conn())
+            .await?;
+
+            let last_finalized = transaction
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await?;
+            let history = operations
+                .into_iter()
+                .sorted_by(|op1, op2| match query.direction {
+                    PaginationDirection::Newer => op1.sequence_number.cmp(&op2.sequence_number),
+                    PaginationDirection::Older => op2.sequence_number.cmp(&op1.sequence_number),
+                })
+                .filter_map(|item| {
+                    let op: ZkSyncOp =
+                        serde_json::from_value(item.operation).expect("Unparsable operation in db");
+                    let changes = BalanceChange::from_executed_op(&op, account_id);
+                    if changes.is_empty() {
+                        return None;
+                    }
+                    let block_number = BlockNumber(item.block_number as u32);
+                    let status = if *block_number <= *last_finalized {
+                        TxInBlockStatus::Finalized
+                    } else {
+                        TxInBlockStatus::Committed
+                    };
+                    Some(BalanceHistoryItem {
+                        tx_hash: TxHash::from_slice(&item.tx_hash).unwrap(),
+                        block_number,
+                        block_index: item.block_index.map(|index| index as u32),
+                        status,
+                        created_at: item.created_at,
+                        changes,
+                    })
+                })
+                .collect();
+            Some(history)
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        report_query("chain.operations_ext.get_account_balance_history", start);
+        Ok(history)
+    }
+
     async fn get_executed_transactions_for_two_accounts(
         &mut self,
         address: Address,
         second_address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<TransactionItem>> {
-        let query_direction = match direction {
-            PaginationDirection::Newer => {
-                "WHERE sequence_number >= $4 
-                ORDER BY sequence_number 
-                LIMIT $5"
-            }
-            PaginationDirection::Older => {
-                "WHERE sequence_number <= $4
-                ORDER BY sequence_number DESC
-                LIMIT $5"
-            }
+        let (sequence_number_query, order_query) = match direction {
+            PaginationDirection::Newer => (
+                "WHERE sequence_number >= $4",
+                "ORDER BY sequence_number
+                LIMIT $5",
+            ),
+            PaginationDirection::Older => (
+                "WHERE sequence_number <= $4",
+                "ORDER BY sequence_number DESC
+                LIMIT $5",
+            ),
         };
+        let filters_query = account_txs_filters_query(
+            filters,
+            "executed_transactions.tx->>'type'",
+            "executed_transactions.created_at",
+            6,
+        );
 
         let token_query = if token.is_some() {
             "AND token = $3"
@@ -1163,10 +1416,10 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     batch_id
                 FROM tx_hashes INNER JOIN executed_transactions 
                     ON tx_hashes.tx_hash = executed_transactions.tx_hash
+                {} {}
                 {}
-                
             "#,
-            token_query, token_query, query_direction
+            token_query, token_query, sequence_number_query, filters_query, order_query
         );
 
         Ok(sqlx::query_as(&query)
@@ -1175,6 +1428,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(filters_tx_types(filters))
+            .bind(filters.from_date)
+            .bind(filters.to_date)
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1183,32 +1439,37 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         &mut self,
         address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<SequenceNumberRecord>> {
         let query_direction = match direction {
             PaginationDirection::Newer => {
-                "AND sequence_number  >= $3
-                ORDER BY sequence_number
+                "AND tx_filters.sequence_number >= $3
+                ORDER BY tx_filters.sequence_number
                 LIMIT $4"
             }
             PaginationDirection::Older => {
-                "AND sequence_number <= $3
-                ORDER BY sequence_number DESC
+                "AND tx_filters.sequence_number <= $3
+                ORDER BY tx_filters.sequence_number DESC
                 LIMIT $4"
             }
         };
 
         let token_query = if token.is_some() {
-            "AND token = $2"
+            "AND tx_filters.token = $2"
         } else {
             ""
         };
 
         let query = format!(
-            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
-            token_query, query_direction
+            "SELECT DISTINCT tx_filters.sequence_number, tx_filters.is_priority FROM tx_filters {} 
+            WHERE tx_filters.address = $1 {} {} {}",
+            filters_join_query(filters),
+            token_query,
+            account_txs_filters_query(filters, FILTERED_TX_TYPE, FILTERED_TX_CREATED_AT, 5),
+            query_direction
         );
 
         Ok(sqlx::query_as(&query)
@@ -1216,6 +1477,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(filters_tx_types(filters))
+            .bind(filters.from_date)
+            .bind(filters.to_date)
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1304,10 +1568,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .await?;
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_last_tx_hash",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.get_account_last_tx_hash", start);
         Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
     }
 
@@ -1342,10 +1603,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_block_last_tx_hash",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.get_block_last_tx_hash", start);
         Ok(record.map(|record| TxHash::from_slice(&record.tx_hash).unwrap()))
     }
 
@@ -1354,10 +1612,14 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         address: Address,
         token: Option<TokenId>,
         second_address: Option<Address>,
+        filters: &AccountTxsFilters,
     ) -> QueryResult<u32> {
         let start = Instant::now();
 
-        let count = if let Some(second_address) = second_address {
+        let count = if !filters.is_empty() {
+            self.get_filtered_account_transactions_count(address, token, second_address, filters)
+                .await?
+        } else if let Some(second_address) = second_address {
             sqlx::query!(
                 r#"
                 WITH tx_hashes AS (
@@ -1395,13 +1657,64 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .await?
             .count
         };
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_account_transactions_count",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.get_account_transactions_count", start);
         Ok(count as u32)
     }
 
+    async fn get_filtered_account_transactions_count(
+        &mut self,
+        address: Address,
+        token: Option<TokenId>,
+        second_address: Option<Address>,
+        filters: &AccountTxsFilters,
+    ) -> QueryResult<i64> {
+        let query = if second_address.is_some() {
+            // It's impossible to have priority operations for two accounts
+            format!(
+                r#"
+                WITH tx_hashes AS (
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $1 AND ($2::boolean OR token = $3)
+                    INTERSECT
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $4 AND ($2::boolean OR token = $3)
+                )
+                SELECT COUNT(DISTINCT tx_hashes.tx_hash) FROM tx_hashes
+                INNER JOIN executed_transactions
+                    ON tx_hashes.tx_hash = executed_transactions.tx_hash
+                WHERE TRUE {}
+                "#,
+                account_txs_filters_query(
+                    filters,
+                    "executed_transactions.tx->>'type'",
+                    "executed_transactions.created_at",
+                    5
+                )
+            )
+        } else {
+            format!(
+                r#"
+                SELECT COUNT(DISTINCT tx_filters.tx_hash) FROM tx_filters {}
+                WHERE tx_filters.address = $1 AND ($2::boolean OR tx_filters.token = $3) {}
+                "#,
+                filters_join_query(filters),
+                account_txs_filters_query(filters, FILTERED_TX_TYPE, FILTERED_TX_CREATED_AT, 5)
+            )
+        };
+
+        let (count,): (i64,) = sqlx::query_as(&query)
+            .bind(address.as_bytes())
+            .bind(token.is_none())
+            .bind(token.unwrap_or_default().0 as i32)
+            .bind(second_address.unwrap_or_default().as_bytes())
+            .bind(filters_tx_types(filters))
+            .bind(filters.from_date)
+            .bind(filters.to_date)
+            .fetch_one(self.0.conn())
+            .await?;
+        Ok(count)
+    }
+
     /// Returns `created_at` for `block_number` fields for transaction with given hash.
     pub async fn get_tx_sequence_number_for_block(
         &mut self,
@@ -1440,7 +1753,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .flatten();
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_tx_sequence_number", start.elapsed());
+        report_query("chain.block.get_tx_sequence_number_for_block", start);
         Ok(result)
     }
     /// Returns `created_at` and `block_number` fields for transaction with given hash.
@@ -1474,7 +1787,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .flatten();
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_tx_sequence_number", start.elapsed());
+        report_query("chain.block.get_tx_sequence_number", start);
         Ok(result)
     }
 
@@ -1543,7 +1856,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_in_block_batch_info", start.elapsed());
+        report_query("chain.block.get_in_block_batch_info", start);
         Ok(result)
     }
 
@@ -1567,7 +1880,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         };
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.get_batch_info", start.elapsed());
+        report_query("chain.block.get_batch_info", start);
         Ok(result)
     }
 
@@ -1620,7 +1933,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.operations_ext.tx_data_for_web3", start.elapsed());
+        report_query("chain.operations_ext.tx_data_for_web3", start);
         Ok(result)
     }
 
@@ -1683,10 +1996,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .fetch_optional(self.0.conn())
             .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.web3_receipt_by_hash",
-            start.elapsed()
-        );
+        report_query("chain.operations_ext.web3_receipt_by_hash", start);
         Ok(tx)
     }
 
@@ -1751,7 +2061,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .fetch_all(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.chain.operations_ext.web3_receipts", start.elapsed());
+        report_query("chain.operations_ext.web3_receipts", start);
         Ok(receipts)
     }
 
@@ -1981,6 +2291,58 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     }
 }
 
+/// Type of the operation joined to `tx_filters` by `filters_join_query`.
+const FILTERED_TX_TYPE: &str =
+    "COALESCE(filtered_txs.tx->>'type', filtered_priority_ops.operation->>'type')";
+/// Creation time of the operation joined to `tx_filters` by `filters_join_query`.
+const FILTERED_TX_CREATED_AT: &str =
+    "COALESCE(filtered_txs.created_at, filtered_priority_ops.created_at)";
+
+/// Joins the operations to `tx_filters`, so the account history can be filtered
+/// by `AccountTxsFilters`. Nothing is joined if there are no filters.
+fn filters_join_query(filters: &AccountTxsFilters) -> &'static str {
+    if filters.is_empty() {
+        ""
+    } else {
+        "LEFT JOIN executed_transactions filtered_txs
+            ON NOT tx_filters.is_priority
+            AND filtered_txs.sequence_number = tx_filters.sequence_number
+        LEFT JOIN executed_priority_operations filtered_priority_ops
+            ON tx_filters.is_priority
+            AND filtered_priority_ops.sequence_number = tx_filters.sequence_number"
+    }
+}
+
+/// Returns the conditions applying `AccountTxsFilters` to the operations with the given
+/// type and creation time expressions. Filters are bound as the three consecutive
+/// parameters starting from `first_param`, see `filters_tx_types`.
+fn account_txs_filters_query(
+    filters: &AccountTxsFilters,
+    tx_type: &str,
+    created_at: &str,
+    first_param: usize,
+) -> String {
+    let mut query = String::new();
+    if !filters.tx_types.is_empty() {
+        query.push_str(&format!(" AND {} = ANY(${})", tx_type, first_param));
+    }
+    if filters.from_date.is_some() {
+        query.push_str(&format!(" AND {} >= ${}", created_at, first_param + 1));
+    }
+    if filters.to_date.is_some() {
+        query.push_str(&format!(" AND {} < ${}", created_at, first_param + 2));
+    }
+    query
+}
+
+fn filters_tx_types(filters: &AccountTxsFilters) -> Vec<String> {
+    filters
+        .tx_types
+        .iter()
+        .map(|tx_type| tx_type.as_str().to_string())
+        .collect()
+}
+
 async fn complete_withdrawals_tx_hash(
     transaction: &mut StorageProcessor<'_>,
     data: &StorageTxData,
@@ -2021,6 +2383,39 @@ async fn is_block_finalized(
     }
 }
 
+async fn l1_finality(
+    transaction: &mut StorageProcessor<'_>,
+    block_number: Option<i64>,
+) -> QueryResult<Option<L1Finality>> {
+    let block_number = match block_number {
+        Some(block_number) => BlockNumber(block_number as u32),
+        None => return Ok(None),
+    };
+    let eth_txs = transaction
+        .ethereum_schema()
+        .load_block_eth_txs(block_number)
+        .await?;
+    let last_eth_block = transaction.ethereum_schema().load_last_eth_block().await?;
+
+    let mut finality = L1Finality::default();
+    for eth_tx in eth_txs {
+        let eth_block = eth_tx.eth_block.map(|block| block as u64);
+        let tx_info = L1TxInfo {
+            tx_hash: H256::from_slice(&eth_tx.tx_hash),
+            eth_block: eth_block.map(EthBlockId),
+            confirmations: eth_block
+                .zip(last_eth_block)
+                .map(|(eth_block, last_eth_block)| last_eth_block.saturating_sub(eth_block)),
+        };
+        match AggregatedActionType::from_str(&eth_tx.action_type) {
+            Ok(AggregatedActionType::CommitBlocks) => finality.commit = Some(tx_info),
+            Ok(AggregatedActionType::ExecuteBlocks) => finality.verify = Some(tx_info),
+            _ => {}
+        }
+    }
+    Ok(Some(finality))
+}
+
 async fn tx_data_from_storage(
     transaction: &mut StorageProcessor<'_>,
     data: StorageTxData,
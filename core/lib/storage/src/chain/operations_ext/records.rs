@@ -161,6 +161,32 @@ pub struct StorageTxReceipt {
     pub priority_op_serialid: Option<i64>,
 }
 
+/// Receipt of the transaction matching the requested `hash`, which is either the hash
+/// of the transaction or the Ethereum hash of the priority operation.
+#[derive(Debug, FromRow, PartialEq)]
+pub struct StorageTxReceiptByHash {
+    pub hash: Vec<u8>,
+    pub tx_hash: Vec<u8>,
+    pub block_number: Option<i64>,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    pub eth_block: Option<i64>,
+    pub priority_op_serialid: Option<i64>,
+}
+
+impl From<StorageTxReceiptByHash> for StorageTxReceipt {
+    fn from(receipt: StorageTxReceiptByHash) -> Self {
+        Self {
+            tx_hash: receipt.tx_hash,
+            block_number: receipt.block_number,
+            success: receipt.success,
+            fail_reason: receipt.fail_reason,
+            eth_block: receipt.eth_block,
+            priority_op_serialid: receipt.priority_op_serialid,
+        }
+    }
+}
+
 #[derive(Debug, FromRow, PartialEq)]
 pub struct StorageTxData {
     pub tx_hash: Vec<u8>,
@@ -204,3 +230,13 @@ pub struct SequenceNumberRecord {
     pub sequence_number: i64,
     pub is_priority: bool,
 }
+
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct AccountOperationItem {
+    pub sequence_number: i64,
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub block_index: Option<i32>,
+    pub operation: Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -18,7 +18,7 @@ use crate::chain::{
 use crate::diff::StorageAccountDiff;
 use crate::utils::address_to_stored_string;
 // use crate::schema::*;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 /// State schema is capable of managing... well, the state of the chain.
 ///
@@ -216,7 +216,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.commit_state_update", start.elapsed());
+        report_query("chain.state.commit_state_update", start);
         Ok(())
     }
 
@@ -436,7 +436,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.apply_state_update", start.elapsed());
+        report_query("chain.state.apply_state_update", start);
         Ok(())
     }
 
@@ -474,7 +474,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.load_committed_state", start.elapsed());
+        report_query("chain.state.load_committed_state", start);
         result
     }
 
@@ -524,7 +524,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_verified_state", start.elapsed());
+        report_query("chain.state.load_verified_state", start);
         Ok((last_block, account_map))
     }
 
@@ -565,46 +565,8 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             cmp::max(from_block, to_block_resolved),
         );
 
-        // Collect the stored updates. This includes collecting entries from three tables:
-        // `account_creates` (for creating/removing accounts),
-        // `account_balance_updates` (for changing the balance of accounts),
-        // `account_pubkey_updates` (for changing the accounts public keys).
-        // The updates are loaded for the given blocks range.
-        let account_balance_diff = sqlx::query_as!(
-            StorageAccountUpdate,
-            "SELECT * FROM account_balance_updates WHERE block_number > $1 AND block_number <= $2 ",
-            i64::from(*start_block),
-            i64::from(*end_block),
-        )
-        .fetch_all(transaction.conn())
-        .await?;
-
-        let account_creation_diff = sqlx::query_as!(
-            StorageAccountCreation,
-            "SELECT * FROM account_creates WHERE block_number > $1 AND block_number <= $2 ",
-            i64::from(*start_block),
-            i64::from(*end_block),
-        )
-        .fetch_all(transaction.conn())
-        .await?;
-
-        let account_pubkey_diff = sqlx::query_as!(
-            StorageAccountPubkeyUpdate,
-            "SELECT * FROM account_pubkey_updates WHERE block_number > $1 AND block_number <= $2 ",
-            i64::from(*start_block),
-            i64::from(*end_block),
-        )
-        .fetch_all(transaction.conn())
-        .await?;
-
-        let mint_nft_diffs = sqlx::query_as!(
-            StorageMintNFTUpdate,
-            "SELECT * FROM mint_nft_updates WHERE block_number > $1 AND block_number <= $2 ",
-            i64::from(*start_block),
-            i64::from(*end_block),
-        )
-        .fetch_all(transaction.conn())
-        .await?;
+        let account_diff =
+            Self::load_account_diff(&mut transaction, start_block, end_block).await?;
 
         vlog::debug!(
             "Loading state diff: forward: {}, start_block: {}, end_block: {}, unbounded: {}",
@@ -613,43 +575,20 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             *end_block,
             to_block.is_none()
         );
-        vlog::debug!("Loaded account balance diff: {:#?}", account_balance_diff);
-        vlog::debug!("Loaded account creation diff: {:#?}", account_creation_diff);
+        vlog::debug!("Loaded account diff: {:#?}", account_diff);
 
         // Fold the updates into one list and determine the actual last block
         // (since user-provided one may not exist yet).
-        let (mut account_updates, last_block) = {
-            let mut account_diff = Vec::new();
-            account_diff.extend(
-                account_balance_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(
-                account_creation_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(
-                account_pubkey_diff
-                    .into_iter()
-                    .map(StorageAccountDiff::from),
-            );
-            account_diff.extend(mint_nft_diffs.into_iter().map(StorageAccountDiff::from));
-            let last_block = account_diff
-                .iter()
-                .map(|acc| acc.block_number())
-                .max()
-                .unwrap_or(0);
-            account_diff.sort_by(StorageAccountDiff::cmp_order);
-            (
-                account_diff
-                    .into_iter()
-                    .map(|d| d.into())
-                    .collect::<AccountUpdates>(),
-                BlockNumber(last_block as u32),
-            )
-        };
+        let last_block = account_diff
+            .iter()
+            .map(|acc| acc.block_number())
+            .max()
+            .unwrap_or(0);
+        let last_block = BlockNumber(last_block as u32);
+        let mut account_updates = account_diff
+            .into_iter()
+            .map(|d| d.into())
+            .collect::<AccountUpdates>();
 
         // Reverse the blocks order if needed.
         if !time_forward {
@@ -665,7 +604,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        report_query("chain.state.load_state_diff", start);
 
         // We don't want to return an empty list to avoid the confusion, so return
         // `None` if there are no changes.
@@ -676,6 +615,36 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         }
     }
 
+    /// Loads the updates of the account states made in the blocks from `from_block` to `to_block`
+    /// inclusive, grouped by the blocks in the ascending order. Blocks without updates are omitted.
+    pub async fn load_blocks_state_diff(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<(BlockNumber, AccountUpdates)>> {
+        let start = Instant::now();
+        let account_diff = Self::load_account_diff(
+            self.0,
+            BlockNumber((*from_block).saturating_sub(1)),
+            to_block,
+        )
+        .await?;
+
+        let mut blocks: Vec<(BlockNumber, AccountUpdates)> = Vec::new();
+        for diff in account_diff {
+            let block_number = BlockNumber(diff.block_number() as u32);
+            match blocks.last_mut() {
+                Some((last_block, updates)) if *last_block == block_number => {
+                    updates.push(diff.into())
+                }
+                _ => blocks.push((block_number, vec![diff.into()])),
+            }
+        }
+
+        report_query("chain.state.load_blocks_state_diff", start);
+        Ok(blocks)
+    }
+
     /// Loads the state of accounts updated in a specific block.
     pub async fn load_state_diff_for_block(
         &mut self,
@@ -687,10 +656,78 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             .await
             .map(|diff| diff.unwrap_or_default().1);
 
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        report_query("chain.state.load_state_diff_for_block", start);
         result
     }
 
+    /// Collects the stored updates of the blocks after `start_block` up to `end_block` inclusive,
+    /// sorted in the order they were applied. This includes collecting entries from four tables:
+    /// `account_creates` (for creating/removing accounts),
+    /// `account_balance_updates` (for changing the balance of accounts),
+    /// `account_pubkey_updates` (for changing the accounts public keys),
+    /// `mint_nft_updates` (for minting the NFTs).
+    async fn load_account_diff(
+        storage: &mut StorageProcessor<'_>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> QueryResult<Vec<StorageAccountDiff>> {
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "SELECT * FROM account_balance_updates WHERE block_number > $1 AND block_number <= $2 ",
+            i64::from(*start_block),
+            i64::from(*end_block),
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "SELECT * FROM account_creates WHERE block_number > $1 AND block_number <= $2 ",
+            i64::from(*start_block),
+            i64::from(*end_block),
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "SELECT * FROM account_pubkey_updates WHERE block_number > $1 AND block_number <= $2 ",
+            i64::from(*start_block),
+            i64::from(*end_block),
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let mint_nft_diffs = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "SELECT * FROM mint_nft_updates WHERE block_number > $1 AND block_number <= $2 ",
+            i64::from(*start_block),
+            i64::from(*end_block),
+        )
+        .fetch_all(storage.conn())
+        .await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_diffs.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+        Ok(account_diff)
+    }
+
     pub async fn get_mint_nft_update(&mut self, token_id: TokenId) -> QueryResult<Option<NFT>> {
         let start = Instant::now();
         let nft = sqlx::query_as!(
@@ -704,7 +741,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_mint_nft_update", start.elapsed());
+        report_query("token.get_mint_nft_update", start);
         Ok(nft.map(|p| p.into()))
     }
 
@@ -726,9 +763,9 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.get_mint_nft_update_by_creator_and_nonce",
-            start.elapsed()
+        report_query(
+            "chain.state.get_mint_nft_update_by_creator_and_nonce",
+            start,
         );
         Ok(nft.map(|p| p.into()))
     }
@@ -748,7 +785,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.get_nft_id_by_tx_hash", start.elapsed());
+        report_query("chain.state.get_nft_id_by_tx_hash", start);
         Ok(record.map(|r| TokenId(r.token_id as u32)))
     }
 
@@ -785,10 +822,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.remove_account_balance_updates",
-            start.elapsed()
-        );
+        report_query("chain.state.remove_account_balance_updates", start);
         Ok(())
     }
 
@@ -802,7 +836,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.remove_account_creates", start.elapsed());
+        report_query("chain.state.remove_account_creates", start);
         Ok(())
     }
 
@@ -816,7 +850,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.state.remove_mint_nft_updates", start.elapsed());
+        report_query("chain.state.remove_mint_nft_updates", start);
         Ok(())
     }
 
@@ -833,10 +867,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.state.remove_account_pubkey_updates",
-            start.elapsed()
-        );
+        report_query("chain.state.remove_account_pubkey_updates", start);
         Ok(())
     }
 
@@ -6,7 +6,7 @@ use std::time::Instant;
 // Workspace imports
 use zksync_types::{BlockNumber, SequentialTxId};
 // Local imports
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 /// Auxiliary schema encapsulating the stats counting logic for the storage tables.
 #[derive(Debug)]
@@ -25,7 +25,7 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         .count
         .unwrap_or(0);
 
-        metrics::histogram!("sql.chain.stats.count_outstanding_proofs", start.elapsed());
+        report_query("chain.stats.count_outstanding_proofs", start);
         Ok(count as u32)
     }
 
@@ -52,7 +52,7 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
+        report_query("chain.stats.count_total_transactions", start);
         Ok((
             (tx_res.count.unwrap_or_default() + prior_ops_res.count.unwrap_or_default()) as u32,
             SequentialTxId(max(
@@ -5,7 +5,7 @@ use std::time::Instant;
 use zksync_types::BlockNumber;
 // Local imports
 use super::records::AccountTreeCache;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 /// Tree cache schema contains methods to store/load Merkle tree cache.
 ///
@@ -39,10 +39,7 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.store_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.bincode.store_account_tree_cache", start);
         Ok(())
     }
 
@@ -60,9 +57,9 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .await?
         .max;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_last_block_with_account_tree_cache",
-            start.elapsed()
+        report_query(
+            "chain.tree_cache.bincode.get_last_block_with_account_tree_cache",
+            start,
         );
         Ok(last_block_with_cache.map(|block| BlockNumber(block as u32)))
     }
@@ -84,10 +81,7 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.bincode.get_account_tree_cache", start);
         Ok(account_tree_cache.map(|w| {
             (
                 BlockNumber(w.block as u32),
@@ -115,9 +109,9 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.get_account_tree_cache_block",
-            start.elapsed()
+        report_query(
+            "chain.tree_cache.bincode.get_account_tree_cache_block",
+            start,
         );
         Ok(account_tree_cache.map(|w| {
             w.tree_cache_binary
@@ -138,9 +132,9 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.remove_new_account_tree_cache",
-            start.elapsed()
+        report_query(
+            "chain.tree_cache.bincode.remove_new_account_tree_cache",
+            start,
         );
         Ok(())
     }
@@ -158,9 +152,9 @@ impl<'a, 'c> TreeCacheSchemaBincode<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.bincode.remove_old_account_tree_cache",
-            start.elapsed()
+        report_query(
+            "chain.tree_cache.bincode.remove_old_account_tree_cache",
+            start,
         );
         Ok(())
     }
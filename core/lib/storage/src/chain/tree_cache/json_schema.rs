@@ -5,7 +5,7 @@ use std::time::Instant;
 use zksync_types::BlockNumber;
 // Local imports
 use super::records::AccountTreeCache;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 /// Tree cache schema contains methods to store/load Merkle tree cache.
 ///
@@ -39,10 +39,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.store_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.json.store_account_tree_cache", start);
         Ok(())
     }
 
@@ -59,9 +56,9 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
                 .await?
                 .max;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_last_block_with_account_tree_cache",
-            start.elapsed()
+        report_query(
+            "chain.tree_cache.json.get_last_block_with_account_tree_cache",
+            start,
         );
         Ok(last_block_with_cache.map(|block| BlockNumber(block as u32)))
     }
@@ -85,10 +82,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.json.get_account_tree_cache", start);
         Ok(account_tree_cache.map(|w| {
             assert!(
                 w.tree_cache.is_some(),
@@ -125,10 +119,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.get_account_tree_cache_block",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.json.get_account_tree_cache_block", start);
         Ok(account_tree_cache.map(|w| {
             serde_json::from_str(
                 &w.tree_cache
@@ -151,10 +142,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.remove_new_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.json.remove_new_account_tree_cache", start);
         Ok(())
     }
 
@@ -171,10 +159,7 @@ impl<'a, 'c> TreeCacheSchemaJSON<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.tree_cache.json.remove_old_account_tree_cache",
-            start.elapsed()
-        );
+        report_query("chain.tree_cache.json.remove_old_account_tree_cache", start);
         Ok(())
     }
 }
@@ -5,7 +5,7 @@ use std::time::Instant;
 use zksync_types::Address;
 // Local imports
 use self::records::ServerConfig;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 
 pub mod records;
 
@@ -25,7 +25,7 @@ impl<'a, 'c> ConfigSchema<'a, 'c> {
             .fetch_one(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.load_config", start.elapsed());
+        report_query("load_config", start);
         Ok(config)
     }
 
@@ -49,7 +49,7 @@ impl<'a, 'c> ConfigSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.store_config", start.elapsed());
+        report_query("store_config", start);
         Ok(())
     }
 }
@@ -1,17 +1,19 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{fmt, sync::Arc, time::Duration, time::Instant};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
 use deadpool::Runtime;
 use sqlx::{Connection, Error as SqlxError, PgConnection};
-use tokio::time;
+use tokio::{task::JoinHandle, time};
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
-use crate::{get_database_replica_url, get_database_url, StorageProcessor};
+use self::replicas::{ReplicaSet, ReplicaSetConfig};
+use crate::{get_database_url, StorageProcessor};
 use zksync_utils::parse_env;
 
 pub mod holder;
+pub mod replicas;
 
 type Pool = deadpool::managed::Pool<DbPool>;
 
@@ -25,10 +27,10 @@ pub struct DbPool {
 }
 
 impl DbPool {
-    fn create(url: impl Into<String>, max_size: usize) -> Pool {
+    fn create(url: impl Into<String>, max_size: usize, timeouts: Timeouts) -> Pool {
         let pool_config = PoolConfig {
             max_size,
-            timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
+            timeouts,
             runtime: Runtime::Tokio1,
         };
         Pool::from_config(DbPool { url: url.into() }, pool_config)
@@ -47,14 +49,35 @@ impl Manager for DbPool {
     }
 }
 
+/// Reports the utilization of the connection pool, `name` is either `primary`
+/// or the host of the replica.
+fn report_pool_status(name: &str, pool: &Pool) {
+    let status = pool.status();
+    // Negative amount of the available connections is the amount of the waiting requests.
+    let in_use = status.size as isize - status.available.max(0);
+    let waiting = (-status.available).max(0);
+
+    metrics::gauge!("sql.pool.connections_in_use", in_use as f64, "pool" => name.to_string());
+    metrics::gauge!("sql.pool.waiting", waiting as f64, "pool" => name.to_string());
+    metrics::gauge!(
+        "sql.pool.utilization",
+        in_use as f64 / status.max_size.max(1) as f64,
+        "pool" => name.to_string()
+    );
+}
+
 /// `ConnectionPool` is a wrapper over a `diesel`s `Pool`, encapsulating
 /// the fixed size pool of connection to the database.
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// The read-only pool routes the connections to the replicas configured via
+/// `DATABASE_REPLICA_URLS`, falling back to the primary database if none of them is healthy.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    replicas: Option<Arc<ReplicaSet>>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -71,23 +94,52 @@ impl ConnectionPool {
         let database_url = get_database_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        // wait 20 seconds before returning error
+        let pool = DbPool::create(
+            database_url,
+            max_size as usize,
+            Timeouts::wait_millis(20_000),
+        );
 
-        Self { pool }
+        Self {
+            pool,
+            replicas: None,
+        }
     }
 
-    /// Establishes a pool of the connections to the replica of database and
+    /// Establishes a pool of the connections to the replicas of database and
     /// creates a new `ConnectionPool` object.
-    /// pool_max_size - number of connections in pool,
+    /// Connections are handed out by the healthy replicas in turn, the primary database is used
+    /// if there are no replicas configured or none of them is healthy.
+    /// pool_max_size - number of connections in pool per database,
     /// if not set env variable "DATABASE_POOL_SIZE" is going to be used.
     pub fn new_readonly_pool(pool_max_size: Option<u32>) -> Self {
-        let database_url = get_database_replica_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
+        let mut pool = Self::new(Some(max_size));
+        let config = ReplicaSetConfig::from_env();
+        if !config.urls.is_empty() {
+            pool.replicas = Some(Arc::new(ReplicaSet::new(config, max_size as usize)));
+        }
+
+        pool
+    }
 
-        let pool = DbPool::create(database_url, max_size as usize);
+    /// Starts the periodic health checks of the replicas, if the pool has any.
+    pub fn run_replica_health_check(&self) -> Option<JoinHandle<()>> {
+        self.replicas
+            .clone()
+            .map(|replicas| replicas.run_health_check())
+    }
 
-        Self { pool }
+    /// Returns `false` if the pool has the replicas configured, but none of them is healthy,
+    /// so the connections are obtained from the primary database.
+    pub fn replicas_available(&self) -> bool {
+        self.replicas
+            .as_ref()
+            .map(|replicas| replicas.is_available())
+            .unwrap_or(true)
     }
+
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -98,12 +150,30 @@ impl ConnectionPool {
     /// database access is must-have (e.g. block committer).
     pub async fn access_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
         let start = Instant::now();
-        let connection = self.get_pooled_connection().await;
+        let connection = match self.get_replica_connection().await {
+            Some(connection) => connection,
+            None => {
+                let connection = self.get_pooled_connection().await;
+                report_pool_status("primary", &self.pool);
+                connection
+            }
+        };
         metrics::histogram!("sql.connection_acquire", start.elapsed());
 
         Ok(StorageProcessor::from_pool(connection))
     }
 
+    async fn get_replica_connection(&self) -> Option<PooledConnection> {
+        let replicas = self.replicas.as_ref()?;
+        let connection = replicas.get_connection().await;
+        if connection.is_none() {
+            vlog::debug!("No database replicas are available, using the primary database");
+            metrics::increment_counter!("sql.replica_fallback");
+        }
+
+        connection
+    }
+
     async fn get_pooled_connection(&self) -> PooledConnection {
         let mut retry_count = 0;
 
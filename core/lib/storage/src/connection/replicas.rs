@@ -0,0 +1,231 @@
+//! Routing of the read-only connections to the database replicas.
+//!
+//! Connections are handed out by the replicas in turn. Replicas that failed to provide
+//! a connection, or are lagging behind the primary for too long, are skipped until the
+//! health check finds them healthy again.
+
+// Built-in deps
+use std::env;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::Duration;
+// External imports
+use deadpool::managed::Timeouts;
+use sqlx::Connection;
+use tokio::{task::JoinHandle, time};
+// Local imports
+use super::{report_pool_status, DbPool, Pool, PooledConnection};
+
+/// Default interval between the health checks of the replicas.
+const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Timeout of obtaining a connection to the replica, the primary is used once it expires.
+const REPLICA_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Parameters of the replicas the read-only connections are routed to.
+#[derive(Debug, Clone)]
+pub struct ReplicaSetConfig {
+    /// URLs of the replicas.
+    pub urls: Vec<String>,
+    /// Interval between the health checks of the replicas.
+    pub health_check_interval: Duration,
+    /// Replication lag after which the replica is not used. Not limited if not set.
+    pub max_lag: Option<Duration>,
+}
+
+impl ReplicaSetConfig {
+    /// Loads the replicas configuration from the environment variables:
+    /// `DATABASE_REPLICA_URLS` (or `DATABASE_REPLICA_URL` for a single replica),
+    /// `DATABASE_REPLICA_HEALTH_CHECK_INTERVAL` in ms and `DATABASE_REPLICA_MAX_LAG` in seconds.
+    pub fn from_env() -> Self {
+        let health_check_interval = env::var("DATABASE_REPLICA_HEALTH_CHECK_INTERVAL")
+            .ok()
+            .map(|interval| {
+                Duration::from_millis(
+                    interval
+                        .parse()
+                        .expect("DATABASE_REPLICA_HEALTH_CHECK_INTERVAL should be a number"),
+                )
+            })
+            .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL);
+        let max_lag = env::var("DATABASE_REPLICA_MAX_LAG").ok().map(|max_lag| {
+            Duration::from_secs(
+                max_lag
+                    .parse()
+                    .expect("DATABASE_REPLICA_MAX_LAG should be a number"),
+            )
+        });
+
+        Self {
+            urls: crate::get_database_replica_urls(),
+            health_check_interval,
+            max_lag,
+        }
+    }
+}
+
+struct Replica {
+    /// Host of the replica used in logs and metrics, the URL itself may contain the credentials.
+    host: String,
+    pool: Pool,
+    healthy: AtomicBool,
+}
+
+impl Replica {
+    fn new(url: String, max_size: usize) -> Self {
+        let host = replica_host(&url);
+        let timeouts = Timeouts {
+            wait: Some(REPLICA_CONNECTION_TIMEOUT),
+            create: Some(REPLICA_CONNECTION_TIMEOUT),
+            recycle: Some(REPLICA_CONNECTION_TIMEOUT),
+        };
+
+        Self {
+            host,
+            pool: DbPool::create(url, max_size, timeouts),
+            healthy: AtomicBool::new(true),
+        }
+    }
+
+    fn set_healthy(&self, healthy: bool) {
+        let was_healthy = self.healthy.swap(healthy, Ordering::SeqCst);
+        if was_healthy && !healthy {
+            vlog::warn!(
+                "Database replica {} is unhealthy, it won't be used",
+                self.host
+            );
+        } else if !was_healthy && healthy {
+            vlog::info!("Database replica {} is healthy again", self.host);
+        }
+        metrics::gauge!("sql.replica_healthy", if healthy { 1.0 } else { 0.0 }, "replica" => self.host.clone());
+    }
+
+    /// Returns the replication lag of the replica, zero if the replica has replayed all the received changes.
+    async fn replication_lag(connection: &mut PooledConnection) -> Result<Duration, sqlx::Error> {
+        let (lag,): (Option<f64>,) = sqlx::query_as(
+            "SELECT CASE
+                WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+                ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp())
+            END::FLOAT8",
+        )
+        .fetch_one(&mut **connection)
+        .await?;
+
+        Ok(Duration::from_secs_f64(lag.unwrap_or_default().max(0.0)))
+    }
+}
+
+/// Set of the replicas the read-only connections are routed to.
+pub struct ReplicaSet {
+    replicas: Vec<Replica>,
+    next_replica: AtomicUsize,
+    config: ReplicaSetConfig,
+}
+
+impl ReplicaSet {
+    pub fn new(config: ReplicaSetConfig, max_size: usize) -> Self {
+        let replicas = config
+            .urls
+            .iter()
+            .map(|url| Replica::new(url.clone(), max_size))
+            .collect();
+
+        Self {
+            replicas,
+            next_replica: AtomicUsize::new(0),
+            config,
+        }
+    }
+
+    /// Returns `true` if at least one of the replicas is healthy.
+    pub fn is_available(&self) -> bool {
+        self.replicas
+            .iter()
+            .any(|replica| replica.healthy.load(Ordering::SeqCst))
+    }
+
+    /// Obtains a connection to the next healthy replica. Replicas that fail to provide
+    /// a connection are marked as unhealthy. Returns `None` if none of the replicas is available.
+    pub async fn get_connection(&self) -> Option<PooledConnection> {
+        let first = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.replicas.len() {
+            let replica = &self.replicas[(first + offset) % self.replicas.len()];
+            if !replica.healthy.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            match replica.pool.get().await {
+                Ok(connection) => {
+                    report_pool_status(&replica.host, &replica.pool);
+                    return Some(connection);
+                }
+                Err(err) => {
+                    vlog::warn!(
+                        "Failed to get connection to the database replica {}: {}",
+                        replica.host,
+                        err
+                    );
+                    replica.set_healthy(false);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Checks whether the replicas are reachable and keep up with the primary.
+    pub async fn check_health(&self) {
+        for replica in &self.replicas {
+            let lag = match replica.pool.get().await {
+                Ok(mut connection) => match connection.ping().await {
+                    Ok(()) => Replica::replication_lag(&mut connection).await,
+                    Err(err) => Err(err),
+                },
+                Err(err) => {
+                    vlog::debug!("Database replica {} is unreachable: {}", replica.host, err);
+                    replica.set_healthy(false);
+                    continue;
+                }
+            };
+
+            match lag {
+                Ok(lag) => {
+                    metrics::gauge!("sql.replica_lag", lag.as_secs_f64(), "replica" => replica.host.clone());
+                    let lagging = matches!(self.config.max_lag, Some(max_lag) if lag > max_lag);
+                    replica.set_healthy(!lagging);
+                }
+                Err(err) => {
+                    vlog::debug!(
+                        "Health check of the database replica {} failed: {}",
+                        replica.host,
+                        err
+                    );
+                    replica.set_healthy(false);
+                }
+            }
+        }
+    }
+
+    /// Runs the periodic health checks of the replicas.
+    pub fn run_health_check(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut timer = time::interval(self.config.health_check_interval);
+            loop {
+                timer.tick().await;
+                self.check_health().await;
+            }
+        })
+    }
+}
+
+/// Extracts the host of the database from its URL, omitting the credentials.
+fn replica_host(url: &str) -> String {
+    let without_scheme = url.split("://").last().unwrap_or(url);
+    let without_credentials = without_scheme.rsplit('@').next().unwrap_or(without_scheme);
+    without_credentials
+        .split('/')
+        .next()
+        .unwrap_or(without_credentials)
+        .to_string()
+}
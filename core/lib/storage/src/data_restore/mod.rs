@@ -18,6 +18,7 @@ use self::records::{
 use crate::chain::operations::OperationsSchema;
 use crate::{
     chain::state::StateSchema,
+    instrumentation::report_query,
     tokens::{StoreTokenError, TokensSchema},
 };
 use crate::{QueryResult, StorageProcessor};
@@ -75,7 +76,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_block_operations", start.elapsed());
+        report_query("data_restore.save_block_operations", start);
         Ok(())
     }
 
@@ -92,7 +93,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .apply_state_update(BlockNumber(0))
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_genesis_state", start.elapsed());
+        report_query("data_restore.save_genesis_state", start);
         Ok(())
     }
 
@@ -122,7 +123,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         )
         .fetch_all(self.0.conn())
         .await?;
-        metrics::histogram!("sql.data_restore.load_rollup_ops_blocks", start.elapsed());
+        report_query("data_restore.load_rollup_ops_blocks", start);
         Ok(stored_blocks)
     }
 
@@ -145,10 +146,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.data_restore.update_last_watched_block_number",
-            start.elapsed()
-        );
+        report_query("data_restore.update_last_watched_block_number", start);
         Ok(())
     }
 
@@ -164,13 +162,24 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.data_restore.load_last_watched_block_number",
-            start.elapsed()
-        );
+        report_query("data_restore.load_last_watched_block_number", start);
         Ok(stored)
     }
 
+    /// Checks whether the last seen Ethereum block number is stored, i.e. the restore has been started.
+    pub async fn is_last_watched_block_number_stored(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let is_stored = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM data_restore_last_watched_eth_block) as "exists!""#,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .exists;
+
+        report_query("data_restore.is_last_watched_block_number_stored", start);
+        Ok(is_stored)
+    }
+
     fn new_storage_state(&self, state: impl ToString) -> NewStorageState {
         NewStorageState {
             storage_state: state.to_string(),
@@ -221,7 +230,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.save_events_state", start.elapsed());
+        report_query("data_restore.save_events_state", start);
         Ok(())
     }
 
@@ -362,7 +371,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_rollup_ops", start.elapsed());
+        report_query("data_restore.save_rollup_ops", start);
         Ok(())
     }
 
@@ -388,7 +397,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.initialize_eth_stats", start.elapsed());
+        report_query("data_restore.initialize_eth_stats", start);
         Ok(())
     }
 
@@ -404,7 +413,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_events_state", start.elapsed());
+        report_query("data_restore.load_events_state", start);
         Ok(events)
     }
 
@@ -426,7 +435,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_storage_state", start.elapsed());
+        report_query("data_restore.load_storage_state", start);
         Ok(state)
     }
 
@@ -445,7 +454,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.update_storage_state", start.elapsed());
+        report_query("data_restore.update_storage_state", start);
         Ok(())
     }
 
@@ -468,7 +477,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.update_block_events", start.elapsed());
+        report_query("data_restore.update_block_events", start);
         Ok(())
     }
 }
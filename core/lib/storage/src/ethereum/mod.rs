@@ -11,11 +11,16 @@ use zksync_types::{
     event::{
         account::AccountStateChangeStatus, block::BlockStatus, transaction::TransactionStatus,
     },
-    BlockNumber, H256, U256,
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
-use self::records::{ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
-use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
+use self::records::{
+    ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageBlockEthTx, StorageETHOperation,
+};
+use crate::{
+    chain::operations::records::StoredAggregatedOperation, instrumentation::report_query,
+    QueryResult, StorageProcessor,
+};
 use chrono::{DateTime, Utc};
 
 pub mod records;
@@ -98,12 +103,20 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                 .map(|entry| H256::from_slice(&entry.tx_hash))
                 .collect();
             let final_hash = eth_op.final_hash.map(|hash| H256::from_slice(&hash));
+            let sender = sqlx::query!(
+                "SELECT sender_address FROM eth_operation_senders WHERE eth_op_id = $1",
+                eth_op.id
+            )
+            .fetch_optional(transaction.conn())
+            .await?
+            .map(|entry| Address::from_slice(&entry.sender_address));
 
             let eth_op = ETHOperation {
                 id: eth_op.id,
                 op_type,
                 op,
                 nonce: eth_op.nonce.into(),
+                sender,
                 last_deadline_block: eth_op.last_deadline_block as u64,
                 last_used_gas_price,
                 used_tx_hashes,
@@ -117,7 +130,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.load_unconfirmed_operations", start.elapsed());
+        report_query("ethereum.load_unconfirmed_operations", start);
         Ok(ops)
     }
 
@@ -142,10 +155,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.ethereum.restore_unprocessed_operations",
-            start.elapsed()
-        );
+        report_query("ethereum.restore_unprocessed_operations", start);
 
         Ok(())
     }
@@ -187,7 +197,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             }
         }
 
-        metrics::histogram!("sql.ethereum.load_unprocessed_operations", start.elapsed());
+        report_query("ethereum.load_unprocessed_operations", start);
         Ok(operations)
     }
 
@@ -206,10 +216,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.ethereum.remove_unprocessed_operations",
-            start.elapsed()
-        );
+        report_query("ethereum.remove_unprocessed_operations", start);
         Ok(())
     }
 
@@ -222,13 +229,42 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         last_deadline_block: i64,
         last_used_gas_price: BigUint,
         raw_tx: Vec<u8>,
+    ) -> QueryResult<InsertedOperationResponse> {
+        self.save_new_eth_tx_from_sender(
+            None,
+            op_type,
+            operation,
+            last_deadline_block,
+            last_used_gas_price,
+            raw_tx,
+        )
+        .await
+    }
+
+    /// Same as `save_new_eth_tx`, but the transaction is sent from the additional operator
+    /// account `sender` (if set) and gets the next nonce of this account.
+    pub async fn save_new_eth_tx_from_sender(
+        &mut self,
+        sender: Option<Address>,
+        op_type: AggregatedActionType,
+        operation: Option<(i64, AggregatedOperation)>,
+        last_deadline_block: i64,
+        last_used_gas_price: BigUint,
+        raw_tx: Vec<u8>,
     ) -> QueryResult<InsertedOperationResponse> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
         // It's important to assign nonce within the same db transaction
         // as saving the operation to avoid the state divergence.
-        let nonce = EthereumSchema(&mut transaction).get_next_nonce().await?;
+        let nonce = match sender {
+            Some(sender) => {
+                EthereumSchema(&mut transaction)
+                    .get_next_sender_nonce(sender)
+                    .await?
+            }
+            None => EthereumSchema(&mut transaction).get_next_nonce().await?,
+        };
 
         // Create and insert the operation.
 
@@ -246,6 +282,16 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .await?
         .id;
 
+        if let Some(sender) = sender {
+            sqlx::query!(
+                "INSERT INTO eth_operation_senders (eth_op_id, sender_address) VALUES ($1, $2)",
+                eth_op_id,
+                sender.as_bytes()
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
         // If the operation ID was provided, we should also insert a binding entry.
         if let Some((op_id, op)) = operation {
             sqlx::query!(
@@ -270,7 +316,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.save_new_eth_tx", start.elapsed());
+        report_query("ethereum.save_new_eth_tx", start);
         Ok(response)
     }
 
@@ -288,7 +334,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .map(|op| op.confirmed)
         .unwrap_or(false);
 
-        metrics::histogram!("sql.ethereum.is_aggregated_op_confirmed", start.elapsed());
+        report_query("ethereum.is_aggregated_op_confirmed", start);
         Ok(confirmed)
     }
 
@@ -303,7 +349,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.get_eth_op_id", start.elapsed());
+        report_query("ethereum.get_eth_op_id", start);
         Ok(hash_entry.eth_op_id)
     }
 
@@ -318,7 +364,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!("sql.ethereum.add_hash_entry", start.elapsed());
+        report_query("ethereum.add_hash_entry", start);
         Ok(())
     }
 
@@ -344,7 +390,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_eth_tx", start.elapsed());
+        report_query("ethereum.update_eth_tx", start);
         Ok(())
     }
 
@@ -411,7 +457,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.report_created_operation", start.elapsed());
+        report_query("ethereum.report_created_operation", start);
         Ok(())
     }
 
@@ -441,7 +487,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_gas_price", start.elapsed());
+        report_query("ethereum.update_gas_price", start);
         Ok(())
     }
 
@@ -452,7 +498,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let gas_price_limit =
             U256::try_from(params.gas_price_limit).expect("Negative gas limit value stored in DB");
 
-        metrics::histogram!("sql.ethereum.load_gas_price_limit", start.elapsed());
+        report_query("ethereum.load_gas_price_limit", start);
         Ok(gas_price_limit)
     }
 
@@ -464,7 +510,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .average_gas_price
             .map(|price| U256::try_from(price).expect("Negative average gas price stored in DB"));
 
-        metrics::histogram!("sql.ethereum.load_average_gas_price", start.elapsed());
+        report_query("ethereum.load_average_gas_price", start);
         Ok(average_gas_price)
     }
 
@@ -473,7 +519,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
 
-        metrics::histogram!("sql.ethereum.load_stats", start.elapsed());
+        report_query("ethereum.load_stats", start);
         Ok(params.into())
     }
 
@@ -482,7 +528,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let params = sqlx::query_as!(ETHParams, "SELECT * FROM eth_parameters WHERE id = true",)
             .fetch_one(self.0.conn())
             .await?;
-        metrics::histogram!("sql.ethereum.load_eth_params", start.elapsed());
+        report_query("ethereum.load_eth_params", start);
         Ok(params)
     }
 
@@ -576,10 +622,94 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.confirm_eth_tx", start.elapsed());
+        report_query("ethereum.confirm_eth_tx", start);
+        Ok(())
+    }
+
+    /// Records the Ethereum block the confirmed transaction is included in.
+    pub async fn save_eth_tx_block(&mut self, hash: &H256, eth_block: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO eth_tx_blocks ( tx_hash, eth_block )
+            VALUES ( $1, $2 )
+            ON CONFLICT ( tx_hash ) DO UPDATE SET eth_block = $2
+            "#,
+            hash.as_bytes(),
+            eth_block as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("ethereum.save_eth_tx_block", start);
+        Ok(())
+    }
+
+    /// Stores the latest Ethereum block seen by `eth_sender`.
+    pub async fn update_last_eth_block(&mut self, block_number: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO eth_last_block ( id, block_number, updated_at )
+            VALUES ( true, $1, now() )
+            ON CONFLICT ( id ) DO UPDATE SET block_number = $1, updated_at = now()
+            "#,
+            block_number as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("ethereum.update_last_eth_block", start);
         Ok(())
     }
 
+    /// Loads the latest Ethereum block seen by `eth_sender`, `None` if it's not stored yet.
+    pub async fn load_last_eth_block(&mut self) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let block_number = sqlx::query!("SELECT block_number FROM eth_last_block")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| row.block_number as u64);
+
+        report_query("ethereum.load_last_eth_block", start);
+        Ok(block_number)
+    }
+
+    /// Loads the confirmed Ethereum transactions committing and executing the rollup block.
+    pub async fn load_block_eth_txs(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<StorageBlockEthTx>> {
+        let start = Instant::now();
+        let action_types = vec![
+            AggregatedActionType::CommitBlocks.to_string(),
+            AggregatedActionType::ExecuteBlocks.to_string(),
+        ];
+        let txs = sqlx::query_as!(
+            StorageBlockEthTx,
+            r#"
+            SELECT
+                aggregate_operations.action_type,
+                eth_operations.final_hash as "tx_hash!",
+                eth_tx_blocks.eth_block as "eth_block?"
+            FROM aggregate_operations
+                INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+                LEFT JOIN eth_tx_blocks ON eth_tx_blocks.tx_hash = eth_operations.final_hash
+            WHERE ($1 BETWEEN aggregate_operations.from_block AND aggregate_operations.to_block)
+                AND aggregate_operations.action_type = ANY($2)
+                AND eth_operations.confirmed = true
+            "#,
+            i64::from(*block_number),
+            &action_types
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("ethereum.load_block_eth_txs", start);
+        Ok(txs)
+    }
+
     /// Obtains the next nonce to use and updates the corresponding entry in the database
     /// for the next invocation.
     ///
@@ -608,10 +738,60 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.get_next_nonce", start.elapsed());
+        report_query("ethereum.get_next_nonce", start);
         Ok(old_nonce_value)
     }
 
+    /// Obtains the next nonce of the additional operator account and updates the corresponding
+    /// entry in the database for the next invocation.
+    ///
+    /// The nonce must be initialized with `initialize_sender_nonce` first.
+    pub async fn get_next_sender_nonce(&mut self, sender: Address) -> QueryResult<i64> {
+        let start = Instant::now();
+
+        let nonce = sqlx::query!(
+            r#"UPDATE eth_sender_nonces
+            SET nonce = nonce + 1
+            WHERE sender_address = $1
+            RETURNING nonce - 1 AS "nonce!""#,
+            sender.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .ok_or_else(|| {
+            format_err!(
+                "Nonce of the operator account {:#x} is not initialized",
+                sender
+            )
+        })?
+        .nonce;
+
+        report_query("ethereum.get_next_sender_nonce", start);
+        Ok(nonce)
+    }
+
+    /// Stores the initial nonce of the additional operator account.
+    /// Does nothing if the nonce of this account is already stored.
+    pub async fn initialize_sender_nonce(
+        &mut self,
+        sender: Address,
+        nonce: i64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "INSERT INTO eth_sender_nonces (sender_address, nonce) VALUES ($1, $2)
+            ON CONFLICT (sender_address) DO NOTHING",
+            sender.as_bytes(),
+            nonce
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("ethereum.initialize_sender_nonce", start);
+        Ok(())
+    }
+
     /// Method that internally initializes the `eth_parameters` table.
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
@@ -650,7 +830,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .await?;
         }
 
-        metrics::histogram!("sql.ethereum.initialize_eth_data", start.elapsed());
+        report_query("ethereum.initialize_eth_data", start);
         Ok(())
     }
 
@@ -666,10 +846,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?
         .created_at;
-        metrics::histogram!(
-            "sql.ethereum.get_eth_operation_creation_time",
-            start.elapsed()
-        );
+        report_query("ethereum.get_eth_operation_creation_time", start);
         Ok(created_at)
     }
 
@@ -718,7 +895,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.update_eth_parameters", start.elapsed());
+        report_query("ethereum.update_eth_parameters", start);
         Ok(())
     }
 }
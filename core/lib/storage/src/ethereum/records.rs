@@ -67,3 +67,11 @@ impl From<ETHParams> for ETHStats {
         }
     }
 }
+
+/// Confirmed Ethereum transaction committing or executing the rollup block.
+#[derive(Debug, FromRow, PartialEq)]
+pub struct StorageBlockEthTx {
+    pub action_type: String,
+    pub tx_hash: Vec<u8>,
+    pub eth_block: Option<i64>,
+}
@@ -13,10 +13,11 @@ use zksync_types::{
         transaction::{TransactionEvent, TransactionStatus},
         EventId,
     },
-    BlockNumber,
+    tx::TxHash,
+    BlockNumber, SignedZkSyncTx,
 };
 // Local uses
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 use records::StoredEvent;
 
 pub mod records;
@@ -65,7 +66,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.event.store_event_data", start.elapsed());
+        report_query("event.store_event_data", start);
         Ok(())
     }
 
@@ -90,7 +91,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.event.fetch_new_events", start.elapsed());
+        report_query("event.fetch_new_events", start);
         Ok(events)
     }
 
@@ -104,10 +105,81 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .max
             .map(|id| EventId(id as u64));
 
-        metrics::histogram!("sql.event.get_last_event_id", start.elapsed());
+        report_query("event.get_last_event_id", start);
         Ok(id)
     }
 
+    /// Load at most `limit` events with the `id` greater than `from`.
+    pub async fn fetch_events_batch(
+        &mut self,
+        from: EventId,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredEvent>> {
+        let start = Instant::now();
+        let events = sqlx::query_as!(
+            StoredEvent,
+            r#"
+            SELECT
+                id,
+                block_number,
+                event_type as "event_type!: EventType",
+                event_data
+            FROM events WHERE id > $1
+            ORDER BY id ASC
+            LIMIT $2
+            "#,
+            *from as i64,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("event.fetch_events_batch", start);
+        Ok(events)
+    }
+
+    /// Load the id of the last event published to the broker by the event publisher.
+    /// Returns `None` if nothing has been published to it yet.
+    pub async fn get_last_published_event_id(
+        &mut self,
+        broker: &str,
+    ) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT last_event_id FROM event_publisher_offsets WHERE broker = $1",
+            broker
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| EventId(row.last_event_id as u64));
+
+        report_query("event.get_last_published_event_id", start);
+        Ok(id)
+    }
+
+    /// Mark the events up to `last_event_id` as published to the broker.
+    pub async fn update_last_published_event_id(
+        &mut self,
+        broker: &str,
+        last_event_id: EventId,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO event_publisher_offsets ( broker, last_event_id, updated_at )
+            VALUES ( $1, $2, now() )
+            ON CONFLICT ( broker ) DO UPDATE SET last_event_id = $2, updated_at = now()
+            "#,
+            broker,
+            *last_event_id as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("event.update_last_published_event_id", start);
+        Ok(())
+    }
+
     /// Create new block event and store it in the database.
     /// This method relies on the `load_block_range` which may return `None`
     /// if there're no Ethereum transactions featuring this block (`Committed` or `Executed`).
@@ -157,7 +229,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_block_event", start.elapsed());
+        report_query("event.store_block_event", start);
         Ok(())
     }
 
@@ -199,7 +271,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_state_updated_event", start.elapsed());
+        report_query("event.store_state_updated_event", start);
         Ok(())
     }
 
@@ -246,7 +318,7 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_transaction_event", start.elapsed());
+        report_query("event.store_transaction_event", start);
         Ok(())
     }
 
@@ -284,7 +356,85 @@ impl<'a, 'c> EventSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.event.store_queued_transaction_event", start.elapsed());
+        report_query("event.store_queued_transaction_event", start);
+        Ok(())
+    }
+
+    /// Store the `Replaced` events of the pending transactions removed from the mempool
+    /// in favor of the transaction `replaced_by`. Since the transactions are not included
+    /// into any block, the events belong to the last committed block.
+    pub async fn store_replaced_transaction_event(
+        &mut self,
+        replaced_txs: &[SignedZkSyncTx],
+        replaced_by: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let block_number = transaction
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+
+        let events: Vec<serde_json::Value> = replaced_txs
+            .iter()
+            .filter_map(|tx| {
+                let transaction_event =
+                    TransactionEvent::from_replaced_tx(tx, block_number, replaced_by)?;
+
+                Some(
+                    serde_json::to_value(transaction_event)
+                        .expect("couldn't serialize transaction event"),
+                )
+            })
+            .collect();
+
+        transaction
+            .event_schema()
+            .store_event_data(block_number, EventType::Transaction, &events)
+            .await?;
+        transaction.commit().await?;
+
+        report_query("event.store_replaced_transaction_event", start);
+        Ok(())
+    }
+
+    /// Store the `Dropped` events of the pending transactions removed from the mempool
+    /// for the `reason`. Since the transactions are not included into any block,
+    /// the events belong to the last committed block.
+    pub async fn store_dropped_transaction_event(
+        &mut self,
+        dropped_txs: &[SignedZkSyncTx],
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let block_number = transaction
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+
+        let events: Vec<serde_json::Value> = dropped_txs
+            .iter()
+            .filter_map(|tx| {
+                let transaction_event =
+                    TransactionEvent::from_dropped_tx(tx, block_number, reason)?;
+
+                Some(
+                    serde_json::to_value(transaction_event)
+                        .expect("couldn't serialize transaction event"),
+                )
+            })
+            .collect();
+
+        transaction
+            .event_schema()
+            .store_event_data(block_number, EventType::Transaction, &events)
+            .await?;
+        transaction.commit().await?;
+
+        report_query("event.store_dropped_transaction_event", start);
         Ok(())
     }
 }
@@ -0,0 +1,227 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use num::BigInt;
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{
+    fast_withdrawals::{
+        FastWithdrawal, FastWithdrawalId, LiquidityProvider, SaveFastWithdrawalQuery,
+    },
+    tx::TxHash,
+    Address, TokenId,
+};
+// Local imports
+use self::records::{DbFastWithdrawal, DbLiquidityProvider};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the liquidity providers fronting the fast withdrawals on Ethereum
+/// and the withdrawals matched with them.
+///
+/// Providers are registered by the operator, the withdrawals are matched by the API.
+#[derive(Debug)]
+pub struct FastWithdrawalsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FastWithdrawalsSchema<'a, 'c> {
+    /// Registers the liquidity provider of the token, replaces the terms if it's already registered.
+    pub async fn add_provider(&mut self, provider: &LiquidityProvider) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO liquidity_providers ( address, token_id, max_amount, fee_bps )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT ( address, token_id ) DO UPDATE SET max_amount = $3, fee_bps = $4
+            "#,
+            provider.address.as_bytes(),
+            *provider.token as i32,
+            BigDecimal::from(BigInt::from(provider.max_amount.clone())),
+            i32::from(provider.fee_bps)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fast_withdrawals.add_provider", start);
+        Ok(())
+    }
+
+    /// Removes the liquidity provider of the token, returns `false` if it's not registered.
+    pub async fn remove_provider(&mut self, address: Address, token: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM liquidity_providers WHERE address = $1 AND token_id = $2",
+            address.as_bytes(),
+            *token as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fast_withdrawals.remove_provider", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all the registered liquidity providers.
+    pub async fn load_providers(&mut self) -> QueryResult<Vec<LiquidityProvider>> {
+        let start = Instant::now();
+        let providers = sqlx::query_as!(
+            DbLiquidityProvider,
+            "SELECT * FROM liquidity_providers ORDER BY token_id, address"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(LiquidityProvider::from)
+        .collect();
+
+        report_query("fast_withdrawals.load_providers", start);
+        Ok(providers)
+    }
+
+    /// Loads the liquidity providers of the token.
+    pub async fn load_token_providers(
+        &mut self,
+        token: TokenId,
+    ) -> QueryResult<Vec<LiquidityProvider>> {
+        let start = Instant::now();
+        let providers = sqlx::query_as!(
+            DbLiquidityProvider,
+            "SELECT * FROM liquidity_providers WHERE token_id = $1 ORDER BY address",
+            *token as i32
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(LiquidityProvider::from)
+        .collect();
+
+        report_query("fast_withdrawals.load_token_providers", start);
+        Ok(providers)
+    }
+
+    /// Stores the withdrawal matched with the liquidity provider.
+    pub async fn store_withdrawal(
+        &mut self,
+        query: &SaveFastWithdrawalQuery,
+    ) -> QueryResult<FastWithdrawal> {
+        let start = Instant::now();
+        let stored = sqlx::query_as!(
+            DbFastWithdrawal,
+            r#"
+            INSERT INTO fast_withdrawals (
+                account, recipient, token_id, amount, provider, fronted_amount, created_at, valid_until
+            )
+            VALUES ( $1, $2, $3, $4, $5, $6, $7, $8 )
+            RETURNING *
+            "#,
+            query.request.account.as_bytes(),
+            query.request.recipient.as_bytes(),
+            *query.request.token as i32,
+            BigDecimal::from(BigInt::from(query.request.amount.clone())),
+            query.provider.as_bytes(),
+            BigDecimal::from(BigInt::from(query.fronted_amount.clone())),
+            query.created_at,
+            query.valid_until
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        report_query("fast_withdrawals.store_withdrawal", start);
+        Ok(stored.into())
+    }
+
+    pub async fn get_withdrawal(
+        &mut self,
+        id: FastWithdrawalId,
+    ) -> QueryResult<Option<FastWithdrawal>> {
+        let start = Instant::now();
+        let withdrawal = sqlx::query_as!(
+            DbFastWithdrawal,
+            "SELECT * FROM fast_withdrawals WHERE id = $1",
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(FastWithdrawal::from);
+
+        report_query("fast_withdrawals.get_withdrawal", start);
+        Ok(withdrawal)
+    }
+
+    /// Loads the withdrawals of the account which haven't been handed off to the providers yet
+    /// and haven't expired by the given time.
+    pub async fn load_pending_withdrawals(
+        &mut self,
+        account: Address,
+        now: DateTime<Utc>,
+    ) -> QueryResult<Vec<FastWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            DbFastWithdrawal,
+            r#"
+            SELECT * FROM fast_withdrawals
+            WHERE account = $1 AND tx_hash IS NULL AND valid_until > $2
+            ORDER BY id
+            "#,
+            account.as_bytes(),
+            now
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(FastWithdrawal::from)
+        .collect();
+
+        report_query("fast_withdrawals.load_pending_withdrawals", start);
+        Ok(withdrawals)
+    }
+
+    /// Loads the latest withdrawals handed off to the provider, so it knows what to front.
+    pub async fn load_provider_withdrawals(
+        &mut self,
+        provider: Address,
+        limit: u32,
+    ) -> QueryResult<Vec<FastWithdrawal>> {
+        let start = Instant::now();
+        let withdrawals = sqlx::query_as!(
+            DbFastWithdrawal,
+            r#"
+            SELECT * FROM fast_withdrawals
+            WHERE provider = $1 AND tx_hash IS NOT NULL
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            provider.as_bytes(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(FastWithdrawal::from)
+        .collect();
+
+        report_query("fast_withdrawals.load_provider_withdrawals", start);
+        Ok(withdrawals)
+    }
+
+    /// Sets the hash of the withdrawal transaction to the provider. Returns `false` if the
+    /// withdrawal has already been handed off with another transaction.
+    pub async fn set_withdrawal_tx_hash(
+        &mut self,
+        id: FastWithdrawalId,
+        tx_hash: TxHash,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "UPDATE fast_withdrawals SET tx_hash = $2 WHERE id = $1 AND tx_hash IS NULL",
+            id,
+            tx_hash.as_ref()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fast_withdrawals.set_withdrawal_tx_hash", start);
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,74 @@
+// External imports
+use chrono::{DateTime, Utc};
+use num::bigint::ToBigInt;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_types::{
+    fast_withdrawals::{FastWithdrawal, LiquidityProvider},
+    tx::TxHash,
+    Address, TokenId,
+};
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbLiquidityProvider {
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub max_amount: BigDecimal,
+    pub fee_bps: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFastWithdrawal {
+    pub id: i64,
+    pub account: Vec<u8>,
+    pub recipient: Vec<u8>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub provider: Vec<u8>,
+    pub fronted_amount: BigDecimal,
+    pub tx_hash: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+}
+
+impl From<DbLiquidityProvider> for LiquidityProvider {
+    fn from(val: DbLiquidityProvider) -> Self {
+        Self {
+            address: Address::from_slice(&val.address),
+            token: TokenId(val.token_id as u32),
+            max_amount: val
+                .max_amount
+                .to_bigint()
+                .and_then(|int| int.to_biguint())
+                .expect("Invalid liquidity provider has been stored"),
+            fee_bps: val.fee_bps as u16,
+        }
+    }
+}
+
+impl From<DbFastWithdrawal> for FastWithdrawal {
+    fn from(val: DbFastWithdrawal) -> Self {
+        let to_biguint = |amount: BigDecimal| {
+            amount
+                .to_bigint()
+                .and_then(|int| int.to_biguint())
+                .expect("Invalid fast withdrawal has been stored")
+        };
+        Self {
+            id: val.id,
+            account: Address::from_slice(&val.account),
+            recipient: Address::from_slice(&val.recipient),
+            token: TokenId(val.token_id as u32),
+            amount: to_biguint(val.amount),
+            provider: Address::from_slice(&val.provider),
+            fronted_amount: to_biguint(val.fronted_amount),
+            tx_hash: val
+                .tx_hash
+                .map(|hash| TxHash::from_slice(&hash).expect("Invalid tx hash has been stored")),
+            created_at: val.created_at,
+            valid_until: val.valid_until,
+        }
+    }
+}
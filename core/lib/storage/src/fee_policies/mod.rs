@@ -0,0 +1,293 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{AccountId, TokenId};
+// Local imports
+use self::records::{
+    NewFeePromotion, StoredFeeFreeAccount, StoredFeePromotion, StoredTokenFeeAcceptability,
+    StoredTokenFeeSubsidy,
+};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the fee policies set by the operator: the subsidies of the fees in the tokens,
+/// the time-boxed promotional discounts, the accounts that don't pay fees at all and the tokens
+/// that can or can't be used to pay fees regardless of their market volume.
+///
+/// The policies are read on every fee calculation, so the changes apply without restarting the server.
+#[derive(Debug)]
+pub struct FeePoliciesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeePoliciesSchema<'a, 'c> {
+    /// Sets the subsidy of the fees in the token, replaces the previous one if it's set.
+    pub async fn set_token_subsidy(
+        &mut self,
+        token_id: TokenId,
+        subsidy_percent: i16,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_fee_subsidies ( token_id, subsidy_percent )
+            VALUES ( $1, $2 )
+            ON CONFLICT ( token_id ) DO UPDATE SET subsidy_percent = $2, updated_at = now()
+            "#,
+            *token_id as i32,
+            subsidy_percent
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.set_token_subsidy", start);
+        Ok(())
+    }
+
+    /// Removes the subsidy of the fees in the token, returns `false` if it's not set.
+    pub async fn remove_token_subsidy(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM token_fee_subsidies WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.remove_token_subsidy", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the subsidies of all the tokens.
+    pub async fn load_token_subsidies(&mut self) -> QueryResult<Vec<StoredTokenFeeSubsidy>> {
+        let start = Instant::now();
+        let subsidies = sqlx::query_as!(
+            StoredTokenFeeSubsidy,
+            "SELECT * FROM token_fee_subsidies ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.load_token_subsidies", start);
+        Ok(subsidies)
+    }
+
+    /// Stores the promotional discount and returns its ID.
+    pub async fn add_promotion(&mut self, promotion: &NewFeePromotion) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO fee_promotions ( token_id, discount_percent, starts_at, ends_at )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING id
+            "#,
+            promotion.token_id,
+            promotion.discount_percent,
+            promotion.starts_at,
+            promotion.ends_at
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        report_query("fee_policies.add_promotion", start);
+        Ok(id)
+    }
+
+    /// Removes the promotional discount, returns `false` if there is no such promotion.
+    pub async fn remove_promotion(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!("DELETE FROM fee_promotions WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?;
+
+        report_query("fee_policies.remove_promotion", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the promotions that haven't ended by the given time.
+    pub async fn load_promotions(
+        &mut self,
+        now: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredFeePromotion>> {
+        let start = Instant::now();
+        let promotions = sqlx::query_as!(
+            StoredFeePromotion,
+            "SELECT * FROM fee_promotions WHERE ends_at > $1 ORDER BY starts_at, id",
+            now
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.load_promotions", start);
+        Ok(promotions)
+    }
+
+    /// Returns the discount of the fees in the token at the given time, in percent.
+    ///
+    /// The subsidy of the token and the active promotions don't stack, the largest discount is used.
+    pub async fn get_fee_discount_percent(
+        &mut self,
+        token_id: TokenId,
+        now: DateTime<Utc>,
+    ) -> QueryResult<i16> {
+        let start = Instant::now();
+        let discount = sqlx::query!(
+            r#"
+            SELECT GREATEST(
+                (SELECT subsidy_percent FROM token_fee_subsidies WHERE token_id = $1),
+                (
+                    SELECT MAX(discount_percent) FROM fee_promotions
+                    WHERE (token_id = $1 OR token_id IS NULL) AND starts_at <= $2 AND ends_at > $2
+                )
+            ) as "discount_percent"
+            "#,
+            *token_id as i32,
+            now
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .discount_percent;
+
+        report_query("fee_policies.get_fee_discount_percent", start);
+        Ok(discount.unwrap_or(0))
+    }
+
+    /// Adds the account to the fee-free list, updates the reason if the account is already listed.
+    pub async fn add_fee_free_account(
+        &mut self,
+        account_id: AccountId,
+        reason: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_free_accounts ( account_id, reason )
+            VALUES ( $1, $2 )
+            ON CONFLICT ( account_id ) DO UPDATE SET reason = $2
+            "#,
+            i64::from(*account_id),
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.add_fee_free_account", start);
+        Ok(())
+    }
+
+    /// Removes the account from the fee-free list, returns `false` if the account is not listed.
+    pub async fn remove_fee_free_account(&mut self, account_id: AccountId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM fee_free_accounts WHERE account_id = $1",
+            i64::from(*account_id)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.remove_fee_free_account", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all the accounts of the fee-free list.
+    pub async fn load_fee_free_accounts(&mut self) -> QueryResult<Vec<StoredFeeFreeAccount>> {
+        let start = Instant::now();
+        let accounts = sqlx::query_as!(
+            StoredFeeFreeAccount,
+            "SELECT * FROM fee_free_accounts ORDER BY account_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.load_fee_free_accounts", start);
+        Ok(accounts)
+    }
+
+    /// Checks whether the account is in the fee-free list.
+    pub async fn is_fee_free_account(&mut self, account_id: AccountId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let is_fee_free = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM fee_free_accounts WHERE account_id = $1) as "exists!""#,
+            i64::from(*account_id)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .exists;
+
+        report_query("fee_policies.is_fee_free_account", start);
+        Ok(is_fee_free)
+    }
+
+    /// Sets whether the fees can be paid in the token, replaces the previous value if it's set.
+    pub async fn set_token_fee_acceptability(
+        &mut self,
+        token_id: TokenId,
+        fee_acceptable: bool,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_fee_acceptability ( token_id, fee_acceptable )
+            VALUES ( $1, $2 )
+            ON CONFLICT ( token_id ) DO UPDATE SET fee_acceptable = $2, updated_at = now()
+            "#,
+            *token_id as i32,
+            fee_acceptable
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.set_token_fee_acceptability", start);
+        Ok(())
+    }
+
+    /// Removes the fee acceptability set for the token, returns `false` if it's not set.
+    pub async fn remove_token_fee_acceptability(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM token_fee_acceptability WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.remove_token_fee_acceptability", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the fee acceptability of all the tokens it's set for.
+    pub async fn load_token_fee_acceptability(
+        &mut self,
+    ) -> QueryResult<Vec<StoredTokenFeeAcceptability>> {
+        let start = Instant::now();
+        let tokens = sqlx::query_as!(
+            StoredTokenFeeAcceptability,
+            "SELECT * FROM token_fee_acceptability ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("fee_policies.load_token_fee_acceptability", start);
+        Ok(tokens)
+    }
+
+    /// Returns whether the fees can be paid in the token, `None` if it's not set for the token.
+    pub async fn get_token_fee_acceptability(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<bool>> {
+        let start = Instant::now();
+        let fee_acceptable = sqlx::query!(
+            "SELECT fee_acceptable FROM token_fee_acceptability WHERE token_id = $1",
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| row.fee_acceptable);
+
+        report_query("fee_policies.get_token_fee_acceptability", start);
+        Ok(fee_acceptable)
+    }
+}
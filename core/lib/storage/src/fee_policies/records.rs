@@ -0,0 +1,49 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredTokenFeeSubsidy {
+    pub token_id: i32,
+    /// Part of the fee in the token paid by the operator, in percent.
+    pub subsidy_percent: i16,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Promotional discount to be stored in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeePromotion {
+    /// Token the fees of which are discounted, the fees in all the tokens are discounted if not set.
+    pub token_id: Option<i32>,
+    pub discount_percent: i16,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredFeePromotion {
+    pub id: i64,
+    pub token_id: Option<i32>,
+    pub discount_percent: i16,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredFeeFreeAccount {
+    pub account_id: i64,
+    /// Why the account doesn't pay fees, e.g. it's the market maker of the operator.
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredTokenFeeAcceptability {
+    pub token_id: i32,
+    /// Whether the fees can be paid in the token regardless of its market volume.
+    pub fee_acceptable: bool,
+    pub updated_at: DateTime<Utc>,
+}
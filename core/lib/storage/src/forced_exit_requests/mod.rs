@@ -6,7 +6,7 @@ use std::{ops::Sub, time::Instant};
 // External imports
 // Workspace imports
 // Local imports
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 use zksync_types::forced_exit_requests::{
     ForcedExitRequest, ForcedExitRequestId, SaveForcedExitRequestQuery,
 };
@@ -56,7 +56,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.store_request", start.elapsed());
+        report_query("forced_exit_requests.store_request", start);
         Ok(stored_request.into())
     }
 
@@ -78,10 +78,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .await?
         .map(|r| r.into());
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_request_by_id",
-            start.elapsed()
-        );
+        report_query("forced_exit_requests.get_request_by_id", start);
 
         Ok(request)
     }
@@ -105,7 +102,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.set_fulfilled_at", start.elapsed());
+        report_query("forced_exit_requests.set_fulfilled_at", start);
 
         Ok(())
     }
@@ -130,10 +127,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .await?
         .map(|r| r.into());
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_oldest_unfulfilled_request",
-            start.elapsed()
-        );
+        report_query("forced_exit_requests.get_oldest_unfulfilled_request", start);
 
         Ok(request)
     }
@@ -159,7 +153,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.forced_exit_requests.set_fulfilled_by", start.elapsed());
+        report_query("forced_exit_requests.set_fulfilled_by", start);
         Ok(())
     }
 
@@ -182,10 +176,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .map(|rec| rec.into())
         .collect();
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.get_unconfirmed_requests",
-            start.elapsed()
-        );
+        report_query("forced_exit_requests.get_unconfirmed_requests", start);
 
         Ok(requests)
     }
@@ -210,9 +201,9 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.forced_exit_requests.delete_old_unfulfilled_requests",
-            start.elapsed()
+        report_query(
+            "forced_exit_requests.delete_old_unfulfilled_requests",
+            start,
         );
 
         Ok(())
@@ -0,0 +1,37 @@
+//! Instrumentation of the storage queries.
+//!
+//! Durations of the queries are reported to the `sql` histogram labeled by the query name,
+//! the queries slower than `DATABASE_SLOW_QUERY_THRESHOLD` (in ms) are logged.
+
+// Built-in deps
+use std::env;
+use std::time::{Duration, Instant};
+// External imports
+use once_cell::sync::Lazy;
+
+/// Threshold of the query duration after which the query is logged. Not logged if not set.
+static SLOW_QUERY_THRESHOLD: Lazy<Option<Duration>> = Lazy::new(|| {
+    env::var("DATABASE_SLOW_QUERY_THRESHOLD")
+        .ok()
+        .map(|threshold| {
+            Duration::from_millis(
+                threshold
+                    .parse()
+                    .expect("DATABASE_SLOW_QUERY_THRESHOLD should be a number"),
+            )
+        })
+});
+
+/// Reports the duration of the query started at `start`.
+///
+/// Query name consists of the schema and the method names separated by dots,
+/// e.g. `chain.block.get_block`.
+pub(crate) fn report_query(query: &'static str, start: Instant) {
+    let elapsed = start.elapsed();
+    metrics::histogram!("sql", elapsed, "query" => query);
+
+    if matches!(*SLOW_QUERY_THRESHOLD, Some(threshold) if elapsed > threshold) {
+        vlog::warn!("Slow query {} took {} ms", query, elapsed.as_millis());
+        metrics::increment_counter!("sql.slow_query", "query" => query);
+    }
+}
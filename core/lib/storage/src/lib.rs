@@ -32,11 +32,21 @@
 //!
 //! There are the following sets of schemas:
 //!
+//! - address_lists, for the allow and deny lists the transactions are screened against.
+//! - api_keys, for the API keys of the partner integrations.
 //! - config, for the server config.
 //! - data_restore, for the data_restore crate.
 //! - ethereum, for the data associated with the Ethereum blockchain.
+//! - fast_withdrawals, for the liquidity providers fronting the withdrawals and their matches.
+//! - fee_policies, for the fee subsidies, promotional discounts, fee-free accounts and fee tokens.
+//! - operator_costs, for the L1 costs of the operator attributed to the blocks and the collected fees.
 //! - prover, for the data on prover jobs, proofs, etc.
+//! - pruning, for the removal of the history of the old blocks.
+//! - snapshot, for the export and import of the full state of the network.
 //! - tokens, for storing and loading known tokens.
+//! - tx_rejections, for the reasons of the transactions rejected by the API.
+//! - webhooks, for the webhooks the network events are posted to and their deliveries.
+//! - withdrawal_limits, for the limits of the withdrawals set by the operator and the withdrawn amounts.
 //! - chain - the biggest one, which includes several schemas for the ZKSync sidechain itself.
 //!
 //! The chain module includes the following schemas:
@@ -87,6 +97,8 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod address_lists;
+pub mod api_keys;
 pub mod chain;
 pub mod config;
 pub mod connection;
@@ -94,13 +106,23 @@ pub mod data_restore;
 pub mod diff;
 pub mod ethereum;
 pub mod event;
+pub mod fast_withdrawals;
+pub mod fee_policies;
 pub mod forced_exit_requests;
+mod instrumentation;
 pub mod listener;
 pub mod misc;
+pub mod network_stats;
+pub mod operator_costs;
 pub mod prover;
+pub mod pruning;
+pub mod snapshot;
 pub mod test_data;
 pub mod tokens;
+pub mod tx_rejections;
 pub mod utils;
+pub mod webhooks;
+pub mod withdrawal_limits;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -113,9 +135,24 @@ pub const MAX_BLOCK_NUMBER: BlockNumber = BlockNumber(u32::MAX);
 /// The maximum possible index value in block in the storage.
 pub const MAX_BLOCK_INDEX: u32 = i32::MAX as u32;
 
-/// Obtains the database URL from the environment variable.
-pub fn get_database_replica_url() -> String {
-    env::var("DATABASE_REPLICA_URL").unwrap_or_else(|_| get_database_url())
+/// Obtains the URLs of the database replicas from the environment variables.
+/// Replicas are listed in `DATABASE_REPLICA_URLS` separated by commas,
+/// `DATABASE_REPLICA_URL` is used if the list is empty.
+pub fn get_database_replica_urls() -> Vec<String> {
+    let urls: Vec<_> = env::var("DATABASE_REPLICA_URLS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(String::from)
+        .collect();
+    if !urls.is_empty() {
+        return urls;
+    }
+
+    env::var("DATABASE_REPLICA_URL")
+        .map(|url| vec![url])
+        .unwrap_or_default()
 }
 
 /// Obtains the database URL from the environment variable.
@@ -226,6 +263,21 @@ impl<'a> StorageProcessor<'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `NetworkStats` schema.
+    pub fn network_stats_schema(&mut self) -> network_stats::NetworkStatsSchema<'_, 'a> {
+        network_stats::NetworkStatsSchema(self)
+    }
+
+    /// Gains access to the `Pruning` schema.
+    pub fn pruning_schema(&mut self) -> pruning::PruningSchema<'_, 'a> {
+        pruning::PruningSchema(self)
+    }
+
+    /// Gains access to the `Snapshot` schema.
+    pub fn snapshot_schema(&mut self) -> snapshot::SnapshotSchema<'_, 'a> {
+        snapshot::SnapshotSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
@@ -243,6 +295,48 @@ impl<'a> StorageProcessor<'a> {
         misc::MiscSchema(self)
     }
 
+    /// Gains access to the `AddressLists` schema.
+    pub fn address_lists_schema(&mut self) -> address_lists::AddressListsSchema<'_, 'a> {
+        address_lists::AddressListsSchema(self)
+    }
+
+    /// Gains access to the `ApiKeys` schema.
+    pub fn api_keys_schema(&mut self) -> api_keys::ApiKeysSchema<'_, 'a> {
+        api_keys::ApiKeysSchema(self)
+    }
+
+    /// Gains access to the `FastWithdrawals` schema.
+    pub fn fast_withdrawals_schema(&mut self) -> fast_withdrawals::FastWithdrawalsSchema<'_, 'a> {
+        fast_withdrawals::FastWithdrawalsSchema(self)
+    }
+
+    /// Gains access to the `FeePolicies` schema.
+    pub fn fee_policies_schema(&mut self) -> fee_policies::FeePoliciesSchema<'_, 'a> {
+        fee_policies::FeePoliciesSchema(self)
+    }
+
+    /// Gains access to the `OperatorCosts` schema.
+    pub fn operator_costs_schema(&mut self) -> operator_costs::OperatorCostsSchema<'_, 'a> {
+        operator_costs::OperatorCostsSchema(self)
+    }
+
+    /// Gains access to the `TxRejections` schema.
+    pub fn tx_rejections_schema(&mut self) -> tx_rejections::TxRejectionsSchema<'_, 'a> {
+        tx_rejections::TxRejectionsSchema(self)
+    }
+
+    /// Gains access to the `Webhooks` schema.
+    pub fn webhooks_schema(&mut self) -> webhooks::WebhooksSchema<'_, 'a> {
+        webhooks::WebhooksSchema(self)
+    }
+
+    /// Gains access to the `WithdrawalLimits` schema.
+    pub fn withdrawal_limits_schema(
+        &mut self,
+    ) -> withdrawal_limits::WithdrawalLimitsSchema<'_, 'a> {
+        withdrawal_limits::WithdrawalLimitsSchema(self)
+    }
+
     fn conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
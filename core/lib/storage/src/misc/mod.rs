@@ -4,8 +4,8 @@ use std::time::Instant;
 use sqlx::types::BigDecimal;
 // Local imports
 
-use self::records::Subsidy;
-use crate::{QueryResult, StorageProcessor};
+use self::records::{EthWatchBackfillProgress, Subsidy};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 use num::ToPrimitive;
 
 pub mod records;
@@ -36,7 +36,7 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.misc.store_subsidy", start.elapsed());
+        report_query("misc.store_subsidy", start);
         Ok(())
     }
 
@@ -61,7 +61,78 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         // It's better move this param to actor and check it periodically
         metrics::gauge!("tx_sender.store_subsidy_data.total_subsidy", sum.to_f64().unwrap_or_default(), "type" => subsidy_type.to_string());
 
-        metrics::histogram!("sql.token.get_total_used_subsidy_for_type", start.elapsed());
+        report_query("token.get_total_used_subsidy_for_type", start);
         Ok(sum)
     }
+
+    /// Loads the progress of the priority operations backfill performed by the Ethereum watcher.
+    pub async fn load_eth_watch_backfill_progress(
+        &mut self,
+    ) -> QueryResult<Option<EthWatchBackfillProgress>> {
+        let start = Instant::now();
+        let progress = sqlx::query!(
+            "SELECT last_processed_block, next_priority_op_id FROM eth_watch_backfill_progress"
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| EthWatchBackfillProgress {
+            last_processed_block: row.last_processed_block as u64,
+            next_priority_op_id: row.next_priority_op_id as u64,
+        });
+
+        report_query("misc.load_eth_watch_backfill_progress", start);
+        Ok(progress)
+    }
+
+    /// Stores the progress of the priority operations backfill performed by the Ethereum watcher.
+    pub async fn store_eth_watch_backfill_progress(
+        &mut self,
+        progress: EthWatchBackfillProgress,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO eth_watch_backfill_progress ( id, last_processed_block, next_priority_op_id )
+            VALUES ( true, $1, $2 )
+            ON CONFLICT ( id ) DO UPDATE SET last_processed_block = $1, next_priority_op_id = $2
+            "#,
+            progress.last_processed_block as i64,
+            progress.next_priority_op_id as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("misc.store_eth_watch_backfill_progress", start);
+        Ok(())
+    }
+
+    /// Returns `true` if the API servers are in the drain mode and reject the new transactions.
+    pub async fn is_tx_intake_drained(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let drained = sqlx::query!("SELECT drained FROM tx_intake_drain")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map_or(false, |row| row.drained);
+
+        report_query("misc.is_tx_intake_drained", start);
+        Ok(drained)
+    }
+
+    /// Enables or disables the drain mode of the API servers.
+    pub async fn set_tx_intake_drained(&mut self, drained: bool) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO tx_intake_drain ( id, drained, updated_at )
+            VALUES ( true, $1, now() )
+            ON CONFLICT ( id ) DO UPDATE SET drained = $1, updated_at = now()
+            "#,
+            drained
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("misc.set_tx_intake_drained", start);
+        Ok(())
+    }
 }
@@ -13,3 +13,12 @@ pub struct Subsidy {
     pub full_cost_token: BigDecimal,
     pub subsidy_type: String,
 }
+
+/// Progress of the priority operations backfill performed by the Ethereum watcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthWatchBackfillProgress {
+    /// The last Ethereum block the priority operations were backfilled from.
+    pub last_processed_block: u64,
+    /// Serial ID of the priority operation following the backfilled ones.
+    pub next_priority_op_id: u64,
+}
@@ -0,0 +1,288 @@
+// Built-in deps
+use std::{collections::BTreeMap, time::Instant};
+// External imports
+use chrono::NaiveDate;
+use num::{bigint::ToBigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_api_types::v02::stats::{
+    DailyActiveAccounts, DailyFees, DailyTxCounts, TokenValueLocked, TotalValueLocked,
+};
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{BlockNumber, TokenId};
+// Local imports
+use self::records::{
+    StorageDailyActiveAccounts, StorageDailyFees, StorageDailyTxCount, StorageTokenValueLocked,
+};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Network stats schema holds the statistics shown by the block explorer: transactions per day,
+/// fees, active accounts and the total value locked.
+///
+/// The statistics are aggregated incrementally, block range by block range, and only from
+/// the finalized blocks, so they're never affected by the reverts. The history of the pruned
+/// blocks can't be aggregated, so the daily statistics of a node pruning its history start
+/// from the first block it has aggregated.
+#[derive(Debug)]
+pub struct NetworkStatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> NetworkStatsSchema<'a, 'c> {
+    /// Loads the last block the statistics are aggregated for.
+    pub async fn load_last_aggregated_block(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let block = sqlx::query!("SELECT last_block FROM stats_aggregation")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| BlockNumber(row.last_block as u32));
+
+        report_query("network_stats.load_last_aggregated_block", start);
+        Ok(block)
+    }
+
+    /// Adds the blocks up to `last_block` that are not aggregated yet to the statistics.
+    /// Returns `false` if there are no such blocks.
+    pub async fn aggregate_blocks(&mut self, last_block: BlockNumber) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let from_block = NetworkStatsSchema(&mut transaction)
+            .load_last_aggregated_block()
+            .await?
+            .unwrap_or(BlockNumber(0));
+        if from_block >= last_block {
+            return Ok(false);
+        }
+
+        let from_block = i64::from(*from_block);
+        let to_block = i64::from(*last_block);
+        sqlx::query!(
+            r#"
+            INSERT INTO daily_tx_stats ( day, tx_type, tx_count )
+            SELECT day, tx_type, COUNT(*) FROM (
+                SELECT (created_at AT TIME ZONE 'UTC')::date AS day, tx->>'type' AS tx_type
+                FROM executed_transactions
+                WHERE success = true AND block_number > $1 AND block_number <= $2
+                UNION ALL
+                SELECT (created_at AT TIME ZONE 'UTC')::date AS day, operation->>'type' AS tx_type
+                FROM executed_priority_operations
+                WHERE block_number > $1 AND block_number <= $2
+            ) AS txs
+            GROUP BY day, tx_type
+            ON CONFLICT ( day, tx_type )
+            DO UPDATE SET tx_count = daily_tx_stats.tx_count + EXCLUDED.tx_count
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        // The fee is paid either in the `token` of the transaction or in its `feeToken`.
+        sqlx::query!(
+            r#"
+            INSERT INTO daily_fee_stats ( day, token_id, tx_count, total_fee )
+            SELECT day, token_id, COUNT(*), SUM(fee) FROM (
+                SELECT
+                    (created_at AT TIME ZONE 'UTC')::date AS day,
+                    COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id,
+                    (tx->>'fee')::numeric AS fee
+                FROM executed_transactions
+                WHERE success = true AND block_number > $1 AND block_number <= $2
+            ) AS fees
+            WHERE token_id IS NOT NULL AND fee > 0
+            GROUP BY day, token_id
+            ON CONFLICT ( day, token_id )
+            DO UPDATE SET
+                tx_count = daily_fee_stats.tx_count + EXCLUDED.tx_count,
+                total_fee = daily_fee_stats.total_fee + EXCLUDED.total_fee
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO daily_active_accounts ( day, address )
+            SELECT (created_at AT TIME ZONE 'UTC')::date, from_account
+            FROM executed_transactions
+            WHERE success = true AND block_number > $1 AND block_number <= $2
+            UNION
+            SELECT (created_at AT TIME ZONE 'UTC')::date, from_account
+            FROM executed_priority_operations
+            WHERE block_number > $1 AND block_number <= $2
+            ON CONFLICT ( day, address ) DO NOTHING
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO token_value_locked ( token_id, amount )
+            SELECT coin_id, SUM(new_balance - old_balance)
+            FROM account_balance_updates
+            WHERE block_number > $1 AND block_number <= $2 AND coin_id < $3
+            GROUP BY coin_id
+            ON CONFLICT ( token_id )
+            DO UPDATE SET amount = token_value_locked.amount + EXCLUDED.amount
+            "#,
+            from_block,
+            to_block,
+            MIN_NFT_TOKEN_ID as i32
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO stats_aggregation ( id, last_block, aggregated_at )
+            VALUES ( true, $1, now() )
+            ON CONFLICT ( id ) DO UPDATE SET last_block = $1, aggregated_at = now()
+            "#,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        report_query("network_stats.aggregate_blocks", start);
+        Ok(true)
+    }
+
+    /// Loads the amounts of the transactions executed since the given day, the days without
+    /// transactions are omitted.
+    pub async fn load_daily_tx_counts(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<DailyTxCounts>> {
+        let start = Instant::now();
+        let records = sqlx::query_as!(
+            StorageDailyTxCount,
+            r#"
+            SELECT day, tx_type, tx_count FROM daily_tx_stats
+            WHERE day >= $1
+            ORDER BY day
+            "#,
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut days: Vec<DailyTxCounts> = Vec::new();
+        for record in records {
+            if days.last().map(|counts| counts.date) != Some(record.day) {
+                days.push(DailyTxCounts {
+                    date: record.day,
+                    total: 0,
+                    by_type: BTreeMap::new(),
+                });
+            }
+            let counts = days.last_mut().unwrap();
+            counts.total += record.tx_count as u64;
+            counts
+                .by_type
+                .insert(record.tx_type, record.tx_count as u64);
+        }
+
+        report_query("network_stats.load_daily_tx_counts", start);
+        Ok(days)
+    }
+
+    /// Loads the amounts of the active accounts since the given day, the days without
+    /// transactions are omitted.
+    pub async fn load_daily_active_accounts(
+        &mut self,
+        from_day: NaiveDate,
+    ) -> QueryResult<Vec<DailyActiveAccounts>> {
+        let start = Instant::now();
+        let records = sqlx::query_as!(
+            StorageDailyActiveAccounts,
+            r#"
+            SELECT day, COUNT(*) as "active_accounts!" FROM daily_active_accounts
+            WHERE day >= $1
+            GROUP BY day
+            ORDER BY day
+            "#,
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("network_stats.load_daily_active_accounts", start);
+        Ok(records
+            .into_iter()
+            .map(|record| DailyActiveAccounts {
+                date: record.day,
+                active_accounts: record.active_accounts as u64,
+            })
+            .collect())
+    }
+
+    /// Loads the fees paid in every token since the given day, ordered by the day and the token.
+    pub async fn load_daily_fees(&mut self, from_day: NaiveDate) -> QueryResult<Vec<DailyFees>> {
+        let start = Instant::now();
+        let records = sqlx::query_as!(
+            StorageDailyFees,
+            r#"
+            SELECT day, token_id, tx_count, total_fee FROM daily_fee_stats
+            WHERE day >= $1
+            ORDER BY day, token_id
+            "#,
+            from_day
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("network_stats.load_daily_fees", start);
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let total_fee = to_biguint(&record.total_fee);
+                DailyFees {
+                    date: record.day,
+                    token_id: TokenId(record.token_id as u32),
+                    tx_count: record.tx_count as u64,
+                    average_fee: &total_fee / BigUint::from(record.tx_count.max(1) as u64),
+                    total_fee,
+                }
+            })
+            .collect())
+    }
+
+    /// Loads the sums of the balances in every fungible token as of the last aggregated block.
+    pub async fn load_total_value_locked(&mut self) -> QueryResult<TotalValueLocked> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let last_aggregated_block = NetworkStatsSchema(&mut transaction)
+            .load_last_aggregated_block()
+            .await?;
+        let records = sqlx::query_as!(
+            StorageTokenValueLocked,
+            "SELECT token_id, amount FROM token_value_locked ORDER BY token_id"
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        report_query("network_stats.load_total_value_locked", start);
+        Ok(TotalValueLocked {
+            last_aggregated_block,
+            tokens: records
+                .into_iter()
+                .map(|record| TokenValueLocked {
+                    token_id: TokenId(record.token_id as u32),
+                    amount: to_biguint(&record.amount),
+                })
+                .collect(),
+        })
+    }
+}
+
+fn to_biguint(amount: &BigDecimal) -> BigUint {
+    amount
+        .to_bigint()
+        .and_then(|amount| amount.to_biguint())
+        .expect("Aggregated amounts can't be negative")
+}
@@ -0,0 +1,30 @@
+// External imports
+use chrono::NaiveDate;
+use sqlx::{types::BigDecimal, FromRow};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageDailyTxCount {
+    pub day: NaiveDate,
+    pub tx_type: String,
+    pub tx_count: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageDailyActiveAccounts {
+    pub day: NaiveDate,
+    pub active_accounts: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageDailyFees {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    pub tx_count: i64,
+    pub total_fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageTokenValueLocked {
+    pub token_id: i32,
+    pub amount: BigDecimal,
+}
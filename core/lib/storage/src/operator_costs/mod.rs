@@ -0,0 +1,286 @@
+// Built-in deps
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Instant,
+};
+// External imports
+use chrono::{Duration, NaiveDate};
+use num::{bigint::ToBigInt, BigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{
+    operator_costs::{BlockCosts, Costs, DailyCosts, L1CostKind, TokenUsdPrice},
+    BlockNumber, TokenId, H256,
+};
+use zksync_utils::big_decimal_to_ratio;
+// Local imports
+use self::records::{
+    StorageBlockCollectedFee, StorageBlockL1Cost, StorageDailyCollectedFee, StorageDailyL1Cost,
+    StorageTokenUsdPrice,
+};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the costs of the operator: the fees of the Ethereum transactions sent by the
+/// `eth_sender` attributed to the blocks, and the fees collected by the transactions of the blocks.
+///
+/// The L1 costs are split between the blocks before they're stored, see `zksync_types::operator_costs`.
+/// The collected fees aren't stored separately, they're summed from the executed transactions.
+#[derive(Debug)]
+pub struct OperatorCostsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+fn to_biguint(amount: &BigDecimal) -> BigUint {
+    amount
+        .to_bigint()
+        .and_then(|amount| amount.to_biguint())
+        .expect("Costs can't be negative")
+}
+
+fn parse_kind(kind: &str) -> L1CostKind {
+    kind.parse().expect("Invalid L1 cost kind has been stored")
+}
+
+impl<'a, 'c> OperatorCostsSchema<'a, 'c> {
+    /// Stores the shares of the fee of the Ethereum transaction attributed to the blocks.
+    /// The shares already stored for the transaction are not updated.
+    pub async fn save_l1_costs(
+        &mut self,
+        tx_hash: &H256,
+        kind: L1CostKind,
+        costs: &[(BlockNumber, BigUint)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if costs.is_empty() {
+            return Ok(());
+        }
+
+        let (blocks, fees): (Vec<_>, Vec<_>) = costs
+            .iter()
+            .map(|(block, fee)| {
+                (
+                    i64::from(**block),
+                    BigDecimal::from(BigInt::from(fee.clone())),
+                )
+            })
+            .unzip();
+        sqlx::query!(
+            r#"
+            INSERT INTO block_l1_costs ( block_number, tx_hash, kind, fee )
+            SELECT u.block_number, $1, $2, u.fee
+            FROM UNNEST ($3::bigint[], $4::numeric[]) AS u(block_number, fee)
+            ON CONFLICT ( block_number, tx_hash ) DO NOTHING
+            "#,
+            tx_hash.as_bytes(),
+            kind.to_string(),
+            &blocks,
+            &fees
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("operator_costs.save_l1_costs", start);
+        Ok(())
+    }
+
+    /// Loads the costs of the blocks in the range, the blocks that are not stored are omitted.
+    pub async fn load_block_costs(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<BlockCosts>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let (from_block, to_block) = (i64::from(*from_block), i64::from(*to_block));
+
+        let blocks = sqlx::query!(
+            "SELECT number, timestamp FROM blocks WHERE number BETWEEN $1 AND $2 ORDER BY number",
+            from_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let l1_costs = sqlx::query_as!(
+            StorageBlockL1Cost,
+            r#"
+            SELECT block_number, kind, SUM(fee) AS "fee!" FROM block_l1_costs
+            WHERE block_number BETWEEN $1 AND $2
+            GROUP BY block_number, kind
+            "#,
+            from_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        // The fee is paid either in the `token` of the transaction or in its `feeToken`.
+        let collected_fees = sqlx::query_as!(
+            StorageBlockCollectedFee,
+            r#"
+            SELECT block_number AS "block_number!", token_id AS "token_id!", SUM(fee) AS "fee!" FROM (
+                SELECT
+                    block_number,
+                    COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id,
+                    (tx->>'fee')::numeric AS fee
+                FROM executed_transactions
+                WHERE success = true AND block_number BETWEEN $1 AND $2
+            ) AS fees
+            WHERE token_id IS NOT NULL AND fee > 0
+            GROUP BY block_number, token_id
+            "#,
+            from_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        let mut costs: BTreeMap<i64, Costs> = BTreeMap::new();
+        for record in l1_costs {
+            costs
+                .entry(record.block_number)
+                .or_default()
+                .add_l1_cost(parse_kind(&record.kind), &to_biguint(&record.fee));
+        }
+        for record in collected_fees {
+            costs
+                .entry(record.block_number)
+                .or_default()
+                .add_collected_fee(TokenId(record.token_id as u32), &to_biguint(&record.fee));
+        }
+
+        report_query("operator_costs.load_block_costs", start);
+        Ok(blocks
+            .into_iter()
+            .map(|block| BlockCosts {
+                block_number: BlockNumber(block.number as u32),
+                timestamp: block.timestamp.map(|timestamp| timestamp as u64),
+                costs: costs.remove(&block.number).unwrap_or_default(),
+                profitability: None,
+            })
+            .collect())
+    }
+
+    /// Loads the costs of the blocks created during the UTC days in the range,
+    /// the days without blocks are omitted.
+    pub async fn load_daily_costs(
+        &mut self,
+        from_day: NaiveDate,
+        to_day: NaiveDate,
+    ) -> QueryResult<Vec<DailyCosts>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let from_timestamp = from_day.and_hms(0, 0, 0).timestamp();
+        let to_timestamp = (to_day + Duration::days(1)).and_hms(0, 0, 0).timestamp();
+
+        let days = sqlx::query!(
+            r#"
+            SELECT (to_timestamp(timestamp) AT TIME ZONE 'UTC')::date AS "day!", COUNT(*) AS "blocks!"
+            FROM blocks
+            WHERE timestamp >= $1 AND timestamp < $2
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            from_timestamp,
+            to_timestamp
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let l1_costs = sqlx::query_as!(
+            StorageDailyL1Cost,
+            r#"
+            SELECT
+                (to_timestamp(blocks.timestamp) AT TIME ZONE 'UTC')::date AS "day!",
+                block_l1_costs.kind,
+                SUM(block_l1_costs.fee) AS "fee!"
+            FROM block_l1_costs
+            INNER JOIN blocks ON blocks.number = block_l1_costs.block_number
+            WHERE blocks.timestamp >= $1 AND blocks.timestamp < $2
+            GROUP BY 1, block_l1_costs.kind
+            "#,
+            from_timestamp,
+            to_timestamp
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let collected_fees = sqlx::query_as!(
+            StorageDailyCollectedFee,
+            r#"
+            SELECT day AS "day!", token_id AS "token_id!", SUM(fee) AS "fee!" FROM (
+                SELECT
+                    (to_timestamp(blocks.timestamp) AT TIME ZONE 'UTC')::date AS day,
+                    COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id,
+                    (tx->>'fee')::numeric AS fee
+                FROM executed_transactions
+                INNER JOIN blocks ON blocks.number = executed_transactions.block_number
+                WHERE success = true AND blocks.timestamp >= $1 AND blocks.timestamp < $2
+            ) AS fees
+            WHERE token_id IS NOT NULL AND fee > 0
+            GROUP BY day, token_id
+            "#,
+            from_timestamp,
+            to_timestamp
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        let mut costs: BTreeMap<NaiveDate, Costs> = BTreeMap::new();
+        for record in l1_costs {
+            costs
+                .entry(record.day)
+                .or_default()
+                .add_l1_cost(parse_kind(&record.kind), &to_biguint(&record.fee));
+        }
+        for record in collected_fees {
+            costs
+                .entry(record.day)
+                .or_default()
+                .add_collected_fee(TokenId(record.token_id as u32), &to_biguint(&record.fee));
+        }
+
+        report_query("operator_costs.load_daily_costs", start);
+        Ok(days
+            .into_iter()
+            .map(|day| DailyCosts {
+                day: day.day,
+                blocks: day.blocks as u64,
+                costs: costs.remove(&day.day).unwrap_or_default(),
+                profitability: None,
+            })
+            .collect())
+    }
+
+    /// Loads the current USD prices of the tokens, the tokens without the price are omitted.
+    pub async fn load_token_prices(
+        &mut self,
+        tokens: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, TokenUsdPrice>> {
+        let start = Instant::now();
+        let tokens: Vec<_> = tokens.iter().map(|token| **token as i32).collect();
+        let records = sqlx::query_as!(
+            StorageTokenUsdPrice,
+            r#"
+            SELECT tokens.id AS token_id, tokens.decimals, ticker_price.usd_price
+            FROM tokens
+            INNER JOIN ticker_price ON ticker_price.token_id = tokens.id
+            WHERE tokens.id = ANY($1)
+            "#,
+            &tokens
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("operator_costs.load_token_prices", start);
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let price = TokenUsdPrice {
+                    decimals: record.decimals as u8,
+                    usd_price: big_decimal_to_ratio(&record.usd_price)
+                        .expect("Price could not be negative"),
+                };
+                (TokenId(record.token_id as u32), price)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,38 @@
+// External imports
+use chrono::NaiveDate;
+use sqlx::{types::BigDecimal, FromRow};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageBlockL1Cost {
+    pub block_number: i64,
+    pub kind: String,
+    pub fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageBlockCollectedFee {
+    pub block_number: i64,
+    pub token_id: i32,
+    pub fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageDailyL1Cost {
+    pub day: NaiveDate,
+    pub kind: String,
+    pub fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageDailyCollectedFee {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    pub fee: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageTokenUsdPrice {
+    pub token_id: i32,
+    pub decimals: i16,
+    pub usd_price: BigDecimal,
+}
@@ -5,14 +5,19 @@ use anyhow::format_err;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    ProverJobQueueStats, StorageProverJobQueue, StorageStuckProverJob, StoredAggregatedProof,
+    StoredProof,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 use chrono::{TimeZone, Utc};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
+use zksync_types::prover::{
+    ProverJob, ProverJobScheduling, ProverJobStatus, ProverJobType, StuckProverJob,
+};
 
 pub mod records;
 
@@ -34,7 +39,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .count
         .unwrap_or(0) as u32;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "pending_jobs_count");
+        report_query("prover.pending_jobs_count", start);
         Ok(pending_jobs_count)
     }
 
@@ -45,15 +50,16 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_data: serde_json::Value,
         job_priority: i32,
         job_type: ProverJobType,
+        scheduling: ProverJobScheduling,
     ) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
         "
           WITH job_values as (
-            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
+            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb, $7::int8, $8::int8
             WHERE NOT EXISTS (SELECT * FROM prover_job_queue WHERE first_block = $4 and last_block = $5 and job_type = $3 LIMIT 1)
           )
-          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
+          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data, block_size, deadline_block)
           SELECT * from job_values
         ",
             ProverJobStatus::Idle.to_number(),
@@ -62,28 +68,106 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             i64::from(*first_block),
             i64::from(*last_block),
             job_data,
+            scheduling.block_size.map(|size| size as i64),
+            scheduling.deadline_block.map(|block| block as i64),
         ).execute(self.0.conn()).await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "add_prover_job_to_job_queue");
+        report_query("prover.add_prover_job_to_job_queue", start);
         Ok(())
     }
 
-    pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
+    /// Returns the amount of the unfinished prover jobs and their earliest deadline,
+    /// grouped by the job type, status and block size.
+    pub async fn prover_job_queue_stats(&mut self) -> QueryResult<Vec<ProverJobQueueStats>> {
         let start = Instant::now();
+        let stats = sqlx::query_as!(
+            ProverJobQueueStats,
+            r#"
+                SELECT job_type, job_status, block_size, COUNT(*) as "jobs_count!", MIN(deadline_block) as min_deadline_block
+                FROM prover_job_queue
+                WHERE job_status != $1
+                GROUP BY job_type, job_status, block_size
+            "#,
+            ProverJobStatus::Done.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("prover.prover_job_queue_stats", start);
+        Ok(stats)
+    }
+
+    /// Returns the jobs whose provers haven't sent a heartbeat for longer than `heartbeat_timeout`
+    /// back to the queue, so they are handed out to the other provers.
+    ///
+    /// Returns the ids of the requeued jobs.
+    pub async fn mark_stale_jobs_as_idle(
+        &mut self,
+        heartbeat_timeout: chrono::Duration,
+    ) -> QueryResult<Vec<i32>> {
+        let start = Instant::now();
+        let last_allowed_heartbeat = Utc::now() - heartbeat_timeout;
         let result = sqlx::query!(
-            "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 AND (now() - INTERVAL '120 seconds') >= updated_at RETURNING id",
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by, reassignments) = ($1, now(), 'server_clean_idle', reassignments + 1)
+            WHERE job_status = $2 AND updated_at <= $3 RETURNING id",
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
+            last_allowed_heartbeat,
         )
         .fetch_all(self.0.conn())
         .await?;
         metrics::counter!("stale_jobs", result.len() as u64);
-        metrics::histogram!("sql", start.elapsed(), "prover" => "mark_stale_jobs_as_idle");
-        Ok(())
+        report_query("prover.mark_stale_jobs_as_idle", start);
+        Ok(result.into_iter().map(|row| row.id).collect())
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Loads the unfinished jobs that either were already taken from the silent provers,
+    /// or whose provers haven't sent a heartbeat for longer than `heartbeat_timeout`.
+    pub async fn load_stuck_prover_jobs(
+        &mut self,
+        heartbeat_timeout: chrono::Duration,
+    ) -> QueryResult<Vec<StuckProverJob>> {
+        let start = Instant::now();
+        let last_allowed_heartbeat = Utc::now() - heartbeat_timeout;
+        let jobs = sqlx::query_as!(
+            StorageStuckProverJob,
+            "SELECT id, job_status, job_type, first_block, last_block, updated_by, updated_at, reassignments
+            FROM prover_job_queue
+            WHERE job_status != $1 AND (reassignments > 0 OR (job_status = $2 AND updated_at <= $3))
+            ORDER BY id",
+            ProverJobStatus::Done.to_number(),
+            ProverJobStatus::InProgress.to_number(),
+            last_allowed_heartbeat,
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|job| StuckProverJob {
+            job_id: job.id,
+            job_type: job.job_type,
+            first_block: BlockNumber(job.first_block as u32),
+            last_block: BlockNumber(job.last_block as u32),
+            in_progress: job.job_status == ProverJobStatus::InProgress.to_number(),
+            updated_by: job.updated_by,
+            updated_at: job.updated_at,
+            reassignments: job.reassignments as u32,
+        })
+        .collect();
+
+        report_query("prover.load_stuck_prover_jobs", start);
+        Ok(jobs)
+    }
+
+    /// Hands out the idle prover job with the highest priority.
+    ///
+    /// Among the jobs of the same priority, the job with the earliest deadline is chosen.
+    /// If `block_sizes` is not empty, only the single proof jobs for the blocks of these sizes
+    /// are handed out, so the provers can be split into pools by the supported block sizes.
+    pub async fn get_idle_prover_job_from_job_queue(
+        &mut self,
+        block_sizes: &[usize],
+    ) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
         // Select the block to prove.
         let mut transaction = self.0.start_transaction().await?;
@@ -96,10 +180,15 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             r#"
                 SELECT * FROM prover_job_queue
                 WHERE job_status = $1
-                ORDER BY (job_priority, id, first_block)
+                    AND (cardinality($2::int8[]) = 0 OR block_size IS NULL OR block_size = ANY($2))
+                ORDER BY job_priority, deadline_block ASC NULLS LAST, id, first_block
                 LIMIT 1
             "#,
-            ProverJobStatus::Idle.to_number()
+            ProverJobStatus::Idle.to_number(),
+            &block_sizes
+                .iter()
+                .map(|&size| size as i64)
+                .collect::<Vec<_>>()
         )
         .fetch_optional(transaction.conn())
         .await?;
@@ -127,7 +216,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             None
         };
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_job_from_job_queue");
+        report_query("prover.get_idle_prover_job_from_job_queue", start);
         Ok(prover_job)
     }
 
@@ -148,7 +237,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
+        report_query("prover.record_prover_is_working", start);
         Ok(())
     }
 
@@ -166,7 +255,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
+        report_query("prover.record_prover_stop", start);
         Ok(())
     }
 
@@ -209,7 +298,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .set_block_processing_metrics(block_number, block_number, "single_proof".to_string())
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_proof");
+        report_query("prover.store_proof", start);
         Ok(())
     }
 
@@ -280,7 +369,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_aggregated_proof");
+        report_query("prover.store_aggregated_proof", start);
         Ok(())
     }
 
@@ -299,7 +388,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof");
+        report_query("prover.load_proof", start);
         Ok(proof)
     }
 
@@ -320,30 +409,32 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
+        report_query("prover.load_aggregated_proof", start);
         Ok(proof)
     }
 
-    /// Stores witness for a block
+    /// Stores witness for a block, compressed.
     pub async fn store_witness(
         &mut self,
         block: BlockNumber,
         witness: serde_json::Value,
     ) -> QueryResult<()> {
         let start = Instant::now();
-        let witness_str = serde_json::to_string(&witness).expect("Failed to serialize witness");
+        let witness = StorageBlockWitness::new(i64::from(*block), &witness)?;
+        let witness_data = witness.witness_data.unwrap_or_default();
+        metrics::histogram!("prover.witness_size", witness_data.len() as f64);
         sqlx::query!(
-            "INSERT INTO block_witness (block, witness)
+            "INSERT INTO block_witness (block, witness_data)
             VALUES ($1, $2)
             ON CONFLICT (block)
             DO NOTHING",
-            i64::from(*block),
-            witness_str
+            witness.block,
+            witness_data
         )
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_witness");
+        report_query("prover.store_witness", start);
         Ok(())
     }
 
@@ -355,15 +446,38 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         let start = Instant::now();
         let block_witness = sqlx::query_as!(
             StorageBlockWitness,
-            "SELECT * FROM block_witness WHERE block = $1",
+            "SELECT block, witness, witness_data FROM block_witness WHERE block = $1",
             i64::from(*block_number),
         )
         .fetch_optional(self.0.conn())
         .await?;
+        let witness = block_witness
+            .map(StorageBlockWitness::into_witness)
+            .transpose()?;
+
+        report_query("prover.get_witness", start);
+        Ok(witness)
+    }
+
+    /// Removes the witnesses of the blocks up to `last_verified_block`
+    /// stored earlier than `max_age` ago, returns the amount of the removed witnesses.
+    pub async fn remove_old_witnesses(
+        &mut self,
+        last_verified_block: BlockNumber,
+        max_age: chrono::Duration,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM block_witness WHERE block <= $1 AND created_at < $2",
+            i64::from(*last_verified_block),
+            Utc::now() - max_age
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
-        Ok(block_witness
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
+        report_query("prover.remove_old_witnesses", start);
+        Ok(removed)
     }
 
     pub async fn get_last_block_prover_job_queue(
@@ -404,7 +518,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_last_block_prover_job_queue");
+        report_query("prover.get_last_block_prover_job_queue", start);
         Ok(result)
     }
 
@@ -418,7 +532,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_witnesses");
+        report_query("prover.remove_witnesses", start);
         Ok(())
     }
 
@@ -432,7 +546,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_proofs");
+        report_query("prover.remove_proofs", start);
         Ok(())
     }
 
@@ -446,7 +560,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_aggregated_proofs");
+        report_query("prover.remove_aggregated_proofs", start);
         Ok(())
     }
 
@@ -469,7 +583,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "remove_prover_jobs");
+        report_query("prover.remove_prover_jobs", start);
         Ok(())
     }
 }
@@ -50,10 +50,45 @@ pub struct IntegerNumber {
     pub integer_value: i64,
 }
 
+/// Level of the zstd compression of the stored witnesses.
+const WITNESS_COMPRESSION_LEVEL: i32 = 3;
+
+/// Witness of the block. Witnesses are stored as zstd-compressed CBOR in `witness_data`,
+/// `witness` is only set for the JSON witnesses stored before the compression was introduced.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct StorageBlockWitness {
     pub block: i64,
-    pub witness: String,
+    pub witness: Option<String>,
+    pub witness_data: Option<Vec<u8>>,
+}
+
+impl StorageBlockWitness {
+    /// Encodes the witness of the block in the storage format.
+    pub fn new(block: i64, witness: &serde_json::Value) -> anyhow::Result<Self> {
+        let encoded = serde_cbor::to_vec(witness)?;
+        let compressed = zstd::encode_all(encoded.as_slice(), WITNESS_COMPRESSION_LEVEL)?;
+
+        Ok(Self {
+            block,
+            witness: None,
+            witness_data: Some(compressed),
+        })
+    }
+
+    /// Decodes the stored witness, falling back to the JSON for the witnesses stored uncompressed.
+    pub fn into_witness(self) -> anyhow::Result<serde_json::Value> {
+        match (self.witness_data, self.witness) {
+            (Some(compressed), _) => {
+                let encoded = zstd::decode_all(compressed.as_slice())?;
+                Ok(serde_cbor::from_slice(&encoded)?)
+            }
+            (None, Some(witness)) => Ok(serde_json::from_str(&witness)?),
+            (None, None) => Err(anyhow::format_err!(
+                "Witness for the block {} is not stored",
+                self.block
+            )),
+        }
+    }
 }
 
 #[derive(Debug, FromRow)]
@@ -68,4 +103,30 @@ pub struct StorageProverJobQueue {
     pub first_block: i64,
     pub last_block: i64,
     pub job_data: serde_json::Value,
+    pub block_size: Option<i64>,
+    pub deadline_block: Option<i64>,
+    pub reassignments: i32,
+}
+
+/// Amount of the prover jobs of the same type, status and block size.
+#[derive(Debug, FromRow)]
+pub struct ProverJobQueueStats {
+    pub job_type: String,
+    pub job_status: i32,
+    pub block_size: Option<i64>,
+    pub jobs_count: i64,
+    pub min_deadline_block: Option<i64>,
+}
+
+/// Unfinished prover job which has lost its prover or whose prover went silent.
+#[derive(Debug, FromRow)]
+pub struct StorageStuckProverJob {
+    pub id: i32,
+    pub job_status: i32,
+    pub job_type: String,
+    pub first_block: i64,
+    pub last_block: i64,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+    pub reassignments: i32,
 }
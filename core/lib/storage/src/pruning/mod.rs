@@ -0,0 +1,104 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use self::records::PrunedHistory;
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Pruning schema removes the history of the old blocks, so the nodes that don't serve
+/// the full history don't have to store it.
+///
+/// Only the executed L2 transactions, events and witnesses are removed. Blocks, accounts,
+/// priority operations and account tree caches are kept, since they're required to continue
+/// the operation and to build the exodus proofs.
+#[derive(Debug)]
+pub struct PruningSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PruningSchema<'a, 'c> {
+    /// Loads the last block whose history is pruned.
+    pub async fn load_last_pruned_block(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let block = sqlx::query!("SELECT last_pruned_block FROM history_pruning")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| BlockNumber(row.last_pruned_block as u32));
+
+        report_query("pruning.load_last_pruned_block", start);
+        Ok(block)
+    }
+
+    /// Removes the history of the blocks up to `last_block` that's not pruned yet.
+    pub async fn prune_history(&mut self, last_block: BlockNumber) -> QueryResult<PrunedHistory> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let from_block = PruningSchema(&mut transaction)
+            .load_last_pruned_block()
+            .await?
+            .unwrap_or(BlockNumber(0));
+        if from_block >= last_block {
+            return Ok(PrunedHistory::default());
+        }
+
+        let from_block = i64::from(*from_block);
+        let to_block = i64::from(*last_block);
+        sqlx::query!(
+            r#"
+            DELETE FROM tx_filters WHERE tx_hash IN (
+                SELECT tx_hash FROM executed_transactions
+                WHERE block_number > $1 AND block_number <= $2
+            )
+            "#,
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        let transactions = sqlx::query!(
+            "DELETE FROM executed_transactions WHERE block_number > $1 AND block_number <= $2",
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        let events = sqlx::query!(
+            "DELETE FROM events WHERE block_number > $1 AND block_number <= $2",
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        let witnesses = sqlx::query!(
+            "DELETE FROM block_witness WHERE block > $1 AND block <= $2",
+            from_block,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO history_pruning ( id, last_pruned_block, pruned_at )
+            VALUES ( true, $1, now() )
+            ON CONFLICT ( id ) DO UPDATE SET last_pruned_block = $1, pruned_at = now()
+            "#,
+            to_block
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        report_query("pruning.prune_history", start);
+        Ok(PrunedHistory {
+            transactions,
+            events,
+            witnesses,
+        })
+    }
+}
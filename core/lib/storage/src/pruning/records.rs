@@ -0,0 +1,8 @@
+/// Amounts of the records removed by the history pruning.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrunedHistory {
+    /// Executed L2 transactions, along with their entries in the accounts history.
+    pub transactions: u64,
+    pub events: u64,
+    pub witnesses: u64,
+}
@@ -0,0 +1,264 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_crypto::params::{NFT_STORAGE_ACCOUNT_ADDRESS, NFT_TOKEN_ID};
+use zksync_types::{
+    aggregated_operations::{BlocksCommitOperation, BlocksExecuteOperation},
+    Account, AccountId, AccountUpdate, BlockNumber, PubKeyHash, Token, TokenKind, NFT,
+};
+// Local imports
+use self::records::{StateSnapshot, SNAPSHOT_FORMAT_VERSION};
+use crate::{
+    chain::{block::BlockSchema, state::StateSchema},
+    data_restore::DataRestoreSchema,
+    instrumentation::report_query,
+    tokens::{records::DbToken, TokensSchema},
+    QueryResult, StorageProcessor,
+};
+
+pub mod records;
+
+/// Snapshot schema exports the verified state of the network and imports it into
+/// a fresh database, so a new node can be bootstrapped without replaying the whole
+/// history with the data restore.
+///
+/// Only the state is transferred: the history of the blocks and transactions before
+/// the snapshot block is not available on the node bootstrapped from the snapshot.
+#[derive(Debug)]
+pub struct SnapshotSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> SnapshotSchema<'a, 'c> {
+    /// Exports the state of the network at the last verified block.
+    pub async fn export_snapshot(&mut self) -> QueryResult<StateSnapshot> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (block_number, accounts) = StateSchema(&mut transaction).load_verified_state().await?;
+        let mut block = BlockSchema(&mut transaction)
+            .get_block(block_number)
+            .await?
+            .ok_or_else(|| anyhow::format_err!("Verified block {} is not found", *block_number))?;
+        block.block_transactions.clear();
+
+        let mut accounts: Vec<_> = accounts.into_iter().collect();
+        accounts.sort_by_key(|(id, _)| *id);
+
+        let tokens = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+            WHERE kind <> 'NFT'::token_kind
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+        let mut nfts: Vec<_> = TokensSchema(&mut transaction)
+            .load_nfts()
+            .await?
+            .into_iter()
+            .map(|(_, nft)| nft)
+            .collect();
+        nfts.sort_by_key(|nft| nft.id);
+
+        transaction.commit().await?;
+
+        report_query("snapshot.export_snapshot", start);
+        Ok(StateSnapshot {
+            version: SNAPSHOT_FORMAT_VERSION,
+            block,
+            accounts,
+            tokens,
+            nfts,
+        })
+    }
+
+    /// Imports the snapshot into the empty database. The snapshot block is stored
+    /// as committed and executed, so the node continues from the block following it.
+    pub async fn import_snapshot(&mut self, snapshot: StateSnapshot) -> QueryResult<()> {
+        let start = Instant::now();
+        anyhow::ensure!(
+            snapshot.version == SNAPSHOT_FORMAT_VERSION,
+            "Unsupported snapshot format version {}, expected {}",
+            snapshot.version,
+            SNAPSHOT_FORMAT_VERSION
+        );
+        let mut transaction = self.0.start_transaction().await?;
+
+        let blocks_count = sqlx::query!(r#"SELECT count(*) as "count!" FROM blocks"#)
+            .fetch_one(transaction.conn())
+            .await?
+            .count;
+        anyhow::ensure!(
+            blocks_count == 0,
+            "The snapshot can only be imported into an empty database"
+        );
+
+        // The special token isn't exported with the NFTs, but the balance of the NFT storage
+        // account refers to it.
+        TokensSchema(&mut transaction)
+            .store_or_update_token(special_token())
+            .await?;
+        for token in snapshot.tokens {
+            TokensSchema(&mut transaction)
+                .store_or_update_token(token)
+                .await?;
+        }
+
+        let block = snapshot.block;
+        let mut accounts = snapshot.accounts;
+        accounts.sort_by_key(|(id, _)| *id);
+        let updates = snapshot_account_updates(&accounts, snapshot.nfts);
+        StateSchema(&mut transaction)
+            .commit_state_update(block.block_number, &updates, 0)
+            .await?;
+        // Stores the operations of the block as confirmed and applies the state update.
+        DataRestoreSchema(&mut transaction)
+            .save_block_operations(
+                BlocksCommitOperation {
+                    last_committed_block: block.clone(),
+                    blocks: vec![block.clone()],
+                },
+                BlocksExecuteOperation {
+                    blocks: vec![block.clone()],
+                },
+            )
+            .await?;
+        let block_number = block.block_number;
+        BlockSchema(&mut transaction).save_full_block(block).await?;
+        DataRestoreSchema(&mut transaction)
+            .initialize_eth_stats(block_number, block_number, block_number)
+            .await?;
+
+        transaction.commit().await?;
+
+        report_query("snapshot.import_snapshot", start);
+        Ok(())
+    }
+
+    /// Imports the snapshot into the empty database as the genesis state of a new network.
+    ///
+    /// Unlike `import_snapshot`, the network starts anew: the accounts are created in the genesis
+    /// block, and the root hash of the snapshot block becomes the genesis root of the contracts.
+    pub async fn import_snapshot_as_genesis(&mut self, snapshot: StateSnapshot) -> QueryResult<()> {
+        let start = Instant::now();
+        anyhow::ensure!(
+            snapshot.version == SNAPSHOT_FORMAT_VERSION,
+            "Unsupported snapshot format version {}, expected {}",
+            snapshot.version,
+            SNAPSHOT_FORMAT_VERSION
+        );
+        let mut transaction = self.0.start_transaction().await?;
+
+        let blocks_count = sqlx::query!(r#"SELECT count(*) as "count!" FROM blocks"#)
+            .fetch_one(transaction.conn())
+            .await?
+            .count;
+        anyhow::ensure!(
+            blocks_count == 0,
+            "The genesis state can only be imported into an empty database"
+        );
+
+        TokensSchema(&mut transaction)
+            .store_or_update_token(special_token())
+            .await?;
+        for token in snapshot.tokens {
+            TokensSchema(&mut transaction)
+                .store_or_update_token(token)
+                .await?;
+        }
+
+        let mut accounts = snapshot.accounts;
+        accounts.sort_by_key(|(id, _)| *id);
+        let updates = snapshot_account_updates(&accounts, snapshot.nfts);
+        StateSchema(&mut transaction)
+            .commit_state_update(BlockNumber(0), &updates, 0)
+            .await?;
+        StateSchema(&mut transaction)
+            .apply_state_update(BlockNumber(0))
+            .await?;
+        BlockSchema(&mut transaction)
+            .save_genesis_block(snapshot.block.new_root_hash)
+            .await?;
+
+        transaction.commit().await?;
+
+        report_query("snapshot.import_snapshot_as_genesis", start);
+        Ok(())
+    }
+}
+
+/// Token the NFT storage account holds the balance of to issue the NFT IDs.
+fn special_token() -> Token {
+    Token::new(
+        NFT_TOKEN_ID,
+        *NFT_STORAGE_ACCOUNT_ADDRESS,
+        "SPECIAL",
+        18,
+        TokenKind::NFT,
+    )
+}
+
+/// Converts the snapshot accounts into the updates creating them from scratch.
+///
+/// Accounts are created first, so the NFTs can refer to their creators, and the NFTs
+/// are minted before the balances are set, since the balances refer to the tokens.
+/// Accounts are expected to be sorted by their IDs.
+fn snapshot_account_updates(
+    accounts: &[(AccountId, Account)],
+    nfts: Vec<NFT>,
+) -> Vec<(AccountId, AccountUpdate)> {
+    let mut updates = Vec::new();
+
+    for (id, account) in accounts {
+        updates.push((
+            *id,
+            AccountUpdate::Create {
+                address: account.address,
+                nonce: account.nonce,
+            },
+        ));
+        if account.pub_key_hash != PubKeyHash::default() {
+            updates.push((
+                *id,
+                AccountUpdate::ChangePubKeyHash {
+                    old_pub_key_hash: PubKeyHash::default(),
+                    new_pub_key_hash: account.pub_key_hash,
+                    old_nonce: account.nonce,
+                    new_nonce: account.nonce,
+                },
+            ));
+        }
+    }
+
+    for nft in nfts {
+        let nonce = accounts
+            .binary_search_by_key(&nft.creator_id, |(id, _)| *id)
+            .map(|idx| accounts[idx].1.nonce)
+            .unwrap_or_default();
+        updates.push((nft.creator_id, AccountUpdate::MintNFT { token: nft, nonce }));
+    }
+
+    for (id, account) in accounts {
+        let mut balances: Vec<_> = account.get_nonzero_balances().into_iter().collect();
+        balances.sort_by_key(|(token, _)| *token);
+        for (token, balance) in balances {
+            updates.push((
+                *id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: account.nonce,
+                    new_nonce: account.nonce,
+                    balance_update: (token, BigUint::from(0u32), balance.0),
+                },
+            ));
+        }
+    }
+
+    updates
+}
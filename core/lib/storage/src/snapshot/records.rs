@@ -0,0 +1,22 @@
+// External imports
+use serde::{Deserialize, Serialize};
+// Workspace imports
+use zksync_types::{block::Block, Account, AccountId, Token, NFT};
+
+/// Version of the snapshot format, snapshots of the other versions can't be imported.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Full state of the network at the verified block, sufficient to bootstrap a new node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// Version of the snapshot format.
+    pub version: u32,
+    /// The block the state corresponds to, without its transactions.
+    pub block: Block,
+    /// All the accounts of the network, sorted by their IDs.
+    pub accounts: Vec<(AccountId, Account)>,
+    /// Tokens known to the network, except for the NFTs.
+    pub tokens: Vec<Token>,
+    /// Minted NFTs, sorted by their IDs.
+    pub nfts: Vec<NFT>,
+}
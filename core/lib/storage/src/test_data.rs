@@ -19,7 +19,7 @@ use zksync_types::{
         AggregatedActionType, AggregatedOperation, BlocksCommitOperation,
         BlocksCreateProofOperation, BlocksExecuteOperation, BlocksProofOperation,
     },
-    tx::{EthSignData, PackedEthSignature, TxEthSignature},
+    tx::{EthSignData, PackedEthSignature, SignedMessage, TxEthSignature},
     Action, Address, Operation, H256, NFT,
     {
         block::{Block, ExecutedOperations},
@@ -137,7 +137,7 @@ pub fn gen_eth_sign_data(message: String) -> EthSignData {
 
     EthSignData {
         signature: TxEthSignature::EthereumSignature(signature),
-        message: message.into_bytes(),
+        message: SignedMessage::Text(message),
     }
 }
 
@@ -0,0 +1,60 @@
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::address_lists::AddressListsSchema;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the allow and deny lists are stored and loaded independently.
+#[db_test]
+async fn store_and_load_lists(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (alice, bob, carol) = (
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0x02),
+        Address::repeat_byte(0x03),
+    );
+
+    AddressListsSchema(&mut storage)
+        .add_address(alice, false, Some("sanctioned"))
+        .await?;
+    AddressListsSchema(&mut storage)
+        .add_address(bob, true, None)
+        .await?;
+    // Adding the address once again updates the reason.
+    AddressListsSchema(&mut storage)
+        .add_address(alice, false, Some("sanctioned twice"))
+        .await?;
+
+    let denied = AddressListsSchema(&mut storage).load_list(false).await?;
+    assert_eq!(denied.len(), 1);
+    assert_eq!(denied[0].address, alice.as_bytes().to_vec());
+    assert_eq!(denied[0].reason.as_deref(), Some("sanctioned twice"));
+
+    let listed = AddressListsSchema(&mut storage)
+        .load_listed_addresses(&[alice, bob, carol], true)
+        .await?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].address, bob.as_bytes().to_vec());
+    assert!(AddressListsSchema(&mut storage)
+        .load_listed_addresses(&[bob, carol], false)
+        .await?
+        .is_empty());
+
+    assert!(
+        AddressListsSchema(&mut storage)
+            .remove_address(alice, false)
+            .await?
+    );
+    assert!(
+        !AddressListsSchema(&mut storage)
+            .remove_address(alice, false)
+            .await?
+    );
+    assert!(AddressListsSchema(&mut storage)
+        .load_list(false)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// External imports
+// Workspace imports
+// Local imports
+use crate::api_keys::{records::NewApiKey, ApiKeysSchema};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn api_key(key_hash: u8, name: &str) -> NewApiKey {
+    NewApiKey {
+        key_hash: vec![key_hash; 32],
+        name: name.to_string(),
+        rps: Some(100),
+        allowed_methods: vec!["tx_submit".to_string(), "/api/v0.2/accounts".to_string()],
+    }
+}
+
+/// Checks that the revoked keys are not loaded and can't be revoked twice.
+#[db_test]
+async fn store_and_revoke_keys(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_id = ApiKeysSchema(&mut storage)
+        .store_key(&api_key(0x01, "partner-1"))
+        .await?;
+    let second_id = ApiKeysSchema(&mut storage)
+        .store_key(&api_key(0x02, "partner-2"))
+        .await?;
+
+    let keys = ApiKeysSchema(&mut storage).load_active_keys().await?;
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].id, first_id);
+    assert_eq!(keys[0].key_hash, vec![0x01; 32]);
+    assert_eq!(keys[0].rps, Some(100));
+    assert_eq!(keys[0].allowed_methods, api_key(0x01, "").allowed_methods);
+    assert!(keys[0].revoked_at.is_none());
+
+    let key = ApiKeysSchema(&mut storage)
+        .load_active_key_by_hash(&[0x02; 32])
+        .await?
+        .expect("Key is stored");
+    assert_eq!(key.id, second_id);
+
+    assert!(ApiKeysSchema(&mut storage).revoke_key(first_id).await?);
+    assert!(!ApiKeysSchema(&mut storage).revoke_key(first_id).await?);
+    assert!(
+        !ApiKeysSchema(&mut storage)
+            .revoke_key(second_id + 1)
+            .await?
+    );
+
+    let keys = ApiKeysSchema(&mut storage).load_active_keys().await?;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].id, second_id);
+    assert_eq!(keys[0].name, "partner-2");
+    assert!(ApiKeysSchema(&mut storage)
+        .load_active_key_by_hash(&[0x01; 32])
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the same key can't be stored twice.
+#[db_test]
+async fn duplicate_key(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    ApiKeysSchema(&mut storage)
+        .store_key(&api_key(0x01, "partner-1"))
+        .await?;
+    let result = ApiKeysSchema(&mut storage)
+        .store_key(&api_key(0x01, "partner-2"))
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
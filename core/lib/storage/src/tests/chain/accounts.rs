@@ -217,6 +217,53 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the historical state of the account is the same as the one in the committed state
+/// of the whole network at that block.
+#[db_test]
+async fn account_state_at_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let mut rng = create_rng();
+
+    let block_size = 100;
+    let mut accounts_map = AccountMap::default();
+    for block_number in 1..=3 {
+        let block_number = BlockNumber(block_number);
+        let (new_accounts_map, updates) = apply_random_updates(accounts_map, &mut rng);
+        accounts_map = new_accounts_map;
+
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                block_number,
+                AggregatedActionType::CommitBlocks,
+                block_size,
+            ))
+            .await?;
+        StateSchema(&mut storage)
+            .commit_state_update(block_number, &updates, 0)
+            .await?;
+    }
+
+    for block_number in 0..=3 {
+        let block_number = BlockNumber(block_number);
+        let (_, expected_state) = StateSchema(&mut storage)
+            .load_committed_state(Some(block_number))
+            .await?;
+        for account_id in accounts_map.keys() {
+            assert_eq!(
+                AccountSchema(&mut storage)
+                    .account_state_at_block(*account_id, block_number)
+                    .await?,
+                expected_state.get(account_id).cloned(),
+                "Invalid state of the account {} at block {}",
+                account_id,
+                block_number
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[db_test]
 async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let _lock = ACCOUNT_MUTEX.lock().await;
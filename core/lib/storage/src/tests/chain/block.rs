@@ -1115,6 +1115,52 @@ async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Check that only the unverified blocks are reverted.
+#[db_test]
+async fn test_revert_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for block_number in 1..=3 {
+        commit_block(&mut storage, BlockNumber(block_number)).await?;
+    }
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    // The verified block can't be reverted.
+    assert!(BlockSchema(&mut storage)
+        .revert_blocks(BlockNumber(0))
+        .await
+        .is_err());
+    assert!(BlockSchema(&mut storage)
+        .get_block(BlockNumber(1))
+        .await?
+        .is_some());
+
+    BlockSchema(&mut storage)
+        .revert_blocks(BlockNumber(1))
+        .await?;
+
+    assert!(BlockSchema(&mut storage)
+        .get_block(BlockNumber(1))
+        .await?
+        .is_some());
+    assert!(BlockSchema(&mut storage)
+        .get_block(BlockNumber(2))
+        .await?
+        .is_none());
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_last_committed_confirmed_block()
+            .await?,
+        BlockNumber(1)
+    );
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_last_verified_confirmed_block()
+            .await?,
+        BlockNumber(1)
+    );
+
+    Ok(())
+}
+
 /// Check that blocks are removed correctly.
 #[db_test]
 async fn test_remove_pending_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -5,7 +5,7 @@ use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
     block::{Block, ExecutedOperations},
     mempool::SignedTxVariant,
-    priority_ops::FullExit,
+    priority_ops::{Deposit, FullExit},
     tx::{ChangePubKey, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
@@ -92,6 +92,8 @@ fn zksync_txs() -> Vec<SignedZkSyncTx> {
                 tx: tx.clone(),
                 eth_sign_data: Some(gen_eth_sign_data(test_message)),
                 created_at: Utc::now(),
+                batch_hash: None,
+                parties_eth_sign_data: Vec::new(),
             }
         })
         .collect()
@@ -121,6 +123,8 @@ fn gen_transfers(n: usize) -> Vec<SignedZkSyncTx> {
                 tx: ZkSyncTx::Transfer(Box::new(transfer)),
                 eth_sign_data: Some(gen_eth_sign_data(test_message)),
                 created_at: Utc::now(),
+                batch_hash: None,
+                parties_eth_sign_data: Vec::new(),
             }
         })
         .collect()
@@ -138,7 +142,9 @@ fn unwrap_tx(tx: SignedTxVariant) -> SignedZkSyncTx {
 #[db_test]
 async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     // Insert several txs into the mempool schema.
-    let txs = zksync_txs();
+    let mut txs = zksync_txs();
+    // Signatures of the other parties must be stored as well.
+    txs[0].parties_eth_sign_data = vec![None, Some(gen_eth_sign_data("party".to_owned()))];
     for tx in &txs {
         MempoolSchema(&mut storage)
             .insert_tx(&tx.clone())
@@ -160,6 +166,10 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
             tx_from_db.eth_sign_data, tx.eth_sign_data,
             "sign data changed"
         );
+        assert_eq!(
+            tx_from_db.parties_eth_sign_data, tx.parties_eth_sign_data,
+            "parties sign data changed"
+        );
     }
 
     Ok(())
@@ -169,7 +179,11 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 #[db_test]
 async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     // Insert several txs into the mempool schema.
-    let txs = gen_transfers(10);
+    let mut txs = gen_transfers(10);
+    let batch_hash = H256::repeat_byte(0x11);
+    for tx in &mut txs[2..4] {
+        tx.batch_hash = Some(batch_hash);
+    }
     let alone_txs_1 = &txs[0..2];
     let batch_1 = &txs[2..4];
     let batch_2 = &txs[4..6];
@@ -211,11 +225,17 @@ async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     assert!(matches!(txs_from_db[1], SignedTxVariant::Tx(_)));
     // Try to load the batches with the signature.
     match &txs_from_db[2] {
-        SignedTxVariant::Batch(batch) => assert_eq!(batch.eth_signatures, batch_1_signature),
+        SignedTxVariant::Batch(batch) => {
+            assert_eq!(batch.eth_signatures, batch_1_signature);
+            assert!(batch.txs.iter().all(|tx| tx.batch_hash == Some(batch_hash)));
+        }
         SignedTxVariant::Tx(_) => panic!("expected to load batch of transactions 1"),
     };
     match &txs_from_db[3] {
-        SignedTxVariant::Batch(batch) => assert_eq!(batch.eth_signatures, batch_2_signatures),
+        SignedTxVariant::Batch(batch) => {
+            assert_eq!(batch.eth_signatures, batch_2_signatures);
+            assert!(batch.txs.iter().all(|tx| tx.batch_hash.is_none()));
+        }
         SignedTxVariant::Tx(_) => panic!("expected to load batch of transactions 2"),
     };
     assert!(matches!(txs_from_db[4], SignedTxVariant::Tx(_)));
@@ -225,6 +245,67 @@ async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the pending transactions are loaded by the account and nonce,
+/// and the transactions of the batches are skipped.
+#[db_test]
+async fn load_txs_by_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let change_pubkey = zksync_txs().pop().unwrap();
+    for tx in txs[..2].iter().chain(Some(&change_pubkey)) {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    MempoolSchema(&mut storage)
+        .insert_batch(&txs[2..], Vec::new())
+        .await?;
+
+    let loaded = MempoolSchema(&mut storage)
+        .load_txs_by_nonce(AccountId(0), Nonce(10))
+        .await?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].hash(), txs[0].hash());
+    let loaded = MempoolSchema(&mut storage)
+        .load_txs_by_nonce(AccountId(123), Nonce(13))
+        .await?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].hash(), change_pubkey.hash());
+
+    assert!(MempoolSchema(&mut storage)
+        .load_txs_by_nonce(AccountId(0), Nonce(11))
+        .await?
+        .is_empty());
+    assert!(MempoolSchema(&mut storage)
+        .load_txs_by_nonce(AccountId(2), Nonce(10))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the pending transactions of the account are counted along with
+/// the transactions of the batches.
+#[db_test]
+async fn get_account_txs_count(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let change_pubkey = zksync_txs().pop().unwrap();
+    for tx in txs[..2].iter().chain(Some(&change_pubkey)) {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    MempoolSchema(&mut storage)
+        .insert_batch(&txs[2..], Vec::new())
+        .await?;
+
+    let mempool_schema = &mut MempoolSchema(&mut storage);
+    assert_eq!(mempool_schema.get_account_txs_count(AccountId(0)).await?, 1);
+    assert_eq!(mempool_schema.get_account_txs_count(AccountId(2)).await?, 1);
+    assert_eq!(
+        mempool_schema.get_account_txs_count(AccountId(123)).await?,
+        1
+    );
+    assert_eq!(mempool_schema.get_account_txs_count(AccountId(5)).await?, 0);
+
+    Ok(())
+}
+
 /// Checks that removed txs won't appear on the next load.
 #[db_test]
 async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -286,6 +367,7 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        batch_hash: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
     };
@@ -512,3 +594,171 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the priority operations from the orphaned Ethereum blocks are removed from the mempool.
+#[db_test]
+async fn remove_priority_ops_after_eth_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let ops: Vec<_> = (0..4)
+        .map(|serial_id| PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::FullExit(FullExit {
+                account_id: AccountId(serial_id as u32),
+                eth_address: Address::random(),
+                token: TokenId(0),
+                is_legacy: false,
+            }),
+            deadline_block: 100,
+            eth_hash: H256::random(),
+            eth_block: 10 + serial_id,
+            eth_block_index: Some(0),
+        })
+        .collect();
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&ops, true)
+        .await?;
+
+    MempoolSchema(&mut storage)
+        .remove_priority_ops_after_eth_block(11)
+        .await?;
+
+    let remaining: Vec<_> = MempoolSchema(&mut storage)
+        .get_confirmed_priority_ops()
+        .await?
+        .into_iter()
+        .map(|op| op.serial_id)
+        .collect();
+    assert_eq!(remaining, vec![0, 1]);
+    Ok(())
+}
+
+/// Checks that the earliest deadline is found for the priority operations in the mempool
+/// and in the blocks.
+#[db_test]
+async fn min_priority_op_deadline(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let full_exit = FullExit {
+        account_id: AccountId(0),
+        eth_address: Address::zero(),
+        token: TokenId(0),
+        is_legacy: false,
+    };
+    let priority_op = |serial_id: u64, deadline_block: u64| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::FullExit(full_exit.clone()),
+        deadline_block,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: Some(serial_id),
+    };
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_min_priority_op_deadline()
+            .await?,
+        None
+    );
+
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(0, 100), priority_op(1, 110)], true)
+        .await?;
+    // Unconfirmed operations are not processed yet, so they're ignored.
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op(2, 50)], false)
+        .await?;
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_min_priority_op_deadline()
+            .await?,
+        Some(100)
+    );
+
+    // Executed operation is removed from the mempool.
+    let executed_op = ExecutedPriorityOp {
+        priority_op: priority_op(0, 100),
+        op: ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: full_exit.clone(),
+            withdraw_amount: None,
+            creator_account_id: None,
+            creator_address: None,
+            serial_id: None,
+            content_hash: None,
+        })),
+        block_index: 0,
+        created_at: Utc::now(),
+    };
+    OperationsSchema(&mut storage)
+        .store_executed_priority_op(NewExecutedPriorityOperation::prepare_stored_priority_op(
+            executed_op,
+            BlockNumber(5),
+        ))
+        .await?;
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_min_priority_op_deadline()
+            .await?,
+        Some(110)
+    );
+    assert_eq!(
+        OperationsSchema(&mut storage)
+            .get_min_priority_op_deadline_after_block(BlockNumber(4))
+            .await?,
+        Some(100)
+    );
+    assert_eq!(
+        OperationsSchema(&mut storage)
+            .get_min_priority_op_deadline_after_block(BlockNumber(5))
+            .await?,
+        None
+    );
+    Ok(())
+}
+
+/// Checks that the pending deposits are loaded for both the sender and the recipient.
+#[db_test]
+async fn pending_deposits_for_l1_address(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (sender, recipient) = (Address::random(), Address::random());
+    let deposit = |serial_id, from, to| PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from,
+            token: TokenId(0),
+            amount: 100u32.into(),
+            to,
+        }),
+        deadline_block: 100,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: Some(serial_id),
+    };
+    let full_exit = PriorityOp {
+        serial_id: 3,
+        data: ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(1),
+            eth_address: sender,
+            token: TokenId(0),
+            is_legacy: false,
+        }),
+        deadline_block: 100,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: Some(3),
+    };
+    let ops = vec![
+        deposit(0, sender, recipient),
+        deposit(1, sender, sender),
+        deposit(2, recipient, recipient),
+        full_exit,
+    ];
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&ops, false)
+        .await?;
+
+    for (address, expected) in [(sender, vec![0, 1]), (recipient, vec![0, 2])] {
+        let serial_ids: Vec<_> = MempoolSchema(&mut storage)
+            .get_pending_deposits_for_l1_address(address)
+            .await?
+            .into_iter()
+            .map(|op| op.serial_id)
+            .collect();
+        assert_eq!(serial_ids, expected);
+    }
+    Ok(())
+}
@@ -64,6 +64,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: Some(10),
+        batch_hash: Some(vec![0x11; 32]),
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
     };
@@ -92,6 +93,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         executed_tx.primary_account_address
     );
     assert_eq!(stored_operation.batch_id, executed_tx.batch_id);
+    assert_eq!(stored_operation.batch_hash, executed_tx.batch_hash);
 
     Ok(())
 }
@@ -159,6 +161,7 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        batch_hash: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
     };
@@ -234,6 +237,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        batch_hash: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
     };
@@ -314,6 +318,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
         created_at: timestamp_1,
         eth_sign_data: None,
         batch_id: None,
+        batch_hash: None,
         affected_accounts: vec![Address::zero().as_bytes().to_vec()],
         used_tokens: vec![0],
     };
@@ -369,7 +374,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 4);
     assert_eq!(count_tx_filters, 4);
@@ -388,7 +393,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 2);
     assert_eq!(count_tx_filters, 2);
@@ -406,7 +411,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
@@ -419,7 +424,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
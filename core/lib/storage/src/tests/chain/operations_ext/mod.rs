@@ -3,13 +3,18 @@ use std::collections::HashMap;
 // External imports
 // Workspace imports
 use zksync_api_types::v02::{
-    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
+    account::BalanceChangeKind,
+    pagination::{
+        AccountBalanceHistoryRequest, AccountTxsFilters, AccountTxsRequest, ApiEither,
+        PaginationDirection, PaginationQuery,
+    },
     transaction::{Receipt, TxInBlockStatus},
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
+    event::transaction::TransactionType,
     tx::TxHash,
     AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
 };
@@ -386,6 +391,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -514,6 +520,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                     tx_hash: ApiEither::from(request.tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: request.limit,
                 direction: request.direction,
@@ -533,6 +540,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(1, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -551,6 +559,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 2,
             direction: PaginationDirection::Newer,
@@ -570,6 +579,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -585,6 +595,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -598,6 +609,82 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
     Ok(())
 }
 
+/// Checks that the balance history of the account contains only the operations
+/// changing its balances, in the order of their execution.
+#[db_test]
+async fn get_account_balance_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let from = &setup.from_zksync_account;
+    let (address, account_id) = (from.address, from.get_account_id());
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_balance_history(&PaginationQuery {
+            from: AccountBalanceHistoryRequest {
+                address,
+                account_id,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
+            },
+            limit: 10,
+            direction: PaginationDirection::Newer,
+        })
+        .await?
+        .unwrap();
+
+    // Close, change pubkey and mint NFT operations have zero fees, so they're skipped.
+    let expected_history = vec![
+        (setup.get_tx_hash(0, 0), vec![BalanceChangeKind::Deposit]),
+        (
+            setup.get_tx_hash(0, 1),
+            vec![BalanceChangeKind::TransferOut],
+        ),
+        (
+            setup.get_tx_hash(0, 2),
+            vec![BalanceChangeKind::TransferOut],
+        ),
+        (setup.get_tx_hash(0, 5), vec![BalanceChangeKind::Withdrawal]),
+        (setup.get_tx_hash(0, 7), vec![BalanceChangeKind::Withdrawal]),
+        (
+            setup.get_tx_hash(0, 8),
+            vec![BalanceChangeKind::SwapOut, BalanceChangeKind::SwapIn],
+        ),
+        (setup.get_tx_hash(0, 9), vec![BalanceChangeKind::FullExit]),
+    ];
+    let actual_history: Vec<_> = history
+        .iter()
+        .map(|item| {
+            let kinds = item.changes.iter().map(|change| change.kind).collect();
+            (item.tx_hash, kinds)
+        })
+        .collect();
+    assert_eq!(actual_history, expected_history);
+    assert!(history
+        .iter()
+        .all(|item| item.status == TxInBlockStatus::Committed));
+
+    // The account which doesn't exist has no history.
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_balance_history(&PaginationQuery {
+            from: AccountBalanceHistoryRequest {
+                address,
+                account_id: None,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
+            },
+            limit: 10,
+            direction: PaginationDirection::Newer,
+        })
+        .await?;
+    assert_eq!(history, Some(Vec::new()));
+
+    Ok(())
+}
+
 /// Test `get_tx_created_at_and_block_number` method
 #[db_test]
 async fn get_tx_sequnecner_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -792,13 +879,80 @@ async fn account_transactions_count(mut storage: StorageProcessor<'_>) -> QueryR
     let count_after_saving = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(setup.from_zksync_account.address, None, None)
+        .get_account_transactions_count(
+            setup.from_zksync_account.address,
+            None,
+            None,
+            &Default::default(),
+        )
         .await?;
     assert_eq!(count_after_saving, 10);
 
     Ok(())
 }
 
+/// Checks that the account transactions history is filtered by the operation type.
+#[db_test]
+async fn account_transactions_filtered_by_type(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let from = setup.from_zksync_account.address;
+    let filters = AccountTxsFilters {
+        tx_types: vec![TransactionType::Transfer, TransactionType::Deposit],
+        ..Default::default()
+    };
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions(&PaginationQuery {
+            from: AccountTxsRequest {
+                address: from,
+                tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
+                token: None,
+                second_address: None,
+                filters: filters.clone(),
+            },
+            limit: 10,
+            direction: PaginationDirection::Older,
+        })
+        .await?
+        .unwrap();
+    let tx_hashes: Vec<TxHash> = txs.into_iter().map(|tx| tx.tx_hash).collect();
+    assert_eq!(
+        tx_hashes,
+        vec![
+            setup.get_tx_hash(0, 2),
+            setup.get_tx_hash(0, 1),
+            setup.get_tx_hash(0, 0),
+        ]
+    );
+
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(from, None, None, &filters)
+        .await?;
+    assert_eq!(count, 3);
+
+    // No transactions have been created after the commit.
+    let filters = AccountTxsFilters {
+        from_date: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+        ..Default::default()
+    };
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_count(from, None, None, &filters)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
 /// Test `get_account_last_tx_hash` method
 #[db_test]
 async fn account_last_tx_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -940,6 +1094,28 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         }
     }
 
+    // Test receipts loaded in one query.
+    let receipts = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipts_api_v02(&[
+            TxHash::from_slice(eth_hash.as_bytes()).unwrap(),
+            setup.get_tx_hash(0, 2),
+            TxHash::from_slice(&[0xDE; 32]).unwrap(),
+            tx.hash(),
+        ])
+        .await?;
+    assert_eq!(receipts.len(), 4);
+    assert!(matches!(&receipts[0], Some(Receipt::L1(receipt)) if receipt.id == expected_id));
+    assert!(
+        matches!(&receipts[1], Some(Receipt::L2(receipt)) if receipt.tx_hash == setup.get_tx_hash(0, 2))
+    );
+    assert!(receipts[2].is_none());
+    assert!(matches!(
+        &receipts[3],
+        Some(Receipt::L2(receipt)) if receipt.tx_hash == tx.hash() && receipt.rollup_block.is_none()
+    ));
+
     Ok(())
 }
 
@@ -1234,6 +1410,7 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
                     tx_hash: ApiEither::from(tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: 1,
                 direction: PaginationDirection::Older,
@@ -217,6 +217,20 @@ async fn state_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     // Apply diff with uncertain end target.
     check_diff_applying(&mut storage, BlockNumber(1), None).await?;
 
+    // Updates grouped by the blocks are the same as the ones loaded for each block.
+    let blocks_diff = StateSchema(&mut storage)
+        .load_blocks_state_diff(BlockNumber(2), BlockNumber(4))
+        .await?;
+    let mut expected_diff = Vec::new();
+    for block_number in 2..=4 {
+        let block_number = BlockNumber(block_number);
+        let updates = StateSchema(&mut storage)
+            .load_state_diff_for_block(block_number)
+            .await?;
+        expected_diff.push((block_number, updates));
+    }
+    assert_eq!(blocks_diff, expected_diff);
+
     Ok(())
 }
 
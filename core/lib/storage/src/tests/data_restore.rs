@@ -16,6 +16,11 @@ async fn last_watched_block(mut storage: StorageProcessor<'_>) -> QueryResult<()
         last_watched_block_number.is_err(),
         "There should be no stored block number in the database"
     );
+    assert!(
+        !DataRestoreSchema(&mut storage)
+            .is_last_watched_block_number_stored()
+            .await?
+    );
 
     // Store the block number.
     DataRestoreSchema(&mut storage)
@@ -28,6 +33,11 @@ async fn last_watched_block(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .await?;
 
     assert_eq!(last_watched_block_number.block_number, "0");
+    assert!(
+        DataRestoreSchema(&mut storage)
+            .is_last_watched_block_number_stored()
+            .await?
+    );
 
     // Repeat save/load with other values.
     DataRestoreSchema(&mut storage)
@@ -5,7 +5,7 @@ use std::str::FromStr;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     ethereum::ETHOperation,
-    BlockNumber, H256, U256,
+    Address, BlockNumber, H256, U256,
 };
 // Local imports
 use crate::test_data::{gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS};
@@ -51,6 +51,7 @@ impl EthereumTxParams {
             op_type,
             op: self.op.clone(),
             nonce: nonce.into(),
+            sender: None,
             last_deadline_block: self.deadline_block,
             last_used_gas_price,
             used_tx_hashes,
@@ -399,3 +400,160 @@ async fn test_update_eth_parameters(mut storage: StorageProcessor<'_>) -> QueryR
 
     Ok(())
 }
+
+/// Checks that the operations sent from the additional operator accounts get the nonces
+/// of these accounts, independent of the main account nonce.
+#[db_test]
+async fn ethereum_sender_nonces(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    let sender = Address::repeat_byte(0x11);
+
+    // Nonce of the account must be initialized first.
+    assert!(EthereumSchema(&mut storage)
+        .get_next_sender_nonce(sender)
+        .await
+        .is_err());
+
+    EthereumSchema(&mut storage)
+        .initialize_sender_nonce(sender, 10)
+        .await?;
+    // Initializing the nonce again doesn't overwrite it.
+    EthereumSchema(&mut storage)
+        .initialize_sender_nonce(sender, 0)
+        .await?;
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let op = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+
+    let params = EthereumTxParams::new("CommitBlocks".into(), op);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx_from_sender(
+            Some(sender),
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+    assert_eq!(response.nonce, 10.into());
+
+    // The main account nonce is not affected.
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 0);
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .get_next_sender_nonce(sender)
+            .await?,
+        11
+    );
+
+    let unconfirmed_operations = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?;
+    assert_eq!(unconfirmed_operations.len(), 1);
+    assert_eq!(unconfirmed_operations[0].sender, Some(sender));
+    assert_eq!(unconfirmed_operations[0].nonce, 10.into());
+
+    Ok(())
+}
+
+/// Checks that the confirmed Ethereum transactions of the block are loaded along with
+/// the Ethereum blocks they're included in.
+#[db_test]
+async fn ethereum_block_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    assert_eq!(
+        EthereumSchema(&mut storage).load_last_eth_block().await?,
+        None
+    );
+
+    let block_number = BlockNumber(1);
+    let mut hashes = Vec::new();
+    for action_type in &[
+        AggregatedActionType::CommitBlocks,
+        AggregatedActionType::ExecuteBlocks,
+    ] {
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                block_number,
+                *action_type,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+        let op = OperationsSchema(&mut storage)
+            .get_aggregated_op_that_affects_block(*action_type, block_number)
+            .await?;
+        let params = EthereumTxParams::new(action_type.to_string(), op);
+        let response = EthereumSchema(&mut storage)
+            .save_new_eth_tx(
+                *action_type,
+                params.op.clone(),
+                params.deadline_block as i64,
+                params.gas_price.clone(),
+                params.raw_tx.clone(),
+            )
+            .await?;
+        EthereumSchema(&mut storage)
+            .add_hash_entry(response.id, &params.hash)
+            .await?;
+        hashes.push(params.hash);
+    }
+
+    // Only the confirmed transactions are loaded.
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&hashes[0])
+        .await?;
+    EthereumSchema(&mut storage)
+        .save_eth_tx_block(&hashes[0], 10)
+        .await?;
+    let txs = EthereumSchema(&mut storage)
+        .load_block_eth_txs(block_number)
+        .await?;
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].action_type, "CommitBlocks");
+    assert_eq!(txs[0].tx_hash, hashes[0].as_bytes());
+    assert_eq!(txs[0].eth_block, Some(10));
+
+    // The block of the transaction may be unknown.
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&hashes[1])
+        .await?;
+    let txs = EthereumSchema(&mut storage)
+        .load_block_eth_txs(block_number)
+        .await?;
+    assert_eq!(txs.len(), 2);
+    let execute_tx = txs
+        .iter()
+        .find(|tx| tx.action_type == "ExecuteBlocks")
+        .unwrap();
+    assert_eq!(execute_tx.eth_block, None);
+    assert!(EthereumSchema(&mut storage)
+        .load_block_eth_txs(BlockNumber(2))
+        .await?
+        .is_empty());
+
+    EthereumSchema(&mut storage)
+        .update_last_eth_block(15)
+        .await?;
+    EthereumSchema(&mut storage)
+        .update_last_eth_block(16)
+        .await?;
+    assert_eq!(
+        EthereumSchema(&mut storage).load_last_eth_block().await?,
+        Some(16)
+    );
+
+    Ok(())
+}
@@ -331,3 +331,44 @@ async fn test_account_events(mut storage: StorageProcessor<'_>) -> QueryResult<(
             && check_account_event(event, AccountStateChangeStatus::Finalized)));
     Ok(())
 }
+
+/// Checks that the events published to every broker are tracked separately.
+#[db_test]
+async fn test_published_event_offsets(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        storage
+            .event_schema()
+            .get_last_published_event_id("kafka")
+            .await?,
+        None
+    );
+
+    storage
+        .event_schema()
+        .update_last_published_event_id("kafka", EventId(5))
+        .await?;
+    storage
+        .event_schema()
+        .update_last_published_event_id("kafka", EventId(7))
+        .await?;
+    storage
+        .event_schema()
+        .update_last_published_event_id("nats", EventId(3))
+        .await?;
+
+    assert_eq!(
+        storage
+            .event_schema()
+            .get_last_published_event_id("kafka")
+            .await?,
+        Some(EventId(7))
+    );
+    assert_eq!(
+        storage
+            .event_schema()
+            .get_last_published_event_id("nats")
+            .await?,
+        Some(EventId(3))
+    );
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::BigUint;
+// Workspace imports
+use zksync_types::{
+    fast_withdrawals::{FastWithdrawalRequest, LiquidityProvider, SaveFastWithdrawalQuery},
+    tx::TxHash,
+    Address, TokenId,
+};
+// Local imports
+use crate::fast_withdrawals::FastWithdrawalsSchema;
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the providers are registered per token and their terms can be updated.
+#[db_test]
+async fn liquidity_providers(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut provider = LiquidityProvider {
+        address: Address::repeat_byte(0x01),
+        token: TokenId(1),
+        max_amount: BigUint::from(1_000u32),
+        fee_bps: 10,
+    };
+    FastWithdrawalsSchema(&mut storage)
+        .add_provider(&provider)
+        .await?;
+    FastWithdrawalsSchema(&mut storage)
+        .add_provider(&LiquidityProvider {
+            token: TokenId(2),
+            ..provider.clone()
+        })
+        .await?;
+    provider.fee_bps = 20;
+    FastWithdrawalsSchema(&mut storage)
+        .add_provider(&provider)
+        .await?;
+
+    assert_eq!(
+        FastWithdrawalsSchema(&mut storage)
+            .load_token_providers(TokenId(1))
+            .await?,
+        vec![provider.clone()]
+    );
+    assert_eq!(
+        FastWithdrawalsSchema(&mut storage)
+            .load_providers()
+            .await?
+            .len(),
+        2
+    );
+
+    assert!(
+        FastWithdrawalsSchema(&mut storage)
+            .remove_provider(provider.address, TokenId(1))
+            .await?
+    );
+    assert!(
+        !FastWithdrawalsSchema(&mut storage)
+            .remove_provider(provider.address, TokenId(1))
+            .await?
+    );
+    assert!(FastWithdrawalsSchema(&mut storage)
+        .load_token_providers(TokenId(1))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the matched withdrawal is pending until it's handed off or expired.
+#[db_test]
+async fn withdrawal_hand_off(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let (account, provider) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let query = SaveFastWithdrawalQuery {
+        request: FastWithdrawalRequest {
+            account,
+            recipient: Address::repeat_byte(0x03),
+            token: TokenId(1),
+            amount: BigUint::from(1_000u32),
+        },
+        provider,
+        fronted_amount: BigUint::from(999u32),
+        created_at: now,
+        valid_until: now + Duration::minutes(10),
+    };
+    let withdrawal = FastWithdrawalsSchema(&mut storage)
+        .store_withdrawal(&query)
+        .await?;
+    assert_eq!(withdrawal.amount, query.request.amount);
+    assert_eq!(withdrawal.fronted_amount, query.fronted_amount);
+    assert_eq!(withdrawal.tx_hash, None);
+
+    let pending = FastWithdrawalsSchema(&mut storage)
+        .load_pending_withdrawals(account, now)
+        .await?;
+    assert_eq!(pending, vec![withdrawal.clone()]);
+    // Match expires if the withdrawal is not submitted in time.
+    assert!(FastWithdrawalsSchema(&mut storage)
+        .load_pending_withdrawals(account, now + Duration::minutes(11))
+        .await?
+        .is_empty());
+
+    let tx_hash = TxHash::from_slice(&[0xab; 32]).unwrap();
+    assert!(
+        FastWithdrawalsSchema(&mut storage)
+            .set_withdrawal_tx_hash(withdrawal.id, tx_hash)
+            .await?
+    );
+    // Withdrawal can only be handed off once.
+    assert!(
+        !FastWithdrawalsSchema(&mut storage)
+            .set_withdrawal_tx_hash(withdrawal.id, TxHash::from_slice(&[0xcd; 32]).unwrap())
+            .await?
+    );
+    assert!(FastWithdrawalsSchema(&mut storage)
+        .load_pending_withdrawals(account, now)
+        .await?
+        .is_empty());
+
+    let handed_off = FastWithdrawalsSchema(&mut storage)
+        .load_provider_withdrawals(provider, 10)
+        .await?;
+    assert_eq!(handed_off.len(), 1);
+    assert_eq!(handed_off[0].tx_hash, Some(tx_hash));
+    assert_eq!(
+        FastWithdrawalsSchema(&mut storage)
+            .get_withdrawal(withdrawal.id)
+            .await?,
+        Some(handed_off[0].clone())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{AccountId, TokenId};
+// Local imports
+use crate::fee_policies::{records::NewFeePromotion, FeePoliciesSchema};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the largest of the token subsidy and the active promotions is used as the discount.
+#[db_test]
+async fn fee_discount(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let (token, other_token) = (TokenId(1), TokenId(2));
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(token, now)
+            .await?,
+        0
+    );
+
+    FeePoliciesSchema(&mut storage)
+        .set_token_subsidy(token, 20)
+        .await?;
+    // Promotion of all the tokens which is active now.
+    FeePoliciesSchema(&mut storage)
+        .add_promotion(&NewFeePromotion {
+            token_id: None,
+            discount_percent: 10,
+            starts_at: now - Duration::hours(1),
+            ends_at: now + Duration::hours(1),
+        })
+        .await?;
+    // Promotion of the token which hasn't started yet.
+    let future_promotion = FeePoliciesSchema(&mut storage)
+        .add_promotion(&NewFeePromotion {
+            token_id: Some(*token as i32),
+            discount_percent: 50,
+            starts_at: now + Duration::hours(1),
+            ends_at: now + Duration::hours(2),
+        })
+        .await?;
+
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(token, now)
+            .await?,
+        20
+    );
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(other_token, now)
+            .await?,
+        10
+    );
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(token, now + Duration::minutes(90))
+            .await?,
+        50
+    );
+    // The discount of all the tokens has ended by then.
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(other_token, now + Duration::minutes(90))
+            .await?,
+        0
+    );
+
+    let promotions = FeePoliciesSchema(&mut storage)
+        .load_promotions(now + Duration::minutes(90))
+        .await?;
+    assert_eq!(promotions.len(), 1);
+    assert_eq!(promotions[0].id, future_promotion);
+
+    assert!(
+        FeePoliciesSchema(&mut storage)
+            .remove_promotion(future_promotion)
+            .await?
+    );
+    assert!(
+        FeePoliciesSchema(&mut storage)
+            .remove_token_subsidy(token)
+            .await?
+    );
+    assert!(
+        !FeePoliciesSchema(&mut storage)
+            .remove_token_subsidy(token)
+            .await?
+    );
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_fee_discount_percent(token, now)
+            .await?,
+        10
+    );
+
+    Ok(())
+}
+
+/// Checks that the accounts are added to and removed from the fee-free list.
+#[db_test]
+async fn fee_free_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (market_maker, user) = (AccountId(1), AccountId(2));
+
+    FeePoliciesSchema(&mut storage)
+        .add_fee_free_account(market_maker, None)
+        .await?;
+    // Adding the account once again updates the reason.
+    FeePoliciesSchema(&mut storage)
+        .add_fee_free_account(market_maker, Some("market maker"))
+        .await?;
+
+    assert!(
+        FeePoliciesSchema(&mut storage)
+            .is_fee_free_account(market_maker)
+            .await?
+    );
+    assert!(
+        !FeePoliciesSchema(&mut storage)
+            .is_fee_free_account(user)
+            .await?
+    );
+    let accounts = FeePoliciesSchema(&mut storage)
+        .load_fee_free_accounts()
+        .await?;
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].reason.as_deref(), Some("market maker"));
+
+    assert!(
+        FeePoliciesSchema(&mut storage)
+            .remove_fee_free_account(market_maker)
+            .await?
+    );
+    assert!(
+        !FeePoliciesSchema(&mut storage)
+            .is_fee_free_account(market_maker)
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks that the fee acceptability of the tokens is set, overridden and removed.
+#[db_test]
+async fn token_fee_acceptability(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (illiquid_token, other_token) = (TokenId(1), TokenId(2));
+
+    FeePoliciesSchema(&mut storage)
+        .set_token_fee_acceptability(illiquid_token, true)
+        .await?;
+    // Setting the acceptability once again overrides it.
+    FeePoliciesSchema(&mut storage)
+        .set_token_fee_acceptability(illiquid_token, false)
+        .await?;
+
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_token_fee_acceptability(illiquid_token)
+            .await?,
+        Some(false)
+    );
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_token_fee_acceptability(other_token)
+            .await?,
+        None
+    );
+    let tokens = FeePoliciesSchema(&mut storage)
+        .load_token_fee_acceptability()
+        .await?;
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].token_id, *illiquid_token as i32);
+
+    assert!(
+        FeePoliciesSchema(&mut storage)
+            .remove_token_fee_acceptability(illiquid_token)
+            .await?
+    );
+    assert_eq!(
+        FeePoliciesSchema(&mut storage)
+            .get_token_fee_acceptability(illiquid_token)
+            .await?,
+        None
+    );
+
+    Ok(())
+}
@@ -2,7 +2,10 @@ use sqlx::types::BigDecimal;
 use zksync_types::TokenId;
 
 use crate::tests::db_test;
-use crate::{misc::records::Subsidy, misc::MiscSchema};
+use crate::{
+    misc::records::{EthWatchBackfillProgress, Subsidy},
+    misc::MiscSchema,
+};
 use crate::{QueryResult, StorageProcessor};
 
 fn get_subsidy(name: String, value: u64) -> Subsidy {
@@ -54,3 +57,44 @@ async fn stored_subsidy(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the Ethereum watcher backfill progress is overwritten by the subsequent updates.
+#[db_test]
+async fn stored_eth_watch_backfill_progress(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(MiscSchema(&mut storage)
+        .load_eth_watch_backfill_progress()
+        .await?
+        .is_none());
+
+    for &last_processed_block in &[100, 200] {
+        let progress = EthWatchBackfillProgress {
+            last_processed_block,
+            next_priority_op_id: last_processed_block / 10,
+        };
+        MiscSchema(&mut storage)
+            .store_eth_watch_backfill_progress(progress)
+            .await?;
+        let stored = MiscSchema(&mut storage)
+            .load_eth_watch_backfill_progress()
+            .await?;
+        assert_eq!(stored, Some(progress));
+    }
+    Ok(())
+}
+
+/// Checks that the drain mode is disabled by default and can be toggled.
+#[db_test]
+async fn tx_intake_drain(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(!MiscSchema(&mut storage).is_tx_intake_drained().await?);
+
+    for &drained in &[true, false] {
+        MiscSchema(&mut storage)
+            .set_tx_intake_drained(drained)
+            .await?;
+        assert_eq!(
+            MiscSchema(&mut storage).is_tx_intake_drained().await?,
+            drained
+        );
+    }
+    Ok(())
+}
@@ -23,15 +23,26 @@ use tokio::sync::Mutex;
 // Workspace imports
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 
+mod address_lists;
+mod api_keys;
 pub(crate) mod chain;
 mod config;
 mod data_restore;
 mod ethereum;
 mod event;
+mod fast_withdrawals;
+mod fee_policies;
 mod forced_exit_requests;
 mod misc;
+mod network_stats;
+mod operator_costs;
 mod prover;
+mod pruning;
+mod snapshot;
 mod tokens;
+mod tx_rejections;
+mod webhooks;
+mod withdrawal_limits;
 
 pub use db_test_macro::test as db_test;
 
@@ -0,0 +1,123 @@
+// External imports
+use chrono::Utc;
+use num::BigUint;
+use serde_json::json;
+// Workspace imports
+use zksync_types::{BlockNumber, TokenId};
+// Local imports
+use crate::{
+    chain::operations::{records::NewExecutedTransaction, OperationsSchema},
+    network_stats::NetworkStatsSchema,
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+fn executed_tx(
+    block_number: i64,
+    tx_hash: Vec<u8>,
+    from_account: Vec<u8>,
+    tx: serde_json::Value,
+) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash,
+        tx,
+        operation: Default::default(),
+        from_account,
+        to_account: None,
+        success: true,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        batch_hash: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
+/// Checks that the statistics are aggregated incrementally, and every block only once.
+#[db_test]
+async fn aggregate_network_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let today = Utc::now().date().naive_utc();
+    assert_eq!(
+        NetworkStatsSchema(&mut storage)
+            .load_last_aggregated_block()
+            .await?,
+        None
+    );
+
+    let txs = vec![
+        executed_tx(
+            1,
+            vec![0x01; 32],
+            vec![0xaa; 20],
+            json!({ "type": "Transfer", "token": 0, "fee": "10" }),
+        ),
+        executed_tx(
+            1,
+            vec![0x02; 32],
+            vec![0xbb; 20],
+            json!({ "type": "ChangePubKey", "feeToken": 0, "fee": "20" }),
+        ),
+        executed_tx(
+            2,
+            vec![0x03; 32],
+            vec![0xaa; 20],
+            json!({ "type": "Transfer", "token": 1, "fee": "0" }),
+        ),
+    ];
+    for tx in txs {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+
+    assert!(
+        NetworkStatsSchema(&mut storage)
+            .aggregate_blocks(BlockNumber(1))
+            .await?
+    );
+    assert!(
+        NetworkStatsSchema(&mut storage)
+            .aggregate_blocks(BlockNumber(2))
+            .await?
+    );
+    // The blocks are already aggregated.
+    assert!(
+        !NetworkStatsSchema(&mut storage)
+            .aggregate_blocks(BlockNumber(2))
+            .await?
+    );
+
+    let tx_counts = NetworkStatsSchema(&mut storage)
+        .load_daily_tx_counts(today)
+        .await?;
+    assert_eq!(tx_counts.len(), 1);
+    assert_eq!(tx_counts[0].total, 3);
+    assert_eq!(tx_counts[0].by_type["Transfer"], 2);
+    assert_eq!(tx_counts[0].by_type["ChangePubKey"], 1);
+
+    let active_accounts = NetworkStatsSchema(&mut storage)
+        .load_daily_active_accounts(today)
+        .await?;
+    assert_eq!(active_accounts[0].active_accounts, 2);
+
+    // The transaction paying no fee is not counted.
+    let fees = NetworkStatsSchema(&mut storage)
+        .load_daily_fees(today)
+        .await?;
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].token_id, TokenId(0));
+    assert_eq!(fees[0].tx_count, 2);
+    assert_eq!(fees[0].total_fee, BigUint::from(30u32));
+    assert_eq!(fees[0].average_fee, BigUint::from(15u32));
+
+    let value_locked = NetworkStatsSchema(&mut storage)
+        .load_total_value_locked()
+        .await?;
+    assert_eq!(value_locked.last_aggregated_block, Some(BlockNumber(2)));
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// External imports
+use chrono::{NaiveDate, Utc};
+use num::{rational::Ratio, BigUint};
+use serde_json::json;
+// Workspace imports
+use zksync_types::{
+    operator_costs::{Costs, L1CostKind},
+    tokens::TokenPrice,
+    BlockNumber, TokenId, H256,
+};
+// Local imports
+use crate::{
+    chain::operations::{records::NewExecutedTransaction, OperationsSchema},
+    operator_costs::OperatorCostsSchema,
+    test_data::{gen_sample_block, BLOCK_SIZE_CHUNKS},
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+fn executed_tx(
+    block_number: i64,
+    tx_hash: Vec<u8>,
+    tx: serde_json::Value,
+) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash,
+        tx,
+        operation: Default::default(),
+        from_account: vec![0xaa; 20],
+        to_account: None,
+        success: true,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        batch_hash: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
+/// Checks that the L1 costs and the collected fees are summed per block and per day.
+#[db_test]
+async fn operator_costs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_day = NaiveDate::from_ymd(2022, 12, 1);
+    let second_day = first_day.succ();
+    let timestamps = vec![
+        first_day.and_hms(10, 0, 0).timestamp(),
+        first_day.and_hms(20, 0, 0).timestamp(),
+        second_day.and_hms(10, 0, 0).timestamp(),
+    ];
+    for (block_number, timestamp) in (1..).zip(timestamps.iter()) {
+        let mut block = gen_sample_block(BlockNumber(block_number), BLOCK_SIZE_CHUNKS, Vec::new());
+        block.timestamp = *timestamp as u64;
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(block)
+            .await?;
+    }
+
+    let fee = |amount: u32| BigUint::from(amount);
+    let commit_hash = H256::repeat_byte(0x01);
+    let costs = vec![(BlockNumber(1), fee(50)), (BlockNumber(2), fee(50))];
+    OperatorCostsSchema(&mut storage)
+        .save_l1_costs(&commit_hash, L1CostKind::Commit, &costs)
+        .await?;
+    // The costs of the transaction are only stored once.
+    OperatorCostsSchema(&mut storage)
+        .save_l1_costs(&commit_hash, L1CostKind::Commit, &costs)
+        .await?;
+    OperatorCostsSchema(&mut storage)
+        .save_l1_costs(
+            &H256::repeat_byte(0x02),
+            L1CostKind::Withdrawals,
+            &[(BlockNumber(1), fee(30)), (BlockNumber(3), fee(70))],
+        )
+        .await?;
+
+    let txs = vec![
+        executed_tx(
+            1,
+            vec![0x01; 32],
+            json!({ "type": "Transfer", "token": 0, "fee": "10" }),
+        ),
+        executed_tx(
+            1,
+            vec![0x02; 32],
+            json!({ "type": "ChangePubKey", "feeToken": 1, "fee": "20" }),
+        ),
+        executed_tx(
+            3,
+            vec![0x03; 32],
+            json!({ "type": "Withdraw", "token": 0, "fee": "40" }),
+        ),
+        executed_tx(3, vec![0x04; 32], json!({ "type": "Close" })),
+    ];
+    for tx in txs {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+
+    let mut first_block = Costs::default();
+    first_block.add_l1_cost(L1CostKind::Commit, &fee(50));
+    first_block.add_l1_cost(L1CostKind::Withdrawals, &fee(30));
+    first_block.add_collected_fee(TokenId(0), &fee(10));
+    first_block.add_collected_fee(TokenId(1), &fee(20));
+    let mut second_block = Costs::default();
+    second_block.add_l1_cost(L1CostKind::Commit, &fee(50));
+    let mut third_block = Costs::default();
+    third_block.add_l1_cost(L1CostKind::Withdrawals, &fee(70));
+    third_block.add_collected_fee(TokenId(0), &fee(40));
+
+    let blocks = OperatorCostsSchema(&mut storage)
+        .load_block_costs(BlockNumber(2), BlockNumber(10))
+        .await?;
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].block_number, BlockNumber(2));
+    assert_eq!(blocks[0].timestamp, Some(timestamps[1] as u64));
+    assert_eq!(blocks[0].costs, second_block);
+    assert_eq!(blocks[1].costs, third_block);
+
+    let days = OperatorCostsSchema(&mut storage)
+        .load_daily_costs(first_day, second_day)
+        .await?;
+    let mut first_day_costs = first_block;
+    first_day_costs.add_l1_cost(L1CostKind::Commit, &fee(50));
+    assert_eq!(days.len(), 2);
+    assert_eq!((days[0].day, days[0].blocks), (first_day, 2));
+    assert_eq!(days[0].costs, first_day_costs);
+    assert_eq!((days[1].day, days[1].blocks), (second_day, 1));
+    assert_eq!(days[1].costs, third_block);
+
+    Ok(())
+}
+
+/// Checks that the prices are loaded only for the tokens with the known price.
+#[db_test]
+async fn operator_costs_token_prices(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(
+            TokenId(0),
+            TokenPrice {
+                usd_price: Ratio::from(BigUint::from(1_000u32)),
+                last_updated: Utc::now(),
+            },
+        )
+        .await?;
+
+    let prices = OperatorCostsSchema(&mut storage)
+        .load_token_prices(&[TokenId(0), TokenId(1)])
+        .await?;
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[&TokenId(0)].decimals, 18);
+    assert_eq!(
+        prices[&TokenId(0)].usd_price,
+        Ratio::from(BigUint::from(1_000u32))
+    );
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobScheduling, ProverJobStatus, ProverJobType},
     BlockNumber,
 };
 // Local imports
@@ -16,7 +16,7 @@ static MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 async fn get_idle_job_from_queue(storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(storage)
-        .get_idle_prover_job_from_job_queue()
+        .get_idle_prover_job_from_job_queue(&[])
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -81,6 +81,7 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
             job_data.clone(),
             0,
             ProverJobType::SingleProof,
+            Default::default(),
         )
         .await;
     let stored_aggregated_job = ProverSchema(storage)
@@ -90,6 +91,7 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
             job_data,
             1,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await;
 
@@ -143,6 +145,7 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
             Default::default(),
             1,
             ProverJobType::SingleProof,
+            Default::default(),
         )
         .await?;
     ProverSchema(storage)
@@ -152,6 +155,7 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
             Default::default(),
             1,
             ProverJobType::SingleProof,
+            Default::default(),
         )
         .await?;
     ProverSchema(storage)
@@ -161,6 +165,7 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
             Default::default(),
             0,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await?;
 
@@ -312,6 +317,80 @@ async fn test_remove_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult
     Ok(())
 }
 
+/// Checks that the witnesses stored as JSON before the compression was introduced are still loaded.
+#[db_test]
+async fn test_load_uncompressed_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_NUMBER: BlockNumber = BlockNumber(1);
+    storage
+        .chain()
+        .block_schema()
+        .save_full_block(gen_sample_block(BLOCK_NUMBER, 100, Default::default()))
+        .await?;
+
+    let expected = String::from("test");
+    sqlx::query("INSERT INTO block_witness (block, witness) VALUES ($1, $2)")
+        .bind(i64::from(*BLOCK_NUMBER))
+        .bind(serde_json::to_string(&expected).unwrap())
+        .execute(storage.conn())
+        .await?;
+
+    let loaded = storage
+        .prover_schema()
+        .get_witness(BLOCK_NUMBER)
+        .await?
+        .map(|value| serde_json::from_value(value).unwrap());
+    assert_eq!(loaded, Some(expected));
+
+    Ok(())
+}
+
+/// Checks that only the old witnesses of the verified blocks are pruned.
+#[db_test]
+async fn test_remove_old_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                100,
+                Default::default(),
+            ))
+            .await?;
+        let witness = serde_json::to_value(String::from("test")).unwrap();
+        storage
+            .prover_schema()
+            .store_witness(BlockNumber(block_number), witness)
+            .await?;
+    }
+
+    // Witnesses are too recent to be removed.
+    let removed = storage
+        .prover_schema()
+        .remove_old_witnesses(BlockNumber(2), chrono::Duration::days(1))
+        .await?;
+    assert_eq!(removed, 0);
+
+    // Only the witnesses of the verified blocks are removed.
+    let removed = storage
+        .prover_schema()
+        .remove_old_witnesses(BlockNumber(2), chrono::Duration::zero())
+        .await?;
+    assert_eq!(removed, 2);
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(2))
+        .await?
+        .is_none());
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(3))
+        .await?
+        .is_some());
+
+    Ok(())
+}
+
 /// Checks that block proofs are removed correctly.
 #[db_test]
 async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -330,6 +409,7 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
                 job_data.clone(),
                 0,
                 ProverJobType::SingleProof,
+                Default::default(),
             )
             .await?;
         let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
@@ -363,6 +443,7 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
             job_data.clone(),
             1,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
@@ -377,6 +458,7 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
             job_data.clone(),
             1,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
@@ -415,6 +497,7 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
             job_data.clone(),
             1,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await?;
     ProverSchema(&mut storage)
@@ -424,6 +507,7 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
             job_data.clone(),
             1,
             ProverJobType::AggregatedProof,
+            Default::default(),
         )
         .await?;
 
@@ -441,3 +525,127 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the idle jobs are handed out by the priority and the deadline,
+/// and only to the provers supporting the block size.
+#[db_test]
+async fn test_prover_job_scheduling(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    let jobs = [
+        (1, 1, ProverJobType::SingleProof, Some(6), None),
+        (2, 2, ProverJobType::SingleProof, Some(30), Some(100)),
+        (3, 3, ProverJobType::SingleProof, Some(6), Some(50)),
+        (1, 3, ProverJobType::AggregatedProof, None, None),
+    ];
+    for (first_block, last_block, job_type, block_size, deadline_block) in jobs.iter().cloned() {
+        let job_priority = match job_type {
+            ProverJobType::SingleProof => 1,
+            ProverJobType::AggregatedProof => 0,
+        };
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(first_block),
+                BlockNumber(last_block),
+                Default::default(),
+                job_priority,
+                job_type,
+                ProverJobScheduling {
+                    block_size,
+                    deadline_block,
+                },
+            )
+            .await?;
+    }
+
+    let stats = ProverSchema(&mut storage).prover_job_queue_stats().await?;
+    assert_eq!(stats.len(), 3);
+    let small_blocks_stats = stats
+        .iter()
+        .find(|stats| stats.block_size == Some(6))
+        .unwrap();
+    assert_eq!(
+        small_blocks_stats.job_status,
+        ProverJobStatus::Idle.to_number()
+    );
+    assert_eq!(small_blocks_stats.jobs_count, 2);
+    assert_eq!(small_blocks_stats.min_deadline_block, Some(50));
+
+    // The aggregated proof has the highest priority, then the earliest deadline is chosen.
+    let mut handed_out = Vec::new();
+    while let Some(job) = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue(&[6])
+        .await?
+    {
+        handed_out.push((*job.first_block, *job.last_block));
+    }
+    assert_eq!(handed_out, vec![(1, 3), (3, 3), (1, 1)]);
+
+    // The job for the bigger block is only handed out to the prover supporting it.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(*job.first_block, 2);
+
+    Ok(())
+}
+
+/// Checks that the jobs of the silent provers are returned to the queue
+/// and are listed as stuck until they are handed out and proven.
+#[db_test]
+async fn test_stale_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+            Default::default(),
+        )
+        .await?;
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, "prover")
+        .await?;
+
+    // The prover sent the heartbeat recently, so the job stays assigned to it.
+    let heartbeat_timeout = chrono::Duration::hours(1);
+    let stale_jobs = ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(heartbeat_timeout)
+        .await?;
+    assert!(stale_jobs.is_empty());
+    let stuck_jobs = ProverSchema(&mut storage)
+        .load_stuck_prover_jobs(heartbeat_timeout)
+        .await?;
+    assert!(stuck_jobs.is_empty());
+
+    // The heartbeat lapsed: the job is listed as stuck and then returned to the queue.
+    let heartbeat_timeout = chrono::Duration::zero();
+    let stuck_jobs = ProverSchema(&mut storage)
+        .load_stuck_prover_jobs(heartbeat_timeout)
+        .await?;
+    assert_eq!(stuck_jobs.len(), 1);
+    assert!(stuck_jobs[0].in_progress);
+    assert_eq!(stuck_jobs[0].updated_by, "prover");
+    assert_eq!(stuck_jobs[0].reassignments, 0);
+
+    let stale_jobs = ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(heartbeat_timeout)
+        .await?;
+    assert_eq!(stale_jobs, vec![job.job_id]);
+    let stuck_jobs = ProverSchema(&mut storage)
+        .load_stuck_prover_jobs(chrono::Duration::hours(1))
+        .await?;
+    assert_eq!(stuck_jobs.len(), 1);
+    assert!(!stuck_jobs[0].in_progress);
+    assert_eq!(stuck_jobs[0].reassignments, 1);
+
+    // The job is handed out to another prover.
+    let reassigned_job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(reassigned_job.job_id, job.job_id);
+
+    Ok(())
+}
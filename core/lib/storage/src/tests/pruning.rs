@@ -0,0 +1,78 @@
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::{
+    chain::operations::{records::NewExecutedTransaction, OperationsSchema},
+    pruning::{records::PrunedHistory, PruningSchema},
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+fn executed_tx(block_number: i64, tx_hash: Vec<u8>) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number,
+        tx_hash,
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success: true,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        batch_hash: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+    }
+}
+
+/// Checks that only the history of the blocks up to the given one is pruned, and only once.
+#[db_test]
+async fn prune_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        None
+    );
+
+    let old_tx = executed_tx(1, vec![0x01; 32]);
+    let recent_tx = executed_tx(3, vec![0x03; 32]);
+    OperationsSchema(&mut storage)
+        .store_executed_tx(old_tx.clone())
+        .await?;
+    OperationsSchema(&mut storage)
+        .store_executed_tx(recent_tx.clone())
+        .await?;
+
+    let pruned = PruningSchema(&mut storage)
+        .prune_history(BlockNumber(2))
+        .await?;
+    assert_eq!(pruned.transactions, 1);
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        Some(BlockNumber(2))
+    );
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(&old_tx.tx_hash)
+        .await?
+        .is_none());
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(&recent_tx.tx_hash)
+        .await?
+        .is_some());
+
+    // The history is already pruned up to this block.
+    let pruned = PruningSchema(&mut storage)
+        .prune_history(BlockNumber(1))
+        .await?;
+    assert_eq!(pruned, PrunedHistory::default());
+    assert_eq!(
+        PruningSchema(&mut storage).load_last_pruned_block().await?,
+        Some(BlockNumber(2))
+    );
+
+    Ok(())
+}
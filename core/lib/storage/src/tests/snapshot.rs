@@ -0,0 +1,139 @@
+// External imports
+use num::BigUint;
+// Workspace imports
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_types::{AccountMap, Address, BlockNumber, Token, TokenId, TokenKind, H256, NFT};
+// Local imports
+use crate::{
+    chain::{block::BlockSchema, state::StateSchema},
+    snapshot::{
+        records::{StateSnapshot, SNAPSHOT_FORMAT_VERSION},
+        SnapshotSchema,
+    },
+    test_data::{gen_sample_block, BLOCK_SIZE_CHUNKS},
+    tests::{chain::apply_random_updates, create_rng, db_test},
+    QueryResult, StorageProcessor,
+};
+
+/// Checks that the imported snapshot is exported back unchanged,
+/// and that it can't be imported into a database that already has blocks.
+#[db_test]
+async fn import_and_export_snapshot(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+    let (accounts, _) = apply_random_updates(AccountMap::default(), &mut rng);
+    let mut accounts: Vec<_> = accounts.into_iter().collect();
+    accounts.sort_by_key(|(id, _)| *id);
+
+    let (creator_id, creator) = accounts[0].clone();
+    let nft = NFT::new(
+        TokenId(MIN_NFT_TOKEN_ID),
+        0,
+        creator_id,
+        creator.address,
+        Address::repeat_byte(0x11),
+        None,
+        H256::repeat_byte(0x22),
+    );
+    accounts[0].1.set_balance(nft.id, BigUint::from(1u32));
+
+    let token = Token::new(
+        TokenId(1),
+        Address::repeat_byte(0x01),
+        "ABC",
+        18,
+        TokenKind::ERC20,
+    );
+    let mut block = gen_sample_block(BlockNumber(5), BLOCK_SIZE_CHUNKS, Vec::new());
+    block.processed_priority_ops = (3, 7);
+
+    let snapshot = StateSnapshot {
+        version: SNAPSHOT_FORMAT_VERSION,
+        block: block.clone(),
+        accounts: accounts.clone(),
+        tokens: vec![token.clone()],
+        nfts: vec![nft.clone()],
+    };
+    SnapshotSchema(&mut storage)
+        .import_snapshot(snapshot.clone())
+        .await?;
+
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_last_verified_confirmed_block()
+            .await?,
+        block.block_number
+    );
+    assert_eq!(
+        BlockSchema(&mut storage).next_expected_serial_id().await?,
+        7
+    );
+
+    let exported = SnapshotSchema(&mut storage).export_snapshot().await?;
+    assert_eq!(exported.block.block_number, block.block_number);
+    assert_eq!(exported.block.new_root_hash, block.new_root_hash);
+    assert_eq!(exported.accounts, accounts);
+    assert!(exported.tokens.contains(&token));
+    assert_eq!(exported.nfts, vec![nft]);
+
+    // The database is not empty anymore.
+    assert!(SnapshotSchema(&mut storage)
+        .import_snapshot(snapshot)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+/// Checks that the snapshot imported as the genesis state starts a new network at the block 0,
+/// and that it can't be imported into a database that already has blocks.
+#[db_test]
+async fn import_snapshot_as_genesis(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+    let (accounts, _) = apply_random_updates(AccountMap::default(), &mut rng);
+    let mut accounts: Vec<_> = accounts.into_iter().collect();
+    accounts.sort_by_key(|(id, _)| *id);
+
+    let token = Token::new(
+        TokenId(1),
+        Address::repeat_byte(0x01),
+        "ABC",
+        18,
+        TokenKind::ERC20,
+    );
+    let block = gen_sample_block(BlockNumber(5), BLOCK_SIZE_CHUNKS, Vec::new());
+    let snapshot = StateSnapshot {
+        version: SNAPSHOT_FORMAT_VERSION,
+        block: block.clone(),
+        accounts: accounts.clone(),
+        tokens: vec![token.clone()],
+        nfts: Vec::new(),
+    };
+    SnapshotSchema(&mut storage)
+        .import_snapshot_as_genesis(snapshot.clone())
+        .await?;
+
+    assert_eq!(
+        BlockSchema(&mut storage).get_last_committed_block().await?,
+        BlockNumber(0)
+    );
+    let genesis_block = BlockSchema(&mut storage)
+        .get_block(BlockNumber(0))
+        .await?
+        .expect("Genesis block is not stored");
+    assert_eq!(genesis_block.new_root_hash, block.new_root_hash);
+
+    let (verified_block, verified_accounts) =
+        StateSchema(&mut storage).load_verified_state().await?;
+    assert_eq!(verified_block, BlockNumber(0));
+    let mut verified_accounts: Vec<_> = verified_accounts.into_iter().collect();
+    verified_accounts.sort_by_key(|(id, _)| *id);
+    assert_eq!(verified_accounts, accounts);
+
+    // The database is not empty anymore.
+    assert!(SnapshotSchema(&mut storage)
+        .import_snapshot_as_genesis(snapshot)
+        .await
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// External imports
+use chrono::Duration;
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use crate::tests::db_test;
+use crate::tx_rejections::{records::NewTxRejection, TxRejectionsSchema};
+use crate::{QueryResult, StorageProcessor};
+
+fn rejection(tx_hash: TxHash, account: Address, error_code: &str) -> NewTxRejection {
+    NewTxRejection {
+        tx_hash,
+        account,
+        error_code: error_code.to_string(),
+        error_message: "Eth signature is incorrect".to_string(),
+        recovered_signer: Some(Address::repeat_byte(0xff)),
+    }
+}
+
+/// Checks that the rejections are loaded by the transaction hash and by the account,
+/// starting from the most recent one.
+#[db_test]
+async fn store_and_load_rejections(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let (alice, bob) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    let other_tx_hash = TxHash::from_slice(&[2; 32]).unwrap();
+
+    TxRejectionsSchema(&mut storage)
+        .store_rejections(&[
+            rejection(tx_hash, alice, "INCORRECT_ETH_SIGNATURE"),
+            rejection(other_tx_hash, bob, "INCORRECT_ETH_SIGNATURE"),
+        ])
+        .await?;
+    TxRejectionsSchema(&mut storage)
+        .store_rejections(&[rejection(tx_hash, alice, "SIGNER_MISMATCH")])
+        .await?;
+
+    let rejections = TxRejectionsSchema(&mut storage)
+        .load_by_tx_hash(tx_hash, 10)
+        .await?;
+    let codes: Vec<_> = rejections.iter().map(|r| r.error_code.as_str()).collect();
+    assert_eq!(codes, vec!["SIGNER_MISMATCH", "INCORRECT_ETH_SIGNATURE"]);
+    assert_eq!(
+        rejections[0].recovered_signer.as_deref(),
+        Some(Address::repeat_byte(0xff).as_bytes())
+    );
+
+    let rejections = TxRejectionsSchema(&mut storage)
+        .load_by_account(alice, 1)
+        .await?;
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].error_code, "SIGNER_MISMATCH");
+
+    let rejections = TxRejectionsSchema(&mut storage)
+        .load_by_account(bob, 10)
+        .await?;
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].tx_hash, other_tx_hash.as_ref().to_vec());
+
+    Ok(())
+}
+
+/// Checks that only the outdated rejections are removed.
+#[db_test]
+async fn remove_old_rejections(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account = Address::repeat_byte(0x01);
+    let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    TxRejectionsSchema(&mut storage)
+        .store_rejections(&[rejection(tx_hash, account, "INCORRECT_TX")])
+        .await?;
+
+    TxRejectionsSchema(&mut storage)
+        .remove_old_rejections(Duration::hours(1))
+        .await?;
+    let rejections = TxRejectionsSchema(&mut storage)
+        .load_by_account(account, 10)
+        .await?;
+    assert_eq!(rejections.len(), 1);
+
+    TxRejectionsSchema(&mut storage)
+        .remove_old_rejections(Duration::seconds(-1))
+        .await?;
+    let rejections = TxRejectionsSchema(&mut storage)
+        .load_by_account(account, 10)
+        .await?;
+    assert!(rejections.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+// External imports
+use chrono::{Duration, Utc};
+use serde_json::json;
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use crate::api_keys::{records::NewApiKey, ApiKeysSchema};
+use crate::tests::db_test;
+use crate::webhooks::{
+    records::{NewWebhook, NewWebhookDelivery},
+    WebhooksSchema,
+};
+use crate::{QueryResult, StorageProcessor};
+
+async fn store_api_key(storage: &mut StorageProcessor<'_>, key_hash: u8) -> QueryResult<i64> {
+    ApiKeysSchema(storage)
+        .store_key(&NewApiKey {
+            key_hash: vec![key_hash; 32],
+            name: format!("partner-{}", key_hash),
+            rps: None,
+            allowed_methods: Vec::new(),
+        })
+        .await
+}
+
+fn webhook(api_key_id: i64, url: &str) -> NewWebhook {
+    NewWebhook {
+        api_key_id,
+        url: url.to_string(),
+        secret: "secret".to_string(),
+        event_types: vec!["tx_committed".to_string(), "tx_verified".to_string()],
+        account_id: Some(1),
+    }
+}
+
+fn delivery(webhook_id: i64, event_id: i64) -> NewWebhookDelivery {
+    NewWebhookDelivery {
+        webhook_id,
+        event_id,
+        event_type: "tx_committed".to_string(),
+        payload: json!({ "eventId": event_id }),
+    }
+}
+
+/// Checks that the webhooks are only removed by the API key they are registered with,
+/// and the webhooks of the revoked keys are not active.
+#[db_test]
+async fn register_and_remove_webhooks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_key = store_api_key(&mut storage, 0x01).await?;
+    let second_key = store_api_key(&mut storage, 0x02).await?;
+
+    let first_id = WebhooksSchema(&mut storage)
+        .add_webhook(&webhook(first_key, "https://first.example"))
+        .await?
+        .id;
+    let second_id = WebhooksSchema(&mut storage)
+        .add_webhook(&webhook(second_key, "https://second.example"))
+        .await?
+        .id;
+
+    let webhooks = WebhooksSchema(&mut storage)
+        .load_webhooks_by_api_key(first_key)
+        .await?;
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].id, first_id);
+    assert_eq!(webhooks[0].url, "https://first.example");
+    assert_eq!(webhooks[0].event_types, webhook(0, "").event_types);
+    assert_eq!(webhooks[0].account_id, Some(1));
+    assert_eq!(
+        WebhooksSchema(&mut storage)
+            .load_active_webhooks()
+            .await?
+            .len(),
+        2
+    );
+
+    ApiKeysSchema(&mut storage).revoke_key(second_key).await?;
+    let webhooks = WebhooksSchema(&mut storage).load_active_webhooks().await?;
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].id, first_id);
+
+    assert!(
+        !WebhooksSchema(&mut storage)
+            .remove_webhook(first_id, second_key)
+            .await?
+    );
+    assert!(
+        WebhooksSchema(&mut storage)
+            .remove_webhook(first_id, first_key)
+            .await?
+    );
+    assert!(
+        !WebhooksSchema(&mut storage)
+            .remove_webhook(first_id, first_key)
+            .await?
+    );
+    assert_eq!(
+        WebhooksSchema(&mut storage)
+            .load_webhooks_by_api_key(second_key)
+            .await?[0]
+            .id,
+        second_id
+    );
+
+    Ok(())
+}
+
+/// Checks the delivery state: the processed events cursor, retries and the delivered events.
+#[db_test]
+async fn webhook_deliveries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let api_key_id = store_api_key(&mut storage, 0x01).await?;
+    let webhook_id = WebhooksSchema(&mut storage)
+        .add_webhook(&webhook(api_key_id, "https://example"))
+        .await?
+        .id;
+    assert!(WebhooksSchema(&mut storage)
+        .get_last_processed_event_id()
+        .await?
+        .is_none());
+
+    WebhooksSchema(&mut storage)
+        .store_deliveries(
+            &[delivery(webhook_id, 10), delivery(webhook_id, 11)],
+            EventId(11),
+        )
+        .await?;
+    // Cursor is moved even if none of the events are delivered to the webhooks.
+    WebhooksSchema(&mut storage)
+        .store_deliveries(&[], EventId(15))
+        .await?;
+    assert_eq!(
+        WebhooksSchema(&mut storage)
+            .get_last_processed_event_id()
+            .await?,
+        Some(EventId(15))
+    );
+
+    let pending = WebhooksSchema(&mut storage)
+        .load_pending_deliveries(3, 10)
+        .await?;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].url, "https://example");
+    assert_eq!(pending[0].payload, json!({ "eventId": 10 }));
+    assert_eq!(pending[0].attempts, 0);
+
+    // The first delivery succeeds, the second one is retried later.
+    WebhooksSchema(&mut storage)
+        .mark_delivered(pending[0].id)
+        .await?;
+    WebhooksSchema(&mut storage)
+        .mark_failed(pending[1].id, "timeout", Utc::now() + Duration::hours(1))
+        .await?;
+    assert!(WebhooksSchema(&mut storage)
+        .load_pending_deliveries(3, 10)
+        .await?
+        .is_empty());
+
+    // Deliveries are not retried after the maximum number of attempts.
+    WebhooksSchema(&mut storage)
+        .mark_failed(pending[1].id, "timeout", Utc::now() - Duration::hours(1))
+        .await?;
+    let pending = WebhooksSchema(&mut storage)
+        .load_pending_deliveries(3, 10)
+        .await?;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].attempts, 2);
+    assert!(WebhooksSchema(&mut storage)
+        .load_pending_deliveries(2, 10)
+        .await?
+        .is_empty());
+
+    let deliveries = WebhooksSchema(&mut storage)
+        .load_deliveries(webhook_id, 10)
+        .await?;
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0].event_id, 11);
+    assert_eq!(deliveries[0].last_error.as_deref(), Some("timeout"));
+    assert!(deliveries[0].delivered_at.is_none());
+    assert_eq!(deliveries[1].event_id, 10);
+    assert_eq!(deliveries[1].attempts, 1);
+    assert!(deliveries[1].delivered_at.is_some());
+
+    // Deliveries are removed along with the webhook.
+    WebhooksSchema(&mut storage)
+        .remove_webhook(webhook_id, api_key_id)
+        .await?;
+    assert!(WebhooksSchema(&mut storage)
+        .load_deliveries(webhook_id, 10)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+// External imports
+use chrono::{Duration, Utc};
+use num::{BigUint, Zero};
+// Workspace imports
+use zksync_types::{tx::TxHash, withdrawal_limits::WithdrawalLimits, Address, TokenId};
+// Local imports
+use crate::tests::db_test;
+use crate::withdrawal_limits::WithdrawalLimitsSchema;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the limits are set per token and can be replaced.
+#[db_test]
+async fn withdrawal_limits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut limits = WithdrawalLimits {
+        token: TokenId(1),
+        account_daily_limit: Some(BigUint::from(100u32).into()),
+        daily_volume_limit: None,
+        large_withdrawal_threshold: Some(BigUint::from(50u32).into()),
+        large_withdrawal_delay: 3600,
+    };
+    WithdrawalLimitsSchema(&mut storage)
+        .set_limits(&limits)
+        .await?;
+    limits.daily_volume_limit = Some(BigUint::from(1_000u32).into());
+    WithdrawalLimitsSchema(&mut storage)
+        .set_limits(&limits)
+        .await?;
+
+    assert_eq!(
+        WithdrawalLimitsSchema(&mut storage)
+            .get_limits(TokenId(1))
+            .await?,
+        Some(limits.clone())
+    );
+    assert_eq!(
+        WithdrawalLimitsSchema(&mut storage).load_limits().await?,
+        vec![limits]
+    );
+
+    assert!(
+        WithdrawalLimitsSchema(&mut storage)
+            .remove_limits(TokenId(1))
+            .await?
+    );
+    assert!(
+        !WithdrawalLimitsSchema(&mut storage)
+            .remove_limits(TokenId(1))
+            .await?
+    );
+    assert_eq!(
+        WithdrawalLimitsSchema(&mut storage)
+            .get_limits(TokenId(1))
+            .await?,
+        None
+    );
+
+    Ok(())
+}
+
+/// Checks that the withdrawn amounts are counted per day, token and account.
+#[db_test]
+async fn withdrawal_volumes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let today = Utc::now().date().naive_utc();
+    let yesterday = today.pred();
+    let (account, other) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+
+    assert_eq!(
+        WithdrawalLimitsSchema(&mut storage)
+            .load_volumes(today, TokenId(1), account)
+            .await?,
+        (BigUint::zero(), BigUint::zero())
+    );
+
+    for (day, token, address, amount) in vec![
+        (today, TokenId(1), account, 10u32),
+        (today, TokenId(1), account, 20),
+        (today, TokenId(1), other, 5),
+        (today, TokenId(2), account, 100),
+        (yesterday, TokenId(1), account, 1_000),
+    ] {
+        WithdrawalLimitsSchema(&mut storage)
+            .add_volume(day, token, address, &BigUint::from(amount))
+            .await?;
+    }
+
+    assert_eq!(
+        WithdrawalLimitsSchema(&mut storage)
+            .load_volumes(today, TokenId(1), account)
+            .await?,
+        (BigUint::from(30u32), BigUint::from(35u32))
+    );
+
+    Ok(())
+}
+
+/// Checks that the large withdrawal is delayed since it was first submitted.
+#[db_test]
+async fn delayed_withdrawals(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let tx_hash = TxHash::from_slice(&[0xab; 32]).unwrap();
+    let amount = BigUint::from(1_000u32);
+    // Timestamps are stored with the microsecond precision.
+    let available_at = Utc::now().date().and_hms(0, 0, 0) + Duration::days(1);
+
+    let delayed_until = WithdrawalLimitsSchema(&mut storage)
+        .delay_withdrawal(
+            tx_hash,
+            Address::repeat_byte(0x01),
+            TokenId(1),
+            &amount,
+            available_at,
+        )
+        .await?;
+    assert_eq!(delayed_until, available_at);
+
+    // Resubmission doesn't restart the delay window.
+    let delayed_until = WithdrawalLimitsSchema(&mut storage)
+        .delay_withdrawal(
+            tx_hash,
+            Address::repeat_byte(0x01),
+            TokenId(1),
+            &amount,
+            available_at + Duration::hours(1),
+        )
+        .await?;
+    assert_eq!(delayed_until, available_at);
+
+    Ok(())
+}
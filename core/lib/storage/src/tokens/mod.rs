@@ -16,7 +16,7 @@ use zksync_utils::ratio_to_big_decimal;
 use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
 
 use crate::utils::address_to_stored_string;
-use crate::{QueryResult, StorageProcessor};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
 
 pub mod records;
@@ -95,7 +95,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await
         .map_err(|err| StoreTokenError::Other(err.into()))?;
 
-        metrics::histogram!("sql.token.store_token", start.elapsed());
+        report_query("token.store_token", start);
         Ok(())
     }
 
@@ -121,7 +121,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.store_token", start.elapsed());
+        report_query("token.store_or_update_token", start);
         Ok(())
     }
 
@@ -148,7 +148,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?;
 
         let result = tokens.into_iter().map(Token::from).collect();
-        metrics::histogram!("sql.token.load_tokens_asc", start.elapsed());
+        report_query("token.load_tokens_asc", start);
         Ok(result)
     }
 
@@ -175,7 +175,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?;
 
         let result = tokens.into_iter().map(Token::from).collect();
-        metrics::histogram!("sql.token.load_tokens_desc", start.elapsed());
+        report_query("token.load_tokens_desc", start);
         Ok(result)
     }
 
@@ -226,7 +226,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|nft| (TokenId(nft.token_id as u32), nft.into()))
         .collect();
 
-        metrics::histogram!("sql.token.load_nfts", start.elapsed());
+        report_query("token.load_nfts", start);
         Ok(nfts)
     }
 
@@ -261,7 +261,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             })
             .collect());
 
-        metrics::histogram!("sql.token.load_tokens_by_market_volume", start.elapsed());
+        report_query("token.load_tokens_by_market_volume", start);
         result
     }
 
@@ -295,10 +295,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             result.insert(TokenId(0));
         }
 
-        metrics::histogram!(
-            "sql.token.load_token_ids_that_enabled_for_fees",
-            start.elapsed()
-        );
+        report_query("token.load_token_ids_that_enabled_for_fees", start);
         Ok(result)
     }
 
@@ -314,7 +311,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.token.get_count", start.elapsed());
+        report_query("token.get_count", start);
         Ok(count as u32)
     }
 
@@ -331,7 +328,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|token| token.id)
         .unwrap_or(0);
 
-        metrics::histogram!("sql.token.get_max_erc20_token_id", start.elapsed());
+        report_query("token.get_max_erc20_token_id", start);
         Ok(last_token_id as u32)
     }
 
@@ -348,7 +345,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .map(|token| token.id)
         .unwrap_or(0);
 
-        metrics::histogram!("sql.token.get_max_token_id", start.elapsed());
+        report_query("token.get_max_token_id", start);
         Ok(last_token_id as u32)
     }
 
@@ -367,7 +364,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.token.get_nft", start.elapsed());
+        report_query("token.get_nft", start);
         Ok(db_token.map(|t| t.into()))
     }
 
@@ -397,7 +394,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!("sql.token.get_nft_with_factories", start.elapsed());
+        report_query("token.get_nft_with_factories", start);
         Ok(db_token.map(|t| t.into()))
     }
 
@@ -453,7 +450,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql.token.get_token", start.elapsed());
+        report_query("token.get_token", start);
         Ok(db_token.map(|t| t.into()))
     }
 
@@ -474,7 +471,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_market_volume", start.elapsed());
+        report_query("token.get_market_volume", start);
         Ok(db_market_volume.map(|p| p.into()))
     }
 
@@ -501,7 +498,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_market_volume", start.elapsed());
+        report_query("token.update_market_volume", start);
         Ok(())
     }
 
@@ -523,7 +520,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_historical_ticker_price", start.elapsed());
+        report_query("token.get_historical_ticker_price", start);
         Ok(db_price.map(|p| p.into()))
     }
 
@@ -553,7 +550,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
+        report_query("token.update_historical_ticker_price", start);
         Ok(())
     }
 
@@ -579,7 +576,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.store_nft_factory", start.elapsed());
+        report_query("token.store_nft_factory", start);
         Ok(())
     }
 }
@@ -0,0 +1,113 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+use zksync_types::{tx::TxHash, Address};
+// Local imports
+use self::records::{NewTxRejection, StoredTxRejection};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the transactions rejected by the API.
+///
+/// Rejection reasons are only useful to investigate the recent failures, so they are
+/// stored for a limited period of time and removed afterwards.
+#[derive(Debug)]
+pub struct TxRejectionsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TxRejectionsSchema<'a, 'c> {
+    /// Stores the rejections of the transactions, e.g. of all the transactions of a rejected batch.
+    pub async fn store_rejections(&mut self, rejections: &[NewTxRejection]) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for rejection in rejections {
+            sqlx::query!(
+                r#"
+                INSERT INTO tx_rejections ( tx_hash, account, error_code, error_message, recovered_signer )
+                VALUES ( $1, $2, $3, $4, $5 )
+                "#,
+                rejection.tx_hash.as_ref(),
+                rejection.account.as_bytes(),
+                rejection.error_code,
+                rejection.error_message,
+                rejection
+                    .recovered_signer
+                    .as_ref()
+                    .map(|signer| signer.as_bytes())
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        report_query("tx_rejections.store_rejections", start);
+        Ok(())
+    }
+
+    /// Loads the latest rejections of the transaction, starting from the most recent one.
+    pub async fn load_by_tx_hash(
+        &mut self,
+        tx_hash: TxHash,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredTxRejection>> {
+        let start = Instant::now();
+        let rejections = sqlx::query_as!(
+            StoredTxRejection,
+            r#"
+            SELECT * FROM tx_rejections
+            WHERE tx_hash = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            tx_hash.as_ref(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("tx_rejections.load_by_tx_hash", start);
+        Ok(rejections)
+    }
+
+    /// Loads the latest rejections of the account transactions, starting from the most recent one.
+    pub async fn load_by_account(
+        &mut self,
+        account: Address,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredTxRejection>> {
+        let start = Instant::now();
+        let rejections = sqlx::query_as!(
+            StoredTxRejection,
+            r#"
+            SELECT * FROM tx_rejections
+            WHERE account = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            account.as_bytes(),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("tx_rejections.load_by_account", start);
+        Ok(rejections)
+    }
+
+    /// Removes all the rejections with an age greater than `max_age` from the database.
+    pub async fn remove_old_rejections(&mut self, max_age: Duration) -> QueryResult<()> {
+        let start = Instant::now();
+        let oldest_allowed = Utc::now() - max_age;
+        sqlx::query!(
+            "DELETE FROM tx_rejections WHERE created_at < $1",
+            oldest_allowed
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("tx_rejections.remove_old_rejections", start);
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+// External imports
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+// Workspace imports
+use zksync_api_types::v02::transaction::ApiTxRejection;
+use zksync_types::{tx::TxHash, Address};
+
+/// Rejected verification attempt to be stored in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTxRejection {
+    pub tx_hash: TxHash,
+    /// Account which transaction has been rejected.
+    pub account: Address,
+    /// Machine-readable code of the rejection reason, e.g. `INCORRECT_ETH_SIGNATURE`.
+    pub error_code: String,
+    pub error_message: String,
+    /// Signer of the Ethereum signature, if it was recovered during the check.
+    pub recovered_signer: Option<Address>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredTxRejection {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub account: Vec<u8>,
+    pub error_code: String,
+    pub error_message: String,
+    pub recovered_signer: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredTxRejection> for ApiTxRejection {
+    fn from(rejection: StoredTxRejection) -> Self {
+        Self {
+            tx_hash: TxHash::from_slice(&rejection.tx_hash)
+                .expect("Invalid tx hash of the rejection has been stored"),
+            account: Address::from_slice(&rejection.account),
+            error_code: rejection.error_code,
+            message: rejection.error_message,
+            recovered_signer: rejection
+                .recovered_signer
+                .map(|signer| Address::from_slice(&signer)),
+            created_at: rejection.created_at,
+        }
+    }
+}
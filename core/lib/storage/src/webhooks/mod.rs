@@ -0,0 +1,246 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use self::records::{
+    NewWebhook, NewWebhookDelivery, PendingWebhookDelivery, StoredWebhook, StoredWebhookDelivery,
+};
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the webhooks the events of the zkSync network are posted to.
+///
+/// Every event is stored as a separate delivery for each webhook subscribed to it,
+/// so the delivery state survives restarts of the notifier.
+#[derive(Debug)]
+pub struct WebhooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WebhooksSchema<'a, 'c> {
+    /// Stores the new webhook.
+    pub async fn add_webhook(&mut self, webhook: &NewWebhook) -> QueryResult<StoredWebhook> {
+        let start = Instant::now();
+        let webhook = sqlx::query_as!(
+            StoredWebhook,
+            r#"
+            INSERT INTO webhooks ( api_key_id, url, secret, event_types, account_id )
+            VALUES ( $1, $2, $3, $4, $5 )
+            RETURNING *
+            "#,
+            webhook.api_key_id,
+            webhook.url,
+            webhook.secret,
+            &webhook.event_types,
+            webhook.account_id
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        report_query("webhooks.add_webhook", start);
+        Ok(webhook)
+    }
+
+    /// Loads the webhooks registered with the API key.
+    pub async fn load_webhooks_by_api_key(
+        &mut self,
+        api_key_id: i64,
+    ) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT * FROM webhooks WHERE api_key_id = $1 ORDER BY id",
+            api_key_id
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("webhooks.load_webhooks_by_api_key", start);
+        Ok(webhooks)
+    }
+
+    /// Loads the webhooks registered with the API keys which are not revoked.
+    pub async fn load_active_webhooks(&mut self) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            r#"
+            SELECT webhooks.* FROM webhooks
+            INNER JOIN api_keys ON api_keys.id = webhooks.api_key_id
+            WHERE api_keys.revoked_at IS NULL
+            ORDER BY webhooks.id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("webhooks.load_active_webhooks", start);
+        Ok(webhooks)
+    }
+
+    /// Removes the webhook along with its deliveries, returns `false` if there is
+    /// no such webhook registered with the API key.
+    pub async fn remove_webhook(&mut self, id: i64, api_key_id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM webhooks WHERE id = $1 AND api_key_id = $2",
+            id,
+            api_key_id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("webhooks.remove_webhook", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the ID of the last event the deliveries were created for.
+    /// Returns `None` if no events have been processed yet.
+    pub async fn get_last_processed_event_id(&mut self) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!("SELECT last_event_id FROM webhook_notifier_state")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| EventId(row.last_event_id as u64));
+
+        report_query("webhooks.get_last_processed_event_id", start);
+        Ok(id)
+    }
+
+    /// Stores the deliveries of the events and marks the events up to `last_event_id`
+    /// as processed, so each event is delivered at most once per webhook.
+    pub async fn store_deliveries(
+        &mut self,
+        deliveries: &[NewWebhookDelivery],
+        last_event_id: EventId,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for delivery in deliveries {
+            sqlx::query!(
+                r#"
+                INSERT INTO webhook_deliveries ( webhook_id, event_id, event_type, payload )
+                VALUES ( $1, $2, $3, $4 )
+                "#,
+                delivery.webhook_id,
+                delivery.event_id,
+                delivery.event_type,
+                delivery.payload
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_notifier_state ( id, last_event_id )
+            VALUES ( true, $1 )
+            ON CONFLICT ( id ) DO UPDATE SET last_event_id = $1
+            "#,
+            *last_event_id as i64
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        report_query("webhooks.store_deliveries", start);
+        Ok(())
+    }
+
+    /// Loads the deliveries which are due to be attempted, starting from the oldest one.
+    /// Deliveries with `max_attempts` failed attempts are not retried anymore.
+    pub async fn load_pending_deliveries(
+        &mut self,
+        max_attempts: u32,
+        limit: u32,
+    ) -> QueryResult<Vec<PendingWebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            PendingWebhookDelivery,
+            r#"
+            SELECT webhook_deliveries.id, webhook_id, url, secret, event_type, payload, attempts
+            FROM webhook_deliveries
+            INNER JOIN webhooks ON webhooks.id = webhook_deliveries.webhook_id
+            WHERE delivered_at IS NULL AND attempts < $1 AND next_attempt_at <= now()
+            ORDER BY webhook_deliveries.id
+            LIMIT $2
+            "#,
+            max_attempts as i32,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("webhooks.load_pending_deliveries", start);
+        Ok(deliveries)
+    }
+
+    /// Loads the latest deliveries of the webhook, starting from the most recent one.
+    pub async fn load_deliveries(
+        &mut self,
+        webhook_id: i64,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredWebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            StoredWebhookDelivery,
+            r#"
+            SELECT * FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            webhook_id,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query("webhooks.load_deliveries", start);
+        Ok(deliveries)
+    }
+
+    /// Marks the delivery as succeeded.
+    pub async fn mark_delivered(&mut self, id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET attempts = attempts + 1, delivered_at = now(), last_error = NULL
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("webhooks.mark_delivered", start);
+        Ok(())
+    }
+
+    /// Records the failed attempt of the delivery and schedules the next one.
+    pub async fn mark_failed(
+        &mut self,
+        id: i64,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            next_attempt_at
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("webhooks.mark_failed", start);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+use zksync_api_types::v02::webhook::{ApiWebhook, ApiWebhookDelivery, WebhookEventType};
+use zksync_types::AccountId;
+// Local imports
+
+/// Webhook to be stored in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewWebhook {
+    /// API key the webhook is registered with.
+    pub api_key_id: i64,
+    /// URL the events are posted to.
+    pub url: String,
+    /// Secret the payloads are signed with.
+    pub secret: String,
+    /// Types of the events the webhook is subscribed to, e.g. `tx_committed`.
+    pub event_types: Vec<String>,
+    /// Account the events are filtered by, `None` if the events of all the accounts are posted.
+    pub account_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhook {
+    pub id: i64,
+    pub api_key_id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub account_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredWebhook {
+    /// Returns the event types the webhook is subscribed to, unknown ones are skipped.
+    pub fn event_types(&self) -> Vec<WebhookEventType> {
+        self.event_types
+            .iter()
+            .filter_map(|event_type| event_type.parse().ok())
+            .collect()
+    }
+}
+
+impl From<StoredWebhook> for ApiWebhook {
+    fn from(webhook: StoredWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            event_types: webhook.event_types(),
+            url: webhook.url,
+            account_id: webhook.account_id.map(|id| AccountId(id as u32)),
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// Event to be posted to the webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: i64,
+    /// ID of the event in the `events` table.
+    pub event_id: i64,
+    pub event_type: String,
+    pub payload: Value,
+}
+
+/// Delivery which hasn't succeeded yet, along with the webhook it's posted to.
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingWebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event_type: String,
+    pub payload: Value,
+    /// Number of the failed attempts to post the event.
+    pub attempts: i32,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_id: i64,
+    pub event_type: String,
+    pub payload: Value,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredWebhookDelivery> for ApiWebhookDelivery {
+    fn from(delivery: StoredWebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            event_id: delivery.event_id as u64,
+            event_type: delivery
+                .event_type
+                .parse()
+                .expect("Unknown event type of the webhook delivery has been stored"),
+            attempts: delivery.attempts as u32,
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+        }
+    }
+}
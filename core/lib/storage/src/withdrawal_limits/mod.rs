@@ -0,0 +1,205 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, NaiveDate, Utc};
+use num::{bigint::ToBigInt, BigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{tx::TxHash, withdrawal_limits::WithdrawalLimits, Address, TokenId};
+use zksync_utils::BigUintSerdeWrapper;
+// Local imports
+use self::records::DbWithdrawalLimits;
+use crate::{instrumentation::report_query, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Schema for the limits of the withdrawals set by the operator, the daily amounts withdrawn
+/// by the accounts and the large withdrawals waiting for the delay window.
+///
+/// The limits are read on every withdrawal submission, so the changes apply without restarting the server.
+#[derive(Debug)]
+pub struct WithdrawalLimitsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+fn to_big_decimal(amount: &BigUint) -> BigDecimal {
+    BigDecimal::from(BigInt::from(amount.clone()))
+}
+
+fn to_biguint(amount: BigDecimal) -> BigUint {
+    amount
+        .to_bigint()
+        .and_then(|int| int.to_biguint())
+        .expect("Invalid withdrawal volume has been stored")
+}
+
+impl<'a, 'c> WithdrawalLimitsSchema<'a, 'c> {
+    /// Sets the limits of the withdrawals of the token, replaces the previous ones if they are set.
+    pub async fn set_limits(&mut self, limits: &WithdrawalLimits) -> QueryResult<()> {
+        let start = Instant::now();
+        let to_big_decimal = |limit: &Option<BigUintSerdeWrapper>| {
+            limit.as_ref().map(|limit| to_big_decimal(&limit.0))
+        };
+        sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_limits (
+                token_id, account_daily_limit, daily_volume_limit,
+                large_withdrawal_threshold, large_withdrawal_delay
+            )
+            VALUES ( $1, $2, $3, $4, $5 )
+            ON CONFLICT ( token_id ) DO UPDATE SET
+                account_daily_limit = $2,
+                daily_volume_limit = $3,
+                large_withdrawal_threshold = $4,
+                large_withdrawal_delay = $5,
+                updated_at = now()
+            "#,
+            *limits.token as i32,
+            to_big_decimal(&limits.account_daily_limit),
+            to_big_decimal(&limits.daily_volume_limit),
+            to_big_decimal(&limits.large_withdrawal_threshold),
+            limits.large_withdrawal_delay as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("withdrawal_limits.set_limits", start);
+        Ok(())
+    }
+
+    /// Removes the limits of the withdrawals of the token, returns `false` if they are not set.
+    pub async fn remove_limits(&mut self, token: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM withdrawal_limits WHERE token_id = $1",
+            *token as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("withdrawal_limits.remove_limits", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the limits of the withdrawals of all the tokens.
+    pub async fn load_limits(&mut self) -> QueryResult<Vec<WithdrawalLimits>> {
+        let start = Instant::now();
+        let limits = sqlx::query_as!(
+            DbWithdrawalLimits,
+            "SELECT * FROM withdrawal_limits ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(WithdrawalLimits::from)
+        .collect();
+
+        report_query("withdrawal_limits.load_limits", start);
+        Ok(limits)
+    }
+
+    /// Loads the limits of the withdrawals of the token, if they are set.
+    pub async fn get_limits(&mut self, token: TokenId) -> QueryResult<Option<WithdrawalLimits>> {
+        let start = Instant::now();
+        let limits = sqlx::query_as!(
+            DbWithdrawalLimits,
+            "SELECT * FROM withdrawal_limits WHERE token_id = $1",
+            *token as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(WithdrawalLimits::from);
+
+        report_query("withdrawal_limits.get_limits", start);
+        Ok(limits)
+    }
+
+    /// Loads the amounts of the token withdrawn during the day by the account and by all the accounts.
+    pub async fn load_volumes(
+        &mut self,
+        day: NaiveDate,
+        token: TokenId,
+        account: Address,
+    ) -> QueryResult<(BigUint, BigUint)> {
+        let start = Instant::now();
+        let volumes = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(SUM(amount) FILTER (WHERE account = $3), 0) AS "account_volume!",
+                COALESCE(SUM(amount), 0) AS "total_volume!"
+            FROM withdrawal_volumes
+            WHERE day = $1 AND token_id = $2
+            "#,
+            day,
+            *token as i32,
+            account.as_bytes()
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        report_query("withdrawal_limits.load_volumes", start);
+        Ok((
+            to_biguint(volumes.account_volume),
+            to_biguint(volumes.total_volume),
+        ))
+    }
+
+    /// Adds the amount of the token withdrawn by the account to its volume of the day.
+    pub async fn add_volume(
+        &mut self,
+        day: NaiveDate,
+        token: TokenId,
+        account: Address,
+        amount: &BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_volumes ( day, token_id, account, amount )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT ( day, token_id, account )
+            DO UPDATE SET amount = withdrawal_volumes.amount + $4
+            "#,
+            day,
+            *token as i32,
+            account.as_bytes(),
+            to_big_decimal(amount)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query("withdrawal_limits.add_volume", start);
+        Ok(())
+    }
+
+    /// Delays the large withdrawal until `available_at`, unless it's already delayed.
+    /// Returns the time the withdrawal is accepted at.
+    pub async fn delay_withdrawal(
+        &mut self,
+        tx_hash: TxHash,
+        account: Address,
+        token: TokenId,
+        amount: &BigUint,
+        available_at: DateTime<Utc>,
+    ) -> QueryResult<DateTime<Utc>> {
+        let start = Instant::now();
+        // The update of the existing row is a no-op, so the stored time is returned for it.
+        let available_at = sqlx::query!(
+            r#"
+            INSERT INTO delayed_withdrawals ( tx_hash, account, token_id, amount, available_at )
+            VALUES ( $1, $2, $3, $4, $5 )
+            ON CONFLICT ( tx_hash ) DO UPDATE SET tx_hash = EXCLUDED.tx_hash
+            RETURNING available_at
+            "#,
+            tx_hash.as_ref(),
+            account.as_bytes(),
+            *token as i32,
+            to_big_decimal(amount),
+            available_at
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .available_at;
+
+        report_query("withdrawal_limits.delay_withdrawal", start);
+        Ok(available_at)
+    }
+}
@@ -0,0 +1,38 @@
+// External imports
+use chrono::{DateTime, Utc};
+use num::bigint::ToBigInt;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+use zksync_types::{withdrawal_limits::WithdrawalLimits, TokenId};
+// Local imports
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbWithdrawalLimits {
+    pub token_id: i32,
+    pub account_daily_limit: Option<BigDecimal>,
+    pub daily_volume_limit: Option<BigDecimal>,
+    pub large_withdrawal_threshold: Option<BigDecimal>,
+    pub large_withdrawal_delay: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DbWithdrawalLimits> for WithdrawalLimits {
+    fn from(val: DbWithdrawalLimits) -> Self {
+        let to_biguint = |amount: Option<BigDecimal>| {
+            amount.map(|amount| {
+                amount
+                    .to_bigint()
+                    .and_then(|int| int.to_biguint())
+                    .expect("Invalid withdrawal limit has been stored")
+                    .into()
+            })
+        };
+        Self {
+            token: TokenId(val.token_id as u32),
+            account_daily_limit: to_biguint(val.account_daily_limit),
+            daily_volume_limit: to_biguint(val.daily_volume_limit),
+            large_withdrawal_threshold: to_biguint(val.large_withdrawal_threshold),
+            large_withdrawal_delay: val.large_withdrawal_delay as u64,
+        }
+    }
+}
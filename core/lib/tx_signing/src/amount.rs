@@ -0,0 +1,142 @@
+//! Packing of the token and fee amounts into the floating-point form used in the transactions,
+//! and the formatting of the amounts in the Ethereum messages.
+
+use alloc::string::{String, ToString};
+
+const AMOUNT_EXPONENT_BIT_WIDTH: u32 = 5;
+const AMOUNT_MANTISSA_BIT_WIDTH: u32 = 35;
+const FEE_EXPONENT_BIT_WIDTH: u32 = 5;
+const FEE_MANTISSA_BIT_WIDTH: u32 = 11;
+
+/// Packs the token amount, rounding it down to the closest packable amount.
+/// Returns `None` if the amount is too big to be packed.
+pub fn pack_token_amount(amount: u128) -> Option<[u8; 5]> {
+    let packed = pack(amount, AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH)?;
+    let mut bytes = [0u8; 5];
+    bytes.copy_from_slice(&packed.to_be_bytes()[3..]);
+    Some(bytes)
+}
+
+/// Packs the fee amount, rounding it down to the closest packable amount.
+/// Returns `None` if the amount is too big to be packed.
+pub fn pack_fee_amount(amount: u128) -> Option<[u8; 2]> {
+    let packed = pack(amount, FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH)?;
+    let mut bytes = [0u8; 2];
+    bytes.copy_from_slice(&packed.to_be_bytes()[6..]);
+    Some(bytes)
+}
+
+/// Checks whether the token amount can be packed without rounding.
+pub fn is_token_amount_packable(amount: u128) -> bool {
+    unpack(amount, AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH) == Some(amount)
+}
+
+/// Checks whether the fee amount can be packed without rounding.
+pub fn is_fee_amount_packable(amount: u128) -> bool {
+    unpack(amount, FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH) == Some(amount)
+}
+
+/// Formats the amount in the smallest units of the token with the given number of decimals,
+/// the same way as `ethers.utils.formatUnits` does.
+pub fn format_units(amount: u128, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+
+    let (integer, fraction) = if digits.len() > decimals {
+        digits.split_at(digits.len() - decimals)
+    } else {
+        ("", digits.as_str())
+    };
+    let mut formatted = String::with_capacity(digits.len() + decimals + 2);
+    formatted.push_str(if integer.is_empty() { "0" } else { integer });
+    formatted.push('.');
+    for _ in fraction.len()..decimals {
+        formatted.push('0');
+    }
+    formatted.push_str(fraction);
+
+    let trimmed_len = formatted.trim_end_matches('0').len();
+    formatted.truncate(trimmed_len);
+    if formatted.ends_with('.') {
+        formatted.push('0');
+    }
+    formatted
+}
+
+/// Packs the amount as `mantissa * 10 ^ exponent`, with the mantissa in the highest bits.
+fn pack(amount: u128, exponent_len: u32, mantissa_len: u32) -> Option<u64> {
+    let max_exponent = 10u128.saturating_pow((1 << exponent_len) - 1);
+    let max_mantissa = (1u128 << mantissa_len) - 1;
+    if amount > max_mantissa.saturating_mul(max_exponent) {
+        return None;
+    }
+
+    // Find the minimal exponent the amount fits in, then choose between it and the previous
+    // exponent with the largest mantissa, whichever is closer to the amount.
+    let mut exponent = 0;
+    let mut power = 1u128;
+    while amount > max_mantissa.saturating_mul(power) {
+        power *= 10;
+        exponent += 1;
+    }
+    let (exponent, mantissa) = if exponent == 0 {
+        (0, amount)
+    } else {
+        let mantissa = amount / power;
+        let rounded = mantissa * power;
+        let max_previous = max_mantissa * (power / 10);
+        if amount - rounded < amount - max_previous {
+            (exponent, mantissa)
+        } else {
+            (exponent - 1, max_mantissa)
+        }
+    };
+    Some(((mantissa as u64) << exponent_len) | exponent)
+}
+
+/// Returns the amount the given amount is packed into.
+fn unpack(amount: u128, exponent_len: u32, mantissa_len: u32) -> Option<u128> {
+    let packed = pack(amount, exponent_len, mantissa_len)?;
+    let exponent = (packed & ((1 << exponent_len) - 1)) as u32;
+    let mantissa = u128::from(packed >> exponent_len);
+    mantissa.checked_mul(10u128.checked_pow(exponent)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        assert_eq!(pack_fee_amount(0), Some([0, 0]));
+        // Mantissa is in the highest 11 bits, exponent is in the lowest 5 bits.
+        assert_eq!(pack_fee_amount(1), Some([0, 0b0010_0000]));
+        assert_eq!(pack_fee_amount(2_047_000), Some([0xff, 0xe3]));
+        assert!(is_fee_amount_packable(2_047_000));
+        // Rounded down to 2047 * 10 ^ 3.
+        assert_eq!(pack_fee_amount(2_047_999), Some([0xff, 0xe3]));
+        assert!(!is_fee_amount_packable(2_047_999));
+
+        assert_eq!(pack_token_amount(1), Some([0, 0, 0, 0, 0b0010_0000]));
+        assert!(is_token_amount_packable((1 << 35) - 1));
+        assert!(!is_token_amount_packable((1 << 35) | 1));
+        assert_eq!(pack_token_amount(u128::MAX).map(|_| ()), Some(()));
+        assert_eq!(pack_fee_amount(u128::MAX), None);
+    }
+
+    #[test]
+    fn formatting() {
+        let vals = [
+            (0, 1000, "1000.0"),
+            (1, 0, "0.0"),
+            (4, 110, "0.011"),
+            (6, 1_000_000_000_000_000_100_000, "1000000000000000.1"),
+            (9, 10_000_000_000_000_000_001, "10000000000.000000001"),
+            (10, 10_001_000_000, "1.0001"),
+            (18, 1_500_000_000_000_000_000, "1.5"),
+        ];
+        for (decimals, amount, expected) in vals.iter() {
+            assert_eq!(format_units(*amount, *decimals), *expected);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Signing primitives of the zkSync transactions: the byte encodings signed with the zkSync key,
+//! the messages signed with the Ethereum key and the packing of the amounts.
+//!
+//! The crate only depends on `alloc`, so the transactions can be constructed on the devices
+//! without `std`. The encodings are the same as the ones of `zksync_types`, which is checked
+//! by its tests.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod amount;
+#[cfg(feature = "musig")]
+pub mod musig;
+pub mod tx;
+
+mod message;
+
+pub use tx::{ChangePubKey, ForcedExit, MintNFT, TimeRange, Transfer, Withdraw, WithdrawNFT};
+
+/// Ethereum address.
+pub type Address = [u8; 20];
+/// Hash of the zkSync public key, as it's stored in the account.
+pub type PubKeyHash = [u8; 20];
+
+/// Version of the transactions encoding.
+pub const CURRENT_TX_VERSION: u8 = 1;
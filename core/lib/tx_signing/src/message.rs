@@ -0,0 +1,63 @@
+//! Building blocks of the messages signed with the Ethereum key.
+
+use alloc::{format, string::String};
+
+use crate::amount::format_units;
+
+/// Encodes the bytes as the lowercase hex string without the prefix.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(DIGITS[(byte >> 4) as usize] as char);
+        hex.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    hex
+}
+
+/// Formats the address or the hash the way `web3` types are formatted with `{:?}`.
+pub(crate) fn to_prefixed_hex(bytes: &[u8]) -> String {
+    format!("0x{}", to_hex(bytes))
+}
+
+/// Appends the line to the message, separating it from the previous ones.
+pub(crate) fn push_line(message: &mut String, line: &str) {
+    if !message.is_empty() {
+        message.push('\n');
+    }
+    message.push_str(line);
+}
+
+/// Appends the fee line, if the fee is not zero.
+pub(crate) fn push_fee(message: &mut String, fee: u128, token_symbol: &str, decimals: u8) {
+    if fee != 0 {
+        let line = format!("Fee: {} {}", format_units(fee, decimals), token_symbol);
+        push_line(message, &line);
+    }
+}
+
+/// Builds the first part of the transfer or withdrawal message:
+///
+/// [{Transfer/Withdraw} {amount} {token} to: {to_address}]
+/// [Fee: {fee} {token}]
+pub(crate) fn amount_and_fee(
+    transaction: &str,
+    token_symbol: &str,
+    decimals: u8,
+    amount: u128,
+    fee: u128,
+    to: &[u8],
+) -> String {
+    let mut message = String::new();
+    if amount != 0 {
+        message = format!(
+            "{} {} {} to: {}",
+            transaction,
+            format_units(amount, decimals),
+            token_symbol,
+            to_prefixed_hex(to)
+        );
+    }
+    push_fee(&mut message, fee, token_symbol, decimals);
+    message
+}
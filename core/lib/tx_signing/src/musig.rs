@@ -0,0 +1,73 @@
+//! Signing of the transaction bytes with the zkSync key.
+//!
+//! The curve arithmetic comes from `zksync_crypto`, so unlike the rest of the crate this module requires `std`.
+
+use zksync_crypto::{
+    circuit::utils::pub_key_hash_bytes,
+    franklin_crypto::{
+        bellman::pairing::ff::{PrimeField, PrimeFieldRepr},
+        eddsa::Seed,
+        jubjub::FixedGenerators,
+    },
+    merkle_tree::rescue_hasher::BabyRescueHasher,
+    params::{JUBJUB_PARAMS, RESCUE_HASHER, RESCUE_PARAMS},
+    primitives::rescue_hash_tx_msg,
+    public_key_from_private,
+};
+
+pub use zksync_crypto::PrivateKey;
+
+use crate::PubKeyHash;
+
+/// MuSig Rescue signature of the transaction, along with the public key of the signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxSignature {
+    /// Packed public key of the signer.
+    pub pub_key: [u8; 32],
+    /// Packed signature: the point `R` followed by the little-endian scalar `s`.
+    pub signature: [u8; 64],
+}
+
+/// Signs the transaction bytes with the zkSync key.
+pub fn sign(private_key: &PrivateKey, tx_bytes: &[u8]) -> TxSignature {
+    let hashed_msg = rescue_hash_tx_msg(tx_bytes);
+    let seed = Seed::deterministic_seed(private_key, &hashed_msg);
+    let signature = private_key.musig_rescue_sign(
+        &hashed_msg,
+        &seed,
+        FixedGenerators::SpendingKeyGenerator,
+        &RESCUE_PARAMS,
+        &JUBJUB_PARAMS,
+    );
+
+    let mut packed = TxSignature {
+        pub_key: [0; 32],
+        signature: [0; 64],
+    };
+    public_key_from_private(private_key)
+        .0
+        .write(packed.pub_key.as_mut())
+        .expect("writing to the array can't fail");
+    let (r_bar, s_bar) = packed.signature.split_at_mut(32);
+    signature
+        .r
+        .write(r_bar)
+        .expect("writing to the array can't fail");
+    signature
+        .s
+        .into_repr()
+        .write_le(s_bar)
+        .expect("writing to the array can't fail");
+    packed
+}
+
+/// Returns the hash of the public key of the zkSync key, as it's set with `ChangePubKey`.
+pub fn pub_key_hash(private_key: &PrivateKey) -> PubKeyHash {
+    let pub_key = public_key_from_private(private_key);
+    let mut hash = pub_key_hash_bytes(&pub_key, &RESCUE_HASHER as &BabyRescueHasher);
+    hash.reverse();
+
+    let mut pub_key_hash = [0u8; 20];
+    pub_key_hash.copy_from_slice(&hash);
+    pub_key_hash
+}
@@ -0,0 +1,402 @@
+//! Transactions signed with the zkSync key.
+//!
+//! Each transaction provides the bytes to sign with the zkSync key and the message to sign with the
+//! Ethereum key for the 2-Factor authentication. The part of the message without the nonce is used
+//! in the message of the transactions batch. Encodings return `None` if the fee or the amount can't
+//! be packed, use `amount::is_*_packable` to check the amounts beforehand.
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    amount::{pack_fee_amount, pack_token_amount},
+    message::{amount_and_fee, push_fee, push_line, to_hex, to_prefixed_hex},
+    Address, PubKeyHash, CURRENT_TX_VERSION,
+};
+
+/// Timestamps after this one are not shown in the Ethereum messages.
+const MAX_TIMESTAMP: u64 = u32::MAX as u64;
+
+/// Unix timestamps, in seconds, of the interval the transaction can be executed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub valid_from: u64,
+    pub valid_until: u64,
+}
+
+impl TimeRange {
+    pub fn new(valid_from: u64, valid_until: u64) -> Self {
+        Self {
+            valid_from,
+            valid_until,
+        }
+    }
+
+    fn as_be_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.valid_from.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.valid_until.to_be_bytes());
+        bytes
+    }
+
+    fn append_ethereum_sign_message_part(&self, message: &mut String) {
+        if self.valid_from != 0 {
+            push_line(message, &format!("Valid from: {}", self.valid_from));
+        }
+        if self.valid_until < MAX_TIMESTAMP {
+            push_line(message, &format!("Valid until: {}", self.valid_until));
+        }
+    }
+}
+
+impl Default for TimeRange {
+    fn default() -> Self {
+        Self::new(0, u64::MAX)
+    }
+}
+
+/// Starts the encoding of the transaction with the given type.
+fn tx_header(tx_type: u8) -> Vec<u8> {
+    alloc::vec![255u8 - tx_type, CURRENT_TX_VERSION]
+}
+
+/// Appends the nonce line, which completes the message of the single transaction.
+fn with_nonce(mut message: String, nonce: u32) -> String {
+    push_line(&mut message, &format!("Nonce: {}", nonce));
+    message
+}
+
+/// Transfer of the tokens to the other account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub account_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub token: u32,
+    pub amount: u128,
+    pub fee: u128,
+    pub nonce: u32,
+    pub time_range: TimeRange,
+}
+
+impl Transfer {
+    pub const TX_TYPE: u8 = 5;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.from);
+        out.extend_from_slice(&self.to);
+        out.extend_from_slice(&self.token.to_be_bytes());
+        out.extend_from_slice(&pack_token_amount(self.amount)?);
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Some(out)
+    }
+
+    /// The message signed with the Ethereum key, without the nonce.
+    pub fn ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
+        let mut message = amount_and_fee(
+            "Transfer",
+            token_symbol,
+            decimals,
+            self.amount,
+            self.fee,
+            &self.to,
+        );
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message
+    }
+
+    /// The message signed with the Ethereum key for the 2-Factor authentication.
+    pub fn ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
+        with_nonce(
+            self.ethereum_sign_message_part(token_symbol, decimals),
+            self.nonce,
+        )
+    }
+}
+
+/// Withdrawal of the tokens to the Ethereum address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Withdraw {
+    pub account_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub token: u32,
+    pub amount: u128,
+    pub fee: u128,
+    pub nonce: u32,
+    pub time_range: TimeRange,
+}
+
+impl Withdraw {
+    pub const TX_TYPE: u8 = 3;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    /// The amount of the withdrawal is not packed.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.from);
+        out.extend_from_slice(&self.to);
+        out.extend_from_slice(&self.token.to_be_bytes());
+        out.extend_from_slice(&self.amount.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Some(out)
+    }
+
+    /// The message signed with the Ethereum key, without the nonce.
+    pub fn ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
+        let mut message = amount_and_fee(
+            "Withdraw",
+            token_symbol,
+            decimals,
+            self.amount,
+            self.fee,
+            &self.to,
+        );
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message
+    }
+
+    /// The message signed with the Ethereum key for the 2-Factor authentication.
+    pub fn ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
+        with_nonce(
+            self.ethereum_sign_message_part(token_symbol, decimals),
+            self.nonce,
+        )
+    }
+}
+
+/// Change of the zkSync key of the account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangePubKey {
+    pub account_id: u32,
+    pub account: Address,
+    pub new_pk_hash: PubKeyHash,
+    pub fee_token: u32,
+    pub fee: u128,
+    pub nonce: u32,
+    pub time_range: TimeRange,
+}
+
+impl ChangePubKey {
+    pub const TX_TYPE: u8 = 7;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.account);
+        out.extend_from_slice(&self.new_pk_hash);
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Some(out)
+    }
+
+    /// The data signed with the Ethereum key to authorize the key change (the `ECDSA` auth type).
+    /// The batch hash is zero if the transaction is not a part of the batch.
+    pub fn eth_signed_data(&self, batch_hash: [u8; 32]) -> [u8; 60] {
+        let mut data = [0u8; 60];
+        data[..20].copy_from_slice(&self.new_pk_hash);
+        data[20..24].copy_from_slice(&self.nonce.to_be_bytes());
+        data[24..28].copy_from_slice(&self.account_id.to_be_bytes());
+        data[28..].copy_from_slice(&batch_hash);
+        data
+    }
+
+    /// The message signed with the Ethereum key in the transactions batch.
+    pub fn ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
+        let mut message = format!("Set signing key: {}", to_hex(&self.new_pk_hash));
+        push_fee(&mut message, self.fee, token_symbol, decimals);
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message
+    }
+}
+
+/// Withdrawal of all the tokens of the account without the zkSync key to its Ethereum address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForcedExit {
+    pub initiator_account_id: u32,
+    pub target: Address,
+    pub token: u32,
+    pub fee: u128,
+    pub nonce: u32,
+    pub time_range: TimeRange,
+}
+
+impl ForcedExit {
+    pub const TX_TYPE: u8 = 8;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.initiator_account_id.to_be_bytes());
+        out.extend_from_slice(&self.target);
+        out.extend_from_slice(&self.token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Some(out)
+    }
+
+    /// The message signed with the Ethereum key, without the nonce.
+    pub fn ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
+        let mut message = format!(
+            "ForcedExit {} to: {}",
+            token_symbol,
+            to_prefixed_hex(&self.target)
+        );
+        push_fee(&mut message, self.fee, token_symbol, decimals);
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message
+    }
+
+    /// The message signed with the Ethereum key for the 2-Factor authentication.
+    pub fn ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
+        with_nonce(
+            self.ethereum_sign_message_part(token_symbol, decimals),
+            self.nonce,
+        )
+    }
+}
+
+/// Minting of the NFT with the given content hash to the recipient.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintNFT {
+    pub creator_id: u32,
+    pub creator_address: Address,
+    pub content_hash: [u8; 32],
+    pub recipient: Address,
+    pub fee_token: u32,
+    pub fee: u128,
+    pub nonce: u32,
+}
+
+impl MintNFT {
+    pub const TX_TYPE: u8 = 9;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.creator_id.to_be_bytes());
+        out.extend_from_slice(&self.creator_address);
+        out.extend_from_slice(&self.content_hash);
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        Some(out)
+    }
+
+    /// The message signed with the Ethereum key, without the nonce.
+    pub fn ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
+        let mut message = format!(
+            "MintNFT {} for: {}",
+            to_prefixed_hex(&self.content_hash),
+            to_prefixed_hex(&self.recipient)
+        );
+        push_fee(&mut message, self.fee, token_symbol, decimals);
+        message
+    }
+
+    /// The message signed with the Ethereum key for the 2-Factor authentication.
+    pub fn ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
+        with_nonce(
+            self.ethereum_sign_message_part(token_symbol, decimals),
+            self.nonce,
+        )
+    }
+}
+
+/// Withdrawal of the NFT to the Ethereum address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawNFT {
+    pub account_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub token: u32,
+    pub fee_token: u32,
+    pub fee: u128,
+    pub nonce: u32,
+    pub time_range: TimeRange,
+}
+
+impl WithdrawNFT {
+    pub const TX_TYPE: u8 = 10;
+
+    /// Encodes the transaction as it's signed with the zkSync key.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        let mut out = tx_header(Self::TX_TYPE);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.from);
+        out.extend_from_slice(&self.to);
+        out.extend_from_slice(&self.token.to_be_bytes());
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Some(out)
+    }
+
+    /// The message signed with the Ethereum key, without the nonce.
+    /// The fee is shown in the fee token.
+    pub fn ethereum_sign_message_part(&self, fee_token_symbol: &str, decimals: u8) -> String {
+        let mut message = format!(
+            "WithdrawNFT {} to: {}",
+            self.token,
+            to_prefixed_hex(&self.to)
+        );
+        push_fee(&mut message, self.fee, fee_token_symbol, decimals);
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message
+    }
+
+    /// The message signed with the Ethereum key for the 2-Factor authentication.
+    pub fn ethereum_sign_message(&self, fee_token_symbol: &str, decimals: u8) -> String {
+        with_nonce(
+            self.ethereum_sign_message_part(fee_token_symbol, decimals),
+            self.nonce,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_message() {
+        let transfer = Transfer {
+            account_id: 1,
+            from: [1; 20],
+            to: [0xab; 20],
+            token: 0,
+            amount: 1_500_000_000_000_000_000,
+            fee: 0,
+            nonce: 7,
+            time_range: TimeRange::new(0, 1_000_000),
+        };
+        assert_eq!(
+            transfer.ethereum_sign_message("ETH", 18),
+            "Transfer 1.5 ETH to: 0xabababababababababababababababababababab\n\
+             Valid until: 1000000\n\
+             Nonce: 7"
+        );
+        // Transaction type, version, account ID, addresses, token, amount, fee, nonce, time range.
+        let bytes = transfer.bytes().unwrap();
+        assert_eq!(bytes.len(), 2 + 4 + 20 + 20 + 4 + 5 + 2 + 4 + 16);
+        assert_eq!(&bytes[..2], &[250, CURRENT_TX_VERSION]);
+    }
+}
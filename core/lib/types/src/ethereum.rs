@@ -4,7 +4,7 @@
 use thiserror::Error;
 // Local uses
 use crate::aggregated_operations::{AggregatedActionType, AggregatedOperation};
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 
 /// Numerical identifier of the Ethereum operation.
 pub type EthOpId = i64;
@@ -20,6 +20,9 @@ pub struct ETHOperation {
     pub op: Option<(i64, AggregatedOperation)>,
     /// Used nonce (fixed for all the sent transactions).
     pub nonce: U256,
+    /// Additional operator account the transactions are sent from,
+    /// `None` stands for the main operator account.
+    pub sender: Option<Address>,
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
     /// Gas price used in the last sent transaction.
@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 // Workspace uses
 // Local uses
 use super::account::AccountStateChangeStatus;
-use crate::{block::ExecutedOperations, AccountId, BlockNumber, TokenId};
+use crate::{
+    block::ExecutedOperations, tx::TxHash, AccountId, BlockNumber, SignedZkSyncTx, TokenId,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -15,11 +17,17 @@ pub enum TransactionStatus {
     Committed,
     Finalized,
     Rejected,
+    /// Pending transaction has been replaced in the mempool by the transaction
+    /// with the same nonce paying a higher fee.
+    Replaced,
+    /// Pending transaction has been removed from the mempool without being executed,
+    /// e.g. because it has not been included into a block in time.
+    Dropped,
 }
 
 /// All possible types of operations in the zkSync network.
 /// Deserialized from the `tx` field of the [TransactionEvent].
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TransactionType {
     Transfer,
     Withdraw,
@@ -32,6 +40,23 @@ pub enum TransactionType {
     Deposit,
 }
 
+impl TransactionType {
+    /// Returns the `type` field of the serialized operation of this type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::Withdraw => "Withdraw",
+            Self::WithdrawNFT => "WithdrawNFT",
+            Self::MintNFT => "MintNFT",
+            Self::Swap => "Swap",
+            Self::ChangePubKey => "ChangePubKey",
+            Self::ForcedExit => "ForcedExit",
+            Self::FullExit => "FullExit",
+            Self::Deposit => "Deposit",
+        }
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionEvent {
@@ -89,6 +114,48 @@ impl TransactionEvent {
         })
     }
 
+    /// Creates the event of the pending transaction replaced by the transaction `replaced_by`.
+    ///
+    /// Returns [`None`] for [close](crate::tx::Close) operation.
+    pub fn from_replaced_tx(
+        signed_tx: &SignedZkSyncTx,
+        block_number: BlockNumber,
+        replaced_by: TxHash,
+    ) -> Option<Self> {
+        Some(Self {
+            tx_hash: signed_tx.tx.hash().to_string(),
+            account_id: signed_tx.account_id().ok()?,
+            token_id: signed_tx.token_id(),
+            block_number,
+            tx: serde_json::to_value(&signed_tx.tx).unwrap(),
+            status: TransactionStatus::Replaced,
+            fail_reason: Some(format!("Replaced by {}", replaced_by.to_string())),
+            created_at: signed_tx.created_at,
+            tx_type: OnceCell::default(),
+        })
+    }
+
+    /// Creates the event of the pending transaction removed from the mempool for the `reason`.
+    ///
+    /// Returns [`None`] for [close](crate::tx::Close) operation.
+    pub fn from_dropped_tx(
+        signed_tx: &SignedZkSyncTx,
+        block_number: BlockNumber,
+        reason: &str,
+    ) -> Option<Self> {
+        Some(Self {
+            tx_hash: signed_tx.tx.hash().to_string(),
+            account_id: signed_tx.account_id().ok()?,
+            token_id: signed_tx.token_id(),
+            block_number,
+            tx: serde_json::to_value(&signed_tx.tx).unwrap(),
+            status: TransactionStatus::Dropped,
+            fail_reason: Some(reason.to_owned()),
+            created_at: signed_tx.created_at,
+            tx_type: OnceCell::default(),
+        })
+    }
+
     pub fn tx_type(&self) -> TransactionType {
         *self
             .tx_type
@@ -0,0 +1,129 @@
+//! Fast withdrawals are fronted on Ethereum by the liquidity providers.
+//!
+//! The user asks the server to match the withdrawal with the registered provider of the token,
+//! and then withdraws the funds on L2 to the address of the provider instead of the recipient.
+//! The provider sends the funds to the recipient on L1 as soon as the withdrawal is executed on L2,
+//! and receives the withdrawn funds once the block is executed on Ethereum.
+
+use chrono::{DateTime, Utc};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{Address, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use crate::tx::{TxHash, Withdraw};
+
+pub type FastWithdrawalId = i64;
+
+/// Liquidity provider fronting the fast withdrawals of the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityProvider {
+    pub address: Address,
+    pub token: TokenId,
+    /// Largest amount of a single withdrawal the provider fronts.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_amount: BigUint,
+    /// Fee of the provider, in basis points of the withdrawn amount.
+    pub fee_bps: u16,
+}
+
+impl LiquidityProvider {
+    /// Fee of the provider can't exceed the withdrawn amount.
+    pub const MAX_FEE_BPS: u16 = 10_000;
+
+    /// Checks whether the provider fronts the withdrawal of the amount in the token.
+    pub fn can_front(&self, token: TokenId, amount: &BigUint) -> bool {
+        self.token == token && *amount <= self.max_amount
+    }
+
+    /// Amount the provider sends to the recipient on L1 for the withdrawal of `amount`.
+    pub fn fronted_amount(&self, amount: &BigUint) -> BigUint {
+        let fee = amount * BigUint::from(self.fee_bps) / BigUint::from(Self::MAX_FEE_BPS);
+        amount - fee
+    }
+}
+
+/// Withdrawal the user asks to match with the liquidity provider.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FastWithdrawalRequest {
+    /// L2 account the funds are withdrawn from.
+    pub account: Address,
+    /// L1 address the provider sends the funds to.
+    pub recipient: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SaveFastWithdrawalQuery {
+    pub request: FastWithdrawalRequest,
+    pub provider: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fronted_amount: BigUint,
+    pub created_at: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+}
+
+/// Withdrawal matched with the liquidity provider.
+///
+/// On L2 it's an ordinary `Withdraw` of the `amount` from the `account` to the `provider`,
+/// which has to be submitted before the match expires. Once it's submitted, its hash is set
+/// and the provider owes the `fronted_amount` to the `recipient` on L1.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FastWithdrawal {
+    pub id: FastWithdrawalId,
+    pub account: Address,
+    pub recipient: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub provider: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fronted_amount: BigUint,
+    pub tx_hash: Option<TxHash>,
+    pub created_at: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+}
+
+impl FastWithdrawal {
+    /// Checks whether the transaction hands the withdrawal off to the provider as it's matched.
+    pub fn is_handed_off_by(&self, withdraw: &Withdraw) -> bool {
+        withdraw.from == self.account
+            && withdraw.to == self.provider
+            && withdraw.token == self.token
+            && withdraw.amount == self.amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(fee_bps: u16) -> LiquidityProvider {
+        LiquidityProvider {
+            address: Address::repeat_byte(0x01),
+            token: TokenId(1),
+            max_amount: BigUint::from(1_000u32),
+            fee_bps,
+        }
+    }
+
+    #[test]
+    fn fronted_amount() {
+        let amount = BigUint::from(1_000u32);
+        assert_eq!(provider(0).fronted_amount(&amount), amount);
+        assert_eq!(provider(25).fronted_amount(&amount), BigUint::from(998u32));
+        assert_eq!(
+            provider(LiquidityProvider::MAX_FEE_BPS).fronted_amount(&amount),
+            BigUint::from(0u32)
+        );
+
+        assert!(provider(25).can_front(TokenId(1), &amount));
+        assert!(!provider(25).can_front(TokenId(1), &BigUint::from(1_001u32)));
+        assert!(!provider(25).can_front(TokenId(2), &amount));
+    }
+}
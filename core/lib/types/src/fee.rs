@@ -1,3 +1,5 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use num::rational::Ratio;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
@@ -37,6 +39,9 @@ pub struct Fee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Prices the fee is calculated with, not set by the older servers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<FeePricing>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -48,6 +53,22 @@ pub struct BatchFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Prices the fee is calculated with, not set by the older servers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<FeePricing>,
+}
+
+/// Prices the fee is calculated with. Lets the clients explain the fee to the users
+/// and detect the fees calculated with stale prices.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePricing {
+    /// Price of the fee token in USD, per the whole token rather than its smallest unit.
+    pub token_price_usd: BigDecimal,
+    /// Time the price of the fee token was last updated.
+    pub price_updated_at: DateTime<Utc>,
+    /// Time the fee was calculated.
+    pub calculated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -64,6 +85,7 @@ impl BatchFee {
             gas_fee,
             zkp_fee,
             total_fee,
+            pricing: None,
         }
     }
 }
@@ -84,6 +106,7 @@ impl Fee {
             gas_fee,
             zkp_fee,
             total_fee,
+            pricing: None,
         }
     }
 }
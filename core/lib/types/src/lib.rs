@@ -41,6 +41,7 @@ pub mod aggregated_operations;
 pub mod block;
 pub mod ethereum;
 pub mod event;
+pub mod fast_withdrawals;
 pub mod fee;
 pub mod forced_exit_requests;
 pub mod gas_counter;
@@ -48,19 +49,21 @@ pub mod helpers;
 pub mod mempool;
 pub mod network;
 pub mod operations;
+pub mod operator_costs;
 pub mod priority_ops;
 pub mod prover;
 pub mod register_factory;
 pub mod tokens;
 pub mod tx;
 mod utils;
+pub mod withdrawal_limits;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
-pub use self::fee::{BatchFee, Fee, OutputFeeType, TotalFee};
+pub use self::fee::{BatchFee, Fee, FeePricing, OutputFeeType, TotalFee};
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, SwapOp, TransferOp,
     TransferToNewOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp,
@@ -1,7 +1,7 @@
 //! Set of all the operations supported by the zkSync network.
 
 use super::ZkSyncTx;
-use crate::ZkSyncPriorityOp;
+use crate::{tx::verify_and_cache_signers, ZkSyncPriorityOp};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{AccountId, TokenId};
@@ -275,6 +275,22 @@ impl ZkSyncOp {
         }
     }
 
+    /// Verifies the zkSync signatures of the transactions of the operations at once, so they're
+    /// not verified one by one afterwards. Returns `true` if all the signatures are valid.
+    pub fn verify_signatures_batch<'a>(ops: impl IntoIterator<Item = &'a mut ZkSyncOp>) -> bool {
+        verify_and_cache_signers(ops.into_iter().filter_map(|op| match op {
+            ZkSyncOp::Transfer(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::TransferToNew(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::Withdraw(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::ChangePubKeyOffchain(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::ForcedExit(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::Swap(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::MintNFTOp(op) => Some(op.tx.signature_data()),
+            ZkSyncOp::WithdrawNFT(op) => Some(op.tx.signature_data()),
+            _ => None,
+        }))
+    }
+
     /// Attempts to interpret the operation as the L1 priority operation.
     pub fn try_get_priority_op(&self) -> Result<ZkSyncPriorityOp, UnexpectedOperationType> {
         match self {
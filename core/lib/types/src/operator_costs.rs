@@ -0,0 +1,233 @@
+//! Costs of the operator of the network and the fees it collects, attributed to the blocks.
+//!
+//! The L1 costs are the fees of the Ethereum transactions sent by the `eth_sender`: the fee of
+//! the transaction committing, proving or executing a range of blocks is split evenly between
+//! the blocks, and the fee of the transaction completing a batch of pending withdrawals is split
+//! between the blocks of the withdrawals in proportion to their gas limits. The collected fees are
+//! the fees of the successful transactions of the block, in the tokens they were paid in.
+//!
+//! The profitability is estimated in USD with the current prices of the tokens, not the ones
+//! at the time of the block.
+
+// Built-in uses
+use std::collections::{BTreeMap, HashMap};
+use std::{fmt, str::FromStr};
+
+// External uses
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use num::{rational::Ratio, BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_basic_types::{BlockNumber, TokenId};
+use zksync_utils::{ratio_to_big_decimal, BigUintSerdeWrapper};
+
+// Local uses
+use crate::aggregated_operations::AggregatedActionType;
+
+/// Precision of the USD values of the reports.
+const USD_PRECISION: usize = 6;
+
+/// Kind of the Ethereum transactions the L1 costs are spent on.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum L1CostKind {
+    Commit,
+    PublishProof,
+    Execute,
+    /// Completion of the pending withdrawals of the executed blocks.
+    Withdrawals,
+}
+
+impl L1CostKind {
+    /// Returns the kind of the costs of the aggregated operation, `None` for the proofs
+    /// created off-chain.
+    pub fn from_action_type(action_type: AggregatedActionType) -> Option<Self> {
+        match action_type {
+            AggregatedActionType::CommitBlocks => Some(Self::Commit),
+            AggregatedActionType::CreateProofBlocks => None,
+            AggregatedActionType::PublishProofBlocksOnchain => Some(Self::PublishProof),
+            AggregatedActionType::ExecuteBlocks => Some(Self::Execute),
+        }
+    }
+}
+
+impl fmt::Display for L1CostKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Commit => write!(f, "commit"),
+            Self::PublishProof => write!(f, "publishProof"),
+            Self::Execute => write!(f, "execute"),
+            Self::Withdrawals => write!(f, "withdrawals"),
+        }
+    }
+}
+
+impl FromStr for L1CostKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "commit" => Ok(Self::Commit),
+            "publishProof" => Ok(Self::PublishProof),
+            "execute" => Ok(Self::Execute),
+            "withdrawals" => Ok(Self::Withdrawals),
+            _ => Err("Incorrect L1 cost kind".to_owned()),
+        }
+    }
+}
+
+/// Splits the fee of the transaction in proportion to the weights, the remainder of the division
+/// is attributed to the last share.
+pub fn split_fee(fee: &BigUint, weights: &[u64]) -> Vec<BigUint> {
+    let total_weight: u64 = weights.iter().sum();
+    if total_weight == 0 {
+        return vec![BigUint::zero(); weights.len()];
+    }
+
+    let mut shares: Vec<_> = weights
+        .iter()
+        .map(|weight| fee * BigUint::from(*weight) / BigUint::from(total_weight))
+        .collect();
+    let distributed: BigUint = shares.iter().sum();
+    if let Some(last) = shares.last_mut() {
+        *last += fee - distributed;
+    }
+    shares
+}
+
+/// Current USD price of the token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenUsdPrice {
+    pub decimals: u8,
+    pub usd_price: Ratio<BigUint>,
+}
+
+impl TokenUsdPrice {
+    fn value(&self, amount: &BigUint) -> Ratio<BigUint> {
+        let scale = BigUint::from(10u32).pow(u32::from(self.decimals));
+        Ratio::new(amount.clone(), scale) * &self.usd_price
+    }
+}
+
+/// L1 costs, in wei, and the fees collected by the operator.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Costs {
+    pub l1_costs: BTreeMap<L1CostKind, BigUintSerdeWrapper>,
+    pub collected_fees: BTreeMap<TokenId, BigUintSerdeWrapper>,
+}
+
+impl Costs {
+    pub fn add_l1_cost(&mut self, kind: L1CostKind, fee: &BigUint) {
+        self.l1_costs.entry(kind).or_default().0 += fee;
+    }
+
+    pub fn add_collected_fee(&mut self, token: TokenId, fee: &BigUint) {
+        self.collected_fees.entry(token).or_default().0 += fee;
+    }
+
+    pub fn total_l1_cost(&self) -> BigUint {
+        self.l1_costs.values().map(|fee| &fee.0).sum()
+    }
+
+    /// Estimates the profitability in USD, returns `None` if the price of ETH or
+    /// of any token the fees are collected in is unknown.
+    pub fn profitability(&self, prices: &HashMap<TokenId, TokenUsdPrice>) -> Option<Profitability> {
+        let cost = prices.get(&TokenId(0))?.value(&self.total_l1_cost());
+        let mut revenue = Ratio::from(BigUint::zero());
+        for (token, fee) in &self.collected_fees {
+            revenue += prices.get(token)?.value(&fee.0);
+        }
+
+        let cost_usd = ratio_to_big_decimal(&cost, USD_PRECISION);
+        let revenue_usd = ratio_to_big_decimal(&revenue, USD_PRECISION);
+        Some(Profitability {
+            profit_usd: &revenue_usd - &cost_usd,
+            cost_usd,
+            revenue_usd,
+        })
+    }
+}
+
+/// Estimation of the profitability in USD.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profitability {
+    pub cost_usd: BigDecimal,
+    pub revenue_usd: BigDecimal,
+    /// Negative if the L1 costs exceed the collected fees.
+    pub profit_usd: BigDecimal,
+}
+
+/// Costs of the block.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockCosts {
+    pub block_number: BlockNumber,
+    /// UNIX timestamp of the block, in seconds.
+    pub timestamp: Option<u64>,
+    pub costs: Costs,
+    /// Not set if the prices of the tokens are unknown.
+    pub profitability: Option<Profitability>,
+}
+
+/// Costs of the blocks created during the UTC day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCosts {
+    pub day: NaiveDate,
+    pub blocks: u64,
+    pub costs: Costs,
+    /// Not set if the prices of the tokens are unknown.
+    pub profitability: Option<Profitability>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_split() {
+        let fee = BigUint::from(100u32);
+        assert_eq!(
+            split_fee(&fee, &[1, 1, 1]),
+            vec![33u32.into(), 33u32.into(), 34u32.into()]
+        );
+        assert_eq!(split_fee(&fee, &[3, 1]), vec![75u32.into(), 25u32.into()]);
+        assert_eq!(split_fee(&fee, &[]), Vec::<BigUint>::new());
+    }
+
+    #[test]
+    fn profitability() {
+        let mut costs = Costs::default();
+        // 0.001 ETH spent on the commitment and 0.0005 ETH on the execution.
+        costs.add_l1_cost(L1CostKind::Commit, &BigUint::from(10u64.pow(15)));
+        costs.add_l1_cost(L1CostKind::Execute, &BigUint::from(5 * 10u64.pow(14)));
+        // 2 USDC collected.
+        costs.add_collected_fee(TokenId(1), &BigUint::from(2_000_000u32));
+
+        let mut prices = HashMap::new();
+        prices.insert(
+            TokenId(0),
+            TokenUsdPrice {
+                decimals: 18,
+                usd_price: Ratio::from(BigUint::from(1_000u32)),
+            },
+        );
+        assert_eq!(costs.profitability(&prices), None);
+
+        prices.insert(
+            TokenId(1),
+            TokenUsdPrice {
+                decimals: 6,
+                usd_price: Ratio::from(BigUint::from(1u32)),
+            },
+        );
+        let profitability = costs.profitability(&prices).unwrap();
+        assert_eq!(profitability.cost_usd, "1.5".parse().unwrap());
+        assert_eq!(profitability.revenue_usd, "2".parse().unwrap());
+        assert_eq!(profitability.profit_usd, "0.5".parse().unwrap());
+    }
+}
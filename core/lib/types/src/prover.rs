@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zksync_basic_types::BlockNumber;
 
@@ -54,6 +56,35 @@ impl ProverJob {
     }
 }
 
+/// Parameters the idle prover jobs are handed out by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProverJobScheduling {
+    /// Size (in chunks) of the proven block, only the provers supporting it get the job.
+    /// `None` for the aggregated proofs, which are handed out to any prover.
+    pub block_size: Option<usize>,
+    /// The earliest Ethereum block by which the priority operations of the proven blocks
+    /// must be executed. Jobs with the earlier deadlines are handed out first.
+    pub deadline_block: Option<u64>,
+}
+
+/// Unfinished prover job which either has already lost its prover, or whose prover
+/// stopped sending the heartbeats and is about to lose it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StuckProverJob {
+    pub job_id: i32,
+    pub job_type: String,
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    /// Whether the job is currently assigned to a prover.
+    pub in_progress: bool,
+    /// Name of the prover the job is assigned to, or the server routine that updated it last.
+    pub updated_by: String,
+    /// Time of the last heartbeat from the prover, or the last update of the job by the server.
+    pub updated_at: DateTime<Utc>,
+    /// Amount of times the job was taken from the silent provers and handed out again.
+    pub reassignments: u32,
+}
+
 #[derive(Debug, Clone)]
 pub enum ProverJobType {
     SingleProof,
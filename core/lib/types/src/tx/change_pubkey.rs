@@ -258,6 +258,15 @@ impl ChangePubKey {
         }
     }
 
+    /// Returns the signature, the message it signs in the current version of the transaction
+    /// and the cache of the signer, so the signature can be verified in a batch.
+    pub(crate) fn signature_data(
+        &mut self,
+    ) -> (&TxSignature, Vec<u8>, &mut VerifiedSignatureCache) {
+        let msg = self.get_bytes();
+        (&self.signature, msg, &mut self.cached_signer)
+    }
+
     /// Encodes the transaction data as the byte sequence according to the old zkSync protocol with 2 bytes token.
     pub fn get_old_bytes(&self) -> Vec<u8> {
         let mut out = Vec::new();
@@ -410,8 +419,10 @@ impl ChangePubKey {
     ///
     /// Set signing key: {pubKeyHash}
     /// [Fee: {fee} {token}]
+    /// [Valid from: {valid_from}]
+    /// [Valid until: {valid_until}]
     ///
-    /// Note that the second line is optional.
+    /// Note that the lines in brackets are optional.
     pub fn get_ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
         let mut message = format!(
             "Set signing key: {}",
@@ -427,6 +438,9 @@ impl ChangePubKey {
                 .as_str(),
             );
         }
+        if let Some(time_range) = &self.time_range {
+            time_range.append_ethereum_sign_message_part(&mut message);
+        }
         message
     }
 
@@ -1,8 +1,15 @@
+use chrono::{DateTime, Utc};
+use http::StatusCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::tx::{
-    change_pubkey, close, forced_exit, mint_nft, swap, transfer, withdraw, withdraw_nft,
+use crate::{
+    tx::{
+        change_pubkey, close, forced_exit, mint_nft, swap, transfer, withdraw, withdraw_nft,
+        BatchMessageFormats, TxHash,
+    },
+    withdrawal_limits::WithdrawalLimitKind,
+    Address, TokenId, H256,
 };
 #[derive(Debug, Error, PartialEq)]
 pub enum ChangePubkeySignedDataError {
@@ -14,13 +21,79 @@ pub enum ChangePubkeySignedDataError {
 #[error("Close operations are disabled")]
 pub struct CloseOperationsDisabled();
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+/// Reason of the transaction rejection.
+///
+/// The HTTP status and the machine-readable error code of every variant are a part
+/// of the public API and must not be changed:
+///
+/// | Variant                             | HTTP status | Error code                              |
+/// | ----------------------------------- | ----------- | --------------------------------------- |
+/// | `NonceMismatch`                     | 400         | `NONCE_MISMATCH`                        |
+/// | `ReplacementUnderpriced`            | 400         | `REPLACEMENT_UNDERPRICED`               |
+/// | `NonceTooHigh`                      | 400         | `NONCE_TOO_HIGH`                        |
+/// | `TooManyAccountTxs`                 | 400         | `TOO_MANY_ACCOUNT_TXS`                  |
+/// | `MempoolFull`                       | 429         | `MEMPOOL_FULL`                          |
+/// | `IncorrectTx`                       | 400         | `INCORRECT_TX`                          |
+/// | `TxFeeTooLow`                       | 400         | `TX_FEE_TOO_LOW`                        |
+/// | `TxBatchFeeTooLow`                  | 400         | `TX_BATCH_FEE_TOO_LOW`                  |
+/// | `EIP1271SignatureVerificationFail`  | 400         | `EIP1271_SIGNATURE_VERIFICATION_FAIL`   |
+/// | `MissingEthSignature`               | 400         | `MISSING_ETH_SIGNATURE`                 |
+/// | `IncorrectEthSignature`             | 400         | `INCORRECT_ETH_SIGNATURE`               |
+/// | `SignerMismatch`                    | 400         | `SIGNER_MISMATCH`                       |
+/// | `Eip1271Rejected`                   | 400         | `EIP1271_REJECTED`                      |
+/// | `MalformedSignature`                | 400         | `MALFORMED_ETH_SIGNATURE`               |
+/// | `UnsupportedSignatureType`          | 400         | `UNSUPPORTED_ETH_SIGNATURE_TYPE`        |
+/// | `ChangePkNotAuthorized`             | 400         | `CHANGE_PK_NOT_AUTHORIZED`              |
+/// | `ChangePkAuthorizationNotConfirmed` | 400         | `CHANGE_PK_AUTHORIZATION_NOT_CONFIRMED` |
+/// | `Other`                             | 500         | `INTERNAL_ERROR`                        |
+/// | `DbError`                           | 503         | `DB_UNAVAILABLE`                        |
+/// | `EmptyBatch`                        | 400         | `EMPTY_BATCH`                           |
+/// | `BatchTooBig`                       | 400         | `BATCH_TOO_BIG`                         |
+/// | `BatchWithdrawalsOverload`          | 400         | `BATCH_WITHDRAWALS_OVERLOAD`            |
+/// | `BatchFeeTokenMismatch`             | 400         | `BATCH_FEE_TOKEN_MISMATCH`              |
+/// | `EthSignaturesLimitExceeded`        | 400         | `ETH_SIGNATURES_LIMIT_EXCEEDED`         |
+/// | `NotYetValid`                       | 400         | `NOT_YET_VALID`                         |
+/// | `SignatureCheckUnavailable`         | 503         | `SIGNATURE_CHECK_UNAVAILABLE`           |
+/// | `SignatureCheckTimeout`             | 503         | `SIGNATURE_CHECK_TIMEOUT`               |
+/// | `EthereumNodeUnavailable`           | 503         | `ETHEREUM_NODE_UNAVAILABLE`             |
+/// | `ServiceOverloaded`                 | 429         | `SERVICE_OVERLOADED`                    |
+/// | `AddressRejected`                   | 403         | `ADDRESS_REJECTED`                      |
+/// | `ScreeningUnavailable`              | 503         | `SCREENING_UNAVAILABLE`                 |
+/// | `Draining`                          | 503         | `SERVICE_DRAINING`                      |
+/// | `WithdrawalLimitExceeded`           | 429         | `WITHDRAWAL_LIMIT_EXCEEDED`             |
+/// | `WithdrawalDelayed`                 | 400         | `WITHDRAWAL_DELAYED`                    |
+///
+/// `BatchTx` has the HTTP status and the error code of the error it wraps.
+#[derive(Debug, Clone, Serialize, Deserialize, Error)]
 pub enum TxAddError {
     #[error("Tx nonce is too low.")]
     NonceMismatch,
 
-    #[error("Tx is incorrect: {0}")]
-    IncorrectTx(#[from] TransactionError),
+    /// Pending transaction with the same nonce is in the mempool. It's only replaced
+    /// by the transaction paying a strictly higher fee in the same token.
+    #[error("Pending tx with the same nonce pays a higher or equal fee")]
+    ReplacementUnderpriced,
+
+    /// Nonce is too far ahead of the account nonce for the transaction to wait
+    /// in the mempool for the missing ones.
+    #[error("Tx nonce is too high.")]
+    NonceTooHigh,
+
+    /// Account has too many pending transactions in the mempool.
+    #[error("Too many pending txs of the account")]
+    TooManyAccountTxs,
+
+    /// Mempool has reached the limit of the pending transactions.
+    /// The request can be retried once the pending transactions are executed.
+    #[error("Mempool is full, try again later")]
+    MempoolFull,
+
+    /// Transaction of the `account` is malformed or its zkSync signature is invalid.
+    #[error("Tx is incorrect: {reason}")]
+    IncorrectTx {
+        account: Address,
+        reason: TransactionError,
+    },
 
     #[error("Transaction fee is too low")]
     TxFeeTooLow,
@@ -34,12 +107,49 @@ pub enum TxAddError {
     #[error("MissingEthSignature")]
     MissingEthSignature,
 
-    #[error("Eth signature is incorrect")]
-    IncorrectEthSignature,
+    /// None of the batch signatures belongs to the sender of the transaction.
+    /// `recovered` is the signer of the ECDSA signature, if the batch has one.
+    /// `attempted_formats` are the formats of the batch message the signatures were checked for,
+    /// they're listed in the message so the client can tell which format it should have signed.
+    #[error("Eth signature is incorrect (tried: {attempted_formats})")]
+    IncorrectEthSignature {
+        recovered: Option<Address>,
+        expected: Address,
+        message_hash: H256,
+        attempted_formats: BatchMessageFormats,
+    },
+
+    /// ECDSA signature is valid, but it's made by another account or for another message.
+    #[error("Eth signature is made by {recovered:?} instead of {expected:?}")]
+    SignerMismatch {
+        expected: Address,
+        recovered: Address,
+    },
+
+    /// Smart contract wallet has not approved the EIP1271 signature.
+    #[error("EIP1271 signature is rejected by the account contract")]
+    Eip1271Rejected,
+
+    #[error("Eth signature is malformed")]
+    MalformedSignature,
+
+    #[error("Eth signature type is not supported")]
+    UnsupportedSignatureType,
 
     #[error("Change pubkey tx is not authorized onchain")]
     ChangePkNotAuthorized,
 
+    /// Onchain authorization of the change pubkey tx exists, but it's not confirmed
+    /// by enough blocks yet, or it's missing but may have been mined without being seen
+    /// by the Ethereum node yet.
+    ///
+    /// Unlike `ChangePkNotAuthorized`, the client doesn't have to change anything: once the
+    /// authorization is confirmed, the same signed transaction is accepted, so the client
+    /// resubmits it after a few Ethereum blocks. The JSON RPC API reports it with the
+    /// code 206.
+    #[error("Change pubkey tx authorization is not confirmed yet, try again later")]
+    ChangePkAuthorizationNotConfirmed,
+
     #[error("Internal error")]
     Other,
 
@@ -55,8 +165,186 @@ pub enum TxAddError {
     #[error("The number of withdrawals in the batch is too big")]
     BatchWithdrawalsOverload,
 
+    /// Transactions of the batch pay non-zero fees in different tokens: the first one observed
+    /// in the batch and the conflicting one.
+    #[error("Batch transactions pay fees in different tokens: {0} and {1}")]
+    BatchFeeTokenMismatch(TokenId, TokenId),
+
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Transaction is not valid yet")]
+    NotYetValid,
+
+    /// Signature could not be checked because of the internal failure, e.g.
+    /// Ethereum node is unreachable. Unlike other errors, it's not caused by
+    /// the transaction itself, so the request can be retried.
+    #[error("Signature could not be checked, try again later")]
+    SignatureCheckUnavailable,
+
+    /// Signature check has not finished before the deadline of the request, e.g. because
+    /// the Ethereum node is stalled. The request can be retried.
+    #[error("Signature check has timed out, try again later")]
+    SignatureCheckTimeout,
+
+    /// Ethereum node has been failing recently, so the checks requiring it are
+    /// rejected without calling the node. The request can be retried.
+    #[error("Ethereum node is unavailable, try again later")]
+    EthereumNodeUnavailable,
+
+    /// Signature checker is saturated and the request could not be queued in time.
+    /// The request can be retried.
+    #[error("Server is overloaded, try again later")]
+    ServiceOverloaded,
+
+    /// Transaction involves the `address` which is not allowed to transact by the screening
+    /// of the incoming transactions.
+    #[error("Address {address:?} is not allowed to transact")]
+    AddressRejected { address: Address },
+
+    /// Addresses of the transaction could not be screened, e.g. the screening service
+    /// is unreachable. The request can be retried.
+    #[error("Transaction could not be screened, try again later")]
+    ScreeningUnavailable,
+
+    /// Server doesn't accept the new transactions for the maintenance, e.g. the upgrade.
+    /// The request can be retried.
+    #[error("Server doesn't accept new transactions for the maintenance, try again later")]
+    Draining,
+
+    /// Withdrawal exceeds the `limit` of the daily withdrawals of the `token` set by the operator.
+    /// The request can be retried once the limit is reset at the start of the next UTC day.
+    #[error("Withdrawal exceeds the daily withdrawal limit of the token {token}")]
+    WithdrawalLimitExceeded {
+        token: TokenId,
+        limit: WithdrawalLimitKind,
+    },
+
+    /// Large withdrawal is only accepted once the delay window set by the operator passes
+    /// since it was first submitted. The same transaction can be resubmitted at `available_at`.
+    #[error("Large withdrawal is delayed until {available_at}")]
+    WithdrawalDelayed { available_at: DateTime<Utc> },
+
+    /// Transaction at `index` of the batch has been rejected with `error`, so the client
+    /// knows which transaction has to be fixed. The message is the one of the `error`.
+    #[error("{error}")]
+    BatchTx {
+        index: usize,
+        tx_hash: TxHash,
+        error: Box<TxAddError>,
+    },
+}
+
+impl TxAddError {
+    /// Creates the `IncorrectTx` error for the transaction of the given account.
+    pub fn incorrect_tx(account: Address, reason: impl Into<TransactionError>) -> Self {
+        Self::IncorrectTx {
+            account,
+            reason: reason.into(),
+        }
+    }
+
+    /// Wraps the rejection of the batch transaction at `index`.
+    pub fn batch_tx(index: usize, tx_hash: TxHash, error: TxAddError) -> Self {
+        Self::BatchTx {
+            index,
+            tx_hash,
+            error: Box::new(error),
+        }
+    }
+
+    /// Returns the actual reason of the rejection, i.e. the error of the batch transaction
+    /// for `BatchTx` and the error itself otherwise.
+    pub fn reason(&self) -> &TxAddError {
+        match self {
+            Self::BatchTx { error, .. } => error.reason(),
+            _ => self,
+        }
+    }
+
+    /// Same as `reason`, but takes the error by value.
+    pub fn into_reason(self) -> TxAddError {
+        match self {
+            Self::BatchTx { error, .. } => (*error).into_reason(),
+            _ => self,
+        }
+    }
+
+    /// Index and hash of the rejected batch transaction, if the error is attributed to one.
+    pub fn batch_tx_location(&self) -> Option<(usize, TxHash)> {
+        match self {
+            Self::BatchTx { index, tx_hash, .. } => Some((*index, *tx_hash)),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of the response rejecting the transaction.
+    pub fn to_http_status(&self) -> StatusCode {
+        match self.reason() {
+            Self::DbError
+            | Self::SignatureCheckUnavailable
+            | Self::SignatureCheckTimeout
+            | Self::EthereumNodeUnavailable
+            | Self::ScreeningUnavailable
+            | Self::Draining => StatusCode::SERVICE_UNAVAILABLE,
+            Self::AddressRejected { .. } => StatusCode::FORBIDDEN,
+            Self::Other => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceOverloaded | Self::MempoolFull | Self::WithdrawalLimitExceeded { .. } => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Signer of the Ethereum signature, if it was recovered but doesn't match the expected one.
+    pub fn recovered_signer(&self) -> Option<Address> {
+        match self.reason() {
+            Self::SignerMismatch { recovered, .. } => Some(*recovered),
+            Self::IncorrectEthSignature { recovered, .. } => *recovered,
+            _ => None,
+        }
+    }
+
+    /// Stable machine-readable code of the error.
+    pub fn to_error_code(&self) -> &'static str {
+        match self {
+            Self::NonceMismatch => "NONCE_MISMATCH",
+            Self::ReplacementUnderpriced => "REPLACEMENT_UNDERPRICED",
+            Self::NonceTooHigh => "NONCE_TOO_HIGH",
+            Self::TooManyAccountTxs => "TOO_MANY_ACCOUNT_TXS",
+            Self::MempoolFull => "MEMPOOL_FULL",
+            Self::IncorrectTx { .. } => "INCORRECT_TX",
+            Self::TxFeeTooLow => "TX_FEE_TOO_LOW",
+            Self::TxBatchFeeTooLow => "TX_BATCH_FEE_TOO_LOW",
+            Self::EIP1271SignatureVerificationFail => "EIP1271_SIGNATURE_VERIFICATION_FAIL",
+            Self::MissingEthSignature => "MISSING_ETH_SIGNATURE",
+            Self::IncorrectEthSignature { .. } => "INCORRECT_ETH_SIGNATURE",
+            Self::SignerMismatch { .. } => "SIGNER_MISMATCH",
+            Self::Eip1271Rejected => "EIP1271_REJECTED",
+            Self::MalformedSignature => "MALFORMED_ETH_SIGNATURE",
+            Self::UnsupportedSignatureType => "UNSUPPORTED_ETH_SIGNATURE_TYPE",
+            Self::ChangePkNotAuthorized => "CHANGE_PK_NOT_AUTHORIZED",
+            Self::ChangePkAuthorizationNotConfirmed => "CHANGE_PK_AUTHORIZATION_NOT_CONFIRMED",
+            Self::Other => "INTERNAL_ERROR",
+            Self::DbError => "DB_UNAVAILABLE",
+            Self::EmptyBatch => "EMPTY_BATCH",
+            Self::BatchTooBig => "BATCH_TOO_BIG",
+            Self::BatchWithdrawalsOverload => "BATCH_WITHDRAWALS_OVERLOAD",
+            Self::BatchFeeTokenMismatch(..) => "BATCH_FEE_TOKEN_MISMATCH",
+            Self::EthSignaturesLimitExceeded => "ETH_SIGNATURES_LIMIT_EXCEEDED",
+            Self::NotYetValid => "NOT_YET_VALID",
+            Self::SignatureCheckUnavailable => "SIGNATURE_CHECK_UNAVAILABLE",
+            Self::SignatureCheckTimeout => "SIGNATURE_CHECK_TIMEOUT",
+            Self::EthereumNodeUnavailable => "ETHEREUM_NODE_UNAVAILABLE",
+            Self::ServiceOverloaded => "SERVICE_OVERLOADED",
+            Self::AddressRejected { .. } => "ADDRESS_REJECTED",
+            Self::ScreeningUnavailable => "SCREENING_UNAVAILABLE",
+            Self::Draining => "SERVICE_DRAINING",
+            Self::WithdrawalLimitExceeded { .. } => "WITHDRAWAL_LIMIT_EXCEEDED",
+            Self::WithdrawalDelayed { .. } => "WITHDRAWAL_DELAYED",
+            Self::BatchTx { error, .. } => error.to_error_code(),
+        }
+    }
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -165,14 +165,25 @@ impl ForcedExit {
         }
     }
 
+    /// Returns the signature, the message it signs in the current version of the transaction
+    /// and the cache of the signer, so the signature can be verified in a batch.
+    pub(crate) fn signature_data(
+        &mut self,
+    ) -> (&TxSignature, Vec<u8>, &mut VerifiedSignatureCache) {
+        let msg = self.get_bytes();
+        (&self.signature, msg, &mut self.cached_signer)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message. The format is:
     ///
     /// ForcedExit {token} to: {target}
     /// [Fee: {fee} {token}]
+    /// [Valid from: {valid_from}]
+    /// [Valid until: {valid_until}]
     ///
-    /// Note that the second line is optional.
+    /// Note that the lines in brackets are optional.
     pub fn get_ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
         let mut message = format!(
             "ForcedExit {token} to: {to:?}",
@@ -189,6 +200,9 @@ impl ForcedExit {
                 .as_str(),
             );
         }
+        if let Some(time_range) = &self.time_range {
+            time_range.append_ethereum_sign_message_part(&mut message);
+        }
         message
     }
 
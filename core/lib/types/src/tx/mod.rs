@@ -32,21 +32,26 @@ pub use self::{
     version::TxVersion,
     withdraw::Withdraw,
     withdraw_nft::WithdrawNFT,
-    zksync_tx::{EthSignData, SignedZkSyncTx, ZkSyncTx},
+    zksync_tx::{EthSignData, EthSigner, SignedZkSyncTx, ZkSyncTx},
 };
 
 // Re-export primitives associated with transactions.
 pub use self::primitives::{
+    aggregated_signature::AggregatedSignature,
     eip1271_signature::EIP1271Signature,
-    eth_batch_sign_data::EthBatchSignData,
+    eip712_signature::{EIP712Signature, Eip712Domain},
+    eth_batch_sign_data::{BatchMessageFormat, BatchMessageFormats, EthBatchSignData},
     eth_batch_signature::EthBatchSignatures,
     eth_signature::{TxEthSignature, TxEthSignatureVariant},
-    packed_eth_signature::PackedEthSignature,
+    packed_eth_signature::{PackedETHSignatureError, PackedEthSignature},
     packed_public_key::PackedPublicKey,
     packed_signature::PackedSignature,
     signature::TxSignature,
+    signed_message::{MessageVersion, SignedMessage},
     time_range::TimeRange,
     tx_hash::TxHash,
 };
 
-pub(crate) use self::primitives::signature_cache::VerifiedSignatureCache;
+pub(crate) use self::primitives::signature_cache::{
+    verify_and_cache_signers, VerifiedSignatureCache,
+};
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zksync_basic_types::Address;
+use zksync_utils::ZeroPrefixHexSerde;
+
+/// Single BLS signature aggregated from the signatures of several accounts.
+///
+/// Not verified by the server yet: this type only reserves the representation
+/// of such signatures in the serialized format.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregatedSignature {
+    /// Aggregated signature bytes.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub signature: Vec<u8>,
+    /// Addresses of the accounts which participated in the aggregation.
+    pub signers: Vec<Address>,
+}
+
+impl fmt::Display for AggregatedSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.signature))
+    }
+}
@@ -0,0 +1,97 @@
+use parity_crypto::Keccak256;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zksync_basic_types::{Address, H256, U256};
+
+use super::packed_eth_signature::{PackedETHSignatureError, PackedEthSignature};
+
+/// Domain of the EIP-712 typed data signed by the wallets exposing `eth_signTypedData_v4`
+/// instead of `personal_sign`.
+///
+/// The typed data wraps the same human-readable message which is signed with `personal_sign`:
+///
+/// ```text
+/// EIP712Domain(string name,string version,uint256 chainId)
+/// Message(string message)
+/// ```
+///
+/// with the domain name `zkSync` and version `1`. Chain ID binds the signature to the network,
+/// so it can't be replayed on the testnet and vice versa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip712Domain {
+    pub chain_id: u64,
+}
+
+impl Eip712Domain {
+    pub const NAME: &'static str = "zkSync";
+    pub const VERSION: &'static str = "1";
+    const DOMAIN_TYPE: &'static str = "EIP712Domain(string name,string version,uint256 chainId)";
+    const MESSAGE_TYPE: &'static str = "Message(string message)";
+
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id }
+    }
+
+    /// Returns the `hashStruct` of the domain.
+    pub fn separator(&self) -> H256 {
+        let mut chain_id = [0u8; 32];
+        U256::from(self.chain_id).to_big_endian(&mut chain_id);
+
+        let mut encoded = Vec::with_capacity(4 * 32);
+        encoded.extend_from_slice(&Self::DOMAIN_TYPE.as_bytes().keccak256());
+        encoded.extend_from_slice(&Self::NAME.as_bytes().keccak256());
+        encoded.extend_from_slice(&Self::VERSION.as_bytes().keccak256());
+        encoded.extend_from_slice(&chain_id);
+        encoded.keccak256().into()
+    }
+
+    /// Returns the hash the typed data with the given message is signed over,
+    /// i.e. `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn message_hash(&self, message: &str) -> H256 {
+        let mut struct_encoded = Vec::with_capacity(2 * 32);
+        struct_encoded.extend_from_slice(&Self::MESSAGE_TYPE.as_bytes().keccak256());
+        struct_encoded.extend_from_slice(&message.as_bytes().keccak256());
+
+        let mut bytes = Vec::with_capacity(2 + 2 * 32);
+        bytes.extend_from_slice(b"\x19\x01");
+        bytes.extend_from_slice(self.separator().as_bytes());
+        bytes.extend_from_slice(&struct_encoded.keccak256());
+        bytes.keccak256().into()
+    }
+}
+
+/// ECDSA signature of the EIP-712 typed data, see `Eip712Domain`.
+/// Serialized in the same way as `PackedEthSignature`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EIP712Signature(pub PackedEthSignature);
+
+impl EIP712Signature {
+    /// Signs the typed data with the message, the same way `eth_signTypedData_v4` does.
+    pub fn sign(
+        private_key: &H256,
+        domain: &Eip712Domain,
+        message: &str,
+    ) -> Result<Self, PackedETHSignatureError> {
+        PackedEthSignature::sign_hash(private_key, &domain.message_hash(message)).map(Self)
+    }
+
+    /// Checks that the signature is well-formed, see `PackedEthSignature::normalize`.
+    pub fn normalize(&self) -> Result<Self, PackedETHSignatureError> {
+        self.0.normalize().map(Self)
+    }
+
+    /// Returns the signer of the typed data with the message.
+    pub fn recover_signer(
+        &self,
+        domain: &Eip712Domain,
+        message: &str,
+    ) -> Result<Address, PackedETHSignatureError> {
+        self.0.hash_recover_signer(&domain.message_hash(message))
+    }
+}
+
+impl fmt::Display for EIP712Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0.serialize_packed()))
+    }
+}
@@ -1,18 +1,89 @@
 // External uses
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 // Workspace uses
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 // Local uses
-use super::eth_signature::TxEthSignature;
+use super::{eth_signature::TxEthSignature, signed_message::SignedMessage};
 use crate::{Token, ZkSyncTx};
 use thiserror::Error;
 
+/// Format of the message signed for the whole batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BatchMessageFormat {
+    /// Human-readable message listing the transactions of every sender along with
+    /// its nonce, see `EthBatchSignData::new`.
+    Structured,
+    /// Hash of the transactions bytes. Only batches of the backwards compatible transactions
+    /// can be signed this way, see `EthBatchSignData::get_old_ethereum_batch_message`.
+    Legacy,
+}
+
+impl BatchMessageFormat {
+    /// All the formats in the order the batch signatures are checked against them.
+    pub const ALL: [BatchMessageFormat; 2] = [Self::Structured, Self::Legacy];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Structured => "structured",
+            Self::Legacy => "legacy",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        match self {
+            Self::Structured => 0b01,
+            Self::Legacy => 0b10,
+        }
+    }
+}
+
+/// Set of the batch message formats, e.g. the ones accepted by the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchMessageFormats(u8);
+
+impl BatchMessageFormats {
+    pub fn all() -> Self {
+        BatchMessageFormat::ALL
+            .iter()
+            .fold(Self::default(), |formats, &format| formats.with(format))
+    }
+
+    pub fn with(self, format: BatchMessageFormat) -> Self {
+        Self(self.0 | format.bit())
+    }
+
+    pub fn without(self, format: BatchMessageFormat) -> Self {
+        Self(self.0 & !format.bit())
+    }
+
+    pub fn contains(self, format: BatchMessageFormat) -> bool {
+        self.0 & format.bit() != 0
+    }
+
+    /// Iterates over the formats in the order of `BatchMessageFormat::ALL`.
+    pub fn iter(self) -> impl Iterator<Item = BatchMessageFormat> {
+        BatchMessageFormat::ALL
+            .iter()
+            .copied()
+            .filter(move |&format| self.contains(format))
+    }
+}
+
+impl fmt::Display for BatchMessageFormats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = itertools::join(self.iter().map(BatchMessageFormat::name), ", ");
+        f.write_str(if names.is_empty() { "none" } else { &names })
+    }
+}
+
 /// Encapsulates transactions batch signature data. Should only be created via `new()`
 /// as long as errors are possible.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EthBatchSignData {
     pub signatures: Vec<TxEthSignature>,
-    pub message: Vec<u8>,
+    pub message: SignedMessage,
 }
 
 impl EthBatchSignData {
@@ -29,7 +100,7 @@ impl EthBatchSignData {
             return Err(EmptyTxBatch);
         }
 
-        let message = EthBatchSignData::get_batch_sign_message(txs);
+        let message = SignedMessage::Text(EthBatchSignData::batch_sign_message(txs));
 
         Ok(EthBatchSignData {
             signatures,
@@ -39,12 +110,16 @@ impl EthBatchSignData {
 
     /// Construct the message user is expected to sign for the given batch.
     pub fn get_batch_sign_message(txs: Vec<(ZkSyncTx, Token, Address)>) -> Vec<u8> {
+        EthBatchSignData::batch_sign_message(txs).into_bytes()
+    }
+
+    fn batch_sign_message(txs: Vec<(ZkSyncTx, Token, Address)>) -> String {
         let grouped = txs.into_iter().group_by(|tx| tx.2);
         let mut iter = grouped.into_iter().peekable();
         // The message is empty if there're no transactions.
         let first = match iter.next() {
             Some(group) => group,
-            None => return Vec::new(),
+            None => return String::new(),
         };
         // Check whether there're mutiple addresses in the batch, concatenate their
         // transaction messages with `From: {address}` separator.
@@ -62,7 +137,6 @@ impl EthBatchSignData {
             }
             None => EthBatchSignData::group_message(first.1, None),
         }
-        .into_bytes()
     }
 
     fn group_message<I>(iter: I, address: Option<Address>) -> String
@@ -94,6 +168,38 @@ impl EthBatchSignData {
         }
     }
 
+    /// Returns the keccak256 hash of the signed batch message.
+    pub fn message_hash(&self) -> H256 {
+        H256::from(tiny_keccak::keccak256(self.message.as_bytes()))
+    }
+
+    /// Returns the message of the given format the batch signatures are expected to be made for.
+    ///
+    /// The structured message is the one the batch signature data has been created with,
+    /// the legacy one is built from the transactions. Returns `None` if the batch can't be
+    /// signed in the given format.
+    pub fn message_in_format<'a, I>(
+        &self,
+        format: BatchMessageFormat,
+        txs: I,
+    ) -> Option<SignedMessage>
+    where
+        I: Iterator<Item = &'a ZkSyncTx> + Clone,
+    {
+        match format {
+            BatchMessageFormat::Structured => Some(self.message.clone()),
+            BatchMessageFormat::Legacy => {
+                if !txs.clone().all(ZkSyncTx::is_backwards_compatible) {
+                    return None;
+                }
+                // Old message is a hash, so it's signed as binary data.
+                Some(SignedMessage::Bytes(
+                    EthBatchSignData::get_old_ethereum_batch_message(txs),
+                ))
+            }
+        }
+    }
+
     /// Returns an old-format message that should be signed by Ethereum account key.
     /// Needed for backwards compatibility.
     pub fn get_old_ethereum_batch_message<'a, I>(txs: I) -> Vec<u8>
@@ -1,17 +1,21 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-use crate::tx::{EIP1271Signature, PackedEthSignature};
+use crate::tx::{AggregatedSignature, EIP1271Signature, EIP712Signature, PackedEthSignature};
 
 /// Representation of the signature secured by L1.
 /// May be either a signature generated via Ethereum private key
-/// corresponding to the account address,
-/// or on-chain signature via EIP-1271.
+/// corresponding to the account address (of the `personal_sign` message
+/// or of the EIP-712 typed data), or on-chain signature via EIP-1271.
+/// Aggregated BLS signatures are reserved for the future use
+/// and are rejected by the server.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", content = "signature")]
 pub enum TxEthSignature {
     EthereumSignature(PackedEthSignature),
     EIP1271Signature(EIP1271Signature),
+    AggregatedBLS(AggregatedSignature),
+    EIP712Signature(EIP712Signature),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -65,6 +69,8 @@ impl Display for TxEthSignature {
                 write!(f, "0x{}", hex::encode(sign.serialize_packed()))
             }
             Self::EIP1271Signature(sign) => write!(f, "0x{}", hex::encode(sign.0.clone())),
+            Self::AggregatedBLS(sign) => write!(f, "{}", sign),
+            Self::EIP712Signature(sign) => write!(f, "{}", sign),
         }
     }
 }
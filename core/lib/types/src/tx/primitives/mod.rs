@@ -1,4 +1,6 @@
+pub mod aggregated_signature;
 pub mod eip1271_signature;
+pub mod eip712_signature;
 pub mod eth_batch_sign_data;
 pub mod eth_batch_signature;
 pub mod eth_signature;
@@ -7,6 +9,7 @@ pub mod packed_public_key;
 pub mod packed_signature;
 pub mod signature;
 pub mod signature_cache;
+pub mod signed_message;
 pub mod time_range;
 pub mod tx_hash;
 
@@ -10,6 +10,7 @@ use zksync_utils::ZeroPrefixHexSerde;
 
 /// Struct used for working with ethereum signatures created using eth_sign (using geth, ethers.js, etc)
 /// message is serialized as 65 bytes long `0x` prefixed string.
+/// 64 bytes long EIP-2098 compact signatures are accepted on deserialization as well.
 ///
 /// Some notes on implementation of methods of this structure:
 ///
@@ -20,6 +21,7 @@ use zksync_utils::ZeroPrefixHexSerde;
 /// That is why:
 /// 1) when we create this structure by deserialization of message produced by user
 /// we subtract 27 from v in `ETHSignature` if necessary and store it in the `ETHSignature` structure this way.
+/// EIP-155 `v` (35 or more) is unfolded into the recovery id at this point as well.
 /// 2) When we serialize/create this structure we add 27 to v in `ETHSignature`.
 ///
 /// This way when we have methods that consumes &self we can be sure that ETHSignature::recover_signer works
@@ -34,6 +36,12 @@ pub enum PackedETHSignatureError {
     LengthMismatched,
     #[error("Crypto Error: {0:?}")]
     CryptoError(#[from] parity_crypto::publickey::Error),
+    #[error("Invalid recovery id: {0}")]
+    InvalidRecoveryId(u8),
+    #[error("Signature is malleable: `s` is in the upper half of the curve order")]
+    HighS,
+    #[error("Signature `r` or `s` value is out of range")]
+    OutOfRange,
 }
 
 impl PackedEthSignature {
@@ -43,20 +51,56 @@ impl PackedEthSignature {
     }
 
     pub fn deserialize_packed(bytes: &[u8]) -> Result<Self, PackedETHSignatureError> {
-        if bytes.len() != 65 {
-            return Err(PackedETHSignatureError::LengthMismatched);
-        }
-
         let mut bytes_array = [0u8; 65];
-        bytes_array.copy_from_slice(bytes);
-
-        if bytes_array[64] >= 27 {
-            bytes_array[64] -= 27;
+        match bytes.len() {
+            65 => bytes_array.copy_from_slice(bytes),
+            // EIP-2098 compact form `r || yParityAndS`: the recovery id is the highest bit of `s`.
+            64 => {
+                bytes_array[..64].copy_from_slice(bytes);
+                bytes_array[64] = bytes_array[32] >> 7;
+                bytes_array[32] &= 0x7f;
+            }
+            _ => return Err(PackedETHSignatureError::LengthMismatched),
         }
 
+        bytes_array[64] = match bytes_array[64] {
+            // EIP-155 `v` is folded with the chain ID: `v = 35 + 2 * chain_id + recovery_id`.
+            v if v >= 35 => (v - 35) % 2,
+            v if v >= 27 => v - 27,
+            // Either a recovery id or an invalid value rejected by `normalize`.
+            v => v,
+        };
+
         Ok(PackedEthSignature(ETHSignature::from(bytes_array)))
     }
 
+    /// Checks that the signature is well-formed and brings `v` to the form expected
+    /// by `signature_recover_signer`.
+    ///
+    /// Besides 0/1 and 27/28, `v` can be folded with the chain ID by wallets applying
+    /// EIP-155 to the `personal_sign` (`v = 35 + 2 * chain_id + recovery_id`), any other
+    /// value is rejected. Malleable signatures with high `s` are rejected as well.
+    pub fn normalize(&self) -> Result<Self, PackedETHSignatureError> {
+        // Note that 27/28 and EIP-155 `v` are already brought to 0/1 on deserialization,
+        // so raw values from 2 to 26 and from 29 to 34 are the only ones left here.
+        let recovery_id = match self.0.v() {
+            v @ 0..=1 => v,
+            v => return Err(PackedETHSignatureError::InvalidRecoveryId(v)),
+        };
+        let signature = ETHSignature::from_rsv(
+            &H256::from_slice(self.0.r()),
+            &H256::from_slice(self.0.s()),
+            recovery_id,
+        );
+        if !signature.is_low_s() {
+            return Err(PackedETHSignatureError::HighS);
+        }
+        if !signature.is_valid() {
+            return Err(PackedETHSignatureError::OutOfRange);
+        }
+        Ok(PackedEthSignature(signature))
+    }
+
     /// Signs message using ethereum private key, results are identical to signature created
     /// using `geth`, `ethecore/lib/types/src/gas_counter.rsrs.js`, etc. No hashing and prefixes required.
     pub fn sign(
@@ -69,6 +113,17 @@ impl PackedEthSignature {
         Ok(PackedEthSignature(signature))
     }
 
+    /// Signs the 32-byte hash as is, without the `personal_sign` prefix,
+    /// e.g. the hash of the EIP-712 typed data.
+    pub fn sign_hash(
+        private_key: &H256,
+        hash: &H256,
+    ) -> Result<PackedEthSignature, PackedETHSignatureError> {
+        let secret_key = (*private_key).into();
+        let signature = sign(&secret_key, hash)?;
+        Ok(PackedEthSignature(signature))
+    }
+
     fn message_to_signed_bytes(msg: &[u8]) -> H256 {
         let prefix = format!("\x19Ethereum Signed Message:\n{}", msg.len());
         let mut bytes = Vec::with_capacity(prefix.len() + msg.len());
@@ -86,6 +141,12 @@ impl PackedEthSignature {
         Ok(public_to_address(&public_key))
     }
 
+    /// Returns ethereum address of the signer of the hash signed with `sign_hash`.
+    pub fn hash_recover_signer(&self, hash: &H256) -> Result<Address, PackedETHSignatureError> {
+        let public_key = recover(&self.0, hash)?;
+        Ok(public_to_address(&public_key))
+    }
+
     /// Get Ethereum address from private key.
     pub fn address_from_private_key(
         private_key: &H256,
@@ -4,6 +4,7 @@ use crate::Engine;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zksync_crypto::{
+    batch_signature::verify_musig_rescue_batch,
     franklin_crypto::{
         eddsa::{PrivateKey, PublicKey, Seed},
         jubjub::FixedGenerators,
@@ -83,6 +84,27 @@ impl TxSignature {
         }
     }
 
+    /// Verifies the signatures of the messages at once.
+    /// Returns `true` only if all the signatures are valid.
+    pub fn verify_musig_rescue_batch(signatures: &[(&TxSignature, &[u8])]) -> bool {
+        let hashed_msgs: Vec<_> = signatures
+            .iter()
+            .map(|(_, msg)| rescue_hash_tx_msg(msg))
+            .collect();
+        let batch: Vec<_> = signatures
+            .iter()
+            .zip(&hashed_msgs)
+            .map(|((signature, _), hashed_msg)| {
+                (
+                    &signature.pub_key.0,
+                    &signature.signature.0,
+                    hashed_msg.as_slice(),
+                )
+            })
+            .collect();
+        verify_musig_rescue_batch(&batch)
+    }
+
     /// Deserializes signature from packed bytes representation.
     /// [0..32] - packed pubkey of the signer.
     /// [32..96] - packed r,s of the signature
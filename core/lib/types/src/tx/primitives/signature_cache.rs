@@ -1,5 +1,6 @@
 use crate::account::PubKeyHash;
 use crate::tx::version::TxVersion;
+use crate::tx::TxSignature;
 
 /// Stores precomputed signature verification result to speedup tx execution
 #[derive(Debug, Clone)]
@@ -15,3 +16,31 @@ impl Default for VerifiedSignatureCache {
         Self::NotCached
     }
 }
+
+/// Verifies the signatures of the transactions at once and caches the signers, skipping
+/// the transactions which signers are already cached. Each signature is expected to sign
+/// the message of the current version of the transaction.
+///
+/// Nothing is cached if the batch is invalid, so the signatures are verified one by one
+/// when the transactions are executed. Returns `true` if all the signatures are valid.
+pub(crate) fn verify_and_cache_signers<'a>(
+    signatures: impl IntoIterator<Item = (&'a TxSignature, Vec<u8>, &'a mut VerifiedSignatureCache)>,
+) -> bool {
+    let signatures: Vec<_> = signatures
+        .into_iter()
+        .filter(|(_, _, cache)| matches!(cache, VerifiedSignatureCache::NotCached))
+        .collect();
+    let batch: Vec<_> = signatures
+        .iter()
+        .map(|(signature, msg, _)| (*signature, msg.as_slice()))
+        .collect();
+    if !TxSignature::verify_musig_rescue_batch(&batch) {
+        return false;
+    }
+
+    for (signature, _, cache) in signatures {
+        let signer = PubKeyHash::from_pubkey(&signature.pub_key.0);
+        *cache = VerifiedSignatureCache::Cached(Some((signer, TxVersion::V1)));
+    }
+    true
+}
@@ -0,0 +1,202 @@
+use serde::{
+    de::{value::SeqAccessDeserializer, Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, ops::Deref};
+
+use super::time_range::{VALID_FROM_LINE, VALID_UNTIL_LINE};
+
+/// Message signed with the Ethereum key.
+///
+/// Some wallets sign a human-readable UTF-8 string, while others sign raw binary data,
+/// e.g. a hash. The signature is made over the bytes of the message in both cases, but
+/// the text and the binary data it represents (say, a hex-encoded hash) are different
+/// messages, so the representation the signature is expected for is explicit.
+///
+/// Text is serialized as a string and binary data as a byte array. This is also how
+/// the messages stored as plain byte vectors and strings are deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignedMessage {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl SignedMessage {
+    /// Returns the bytes the signature is made over: UTF-8 encoding of the text
+    /// or the binary data as is.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Bytes(bytes) => bytes,
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Text(text) => text.into_bytes(),
+            Self::Bytes(bytes) => bytes,
+        }
+    }
+
+    /// Name of the message representation, e.g. for logging.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Text(_) => "text",
+            Self::Bytes(_) => "bytes",
+        }
+    }
+}
+
+/// Version of the human-readable message format signed with the Ethereum key.
+///
+/// Messages of the first version don't commit to the chain, so their signatures are valid
+/// on every network the account exists on, e.g. on both testnet and mainnet. The second
+/// version binds the message to the chain by appending its ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessageVersion {
+    /// Message as is.
+    Unbound,
+    /// Message followed by the `Chain ID: {chain_id}` line.
+    ChainBound,
+}
+
+impl MessageVersion {
+    pub const LATEST: MessageVersion = Self::ChainBound;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Unbound => "unbound",
+            Self::ChainBound => "chain_bound",
+        }
+    }
+}
+
+impl SignedMessage {
+    /// Returns the message of the given version for the chain with `chain_id`.
+    ///
+    /// Only the text messages are versioned: binary data, e.g. the hash signed for
+    /// the legacy batch message, is returned as is for the unbound version only.
+    pub fn in_version(&self, version: MessageVersion, chain_id: u64) -> Option<SignedMessage> {
+        match (version, self) {
+            (MessageVersion::Unbound, message) => Some(message.clone()),
+            (MessageVersion::ChainBound, Self::Text(text)) => Some(Self::Text(format!(
+                "{text}\n\
+                Chain ID: {chain_id}",
+                text = text,
+                chain_id = chain_id
+            ))),
+            (MessageVersion::ChainBound, Self::Bytes(_)) => None,
+        }
+    }
+
+    /// Returns the text message without the bounds of the time ranges, i.e. in the format
+    /// signed before they were included into the messages of the transactions, batches and
+    /// swap orders, see `TimeRange::append_ethereum_sign_message_part`.
+    ///
+    /// Returns `None` if the message doesn't contain any bounds or is binary.
+    pub fn without_time_range(&self) -> Option<SignedMessage> {
+        let text = match self {
+            Self::Text(text) => text,
+            Self::Bytes(_) => return None,
+        };
+        let is_bound =
+            |line: &str| line.starts_with(VALID_FROM_LINE) || line.starts_with(VALID_UNTIL_LINE);
+        let lines: Vec<_> = text.split('\n').collect();
+        let untimed_lines: Vec<_> = lines
+            .iter()
+            .copied()
+            .filter(|line| !is_bound(*line))
+            .collect();
+        if untimed_lines.len() == lines.len() {
+            return None;
+        }
+        Some(Self::Text(untimed_lines.join("\n")))
+    }
+}
+
+impl Default for SignedMessage {
+    fn default() -> Self {
+        Self::Bytes(Vec::new())
+    }
+}
+
+impl Deref for SignedMessage {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for SignedMessage {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl From<String> for SignedMessage {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for SignedMessage {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<u8>> for SignedMessage {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl Serialize for SignedMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Text(text) => serializer.serialize_str(text),
+            Self::Bytes(bytes) => bytes.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SignedMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StringOrVec;
+
+        impl<'de> Visitor<'de> for StringOrVec {
+            type Value = SignedMessage;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte array or a string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(SignedMessage::Text(v.to_owned()))
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(SignedMessage::Bytes(v.to_vec()))
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                Deserialize::deserialize(SeqAccessDeserializer::new(seq)).map(SignedMessage::Bytes)
+            }
+        }
+
+        deserializer.deserialize_any(StringOrVec)
+    }
+}
@@ -1,9 +1,10 @@
 use std::str::FromStr;
 // External uses
 // Workspace uses
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256, U256};
 use zksync_utils::format_units;
 // Local uses
+use super::packed_eth_signature::PackedETHSignatureError;
 use crate::{tx::*, AccountId, Nonce, Token, TokenId, TokenKind, Transfer, Withdraw, ZkSyncTx};
 
 fn get_transfer() -> Transfer {
@@ -96,7 +97,7 @@ fn test_batch_message() {
         .collect::<Vec<_>>();
     // Shouldn't fail.
     let batch_sign_data = EthBatchSignData::new(txs, Vec::new()).unwrap();
-    assert_eq!(batch_sign_data.message, expected.into_bytes());
+    assert_eq!(batch_sign_data.message, SignedMessage::Text(expected));
 
     // Batch from a single wallet, send withdraw without fee, cover the fee with phantom transfer.
     let mut withdraw = get_withdraw();
@@ -130,3 +131,244 @@ fn test_batch_message() {
     let message = EthBatchSignData::get_batch_sign_message(txs);
     assert_eq!(message, expected.into_bytes());
 }
+
+#[test]
+fn test_message_versions() {
+    let token = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
+    let transfer = ZkSyncTx::from(get_transfer());
+    let text = transfer.get_ethereum_sign_message(token).unwrap();
+    let message = SignedMessage::Text(text.clone());
+
+    assert_eq!(
+        message.in_version(MessageVersion::Unbound, 1),
+        Some(message.clone())
+    );
+    assert_eq!(
+        message.in_version(MessageVersion::ChainBound, 1),
+        Some(SignedMessage::Text(format!("{}\nChain ID: 1", text)))
+    );
+    // Messages bound to different chains are different.
+    assert_ne!(
+        message.in_version(MessageVersion::ChainBound, 1),
+        message.in_version(MessageVersion::ChainBound, 4)
+    );
+
+    // Binary messages can't be bound to the chain.
+    let message = SignedMessage::Bytes(vec![1, 2, 3]);
+    assert_eq!(
+        message.in_version(MessageVersion::Unbound, 1),
+        Some(message.clone())
+    );
+    assert_eq!(message.in_version(MessageVersion::ChainBound, 1), None);
+}
+
+/// Checks that the bounds of the time range are removed from the messages,
+/// so they're the same as the ones signed before the bounds were included.
+#[test]
+fn test_message_without_time_range() {
+    let token = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
+    let transfer = |time_range: TimeRange| {
+        let mut transfer = get_transfer();
+        transfer.time_range = Some(time_range);
+        ZkSyncTx::from(transfer)
+    };
+    let message =
+        |tx: &ZkSyncTx| SignedMessage::Text(tx.get_ethereum_sign_message(token.clone()).unwrap());
+    let untimed = transfer(TimeRange::default());
+    let timed = transfer(TimeRange::new(1_000, 2_000));
+
+    assert_eq!(
+        message(&timed).without_time_range(),
+        Some(message(&untimed))
+    );
+    assert_eq!(
+        message(&transfer(TimeRange::new(0, 2_000))).without_time_range(),
+        Some(message(&untimed))
+    );
+    // Nothing is removed from the messages without the bounds.
+    assert_eq!(message(&untimed).without_time_range(), None);
+    assert_eq!(
+        SignedMessage::Bytes(vec![1, 2, 3]).without_time_range(),
+        None
+    );
+
+    // Bounds of every transaction of the batch are removed.
+    let batch_message = |txs: Vec<ZkSyncTx>| {
+        let txs = txs
+            .into_iter()
+            .map(|tx| (tx.clone(), token.clone(), tx.account()))
+            .collect();
+        let message = EthBatchSignData::get_batch_sign_message(txs);
+        SignedMessage::Text(String::from_utf8(message).unwrap())
+    };
+    assert_eq!(
+        batch_message(vec![timed.clone(), timed]).without_time_range(),
+        Some(batch_message(vec![untimed.clone(), untimed]))
+    );
+}
+
+/// Checks that the raw Ethereum signatures are normalized before the recovery
+/// and the malformed ones are rejected.
+#[test]
+fn test_eth_signature_normalization() {
+    // Order of the secp256k1 curve.
+    const CURVE_ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+    let private_key = H256::repeat_byte(0x11);
+    let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+    let message = b"hello-world";
+    let packed = PackedEthSignature::sign(&private_key, message)
+        .unwrap()
+        .serialize_packed();
+    let recovery_id = packed[64] - 27;
+
+    let with_v = |v: u8| {
+        let mut bytes = packed;
+        bytes[64] = v;
+        PackedEthSignature::deserialize_packed(&bytes).unwrap()
+    };
+    let recover = |signature: PackedEthSignature| {
+        signature
+            .normalize()
+            .and_then(|signature| signature.signature_recover_signer(message))
+    };
+
+    // 27/28 and 0/1 encodings, EIP-155 encoding for chain IDs 1 and 5.
+    for v in &[
+        27 + recovery_id,
+        recovery_id,
+        35 + 2 + recovery_id,
+        35 + 10 + recovery_id,
+    ] {
+        assert_eq!(recover(with_v(*v)).unwrap(), address, "v = {}", v);
+    }
+
+    // Neither a recovery id nor an EIP-155 value.
+    for v in &[2, 8, 26, 29, 34] {
+        assert!(matches!(
+            with_v(*v).normalize(),
+            Err(PackedETHSignatureError::InvalidRecoveryId(_))
+        ));
+    }
+
+    // Malleable counterpart of the signature: `s' = n - s` with the flipped recovery id.
+    let curve_order = U256::from_str_radix(CURVE_ORDER, 16).unwrap();
+    let high_s = curve_order - U256::from_big_endian(&packed[32..64]);
+    let mut bytes = packed;
+    high_s.to_big_endian(&mut bytes[32..64]);
+    bytes[64] = 27 + (recovery_id ^ 1);
+    let signature = PackedEthSignature::deserialize_packed(&bytes).unwrap();
+    assert!(matches!(
+        signature.normalize(),
+        Err(PackedETHSignatureError::HighS)
+    ));
+
+    // Zero `r` and `s`.
+    let mut bytes = [0u8; 65];
+    bytes[64] = 27;
+    let signature = PackedEthSignature::deserialize_packed(&bytes).unwrap();
+    assert!(matches!(
+        signature.normalize(),
+        Err(PackedETHSignatureError::OutOfRange)
+    ));
+
+    // Signature length is checked on deserialization.
+    assert!(matches!(
+        PackedEthSignature::deserialize_packed(&packed[..63]),
+        Err(PackedETHSignatureError::LengthMismatched)
+    ));
+}
+
+/// Checks that the EIP-2098 compact signatures are recovered to the same signer
+/// as the full ones.
+#[test]
+fn test_compact_eth_signature() {
+    let private_key = H256::repeat_byte(0x11);
+    let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+
+    // Signatures of the different messages cover both recovery ids.
+    let mut recovery_ids = Vec::new();
+    for i in 0..16 {
+        let message = format!("hello-world-{}", i);
+        let packed = PackedEthSignature::sign(&private_key, message.as_bytes())
+            .unwrap()
+            .serialize_packed();
+        let recovery_id = packed[64] - 27;
+        recovery_ids.push(recovery_id);
+
+        let mut compact = [0u8; 64];
+        compact.copy_from_slice(&packed[..64]);
+        compact[32] |= recovery_id << 7;
+        let signature = PackedEthSignature::deserialize_packed(&compact).unwrap();
+        assert_eq!(signature.serialize_packed(), packed);
+        let signer = signature
+            .normalize()
+            .and_then(|signature| signature.signature_recover_signer(message.as_bytes()))
+            .unwrap();
+        assert_eq!(signer, address);
+    }
+    assert!(recovery_ids.contains(&0) && recovery_ids.contains(&1));
+
+    let signature = PackedEthSignature::sign(&private_key, b"hello-world").unwrap();
+    let compact = format!("0x{}", hex::encode(&signature.serialize_packed()[..64]));
+    let json = serde_json::json!({ "type": "EthereumSignature", "signature": compact });
+    let deserialized: TxEthSignature = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, TxEthSignature::EthereumSignature(signature));
+}
+
+/// Checks that the aggregated signature is serialized in the same way as
+/// the other Ethereum signature variants.
+#[test]
+fn test_aggregated_signature_serialization() {
+    let signature = TxEthSignature::AggregatedBLS(AggregatedSignature {
+        signature: vec![0xab; 4],
+        signers: vec![Address::repeat_byte(0x01)],
+    });
+    let json = serde_json::to_value(&signature).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "type": "AggregatedBLS",
+            "signature": {
+                "signature": "0xabababab",
+                "signers": ["0x0101010101010101010101010101010101010101"],
+            },
+        })
+    );
+    let deserialized: TxEthSignature = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, signature);
+}
+
+/// Checks that the signer of the EIP-712 typed data is recovered only within the same domain.
+#[test]
+fn test_eip712_signature() {
+    let private_key = H256::random();
+    let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+    let domain = Eip712Domain::new(1);
+    let message = "Transfer 1.0 ETH\nTo: 0x0202020202020202020202020202020202020202";
+
+    let signature = EIP712Signature::sign(&private_key, &domain, message).unwrap();
+    let signature = signature.normalize().unwrap();
+    assert_eq!(signature.recover_signer(&domain, message).unwrap(), address);
+    // Typed data isn't the `personal_sign` message.
+    assert_ne!(
+        signature
+            .0
+            .signature_recover_signer(message.as_bytes())
+            .unwrap(),
+        address
+    );
+    // Signature is bound to the chain.
+    assert_ne!(
+        signature
+            .recover_signer(&Eip712Domain::new(4), message)
+            .unwrap(),
+        address
+    );
+
+    let signature = TxEthSignature::EIP712Signature(signature);
+    let json = serde_json::to_value(&signature).unwrap();
+    assert_eq!(json["type"], "EIP712Signature");
+    let deserialized: TxEthSignature = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, signature);
+}
@@ -1,6 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 
+/// `valid_until` used by the SDKs for the transactions which don't expire.
+pub const MAX_TIMESTAMP: u64 = u32::MAX as u64;
+
+/// Prefixes of the lines the bounds of the range are signed in, see
+/// `TimeRange::append_ethereum_sign_message_part`.
+pub(crate) const VALID_FROM_LINE: &str = "Valid from: ";
+pub(crate) const VALID_UNTIL_LINE: &str = "Valid until: ";
+
 /// Defines time range `[valid_from, valid_until]` for which transaction is valid,
 /// time format is the same as Ethereum (UNIX timestamp in seconds)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -39,6 +47,36 @@ impl TimeRange {
     pub fn intersects(&self, other: Self) -> bool {
         self.valid_from <= other.valid_until && other.valid_from <= self.valid_until
     }
+
+    /// Returns `true` if the range doesn't restrict the execution time, i.e. it starts
+    /// at zero and doesn't end before `MAX_TIMESTAMP`.
+    pub fn is_unbounded(&self) -> bool {
+        self.valid_from == 0 && self.valid_until >= MAX_TIMESTAMP
+    }
+
+    /// Appends the bounds of the range to the message signed by the Ethereum account key,
+    /// so the user signs the time the transaction may be executed in. Nothing is appended
+    /// for the unbounded bounds, so the messages of the transactions without the time
+    /// restrictions stay the same. The format is:
+    ///
+    /// [Valid from: {valid_from}]
+    /// [Valid until: {valid_until}]
+    ///
+    /// Timestamps are UNIX timestamps in seconds.
+    pub fn append_ethereum_sign_message_part(&self, message: &mut String) {
+        let mut push_line = |line: String| {
+            if !message.is_empty() {
+                message.push('\n');
+            }
+            message.push_str(&line);
+        };
+        if self.valid_from != 0 {
+            push_line(format!("{}{}", VALID_FROM_LINE, self.valid_from));
+        }
+        if self.valid_until < MAX_TIMESTAMP {
+            push_line(format!("{}{}", VALID_UNTIL_LINE, self.valid_until));
+        }
+    }
 }
 
 impl Default for TimeRange {
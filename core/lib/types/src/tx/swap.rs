@@ -114,14 +114,15 @@ impl Order {
         };
         message += format!(
             "Ratio: {sell}:{buy}\n\
-            Address: {recipient:?}\n\
-            Nonce: {nonce}",
+            Address: {recipient:?}",
             sell = self.price.0,
             buy = self.price.1,
             recipient = self.recipient_address,
-            nonce = self.nonce
         )
         .as_str();
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
+        message += format!("\nNonce: {}", self.nonce).as_str();
         message
     }
 
@@ -355,6 +356,15 @@ impl Swap {
         }
     }
 
+    /// Returns the signature, the message it signs in the current version of the transaction
+    /// and the cache of the signer, so the signature can be verified in a batch.
+    pub(crate) fn signature_data(
+        &mut self,
+    ) -> (&TxSignature, Vec<u8>, &mut VerifiedSignatureCache) {
+        let msg = self.get_sign_bytes();
+        (&self.signature, msg, &mut self.cached_signer)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
@@ -451,6 +451,46 @@ fn test_musig_rescue_signing_verification() {
     }
 }
 
+/// Checks that the signatures of the transactions are verified at once,
+/// and that the batch is invalid if any of its signatures is invalid.
+#[test]
+fn verify_signatures_batch() {
+    let (pk, _) = gen_pk_and_msg();
+    let mut txs: Vec<ZkSyncTx> = (0..5)
+        .map(|nonce| {
+            let mut transfer = Transfer::new_signed(
+                AccountId(1),
+                Address::repeat_byte(0x01),
+                Address::repeat_byte(0x02),
+                TokenId(0),
+                BigUint::from(100u32),
+                BigUint::from(10u32),
+                Nonce(nonce),
+                Default::default(),
+                &pk,
+            )
+            .expect("failed to sign transfer");
+            transfer.wipe_signer_cache();
+            transfer.into()
+        })
+        .collect();
+
+    // The signature of the other transaction.
+    let mut invalid_txs = txs.clone();
+    if let ZkSyncTx::Transfer(tx) = &mut invalid_txs[2] {
+        tx.signature = txs[3].signature();
+    }
+    assert!(!ZkSyncTx::verify_signatures_batch(&mut invalid_txs));
+
+    assert!(ZkSyncTx::verify_signatures_batch(&mut txs));
+    let signer = PubKeyHash::from_privkey(&pk);
+    for tx in &txs {
+        if let ZkSyncTx::Transfer(tx) = tx {
+            assert_eq!(tx.verify_signature(), Some((signer, TxVersion::V1)));
+        }
+    }
+}
+
 #[test]
 fn test_ethereum_signature_verify_with_serialization() {
     let address: Address = "52312AD6f01657413b2eaE9287f6B9ADaD93D5FE".parse().unwrap();
@@ -564,7 +604,7 @@ fn eth_sign_data_compatibility() {
     assert_eq!(old_eth_sign_data.signature, eth_sign_data.signature);
     assert_eq!(
         old_eth_sign_data.message.as_bytes(),
-        eth_sign_data.message.as_slice()
+        eth_sign_data.message.as_bytes()
     );
     // We are able to encode/decode messages in new format.
     let value = serde_json::to_value(eth_sign_data.clone()).unwrap();
@@ -575,6 +615,65 @@ fn eth_sign_data_compatibility() {
     assert_eq!(deserialized.message, eth_sign_data.message);
 }
 
+/// Checks that the representation of the signed message survives the serialization.
+#[test]
+fn signed_message_serialization() {
+    let text = SignedMessage::Text("Sample text".to_owned());
+    let value = serde_json::to_value(&text).unwrap();
+    assert_eq!(value, serde_json::json!("Sample text"));
+    assert_eq!(
+        serde_json::from_value::<SignedMessage>(value).unwrap(),
+        text
+    );
+
+    let bytes = SignedMessage::Bytes(vec![0xde, 0xad, 0xbe, 0xef]);
+    let value = serde_json::to_value(&bytes).unwrap();
+    assert_eq!(value, serde_json::json!([0xde, 0xad, 0xbe, 0xef]));
+    assert_eq!(
+        serde_json::from_value::<SignedMessage>(value).unwrap(),
+        bytes
+    );
+
+    // Both representations are signed as bytes.
+    assert_eq!(text.as_bytes(), b"Sample text");
+    assert_eq!(bytes.as_bytes(), &[0xde, 0xad, 0xbe, 0xef][..]);
+}
+
+/// Checks that the expected Ethereum signer is the account initiating the transaction,
+/// even if the transaction affects another one.
+#[test]
+fn eth_signer() {
+    let initiator = Address::repeat_byte(0x01);
+    let target = Address::repeat_byte(0x02);
+
+    let forced_exit = ForcedExit::new(
+        AccountId(1),
+        target,
+        TokenId(0),
+        BigUint::from(10u32),
+        Nonce(0),
+        Default::default(),
+        None,
+    );
+    let tx = ZkSyncTx::ForcedExit(Box::new(forced_exit));
+    assert_eq!(tx.account(), target);
+    assert_eq!(tx.eth_signer(), EthSigner::AccountId(AccountId(1)));
+
+    let mint_nft = MintNFT::new(
+        AccountId(1),
+        initiator,
+        H256::random(),
+        target,
+        BigUint::from(10u32),
+        TokenId(0),
+        Nonce(0),
+        None,
+    );
+    let tx = ZkSyncTx::MintNFT(Box::new(mint_nft));
+    assert_eq!(tx.to_account(), Some(target));
+    assert_eq!(tx.eth_signer(), EthSigner::Address(initiator));
+}
+
 #[test]
 fn test_check_signature() {
     let (pk, msg) = gen_pk_and_msg();
@@ -585,3 +684,242 @@ fn test_check_signature() {
 
     assert_eq!(hex::encode(signature), "4e3298ac8cc13868dbbc94ad6fb41085ffe05b3c2eee22f88b05e69b7a5126aea723d7a3e7282ef5a32d9479c9c8dde52b3e3c462dd445dcd8158ebb6edb6000");
 }
+
+/// Checks that the bounded time range is included into the Ethereum message,
+/// while the messages of the transactions without the time restrictions don't change.
+#[test]
+fn time_range_in_eth_sign_message() {
+    let transfer = |time_range: TimeRange| {
+        Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            TokenId(0),
+            BigUint::from(100u32),
+            BigUint::from(1u32),
+            Nonce(3),
+            time_range,
+            None,
+        )
+    };
+    let message = |time_range: TimeRange| transfer(time_range).get_ethereum_sign_message("ETH", 0);
+    let base_message = "Transfer 100.0 ETH to: 0x0202020202020202020202020202020202020202\n\
+                        Fee: 1.0 ETH";
+
+    assert_eq!(
+        message(TimeRange::default()),
+        format!("{}\nNonce: 3", base_message)
+    );
+    assert_eq!(
+        message(TimeRange::new(0, u32::MAX as u64)),
+        format!("{}\nNonce: 3", base_message)
+    );
+    assert_eq!(
+        message(TimeRange::new(1_000, u32::MAX as u64)),
+        format!("{}\nValid from: 1000\nNonce: 3", base_message)
+    );
+    assert_eq!(
+        message(TimeRange::new(1_000, 2_000)),
+        format!(
+            "{}\nValid from: 1000\nValid until: 2000\nNonce: 3",
+            base_message
+        )
+    );
+}
+
+/// Checks that the `no_std` signing library encodes and signs the transactions the same way.
+#[test]
+fn tx_signing_compatibility() {
+    use zksync_tx_signing as signing;
+
+    let (pk, _) = gen_pk_and_msg();
+    let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let time_range = TimeRange::new(1_000, 2_000);
+    let signing_time_range = signing::TimeRange::new(1_000, 2_000);
+    let (amount, fee) = (1_234_500_000u128, 56_000u128);
+
+    let transfer = Transfer::new_signed(
+        AccountId(1),
+        from,
+        to,
+        TokenId(2),
+        BigUint::from(amount),
+        BigUint::from(fee),
+        Nonce(3),
+        time_range,
+        &pk,
+    )
+    .unwrap();
+    let signing_transfer = signing::Transfer {
+        account_id: 1,
+        from: from.0,
+        to: to.0,
+        token: 2,
+        amount,
+        fee,
+        nonce: 3,
+        time_range: signing_time_range,
+    };
+    let bytes = signing_transfer.bytes().unwrap();
+    assert_eq!(bytes, transfer.get_bytes());
+    assert_eq!(
+        signing_transfer.ethereum_sign_message("DAI", 6),
+        transfer.get_ethereum_sign_message("DAI", 6)
+    );
+    let signature = signing::musig::sign(&pk, &bytes);
+    assert_eq!(
+        signature.pub_key.to_vec(),
+        transfer.signature.pub_key.serialize_packed().unwrap()
+    );
+    assert_eq!(
+        signature.signature.to_vec(),
+        transfer.signature.signature.serialize_packed().unwrap()
+    );
+    assert_eq!(
+        signing::musig::pub_key_hash(&pk),
+        PubKeyHash::from_privkey(&pk).data
+    );
+
+    let withdraw = Withdraw::new(
+        AccountId(1),
+        from,
+        to,
+        TokenId(2),
+        BigUint::from(amount + 1),
+        BigUint::from(fee),
+        Nonce(3),
+        time_range,
+        None,
+    );
+    let signing_withdraw = signing::Withdraw {
+        account_id: 1,
+        from: from.0,
+        to: to.0,
+        token: 2,
+        amount: amount + 1,
+        fee,
+        nonce: 3,
+        time_range: signing_time_range,
+    };
+    assert_eq!(signing_withdraw.bytes().unwrap(), withdraw.get_bytes());
+    assert_eq!(
+        signing_withdraw.ethereum_sign_message("DAI", 6),
+        withdraw.get_ethereum_sign_message("DAI", 6)
+    );
+
+    let new_pk_hash = PubKeyHash::from_privkey(&pk);
+    let change_pubkey = ChangePubKey::new(
+        AccountId(1),
+        from,
+        new_pk_hash,
+        TokenId(2),
+        BigUint::from(fee),
+        Nonce(3),
+        time_range,
+        None,
+        None,
+    );
+    let signing_change_pubkey = signing::ChangePubKey {
+        account_id: 1,
+        account: from.0,
+        new_pk_hash: new_pk_hash.data,
+        fee_token: 2,
+        fee,
+        nonce: 3,
+        time_range: signing_time_range,
+    };
+    assert_eq!(
+        signing_change_pubkey.bytes().unwrap(),
+        change_pubkey.get_bytes()
+    );
+    assert_eq!(
+        signing_change_pubkey.eth_signed_data([0; 32]).to_vec(),
+        change_pubkey.get_eth_signed_data().unwrap()
+    );
+    assert_eq!(
+        signing_change_pubkey.ethereum_sign_message_part("DAI", 6),
+        change_pubkey.get_ethereum_sign_message_part("DAI", 6)
+    );
+
+    let forced_exit = ForcedExit::new(
+        AccountId(1),
+        to,
+        TokenId(2),
+        BigUint::from(fee),
+        Nonce(3),
+        time_range,
+        None,
+    );
+    let signing_forced_exit = signing::ForcedExit {
+        initiator_account_id: 1,
+        target: to.0,
+        token: 2,
+        fee,
+        nonce: 3,
+        time_range: signing_time_range,
+    };
+    assert_eq!(
+        signing_forced_exit.bytes().unwrap(),
+        forced_exit.get_bytes()
+    );
+    assert_eq!(
+        signing_forced_exit.ethereum_sign_message("DAI", 6),
+        forced_exit.get_ethereum_sign_message("DAI", 6)
+    );
+
+    let content_hash = H256::repeat_byte(0x03);
+    let mint_nft = MintNFT::new(
+        AccountId(1),
+        from,
+        content_hash,
+        to,
+        BigUint::from(fee),
+        TokenId(2),
+        Nonce(3),
+        None,
+    );
+    let signing_mint_nft = signing::MintNFT {
+        creator_id: 1,
+        creator_address: from.0,
+        content_hash: content_hash.0,
+        recipient: to.0,
+        fee_token: 2,
+        fee,
+        nonce: 3,
+    };
+    assert_eq!(signing_mint_nft.bytes().unwrap(), mint_nft.get_bytes());
+    assert_eq!(
+        signing_mint_nft.ethereum_sign_message("DAI", 6),
+        mint_nft.get_ethereum_sign_message("DAI", 6)
+    );
+
+    let withdraw_nft = WithdrawNFT::new(
+        AccountId(1),
+        from,
+        to,
+        TokenId(70_000),
+        TokenId(2),
+        BigUint::from(fee),
+        Nonce(3),
+        time_range,
+        None,
+    );
+    let signing_withdraw_nft = signing::WithdrawNFT {
+        account_id: 1,
+        from: from.0,
+        to: to.0,
+        token: 70_000,
+        fee_token: 2,
+        fee,
+        nonce: 3,
+        time_range: signing_time_range,
+    };
+    assert_eq!(
+        signing_withdraw_nft.bytes().unwrap(),
+        withdraw_nft.get_bytes()
+    );
+    assert_eq!(
+        signing_withdraw_nft.ethereum_sign_message("DAI", 6),
+        withdraw_nft.get_ethereum_sign_message("DAI", 6)
+    );
+}
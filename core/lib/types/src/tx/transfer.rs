@@ -187,18 +187,31 @@ impl Transfer {
         }
     }
 
+    /// Returns the signature, the message it signs in the current version of the transaction
+    /// and the cache of the signer, so the signature can be verified in a batch.
+    pub(crate) fn signature_data(
+        &mut self,
+    ) -> (&TxSignature, Vec<u8>, &mut VerifiedSignatureCache) {
+        let msg = self.get_bytes();
+        (&self.signature, msg, &mut self.cached_signer)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
     pub fn get_ethereum_sign_message_part(&self, token_symbol: &str, decimals: u8) -> String {
-        ethereum_sign_message_part(
+        let mut message = ethereum_sign_message_part(
             "Transfer",
             token_symbol,
             decimals,
             &self.amount,
             &self.fee,
             &self.to,
-        )
+        );
+        if let Some(time_range) = &self.time_range {
+            time_range.append_ethereum_sign_message_part(&mut message);
+        }
+        message
     }
 
     /// Gets message that should be signed by Ethereum keys of the account for 2-Factor authentication.
@@ -150,6 +150,15 @@ impl WithdrawNFT {
         }
     }
 
+    /// Returns the signature, the message it signs in the current version of the transaction
+    /// and the cache of the signer, so the signature can be verified in a batch.
+    pub(crate) fn signature_data(
+        &mut self,
+    ) -> (&TxSignature, Vec<u8>, &mut VerifiedSignatureCache) {
+        let msg = self.get_bytes();
+        (&self.signature, msg, &mut self.cached_signer)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
@@ -172,6 +181,8 @@ impl WithdrawNFT {
                 .as_str(),
             );
         }
+        self.time_range
+            .append_ethereum_sign_message_part(&mut message);
         message
     }
 
@@ -4,26 +4,34 @@ use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use zksync_basic_types::{AccountId, Address};
+use zksync_basic_types::{AccountId, Address, H256};
 use zksync_crypto::params::ETH_TOKEN_ID;
 
 use crate::{
     operations::{ChangePubKeyOp, MintNFTOp},
     tx::{
         error::{CloseOperationsDisabled, TransactionError},
-        ChangePubKey, Close, ForcedExit, MintNFT, Swap, TimeRange, Transfer, TxEthSignature,
-        TxHash, TxSignature, Withdraw, WithdrawNFT,
+        verify_and_cache_signers, ChangePubKey, Close, ForcedExit, MintNFT, SignedMessage, Swap,
+        TimeRange, Transfer, TxEthSignature, TxHash, TxSignature, VerifiedSignatureCache, Withdraw,
+        WithdrawNFT,
     },
-    utils::deserialize_eth_message,
     CloseOp, ForcedExitOp, Nonce, SwapOp, Token, TokenId, TokenLike, TransferOp, TxFeeTypes,
     WithdrawNFTOp, WithdrawOp,
 };
 
+/// Account expected to sign the transaction with its Ethereum key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EthSigner {
+    /// Address of the signer is known from the transaction itself.
+    Address(Address),
+    /// Transaction only contains the ID of the signer, so its address has to be looked up.
+    AccountId(AccountId),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EthSignData {
     pub signature: TxEthSignature,
-    #[serde(deserialize_with = "deserialize_eth_message")]
-    pub message: Vec<u8>,
+    pub message: SignedMessage,
 }
 
 /// Represents transaction with the corresponding Ethereum signature and the message.
@@ -38,6 +46,17 @@ pub struct SignedZkSyncTx {
 
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
+    /// Keccak256 hash of the batch message signed by the user. Set only for transactions
+    /// which were verified as a part of a batch with the batch signature, so the
+    /// transaction can be tied to the batch it was accepted in.
+    #[serde(default)]
+    pub batch_hash: Option<H256>,
+    /// Ethereum signatures of the other parties of the transaction, e.g. of the `Swap`
+    /// orders owners. Parties are defined by the transaction type, and each entry belongs
+    /// to the party with the same index. Entry is `None` if the party doesn't need the
+    /// Ethereum signature. Empty for the transactions signed by the sender only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parties_eth_sign_data: Vec<Option<EthSignData>>,
 }
 
 impl SignedZkSyncTx {
@@ -115,6 +134,8 @@ impl From<ZkSyncTx> for SignedZkSyncTx {
             tx,
             eth_sign_data: None,
             created_at: Utc::now(),
+            batch_hash: None,
+            parties_eth_sign_data: Vec::new(),
         }
     }
 }
@@ -151,6 +172,24 @@ impl ZkSyncTx {
         }
     }
 
+    /// Returns the account expected to sign the transaction with its Ethereum key, i.e.
+    /// the one initiating the transaction and paying its fee.
+    ///
+    /// It differs from `account` for `ForcedExit`, which affects the target account,
+    /// but is initiated by another one.
+    pub fn eth_signer(&self) -> EthSigner {
+        match self {
+            ZkSyncTx::ForcedExit(tx) => EthSigner::AccountId(tx.initiator_account_id),
+            ZkSyncTx::Transfer(tx) => EthSigner::Address(tx.from),
+            ZkSyncTx::Withdraw(tx) => EthSigner::Address(tx.from),
+            ZkSyncTx::Close(tx) => EthSigner::Address(tx.account),
+            ZkSyncTx::ChangePubKey(tx) => EthSigner::Address(tx.account),
+            ZkSyncTx::Swap(tx) => EthSigner::Address(tx.submitter_address),
+            ZkSyncTx::MintNFT(tx) => EthSigner::Address(tx.creator_address),
+            ZkSyncTx::WithdrawNFT(tx) => EthSigner::Address(tx.from),
+        }
+    }
+
     pub fn from_account(&self) -> Address {
         self.account()
     }
@@ -270,6 +309,25 @@ impl ZkSyncTx {
         Ok(())
     }
 
+    /// Verifies the zkSync signatures of the transactions at once, so they're not verified
+    /// one by one when the transactions are executed. Returns `true` if all the signatures are valid.
+    pub fn verify_signatures_batch<'a>(txs: impl IntoIterator<Item = &'a mut ZkSyncTx>) -> bool {
+        verify_and_cache_signers(txs.into_iter().filter_map(|tx| tx.signature_data()))
+    }
+
+    fn signature_data(&mut self) -> Option<(&TxSignature, Vec<u8>, &mut VerifiedSignatureCache)> {
+        match self {
+            ZkSyncTx::Transfer(tx) => Some(tx.signature_data()),
+            ZkSyncTx::Withdraw(tx) => Some(tx.signature_data()),
+            ZkSyncTx::Close(_) => None,
+            ZkSyncTx::ChangePubKey(tx) => Some(tx.signature_data()),
+            ZkSyncTx::ForcedExit(tx) => Some(tx.signature_data()),
+            ZkSyncTx::MintNFT(tx) => Some(tx.signature_data()),
+            ZkSyncTx::Swap(tx) => Some(tx.signature_data()),
+            ZkSyncTx::WithdrawNFT(tx) => Some(tx.signature_data()),
+        }
+    }
+
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// `ChangePubKey` message is handled separately since its Ethereum signature
@@ -0,0 +1,111 @@
+//! Limits of the withdrawals set by the operator of the network for the risk control.
+//!
+//! Limits are set per token: the amount a single account may withdraw during a UTC day,
+//! the amount all the accounts may withdraw during a UTC day, and the threshold above which
+//! the withdrawal is only accepted once the delay window passes since it was first submitted.
+//! Withdrawals are checked against the limits by the API before they are sent to the mempool.
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::TokenId;
+use zksync_utils::BigUintSerdeWrapper;
+
+/// Limit of the withdrawals which has been reached.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WithdrawalLimitKind {
+    /// Daily withdrawals of the account.
+    AccountDaily,
+    /// Daily withdrawals of all the accounts.
+    DailyVolume,
+}
+
+/// Limits of the withdrawals of the token, the withdrawals are not limited if the limit is not set.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalLimits {
+    pub token: TokenId,
+    /// Largest amount a single account may withdraw during a UTC day.
+    pub account_daily_limit: Option<BigUintSerdeWrapper>,
+    /// Largest amount all the accounts may withdraw during a UTC day.
+    pub daily_volume_limit: Option<BigUintSerdeWrapper>,
+    /// Smallest amount of the withdrawal which is delayed.
+    pub large_withdrawal_threshold: Option<BigUintSerdeWrapper>,
+    /// Delay of the large withdrawals, in seconds.
+    pub large_withdrawal_delay: u64,
+}
+
+impl WithdrawalLimits {
+    /// Checks whether the account may withdraw `amount`, given the amounts withdrawn today
+    /// by the account and by all the accounts.
+    pub fn check(
+        &self,
+        amount: &BigUint,
+        account_volume: &BigUint,
+        total_volume: &BigUint,
+    ) -> Result<(), WithdrawalLimitKind> {
+        let exceeds = |limit: &Option<BigUintSerdeWrapper>, volume: &BigUint| {
+            limit
+                .as_ref()
+                .map_or(false, |limit| volume + amount > limit.0)
+        };
+        if exceeds(&self.account_daily_limit, account_volume) {
+            return Err(WithdrawalLimitKind::AccountDaily);
+        }
+        if exceeds(&self.daily_volume_limit, total_volume) {
+            return Err(WithdrawalLimitKind::DailyVolume);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the withdrawal of `amount` has to wait for the delay window.
+    pub fn is_delayed(&self, amount: &BigUint) -> bool {
+        self.large_withdrawal_delay > 0
+            && self
+                .large_withdrawal_threshold
+                .as_ref()
+                .map_or(false, |threshold| *amount >= threshold.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdrawal_limits() {
+        let amount = |amount: u32| BigUint::from(amount);
+        let limits = WithdrawalLimits {
+            token: TokenId(1),
+            account_daily_limit: Some(amount(100).into()),
+            daily_volume_limit: Some(amount(1_000).into()),
+            large_withdrawal_threshold: Some(amount(50).into()),
+            large_withdrawal_delay: 3600,
+        };
+
+        assert_eq!(limits.check(&amount(40), &amount(60), &amount(60)), Ok(()));
+        assert_eq!(
+            limits.check(&amount(41), &amount(60), &amount(60)),
+            Err(WithdrawalLimitKind::AccountDaily)
+        );
+        assert_eq!(
+            limits.check(&amount(40), &amount(0), &amount(980)),
+            Err(WithdrawalLimitKind::DailyVolume)
+        );
+
+        assert!(!limits.is_delayed(&amount(49)));
+        assert!(limits.is_delayed(&amount(50)));
+        let unlimited = WithdrawalLimits {
+            token: TokenId(1),
+            account_daily_limit: None,
+            daily_volume_limit: None,
+            large_withdrawal_threshold: Some(amount(50).into()),
+            large_withdrawal_delay: 0,
+        };
+        assert_eq!(
+            unlimited.check(&amount(10_000), &amount(0), &amount(0)),
+            Ok(())
+        );
+        assert!(!unlimited.is_delayed(&amount(10_000)));
+    }
+}
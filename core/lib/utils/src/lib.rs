@@ -6,6 +6,7 @@ mod format;
 mod macros;
 pub mod panic_notify;
 mod serde_wrappers;
+pub mod shutdown;
 mod string;
 
 pub use convert::*;
@@ -0,0 +1,188 @@
+//! Graceful shutdown of the components.
+//!
+//! Every component running until the shutdown gets a `ShutdownToken`. Once the shutdown is signalled,
+//! the component stops taking the new work, finishes the work it has already taken and drops
+//! the token. `Shutdown::stop` waits for all the tokens to be dropped, so the process exits
+//! only after the in-flight work is flushed.
+//!
+//! Components exchanging the messages are stopped in stages (see `ShutdownToken::propagate`),
+//! so every component handles the messages sent by the ones stopped before it.
+
+// Built-in deps
+// External uses
+use futures::{channel::mpsc, StreamExt};
+use tokio::{
+    sync::{mpsc as done_mpsc, watch},
+    task::JoinHandle,
+};
+// Local uses
+
+/// Sender of the shutdown signal.
+#[derive(Debug)]
+pub struct Shutdown {
+    signal: watch::Sender<bool>,
+    token: ShutdownToken,
+    stopped: done_mpsc::Receiver<()>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (signal, signal_receiver) = watch::channel(false);
+        let (running, stopped) = done_mpsc::channel(1);
+        Self {
+            signal,
+            token: ShutdownToken {
+                signal: signal_receiver,
+                _running: running,
+            },
+            stopped,
+        }
+    }
+
+    /// Returns the token for the component to be stopped.
+    pub fn token(&self) -> ShutdownToken {
+        self.token.clone()
+    }
+
+    /// Signals the shutdown and waits for all the tokens to be dropped.
+    ///
+    /// Dropping `Shutdown` without calling this method doesn't signal anything, the components
+    /// keep running.
+    pub async fn stop(self) {
+        let Self {
+            signal,
+            token,
+            mut stopped,
+        } = self;
+        drop(token);
+        signal.send(true).ok();
+        // Receiver gets `None` once all the senders held by the tokens are dropped.
+        stopped.recv().await;
+    }
+}
+
+/// Receiver of the shutdown signal, the shutdown is completed once every token is dropped.
+#[derive(Debug, Clone)]
+pub struct ShutdownToken {
+    signal: watch::Receiver<bool>,
+    _running: done_mpsc::Sender<()>,
+}
+
+impl ShutdownToken {
+    /// Returns the token which is never signalled, for the components run without
+    /// the graceful shutdown, e.g. in tests.
+    pub fn never() -> Self {
+        Shutdown::new().token()
+    }
+
+    pub fn is_signalled(&self) -> bool {
+        *self.signal.borrow()
+    }
+
+    /// Resolves once the shutdown is signalled.
+    pub async fn signalled(&self) {
+        let mut signal = self.signal.clone();
+        while !*signal.borrow() {
+            if signal.changed().await.is_err() {
+                // `Shutdown` is dropped, so the signal is never sent.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Receives the next message from the channel. Once the shutdown is signalled, the channel
+    /// is closed: the new messages are rejected and the ones sent before are still received,
+    /// then `None` is returned.
+    pub async fn recv<T>(&self, receiver: &mut mpsc::Receiver<T>) -> Option<T> {
+        tokio::select! {
+            biased;
+            message = receiver.next() => message,
+            _ = self.signalled() => {
+                receiver.close();
+                receiver.next().await
+            }
+        }
+    }
+
+    /// Spawns the task stopping the `stages` one by one once the shutdown is signalled.
+    /// The token is dropped after the last stage has stopped.
+    pub fn propagate(self, stages: Vec<Shutdown>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            self.signalled().await;
+            for stage in stages {
+                stage.stop().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::SinkExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn stop_waits_for_tokens() {
+        let shutdown = Shutdown::new();
+        let token = shutdown.token();
+        let (mut sender, mut receiver) = mpsc::channel(4);
+        let component = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(message) = token.recv(&mut receiver).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                received.push(message);
+            }
+            received
+        });
+
+        for message in 0..3 {
+            sender.send(message).await.unwrap();
+        }
+        shutdown.stop().await;
+
+        // Messages sent before the shutdown are received, the new ones are rejected.
+        assert_eq!(component.await.unwrap(), vec![0, 1, 2]);
+        assert!(sender.send(3).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stages_are_stopped_in_order() {
+        let shutdown = Shutdown::new();
+        let (first, second) = (Shutdown::new(), Shutdown::new());
+        let (first_token, second_token) = (first.token(), second.token());
+        shutdown.token().propagate(vec![first, second]);
+
+        let (mut sender, mut receiver) = mpsc::channel(4);
+        let consumer = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(message) = second_token.recv(&mut receiver).await {
+                received.push(message);
+            }
+            received
+        });
+        tokio::spawn(async move {
+            first_token.signalled().await;
+            // Consumer is not stopped until the producer has stopped.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sender.send("last").await.unwrap();
+        });
+
+        shutdown.stop().await;
+        assert_eq!(consumer.await.unwrap(), vec!["last"]);
+    }
+
+    #[tokio::test]
+    async fn dropped_shutdown_is_not_signalled() {
+        let token = ShutdownToken::never();
+        assert!(!token.is_signalled());
+        let signalled = tokio::time::timeout(Duration::from_millis(10), token.signalled()).await;
+        assert!(signalled.is_err());
+    }
+}
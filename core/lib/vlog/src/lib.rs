@@ -6,13 +6,24 @@
 //!
 //! The format of the logs in stdout can be `plain` or` json` and is set by the `MISC_LOG_FORMAT` env variable.
 //!
+//! If the `MISC_OTLP_ENDPOINT` env variable is set, the spans are also exported to the OpenTelemetry
+//! collector with this gRPC endpoint. Spans of the transaction processing have the `tx_hash` field,
+//! which is used as the trace id of the transaction across the services: API, mempool,
+//! state keeper and committer.
+//!
 //! Full documentation for the `tracing` crate here https://docs.rs/tracing/
 //!
 //! Integration with sentry for catching errors and react on them immediately
 //! https://docs.sentry.io/platforms/rust/
 //!
 
-use std::{borrow::Cow, str::FromStr};
+use std::{borrow::Cow, path::Path, str::FromStr};
+
+use opentelemetry::{
+    sdk::{trace, Resource},
+    KeyValue,
+};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 pub use sentry;
 use sentry::{types::Dsn, ClientInitGuard};
@@ -49,6 +60,46 @@ macro_rules! error {
 pub struct VlogGuard {
     _sentry_guard: Option<ClientInitGuard>,
     _logger_guard: WorkerGuard,
+    _otlp_guard: Option<OtlpGuard>,
+}
+
+/// Exports the remaining spans when dropped.
+struct OtlpGuard;
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// Creates the OpenTelemetry tracer exporting the spans to the `MISC_OTLP_ENDPOINT`, if it's set.
+/// Spans are exported in the batches by the Tokio task, so it has to be called within the Tokio runtime.
+fn get_otlp_tracer() -> Option<trace::Tracer> {
+    let endpoint = std::env::var("MISC_OTLP_ENDPOINT").ok()?;
+    // Spans of the different binaries are told apart by the name of the binary.
+    let service_name = std::env::args()
+        .next()
+        .as_ref()
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "zksync".to_owned());
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name,
+            )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)
+        .expect("failed to install the OTLP exporter");
+    Some(tracer)
 }
 
 fn get_sentry_url() -> Option<Dsn> {
@@ -63,6 +114,7 @@ fn get_sentry_url() -> Option<Dsn> {
 /// Initialize logging with non blocking tracing and set up log format
 ///
 /// If the sentry URL is provided via an environment variable, this function will also initialize sentry.
+/// If the OTLP endpoint is provided, the spans are exported to it, which requires the Tokio runtime.
 /// Returns a VlogGuard guard. Which contains Sentry Guard and Logger Guard
 ///
 /// The full description can be found in the official documentation:
@@ -71,20 +123,27 @@ fn get_sentry_url() -> Option<Dsn> {
 pub fn init() -> VlogGuard {
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
     let (non_blocking, _logger_guard) = tracing_appender::non_blocking(std::io::stdout());
+    let otlp_tracer = get_otlp_tracer();
+    let _otlp_guard = otlp_tracer.as_ref().map(|_| OtlpGuard);
+    let otlp_layer = otlp_tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer));
+    let subscriber = tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(otlp_layer);
     match log_format.as_str() {
         "plain" => {
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .with_writer(non_blocking)
+            subscriber
+                .with(fmt::layer().with_writer(non_blocking))
                 .init();
         }
         "json" => {
-            let timer = tracing_subscriber::fmt::time::ChronoUtc::rfc3339();
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .with_writer(non_blocking)
-                .with_timer(timer)
-                .json()
+            let timer = fmt::time::ChronoUtc::rfc3339();
+            subscriber
+                .with(
+                    fmt::layer()
+                        .with_writer(non_blocking)
+                        .with_timer(timer)
+                        .json(),
+                )
                 .init();
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
@@ -106,5 +165,6 @@ pub fn init() -> VlogGuard {
     VlogGuard {
         _sentry_guard,
         _logger_guard,
+        _otlp_guard,
     }
 }
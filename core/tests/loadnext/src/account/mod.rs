@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
@@ -14,8 +15,9 @@ use crate::{
     command::{Command, ExpectedOutcome, IncorrectnessModifier, TxCommand},
     config::LoadtestConfig,
     constants::{COMMIT_TIMEOUT, POLLING_INTERVAL},
-    report::{Report, ReportBuilder, ReportLabel},
+    report::{PipelineStage, Report, ReportBuilder, ReportLabel},
     rng::LoadtestRng,
+    scenario::RateLimiter,
 };
 
 mod batch_command_executor;
@@ -43,6 +45,12 @@ pub struct AccountLifespan {
     main_token: Token,
     /// Channel for sending reports about performed operations.
     report_sink: Sender<Report>,
+    /// Moment the account starts sending operations, used to ramp up the load.
+    start_at: Instant,
+    /// Limiter of the operations rate shared by all the accounts, if the target TPS is set.
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Durations of the pipeline stages of the operation being executed.
+    stages: Mutex<Vec<(PipelineStage, Duration)>>,
 }
 
 impl AccountLifespan {
@@ -51,6 +59,8 @@ impl AccountLifespan {
         addresses: AddressPool,
         test_account: TestWallet,
         report_sink: Sender<Report>,
+        start_at: Instant,
+        rate_limiter: Option<Arc<RateLimiter>>,
     ) -> Self {
         let main_token = test_account
             .wallet
@@ -67,6 +77,9 @@ impl AccountLifespan {
             main_token,
 
             report_sink,
+            start_at,
+            rate_limiter,
+            stages: Mutex::default(),
         }
     }
 
@@ -96,8 +109,13 @@ impl AccountLifespan {
             }
         }
 
+        tokio::time::sleep_until(self.start_at.into()).await;
+
         let command_sequence = self.generate_commands();
         for command in command_sequence {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
             self.execute_command(command).await;
         }
     }
@@ -114,6 +132,7 @@ impl AccountLifespan {
         let mut attempt = 0;
         loop {
             let start = Instant::now();
+            self.stages.lock().unwrap().clear();
             let result = match &command {
                 Command::SingleTx(tx_command) => self.execute_tx_command(tx_command).await,
                 Command::Batch(tx_commands) => {
@@ -164,12 +183,14 @@ impl AccountLifespan {
             vlog::error!("Command failed: {:#?}", command);
         }
 
+        let stages = std::mem::take(&mut *self.stages.lock().unwrap());
         let report = ReportBuilder::new()
             .label(label)
             .reporter(self.wallet.address())
             .time(time)
             .retries(retries)
             .action(command)
+            .stages(stages)
             .finish();
 
         if let Err(_err) = self.report_sink.send(report).await {
@@ -178,6 +199,14 @@ impl AccountLifespan {
         };
     }
 
+    /// Records the duration of the pipeline stage of the operation being executed.
+    fn record_stage(&self, stage: PipelineStage, started_at: Instant) {
+        self.stages
+            .lock()
+            .unwrap()
+            .push((stage, started_at.elapsed()));
+    }
+
     /// Generic sumbitter for zkSync network: it can operate both individual transactions and
     /// batches, as long as we can provide a `SyncTransactionHandle` to wait for the commitment and the
     /// execution result.
@@ -194,7 +223,9 @@ impl AccountLifespan {
     {
         let expected_outcome = modifier.expected_outcome();
 
+        let submission_start = Instant::now();
         let send_result = send().await;
+        self.record_stage(PipelineStage::Submission, submission_start);
         let mut handle = match (expected_outcome, send_result) {
             (ExpectedOutcome::ApiRequestFailed, Ok(_handle)) => {
                 // Transaction got accepted, but should have not been.
@@ -217,10 +248,12 @@ impl AccountLifespan {
         };
 
         handle.polling_interval(POLLING_INTERVAL).unwrap();
+        let commitment_start = Instant::now();
         let transaction_receipt = handle
             .commit_timeout(COMMIT_TIMEOUT)
             .wait_for_commit()
             .await?;
+        self.record_stage(PipelineStage::Commitment, commitment_start);
 
         match expected_outcome {
             ExpectedOutcome::TxSucceed if transaction_receipt.fail_reason.is_none() => {
@@ -250,7 +283,12 @@ impl AccountLifespan {
         ))];
 
         for _ in 0..self.config.operations_per_account {
-            let command = Command::random(&mut self.rng, self.wallet.address(), &self.addresses);
+            let command = Command::random(
+                &mut self.rng,
+                self.wallet.address(),
+                &self.addresses,
+                &self.config.tx_mix,
+            );
             commands.push(command)
         }
 
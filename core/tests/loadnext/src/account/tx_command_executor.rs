@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Instant};
 
 use num::{BigUint, Zero};
 use zksync::{
@@ -12,7 +12,7 @@ use crate::{
     command::{IncorrectnessModifier, TxCommand, TxType},
     constants::{COMMIT_TIMEOUT, POLLING_INTERVAL},
     corrupted_tx::Corrupted,
-    report::ReportLabel,
+    report::{PipelineStage, ReportLabel},
 };
 
 impl AccountLifespan {
@@ -159,7 +159,9 @@ impl AccountLifespan {
 
     async fn handle_priority_op(&self, eth_tx_hash: H256) -> Result<ReportLabel, ClientError> {
         let ethereum = self.wallet.ethereum(&self.config.web3_url).await?;
+        let confirmation_start = Instant::now();
         let receipt = ethereum.wait_for_tx(eth_tx_hash).await?;
+        self.record_stage(PipelineStage::EthConfirmation, confirmation_start);
 
         let mut priority_op_handle = match receipt.priority_op_handle(self.wallet.provider.clone())
         {
@@ -175,10 +177,12 @@ impl AccountLifespan {
         priority_op_handle
             .polling_interval(POLLING_INTERVAL)
             .unwrap();
+        let commitment_start = Instant::now();
         priority_op_handle
             .commit_timeout(COMMIT_TIMEOUT)
             .wait_for_commit()
             .await?;
+        self.record_stage(PipelineStage::Commitment, commitment_start);
 
         Ok(ReportLabel::done())
     }
@@ -77,6 +77,8 @@ pub struct AccountPool {
     pub accounts: VecDeque<TestWallet>,
     /// Pool of addresses of the test accounts.
     pub addresses: AddressPool,
+    /// Hex-encoded master seed of the test, it can be used to reproduce the test.
+    pub seed: String,
 }
 
 impl AccountPool {
@@ -146,6 +148,7 @@ impl AccountPool {
             master_wallet,
             accounts,
             addresses: AddressPool::new(addresses),
+            seed: rng.seed_hex(),
         })
     }
 }
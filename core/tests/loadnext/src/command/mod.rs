@@ -1,12 +1,12 @@
 use rand::Rng;
-use static_assertions::const_assert;
 
 use zksync_types::Address;
 
 use crate::{
     account_pool::AddressPool,
     constants::MAX_BATCH_SIZE,
-    rng::{LoadtestRng, Random},
+    rng::LoadtestRng,
+    scenario::{MixedOperation, TxMix},
 };
 
 pub use self::{
@@ -27,50 +27,30 @@ pub enum Command {
     ApiRequest(ApiRequestCommand),
 }
 
-/// Decision regarding the type of command to be spawned.
-#[derive(Debug, Clone, Copy)]
-enum CommandType {
-    SingleTx,
-    Batch,
-    ApiRequest,
-}
-
-impl Random for CommandType {
-    fn random(rng: &mut LoadtestRng) -> Self {
-        // Chances of a certain event generation.
-        // You must maintain the sum of these constants to be equal to 1.0f32.
-        const SINGLE_TX_CHANCE: f32 = 0.7;
-        const BATCH_CHANCE: f32 = 0.3;
-        // We don't generate API requests at the moment.
-        const _API_REQUEST_CHANCE: f32 = 0.0;
-
-        const _CHANCES_SUM: f32 = SINGLE_TX_CHANCE + BATCH_CHANCE + _API_REQUEST_CHANCE;
-        // Unfortunately. f64::abs()` is not yet a `const` function.
-        const_assert!(
-            -f32::EPSILON <= (_CHANCES_SUM - 1.0f32) && (_CHANCES_SUM - 1.0f32) <= f32::EPSILON
-        );
-        let chance = rng.gen_range(0.0f32..1.0f32);
-
-        if chance <= SINGLE_TX_CHANCE {
-            Self::SingleTx
-        } else if chance <= (SINGLE_TX_CHANCE + BATCH_CHANCE) {
-            Self::Batch
-        } else {
-            Self::ApiRequest
-        }
-    }
-}
-
 impl Command {
-    pub fn random(rng: &mut LoadtestRng, own_address: Address, addresses: &AddressPool) -> Self {
-        match CommandType::random(rng) {
-            CommandType::SingleTx => Self::SingleTx(TxCommand::random(rng, own_address, addresses)),
-            CommandType::Batch => {
+    /// Generates a random command according to the operations mix.
+    pub fn random(
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+        mix: &TxMix,
+    ) -> Self {
+        match mix.random_operation(rng) {
+            MixedOperation::Tx(tx_type) => Self::SingleTx(TxCommand::new_with_type(
+                rng,
+                own_address,
+                addresses,
+                tx_type,
+            )),
+            MixedOperation::Batch => {
                 // TODO: For some reason, batches of size 1 are being rejected because of nonce mistmatch.
                 // It may be either bug in loadtest or server code, thus it should be investigated.
                 let batch_size = rng.gen_range(2..=MAX_BATCH_SIZE);
                 let mut batch_command: Vec<_> = (0..batch_size)
-                    .map(|_| TxCommand::random_batchable(rng, own_address, addresses))
+                    .map(|_| {
+                        let tx_type = mix.random_batchable_tx_type(rng);
+                        TxCommand::new_with_type(rng, own_address, addresses, tx_type)
+                    })
                     .collect();
 
                 if batch_command
@@ -88,9 +68,6 @@ impl Command {
 
                 Self::Batch(batch_command)
             }
-            CommandType::ApiRequest => {
-                unreachable!("We don't generate API commands currently")
-            }
         }
     }
 }
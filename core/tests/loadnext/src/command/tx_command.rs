@@ -232,7 +232,8 @@ impl TxCommand {
         Self::new_with_type(rng, own_address, addresses, command_type)
     }
 
-    fn new_with_type(
+    /// Generates a random transaction command of the given type.
+    pub fn new_with_type(
         rng: &mut LoadtestRng,
         own_address: Address,
         addresses: &AddressPool,
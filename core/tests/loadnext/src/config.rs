@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::scenario::TxMix;
+
 /// Configuration for the loadtest.
 ///
 /// This structure is meant to provide the least possible amount of parameters:
@@ -45,6 +47,20 @@ pub struct LoadtestConfig {
     pub seed: Option<String>,
     /// Allowed percent of failed transactions
     pub allowed_percent: u8,
+
+    /// Weights of the operations performed by the accounts, e.g. `transfer=6,withdraw=2,batch=5`.
+    /// Supported kinds are `transfer`, `withdraw`, `change_pubkey`, `deposit`, `full_exit` and `batch`.
+    #[serde(default)]
+    pub tx_mix: TxMix,
+    /// Duration of the load ramp-up in seconds: accounts start sending operations evenly over it.
+    #[serde(default)]
+    pub ramp_up_secs: u64,
+    /// Optional cap on the amount of operations sent by all the accounts per second.
+    /// Since every account waits for its operation to be committed before sending the next one,
+    /// the rate can only be reached with enough accounts.
+    pub target_tps: Option<f64>,
+    /// Optional path of the JSON report with the latencies of the operations.
+    pub report_path: Option<String>,
 }
 
 impl LoadtestConfig {
@@ -69,6 +85,10 @@ impl Default for LoadtestConfig {
             main_token: "DAI".into(),
             seed: None,
             allowed_percent: 10,
+            tx_mix: TxMix::default(),
+            ramp_up_secs: 0,
+            target_tps: None,
+            report_path: None,
         }
     }
 }
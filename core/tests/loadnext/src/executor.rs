@@ -1,3 +1,8 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use futures::{channel::mpsc, future::join_all};
 
 use tokio::task::JoinHandle;
@@ -8,8 +13,11 @@ use zksync::{
 use zksync_types::{tx::TxHash, TransactionReceipt, TxFeeTypes, U256};
 
 use crate::{
-    account::AccountLifespan, account_pool::AccountPool, config::LoadtestConfig,
+    account::AccountLifespan,
+    account_pool::AccountPool,
+    config::LoadtestConfig,
     report_collector::LoadtestResult,
+    scenario::{account_start, RateLimiter},
 };
 use crate::{constants::*, report_collector::ReportCollector};
 
@@ -328,13 +336,26 @@ impl Executor {
         // Prepare channels for the report collector.
         let (report_sender, report_receiver) = mpsc::channel(256);
 
-        let report_collector = ReportCollector::new(report_receiver, self.config.allowed_percent);
+        let report_collector = ReportCollector::new(
+            report_receiver,
+            self.config.allowed_percent,
+            self.pool.seed.clone(),
+        )
+        .with_report_path(self.config.report_path.clone())
+        .with_target_tps(self.config.target_tps);
         let report_collector_future = tokio::spawn(report_collector.run());
 
         let config = &self.config;
         let accounts_amount = config.accounts_amount;
         let addresses = self.pool.addresses.clone();
 
+        // The load is ramped up from the moment the first accounts are funded.
+        let load_start = Instant::now();
+        let ramp_up = Duration::from_secs(config.ramp_up_secs);
+        let rate_limiter = config
+            .target_tps
+            .map(|target_tps| Arc::new(RateLimiter::new(target_tps)));
+
         let mut retry_counter = 0;
         let mut accounts_processed = 0;
 
@@ -388,19 +409,25 @@ impl Executor {
             accounts_processed += accounts_to_process;
 
             // Spawn each account lifespan.
-            let new_account_futures =
-                self.pool
-                    .accounts
-                    .drain(..accounts_to_process)
-                    .map(|wallet| {
-                        let account = AccountLifespan::new(
-                            config,
-                            addresses.clone(),
-                            wallet,
-                            report_sender.clone(),
-                        );
-                        tokio::spawn(account.run())
-                    });
+            let spawned_accounts = account_futures.len();
+            let new_account_futures = self
+                .pool
+                .accounts
+                .drain(..accounts_to_process)
+                .enumerate()
+                .map(|(idx, wallet)| {
+                    let start_at =
+                        account_start(load_start, ramp_up, spawned_accounts + idx, accounts_amount);
+                    let account = AccountLifespan::new(
+                        config,
+                        addresses.clone(),
+                        wallet,
+                        report_sender.clone(),
+                        start_at,
+                        rate_limiter.clone(),
+                    );
+                    tokio::spawn(account.run())
+                });
 
             account_futures.extend(new_account_futures);
         }
@@ -9,3 +9,4 @@ pub mod executor;
 pub mod report;
 pub mod report_collector;
 pub mod rng;
+pub mod scenario;
@@ -27,6 +27,8 @@ pub struct Report {
     pub retries: usize,
     /// Duration of the latest execution attempt.
     pub time: Duration,
+    /// Durations of the pipeline stages of the latest execution attempt.
+    pub stages: Vec<(PipelineStage, Duration)>,
 }
 
 /// Builder structure for `Report`.
@@ -50,6 +52,7 @@ impl ReportBuilder {
                 action: ActionType::Tx(TxActionType::Transfer),
                 retries: 0,
                 time: Default::default(),
+                stages: Vec::new(),
             },
         }
     }
@@ -79,6 +82,11 @@ impl ReportBuilder {
         self
     }
 
+    pub fn stages(mut self, stages: Vec<(PipelineStage, Duration)>) -> Self {
+        self.report.stages = stages;
+        self
+    }
+
     pub fn finish(self) -> Report {
         self.report
     }
@@ -110,6 +118,27 @@ impl ReportLabel {
     }
 }
 
+/// Stage of the operation processing, the latency of each stage is reported separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PipelineStage {
+    /// Waiting for the Ethereum transaction of the priority operation to be mined.
+    EthConfirmation,
+    /// Sending the transaction until it's accepted by the server.
+    Submission,
+    /// Waiting for the accepted operation to be included into the committed block.
+    Commitment,
+}
+
+impl PipelineStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EthConfirmation => "eth_confirmation",
+            Self::Submission => "submission",
+            Self::Commitment => "commitment",
+        }
+    }
+}
+
 /// Denotes the type of executed transaction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TxActionType {
@@ -136,6 +165,19 @@ impl All for TxActionType {
     }
 }
 
+impl TxActionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transfer => "transfer",
+            Self::Withdraw => "withdraw",
+            Self::ForcedExit => "forced_exit",
+            Self::ChangePubKey => "change_pubkey",
+            Self::FullExit => "full_exit",
+            Self::Deposit => "deposit",
+        }
+    }
+}
+
 impl From<TxType> for TxActionType {
     fn from(command: TxType) -> Self {
         match command {
@@ -201,6 +243,24 @@ impl From<Command> for ActionType {
 }
 
 impl ActionType {
+    /// Returns the name of the action in the loadtest report, batches of all sizes share the name.
+    pub fn report_name(&self) -> &'static str {
+        match self {
+            Self::Tx(action) => action.as_str(),
+            Self::Api(action) => match *action {},
+            Self::Batch { .. } => "batch",
+        }
+    }
+
+    /// Returns the amount of transactions in the action.
+    pub fn txs_count(&self) -> usize {
+        match self {
+            Self::Tx(_) => 1,
+            Self::Api(_) => 0,
+            Self::Batch { batch_size } => *batch_size,
+        }
+    }
+
     /// Returns the vector containing the list of all the supported actions.
     /// May be useful in different collectors to initialize their internal states.
     pub fn all() -> Vec<Self> {
@@ -0,0 +1,167 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::Serialize;
+
+use crate::report::{ActionType, PipelineStage};
+
+/// Latency percentiles of the set of operations, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub p50_ms: u128,
+    pub p90_ms: u128,
+    pub p95_ms: u128,
+    pub p99_ms: u128,
+    pub max_ms: u128,
+}
+
+impl LatencySummary {
+    fn new(mut latencies: Vec<Duration>) -> Self {
+        latencies.sort_unstable();
+        // Nearest-rank percentile, the collected latencies are never empty.
+        let percentile = |percentile: usize| {
+            let rank = (latencies.len() * percentile + 99) / 100;
+            latencies[rank.max(1) - 1].as_millis()
+        };
+        Self {
+            count: latencies.len(),
+            p50_ms: percentile(50),
+            p90_ms: percentile(90),
+            p95_ms: percentile(95),
+            p99_ms: percentile(99),
+            max_ms: latencies.last().unwrap().as_millis(),
+        }
+    }
+}
+
+/// Latencies of the successful actions of the same kind.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActionLatencies {
+    /// Amount of the transactions in the actions, batches count every transaction.
+    pub txs: usize,
+    /// Latency of the whole action.
+    pub total: LatencySummary,
+    /// Latencies of the pipeline stages passed by the actions.
+    pub stages: BTreeMap<&'static str, LatencySummary>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ActionEntries {
+    txs: usize,
+    total: Vec<Duration>,
+    stages: BTreeMap<PipelineStage, Vec<Duration>>,
+}
+
+/// Collector of the exact latencies of the successful actions.
+///
+/// Unlike the `MetricsCollector`, it keeps every measured latency to calculate the exact percentiles
+/// for the machine-readable report, which is fine for the amount of operations performed by the loadtest.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyCollector {
+    actions: BTreeMap<&'static str, ActionEntries>,
+}
+
+impl LatencyCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_latencies(
+        &mut self,
+        action: ActionType,
+        time: Duration,
+        stages: &[(PipelineStage, Duration)],
+    ) {
+        let entries = self.actions.entry(action.report_name()).or_default();
+        entries.txs += action.txs_count();
+        entries.total.push(time);
+        for (stage, stage_time) in stages {
+            entries.stages.entry(*stage).or_default().push(*stage_time);
+        }
+    }
+
+    /// Returns the amount of the transactions in the successful actions.
+    pub fn txs(&self) -> usize {
+        self.actions.values().map(|entries| entries.txs).sum()
+    }
+
+    pub fn summary(&self) -> BTreeMap<&'static str, ActionLatencies> {
+        self.actions
+            .iter()
+            .map(|(action, entries)| {
+                let stages = entries
+                    .stages
+                    .iter()
+                    .map(|(stage, latencies)| {
+                        (stage.as_str(), LatencySummary::new(latencies.clone()))
+                    })
+                    .collect();
+                let latencies = ActionLatencies {
+                    txs: entries.txs,
+                    total: LatencySummary::new(entries.total.clone()),
+                    stages,
+                };
+                (*action, latencies)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::TxActionType;
+
+    #[test]
+    fn latency_percentiles() {
+        let latencies = (1..=200).map(Duration::from_millis).collect();
+        let summary = LatencySummary::new(latencies);
+        assert_eq!(
+            summary,
+            LatencySummary {
+                count: 200,
+                p50_ms: 100,
+                p90_ms: 180,
+                p95_ms: 190,
+                p99_ms: 198,
+                max_ms: 200,
+            }
+        );
+
+        let summary = LatencySummary::new(vec![Duration::from_millis(7)]);
+        assert_eq!(summary.p50_ms, 7);
+        assert_eq!(summary.p99_ms, 7);
+    }
+
+    #[test]
+    fn latencies_by_action() {
+        let mut collector = LatencyCollector::new();
+        let stages = [
+            (PipelineStage::Submission, Duration::from_millis(10)),
+            (PipelineStage::Commitment, Duration::from_millis(90)),
+        ];
+        collector.add_latencies(
+            ActionType::Tx(TxActionType::Transfer),
+            Duration::from_millis(100),
+            &stages,
+        );
+        collector.add_latencies(
+            ActionType::Batch { batch_size: 5 },
+            Duration::from_millis(200),
+            &stages,
+        );
+        collector.add_latencies(
+            ActionType::Batch { batch_size: 3 },
+            Duration::from_millis(300),
+            &stages[..1],
+        );
+
+        assert_eq!(collector.txs(), 9);
+        let summary = collector.summary();
+        assert_eq!(summary["transfer"].txs, 1);
+        assert_eq!(summary["batch"].txs, 8);
+        assert_eq!(summary["batch"].total.max_ms, 300);
+        assert_eq!(summary["batch"].stages["submission"].count, 2);
+        assert_eq!(summary["batch"].stages["commitment"].count, 1);
+    }
+}
@@ -1,21 +1,49 @@
+use std::{collections::BTreeMap, fs::File, time::Instant};
+
 use futures::{channel::mpsc::Receiver, StreamExt};
 use operation_results_collector::OperationResultsCollector;
+use serde::Serialize;
 
 use crate::{
     report::{Report, ReportLabel},
-    report_collector::metrics_collector::MetricsCollector,
+    report_collector::{
+        latency_collector::{ActionLatencies, LatencyCollector},
+        metrics_collector::MetricsCollector,
+    },
 };
 
+mod latency_collector;
 mod metrics_collector;
 mod operation_results_collector;
 
 /// Decision on whether loadtest considered passed or failed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum LoadtestResult {
+    #[serde(rename = "passed")]
     TestPassed,
+    #[serde(rename = "failed")]
     TestFailed,
 }
 
+/// Machine-readable summary of the loadtest, written to the `REPORT_PATH` file if it's set.
+#[derive(Debug, Serialize)]
+struct LoadtestReport {
+    result: LoadtestResult,
+    /// Seed of the loadtest, so the same run can be reproduced.
+    seed: String,
+    duration_secs: f64,
+    successes: u64,
+    skipped: u64,
+    failures: u64,
+    total: u64,
+    /// Rate of the successful actions.
+    actions_per_sec: f64,
+    /// Rate of the successful transactions, batches count every transaction.
+    txs_per_sec: f64,
+    target_tps: Option<f64>,
+    actions: BTreeMap<&'static str, ActionLatencies>,
+}
+
 /// ReportCollector is an entity capable of analyzing everything that happens in the loadtest.
 ///
 /// It is designed to be separated from the actual execution, so that logic of the execution does not
@@ -27,6 +55,7 @@ pub enum LoadtestResult {
 /// - MetricsCollector, which builds time distribution histograms for each kind of performed action.
 /// - OperationResultsCollector, a primitive collector that counts the amount of failures and decides whether
 ///   test is passed.
+/// - LatencyCollector, which calculates the exact latency percentiles for the JSON report.
 ///
 /// Other possible collectors that can be implemented:
 ///
@@ -44,23 +73,44 @@ pub enum LoadtestResult {
 #[derive(Debug)]
 pub struct ReportCollector {
     allowed_percent: u8,
+    seed: String,
+    report_path: Option<String>,
+    target_tps: Option<f64>,
     reports_stream: Receiver<Report>,
     metrics_collector: MetricsCollector,
+    latency_collector: LatencyCollector,
     operations_results_collector: OperationResultsCollector,
 }
 
 impl ReportCollector {
-    pub fn new(reports_stream: Receiver<Report>, allowed_percent: u8) -> Self {
+    pub fn new(reports_stream: Receiver<Report>, allowed_percent: u8, seed: String) -> Self {
         assert!(allowed_percent < 100, "Allowed percent more than 100");
         Self {
             allowed_percent,
+            seed,
+            report_path: None,
+            target_tps: None,
             reports_stream,
             metrics_collector: MetricsCollector::new(),
+            latency_collector: LatencyCollector::new(),
             operations_results_collector: OperationResultsCollector::new(),
         }
     }
 
+    /// Sets the path of the file to write the JSON report to.
+    pub fn with_report_path(mut self, report_path: Option<String>) -> Self {
+        self.report_path = report_path;
+        self
+    }
+
+    /// Sets the target rate of the loadtest to be mentioned in the JSON report.
+    pub fn with_target_tps(mut self, target_tps: Option<f64>) -> Self {
+        self.target_tps = target_tps;
+        self
+    }
+
     pub async fn run(mut self) -> LoadtestResult {
+        let started_at = Instant::now();
         while let Some(report) = self.reports_stream.next().await {
             vlog::trace!("Report: {:?}", &report);
 
@@ -68,6 +118,8 @@ impl ReportCollector {
                 // We only count successfully created statistics.
                 self.metrics_collector
                     .add_metric(report.action, report.time);
+                self.latency_collector
+                    .add_latencies(report.action, report.time, &report.stages);
             }
 
             self.operations_results_collector.add_status(&report.label);
@@ -83,7 +135,39 @@ impl ReportCollector {
         self.metrics_collector.report();
         self.operations_results_collector.report();
 
-        self.final_resolution()
+        let result = self.final_resolution();
+        if let Some(report_path) = &self.report_path {
+            match self.write_report(report_path, result, started_at) {
+                Ok(()) => vlog::info!("Loadtest report is written to {}", report_path),
+                Err(err) => vlog::error!("Failed to write the loadtest report: {}", err),
+            }
+        }
+        result
+    }
+
+    fn write_report(
+        &self,
+        report_path: &str,
+        result: LoadtestResult,
+        started_at: Instant,
+    ) -> anyhow::Result<()> {
+        let duration_secs = started_at.elapsed().as_secs_f64();
+        let results = &self.operations_results_collector;
+        let report = LoadtestReport {
+            result,
+            seed: self.seed.clone(),
+            duration_secs,
+            successes: results.successes(),
+            skipped: results.skipped(),
+            failures: results.failures(),
+            total: results.total(),
+            actions_per_sec: results.successes() as f64 / duration_secs,
+            txs_per_sec: self.latency_collector.txs() as f64 / duration_secs,
+            target_tps: self.target_tps,
+            actions: self.latency_collector.summary(),
+        };
+        serde_json::to_writer_pretty(File::create(report_path)?, &report)?;
+        Ok(())
     }
 
     fn final_resolution(&self) -> LoadtestResult {
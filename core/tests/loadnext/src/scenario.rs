@@ -0,0 +1,288 @@
+//! Scenario of the loadtest: the mix of the performed operations and the shape of the load.
+//!
+//! The mix is set by the weights of the operation kinds (see `TxMix`), the load is ramped up
+//! by starting the accounts evenly over the ramp-up period, and the rate of the operations
+//! can be capped by the `RateLimiter` shared by all the accounts.
+
+use std::{
+    convert::TryFrom,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::{command::TxType, rng::LoadtestRng};
+
+/// Kind of the operation in the mix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OperationKind {
+    Transfer,
+    Withdraw,
+    ChangePubKey,
+    Deposit,
+    FullExit,
+    Batch,
+}
+
+impl FromStr for OperationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transfer" => Ok(Self::Transfer),
+            "withdraw" => Ok(Self::Withdraw),
+            "change_pubkey" => Ok(Self::ChangePubKey),
+            "deposit" => Ok(Self::Deposit),
+            "full_exit" => Ok(Self::FullExit),
+            "batch" => Ok(Self::Batch),
+            other => anyhow::bail!("Unknown operation kind: {}", other),
+        }
+    }
+}
+
+/// Operation picked from the mix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MixedOperation {
+    Tx(TxType),
+    Batch,
+}
+
+/// Weights of the operations performed by the accounts, e.g. `transfer=6,withdraw=2,batch=5`.
+///
+/// Kinds missing in the string get the zero weight. Transactions in the batches are picked
+/// among the transfers, withdrawals and `ChangePubKey`s with the same weights.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TxMix {
+    weights: Vec<(OperationKind, f32)>,
+}
+
+impl Default for TxMix {
+    fn default() -> Self {
+        // Roughly 70% of single transactions and 30% of batches, transfers being the most likely.
+        Self {
+            weights: vec![
+                (OperationKind::Transfer, 6.0),
+                (OperationKind::Withdraw, 2.0),
+                (OperationKind::ChangePubKey, 1.0),
+                (OperationKind::Deposit, 1.0),
+                (OperationKind::FullExit, 1.0),
+                (OperationKind::Batch, 5.0),
+            ],
+        }
+    }
+}
+
+impl FromStr for TxMix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights: Vec<(OperationKind, f32)> = Vec::new();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (kind, weight) = entry.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("Mix entry must have the `kind=weight` format: {}", entry)
+            })?;
+            let kind = OperationKind::from_str(kind.trim())?;
+            let weight = f32::from_str(weight.trim())
+                .map_err(|err| anyhow::anyhow!("Invalid weight of {:?}: {}", kind, err))?;
+            anyhow::ensure!(
+                weight.is_finite() && weight >= 0.0,
+                "Weight of {:?} must be non-negative",
+                kind
+            );
+            anyhow::ensure!(
+                weights.iter().all(|(other, _)| *other != kind),
+                "{:?} is set several times",
+                kind
+            );
+            weights.push((kind, weight));
+        }
+
+        let mix = Self { weights };
+        anyhow::ensure!(
+            mix.weights.iter().any(|(_, weight)| *weight > 0.0),
+            "At least one operation kind must have the positive weight"
+        );
+        anyhow::ensure!(
+            mix.weight(OperationKind::Batch) == 0.0 || mix.batchable_weights().next().is_some(),
+            "Batches require the positive weight of transfers, withdrawals or `ChangePubKey`s"
+        );
+        Ok(mix)
+    }
+}
+
+impl TryFrom<String> for TxMix {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl TxMix {
+    fn weight(&self, kind: OperationKind) -> f32 {
+        self.weights
+            .iter()
+            .find(|(other, _)| *other == kind)
+            .map_or(0.0, |(_, weight)| *weight)
+    }
+
+    fn batchable_weights(&self) -> impl Iterator<Item = &(OperationKind, f32)> {
+        self.weights.iter().filter(|(kind, weight)| {
+            *weight > 0.0
+                && matches!(
+                    kind,
+                    OperationKind::Transfer | OperationKind::Withdraw | OperationKind::ChangePubKey
+                )
+        })
+    }
+
+    /// Picks the random operation according to the weights.
+    pub fn random_operation(&self, rng: &mut LoadtestRng) -> MixedOperation {
+        let kind = self
+            .weights
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .expect("Mix is validated on creation")
+            .0;
+        match kind {
+            OperationKind::Batch => MixedOperation::Batch,
+            kind => MixedOperation::Tx(Self::tx_type(rng, kind)),
+        }
+    }
+
+    /// Picks the random type of the transaction in the batch according to the weights.
+    pub fn random_batchable_tx_type(&self, rng: &mut LoadtestRng) -> TxType {
+        let batchable: Vec<_> = self.batchable_weights().copied().collect();
+        let kind = batchable
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .expect("Mix with batches has batchable operations")
+            .0;
+        Self::tx_type(rng, kind)
+    }
+
+    fn tx_type(rng: &mut LoadtestRng, kind: OperationKind) -> TxType {
+        match kind {
+            OperationKind::Transfer if rng.gen_bool(0.5) => TxType::TransferToNew,
+            OperationKind::Transfer => TxType::TransferToExisting,
+            OperationKind::Withdraw if rng.gen_bool(0.5) => TxType::WithdrawToSelf,
+            OperationKind::Withdraw => TxType::WithdrawToOther,
+            OperationKind::ChangePubKey => TxType::ChangePubKey,
+            OperationKind::Deposit => TxType::Deposit,
+            OperationKind::FullExit => TxType::FullExit,
+            OperationKind::Batch => unreachable!("Batch is not a transaction type"),
+        }
+    }
+}
+
+/// Returns the moment the account should start sending operations, so the accounts
+/// are started evenly over the ramp-up period.
+pub fn account_start(
+    load_start: Instant,
+    ramp_up: Duration,
+    account_idx: usize,
+    accounts_amount: usize,
+) -> Instant {
+    if accounts_amount == 0 {
+        return load_start;
+    }
+    load_start + ramp_up.mul_f64(account_idx as f64 / accounts_amount as f64)
+}
+
+/// Limits the rate of the operations sent by all the accounts together.
+///
+/// Every operation gets its own time slot, so the operations are spread evenly
+/// instead of being sent in bursts.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(target_tps: f64) -> Self {
+        assert!(target_tps > 0.0, "Target TPS must be positive");
+        Self {
+            interval: Duration::from_secs_f64(1.0 / target_tps),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next operation can be sent.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot.into()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mix() {
+        let mix = TxMix::from_str("transfer=3, batch=1,withdraw=0.5").unwrap();
+        assert_eq!(mix.weight(OperationKind::Transfer), 3.0);
+        assert_eq!(mix.weight(OperationKind::Batch), 1.0);
+        assert_eq!(mix.weight(OperationKind::Withdraw), 0.5);
+        assert_eq!(mix.weight(OperationKind::Deposit), 0.0);
+
+        for invalid in &[
+            "",
+            "transfer",
+            "transfer=-1",
+            "transfer=0",
+            "mint=1",
+            "transfer=1,transfer=2",
+            "batch=1,deposit=1",
+        ] {
+            assert!(TxMix::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn operations_follow_mix() {
+        let mut rng = LoadtestRng::new_generic(None);
+
+        let mix = TxMix::from_str("withdraw=1").unwrap();
+        for _ in 0..100 {
+            assert!(matches!(
+                mix.random_operation(&mut rng),
+                MixedOperation::Tx(TxType::WithdrawToSelf)
+                    | MixedOperation::Tx(TxType::WithdrawToOther)
+            ));
+        }
+
+        let mix = TxMix::from_str("batch=1,change_pubkey=1,deposit=0").unwrap();
+        for _ in 0..100 {
+            assert!(matches!(
+                mix.random_operation(&mut rng),
+                MixedOperation::Batch | MixedOperation::Tx(TxType::ChangePubKey)
+            ));
+            assert_eq!(mix.random_batchable_tx_type(&mut rng), TxType::ChangePubKey);
+        }
+    }
+
+    #[test]
+    fn ramp_up() {
+        let start = Instant::now();
+        let ramp_up = Duration::from_secs(10);
+        assert_eq!(account_start(start, ramp_up, 0, 4), start);
+        assert_eq!(
+            account_start(start, ramp_up, 2, 4),
+            start + Duration::from_secs(5)
+        );
+        assert_eq!(account_start(start, Duration::default(), 3, 4), start);
+    }
+}
@@ -0,0 +1,352 @@
+//! Deterministic in-process harness for the transaction pipeline.
+//!
+//! Harness wires the signature checker, the mempool and the state keeper together in one process,
+//! so the integration tests of the new operation types don't need Postgres or an Ethereum node:
+//!
+//! - Requests are verified with `verify_tx_signature`, the same check the API runs, against
+//!   the `EthereumChecker` backed by `MockEthereum`. Results of the onchain calls (e.g. for EIP-1271
+//!   signatures) can be configured on the mock before passing the checker to `with_eth_checker`.
+//! - Verified transactions are queued in the `InMemoryMempool`, which replaces the database-backed one.
+//! - State keeper runs in the testkit mode, i.e. the miniblocks are only executed and the blocks are
+//!   only sealed when the test asks for it, so the outcome doesn't depend on timers.
+//!
+//! Every call waits for the state keeper to finish the requested step before returning.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::time::Duration;
+// External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
+use num::BigUint;
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_api::{
+    eth_checker::EthereumChecker,
+    signature_checker::{
+        verify_tx_signature, EthSignatureExemptions, RequestData, TxRequest, ValidFromWindow,
+    },
+};
+use zksync_core::{
+    committer::CommitRequest,
+    state_keeper::{
+        start_root_hash_calculator, BlockSealPolicy, StateKeeperTestkitRequest,
+        ZkSyncStateInitParams, ZkSyncStateKeeper,
+    },
+    tx_event_emitter::ProcessedOperations,
+};
+use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+use zksync_mempool::MempoolBlocksRequest;
+use zksync_types::{
+    block::IncompleteBlock,
+    tx::{
+        error::TxAddError, EthSignData, PackedEthSignature, SignedMessage, TxEthSignature, TxHash,
+    },
+    Account, AccountId, Address, BlockNumber, ExecutedOperations, Fr, SignedZkSyncTx, Token,
+    TokenId, TokenKind, ZkSyncTx,
+};
+use zksync_utils::shutdown::ShutdownToken;
+// Local uses
+pub use self::mempool::InMemoryMempool;
+
+mod mempool;
+#[cfg(test)]
+mod tests;
+
+/// Block sizes the state keeper seals the blocks with, same as in the default chain config.
+pub const BLOCK_CHUNK_SIZES: [usize; 3] = [26, 78, 182];
+
+/// Blocks are only sealed when they are full or on the request, never after a number of miniblocks.
+const MAX_MINIBLOCK_ITERATIONS: usize = 1_000_000;
+
+const CHANNEL_SIZE: usize = 256;
+
+/// Returns the token the transactions are signed with, ETH by default.
+pub fn eth_token() -> Token {
+    Token::new(TokenId(0), Address::zero(), "ETH", 18, TokenKind::ERC20)
+}
+
+/// Creates the request to verify the transaction, attaching the Ethereum signature of the message
+/// the transaction requires (if any).
+pub fn tx_request(
+    tx: ZkSyncTx,
+    eth_signature: Option<PackedEthSignature>,
+    sender: Address,
+    token: Token,
+) -> RequestData {
+    let eth_sign_data = eth_signature.map(|signature| EthSignData {
+        signature: TxEthSignature::EthereumSignature(signature),
+        message: SignedMessage::Text(
+            tx.get_ethereum_sign_message(token.clone())
+                .expect("Transaction doesn't require the Ethereum signature"),
+        ),
+    });
+    RequestData::Tx(TxRequest::new(
+        SignedZkSyncTx {
+            eth_sign_data,
+            ..SignedZkSyncTx::from(tx)
+        },
+        sender,
+        token,
+    ))
+}
+
+/// Transaction pipeline running in the current Tokio runtime. See the crate docs for details.
+pub struct PipelineHarness {
+    eth_checker: EthereumChecker,
+    valid_from_window: ValidFromWindow,
+    eth_sig_exemptions: EthSignatureExemptions,
+    mempool: InMemoryMempool,
+    fee_account: Address,
+
+    state_keeper: mpsc::Sender<StateKeeperTestkitRequest>,
+    commit_requests: mpsc::Receiver<CommitRequest>,
+    processed_ops: mpsc::Receiver<ProcessedOperations>,
+    // State keeper in the testkit mode never asks the mempool for the transactions,
+    // the receiver is only kept to keep the channel open.
+    _mempool_requests: mpsc::Receiver<MempoolBlocksRequest>,
+    tasks: Vec<JoinHandle<()>>,
+
+    sealed_blocks: Vec<IncompleteBlock>,
+    root_hashes: HashMap<BlockNumber, Fr>,
+}
+
+impl PipelineHarness {
+    /// Starts the pipeline with the Ethereum checker answering with the default mock state.
+    /// Has to be called within the Tokio runtime.
+    pub fn new() -> Self {
+        let client = EthereumGateway::Mock(MockEthereum::default());
+        Self::with_eth_checker(EthereumChecker::new(client))
+    }
+
+    /// Starts the pipeline checking the signatures with the given Ethereum checker.
+    /// Has to be called within the Tokio runtime.
+    pub fn with_eth_checker(eth_checker: EthereumChecker) -> Self {
+        let (commit_requests_sender, commit_requests) = mpsc::channel(CHANNEL_SIZE);
+        let (state_keeper_sender, state_keeper_receiver) = mpsc::channel(CHANNEL_SIZE);
+        let (mempool_requests_sender, mempool_requests) = mpsc::channel(CHANNEL_SIZE);
+        let (processed_ops_sender, processed_ops) = mpsc::channel(CHANNEL_SIZE);
+
+        // Fee account is the only account in the genesis state.
+        let fee_account = Address::repeat_byte(0xfe);
+        let mut init_params = ZkSyncStateInitParams::new();
+        init_params
+            .state
+            .insert_account(AccountId(0), Account::default_with_address(&fee_account));
+
+        let (state_keeper, root_hash_calculator) = ZkSyncStateKeeper::new(
+            init_params,
+            fee_account,
+            commit_requests_sender,
+            mempool_requests_sender,
+            BLOCK_CHUNK_SIZES.to_vec(),
+            MAX_MINIBLOCK_ITERATIONS,
+            MAX_MINIBLOCK_ITERATIONS,
+            Duration::from_secs(0),
+            BlockSealPolicy::default(),
+            processed_ops_sender,
+        );
+        let tasks = vec![
+            tokio::spawn(state_keeper.run_for_testkit(state_keeper_receiver)),
+            start_root_hash_calculator(root_hash_calculator, ShutdownToken::never()),
+        ];
+
+        Self {
+            eth_checker,
+            valid_from_window: ValidFromWindow::default(),
+            eth_sig_exemptions: EthSignatureExemptions::default(),
+            mempool: InMemoryMempool::new(),
+            fee_account,
+            state_keeper: state_keeper_sender,
+            commit_requests,
+            processed_ops,
+            _mempool_requests: mempool_requests,
+            tasks,
+            sealed_blocks: Vec::new(),
+            root_hashes: HashMap::new(),
+        }
+    }
+
+    pub fn with_valid_from_window(mut self, valid_from_window: ValidFromWindow) -> Self {
+        self.valid_from_window = valid_from_window;
+        self
+    }
+
+    pub fn with_eth_sig_exemptions(mut self, eth_sig_exemptions: EthSignatureExemptions) -> Self {
+        self.eth_sig_exemptions = eth_sig_exemptions;
+        self
+    }
+
+    /// Address of the account collecting the fees.
+    pub fn fee_account(&self) -> Address {
+        self.fee_account
+    }
+
+    pub fn mempool(&self) -> &InMemoryMempool {
+        &self.mempool
+    }
+
+    /// Blocks sealed so far, in order.
+    pub fn sealed_blocks(&self) -> &[IncompleteBlock] {
+        &self.sealed_blocks
+    }
+
+    /// Queues the deposit to the given address, returns its serial ID.
+    pub fn deposit(&mut self, to: Address, token: TokenId, amount: impl Into<BigUint>) -> u64 {
+        self.mempool.add_deposit(to, to, token, amount.into())
+    }
+
+    /// Verifies the transaction or the batch the same way the API does and queues it in the mempool.
+    ///
+    /// Panics if the request is neither a transaction nor a batch, since only those get into the mempool.
+    pub async fn submit(&mut self, request: RequestData) -> Result<Vec<TxHash>, TxAddError> {
+        let is_batch = match &request {
+            RequestData::Tx(_) => false,
+            RequestData::Batch(_) => true,
+            RequestData::Order(_) | RequestData::Toggle2FA(_) => {
+                panic!("Only transactions and batches can be submitted to the mempool")
+            }
+        };
+        let verified_tx = verify_tx_signature(
+            request,
+            &self.eth_checker,
+            &self.valid_from_window,
+            &self.eth_sig_exemptions,
+        )
+        .await?;
+
+        if is_batch {
+            let (txs, batch_sign_data) = verified_tx.unwrap_batch();
+            let hashes = txs.iter().map(|tx| tx.hash()).collect();
+            let eth_signatures = batch_sign_data
+                .map(|sign_data| sign_data.signatures)
+                .unwrap_or_default();
+            self.mempool.add_batch(txs, eth_signatures);
+            Ok(hashes)
+        } else {
+            let tx = verified_tx.unwrap_tx();
+            let hash = tx.hash();
+            self.mempool.add_tx(tx);
+            Ok(vec![hash])
+        }
+    }
+
+    /// Executes all the operations queued in the mempool as a single miniblock and returns
+    /// the execution results. Blocks sealed by the state keeper in the process, e.g. since
+    /// the operations didn't fit into one block, are available in `sealed_blocks`.
+    pub async fn execute_miniblock(&mut self) -> Vec<ExecutedOperations> {
+        let proposed_block = self.mempool.propose_block();
+        self.send(StateKeeperTestkitRequest::ExecuteMiniBlock(proposed_block))
+            .await;
+        self.sync().await;
+
+        let mut executed_ops = Vec::new();
+        while let Ok(Some(processed)) = self.processed_ops.try_next() {
+            executed_ops.extend(processed.executed_ops);
+        }
+        executed_ops
+    }
+
+    /// Seals the pending block and returns it.
+    ///
+    /// Panics if there is no pending block, i.e. nothing was executed since the last sealed block.
+    pub async fn seal_block(&mut self) -> IncompleteBlock {
+        let sealed_blocks = self.sealed_blocks.len();
+        self.send(StateKeeperTestkitRequest::SealBlock).await;
+        self.sync().await;
+        assert!(
+            self.sealed_blocks.len() > sealed_blocks,
+            "State keeper has no pending block to seal"
+        );
+        self.sealed_blocks.last().cloned().unwrap()
+    }
+
+    /// Waits for the root hash of the sealed block to be calculated and returns it.
+    pub async fn root_hash(&mut self, block_number: BlockNumber) -> Fr {
+        assert!(
+            self.sealed_blocks
+                .iter()
+                .any(|block| block.block_number == block_number),
+            "Block {} is not sealed",
+            block_number
+        );
+        loop {
+            if let Some(root_hash) = self.root_hashes.get(&block_number) {
+                return *root_hash;
+            }
+            let request = self
+                .commit_requests
+                .next()
+                .await
+                .expect("State keeper has stopped");
+            self.handle_commit_request(request);
+        }
+    }
+
+    /// Returns the account from the current state of the state keeper.
+    pub async fn account(&mut self, address: Address) -> Option<(AccountId, Account)> {
+        let (sender, receiver) = oneshot::channel();
+        self.send(StateKeeperTestkitRequest::GetAccount(address, sender))
+            .await;
+        let account = receiver.await.expect("State keeper has stopped");
+        self.collect_commit_requests();
+        account
+    }
+
+    /// Returns the balance of the account in the current state, zero for the missing accounts.
+    pub async fn balance(&mut self, address: Address, token: TokenId) -> BigUint {
+        self.account(address)
+            .await
+            .map(|(_, account)| account.get_balance(token))
+            .unwrap_or_default()
+    }
+
+    async fn send(&mut self, request: StateKeeperTestkitRequest) {
+        self.state_keeper
+            .send(request)
+            .await
+            .expect("State keeper has stopped");
+    }
+
+    /// Waits until the state keeper handles all the previously sent requests. State keeper handles
+    /// the requests one by one, so once it answers the request, the previous ones are done.
+    async fn sync(&mut self) {
+        let fee_account = self.fee_account;
+        self.account(fee_account).await;
+    }
+
+    fn collect_commit_requests(&mut self) {
+        while let Ok(Some(request)) = self.commit_requests.try_next() {
+            self.handle_commit_request(request);
+        }
+    }
+
+    fn handle_commit_request(&mut self, request: CommitRequest) {
+        match request {
+            CommitRequest::SealIncompleteBlock((request, _)) => {
+                self.sealed_blocks.push(request.block);
+            }
+            CommitRequest::FinishBlock(request) => {
+                self.root_hashes
+                    .insert(request.block_number, request.root_hash);
+            }
+            CommitRequest::PendingBlock(_) | CommitRequest::RemoveRevertedBlock(_) => {}
+        }
+    }
+}
+
+impl Default for PipelineHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for PipelineHarness {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
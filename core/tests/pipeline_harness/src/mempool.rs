@@ -0,0 +1,87 @@
+// Built-in uses
+use std::collections::VecDeque;
+// External uses
+use num::BigUint;
+// Workspace uses
+use zksync_mempool::ProposedBlock;
+use zksync_types::{
+    mempool::SignedTxVariant, tx::TxEthSignature, Address, Deposit, PriorityOp, SignedZkSyncTx,
+    TokenId, ZkSyncPriorityOp, H256,
+};
+
+/// In-memory replacement of the mempool for the harness.
+///
+/// Unlike the real mempool, it doesn't persist the transactions and doesn't reorder them:
+/// the transactions are proposed in the order they were added, and the state keeper decides
+/// whether they fit into the pending block. All the queued operations are proposed at once.
+#[derive(Debug, Default)]
+pub struct InMemoryMempool {
+    priority_ops: VecDeque<PriorityOp>,
+    txs: VecDeque<SignedTxVariant>,
+    next_serial_id: u64,
+    next_batch_id: i64,
+}
+
+impl InMemoryMempool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_tx(&mut self, tx: SignedZkSyncTx) {
+        self.txs.push_back(tx.into());
+    }
+
+    /// Adds the batch, assigning the next batch ID to it.
+    pub fn add_batch(&mut self, txs: Vec<SignedZkSyncTx>, eth_signatures: Vec<TxEthSignature>) {
+        self.next_batch_id += 1;
+        self.txs.push_back(SignedTxVariant::batch(
+            txs,
+            self.next_batch_id,
+            eth_signatures,
+        ));
+    }
+
+    /// Adds the deposit as if it was received from the Ethereum, returns its serial ID.
+    pub fn add_deposit(
+        &mut self,
+        from: Address,
+        to: Address,
+        token: TokenId,
+        amount: BigUint,
+    ) -> u64 {
+        let serial_id = self.next_serial_id;
+        self.next_serial_id += 1;
+        self.priority_ops.push_back(PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from,
+                token,
+                amount,
+                to,
+            }),
+            deadline_block: 0,
+            eth_hash: H256::from_low_u64_be(serial_id),
+            eth_block: serial_id,
+            eth_block_index: Some(0),
+        });
+        serial_id
+    }
+
+    /// Returns the number of the queued transactions and batches.
+    pub fn pending_txs(&self) -> usize {
+        self.txs.len()
+    }
+
+    /// Returns the number of the queued priority operations.
+    pub fn pending_priority_ops(&self) -> usize {
+        self.priority_ops.len()
+    }
+
+    /// Takes all the queued operations out of the mempool.
+    pub fn propose_block(&mut self) -> ProposedBlock {
+        ProposedBlock {
+            priority_ops: self.priority_ops.drain(..).collect(),
+            txs: self.txs.drain(..).collect(),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use num::BigUint;
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::{
+    tx::{ChangePubKeyType, TimeRange},
+    Nonce,
+};
+
+use super::*;
+
+/// Deposits the funds to the new account and sets its signing key.
+async fn create_account(harness: &mut PipelineHarness, amount: u64) -> ZkSyncAccount {
+    let account = ZkSyncAccount::rand();
+    harness.deposit(account.address, TokenId(0), amount);
+    harness.execute_miniblock().await;
+
+    let (account_id, _) = harness.account(account.address).await.unwrap();
+    account.set_account_id(Some(account_id));
+    let change_pubkey = account.sign_change_pubkey_tx(
+        None,
+        true,
+        TokenId(0),
+        BigUint::from(0u32),
+        ChangePubKeyType::ECDSA,
+        TimeRange::default(),
+    );
+    let request = tx_request(change_pubkey.into(), None, account.address, eth_token());
+    harness.submit(request).await.unwrap();
+    let executed = harness.execute_miniblock().await;
+    assert!(matches!(&executed[..], [ExecutedOperations::Tx(tx)] if tx.success));
+    account
+}
+
+#[tokio::test]
+async fn transfer_to_new_account() {
+    let mut harness = PipelineHarness::new();
+    let sender = create_account(&mut harness, 1_000).await;
+    let recipient = ZkSyncAccount::rand();
+
+    let (transfer, eth_signature) = sender.sign_transfer(
+        TokenId(0),
+        "ETH",
+        BigUint::from(300u32),
+        BigUint::from(10u32),
+        &recipient.address,
+        None,
+        true,
+        TimeRange::default(),
+    );
+    let request = tx_request(transfer.into(), eth_signature, sender.address, eth_token());
+    let hashes = harness.submit(request).await.unwrap();
+    assert_eq!(harness.mempool().pending_txs(), 1);
+
+    let executed = harness.execute_miniblock().await;
+    match &executed[..] {
+        [ExecutedOperations::Tx(tx)] => {
+            assert!(tx.success, "{:?}", tx.fail_reason);
+            assert_eq!(tx.signed_tx.hash(), hashes[0]);
+        }
+        other => panic!("Unexpected operations: {:?}", other),
+    }
+    assert_eq!(harness.mempool().pending_txs(), 0);
+
+    assert_eq!(
+        harness.balance(sender.address, TokenId(0)).await,
+        690u32.into()
+    );
+    assert_eq!(
+        harness.balance(recipient.address, TokenId(0)).await,
+        300u32.into()
+    );
+
+    let block = harness.seal_block().await;
+    assert_eq!(block.block_number, BlockNumber(1));
+    // Deposit, `ChangePubKey` and transfer.
+    assert_eq!(block.block_transactions.len(), 3);
+    assert_eq!(block.processed_priority_ops, (0, 1));
+    harness.root_hash(block.block_number).await;
+}
+
+#[tokio::test]
+async fn rejected_requests_dont_reach_mempool() {
+    let mut harness = PipelineHarness::new();
+    let sender = create_account(&mut harness, 1_000).await;
+
+    // Transfer is signed by the zkSync key, but not by the Ethereum one.
+    let (transfer, _) = sender.sign_transfer(
+        TokenId(0),
+        "ETH",
+        BigUint::from(300u32),
+        BigUint::from(10u32),
+        &Address::repeat_byte(0x01),
+        None,
+        true,
+        TimeRange::default(),
+    );
+    let request = tx_request(transfer.into(), None, sender.address, eth_token());
+    assert!(matches!(
+        harness.submit(request).await,
+        Err(TxAddError::MissingEthSignature)
+    ));
+    assert_eq!(harness.mempool().pending_txs(), 0);
+
+    // Transfer with the nonce already used is accepted by the signature checker,
+    // but fails in the state keeper.
+    let (transfer, eth_signature) = sender.sign_transfer(
+        TokenId(0),
+        "ETH",
+        BigUint::from(300u32),
+        BigUint::from(10u32),
+        &Address::repeat_byte(0x01),
+        Some(Nonce(0)),
+        false,
+        TimeRange::default(),
+    );
+    let request = tx_request(transfer.into(), eth_signature, sender.address, eth_token());
+    harness.submit(request).await.unwrap();
+    let executed = harness.execute_miniblock().await;
+    assert!(matches!(&executed[..], [ExecutedOperations::Tx(tx)] if !tx.success));
+}
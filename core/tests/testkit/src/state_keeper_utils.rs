@@ -3,18 +3,20 @@ use futures::{
     SinkExt,
 };
 use std::thread::JoinHandle;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 use zksync_core::{
     committer::CommitRequest,
     state_keeper::{
-        start_root_hash_calculator, StateKeeperTestkitRequest, ZkSyncStateInitParams,
-        ZkSyncStateKeeper,
+        start_root_hash_calculator, BlockSealPolicy, StateKeeperTestkitRequest,
+        ZkSyncStateInitParams, ZkSyncStateKeeper,
     },
     tx_event_emitter::ProcessedOperations,
 };
 use zksync_types::{
     Account, AccountId, Address, DepositOp, FullExitOp, TransferOp, TransferToNewOp, WithdrawOp,
 };
+use zksync_utils::shutdown::ShutdownToken;
 
 use itertools::Itertools;
 use zksync_mempool::MempoolBlocksRequest;
@@ -72,6 +74,8 @@ pub fn spawn_state_keeper(
         block_chunks_sizes,
         max_miniblock_iterations,
         max_miniblock_iterations,
+        Duration::from_secs(0),
+        BlockSealPolicy::default(),
         processed_tx_events_sender,
     );
 
@@ -81,7 +85,8 @@ pub fn spawn_state_keeper(
         main_runtime.block_on(async move {
             let state_keeper_task =
                 tokio::spawn(state_keeper.run_for_testkit(state_keeper_req_receiver));
-            let root_hash_calculator_task = start_root_hash_calculator(root_hash_calculator);
+            let root_hash_calculator_task =
+                start_root_hash_calculator(root_hash_calculator, ShutdownToken::never());
             tokio::select! {
                 _ = stop_state_keeper_receiver => {},
                 _ = root_hash_calculator_task => {},
@@ -262,9 +262,9 @@ Tester.prototype.testMultipleWalletsWrongSignature = async function (
         thrown = false; // this line should be unreachable
     } catch (e: any) {
         if (providerType === 'REST') {
-            expect(e.restError.message).to.equal('Transaction adding error: Eth signature is incorrect.');
+            expect(e.restError.message).to.include('Transaction adding error: Eth signature is incorrect (tried: ');
         } else {
-            expect(e.jrpcError.message).to.equal('Eth signature is incorrect');
+            expect(e.jrpcError.message).to.include('Eth signature is incorrect (tried: ');
         }
     }
     expect(thrown, 'Sending batch with incorrect ETH signature must throw').to.be.true;
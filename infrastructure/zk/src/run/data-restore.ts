@@ -25,7 +25,7 @@ export async function restart() {
 }
 
 export async function resume() {
-    await utils.spawn('cargo run --bin zksync_data_restore --release -- --continue');
+    await utils.spawn('cargo run --bin zksync_data_restore --release -- --resume');
 }
 
 export async function run() {
@@ -225,15 +225,23 @@ command
 
 command
     .command('exit-proof')
-    .option('--account <id>')
-    .option('--token <id>')
+    .option('--address <address>')
+    .option('--token <token>', 'token to withdraw, all the tokens of the account if not set')
+    .option('--output <path>', 'file to write the exit transaction inputs to')
     .option('--help')
-    .description('generate exit proof')
+    .description('generate exit proofs')
     .action(async (cmd: Command) => {
-        if (!cmd.account || !cmd.token) {
+        if (!cmd.address) {
             await exitProof('--help');
         } else {
-            await exitProof('--account_id', cmd.account, '--token', cmd.token);
+            const args = ['--address', cmd.address];
+            if (cmd.token) {
+                args.push('--token', cmd.token);
+            }
+            if (cmd.output) {
+                args.push('--output', cmd.output);
+            }
+            await exitProof(...args);
         }
     });
 
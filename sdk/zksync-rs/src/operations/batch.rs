@@ -0,0 +1,325 @@
+use num::BigUint;
+use zksync_eth_signer::{error::SignerError, EthereumSigner};
+use zksync_types::{
+    helpers::{
+        closest_packable_fee_amount, closest_packable_token_amount, is_fee_amount_packable,
+        is_token_amount_packable,
+    },
+    tokens::{ChangePubKeyFeeTypeArg, TxFeeTypes},
+    tx::{ChangePubKeyType, PackedEthSignature, TimeRange},
+    Address, Nonce, Token, TokenLike, Transfer, Withdraw, ZkSyncTx,
+};
+
+use crate::{
+    error::ClientError, operations::SyncTransactionHandle, provider::Provider, wallet::Wallet,
+};
+
+/// Transaction of the batch before it's signed.
+#[derive(Debug, Clone)]
+enum BatchTx {
+    Transfer {
+        token: Token,
+        amount: BigUint,
+        to: Address,
+    },
+    Withdraw {
+        token: Token,
+        amount: BigUint,
+        to: Address,
+    },
+    ChangePubKey {
+        onchain_auth: bool,
+    },
+}
+
+impl BatchTx {
+    fn fee_type(&self) -> TxFeeTypes {
+        match self {
+            BatchTx::Transfer { .. } => TxFeeTypes::Transfer,
+            BatchTx::Withdraw { .. } => TxFeeTypes::Withdraw,
+            BatchTx::ChangePubKey { onchain_auth } => {
+                let auth_type = if *onchain_auth {
+                    ChangePubKeyType::Onchain
+                } else {
+                    ChangePubKeyType::ECDSA
+                };
+                TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(auth_type))
+            }
+        }
+    }
+}
+
+/// Builder of the transactions batch: the transactions are executed all together or not at all,
+/// and the whole batch is signed with a single Ethereum signature.
+///
+/// The fee of the whole batch is paid with the additional transfer to the wallet itself
+/// at the end of the batch.
+#[derive(Debug)]
+pub struct BatchBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    txs: Vec<BatchTx>,
+    fee_token: Option<Token>,
+    fee: Option<BigUint>,
+    nonce: Option<Nonce>,
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+}
+
+impl<'a, S, P> BatchBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    /// Initializes a transactions batch building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            txs: Vec::new(),
+            fee_token: None,
+            fee: None,
+            nonce: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    /// Directly returns the signed transactions of the batch and the Ethereum signature
+    /// of the batch for the subsequent usage.
+    pub async fn txs(
+        self,
+    ) -> Result<
+        (
+            Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+            Option<PackedEthSignature>,
+        ),
+        ClientError,
+    > {
+        if self.txs.is_empty() {
+            return Err(ClientError::MissingRequiredField("txs".into()));
+        }
+        let fee_token = self
+            .fee_token
+            .ok_or_else(|| ClientError::MissingRequiredField("fee_token".into()))?;
+        let address = self.wallet.address();
+        let account_id = self
+            .wallet
+            .signer
+            .get_account_id()
+            .ok_or(ClientError::SigningError(SignerError::NoSigningKey))?;
+        let time_range = TimeRange::new(
+            self.valid_from.unwrap_or(0),
+            self.valid_until.unwrap_or(u64::MAX),
+        );
+
+        let fee = match self.fee {
+            Some(fee) => fee,
+            None => {
+                // The fee transfer is the part of the batch too.
+                let mut fee_types: Vec<_> = self.txs.iter().map(BatchTx::fee_type).collect();
+                fee_types.push(TxFeeTypes::Transfer);
+                let addresses = vec![address; fee_types.len()];
+                self.wallet
+                    .provider
+                    .get_txs_batch_fee(fee_types, addresses, fee_token.id)
+                    .await?
+            }
+        };
+
+        let mut nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let account_info = self.wallet.provider.account_info(address).await?;
+                account_info.committed.nonce
+            }
+        };
+
+        let private_key = &self.wallet.signer.private_key;
+        let fee_transfer = BatchTx::Transfer {
+            token: fee_token.clone(),
+            amount: BigUint::from(0u32),
+            to: address,
+        };
+        let fee_transfer_idx = self.txs.len();
+        let mut signed_txs = Vec::with_capacity(fee_transfer_idx + 1);
+        for (idx, tx) in self
+            .txs
+            .into_iter()
+            .chain(std::iter::once(fee_transfer))
+            .enumerate()
+        {
+            let tx_fee = if idx == fee_transfer_idx {
+                fee.clone()
+            } else {
+                BigUint::from(0u32)
+            };
+            let signed_tx = match tx {
+                BatchTx::Transfer { token, amount, to } => {
+                    let transfer = Transfer::new_signed(
+                        account_id,
+                        address,
+                        to,
+                        token.id,
+                        amount,
+                        tx_fee,
+                        nonce,
+                        time_range,
+                        private_key,
+                    )
+                    .map_err(|err| signing_error(err.to_string()))?;
+                    (ZkSyncTx::from(transfer), token)
+                }
+                BatchTx::Withdraw { token, amount, to } => {
+                    let withdraw = Withdraw::new_signed(
+                        account_id,
+                        address,
+                        to,
+                        token.id,
+                        amount,
+                        tx_fee,
+                        nonce,
+                        time_range,
+                        private_key,
+                    )
+                    .map_err(|err| signing_error(err.to_string()))?;
+                    (ZkSyncTx::from(withdraw), token)
+                }
+                BatchTx::ChangePubKey { onchain_auth } => {
+                    let change_pubkey = self
+                        .wallet
+                        .signer
+                        .sign_change_pubkey_tx(
+                            nonce,
+                            onchain_auth,
+                            fee_token.clone(),
+                            tx_fee,
+                            time_range,
+                        )
+                        .await
+                        .map_err(ClientError::SigningError)?;
+                    (ZkSyncTx::from(change_pubkey), fee_token.clone())
+                }
+            };
+            signed_txs.push(signed_tx);
+            nonce = nonce + 1;
+        }
+
+        let batch_signature = self
+            .wallet
+            .signer
+            .sign_batch(signed_txs.clone())
+            .await
+            .map_err(ClientError::SigningError)?;
+        let txs = signed_txs.into_iter().map(|(tx, _)| (tx, None)).collect();
+        Ok((txs, batch_signature))
+    }
+
+    /// Sends the transactions batch, returning the handles for its transactions awaiting.
+    pub async fn send(self) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
+        let provider = self.wallet.provider.clone();
+
+        let (txs, batch_signature) = self.txs().await?;
+        let tx_hashes = provider.send_txs_batch(txs, batch_signature).await?;
+
+        Ok(tx_hashes
+            .into_iter()
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
+            .collect())
+    }
+
+    /// Adds the transfer to the batch. If the amount is not packable, rounds it
+    /// to the closest packable amount.
+    pub fn add_transfer(
+        mut self,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+        to: Address,
+    ) -> Result<Self, ClientError> {
+        let token = self.resolve_token(token)?;
+        let amount = closest_packable_token_amount(&amount.into());
+        self.txs.push(BatchTx::Transfer { token, amount, to });
+        Ok(self)
+    }
+
+    /// Adds the withdrawal to the batch. Returns an error if the amount is not packable.
+    pub fn add_withdraw(
+        mut self,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+        to: Address,
+    ) -> Result<Self, ClientError> {
+        let token = self.resolve_token(token)?;
+        let amount = amount.into();
+        if !is_token_amount_packable(&amount) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.txs.push(BatchTx::Withdraw { token, amount, to });
+        Ok(self)
+    }
+
+    /// Adds the change of the signing key to the batch. It has to be the first transaction
+    /// of the batch if the signing key of the account is not set yet.
+    pub fn add_change_pubkey(mut self, onchain_auth: bool) -> Self {
+        self.txs.push(BatchTx::ChangePubKey { onchain_auth });
+        self
+    }
+
+    /// Sets the token the fee of the batch is paid in. Returns an error if token is not supported by zkSync.
+    pub fn fee_token(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        self.fee_token = Some(self.resolve_token(token)?);
+        Ok(self)
+    }
+
+    /// Set the fee amount of the whole batch. If the provided fee is not packable,
+    /// rounds it to the closest packable fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: impl Into<BigUint>) -> Self {
+        let fee = closest_packable_fee_amount(&fee.into());
+        self.fee = Some(fee);
+
+        self
+    }
+
+    /// Set the fee amount of the whole batch. If the provided fee is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee_exact(mut self, fee: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let fee = fee.into();
+        if !is_fee_amount_packable(&fee) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.fee = Some(fee);
+
+        Ok(self)
+    }
+
+    /// Sets the nonce of the first transaction of the batch.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the unix format timestamp of the first moment when the batch execution is valid.
+    pub fn valid_from(mut self, valid_from: u64) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Sets the unix format timestamp of the last moment when the batch execution is valid.
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    fn resolve_token(&self, token: impl Into<TokenLike>) -> Result<Token, ClientError> {
+        self.wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)
+    }
+}
+
+fn signing_error(err: String) -> ClientError {
+    ClientError::SigningError(SignerError::SigningFailed(err))
+}
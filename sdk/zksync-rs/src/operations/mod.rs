@@ -11,10 +11,12 @@ use crate::{
 };
 
 pub use self::{
-    change_pubkey::ChangePubKeyBuilder, mint_nft::MintNFTBuilder, transfer::TransferBuilder,
-    transfer_nft::TransferNFTBuilder, withdraw::WithdrawBuilder, withdraw_nft::WithdrawNFTBuilder,
+    batch::BatchBuilder, change_pubkey::ChangePubKeyBuilder, mint_nft::MintNFTBuilder,
+    transfer::TransferBuilder, transfer_nft::TransferNFTBuilder, withdraw::WithdrawBuilder,
+    withdraw_nft::WithdrawNFTBuilder,
 };
 
+mod batch;
 mod change_pubkey;
 mod mint_nft;
 mod transfer;
@@ -7,11 +7,11 @@ use zksync_crypto::PrivateKey;
 use zksync_eth_signer::{error::SignerError, EthereumSigner};
 use zksync_types::{
     tx::{
-        ChangePubKey, ChangePubKeyECDSAData, ChangePubKeyEthAuthData, PackedEthSignature,
-        TimeRange, TxEthSignature,
+        ChangePubKey, ChangePubKeyECDSAData, ChangePubKeyEthAuthData, EthBatchSignData,
+        PackedEthSignature, TimeRange, TxEthSignature,
     },
     AccountId, Address, ForcedExit, MintNFT, Nonce, PubKeyHash, Token, TokenId, Transfer, Withdraw,
-    WithdrawNFT, H256,
+    WithdrawNFT, ZkSyncTx, H256,
 };
 // Local imports
 use crate::WalletCredentials;
@@ -119,6 +119,12 @@ impl<S: EthereumSigner> Signer<S> {
                 TxEthSignature::EIP1271Signature(..) => Err(SignerError::CustomError(
                     "Can't sign ChangePubKey message with EIP1271 signer".to_string(),
                 )),
+                TxEthSignature::AggregatedBLS(..) => Err(SignerError::CustomError(
+                    "Can't sign ChangePubKey message with aggregated signature".to_string(),
+                )),
+                TxEthSignature::EIP712Signature(..) => Err(SignerError::CustomError(
+                    "Can't sign ChangePubKey message with EIP712 signature".to_string(),
+                )),
             }?;
 
             ChangePubKeyEthAuthData::ECDSA(ChangePubKeyECDSAData {
@@ -338,4 +344,31 @@ impl<S: EthereumSigner> Signer<S> {
 
         Ok((withdraw_nft, eth_signature))
     }
+
+    /// Signs the whole transactions batch with a single Ethereum signature. Every transaction
+    /// goes with the token its amounts are shown in.
+    ///
+    /// Returns `None` if there is no Ethereum signer.
+    pub async fn sign_batch(
+        &self,
+        txs: Vec<(ZkSyncTx, Token)>,
+    ) -> Result<Option<PackedEthSignature>, SignerError> {
+        let signer = match &self.eth_signer {
+            Some(signer) => signer,
+            None => return Ok(None),
+        };
+
+        let txs = txs
+            .into_iter()
+            .map(|(tx, token)| (tx, token, self.address))
+            .collect();
+        let message = EthBatchSignData::get_batch_sign_message(txs);
+        let signature = signer.sign_message(&message).await?;
+
+        if let TxEthSignature::EthereumSignature(packed_signature) = signature {
+            Ok(Some(packed_signature))
+        } else {
+            Err(SignerError::MissingEthSigner)
+        }
+    }
 }
@@ -158,6 +158,11 @@ where
         WithdrawNFTBuilder::new(self)
     }
 
+    /// Initializes the sending of the transactions batch signed with a single Ethereum signature.
+    pub fn start_batch(&self) -> BatchBuilder<'_, S, P> {
+        BatchBuilder::new(self)
+    }
+
     /// Creates an `EthereumProvider` to interact with the Ethereum network.
     ///
     /// Returns an error if wallet was created without providing an Ethereum private key.
@@ -522,7 +522,7 @@ mod wallet_tests {
     use zksync_eth_signer::PrivateKeySigner;
     use zksync_types::{
         tokens::get_genesis_token_list,
-        tx::{PackedEthSignature, TxHash},
+        tx::{EthBatchSignData, PackedEthSignature, TxHash},
         Address, PubKeyHash, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H256,
     };
 
@@ -764,6 +764,54 @@ mod wallet_tests {
         assert!(wallet.is_signing_key_set().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_wallet_batch() {
+        let wallet = get_test_wallet(&[50; 32], Network::Mainnet).await;
+        let recipient = Address::repeat_byte(0x11);
+        let (txs, batch_signature) = wallet
+            .start_batch()
+            .add_transfer(TokenId(1), 100_u32, recipient)
+            .unwrap()
+            .add_withdraw(TokenId(2), 200_u32, recipient)
+            .unwrap()
+            .fee_token(TokenId(1))
+            .unwrap()
+            .fee(1000_u32)
+            .nonce(Nonce(5))
+            .txs()
+            .await
+            .unwrap();
+
+        // The fee of the whole batch is paid with the transfer to the wallet itself.
+        assert_eq!(txs.len(), 3);
+        for (idx, (tx, eth_signature)) in txs.iter().enumerate() {
+            assert_eq!(tx.nonce(), Nonce(5 + idx as u32));
+            assert!(eth_signature.is_none());
+        }
+        match &txs[2].0 {
+            ZkSyncTx::Transfer(transfer) => {
+                assert_eq!(transfer.to, wallet.address());
+                assert_eq!(transfer.fee, BigUint::from(1000_u32));
+            }
+            tx => panic!("Unexpected fee transaction: {:?}", tx),
+        }
+
+        let batch_message = EthBatchSignData::get_batch_sign_message(
+            txs.into_iter()
+                .zip(&[TokenId(1), TokenId(2), TokenId(1)])
+                .map(|((tx, _), token_id)| {
+                    let token = wallet.tokens.resolve((*token_id).into()).unwrap();
+                    (tx, token, wallet.address())
+                })
+                .collect(),
+        );
+        let signer = batch_signature
+            .unwrap()
+            .signature_recover_signer(&batch_message)
+            .unwrap();
+        assert_eq!(signer, wallet.address());
+    }
+
     #[tokio::test]
     async fn test_wallet_ethereum() {
         let wallet = get_test_wallet(&[50; 32], Network::Mainnet).await;
@@ -5,7 +5,8 @@ import {
     signMessagePersonalAPI,
     getChangePubkeyMessage,
     serializeAddress,
-    serializeAccountId
+    serializeAccountId,
+    MAX_TIMESTAMP
 } from './utils';
 
 /**
@@ -35,6 +36,8 @@ export class EthMessageSigner {
         to: string;
         nonce: number;
         accountId: number;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let humanReadableTxInfo = this.getTransferEthMessagePart(transfer);
         if (humanReadableTxInfo.length != 0) {
@@ -52,6 +55,8 @@ export class EthMessageSigner {
         to: string;
         nonce: number;
         accountId: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<TxEthSignature> {
         const message = this.getTransferEthSignMessage(transfer);
         return await this.getEthMessageSignature(message);
@@ -69,6 +74,8 @@ export class EthMessageSigner {
         amount: string;
         ratio: Ratio;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<TxEthSignature> {
         const message = this.getOrderEthSignMessage(order);
         return await this.getEthMessageSignature(message);
@@ -97,6 +104,8 @@ export class EthMessageSigner {
         amount: string;
         ratio: Ratio;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let message: string;
         if (order.amount == '0' || order.amount == null) {
@@ -106,8 +115,9 @@ export class EthMessageSigner {
         }
         message +=
             `Ratio: ${order.ratio[0].toString()}:${order.ratio[1].toString()}\n` +
-            `Address: ${order.recipient.toLowerCase()}\n` +
-            `Nonce: ${order.nonce}`;
+            `Address: ${order.recipient.toLowerCase()}`;
+        message = this.appendTimeRangeEthMessagePart(message, order);
+        message += `\nNonce: ${order.nonce}`;
         return message;
     }
 
@@ -116,6 +126,8 @@ export class EthMessageSigner {
         stringFee: string;
         target: string;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<TxEthSignature> {
         const message = this.getForcedExitEthSignMessage(forcedExit);
         return await this.getEthMessageSignature(message);
@@ -155,6 +167,8 @@ export class EthMessageSigner {
         to: string;
         stringFee: string;
         stringFeeToken: string;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let humanReadableTxInfo = `WithdrawNFT ${withdrawNFT.token} to: ${withdrawNFT.to.toLowerCase()}`;
 
@@ -162,7 +176,7 @@ export class EthMessageSigner {
             humanReadableTxInfo += `\nFee: ${withdrawNFT.stringFee} ${withdrawNFT.stringFeeToken}`;
         }
 
-        return humanReadableTxInfo;
+        return this.appendTimeRangeEthMessagePart(humanReadableTxInfo, withdrawNFT);
     }
 
     getWithdrawNFTEthSignMessage(withdrawNFT: {
@@ -171,6 +185,8 @@ export class EthMessageSigner {
         stringFee: string;
         stringFeeToken: string;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let humanReadableTxInfo = this.getWithdrawNFTEthMessagePart(withdrawNFT);
 
@@ -186,6 +202,8 @@ export class EthMessageSigner {
         ethAddress: string;
         nonce: number;
         accountId: number;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let humanReadableTxInfo = this.getWithdrawEthMessagePart(withdraw);
         if (humanReadableTxInfo.length != 0) {
@@ -201,6 +219,8 @@ export class EthMessageSigner {
         stringFee: string;
         target: string;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let humanReadableTxInfo = this.getForcedExitEthMessagePart(forcedExit);
         humanReadableTxInfo += `\nNonce: ${forcedExit.nonce}`;
@@ -213,6 +233,8 @@ export class EthMessageSigner {
         stringFee: string;
         ethAddress?: string;
         to?: string;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let txType: string, to: string;
         if (tx.ethAddress != undefined) {
@@ -235,7 +257,7 @@ export class EthMessageSigner {
             }
             message += `Fee: ${tx.stringFee} ${tx.stringToken}`;
         }
-        return message;
+        return this.appendTimeRangeEthMessagePart(message, tx);
     }
 
     getWithdrawEthMessagePart(tx: {
@@ -244,6 +266,8 @@ export class EthMessageSigner {
         stringFee: string;
         ethAddress?: string;
         to?: string;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         return this.getTransferEthMessagePart(tx);
     }
@@ -252,20 +276,49 @@ export class EthMessageSigner {
         pubKeyHash: PubKeyHash;
         stringToken: string;
         stringFee: string;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         let message = '';
         message += `Set signing key: ${changePubKey.pubKeyHash.replace('sync:', '').toLowerCase()}`;
         if (changePubKey.stringFee != null) {
             message += `\nFee: ${changePubKey.stringFee} ${changePubKey.stringToken}`;
         }
-        return message;
+        return this.appendTimeRangeEthMessagePart(message, changePubKey);
     }
 
-    getForcedExitEthMessagePart(forcedExit: { stringToken: string; stringFee: string; target: string }): string {
+    getForcedExitEthMessagePart(forcedExit: {
+        stringToken: string;
+        stringFee: string;
+        target: string;
+        validFrom?: number;
+        validUntil?: number;
+    }): string {
         let message = `ForcedExit ${forcedExit.stringToken} to: ${forcedExit.target.toLowerCase()}`;
         if (forcedExit.stringFee != null) {
             message += `\nFee: ${forcedExit.stringFee} ${forcedExit.stringToken}`;
         }
+        return this.appendTimeRangeEthMessagePart(message, forcedExit);
+    }
+
+    /**
+     * Appends the time range of the transaction to the message, unless the transaction
+     * may be executed at any time (`validFrom` is 0 and `validUntil` is `MAX_TIMESTAMP`).
+     */
+    appendTimeRangeEthMessagePart(message: string, tx: { validFrom?: number; validUntil?: number }): string {
+        const lines = [];
+        if (tx.validFrom) {
+            lines.push(`Valid from: ${tx.validFrom}`);
+        }
+        if (tx.validUntil != null && tx.validUntil < MAX_TIMESTAMP) {
+            lines.push(`Valid until: ${tx.validUntil}`);
+        }
+        for (const line of lines) {
+            if (message.length != 0) {
+                message += '\n';
+            }
+            message += line;
+        }
         return message;
     }
 
@@ -286,6 +339,8 @@ export class EthMessageSigner {
         stringFee: string;
         stringFeeToken: string;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<TxEthSignature> {
         const message = this.getWithdrawNFTEthSignMessage(withdrawNFT);
         return await this.getEthMessageSignature(message);
@@ -298,6 +353,8 @@ export class EthMessageSigner {
         ethAddress: string;
         nonce: number;
         accountId: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<TxEthSignature> {
         const message = this.getWithdrawEthSignMessage(withdraw);
         return await this.getEthMessageSignature(message);
@@ -265,7 +265,8 @@ export class Provider extends SyncProvider {
             gasPriceWei: BigNumber.from(transactionFee.gasPriceWei),
             gasFee: BigNumber.from(transactionFee.gasFee),
             zkpFee: BigNumber.from(transactionFee.zkpFee),
-            totalFee: BigNumber.from(transactionFee.totalFee)
+            totalFee: BigNumber.from(transactionFee.totalFee),
+            pricing: transactionFee.pricing
         };
     }
 
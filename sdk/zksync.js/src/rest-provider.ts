@@ -120,6 +120,20 @@ export class RestProvider extends SyncProvider {
         return this.parseResponse(await this.accountInfoDetailed(idOrAddress, infoType));
     }
 
+    async accountNftsDetailed(
+        idOrAddress: number | types.Address,
+        infoType: 'committed' | 'finalized'
+    ): Promise<Response<types.ApiAccountNfts>> {
+        return await this.get(`${this.address}/accounts/${idOrAddress}/${infoType}/nfts`);
+    }
+
+    async accountNfts(
+        idOrAddress: number | types.Address,
+        infoType: 'committed' | 'finalized'
+    ): Promise<types.ApiAccountNfts> {
+        return this.parseResponse(await this.accountNftsDetailed(idOrAddress, infoType));
+    }
+
     async toggle2FADetailed(data: types.Toggle2FARequest): Promise<Response<types.Toggle2FAResponse>> {
         return await this.post(`${this.address}/transactions/toggle2FA`, data);
     }
@@ -129,6 +143,7 @@ export class RestProvider extends SyncProvider {
         return response.success;
     }
 
+    // `idOrAddress` may be the ENS name of the account, e.g. `name.eth`, if the node resolves the ENS names.
     async accountFullInfoDetailed(idOrAddress: number | types.Address): Promise<Response<types.ApiAccountFullInfo>> {
         return await this.get(`${this.address}/accounts/${idOrAddress}`);
     }
@@ -179,6 +194,33 @@ export class RestProvider extends SyncProvider {
         return this.parseResponse(await this.accountPendingTxsDetailed(idOrAddress, paginationQuery));
     }
 
+    async accountPendingDepositsDetailed(
+        idOrAddress: number | types.Address
+    ): Promise<Response<types.ApiPendingDeposit[]>> {
+        return await this.get(`${this.address}/accounts/${idOrAddress}/deposits/pending`);
+    }
+
+    async accountPendingDeposits(idOrAddress: number | types.Address): Promise<types.ApiPendingDeposit[]> {
+        return this.parseResponse(await this.accountPendingDepositsDetailed(idOrAddress));
+    }
+
+    async accountBalanceHistoryDetailed(
+        idOrAddress: number | types.Address,
+        paginationQuery: types.PaginationQuery<string>
+    ): Promise<Response<types.Paginated<types.ApiBalanceHistoryItem, string>>> {
+        return await this.get(
+            `${this.address}/accounts/${idOrAddress}/balance_history?from=${paginationQuery.from}` +
+                `&limit=${paginationQuery.limit}&direction=${paginationQuery.direction}`
+        );
+    }
+
+    async accountBalanceHistory(
+        idOrAddress: number | types.Address,
+        paginationQuery: types.PaginationQuery<string>
+    ): Promise<types.Paginated<types.ApiBalanceHistoryItem, string>> {
+        return this.parseResponse(await this.accountBalanceHistoryDetailed(idOrAddress, paginationQuery));
+    }
+
     async blockPaginationDetailed(
         paginationQuery: types.PaginationQuery<number>
     ): Promise<Response<types.Paginated<types.ApiBlockInfo, number>>> {
@@ -227,12 +269,18 @@ export class RestProvider extends SyncProvider {
         return this.parseResponse(await this.configDetailed());
     }
 
+    // `address` of the recipient may be its ENS name, e.g. `name.eth`, if the node resolves the ENS names.
     async getTransactionFeeDetailed(
         txType: types.IncomingTxFeeType,
         address: types.Address,
         tokenLike: types.TokenLike
     ): Promise<Response<types.FeeRest>> {
-        const rawFee = await this.post<{ gasFee: string; zkpFee: string; totalFee: string }>(`${this.address}/fee`, {
+        const rawFee = await this.post<{
+            gasFee: string;
+            zkpFee: string;
+            totalFee: string;
+            ensResolution?: types.ApiEnsResolution;
+        }>(`${this.address}/fee`, {
             txType,
             address,
             tokenLike
@@ -246,7 +294,8 @@ export class RestProvider extends SyncProvider {
                 result: {
                     gasFee: BigNumber.from(rawFee.result.gasFee),
                     zkpFee: BigNumber.from(rawFee.result.zkpFee),
-                    totalFee: BigNumber.from(rawFee.result.totalFee)
+                    totalFee: BigNumber.from(rawFee.result.totalFee),
+                    ensResolution: rawFee.result.ensResolution
                 }
             };
         } else {
@@ -382,6 +431,14 @@ export class RestProvider extends SyncProvider {
         return this.parseResponse(await this.txStatusDetailed(txHash));
     }
 
+    async txStatusesDetailed(txHashes: string[]): Promise<Response<(types.ApiTxReceipt | null)[]>> {
+        return await this.post(`${this.address}/transactions/receipts`, { txHashes });
+    }
+
+    async txStatuses(txHashes: string[]): Promise<(types.ApiTxReceipt | null)[]> {
+        return this.parseResponse(await this.txStatusesDetailed(txHashes));
+    }
+
     async txDataDetailed(txHash: string): Promise<Response<types.ApiSignedTx>> {
         return await this.get(`${this.address}/transactions/${txHash}/data`);
     }
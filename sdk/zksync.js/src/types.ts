@@ -374,6 +374,17 @@ export interface FeeRpc {
     zkpFee: BigNumber;
     // Total fee amount (in wei)
     totalFee: BigNumber;
+    // Prices the fee is calculated with (not set by the older servers)
+    pricing?: FeePricing;
+}
+
+export interface FeePricing {
+    // Price of the fee token in USD, per the whole token
+    tokenPriceUsd: string;
+    // Time the price of the fee token was last updated (RFC 3339)
+    priceUpdatedAt: string;
+    // Time the fee was calculated (RFC 3339)
+    calculatedAt: string;
 }
 
 export type BatchFee = BatchFeeRpc | FeeRest;
@@ -442,10 +453,67 @@ export interface ApiAccountInfo {
     };
 }
 
+export interface ApiAccountNfts {
+    nfts: {
+        [tokenId: number]: NFT;
+    };
+    mintedNfts: {
+        [tokenId: number]: NFT;
+    };
+}
+
 export interface ApiAccountFullInfo {
     depositing: Depositing;
     committed: ApiAccountInfo;
     finalized: ApiAccountInfo;
+    // Set if the account is requested by the ENS name.
+    ensResolution?: ApiEnsResolution;
+}
+
+export interface ApiEnsResolution {
+    name: string;
+    address: Address;
+    resolvedAt: string;
+}
+
+export interface ApiPendingDeposit {
+    serialId: number;
+    txHash: string;
+    ethHash: string;
+    ethBlock: number;
+    from: Address;
+    to: Address;
+    accountId?: number;
+    tokenId: number;
+    amount: BigNumberish;
+    confirmationsLeft?: number;
+}
+
+export type ApiBalanceChangeKind =
+    | 'deposit'
+    | 'transferIn'
+    | 'transferOut'
+    | 'withdrawal'
+    | 'forcedExit'
+    | 'fullExit'
+    | 'swapIn'
+    | 'swapOut'
+    | 'fee';
+
+export interface ApiBalanceChange {
+    kind: ApiBalanceChangeKind;
+    tokenId: number;
+    amount: BigNumberish;
+    counterparty?: Address;
+}
+
+export interface ApiBalanceHistoryItem {
+    txHash: string;
+    blockNumber: number;
+    blockIndex?: number;
+    status: 'committed' | 'finalized';
+    createdAt: string;
+    changes: ApiBalanceChange[];
 }
 
 export interface ApiConfig {
@@ -461,6 +529,8 @@ export interface FeeRest {
     gasFee: BigNumber;
     zkpFee: BigNumber;
     totalFee: BigNumber;
+    // Set if the fee is requested for the ENS name of the recipient.
+    ensResolution?: ApiEnsResolution;
 }
 
 export interface NetworkStatus {
@@ -491,11 +561,23 @@ export interface SubmitBatchResponse {
     batchHash: string;
 }
 
+export interface ApiL1TxInfo {
+    txHash: string;
+    ethBlock?: number;
+    confirmations?: number;
+}
+
+export interface ApiL1Finality {
+    commit?: ApiL1TxInfo;
+    verify?: ApiL1TxInfo;
+}
+
 export interface ApiL1TxReceipt {
     status: 'queued' | 'committed' | 'finalized';
     ethBlock: number;
     rollupBlock?: number;
     id: number;
+    l1Finality?: ApiL1Finality;
 }
 
 export type L2TxStatus = 'queued' | 'committed' | 'finalized' | 'rejected';
@@ -505,6 +587,7 @@ export interface ApiL2TxReceipt {
     rollupBlock?: number;
     status: L2TxStatus;
     failReason?: string;
+    l1Finality?: ApiL1Finality;
 }
 
 export type ApiTxReceipt = ApiL1TxReceipt | ApiL2TxReceipt;
@@ -185,7 +185,9 @@ export class Wallet extends AbstractWallet {
                         this.getChangePubKeyEthMessagePart({
                             pubKeyHash: changePubKey.newPkHash,
                             feeToken: tx.token,
-                            fee: changePubKey.fee
+                            fee: changePubKey.fee,
+                            validFrom: changePubKey.validFrom,
+                            validUntil: changePubKey.validUntil
                         })
                     );
                     processedTxs.push({ tx: changePubKey });
@@ -261,7 +263,9 @@ export class Wallet extends AbstractWallet {
                   stringToken,
                   to: transfer.to,
                   nonce: transfer.nonce,
-                  accountId: this.accountId
+                  accountId: this.accountId,
+                  validFrom: transfer.validFrom,
+                  validUntil: transfer.validUntil
               });
         return {
             tx: signedTransferTransaction,
@@ -423,7 +427,9 @@ export class Wallet extends AbstractWallet {
                   stringToken,
                   ethAddress: withdraw.ethAddress,
                   nonce: withdraw.nonce,
-                  accountId: this.accountId
+                  accountId: this.accountId,
+                  validFrom: withdraw.validFrom,
+                  validUntil: withdraw.validUntil
               });
 
         return {
@@ -478,7 +484,9 @@ export class Wallet extends AbstractWallet {
                   stringToken,
                   stringFee,
                   target: forcedExit.target,
-                  nonce: forcedExit.nonce
+                  nonce: forcedExit.nonce,
+                  validFrom: forcedExit.validFrom,
+                  validUntil: forcedExit.validUntil
               });
 
         return {
@@ -532,7 +540,9 @@ export class Wallet extends AbstractWallet {
                   tokenBuy: stringTokenBuy,
                   nonce: order.nonce,
                   recipient: order.recipient,
-                  ratio: order.ratio
+                  ratio: order.ratio,
+                  validFrom: order.validFrom,
+                  validUntil: order.validUntil
               });
         order.ethSignature = ethereumSignature;
         return order;
@@ -672,7 +682,9 @@ export class Wallet extends AbstractWallet {
                   to: withdrawNFT.to,
                   stringFee,
                   stringFeeToken,
-                  nonce: withdrawNFT.nonce
+                  nonce: withdrawNFT.nonce,
+                  validFrom: withdrawNFT.validFrom,
+                  validUntil: withdrawNFT.validUntil
               });
 
         return {
@@ -1017,6 +1029,8 @@ export class Wallet extends AbstractWallet {
         token: number;
         feeToken: TokenLike;
         fee: BigNumberish;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         const stringFee = BigNumber.from(withdrawNFT.fee).isZero()
             ? null
@@ -1026,7 +1040,9 @@ export class Wallet extends AbstractWallet {
             token: withdrawNFT.token,
             to: withdrawNFT.to,
             stringFee,
-            stringFeeToken
+            stringFeeToken,
+            validFrom: withdrawNFT.validFrom,
+            validUntil: withdrawNFT.validUntil
         });
     }
 
@@ -1040,6 +1056,8 @@ export class Wallet extends AbstractWallet {
         token: TokenLike;
         amount: BigNumberish;
         fee: BigNumberish;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<string> {
         const stringAmount = BigNumber.from(transfer.amount).isZero()
             ? null
@@ -1052,7 +1070,9 @@ export class Wallet extends AbstractWallet {
             stringAmount,
             stringFee,
             stringToken,
-            to: transfer.to
+            to: transfer.to,
+            validFrom: transfer.validFrom,
+            validUntil: transfer.validUntil
         });
     }
 
@@ -1061,6 +1081,8 @@ export class Wallet extends AbstractWallet {
         token: TokenLike;
         amount: BigNumberish;
         fee: BigNumberish;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         const stringAmount = BigNumber.from(withdraw.amount).isZero()
             ? null
@@ -1073,7 +1095,9 @@ export class Wallet extends AbstractWallet {
             stringAmount,
             stringFee,
             stringToken,
-            ethAddress: withdraw.ethAddress
+            ethAddress: withdraw.ethAddress,
+            validFrom: withdraw.validFrom,
+            validUntil: withdraw.validUntil
         });
     }
 
@@ -1081,6 +1105,8 @@ export class Wallet extends AbstractWallet {
         pubKeyHash: string;
         feeToken: TokenLike;
         fee: BigNumberish;
+        validFrom?: number;
+        validUntil?: number;
     }): string {
         const stringFee = BigNumber.from(changePubKey.fee).isZero()
             ? null
@@ -1089,7 +1115,9 @@ export class Wallet extends AbstractWallet {
         return this.ethMessageSigner().getChangePubKeyEthMessagePart({
             pubKeyHash: changePubKey.pubKeyHash,
             stringToken,
-            stringFee
+            stringFee,
+            validFrom: changePubKey.validFrom,
+            validUntil: changePubKey.validUntil
         });
     }
 
@@ -1122,7 +1150,13 @@ export class Wallet extends AbstractWallet {
         });
     }
 
-    getForcedExitEthMessagePart(forcedExit: { target: Address; token: TokenLike; fee: BigNumberish }): string {
+    getForcedExitEthMessagePart(forcedExit: {
+        target: Address;
+        token: TokenLike;
+        fee: BigNumberish;
+        validFrom?: number;
+        validUntil?: number;
+    }): string {
         const stringFee = BigNumber.from(forcedExit.fee).isZero()
             ? null
             : this.provider.tokenSet.formatToken(forcedExit.token, forcedExit.fee);
@@ -1130,7 +1164,9 @@ export class Wallet extends AbstractWallet {
         return this.ethMessageSigner().getForcedExitEthMessagePart({
             stringToken,
             stringFee,
-            target: forcedExit.target
+            target: forcedExit.target,
+            validFrom: forcedExit.validFrom,
+            validUntil: forcedExit.validUntil
         });
     }
 